@@ -6,10 +6,15 @@ use super::types::{
     Attestation, AttesterDuty, BeaconListResponse, BeaconResponse, BlobSidecar,
     BlockHeaderResponse, BlockReward, DepositContract, FinalityCheckpoints, ForkInfo,
     ForkScheduleEntry, GenesisInfo, NodeVersion, PeerCount, PeerInfo, ProposerDuty,
-    SignedVoluntaryExit, SyncCommittee, SyncDuty, SyncStatus, ValidatorInfo,
+    SignedVoluntaryExit, SyncCommittee, SyncDuty, SyncStatus, ValidatorId, ValidatorInfo,
 };
 use crate::client::Client;
-use crate::error::{Error, Result};
+use crate::error::{self, Error, Result};
+
+/// Maximum number of validator IDs sent in a single `validators` request
+///
+/// Beacon nodes cap the number of `id` query values to keep URLs bounded.
+pub const MAX_VALIDATOR_IDS_PER_REQUEST: usize = 64;
 
 /// Beacon API for Ethereum consensus layer
 pub struct BeaconApi<'a> {
@@ -175,13 +180,47 @@ impl<'a> BeaconApi<'a> {
             .await
     }
 
-    /// Get validators
+    /// Get validators by index or public key
+    ///
+    /// Indices and pubkeys may be mixed. Large lists are split into chunks of
+    /// [`MAX_VALIDATOR_IDS_PER_REQUEST`] and the results concatenated, with
+    /// duplicates (e.g. the same validator given by index and pubkey) removed.
+    /// An empty slice returns every validator in the state.
     pub async fn get_validators(
         &self,
+        indices_or_pubkeys: &[String],
         state_id: &str,
-    ) -> Result<BeaconListResponse<ValidatorInfo>> {
-        self.get(&format!("/beacon/states/{state_id}/validators"))
-            .await
+    ) -> Result<Vec<ValidatorInfo>> {
+        let path = format!("/beacon/states/{state_id}/validators");
+        if indices_or_pubkeys.is_empty() {
+            let response: BeaconListResponse<ValidatorInfo> = self.get(&path).await?;
+            return Ok(response.data);
+        }
+
+        let mut ids: Vec<ValidatorId> = Vec::with_capacity(indices_or_pubkeys.len());
+        for raw in indices_or_pubkeys {
+            let id = ValidatorId::parse(raw).ok_or_else(|| error::invalid_validator_id(raw))?;
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+
+        let mut validators: Vec<ValidatorInfo> = Vec::with_capacity(ids.len());
+        for chunk in ids.chunks(MAX_VALIDATOR_IDS_PER_REQUEST) {
+            let query = chunk
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(",");
+            let response: BeaconListResponse<ValidatorInfo> =
+                self.get(&format!("{path}?id={query}")).await?;
+            for validator in response.data {
+                if !validators.iter().any(|v| v.index == validator.index) {
+                    validators.push(validator);
+                }
+            }
+        }
+        Ok(validators)
     }
 
     /// Get specific validator
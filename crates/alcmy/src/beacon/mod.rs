@@ -3,5 +3,7 @@
 mod api;
 mod types;
 
-pub use api::{BeaconApi, RandaoResponse, RootResponse, ValidatorBalance};
+pub use api::{
+    BeaconApi, RandaoResponse, RootResponse, ValidatorBalance, MAX_VALIDATOR_IDS_PER_REQUEST,
+};
 pub use types::*;
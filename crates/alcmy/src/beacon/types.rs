@@ -71,10 +71,110 @@ pub struct Checkpoint {
 pub struct ValidatorInfo {
     pub index: String,
     pub balance: String,
-    pub status: String,
+    pub status: ValidatorStatus,
     pub validator: ValidatorData,
 }
 
+impl ValidatorInfo {
+    /// Validator public key
+    #[must_use]
+    pub fn pubkey(&self) -> &str {
+        &self.validator.pubkey
+    }
+
+    /// Current balance in gwei
+    #[must_use]
+    pub fn balance_gwei(&self) -> Option<u64> {
+        self.balance.parse().ok()
+    }
+
+    /// Effective balance in gwei
+    #[must_use]
+    pub fn effective_balance_gwei(&self) -> Option<u64> {
+        self.validator.effective_balance.parse().ok()
+    }
+}
+
+/// Validator lifecycle status as defined by the Beacon API spec
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidatorStatus {
+    PendingInitialized,
+    PendingQueued,
+    ActiveOngoing,
+    ActiveExiting,
+    ActiveSlashed,
+    ExitedUnslashed,
+    ExitedSlashed,
+    WithdrawalPossible,
+    WithdrawalDone,
+    /// Status not known to this client version
+    #[serde(other)]
+    Unknown,
+}
+
+impl ValidatorStatus {
+    /// Whether the validator is currently attesting
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        matches!(
+            self,
+            Self::ActiveOngoing | Self::ActiveExiting | Self::ActiveSlashed
+        )
+    }
+
+    /// Whether the validator has been slashed
+    #[must_use]
+    pub fn is_slashed(&self) -> bool {
+        matches!(self, Self::ActiveSlashed | Self::ExitedSlashed)
+    }
+
+    /// Whether the validator has exited (including withdrawal states)
+    #[must_use]
+    pub fn is_exited(&self) -> bool {
+        matches!(
+            self,
+            Self::ExitedUnslashed
+                | Self::ExitedSlashed
+                | Self::WithdrawalPossible
+                | Self::WithdrawalDone
+        )
+    }
+}
+
+/// Validator identifier accepted by the Beacon API
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ValidatorId {
+    /// Validator index
+    Index(u64),
+    /// 48-byte BLS public key (0x-prefixed, lowercase hex)
+    Pubkey(String),
+}
+
+impl ValidatorId {
+    /// Parse a validator index or 0x-prefixed public key
+    #[must_use]
+    pub fn parse(id: &str) -> Option<Self> {
+        let id = id.trim();
+        if let Some(hex) = id.strip_prefix("0x").or_else(|| id.strip_prefix("0X")) {
+            if hex.len() == 96 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Some(Self::Pubkey(format!("0x{}", hex.to_ascii_lowercase())));
+            }
+            return None;
+        }
+        id.parse().ok().map(Self::Index)
+    }
+}
+
+impl std::fmt::Display for ValidatorId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Index(index) => write!(f, "{index}"),
+            Self::Pubkey(pubkey) => f.write_str(pubkey),
+        }
+    }
+}
+
 /// Validator data
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
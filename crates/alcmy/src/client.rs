@@ -7,6 +7,15 @@ use serde::{de::DeserializeOwned, Serialize};
 use std::time::Duration;
 use yldfi_common::api::{extract_retry_after, ApiConfig, SecretApiKey};
 
+/// JSON-RPC request envelope
+#[derive(Serialize)]
+struct JsonRpcRequest<'a, P> {
+    jsonrpc: &'static str,
+    id: usize,
+    method: &'a str,
+    params: P,
+}
+
 /// Supported blockchain networks
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Network {
@@ -138,6 +147,8 @@ pub struct Config {
     pub network: Network,
     /// Inner API configuration
     inner: ApiConfig,
+    /// JSON-RPC endpoint replacing the network's Alchemy URL
+    rpc_url: Option<String>,
 }
 
 impl Config {
@@ -148,9 +159,18 @@ impl Config {
             api_key: SecretApiKey::new(api_key),
             network,
             inner: ApiConfig::new("https://api.g.alchemy.com"),
+            rpc_url: None,
         }
     }
 
+    /// Send JSON-RPC requests to a custom endpoint (e.g. a proxy or a local
+    /// node) instead of the network's Alchemy URL
+    #[must_use]
+    pub fn with_rpc_url(mut self, url: impl Into<String>) -> Self {
+        self.rpc_url = Some(url.into());
+        self
+    }
+
     /// Set a custom timeout
     #[must_use]
     pub fn with_timeout(mut self, timeout: Duration) -> Self {
@@ -179,6 +199,7 @@ impl std::fmt::Debug for Config {
             .field("api_key", &"[REDACTED]")
             .field("network", &self.network)
             .field("inner", &self.inner)
+            .field("rpc_url", &self.rpc_url)
             .finish()
     }
 }
@@ -189,6 +210,7 @@ pub struct Client {
     http: reqwest::Client,
     api_key: SecretApiKey,
     network: Network,
+    rpc_url: Option<String>,
 }
 
 impl Client {
@@ -212,6 +234,7 @@ impl Client {
             http,
             api_key: config.api_key,
             network: config.network,
+            rpc_url: config.rpc_url,
         })
     }
 
@@ -238,6 +261,20 @@ impl Client {
         self.network
     }
 
+    /// A client for another network sharing this client's API key and
+    /// HTTP connection pool
+    ///
+    /// A custom RPC URL is kept only for the same network.
+    #[must_use]
+    pub fn for_network(&self, network: Network) -> Self {
+        Self {
+            http: self.http.clone(),
+            api_key: self.api_key.clone(),
+            network,
+            rpc_url: self.rpc_url.clone().filter(|_| network == self.network),
+        }
+    }
+
     /// Get the HTTP client
     #[must_use]
     pub fn http(&self) -> &reqwest::Client {
@@ -247,6 +284,9 @@ impl Client {
     /// Get the base URL for JSON-RPC requests
     #[must_use]
     pub fn rpc_url(&self) -> String {
+        if let Some(url) = &self.rpc_url {
+            return url.clone();
+        }
         format!(
             "https://{}.g.alchemy.com/v2/{}",
             self.network.slug(),
@@ -289,14 +329,6 @@ impl Client {
         R: DeserializeOwned,
     {
         // PERF-012 fix: use typed struct instead of json! macro to avoid double serialization
-        #[derive(Serialize)]
-        struct JsonRpcRequest<'a, P> {
-            jsonrpc: &'static str,
-            id: u32,
-            method: &'a str,
-            params: P,
-        }
-
         let request = JsonRpcRequest {
             jsonrpc: "2.0",
             id: 1,
@@ -312,26 +344,48 @@ impl Client {
         }
 
         let result: serde_json::Value = response.json().await?;
+        rpc_result(result)
+    }
 
-        if let Some(error) = result.get("error") {
-            let code = error
-                .get("code")
-                .and_then(serde_json::Value::as_i64)
-                .unwrap_or(-1);
-            let message = error
-                .get("message")
-                .and_then(|m| m.as_str())
-                .unwrap_or("Unknown error")
-                .to_string();
-            return Err(error::rpc(code, message));
+    /// Make a batch of JSON-RPC requests to one method
+    ///
+    /// Sends one request per `params` entry in a single HTTP call and
+    /// returns their results in input order. Errors of individual calls stay
+    /// in their slot; the outer error covers the batch as a whole.
+    pub async fn rpc_batch<P, R>(&self, method: &str, params: &[P]) -> Result<Vec<Result<R>>>
+    where
+        P: Serialize,
+        R: DeserializeOwned,
+    {
+        if params.is_empty() {
+            return Ok(Vec::new());
         }
 
-        let result = result
-            .get("result")
-            .ok_or_else(|| error::rpc(-1, "No result in response"))?
-            .clone();
+        let requests: Vec<JsonRpcRequest<'_, &P>> = params
+            .iter()
+            .enumerate()
+            .map(|(id, params)| JsonRpcRequest {
+                jsonrpc: "2.0",
+                id,
+                method,
+                params,
+            })
+            .collect();
+
+        let response = self
+            .http
+            .post(self.rpc_url())
+            .json(&requests)
+            .send()
+            .await?;
+
+        if response.status() == 429 {
+            let retry_after = extract_retry_after(response.headers());
+            return Err(Error::rate_limited(retry_after));
+        }
 
-        Ok(serde_json::from_value(result)?)
+        let body: serde_json::Value = response.json().await?;
+        batch_results(body, params.len())
     }
 
     /// Make a GET request to the NFT API
@@ -411,3 +465,60 @@ impl Client {
         }
     }
 }
+
+/// Extract the result of a JSON-RPC response, or its error
+fn rpc_result<R: DeserializeOwned>(response: serde_json::Value) -> Result<R> {
+    if let Some(error) = response.get("error") {
+        let code = error
+            .get("code")
+            .and_then(serde_json::Value::as_i64)
+            .unwrap_or(-1);
+        let message = error
+            .get("message")
+            .and_then(|m| m.as_str())
+            .unwrap_or("Unknown error")
+            .to_string();
+        return Err(error::rpc(code, message));
+    }
+
+    let result = response
+        .get("result")
+        .ok_or_else(|| error::rpc(-1, "No result in response"))?
+        .clone();
+
+    Ok(serde_json::from_value(result)?)
+}
+
+/// Match a JSON-RPC batch response to request ids `0..count`
+///
+/// Nodes may answer batch entries in any order. A lone error object means
+/// the whole batch was rejected.
+pub(crate) fn batch_results<R: DeserializeOwned>(
+    body: serde_json::Value,
+    count: usize,
+) -> Result<Vec<Result<R>>> {
+    let serde_json::Value::Array(responses) = body else {
+        return Err(rpc_result::<serde_json::Value>(body)
+            .err()
+            .unwrap_or_else(|| error::rpc(-1, "Expected a batch response")));
+    };
+
+    let mut results: Vec<Option<Result<R>>> = (0..count).map(|_| None).collect();
+    for response in responses {
+        let slot = response
+            .get("id")
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|id| results.get_mut(usize::try_from(id).ok()?));
+        if let Some(slot) = slot {
+            *slot = Some(rpc_result(response));
+        }
+    }
+
+    Ok(results
+        .into_iter()
+        .enumerate()
+        .map(|(id, result)| {
+            result.unwrap_or_else(|| Err(error::rpc(-1, format!("No response for request {id}"))))
+        })
+        .collect())
+}
@@ -16,6 +16,22 @@ pub enum DomainError {
     /// Invalid API key
     #[error("Invalid API key")]
     InvalidApiKey,
+
+    /// Validator ID is neither an index nor a 48-byte pubkey
+    #[error("Invalid validator ID: {0}")]
+    InvalidValidatorId(String),
+
+    /// No receipt for a transaction (unknown or still pending)
+    #[error("Transaction receipt not found: {0}")]
+    ReceiptNotFound(String),
+
+    /// Created webhook came back without a signing key
+    #[error("Webhook {0} has no signing key")]
+    MissingSigningKey(String),
+
+    /// Ownership snapshot pages were malformed or did not terminate
+    #[error("Invalid ownership snapshot: {0}")]
+    InvalidSnapshot(String),
 }
 
 /// Error type for Alchemy API operations
@@ -37,3 +53,23 @@ pub fn rpc(code: i64, message: impl Into<String>) -> Error {
 pub fn invalid_api_key() -> Error {
     ApiError::domain(DomainError::InvalidApiKey)
 }
+
+/// Create an invalid validator ID error
+pub fn invalid_validator_id(id: impl Into<String>) -> Error {
+    ApiError::domain(DomainError::InvalidValidatorId(id.into()))
+}
+
+/// Create a receipt not found error
+pub fn receipt_not_found(hash: impl Into<String>) -> Error {
+    ApiError::domain(DomainError::ReceiptNotFound(hash.into()))
+}
+
+/// Create a missing webhook signing key error
+pub fn missing_signing_key(webhook_id: impl Into<String>) -> Error {
+    ApiError::domain(DomainError::MissingSigningKey(webhook_id.into()))
+}
+
+/// Create an invalid ownership snapshot error
+pub fn invalid_snapshot(message: impl Into<String>) -> Error {
+    ApiError::domain(DomainError::InvalidSnapshot(message.into()))
+}
@@ -8,6 +8,7 @@
 //! - **Portfolio API**: Multi-chain wallet balances and NFT holdings
 //! - **Token API**: ERC-20 token balances, metadata, and allowances
 //! - **Transfers API**: Historical asset transfers via `alchemy_getAssetTransfers`
+//! - **Receipts API**: Batch transaction receipts via `alchemy_getTransactionReceipts`
 //!
 //! ## Debugging & Tracing
 //! - **Debug API**: Transaction and block tracing (debug_* methods)
@@ -64,6 +65,7 @@ mod error;
 pub mod nft;
 pub mod portfolio;
 pub mod prices;
+pub mod receipts;
 pub mod token;
 pub mod transfers;
 
@@ -135,6 +137,22 @@ impl Client {
         transfers::TransfersApi::new(self)
     }
 
+    /// Access the Receipts API
+    #[must_use]
+    pub fn receipts(&self) -> receipts::ReceiptsApi<'_> {
+        receipts::ReceiptsApi::new(self)
+    }
+
+    /// Get receipts for many transactions, one result per hash in input order
+    ///
+    /// Shorthand for [`receipts::ReceiptsApi::get_transaction_receipts`].
+    pub async fn get_transaction_receipts(
+        &self,
+        hashes: &[&str],
+    ) -> Result<Vec<(String, Result<receipts::Receipt>)>> {
+        self.receipts().get_transaction_receipts(hashes).await
+    }
+
     // ========== Debugging & Tracing ==========
 
     /// Access the Debug API (debug_* methods)
@@ -226,6 +244,42 @@ mod tests {
         assert!(client.data_url().contains("data/v1"));
     }
 
+    #[test]
+    fn test_client_for_network() {
+        let client = Client::new("test-key", Network::EthMainnet).unwrap();
+        let base = client.for_network(Network::BaseMainnet);
+        assert_eq!(base.network(), Network::BaseMainnet);
+        assert_eq!(base.api_key(), "test-key");
+        assert!(base
+            .rpc_url()
+            .starts_with("https://base-mainnet.g.alchemy.com/v2/"));
+        // The original client is untouched
+        assert_eq!(client.network(), Network::EthMainnet);
+    }
+
+    #[test]
+    fn test_multichain_balances_lookup() {
+        let balance = token::RpcTokenBalance {
+            contract_address: "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48".to_string(),
+            token_balance: Some("0x1".to_string()),
+            error: None,
+        };
+        let result = token::MultichainBalances {
+            balances: vec![
+                (Network::EthMainnet, vec![balance]),
+                (Network::BaseMainnet, vec![]),
+            ],
+            errors: vec![(
+                Network::SolanaMainnet,
+                error::rpc(-32601, "Method not found"),
+            )],
+        };
+        assert!(!result.is_complete());
+        assert_eq!(result.for_network(Network::EthMainnet).unwrap().len(), 1);
+        assert!(result.for_network(Network::BaseMainnet).unwrap().is_empty());
+        assert!(result.for_network(Network::SolanaMainnet).is_none());
+    }
+
     #[test]
     fn test_asset_transfers_options() {
         let opts = transfers::AssetTransfersOptions::from_address("0x123")
@@ -239,6 +293,684 @@ mod tests {
         assert_eq!(opts.max_count, Some("0x64".to_string()));
     }
 
+    #[test]
+    fn test_validator_id_parse() {
+        assert_eq!(
+            beacon::ValidatorId::parse(" 42 "),
+            Some(beacon::ValidatorId::Index(42))
+        );
+        let pubkey = format!("0x{}", "AB".repeat(48));
+        assert_eq!(
+            beacon::ValidatorId::parse(&pubkey),
+            Some(beacon::ValidatorId::Pubkey(format!(
+                "0x{}",
+                "ab".repeat(48)
+            )))
+        );
+        assert_eq!(beacon::ValidatorId::parse("0x1234"), None);
+        assert_eq!(beacon::ValidatorId::parse("head"), None);
+    }
+
+    #[test]
+    fn test_validator_status_deserialize() {
+        let status: beacon::ValidatorStatus = serde_json::from_str("\"active_ongoing\"").unwrap();
+        assert_eq!(status, beacon::ValidatorStatus::ActiveOngoing);
+        assert!(status.is_active());
+
+        let status: beacon::ValidatorStatus = serde_json::from_str("\"exited_slashed\"").unwrap();
+        assert!(status.is_slashed() && status.is_exited());
+
+        let status: beacon::ValidatorStatus = serde_json::from_str("\"something_new\"").unwrap();
+        assert_eq!(status, beacon::ValidatorStatus::Unknown);
+    }
+
+    #[test]
+    fn test_prepare_calls_request_serialization() {
+        let calls = [
+            wallet::WalletCall::new("0xtarget").data("0xdeadbeef"),
+            wallet::WalletCall::new("0xrecipient").value(1_000_000_000_000_000_000),
+        ];
+        let request = wallet::PrepareCallsRequest::new("0xaccount", 8453, &calls)
+            .with_capabilities(
+                wallet::PrepareCallsCapabilities::new()
+                    .paymaster("policy-123")
+                    .session("0xcontext"),
+            );
+
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            serde_json::json!({
+                "calls": [
+                    { "to": "0xtarget", "data": "0xdeadbeef" },
+                    { "to": "0xrecipient", "value": "0xde0b6b3a7640000" }
+                ],
+                "from": "0xaccount",
+                "chainId": "0x2105",
+                "capabilities": {
+                    "paymasterService": { "policyId": "policy-123" },
+                    "permissions": { "context": "0xcontext" }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_send_prepared_calls_from_response() {
+        let prepared: wallet::PreparedCallsResponse = serde_json::from_value(serde_json::json!({
+            "type": "user-operation-v070",
+            "data": { "sender": "0xaccount" },
+            "chainId": "0x2105",
+            "signatureRequest": { "type": "personal_sign", "data": { "raw": "0xabc" } },
+            "someFutureField": true
+        }))
+        .unwrap();
+        let signature = wallet::Signature {
+            sig_type: "secp256k1".to_string(),
+            data: "0xsig".to_string(),
+        };
+
+        assert_eq!(
+            serde_json::to_value(wallet::SendPreparedCallsRequest::signed(
+                &prepared, signature
+            ))
+            .unwrap(),
+            serde_json::json!({
+                "type": "user-operation-v070",
+                "data": { "sender": "0xaccount" },
+                "chainId": "0x2105",
+                "signature": { "type": "secp256k1", "data": "0xsig" }
+            })
+        );
+
+        let sent: wallet::SendPreparedCallsResponse =
+            serde_json::from_value(serde_json::json!({ "preparedCallIds": ["0xbundle"] })).unwrap();
+        assert_eq!(sent.bundle_id(), Some("0xbundle"));
+    }
+
+    #[test]
+    fn test_session_policy_serialization() {
+        let policy = wallet::SessionPolicy::new()
+            .expiry(1_900_000_000)
+            .allow_target("0xrouter")
+            .spending_limit(1_000);
+        let key = wallet::SessionKey {
+            key_type: wallet::KeyType::Secp256k1,
+            public_key: "0xsessionkey".to_string(),
+        };
+        let request = wallet::CreateSessionRequest::from_policy("0xaccount", 1, key, &policy);
+
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            serde_json::json!({
+                "account": "0xaccount",
+                "chainId": "0x1",
+                "key": { "type": "secp256k1", "publicKey": "0xsessionkey" },
+                "permissions": [
+                    { "type": "contract-access", "data": { "address": "0xrouter" } },
+                    { "type": "native-token-transfer", "data": { "allowance": "0x3e8" } }
+                ],
+                "expirySec": 1_900_000_000
+            })
+        );
+    }
+
+    #[test]
+    fn test_calls_status_codes() {
+        use wallet::CallsStatus;
+
+        let status: wallet::CallsStatusResponse =
+            serde_json::from_value(serde_json::json!({ "status": 200 })).unwrap();
+        assert_eq!(status.status_kind(), CallsStatus::Confirmed);
+        assert!(status.receipts.is_empty());
+
+        assert_eq!(CallsStatus::from_code(100), CallsStatus::Pending);
+        assert_eq!(CallsStatus::from_code(400), CallsStatus::OffchainFailure);
+        assert_eq!(CallsStatus::from_code(500), CallsStatus::Reverted);
+        assert_eq!(CallsStatus::from_code(600), CallsStatus::PartiallyReverted);
+        assert_eq!(CallsStatus::from_code(42), CallsStatus::Unknown(42));
+        assert!(!CallsStatus::Pending.is_final());
+        assert!(CallsStatus::Reverted.is_final());
+    }
+
+    #[test]
+    fn test_address_activity_batching() {
+        use notify::{address_batches, UpdateWebhookAddressesRequest, MAX_ADDRESSES_PER_REQUEST};
+
+        let addresses: Vec<String> = (0..MAX_ADDRESSES_PER_REQUEST + 10)
+            .map(|i| format!("0x{i:040x}"))
+            .collect();
+        let mut refs: Vec<&str> = addresses.iter().map(String::as_str).collect();
+        let upper = addresses[0].to_uppercase();
+        refs.push(&upper);
+
+        let batches = address_batches(&refs);
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].len(), MAX_ADDRESSES_PER_REQUEST);
+        assert_eq!(batches[1].len(), 10);
+        assert!(address_batches(&[]).is_empty());
+
+        let request = UpdateWebhookAddressesRequest::remove("wh_1", vec!["0xabc".into()]);
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            serde_json::json!({ "webhookId": "wh_1", "addressesToRemove": ["0xabc"] })
+        );
+    }
+
+    const OWNER: &str = "0x00000000000000000000000000000000000000aa";
+    const ROUTER: &str = "0x00000000000000000000000000000000000000bb";
+    const USDC: &str = "0x00000000000000000000000000000000000000c1";
+    const WETH: &str = "0x00000000000000000000000000000000000000c2";
+
+    fn transfer_fixture() -> Vec<transfers::AssetTransfer> {
+        let transfer = |category: &str, from: &str, to: &str, contract: Option<&str>| {
+            serde_json::json!({
+                "blockNum": "0x10",
+                "uniqueId": "id",
+                "hash": "0xhash",
+                "from": from,
+                "to": to,
+                "category": category,
+                "rawContract": { "address": contract }
+            })
+        };
+        serde_json::from_value(serde_json::json!([
+            // Owner sends USDC into the router
+            transfer(
+                "erc20",
+                &OWNER.to_uppercase().replace("0X", "0x"),
+                ROUTER,
+                Some(USDC)
+            ),
+            // Owner receives WETH; the sender is not a spender
+            transfer(
+                "erc20",
+                "0x00000000000000000000000000000000000000dd",
+                OWNER,
+                Some(WETH)
+            ),
+            // Owner calls a contract directly
+            transfer(
+                "external",
+                OWNER,
+                "0x00000000000000000000000000000000000000ee",
+                None
+            ),
+            // Someone else's transaction is ignored
+            transfer(
+                "external",
+                "0x00000000000000000000000000000000000000dd",
+                "0x00000000000000000000000000000000000000ff",
+                None
+            ),
+        ]))
+        .unwrap()
+    }
+
+    fn approval_log(token: &str, owner: &str, spender: &str, block: u64) -> token::ApprovalLog {
+        let topic = |address: &str| format!("0x{:0>64}", address.trim_start_matches("0x"));
+        token::ApprovalLog {
+            address: token.to_string(),
+            topics: vec![
+                token::APPROVAL_TOPIC.to_string(),
+                topic(owner),
+                topic(spender),
+            ],
+            data: format!("0x{}", "f".repeat(64)),
+            block_number: Some(format!("0x{block:x}")),
+            block_timestamp: Some("0x6553f100".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_allowance_discovery_from_transfers() {
+        let options = token::AllowanceScanOptions {
+            known_spenders: vec!["0x00000000000000000000000000000000000000CC".to_string()],
+            discover_from_transfers: true,
+            ..Default::default()
+        };
+        let mut candidates = token::AllowanceCandidates::from_options(&options);
+        candidates.add_transfers(OWNER, &transfer_fixture());
+
+        assert_eq!(
+            candidates.contracts.iter().collect::<Vec<_>>(),
+            vec![USDC, WETH]
+        );
+        assert_eq!(
+            candidates.spenders.iter().collect::<Vec<_>>(),
+            vec![
+                ROUTER,
+                "0x00000000000000000000000000000000000000cc",
+                "0x00000000000000000000000000000000000000ee"
+            ]
+        );
+        assert_eq!(
+            candidates
+                .pairs(OWNER, token::DEFAULT_MAX_ALLOWANCE_PAIRS)
+                .len(),
+            6
+        );
+
+        candidates.restrict_contracts(&[USDC.to_uppercase().replace("0X", "0x")]);
+        assert_eq!(
+            candidates
+                .pairs(OWNER, token::DEFAULT_MAX_ALLOWANCE_PAIRS)
+                .len(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_allowance_discovery_from_approval_logs() {
+        let mut candidates = token::AllowanceCandidates::default();
+        candidates.add_approval_logs(
+            OWNER,
+            &[
+                approval_log(USDC, OWNER, ROUTER, 100),
+                approval_log(USDC, OWNER, ROUTER, 250),
+                approval_log(USDC, OWNER, ROUTER, 200),
+                // Another owner's approval
+                approval_log(
+                    WETH,
+                    "0x00000000000000000000000000000000000000dd",
+                    ROUTER,
+                    300,
+                ),
+                // Owner approving itself is never queried
+                approval_log(WETH, OWNER, OWNER, 300),
+            ],
+        );
+
+        let latest = candidates.approvals[&(USDC.to_string(), ROUTER.to_string())];
+        assert_eq!(latest.block_number, 250);
+        assert_eq!(latest.timestamp, Some(0x6553_f100));
+        assert_eq!(
+            candidates.pairs(OWNER, token::DEFAULT_MAX_ALLOWANCE_PAIRS),
+            vec![
+                (USDC.to_string(), ROUTER.to_string()),
+                (WETH.to_string(), ROUTER.to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_allowance_pairs_capped() {
+        let options = token::AllowanceScanOptions {
+            known_spenders: vec!["0x00000000000000000000000000000000000000cc".to_string()],
+            discover_from_transfers: true,
+            ..Default::default()
+        };
+        let mut candidates = token::AllowanceCandidates::from_options(&options);
+        candidates.add_transfers(OWNER, &transfer_fixture());
+        candidates.add_approval_logs(OWNER, &[approval_log(WETH, OWNER, ROUTER, 100)]);
+
+        // Approved pairs come first and survive the cap
+        let pairs = candidates.pairs(OWNER, 2);
+        assert_eq!(pairs.len(), 2);
+        assert_eq!(pairs[0], (WETH.to_string(), ROUTER.to_string()));
+        assert!(candidates.pairs(OWNER, 0).is_empty());
+        assert_eq!(candidates.pairs(OWNER, 100).len(), 6);
+    }
+
+    #[test]
+    fn test_approval_logs_skip_malformed_topics() {
+        let mut short = approval_log(USDC, OWNER, ROUTER, 100);
+        short.topics[2] = "0xabc".to_string();
+        let mut odd = approval_log(WETH, OWNER, ROUTER, 100);
+        odd.topics[1] = format!("0x{}", "é".repeat(32));
+        let mut missing = approval_log(WETH, OWNER, ROUTER, 100);
+        missing.topics.truncate(2);
+
+        let mut candidates = token::AllowanceCandidates::default();
+        candidates.add_approval_logs(OWNER, &[short, odd, missing]);
+        assert!(candidates.approvals.is_empty());
+        assert!(candidates
+            .pairs(OWNER, token::DEFAULT_MAX_ALLOWANCE_PAIRS)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_split_block_range() {
+        use token::split_block_range;
+
+        assert_eq!(
+            split_block_range(0, 1_000_000),
+            Some(((0, 500_000), (500_001, 1_000_000)))
+        );
+        assert_eq!(
+            split_block_range(10, 10_011),
+            Some(((10, 5_010), (5_011, 10_011)))
+        );
+        // Small ranges are not split further
+        assert_eq!(split_block_range(100, 1_000), None);
+        assert_eq!(split_block_range(5, 5), None);
+    }
+
+    #[test]
+    fn test_parse_allowance() {
+        use token::{is_unlimited_allowance, parse_allowance};
+
+        assert_eq!(parse_allowance("0x0"), Some(0));
+        assert_eq!(parse_allowance("0x3e8"), Some(1000));
+        assert_eq!(parse_allowance("1000"), Some(1000));
+        assert_eq!(parse_allowance("0x"), None);
+        assert_eq!(parse_allowance("12ab"), None);
+        let max_uint256 = format!("0x{}", "f".repeat(64));
+        assert_eq!(parse_allowance(&max_uint256), Some(u128::MAX));
+        assert!(is_unlimited_allowance(u128::MAX));
+        // type(uint96).max, used by UNI/COMP-style tokens
+        assert!(is_unlimited_allowance(
+            parse_allowance("0xffffffffffffffffffffffff").unwrap()
+        ));
+        assert!(!is_unlimited_allowance(1_000_000 * 10u128.pow(18)));
+    }
+
+    #[tokio::test]
+    async fn test_bounded_map_limits_concurrency() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let results = token::bounded_map((0..20u64).collect(), 3, |i| {
+            let in_flight = Arc::clone(&in_flight);
+            let peak = Arc::clone(&peak);
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(std::time::Duration::from_millis(20 - i)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                i * 2
+            }
+        })
+        .await;
+
+        assert_eq!(results, (0..20u64).map(|i| i * 2).collect::<Vec<_>>());
+        assert!(peak.load(Ordering::SeqCst) <= 3);
+        assert!(peak.load(Ordering::SeqCst) > 1);
+    }
+
+    fn receipt(hash: &str, status: &str) -> receipts::Receipt {
+        serde_json::from_value(serde_json::json!({
+            "transactionHash": hash,
+            "transactionIndex": "0x0",
+            "blockHash": "0xb10c",
+            "blockNumber": "0x10",
+            "from": "0xsender",
+            "to": null,
+            "contractAddress": "0xcreated",
+            "gasUsed": "0x5208",
+            "cumulativeGasUsed": "0x5208",
+            "status": status,
+            "type": "0x2",
+            "logs": [{
+                "address": "0xtoken",
+                "topics": ["0xddf252ad"],
+                "data": "0x01",
+                "logIndex": "0x0"
+            }]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_receipt_deserialize() {
+        let ok = receipt("0xaa", "0x1");
+        assert_eq!(ok.succeeded(), Some(true));
+        assert_eq!(ok.block_number_u64(), Some(16));
+        assert_eq!(ok.gas_used_u64(), Some(21_000));
+        assert_eq!(ok.to, None);
+        assert_eq!(ok.logs.len(), 1);
+        assert!(!ok.logs[0].removed);
+
+        assert_eq!(receipt("0xbb", "0x0").succeeded(), Some(false));
+    }
+
+    #[test]
+    fn test_batch_results_match_ids() {
+        // Answered out of order, one error, one request unanswered
+        let body = serde_json::json!([
+            { "jsonrpc": "2.0", "id": 2, "result": receipt("0xcc", "0x1") },
+            { "jsonrpc": "2.0", "id": 0, "result": null },
+            { "jsonrpc": "2.0", "id": 1, "error": { "code": -32000, "message": "boom" } },
+            { "jsonrpc": "2.0", "id": 9, "result": null }
+        ]);
+        let results: Vec<Result<Option<receipts::Receipt>>> =
+            client::batch_results(body, 4).unwrap();
+
+        assert_eq!(results.len(), 4);
+        assert!(matches!(results[0], Ok(None)));
+        assert!(matches!(
+            &results[1],
+            Err(Error::Domain(error::DomainError::Rpc { code: -32000, .. }))
+        ));
+        assert_eq!(
+            results[2]
+                .as_ref()
+                .unwrap()
+                .as_ref()
+                .unwrap()
+                .transaction_hash,
+            "0xcc"
+        );
+        assert!(results[3].is_err());
+
+        let rejected = serde_json::json!({
+            "jsonrpc": "2.0", "id": null, "error": { "code": -32600, "message": "batch too large" }
+        });
+        assert!(client::batch_results::<serde_json::Value>(rejected, 1).is_err());
+    }
+
+    #[test]
+    fn test_single_block() {
+        use receipts::single_block;
+
+        let block = |h: &str| Some(h.to_string());
+        assert_eq!(
+            single_block(&[block("0xB10C"), block("0xb10c")]),
+            block("0xB10C")
+        );
+        assert_eq!(single_block(&[block("0xb10c"), block("0xb10d")]), None);
+        // Pending or unknown transactions have no block
+        assert_eq!(single_block(&[block("0xb10c"), None]), None);
+        assert_eq!(single_block(&[]), None);
+    }
+
+    #[test]
+    fn test_fill_from_block_preserves_order() {
+        let hashes = ["0xBB", "0xaa", "0xdd", "0xaa"];
+        let block = [receipt("0xaa", "0x1"), receipt("0xbb", "0x0")];
+        let mut slots: Vec<Option<Result<receipts::Receipt>>> =
+            hashes.iter().map(|_| None).collect();
+        slots[0] = Some(Err(error::receipt_not_found("0xBB")));
+
+        receipts::fill_from_block(&hashes, &block, &mut slots);
+        // Already-filled slots are kept
+        assert!(matches!(slots[0], Some(Err(_))));
+        assert_eq!(
+            slots[1]
+                .as_ref()
+                .unwrap()
+                .as_ref()
+                .unwrap()
+                .transaction_hash,
+            "0xaa"
+        );
+        assert!(slots[2].is_none());
+        assert!(slots[3].as_ref().unwrap().is_ok());
+    }
+
+    fn owners_page(
+        owners: serde_json::Value,
+        page_key: Option<&str>,
+    ) -> nft::OwnersForContractResponse {
+        serde_json::from_value(serde_json::json!({ "owners": owners, "pageKey": page_key }))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_owners_for_contract_response_forms() {
+        let bare = owners_page(serde_json::json!(["0xabc", "0xdef"]), Some("next"));
+        assert_eq!(bare.owners.len(), 2);
+        assert_eq!(bare.owners[0].owner_address, "0xabc");
+        assert!(bare.owners[0].token_balances.is_empty());
+        assert_eq!(bare.page_key.as_deref(), Some("next"));
+
+        let with_balances = owners_page(
+            serde_json::json!([{
+                "ownerAddress": "0xabc",
+                "tokenBalances": [{ "tokenId": "7", "balance": 2 }]
+            }]),
+            None,
+        );
+        assert_eq!(with_balances.owners[0].token_balances[0].balance, "2");
+    }
+
+    #[test]
+    fn test_owners_query_params() {
+        let query = nft::OwnersQuery {
+            page_key: Some("abc".to_string()),
+            ..Default::default()
+        }
+        .with_token_balances()
+        .at_block(19_000_000);
+        assert_eq!(
+            query.to_query_params(),
+            vec![
+                ("withTokenBalances", "true".to_string()),
+                ("block", "19000000".to_string()),
+                ("pageKey", "abc".to_string()),
+            ]
+        );
+        assert!(nft::OwnersQuery::default().to_query_params().is_empty());
+    }
+
+    #[test]
+    fn test_ownership_snapshot_stitches_pages() {
+        const A: &str = "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+        const B: &str = "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+        const C: &str = "0xcccccccccccccccccccccccccccccccccccccccc";
+
+        let first = owners_page(
+            serde_json::json!([
+                { "ownerAddress": C, "tokenBalances": [{ "tokenId": "1", "balance": "1" }] },
+                {
+                    "ownerAddress": B.to_uppercase().replace("0X", "0x"),
+                    "tokenBalances": [{ "tokenId": "2", "balance": "1" }, { "tokenId": "3", "balance": "1" }]
+                }
+            ]),
+            Some("page-2"),
+        );
+        let second = owners_page(
+            serde_json::json!([
+                // B again: token 3 repeats, token 4 is new
+                {
+                    "ownerAddress": B,
+                    "tokenBalances": [{ "tokenId": "3", "balance": "1" }, { "tokenId": "4", "balance": "1" }]
+                },
+                // ERC-1155 balance
+                { "ownerAddress": A, "tokenBalances": [{ "tokenId": "9", "balance": "0x5" }] },
+                // Nothing held
+                { "ownerAddress": "0xdddddddddddddddddddddddddddddddddddddddd", "tokenBalances": [] }
+            ]),
+            None,
+        );
+
+        let build = |pages: [&nft::OwnersForContractResponse; 2]| {
+            let mut snapshot = nft::OwnershipSnapshot::new("0xCONTRACT", Some(19_000_000));
+            for page in pages {
+                snapshot.add_page(page).unwrap();
+            }
+            snapshot
+        };
+        let snapshot = build([&first, &second]);
+        assert_eq!(snapshot.contract, "0xcontract");
+
+        let holders = snapshot.holders();
+        let rows: Vec<(&str, u128)> = holders
+            .iter()
+            .map(|h| (h.address.as_str(), h.token_count))
+            .collect();
+        assert_eq!(rows, vec![(A, 5), (B, 3), (C, 1)]);
+
+        // Page order does not change the result
+        assert_eq!(build([&second, &first]).holders(), holders);
+
+        let mut csv = Vec::new();
+        snapshot.export_csv(&mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            format!("address,token_count\n{A},5\n{B},3\n{C},1\n")
+        );
+
+        let leaves = snapshot.export_merkle_leaves();
+        assert_eq!(leaves.len(), 3);
+        assert_eq!(leaves[0].address, A);
+        assert_eq!(
+            leaves[0].leaf,
+            format!(
+                "0x{}",
+                nft::merkle_leaf(A, 5)
+                    .unwrap()
+                    .iter()
+                    .map(|b| format!("{b:02x}"))
+                    .collect::<String>()
+            )
+        );
+    }
+
+    #[test]
+    fn test_merkle_leaf_matches_solidity_encode_packed() {
+        // keccak256(abi.encodePacked(address, uint256)) computed with alloy
+        let hex = |address: &str, count: u128| {
+            nft::merkle_leaf(address, count)
+                .unwrap()
+                .iter()
+                .map(|b| format!("{b:02x}"))
+                .collect::<String>()
+        };
+        assert_eq!(
+            hex("0xd8da6bf26964af9d7eed9e03e53415d37aa96045", 3),
+            "fb1a2ecd42676b1b47222eebfe8b1bc2e75230ee104d06571a31edcea38459a4"
+        );
+        assert_eq!(
+            hex("0x0000000000000000000000000000000000000001", 1),
+            "2a5bb61d4b6540294819af4b6a2b302e0fcb2b698020f535cd8182b0a910da9f"
+        );
+        // Checksummed input hashes the same as lowercase
+        assert_eq!(
+            hex("0xBC4CA0EdA7647A8aB7C2061c2E118A18a936f13D", 10_000),
+            "e1f55880a33b1f04f1dd22bb34b488ef8039940c70354366a155a38bc21a59f4"
+        );
+        assert!(nft::merkle_leaf("0x1234", 1).is_none());
+    }
+
+    #[test]
+    fn test_ownership_snapshot_rejects_bad_rows() {
+        let mut snapshot = nft::OwnershipSnapshot::new("0xc", None);
+        let bad_balance = owners_page(
+            serde_json::json!([{
+                "ownerAddress": "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+                "tokenBalances": [{ "tokenId": "1", "balance": "lots" }]
+            }]),
+            None,
+        );
+        assert!(snapshot.add_page(&bad_balance).is_err());
+
+        let bad_address = owners_page(serde_json::json!(["0xnotanaddress"]), None);
+        assert!(snapshot.add_page(&bad_address).is_err());
+    }
+
+    #[test]
+    fn test_ownership_verification_helpers() {
+        let verification = nft::OwnershipVerification {
+            owner: "0xowner".to_string(),
+            contracts: vec![("0xa".to_string(), true), ("0xb".to_string(), false)],
+        };
+        assert!(verification.holds_any());
+        assert!(!verification.holds_all());
+        assert_eq!(verification.held_contracts(), vec!["0xa"]);
+    }
+
     #[test]
     fn test_all_apis_accessible() {
         let client = Client::new("test-key", Network::EthMainnet).unwrap();
@@ -248,6 +980,7 @@ mod tests {
         let _ = client.portfolio();
         let _ = client.token();
         let _ = client.transfers();
+        let _ = client.receipts();
         let _ = client.debug();
         let _ = client.trace();
         let _ = client.simulation();
@@ -6,10 +6,13 @@ use super::types::{
     FloorPriceResponse, GetNftsForOwnerOptions, InvalidateContractResponse, IsAirdropResponse,
     IsHolderResponse, IsSpamResponse, Nft, NftRarityResponse, NftSalesResponse,
     NftsForContractResponse, OwnedNftsResponse, OwnersForContractResponse, OwnersForNftResponse,
-    RefreshMetadataResponse, SpamContractsResponse,
+    OwnersQuery, OwnershipSnapshot, OwnershipVerification, RefreshMetadataResponse,
+    SpamContractsResponse, OWNERSHIP_CHECK_CONCURRENCY,
 };
 use crate::client::Client;
-use crate::error::Result;
+use crate::error::{self, Result};
+use crate::token::bounded_map;
+use std::collections::HashSet;
 
 /// NFT API for ownership, metadata, sales, and spam detection
 pub struct NftApi<'a> {
@@ -52,13 +55,63 @@ impl<'a> NftApi<'a> {
         self.client.nft_get("getOwnersForNFT", &query).await
     }
 
-    /// Get all owners of an NFT contract
+    /// Get one page of owners of an NFT contract
+    ///
+    /// Pass the returned `page_key` back in `query.page_key` for the next
+    /// page, or use [`Self::get_ownership_snapshot`] to fetch them all.
     pub async fn get_owners_for_contract(
         &self,
         contract_address: &str,
+        query: &OwnersQuery,
     ) -> Result<OwnersForContractResponse> {
-        let query = [("contractAddress", contract_address)];
-        self.client.nft_get("getOwnersForContract", &query).await
+        let mut params: Vec<(&str, String)> =
+            vec![("contractAddress", contract_address.to_string())];
+        params.extend(query.to_query_params());
+
+        let query_refs: Vec<(&str, &str)> = params.iter().map(|(k, v)| (*k, v.as_str())).collect();
+        self.client
+            .nft_get("getOwnersForContract", &query_refs)
+            .await
+    }
+
+    /// Every owner of an NFT contract with their token counts, at
+    /// `query.block` (or latest)
+    ///
+    /// Follows `pageKey` until the last page; token balances are always
+    /// requested. Fails if the API hands back a page key it already sent,
+    /// rather than looping forever.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let snapshot = client
+    ///     .nft()
+    ///     .get_ownership_snapshot("0xbc4ca0...", &OwnersQuery::default().at_block(19_000_000))
+    ///     .await?;
+    /// snapshot.export_csv(std::fs::File::create("owners.csv")?)?;
+    /// ```
+    pub async fn get_ownership_snapshot(
+        &self,
+        contract_address: &str,
+        query: &OwnersQuery,
+    ) -> Result<OwnershipSnapshot> {
+        let mut query = query.clone().with_token_balances();
+        let mut snapshot = OwnershipSnapshot::new(contract_address, query.block);
+        let mut seen_keys = HashSet::new();
+        loop {
+            let page = self
+                .get_owners_for_contract(contract_address, &query)
+                .await?;
+            snapshot.add_page(&page)?;
+            match page.page_key.filter(|key| !key.is_empty()) {
+                Some(key) if !seen_keys.insert(key.clone()) => {
+                    return Err(error::invalid_snapshot(format!(
+                        "page key {key} was returned twice"
+                    )));
+                }
+                Some(key) => query.page_key = Some(key),
+                None => return Ok(snapshot),
+            }
+        }
     }
 
     /// Get NFT contracts owned by an address
@@ -77,6 +130,43 @@ impl<'a> NftApi<'a> {
         self.client.nft_get("isHolderOfContract", &query).await
     }
 
+    /// Check which of `contracts` an address holds any token from
+    ///
+    /// Contracts are de-duplicated case-insensitively and checked with
+    /// `isHolderOfContract`, at most [`OWNERSHIP_CHECK_CONCURRENCY`] at a
+    /// time. Results keep the order of first appearance.
+    pub async fn verify_ownership(
+        &self,
+        owner: &str,
+        contracts: &[&str],
+    ) -> Result<OwnershipVerification> {
+        let mut seen = HashSet::new();
+        let unique: Vec<String> = contracts
+            .iter()
+            .map(|c| c.to_lowercase())
+            .filter(|c| seen.insert(c.clone()))
+            .collect();
+
+        let owner = owner.to_string();
+        let checks = bounded_map(unique, OWNERSHIP_CHECK_CONCURRENCY, |contract| {
+            let client = self.client.clone();
+            let owner = owner.clone();
+            async move {
+                let held = NftApi::new(&client)
+                    .is_holder_of_contract(&owner, &contract)
+                    .await;
+                (contract, held)
+            }
+        })
+        .await;
+
+        let contracts = checks
+            .into_iter()
+            .map(|(contract, held)| Ok((contract, held?.is_holder_of_contract)))
+            .collect::<Result<_>>()?;
+        Ok(OwnershipVerification { owner, contracts })
+    }
+
     // ========== Metadata Methods ==========
 
     /// Get metadata for a specific NFT
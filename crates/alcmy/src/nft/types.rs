@@ -1,6 +1,14 @@
 //! Types for the NFT API
 
-use serde::{Deserialize, Serialize};
+use crate::error::{self, Result};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write;
+use tiny_keccak::{Hasher, Keccak};
+
+/// Maximum `isHolderOfContract` lookups in flight during
+/// [`NftApi::verify_ownership`](super::NftApi::verify_ownership)
+pub const OWNERSHIP_CHECK_CONCURRENCY: usize = 8;
 
 /// NFT token type
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -178,21 +186,295 @@ pub struct OwnersForContractResponse {
 }
 
 /// Owner info with token balances
+///
+/// Without `withTokenBalances` the API lists bare addresses; those
+/// deserialize with empty `token_balances`.
 #[derive(Debug, Clone, Deserialize, Serialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", from = "RawOwner")]
 pub struct OwnerInfo {
     pub owner_address: String,
     pub token_balances: Vec<TokenBalance>,
 }
 
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawOwner {
+    Address(String),
+    #[serde(rename_all = "camelCase")]
+    WithBalances {
+        owner_address: String,
+        #[serde(default)]
+        token_balances: Vec<TokenBalance>,
+    },
+}
+
+impl From<RawOwner> for OwnerInfo {
+    fn from(raw: RawOwner) -> Self {
+        match raw {
+            RawOwner::Address(owner_address) => Self {
+                owner_address,
+                token_balances: Vec::new(),
+            },
+            RawOwner::WithBalances {
+                owner_address,
+                token_balances,
+            } => Self {
+                owner_address,
+                token_balances,
+            },
+        }
+    }
+}
+
 /// Token balance for owner
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TokenBalance {
     pub token_id: String,
+    #[serde(deserialize_with = "string_or_number")]
     pub balance: String,
 }
 
+fn string_or_number<'de, D>(deserializer: D) -> std::result::Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::String(s) => Ok(s),
+        serde_json::Value::Number(n) => Ok(n.to_string()),
+        other => Err(serde::de::Error::custom(format!(
+            "expected a string or number balance, got {other}"
+        ))),
+    }
+}
+
+/// Options for getOwnersForContract
+#[derive(Debug, Clone, Default)]
+pub struct OwnersQuery {
+    /// Include each owner's token IDs and balances
+    pub with_token_balances: bool,
+    /// Block number to read ownership at (latest if `None`)
+    pub block: Option<u64>,
+    /// Page key from a previous response
+    pub page_key: Option<String>,
+}
+
+impl OwnersQuery {
+    /// Include each owner's token IDs and balances
+    #[must_use]
+    pub fn with_token_balances(mut self) -> Self {
+        self.with_token_balances = true;
+        self
+    }
+
+    /// Read ownership as of `block`
+    #[must_use]
+    pub fn at_block(mut self, block: u64) -> Self {
+        self.block = Some(block);
+        self
+    }
+
+    #[must_use]
+    pub fn to_query_params(&self) -> Vec<(&'static str, String)> {
+        let mut params = Vec::new();
+        if self.with_token_balances {
+            params.push(("withTokenBalances", "true".to_string()));
+        }
+        if let Some(block) = self.block {
+            params.push(("block", block.to_string()));
+        }
+        if let Some(ref key) = self.page_key {
+            params.push(("pageKey", key.clone()));
+        }
+        params
+    }
+}
+
+/// All owners of a contract and how many tokens each holds, stitched
+/// together from every `getOwnersForContract` page
+///
+/// Addresses are stored lowercased. An owner listed on more than one page
+/// is merged, and a token ID seen twice for the same owner is counted once.
+#[derive(Debug, Clone, Default)]
+pub struct OwnershipSnapshot {
+    /// NFT contract address
+    pub contract: String,
+    /// Block the snapshot was taken at (latest if `None`)
+    pub block: Option<u64>,
+    holdings: BTreeMap<String, BTreeMap<String, u128>>,
+}
+
+/// One owner row of an [`OwnershipSnapshot`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotHolder {
+    /// Owner address, lowercased
+    pub address: String,
+    /// Tokens held: one per ERC-721 token, the balance for ERC-1155
+    pub token_count: u128,
+}
+
+/// Allowlist merkle leaf for one holder
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleLeaf {
+    /// Owner address, lowercased
+    pub address: String,
+    /// Tokens held
+    pub token_count: u128,
+    /// `keccak256(abi.encodePacked(address, uint256(token_count)))`, 0x-prefixed
+    pub leaf: String,
+}
+
+impl OwnershipSnapshot {
+    /// Empty snapshot of `contract` at `block`
+    pub fn new(contract: impl Into<String>, block: Option<u64>) -> Self {
+        Self {
+            contract: contract.into().to_lowercase(),
+            block,
+            holdings: BTreeMap::new(),
+        }
+    }
+
+    /// Merge one page of owners (fetched with token balances)
+    pub fn add_page(&mut self, page: &OwnersForContractResponse) -> Result<()> {
+        for owner in &page.owners {
+            let address = owner.owner_address.to_lowercase();
+            if address_bytes(&address).is_none() {
+                return Err(error::invalid_snapshot(format!(
+                    "invalid owner address {}",
+                    owner.owner_address
+                )));
+            }
+            let tokens = self.holdings.entry(address).or_default();
+            for balance in &owner.token_balances {
+                let amount = parse_balance(&balance.balance).ok_or_else(|| {
+                    error::invalid_snapshot(format!(
+                        "invalid balance {:?} for token {} of {}",
+                        balance.balance, balance.token_id, owner.owner_address
+                    ))
+                })?;
+                tokens.insert(balance.token_id.clone(), amount);
+            }
+        }
+        Ok(())
+    }
+
+    /// Holders with a non-zero token count, sorted by address
+    #[must_use]
+    pub fn holders(&self) -> Vec<SnapshotHolder> {
+        self.holdings
+            .iter()
+            .map(|(address, tokens)| SnapshotHolder {
+                address: address.clone(),
+                token_count: tokens.values().fold(0u128, |sum, n| sum.saturating_add(*n)),
+            })
+            .filter(|holder| holder.token_count > 0)
+            .collect()
+    }
+
+    /// Write `address,token_count` rows, sorted by address, with a header
+    pub fn export_csv<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        writeln!(writer, "address,token_count")?;
+        for holder in self.holders() {
+            writeln!(writer, "{},{}", holder.address, holder.token_count)?;
+        }
+        writer.flush()
+    }
+
+    /// Merkle leaves for an allowlist, in the same order as [`Self::holders`]
+    ///
+    /// Each leaf hashes the 20-byte address followed by the count as a
+    /// 32-byte big-endian word, matching
+    /// `keccak256(abi.encodePacked(address, uint256))` in Solidity.
+    #[must_use]
+    pub fn export_merkle_leaves(&self) -> Vec<MerkleLeaf> {
+        self.holders()
+            .into_iter()
+            .filter_map(|holder| {
+                let leaf = merkle_leaf(&holder.address, holder.token_count)?;
+                Some(MerkleLeaf {
+                    address: holder.address,
+                    token_count: holder.token_count,
+                    leaf: format!("0x{}", hex_encode(&leaf)),
+                })
+            })
+            .collect()
+    }
+}
+
+/// `keccak256(abi.encodePacked(address, uint256(count)))`
+///
+/// Returns `None` if `address` is not 20 bytes of hex.
+#[must_use]
+pub fn merkle_leaf(address: &str, count: u128) -> Option<[u8; 32]> {
+    let mut packed = [0u8; 52];
+    packed[..20].copy_from_slice(&address_bytes(address)?);
+    packed[36..].copy_from_slice(&count.to_be_bytes());
+
+    let mut hash = [0u8; 32];
+    let mut keccak = Keccak::v256();
+    keccak.update(&packed);
+    keccak.finalize(&mut hash);
+    Some(hash)
+}
+
+fn address_bytes(address: &str) -> Option<[u8; 20]> {
+    let hex = address.strip_prefix("0x").unwrap_or(address);
+    if hex.len() != 40 {
+        return None;
+    }
+    let mut bytes = [0u8; 20];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok()?;
+    }
+    Some(bytes)
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Balances come back as decimal strings; accept hex as well
+fn parse_balance(balance: &str) -> Option<u128> {
+    match balance.strip_prefix("0x") {
+        Some(hex) => u128::from_str_radix(hex, 16).ok(),
+        None => balance.parse().ok(),
+    }
+}
+
+/// Result of [`NftApi::verify_ownership`](super::NftApi::verify_ownership)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OwnershipVerification {
+    /// Wallet that was checked
+    pub owner: String,
+    /// `(contract, holds any token)` for each distinct contract, in the
+    /// order given
+    pub contracts: Vec<(String, bool)>,
+}
+
+impl OwnershipVerification {
+    /// Whether the owner holds a token from at least one contract
+    #[must_use]
+    pub fn holds_any(&self) -> bool {
+        self.contracts.iter().any(|(_, held)| *held)
+    }
+
+    /// Whether the owner holds a token from every contract
+    #[must_use]
+    pub fn holds_all(&self) -> bool {
+        self.contracts.iter().all(|(_, held)| *held)
+    }
+
+    /// Contracts the owner holds a token from
+    #[must_use]
+    pub fn held_contracts(&self) -> Vec<&str> {
+        self.contracts
+            .iter()
+            .filter(|(_, held)| *held)
+            .map(|(contract, _)| contract.as_str())
+            .collect()
+    }
+}
+
 /// Response for getContractsForOwner
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
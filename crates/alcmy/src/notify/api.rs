@@ -1,12 +1,13 @@
 //! Notify/Webhooks API implementation
 
 use super::types::{
-    CreateWebhookRequest, GraphqlVariable, ListAddressesResponse, ListNftFiltersResponse,
-    ListWebhooksResponse, PatchGraphqlVariableRequest, ReplaceWebhookAddressesRequest,
-    UpdateNftFiltersRequest, UpdateWebhookAddressesRequest, UpdateWebhookRequest, Webhook,
+    address_batches, AddressActivityWebhook, CreateWebhookRequest, GraphqlVariable,
+    ListAddressesResponse, ListNftFiltersResponse, ListWebhooksResponse,
+    PatchGraphqlVariableRequest, ReplaceWebhookAddressesRequest, UpdateNftFiltersRequest,
+    UpdateWebhookAddressesRequest, UpdateWebhookRequest, Webhook, WebhookNetwork,
 };
 use crate::client::Client;
-use crate::error::{Error, Result};
+use crate::error::{self, Error, Result};
 
 const NOTIFY_BASE_URL: &str = "https://dashboard.alchemy.com/api";
 
@@ -222,6 +223,97 @@ impl<'a> NotifyApi<'a> {
         Ok(())
     }
 
+    /// Create an address activity webhook for `addresses`
+    ///
+    /// The first [`MAX_ADDRESSES_PER_REQUEST`](super::MAX_ADDRESSES_PER_REQUEST)
+    /// unique addresses are sent with the create request and the rest are
+    /// added in batches. Returns the webhook together with its signing key.
+    ///
+    /// If the created webhook has no signing key or a batch fails, the
+    /// webhook is deleted again before the error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example(client: &alcmy::Client) -> alcmy::Result<()> {
+    /// use alcmy::notify::WebhookNetwork;
+    ///
+    /// let created = client
+    ///     .notify()
+    ///     .create_address_activity(
+    ///         "https://example.com/hook",
+    ///         &["0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045"],
+    ///         WebhookNetwork::EthMainnet,
+    ///     )
+    ///     .await?;
+    /// println!("{} signed with {}", created.webhook.id, created.signing_key);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_address_activity(
+        &self,
+        url: &str,
+        addresses: &[&str],
+        network: WebhookNetwork,
+    ) -> Result<AddressActivityWebhook> {
+        let mut batches = address_batches(addresses).into_iter();
+        let request = CreateWebhookRequest::address_activity(
+            url,
+            network,
+            batches.next().unwrap_or_default(),
+        );
+        let webhook = self.create_webhook(&request).await?;
+
+        let configured = async {
+            let signing_key = webhook
+                .signing_key
+                .clone()
+                .ok_or_else(|| error::missing_signing_key(&webhook.id))?;
+            for batch in batches {
+                self.update_webhook_addresses(&UpdateWebhookAddressesRequest::add(
+                    &webhook.id,
+                    batch,
+                ))
+                .await?;
+            }
+            Ok(signing_key)
+        }
+        .await;
+
+        match configured {
+            Ok(signing_key) => Ok(AddressActivityWebhook {
+                webhook,
+                signing_key,
+            }),
+            Err(e) => {
+                // Don't leave a half-configured webhook on the account; the
+                // original error matters more than a failed cleanup
+                let _ = self.delete_webhook(&webhook.id).await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Add addresses to an address activity webhook, batching as needed
+    pub async fn add_addresses(&self, webhook_id: &str, addresses: &[&str]) -> Result<()> {
+        for batch in address_batches(addresses) {
+            self.update_webhook_addresses(&UpdateWebhookAddressesRequest::add(webhook_id, batch))
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Remove addresses from an address activity webhook, batching as needed
+    pub async fn remove_addresses(&self, webhook_id: &str, addresses: &[&str]) -> Result<()> {
+        for batch in address_batches(addresses) {
+            self.update_webhook_addresses(&UpdateWebhookAddressesRequest::remove(
+                webhook_id, batch,
+            ))
+            .await?;
+        }
+        Ok(())
+    }
+
     // ========== NFT Filter Methods ==========
 
     /// List NFT filters for a webhook
@@ -2,6 +2,11 @@
 
 use serde::{Deserialize, Serialize};
 
+/// Maximum addresses sent in a single create or update request
+///
+/// Larger watchlists are split into batches of this size.
+pub const MAX_ADDRESSES_PER_REQUEST: usize = 500;
+
 /// Webhook type
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -99,6 +104,34 @@ pub struct CreateWebhookRequest {
     pub graphql_query: Option<String>,
 }
 
+impl CreateWebhookRequest {
+    /// Address activity webhook tracking `addresses`
+    #[must_use]
+    pub fn address_activity(
+        webhook_url: impl Into<String>,
+        network: WebhookNetwork,
+        addresses: Vec<String>,
+    ) -> Self {
+        Self {
+            network,
+            webhook_type: WebhookType::AddressActivity,
+            webhook_url: webhook_url.into(),
+            addresses: Some(addresses),
+            graphql_query: None,
+        }
+    }
+}
+
+/// Address activity webhook returned by
+/// [`NotifyApi::create_address_activity`](super::NotifyApi::create_address_activity)
+#[derive(Debug, Clone)]
+pub struct AddressActivityWebhook {
+    /// The created webhook
+    pub webhook: Webhook,
+    /// Key for verifying the `X-Alchemy-Signature` header of deliveries
+    pub signing_key: String,
+}
+
 /// Request to update webhook addresses
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -113,6 +146,45 @@ pub struct UpdateWebhookAddressesRequest {
     pub addresses_to_remove: Option<Vec<String>>,
 }
 
+impl UpdateWebhookAddressesRequest {
+    /// Add `addresses` to a webhook
+    #[must_use]
+    pub fn add(webhook_id: impl Into<String>, addresses: Vec<String>) -> Self {
+        Self {
+            webhook_id: webhook_id.into(),
+            addresses_to_add: Some(addresses),
+            addresses_to_remove: None,
+        }
+    }
+
+    /// Remove `addresses` from a webhook
+    #[must_use]
+    pub fn remove(webhook_id: impl Into<String>, addresses: Vec<String>) -> Self {
+        Self {
+            webhook_id: webhook_id.into(),
+            addresses_to_add: None,
+            addresses_to_remove: Some(addresses),
+        }
+    }
+}
+
+/// Split `addresses` into request-sized batches
+///
+/// Duplicates are dropped case-insensitively, keeping the first occurrence.
+#[must_use]
+pub fn address_batches(addresses: &[&str]) -> Vec<Vec<String>> {
+    let mut seen = std::collections::HashSet::new();
+    let unique: Vec<String> = addresses
+        .iter()
+        .filter(|a| seen.insert(a.to_lowercase()))
+        .map(|a| (*a).to_string())
+        .collect();
+    unique
+        .chunks(MAX_ADDRESSES_PER_REQUEST)
+        .map(<[String]>::to_vec)
+        .collect()
+}
+
 /// Request to replace webhook addresses
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -0,0 +1,157 @@
+//! Receipts API implementation
+
+use super::types::{BlockReceipts, Receipt, TransactionBlock};
+use crate::client::Client;
+use crate::error::{self, Result};
+
+/// Most receipts requested in one JSON-RPC batch
+pub const RECEIPT_BATCH_SIZE: usize = 100;
+
+/// Receipts API for batch transaction receipt lookups
+pub struct ReceiptsApi<'a> {
+    client: &'a Client,
+}
+
+impl<'a> ReceiptsApi<'a> {
+    pub(crate) fn new(client: &'a Client) -> Self {
+        Self { client }
+    }
+
+    /// Get a transaction receipt
+    ///
+    /// Errors with `ReceiptNotFound` for unknown or pending transactions.
+    ///
+    /// # Arguments
+    /// * `hash` - Transaction hash
+    pub async fn get_transaction_receipt(&self, hash: &str) -> Result<Receipt> {
+        let receipt: Option<Receipt> = self
+            .client
+            .rpc("eth_getTransactionReceipt", vec![hash])
+            .await?;
+        receipt.ok_or_else(|| error::receipt_not_found(hash))
+    }
+
+    /// Get every receipt in a block via `alchemy_getTransactionReceipts`
+    ///
+    /// # Arguments
+    /// * `block_hash` - Block hash
+    pub async fn get_block_receipts(&self, block_hash: &str) -> Result<Vec<Receipt>> {
+        let params = serde_json::json!({ "blockHash": block_hash });
+        let response: BlockReceipts = self
+            .client
+            .rpc("alchemy_getTransactionReceipts", vec![params])
+            .await?;
+        Ok(response.receipts)
+    }
+
+    /// Get receipts for many transactions, one result per hash in input order
+    ///
+    /// With more than one hash, the transactions' blocks are looked up first
+    /// with batched `eth_getTransactionByHash` calls. Only when every hash
+    /// resolves to the same block is that block read with
+    /// [`get_block_receipts`](Self::get_block_receipts); otherwise, and for
+    /// anything the block lookup missed, receipts are fetched with batched
+    /// `eth_getTransactionReceipt` calls. Batches hold up to
+    /// [`RECEIPT_BATCH_SIZE`] requests.
+    ///
+    /// Unknown transactions yield `ReceiptNotFound` in their slot; the outer
+    /// error is reserved for a batch request failing outright.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let receipts = client.receipts().get_transaction_receipts(&[tx_a, tx_b]).await?;
+    /// for (hash, receipt) in receipts {
+    ///     match receipt {
+    ///         Ok(r) => println!("{hash}: {} logs, success={:?}", r.logs.len(), r.succeeded()),
+    ///         Err(e) => println!("{hash}: {e}"),
+    ///     }
+    /// }
+    /// ```
+    pub async fn get_transaction_receipts(
+        &self,
+        hashes: &[&str],
+    ) -> Result<Vec<(String, Result<Receipt>)>> {
+        let mut slots: Vec<Option<Result<Receipt>>> = hashes.iter().map(|_| None).collect();
+
+        if hashes.len() > 1 {
+            if let Some(block_hash) = self.shared_block(hashes).await? {
+                if let Ok(block) = self.get_block_receipts(&block_hash).await {
+                    fill_from_block(hashes, &block, &mut slots);
+                }
+            }
+        }
+
+        let pending: Vec<usize> = slots
+            .iter()
+            .enumerate()
+            .filter(|(_, slot)| slot.is_none())
+            .map(|(i, _)| i)
+            .collect();
+        for chunk in pending.chunks(RECEIPT_BATCH_SIZE) {
+            let params: Vec<[&str; 1]> = chunk.iter().map(|&i| [hashes[i]]).collect();
+            let results = self
+                .client
+                .rpc_batch::<_, Option<Receipt>>("eth_getTransactionReceipt", &params)
+                .await?;
+            for (&i, result) in chunk.iter().zip(results) {
+                slots[i] =
+                    Some(result.and_then(|r| r.ok_or_else(|| error::receipt_not_found(hashes[i]))));
+            }
+        }
+
+        Ok(hashes
+            .iter()
+            .zip(slots)
+            .map(|(hash, slot)| {
+                let receipt = slot.unwrap_or_else(|| Err(error::receipt_not_found(*hash)));
+                ((*hash).to_string(), receipt)
+            })
+            .collect())
+    }
+
+    /// The block holding every transaction, if they all share one
+    async fn shared_block(&self, hashes: &[&str]) -> Result<Option<String>> {
+        let mut blocks = Vec::with_capacity(hashes.len());
+        for chunk in hashes.chunks(RECEIPT_BATCH_SIZE) {
+            let params: Vec<[&str; 1]> = chunk.iter().map(|&hash| [hash]).collect();
+            let results = self
+                .client
+                .rpc_batch::<_, Option<TransactionBlock>>("eth_getTransactionByHash", &params)
+                .await?;
+            blocks.extend(
+                results
+                    .into_iter()
+                    .map(|r| r.ok().flatten().and_then(|tx| tx.block_hash)),
+            );
+        }
+        Ok(single_block(&blocks))
+    }
+}
+
+/// The block hash every entry shares, `None` if any is missing or differs
+pub(crate) fn single_block(blocks: &[Option<String>]) -> Option<String> {
+    let first = blocks.first()?.as_ref()?;
+    blocks
+        .iter()
+        .all(|b| b.as_ref().is_some_and(|b| b.eq_ignore_ascii_case(first)))
+        .then(|| first.clone())
+}
+
+/// Fill the empty slots whose hash appears among a block's receipts
+pub(crate) fn fill_from_block(
+    hashes: &[&str],
+    block: &[Receipt],
+    slots: &mut [Option<Result<Receipt>>],
+) {
+    for (hash, slot) in hashes.iter().zip(slots.iter_mut()) {
+        if slot.is_some() {
+            continue;
+        }
+        if let Some(receipt) = block
+            .iter()
+            .find(|r| r.transaction_hash.eq_ignore_ascii_case(hash))
+        {
+            *slot = Some(Ok(receipt.clone()));
+        }
+    }
+}
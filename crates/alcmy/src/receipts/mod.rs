@@ -0,0 +1,9 @@
+//! Receipts API for batch transaction receipt lookups
+
+mod api;
+mod types;
+
+#[cfg(test)]
+pub(crate) use api::{fill_from_block, single_block};
+pub use api::{ReceiptsApi, RECEIPT_BATCH_SIZE};
+pub use types::*;
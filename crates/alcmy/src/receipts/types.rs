@@ -0,0 +1,103 @@
+//! Types for the Receipts API
+
+use serde::{Deserialize, Serialize};
+
+/// Transaction receipt from `eth_getTransactionReceipt`
+///
+/// Quantities are kept as the hex strings the node returns.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Receipt {
+    /// Transaction hash
+    pub transaction_hash: String,
+    /// Index of the transaction in its block
+    pub transaction_index: String,
+    /// Block hash
+    pub block_hash: String,
+    /// Block number
+    pub block_number: String,
+    /// Sender
+    pub from: String,
+    /// Recipient (`None` for contract creation)
+    pub to: Option<String>,
+    /// Created contract address, if any
+    pub contract_address: Option<String>,
+    /// Gas used by this transaction
+    pub gas_used: String,
+    /// Gas used by the block up to and including this transaction
+    pub cumulative_gas_used: String,
+    /// Price paid per unit of gas
+    pub effective_gas_price: Option<String>,
+    /// `0x1` on success, `0x0` on revert (absent before Byzantium)
+    pub status: Option<String>,
+    /// Transaction type
+    #[serde(rename = "type")]
+    pub tx_type: Option<String>,
+    /// Logs emitted
+    #[serde(default)]
+    pub logs: Vec<ReceiptLog>,
+    /// Logs bloom filter
+    pub logs_bloom: Option<String>,
+}
+
+impl Receipt {
+    /// Whether the transaction succeeded
+    ///
+    /// `None` for pre-Byzantium receipts, which carry a state root instead
+    /// of a status.
+    #[must_use]
+    pub fn succeeded(&self) -> Option<bool> {
+        self.status
+            .as_deref()
+            .and_then(parse_hex_u64)
+            .map(|s| s == 1)
+    }
+
+    /// Block number as an integer
+    #[must_use]
+    pub fn block_number_u64(&self) -> Option<u64> {
+        parse_hex_u64(&self.block_number)
+    }
+
+    /// Gas used as an integer
+    #[must_use]
+    pub fn gas_used_u64(&self) -> Option<u64> {
+        parse_hex_u64(&self.gas_used)
+    }
+}
+
+/// Log emitted by a transaction
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReceiptLog {
+    /// Emitting contract
+    pub address: String,
+    /// Indexed topics
+    pub topics: Vec<String>,
+    /// Non-indexed data
+    pub data: String,
+    /// Index of the log in its block
+    pub log_index: Option<String>,
+    /// Whether the log was removed by a reorg
+    #[serde(default)]
+    pub removed: bool,
+}
+
+/// Response of `alchemy_getTransactionReceipts`
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct BlockReceipts {
+    pub receipts: Vec<Receipt>,
+}
+
+/// Block hash of a transaction from `eth_getTransactionByHash`
+///
+/// `None` while the transaction is pending.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TransactionBlock {
+    pub block_hash: Option<String>,
+}
+
+fn parse_hex_u64(value: &str) -> Option<u64> {
+    u64::from_str_radix(value.strip_prefix("0x").unwrap_or(value), 16).ok()
+}
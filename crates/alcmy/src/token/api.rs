@@ -1,10 +1,25 @@
 //! Token API implementation (RPC methods)
 
 use super::types::{
-    RpcTokenBalancesOptions, RpcTokenBalancesResponse, RpcTokenMetadata, TokenSpec,
+    is_unlimited_allowance, parse_allowance, AllowanceCandidates, AllowanceScan,
+    AllowanceScanOptions, ApprovalLog, MultichainBalances, RpcTokenBalancesOptions,
+    RpcTokenBalancesResponse, RpcTokenMetadata, TokenAllowance, TokenSpec,
+    ALLOWANCE_SCAN_CONCURRENCY, APPROVAL_TOPIC, DEFAULT_MAX_ALLOWANCE_PAIRS,
+    MULTICHAIN_CONCURRENCY,
 };
-use crate::client::Client;
-use crate::error::Result;
+use crate::client::{Client, Network};
+use crate::error::{Error, Result};
+use crate::transfers::{AssetTransfer, AssetTransfersOptions, TransferCategory, TransfersApi};
+use std::future::Future;
+
+/// Maximum pages of transfers read per direction during discovery
+const MAX_DISCOVERY_PAGES: usize = 5;
+
+/// Maximum `eth_getLogs` calls made while searching for Approval logs
+const MAX_APPROVAL_LOG_REQUESTS: usize = 32;
+
+/// Smallest block range split further when a node refuses an `eth_getLogs` range
+const MIN_APPROVAL_LOG_SPAN: u64 = 2_000;
 
 /// Token API for ERC-20 token operations via RPC
 pub struct TokenApi<'a> {
@@ -114,4 +129,290 @@ impl<'a> TokenApi<'a> {
             .rpc("alchemy_getTokenAllowance", vec![params])
             .await
     }
+
+    /// Find a wallet's non-zero ERC-20 allowances
+    ///
+    /// Checks every `(token, spender)` pair built from `options`. With
+    /// [`discover_from_transfers`](AllowanceScanOptions::discover_from_transfers)
+    /// the token and spender sets are widened from the owner's asset
+    /// transfers and, where the node serves them, its `Approval` logs (see
+    /// [`AllowanceCandidates`]); logs also supply the block and time of the
+    /// latest approval. If `options.contracts` is set, only those tokens are
+    /// checked.
+    ///
+    /// Lookups run at most [`ALLOWANCE_SCAN_CONCURRENCY`] at a time, and
+    /// token metadata is joined onto the results. If Approval logs can only
+    /// be read for part of the range, the scan uses what it got and reports
+    /// the failure in [`AllowanceScan::log_error`].
+    ///
+    /// # Example
+    /// ```ignore
+    /// let options = AllowanceScanOptions {
+    ///     known_spenders: vec!["0x000000000022d473030f116ddee9f6b43ac78ba3".into()],
+    ///     discover_from_transfers: true,
+    ///     ..Default::default()
+    /// };
+    /// let scan = client.token().get_allowances_for_owner("0x123...", &options).await?;
+    /// for a in &scan.allowances {
+    ///     println!("{} -> {} unlimited={}", a.contract, a.spender, a.unlimited);
+    /// }
+    /// ```
+    pub async fn get_allowances_for_owner(
+        &self,
+        owner: &str,
+        options: &AllowanceScanOptions,
+    ) -> Result<AllowanceScan> {
+        let mut candidates = AllowanceCandidates::from_options(options);
+        let mut log_error = None;
+        if options.discover_from_transfers {
+            let transfers = self.owner_transfers(owner).await?;
+            candidates.add_transfers(owner, &transfers);
+            // Not every network serves an unbounded eth_getLogs; discovery
+            // from transfers and any logs already read still stand
+            let (logs, error) = self
+                .approval_logs(owner, options.from_block.unwrap_or(0))
+                .await;
+            candidates.add_approval_logs(owner, &logs);
+            log_error = error;
+        }
+        if let Some(contracts) = &options.contracts {
+            candidates.restrict_contracts(contracts);
+        }
+
+        let owner = owner.to_string();
+        let lookups = bounded_map(
+            candidates.pairs(
+                &owner,
+                options.max_pairs.unwrap_or(DEFAULT_MAX_ALLOWANCE_PAIRS),
+            ),
+            ALLOWANCE_SCAN_CONCURRENCY,
+            |(contract, spender)| {
+                let client = self.client.clone();
+                let owner = owner.clone();
+                async move {
+                    let allowance = TokenApi::new(&client)
+                        .get_token_allowance(&contract, &owner, &spender)
+                        .await;
+                    (contract, spender, allowance)
+                }
+            },
+        )
+        .await;
+
+        let mut allowances = Vec::new();
+        for (contract, spender, allowance) in lookups {
+            let allowance = allowance?;
+            let Some(value) = parse_allowance(&allowance).filter(|v| *v > 0) else {
+                continue;
+            };
+            let approval = candidates
+                .approvals
+                .get(&(contract.clone(), spender.clone()));
+            allowances.push(TokenAllowance {
+                contract,
+                spender,
+                allowance,
+                unlimited: is_unlimited_allowance(value),
+                metadata: None,
+                last_approval_block: approval.map(|a| a.block_number),
+                last_approval_timestamp: approval.and_then(|a| a.timestamp),
+            });
+        }
+
+        let mut contracts: Vec<String> = allowances.iter().map(|a| a.contract.clone()).collect();
+        contracts.sort();
+        contracts.dedup();
+        let metadata = bounded_map(contracts, ALLOWANCE_SCAN_CONCURRENCY, |contract| {
+            let client = self.client.clone();
+            async move {
+                let metadata = TokenApi::new(&client)
+                    .get_token_metadata(&contract)
+                    .await
+                    .ok();
+                (contract, metadata)
+            }
+        })
+        .await;
+        for allowance in &mut allowances {
+            allowance.metadata = metadata
+                .iter()
+                .find(|(contract, _)| *contract == allowance.contract)
+                .and_then(|(_, metadata)| metadata.clone());
+        }
+        Ok(AllowanceScan {
+            allowances,
+            log_error,
+        })
+    }
+
+    /// ERC-20 transfers to and from the owner, plus the owner's transactions
+    async fn owner_transfers(&self, owner: &str) -> Result<Vec<AssetTransfer>> {
+        let transfers = TransfersApi::new(self.client);
+        let outgoing = AssetTransfersOptions {
+            category: Some(vec![TransferCategory::External, TransferCategory::Erc20]),
+            ..AssetTransfersOptions::from_address(owner)
+        };
+        let incoming = AssetTransfersOptions {
+            category: Some(vec![TransferCategory::Erc20]),
+            ..AssetTransfersOptions::to_address(owner)
+        };
+
+        let mut all = Vec::new();
+        for options in [outgoing, incoming] {
+            let mut response = transfers.get_asset_transfers(&options).await?;
+            for _ in 1..MAX_DISCOVERY_PAGES {
+                all.append(&mut response.transfers);
+                let Some(page_key) = response.page_key.take() else {
+                    break;
+                };
+                response = transfers.get_next_page(&options, &page_key).await?;
+            }
+            all.append(&mut response.transfers);
+        }
+        Ok(all)
+    }
+
+    /// `Approval` logs emitted for the owner since `from_block`
+    ///
+    /// The whole range is asked for first; a range the node refuses (too
+    /// many results or blocks) is split in half and retried, down to
+    /// [`MIN_APPROVAL_LOG_SPAN`] blocks and at most
+    /// [`MAX_APPROVAL_LOG_REQUESTS`] calls. The logs read before a range
+    /// fails for good are returned together with that error.
+    async fn approval_logs(
+        &self,
+        owner: &str,
+        from_block: u64,
+    ) -> (Vec<ApprovalLog>, Option<Error>) {
+        let owner_topic = format!("0x{:0>64}", owner.trim_start_matches("0x").to_lowercase());
+        let latest: String = match self.client.rpc("eth_blockNumber", [(); 0]).await {
+            Ok(latest) => latest,
+            Err(e) => return (Vec::new(), Some(e)),
+        };
+        let latest = u64::from_str_radix(latest.trim_start_matches("0x"), 16).unwrap_or(u64::MAX);
+        if from_block > latest {
+            return (Vec::new(), None);
+        }
+
+        let mut logs = Vec::new();
+        let mut ranges = vec![(from_block, latest)];
+        let mut requests = 0;
+        while let Some((from, to)) = ranges.pop() {
+            requests += 1;
+            let filter = serde_json::json!({
+                "fromBlock": format!("0x{from:x}"),
+                "toBlock": format!("0x{to:x}"),
+                "topics": [APPROVAL_TOPIC, owner_topic]
+            });
+            match self
+                .client
+                .rpc::<_, Vec<ApprovalLog>>("eth_getLogs", vec![filter])
+                .await
+            {
+                Ok(mut page) => logs.append(&mut page),
+                Err(e) => match split_block_range(from, to) {
+                    Some((first, second)) if requests < MAX_APPROVAL_LOG_REQUESTS => {
+                        // Popped last-in first, so `first` runs next
+                        ranges.push(second);
+                        ranges.push(first);
+                    }
+                    _ => return (logs, Some(e)),
+                },
+            }
+        }
+        (logs, None)
+    }
+}
+
+impl Client {
+    /// Get ERC-20 token balances for an address on several networks at once
+    ///
+    /// Runs [`TokenApi::get_token_balances`] concurrently on each network
+    /// with clients from [`Client::for_network`], so one API key and HTTP
+    /// pool serve every chain. Repeated networks are queried once. A failing
+    /// network is reported in [`MultichainBalances::errors`] and does not
+    /// stop the others.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let result = client
+    ///     .get_balances_multichain("0x123...", &[Network::EthMainnet, Network::BaseMainnet])
+    ///     .await;
+    /// for (network, balances) in &result.balances {
+    ///     println!("{}: {} tokens", network.slug(), balances.len());
+    /// }
+    /// ```
+    pub async fn get_balances_multichain(
+        &self,
+        address: &str,
+        networks: &[Network],
+    ) -> MultichainBalances {
+        let mut unique: Vec<Network> = Vec::with_capacity(networks.len());
+        for network in networks {
+            if !unique.contains(network) {
+                unique.push(*network);
+            }
+        }
+
+        let address = address.to_string();
+        let lookups = bounded_map(unique, MULTICHAIN_CONCURRENCY, |network| {
+            let client = self.for_network(network);
+            let address = address.clone();
+            async move {
+                let balances = client.token().get_token_balances(&address).await;
+                (network, balances)
+            }
+        })
+        .await;
+
+        let mut result = MultichainBalances::default();
+        for (network, balances) in lookups {
+            match balances {
+                Ok(response) => result.balances.push((network, response.token_balances)),
+                Err(e) => result.errors.push((network, e)),
+            }
+        }
+        result
+    }
+}
+
+/// Halves of an inclusive block range, `None` if it is too small to split
+pub(crate) fn split_block_range(from: u64, to: u64) -> Option<((u64, u64), (u64, u64))> {
+    if to.saturating_sub(from) < MIN_APPROVAL_LOG_SPAN {
+        return None;
+    }
+    let mid = from + (to - from) / 2;
+    Some(((from, mid), (mid + 1, to)))
+}
+
+/// Run `f` over `items` with at most `limit` futures in flight
+///
+/// Results come back in input order.
+pub(crate) async fn bounded_map<I, F, Fut>(items: Vec<I>, limit: usize, f: F) -> Vec<Fut::Output>
+where
+    F: Fn(I) -> Fut,
+    Fut: Future + Send + 'static,
+    Fut::Output: Send + 'static,
+{
+    let total = items.len();
+    let mut results: Vec<Option<Fut::Output>> = (0..total).map(|_| None).collect();
+    let mut tasks = tokio::task::JoinSet::new();
+    let mut collect = |joined: std::result::Result<(usize, Fut::Output), _>| match joined {
+        Ok((index, output)) => results[index] = Some(output),
+        Err(e) => std::panic::resume_unwind(tokio::task::JoinError::into_panic(e)),
+    };
+
+    for (index, item) in items.into_iter().enumerate() {
+        if tasks.len() >= limit.max(1) {
+            if let Some(joined) = tasks.join_next().await {
+                collect(joined);
+            }
+        }
+        let future = f(item);
+        tasks.spawn(async move { (index, future.await) });
+    }
+    while let Some(joined) = tasks.join_next().await {
+        collect(joined);
+    }
+    results.into_iter().flatten().collect()
 }
@@ -3,5 +3,8 @@
 mod api;
 mod types;
 
+pub(crate) use api::bounded_map;
+#[cfg(test)]
+pub(crate) use api::split_block_range;
 pub use api::TokenApi;
 pub use types::*;
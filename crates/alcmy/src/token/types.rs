@@ -1,6 +1,10 @@
 //! Types for the Token API (RPC methods)
 
+use crate::client::Network;
+use crate::error::Error;
+use crate::transfers::{AssetTransfer, TransferCategory};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 
 /// Token balance result
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -78,3 +82,324 @@ pub struct RpcTokenBalancesOptions {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_count: Option<u32>,
 }
+
+/// Allowances at or above this are reported as unlimited
+///
+/// This is `type(uint96).max`, which tokens such as UNI and COMP store in
+/// place of `type(uint256).max`, so it catches both conventions.
+pub const UNLIMITED_ALLOWANCE_THRESHOLD: u128 = (1u128 << 96) - 1;
+
+/// Maximum allowance lookups in flight during an allowance scan
+pub const ALLOWANCE_SCAN_CONCURRENCY: usize = 8;
+
+/// Default maximum `(contract, spender)` pairs checked by one allowance scan
+pub const DEFAULT_MAX_ALLOWANCE_PAIRS: usize = 500;
+
+/// Maximum networks queried at once by
+/// [`Client::get_balances_multichain`](crate::Client::get_balances_multichain)
+pub const MULTICHAIN_CONCURRENCY: usize = 8;
+
+/// Token balances for one address across several networks
+///
+/// A network whose lookup fails lands in `errors` instead of aborting the
+/// whole query.
+#[derive(Debug, Default)]
+pub struct MultichainBalances {
+    /// Balances per network, in the order the networks were requested
+    pub balances: Vec<(Network, Vec<RpcTokenBalance>)>,
+    /// Networks whose lookup failed, with the error
+    pub errors: Vec<(Network, Error)>,
+}
+
+impl MultichainBalances {
+    /// Whether every requested network returned balances
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Balances for one network, if its lookup succeeded
+    #[must_use]
+    pub fn for_network(&self, network: Network) -> Option<&[RpcTokenBalance]> {
+        self.balances
+            .iter()
+            .find(|(n, _)| *n == network)
+            .map(|(_, balances)| balances.as_slice())
+    }
+}
+
+/// `keccak256("Approval(address,address,uint256)")`
+pub const APPROVAL_TOPIC: &str =
+    "0x8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925";
+
+/// Options for [`TokenApi::get_allowances_for_owner`](super::TokenApi::get_allowances_for_owner)
+#[derive(Debug, Clone, Default)]
+pub struct AllowanceScanOptions {
+    /// Only check these token contracts (default: known and discovered tokens)
+    pub contracts: Option<Vec<String>>,
+    /// Spenders to always check
+    pub known_spenders: Vec<String>,
+    /// Find tokens and spenders from the owner's transfers and Approval logs
+    pub discover_from_transfers: bool,
+    /// First block searched for Approval logs (default: genesis)
+    pub from_block: Option<u64>,
+    /// Maximum pairs to check (default: [`DEFAULT_MAX_ALLOWANCE_PAIRS`])
+    pub max_pairs: Option<usize>,
+}
+
+/// Approval log as returned by `eth_getLogs`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalLog {
+    /// Token contract that emitted the event
+    pub address: String,
+    /// Event topics (signature, owner, spender)
+    pub topics: Vec<String>,
+    /// Approved amount (hex)
+    #[serde(default)]
+    pub data: String,
+    /// Block number (hex)
+    pub block_number: Option<String>,
+    /// Block timestamp (hex), if the node includes it
+    pub block_timestamp: Option<String>,
+}
+
+/// Latest Approval event seen for a token/spender pair
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApprovalEvent {
+    /// Block the approval was mined in
+    pub block_number: u64,
+    /// Block timestamp (unix seconds), if known
+    pub timestamp: Option<u64>,
+}
+
+/// Token contracts and spenders to check allowances for
+///
+/// Addresses are stored lowercased.
+#[derive(Debug, Clone, Default)]
+pub struct AllowanceCandidates {
+    /// Token contracts
+    pub contracts: BTreeSet<String>,
+    /// Spender addresses
+    pub spenders: BTreeSet<String>,
+    /// Latest approval per `(contract, spender)`
+    pub approvals: BTreeMap<(String, String), ApprovalEvent>,
+}
+
+impl AllowanceCandidates {
+    /// Seed candidates from scan options
+    #[must_use]
+    pub fn from_options(options: &AllowanceScanOptions) -> Self {
+        let mut candidates = Self::default();
+        for contract in options.contracts.iter().flatten() {
+            candidates.contracts.insert(contract.to_lowercase());
+        }
+        for spender in &options.known_spenders {
+            candidates.spenders.insert(spender.to_lowercase());
+        }
+        candidates
+    }
+
+    /// Add tokens and counterparties from the owner's asset transfers
+    ///
+    /// Every ERC-20 moved to or from the owner is a candidate token.
+    /// Recipients of the owner's outgoing ERC-20 transfers and targets of the
+    /// owner's transactions are candidate spenders: routers and pools that
+    /// pull tokens with `transferFrom` show up this way.
+    pub fn add_transfers(&mut self, owner: &str, transfers: &[AssetTransfer]) {
+        let owner = owner.to_lowercase();
+        for transfer in transfers {
+            let from_owner = transfer.from.eq_ignore_ascii_case(&owner);
+            let counterparty = transfer
+                .to
+                .as_deref()
+                .map(str::to_lowercase)
+                .filter(|to| *to != owner);
+            match transfer.category {
+                TransferCategory::Erc20 => {
+                    if let Some(contract) = transfer
+                        .raw_contract
+                        .as_ref()
+                        .and_then(|raw| raw.address.as_deref())
+                    {
+                        self.contracts.insert(contract.to_lowercase());
+                    }
+                    if from_owner {
+                        self.spenders.extend(counterparty);
+                    }
+                }
+                TransferCategory::External if from_owner => {
+                    self.spenders.extend(counterparty);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Add tokens, spenders and approval times from the owner's Approval logs
+    ///
+    /// Logs for other owners or with malformed topics are ignored.
+    pub fn add_approval_logs(&mut self, owner: &str, logs: &[ApprovalLog]) {
+        for log in logs {
+            let [signature, log_owner, spender, ..] = log.topics.as_slice() else {
+                continue;
+            };
+            if !signature.eq_ignore_ascii_case(APPROVAL_TOPIC)
+                || !topic_address(log_owner).is_some_and(|o| o.eq_ignore_ascii_case(owner))
+            {
+                continue;
+            }
+            let Some(spender) = topic_address(spender) else {
+                continue;
+            };
+            let contract = log.address.to_lowercase();
+            self.contracts.insert(contract.clone());
+            self.spenders.insert(spender.clone());
+
+            let Some(block_number) = log.block_number.as_deref().and_then(parse_hex_u64) else {
+                continue;
+            };
+            let event = ApprovalEvent {
+                block_number,
+                timestamp: log.block_timestamp.as_deref().and_then(parse_hex_u64),
+            };
+            self.approvals
+                .entry((contract, spender))
+                .and_modify(|latest| {
+                    if event.block_number > latest.block_number {
+                        *latest = event;
+                    }
+                })
+                .or_insert(event);
+        }
+    }
+
+    /// Keep only the given token contracts
+    pub fn restrict_contracts(&mut self, contracts: &[String]) {
+        let allowed: BTreeSet<String> = contracts.iter().map(|c| c.to_lowercase()).collect();
+        self.contracts.retain(|c| allowed.contains(c));
+    }
+
+    /// Up to `limit` `(contract, spender)` pairs to query
+    ///
+    /// Pairs with a recorded Approval event come first, since they are the
+    /// likeliest to hold an allowance; the rest of the contracts × spenders
+    /// product fills the remaining slots. Pairs where the spender is the
+    /// owner or the token itself are skipped.
+    #[must_use]
+    pub fn pairs(&self, owner: &str, limit: usize) -> Vec<(String, String)> {
+        let owner = owner.to_lowercase();
+        let wanted = |contract: &String, spender: &String| {
+            *spender != owner
+                && spender != contract
+                && self.contracts.contains(contract)
+                && self.spenders.contains(spender)
+        };
+
+        let mut pairs: Vec<(String, String)> = self
+            .approvals
+            .keys()
+            .filter(|(contract, spender)| wanted(contract, spender))
+            .take(limit)
+            .cloned()
+            .collect();
+        'outer: for contract in &self.contracts {
+            for spender in &self.spenders {
+                if pairs.len() >= limit {
+                    break 'outer;
+                }
+                let pair = (contract.clone(), spender.clone());
+                if wanted(contract, spender) && !self.approvals.contains_key(&pair) {
+                    pairs.push(pair);
+                }
+            }
+        }
+        pairs
+    }
+}
+
+/// A non-zero allowance found by an allowance scan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenAllowance {
+    /// Token contract (lowercased)
+    pub contract: String,
+    /// Spender (lowercased)
+    pub spender: String,
+    /// Allowance as returned by `alchemy_getTokenAllowance`
+    pub allowance: String,
+    /// Whether the allowance is effectively unlimited
+    pub unlimited: bool,
+    /// Token metadata, if it could be fetched
+    pub metadata: Option<RpcTokenMetadata>,
+    /// Block of the latest Approval event, if one was found
+    pub last_approval_block: Option<u64>,
+    /// Timestamp of the latest Approval event, if known
+    pub last_approval_timestamp: Option<u64>,
+}
+
+/// Result of [`TokenApi::get_allowances_for_owner`](super::TokenApi::get_allowances_for_owner)
+///
+/// Approval log discovery that fails partway keeps the logs it already
+/// fetched and records why it stopped in `log_error`, instead of failing the
+/// whole scan.
+#[derive(Debug, Default)]
+pub struct AllowanceScan {
+    /// Non-zero allowances, in check order
+    pub allowances: Vec<TokenAllowance>,
+    /// Why Approval log discovery stopped early; pairs only seen in the
+    /// missing blocks were not checked
+    pub log_error: Option<Error>,
+}
+
+impl AllowanceScan {
+    /// Whether Approval log discovery covered the whole block range
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.log_error.is_none()
+    }
+}
+
+/// Parse an allowance string (hex with `0x`, or decimal), saturating at `u128::MAX`
+#[must_use]
+pub fn parse_allowance(value: &str) -> Option<u128> {
+    let value = value.trim();
+    let (digits, radix) = match value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        Some(hex) => (hex, 16),
+        None => (value, 10),
+    };
+    if digits.is_empty() {
+        return None;
+    }
+    digits.chars().try_fold(0u128, |acc, c| {
+        let digit = c.to_digit(radix)?;
+        Some(
+            acc.saturating_mul(u128::from(radix))
+                .saturating_add(u128::from(digit)),
+        )
+    })
+}
+
+/// Whether an allowance counts as unlimited
+#[must_use]
+pub fn is_unlimited_allowance(value: u128) -> bool {
+    value >= UNLIMITED_ALLOWANCE_THRESHOLD
+}
+
+/// Address held in the low 20 bytes of a 32-byte log topic
+///
+/// `None` unless the topic is exactly 32 bytes of hex.
+fn topic_address(topic: &str) -> Option<String> {
+    let hex = topic.strip_prefix("0x").unwrap_or(topic);
+    if hex.len() != 64 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    Some(format!("0x{}", hex[24..].to_ascii_lowercase()))
+}
+
+fn parse_hex_u64(value: &str) -> Option<u64> {
+    u64::from_str_radix(value.strip_prefix("0x").unwrap_or(value), 16).ok()
+}
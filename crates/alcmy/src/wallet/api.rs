@@ -3,8 +3,8 @@
 use super::types::{
     AccountResponse, CallsStatusResponse, CreateSessionRequest, CreateSessionResponse,
     ListAccountsResponse, PrepareCallsRequest, PreparedCallsResponse, RequestAccountParams,
-    SendPreparedCallsRequest, SendPreparedCallsResponse, Signature, SignatureRequest,
-    WalletCapabilities,
+    SendPreparedCallsRequest, SendPreparedCallsResponse, SessionKey, SessionPolicy, Signature,
+    SignatureRequest, WalletCapabilities,
 };
 use crate::client::Client;
 use crate::error::Result;
@@ -64,6 +64,8 @@ impl<'a> WalletApi<'a> {
     }
 
     /// Get status of submitted calls
+    ///
+    /// Use [`CallsStatusResponse::status_kind`] for the typed status.
     pub async fn get_calls_status(&self, call_id: &str) -> Result<CallsStatusResponse> {
         self.client
             .rpc("wallet_getCallsStatus", vec![call_id])
@@ -91,6 +93,24 @@ impl<'a> WalletApi<'a> {
         self.client.rpc("wallet_createSession", vec![request]).await
     }
 
+    /// Create a session key from a high-level policy
+    pub async fn create_session_with_policy(
+        &self,
+        account: &str,
+        chain_id: u64,
+        key: SessionKey,
+        policy: &SessionPolicy,
+    ) -> Result<CreateSessionResponse> {
+        let request = CreateSessionRequest::from_policy(account, chain_id, key, policy);
+        self.create_session(&request).await
+    }
+
+    /// Revoke a session key
+    pub async fn revoke_session(&self, session_id: &str) -> Result<serde_json::Value> {
+        let params = serde_json::json!({ "sessionId": session_id });
+        self.client.rpc("wallet_revokeSession", vec![params]).await
+    }
+
     /// Format a signature for the wallet
     pub async fn format_sign(
         &self,
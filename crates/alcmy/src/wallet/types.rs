@@ -110,6 +110,32 @@ pub struct WalletCall {
     pub value: Option<String>,
 }
 
+impl WalletCall {
+    /// Create a call to `to` with no data or value
+    #[must_use]
+    pub fn new(to: impl Into<String>) -> Self {
+        Self {
+            to: to.into(),
+            data: None,
+            value: None,
+        }
+    }
+
+    /// Set the call data (hex)
+    #[must_use]
+    pub fn data(mut self, data: impl Into<String>) -> Self {
+        self.data = Some(data.into());
+        self
+    }
+
+    /// Set the value in wei
+    #[must_use]
+    pub fn value(mut self, wei: u128) -> Self {
+        self.value = Some(format!("0x{wei:x}"));
+        self
+    }
+}
+
 /// Capabilities for prepare calls
 #[derive(Debug, Clone, Default, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -117,11 +143,55 @@ pub struct PrepareCallsCapabilities {
     /// Paymaster service
     #[serde(skip_serializing_if = "Option::is_none")]
     pub paymaster_service: Option<PaymasterServiceCapability>,
+    /// Session key permissions used to authorize the calls
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<PermissionsCapability>,
     /// Gas overrides
     #[serde(skip_serializing_if = "Option::is_none")]
     pub gas_overrides: Option<serde_json::Value>,
 }
 
+impl PrepareCallsCapabilities {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sponsor gas with a Gas Manager policy
+    #[must_use]
+    pub fn paymaster(mut self, policy_id: impl Into<String>) -> Self {
+        self.paymaster_service = Some(PaymasterServiceCapability {
+            policy_id: policy_id.into(),
+        });
+        self
+    }
+
+    /// Authorize the calls with a session's permissions context
+    #[must_use]
+    pub fn session(mut self, context: impl Into<String>) -> Self {
+        self.permissions = Some(PermissionsCapability {
+            context: Some(context.into()),
+        });
+        self
+    }
+
+    /// Set raw gas overrides
+    #[must_use]
+    pub fn gas_overrides(mut self, overrides: serde_json::Value) -> Self {
+        self.gas_overrides = Some(overrides);
+        self
+    }
+}
+
+/// Permissions capability (session key context)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionsCapability {
+    /// Opaque permissions context returned when the session was created
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+}
+
 /// Paymaster service capability
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -145,15 +215,54 @@ pub struct PrepareCallsRequest {
     pub capabilities: Option<PrepareCallsCapabilities>,
 }
 
+impl PrepareCallsRequest {
+    /// Create a batched call request for an account
+    #[must_use]
+    pub fn new(from: impl Into<String>, chain_id: u64, calls: &[WalletCall]) -> Self {
+        Self {
+            calls: calls.to_vec(),
+            from: from.into(),
+            chain_id: format!("0x{chain_id:x}"),
+            capabilities: None,
+        }
+    }
+
+    /// Attach capabilities (paymaster, session permissions)
+    #[must_use]
+    pub fn with_capabilities(mut self, capabilities: PrepareCallsCapabilities) -> Self {
+        self.capabilities = Some(capabilities);
+        self
+    }
+}
+
 /// Prepared calls response
+///
+/// Fields are optional because the response shape differs between API
+/// versions (older responses carry `preparedCallId`, newer ones return the
+/// typed payload with a single `signatureRequest`).
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PreparedCallsResponse {
-    /// Prepared call ID
-    pub prepared_call_id: String,
-    /// User operations
+    /// Prepared call ID (older API versions)
+    #[serde(default)]
+    pub prepared_call_id: Option<String>,
+    /// Payload type (e.g. `user-operation-v070`)
+    #[serde(rename = "type", default)]
+    pub call_type: Option<String>,
+    /// Payload to be signed and sent back
+    #[serde(default)]
+    pub data: Option<serde_json::Value>,
+    /// Chain ID (hex)
+    #[serde(default)]
+    pub chain_id: Option<String>,
+    /// User operations (older API versions)
+    #[serde(default)]
     pub user_operations: Vec<serde_json::Value>,
-    /// Signature requests
+    /// Signature request
+    #[serde(default)]
+    pub signature_request: Option<SignatureRequest>,
+    /// Signature requests (older API versions)
+    #[serde(default)]
     pub signature_requests: Vec<SignatureRequest>,
     /// Fee info
     pub fee_info: Option<FeeInfo>,
@@ -181,13 +290,52 @@ pub struct FeeInfo {
 }
 
 /// Send prepared calls request
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SendPreparedCallsRequest {
-    /// Prepared call ID
-    pub prepared_call_id: String,
-    /// Signatures
+    /// Prepared call ID (older API versions)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prepared_call_id: Option<String>,
+    /// Payload type, echoed from the prepare response
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub call_type: Option<String>,
+    /// Payload, echoed from the prepare response
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+    /// Chain ID (hex)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<String>,
+    /// Signature over the signature request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<Signature>,
+    /// Signatures (older API versions)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub signatures: Vec<Signature>,
+    /// Capabilities (e.g. session permissions)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capabilities: Option<PrepareCallsCapabilities>,
+}
+
+impl SendPreparedCallsRequest {
+    /// Build a send request from a prepare response and its signature
+    #[must_use]
+    pub fn signed(prepared: &PreparedCallsResponse, signature: Signature) -> Self {
+        Self {
+            prepared_call_id: prepared.prepared_call_id.clone(),
+            call_type: prepared.call_type.clone(),
+            data: prepared.data.clone(),
+            chain_id: prepared.chain_id.clone(),
+            signature: Some(signature),
+            ..Self::default()
+        }
+    }
+
+    /// Attach capabilities (required when the calls were prepared with a session)
+    #[must_use]
+    pub fn with_capabilities(mut self, capabilities: PrepareCallsCapabilities) -> Self {
+        self.capabilities = Some(capabilities);
+        self
+    }
 }
 
 /// Signature
@@ -205,24 +353,96 @@ pub struct Signature {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SendPreparedCallsResponse {
-    /// Call ID
-    pub call_id: String,
+    /// Call ID (older API versions)
+    #[serde(default)]
+    pub call_id: Option<String>,
+    /// Bundle IDs to pass to `wallet_getCallsStatus`
+    #[serde(default)]
+    pub prepared_call_ids: Vec<String>,
     /// User operation hashes
+    #[serde(default)]
     pub user_op_hashes: Vec<String>,
 }
 
+impl SendPreparedCallsResponse {
+    /// The ID to poll with `get_calls_status`
+    #[must_use]
+    pub fn bundle_id(&self) -> Option<&str> {
+        self.prepared_call_ids
+            .first()
+            .map(String::as_str)
+            .or(self.call_id.as_deref())
+    }
+}
+
+/// Status of a call bundle (EIP-5792 status codes)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallsStatus {
+    /// 1xx: batch received but not yet included onchain
+    Pending,
+    /// 2xx: batch included onchain without reverts
+    Confirmed,
+    /// 4xx: batch not included onchain, wallet will not retry
+    OffchainFailure,
+    /// 5xx: batch reverted completely
+    Reverted,
+    /// 6xx: batch reverted partially
+    PartiallyReverted,
+    /// Any other code
+    Unknown(u16),
+}
+
+impl CallsStatus {
+    /// Map an EIP-5792 status code
+    #[must_use]
+    pub fn from_code(code: u16) -> Self {
+        match code {
+            100..=199 => Self::Pending,
+            200..=299 => Self::Confirmed,
+            400..=499 => Self::OffchainFailure,
+            500..=599 => Self::Reverted,
+            600..=699 => Self::PartiallyReverted,
+            other => Self::Unknown(other),
+        }
+    }
+
+    /// Whether the bundle has reached a final state
+    #[must_use]
+    pub fn is_final(&self) -> bool {
+        !matches!(self, Self::Pending | Self::Unknown(_))
+    }
+}
+
 /// Calls status response
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CallsStatusResponse {
+    /// Bundle ID
+    #[serde(default)]
+    pub id: Option<String>,
+    /// Chain ID (hex)
+    #[serde(default)]
+    pub chain_id: Option<String>,
     /// Status code (100-600)
     pub status: u16,
+    /// Whether the calls were executed atomically
+    #[serde(default)]
+    pub atomic: Option<bool>,
     /// Status message
     pub message: Option<String>,
     /// Transaction receipts
+    #[serde(default)]
     pub receipts: Vec<serde_json::Value>,
 }
 
+impl CallsStatusResponse {
+    /// Typed status
+    #[must_use]
+    pub fn status_kind(&self) -> CallsStatus {
+        CallsStatus::from_code(self.status)
+    }
+}
+
 /// Wallet capabilities
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -273,6 +493,82 @@ pub struct SessionPermission {
     pub data: serde_json::Value,
 }
 
+impl SessionPermission {
+    /// Allow calls to a single contract
+    #[must_use]
+    pub fn contract_access(address: impl Into<String>) -> Self {
+        Self {
+            permission_type: "contract-access".to_string(),
+            data: serde_json::json!({ "address": address.into() }),
+        }
+    }
+
+    /// Allow native token transfers up to `allowance_wei`
+    #[must_use]
+    pub fn native_token_transfer(allowance_wei: u128) -> Self {
+        Self {
+            permission_type: "native-token-transfer".to_string(),
+            data: serde_json::json!({ "allowance": format!("0x{allowance_wei:x}") }),
+        }
+    }
+}
+
+/// High-level session key policy
+///
+/// Converted into `wallet_createSession` permissions: one `contract-access`
+/// permission per allowed target plus an optional `native-token-transfer`
+/// allowance.
+#[derive(Debug, Clone, Default)]
+pub struct SessionPolicy {
+    /// Expiry as a unix timestamp (seconds)
+    pub expiry: Option<u64>,
+    /// Contracts the session may call
+    pub allowed_targets: Vec<String>,
+    /// Maximum native token spend in wei
+    pub spending_limit: Option<u128>,
+}
+
+impl SessionPolicy {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expire the session at a unix timestamp
+    #[must_use]
+    pub fn expiry(mut self, expiry_sec: u64) -> Self {
+        self.expiry = Some(expiry_sec);
+        self
+    }
+
+    /// Allow calls to a contract
+    #[must_use]
+    pub fn allow_target(mut self, address: impl Into<String>) -> Self {
+        self.allowed_targets.push(address.into());
+        self
+    }
+
+    /// Cap native token spend
+    #[must_use]
+    pub fn spending_limit(mut self, wei: u128) -> Self {
+        self.spending_limit = Some(wei);
+        self
+    }
+
+    /// Permissions granted by this policy
+    #[must_use]
+    pub fn permissions(&self) -> Vec<SessionPermission> {
+        self.allowed_targets
+            .iter()
+            .map(SessionPermission::contract_access)
+            .chain(
+                self.spending_limit
+                    .map(SessionPermission::native_token_transfer),
+            )
+            .collect()
+    }
+}
+
 /// Create session request
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -290,6 +586,25 @@ pub struct CreateSessionRequest {
     pub expiry_sec: Option<u64>,
 }
 
+impl CreateSessionRequest {
+    /// Build a session request from a policy
+    #[must_use]
+    pub fn from_policy(
+        account: impl Into<String>,
+        chain_id: u64,
+        key: SessionKey,
+        policy: &SessionPolicy,
+    ) -> Self {
+        Self {
+            account: account.into(),
+            chain_id: format!("0x{chain_id:x}"),
+            key,
+            permissions: policy.permissions(),
+            expiry_sec: policy.expiry,
+        }
+    }
+}
+
 /// Session key
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -311,4 +626,7 @@ pub struct CreateSessionResponse {
     pub chain_id: String,
     /// Signature request
     pub signature_request: SignatureRequest,
+    /// Permissions context to pass in `PrepareCallsCapabilities::session`
+    #[serde(default)]
+    pub context: Option<String>,
 }
@@ -0,0 +1,227 @@
+//! Mock API tests for the Alchemy client using wiremock
+//!
+//! These tests verify client behavior without hitting the real Alchemy API.
+
+use alcmy::{token, Client, Config, Network};
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+/// Transactions `0xa1` and `0xa2` live in block `0xb1`, `0xc1` in `0xb2`
+fn block_of(hash: &str) -> Option<&'static str> {
+    match hash.to_lowercase().as_str() {
+        "0xa1" | "0xa2" => Some("0xb1"),
+        "0xc1" => Some("0xb2"),
+        _ => None,
+    }
+}
+
+fn receipt(hash: &str) -> serde_json::Value {
+    serde_json::json!({
+        "transactionHash": hash,
+        "transactionIndex": "0x0",
+        "blockHash": block_of(hash),
+        "blockNumber": "0x10",
+        "from": "0xsender",
+        "to": "0xrecipient",
+        "gasUsed": "0x5208",
+        "cumulativeGasUsed": "0x5208",
+        "status": "0x1",
+        "logs": []
+    })
+}
+
+/// Answers single and batched JSON-RPC calls from the fixture chain
+struct Node;
+
+impl Node {
+    fn answer(call: &serde_json::Value) -> serde_json::Value {
+        let param = &call["params"][0];
+        let result = match call["method"].as_str().unwrap_or_default() {
+            "eth_getTransactionByHash" => {
+                let hash = param.as_str().unwrap();
+                match block_of(hash) {
+                    Some(block) => serde_json::json!({ "hash": hash, "blockHash": block }),
+                    None => serde_json::Value::Null,
+                }
+            }
+            "eth_getTransactionReceipt" => {
+                let hash = param.as_str().unwrap();
+                block_of(hash).map_or(serde_json::Value::Null, |_| receipt(hash))
+            }
+            "alchemy_getTransactionReceipts" => {
+                let block = param["blockHash"].as_str().unwrap();
+                let receipts: Vec<_> = ["0xa1", "0xa2", "0xc1"]
+                    .into_iter()
+                    .filter(|h| block_of(h) == Some(block))
+                    .map(receipt)
+                    .collect();
+                serde_json::json!({ "receipts": receipts })
+            }
+            other => panic!("unexpected method {other}"),
+        };
+        serde_json::json!({ "jsonrpc": "2.0", "id": call["id"], "result": result })
+    }
+}
+
+impl Respond for Node {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        let response = match body.as_array() {
+            Some(calls) => calls.iter().map(Self::answer).collect(),
+            None => Self::answer(&body),
+        };
+        ResponseTemplate::new(200).set_body_json(response)
+    }
+}
+
+async fn mock_node() -> (MockServer, Client) {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(Node)
+        .mount(&server)
+        .await;
+    let config = Config::new("test-key", Network::EthMainnet).with_rpc_url(server.uri());
+    (server, Client::with_config(config).unwrap())
+}
+
+/// Methods called per HTTP request, in order
+async fn calls(server: &MockServer) -> Vec<Vec<String>> {
+    server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|r| {
+            let body: serde_json::Value = serde_json::from_slice(&r.body).unwrap();
+            let calls = body.as_array().cloned().unwrap_or_else(|| vec![body]);
+            calls
+                .iter()
+                .map(|c| c["method"].as_str().unwrap().to_string())
+                .collect()
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_receipts_from_shared_block() {
+    let (server, client) = mock_node().await;
+    let receipts = client
+        .receipts()
+        .get_transaction_receipts(&["0xa2", "0xA1"])
+        .await
+        .unwrap();
+
+    assert_eq!(receipts.len(), 2);
+    assert_eq!(receipts[0].0, "0xa2");
+    assert_eq!(receipts[0].1.as_ref().unwrap().transaction_hash, "0xa2");
+    assert_eq!(receipts[1].0, "0xA1");
+    assert_eq!(receipts[1].1.as_ref().unwrap().transaction_hash, "0xa1");
+    assert_eq!(
+        calls(&server).await,
+        [
+            vec!["eth_getTransactionByHash"; 2],
+            vec!["alchemy_getTransactionReceipts"]
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_receipts_across_blocks_batched() {
+    let (server, client) = mock_node().await;
+    let receipts = client
+        .receipts()
+        .get_transaction_receipts(&["0xa1", "0xc1", "0xdead"])
+        .await
+        .unwrap();
+
+    assert_eq!(receipts[0].1.as_ref().unwrap().block_hash, "0xb1");
+    assert_eq!(receipts[1].1.as_ref().unwrap().block_hash, "0xb2");
+    let missing = receipts[2].1.as_ref().unwrap_err();
+    assert!(missing.to_string().contains("receipt not found: 0xdead"));
+    // Transactions in different blocks never read a whole block
+    assert_eq!(
+        calls(&server).await,
+        [
+            vec!["eth_getTransactionByHash"; 3],
+            vec!["eth_getTransactionReceipt"; 3]
+        ]
+    );
+}
+
+const OWNER: &str = "0x00000000000000000000000000000000000000aa";
+const TOKEN: &str = "0x00000000000000000000000000000000000000cc";
+const SPENDER: &str = "0x00000000000000000000000000000000000000bb";
+
+/// A node at block 10000 that refuses `eth_getLogs` ranges reaching block
+/// 5000, with one Approval by the owner in block 100
+struct ApprovalNode;
+
+impl Respond for ApprovalNode {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let call: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        let block = |value: &serde_json::Value| {
+            u64::from_str_radix(value.as_str().unwrap().trim_start_matches("0x"), 16).unwrap()
+        };
+        let word = |addr: &str| format!("0x{:0>64}", &addr[2..]);
+        let result = match call["method"].as_str().unwrap() {
+            "eth_blockNumber" => serde_json::json!("0x2710"),
+            "alchemy_getAssetTransfers" => serde_json::json!({ "transfers": [] }),
+            "eth_getLogs" => {
+                let filter = &call["params"][0];
+                let (from, to) = (block(&filter["fromBlock"]), block(&filter["toBlock"]));
+                if to >= 5000 {
+                    return ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "id": call["id"],
+                        "error": { "code": -32005, "message": "query returned more than 10000 results" }
+                    }));
+                }
+                if from <= 100 {
+                    serde_json::json!([{
+                        "address": TOKEN,
+                        "topics": [token::APPROVAL_TOPIC, word(OWNER), word(SPENDER)],
+                        "data": format!("0x{:064x}", 5),
+                        "blockNumber": "0x64"
+                    }])
+                } else {
+                    serde_json::json!([])
+                }
+            }
+            "alchemy_getTokenAllowance" => serde_json::json!("5"),
+            "alchemy_getTokenMetadata" => serde_json::json!({ "symbol": "TKN", "decimals": 18 }),
+            other => panic!("unexpected method {other}"),
+        };
+        ResponseTemplate::new(200).set_body_json(
+            serde_json::json!({ "jsonrpc": "2.0", "id": call["id"], "result": result }),
+        )
+    }
+}
+
+#[tokio::test]
+async fn test_allowance_scan_keeps_logs_before_failure() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ApprovalNode)
+        .mount(&server)
+        .await;
+    let config = Config::new("test-key", Network::EthMainnet).with_rpc_url(server.uri());
+    let client = Client::with_config(config).unwrap();
+
+    let options = token::AllowanceScanOptions {
+        discover_from_transfers: true,
+        ..Default::default()
+    };
+    let scan = client
+        .token()
+        .get_allowances_for_owner(OWNER, &options)
+        .await
+        .unwrap();
+
+    // 3751..=5000 can't be split below the minimum span and stays refused,
+    // but the approval found in 0..=2500 is still checked
+    assert!(!scan.is_complete());
+    assert_eq!(scan.allowances.len(), 1);
+    assert_eq!(scan.allowances[0].contract, TOKEN);
+    assert_eq!(scan.allowances[0].spender, SPENDER);
+    assert_eq!(scan.allowances[0].last_approval_block, Some(100));
+}
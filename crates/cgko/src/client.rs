@@ -3,11 +3,20 @@
 //! This client uses common utilities from `yldfi-common` for HTTP operations.
 
 use reqwest::Client as HttpClient;
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use url::Url;
 use yldfi_common::api::{extract_retry_after, ApiConfig, SecretApiKey};
+use yldfi_common::rate_limit::RateLimiter;
 
 use crate::error::{Error, Result};
+use crate::simple::SupportedCurrencies;
+
+/// Default lifetime of the cached supported vs-currency list
+///
+/// `CoinGecko` adds currencies rarely, so the list is fetched at most once a
+/// day per client.
+pub const DEFAULT_CURRENCIES_TTL: Duration = Duration::from_secs(24 * 60 * 60);
 
 /// Base URLs
 pub mod base_urls {
@@ -29,6 +38,12 @@ pub struct Config {
     pub is_pro: bool,
     /// Inner API configuration
     inner: ApiConfig,
+    /// Optional rate limiter
+    rate_limiter: Option<RateLimiter>,
+    /// How long the supported vs-currency list is reused
+    currencies_ttl: Duration,
+    /// Check `vs_currencies` and coin ids in simple price calls
+    validate_params: bool,
 }
 
 impl Config {
@@ -39,6 +54,9 @@ impl Config {
             api_key: None,
             is_pro: false,
             inner: ApiConfig::new(base_urls::DEMO),
+            rate_limiter: None,
+            currencies_ttl: DEFAULT_CURRENCIES_TTL,
+            validate_params: true,
         }
     }
 
@@ -48,6 +66,9 @@ impl Config {
             api_key: Some(SecretApiKey::new(api_key)),
             is_pro: false,
             inner: ApiConfig::new(base_urls::DEMO),
+            rate_limiter: None,
+            currencies_ttl: DEFAULT_CURRENCIES_TTL,
+            validate_params: true,
         }
     }
 
@@ -57,9 +78,19 @@ impl Config {
             api_key: Some(SecretApiKey::new(api_key)),
             is_pro: true,
             inner: ApiConfig::new(base_urls::PRO),
+            rate_limiter: None,
+            currencies_ttl: DEFAULT_CURRENCIES_TTL,
+            validate_params: true,
         }
     }
 
+    /// Set a custom base URL
+    #[must_use]
+    pub fn with_base_url(mut self, url: impl Into<String>) -> Self {
+        self.inner.base_url = url.into();
+        self
+    }
+
     /// Set a custom timeout
     #[must_use]
     pub fn with_timeout(mut self, timeout: Duration) -> Self {
@@ -80,6 +111,49 @@ impl Config {
         self.inner.http.proxy = proxy;
         self
     }
+
+    /// Enable rate limiting with specified requests per window
+    ///
+    /// The public API allows roughly 30 requests per minute; paginated
+    /// helpers such as [`crate::coins::CoinsApi::tickers`] wait on the
+    /// limiter before every page.
+    #[must_use]
+    pub fn with_rate_limit(mut self, max_requests: u32, window: Duration) -> Self {
+        self.rate_limiter = Some(RateLimiter::new(max_requests, window));
+        self
+    }
+
+    /// Set a custom rate limiter
+    #[must_use]
+    pub fn with_rate_limiter(mut self, limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Set how long the supported vs-currency list is cached
+    #[must_use]
+    pub fn with_currencies_ttl(mut self, ttl: Duration) -> Self {
+        self.currencies_ttl = ttl;
+        self
+    }
+
+    /// Enable or disable parameter validation in simple price calls
+    ///
+    /// Enabled by default: unsupported `vs_currencies` and coin ids missing
+    /// from the response are reported as errors instead of empty results.
+    /// Disable it to pass through currencies `CoinGecko` added after the
+    /// cached list was fetched.
+    #[must_use]
+    pub fn with_param_validation(mut self, enabled: bool) -> Self {
+        self.validate_params = enabled;
+        self
+    }
+}
+
+/// Supported vs-currency list shared by a client and its clones
+#[derive(Debug, Default)]
+struct CurrencyCache {
+    entry: Mutex<Option<(Instant, Arc<SupportedCurrencies>)>>,
 }
 
 /// `CoinGecko` API client
@@ -89,6 +163,10 @@ pub struct Client {
     base_url: Url,
     api_key: Option<SecretApiKey>,
     is_pro: bool,
+    rate_limiter: Option<RateLimiter>,
+    currencies: Arc<CurrencyCache>,
+    currencies_ttl: Duration,
+    validate_params: bool,
 }
 
 impl Client {
@@ -114,17 +192,17 @@ impl Client {
     /// Create a client with custom configuration
     pub fn with_config(config: Config) -> Result<Self> {
         let http = config.inner.build_client()?;
-        let base_url = if config.is_pro {
-            Url::parse(base_urls::PRO)?
-        } else {
-            Url::parse(base_urls::DEMO)?
-        };
+        let base_url = Url::parse(&config.inner.base_url)?;
 
         Ok(Self {
             http,
             base_url,
             api_key: config.api_key,
             is_pro: config.is_pro,
+            rate_limiter: config.rate_limiter,
+            currencies: Arc::default(),
+            currencies_ttl: config.currencies_ttl,
+            validate_params: config.validate_params,
         })
     }
 
@@ -148,7 +226,44 @@ impl Client {
         self.is_pro
     }
 
+    /// Get the rate limiter if configured
+    #[must_use]
+    pub fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_ref()
+    }
+
+    /// Whether simple price calls validate their parameters
+    #[must_use]
+    pub fn validates_params(&self) -> bool {
+        self.validate_params
+    }
+
+    /// Cached supported vs-currency list, if fetched within the TTL
+    pub(crate) fn cached_currencies(&self) -> Option<Arc<SupportedCurrencies>> {
+        let entry = self
+            .currencies
+            .entry
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        entry
+            .as_ref()
+            .filter(|(fetched, _)| fetched.elapsed() < self.currencies_ttl)
+            .map(|(_, currencies)| Arc::clone(currencies))
+    }
+
+    pub(crate) fn store_currencies(&self, currencies: Arc<SupportedCurrencies>) {
+        *self
+            .currencies
+            .entry
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some((Instant::now(), currencies));
+    }
+
     pub(crate) async fn get<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T> {
+        if let Some(limiter) = &self.rate_limiter {
+            limiter.acquire().await;
+        }
+
         let url = format!("{}{}", self.base_url, path);
         let mut req = self.http.get(&url);
 
@@ -2,11 +2,17 @@
 
 use super::types::{
     CoinContractData, CoinData, CoinHistory, CoinListItem, CoinMarket, CoinTickers, MarketChart,
-    MarketsOptions, OhlcData, RecentlyAddedCoin, SupplyChart, TopMoversResponse,
+    MarketsOptions, OhlcData, RecentlyAddedCoin, SupplyChart, Ticker, TopMoversResponse,
 };
 use crate::client::Client;
 use crate::error::Result;
 
+/// Tickers returned per page by `/coins/{id}/tickers`
+const TICKERS_PAGE_SIZE: usize = 100;
+
+/// Upper bound on pages fetched by [`CoinsApi::tickers`]
+const MAX_TICKER_PAGES: u32 = 50;
+
 /// Coins API
 pub struct CoinsApi<'a> {
     client: &'a Client,
@@ -66,9 +72,59 @@ impl<'a> CoinsApi<'a> {
         self.client.get(&path).await
     }
 
-    /// Get coin tickers
-    pub async fn tickers(&self, id: &str) -> Result<CoinTickers> {
-        let path = format!("/coins/{id}/tickers");
+    /// Get all coin tickers, optionally filtered by exchange
+    ///
+    /// Follows pagination (100 tickers per page) until a short page is
+    /// returned. Each page goes through the client's rate limiter, if any.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # async fn example() -> cgko::error::Result<()> {
+    /// let client = cgko::Client::new()?;
+    /// let tickers = client
+    ///     .coins()
+    ///     .tickers("ethereum", Some(&["binance", "coinbase"]), false)
+    ///     .await?;
+    /// let trusted = tickers.iter().filter(|t| t.is_trustworthy()).count();
+    /// println!("{trusted}/{} trustworthy tickers", tickers.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn tickers(
+        &self,
+        id: &str,
+        exchange_ids: Option<&[&str]>,
+        include_exchange_logo: bool,
+    ) -> Result<Vec<Ticker>> {
+        let mut tickers = Vec::new();
+        for page in 1..=MAX_TICKER_PAGES {
+            let response = self
+                .tickers_page(id, exchange_ids, include_exchange_logo, page)
+                .await?;
+            let count = response.tickers.len();
+            tickers.extend(response.tickers);
+            if count < TICKERS_PAGE_SIZE {
+                break;
+            }
+        }
+        Ok(tickers)
+    }
+
+    /// Get a single page of coin tickers
+    pub async fn tickers_page(
+        &self,
+        id: &str,
+        exchange_ids: Option<&[&str]>,
+        include_exchange_logo: bool,
+        page: u32,
+    ) -> Result<CoinTickers> {
+        let mut path = format!("/coins/{id}/tickers?page={page}");
+        if let Some(ids) = exchange_ids.filter(|ids| !ids.is_empty()) {
+            path.push_str(&format!("&exchange_ids={}", ids.join(",")));
+        }
+        if include_exchange_logo {
+            path.push_str("&include_exchange_logo=true");
+        }
         self.client.get(&path).await
     }
 
@@ -184,12 +184,31 @@ pub struct Ticker {
     pub target_coin_id: Option<String>,
 }
 
+impl Ticker {
+    /// Whether the ticker is usable for pricing: not flagged as an anomaly,
+    /// not stale, and without a red trust score
+    #[must_use]
+    pub fn is_trustworthy(&self) -> bool {
+        !self.is_anomaly.unwrap_or(false)
+            && !self.is_stale.unwrap_or(false)
+            && self.trust_score.as_deref() != Some("red")
+    }
+
+    /// Volume converted to the given currency (e.g. "usd")
+    #[must_use]
+    pub fn converted_volume_in(&self, currency: &str) -> Option<f64> {
+        self.converted_volume.as_ref()?.get(currency).copied()
+    }
+}
+
 /// Ticker market info
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct TickerMarket {
     pub name: Option<String>,
     pub identifier: Option<String>,
     pub has_trading_incentive: Option<bool>,
+    /// Exchange logo URL (only with `include_exchange_logo`)
+    pub logo: Option<String>,
 }
 
 /// Coin contract data (coin data by contract address)
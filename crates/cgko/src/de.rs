@@ -0,0 +1,57 @@
+//! Tolerant deserializers for fields `CoinGecko` returns inconsistently
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+
+/// Deserialize a number that may arrive as a JSON number, a numeric string,
+/// an empty string, or null
+pub(crate) fn optional_f64<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum NumberOrString {
+        Number(f64),
+        String(String),
+    }
+
+    match Option::<NumberOrString>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(NumberOrString::Number(n)) => Ok(Some(n)),
+        Some(NumberOrString::String(s)) => {
+            let s = s.trim();
+            if s.is_empty() {
+                return Ok(None);
+            }
+            s.parse::<f64>()
+                .map(Some)
+                .map_err(|_| serde::de::Error::custom(format!("invalid number: {s:?}")))
+        }
+    }
+}
+
+/// Deserialize a `{symbol: percentage}` map into entries sorted by
+/// percentage, largest first (ties by symbol)
+pub(crate) fn sorted_percentages<'de, D>(deserializer: D) -> Result<Vec<(String, f64)>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let map = Option::<BTreeMap<String, f64>>::deserialize(deserializer)?.unwrap_or_default();
+    let mut entries: Vec<(String, f64)> = map.into_iter().collect();
+    entries.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    Ok(entries)
+}
+
+/// Serialize percentage entries back into the `{symbol: percentage}` map
+/// `CoinGecko` sends
+pub(crate) fn percentages_as_map<S>(
+    entries: &[(String, f64)],
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let map: BTreeMap<&str, f64> = entries.iter().map(|(k, v)| (k.as_str(), *v)).collect();
+    map.serialize(serializer)
+}
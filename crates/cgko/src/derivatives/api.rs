@@ -1,8 +1,8 @@
 //! Derivatives API endpoints
 
 use super::types::{
-    DerivativeExchange, DerivativeExchangeDetail, DerivativeExchangeListItem,
-    DerivativeExchangesOptions, DerivativeTicker, DerivativesOptions,
+    DerivativeExchange, DerivativeExchangeDetail, DerivativeExchangeListItem, DerivativeTicker,
+    DerivativesOptions, DerivativesQuery,
 };
 use crate::client::Client;
 use crate::error::Result;
@@ -32,18 +32,33 @@ impl<'a> DerivativesApi<'a> {
         self.client.get(&path).await
     }
 
-    /// List derivatives exchanges
-    pub async fn exchanges(&self) -> Result<Vec<DerivativeExchange>> {
-        self.client.get("/derivatives/exchanges").await
+    /// List derivatives exchanges with open interest and 24h volume (in BTC)
+    ///
+    /// # Example
+    /// ```no_run
+    /// # async fn example() -> cgko::error::Result<()> {
+    /// use cgko::derivatives::DerivativesQuery;
+    ///
+    /// let client = cgko::Client::new()?;
+    /// let query = DerivativesQuery::by_open_interest().with_per_page(10);
+    /// for exchange in client.derivatives().exchanges(&query).await? {
+    ///     println!("{}: {:?} BTC OI", exchange.name, exchange.open_interest_btc);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn exchanges(&self, query: &DerivativesQuery) -> Result<Vec<DerivativeExchange>> {
+        let path = format!("/derivatives/exchanges{}", query.to_query_string());
+        self.client.get(&path).await
     }
 
     /// List derivatives exchanges with options
+    #[deprecated(note = "use `exchanges`, which now takes a `DerivativesQuery`")]
     pub async fn exchanges_with_options(
         &self,
-        options: &DerivativeExchangesOptions,
+        options: &DerivativesQuery,
     ) -> Result<Vec<DerivativeExchange>> {
-        let path = format!("/derivatives/exchanges{}", options.to_query_string());
-        self.client.get(&path).await
+        self.exchanges(options).await
     }
 
     /// Get derivatives exchange by ID
@@ -33,8 +33,12 @@ pub struct DerivativeExchangeListItem {
 pub struct DerivativeExchange {
     pub name: String,
     pub id: String,
+    /// Open interest in BTC
+    #[serde(default, deserialize_with = "crate::de::optional_f64")]
     pub open_interest_btc: Option<f64>,
-    pub trade_volume_24h_btc: Option<String>,
+    /// 24h trading volume in BTC
+    #[serde(default, deserialize_with = "crate::de::optional_f64")]
+    pub trade_volume_24h_btc: Option<f64>,
     pub number_of_perpetual_pairs: Option<u32>,
     pub number_of_futures_pairs: Option<u32>,
     pub image: Option<String>,
@@ -48,8 +52,12 @@ pub struct DerivativeExchange {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct DerivativeExchangeDetail {
     pub name: String,
+    /// Open interest in BTC
+    #[serde(default, deserialize_with = "crate::de::optional_f64")]
     pub open_interest_btc: Option<f64>,
-    pub trade_volume_24h_btc: Option<String>,
+    /// 24h trading volume in BTC
+    #[serde(default, deserialize_with = "crate::de::optional_f64")]
+    pub trade_volume_24h_btc: Option<f64>,
     pub number_of_perpetual_pairs: Option<u32>,
     pub number_of_futures_pairs: Option<u32>,
     pub image: Option<String>,
@@ -133,20 +141,61 @@ impl DerivativesOptions {
     }
 }
 
-/// Options for derivatives exchanges query
+/// Query for the derivatives exchanges list
 #[derive(Debug, Clone, Default)]
-pub struct DerivativeExchangesOptions {
+pub struct DerivativesQuery {
+    /// Sort order: `name_asc`, `name_desc`, `open_interest_btc_asc`,
+    /// `open_interest_btc_desc`, `trade_volume_24h_btc_asc` or
+    /// `trade_volume_24h_btc_desc` (API default)
     pub order: Option<String>,
+    /// Results per page
     pub per_page: Option<u32>,
+    /// Page number
     pub page: Option<u32>,
 }
 
-impl DerivativeExchangesOptions {
+/// Former name of [`DerivativesQuery`]
+#[deprecated(note = "renamed to `DerivativesQuery`")]
+pub type DerivativeExchangesOptions = DerivativesQuery;
+
+impl DerivativesQuery {
     #[must_use]
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Sort by open interest, largest first
+    #[must_use]
+    pub fn by_open_interest() -> Self {
+        Self {
+            order: Some("open_interest_btc_desc".to_string()),
+            ..Self::default()
+        }
+    }
+
+    /// Sort by 24h volume, largest first
+    #[must_use]
+    pub fn by_volume() -> Self {
+        Self {
+            order: Some("trade_volume_24h_btc_desc".to_string()),
+            ..Self::default()
+        }
+    }
+
+    /// Set the page size
+    #[must_use]
+    pub fn with_per_page(mut self, per_page: u32) -> Self {
+        self.per_page = Some(per_page);
+        self
+    }
+
+    /// Set the page number
+    #[must_use]
+    pub fn with_page(mut self, page: u32) -> Self {
+        self.page = Some(page);
+        self
+    }
+
     #[must_use]
     pub fn to_query_string(&self) -> String {
         let mut params = Vec::new();
@@ -3,6 +3,7 @@
 //! This module provides the error types for the `CoinGecko` API client,
 //! built on top of the shared `ApiError` infrastructure.
 
+use crate::simple::PricesResponse;
 use thiserror::Error;
 pub use yldfi_common::api::ApiError;
 
@@ -17,6 +18,19 @@ pub enum DomainError {
     #[error("Invalid parameter: {0}")]
     InvalidParam(String),
 
+    /// `vs_currencies` that `CoinGecko` does not support
+    #[error("Unsupported vs currencies: {}", .0.join(", "))]
+    UnsupportedCurrencies(Vec<String>),
+
+    /// Coin ids `CoinGecko` returned no data for
+    ///
+    /// `prices` holds the response for the ids that were found.
+    #[error("Unknown coin ids: {}", .ids.join(", "))]
+    UnknownCoinIds {
+        ids: Vec<String>,
+        prices: PricesResponse,
+    },
+
     /// URL parse error
     #[error("URL parse error: {0}")]
     UrlParse(#[from] url::ParseError),
@@ -38,3 +52,13 @@ pub fn not_found(resource: impl Into<String>) -> Error {
 pub fn invalid_param(message: impl Into<String>) -> Error {
     ApiError::domain(DomainError::InvalidParam(message.into()))
 }
+
+/// Create an unsupported currencies error
+pub fn unsupported_currencies(currencies: Vec<String>) -> Error {
+    ApiError::domain(DomainError::UnsupportedCurrencies(currencies))
+}
+
+/// Create an unknown coin ids error carrying the prices that were found
+pub fn unknown_coin_ids(ids: Vec<String>, prices: PricesResponse) -> Error {
+    ApiError::domain(DomainError::UnknownCoinIds { ids, prices })
+}
@@ -1,7 +1,9 @@
 //! Global and general data endpoints
 
 mod api;
+mod snapshot;
 mod types;
 
 pub use api::GlobalApi;
+pub use snapshot::MarketSnapshot;
 pub use types::*;
@@ -0,0 +1,38 @@
+//! Combined market snapshot for dashboards
+
+use super::types::{DefiGlobalData, GlobalData, TrendingResponse};
+use crate::client::Client;
+use crate::error::Result;
+
+/// Global, `DeFi` and trending market data fetched together
+///
+/// Each section is its own `Result`, so one failing endpoint (e.g. a rate
+/// limit on `/search/trending`) still leaves the others usable.
+#[derive(Debug)]
+pub struct MarketSnapshot {
+    /// `/global`
+    pub global: Result<GlobalData>,
+    /// `/global/decentralized_finance_defi`
+    pub defi: Result<DefiGlobalData>,
+    /// `/search/trending`
+    pub trending: Result<TrendingResponse>,
+}
+
+impl MarketSnapshot {
+    /// Fetch all sections concurrently
+    pub async fn fetch(client: &Client) -> Self {
+        let api = client.global();
+        let (global, defi, trending) = tokio::join!(api.data(), api.defi(), api.trending());
+        Self {
+            global: global.map(|r| r.data),
+            defi: defi.map(|r| r.data),
+            trending,
+        }
+    }
+
+    /// Whether every section loaded
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.global.is_ok() && self.defi.is_ok() && self.trending.is_ok()
+    }
+}
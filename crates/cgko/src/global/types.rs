@@ -19,11 +19,38 @@ pub struct GlobalData {
     pub markets: Option<u64>,
     pub total_market_cap: Option<HashMap<String, f64>>,
     pub total_volume: Option<HashMap<String, f64>>,
-    pub market_cap_percentage: Option<HashMap<String, f64>>,
+    /// Market cap dominance per coin symbol in percent, largest first
+    #[serde(
+        default,
+        deserialize_with = "crate::de::sorted_percentages",
+        serialize_with = "crate::de::percentages_as_map"
+    )]
+    pub market_cap_percentage: Vec<(String, f64)>,
     pub market_cap_change_percentage_24h_usd: Option<f64>,
     pub updated_at: Option<u64>,
 }
 
+impl GlobalData {
+    /// Market cap dominance of a coin in percent, by symbol (case-insensitive)
+    ///
+    /// ```
+    /// let global: cgko::global::GlobalResponse = serde_json::from_str(
+    ///     r#"{"data": {"market_cap_percentage": {"eth": 12.1, "btc": 56.4, "usdt": 4.2}}}"#,
+    /// )
+    /// .unwrap();
+    /// assert_eq!(global.data.dominance_of("BTC"), Some(56.4));
+    /// assert_eq!(global.data.market_cap_percentage[1].0, "eth");
+    /// assert_eq!(global.data.dominance_of("doge"), None);
+    /// ```
+    #[must_use]
+    pub fn dominance_of(&self, symbol: &str) -> Option<f64> {
+        self.market_cap_percentage
+            .iter()
+            .find(|(s, _)| s.eq_ignore_ascii_case(symbol))
+            .map(|(_, pct)| *pct)
+    }
+}
+
 /// `DeFi` global data response
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct DefiGlobalResponse {
@@ -31,14 +58,42 @@ pub struct DefiGlobalResponse {
 }
 
 /// `DeFi` global data
+///
+/// `CoinGecko` sends the figures as decimal strings; they are parsed to
+/// `f64` here (numbers are accepted too).
+///
+/// ```
+/// let defi: cgko::global::DefiGlobalResponse = serde_json::from_str(
+///     r#"{"data": {"defi_market_cap": "105273842288.2", "defi_dominance": "3.0869",
+///        "trading_volume_24h": 5046503746.3, "top_coin_name": "Lido Staked Ether",
+///        "top_coin_defi_dominance": 30.6}}"#,
+/// )
+/// .unwrap();
+/// assert_eq!(defi.data.defi_market_cap, Some(105_273_842_288.2));
+/// assert_eq!(defi.data.defi_dominance, Some(3.0869));
+/// assert_eq!(defi.data.eth_market_cap, None);
+/// ```
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct DefiGlobalData {
-    pub defi_market_cap: Option<String>,
-    pub eth_market_cap: Option<String>,
-    pub defi_to_eth_ratio: Option<String>,
-    pub trading_volume_24h: Option<String>,
-    pub defi_dominance: Option<String>,
+    /// Total `DeFi` market cap in USD
+    #[serde(default, deserialize_with = "crate::de::optional_f64")]
+    pub defi_market_cap: Option<f64>,
+    /// ETH market cap in USD
+    #[serde(default, deserialize_with = "crate::de::optional_f64")]
+    pub eth_market_cap: Option<f64>,
+    /// `DeFi` market cap relative to ETH market cap, in percent
+    #[serde(default, deserialize_with = "crate::de::optional_f64")]
+    pub defi_to_eth_ratio: Option<f64>,
+    /// `DeFi` trading volume over 24h in USD
+    #[serde(default, deserialize_with = "crate::de::optional_f64")]
+    pub trading_volume_24h: Option<f64>,
+    /// `DeFi` share of the total crypto market cap, in percent
+    #[serde(default, deserialize_with = "crate::de::optional_f64")]
+    pub defi_dominance: Option<f64>,
+    /// Largest `DeFi` coin by market cap
     pub top_coin_name: Option<String>,
+    /// Top coin's share of the `DeFi` market cap, in percent
+    #[serde(default, deserialize_with = "crate::de::optional_f64")]
     pub top_coin_defi_dominance: Option<f64>,
 }
 
@@ -21,6 +21,25 @@
 //! # }
 //! ```
 //!
+//! ## Market Snapshot
+//!
+//! Global, `DeFi` and trending data in one concurrent call; each section
+//! succeeds or fails on its own:
+//!
+//! ```no_run
+//! # async fn example() -> cgko::error::Result<()> {
+//! let client = cgko::Client::new()?;
+//! let snapshot = cgko::global::MarketSnapshot::fetch(&client).await;
+//! if let Ok(global) = &snapshot.global {
+//!     println!("BTC dominance: {:?}%", global.dominance_of("btc"));
+//! }
+//! if let Ok(defi) = &snapshot.defi {
+//!     println!("DeFi dominance: {:?}%", defi.defi_dominance);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
 //! ## Pro API
 //!
 //! ```no_run
@@ -42,6 +61,7 @@
 //! ```
 
 pub mod client;
+mod de;
 pub mod error;
 
 pub mod categories;
@@ -2,7 +2,15 @@
 
 use super::types::{PriceOptions, PricesResponse, SupportedCurrencies, TokenPricesResponse};
 use crate::client::Client;
-use crate::error::Result;
+use crate::error::{unknown_coin_ids, unsupported_currencies, Result};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Currencies `CoinGecko` has long supported; validating only these does not
+/// need the supported list
+const COMMON_VS_CURRENCIES: &[&str] = &[
+    "usd", "eur", "gbp", "jpy", "cny", "krw", "inr", "cad", "aud", "chf", "btc", "eth",
+];
 
 /// Simple price API
 pub struct SimpleApi<'a> {
@@ -17,6 +25,12 @@ impl<'a> SimpleApi<'a> {
 
     /// Get price for coins in given currencies
     ///
+    /// Unless disabled with [`Config::with_param_validation`](crate::Config::with_param_validation),
+    /// unsupported `vs_currencies` fail before the request is sent and ids
+    /// missing from the response fail with
+    /// [`DomainError::UnknownCoinIds`](crate::error::DomainError::UnknownCoinIds),
+    /// which still carries the prices of the ids that were found.
+    ///
     /// # Example
     /// ```no_run
     /// # async fn example() -> cgko::error::Result<()> {
@@ -26,15 +40,13 @@ impl<'a> SimpleApi<'a> {
     /// # }
     /// ```
     pub async fn price(&self, ids: &[&str], vs_currencies: &[&str]) -> Result<PricesResponse> {
-        let path = format!(
-            "/simple/price?ids={}&vs_currencies={}",
-            ids.join(","),
-            vs_currencies.join(",")
-        );
-        self.client.get(&path).await
+        self.price_with_options(ids, vs_currencies, &PriceOptions::default())
+            .await
     }
 
     /// Get price with options
+    ///
+    /// Validated like [`price`](Self::price).
     pub async fn price_with_options(
         &self,
         ids: &[&str],
@@ -47,7 +59,15 @@ impl<'a> SimpleApi<'a> {
             vs_currencies.join(","),
             options.to_query_string()
         );
-        self.client.get(&path).await
+        self.check_currencies(vs_currencies).await?;
+        let response: PricesResponse = self.client.get(&path).await?;
+        if self.client.validates_params() {
+            let missing = missing_ids(ids, &response);
+            if !missing.is_empty() {
+                return Err(unknown_coin_ids(missing, response));
+            }
+        }
+        Ok(response)
     }
 
     /// Get token prices by contract address
@@ -55,7 +75,7 @@ impl<'a> SimpleApi<'a> {
     /// # Arguments
     /// * `platform` - Asset platform (e.g., "ethereum", "polygon-pos")
     /// * `contract_addresses` - Token contract addresses
-    /// * `vs_currencies` - Target currencies
+    /// * `vs_currencies` - Target currencies (validated like [`price`](Self::price))
     pub async fn token_price(
         &self,
         platform: &str,
@@ -68,11 +88,113 @@ impl<'a> SimpleApi<'a> {
             contract_addresses.join(","),
             vs_currencies.join(",")
         );
+        self.check_currencies(vs_currencies).await?;
         self.client.get(&path).await
     }
 
     /// Get list of supported vs currencies
+    ///
+    /// The list is cached per client (and its clones) for
+    /// [`Config::with_currencies_ttl`](crate::Config::with_currencies_ttl),
+    /// a day by default.
     pub async fn supported_vs_currencies(&self) -> Result<SupportedCurrencies> {
-        self.client.get("/simple/supported_vs_currencies").await
+        Ok(self.cached_currencies().await?.as_ref().clone())
+    }
+
+    /// Check `vs_currencies` against the supported list
+    ///
+    /// Comparison is case-insensitive. Fails with
+    /// [`DomainError::UnsupportedCurrencies`](crate::error::DomainError::UnsupportedCurrencies)
+    /// listing every unsupported currency, or with the request error if the
+    /// list can't be fetched.
+    pub async fn validate_vs_currencies(&self, vs_currencies: &[&str]) -> Result<()> {
+        let unsupported = self.unsupported_currencies(vs_currencies).await?;
+        if unsupported.is_empty() {
+            Ok(())
+        } else {
+            Err(unsupported_currencies(unsupported))
+        }
+    }
+
+    /// Validation before a price call
+    ///
+    /// A failure to fetch the supported list doesn't block the call itself.
+    async fn check_currencies(&self, vs_currencies: &[&str]) -> Result<()> {
+        if !self.client.validates_params() {
+            return Ok(());
+        }
+        match self.unsupported_currencies(vs_currencies).await {
+            Ok(unsupported) if !unsupported.is_empty() => Err(unsupported_currencies(unsupported)),
+            _ => Ok(()),
+        }
+    }
+
+    async fn unsupported_currencies(&self, vs_currencies: &[&str]) -> Result<Vec<String>> {
+        let unchecked: Vec<String> = unique_lowercase(vs_currencies)
+            .filter(|c| !COMMON_VS_CURRENCIES.contains(&c.as_str()))
+            .collect();
+        if unchecked.is_empty() {
+            return Ok(Vec::new());
+        }
+        let supported = self.cached_currencies().await?;
+        Ok(unchecked
+            .into_iter()
+            .filter(|c| !supported.iter().any(|s| s.eq_ignore_ascii_case(c)))
+            .collect())
+    }
+
+    async fn cached_currencies(&self) -> Result<Arc<SupportedCurrencies>> {
+        if let Some(currencies) = self.client.cached_currencies() {
+            return Ok(currencies);
+        }
+        let currencies: SupportedCurrencies =
+            self.client.get("/simple/supported_vs_currencies").await?;
+        let currencies = Arc::new(currencies);
+        self.client.store_currencies(Arc::clone(&currencies));
+        Ok(currencies)
+    }
+}
+
+/// Requested ids with no entry in a price response
+fn missing_ids(ids: &[&str], response: &PricesResponse) -> Vec<String> {
+    unique_lowercase(ids)
+        .filter(|id| !response.contains_key(id))
+        .collect()
+}
+
+/// Trimmed, lowercased, non-empty values in first-seen order
+fn unique_lowercase<'a>(values: &'a [&str]) -> impl Iterator<Item = String> + 'a {
+    let mut seen = HashSet::new();
+    values
+        .iter()
+        .map(|v| v.trim().to_lowercase())
+        .filter(move |v| !v.is_empty() && seen.insert(v.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unique_lowercase() {
+        let values = unique_lowercase(&["USD", " eur ", "usd", "", "  ", "Eur", "btc"]);
+        assert_eq!(values.collect::<Vec<_>>(), ["usd", "eur", "btc"]);
+    }
+
+    #[test]
+    fn test_missing_ids() {
+        let response: PricesResponse = serde_json::from_value(serde_json::json!({
+            "bitcoin": { "usd": 1.0 },
+            "ethereum": {}
+        }))
+        .unwrap();
+        assert_eq!(
+            missing_ids(
+                &["Bitcoin", "ethereum", "bitcoyn", "BITCOYN", " "],
+                &response
+            ),
+            ["bitcoyn"]
+        );
+        assert!(missing_ids(&["bitcoin"], &response).is_empty());
     }
 }
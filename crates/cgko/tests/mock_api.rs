@@ -0,0 +1,252 @@
+//! Mock API tests for the `CoinGecko` client using wiremock
+//!
+//! These tests verify client behavior without hitting the real `CoinGecko` API.
+
+use cgko::error::{ApiError, DomainError};
+use cgko::{Client, Config};
+use std::time::Duration;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Create a client configured to use the mock server
+fn mock_client(server: &MockServer, config: Config) -> Client {
+    Client::with_config(config.with_base_url(format!("{}/api/v3", server.uri()))).unwrap()
+}
+
+async fn mount_currencies(server: &MockServer, expected_calls: u64) {
+    Mock::given(method("GET"))
+        .and(path("/api/v3/simple/supported_vs_currencies"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(["usd", "eur", "sgd"]))
+        .expect(expected_calls)
+        .mount(server)
+        .await;
+}
+
+async fn mount_prices(server: &MockServer, body: serde_json::Value) {
+    Mock::given(method("GET"))
+        .and(path("/api/v3/simple/price"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(body))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_common_currencies_skip_supported_list() {
+    let server = MockServer::start().await;
+    mount_currencies(&server, 0).await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/simple/price"))
+        .and(query_param("ids", "bitcoin"))
+        .and(query_param("vs_currencies", "USD,eth"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({ "bitcoin": { "usd": 1.0, "eth": 30.0 } })),
+        )
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server, Config::demo());
+    let prices = client
+        .simple()
+        .price(&["bitcoin"], &["USD", "eth"])
+        .await
+        .unwrap();
+    assert_eq!(prices["bitcoin"]["eth"], 30.0);
+}
+
+#[tokio::test]
+async fn test_supported_currencies_cached() {
+    let server = MockServer::start().await;
+    mount_currencies(&server, 1).await;
+    mount_prices(&server, serde_json::json!({ "bitcoin": { "sgd": 1.0 } })).await;
+
+    let client = mock_client(&server, Config::demo());
+    client.simple().price(&["bitcoin"], &["sgd"]).await.unwrap();
+    // Clones share the cache
+    client
+        .clone()
+        .simple()
+        .price(&["bitcoin"], &["SGD"])
+        .await
+        .unwrap();
+    assert_eq!(
+        client.simple().supported_vs_currencies().await.unwrap(),
+        ["usd", "eur", "sgd"]
+    );
+}
+
+#[tokio::test]
+async fn test_supported_currencies_ttl_expiry() {
+    let server = MockServer::start().await;
+    mount_currencies(&server, 2).await;
+
+    let client = mock_client(&server, Config::demo().with_currencies_ttl(Duration::ZERO));
+    client.simple().supported_vs_currencies().await.unwrap();
+    client.simple().supported_vs_currencies().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_unsupported_currencies_rejected() {
+    let server = MockServer::start().await;
+    mount_currencies(&server, 1).await;
+    mount_prices(&server, serde_json::json!({})).await;
+
+    let client = mock_client(&server, Config::demo());
+    let err = client
+        .simple()
+        .price(&["bitcoin"], &["usd", "usdd", "sgd", "xyz", "USDD"])
+        .await
+        .unwrap_err();
+    match err {
+        ApiError::Domain(DomainError::UnsupportedCurrencies(bad)) => {
+            assert_eq!(bad, ["usdd", "xyz"]);
+        }
+        other => panic!("expected UnsupportedCurrencies, got {other:?}"),
+    }
+    // Rejected before the price request was sent
+    let requests = server.received_requests().await.unwrap();
+    assert!(requests
+        .iter()
+        .all(|r| r.url.path() != "/api/v3/simple/price"));
+}
+
+#[tokio::test]
+async fn test_unknown_ids_keep_found_prices() {
+    let server = MockServer::start().await;
+    mount_prices(&server, serde_json::json!({ "bitcoin": { "usd": 1.0 } })).await;
+
+    let client = mock_client(&server, Config::demo());
+    let err = client
+        .simple()
+        .price(&["bitcoin", "bitcoyn"], &["usd"])
+        .await
+        .unwrap_err();
+    match err {
+        ApiError::Domain(DomainError::UnknownCoinIds { ids, prices }) => {
+            assert_eq!(ids, ["bitcoyn"]);
+            assert_eq!(prices["bitcoin"]["usd"], 1.0);
+        }
+        other => panic!("expected UnknownCoinIds, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_param_validation_disabled() {
+    let server = MockServer::start().await;
+    mount_currencies(&server, 0).await;
+    mount_prices(&server, serde_json::json!({ "bitcoin": { "xyz": 1.0 } })).await;
+
+    let client = mock_client(&server, Config::demo().with_param_validation(false));
+    let prices = client
+        .simple()
+        .price(&["bitcoin", "bitcoyn"], &["xyz"])
+        .await
+        .unwrap();
+    assert_eq!(prices.len(), 1);
+    assert_eq!(prices["bitcoin"]["xyz"], 1.0);
+}
+
+/// A `/coins/{id}/tickers` page holding `count` tickers
+fn tickers_page(count: usize) -> ResponseTemplate {
+    let tickers: Vec<_> = (0..count)
+        .map(|i| serde_json::json!({ "base": "ETH", "target": format!("T{i}") }))
+        .collect();
+    ResponseTemplate::new(200)
+        .set_body_json(serde_json::json!({ "name": "Ethereum", "tickers": tickers }))
+}
+
+async fn mount_tickers_page(server: &MockServer, page: &str, count: usize, expected_calls: u64) {
+    Mock::given(method("GET"))
+        .and(path("/api/v3/coins/ethereum/tickers"))
+        .and(query_param("page", page))
+        .respond_with(tickers_page(count))
+        .expect(expected_calls)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_tickers_stop_at_empty_page() {
+    let server = MockServer::start().await;
+    mount_tickers_page(&server, "1", 100, 1).await;
+    mount_tickers_page(&server, "2", 100, 1).await;
+    mount_tickers_page(&server, "3", 0, 1).await;
+    mount_tickers_page(&server, "4", 100, 0).await;
+
+    let client = mock_client(&server, Config::demo());
+    let tickers = client
+        .coins()
+        .tickers("ethereum", None, false)
+        .await
+        .unwrap();
+    assert_eq!(tickers.len(), 200);
+    assert_eq!(tickers[100].target, "T0");
+}
+
+#[tokio::test]
+async fn test_tickers_stop_at_short_page_with_filters() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/coins/ethereum/tickers"))
+        .and(query_param("page", "1"))
+        .and(query_param("exchange_ids", "binance,gdax"))
+        .and(query_param("include_exchange_logo", "true"))
+        .respond_with(tickers_page(3))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server, Config::demo());
+    let tickers = client
+        .coins()
+        .tickers("ethereum", Some(&["binance", "gdax"]), true)
+        .await
+        .unwrap();
+    assert_eq!(tickers.len(), 3);
+}
+
+#[tokio::test]
+async fn test_tickers_page_cap() {
+    let server = MockServer::start().await;
+    // Every page is full, so only the page cap ends the loop
+    Mock::given(method("GET"))
+        .and(path("/api/v3/coins/ethereum/tickers"))
+        .respond_with(tickers_page(100))
+        .expect(50)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server, Config::demo());
+    let tickers = client
+        .coins()
+        .tickers("ethereum", None, false)
+        .await
+        .unwrap();
+    assert_eq!(tickers.len(), 5_000);
+}
+
+#[tokio::test]
+async fn test_tickers_pages_wait_on_rate_limiter() {
+    let server = MockServer::start().await;
+    mount_tickers_page(&server, "1", 100, 1).await;
+    mount_tickers_page(&server, "2", 100, 1).await;
+    mount_tickers_page(&server, "3", 0, 1).await;
+
+    // One request per 200ms window: three pages span at least two windows
+    let client = mock_client(
+        &server,
+        Config::demo().with_rate_limit(1, Duration::from_millis(200)),
+    );
+    let start = std::time::Instant::now();
+    let tickers = client
+        .coins()
+        .tickers("ethereum", None, false)
+        .await
+        .unwrap();
+    assert_eq!(tickers.len(), 200);
+    assert!(
+        start.elapsed() >= Duration::from_millis(400),
+        "pages were not rate limited: {:?}",
+        start.elapsed()
+    );
+}
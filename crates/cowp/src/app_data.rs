@@ -0,0 +1,541 @@
+//! App data documents attached to orders
+//!
+//! Every order commits to a 32-byte `appData` hash. Its preimage is a JSON
+//! document describing who placed the order: the integrator's `appCode`,
+//! an optional referrer, partner fee policies and pre/post hooks. The API
+//! serves registered documents from `/api/v1/app_data/{hash}`.
+//!
+//! Fields have moved between schema versions; parsing accepts all of them:
+//!
+//! - `partnerFee` is one policy or (in newer versions) a list of them.
+//! - Volume fees are `bps` up to v1.1 and `volumeBps` afterwards; surplus and
+//!   price improvement fees carry a `maxVolumeBps` cap.
+//! - Hook gas limits are strings, but numbers are accepted too.
+
+use crate::client::Client;
+use crate::error::{self, Result};
+use crate::types::{Chain, Trade};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
+
+/// Documents kept by a client's app data cache unless configured otherwise
+pub const DEFAULT_APP_DATA_CACHE_SIZE: usize = 1024;
+
+/// Response of `/api/v1/app_data/{hash}`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AppDataResponse {
+    /// The document, as the JSON string that was hashed
+    pub full_app_data: String,
+}
+
+/// An app data document
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppDataDocument {
+    /// Schema version (e.g. `1.1.0`)
+    #[serde(default)]
+    pub version: String,
+    /// Integrator that created the order
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app_code: Option<String>,
+    /// Environment the integrator ran in (e.g. `production`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<String>,
+    /// Attribution and execution metadata
+    #[serde(default)]
+    pub metadata: AppDataMetadata,
+}
+
+/// The `metadata` section of an app data document
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppDataMetadata {
+    /// Referrer that sent the user
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub referrer: Option<Referrer>,
+    /// Partner fee policies (empty if the order pays none)
+    #[serde(
+        default,
+        deserialize_with = "one_or_many",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub partner_fee: Vec<PartnerFee>,
+    /// Pre- and post-interaction hooks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hooks: Option<AppDataHooks>,
+    /// Order class (`market`, `limit`, `liquidity`, `twap`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order_class: Option<OrderClass>,
+}
+
+/// Referrer metadata
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Referrer {
+    /// Referrer address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    /// Referrer schema version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+/// The `orderClass` metadata
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderClass {
+    /// Order class name
+    pub order_class: String,
+}
+
+/// Kind of partner fee policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PartnerFeeKind {
+    /// Share of the traded volume
+    Volume,
+    /// Share of the surplus over the limit price
+    Surplus,
+    /// Share of the improvement over the quote
+    PriceImprovement,
+}
+
+/// One partner fee policy
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartnerFee {
+    /// Address receiving the fee
+    pub recipient: String,
+    /// Volume fee in basis points (`bps` before v1.2)
+    #[serde(default, alias = "bps", skip_serializing_if = "Option::is_none")]
+    pub volume_bps: Option<u32>,
+    /// Surplus fee in basis points of the surplus
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub surplus_bps: Option<u32>,
+    /// Price improvement fee in basis points of the improvement
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_improvement_bps: Option<u32>,
+    /// Cap on surplus and price improvement fees, in basis points of volume
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_volume_bps: Option<u32>,
+}
+
+impl PartnerFee {
+    /// The policy kind, or `None` if no fee rate is set
+    #[must_use]
+    pub fn kind(&self) -> Option<PartnerFeeKind> {
+        if self.volume_bps.is_some() {
+            Some(PartnerFeeKind::Volume)
+        } else if self.surplus_bps.is_some() {
+            Some(PartnerFeeKind::Surplus)
+        } else if self.price_improvement_bps.is_some() {
+            Some(PartnerFeeKind::PriceImprovement)
+        } else {
+            None
+        }
+    }
+
+    /// The policy's fee rate in basis points
+    #[must_use]
+    pub fn bps(&self) -> Option<u32> {
+        self.volume_bps
+            .or(self.surplus_bps)
+            .or(self.price_improvement_bps)
+    }
+}
+
+/// The `hooks` metadata
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppDataHooks {
+    /// Hooks schema version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Hooks run before the settlement
+    #[serde(default)]
+    pub pre: Vec<Hook>,
+    /// Hooks run after the settlement
+    #[serde(default)]
+    pub post: Vec<Hook>,
+}
+
+/// A call made around the settlement
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Hook {
+    /// Contract called
+    pub target: String,
+    /// Calldata
+    #[serde(default)]
+    pub call_data: String,
+    /// Gas limit
+    #[serde(default, deserialize_with = "string_or_number")]
+    pub gas_limit: String,
+    /// App that added the hook
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dapp_id: Option<String>,
+}
+
+/// Hooks reduced to what attribution needs
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HookSummary {
+    /// Number of pre-hooks
+    pub pre: usize,
+    /// Number of post-hooks
+    pub post: usize,
+    /// Distinct hook targets, lowercase, in order of appearance
+    pub targets: Vec<String>,
+    /// Distinct dapp IDs, in order of appearance
+    pub dapp_ids: Vec<String>,
+}
+
+impl AppDataDocument {
+    /// Parse a document from its JSON (the `fullAppData` string)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON doesn't match any known schema version.
+    pub fn from_json(full_app_data: &str) -> Result<Self> {
+        Ok(serde_json::from_str(full_app_data)?)
+    }
+
+    /// Referrer address, if any
+    #[must_use]
+    pub fn referrer(&self) -> Option<&str> {
+        self.metadata.referrer.as_ref()?.address.as_deref()
+    }
+
+    /// Whether the order pays a partner fee
+    #[must_use]
+    pub fn has_partner_fee(&self) -> bool {
+        !self.metadata.partner_fee.is_empty()
+    }
+
+    /// Summary of the document's hooks
+    #[must_use]
+    pub fn hook_summary(&self) -> HookSummary {
+        let mut summary = HookSummary::default();
+        let Some(hooks) = &self.metadata.hooks else {
+            return summary;
+        };
+        summary.pre = hooks.pre.len();
+        summary.post = hooks.post.len();
+        for hook in hooks.pre.iter().chain(&hooks.post) {
+            let target = hook.target.to_lowercase();
+            if !summary.targets.contains(&target) {
+                summary.targets.push(target);
+            }
+            if let Some(dapp_id) = &hook.dapp_id {
+                if !summary.dapp_ids.contains(dapp_id) {
+                    summary.dapp_ids.push(dapp_id.clone());
+                }
+            }
+        }
+        summary
+    }
+}
+
+impl Trade {
+    /// Look up the app data document of the order this trade filled
+    ///
+    /// Trades don't carry the app data hash, so the order is fetched first.
+    /// When the order already embeds the document it is used (and cached)
+    /// without a separate app data lookup.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppDataNotFound` if the order has no app data hash or the
+    /// hash isn't registered.
+    pub async fn resolve_app_data(
+        &self,
+        client: &Client,
+        chain: Option<Chain>,
+    ) -> Result<AppDataDocument> {
+        let order = client.get_order(chain, &self.order_uid).await?;
+        let hash = order
+            .app_data
+            .ok_or_else(|| error::app_data_not_found(format!("order {}", self.order_uid)))?;
+        match order.full_app_data.as_deref() {
+            Some(full_app_data) if is_app_data_hash(&hash) => {
+                let document = AppDataDocument::from_json(full_app_data)?;
+                client.remember_app_data(
+                    chain.unwrap_or(client.default_chain()),
+                    &hash,
+                    Some(document.clone()),
+                );
+                Ok(document)
+            }
+            _ => client.get_app_data(chain, &hash).await,
+        }
+    }
+}
+
+/// Whether `hash` is a 0x-prefixed 32-byte hex string
+#[must_use]
+pub fn is_app_data_hash(hash: &str) -> bool {
+    hash.strip_prefix("0x")
+        .is_some_and(|hex| hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// Check an app data hash, for use before building a request path
+pub(crate) fn validate_app_data_hash(hash: &str) -> Result<()> {
+    if is_app_data_hash(hash) {
+        Ok(())
+    } else {
+        Err(error::invalid_param(format!(
+            "app data hash must be 0x followed by 64 hex characters, got '{hash}'"
+        )))
+    }
+}
+
+/// Cache key: chain and lowercase hash
+type CacheKey = (Chain, String);
+
+/// Least-recently-used cache of app data lookups
+///
+/// Hashes that aren't registered are cached as `None`, so orders sharing an
+/// unknown hash are only looked up once.
+#[derive(Debug)]
+pub(crate) struct AppDataCache {
+    capacity: usize,
+    /// Entry and the tick it was last used at
+    entries: HashMap<CacheKey, (Option<AppDataDocument>, u64)>,
+    tick: u64,
+}
+
+impl AppDataCache {
+    /// Create a cache holding up to `capacity` lookups (0 disables caching)
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            tick: 0,
+        }
+    }
+
+    /// Look up a hash, marking it as recently used
+    ///
+    /// The outer `Option` is `None` on a cache miss.
+    pub(crate) fn get(&mut self, key: &CacheKey) -> Option<Option<AppDataDocument>> {
+        self.tick += 1;
+        let (document, used) = self.entries.get_mut(key)?;
+        *used = self.tick;
+        Some(document.clone())
+    }
+
+    /// Store a lookup, evicting the least recently used entry if full
+    pub(crate) fn insert(&mut self, key: CacheKey, document: Option<AppDataDocument>) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.tick += 1;
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.capacity {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(k, _)| k.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.entries.insert(key, (document, self.tick));
+    }
+
+    /// Number of cached lookups
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+/// Accept a single value or a list of values
+fn one_or_many<'de, D, T>(deserializer: D) -> std::result::Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany<T> {
+        One(T),
+        Many(Vec<T>),
+    }
+
+    Ok(match Option::<OneOrMany<T>>::deserialize(deserializer)? {
+        None => Vec::new(),
+        Some(OneOrMany::One(value)) => vec![value],
+        Some(OneOrMany::Many(values)) => values,
+    })
+}
+
+/// Accept a string or a number, keeping it as a string
+fn string_or_number<'de, D>(deserializer: D) -> std::result::Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StringOrNumber {
+        String(String),
+        Number(serde_json::Number),
+    }
+
+    Ok(match StringOrNumber::deserialize(deserializer)? {
+        StringOrNumber::String(s) => s,
+        StringOrNumber::Number(n) => n.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NO_PARTNER_FEE_V1_1: &str =
+        include_str!("../tests/fixtures/app_data/no_partner_fee_v1_1.json");
+    const PARTNER_FEE_V1_1: &str = include_str!("../tests/fixtures/app_data/partner_fee_v1_1.json");
+    const PARTNER_FEE_HOOKS_V1_3: &str =
+        include_str!("../tests/fixtures/app_data/partner_fee_hooks_v1_3.json");
+    const PARTNER_FEE_POLICIES_V1_4: &str =
+        include_str!("../tests/fixtures/app_data/partner_fee_policies_v1_4.json");
+
+    const RECIPIENT: &str = "0x1F0b2C3D4e5F60718293A4b5C6d7E8f901234567";
+
+    #[test]
+    fn test_document_without_partner_fee() {
+        let doc = AppDataDocument::from_json(NO_PARTNER_FEE_V1_1).unwrap();
+        assert_eq!(doc.version, "1.1.0");
+        assert_eq!(doc.app_code.as_deref(), Some("CoW Swap"));
+        assert_eq!(doc.environment.as_deref(), Some("production"));
+        assert!(!doc.has_partner_fee());
+        assert_eq!(doc.referrer(), None);
+        assert_eq!(
+            doc.metadata.order_class.as_ref().unwrap().order_class,
+            "market"
+        );
+        assert_eq!(doc.hook_summary(), HookSummary::default());
+    }
+
+    #[test]
+    fn test_v1_1_partner_fee_and_referrer() {
+        let doc = AppDataDocument::from_json(PARTNER_FEE_V1_1).unwrap();
+        assert_eq!(doc.app_code.as_deref(), Some("Yield Desk"));
+        assert_eq!(
+            doc.referrer(),
+            Some("0x9A8B7c6D5e4F3a2B1c0D9e8F7a6B5c4D3e2F1a0B")
+        );
+        // `bps` is the pre-v1.2 name for a volume fee
+        assert_eq!(
+            doc.metadata.partner_fee,
+            vec![PartnerFee {
+                recipient: RECIPIENT.to_string(),
+                volume_bps: Some(25),
+                ..PartnerFee::default()
+            }]
+        );
+        assert_eq!(
+            doc.metadata.partner_fee[0].kind(),
+            Some(PartnerFeeKind::Volume)
+        );
+    }
+
+    #[test]
+    fn test_v1_3_volume_fee_and_hooks() {
+        let doc = AppDataDocument::from_json(PARTNER_FEE_HOOKS_V1_3).unwrap();
+        assert_eq!(doc.version, "1.3.0");
+        assert_eq!(doc.environment, None);
+        assert_eq!(doc.metadata.partner_fee[0].bps(), Some(30));
+
+        let hooks = doc.metadata.hooks.as_ref().unwrap();
+        assert_eq!(hooks.pre[0].gas_limit, "80000");
+        // Numeric gas limits are accepted
+        assert_eq!(hooks.pre[1].gas_limit, "21000");
+
+        let summary = doc.hook_summary();
+        assert_eq!(summary.pre, 2);
+        assert_eq!(summary.post, 1);
+        assert_eq!(
+            summary.targets,
+            vec![
+                "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+                "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+            ]
+        );
+        assert_eq!(summary.dapp_ids, vec!["cow.fi/permit"]);
+    }
+
+    #[test]
+    fn test_v1_4_partner_fee_policies() {
+        let doc = AppDataDocument::from_json(PARTNER_FEE_POLICIES_V1_4).unwrap();
+        let fees = &doc.metadata.partner_fee;
+        let kinds: Vec<_> = fees.iter().map(|f| (f.kind().unwrap(), f.bps())).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (PartnerFeeKind::Volume, Some(10)),
+                (PartnerFeeKind::Surplus, Some(5000)),
+                (PartnerFeeKind::PriceImprovement, Some(2000)),
+            ]
+        );
+        assert_eq!(fees[1].max_volume_bps, Some(100));
+        assert_eq!(
+            doc.referrer(),
+            Some("0x9A8B7c6D5e4F3a2B1c0D9e8F7a6B5c4D3e2F1a0B")
+        );
+    }
+
+    #[test]
+    fn test_round_trip_uses_current_field_names() {
+        let doc = AppDataDocument::from_json(PARTNER_FEE_V1_1).unwrap();
+        let json = serde_json::to_value(&doc).unwrap();
+        assert_eq!(json["metadata"]["partnerFee"][0]["volumeBps"], 25);
+        assert_eq!(AppDataDocument::from_json(&json.to_string()).unwrap(), doc);
+    }
+
+    #[test]
+    fn test_is_app_data_hash() {
+        assert!(is_app_data_hash(&format!("0x{}", "ab".repeat(32))));
+        assert!(!is_app_data_hash(&"ab".repeat(32)));
+        assert!(!is_app_data_hash("0x1234"));
+        assert!(!is_app_data_hash(&format!("0x{}", "zz".repeat(32))));
+        assert!(validate_app_data_hash("0x").is_err());
+    }
+
+    #[test]
+    fn test_cache_evicts_least_recently_used() {
+        let key = |n: u8| (Chain::Mainnet, format!("0x{n:064x}"));
+        let doc = |code: &str| AppDataDocument {
+            app_code: Some(code.to_string()),
+            ..AppDataDocument::default()
+        };
+
+        let mut cache = AppDataCache::new(2);
+        cache.insert(key(1), Some(doc("a")));
+        cache.insert(key(2), None);
+        // Touch 1 so 2 is the oldest
+        assert_eq!(cache.get(&key(1)), Some(Some(doc("a"))));
+        cache.insert(key(3), Some(doc("c")));
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&key(2)), None);
+        assert!(cache.get(&key(1)).is_some());
+        assert_eq!(cache.get(&key(3)), Some(Some(doc("c"))));
+        // Same hash on another chain is a different entry
+        assert_eq!(cache.get(&(Chain::Gnosis, key(1).1)), None);
+
+        let mut disabled = AppDataCache::new(0);
+        disabled.insert(key(1), None);
+        assert_eq!(disabled.len(), 0);
+    }
+
+    #[test]
+    fn test_cached_not_found() {
+        let mut cache = AppDataCache::new(4);
+        let key = (Chain::Mainnet, format!("0x{}", "0".repeat(64)));
+        cache.insert(key.clone(), None);
+        // A hit that records the hash as unregistered
+        assert_eq!(cache.get(&key), Some(None));
+    }
+}
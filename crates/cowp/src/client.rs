@@ -3,15 +3,40 @@
 //! This client uses the shared `BaseClient` infrastructure from `yldfi_common`
 //! while handling `CoW` Protocol's chain-specific base URLs.
 
+use crate::app_data::{
+    self, AppDataCache, AppDataDocument, AppDataResponse, DEFAULT_APP_DATA_CACHE_SIZE,
+};
 use crate::error::{self, Error, Result};
+use crate::report::{AppCodeVolume, TradeReport};
 use crate::types::{
-    ApiError, Chain, Order, OrderCreation, OrderResponse, QuoteRequest, QuoteResponse, Trade,
+    ApiError, Chain, HumanAmounts, Order, OrderCreation, OrderResponse, QuoteRequest,
+    QuoteResponse, QuoteSanity, TokenInfo, Trade,
 };
 use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::Duration;
 
 use yldfi_common::api::{ApiConfig, BaseClient};
 
+/// Page size used when paging orders and trades for reports
+const REPORT_PAGE_SIZE: u32 = 1000;
+
+/// Upper bound on pages fetched for each of a report's orders and trades
+pub const MAX_REPORT_PAGES: u32 = 50;
+
+/// `decimals()` selector
+const DECIMALS_SELECTOR: &str = "0x313ce567";
+
+/// `symbol()` selector
+const SYMBOL_SELECTOR: &str = "0x95d89b41";
+
+/// Token info cache keyed by chain and lowercase address
+type TokenCache = Arc<RwLock<HashMap<(Chain, String), TokenInfo>>>;
+
+/// App data cache shared between a client and its clones
+type SharedAppDataCache = Arc<Mutex<AppDataCache>>;
+
 /// Configuration for the `CoW` Protocol API client
 #[derive(Debug, Clone)]
 pub struct Config {
@@ -23,6 +48,12 @@ pub struct Config {
     pub proxy: Option<String>,
     /// Default chain
     pub chain: Chain,
+    /// JSON-RPC endpoints used to resolve token metadata
+    pub rpc_urls: HashMap<Chain, String>,
+    /// API base URLs replacing the public endpoint of a chain
+    pub api_urls: HashMap<Chain, String>,
+    /// App data lookups kept in the client's LRU cache
+    pub app_data_cache_size: usize,
 }
 
 impl Default for Config {
@@ -33,6 +64,9 @@ impl Default for Config {
             timeout: Duration::from_secs(30),
             proxy: None,
             chain: Chain::Mainnet,
+            rpc_urls: HashMap::new(),
+            api_urls: HashMap::new(),
+            app_data_cache_size: DEFAULT_APP_DATA_CACHE_SIZE,
         }
     }
 }
@@ -79,6 +113,27 @@ impl Config {
         self.with_optional_proxy(proxy)
     }
 
+    /// Set the JSON-RPC endpoint used to look up token decimals and symbols on `chain`
+    #[must_use]
+    pub fn with_rpc_url(mut self, chain: Chain, url: impl Into<String>) -> Self {
+        self.rpc_urls.insert(chain, url.into());
+        self
+    }
+
+    /// Send API requests for `chain` to `url` instead of its public endpoint
+    #[must_use]
+    pub fn with_api_url(mut self, chain: Chain, url: impl Into<String>) -> Self {
+        self.api_urls.insert(chain, url.into());
+        self
+    }
+
+    /// Set how many app data lookups are cached (0 disables the cache)
+    #[must_use]
+    pub fn with_app_data_cache_size(mut self, size: usize) -> Self {
+        self.app_data_cache_size = size;
+        self
+    }
+
     /// Convert to `ApiConfig` for `BaseClient`
     fn to_api_config(&self) -> ApiConfig {
         ApiConfig::new(&self.base_url)
@@ -99,6 +154,10 @@ impl Config {
 pub struct Client {
     base: BaseClient,
     default_chain: Chain,
+    rpc_urls: HashMap<Chain, String>,
+    api_urls: HashMap<Chain, String>,
+    tokens: TokenCache,
+    app_data: SharedAppDataCache,
 }
 
 impl Client {
@@ -115,12 +174,25 @@ impl Client {
         Ok(Self {
             base,
             default_chain,
+            rpc_urls: config.rpc_urls,
+            api_urls: config.api_urls,
+            tokens: TokenCache::default(),
+            app_data: Arc::new(Mutex::new(AppDataCache::new(config.app_data_cache_size))),
         })
     }
 
+    /// Chain used when a call doesn't name one
+    #[must_use]
+    pub fn default_chain(&self) -> Chain {
+        self.default_chain
+    }
+
     /// Get the base URL for a chain
-    fn base_url(&self, chain: Option<Chain>) -> &'static str {
-        chain.unwrap_or(self.default_chain).api_url()
+    fn base_url(&self, chain: Option<Chain>) -> &str {
+        let chain = chain.unwrap_or(self.default_chain);
+        self.api_urls
+            .get(&chain)
+            .map_or(chain.api_url(), String::as_str)
     }
 
     /// Make a GET request to the API
@@ -289,6 +361,103 @@ impl Client {
         self.get(chain, &path).await
     }
 
+    /// Get one page of orders by owner, newest first
+    pub async fn get_orders_by_owner_page(
+        &self,
+        chain: Option<Chain>,
+        owner: &str,
+        offset: u32,
+        limit: u32,
+    ) -> Result<Vec<Order>> {
+        let path = format!("/api/v1/account/{owner}/orders?offset={offset}&limit={limit}");
+        self.get(chain, &path).await
+    }
+
+    /// Get one page of trades by owner, newest first
+    pub async fn get_trades_by_owner_page(
+        &self,
+        chain: Option<Chain>,
+        owner: &str,
+        offset: u32,
+        limit: u32,
+    ) -> Result<Vec<Trade>> {
+        let path = format!("/api/v2/trades?owner={owner}&offset={offset}&limit={limit}");
+        self.get(chain, &path).await
+    }
+
+    /// Build a surplus and fee report for an owner's trades in a time window
+    ///
+    /// Fetches the owner's orders (newest first, stopping once orders predate
+    /// `from_ts`) and all of the owner's trades, then joins them with
+    /// [`TradeReport::build`]. The window is inclusive Unix seconds and is
+    /// matched against order creation time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error rather than a truncated report if the orders or
+    /// trades run past [`MAX_REPORT_PAGES`] pages of 1000.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use cowp::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), cowp::Error> {
+    ///     let client = Client::new()?;
+    ///
+    ///     // Q2 2024
+    ///     let report = client
+    ///         .trade_report(None, "0xYourTreasury", 1_711_929_600, 1_719_791_999)
+    ///         .await?;
+    ///     for total in &report.totals {
+    ///         println!("{}: fees {} surplus {}", total.token, total.fees, total.surplus);
+    ///     }
+    ///     std::fs::write("cow_trades.csv", report.to_csv()).ok();
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn trade_report(
+        &self,
+        chain: Option<Chain>,
+        owner: &str,
+        from_ts: u64,
+        to_ts: u64,
+    ) -> Result<TradeReport> {
+        if from_ts > to_ts {
+            return Err(error::invalid_param(format!(
+                "from_ts ({from_ts}) is after to_ts ({to_ts})"
+            )));
+        }
+
+        let orders = report_pages(
+            "orders",
+            |offset| self.get_orders_by_owner_page(chain, owner, offset, REPORT_PAGE_SIZE),
+            |page| {
+                page.last()
+                    .and_then(crate::report::created_at)
+                    .is_some_and(|created| created < from_ts)
+            },
+        )
+        .await?;
+        let trades = report_pages(
+            "trades",
+            |offset| self.get_trades_by_owner_page(chain, owner, offset, REPORT_PAGE_SIZE),
+            |_| false,
+        )
+        .await?;
+
+        TradeReport::build(
+            chain.unwrap_or(self.default_chain),
+            owner,
+            from_ts,
+            to_ts,
+            &orders,
+            &trades,
+        )
+    }
+
     /// Get trades for an order
     pub async fn get_trades_by_order(
         &self,
@@ -299,6 +468,92 @@ impl Client {
         self.get(chain, &path).await
     }
 
+    /// Get the app data document registered for a hash
+    ///
+    /// Documents are cached per chain in an LRU cache shared by the client
+    /// and its clones (see [`Config::with_app_data_cache_size`]), since the
+    /// same hash repeats across many orders.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::AppDataNotFound`](crate::error::DomainError::AppDataNotFound)
+    /// if no document is registered for the hash (see
+    /// [`error::is_app_data_not_found`]); unregistered hashes are cached too.
+    pub async fn get_app_data(
+        &self,
+        chain: Option<Chain>,
+        app_data_hash: &str,
+    ) -> Result<AppDataDocument> {
+        app_data::validate_app_data_hash(app_data_hash)?;
+        let chain = chain.unwrap_or(self.default_chain);
+        let key = (chain, app_data_hash.to_lowercase());
+        let cached = self.app_data.lock().ok().and_then(|mut c| c.get(&key));
+        if let Some(document) = cached {
+            return document.ok_or_else(|| error::app_data_not_found(app_data_hash));
+        }
+
+        let url = format!("{}/api/v1/app_data/{}", self.base_url(Some(chain)), key.1);
+        let response = self.base.http().get(&url).send().await?;
+        let document = if response.status() == reqwest::StatusCode::NOT_FOUND {
+            None
+        } else {
+            let response: AppDataResponse = self.handle_response(response).await?;
+            Some(AppDataDocument::from_json(&response.full_app_data)?)
+        };
+
+        self.remember_app_data(chain, app_data_hash, document.clone());
+        document.ok_or_else(|| error::app_data_not_found(app_data_hash))
+    }
+
+    /// Cache an app data lookup (`None` for an unregistered hash)
+    pub(crate) fn remember_app_data(
+        &self,
+        chain: Chain,
+        app_data_hash: &str,
+        document: Option<AppDataDocument>,
+    ) {
+        if let Ok(mut cache) = self.app_data.lock() {
+            cache.insert((chain, app_data_hash.to_lowercase()), document);
+        }
+    }
+
+    /// Resolve app data hashes to app codes
+    ///
+    /// Returns a map from lowercase hash to app code. Hashes that aren't
+    /// registered, or whose document has no app code, are left out.
+    pub async fn app_codes(
+        &self,
+        chain: Option<Chain>,
+        hashes: &[String],
+    ) -> Result<HashMap<String, String>> {
+        let mut app_codes = HashMap::new();
+        for hash in hashes {
+            match self.get_app_data(chain, hash).await {
+                Ok(document) => {
+                    if let Some(app_code) = document.app_code {
+                        app_codes.insert(hash.to_lowercase(), app_code);
+                    }
+                }
+                Err(e) if error::is_app_data_not_found(&e) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(app_codes)
+    }
+
+    /// Group a trade report's volume by the app code of each order
+    ///
+    /// See [`TradeReport::volume_by_app_code`].
+    pub async fn app_code_volume(
+        &self,
+        chain: Option<Chain>,
+        report: &TradeReport,
+    ) -> Result<Vec<AppCodeVolume>> {
+        let chain = chain.or(Some(report.chain));
+        let app_codes = self.app_codes(chain, &report.app_data_hashes()).await?;
+        report.volume_by_app_code(&app_codes)
+    }
+
     /// Get the current auction
     pub async fn get_auction(&self, chain: Option<Chain>) -> Result<serde_json::Value> {
         self.get(chain, "/api/v1/auction").await
@@ -323,6 +578,204 @@ impl Client {
         let path = format!("/api/v1/token/{token}/native_price");
         self.get(chain, &path).await
     }
+
+    /// Get a token's symbol and decimals
+    ///
+    /// The `CoW` API has no token metadata endpoint, so ERC-20s are resolved
+    /// with `decimals()` and `symbol()` calls against the chain's RPC
+    /// endpoint (see [`Config::with_rpc_url`]). The native currency
+    /// placeholder and the wrapped native token are answered locally.
+    /// Results are cached per chain for the lifetime of the client and its
+    /// clones, so formatting many quotes only looks each token up once.
+    pub async fn get_token_info(&self, chain: Option<Chain>, token: &str) -> Result<TokenInfo> {
+        let chain = chain.unwrap_or(self.default_chain);
+        if let Some(info) = TokenInfo::well_known(chain, token) {
+            return Ok(info);
+        }
+
+        let key = (chain, token.to_lowercase());
+        if let Some(info) = self.cached_token(&key) {
+            return Ok(info);
+        }
+
+        let rpc_url = self.rpc_urls.get(&chain).ok_or_else(|| {
+            error::token_info_unavailable(format!(
+                "no RPC URL configured for {chain} (use Config::with_rpc_url)"
+            ))
+        })?;
+
+        let (decimals, symbol) = tokio::join!(
+            self.eth_call(rpc_url, &key.1, DECIMALS_SELECTOR),
+            self.eth_call(rpc_url, &key.1, SYMBOL_SELECTOR),
+        );
+        let decimals = decode_decimals(&decimals?).ok_or_else(|| {
+            error::token_info_unavailable(format!("{token} returned invalid decimals()"))
+        })?;
+        // symbol() is optional in ERC-20
+        let symbol = symbol
+            .ok()
+            .and_then(|s| decode_symbol(&s))
+            .unwrap_or_default();
+
+        let info = TokenInfo {
+            address: key.1.clone(),
+            symbol,
+            decimals,
+            is_native: false,
+        };
+        if let Ok(mut cache) = self.tokens.write() {
+            cache.insert(key, info.clone());
+        }
+        Ok(info)
+    }
+
+    /// Format a quote's amounts with each token's decimals
+    pub async fn human_amounts(
+        &self,
+        chain: Option<Chain>,
+        quote: &QuoteResponse,
+    ) -> Result<HumanAmounts> {
+        let (sell, buy) = tokio::try_join!(
+            self.get_token_info(chain, &quote.quote.sell_token),
+            self.get_token_info(chain, &quote.quote.buy_token),
+        )?;
+        Ok(quote.human_amounts(sell.decimals, buy.decimals))
+    }
+
+    /// Check a quote against `CoW`'s native prices for both tokens
+    ///
+    /// See [`QuoteResponse::sanity`].
+    pub async fn quote_sanity(
+        &self,
+        chain: Option<Chain>,
+        quote: &QuoteResponse,
+    ) -> Result<QuoteSanity> {
+        let (sell, buy) = tokio::try_join!(
+            self.native_price(chain, &quote.quote.sell_token),
+            self.native_price(chain, &quote.quote.buy_token),
+        )?;
+        quote
+            .sanity(sell, buy)
+            .ok_or_else(|| error::no_quote("quote amounts or native prices are not usable"))
+    }
+
+    /// Native price of a token as a number (1.0 for the native currency)
+    async fn native_price(&self, chain: Option<Chain>, token: &str) -> Result<f64> {
+        if crate::types::is_native_token(token) {
+            return Ok(1.0);
+        }
+        let response = self.get_native_price(chain, token).await?;
+        response["price"]
+            .as_f64()
+            .ok_or_else(|| error::no_quote(format!("no native price for {token}")))
+    }
+
+    /// Look up a cached token
+    fn cached_token(&self, key: &(Chain, String)) -> Option<TokenInfo> {
+        self.tokens.read().ok()?.get(key).cloned()
+    }
+
+    /// Make an `eth_call` and return the hex result
+    async fn eth_call(&self, rpc_url: &str, to: &str, data: &str) -> Result<String> {
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "eth_call",
+            "params": [{ "to": to, "data": data }, "latest"],
+        });
+        let response = self.base.http().post(rpc_url).json(&body).send().await?;
+        let status = response.status().as_u16();
+        if !response.status().is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(Error::from_response(status, &body, None));
+        }
+
+        let response: serde_json::Value = response.json().await?;
+        if let Some(err) = response.get("error") {
+            return Err(error::token_info_unavailable(format!(
+                "eth_call to {to} failed: {}",
+                err["message"].as_str().unwrap_or("unknown error")
+            )));
+        }
+        response["result"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| {
+                error::token_info_unavailable(format!("eth_call to {to} returned no result"))
+            })
+    }
+}
+
+/// Decode a `decimals()` return value
+fn decode_decimals(hex: &str) -> Option<u8> {
+    let bytes = decode_hex(hex)?;
+    let word = bytes.get(..32)?;
+    // uint8 is left-padded; anything over 255 is not a decimals value
+    if word[..31].iter().any(|&b| b != 0) {
+        return None;
+    }
+    Some(word[31])
+}
+
+/// Decode a `symbol()` return value, ABI `string` or legacy `bytes32`
+fn decode_symbol(hex: &str) -> Option<String> {
+    let bytes = decode_hex(hex)?;
+    let raw = if bytes.len() == 32 {
+        // bytes32 (e.g. MKR), right-padded with zeros
+        let end = bytes.iter().position(|&b| b == 0).unwrap_or(32);
+        bytes[..end].to_vec()
+    } else {
+        let word = |i: usize| -> Option<usize> {
+            let w = bytes.get(i..i + 32)?;
+            if w[..24].iter().any(|&b| b != 0) {
+                return None;
+            }
+            Some(u64::from_be_bytes(w[24..].try_into().ok()?) as usize)
+        };
+        let offset = word(0)?;
+        let len = word(offset)?;
+        bytes.get(offset + 32..offset + 32 + len)?.to_vec()
+    };
+    String::from_utf8(raw).ok().filter(|s| !s.is_empty())
+}
+
+/// Decode a 0x-prefixed hex string
+/// Page through a report endpoint until a short page or `last_page`
+///
+/// `fetch` receives the offset of each page. Errors once
+/// [`MAX_REPORT_PAGES`] full pages have been read without reaching the end.
+async fn report_pages<T, Fut>(
+    what: &str,
+    mut fetch: impl FnMut(u32) -> Fut,
+    last_page: impl Fn(&[T]) -> bool,
+) -> Result<Vec<T>>
+where
+    Fut: std::future::Future<Output = Result<Vec<T>>>,
+{
+    let mut items = Vec::new();
+    for page_index in 0..MAX_REPORT_PAGES {
+        let page = fetch(page_index * REPORT_PAGE_SIZE).await?;
+        let done = page.len() < REPORT_PAGE_SIZE as usize || last_page(&page);
+        items.extend(page);
+        if done {
+            return Ok(items);
+        }
+    }
+    Err(error::invalid_param(format!(
+        "more than {} {what} to page through; the report would be incomplete",
+        MAX_REPORT_PAGES * REPORT_PAGE_SIZE
+    )))
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
 }
 
 #[cfg(test)]
@@ -371,6 +824,29 @@ mod tests {
         assert!(request.buy_amount_after_fee.is_some());
     }
 
+    #[test]
+    fn test_decode_decimals() {
+        let word = |last: &str| format!("0x{last:0>64}");
+        assert_eq!(decode_decimals(&word("12")), Some(18));
+        assert_eq!(decode_decimals(&word("06")), Some(6));
+        assert_eq!(decode_decimals(&word("0100")), None);
+        assert_eq!(decode_decimals("0x"), None);
+    }
+
+    #[test]
+    fn test_decode_symbol() {
+        // ABI string "USDC"
+        let string = format!("0x{:0>64}{:0>64}{:0<64}", "20", "04", "55534443");
+        assert_eq!(decode_symbol(&string).as_deref(), Some("USDC"));
+
+        // bytes32 "MKR"
+        let bytes32 = format!("0x{:0<64}", "4d4b52");
+        assert_eq!(decode_symbol(&bytes32).as_deref(), Some("MKR"));
+
+        assert_eq!(decode_symbol("0x"), None);
+        assert_eq!(decode_symbol(&format!("0x{:0>64}", "")), None);
+    }
+
     #[test]
     fn test_chain_parsing() {
         assert_eq!(Chain::try_from_str("mainnet"), Some(Chain::Mainnet));
@@ -29,6 +29,14 @@ pub enum DomainError {
     /// Order not found
     #[error("Order not found: {0}")]
     OrderNotFound(String),
+
+    /// Token metadata could not be resolved
+    #[error("Token info unavailable: {0}")]
+    TokenInfoUnavailable(String),
+
+    /// No app data document is registered for the hash
+    #[error("App data not found: {0}")]
+    AppDataNotFound(String),
 }
 
 /// Error type for `CoW` Protocol API operations
@@ -64,6 +72,22 @@ pub fn order_not_found(order_id: impl Into<String>) -> Error {
     ApiError::domain(DomainError::OrderNotFound(order_id.into()))
 }
 
+/// Create a token info unavailable error
+pub fn token_info_unavailable(message: impl Into<String>) -> Error {
+    ApiError::domain(DomainError::TokenInfoUnavailable(message.into()))
+}
+
+/// Create an app data not found error
+pub fn app_data_not_found(hash: impl Into<String>) -> Error {
+    ApiError::domain(DomainError::AppDataNotFound(hash.into()))
+}
+
+/// Whether an error is [`DomainError::AppDataNotFound`]
+#[must_use]
+pub fn is_app_data_not_found(error: &Error) -> bool {
+    matches!(error, ApiError::Domain(DomainError::AppDataNotFound(_)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -107,6 +131,14 @@ mod tests {
         assert!(display.contains("0x1234abcd"));
     }
 
+    #[test]
+    fn test_app_data_not_found_error() {
+        let err = app_data_not_found("0xabcd");
+        assert!(format!("{err}").contains("App data not found"));
+        assert!(is_app_data_not_found(&err));
+        assert!(!is_app_data_not_found(&order_not_found("0xabcd")));
+    }
+
     #[test]
     fn test_domain_error_variants() {
         // Test that all variants are constructable
@@ -115,6 +147,8 @@ mod tests {
         let _ = DomainError::NoQuote("test".to_string());
         let _ = DomainError::InsufficientLiquidity;
         let _ = DomainError::OrderNotFound("test".to_string());
+        let _ = DomainError::TokenInfoUnavailable("test".to_string());
+        let _ = DomainError::AppDataNotFound("test".to_string());
     }
 
     #[test]
@@ -71,15 +71,23 @@
 //! provides the types for order submission, but signing must be done
 //! externally (e.g., with ethers-rs or alloy).
 
+pub mod app_data;
 pub mod client;
 pub mod error;
+pub mod report;
 pub mod types;
 
-pub use client::{Client, Config};
+pub use app_data::{
+    is_app_data_hash, AppDataDocument, AppDataHooks, AppDataMetadata, Hook, HookSummary,
+    OrderClass, PartnerFee, PartnerFeeKind, Referrer, DEFAULT_APP_DATA_CACHE_SIZE,
+};
+pub use client::{Client, Config, MAX_REPORT_PAGES};
 pub use error::{Error, Result};
+pub use report::{format_units, AppCodeVolume, TokenTotals, TradeReport, TradeReportEntry};
 pub use types::{
-    ApiError, Chain, Order, OrderCreation, OrderKind, OrderResponse, OrderStatus, PriceQuality,
-    QuoteDetails, QuoteRequest, QuoteResponse, SigningScheme, Trade,
+    is_native_token, ApiError, Chain, HumanAmounts, Order, OrderCreation, OrderKind, OrderResponse,
+    OrderStatus, PriceQuality, QuoteDetails, QuoteRequest, QuoteResponse, QuoteSanity,
+    SigningScheme, TokenInfo, Trade, NATIVE_TOKEN,
 };
 
 // Re-export common utilities
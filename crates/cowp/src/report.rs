@@ -0,0 +1,815 @@
+//! Surplus and fee accounting for an owner's historical trades
+//!
+//! Orders are joined with the trades that filled them. Every amount is
+//! kept as a base-unit integer string; token decimals are only applied
+//! when formatting (see [`TradeReport::to_csv_with_decimals`]).
+//!
+//! Surplus is measured against the order's limit price, taken from the
+//! order's `sellAmount`/`buyAmount`:
+//!
+//! - Sell orders: surplus = bought - sold × buyAmount / sellAmount, in the
+//!   buy token.
+//! - Buy orders: surplus = bought × sellAmount / buyAmount - sold, in the
+//!   sell token.
+//!
+//! "Sold" excludes fees. Fees are reported separately in the sell token.
+
+use crate::error::{self, Result};
+use crate::types::{Chain, Order, OrderKind, Trade};
+use alloy_primitives::{I256, U256};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use yldfi_common::time::parse_iso8601;
+
+/// Surplus and fees for one order, aggregated across all of its fills
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeReportEntry {
+    /// Order UID
+    pub order_uid: String,
+    /// Order kind
+    pub kind: OrderKind,
+    /// Order creation time (RFC 3339)
+    pub created_date: String,
+    /// Token sold
+    pub sell_token: String,
+    /// Token bought
+    pub buy_token: String,
+    /// Number of trades that filled the order
+    pub fill_count: usize,
+    /// Settlement transaction hashes, in fill order
+    pub tx_hashes: Vec<String>,
+    /// Sell token spent, excluding fees (base units)
+    pub executed_sell_amount: String,
+    /// Buy token received (base units)
+    pub executed_buy_amount: String,
+    /// Fees paid in the sell token (base units)
+    pub fee_amount: String,
+    /// Surplus over the limit price (base units, may be negative)
+    pub surplus: String,
+    /// Token the surplus is denominated in
+    pub surplus_token: String,
+    /// The order's app data hash
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app_data: Option<String>,
+}
+
+/// Totals for one token across a report
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenTotals {
+    /// Token address (lowercase)
+    pub token: String,
+    /// Amount sold, excluding fees (base units)
+    pub sold: String,
+    /// Amount bought (base units)
+    pub bought: String,
+    /// Fees paid in this token (base units)
+    pub fees: String,
+    /// Surplus earned in this token (base units, may be negative)
+    pub surplus: String,
+}
+
+/// Volume attributed to one app code
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppCodeVolume {
+    /// App code (`None` for orders without a resolvable app data document)
+    pub app_code: Option<String>,
+    /// Number of filled orders
+    pub order_count: usize,
+    /// Totals per token, sorted by token address
+    pub totals: Vec<TokenTotals>,
+}
+
+/// Surplus and fee report for an owner's trades in a time window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeReport {
+    /// Chain the trades settled on
+    pub chain: Chain,
+    /// Order owner
+    pub owner: String,
+    /// Window start (Unix seconds, inclusive)
+    pub from_ts: u64,
+    /// Window end (Unix seconds, inclusive)
+    pub to_ts: u64,
+    /// One entry per filled order, oldest first
+    pub entries: Vec<TradeReportEntry>,
+    /// Totals per token, sorted by token address
+    pub totals: Vec<TokenTotals>,
+}
+
+/// CSV header for [`TradeReport::to_csv`]
+const CSV_HEADER: &str = "order_uid,kind,created_date,sell_token,buy_token,fill_count,\
+executed_sell_amount,executed_buy_amount,fee_amount,surplus,surplus_token,tx_hashes";
+
+#[derive(Default)]
+struct Totals {
+    sold: U256,
+    bought: U256,
+    fees: U256,
+    surplus: I256,
+}
+
+impl Totals {
+    /// Sum entries into per-token totals, sorted by token address
+    fn sum<'a>(
+        entries: impl IntoIterator<Item = &'a TradeReportEntry>,
+    ) -> Result<Vec<TokenTotals>> {
+        let mut totals: BTreeMap<String, Totals> = BTreeMap::new();
+        for e in entries {
+            let sell = totals.entry(e.sell_token.to_lowercase()).or_default();
+            sell.sold += parse_amount(&e.executed_sell_amount, "sold", &e.order_uid)?;
+            sell.fees += parse_amount(&e.fee_amount, "fee", &e.order_uid)?;
+            let buy = totals.entry(e.buy_token.to_lowercase()).or_default();
+            buy.bought += parse_amount(&e.executed_buy_amount, "bought", &e.order_uid)?;
+            let surplus = I256::from_dec_str(&e.surplus).map_err(|_| {
+                error::invalid_param(format!(
+                    "surplus '{}' for order {} is not an integer",
+                    e.surplus, e.order_uid
+                ))
+            })?;
+            totals
+                .entry(e.surplus_token.to_lowercase())
+                .or_default()
+                .surplus += surplus;
+        }
+        Ok(totals
+            .into_iter()
+            .map(|(token, t)| TokenTotals {
+                token,
+                sold: t.sold.to_string(),
+                bought: t.bought.to_string(),
+                fees: t.fees.to_string(),
+                surplus: t.surplus.to_string(),
+            })
+            .collect())
+    }
+}
+
+impl TradeReport {
+    /// Build a report by joining orders with their trades
+    ///
+    /// Only orders created within `[from_ts, to_ts]` that have at least one
+    /// trade are included. Trades for other orders are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an amount is not a base-unit integer, a creation
+    /// date cannot be parsed, or an order has a zero limit amount.
+    pub fn build(
+        chain: Chain,
+        owner: &str,
+        from_ts: u64,
+        to_ts: u64,
+        orders: &[Order],
+        trades: &[Trade],
+    ) -> Result<Self> {
+        let mut fills: HashMap<&str, Vec<&Trade>> = HashMap::new();
+        for trade in trades {
+            fills
+                .entry(trade.order_uid.as_str())
+                .or_default()
+                .push(trade);
+        }
+        for order_fills in fills.values_mut() {
+            order_fills.sort_by_key(|t| (t.block_number, t.log_index));
+        }
+
+        let mut dated = Vec::new();
+        for order in orders {
+            let created = created_at(order).ok_or_else(|| {
+                error::invalid_param(format!(
+                    "order {} has invalid creationDate '{}'",
+                    order.uid, order.created_date
+                ))
+            })?;
+            if created < from_ts || created > to_ts {
+                continue;
+            }
+            if let Some(order_fills) = fills.get(order.uid.as_str()) {
+                dated.push((created, order, order_fills.as_slice()));
+            }
+        }
+        dated.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.uid.cmp(&b.1.uid)));
+
+        let mut entries = Vec::with_capacity(dated.len());
+        for (_, order, order_fills) in dated {
+            let entry = OrderFill::aggregate(order, order_fills)?;
+            let surplus = entry.surplus()?;
+            let surplus_token = match order.kind {
+                OrderKind::Sell => &order.buy_token,
+                OrderKind::Buy => &order.sell_token,
+            };
+
+            entries.push(TradeReportEntry {
+                order_uid: order.uid.clone(),
+                kind: order.kind,
+                created_date: order.created_date.clone(),
+                sell_token: order.sell_token.clone(),
+                buy_token: order.buy_token.clone(),
+                fill_count: order_fills.len(),
+                tx_hashes: order_fills.iter().map(|t| t.tx_hash.clone()).collect(),
+                executed_sell_amount: entry.sold.to_string(),
+                executed_buy_amount: entry.bought.to_string(),
+                fee_amount: entry.fee.to_string(),
+                surplus: surplus.to_string(),
+                surplus_token: surplus_token.clone(),
+                app_data: order.app_data.clone(),
+            });
+        }
+
+        Ok(Self {
+            chain,
+            owner: owner.to_string(),
+            from_ts,
+            to_ts,
+            totals: Totals::sum(&entries)?,
+            entries,
+        })
+    }
+
+    /// Distinct app data hashes of the report's orders, lowercase and sorted
+    #[must_use]
+    pub fn app_data_hashes(&self) -> Vec<String> {
+        let mut hashes: Vec<String> = self
+            .entries
+            .iter()
+            .filter_map(|e| e.app_data.as_deref().map(str::to_lowercase))
+            .collect();
+        hashes.sort();
+        hashes.dedup();
+        hashes
+    }
+
+    /// Group the report's volume by app code
+    ///
+    /// `app_codes` maps lowercase app data hashes to app codes (see
+    /// [`crate::Client::app_codes`]). Orders whose hash is missing from the
+    /// map are grouped under `None`, which sorts first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an entry's amounts are not base-unit integers.
+    pub fn volume_by_app_code(
+        &self,
+        app_codes: &HashMap<String, String>,
+    ) -> Result<Vec<AppCodeVolume>> {
+        let mut groups: BTreeMap<Option<&str>, Vec<&TradeReportEntry>> = BTreeMap::new();
+        for e in &self.entries {
+            let app_code = e
+                .app_data
+                .as_ref()
+                .and_then(|hash| app_codes.get(&hash.to_lowercase()))
+                .map(String::as_str);
+            groups.entry(app_code).or_default().push(e);
+        }
+        groups
+            .into_iter()
+            .map(|(app_code, entries)| {
+                Ok(AppCodeVolume {
+                    app_code: app_code.map(str::to_string),
+                    order_count: entries.len(),
+                    totals: Totals::sum(entries)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Render entries as CSV with raw base-unit amounts
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        self.to_csv_with_decimals(&HashMap::new())
+    }
+
+    /// Render entries as CSV, scaling amounts by token decimals
+    ///
+    /// `decimals` is keyed by lowercase token address; amounts in tokens
+    /// without an entry are left in base units.
+    #[must_use]
+    pub fn to_csv_with_decimals(&self, decimals: &HashMap<String, u8>) -> String {
+        let scale = |amount: &str, token: &str| match decimals.get(&token.to_lowercase()) {
+            Some(&d) => format_units(amount, d),
+            None => amount.to_string(),
+        };
+
+        let mut out = String::from(CSV_HEADER);
+        out.push('\n');
+        for e in &self.entries {
+            let kind = match e.kind {
+                OrderKind::Sell => "sell",
+                OrderKind::Buy => "buy",
+            };
+            let row = [
+                e.order_uid.clone(),
+                kind.to_string(),
+                e.created_date.clone(),
+                e.sell_token.clone(),
+                e.buy_token.clone(),
+                e.fill_count.to_string(),
+                scale(&e.executed_sell_amount, &e.sell_token),
+                scale(&e.executed_buy_amount, &e.buy_token),
+                scale(&e.fee_amount, &e.sell_token),
+                scale(&e.surplus, &e.surplus_token),
+                e.surplus_token.clone(),
+                e.tx_hashes.join(";"),
+            ];
+            let row: Vec<Cow<'_, str>> = row.iter().map(|field| csv_field(field)).collect();
+            out.push_str(&row.join(","));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Quote a CSV field holding a comma, quote or line break (RFC 4180)
+fn csv_field(value: &str) -> Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+/// One order's fills summed in base units
+struct OrderFill<'a> {
+    order: &'a Order,
+    sold: U256,
+    bought: U256,
+    fee: U256,
+}
+
+impl<'a> OrderFill<'a> {
+    /// Sum an order's trades
+    ///
+    /// Per-trade fees come from `sellAmount - sellAmountBeforeFees`. If any
+    /// trade lacks `sellAmountBeforeFees`, the order's `executedFeeAmount`
+    /// is used as the total fee instead.
+    fn aggregate(order: &'a Order, trades: &[&Trade]) -> Result<Self> {
+        let mut gross_sold = U256::ZERO;
+        let mut bought = U256::ZERO;
+        let mut trade_fees = Some(U256::ZERO);
+        for trade in trades {
+            let sell = parse_amount(&trade.sell_amount, "sellAmount", &trade.order_uid)?;
+            gross_sold += sell;
+            bought += parse_amount(&trade.buy_amount, "buyAmount", &trade.order_uid)?;
+            trade_fees = match (trade_fees, trade.sell_amount_before_fees.as_deref()) {
+                (Some(total), Some(before)) => {
+                    let before = parse_amount(before, "sellAmountBeforeFees", &trade.order_uid)?;
+                    Some(total + sell.saturating_sub(before))
+                }
+                _ => None,
+            };
+        }
+
+        let fee = match trade_fees {
+            Some(fee) => fee,
+            None => match order.executed_fee_amount.as_deref() {
+                Some(fee) => parse_amount(fee, "executedFeeAmount", &order.uid)?,
+                None => U256::ZERO,
+            },
+        };
+
+        Ok(Self {
+            order,
+            sold: gross_sold.saturating_sub(fee),
+            bought,
+            fee,
+        })
+    }
+
+    /// Surplus over the order's limit price
+    fn surplus(&self) -> Result<I256> {
+        let limit_sell = parse_amount(&self.order.sell_amount, "sellAmount", &self.order.uid)?;
+        let limit_buy = parse_amount(&self.order.buy_amount, "buyAmount", &self.order.uid)?;
+        if limit_sell.is_zero() || limit_buy.is_zero() {
+            return Err(error::invalid_param(format!(
+                "order {} has a zero limit amount",
+                self.order.uid
+            )));
+        }
+
+        let uid = &self.order.uid;
+        let pro_rata = |amount: U256, numerator: U256, denominator: U256| {
+            amount
+                .checked_mul(numerator)
+                .map(|product| product / denominator)
+                .ok_or_else(|| {
+                    error::invalid_param(format!("limit amount for order {uid} overflows"))
+                })
+        };
+        let (actual, limit) = match self.order.kind {
+            // Minimum acceptable buy for what was actually sold
+            OrderKind::Sell => (self.bought, pro_rata(self.sold, limit_buy, limit_sell)?),
+            // Maximum acceptable sell for what was actually bought
+            OrderKind::Buy => (pro_rata(self.bought, limit_sell, limit_buy)?, self.sold),
+        };
+        Ok(signed(actual, uid)? - signed(limit, uid)?)
+    }
+}
+
+/// Convert an amount for surplus arithmetic, rejecting values of 2^255 or more
+fn signed(value: U256, uid: &str) -> Result<I256> {
+    I256::try_from(value).map_err(|_| {
+        error::invalid_param(format!(
+            "amount {value} for order {uid} is too large for a signed surplus"
+        ))
+    })
+}
+
+fn parse_amount(value: &str, field: &str, uid: &str) -> Result<U256> {
+    value.parse::<U256>().map_err(|_| {
+        error::invalid_param(format!(
+            "{field} '{value}' for order {uid} is not an integer"
+        ))
+    })
+}
+
+/// Format a base-unit integer string with `decimals` places
+///
+/// Trailing zeros are trimmed; negative values keep their sign.
+#[must_use]
+pub fn format_units(amount: &str, decimals: u8) -> String {
+    let (sign, digits) = match amount.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", amount),
+    };
+    let decimals = usize::from(decimals);
+    if decimals == 0 {
+        return amount.to_string();
+    }
+    let padded = format!("{digits:0>width$}", width = decimals + 1);
+    let (whole, frac) = padded.split_at(padded.len() - decimals);
+    let frac = frac.trim_end_matches('0');
+    if frac.is_empty() {
+        format!("{sign}{whole}")
+    } else {
+        format!("{sign}{whole}.{frac}")
+    }
+}
+
+/// An order's creation time in Unix seconds
+pub(crate) fn created_at(order: &Order) -> Option<u64> {
+    u64::try_from(parse_iso8601(&order.created_date)?).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::OrderStatus;
+
+    const WETH: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
+    const USDC: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+    const OWNER: &str = "0x9008D19f58AAbD9eD0D60971565AA8510560ab41";
+
+    fn order(uid: &str, kind: OrderKind, sell: &str, buy: &str, created: &str) -> Order {
+        Order {
+            uid: uid.to_string(),
+            sell_token: WETH.to_string(),
+            buy_token: USDC.to_string(),
+            sell_amount: sell.to_string(),
+            buy_amount: buy.to_string(),
+            kind,
+            status: OrderStatus::Fulfilled,
+            created_date: created.to_string(),
+            executed_sell_amount: None,
+            executed_buy_amount: None,
+            executed_fee_amount: None,
+            owner: OWNER.to_string(),
+            receiver: None,
+            app_data: None,
+            full_app_data: None,
+        }
+    }
+
+    fn trade(uid: &str, block: u64, sell: &str, before_fees: Option<&str>, buy: &str) -> Trade {
+        Trade {
+            block_number: block,
+            log_index: 0,
+            order_uid: uid.to_string(),
+            owner: OWNER.to_string(),
+            sell_token: WETH.to_string(),
+            buy_token: USDC.to_string(),
+            sell_amount: sell.to_string(),
+            sell_amount_before_fees: before_fees.map(str::to_string),
+            buy_amount: buy.to_string(),
+            tx_hash: format!("0x{block:064x}"),
+        }
+    }
+
+    #[test]
+    fn test_sell_order_surplus_across_fills() {
+        // Sell 2 WETH for at least 6000 USDC, filled in two halves
+        let orders = [order(
+            "0x01",
+            OrderKind::Sell,
+            "2000000000000000000",
+            "6000000000",
+            "2024-04-02T10:00:00.123456Z",
+        )];
+        let trades = [
+            // Second fill listed first to check ordering
+            trade(
+                "0x01",
+                11,
+                "1000000000000000000",
+                Some("1000000000000000000"),
+                "3050000000",
+            ),
+            trade(
+                "0x01",
+                10,
+                "1010000000000000000",
+                Some("1000000000000000000"),
+                "3020000000",
+            ),
+        ];
+
+        let report = TradeReport::build(
+            Chain::Mainnet,
+            OWNER,
+            1_711_929_600, // 2024-04-01
+            1_719_791_999, // 2024-06-30
+            &orders,
+            &trades,
+        )
+        .unwrap();
+
+        assert_eq!(report.entries.len(), 1);
+        let entry = &report.entries[0];
+        assert_eq!(entry.fill_count, 2);
+        assert_eq!(entry.tx_hashes[0], format!("0x{:064x}", 10));
+        assert_eq!(entry.executed_sell_amount, "2000000000000000000");
+        assert_eq!(entry.executed_buy_amount, "6070000000");
+        assert_eq!(entry.fee_amount, "10000000000000000");
+        // Limit for 2 WETH is 6000 USDC; received 6070
+        assert_eq!(entry.surplus, "70000000");
+        assert_eq!(entry.surplus_token, USDC);
+
+        let usdc = report
+            .totals
+            .iter()
+            .find(|t| t.token == USDC.to_lowercase());
+        assert_eq!(usdc.unwrap().surplus, "70000000");
+        let weth = report
+            .totals
+            .iter()
+            .find(|t| t.token == WETH.to_lowercase());
+        assert_eq!(weth.unwrap().fees, "10000000000000000");
+    }
+
+    #[test]
+    fn test_sell_order_partial_fill_uses_pro_rata_limit() {
+        // Half filled: limit applies to the half actually sold
+        let orders = [order(
+            "0x02",
+            OrderKind::Sell,
+            "2000000000000000000",
+            "6000000000",
+            "2024-05-01T00:00:00Z",
+        )];
+        let trades = [trade(
+            "0x02",
+            20,
+            "1000000000000000000",
+            Some("1000000000000000000"),
+            "2990000000",
+        )];
+
+        let report =
+            TradeReport::build(Chain::Mainnet, OWNER, 0, u64::MAX, &orders, &trades).unwrap();
+        // Limit for 1 WETH is 3000 USDC; received 2990
+        assert_eq!(report.entries[0].surplus, "-10000000");
+    }
+
+    #[test]
+    fn test_buy_order_surplus_across_fills() {
+        // Buy 6000 USDC paying at most 2.1 WETH, filled in two parts
+        let orders = [order(
+            "0x03",
+            OrderKind::Buy,
+            "2100000000000000000",
+            "6000000000",
+            "2024-05-01T00:00:00+02:00",
+        )];
+        let trades = [
+            trade("0x03", 30, "1000000000000000000", None, "4000000000"),
+            trade("0x03", 31, "520000000000000000", None, "2000000000"),
+        ];
+        let mut orders = orders;
+        orders[0].executed_fee_amount = Some("20000000000000000".to_string());
+
+        let report =
+            TradeReport::build(Chain::Mainnet, OWNER, 0, u64::MAX, &orders, &trades).unwrap();
+        let entry = &report.entries[0];
+        assert_eq!(entry.fill_count, 2);
+        // Fee falls back to the order's executedFeeAmount
+        assert_eq!(entry.fee_amount, "20000000000000000");
+        assert_eq!(entry.executed_sell_amount, "1500000000000000000");
+        assert_eq!(entry.executed_buy_amount, "6000000000");
+        // Limit for 6000 USDC is 2.1 WETH; paid 1.5 WETH net of fees
+        assert_eq!(entry.surplus, "600000000000000000");
+        assert_eq!(entry.surplus_token, WETH);
+    }
+
+    #[test]
+    fn test_window_and_join() {
+        let orders = [
+            order("0x04", OrderKind::Sell, "1", "1", "2023-12-31T23:59:59Z"),
+            order("0x05", OrderKind::Sell, "1", "1", "2024-01-01T00:00:00Z"),
+            order("0x06", OrderKind::Sell, "1", "1", "2024-01-02T00:00:00Z"),
+        ];
+        let trades = [
+            trade("0x04", 1, "1", Some("1"), "1"),
+            trade("0x05", 2, "1", Some("1"), "1"),
+            trade("0xff", 3, "1", Some("1"), "1"),
+        ];
+
+        let report = TradeReport::build(
+            Chain::Mainnet,
+            OWNER,
+            1_704_067_200, // 2024-01-01T00:00:00Z
+            1_704_153_600,
+            &orders,
+            &trades,
+        )
+        .unwrap();
+        // 0x04 is before the window; 0x06 has no trades; 0xff has no order
+        let uids: Vec<&str> = report
+            .entries
+            .iter()
+            .map(|e| e.order_uid.as_str())
+            .collect();
+        assert_eq!(uids, ["0x05"]);
+    }
+
+    #[test]
+    fn test_invalid_amount_is_error() {
+        let orders = [order(
+            "0x07",
+            OrderKind::Sell,
+            "1.5",
+            "1",
+            "2024-01-01T00:00:00Z",
+        )];
+        let trades = [trade("0x07", 1, "1", Some("1"), "1")];
+        assert!(TradeReport::build(Chain::Mainnet, OWNER, 0, u64::MAX, &orders, &trades).is_err());
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let orders = [order(
+            "0x08",
+            OrderKind::Sell,
+            "1000000000000000000",
+            "3000000000",
+            "2024-01-01T00:00:00Z",
+        )];
+        let trades = [trade(
+            "0x08",
+            1,
+            "1005000000000000000",
+            Some("1000000000000000000"),
+            "3012500000",
+        )];
+        let report =
+            TradeReport::build(Chain::Mainnet, OWNER, 0, u64::MAX, &orders, &trades).unwrap();
+
+        let raw = report.to_csv();
+        let mut lines = raw.lines();
+        assert_eq!(lines.next(), Some(CSV_HEADER));
+        assert!(lines
+            .next()
+            .unwrap()
+            .contains(",1000000000000000000,3012500000,5000000000000000,12500000,"));
+
+        let decimals = HashMap::from([(WETH.to_lowercase(), 18), (USDC.to_lowercase(), 6)]);
+        let scaled = report.to_csv_with_decimals(&decimals);
+        assert!(scaled
+            .lines()
+            .nth(1)
+            .unwrap()
+            .contains(",1,3012.5,0.005,12.5,"));
+    }
+
+    #[test]
+    fn test_to_csv_escapes_fields() {
+        let orders = [order(
+            "0x0d",
+            OrderKind::Sell,
+            "1",
+            "1",
+            "2024-01-01T00:00:00Z",
+        )];
+        let trades = [trade("0x0d", 1, "1", Some("1"), "1")];
+        let mut report =
+            TradeReport::build(Chain::Mainnet, OWNER, 0, u64::MAX, &orders, &trades).unwrap();
+        report.entries[0].created_date = "Jan 1, 2024 \"noon\"".to_string();
+
+        let csv = report.to_csv();
+        let row = csv.lines().nth(1).unwrap();
+        assert!(
+            row.starts_with("0x0d,sell,\"Jan 1, 2024 \"\"noon\"\"\","),
+            "{row}"
+        );
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a\nb"), "\"a\nb\"");
+    }
+
+    #[test]
+    fn test_amount_over_i256_is_error() {
+        // 2^255 bought for a limit of 1: the surplus can't be signed
+        let huge = (U256::from(1u8) << 255usize).to_string();
+        let orders = [order(
+            "0x0e",
+            OrderKind::Sell,
+            "1",
+            "1",
+            "2024-01-01T00:00:00Z",
+        )];
+        let trades = [trade("0x0e", 1, "1", Some("1"), &huge)];
+        let err =
+            TradeReport::build(Chain::Mainnet, OWNER, 0, u64::MAX, &orders, &trades).unwrap_err();
+        assert!(err.to_string().contains("too large"), "{err}");
+
+        // The pro-rata limit overflows before any conversion
+        let orders = [order(
+            "0x0f",
+            OrderKind::Sell,
+            "1",
+            &huge,
+            "2024-01-01T00:00:00Z",
+        )];
+        let trades = [trade("0x0f", 1, "4", Some("4"), "1")];
+        let err =
+            TradeReport::build(Chain::Mainnet, OWNER, 0, u64::MAX, &orders, &trades).unwrap_err();
+        assert!(err.to_string().contains("overflows"), "{err}");
+    }
+
+    #[test]
+    fn test_volume_by_app_code() {
+        let hash = |n: u8| format!("0x{n:064x}");
+        let mut orders = [
+            order("0x09", OrderKind::Sell, "2", "2", "2024-01-01T00:00:00Z"),
+            order("0x0a", OrderKind::Sell, "3", "3", "2024-01-02T00:00:00Z"),
+            order("0x0b", OrderKind::Sell, "5", "5", "2024-01-03T00:00:00Z"),
+            order("0x0c", OrderKind::Sell, "7", "7", "2024-01-04T00:00:00Z"),
+        ];
+        orders[0].app_data = Some(hash(1));
+        orders[1].app_data = Some(hash(1).to_uppercase().replace("0X", "0x"));
+        orders[2].app_data = Some(hash(2));
+        let trades = [
+            trade("0x09", 1, "2", Some("2"), "2"),
+            trade("0x0a", 2, "3", Some("3"), "4"),
+            trade("0x0b", 3, "5", Some("5"), "5"),
+            trade("0x0c", 4, "7", Some("7"), "7"),
+        ];
+        let report =
+            TradeReport::build(Chain::Mainnet, OWNER, 0, u64::MAX, &orders, &trades).unwrap();
+        assert_eq!(report.app_data_hashes(), vec![hash(1), hash(2)]);
+
+        // hash(2) isn't registered; 0x0c has no app data
+        let app_codes = HashMap::from([(hash(1), "Yield Desk".to_string())]);
+        let volume = report.volume_by_app_code(&app_codes).unwrap();
+        assert_eq!(volume.len(), 2);
+        assert_eq!(volume[0].app_code, None);
+        assert_eq!(volume[0].order_count, 2);
+        assert_eq!(volume[1].app_code.as_deref(), Some("Yield Desk"));
+        assert_eq!(volume[1].order_count, 2);
+
+        let usdc = |v: &AppCodeVolume| {
+            v.totals
+                .iter()
+                .find(|t| t.token == USDC.to_lowercase())
+                .cloned()
+                .unwrap()
+        };
+        let weth = |v: &AppCodeVolume| {
+            v.totals
+                .iter()
+                .find(|t| t.token == WETH.to_lowercase())
+                .cloned()
+                .unwrap()
+        };
+        assert_eq!(weth(&volume[1]).sold, "5");
+        assert_eq!(usdc(&volume[1]).bought, "6");
+        assert_eq!(usdc(&volume[1]).surplus, "1");
+        assert_eq!(weth(&volume[0]).sold, "12");
+
+        // Per-group totals add up to the report's
+        let all = report.volume_by_app_code(&HashMap::new()).unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].totals, report.totals);
+    }
+
+    #[test]
+    fn test_format_units() {
+        assert_eq!(format_units("1500000", 6), "1.5");
+        assert_eq!(format_units("-10000000", 6), "-10");
+        assert_eq!(format_units("5", 3), "0.005");
+        assert_eq!(format_units("0", 18), "0");
+        assert_eq!(format_units("42", 0), "42");
+    }
+}
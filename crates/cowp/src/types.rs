@@ -1,5 +1,6 @@
 //! Types for the `CoW` Protocol API responses
 
+use crate::report::format_units;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::str::FromStr;
@@ -73,6 +74,26 @@ impl Chain {
         s.parse().ok()
     }
 
+    /// Symbol of the chain's native currency
+    #[must_use]
+    pub fn native_symbol(&self) -> &'static str {
+        match self {
+            Chain::Gnosis => "xDAI",
+            Chain::Mainnet | Chain::Arbitrum | Chain::Sepolia => "ETH",
+        }
+    }
+
+    /// Wrapped native token (address, symbol)
+    #[must_use]
+    pub fn wrapped_native(&self) -> (&'static str, &'static str) {
+        match self {
+            Chain::Mainnet => ("0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2", "WETH"),
+            Chain::Gnosis => ("0xe91d153e0b41518a2ce8dd3d7944fa863463a97d", "WXDAI"),
+            Chain::Arbitrum => ("0x82af49447d8a07e3bd95bd0d56f35241523fbab1", "WETH"),
+            Chain::Sepolia => ("0xfff9976782d46cc05630d1f6ebab18b2324d6b14", "WETH"),
+        }
+    }
+
     /// Get chain name
     #[must_use]
     pub fn as_str(&self) -> &'static str {
@@ -132,6 +153,65 @@ impl From<Chain> for yldfi_common::Chain {
     }
 }
 
+/// Address `CoW` Protocol uses for the chain's native currency as a buy token
+pub const NATIVE_TOKEN: &str = "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE";
+
+/// Whether `token` is the native currency placeholder ([`NATIVE_TOKEN`])
+#[must_use]
+pub fn is_native_token(token: &str) -> bool {
+    token.eq_ignore_ascii_case(NATIVE_TOKEN)
+}
+
+/// Token symbol and decimals
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenInfo {
+    /// Token address, lowercase ([`NATIVE_TOKEN`] for the native currency)
+    pub address: String,
+    /// Token symbol (empty if the token doesn't expose one)
+    pub symbol: String,
+    /// Token decimals
+    pub decimals: u8,
+    /// Whether this is the native currency rather than an ERC-20
+    pub is_native: bool,
+}
+
+impl TokenInfo {
+    /// The chain's native currency (18 decimals)
+    #[must_use]
+    pub fn native(chain: Chain) -> Self {
+        Self {
+            address: NATIVE_TOKEN.to_lowercase(),
+            symbol: chain.native_symbol().to_string(),
+            decimals: 18,
+            is_native: true,
+        }
+    }
+
+    /// The chain's wrapped native token (18 decimals)
+    #[must_use]
+    pub fn wrapped_native(chain: Chain) -> Self {
+        let (address, symbol) = chain.wrapped_native();
+        Self {
+            address: address.to_string(),
+            symbol: symbol.to_string(),
+            decimals: 18,
+            is_native: false,
+        }
+    }
+
+    /// Token info known without a network call (native and wrapped native)
+    #[must_use]
+    pub fn well_known(chain: Chain, token: &str) -> Option<Self> {
+        if is_native_token(token) {
+            Some(Self::native(chain))
+        } else if token.eq_ignore_ascii_case(chain.wrapped_native().0) {
+            Some(Self::wrapped_native(chain))
+        } else {
+            None
+        }
+    }
+}
+
 /// Order kind (buy or sell)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -345,6 +425,72 @@ pub struct QuoteResponse {
     pub verified: bool,
 }
 
+impl QuoteResponse {
+    /// Quote amounts as decimal strings
+    ///
+    /// The fee is paid in the sell token.
+    #[must_use]
+    pub fn human_amounts(&self, sell_decimals: u8, buy_decimals: u8) -> HumanAmounts {
+        HumanAmounts {
+            sell_amount: format_units(&self.quote.sell_amount, sell_decimals),
+            buy_amount: format_units(&self.quote.buy_amount, buy_decimals),
+            fee_amount: format_units(&self.quote.fee_amount, sell_decimals),
+        }
+    }
+
+    /// Compare the quote against `CoW`'s native prices
+    ///
+    /// Prices are native-currency wei per token base unit, as returned by
+    /// the native price endpoint. The sell side includes the fee.
+    #[must_use]
+    pub fn sanity(&self, sell_native_price: f64, buy_native_price: f64) -> Option<QuoteSanity> {
+        let sell: f64 = self.quote.sell_amount.parse().ok()?;
+        let fee: f64 = self.quote.fee_amount.parse().ok()?;
+        let buy: f64 = self.quote.buy_amount.parse().ok()?;
+
+        let sell_value_native = (sell + fee) * sell_native_price;
+        let buy_value_native = buy * buy_native_price;
+        if !(sell_value_native > 0.0 && buy_value_native.is_finite()) {
+            return None;
+        }
+        Some(QuoteSanity {
+            sell_value_native,
+            buy_value_native,
+            deviation: buy_value_native / sell_value_native - 1.0,
+        })
+    }
+}
+
+/// Quote amounts formatted with token decimals
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HumanAmounts {
+    /// Sell amount (excluding fee)
+    pub sell_amount: String,
+    /// Buy amount
+    pub buy_amount: String,
+    /// Fee, in the sell token
+    pub fee_amount: String,
+}
+
+/// Value of a quote's two sides in native-currency wei
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct QuoteSanity {
+    /// Value of the sell amount plus fee
+    pub sell_value_native: f64,
+    /// Value of the buy amount
+    pub buy_value_native: f64,
+    /// `buy / sell - 1`; `-0.05` means the quote returns 5% less value than it takes
+    pub deviation: f64,
+}
+
+impl QuoteSanity {
+    /// Whether the quote loses more than `max_loss` (e.g. `0.05` for 5%)
+    #[must_use]
+    pub fn exceeds_loss(&self, max_loss: f64) -> bool {
+        self.deviation < -max_loss
+    }
+}
+
 /// Quote details
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -457,6 +603,12 @@ pub struct Order {
     /// Receiver address
     #[serde(default)]
     pub receiver: Option<String>,
+    /// App data hash (see [`crate::AppDataDocument`])
+    #[serde(default)]
+    pub app_data: Option<String>,
+    /// App data document JSON, when the API has it
+    #[serde(default)]
+    pub full_app_data: Option<String>,
 }
 
 /// Order status
@@ -502,10 +654,78 @@ pub struct Trade {
     pub sell_token: String,
     /// Token bought
     pub buy_token: String,
-    /// Amount sold
+    /// Amount sold, including fees
     pub sell_amount: String,
+    /// Amount sold, excluding fees
+    #[serde(default)]
+    pub sell_amount_before_fees: Option<String>,
     /// Amount bought
     pub buy_amount: String,
     /// Transaction hash
     pub tx_hash: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(sell: &str, fee: &str, buy: &str) -> QuoteResponse {
+        serde_json::from_value(serde_json::json!({
+            "quote": {
+                "sellToken": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+                "buyToken": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+                "sellAmount": sell,
+                "buyAmount": buy,
+                "validTo": 0,
+                "appData": "0x",
+                "feeAmount": fee,
+                "kind": "sell",
+                "partiallyFillable": false
+            },
+            "from": "0x0000000000000000000000000000000000000001",
+            "expiration": "2024-01-01T00:00:00Z"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_human_amounts() {
+        let q = quote("999000000000000000", "1000000000000000", "3250120000");
+        let amounts = q.human_amounts(18, 6);
+        assert_eq!(amounts.sell_amount, "0.999");
+        assert_eq!(amounts.fee_amount, "0.001");
+        assert_eq!(amounts.buy_amount, "3250.12");
+    }
+
+    #[test]
+    fn test_quote_sanity() {
+        // 1 WETH in (incl. fee) for 3000 USDC, USDC valued at 1/3000 ETH per unit
+        let q = quote("990000000000000000", "10000000000000000", "2940000000");
+        let usdc_price = 1e18 / 3000.0 / 1e6;
+        let sanity = q.sanity(1.0, usdc_price).unwrap();
+        assert!((sanity.deviation - (-0.02)).abs() < 1e-9);
+        assert!(sanity.exceeds_loss(0.01));
+        assert!(!sanity.exceeds_loss(0.05));
+
+        assert!(q.sanity(0.0, usdc_price).is_none());
+    }
+
+    #[test]
+    fn test_well_known_tokens() {
+        let native = TokenInfo::well_known(Chain::Mainnet, NATIVE_TOKEN).unwrap();
+        assert!(native.is_native);
+        assert_eq!(native.symbol, "ETH");
+
+        let weth =
+            TokenInfo::well_known(Chain::Mainnet, "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2")
+                .unwrap();
+        assert!(!weth.is_native);
+        assert_eq!(weth.symbol, "WETH");
+
+        // WETH's mainnet address isn't wrapped native on Gnosis
+        assert!(
+            TokenInfo::well_known(Chain::Gnosis, "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2")
+                .is_none()
+        );
+    }
+}
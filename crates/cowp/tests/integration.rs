@@ -3,7 +3,11 @@
 //! These tests verify the client correctly handles API responses without
 //! making actual network calls.
 
-use cowp::{Chain, Client, Config, QuoteRequest};
+use cowp::error::is_app_data_not_found;
+use cowp::{Chain, Client, Config, QuoteRequest, Trade, NATIVE_TOKEN};
+use serde_json::json;
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
 
 #[tokio::test]
 async fn test_client_creation() {
@@ -72,6 +76,268 @@ async fn test_chain_from_str_invalid() {
     assert!(result.is_err());
 }
 
-// Note: Full HTTP integration tests would require modifications
-// to cowp's Client to accept a custom base URL. The tests above verify
-// the core functionality that can be tested without mocking HTTP.
+#[tokio::test]
+async fn test_get_token_info_via_rpc_is_cached() {
+    let rpc = MockServer::start().await;
+    let usdc = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+
+    Mock::given(method("POST"))
+        .and(body_partial_json(
+            json!({ "params": [{ "data": "0x313ce567" }] }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0", "id": 1,
+            "result": format!("0x{:0>64}", "06"),
+        })))
+        .expect(1)
+        .mount(&rpc)
+        .await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(
+            json!({ "params": [{ "data": "0x95d89b41" }] }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0", "id": 1,
+            "result": format!("0x{:0>64}{:0>64}{:0<64}", "20", "04", "55534443"),
+        })))
+        .expect(1)
+        .mount(&rpc)
+        .await;
+
+    let client =
+        Client::with_config(Config::new().with_rpc_url(Chain::Mainnet, rpc.uri())).unwrap();
+    let info = client.get_token_info(None, usdc).await.unwrap();
+    assert_eq!(info.symbol, "USDC");
+    assert_eq!(info.decimals, 6);
+    assert!(!info.is_native);
+
+    // Second lookup (any case, and through a clone) is served from the cache
+    let again = client
+        .clone()
+        .get_token_info(Some(Chain::Mainnet), &usdc.to_lowercase())
+        .await
+        .unwrap();
+    assert_eq!(again, info);
+}
+
+#[tokio::test]
+async fn test_get_token_info_native_and_wrapped_without_rpc() {
+    let client = Client::with_config(Config::new().with_chain(Chain::Gnosis)).unwrap();
+
+    let native = client.get_token_info(None, NATIVE_TOKEN).await.unwrap();
+    assert!(native.is_native);
+    assert_eq!(native.symbol, "xDAI");
+    assert_eq!(native.decimals, 18);
+
+    let wrapped = client
+        .get_token_info(None, "0xe91D153E0b41518A2Ce8Dd3D7944Fa863463a97d")
+        .await
+        .unwrap();
+    assert!(!wrapped.is_native);
+    assert_eq!(wrapped.symbol, "WXDAI");
+
+    // Anything else needs an RPC endpoint
+    let err = client
+        .get_token_info(None, "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("no RPC URL configured for gnosis"));
+}
+
+#[tokio::test]
+async fn test_get_token_info_rpc_error() {
+    let rpc = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0", "id": 1,
+            "error": { "code": -32000, "message": "execution reverted" },
+        })))
+        .mount(&rpc)
+        .await;
+
+    let client =
+        Client::with_config(Config::new().with_rpc_url(Chain::Mainnet, rpc.uri())).unwrap();
+    let err = client
+        .get_token_info(None, "0x0000000000000000000000000000000000000001")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("execution reverted"));
+}
+
+const PARTNER_FEE_APP_DATA: &str = include_str!("fixtures/app_data/partner_fee_v1_1.json");
+
+/// Client whose mainnet API requests go to the mock server
+fn api_client(server: &MockServer) -> Client {
+    Client::with_config(Config::new().with_api_url(Chain::Mainnet, server.uri())).unwrap()
+}
+
+fn app_data_hash(byte: &str) -> String {
+    format!("0x{}", byte.repeat(32))
+}
+
+fn order(uid: &str, app_data: &str, full_app_data: Option<&str>) -> serde_json::Value {
+    json!({
+        "uid": uid,
+        "sellToken": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+        "buyToken": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+        "sellAmount": "1000000000000000000",
+        "buyAmount": "3000000000",
+        "kind": "sell",
+        "status": "fulfilled",
+        "createdDate": "2024-01-01T00:00:00Z",
+        "owner": "0x00000000000000000000000000000000000000a1",
+        "appData": app_data,
+        "fullAppData": full_app_data
+    })
+}
+
+fn trade(uid: &str) -> Trade {
+    serde_json::from_value(json!({
+        "blockNumber": 19000000,
+        "logIndex": 3,
+        "orderUid": uid,
+        "owner": "0x00000000000000000000000000000000000000a1",
+        "sellToken": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+        "buyToken": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+        "sellAmount": "1000000000000000000",
+        "buyAmount": "3000000000",
+        "txHash": "0xdead"
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_get_app_data_not_found_is_cached() {
+    let server = MockServer::start().await;
+    let hash = app_data_hash("ab");
+    Mock::given(method("GET"))
+        .and(path(format!("/api/v1/app_data/{hash}")))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+            "errorType": "NotFound", "description": "app data not found"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api_client(&server);
+    let err = client.get_app_data(None, &hash).await.unwrap_err();
+    assert!(is_app_data_not_found(&err), "{err:?}");
+    // The negative lookup is answered from the cache, in any case
+    let err = client
+        .get_app_data(
+            Some(Chain::Mainnet),
+            &hash.to_uppercase().replace("0X", "0x"),
+        )
+        .await
+        .unwrap_err();
+    assert!(is_app_data_not_found(&err), "{err:?}");
+}
+
+#[tokio::test]
+async fn test_get_app_data_document_is_cached() {
+    let server = MockServer::start().await;
+    let hash = app_data_hash("cd");
+    Mock::given(method("GET"))
+        .and(path(format!("/api/v1/app_data/{hash}")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "fullAppData": PARTNER_FEE_APP_DATA
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api_client(&server);
+    let document = client.get_app_data(None, &hash).await.unwrap();
+    assert_eq!(document.app_code.as_deref(), Some("Yield Desk"));
+    let again = client.clone().get_app_data(None, &hash).await.unwrap();
+    assert_eq!(again, document);
+}
+
+#[tokio::test]
+async fn test_resolve_app_data_uses_embedded_document() {
+    let server = MockServer::start().await;
+    let hash = app_data_hash("ef");
+    Mock::given(method("GET"))
+        .and(path("/api/v1/orders/0xorder1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(order(
+            "0xorder1",
+            &hash,
+            Some(PARTNER_FEE_APP_DATA),
+        )))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/api/v1/app_data/{hash}")))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let client = api_client(&server);
+    let document = trade("0xorder1")
+        .resolve_app_data(&client, None)
+        .await
+        .unwrap();
+    assert_eq!(document.app_code.as_deref(), Some("Yield Desk"));
+    // The embedded document also fills the cache
+    let cached = client.get_app_data(None, &hash).await.unwrap();
+    assert_eq!(cached, document);
+}
+
+#[tokio::test]
+async fn test_resolve_app_data_falls_back_to_lookup() {
+    let server = MockServer::start().await;
+    let hash = app_data_hash("12");
+    Mock::given(method("GET"))
+        .and(path("/api/v1/orders/0xorder2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(order("0xorder2", &hash, None)))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/api/v1/app_data/{hash}")))
+        .respond_with(ResponseTemplate::new(404))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api_client(&server);
+    let err = trade("0xorder2")
+        .resolve_app_data(&client, None)
+        .await
+        .unwrap_err();
+    assert!(is_app_data_not_found(&err), "{err:?}");
+}
+
+#[tokio::test]
+async fn test_trade_report_order_paging_is_bounded() {
+    let server = MockServer::start().await;
+    // Every page is full and newer than the window start
+    let page: Vec<_> = (0..1000)
+        .map(|i| order(&format!("0x{i:04x}"), &app_data_hash("00"), None))
+        .collect();
+    Mock::given(method("GET"))
+        .and(path(
+            "/api/v1/account/0x00000000000000000000000000000000000000a1/orders",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page))
+        .expect(u64::from(cowp::MAX_REPORT_PAGES))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v2/trades"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let err = api_client(&server)
+        .trade_report(
+            None,
+            "0x00000000000000000000000000000000000000a1",
+            1_700_000_000,
+            1_800_000_000,
+        )
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("more than 50000 orders"), "{err}");
+}
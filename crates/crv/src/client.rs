@@ -7,12 +7,15 @@ use std::time::Duration;
 use yldfi_common::http::HttpClientConfig;
 
 const DEFAULT_BASE_URL: &str = "https://api.curve.finance/v1";
+const DEFAULT_PRICES_BASE_URL: &str = "https://prices.curve.finance/v1";
 
 /// Configuration for the Curve API client
 #[derive(Debug, Clone)]
 pub struct Config {
     /// Base URL for the API
     pub base_url: String,
+    /// Base URL for the Prices API, used for per-user lending data
+    pub prices_base_url: String,
     /// HTTP client configuration (timeout, proxy, user-agent)
     pub http: HttpClientConfig,
 }
@@ -21,6 +24,7 @@ impl Default for Config {
     fn default() -> Self {
         Self {
             base_url: DEFAULT_BASE_URL.to_string(),
+            prices_base_url: DEFAULT_PRICES_BASE_URL.to_string(),
             http: HttpClientConfig::default(),
         }
     }
@@ -40,6 +44,13 @@ impl Config {
         self
     }
 
+    /// Set a custom Prices API base URL
+    #[must_use]
+    pub fn with_prices_base_url(mut self, url: impl Into<String>) -> Self {
+        self.prices_base_url = url.into();
+        self
+    }
+
     /// Set a custom timeout
     #[must_use]
     pub fn with_timeout(mut self, timeout: Duration) -> Self {
@@ -67,6 +78,7 @@ impl Config {
 pub struct Client {
     http: HttpClient,
     base_url: String,
+    prices_base_url: String,
 }
 
 impl Client {
@@ -82,13 +94,31 @@ impl Client {
         Ok(Self {
             http,
             base_url: config.base_url,
+            prices_base_url: config.prices_base_url,
         })
     }
 
     /// Make a GET request to the API
     pub(crate) async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
         let url = format!("{}{}", self.base_url, path);
-        let response = self.http.get(&url).send().await?;
+        self.get_url(&url).await
+    }
+
+    /// Make a GET request to the Prices API, mapping 404 to `None`
+    pub(crate) async fn get_prices_optional<T: DeserializeOwned>(
+        &self,
+        path: &str,
+    ) -> Result<Option<T>> {
+        let url = format!("{}{}", self.prices_base_url, path);
+        match self.get_url(&url).await {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.status_code() == Some(404) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn get_url<T: DeserializeOwned>(&self, url: &str) -> Result<T> {
+        let response = self.http.get(url).send().await?;
 
         let status = response.status().as_u16();
 
@@ -1,6 +1,6 @@
 //! Lending API client
 
-use super::types::LendingVaultsResponse;
+use super::types::{LendingPosition, LendingUserStats, LendingVaultsResponse};
 use crate::client::Client;
 use crate::error::Result;
 
@@ -32,4 +32,19 @@ impl<'a> LendingApi<'a> {
     pub async fn get_all(&self) -> Result<LendingVaultsResponse> {
         self.client.get("/getLendingVaults/all").await
     }
+
+    /// Get a user's borrow position in a lending market
+    ///
+    /// Reads per-user stats from the Prices API for the market's `controller`.
+    /// Returns `None` if the user has no open position.
+    pub async fn user_position(
+        &self,
+        chain: &str,
+        controller: &str,
+        user: &str,
+    ) -> Result<Option<LendingPosition>> {
+        let path = format!("/lending/users/{chain}/{controller}/{user}/stats");
+        let stats: Option<LendingUserStats> = self.client.get_prices_optional(&path).await?;
+        Ok(stats.as_ref().and_then(LendingPosition::from_stats))
+    }
 }
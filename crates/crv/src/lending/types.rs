@@ -58,3 +58,124 @@ pub struct Token {
     /// Token decimals
     pub decimals: Option<u8>,
 }
+
+/// Per-user loan stats from the Prices API
+///
+/// Every field is optional; the API omits values it cannot compute.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LendingUserStats {
+    /// Health in percent (hard liquidation at 0)
+    pub health: Option<f64>,
+    /// Health in percent, counting collateral above the bands at market price
+    pub health_full: Option<f64>,
+    /// Number of bands the collateral is spread over
+    pub n: Option<i64>,
+    /// Top band
+    pub n1: Option<i64>,
+    /// Bottom band
+    pub n2: Option<i64>,
+    /// Outstanding debt in borrowed token units
+    pub debt: Option<f64>,
+    /// Collateral in collateral token units
+    pub collateral: Option<f64>,
+    /// Borrowed token held in the bands (non-zero in soft liquidation)
+    pub borrowed: Option<f64>,
+    /// Whether the position is in soft liquidation
+    pub soft_liquidation: Option<bool>,
+    /// Current oracle price of the collateral in borrowed token units
+    pub oracle_price: Option<f64>,
+}
+
+/// Band range a position's collateral is deposited in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LendingBands {
+    /// Top band
+    pub n1: i64,
+    /// Bottom band
+    pub n2: i64,
+}
+
+/// A user's borrow position in a lending market
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LendingPosition {
+    /// Collateral in collateral token units
+    pub collateral: f64,
+    /// Outstanding debt in borrowed token units
+    pub debt: f64,
+    /// Health in percent, if reported
+    pub health: Option<f64>,
+    /// Estimated collateral price at which health reaches zero
+    pub liquidation_price: Option<f64>,
+    /// Band range, if reported
+    pub bands: Option<LendingBands>,
+    /// Whether the position is in soft liquidation, if reported
+    pub soft_liquidation: Option<bool>,
+}
+
+impl LendingPosition {
+    /// Build a position from user stats
+    ///
+    /// Returns `None` when the user has neither collateral nor debt. The
+    /// liquidation price is a linear estimate, `oracle_price / (1 + health)`,
+    /// and ignores how collateral converts across bands on the way down.
+    #[must_use]
+    pub fn from_stats(stats: &LendingUserStats) -> Option<Self> {
+        let collateral = stats.collateral.unwrap_or(0.0);
+        let debt = stats.debt.unwrap_or(0.0);
+        if collateral <= 0.0 && debt <= 0.0 {
+            return None;
+        }
+        let liquidation_price = match (stats.oracle_price, stats.health) {
+            (Some(price), Some(health)) if debt > 0.0 && health > -100.0 => {
+                Some(price / (1.0 + health / 100.0))
+            }
+            _ => None,
+        };
+        let bands = match (stats.n1, stats.n2) {
+            (Some(n1), Some(n2)) => Some(LendingBands { n1, n2 }),
+            _ => None,
+        };
+        Some(Self {
+            collateral,
+            debt,
+            health: stats.health,
+            liquidation_price,
+            bands,
+            soft_liquidation: stats.soft_liquidation,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_from_stats() {
+        let stats: LendingUserStats = serde_json::from_value(serde_json::json!({
+            "health": 25.0,
+            "n1": 10,
+            "n2": 13,
+            "debt": 1000.0,
+            "collateral": 1.0,
+            "soft_liquidation": false,
+            "oracle_price": 2500.0,
+            "last_updated": "2024-01-01T00:00:00"
+        }))
+        .unwrap();
+        let position = LendingPosition::from_stats(&stats).unwrap();
+        assert_eq!(position.bands, Some(LendingBands { n1: 10, n2: 13 }));
+        assert_eq!(position.liquidation_price, Some(2000.0));
+
+        let partial = LendingUserStats {
+            collateral: Some(1.0),
+            ..Default::default()
+        };
+        let position = LendingPosition::from_stats(&partial).unwrap();
+        assert_eq!(position.health, None);
+        assert_eq!(position.liquidation_price, None);
+        assert_eq!(position.bands, None);
+
+        assert!(LendingPosition::from_stats(&LendingUserStats::default()).is_none());
+    }
+}
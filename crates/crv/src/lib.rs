@@ -66,7 +66,7 @@ pub fn default_config() -> Config {
 }
 
 // Re-export common types
-pub use lending::{LendingVault, LendingVaultsResponse};
+pub use lending::{LendingPosition, LendingVault, LendingVaultsResponse};
 pub use pools::{Pool, PoolsResponse};
 pub use router::{Route, RouteGraph, RouterApi, RouterStats};
 pub use tokens::{TokenInfo, TokensResponse};
@@ -129,4 +129,6 @@ impl Client {
 }
 
 // Prices API client (separate base URL)
-pub use prices::PricesClient;
+pub use prices::{
+    PriceDetail, PriceSource, PricesClient, Proposal, ProposalStatus, ProposalType, UserLocks,
+};
@@ -3,10 +3,14 @@
 //! Client for the Curve Prices API at `prices.curve.finance`
 //! Provides detailed pricing, OHLC, trades, and DAO data.
 
+mod types;
+
+pub use types::*;
+
 use crate::error::{Error, Result};
 use reqwest::Client as HttpClient;
 use serde::de::DeserializeOwned;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 const PRICES_BASE_URL: &str = "https://prices.curve.finance/v1";
 const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
@@ -79,6 +83,32 @@ impl PricesClient {
         self.get(&path).await
     }
 
+    /// Get USD price for a token with its update time and source
+    ///
+    /// Use [`PriceDetail::is_stale`] to reject old prices before valuing
+    /// positions with them.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # async fn example() -> crv::Result<()> {
+    /// use std::time::Duration;
+    ///
+    /// let client = crv::PricesClient::new()?;
+    /// let price = client
+    ///     .get_usd_price_detailed("ethereum", "0xD533a949740bb3306d119CC777fa900bA034cd52")
+    ///     .await?;
+    /// if price.is_stale(Duration::from_secs(600)) {
+    ///     println!("price too old or undated ({:?})", price.source);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_usd_price_detailed(&self, chain: &str, address: &str) -> Result<PriceDetail> {
+        let path = format!("/usd_price/{chain}/{address}");
+        let response: UsdPriceResponse = self.get(&path).await?;
+        Ok(response.data.into())
+    }
+
     /// Get price history for a token
     pub async fn get_price_history(&self, chain: &str, address: &str) -> Result<serde_json::Value> {
         let path = format!("/usd_price/{chain}/{address}/history");
@@ -177,6 +207,45 @@ impl PricesClient {
         self.get("/dao/proposals").await
     }
 
+    /// Get DAO proposals with typed tallies and status
+    ///
+    /// Status is worked out against the current time, so ongoing votes come
+    /// back [`ProposalStatus::Active`]; use [`Proposal::status_at`] to
+    /// re-check later.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # async fn example() -> crv::Result<()> {
+    /// use crv::prices::ProposalStatus;
+    ///
+    /// let client = crv::PricesClient::new()?;
+    /// for proposal in client.dao_proposals().await? {
+    ///     if proposal.status == ProposalStatus::Active {
+    ///         println!("#{} for {} against {}", proposal.id, proposal.votes_for, proposal.votes_against);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn dao_proposals(&self) -> Result<Vec<Proposal>> {
+        let response: ProposalsResponse = self.get("/dao/proposals").await?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Ok(response
+            .proposals
+            .into_iter()
+            .map(|p| Proposal::from_data(p, now))
+            .collect())
+    }
+
+    /// Get a user's veCRV lock history
+    pub async fn dao_locks(&self, user: &str) -> Result<UserLocks> {
+        let path = format!("/dao/locks/{user}");
+        let response: LocksResponse = self.get(&path).await?;
+        Ok(UserLocks::new(user, response.locks))
+    }
+
     /// Get top CRV lockers
     pub async fn get_top_lockers(&self, top: u32) -> Result<serde_json::Value> {
         let path = format!("/dao/lockers/{top}");
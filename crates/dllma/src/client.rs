@@ -4,11 +4,16 @@
 //! while supporting `DefiLlama`'s multi-URL API structure.
 
 use reqwest::Client as HttpClient;
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use url::Url;
 use yldfi_common::api::{extract_retry_after, ApiConfig, SecretApiKey};
 
 use crate::error::{Error, Result};
+use crate::registry::{ProtocolRegistry, Slug};
+
+/// Default lifetime of the cached protocol registry
+pub const DEFAULT_REGISTRY_TTL: Duration = Duration::from_secs(60 * 60);
 
 /// Base URLs for `DefiLlama` APIs
 pub mod base_urls {
@@ -32,6 +37,8 @@ pub mod base_urls {
 pub struct Config {
     /// Pro API key (optional, enables Pro endpoints)
     pub api_key: Option<SecretApiKey>,
+    /// How long a fetched protocol registry is reused
+    pub registry_ttl: Duration,
     /// HTTP client configuration
     inner: ApiConfig,
 }
@@ -42,6 +49,7 @@ impl Config {
     pub fn new() -> Self {
         Self {
             api_key: None,
+            registry_ttl: DEFAULT_REGISTRY_TTL,
             inner: ApiConfig::new(base_urls::MAIN),
         }
     }
@@ -50,6 +58,7 @@ impl Config {
     pub fn with_api_key(api_key: impl Into<String>) -> Self {
         Self {
             api_key: Some(SecretApiKey::new(api_key)),
+            registry_ttl: DEFAULT_REGISTRY_TTL,
             inner: ApiConfig::new(base_urls::MAIN),
         }
     }
@@ -80,6 +89,19 @@ impl Config {
         self.inner.http.proxy = proxy;
         self
     }
+
+    /// Set how long the protocol registry is cached
+    #[must_use]
+    pub fn registry_ttl(mut self, ttl: Duration) -> Self {
+        self.registry_ttl = ttl;
+        self
+    }
+}
+
+/// Protocol registry shared by a client and its clones
+#[derive(Debug, Default)]
+struct RegistryCache {
+    entry: Mutex<Option<(Instant, Arc<ProtocolRegistry>)>>,
 }
 
 impl Default for Config {
@@ -100,6 +122,8 @@ pub struct Client {
     yields_url: Url,
     /// Pro API key (if provided, enables Pro endpoints)
     api_key: Option<SecretApiKey>,
+    registry: Arc<RegistryCache>,
+    registry_ttl: Duration,
 }
 
 impl Client {
@@ -127,6 +151,8 @@ impl Client {
             stablecoins_url: Url::parse(base_urls::STABLECOINS)?,
             yields_url: Url::parse(base_urls::YIELDS)?,
             api_key: config.api_key,
+            registry: Arc::default(),
+            registry_ttl: config.registry_ttl,
         })
     }
 
@@ -149,6 +175,8 @@ impl Client {
             stablecoins_url: Url::parse(base_urls::STABLECOINS)?,
             yields_url: Url::parse(base_urls::YIELDS)?,
             api_key: None,
+            registry: Arc::default(),
+            registry_ttl: DEFAULT_REGISTRY_TTL,
         })
     }
 
@@ -188,6 +216,55 @@ impl Client {
         &self.yields_url
     }
 
+    /// Get the protocol registry, fetching `/protocols` if the cache is stale
+    ///
+    /// The registry is cached for [`Config::registry_ttl`] and shared by all
+    /// clones of this client.
+    pub async fn protocol_registry(&self) -> Result<Arc<ProtocolRegistry>> {
+        if let Some((fetched, registry)) = self.cached_registry() {
+            if fetched.elapsed() < self.registry_ttl {
+                return Ok(registry);
+            }
+        }
+        let protocols = self.tvl().protocols().await?;
+        let registry = Arc::new(ProtocolRegistry::from_protocols(&protocols));
+        *self
+            .registry
+            .entry
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some((Instant::now(), Arc::clone(&registry)));
+        Ok(registry)
+    }
+
+    fn cached_registry(&self) -> Option<(Instant, Arc<ProtocolRegistry>)> {
+        self.registry
+            .entry
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Resolve a protocol name to its slug
+    ///
+    /// See [`ProtocolRegistry::resolve`] for the matching rules.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example() -> dllma::error::Result<()> {
+    /// let client = dllma::Client::new()?;
+    /// let slug = client.resolve_protocol("yearn").await?;
+    /// assert_eq!(slug.as_str(), "yearn-finance");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn resolve_protocol(&self, name: &str) -> Result<Slug> {
+        self.protocol_registry()
+            .await?
+            .resolve(name)
+            .map_err(crate::error::resolve)
+    }
+
     /// Make a GET request to the main API (free endpoints)
     pub(crate) async fn get_main<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T> {
         let url = self.main_url.join(path)?;
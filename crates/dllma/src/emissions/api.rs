@@ -35,4 +35,15 @@ impl<'a> EmissionsApi<'a> {
         let path = format!("/emission/{protocol}");
         self.client.get_pro(&path).await
     }
+
+    /// Get detailed vesting schedule for a protocol by name
+    ///
+    /// **Requires Pro API key**
+    ///
+    /// Resolves `name` with [`Client::resolve_protocol`] before calling
+    /// [`get`](Self::get).
+    pub async fn get_by_name(&self, name: &str) -> Result<EmissionDetail> {
+        let slug = self.client.resolve_protocol(name).await?;
+        self.get(slug.as_str()).await
+    }
 }
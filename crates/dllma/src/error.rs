@@ -20,6 +20,10 @@ pub enum DomainError {
     /// URL parse error
     #[error("URL parsing error: {0}")]
     UrlParse(#[from] url::ParseError),
+
+    /// Protocol name did not resolve to a single slug
+    #[error(transparent)]
+    Resolve(#[from] crate::registry::ResolveError),
 }
 
 /// Error type for `DefiLlama` API operations
@@ -39,6 +43,11 @@ pub fn invalid_param(message: impl Into<String>) -> Error {
     ApiError::domain(DomainError::InvalidParam(message.into()))
 }
 
+/// Create a protocol resolution error
+pub fn resolve(error: crate::registry::ResolveError) -> Error {
+    ApiError::domain(DomainError::Resolve(error))
+}
+
 /// Create from HTTP response status and body
 ///
 /// Handles DefiLlama-specific error patterns (404 as `NotFound`)
@@ -3,7 +3,9 @@
 use crate::client::Client;
 use crate::error::Result;
 
-use super::types::{EtfFlow, EtfHistoryPoint, EtfOverview, EtfSnapshot, FdvPerformance};
+use super::types::{
+    EtfAsset, EtfDailyFlow, EtfFlow, EtfHistoryPoint, EtfOverview, EtfSnapshot, FdvPerformance,
+};
 
 /// ETF API client (Pro only)
 pub struct EtfApi<'a> {
@@ -158,6 +160,32 @@ impl<'a> EtfApi<'a> {
         self.client.get_pro("/etfs/flows").await
     }
 
+    /// Get daily gross and net ETF flows for one asset
+    ///
+    /// **Requires Pro API key**
+    ///
+    /// Derived from the asset's daily history; see
+    /// [`EtfDailyFlow::from_history`] for how flows are split and which days
+    /// are included. Days without reported flows are skipped, not zero-filled.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example() -> dllma::error::Result<()> {
+    /// use dllma::etf::EtfAsset;
+    ///
+    /// let client = dllma::Client::with_api_key("your-api-key")?;
+    /// for day in client.etf().asset_flows(EtfAsset::Btc, 30).await? {
+    ///     println!("{}: net ${:.0}M", day.date, day.net_usd / 1_000_000.0);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn asset_flows(&self, asset: EtfAsset, days: u32) -> Result<Vec<EtfDailyFlow>> {
+        let history: Vec<EtfHistoryPoint> = self.client.get_pro(asset.history_path()).await?;
+        Ok(EtfDailyFlow::from_history(&history, days))
+    }
+
     /// Get ETF snapshot
     ///
     /// **Requires Pro API key**
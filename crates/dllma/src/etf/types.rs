@@ -1,6 +1,7 @@
 //! Types for ETF data (Pro)
 
 use serde::{Deserialize, Serialize};
+use yldfi_common::time::{civil_from_days, parse_iso8601};
 
 /// ETF overview data
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -117,3 +118,188 @@ pub struct EtfSnapshot {
     /// Trading volume
     pub volume: Option<f64>,
 }
+
+/// Asset an ETF holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum EtfAsset {
+    /// Spot Bitcoin ETFs
+    Btc,
+    /// Spot Ethereum ETFs
+    Eth,
+}
+
+impl EtfAsset {
+    /// Path of the daily history endpoint for this asset
+    pub(crate) fn history_path(self) -> &'static str {
+        match self {
+            Self::Btc => "/etfs/history",
+            Self::Eth => "/etfs/historyEth",
+        }
+    }
+}
+
+/// Aggregate ETF flows for one trading day
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EtfDailyFlow {
+    /// Date (e.g., "2024-01-15")
+    pub date: String,
+    /// Sum of positive per-ETF flows in USD
+    pub inflow_usd: f64,
+    /// Sum of negative per-ETF flows in USD, as a positive number
+    pub outflow_usd: f64,
+    /// `inflow_usd - outflow_usd`
+    pub net_usd: f64,
+    /// Total AUM across ETFs, if reported
+    pub aum_usd: Option<f64>,
+}
+
+impl EtfDailyFlow {
+    /// Build a daily flow series from ETF history, oldest first
+    ///
+    /// Gross in- and outflows come from the per-ETF flows of each day. When a
+    /// day has no per-ETF flows, its total flow is counted as inflow or
+    /// outflow by sign. Only days within `days` of the latest day with flows
+    /// are kept.
+    ///
+    /// Days without reported flows (weekends, market holidays, or points
+    /// with neither per-ETF nor total flows) are skipped, not zero-filled,
+    /// so consecutive entries can be more than a day apart. Points without
+    /// a parseable date are dropped.
+    #[must_use]
+    pub fn from_history(history: &[EtfHistoryPoint], days: u32) -> Vec<Self> {
+        let mut dated: Vec<(i64, &EtfHistoryPoint, (f64, f64))> = history
+            .iter()
+            .filter_map(|point| Some((day_number(point)?, point, gross_flows(point)?)))
+            .collect();
+        dated.sort_by_key(|&(day, _, _)| day);
+        let Some(&(latest, _, _)) = dated.last() else {
+            return Vec::new();
+        };
+        let first = latest - i64::from(days) + 1;
+
+        dated
+            .into_iter()
+            .filter(|&(day, _, _)| day >= first)
+            .map(|(day, point, (inflow_usd, outflow_usd))| Self {
+                date: point.date.clone().unwrap_or_else(|| format_day(day)),
+                inflow_usd,
+                outflow_usd,
+                net_usd: inflow_usd - outflow_usd,
+                aum_usd: point.total_aum,
+            })
+            .collect()
+    }
+}
+
+/// Gross `(inflow, outflow)` for a history point, `None` if it reports no flows
+fn gross_flows(point: &EtfHistoryPoint) -> Option<(f64, f64)> {
+    let mut flows = point.etfs.iter().filter_map(|etf| etf.flow).peekable();
+    if flows.peek().is_none() {
+        let total = point.total_flow?;
+        return Some((total.max(0.0), (-total).max(0.0)));
+    }
+    Some(flows.fold((0.0, 0.0), |(inflow, outflow), flow| {
+        if flow >= 0.0 {
+            (inflow + flow, outflow)
+        } else {
+            (inflow, outflow - flow)
+        }
+    }))
+}
+
+/// Days since the Unix epoch for a history point (timestamp, else `YYYY-MM-DD` date)
+fn day_number(point: &EtfHistoryPoint) -> Option<i64> {
+    if let Some(ts) = point.timestamp {
+        // Some responses use milliseconds
+        let secs = if ts > 10_000_000_000 { ts / 1000 } else { ts };
+        return i64::try_from(secs / 86_400).ok();
+    }
+    let date = point.date.as_deref()?;
+    Some(parse_iso8601(date)?.div_euclid(86_400))
+}
+
+/// `YYYY-MM-DD` for days since 1970-01-01
+fn format_day(days: i64) -> String {
+    let (year, month, day) = civil_from_days(days);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(date: &str, flows: &[f64], total_flow: Option<f64>) -> EtfHistoryPoint {
+        EtfHistoryPoint {
+            date: Some(date.to_string()),
+            timestamp: None,
+            total_aum: Some(1_000.0),
+            total_flow,
+            etfs: flows
+                .iter()
+                .map(|&flow| EtfHistoryData {
+                    ticker: None,
+                    aum: None,
+                    flow: Some(flow),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_daily_flows_from_history() {
+        let history = vec![
+            // Friday, then the following Monday: the weekend is skipped
+            point("2024-01-15", &[100.0, -40.0, 10.0], None),
+            point("2024-01-12", &[], Some(-25.0)),
+            point("2024-01-01", &[5.0], None),
+            point("not-a-date", &[1.0], None),
+        ];
+        let flows = EtfDailyFlow::from_history(&history, 7);
+        assert_eq!(flows.len(), 2);
+        assert_eq!(flows[0].date, "2024-01-12");
+        assert_eq!((flows[0].inflow_usd, flows[0].outflow_usd), (0.0, 25.0));
+        assert_eq!(flows[0].net_usd, -25.0);
+        assert_eq!(flows[1].date, "2024-01-15");
+        assert_eq!((flows[1].inflow_usd, flows[1].outflow_usd), (110.0, 40.0));
+        assert_eq!(flows[1].net_usd, 70.0);
+        assert_eq!(flows[1].aum_usd, Some(1_000.0));
+
+        assert_eq!(EtfDailyFlow::from_history(&history, 30).len(), 3);
+        assert!(EtfDailyFlow::from_history(&[], 7).is_empty());
+    }
+
+    #[test]
+    fn test_daily_flows_skip_days_without_flows() {
+        let history = vec![
+            point("2024-01-10", &[20.0], None),
+            // Listed but with no flows reported: a gap, not a zero day
+            point("2024-01-11", &[], None),
+            point("2024-01-12", &[-5.0], None),
+            point("2024-01-13", &[], None),
+        ];
+        let flows = EtfDailyFlow::from_history(&history, 2);
+        let dates: Vec<_> = flows.iter().map(|f| f.date.as_str()).collect();
+        // The window ends at the last day with flows (the 12th)
+        assert_eq!(dates, ["2024-01-12"]);
+
+        let flows = EtfDailyFlow::from_history(&history, 7);
+        let dates: Vec<_> = flows.iter().map(|f| f.date.as_str()).collect();
+        assert_eq!(dates, ["2024-01-10", "2024-01-12"]);
+    }
+
+    #[test]
+    fn test_civil_days_round_trip() {
+        assert_eq!(format_day(0), "1970-01-01");
+        assert_eq!(format_day(19_782), "2024-02-29");
+        let point = EtfHistoryPoint {
+            date: None,
+            timestamp: Some(1_709_164_800_000),
+            total_aum: None,
+            total_flow: None,
+            etfs: Vec::new(),
+        };
+        assert_eq!(day_number(&point), Some(19_782));
+    }
+}
@@ -99,4 +99,13 @@ impl<'a> FeesApi<'a> {
         let path = format!("/summary/fees/{protocol}");
         self.client.get_main(&path).await
     }
+
+    /// Get fees summary for a protocol by name
+    ///
+    /// Resolves `name` with [`Client::resolve_protocol`] before calling
+    /// [`protocol`](Self::protocol).
+    pub async fn protocol_by_name(&self, name: &str) -> Result<ProtocolFeesSummary> {
+        let slug = self.client.resolve_protocol(name).await?;
+        self.protocol(slug.as_str()).await
+    }
 }
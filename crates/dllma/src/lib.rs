@@ -55,6 +55,7 @@
 //! - [`stablecoins`] - Stablecoin supply and dominance
 //! - [`volumes`] - DEX and options trading volumes
 //! - [`fees`] - Protocol fees and revenue
+//! - [`registry`] - Resolve protocol names to slugs
 //!
 //! ### Free and Pro Endpoints
 //!
@@ -74,6 +75,7 @@ pub mod emissions;
 pub mod error;
 pub mod etf;
 pub mod fees;
+pub mod registry;
 pub mod stablecoins;
 pub mod tvl;
 pub mod volumes;
@@ -81,6 +83,7 @@ pub mod yields;
 
 pub use client::{Client, Config};
 pub use error::{Error, Result};
+pub use registry::{ProtocolRegistry, ResolveError, Slug};
 pub use yldfi_common::http::HttpClientConfig;
 pub use yldfi_common::{with_retry, with_simple_retry, RetryConfig, RetryError, RetryableError};
 
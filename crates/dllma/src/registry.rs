@@ -0,0 +1,310 @@
+//! Protocol name to slug resolution
+//!
+//! Every module addresses protocols by their `DefiLlama` slug, and a near miss
+//! like `"yearn"` for `"yearn-finance"` is a 404. [`ProtocolRegistry`] maps
+//! free-form names onto slugs from the `/protocols` list in three tiers:
+//!
+//! 1. Exact match on the normalized slug or display name
+//! 2. Prefix match on the normalized slug or display name
+//! 3. Trigram similarity
+//!
+//! A tier with more than one candidate is reported as
+//! [`ResolveError::Ambiguous`] rather than guessed. The client caches a
+//! registry for [`Config::registry_ttl`](crate::Config::registry_ttl); see
+//! [`Client::resolve_protocol`](crate::Client::resolve_protocol).
+
+use std::collections::HashSet;
+use std::fmt;
+
+use thiserror::Error;
+
+use crate::tvl::Protocol;
+
+/// Maximum candidates listed in an ambiguity or not-found error
+pub const MAX_SUGGESTIONS: usize = 5;
+
+/// Trigram similarity at or above which a name resolves on its own
+const FUZZY_MATCH_THRESHOLD: f64 = 0.6;
+
+/// Trigram similarity at or above which a protocol is suggested
+const SUGGEST_THRESHOLD: f64 = 0.1;
+
+/// A resolved protocol slug
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Slug(String);
+
+impl Slug {
+    /// The slug as used in API paths
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Slug {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for Slug {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Why a name could not be resolved to a single slug
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ResolveError {
+    /// Several protocols match equally well (ranked by TVL)
+    #[error("Ambiguous protocol name, candidates: {}", .0.join(", "))]
+    Ambiguous(Vec<String>),
+
+    /// Nothing matches; closest slugs first
+    #[error("Unknown protocol{}", format_suggestions(.suggestions))]
+    NotFound {
+        /// Similar slugs, best first
+        suggestions: Vec<String>,
+    },
+}
+
+fn format_suggestions(suggestions: &[String]) -> String {
+    if suggestions.is_empty() {
+        String::new()
+    } else {
+        format!(", did you mean: {}?", suggestions.join(", "))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    slug: String,
+    slug_key: String,
+    name_key: String,
+    tvl: f64,
+    slug_trigrams: HashSet<[char; 3]>,
+    name_trigrams: HashSet<[char; 3]>,
+}
+
+impl Entry {
+    fn similarity(&self, query: &HashSet<[char; 3]>) -> f64 {
+        similarity(query, &self.slug_trigrams).max(similarity(query, &self.name_trigrams))
+    }
+}
+
+/// Lookup table from protocol names to slugs
+#[derive(Debug, Clone, Default)]
+pub struct ProtocolRegistry {
+    entries: Vec<Entry>,
+}
+
+impl ProtocolRegistry {
+    /// Build a registry from the `/protocols` list
+    #[must_use]
+    pub fn from_protocols(protocols: &[Protocol]) -> Self {
+        let entries = protocols
+            .iter()
+            .map(|p| {
+                let slug_key = normalize(&p.slug);
+                let name_key = normalize(&p.name);
+                Entry {
+                    slug: p.slug.clone(),
+                    slug_trigrams: trigrams(&slug_key),
+                    name_trigrams: trigrams(&name_key),
+                    slug_key,
+                    name_key,
+                    tvl: p.tvl.unwrap_or(0.0),
+                }
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// Number of protocols in the registry
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the registry is empty
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Resolve a protocol name or slug
+    ///
+    /// Matching ignores case and any non-alphanumeric characters, so
+    /// `"Yearn Finance"` and `"yearn-finance"` are the same key.
+    pub fn resolve(&self, name: &str) -> Result<Slug, ResolveError> {
+        let key = normalize(name);
+        if key.is_empty() {
+            return Err(ResolveError::NotFound {
+                suggestions: Vec::new(),
+            });
+        }
+
+        let exact = self.matching(|e| e.slug_key == key || e.name_key == key);
+        if !exact.is_empty() {
+            return Self::single(exact);
+        }
+
+        let prefix =
+            self.matching(|e| e.slug_key.starts_with(&key) || e.name_key.starts_with(&key));
+        if !prefix.is_empty() {
+            return Self::single(prefix);
+        }
+
+        let query = trigrams(&key);
+        let mut scored: Vec<(f64, &Entry)> = self
+            .entries
+            .iter()
+            .map(|e| (e.similarity(&query), e))
+            .filter(|(score, _)| *score >= SUGGEST_THRESHOLD)
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0).then(b.1.tvl.total_cmp(&a.1.tvl)));
+
+        let confident: Vec<&Entry> = scored
+            .iter()
+            .filter(|(score, _)| *score >= FUZZY_MATCH_THRESHOLD)
+            .map(|(_, e)| *e)
+            .collect();
+        if !confident.is_empty() {
+            return Self::single(confident);
+        }
+
+        Err(ResolveError::NotFound {
+            suggestions: scored
+                .into_iter()
+                .take(MAX_SUGGESTIONS)
+                .map(|(_, e)| e.slug.clone())
+                .collect(),
+        })
+    }
+
+    fn matching(&self, predicate: impl Fn(&Entry) -> bool) -> Vec<&Entry> {
+        self.entries.iter().filter(|e| predicate(e)).collect()
+    }
+
+    fn single(mut candidates: Vec<&Entry>) -> Result<Slug, ResolveError> {
+        if let [only] = candidates.as_slice() {
+            return Ok(Slug(only.slug.clone()));
+        }
+        candidates.sort_by(|a, b| b.tvl.total_cmp(&a.tvl));
+        Err(ResolveError::Ambiguous(
+            candidates
+                .into_iter()
+                .take(MAX_SUGGESTIONS)
+                .map(|e| e.slug.clone())
+                .collect(),
+        ))
+    }
+}
+
+/// Lowercase and drop everything but letters and digits
+fn normalize(s: &str) -> String {
+    s.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Trigrams of a normalized key, padded so short keys still produce some
+fn trigrams(key: &str) -> HashSet<[char; 3]> {
+    let padded: Vec<char> = "  ".chars().chain(key.chars()).chain(" ".chars()).collect();
+    padded.windows(3).map(|w| [w[0], w[1], w[2]]).collect()
+}
+
+/// Jaccard similarity of two trigram sets
+fn similarity(a: &HashSet<[char; 3]>, b: &HashSet<[char; 3]>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture() -> ProtocolRegistry {
+        let protocols: Vec<Protocol> = serde_json::from_value(serde_json::json!([
+            { "id": "1", "name": "Yearn Finance", "slug": "yearn-finance", "tvl": 300.0 },
+            { "id": "2", "name": "Uniswap V2", "slug": "uniswap-v2", "tvl": 1000.0 },
+            { "id": "3", "name": "Uniswap V3", "slug": "uniswap-v3", "tvl": 4000.0 },
+            { "id": "4", "name": "Uniswap Labs", "slug": "uniswap-labs", "tvl": 10.0 },
+            { "id": "5", "name": "Lido", "slug": "lido", "tvl": 9000.0 },
+            { "id": "6", "name": "Curve DEX", "slug": "curve-dex", "tvl": 2000.0 },
+            { "id": "7", "name": "Convex Finance", "slug": "convex-finance", "tvl": 1500.0 }
+        ]))
+        .unwrap();
+        ProtocolRegistry::from_protocols(&protocols)
+    }
+
+    #[test]
+    fn test_resolve_exact() {
+        let registry = fixture();
+        assert_eq!(registry.resolve("lido").unwrap().as_str(), "lido");
+        assert_eq!(registry.resolve("LIDO").unwrap().as_str(), "lido");
+        assert_eq!(
+            registry.resolve("Yearn Finance").unwrap().as_str(),
+            "yearn-finance"
+        );
+        assert_eq!(
+            registry.resolve("uniswap_v3").unwrap().as_str(),
+            "uniswap-v3"
+        );
+    }
+
+    #[test]
+    fn test_resolve_prefix() {
+        let registry = fixture();
+        assert_eq!(registry.resolve("yearn").unwrap().as_str(), "yearn-finance");
+        assert_eq!(registry.resolve("curve").unwrap().as_str(), "curve-dex");
+    }
+
+    #[test]
+    fn test_resolve_ambiguous_ranked_by_tvl() {
+        let registry = fixture();
+        assert_eq!(
+            registry.resolve("uniswap"),
+            Err(ResolveError::Ambiguous(vec![
+                "uniswap-v3".to_string(),
+                "uniswap-v2".to_string(),
+                "uniswap-labs".to_string(),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_resolve_fuzzy() {
+        let registry = fixture();
+        assert_eq!(
+            registry.resolve("convex-fnance").unwrap().as_str(),
+            "convex-finance"
+        );
+    }
+
+    #[test]
+    fn test_resolve_not_found_suggestions() {
+        let registry = fixture();
+        match registry.resolve("yern") {
+            Err(ResolveError::NotFound { suggestions }) => {
+                assert_eq!(
+                    suggestions.first().map(String::as_str),
+                    Some("yearn-finance")
+                );
+                assert!(suggestions.len() <= MAX_SUGGESTIONS);
+            }
+            other => panic!("expected NotFound, got {other:?}"),
+        }
+        assert_eq!(
+            registry.resolve("zzzzzz"),
+            Err(ResolveError::NotFound {
+                suggestions: Vec::new()
+            })
+        );
+        assert!(registry.resolve(" - ").is_err());
+    }
+}
@@ -104,6 +104,15 @@ impl<'a> VolumesApi<'a> {
         self.client.get_main(&path).await
     }
 
+    /// Get DEX volume summary for a protocol by name
+    ///
+    /// Resolves `name` with [`Client::resolve_protocol`] before calling
+    /// [`dex_protocol`](Self::dex_protocol).
+    pub async fn dex_protocol_by_name(&self, name: &str) -> Result<ProtocolVolumeSummary> {
+        let slug = self.client.resolve_protocol(name).await?;
+        self.dex_protocol(slug.as_str()).await
+    }
+
     // ==================== Options Endpoints ====================
 
     /// Get aggregated options trading volumes overview
@@ -162,6 +171,15 @@ impl<'a> VolumesApi<'a> {
         self.client.get_main(&path).await
     }
 
+    /// Get options volume summary for a protocol by name
+    ///
+    /// Resolves `name` with [`Client::resolve_protocol`] before calling
+    /// [`options_protocol`](Self::options_protocol).
+    pub async fn options_protocol_by_name(&self, name: &str) -> Result<ProtocolVolumeSummary> {
+        let slug = self.client.resolve_protocol(name).await?;
+        self.options_protocol(slug.as_str()).await
+    }
+
     // ==================== Derivatives Endpoints (Pro) ====================
 
     /// Get aggregated derivatives volumes overview
@@ -204,6 +222,15 @@ impl<'a> VolumesApi<'a> {
         self.client.get_pro(&path).await
     }
 
+    /// Get derivatives volume summary for a protocol by name
+    ///
+    /// Resolves `name` with [`Client::resolve_protocol`] before calling
+    /// [`derivatives_protocol`](Self::derivatives_protocol).
+    pub async fn derivatives_protocol_by_name(&self, name: &str) -> Result<ProtocolVolumeSummary> {
+        let slug = self.client.resolve_protocol(name).await?;
+        self.derivatives_protocol(slug.as_str()).await
+    }
+
     // ==================== Open Interest Endpoints ====================
 
     /// Get open interest overview for perpetual futures
@@ -44,6 +44,17 @@ impl<'a> YieldsApi<'a> {
         Ok(resp.data)
     }
 
+    /// Get yield pools for a protocol by name
+    ///
+    /// Resolves `name` with [`Client::resolve_protocol`] and keeps the pools
+    /// whose `project` is that slug.
+    pub async fn pools_by_name(&self, name: &str) -> Result<Vec<YieldPool>> {
+        let slug = self.client.resolve_protocol(name).await?;
+        let mut pools = self.pools().await?;
+        pools.retain(|pool| pool.project == slug.as_str());
+        Ok(pools)
+    }
+
     /// Get historical APY/TVL chart for a pool
     ///
     /// This is a **free** endpoint that doesn't require an API key.
@@ -85,7 +96,7 @@ impl<'a> YieldsApi<'a> {
         Ok(resp.data)
     }
 
-    /// Get borrowing rates across protocols
+    /// Get supply and borrow rates, totals and LTV for lending pools
     ///
     /// **Requires Pro API key**
     ///
@@ -96,10 +107,10 @@ impl<'a> YieldsApi<'a> {
     /// let client = dllma::Client::with_api_key("your-api-key")?;
     /// let borrow_pools = client.yields().pools_borrow().await?;
     /// for pool in borrow_pools.iter().take(5) {
-    ///     println!("{}: lend {:.2}%, borrow {:.2}%",
-    ///         pool.symbol,
-    ///         pool.apy.unwrap_or(0.0),
-    ///         pool.apy_borrow.unwrap_or(0.0));
+    ///     let supply = pool.supply_rate().map_or(0.0, |r| r.percent());
+    ///     let borrow = pool.borrow_rate().map_or(0.0, |r| r.percent());
+    ///     println!("{}: lend {supply:.2}%, borrow {borrow:.2}%, ltv {:?}",
+    ///         pool.symbol, pool.ltv);
     /// }
     /// # Ok(())
     /// # }
@@ -133,7 +144,10 @@ impl<'a> YieldsApi<'a> {
         Ok(resp.data)
     }
 
-    /// Get perpetual futures funding rates
+    /// Get perpetual futures funding rates and open interest
+    ///
+    /// Funding rates are per funding interval; see [`PerpRate`] for
+    /// normalizing them to 8h or annual rates.
     ///
     /// **Requires Pro API key**
     ///
@@ -142,10 +156,14 @@ impl<'a> YieldsApi<'a> {
     /// ```no_run
     /// # async fn example() -> dllma::error::Result<()> {
     /// let client = dllma::Client::with_api_key("your-api-key")?;
+    /// use dllma::yields::{Compounding, RatePeriod};
+    ///
     /// let perps = client.yields().perps().await?;
     /// for perp in perps.iter().take(5) {
-    ///     println!("{} on {}: {:.4}% funding rate",
-    ///         perp.market, perp.marketplace, perp.funding_rate.unwrap_or(0.0));
+    ///     if let Some(rate) = perp.funding_annualized(RatePeriod::EightHour, Compounding::Simple) {
+    ///         println!("{} on {}: {:.2}% annualized funding",
+    ///             perp.market, perp.marketplace, rate.percent());
+    ///     }
     /// }
     /// # Ok(())
     /// # }
@@ -1,6 +1,8 @@
 //! Yield farming and lending data (Pro)
 //!
 //! Access yield pools, APY data, lending/borrowing rates, and more.
+//! Lending APYs and perp funding rates convert to a common scale through
+//! [`Rate`] and [`RatePeriod`].
 //!
 //! **All endpoints require a Pro API key.**
 //!
@@ -20,7 +22,9 @@
 //! ```
 
 mod api;
+mod rate;
 mod types;
 
 pub use api::YieldsApi;
+pub use rate::{Compounding, Rate, RatePeriod};
 pub use types::*;
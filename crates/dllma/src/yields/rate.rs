@@ -0,0 +1,165 @@
+//! Rate period conversion shared by lending and funding rates
+//!
+//! `DefiLlama` reports lending yields as annual percentages and perp funding
+//! as a fraction per funding interval. [`Rate`] carries the period with the
+//! value so the two can be compared on one scale.
+
+use serde::{Deserialize, Serialize};
+
+/// Hours in a (non-leap) year
+const HOURS_PER_YEAR: f64 = 365.0 * 24.0;
+
+/// Period a rate accrues over
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RatePeriod {
+    /// One hour
+    Hourly,
+    /// Eight hours, the usual perp funding interval
+    EightHour,
+    /// One day
+    Daily,
+    /// One 365-day year
+    Annual,
+}
+
+impl RatePeriod {
+    /// Length of the period in hours
+    #[must_use]
+    pub fn hours(self) -> f64 {
+        match self {
+            Self::Hourly => 1.0,
+            Self::EightHour => 8.0,
+            Self::Daily => 24.0,
+            Self::Annual => HOURS_PER_YEAR,
+        }
+    }
+
+    /// Number of these periods in a year
+    #[must_use]
+    pub fn per_year(self) -> f64 {
+        HOURS_PER_YEAR / self.hours()
+    }
+}
+
+/// How a rate carries over when changing period
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Compounding {
+    /// Scale linearly with time (APR-style)
+    Simple,
+    /// Reinvest at the end of every period (APY-style)
+    Compound,
+}
+
+/// A rate over a period, as a fraction (`0.05` = 5%)
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct Rate {
+    /// Rate as a fraction of principal; negative for rates paid rather than received
+    pub value: f64,
+    /// Period the rate accrues over
+    pub period: RatePeriod,
+}
+
+impl Rate {
+    /// Create a rate from a fraction
+    #[must_use]
+    pub fn new(value: f64, period: RatePeriod) -> Self {
+        Self { value, period }
+    }
+
+    /// Create a rate from a percentage (`5.0` = 5%)
+    #[must_use]
+    pub fn from_percent(percent: f64, period: RatePeriod) -> Self {
+        Self::new(percent / 100.0, period)
+    }
+
+    /// The rate as a percentage
+    #[must_use]
+    pub fn percent(&self) -> f64 {
+        self.value * 100.0
+    }
+
+    /// Express the rate over another period
+    ///
+    /// With [`Compounding::Compound`] a rate of -100% or worse stays at -100%:
+    /// the principal is gone and there is nothing left to compound.
+    #[must_use]
+    pub fn to_period(&self, period: RatePeriod, compounding: Compounding) -> Self {
+        let ratio = period.hours() / self.period.hours();
+        let value = match compounding {
+            Compounding::Simple => self.value * ratio,
+            Compounding::Compound if self.value <= -1.0 => -1.0,
+            Compounding::Compound => (1.0 + self.value).powf(ratio) - 1.0,
+        };
+        Self::new(value, period)
+    }
+
+    /// Express the rate per year
+    #[must_use]
+    pub fn annualized(&self, compounding: Compounding) -> Self {
+        self.to_period(RatePeriod::Annual, compounding)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn test_period_lengths() {
+        assert_eq!(RatePeriod::EightHour.per_year(), 1095.0);
+        assert_eq!(RatePeriod::Daily.per_year(), 365.0);
+        assert_eq!(RatePeriod::Annual.per_year(), 1.0);
+    }
+
+    #[test]
+    fn test_simple_conversion() {
+        let funding = Rate::new(0.0001, RatePeriod::EightHour);
+        assert_close(funding.annualized(Compounding::Simple).value, 0.1095);
+        assert_close(
+            funding
+                .to_period(RatePeriod::Hourly, Compounding::Simple)
+                .value,
+            0.0000125,
+        );
+        let daily = funding.to_period(RatePeriod::Daily, Compounding::Simple);
+        assert_eq!(daily.period, RatePeriod::Daily);
+        assert_close(daily.value, 0.0003);
+    }
+
+    #[test]
+    fn test_compound_conversion() {
+        let apy = Rate::from_percent(10.0, RatePeriod::Annual);
+        let daily = apy.to_period(RatePeriod::Daily, Compounding::Compound);
+        assert_close(daily.value, 1.1f64.powf(1.0 / 365.0) - 1.0);
+        // Round trip back to the same APY
+        assert_close(daily.annualized(Compounding::Compound).percent(), 10.0);
+        // Compounding reaches the same APY from a smaller daily rate
+        let simple = apy.to_period(RatePeriod::Daily, Compounding::Simple);
+        assert!(daily.value < simple.value);
+    }
+
+    #[test]
+    fn test_negative_funding() {
+        let funding = Rate::new(-0.0002, RatePeriod::EightHour);
+        let simple = funding.annualized(Compounding::Simple);
+        assert_close(simple.value, -0.219);
+        assert_close(simple.percent(), -21.9);
+
+        let compound = funding.annualized(Compounding::Compound);
+        assert_close(compound.value, 0.9998f64.powf(1095.0) - 1.0);
+        assert!(compound.value < 0.0 && compound.value > simple.value);
+
+        let wiped = Rate::new(-1.5, RatePeriod::Daily);
+        assert_eq!(wiped.annualized(Compounding::Compound).value, -1.0);
+        assert_close(wiped.annualized(Compounding::Simple).value, -547.5);
+    }
+}
@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Deserializer, Serialize};
 
+use super::rate::{Compounding, Rate, RatePeriod};
+
 /// Deserialize a Vec that may be null or missing as an empty Vec
 fn null_to_empty_vec<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
 where
@@ -136,6 +138,8 @@ pub struct YieldChartPoint {
 }
 
 /// Borrow pool data
+///
+/// APY fields are annual percentages (`5.0` = 5%).
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BorrowPool {
@@ -151,8 +155,16 @@ pub struct BorrowPool {
     pub tvl_usd: Option<f64>,
     /// Lend APY (supply APY)
     pub apy: Option<f64>,
-    /// Borrow APY
+    /// Supply APY from interest
+    pub apy_base: Option<f64>,
+    /// Supply APY from token rewards
+    pub apy_reward: Option<f64>,
+    /// Borrow APY, when reported as a single figure
     pub apy_borrow: Option<f64>,
+    /// Borrow APY paid in interest
+    pub apy_base_borrow: Option<f64>,
+    /// Borrow APY earned back in token rewards
+    pub apy_reward_borrow: Option<f64>,
     /// Total supply in USD
     pub total_supply_usd: Option<f64>,
     /// Total borrow in USD
@@ -161,9 +173,52 @@ pub struct BorrowPool {
     pub ltv: Option<f64>,
     /// Utilization rate
     pub utilization: Option<f64>,
+    /// Whether the asset can be borrowed
+    pub borrowable: Option<bool>,
+    /// Debt ceiling in USD
+    pub debt_ceiling_usd: Option<f64>,
     /// Reward tokens
     #[serde(default, deserialize_with = "strings_filtering_nulls")]
     pub reward_tokens: Vec<String>,
+    /// Underlying tokens
+    #[serde(default, deserialize_with = "strings_filtering_nulls")]
+    pub underlying_tokens: Vec<String>,
+}
+
+impl BorrowPool {
+    /// Annual supply rate: `apy`, or base plus reward APY
+    #[must_use]
+    pub fn supply_rate(&self) -> Option<Rate> {
+        let percent = self
+            .apy
+            .or_else(|| match (self.apy_base, self.apy_reward) {
+                (None, None) => None,
+                (base, reward) => Some(base.unwrap_or(0.0) + reward.unwrap_or(0.0)),
+            })?;
+        Some(Rate::from_percent(percent, RatePeriod::Annual))
+    }
+
+    /// Annual net borrow cost: `apy_borrow`, or base borrow APY minus rewards
+    ///
+    /// Negative when rewards exceed the interest paid.
+    #[must_use]
+    pub fn borrow_rate(&self) -> Option<Rate> {
+        let percent = self.apy_borrow.or_else(|| {
+            self.apy_base_borrow
+                .map(|base| base - self.apy_reward_borrow.unwrap_or(0.0))
+        })?;
+        Some(Rate::from_percent(percent, RatePeriod::Annual))
+    }
+
+    /// Borrowed share of supply: `utilization`, or total borrowed over total supplied
+    #[must_use]
+    pub fn utilization_ratio(&self) -> Option<f64> {
+        self.utilization
+            .or_else(|| match (self.total_borrow_usd, self.total_supply_usd) {
+                (Some(borrow), Some(supply)) if supply > 0.0 => Some(borrow / supply),
+                _ => None,
+            })
+    }
 }
 
 /// Lend/Borrow chart data point
@@ -183,6 +238,10 @@ pub struct LendBorrowChartPoint {
 }
 
 /// Perpetual futures funding rate
+///
+/// Funding rates are fractions per funding interval (`0.0001` = 0.01%),
+/// positive when longs pay shorts. Most venues fund every 8 hours; use
+/// [`PerpRate::funding`] with the venue's interval for the others.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PerpRate {
@@ -192,12 +251,44 @@ pub struct PerpRate {
     pub base_asset: String,
     /// Exchange/protocol
     pub marketplace: String,
-    /// Funding rate (annualized)
+    /// Current funding rate per funding interval
     pub funding_rate: Option<f64>,
+    /// Previous funding rate per funding interval
+    pub funding_rate_previous: Option<f64>,
     /// Open interest in USD
     pub open_interest: Option<f64>,
+    /// Index price
+    pub index_price: Option<f64>,
     /// Funding rate 7-day average
     pub funding_rate7d_average: Option<f64>,
+    /// Funding rate 30-day average
+    pub funding_rate30d_average: Option<f64>,
+}
+
+impl PerpRate {
+    /// Current funding rate, accruing every `interval`
+    #[must_use]
+    pub fn funding(&self, interval: RatePeriod) -> Option<Rate> {
+        self.funding_rate.map(|rate| Rate::new(rate, interval))
+    }
+
+    /// Current funding normalized to an 8-hour interval (simple scaling)
+    #[must_use]
+    pub fn funding_8h(&self, interval: RatePeriod) -> Option<Rate> {
+        self.funding(interval)
+            .map(|rate| rate.to_period(RatePeriod::EightHour, Compounding::Simple))
+    }
+
+    /// Current funding as an annual rate
+    #[must_use]
+    pub fn funding_annualized(
+        &self,
+        interval: RatePeriod,
+        compounding: Compounding,
+    ) -> Option<Rate> {
+        self.funding(interval)
+            .map(|rate| rate.annualized(compounding))
+    }
 }
 
 /// Liquid staking derivative rate
@@ -219,3 +310,75 @@ pub struct LsdRate {
     /// Market share
     pub market_share: Option<f64>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POOLS_BORROW: &str = include_str!("../../tests/fixtures/yields/pools_borrow.json");
+    const PERPS: &str = include_str!("../../tests/fixtures/yields/perps.json");
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn test_pools_borrow_fixture() {
+        let resp: YieldsResponse<Vec<BorrowPool>> = serde_json::from_str(POOLS_BORROW).unwrap();
+        let [aave, morpho, sparse] = resp.data.as_slice() else {
+            panic!("expected three pools");
+        };
+
+        assert_eq!(aave.project, "aave-v3");
+        assert_eq!(aave.ltv, Some(0.75));
+        assert_close(aave.supply_rate().unwrap().percent(), 4.9);
+        // Interest paid minus rewards earned back
+        let borrow = aave.borrow_rate().unwrap();
+        assert_eq!(borrow.period, RatePeriod::Annual);
+        assert_close(borrow.percent(), 5.75);
+        assert_close(aave.utilization_ratio().unwrap(), 0.8);
+        assert_eq!(aave.underlying_tokens.len(), 1);
+
+        // Rewards larger than the interest make borrowing pay
+        assert_close(morpho.borrow_rate().unwrap().percent(), -0.5);
+        assert_close(morpho.supply_rate().unwrap().percent(), 3.25);
+        assert_eq!(morpho.utilization_ratio(), Some(0.5));
+
+        assert!(sparse.supply_rate().is_none());
+        assert!(sparse.borrow_rate().is_none());
+        assert!(sparse.utilization_ratio().is_none());
+        assert!(sparse.reward_tokens.is_empty());
+    }
+
+    #[test]
+    fn test_perps_fixture() {
+        let resp: YieldsResponse<Vec<PerpRate>> = serde_json::from_str(PERPS).unwrap();
+        let [btc, eth, sol] = resp.data.as_slice() else {
+            panic!("expected three perps");
+        };
+
+        assert_eq!(btc.marketplace, "Binance");
+        assert_eq!(btc.open_interest, Some(8_250_000_000.0));
+        let annual = btc
+            .funding_annualized(RatePeriod::EightHour, Compounding::Simple)
+            .unwrap();
+        assert_close(annual.percent(), 10.95);
+
+        // Negative funding: shorts pay longs
+        let eth_8h = eth.funding_8h(RatePeriod::EightHour).unwrap();
+        assert_close(eth_8h.value, -0.00005);
+        let eth_annual = eth
+            .funding_annualized(RatePeriod::EightHour, Compounding::Simple)
+            .unwrap();
+        assert_close(eth_annual.percent(), -5.475);
+
+        // Hourly venue normalized to the 8h convention
+        let sol_8h = sol.funding_8h(RatePeriod::Hourly).unwrap();
+        assert_close(sol_8h.value, 0.0001);
+        assert!(sol.index_price.is_none());
+        assert!(sol.funding(RatePeriod::Hourly).is_some());
+    }
+}
@@ -0,0 +1,302 @@
+//! Client-side credit budget
+//!
+//! Dune bills executions in credits. A [`CreditBudget`] attached to a
+//! [`Client`](crate::Client) estimates the cost of each call from a
+//! [`CreditCosts`] table and refuses calls that would exceed the budget,
+//! before any request is sent.
+
+use crate::error::{self, Result};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// Billable endpoint groups used to look up credit costs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endpoint {
+    /// Query, SQL or materialized view execution on the medium engine
+    ExecuteMedium,
+    /// Query, SQL or materialized view execution on the large engine
+    ExecuteLarge,
+    /// Pipeline execution
+    PipelineExecute,
+    /// Reading execution or query results (JSON or CSV)
+    Results,
+    /// Execution and pipeline status polls
+    Status,
+    /// Cancelling an execution
+    Cancel,
+    /// Query management (create, get, update, list, archive, visibility)
+    Queries,
+    /// Table management, uploads and inserts
+    Tables,
+    /// Materialized view management (upsert, get, list, delete)
+    Matviews,
+    /// Usage lookups
+    Usage,
+}
+
+impl Endpoint {
+    /// Execution endpoint for a performance tier (`"large"` or medium)
+    #[must_use]
+    pub fn execution(performance: Option<&str>) -> Self {
+        match performance {
+            Some(tier) if tier.eq_ignore_ascii_case("large") => Self::ExecuteLarge,
+            _ => Self::ExecuteMedium,
+        }
+    }
+}
+
+/// Estimated credit cost per endpoint
+///
+/// Defaults follow Dune's published pricing: 10 credits for a medium
+/// execution, 20 for large, and a minimum of 1 credit to read results.
+/// Management calls are free. Override entries when pricing changes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreditCosts {
+    costs: HashMap<Endpoint, f64>,
+}
+
+impl Default for CreditCosts {
+    fn default() -> Self {
+        Self {
+            costs: HashMap::from([
+                (Endpoint::ExecuteMedium, 10.0),
+                (Endpoint::ExecuteLarge, 20.0),
+                (Endpoint::PipelineExecute, 10.0),
+                (Endpoint::Results, 1.0),
+            ]),
+        }
+    }
+}
+
+impl CreditCosts {
+    /// Create the default cost table
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the cost of an endpoint
+    #[must_use]
+    pub fn with_cost(mut self, endpoint: Endpoint, credits: f64) -> Self {
+        self.set_cost(endpoint, credits);
+        self
+    }
+
+    /// Set the cost of an endpoint in place
+    pub fn set_cost(&mut self, endpoint: Endpoint, credits: f64) {
+        self.costs.insert(endpoint, credits.max(0.0));
+    }
+
+    /// Estimated cost of an endpoint (0 if not listed)
+    #[must_use]
+    pub fn cost(&self, endpoint: Endpoint) -> f64 {
+        self.costs.get(&endpoint).copied().unwrap_or(0.0)
+    }
+}
+
+#[derive(Debug)]
+struct BudgetState {
+    max_credits: f64,
+    spent: f64,
+    costs: CreditCosts,
+}
+
+/// Shared credit budget
+///
+/// Cloning a budget (or a [`Client`](crate::Client) holding one) shares the
+/// same counter. Credits are reserved before a request is sent and are not
+/// refunded if the request fails, so the budget errs on the side of
+/// underspending.
+#[derive(Debug, Clone)]
+pub struct CreditBudget {
+    state: Arc<Mutex<BudgetState>>,
+}
+
+impl CreditBudget {
+    /// Create a budget of `max_credits` with the default cost table
+    #[must_use]
+    pub fn new(max_credits: f64) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(BudgetState {
+                max_credits,
+                spent: 0.0,
+                costs: CreditCosts::default(),
+            })),
+        }
+    }
+
+    /// Use a custom cost table
+    #[must_use]
+    pub fn with_costs(self, costs: CreditCosts) -> Self {
+        self.set_costs(costs);
+        self
+    }
+
+    /// Replace the cost table
+    pub fn set_costs(&self, costs: CreditCosts) {
+        self.lock().costs = costs;
+    }
+
+    /// Current cost table
+    #[must_use]
+    pub fn costs(&self) -> CreditCosts {
+        self.lock().costs.clone()
+    }
+
+    /// Total credits allowed
+    #[must_use]
+    pub fn max_credits(&self) -> f64 {
+        self.lock().max_credits
+    }
+
+    /// Estimated credits spent so far
+    #[must_use]
+    pub fn spent(&self) -> f64 {
+        self.lock().spent
+    }
+
+    /// Credits left before calls are refused
+    #[must_use]
+    pub fn remaining(&self) -> f64 {
+        let state = self.lock();
+        (state.max_credits - state.spent).max(0.0)
+    }
+
+    /// Reset spent credits to zero
+    pub fn reset(&self) {
+        self.lock().spent = 0.0;
+    }
+
+    /// Reset spent credits and set a new limit
+    pub fn reset_to(&self, max_credits: f64) {
+        let mut state = self.lock();
+        state.max_credits = max_credits;
+        state.spent = 0.0;
+    }
+
+    /// Reserve the cost of a call, or fail with `BudgetExceeded`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BudgetExceeded`](crate::error::DomainError::BudgetExceeded)
+    /// if the call's estimated cost is more than the remaining credits.
+    pub fn charge(&self, endpoint: Endpoint) -> Result<()> {
+        let mut state = self.lock();
+        let cost = state.costs.cost(endpoint);
+        let remaining = (state.max_credits - state.spent).max(0.0);
+        if cost > remaining {
+            return Err(error::budget_exceeded(endpoint, cost, remaining));
+        }
+        state.spent += cost;
+        Ok(())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BudgetState> {
+        // The state is always left consistent, so a poisoned lock is usable
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::DomainError;
+    use yldfi_common::api::ApiError;
+
+    #[test]
+    fn test_charge_until_exceeded() {
+        let budget = CreditBudget::new(25.0);
+        budget.charge(Endpoint::ExecuteMedium).unwrap();
+        budget.charge(Endpoint::ExecuteMedium).unwrap();
+        // Free endpoints never run out
+        budget.charge(Endpoint::Status).unwrap();
+        assert_eq!(budget.spent(), 20.0);
+        assert_eq!(budget.remaining(), 5.0);
+
+        let err = budget.charge(Endpoint::ExecuteLarge).unwrap_err();
+        match err {
+            ApiError::Domain(DomainError::BudgetExceeded {
+                endpoint,
+                cost,
+                remaining,
+            }) => {
+                assert_eq!(endpoint, Endpoint::ExecuteLarge);
+                assert_eq!(cost, 20.0);
+                assert_eq!(remaining, 5.0);
+            }
+            other => panic!("expected BudgetExceeded, got {other:?}"),
+        }
+        // A refused call reserves nothing
+        assert_eq!(budget.spent(), 20.0);
+        budget.charge(Endpoint::Results).unwrap();
+        assert_eq!(budget.remaining(), 4.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let budget = CreditBudget::new(10.0);
+        budget.charge(Endpoint::ExecuteMedium).unwrap();
+        assert_eq!(budget.remaining(), 0.0);
+        assert!(budget.charge(Endpoint::Results).is_err());
+
+        budget.reset();
+        assert_eq!(budget.spent(), 0.0);
+        assert_eq!(budget.remaining(), 10.0);
+
+        budget.charge(Endpoint::ExecuteMedium).unwrap();
+        budget.reset_to(50.0);
+        assert_eq!(budget.max_credits(), 50.0);
+        assert_eq!(budget.spent(), 0.0);
+        assert_eq!(budget.remaining(), 50.0);
+
+        // Lowering the limit below what was spent never goes negative
+        budget.charge(Endpoint::ExecuteLarge).unwrap();
+        budget.lock().max_credits = 5.0;
+        assert_eq!(budget.remaining(), 0.0);
+    }
+
+    #[test]
+    fn test_costs() {
+        let costs = CreditCosts::new()
+            .with_cost(Endpoint::ExecuteLarge, 40.0)
+            .with_cost(Endpoint::Queries, -3.0);
+        assert_eq!(costs.cost(Endpoint::ExecuteLarge), 40.0);
+        assert_eq!(costs.cost(Endpoint::ExecuteMedium), 10.0);
+        // Negative costs are clamped, unlisted endpoints are free
+        assert_eq!(costs.cost(Endpoint::Queries), 0.0);
+        assert_eq!(costs.cost(Endpoint::Usage), 0.0);
+
+        let budget = CreditBudget::new(100.0).with_costs(costs.clone());
+        assert_eq!(budget.costs(), costs);
+        budget.charge(Endpoint::ExecuteLarge).unwrap();
+        assert_eq!(budget.spent(), 40.0);
+    }
+
+    #[test]
+    fn test_execution_endpoint() {
+        assert_eq!(Endpoint::execution(Some("large")), Endpoint::ExecuteLarge);
+        assert_eq!(Endpoint::execution(Some("LARGE")), Endpoint::ExecuteLarge);
+        assert_eq!(Endpoint::execution(Some("medium")), Endpoint::ExecuteMedium);
+        assert_eq!(Endpoint::execution(None), Endpoint::ExecuteMedium);
+
+        let costs = CreditCosts::default();
+        assert_eq!(costs.cost(Endpoint::execution(Some("large"))), 20.0);
+        assert_eq!(costs.cost(Endpoint::execution(None)), 10.0);
+    }
+
+    #[test]
+    fn test_clones_share_budget() {
+        let budget = CreditBudget::new(30.0);
+        let clone = budget.clone();
+        clone.charge(Endpoint::ExecuteLarge).unwrap();
+        assert_eq!(budget.spent(), 20.0);
+        assert!(budget.charge(Endpoint::ExecuteLarge).is_err());
+
+        budget.set_costs(CreditCosts::new().with_cost(Endpoint::ExecuteLarge, 5.0));
+        clone.charge(Endpoint::ExecuteLarge).unwrap();
+        assert_eq!(budget.spent(), 25.0);
+
+        budget.reset();
+        assert_eq!(clone.remaining(), 30.0);
+    }
+}
@@ -1,5 +1,6 @@
 //! HTTP client for the Dune API
 
+use crate::budget::{CreditBudget, Endpoint};
 use crate::error::{self, Error, Result};
 use crate::executions::ExecutionsApi;
 use crate::matviews::MatviewsApi;
@@ -80,6 +81,7 @@ impl Config {
 pub struct Client {
     http: reqwest::Client,
     base_url: String,
+    budget: Option<CreditBudget>,
 }
 
 impl Client {
@@ -121,6 +123,7 @@ impl Client {
         Ok(Self {
             http,
             base_url: config.base_url,
+            budget: None,
         })
     }
 
@@ -132,6 +135,61 @@ impl Client {
         Self::new(&api_key)
     }
 
+    /// Refuse calls that would spend more than `max_credits`
+    ///
+    /// Costs are estimated with the default [`CreditCosts`](crate::CreditCosts)
+    /// table; use [`Client::with_budget`] to supply a custom one. Clones of
+    /// the client share the budget.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example() -> dnapi::error::Result<()> {
+    /// let client = dnapi::Client::new("your-api-key")?.with_credit_budget(500.0);
+    /// let result = client.executions().run_query(1234, None).await?;
+    /// println!("{:?} credits left", client.remaining_credits());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_credit_budget(self, max_credits: f64) -> Self {
+        self.with_budget(CreditBudget::new(max_credits))
+    }
+
+    /// Attach a credit budget
+    #[must_use]
+    pub fn with_budget(mut self, budget: CreditBudget) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// The attached credit budget, if any
+    #[must_use]
+    pub fn credit_budget(&self) -> Option<&CreditBudget> {
+        self.budget.as_ref()
+    }
+
+    /// Credits left in the budget (`None` without a budget)
+    #[must_use]
+    pub fn remaining_credits(&self) -> Option<f64> {
+        self.budget.as_ref().map(CreditBudget::remaining)
+    }
+
+    /// Reset spent credits to zero (no-op without a budget)
+    pub fn reset_credit_budget(&self) {
+        if let Some(budget) = &self.budget {
+            budget.reset();
+        }
+    }
+
+    /// Charge the budget for a call before sending it
+    pub(crate) fn charge(&self, endpoint: Endpoint) -> Result<()> {
+        match &self.budget {
+            Some(budget) => budget.charge(endpoint),
+            None => Ok(()),
+        }
+    }
+
     /// Get the HTTP client
     pub(crate) fn http(&self) -> &reqwest::Client {
         &self.http
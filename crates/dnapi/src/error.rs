@@ -3,6 +3,8 @@
 //! This module provides the error types for the Dune API client,
 //! built on top of the shared `ApiError` infrastructure.
 
+use crate::budget::Endpoint;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 pub use yldfi_common::api::ApiError;
 
@@ -28,6 +30,26 @@ pub enum DomainError {
     /// Resource not found
     #[error("Resource not found: {0}")]
     NotFound(String),
+
+    /// Call refused by the client-side credit budget
+    #[error("Credit budget exceeded: {endpoint:?} costs {cost} credits, {remaining} remaining")]
+    BudgetExceeded {
+        /// Endpoint that was refused
+        endpoint: Endpoint,
+        /// Estimated cost of the call
+        cost: f64,
+        /// Credits left in the budget
+        remaining: f64,
+    },
+
+    /// A query file for sync could not be read, parsed or written
+    #[error("{}: {message}", path.display())]
+    QueryFile {
+        /// Offending file (or directory)
+        path: PathBuf,
+        /// What went wrong
+        message: String,
+    },
 }
 
 /// Error type for Dune API operations
@@ -64,3 +86,21 @@ pub fn insufficient_credits() -> Error {
 pub fn not_found(resource: impl Into<String>) -> Error {
     ApiError::domain(DomainError::NotFound(resource.into()))
 }
+
+/// Create a budget exceeded error
+#[must_use]
+pub fn budget_exceeded(endpoint: Endpoint, cost: f64, remaining: f64) -> Error {
+    ApiError::domain(DomainError::BudgetExceeded {
+        endpoint,
+        cost,
+        remaining,
+    })
+}
+
+/// Create a query file error
+pub fn query_file(path: impl AsRef<Path>, message: impl Into<String>) -> Error {
+    ApiError::domain(DomainError::QueryFile {
+        path: path.as_ref().to_path_buf(),
+        message: message.into(),
+    })
+}
@@ -4,6 +4,7 @@ use super::types::{
     CancelExecutionResponse, ExecuteQueryRequest, ExecuteQueryResponse, ExecuteSqlRequest,
     ExecutionResult, ExecutionStatus, GetResultsOptions,
 };
+use crate::budget::Endpoint;
 use crate::client::Client;
 use crate::error::{self, Error, Result};
 use std::time::Duration;
@@ -31,6 +32,8 @@ impl<'a> ExecutionsApi<'a> {
         query_id: i64,
         request: &ExecuteQueryRequest,
     ) -> Result<ExecuteQueryResponse> {
+        self.client
+            .charge(Endpoint::execution(request.performance.as_deref()))?;
         let url = format!("{}/v1/query/{}/execute", self.client.base_url(), query_id);
         let response = self.client.http().post(&url).json(request).send().await?;
 
@@ -51,6 +54,8 @@ impl<'a> ExecutionsApi<'a> {
 
     /// Execute raw SQL
     pub async fn execute_sql(&self, request: &ExecuteSqlRequest) -> Result<ExecuteQueryResponse> {
+        self.client
+            .charge(Endpoint::execution(request.performance.as_deref()))?;
         let url = format!("{}/v1/sql/execute", self.client.base_url());
         let response = self.client.http().post(&url).json(request).send().await?;
 
@@ -69,6 +74,7 @@ impl<'a> ExecutionsApi<'a> {
 
     /// Get execution status
     pub async fn status(&self, execution_id: &str) -> Result<ExecutionStatus> {
+        self.client.charge(Endpoint::Status)?;
         let url = format!(
             "{}/v1/execution/{}/status",
             self.client.base_url(),
@@ -99,6 +105,7 @@ impl<'a> ExecutionsApi<'a> {
         execution_id: &str,
         options: &GetResultsOptions,
     ) -> Result<ExecutionResult> {
+        self.client.charge(Endpoint::Results)?;
         let url = format!(
             "{}/v1/execution/{}/results{}",
             self.client.base_url(),
@@ -130,6 +137,7 @@ impl<'a> ExecutionsApi<'a> {
         execution_id: &str,
         options: &GetResultsOptions,
     ) -> Result<String> {
+        self.client.charge(Endpoint::Results)?;
         let url = format!(
             "{}/v1/execution/{}/results/csv{}",
             self.client.base_url(),
@@ -151,6 +159,7 @@ impl<'a> ExecutionsApi<'a> {
 
     /// Cancel an execution
     pub async fn cancel(&self, execution_id: &str) -> Result<CancelExecutionResponse> {
+        self.client.charge(Endpoint::Cancel)?;
         let url = format!(
             "{}/v1/execution/{}/cancel",
             self.client.base_url(),
@@ -181,6 +190,7 @@ impl<'a> ExecutionsApi<'a> {
         query_id: i64,
         options: &GetResultsOptions,
     ) -> Result<ExecutionResult> {
+        self.client.charge(Endpoint::Results)?;
         let url = format!(
             "{}/v1/query/{}/results{}",
             self.client.base_url(),
@@ -212,6 +222,7 @@ impl<'a> ExecutionsApi<'a> {
         query_id: i64,
         options: &GetResultsOptions,
     ) -> Result<String> {
+        self.client.charge(Endpoint::Results)?;
         let url = format!(
             "{}/v1/query/{}/results/csv{}",
             self.client.base_url(),
@@ -27,13 +27,16 @@
 //!
 //! # Features
 //!
-//! - **Queries**: Create, read, update, archive/unarchive queries
+//! - **Queries**: Create, read, update, archive/unarchive queries, and sync
+//!   them with a directory of version-controlled `.sql` files
 //! - **Executions**: Execute queries, run SQL, get results (JSON or CSV)
 //! - **Tables**: Upload data, create tables, insert rows
 //! - **Materialized Views**: Create, refresh, manage materialized views
 //! - **Pipelines**: Execute coordinated query workflows
 //! - **Usage**: Track API consumption and credits
+//! - **Credit Budget**: Refuse calls that would exceed a credit budget
 
+pub mod budget;
 mod client;
 pub mod error;
 
@@ -44,6 +47,7 @@ pub mod queries;
 pub mod tables;
 pub mod usage;
 
+pub use budget::{CreditBudget, CreditCosts, Endpoint};
 pub use client::{Client, Config};
 pub use error::{Error, Result};
 pub use yldfi_common::http::HttpClientConfig;
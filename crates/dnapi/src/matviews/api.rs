@@ -4,6 +4,7 @@ use super::types::{
     DeleteMatviewResponse, ListMatviewsOptions, ListMatviewsResponse, Matview,
     RefreshMatviewRequest, RefreshMatviewResponse, UpsertMatviewRequest, UpsertMatviewResponse,
 };
+use crate::budget::Endpoint;
 use crate::client::Client;
 use crate::error::{self, Error, Result};
 
@@ -19,6 +20,7 @@ impl<'a> MatviewsApi<'a> {
 
     /// Create or update a materialized view
     pub async fn upsert(&self, request: &UpsertMatviewRequest) -> Result<UpsertMatviewResponse> {
+        self.client.charge(Endpoint::Matviews)?;
         let url = format!("{}/v1/materialized-views", self.client.base_url());
         let response = self.client.http().post(&url).json(request).send().await?;
 
@@ -33,6 +35,7 @@ impl<'a> MatviewsApi<'a> {
 
     /// Get a materialized view by name
     pub async fn get(&self, name: &str) -> Result<Matview> {
+        self.client.charge(Endpoint::Matviews)?;
         let url = format!("{}/v1/materialized-views/{}", self.client.base_url(), name);
         let response = self.client.http().get(&url).send().await?;
 
@@ -58,6 +61,7 @@ impl<'a> MatviewsApi<'a> {
         &self,
         options: &ListMatviewsOptions,
     ) -> Result<ListMatviewsResponse> {
+        self.client.charge(Endpoint::Matviews)?;
         let url = format!(
             "{}/v1/materialized-views{}",
             self.client.base_url(),
@@ -86,6 +90,8 @@ impl<'a> MatviewsApi<'a> {
         name: &str,
         request: &RefreshMatviewRequest,
     ) -> Result<RefreshMatviewResponse> {
+        self.client
+            .charge(Endpoint::execution(request.performance.as_deref()))?;
         let url = format!(
             "{}/v1/materialized-views/{}/refresh",
             self.client.base_url(),
@@ -106,6 +112,7 @@ impl<'a> MatviewsApi<'a> {
 
     /// Delete a materialized view
     pub async fn delete(&self, name: &str) -> Result<DeleteMatviewResponse> {
+        self.client.charge(Endpoint::Matviews)?;
         let url = format!("{}/v1/materialized-views/{}", self.client.base_url(), name);
         let response = self.client.http().delete(&url).send().await?;
 
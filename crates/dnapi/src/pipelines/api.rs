@@ -1,6 +1,7 @@
 //! Pipelines API implementation
 
 use super::types::{ExecutePipelineRequest, ExecutePipelineResponse, PipelineExecutionStatus};
+use crate::budget::Endpoint;
 use crate::client::Client;
 use crate::error::{self, Error, Result};
 
@@ -19,6 +20,7 @@ impl<'a> PipelinesApi<'a> {
         &self,
         request: &ExecutePipelineRequest,
     ) -> Result<ExecutePipelineResponse> {
+        self.client.charge(Endpoint::PipelineExecute)?;
         let url = format!("{}/v1/pipelines/execute", self.client.base_url());
         let response = self.client.http().post(&url).json(request).send().await?;
 
@@ -37,6 +39,7 @@ impl<'a> PipelinesApi<'a> {
 
     /// Get pipeline execution status
     pub async fn status(&self, pipeline_execution_id: &str) -> Result<PipelineExecutionStatus> {
+        self.client.charge(Endpoint::Status)?;
         let url = format!(
             "{}/v1/pipelines/executions/{}/status",
             self.client.base_url(),
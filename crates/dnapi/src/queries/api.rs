@@ -1,11 +1,18 @@
 //! Queries API implementation
 
+use super::sync::{diff_query, load_query_files, QueryFile, SyncAction, SyncChange, SyncReport};
 use super::types::{
     CreateQueryRequest, CreateQueryResponse, ListQueriesOptions, ListQueriesResponse, Query,
-    UpdateQueryRequest, UpdateQueryResponse,
+    QuerySpec, UpdateQueryRequest, UpdateQueryResponse,
 };
+use crate::budget::Endpoint;
 use crate::client::Client;
 use crate::error::{self, Error, Result};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Page size used when listing every query
+const LIST_PAGE_SIZE: u32 = 100;
 
 /// Queries API
 pub struct QueriesApi<'a> {
@@ -17,8 +24,18 @@ impl<'a> QueriesApi<'a> {
         Self { client }
     }
 
-    /// Create a new query
-    pub async fn create(&self, request: &CreateQueryRequest) -> Result<CreateQueryResponse> {
+    /// Create a new query from a spec
+    pub async fn create(&self, spec: &QuerySpec) -> Result<CreateQueryResponse> {
+        self.create_with_request(&CreateQueryRequest::from(spec))
+            .await
+    }
+
+    /// Create a new query from a raw request
+    pub async fn create_with_request(
+        &self,
+        request: &CreateQueryRequest,
+    ) -> Result<CreateQueryResponse> {
+        self.client.charge(Endpoint::Queries)?;
         let url = format!("{}/v1/query", self.client.base_url());
         let response = self.client.http().post(&url).json(request).send().await?;
 
@@ -33,6 +50,7 @@ impl<'a> QueriesApi<'a> {
 
     /// Get a query by ID
     pub async fn get(&self, query_id: i64) -> Result<Query> {
+        self.client.charge(Endpoint::Queries)?;
         let url = format!("{}/v1/query/{}", self.client.base_url(), query_id);
         let response = self.client.http().get(&url).send().await?;
 
@@ -47,12 +65,19 @@ impl<'a> QueriesApi<'a> {
         }
     }
 
-    /// Update a query
-    pub async fn update(
+    /// Replace a query's name, SQL, parameters and visibility with a spec
+    pub async fn update(&self, query_id: i64, spec: &QuerySpec) -> Result<UpdateQueryResponse> {
+        self.update_with_request(query_id, &UpdateQueryRequest::from(spec))
+            .await
+    }
+
+    /// Update a query with a raw (partial) request
+    pub async fn update_with_request(
         &self,
         query_id: i64,
         request: &UpdateQueryRequest,
     ) -> Result<UpdateQueryResponse> {
+        self.client.charge(Endpoint::Queries)?;
         let url = format!("{}/v1/query/{}", self.client.base_url(), query_id);
         let response = self.client.http().patch(&url).json(request).send().await?;
 
@@ -77,6 +102,7 @@ impl<'a> QueriesApi<'a> {
         &self,
         options: &ListQueriesOptions,
     ) -> Result<ListQueriesResponse> {
+        self.client.charge(Endpoint::Queries)?;
         let url = format!(
             "{}/v1/queries{}",
             self.client.base_url(),
@@ -95,6 +121,7 @@ impl<'a> QueriesApi<'a> {
 
     /// Archive a query
     pub async fn archive(&self, query_id: i64) -> Result<()> {
+        self.client.charge(Endpoint::Queries)?;
         let url = format!("{}/v1/query/{}/archive", self.client.base_url(), query_id);
         let response = self.client.http().post(&url).send().await?;
 
@@ -111,6 +138,7 @@ impl<'a> QueriesApi<'a> {
 
     /// Unarchive a query
     pub async fn unarchive(&self, query_id: i64) -> Result<()> {
+        self.client.charge(Endpoint::Queries)?;
         let url = format!("{}/v1/query/{}/unarchive", self.client.base_url(), query_id);
         let response = self.client.http().post(&url).send().await?;
 
@@ -127,6 +155,7 @@ impl<'a> QueriesApi<'a> {
 
     /// Make a query private
     pub async fn make_private(&self, query_id: i64) -> Result<()> {
+        self.client.charge(Endpoint::Queries)?;
         let url = format!("{}/v1/query/{}/private", self.client.base_url(), query_id);
         let response = self.client.http().post(&url).send().await?;
 
@@ -143,6 +172,7 @@ impl<'a> QueriesApi<'a> {
 
     /// Make a query public (unprivate)
     pub async fn make_public(&self, query_id: i64) -> Result<()> {
+        self.client.charge(Endpoint::Queries)?;
         let url = format!("{}/v1/query/{}/unprivate", self.client.base_url(), query_id);
         let response = self.client.http().post(&url).send().await?;
 
@@ -156,4 +186,205 @@ impl<'a> QueriesApi<'a> {
             Err(Error::api(status, message))
         }
     }
+
+    /// List every query, following pagination
+    pub async fn list_all(&self) -> Result<Vec<Query>> {
+        let mut queries = Vec::new();
+        let mut options = ListQueriesOptions {
+            limit: Some(LIST_PAGE_SIZE),
+            offset: None,
+        };
+        loop {
+            let page = self.list_with_options(&options).await?;
+            let empty = page.queries.is_empty();
+            queries.extend(page.queries);
+            match page.next_offset {
+                Some(next) if !empty && Some(next) != options.offset => options.offset = Some(next),
+                _ => return Ok(queries),
+            }
+        }
+    }
+
+    /// Sync a directory of `.sql` query files with Dune
+    ///
+    /// Files with an `id` are compared with that query and updated when
+    /// they differ (see [`diff_query`](super::diff_query)); archived queries
+    /// are unarchived. Files without an `id` are matched to a remote query
+    /// of the same name, or created. Newly known ids are written back into
+    /// the files, so a second run reports everything unchanged.
+    ///
+    /// Each remote query is linked to at most one file: a second file with
+    /// the same `id`, or one that would link to (or create) a query already
+    /// claimed by another file, is reported as
+    /// [`SyncAction::Failed`](super::SyncAction::Failed). Errors for one
+    /// file don't stop the others; only failing to read the directory or
+    /// list the remote queries fails the whole sync.
+    ///
+    /// With `dry_run`, the report describes what would happen and neither
+    /// Dune nor the files are modified.
+    pub async fn sync(&self, dir: impl AsRef<Path>, dry_run: bool) -> Result<SyncReport> {
+        let dir = dir.as_ref();
+        let mut files = load_query_files(dir)?;
+
+        // Remote query id -> the file it is linked to. Explicit ids claim
+        // first so a name match can't take a query another file names by id.
+        let mut claimed: HashMap<i64, PathBuf> = HashMap::new();
+        let mut conflicts: HashMap<PathBuf, Error> = HashMap::new();
+        for file in &files {
+            if let Some(id) = file.id {
+                if let Some(other) = claimed.get(&id) {
+                    let message = format!("query {id} is also used by {}", other.display());
+                    conflicts.insert(file.path.clone(), error::query_file(&file.path, message));
+                } else {
+                    claimed.insert(id, file.path.clone());
+                }
+            }
+        }
+
+        // Remote ids by name, only needed to link files without an id
+        let mut by_name: HashMap<String, Vec<i64>> = HashMap::new();
+        if files.iter().any(|f| f.id.is_none()) {
+            for query in self.list_all().await? {
+                by_name.entry(query.name).or_default().push(query.query_id);
+            }
+        }
+        // Names of queries this run creates -> the file creating them
+        let mut creating: HashMap<String, PathBuf> = HashMap::new();
+
+        let mut changes = Vec::with_capacity(files.len());
+        for file in &mut files {
+            let linked = match conflicts.remove(&file.path) {
+                Some(e) => Err(e),
+                None => link_file(file, &by_name, &mut claimed, &mut creating),
+            };
+            let change = match linked {
+                Ok(linked_id) => self.sync_file(file, linked_id, dry_run).await,
+                Err(e) => Err(e),
+            };
+            changes.push(change.unwrap_or_else(|e| SyncChange {
+                path: file.path.clone(),
+                name: file.spec.name.clone(),
+                query_id: file.id,
+                action: SyncAction::Failed,
+                changed: Vec::new(),
+                id_recorded: false,
+                error: Some(e.to_string()),
+            }));
+        }
+
+        Ok(SyncReport { dry_run, changes })
+    }
+
+    /// Bring one file's query in line with the file, given the remote id
+    /// it is linked to (`None` to create it)
+    async fn sync_file(
+        &self,
+        file: &mut QueryFile,
+        linked_id: Option<i64>,
+        dry_run: bool,
+    ) -> Result<SyncChange> {
+        let needs_id = file.id.is_none();
+        match linked_id {
+            Some(id) => {
+                let remote = self.get(id).await?;
+                let changed = diff_query(&file.spec, &remote);
+                if !dry_run {
+                    if changed.contains(&"archived") {
+                        self.unarchive(id).await?;
+                    }
+                    if changed.iter().any(|field| *field != "archived") {
+                        self.update(id, &file.spec).await?;
+                    }
+                    if needs_id {
+                        file.write_id(id)?;
+                    }
+                }
+                Ok(SyncChange {
+                    path: file.path.clone(),
+                    name: file.spec.name.clone(),
+                    query_id: Some(id),
+                    action: if changed.is_empty() {
+                        SyncAction::Unchanged
+                    } else {
+                        SyncAction::Update
+                    },
+                    changed,
+                    id_recorded: needs_id && !dry_run,
+                    error: None,
+                })
+            }
+            None => {
+                let query_id = if dry_run {
+                    None
+                } else {
+                    let id = self.create(&file.spec).await?.query_id;
+                    file.write_id(id).map_err(|e| {
+                        error::query_file(
+                            &file.path,
+                            format!("created query {id} but could not record its id: {e}"),
+                        )
+                    })?;
+                    Some(id)
+                };
+                Ok(SyncChange {
+                    path: file.path.clone(),
+                    name: file.spec.name.clone(),
+                    query_id,
+                    action: SyncAction::Create,
+                    changed: Vec::new(),
+                    id_recorded: !dry_run,
+                    error: None,
+                })
+            }
+        }
+    }
+}
+
+/// The remote query a file is linked to, claiming it for the file
+///
+/// Files with an id were claimed up front; files without one link to the
+/// only remote query of the same name, or to none (a create). A query or
+/// new name already claimed by another file is an error.
+fn link_file(
+    file: &QueryFile,
+    by_name: &HashMap<String, Vec<i64>>,
+    claimed: &mut HashMap<i64, PathBuf>,
+    creating: &mut HashMap<String, PathBuf>,
+) -> Result<Option<i64>> {
+    if file.id.is_some() {
+        return Ok(file.id);
+    }
+    let name = &file.spec.name;
+    match by_name.get(name).map(Vec::as_slice) {
+        None | Some([]) => {
+            if let Some(other) = creating.get(name) {
+                return Err(error::query_file(
+                    &file.path,
+                    format!("{} also creates a query named '{name}'", other.display()),
+                ));
+            }
+            creating.insert(name.clone(), file.path.clone());
+            Ok(None)
+        }
+        Some([id]) => {
+            if let Some(other) = claimed.get(id) {
+                return Err(error::query_file(
+                    &file.path,
+                    format!(
+                        "query {id} named '{name}' is already linked to {}; add `-- id: <id>` to one of them",
+                        other.display()
+                    ),
+                ));
+            }
+            claimed.insert(*id, file.path.clone());
+            Ok(Some(*id))
+        }
+        Some(ids) => Err(error::query_file(
+            &file.path,
+            format!(
+                "{} remote queries are named '{name}'; add `-- id: <id>` to choose one",
+                ids.len()
+            ),
+        )),
+    }
 }
@@ -1,7 +1,9 @@
 //! Queries API for managing Dune queries
 
 mod api;
+mod sync;
 mod types;
 
 pub use api::QueriesApi;
+pub use sync::*;
 pub use types::*;
@@ -0,0 +1,533 @@
+//! Version-controlled query files
+//!
+//! [`QueriesApi::sync`](super::QueriesApi::sync) keeps Dune queries in step
+//! with a directory of `.sql` files. Each file starts with a front-matter
+//! header written as SQL comments:
+//!
+//! ```sql
+//! -- ---
+//! -- name: DEX volume by day
+//! -- id: 3493826
+//! -- private: true
+//! -- param: chain text = ethereum
+//! -- param: days number = 30
+//! -- param: side enum(buy, sell) = buy
+//! -- ---
+//! SELECT * FROM dex.trades WHERE blockchain = '{{chain}}'
+//! ```
+//!
+//! `name` is required. `id` is written into the header once a query has been
+//! created (or matched to a remote query by name), so running the sync again
+//! updates the same query instead of creating a duplicate.
+
+use super::types::{ParamDef, ParamType, Query, QuerySpec};
+use crate::error::{query_file, Result};
+use std::path::{Path, PathBuf};
+
+/// Line that opens and closes the front matter (after the `--` prefix)
+const FENCE: &str = "---";
+
+/// A parsed `.sql` query file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryFile {
+    /// Where the file was read from
+    pub path: PathBuf,
+    /// Remote query id, if the file has been synced before
+    pub id: Option<i64>,
+    /// Query definition
+    pub spec: QuerySpec,
+}
+
+impl QueryFile {
+    /// Parse a query file's contents
+    pub fn parse(path: impl AsRef<Path>, contents: &str) -> Result<Self> {
+        let path = path.as_ref();
+        let err = |message: String| query_file(path, message);
+
+        let mut lines = contents
+            .lines()
+            .enumerate()
+            .skip_while(|(_, l)| l.trim().is_empty());
+        match lines.next() {
+            Some((_, line)) if comment_body(line) == Some(FENCE) => {}
+            _ => return Err(err("missing front matter (expected `-- ---` first)".into())),
+        }
+
+        let mut name = None;
+        let mut id = None;
+        let mut is_private = false;
+        let mut parameters: Vec<ParamDef> = Vec::new();
+        let mut sql_start = None;
+
+        for (index, line) in lines {
+            let line_no = index + 1;
+            let Some(body) = comment_body(line) else {
+                return Err(err(format!(
+                    "line {line_no}: front matter is not closed with `-- ---`"
+                )));
+            };
+            if body == FENCE {
+                sql_start = Some(index + 1);
+                break;
+            }
+            if body.is_empty() {
+                continue;
+            }
+
+            let (key, value) = body
+                .split_once(':')
+                .map(|(k, v)| (k.trim(), v.trim()))
+                .ok_or_else(|| err(format!("line {line_no}: expected `key: value`")))?;
+            match key {
+                "name" if !value.is_empty() => name = Some(value.to_string()),
+                "id" => {
+                    id =
+                        Some(value.parse::<i64>().map_err(|_| {
+                            err(format!("line {line_no}: invalid query id '{value}'"))
+                        })?)
+                }
+                "private" => {
+                    is_private = value.parse::<bool>().map_err(|_| {
+                        err(format!("line {line_no}: private must be true or false"))
+                    })?
+                }
+                "param" => {
+                    let param =
+                        parse_param(value).map_err(|e| err(format!("line {line_no}: {e}")))?;
+                    if parameters.iter().any(|p| p.key == param.key) {
+                        return Err(err(format!(
+                            "line {line_no}: duplicate parameter '{}'",
+                            param.key
+                        )));
+                    }
+                    parameters.push(param);
+                }
+                other => {
+                    return Err(err(format!(
+                        "line {line_no}: unknown front matter key '{other}'"
+                    )))
+                }
+            }
+        }
+
+        let sql_start =
+            sql_start.ok_or_else(|| err("front matter is not closed with `-- ---`".into()))?;
+        let name = name.ok_or_else(|| err("front matter has no name".into()))?;
+        let sql = contents
+            .lines()
+            .skip(sql_start)
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string();
+        if sql.is_empty() {
+            return Err(err("no SQL after the front matter".into()));
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            id,
+            spec: QuerySpec {
+                name,
+                sql,
+                parameters,
+                is_private,
+            },
+        })
+    }
+
+    /// Read and parse a query file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents =
+            std::fs::read_to_string(path).map_err(|e| query_file(path, e.to_string()))?;
+        Self::parse(path, &contents)
+    }
+
+    /// Record `id` in the file's front matter
+    pub fn write_id(&mut self, id: i64) -> Result<()> {
+        let contents = std::fs::read_to_string(&self.path)
+            .map_err(|e| query_file(&self.path, e.to_string()))?;
+        std::fs::write(&self.path, set_front_matter_id(&contents, id))
+            .map_err(|e| query_file(&self.path, e.to_string()))?;
+        self.id = Some(id);
+        Ok(())
+    }
+}
+
+/// Text of a `--` comment line, trimmed; `None` for anything else
+fn comment_body(line: &str) -> Option<&str> {
+    line.trim().strip_prefix("--").map(str::trim)
+}
+
+/// Parse `key type[(options)] [= default]`
+fn parse_param(value: &str) -> std::result::Result<ParamDef, String> {
+    let (decl, default) = match value.split_once('=') {
+        Some((decl, default)) => (decl.trim(), Some(unquote(default.trim()))),
+        None => (value.trim(), None),
+    };
+    let (key, type_spec) = decl
+        .split_once(char::is_whitespace)
+        .map(|(k, t)| (k.trim(), t.trim()))
+        .ok_or_else(|| format!("expected `param: <key> <type>`, got '{value}'"))?;
+
+    let (type_name, options) = match type_spec.split_once('(') {
+        Some((type_name, rest)) => {
+            let options = rest
+                .strip_suffix(')')
+                .ok_or_else(|| format!("unclosed options in '{type_spec}'"))?;
+            let options: Vec<String> = options
+                .split(',')
+                .map(|o| unquote(o.trim()))
+                .filter(|o| !o.is_empty())
+                .collect();
+            (type_name.trim(), options)
+        }
+        None => (type_spec, Vec::new()),
+    };
+
+    let param_type: ParamType = type_name.parse()?;
+    match param_type {
+        ParamType::Enum if options.is_empty() => {
+            return Err(format!("enum parameter '{key}' needs options: enum(a, b)"))
+        }
+        ParamType::Enum => {}
+        _ if !options.is_empty() => {
+            return Err(format!("only enum parameters take options ('{key}')"))
+        }
+        _ => {}
+    }
+    if let (Some(default), false) = (&default, options.is_empty()) {
+        if !options.contains(default) {
+            return Err(format!("default '{default}' of '{key}' is not an option"));
+        }
+    }
+
+    Ok(ParamDef {
+        key: key.to_string(),
+        param_type,
+        default,
+        enum_options: options,
+    })
+}
+
+/// Strip one pair of surrounding double quotes
+fn unquote(value: &str) -> String {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value)
+        .to_string()
+}
+
+/// Insert or replace the `id:` line of a file's front matter
+///
+/// The id goes right after the `name:` line when there is none yet. Every
+/// other byte of the file is kept as it was.
+#[must_use]
+pub fn set_front_matter_id(contents: &str, id: i64) -> String {
+    let mut out = String::with_capacity(contents.len() + 24);
+    let mut fences = 0;
+    let mut written = false;
+    let mut name_line_end = None;
+
+    for line in contents.split_inclusive('\n') {
+        let body = comment_body(line.trim_end_matches(['\r', '\n']));
+        if fences == 1 && !written {
+            match body.and_then(|b| b.split_once(':')).map(|(k, _)| k.trim()) {
+                Some("id") => {
+                    let newline = &line[line.trim_end_matches(['\r', '\n']).len()..];
+                    out.push_str(&format!("-- id: {id}{newline}"));
+                    written = true;
+                    continue;
+                }
+                Some("name") => name_line_end = Some(out.len() + line.len()),
+                _ => {}
+            }
+        }
+        if body == Some(FENCE) {
+            fences += 1;
+        }
+        out.push_str(line);
+    }
+
+    if !written {
+        let insert_at = name_line_end.unwrap_or(0);
+        let newline = if contents.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+        let prefix = if insert_at > 0 && !out[..insert_at].ends_with('\n') {
+            newline
+        } else {
+            ""
+        };
+        out.insert_str(insert_at, &format!("{prefix}-- id: {id}{newline}"));
+    }
+    out
+}
+
+/// Load every `.sql` file directly in `dir`, sorted by path
+pub fn load_query_files(dir: impl AsRef<Path>) -> Result<Vec<QueryFile>> {
+    let dir = dir.as_ref();
+    let entries = std::fs::read_dir(dir).map_err(|e| query_file(dir, e.to_string()))?;
+
+    let mut paths = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(|e| query_file(dir, e.to_string()))?.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext == "sql") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    paths.iter().map(QueryFile::load).collect()
+}
+
+/// Collapse whitespace runs so formatting-only edits are not changes
+#[must_use]
+pub fn normalize_sql(sql: &str) -> String {
+    sql.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Fields of `remote` that differ from `spec`
+///
+/// SQL is compared after [`normalize_sql`] and parameters regardless of
+/// order. An archived remote query reports `"archived"`.
+#[must_use]
+pub fn diff_query(spec: &QuerySpec, remote: &Query) -> Vec<&'static str> {
+    let mut changed = Vec::new();
+    if spec.name != remote.name {
+        changed.push("name");
+    }
+    if normalize_sql(&spec.sql) != normalize_sql(remote.query_sql.as_deref().unwrap_or_default()) {
+        changed.push("sql");
+    }
+
+    let mut local = spec.parameters.clone();
+    local.sort_by(|a, b| a.key.cmp(&b.key));
+    let remote_params: Option<Vec<ParamDef>> = remote
+        .parameters
+        .iter()
+        .map(ParamDef::from_parameter)
+        .collect();
+    let params_match = remote_params.is_some_and(|mut remote_params| {
+        remote_params.sort_by(|a, b| a.key.cmp(&b.key));
+        remote_params == local
+    });
+    if !params_match {
+        changed.push("parameters");
+    }
+
+    if spec.is_private != remote.is_private.unwrap_or(false) {
+        changed.push("private");
+    }
+    if remote.is_archived.unwrap_or(false) {
+        changed.push("archived");
+    }
+    changed
+}
+
+/// What a sync did (or would do) with one file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncAction {
+    /// No remote query yet: create one
+    Create,
+    /// Remote query differs: update it
+    Update,
+    /// Remote query already matches
+    Unchanged,
+    /// The file could not be synced (see [`SyncChange::error`])
+    Failed,
+}
+
+/// Outcome for one query file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncChange {
+    /// Query file
+    pub path: PathBuf,
+    /// Query name
+    pub name: String,
+    /// Remote query id (`None` for a create in a dry run)
+    pub query_id: Option<i64>,
+    /// Action taken
+    pub action: SyncAction,
+    /// Fields that differ from the remote query (see [`diff_query`])
+    pub changed: Vec<&'static str>,
+    /// Whether the remote id was written to the file (never in a dry run)
+    pub id_recorded: bool,
+    /// Why the file failed, for [`SyncAction::Failed`]
+    pub error: Option<String>,
+}
+
+/// Result of [`QueriesApi::sync`](super::QueriesApi::sync)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncReport {
+    /// Nothing was changed remotely or on disk
+    pub dry_run: bool,
+    /// One entry per query file, in path order
+    pub changes: Vec<SyncChange>,
+}
+
+impl SyncReport {
+    /// Number of files with the given action
+    #[must_use]
+    pub fn count(&self, action: SyncAction) -> usize {
+        self.changes.iter().filter(|c| c.action == action).count()
+    }
+
+    /// Whether any query was (or would be) created or updated
+    #[must_use]
+    pub fn has_changes(&self) -> bool {
+        self.changes
+            .iter()
+            .any(|c| matches!(c.action, SyncAction::Create | SyncAction::Update) || c.id_recorded)
+    }
+
+    /// Files that could not be synced
+    pub fn failures(&self) -> impl Iterator<Item = &SyncChange> {
+        self.changes
+            .iter()
+            .filter(|c| c.action == SyncAction::Failed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::queries::Parameter;
+
+    const FILE: &str = "\
+-- ---
+-- name: DEX volume by day
+-- private: true
+-- param: chain text = ethereum
+-- param: days number = 30
+-- param: side enum(buy, \"sell\") = buy
+-- ---
+SELECT *
+FROM dex.trades
+WHERE blockchain = '{{chain}}'
+";
+
+    fn remote(sql: &str, parameters: Vec<Parameter>) -> Query {
+        serde_json::from_value(serde_json::json!({
+            "query_id": 7,
+            "name": "DEX volume by day",
+            "query_sql": sql,
+            "parameters": parameters,
+            "is_private": true,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_front_matter() {
+        let file = QueryFile::parse("volume.sql", FILE).unwrap();
+        assert_eq!(file.id, None);
+        assert_eq!(file.spec.name, "DEX volume by day");
+        assert!(file.spec.is_private);
+        assert_eq!(
+            file.spec.sql,
+            "SELECT *\nFROM dex.trades\nWHERE blockchain = '{{chain}}'"
+        );
+        assert_eq!(
+            file.spec.parameters,
+            [
+                ParamDef::text("chain").with_default("ethereum"),
+                ParamDef::number("days").with_default("30"),
+                ParamDef::enumeration("side", vec!["buy".into(), "sell".into()])
+                    .with_default("buy"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_headers() {
+        let cases = [
+            ("SELECT 1", "missing front matter"),
+            ("-- ---\n-- name: x\nSELECT 1", "not closed"),
+            ("-- ---\n-- id: 1\n-- ---\nSELECT 1", "no name"),
+            (
+                "-- ---\n-- name: x\n-- id: abc\n-- ---\nSELECT 1",
+                "invalid query id",
+            ),
+            (
+                "-- ---\n-- name: x\n-- owner: me\n-- ---\nSELECT 1",
+                "unknown front matter key",
+            ),
+            (
+                "-- ---\n-- name: x\n-- param: a enum\n-- ---\nSELECT 1",
+                "needs options",
+            ),
+            (
+                "-- ---\n-- name: x\n-- param: a text(b)\n-- ---\nSELECT 1",
+                "only enum",
+            ),
+            (
+                "-- ---\n-- name: x\n-- param: a blob\n-- ---\nSELECT 1",
+                "unknown parameter type",
+            ),
+            (
+                "-- ---\n-- name: x\n-- param: a enum(b) = c\n-- ---\nSELECT 1",
+                "not an option",
+            ),
+            (
+                "-- ---\n-- name: x\n-- param: a text\n-- param: a number\n-- ---\nSELECT 1",
+                "duplicate parameter",
+            ),
+            ("-- ---\n-- name: x\n-- ---\n\n", "no SQL"),
+        ];
+        for (contents, expected) in cases {
+            let err = QueryFile::parse("q.sql", contents).unwrap_err().to_string();
+            assert!(err.contains(expected), "{contents:?}: {err}");
+            assert!(err.starts_with("q.sql: "), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_set_front_matter_id() {
+        let with_id = set_front_matter_id(FILE, 42);
+        assert_eq!(
+            with_id.lines().take(3).collect::<Vec<_>>(),
+            ["-- ---", "-- name: DEX volume by day", "-- id: 42"]
+        );
+        assert_eq!(QueryFile::parse("q.sql", &with_id).unwrap().id, Some(42));
+        // Everything else is untouched
+        assert_eq!(with_id.replace("-- id: 42\n", ""), FILE);
+
+        // Replaces an existing id in place; SQL mentioning "id:" is left alone
+        let updated = set_front_matter_id(
+            "-- ---\r\n-- id: 1\r\n-- name: x\r\n-- ---\r\n-- id: not header\r\nSELECT 1\r\n",
+            2,
+        );
+        assert_eq!(
+            updated,
+            "-- ---\r\n-- id: 2\r\n-- name: x\r\n-- ---\r\n-- id: not header\r\nSELECT 1\r\n"
+        );
+    }
+
+    #[test]
+    fn test_diff_query() {
+        let spec = QueryFile::parse("q.sql", FILE).unwrap().spec;
+        // Parameters in a different order, SQL reformatted
+        let mut params: Vec<Parameter> =
+            spec.parameters.iter().map(ParamDef::to_parameter).collect();
+        params.reverse();
+        let same = remote(
+            "SELECT * FROM dex.trades\n  WHERE blockchain = '{{chain}}'",
+            params.clone(),
+        );
+        assert!(diff_query(&spec, &same).is_empty());
+
+        let mut changed = remote("SELECT 1", params[1..].to_vec());
+        changed.name = "Old name".into();
+        changed.is_private = Some(false);
+        changed.is_archived = Some(true);
+        assert_eq!(
+            diff_query(&spec, &changed),
+            ["name", "sql", "parameters", "private", "archived"]
+        );
+    }
+}
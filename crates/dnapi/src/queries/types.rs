@@ -2,6 +2,9 @@
 
 use serde::{Deserialize, Serialize};
 
+use std::fmt;
+use std::str::FromStr;
+
 /// Query parameter definition
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Parameter {
@@ -11,23 +14,241 @@ pub struct Parameter {
     #[serde(rename = "type")]
     pub param_type: Option<String>,
     /// Default value
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub value: Option<String>,
     /// Multiple values (for multiselect)
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub values: Vec<String>,
     /// Description
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     /// Enum options
-    #[serde(default, rename = "enumOptions")]
+    #[serde(default, rename = "enumOptions", skip_serializing_if = "Vec::is_empty")]
     pub enum_options: Vec<String>,
     /// Allow freeform input for enum
-    #[serde(rename = "isFreeformAllowed")]
+    #[serde(rename = "isFreeformAllowed", skip_serializing_if = "Option::is_none")]
     pub is_freeform_allowed: Option<bool>,
     /// Allow multiple selections
-    #[serde(rename = "isMultiselect")]
+    #[serde(rename = "isMultiselect", skip_serializing_if = "Option::is_none")]
     pub is_multiselect: Option<bool>,
 }
 
+/// Type of a query parameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParamType {
+    /// Free text
+    Text,
+    /// Number
+    Number,
+    /// Date and time (`YYYY-MM-DD hh:mm:ss`)
+    Datetime,
+    /// One of a fixed list of options
+    Enum,
+}
+
+impl ParamType {
+    /// Name used by the API
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Text => "text",
+            Self::Number => "number",
+            Self::Datetime => "datetime",
+            Self::Enum => "enum",
+        }
+    }
+}
+
+impl fmt::Display for ParamType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ParamType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "number" => Ok(Self::Number),
+            "datetime" | "date" => Ok(Self::Datetime),
+            "enum" => Ok(Self::Enum),
+            other => Err(format!("unknown parameter type '{other}'")),
+        }
+    }
+}
+
+/// Typed parameter definition for a [`QuerySpec`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParamDef {
+    /// Parameter key, as used in `{{key}}` in the SQL
+    pub key: String,
+    /// Parameter type
+    pub param_type: ParamType,
+    /// Default value
+    pub default: Option<String>,
+    /// Allowed values (enum parameters only)
+    pub enum_options: Vec<String>,
+}
+
+impl ParamDef {
+    /// Create a parameter definition without a default
+    #[must_use]
+    pub fn new(key: impl Into<String>, param_type: ParamType) -> Self {
+        Self {
+            key: key.into(),
+            param_type,
+            default: None,
+            enum_options: Vec::new(),
+        }
+    }
+
+    /// Text parameter
+    #[must_use]
+    pub fn text(key: impl Into<String>) -> Self {
+        Self::new(key, ParamType::Text)
+    }
+
+    /// Number parameter
+    #[must_use]
+    pub fn number(key: impl Into<String>) -> Self {
+        Self::new(key, ParamType::Number)
+    }
+
+    /// Datetime parameter
+    #[must_use]
+    pub fn datetime(key: impl Into<String>) -> Self {
+        Self::new(key, ParamType::Datetime)
+    }
+
+    /// Enum parameter with its allowed values
+    #[must_use]
+    pub fn enumeration(key: impl Into<String>, options: Vec<String>) -> Self {
+        Self {
+            enum_options: options,
+            ..Self::new(key, ParamType::Enum)
+        }
+    }
+
+    /// Set the default value
+    #[must_use]
+    pub fn with_default(mut self, default: impl Into<String>) -> Self {
+        self.default = Some(default.into());
+        self
+    }
+
+    /// Convert to the API representation
+    #[must_use]
+    pub fn to_parameter(&self) -> Parameter {
+        Parameter {
+            key: self.key.clone(),
+            param_type: Some(self.param_type.as_str().to_string()),
+            value: self.default.clone(),
+            values: Vec::new(),
+            description: None,
+            enum_options: self.enum_options.clone(),
+            is_freeform_allowed: None,
+            is_multiselect: None,
+        }
+    }
+
+    /// Read a definition back from the API representation
+    ///
+    /// Returns `None` when the type is missing or not one of [`ParamType`].
+    #[must_use]
+    pub fn from_parameter(parameter: &Parameter) -> Option<Self> {
+        Some(Self {
+            key: parameter.key.clone(),
+            param_type: parameter.param_type.as_deref()?.parse().ok()?,
+            default: parameter.value.clone(),
+            enum_options: parameter.enum_options.clone(),
+        })
+    }
+}
+
+/// Everything needed to create or fully update a query
+///
+/// ```
+/// use dnapi::queries::{ParamDef, QuerySpec};
+///
+/// let spec = QuerySpec::new("Daily volume", "SELECT * FROM dex.trades WHERE blockchain = '{{chain}}'")
+///     .with_param(ParamDef::text("chain").with_default("ethereum"))
+///     .private(true);
+/// assert_eq!(spec.parameters.len(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuerySpec {
+    /// Query name
+    pub name: String,
+    /// SQL query text
+    pub sql: String,
+    /// Parameter definitions
+    pub parameters: Vec<ParamDef>,
+    /// Whether the query is private
+    pub is_private: bool,
+}
+
+impl QuerySpec {
+    /// Create a public query spec without parameters
+    #[must_use]
+    pub fn new(name: impl Into<String>, sql: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            sql: sql.into(),
+            parameters: Vec::new(),
+            is_private: false,
+        }
+    }
+
+    /// Add a parameter definition
+    #[must_use]
+    pub fn with_param(mut self, param: ParamDef) -> Self {
+        self.parameters.push(param);
+        self
+    }
+
+    /// Set as private
+    #[must_use]
+    pub fn private(mut self, is_private: bool) -> Self {
+        self.is_private = is_private;
+        self
+    }
+
+    fn api_parameters(&self) -> Vec<Parameter> {
+        self.parameters.iter().map(ParamDef::to_parameter).collect()
+    }
+}
+
+impl From<&QuerySpec> for CreateQueryRequest {
+    fn from(spec: &QuerySpec) -> Self {
+        Self {
+            name: spec.name.clone(),
+            query_sql: spec.sql.clone(),
+            description: None,
+            parameters: Some(spec.api_parameters()),
+            is_private: Some(spec.is_private),
+            tags: None,
+        }
+    }
+}
+
+impl From<&QuerySpec> for UpdateQueryRequest {
+    /// Full update: name, SQL, parameters and visibility are all sent, so
+    /// parameters removed from the spec are removed from the query
+    fn from(spec: &QuerySpec) -> Self {
+        Self {
+            name: Some(spec.name.clone()),
+            query_sql: Some(spec.sql.clone()),
+            description: None,
+            parameters: Some(spec.api_parameters()),
+            is_private: Some(spec.is_private),
+            tags: None,
+        }
+    }
+}
+
 /// Query response from get/create operations
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Query {
@@ -145,6 +366,9 @@ pub struct UpdateQueryRequest {
     /// Query parameters
     #[serde(skip_serializing_if = "Option::is_none")]
     pub parameters: Option<Vec<Parameter>>,
+    /// Whether the query is private
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_private: Option<bool>,
     /// Tags
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<String>>,
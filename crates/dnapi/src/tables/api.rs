@@ -5,6 +5,7 @@ use super::types::{
     InsertResponse, ListTablesOptions, ListTablesResponse, Table, UploadCsvRequest,
     UploadCsvResponse,
 };
+use crate::budget::Endpoint;
 use crate::client::Client;
 use crate::error::{self, Error, Result};
 
@@ -20,6 +21,7 @@ impl<'a> TablesApi<'a> {
 
     /// Create a new table
     pub async fn create(&self, request: &CreateTableRequest) -> Result<CreateTableResponse> {
+        self.client.charge(Endpoint::Tables)?;
         let url = format!("{}/v1/datasets", self.client.base_url());
         let response = self.client.http().post(&url).json(request).send().await?;
 
@@ -34,6 +36,7 @@ impl<'a> TablesApi<'a> {
 
     /// Upload CSV data to create or update a table
     pub async fn upload_csv(&self, request: &UploadCsvRequest) -> Result<UploadCsvResponse> {
+        self.client.charge(Endpoint::Tables)?;
         let url = format!("{}/v1/uploads/csv", self.client.base_url());
         let response = self.client.http().post(&url).json(request).send().await?;
 
@@ -56,6 +59,7 @@ impl<'a> TablesApi<'a> {
         &self,
         options: &ListTablesOptions,
     ) -> Result<ListTablesResponse> {
+        self.client.charge(Endpoint::Tables)?;
         let url = format!(
             "{}/v1/uploads{}",
             self.client.base_url(),
@@ -74,6 +78,7 @@ impl<'a> TablesApi<'a> {
 
     /// Get a specific table
     pub async fn get(&self, namespace: &str, table_name: &str) -> Result<Table> {
+        self.client.charge(Endpoint::Tables)?;
         let url = format!(
             "{}/v1/datasets/{}/{}",
             self.client.base_url(),
@@ -102,6 +107,7 @@ impl<'a> TablesApi<'a> {
         table_name: &str,
         data: &serde_json::Value,
     ) -> Result<InsertResponse> {
+        self.client.charge(Endpoint::Tables)?;
         let url = format!(
             "{}/v1/uploads/{}/{}/insert",
             self.client.base_url(),
@@ -123,6 +129,7 @@ impl<'a> TablesApi<'a> {
 
     /// Clear all data from a table
     pub async fn clear(&self, namespace: &str, table_name: &str) -> Result<ClearTableResponse> {
+        self.client.charge(Endpoint::Tables)?;
         let url = format!(
             "{}/v1/uploads/{}/{}/clear",
             self.client.base_url(),
@@ -144,6 +151,7 @@ impl<'a> TablesApi<'a> {
 
     /// Delete a table
     pub async fn delete(&self, namespace: &str, table_name: &str) -> Result<DeleteTableResponse> {
+        self.client.charge(Endpoint::Tables)?;
         let url = format!(
             "{}/v1/uploads/{}/{}",
             self.client.base_url(),
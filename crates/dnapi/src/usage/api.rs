@@ -1,6 +1,7 @@
 //! Usage API implementation
 
 use super::types::UsageResponse;
+use crate::budget::Endpoint;
 use crate::client::Client;
 use crate::error::{Error, Result};
 
@@ -16,6 +17,7 @@ impl<'a> UsageApi<'a> {
 
     /// Get account usage statistics
     pub async fn get(&self) -> Result<UsageResponse> {
+        self.client.charge(Endpoint::Usage)?;
         let url = format!("{}/v1/usage", self.client.base_url());
         let response = self.client.http().get(&url).send().await?;
 
@@ -0,0 +1,536 @@
+//! Query CRUD and sync tests against a mocked Dune API
+
+use dnapi::queries::{ParamDef, QuerySpec, SyncAction};
+use dnapi::Client;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn mock_client(server: &MockServer) -> Client {
+    Client::with_base_url("test_key", &server.uri()).expect("failed to create client")
+}
+
+fn remote_query(
+    id: i64,
+    name: &str,
+    sql: &str,
+    parameters: serde_json::Value,
+) -> serde_json::Value {
+    serde_json::json!({
+        "query_id": id,
+        "name": name,
+        "query_sql": sql,
+        "parameters": parameters,
+        "is_private": false,
+        "is_archived": false,
+    })
+}
+
+async fn mount_get(server: &MockServer, body: serde_json::Value) {
+    let id = body["query_id"].as_i64().unwrap();
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/query/{id}")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(body))
+        .mount(server)
+        .await;
+}
+
+/// Requests received with the given method
+async fn requests(server: &MockServer, verb: &str) -> Vec<(String, serde_json::Value)> {
+    server
+        .received_requests()
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|r| r.method.as_str() == verb)
+        .map(|r| {
+            let body = serde_json::from_slice(&r.body).unwrap_or(serde_json::Value::Null);
+            (r.url.path().to_string(), body)
+        })
+        .collect()
+}
+
+// ==================== CRUD ====================
+
+#[tokio::test]
+async fn test_create_sends_typed_parameters() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/query"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"query_id": 9})))
+        .mount(&server)
+        .await;
+
+    let spec = QuerySpec::new("Volume", "SELECT {{days}}")
+        .with_param(ParamDef::number("days").with_default("30"))
+        .with_param(ParamDef::enumeration(
+            "side",
+            vec!["buy".into(), "sell".into()],
+        ))
+        .private(true);
+    let created = mock_client(&server).queries().create(&spec).await.unwrap();
+    assert_eq!(created.query_id, 9);
+
+    let sent = requests(&server, "POST").await;
+    assert_eq!(
+        sent[0].1,
+        serde_json::json!({
+            "name": "Volume",
+            "query_sql": "SELECT {{days}}",
+            "is_private": true,
+            "parameters": [
+                {"key": "days", "type": "number", "value": "30"},
+                {"key": "side", "type": "enum", "enumOptions": ["buy", "sell"]}
+            ]
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_update_replaces_parameters() {
+    let server = MockServer::start().await;
+    Mock::given(method("PATCH"))
+        .and(path("/v1/query/9"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"query_id": 9})))
+        .mount(&server)
+        .await;
+
+    let spec = QuerySpec::new("Volume", "SELECT 1");
+    mock_client(&server)
+        .queries()
+        .update(9, &spec)
+        .await
+        .unwrap();
+
+    let sent = requests(&server, "PATCH").await;
+    // An empty list is sent so removed parameters are removed remotely
+    assert_eq!(sent[0].1["parameters"], serde_json::json!([]));
+    assert_eq!(sent[0].1["is_private"], false);
+}
+
+#[tokio::test]
+async fn test_get_not_found() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/query/404"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+
+    let err = mock_client(&server).queries().get(404).await.unwrap_err();
+    assert!(err.to_string().contains("Query 404"), "{err}");
+}
+
+#[tokio::test]
+async fn test_list_all_follows_pagination() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/queries"))
+        .and(wiremock::matchers::query_param("offset", "100"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "queries": [remote_query(2, "b", "SELECT 2", serde_json::json!([]))],
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/queries"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "queries": [remote_query(1, "a", "SELECT 1", serde_json::json!([]))],
+            "next_offset": 100,
+        })))
+        .mount(&server)
+        .await;
+
+    let all = mock_client(&server).queries().list_all().await.unwrap();
+    assert_eq!(all.iter().map(|q| q.query_id).collect::<Vec<_>>(), [1, 2]);
+}
+
+// ==================== Sync ====================
+
+fn write(dir: &Path, name: &str, contents: &str) {
+    fs::write(dir.join(name), contents).unwrap();
+}
+
+/// Three files: one changed, one matching a remote query by name, one new
+fn sync_dir() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    write(
+        dir.path(),
+        "a_changed.sql",
+        "-- ---\n-- name: Changed\n-- id: 11\n-- param: days number = 7\n-- ---\nSELECT {{days}}\n",
+    );
+    write(
+        dir.path(),
+        "b_linked.sql",
+        "-- ---\n-- name: Existing\n-- ---\nSELECT   *\nFROM t\n",
+    );
+    write(
+        dir.path(),
+        "c_new.sql",
+        "-- ---\n-- name: Brand new\n-- ---\nSELECT 3\n",
+    );
+    // Not a query file
+    write(dir.path(), "README.md", "# queries\n");
+    dir
+}
+
+/// Remote state before the first sync
+async fn remote_before() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/queries"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "queries": [
+                remote_query(11, "Changed", "SELECT 1", serde_json::json!([])),
+                remote_query(22, "Existing", "SELECT * FROM t", serde_json::json!([])),
+            ],
+        })))
+        .mount(&server)
+        .await;
+    mount_get(
+        &server,
+        remote_query(11, "Changed", "SELECT 1", serde_json::json!([])),
+    )
+    .await;
+    mount_get(
+        &server,
+        remote_query(22, "Existing", "SELECT * FROM t", serde_json::json!([])),
+    )
+    .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/query"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"query_id": 33})))
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path("/v1/query/11"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"query_id": 11})))
+        .mount(&server)
+        .await;
+    server
+}
+
+#[tokio::test]
+async fn test_sync_dry_run_changes_nothing() {
+    let dir = sync_dir();
+    let before: Vec<_> = ["a_changed.sql", "b_linked.sql", "c_new.sql"]
+        .iter()
+        .map(|f| fs::read_to_string(dir.path().join(f)).unwrap())
+        .collect();
+    let server = remote_before().await;
+
+    let report = mock_client(&server)
+        .queries()
+        .sync(dir.path(), true)
+        .await
+        .unwrap();
+
+    assert!(report.dry_run);
+    assert!(report.has_changes());
+    let summary: Vec<_> = report
+        .changes
+        .iter()
+        .map(|c| (c.name.as_str(), c.action, c.query_id, c.id_recorded))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("Changed", SyncAction::Update, Some(11), false),
+            ("Existing", SyncAction::Unchanged, Some(22), false),
+            ("Brand new", SyncAction::Create, None, false),
+        ]
+    );
+    assert_eq!(report.changes[0].changed, ["sql", "parameters"]);
+
+    assert!(requests(&server, "POST").await.is_empty());
+    assert!(requests(&server, "PATCH").await.is_empty());
+    let after: Vec<_> = ["a_changed.sql", "b_linked.sql", "c_new.sql"]
+        .iter()
+        .map(|f| fs::read_to_string(dir.path().join(f)).unwrap())
+        .collect();
+    assert_eq!(before, after);
+}
+
+#[tokio::test]
+async fn test_sync_applies_and_records_ids() {
+    let dir = sync_dir();
+    let server = remote_before().await;
+
+    let report = mock_client(&server)
+        .queries()
+        .sync(dir.path(), false)
+        .await
+        .unwrap();
+    assert_eq!(report.count(SyncAction::Create), 1);
+    assert_eq!(report.count(SyncAction::Update), 1);
+    assert_eq!(report.count(SyncAction::Unchanged), 1);
+    assert_eq!(report.changes[2].query_id, Some(33));
+
+    let patches = requests(&server, "PATCH").await;
+    assert_eq!(patches.len(), 1);
+    assert_eq!(patches[0].0, "/v1/query/11");
+    assert_eq!(patches[0].1["query_sql"], "SELECT {{days}}");
+    let posts = requests(&server, "POST").await;
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0].1["name"], "Brand new");
+
+    assert_eq!(
+        fs::read_to_string(dir.path().join("b_linked.sql")).unwrap(),
+        "-- ---\n-- name: Existing\n-- id: 22\n-- ---\nSELECT   *\nFROM t\n"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.path().join("c_new.sql")).unwrap(),
+        "-- ---\n-- name: Brand new\n-- id: 33\n-- ---\nSELECT 3\n"
+    );
+
+    // Second run against the updated remote: nothing to do, no listing needed
+    let server = MockServer::start().await;
+    mount_get(
+        &server,
+        remote_query(
+            11,
+            "Changed",
+            "SELECT {{days}}",
+            serde_json::json!([{"key": "days", "type": "number", "value": "7"}]),
+        ),
+    )
+    .await;
+    mount_get(
+        &server,
+        remote_query(22, "Existing", "SELECT * FROM t", serde_json::json!([])),
+    )
+    .await;
+    mount_get(
+        &server,
+        remote_query(33, "Brand new", "SELECT 3", serde_json::json!([])),
+    )
+    .await;
+
+    let report = mock_client(&server)
+        .queries()
+        .sync(dir.path(), false)
+        .await
+        .unwrap();
+    assert!(!report.has_changes());
+    assert_eq!(report.count(SyncAction::Unchanged), 3);
+    let received = server.received_requests().await.unwrap();
+    assert!(received.iter().all(|r| r.method.as_str() == "GET"));
+    assert!(received.iter().all(|r| r.url.path() != "/v1/queries"));
+}
+
+#[tokio::test]
+async fn test_sync_unarchives() {
+    let dir = TempDir::new().unwrap();
+    write(
+        dir.path(),
+        "q.sql",
+        "-- ---\n-- name: Old\n-- id: 5\n-- ---\nSELECT 1\n",
+    );
+    let server = MockServer::start().await;
+    let mut archived = remote_query(5, "Old", "SELECT 1", serde_json::json!([]));
+    archived["is_archived"] = serde_json::json!(true);
+    mount_get(&server, archived).await;
+    Mock::given(method("POST"))
+        .and(path("/v1/query/5/unarchive"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"query_id": 5})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let report = mock_client(&server)
+        .queries()
+        .sync(dir.path(), false)
+        .await
+        .unwrap();
+    assert_eq!(report.changes[0].changed, ["archived"]);
+    // Only archived: no update sent
+    assert!(requests(&server, "PATCH").await.is_empty());
+}
+
+/// The error reported for the file with the given name
+fn failure(report: &dnapi::queries::SyncReport, file: &str) -> String {
+    let change = report
+        .changes
+        .iter()
+        .find(|c| c.path.file_name().unwrap() == file)
+        .unwrap();
+    assert_eq!(change.action, SyncAction::Failed, "{change:?}");
+    change.error.clone().unwrap()
+}
+
+#[tokio::test]
+async fn test_sync_reports_ambiguous_name_and_duplicate_id() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/queries"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "queries": [
+                remote_query(1, "Twin", "SELECT 1", serde_json::json!([])),
+                remote_query(2, "Twin", "SELECT 2", serde_json::json!([])),
+            ],
+        })))
+        .mount(&server)
+        .await;
+    mount_get(
+        &server,
+        remote_query(1, "A", "SELECT 1", serde_json::json!([])),
+    )
+    .await;
+    let client = mock_client(&server);
+
+    let dir = TempDir::new().unwrap();
+    write(
+        dir.path(),
+        "a.sql",
+        "-- ---\n-- name: A\n-- id: 1\n-- ---\nSELECT 1\n",
+    );
+    write(
+        dir.path(),
+        "b.sql",
+        "-- ---\n-- name: B\n-- id: 1\n-- ---\nSELECT 2\n",
+    );
+    write(
+        dir.path(),
+        "twin.sql",
+        "-- ---\n-- name: Twin\n-- ---\nSELECT 1\n",
+    );
+    let report = client.queries().sync(dir.path(), true).await.unwrap();
+
+    // The other files are still synced
+    assert_eq!(report.changes[0].action, SyncAction::Unchanged);
+    assert_eq!(report.failures().count(), 2);
+    let err = failure(&report, "b.sql");
+    assert!(err.contains("query 1 is also used by"), "{err}");
+    let err = failure(&report, "twin.sql");
+    assert!(err.contains("2 remote queries are named 'Twin'"), "{err}");
+}
+
+#[tokio::test]
+async fn test_sync_links_each_remote_query_once() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/queries"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "queries": [
+                remote_query(22, "Existing", "SELECT 1", serde_json::json!([])),
+                remote_query(44, "Claimed", "SELECT 1", serde_json::json!([])),
+            ],
+        })))
+        .mount(&server)
+        .await;
+    mount_get(
+        &server,
+        remote_query(22, "Existing", "SELECT 1", serde_json::json!([])),
+    )
+    .await;
+    mount_get(
+        &server,
+        remote_query(44, "Renamed", "SELECT 1", serde_json::json!([])),
+    )
+    .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/query"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"query_id": 33})))
+        .mount(&server)
+        .await;
+
+    let dir = TempDir::new().unwrap();
+    for (file, name) in [
+        ("a.sql", "Existing"),
+        ("b.sql", "Existing"),
+        ("c.sql", "New"),
+        ("d.sql", "New"),
+        ("f.sql", "Claimed"),
+    ] {
+        write(
+            dir.path(),
+            file,
+            &format!("-- ---\n-- name: {name}\n-- ---\nSELECT 1\n"),
+        );
+    }
+    // Claims 44 by id even though it sorts after f.sql
+    write(
+        dir.path(),
+        "g.sql",
+        "-- ---\n-- name: Renamed\n-- id: 44\n-- ---\nSELECT 1\n",
+    );
+
+    let report = mock_client(&server)
+        .queries()
+        .sync(dir.path(), false)
+        .await
+        .unwrap();
+
+    let err = failure(&report, "b.sql");
+    assert!(
+        err.contains("query 22 named 'Existing' is already linked to"),
+        "{err}"
+    );
+    let err = failure(&report, "d.sql");
+    assert!(err.contains("also creates a query named 'New'"), "{err}");
+    let err = failure(&report, "f.sql");
+    assert!(
+        err.contains("query 44 named 'Claimed' is already linked to"),
+        "{err}"
+    );
+    assert_eq!(report.failures().count(), 3);
+    assert_eq!(requests(&server, "POST").await.len(), 1);
+
+    // Only the linked and created files get an id written
+    assert!(fs::read_to_string(dir.path().join("a.sql"))
+        .unwrap()
+        .contains("-- id: 22"));
+    assert!(fs::read_to_string(dir.path().join("c.sql"))
+        .unwrap()
+        .contains("-- id: 33"));
+    for file in ["b.sql", "d.sql", "f.sql"] {
+        assert!(!fs::read_to_string(dir.path().join(file))
+            .unwrap()
+            .contains("-- id:"));
+    }
+}
+
+#[tokio::test]
+async fn test_sync_continues_after_a_failed_file() {
+    let dir = TempDir::new().unwrap();
+    write(
+        dir.path(),
+        "a_missing.sql",
+        "-- ---\n-- name: Gone\n-- id: 9\n-- ---\nSELECT 1\n",
+    );
+    write(
+        dir.path(),
+        "b_changed.sql",
+        "-- ---\n-- name: Changed\n-- id: 11\n-- ---\nSELECT 2\n",
+    );
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/query/9"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+    mount_get(
+        &server,
+        remote_query(11, "Changed", "SELECT 1", serde_json::json!([])),
+    )
+    .await;
+    Mock::given(method("PATCH"))
+        .and(path("/v1/query/11"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"query_id": 11})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let report = mock_client(&server)
+        .queries()
+        .sync(dir.path(), false)
+        .await
+        .unwrap();
+
+    assert_eq!(report.changes[0].query_id, Some(9));
+    assert!(!failure(&report, "a_missing.sql").is_empty());
+    assert_eq!(report.changes[1].action, SyncAction::Update);
+    assert!(report.has_changes());
+}
@@ -1,6 +1,8 @@
 //! Collectibles API endpoints
 
-use super::types::{CollectiblesOptions, CollectiblesResponse};
+use super::types::{
+    CollectibleEntry, CollectiblesOptions, CollectiblesQuery, CollectiblesResponse,
+};
 use crate::client::Client;
 use crate::error::Result;
 
@@ -41,4 +43,49 @@ impl<'a> CollectiblesApi<'a> {
         );
         self.client.get(&path).await
     }
+
+    /// Get collectibles with a typed query
+    ///
+    /// # Arguments
+    /// * `address` - Wallet address
+    /// * `query` - Chain, spam and pagination filters
+    pub async fn query(
+        &self,
+        address: &str,
+        query: &CollectiblesQuery,
+    ) -> Result<CollectiblesResponse> {
+        let path = format!(
+            "/v1/evm/collectibles/{}{}",
+            address,
+            query.to_query_string()
+        );
+        self.client.get(&path).await
+    }
+
+    /// Get every collectible for a wallet, following `next_offset`
+    ///
+    /// Stops after `max_pages` requests to bound the number of calls.
+    ///
+    /// # Arguments
+    /// * `address` - Wallet address
+    /// * `query` - Filters applied to every page (its `offset` is the start)
+    /// * `max_pages` - Maximum number of pages to fetch
+    pub async fn get_all(
+        &self,
+        address: &str,
+        query: &CollectiblesQuery,
+        max_pages: usize,
+    ) -> Result<Vec<CollectibleEntry>> {
+        let mut query = query.clone();
+        let mut entries = Vec::new();
+        for _ in 0..max_pages {
+            let page = self.query(address, &query).await?;
+            entries.extend(page.entries);
+            match page.next_offset {
+                Some(offset) if !offset.is_empty() => query.offset = Some(offset),
+                _ => break,
+            }
+        }
+        Ok(entries)
+    }
 }
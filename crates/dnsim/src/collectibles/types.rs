@@ -1,6 +1,7 @@
 //! Types for collectibles (NFTs)
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Collectibles response
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -23,6 +24,7 @@ pub struct CollectibleEntry {
     /// Contract address
     pub contract_address: String,
     /// Token standard (ERC721, ERC1155)
+    #[serde(default)]
     pub token_standard: String,
     /// Token ID
     pub token_id: String,
@@ -34,6 +36,9 @@ pub struct CollectibleEntry {
     pub name: Option<String>,
     /// Collectible symbol
     pub symbol: Option<String>,
+    /// Collection name
+    #[serde(default, alias = "collection")]
+    pub collection_name: Option<String>,
     /// Description
     pub description: Option<String>,
     /// Image URL
@@ -45,7 +50,8 @@ pub struct CollectibleEntry {
     /// Balance (quantity held)
     pub balance: String,
     /// Last acquired timestamp
-    pub last_acquired: String,
+    #[serde(default)]
+    pub last_acquired: Option<String>,
     /// Spam flag
     pub is_spam: bool,
     /// Spam score (0-100)
@@ -54,6 +60,26 @@ pub struct CollectibleEntry {
     pub explanations: Option<Vec<SpamExplanation>>,
 }
 
+impl CollectibleEntry {
+    /// Whether the entry carries resolved display metadata (name and image)
+    #[must_use]
+    pub fn has_metadata(&self) -> bool {
+        self.name.is_some() && self.image_url.is_some()
+    }
+
+    /// Display name of the collection, falling back to the symbol
+    #[must_use]
+    pub fn collection_label(&self) -> Option<&str> {
+        self.collection_name.as_deref().or(self.symbol.as_deref())
+    }
+
+    /// Quantity held, parsed from the balance string
+    #[must_use]
+    pub fn quantity(&self) -> u64 {
+        self.balance.parse().unwrap_or(0)
+    }
+}
+
 /// Collectible metadata
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct CollectibleMetadata {
@@ -87,6 +113,165 @@ pub struct SpamExplanation {
     pub feature_weight: Option<f64>,
 }
 
+/// Typed collectibles query
+///
+/// Spam scores are always requested so callers can inspect `spam_score`
+/// even when spam is not excluded.
+#[derive(Debug, Clone, Default)]
+pub struct CollectiblesQuery {
+    /// Restrict to these chain IDs
+    pub chain_ids: Vec<u64>,
+    /// Drop entries the API flags as spam
+    pub exclude_spam: Option<bool>,
+    /// Results per page (max 2500)
+    pub limit: Option<u32>,
+    /// Pagination offset from a previous response
+    pub offset: Option<String>,
+}
+
+impl CollectiblesQuery {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restrict to the given chain IDs
+    #[must_use]
+    pub fn chain_ids(mut self, chain_ids: impl IntoIterator<Item = u64>) -> Self {
+        self.chain_ids = chain_ids.into_iter().collect();
+        self
+    }
+
+    /// Exclude (or include) spam entries
+    #[must_use]
+    pub fn exclude_spam(mut self, exclude: bool) -> Self {
+        self.exclude_spam = Some(exclude);
+        self
+    }
+
+    /// Set the page size
+    #[must_use]
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Set the pagination offset
+    #[must_use]
+    pub fn offset(mut self, offset: impl Into<String>) -> Self {
+        self.offset = Some(offset.into());
+        self
+    }
+
+    #[must_use]
+    pub fn to_query_string(&self) -> String {
+        let options = CollectiblesOptions {
+            chain_ids: (!self.chain_ids.is_empty()).then(|| {
+                self.chain_ids
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(",")
+            }),
+            offset: self.offset.clone(),
+            limit: self.limit,
+            filter_spam: self.exclude_spam,
+            show_spam_scores: Some(true),
+        };
+        options.to_query_string()
+    }
+}
+
+/// Collectibles from a single contract
+#[derive(Debug, Clone, Serialize)]
+pub struct CollectionGroup {
+    /// Chain ID
+    pub chain_id: i64,
+    /// Collection contract address
+    pub contract_address: String,
+    /// Collection name (or symbol when no name is known)
+    pub collection_name: Option<String>,
+    /// Token standard of the collection
+    pub token_standard: String,
+    /// Total quantity held across all token IDs
+    pub item_count: u64,
+    /// Floor price supplied by the caller, if any
+    pub floor_price: Option<f64>,
+    /// Entries in this collection
+    pub items: Vec<CollectibleEntry>,
+}
+
+/// Collectibles grouped by collection
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CollectionSummary {
+    /// Collections sorted by item count, largest first
+    pub collections: Vec<CollectionGroup>,
+    /// Number of entries without resolved name/image metadata
+    pub missing_metadata: usize,
+    /// Number of entries flagged as spam
+    pub spam: usize,
+}
+
+/// Group collectibles by `(chain_id, contract_address)`
+///
+/// `floor_prices` is keyed by lowercase contract address; matching collections
+/// get `floor_price` attached. Entries without metadata are kept and counted.
+#[must_use]
+pub fn group_by_collection(
+    entries: &[CollectibleEntry],
+    floor_prices: Option<&HashMap<String, f64>>,
+) -> CollectionSummary {
+    let mut groups: HashMap<(i64, String), CollectionGroup> = HashMap::new();
+    let mut summary = CollectionSummary::default();
+
+    for entry in entries {
+        if !entry.has_metadata() {
+            summary.missing_metadata += 1;
+        }
+        if entry.is_spam {
+            summary.spam += 1;
+        }
+
+        let contract = entry.contract_address.to_lowercase();
+        let group = groups
+            .entry((entry.chain_id, contract.clone()))
+            .or_insert_with(|| CollectionGroup {
+                chain_id: entry.chain_id,
+                floor_price: floor_prices.and_then(|prices| prices.get(&contract).copied()),
+                contract_address: contract,
+                collection_name: None,
+                token_standard: entry.token_standard.clone(),
+                item_count: 0,
+                items: Vec::new(),
+            });
+        if group.collection_name.is_none() {
+            group.collection_name = entry.collection_label().map(str::to_string);
+        }
+        group.item_count += entry.quantity().max(1);
+        group.items.push(entry.clone());
+    }
+
+    summary.collections = groups.into_values().collect();
+    summary.collections.sort_by(|a, b| {
+        b.item_count
+            .cmp(&a.item_count)
+            .then_with(|| a.chain_id.cmp(&b.chain_id))
+            .then_with(|| a.contract_address.cmp(&b.contract_address))
+    });
+    summary
+}
+
+impl CollectiblesResponse {
+    /// Group this page's entries by collection
+    #[must_use]
+    pub fn group_by_collection(
+        &self,
+        floor_prices: Option<&HashMap<String, f64>>,
+    ) -> CollectionSummary {
+        group_by_collection(&self.entries, floor_prices)
+    }
+}
+
 /// Query options for collectibles
 #[derive(Debug, Clone, Default)]
 pub struct CollectiblesOptions {
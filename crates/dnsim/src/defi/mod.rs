@@ -1,7 +1,9 @@
 //! `DeFi` positions module (Beta)
 
 mod api;
+mod portfolio;
 mod types;
 
 pub use api::DefiApi;
+pub use portfolio::{NormalizedPosition, PortfolioDefi, PositionKind, TokenAmount};
 pub use types::*;
@@ -0,0 +1,429 @@
+//! Normalized view over `DeFi` positions
+//!
+//! The positions endpoint returns one flat shape per protocol family. This
+//! module maps each position to a [`PositionKind`] and rolls positions up
+//! into USD totals, optionally pricing tokens client-side when the API
+//! omits USD values.
+
+use super::types::{DefiPosition, DefiPositionsResponse};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// An amount of a token inside a position
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct TokenAmount {
+    /// Token address (lowercase)
+    pub address: Option<String>,
+    /// Token symbol
+    pub symbol: Option<String>,
+    /// Amount in whole tokens (decimal string)
+    pub amount: Option<String>,
+    /// Price per whole token in USD, if the API supplied one
+    pub price_usd: Option<f64>,
+    /// USD value, if the API supplied one
+    pub value_usd: Option<f64>,
+}
+
+impl TokenAmount {
+    /// USD value, from the API or `amount` × price
+    ///
+    /// The position's own price wins over `prices`, which is keyed by
+    /// lowercase token address.
+    #[must_use]
+    pub fn value_with(&self, prices: &HashMap<String, f64>) -> Option<f64> {
+        if let Some(value) = self.value_usd {
+            return Some(value);
+        }
+        let amount: f64 = self.amount.as_deref()?.parse().ok()?;
+        let price = self
+            .price_usd
+            .or_else(|| prices.get(self.address.as_deref()?).copied())?;
+        Some(amount * price)
+    }
+
+    /// Parse a token entry from a protocol payload
+    ///
+    /// Accepts the key spellings used across the API: `token`/`address`,
+    /// `token_symbol`/`symbol`, `amount`/`balance`/`calculated_balance`,
+    /// `price_in_usd`/`price_usd`/`price` and `usd_value`/`value_usd`.
+    fn from_value(value: &Value) -> Option<Self> {
+        let object = value.as_object()?;
+        let str_field = |keys: &[&str]| {
+            keys.iter().find_map(|k| match object.get(*k)? {
+                Value::String(s) => Some(s.clone()),
+                Value::Number(n) => Some(n.to_string()),
+                _ => None,
+            })
+        };
+        let f64_field = |keys: &[&str]| keys.iter().find_map(|k| object.get(*k)?.as_f64());
+
+        Some(Self {
+            address: str_field(&["token", "address"]).map(|a| a.to_lowercase()),
+            symbol: str_field(&["token_symbol", "symbol"]),
+            amount: str_field(&["amount", "balance", "calculated_balance"]),
+            price_usd: f64_field(&["price_in_usd", "price_usd", "price"]),
+            value_usd: f64_field(&["usd_value", "value_usd"]),
+        })
+    }
+
+    /// Parse a list of token entries
+    fn list(value: Option<&Value>) -> Vec<Self> {
+        value
+            .and_then(Value::as_array)
+            .map(|items| items.iter().filter_map(Self::from_value).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Normalized position category
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PositionKind {
+    /// Money market supply and debt
+    Lending {
+        /// Supplied collateral
+        supplied: Vec<TokenAmount>,
+        /// Outstanding debt
+        borrowed: Vec<TokenAmount>,
+        /// Health factor, if the protocol reports one
+        health_factor: Option<f64>,
+    },
+    /// AMM liquidity
+    LiquidityPool {
+        /// Underlying tokens held through the position
+        tokens: Vec<TokenAmount>,
+        /// Share of the pool (0-1), if known
+        share: Option<f64>,
+    },
+    /// Staked tokens
+    Staking {
+        /// Staked token
+        token: TokenAmount,
+        /// Staked amount (whole tokens)
+        amount: Option<String>,
+        /// Accrued rewards
+        rewards: Vec<TokenAmount>,
+    },
+    /// Vault shares (e.g. ERC-4626)
+    Vault {
+        /// Vault share token and balance
+        shares: TokenAmount,
+        /// Underlying asset
+        underlying: TokenAmount,
+    },
+    /// A position type this crate doesn't model; see [`NormalizedPosition::raw`]
+    Unknown,
+}
+
+/// A position with its normalized category
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NormalizedPosition {
+    /// Normalized category
+    pub kind: PositionKind,
+    /// The position as returned by the API
+    pub raw: DefiPosition,
+}
+
+impl NormalizedPosition {
+    /// Classify a position from its `type` and protocol payload
+    ///
+    /// `Tokenized` positions are classified by `token_type`: names
+    /// containing "debt" are borrows, "atoken"/"ctoken"/"supply"/"lend" are
+    /// supplies and "stak" is staking. Anything unrecognized is
+    /// [`PositionKind::Unknown`].
+    #[must_use]
+    pub fn from_position(position: &DefiPosition) -> Self {
+        Self {
+            kind: classify(position),
+            raw: position.clone(),
+        }
+    }
+
+    /// Chain ID
+    #[must_use]
+    pub fn chain_id(&self) -> i64 {
+        self.raw.chain_id
+    }
+
+    /// Protocol name, falling back to the position type
+    #[must_use]
+    pub fn protocol(&self) -> &str {
+        self.raw
+            .protocol
+            .as_deref()
+            .unwrap_or(&self.raw.position_type)
+    }
+
+    /// Net USD value (debt counts negative), `None` if it can't be priced
+    ///
+    /// Lending positions are valued from their token amounts; other
+    /// positions use the API's `usd_value` when present.
+    #[must_use]
+    pub fn net_value_with(&self, prices: &HashMap<String, f64>) -> Option<f64> {
+        match &self.kind {
+            PositionKind::Lending {
+                supplied, borrowed, ..
+            } => {
+                let supplied = sum_values(supplied, prices)?;
+                let borrowed = sum_values(borrowed, prices)?;
+                Some(supplied - borrowed)
+            }
+            _ if self.raw.usd_value.is_some() => self.raw.usd_value,
+            PositionKind::LiquidityPool { tokens, .. } => sum_values(tokens, prices),
+            PositionKind::Staking { token, rewards, .. } => {
+                Some(token.value_with(prices)? + sum_values(rewards, prices)?)
+            }
+            PositionKind::Vault { shares, .. } => shares.value_with(prices),
+            PositionKind::Unknown => None,
+        }
+    }
+
+    /// USD value of the position's debt, `None` if it can't be priced
+    #[must_use]
+    pub fn debt_with(&self, prices: &HashMap<String, f64>) -> Option<f64> {
+        match &self.kind {
+            PositionKind::Lending { borrowed, .. } => sum_values(borrowed, prices),
+            _ => Some(0.0),
+        }
+    }
+
+    /// Health factor of a lending position
+    #[must_use]
+    pub fn health_factor(&self) -> Option<f64> {
+        match self.kind {
+            PositionKind::Lending { health_factor, .. } => health_factor,
+            _ => None,
+        }
+    }
+}
+
+/// Normalized `DeFi` portfolio with USD rollups
+#[derive(Debug, Clone, Default)]
+pub struct PortfolioDefi {
+    /// Normalized positions, in API order
+    pub positions: Vec<NormalizedPosition>,
+    /// Client-side token prices (USD per whole token, lowercase address keys)
+    prices: HashMap<String, f64>,
+}
+
+impl PortfolioDefi {
+    /// Normalize every position of a response
+    #[must_use]
+    pub fn from_response(response: &DefiPositionsResponse) -> Self {
+        Self {
+            positions: response
+                .positions
+                .iter()
+                .map(NormalizedPosition::from_position)
+                .collect(),
+            prices: HashMap::new(),
+        }
+    }
+
+    /// Price tokens the API didn't value (USD per whole token, by address)
+    #[must_use]
+    pub fn with_prices(mut self, prices: HashMap<String, f64>) -> Self {
+        self.prices = prices
+            .into_iter()
+            .map(|(address, price)| (address.to_lowercase(), price))
+            .collect();
+        self
+    }
+
+    /// Sum of position values net of debt
+    ///
+    /// Positions that can't be priced count as zero; see [`Self::unpriced`].
+    #[must_use]
+    pub fn net_value_usd(&self) -> f64 {
+        self.positions
+            .iter()
+            .filter_map(|p| p.net_value_with(&self.prices))
+            .sum()
+    }
+
+    /// Sum of borrowed values across lending positions
+    ///
+    /// Debt that can't be priced counts as zero; see [`Self::unpriced`].
+    #[must_use]
+    pub fn total_debt_usd(&self) -> f64 {
+        self.positions
+            .iter()
+            .filter_map(|p| p.debt_with(&self.prices))
+            .sum()
+    }
+
+    /// Positions left out of the totals because they couldn't be priced
+    #[must_use]
+    pub fn unpriced(&self) -> Vec<&NormalizedPosition> {
+        self.positions
+            .iter()
+            .filter(|p| {
+                p.net_value_with(&self.prices).is_none() || p.debt_with(&self.prices).is_none()
+            })
+            .collect()
+    }
+
+    /// Lending positions with a health factor below `health_threshold`
+    ///
+    /// Positions without a reported health factor are not included.
+    #[must_use]
+    pub fn at_risk_positions(&self, health_threshold: f64) -> Vec<&NormalizedPosition> {
+        self.positions
+            .iter()
+            .filter(|p| p.health_factor().is_some_and(|h| h < health_threshold))
+            .collect()
+    }
+}
+
+impl DefiPositionsResponse {
+    /// Normalized view of the positions
+    #[must_use]
+    pub fn portfolio(&self) -> PortfolioDefi {
+        PortfolioDefi::from_response(self)
+    }
+}
+
+/// Sum token values, `None` if any can't be priced
+fn sum_values(tokens: &[TokenAmount], prices: &HashMap<String, f64>) -> Option<f64> {
+    tokens.iter().map(|t| t.value_with(prices)).sum()
+}
+
+/// The position's main token as a [`TokenAmount`]
+fn main_token(position: &DefiPosition) -> TokenAmount {
+    TokenAmount {
+        address: position.token.as_ref().map(|a| a.to_lowercase()),
+        symbol: position.token_symbol.clone(),
+        amount: position.calculated_balance.clone(),
+        price_usd: position.price_in_usd,
+        value_usd: position.usd_value,
+    }
+}
+
+/// A pair token (0 or 1) with the amount held through the position
+fn pair_token(
+    address: Option<&String>,
+    symbol: Option<&String>,
+    price: Option<f64>,
+    amount: Option<f64>,
+) -> TokenAmount {
+    TokenAmount {
+        address: address.map(|a| a.to_lowercase()),
+        symbol: symbol.cloned(),
+        amount: amount.map(|a| a.to_string()),
+        price_usd: price,
+        value_usd: None,
+    }
+}
+
+/// Sum concentrated liquidity holdings plus rewards for one side
+fn nft_holdings(position: &DefiPosition, token1: bool) -> Option<f64> {
+    let nfts = position.positions.as_ref()?;
+    let mut total = 0.0;
+    for nft in nfts {
+        let (holdings, rewards) = if token1 {
+            (&nft.token1_holdings, &nft.token1_rewards)
+        } else {
+            (&nft.token0_holdings, &nft.token0_rewards)
+        };
+        for value in [holdings, rewards].into_iter().flatten() {
+            total += value.parse::<f64>().ok()?;
+        }
+    }
+    Some(total)
+}
+
+/// Map a position to its category
+fn classify(position: &DefiPosition) -> PositionKind {
+    let extra = &position.extra;
+    match position.position_type.as_str() {
+        "Erc4626" => PositionKind::Vault {
+            shares: main_token(position),
+            underlying: TokenAmount {
+                address: position.underlying_token.as_ref().map(|a| a.to_lowercase()),
+                symbol: position.underlying_token_symbol.clone(),
+                ..TokenAmount::default()
+            },
+        },
+        "UniswapV2" => PositionKind::LiquidityPool {
+            tokens: vec![
+                pair_token(
+                    position.token0.as_ref(),
+                    position.token0_symbol.as_ref(),
+                    position.token0_price,
+                    None,
+                ),
+                pair_token(
+                    position.token1.as_ref(),
+                    position.token1_symbol.as_ref(),
+                    position.token1_price,
+                    None,
+                ),
+            ],
+            share: extra.get("pool_share").and_then(Value::as_f64),
+        },
+        "Nft" | "NftV4" => {
+            let first = position.positions.as_ref().and_then(|p| p.first());
+            PositionKind::LiquidityPool {
+                tokens: vec![
+                    pair_token(
+                        position.token0.as_ref(),
+                        position.token0_symbol.as_ref(),
+                        first.and_then(|p| p.token0_price),
+                        nft_holdings(position, false),
+                    ),
+                    pair_token(
+                        position.token1.as_ref(),
+                        position.token1_symbol.as_ref(),
+                        first.and_then(|p| p.token1_price),
+                        nft_holdings(position, true),
+                    ),
+                ],
+                share: None,
+            }
+        }
+        "Lending" => PositionKind::Lending {
+            supplied: TokenAmount::list(extra.get("supplied")),
+            borrowed: TokenAmount::list(extra.get("borrowed")),
+            health_factor: extra.get("health_factor").and_then(Value::as_f64),
+        },
+        "Staking" => PositionKind::Staking {
+            token: main_token(position),
+            amount: position.calculated_balance.clone(),
+            rewards: TokenAmount::list(extra.get("rewards")),
+        },
+        "Tokenized" => {
+            let token_type = position
+                .token_type
+                .as_deref()
+                .unwrap_or_default()
+                .to_lowercase();
+            let health_factor = extra.get("health_factor").and_then(Value::as_f64);
+            if token_type.contains("debt") {
+                PositionKind::Lending {
+                    supplied: Vec::new(),
+                    borrowed: vec![main_token(position)],
+                    health_factor,
+                }
+            } else if ["atoken", "ctoken", "supply", "lend"]
+                .iter()
+                .any(|t| token_type.contains(t))
+            {
+                PositionKind::Lending {
+                    supplied: vec![main_token(position)],
+                    borrowed: Vec::new(),
+                    health_factor,
+                }
+            } else if token_type.contains("stak") {
+                PositionKind::Staking {
+                    token: main_token(position),
+                    amount: position.calculated_balance.clone(),
+                    rewards: Vec::new(),
+                }
+            } else {
+                PositionKind::Unknown
+            }
+        }
+        _ => PositionKind::Unknown,
+    }
+}
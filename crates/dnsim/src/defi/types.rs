@@ -20,75 +20,109 @@ pub struct DefiPosition {
     /// Chain ID
     pub chain_id: i64,
     /// USD value
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub usd_value: Option<f64>,
     /// Logo URL
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub logo: Option<String>,
 
     // Erc4626 fields
     /// Token address (Erc4626, Tokenized)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub token: Option<String>,
     /// Token name
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub token_name: Option<String>,
     /// Token symbol
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub token_symbol: Option<String>,
     /// Underlying token address (Erc4626)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub underlying_token: Option<String>,
     /// Underlying token name (Erc4626)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub underlying_token_name: Option<String>,
     /// Underlying token symbol (Erc4626)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub underlying_token_symbol: Option<String>,
     /// Underlying token decimals (Erc4626)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub underlying_token_decimals: Option<u8>,
 
     // Tokenized fields
     /// Token type (Tokenized)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub token_type: Option<String>,
 
     // UniswapV2/Nft/NftV4 fields
     /// Protocol name
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub protocol: Option<String>,
     /// Pool address (`UniswapV2`, Nft)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub pool: Option<String>,
     /// Pool ID (`NftV4`, as byte array)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub pool_id: Option<Vec<u8>>,
     /// Pool manager (`NftV4`)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub pool_manager: Option<String>,
     /// Salt (`NftV4`, as byte array)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub salt: Option<Vec<u8>>,
 
     // Token pair fields (UniswapV2, Nft, NftV4)
     /// Token 0 address
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub token0: Option<String>,
     /// Token 0 name
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub token0_name: Option<String>,
     /// Token 0 symbol
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub token0_symbol: Option<String>,
     /// Token 0 decimals
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub token0_decimals: Option<u8>,
     /// Token 0 price
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub token0_price: Option<f64>,
     /// Token 1 address
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub token1: Option<String>,
     /// Token 1 name
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub token1_name: Option<String>,
     /// Token 1 symbol
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub token1_symbol: Option<String>,
     /// Token 1 decimals
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub token1_decimals: Option<u8>,
     /// Token 1 price
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub token1_price: Option<f64>,
 
     // Balance fields
     /// LP balance (`UniswapV2`)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub lp_balance: Option<String>,
     /// Calculated balance
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub calculated_balance: Option<String>,
     /// Price in USD
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub price_in_usd: Option<f64>,
 
     // NFT positions (Nft, NftV4)
     /// Concentrated liquidity positions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub positions: Option<Vec<NftPositionDetails>>,
+
+    /// Fields not modelled above (protocol-specific payloads), kept so
+    /// positions round-trip losslessly
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// NFT position details (for Uniswap V3/V4)
@@ -6,11 +6,15 @@ mod deserialization_tests {
     use crate::balances::{BalancesResponse, SingleBalanceResponse};
     use crate::chains::ChainsResponse;
     use crate::collectibles::CollectiblesResponse;
-    use crate::defi::DefiPositionsResponse;
+    use crate::defi::{DefiPositionsResponse, PositionKind};
     use crate::holders::TokenHoldersResponse;
-    use crate::tokens::TokensResponse;
+    use crate::tokens::{multi_path, SymbolMatches, TokensResponse};
     use crate::transactions::TransactionsResponse;
-    use crate::webhooks::{AddressesListResponse, Webhook, WebhooksListResponse};
+    use crate::webhooks::{
+        ActivityType, AddressesListResponse, AssetType, CreateWebhookRequest, CreatedWebhook,
+        TransactionDirection, UpdateWebhookRequest, Webhook, WebhookFilter, WebhookType,
+        WebhooksListResponse,
+    };
 
     #[test]
     fn test_activity_response_deserialization() {
@@ -254,6 +258,237 @@ mod deserialization_tests {
         assert_eq!(response.tokens[0].price_usd, Some(3500.50));
     }
 
+    // Hand-written in the token-info response shape rather than recorded:
+    // USDC on three chains plus a shallow bridged copy, and a single-chain
+    // meme token without market data
+    const USDC_FIXTURE: &str = r#"{
+        "contract_address": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+        "tokens": [
+          {
+            "chain": "base",
+            "chain_id": 8453,
+            "address": "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913",
+            "symbol": "USDC",
+            "name": "USD Coin",
+            "decimals": 6,
+            "price_usd": 0.9999,
+            "total_supply": "4100000000000000",
+            "market_cap": 4100000000.0,
+            "fully_diluted_value": 4100000000.0,
+            "volume_24h": 812000000.0,
+            "pool_size": 95000000.0,
+            "pools": [
+              {
+                "dex": "aerodrome",
+                "pool_address": "0xcdac0d6c6c59727a65f871236188350531885c43",
+                "liquidity_usd": 95000000.0
+              }
+            ],
+            "logo": "https://api.sim.dune.com/v1/token/logo/8453/0x833589fcd6edb6e08f4c7c32d4f71b54bda02913"
+          },
+          {
+            "chain": "ethereum",
+            "chain_id": 1,
+            "address": "0x2f0e5d1ab7a4e5d2a1c7a0a7f3d1e5b1c2a3b4c5",
+            "symbol": "usdc",
+            "name": "USD Coin (bridged)",
+            "decimals": 6,
+            "price_usd": 0.41,
+            "volume_24h": 350.0,
+            "pool_size": 1200.0,
+            "pools": [
+              {
+                "dex": "uniswap_v2",
+                "pool_address": "0x9a1b2c3d4e5f60718293a4b5c6d7e8f901234567",
+                "liquidity_usd": 1200.0
+              }
+            ]
+          },
+          {
+            "chain": "ethereum",
+            "chain_id": 1,
+            "address": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+            "symbol": "USDC",
+            "name": "USD Coin",
+            "decimals": 6,
+            "price_usd": 1.0001,
+            "total_supply": "37000000000000000",
+            "market_cap": 37000000000.0,
+            "fully_diluted_value": 37000000000.0,
+            "volume_24h": 6400000000.0,
+            "pool_size": 250000000.0,
+            "pools": [
+              {
+                "dex": "uniswap_v3",
+                "pool_address": "0x88e6a0c2ddd26feeb64f039a2c41296fcb3f5640",
+                "liquidity_usd": 250000000.0
+              },
+              {
+                "dex": "curve",
+                "pool_address": "0xbebc44782c7db0a1a60cb6fe97d0b483032ff1c7",
+                "liquidity_usd": 180000000.0
+              }
+            ],
+            "logo": "https://api.sim.dune.com/v1/token/logo/1/0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"
+          },
+          {
+            "chain": "arbitrum",
+            "chain_id": 42161,
+            "address": "0xaf88d065e77c8cc2239327c5edb3a432268e5831",
+            "symbol": "USDC",
+            "name": "USD Coin",
+            "decimals": 6,
+            "price_usd": 1.0,
+            "volume_24h": 540000000.0,
+            "pools": [
+              {
+                "dex": "uniswap_v3",
+                "pool_address": "0xc6962004f452be9203591991d15f6b388e09e8d0",
+                "liquidity_usd": 60000000.0
+              }
+            ]
+          }
+        ],
+        "next_offset": null
+        }"#;
+    const MEME_FIXTURE: &str = r#"{
+        "contract_address": "0x4ed4e862860bed51a9570b96d89af5e1b0efefed",
+        "tokens": [
+          {
+            "chain": "base",
+            "chain_id": 8453,
+            "symbol": "DEGEN",
+            "name": "Degen",
+            "decimals": 18,
+            "price_usd": null,
+            "total_supply": "36965935954000000000000000000"
+          }
+        ],
+        "next_offset": null
+        }"#;
+    const USDC_ETH: &str = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+    const USDC_BASE: &str = "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913";
+
+    #[test]
+    fn test_tokens_market_data_multi_chain() {
+        let response: TokensResponse = serde_json::from_str(USDC_FIXTURE).unwrap();
+        assert_eq!(response.chain_ids(), vec![1, 8453, 42161]);
+
+        let eth = &response.tokens[2];
+        assert_eq!(eth.address.as_deref(), Some(USDC_ETH));
+        assert_eq!(eth.volume_24h, Some(6_400_000_000.0));
+        assert_eq!(eth.fully_diluted_value, Some(37_000_000_000.0));
+        assert_eq!(eth.pools.len(), 2);
+        assert_eq!(eth.pools[0].dex, "uniswap_v3");
+        assert_eq!(
+            eth.pools[0].pair_address,
+            "0x88e6a0c2ddd26feeb64f039a2c41296fcb3f5640"
+        );
+        assert_eq!(eth.pools[1].liquidity_usd, Some(180_000_000.0));
+        assert_eq!(eth.liquidity_usd(), Some(250_000_000.0));
+
+        // No pool_size: deepest listed pool
+        let arb = &response.tokens[3];
+        assert_eq!(arb.pool_size, None);
+        assert_eq!(arb.liquidity_usd(), Some(60_000_000.0));
+    }
+
+    #[test]
+    fn test_tokens_missing_market_data() {
+        let response: TokensResponse = serde_json::from_str(MEME_FIXTURE).unwrap();
+        assert_eq!(response.chain_ids(), vec![8453]);
+        let token = &response.tokens[0];
+        assert_eq!(token.symbol, "DEGEN");
+        assert_eq!(token.address, None);
+        assert_eq!(token.price_usd, None);
+        assert_eq!(token.volume_24h, None);
+        assert_eq!(token.market_cap, None);
+        assert_eq!(token.pool_size, None);
+        assert!(token.pools.is_empty());
+        assert_eq!(token.liquidity_usd(), None);
+    }
+
+    #[test]
+    fn test_tokens_multi_path() {
+        assert_eq!(
+            multi_path(&[
+                (1, "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"),
+                (8453, USDC_BASE),
+                (1, USDC_BASE),
+            ]),
+            format!("/v1/evm/token-info/{USDC_ETH},{USDC_BASE}?chain_ids=1,8453")
+        );
+    }
+
+    #[test]
+    fn test_tokens_select_lookups() {
+        let response: TokensResponse = serde_json::from_str(USDC_FIXTURE).unwrap();
+        // Requested order; pairs not asked for (bridged USDC, arbitrum) and
+        // pairs not found are dropped
+        let tokens = response.select(&[
+            (8453, USDC_BASE),
+            (1, "0xA0B86991C6218B36C1D19D4A2E9EB0CE3606EB48"),
+            (10, USDC_ETH),
+        ]);
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[0].chain_id, 8453);
+        assert_eq!(tokens[1].address.as_deref(), Some(USDC_ETH));
+
+        // Single-address responses carry the address at the top level
+        let response: TokensResponse = serde_json::from_str(MEME_FIXTURE).unwrap();
+        let tokens = response.select(&[(8453, "0x4ed4e862860bed51a9570b96d89af5e1b0efefed")]);
+        assert_eq!(
+            tokens[0].address.as_deref(),
+            Some("0x4ed4e862860bed51a9570b96d89af5e1b0efefed")
+        );
+    }
+
+    #[test]
+    fn test_resolve_symbol_ranking() {
+        let response: TokensResponse = serde_json::from_str(USDC_FIXTURE).unwrap();
+
+        let matches = SymbolMatches::rank("usdc", response.tokens.clone(), &[]);
+        let ranked: Vec<(i64, f64)> = matches
+            .candidates
+            .iter()
+            .map(|t| (t.chain_id, t.liquidity_usd().unwrap()))
+            .collect();
+        assert_eq!(
+            ranked,
+            vec![
+                (1, 250_000_000.0),
+                (8453, 95_000_000.0),
+                (42161, 60_000_000.0),
+                (1, 1200.0)
+            ]
+        );
+        // The bridged copy on mainnet is far shallower
+        assert!(!matches.is_ambiguous());
+        assert_eq!(matches.best().unwrap().address.as_deref(), Some(USDC_ETH));
+
+        let matches = SymbolMatches::rank("USDC", response.tokens.clone(), &[8453, 1]);
+        assert_eq!(matches.candidates[0].chain_id, 8453);
+        assert_eq!(matches.candidates[1].address.as_deref(), Some(USDC_ETH));
+        assert_eq!(matches.candidates[3].chain_id, 42161);
+        assert_eq!(matches.best().unwrap().address.as_deref(), Some(USDC_BASE));
+
+        // Comparable liquidity on one chain: candidates only, no guess
+        let mut tokens = response.tokens;
+        tokens[1].pool_size = Some(50_000_000.0);
+        let matches = SymbolMatches::rank("USDC", tokens, &[1]);
+        assert!(matches.is_ambiguous());
+        assert!(matches.best().is_none());
+        assert_eq!(matches.candidates.len(), 4);
+
+        let response: TokensResponse = serde_json::from_str(MEME_FIXTURE).unwrap();
+        assert!(SymbolMatches::rank("PEPE", response.tokens.clone(), &[])
+            .candidates
+            .is_empty());
+        let matches = SymbolMatches::rank("degen", response.tokens, &[]);
+        assert!(!matches.is_ambiguous());
+        assert_eq!(matches.best().unwrap().name, "Degen");
+    }
+
     #[test]
     fn test_token_holders_response_deserialization() {
         let json = r#"{
@@ -477,6 +712,112 @@ mod deserialization_tests {
         assert_eq!(explanations.len(), 2);
     }
 
+    #[test]
+    fn test_collectibles_group_by_collection_mixed_standards() {
+        use crate::collectibles::group_by_collection;
+        use std::collections::HashMap;
+
+        let json = r#"{
+            "address": "0xtest",
+            "entries": [
+                {
+                    "contract_address": "0xBC4CA0EdA7647A8aB7C2061c2E118A18a936f13D",
+                    "token_standard": "ERC721",
+                    "token_id": "1",
+                    "chain": "ethereum",
+                    "chain_id": 1,
+                    "name": "Ape #1",
+                    "symbol": "BAYC",
+                    "collection_name": "Bored Ape Yacht Club",
+                    "image_url": "https://example.com/1.png",
+                    "balance": "1",
+                    "last_acquired": "2025-08-10T03:58:59Z",
+                    "is_spam": false
+                },
+                {
+                    "contract_address": "0xbc4ca0eda7647a8ab7c2061c2e118a18a936f13d",
+                    "token_standard": "ERC721",
+                    "token_id": "2",
+                    "chain": "ethereum",
+                    "chain_id": 1,
+                    "balance": "1",
+                    "is_spam": false
+                },
+                {
+                    "contract_address": "0x5d28dcf2fbbd3738c0ebe9de03eafcb4ec33015d",
+                    "token_standard": "ERC1155",
+                    "token_id": "1",
+                    "chain": "ethereum",
+                    "chain_id": 1,
+                    "name": "Beeplfg",
+                    "symbol": "CRAP",
+                    "image_url": "https://example.com/b.png",
+                    "balance": "8",
+                    "last_acquired": "2025-08-10T03:58:59Z",
+                    "is_spam": true,
+                    "spam_score": 90
+                }
+            ],
+            "request_time": "2025-08-13T09:40:53Z",
+            "response_time": "2025-08-13T09:40:53Z"
+        }"#;
+
+        let response: CollectiblesResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.entries.len(), 3);
+        assert!(response.entries[1].last_acquired.is_none());
+        assert!(!response.entries[1].has_metadata());
+
+        let floors = HashMap::from([(
+            "0xbc4ca0eda7647a8ab7c2061c2e118a18a936f13d".to_string(),
+            12.5,
+        )]);
+        let summary = group_by_collection(&response.entries, Some(&floors));
+        assert_eq!(summary.missing_metadata, 1);
+        assert_eq!(summary.spam, 1);
+        assert_eq!(summary.collections.len(), 2);
+
+        // ERC1155 balance of 8 outranks two ERC721 tokens
+        let first = &summary.collections[0];
+        assert_eq!(first.token_standard, "ERC1155");
+        assert_eq!(first.item_count, 8);
+        assert_eq!(first.collection_name.as_deref(), Some("CRAP"));
+        assert!(first.floor_price.is_none());
+
+        let second = &summary.collections[1];
+        assert_eq!(second.item_count, 2);
+        assert_eq!(second.items.len(), 2);
+        assert_eq!(
+            second.collection_name.as_deref(),
+            Some("Bored Ape Yacht Club")
+        );
+        assert_eq!(second.floor_price, Some(12.5));
+
+        let without_floors = response.group_by_collection(None);
+        assert!(without_floors
+            .collections
+            .iter()
+            .all(|c| c.floor_price.is_none()));
+    }
+
+    #[test]
+    fn test_collectibles_query_string() {
+        use crate::collectibles::CollectiblesQuery;
+
+        let query = CollectiblesQuery::new()
+            .chain_ids([1, 8453])
+            .exclude_spam(true)
+            .limit(50)
+            .offset("abc");
+        assert_eq!(
+            query.to_query_string(),
+            "?chain_ids=1,8453&offset=abc&limit=50&filter_spam=true&show_spam_scores=true"
+        );
+        assert_eq!(
+            CollectiblesQuery::new().to_query_string(),
+            "?show_spam_scores=true"
+        );
+    }
+
     #[test]
     fn test_defi_positions_response_erc4626() {
         let json = r#"{
@@ -637,6 +978,262 @@ mod deserialization_tests {
         assert!(response.positions[0].salt.is_some());
     }
 
+    #[test]
+    fn test_portfolio_vault() {
+        let json = r#"{
+            "positions": [
+                {
+                    "type": "Erc4626",
+                    "chain_id": 1,
+                    "token": "0xDCD0F5AB30856F28385F641580BBD85F88349124",
+                    "token_symbol": "alUSD",
+                    "underlying_token": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+                    "underlying_token_symbol": "USDC",
+                    "calculated_balance": "100"
+                }
+            ]
+        }"#;
+
+        let response: DefiPositionsResponse = serde_json::from_str(json).unwrap();
+        let portfolio = response.portfolio();
+        let PositionKind::Vault { shares, underlying } = &portfolio.positions[0].kind else {
+            panic!("expected vault");
+        };
+        assert_eq!(
+            shares.address.as_deref(),
+            Some("0xdcd0f5ab30856f28385f641580bbd85f88349124")
+        );
+        assert_eq!(underlying.symbol.as_deref(), Some("USDC"));
+
+        // No USD value from the API: unpriced until a price map is supplied
+        assert_eq!(portfolio.net_value_usd(), 0.0);
+        assert_eq!(portfolio.unpriced().len(), 1);
+        let priced = portfolio.with_prices(
+            [(
+                "0xDCD0F5AB30856F28385F641580BBD85F88349124".to_string(),
+                1.02,
+            )]
+            .into(),
+        );
+        assert!((priced.net_value_usd() - 102.0).abs() < 1e-9);
+        assert!(priced.unpriced().is_empty());
+    }
+
+    #[test]
+    fn test_portfolio_liquidity_pool() {
+        let json = r#"{
+            "positions": [
+                {
+                    "type": "UniswapV2",
+                    "chain_id": 1,
+                    "protocol": "UniswapV2",
+                    "token0": "0x4206975c6d7135ad73129476ebe2b06e42f41f50",
+                    "token0_symbol": "FWOG",
+                    "token1": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+                    "token1_symbol": "WETH",
+                    "token0_price": 3.981792910653e-11,
+                    "token1_price": 3553.20946160682,
+                    "pool_share": 0.0025,
+                    "usd_value": 47.9643563369592
+                },
+                {
+                    "type": "Nft",
+                    "chain_id": 8453,
+                    "protocol": "UniswapV3",
+                    "token0": "0x07449b62ec61f12d6110c36db240436222b02530",
+                    "token0_symbol": "DOGE",
+                    "token1": "0x4200000000000000000000000000000000000006",
+                    "token1_symbol": "WETH",
+                    "positions": [
+                        {
+                            "token0_price": 0.5,
+                            "token0_holdings": "10",
+                            "token0_rewards": "2",
+                            "token1_price": 3000,
+                            "token1_holdings": "0.5",
+                            "token1_rewards": "0.25"
+                        },
+                        {
+                            "token0_holdings": "8",
+                            "token1_holdings": "0.25"
+                        }
+                    ]
+                }
+            ]
+        }"#;
+
+        let response: DefiPositionsResponse = serde_json::from_str(json).unwrap();
+        let portfolio = response.portfolio();
+
+        let PositionKind::LiquidityPool { tokens, share } = &portfolio.positions[0].kind else {
+            panic!("expected liquidity pool");
+        };
+        assert_eq!(tokens[1].symbol.as_deref(), Some("WETH"));
+        assert_eq!(*share, Some(0.0025));
+
+        // Concentrated liquidity holdings and rewards are summed across NFTs
+        let PositionKind::LiquidityPool { tokens, share } = &portfolio.positions[1].kind else {
+            panic!("expected liquidity pool");
+        };
+        assert_eq!(tokens[0].amount.as_deref(), Some("20"));
+        assert_eq!(tokens[1].amount.as_deref(), Some("1"));
+        assert_eq!(*share, None);
+
+        // API value for the V2 pool, client-side value (20 * 0.5 + 1 * 3000) for the NFT
+        let expected = 47.9643563369592 + 3010.0;
+        assert!((portfolio.net_value_usd() - expected).abs() < 1e-9);
+        assert_eq!(portfolio.total_debt_usd(), 0.0);
+    }
+
+    #[test]
+    fn test_portfolio_lending() {
+        let json = r#"{
+            "positions": [
+                {
+                    "type": "Lending",
+                    "chain_id": 1,
+                    "protocol": "AaveV3",
+                    "supplied": [
+                        {"token": "0xC02AAA39B223FE8D0A0E5C4F27EAD9083C756CC2", "symbol": "WETH", "amount": "2", "price_in_usd": 3000}
+                    ],
+                    "borrowed": [
+                        {"token": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48", "symbol": "USDC", "amount": "4000", "usd_value": 4000}
+                    ],
+                    "health_factor": 1.05
+                },
+                {
+                    "type": "Tokenized",
+                    "chain_id": 1,
+                    "protocol": "AaveV3",
+                    "token_type": "AaveV3VariableDebt",
+                    "token": "0x72e95b8931767c79ba4eee721354d6e99a61d004",
+                    "token_symbol": "variableDebtEthUSDC",
+                    "calculated_balance": "500",
+                    "price_in_usd": 1.0,
+                    "health_factor": 2.4
+                },
+                {
+                    "type": "Tokenized",
+                    "chain_id": 1,
+                    "protocol": "AaveV3",
+                    "token_type": "AaveV3AToken",
+                    "token": "0x98c23e9d8f34fefb1b7bd6a91b7ff122f4e16f5c",
+                    "token_symbol": "aEthUSDC",
+                    "calculated_balance": "1000",
+                    "usd_value": 1000
+                }
+            ]
+        }"#;
+
+        let response: DefiPositionsResponse = serde_json::from_str(json).unwrap();
+        let portfolio = response.portfolio();
+
+        let PositionKind::Lending {
+            supplied,
+            borrowed,
+            health_factor,
+        } = &portfolio.positions[0].kind
+        else {
+            panic!("expected lending");
+        };
+        assert_eq!(
+            supplied[0].address.as_deref(),
+            Some("0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2")
+        );
+        assert_eq!(borrowed[0].symbol.as_deref(), Some("USDC"));
+        assert_eq!(*health_factor, Some(1.05));
+
+        assert!(matches!(
+            &portfolio.positions[1].kind,
+            PositionKind::Lending { supplied, borrowed, .. } if supplied.is_empty() && borrowed.len() == 1
+        ));
+        assert!(matches!(
+            &portfolio.positions[2].kind,
+            PositionKind::Lending { supplied, borrowed, .. } if supplied.len() == 1 && borrowed.is_empty()
+        ));
+
+        // 6000 - 4000 supplied net, -500 debt token, +1000 aToken
+        assert!((portfolio.net_value_usd() - 2500.0).abs() < 1e-9);
+        assert!((portfolio.total_debt_usd() - 4500.0).abs() < 1e-9);
+
+        let at_risk = portfolio.at_risk_positions(1.5);
+        assert_eq!(at_risk.len(), 1);
+        assert_eq!(at_risk[0].protocol(), "AaveV3");
+        assert_eq!(at_risk[0].health_factor(), Some(1.05));
+        assert_eq!(portfolio.at_risk_positions(3.0).len(), 2);
+    }
+
+    #[test]
+    fn test_portfolio_staking() {
+        let json = r#"{
+            "positions": [
+                {
+                    "type": "Staking",
+                    "chain_id": 1,
+                    "protocol": "Lido",
+                    "token": "0xae7ab96520de3a18e5e111b5eaab095312d7fe84",
+                    "token_symbol": "stETH",
+                    "calculated_balance": "1.5",
+                    "price_in_usd": 3000,
+                    "rewards": [
+                        {"address": "0x5a98fcbea516cf06857215779fd812ca3bef1b32", "symbol": "LDO", "balance": "10"}
+                    ]
+                }
+            ]
+        }"#;
+
+        let response: DefiPositionsResponse = serde_json::from_str(json).unwrap();
+        let portfolio = response.portfolio();
+        let PositionKind::Staking {
+            token,
+            amount,
+            rewards,
+        } = &portfolio.positions[0].kind
+        else {
+            panic!("expected staking");
+        };
+        assert_eq!(token.symbol.as_deref(), Some("stETH"));
+        assert_eq!(amount.as_deref(), Some("1.5"));
+        assert_eq!(rewards[0].amount.as_deref(), Some("10"));
+
+        // The LDO reward has no price yet
+        assert_eq!(portfolio.unpriced().len(), 1);
+        let priced = portfolio.with_prices(
+            [(
+                "0x5a98fcbea516cf06857215779fd812ca3bef1b32".to_string(),
+                1.2,
+            )]
+            .into(),
+        );
+        assert!((priced.net_value_usd() - 4512.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_portfolio_unknown_protocol_round_trip() {
+        let json = r#"{
+            "type": "Perpetual",
+            "chain_id": 42161,
+            "protocol": "GMX",
+            "usd_value": 250.5,
+            "market": "ETH-USD",
+            "leverage": 3.5,
+            "collateral": {"token": "0xaf88d065e77c8cc2239327c5edb3a432268e5831", "amount": "100"},
+            "orders": [1, 2, 3]
+        }"#;
+        let response: DefiPositionsResponse =
+            serde_json::from_str(&format!(r#"{{"positions": [{json}]}}"#)).unwrap();
+        let portfolio = response.portfolio();
+        let position = &portfolio.positions[0];
+        assert_eq!(position.kind, PositionKind::Unknown);
+        assert_eq!(position.protocol(), "GMX");
+        assert_eq!(position.chain_id(), 42161);
+        // Unknown positions still count when the API values them
+        assert!((portfolio.net_value_usd() - 250.5).abs() < 1e-9);
+
+        let original: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(serde_json::to_value(&position.raw).unwrap(), original);
+    }
+
     #[test]
     fn test_webhook_deserialization() {
         let json = r#"{
@@ -655,7 +1252,14 @@ mod deserialization_tests {
 
         let webhook: Webhook = serde_json::from_str(json).unwrap();
         assert_eq!(webhook.id, "019a81c2-d84b-7141-85e8-86b072a59142");
-        assert_eq!(webhook.webhook_type, "balances");
+        assert_eq!(webhook.webhook_type(), WebhookType::Balances);
+        assert_eq!(
+            webhook.filter,
+            WebhookFilter::Balances {
+                asset_type: Some(AssetType::Erc20),
+                token_address: Some("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48".to_string()),
+            }
+        );
         assert!(webhook.active);
         assert_eq!(webhook.chain_ids.as_ref().unwrap().len(), 3);
     }
@@ -685,7 +1289,86 @@ mod deserialization_tests {
 
         let response: WebhooksListResponse = serde_json::from_str(json).unwrap();
         assert_eq!(response.webhooks.len(), 2);
-        assert_eq!(response.webhooks[1].activity_type.as_deref(), Some("swap"));
+        assert_eq!(
+            response.webhooks[1].filter,
+            WebhookFilter::Activities {
+                activity_type: Some(ActivityType::Swap),
+                asset_type: None,
+                token_address: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_webhook_filter_unknown_values() {
+        let json = r#"{
+            "type": "activities",
+            "activity_type": "bridge",
+            "asset_type": "erc4626"
+        }"#;
+        let filter: WebhookFilter = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            filter,
+            WebhookFilter::Activities {
+                activity_type: Some(ActivityType::Unknown),
+                asset_type: Some(AssetType::Unknown),
+                token_address: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_unknown_filter_values_are_not_sent_back() {
+        let filter = WebhookFilter::Activities {
+            activity_type: Some(ActivityType::Unknown),
+            asset_type: None,
+            token_address: None,
+        };
+        let request = CreateWebhookRequest::new("a", "https://example.com", vec![], filter);
+        assert!(serde_json::to_value(&request).is_err());
+        assert!(serde_json::to_value(TransactionDirection::Unknown).is_err());
+        assert!(serde_json::to_value(AssetType::Unknown).is_err());
+    }
+
+    #[test]
+    fn test_webhooks_list_tolerates_unknown_type() {
+        let json = r#"{
+            "webhooks": [
+                {
+                    "id": "019a81c2-d84b-7141-85e8-86b072a59142",
+                    "name": "Balance Monitor",
+                    "type": "balances",
+                    "url": "https://example.com/webhooks",
+                    "active": true
+                },
+                {
+                    "id": "019a81c2-e95c-7241-95f9-97c183b6a253",
+                    "name": "Log Watcher",
+                    "type": "logs",
+                    "url": "https://example.com/logs",
+                    "active": true,
+                    "topic0": "0xddf252ad"
+                }
+            ],
+            "next_offset": null
+        }"#;
+
+        let response: WebhooksListResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.webhooks.len(), 2);
+        let unknown = &response.webhooks[1];
+        assert_eq!(unknown.name, "Log Watcher");
+        assert_eq!(unknown.filter, WebhookFilter::Unknown);
+        assert_eq!(unknown.webhook_type(), WebhookType::Unknown);
+        assert!(serde_json::to_value(unknown).is_err());
+
+        // Updating other fields of such a webhook leaves its filters alone
+        let update = UpdateWebhookRequest::new()
+            .with_active(false)
+            .with_filter(unknown.filter.clone());
+        assert_eq!(
+            serde_json::to_value(&update).unwrap(),
+            serde_json::json!({ "active": false })
+        );
     }
 
     #[test]
@@ -702,4 +1385,89 @@ mod deserialization_tests {
         assert_eq!(response.addresses.len(), 2);
         assert!(response.next_offset.is_some());
     }
+
+    #[test]
+    fn test_create_webhook_request_serializes_typed_filter() {
+        let request = CreateWebhookRequest::new(
+            "USDC transfers",
+            "https://example.com/hook",
+            vec!["0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string()],
+            WebhookFilter::Activities {
+                activity_type: Some(ActivityType::Receive),
+                asset_type: Some(AssetType::Erc20),
+                token_address: Some("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48".to_string()),
+            },
+        )
+        .with_chain_ids(vec![1, 8453]);
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "name": "USDC transfers",
+                "url": "https://example.com/hook",
+                "type": "activities",
+                "activity_type": "receive",
+                "asset_type": "erc20",
+                "token_address": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+                "addresses": ["0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045"],
+                "chain_ids": [1, 8453]
+            })
+        );
+
+        // Unset filters are omitted
+        let json = serde_json::to_value(CreateWebhookRequest::transactions(
+            "txs",
+            "https://example.com",
+            vec![],
+        ))
+        .unwrap();
+        assert_eq!(json["type"], "transactions");
+        assert!(json.get("transaction_type").is_none());
+    }
+
+    #[test]
+    fn test_update_webhook_request_with_filter() {
+        let request = UpdateWebhookRequest::new().with_active(false).with_filter(
+            WebhookFilter::Transactions {
+                transaction_type: Some(TransactionDirection::Sender),
+                counterparty: None,
+            },
+        );
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"active": false, "transaction_type": "sender"})
+        );
+    }
+
+    #[test]
+    fn test_created_webhook_returns_id_and_secret() {
+        let json = r#"{
+            "id": "019a81c2-d84b-7141-85e8-86b072a59142",
+            "name": "Sends",
+            "type": "transactions",
+            "url": "https://example.com/hook",
+            "active": true,
+            "transaction_type": "sender",
+            "secret": "whsec_abc123"
+        }"#;
+
+        let created: CreatedWebhook = serde_json::from_str(json).unwrap();
+        assert_eq!(created.id(), "019a81c2-d84b-7141-85e8-86b072a59142");
+        assert_eq!(created.secret(), Some("whsec_abc123"));
+        assert_eq!(created.webhook.webhook_type(), WebhookType::Transactions);
+
+        // Filter values newer than this client still deserialize
+        let newer = json.replace(r#""sender""#, r#""both""#);
+        let created: CreatedWebhook = serde_json::from_str(&newer).unwrap();
+        assert_eq!(
+            created.webhook.filter,
+            WebhookFilter::Transactions {
+                transaction_type: Some(TransactionDirection::Unknown),
+                counterparty: None,
+            }
+        );
+    }
 }
@@ -1,6 +1,6 @@
 //! Token info API endpoints
 
-use super::types::{TokenInfoOptions, TokensResponse};
+use super::types::{SymbolMatches, TokenInfo, TokenInfoOptions, TokensResponse};
 use crate::client::Client;
 use crate::error::Result;
 
@@ -28,4 +28,67 @@ impl<'a> TokensApi<'a> {
         );
         self.client.get(&path).await
     }
+
+    /// Get token info for several `(chain_id, address)` pairs in one request
+    ///
+    /// Returns the tokens found, in the order requested; pairs the API has
+    /// nothing for are left out.
+    pub async fn get_multi(&self, tokens: &[(u64, &str)]) -> Result<Vec<TokenInfo>> {
+        if tokens.is_empty() {
+            return Ok(Vec::new());
+        }
+        let response: TokensResponse = self.client.get(&multi_path(tokens)).await?;
+        Ok(response.select(tokens))
+    }
+
+    /// Rank candidate tokens for a symbol by chain preference and liquidity
+    ///
+    /// The token-info endpoint looks tokens up by address only, so the
+    /// candidates come from the caller (e.g. a token list) and are fetched
+    /// in one batched request. Several tokens often share a symbol; check
+    /// [`SymbolMatches::best`] or go through the candidates rather than
+    /// taking the first one.
+    ///
+    /// # Arguments
+    /// * `symbol` - Token symbol, e.g. "USDC"
+    /// * `candidates` - `(chain_id, address)` pairs that may carry the symbol
+    /// * `preferred_chains` - Chain IDs, most preferred first
+    pub async fn resolve_symbol(
+        &self,
+        symbol: &str,
+        candidates: &[(u64, &str)],
+        preferred_chains: &[u64],
+    ) -> Result<SymbolMatches> {
+        let tokens = self.get_multi(candidates).await?;
+        Ok(SymbolMatches::rank(symbol, tokens, preferred_chains))
+    }
+}
+
+fn join_chain_ids(chain_ids: impl IntoIterator<Item = u64>) -> String {
+    let mut seen = Vec::new();
+    for id in chain_ids {
+        if !seen.contains(&id) {
+            seen.push(id);
+        }
+    }
+    seen.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// `/v1/evm/token-info/{a},{b}?chain_ids={x},{y}` with duplicates removed
+pub(crate) fn multi_path(tokens: &[(u64, &str)]) -> String {
+    let mut addresses: Vec<String> = Vec::new();
+    for (_, address) in tokens {
+        let address = address.to_lowercase();
+        if !addresses.contains(&address) {
+            addresses.push(address);
+        }
+    }
+    format!(
+        "/v1/evm/token-info/{}?chain_ids={}",
+        addresses.join(","),
+        join_chain_ids(tokens.iter().map(|(id, _)| *id))
+    )
 }
@@ -3,5 +3,7 @@
 mod api;
 mod types;
 
+#[cfg(test)]
+pub(crate) use api::multi_path;
 pub use api::TokensApi;
 pub use types::*;
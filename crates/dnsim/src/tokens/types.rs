@@ -2,6 +2,11 @@
 
 use crate::balances::HistoricalPricePoint;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// A same-chain candidate needs this much less liquidity than the top one
+/// for a symbol lookup to be unambiguous
+pub const LIQUIDITY_DOMINANCE: f64 = 10.0;
 
 /// Tokens response
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -14,6 +19,47 @@ pub struct TokensResponse {
     pub next_offset: Option<String>,
 }
 
+impl TokensResponse {
+    /// Chain IDs the token exists on, ascending
+    #[must_use]
+    pub fn chain_ids(&self) -> Vec<i64> {
+        let mut chain_ids: Vec<i64> = self.tokens.iter().map(|t| t.chain_id).collect();
+        chain_ids.sort_unstable();
+        chain_ids.dedup();
+        chain_ids
+    }
+
+    /// Tokens matching `lookups`, in lookup order
+    ///
+    /// Batched responses cover every requested address on every requested
+    /// chain; pairs that weren't asked for are dropped, as are lookups the
+    /// API had nothing for. Each returned token has `address` set.
+    #[must_use]
+    pub fn select(&self, lookups: &[(u64, &str)]) -> Vec<TokenInfo> {
+        lookups
+            .iter()
+            .filter_map(|(chain_id, address)| {
+                self.tokens
+                    .iter()
+                    .find(|t| {
+                        i64::try_from(*chain_id).is_ok_and(|id| id == t.chain_id)
+                            && t.address
+                                .as_deref()
+                                .unwrap_or(&self.contract_address)
+                                .eq_ignore_ascii_case(address)
+                    })
+                    .map(|t| {
+                        let mut token = t.clone();
+                        token
+                            .address
+                            .get_or_insert_with(|| self.contract_address.clone());
+                        token
+                    })
+            })
+            .collect()
+    }
+}
+
 /// Token info
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct TokenInfo {
@@ -21,6 +67,8 @@ pub struct TokenInfo {
     pub chain: String,
     /// Chain ID
     pub chain_id: i64,
+    /// Contract address (set per token in batched lookups)
+    pub address: Option<String>,
     /// Token symbol
     pub symbol: String,
     /// Token name
@@ -35,10 +83,118 @@ pub struct TokenInfo {
     pub total_supply: Option<String>,
     /// Market cap
     pub market_cap: Option<f64>,
+    /// Fully diluted value in USD
+    pub fully_diluted_value: Option<f64>,
+    /// Trading volume over the last 24 hours in USD
+    pub volume_24h: Option<f64>,
+    /// Liquidity of the deepest pool in USD
+    pub pool_size: Option<f64>,
+    /// Deepest liquidity pools
+    #[serde(default)]
+    pub pools: Vec<TokenPool>,
     /// Logo URL
     pub logo: Option<String>,
 }
 
+impl TokenInfo {
+    /// Liquidity in USD: `pool_size`, or the deepest pool listed
+    #[must_use]
+    pub fn liquidity_usd(&self) -> Option<f64> {
+        self.pool_size.or_else(|| {
+            self.pools
+                .iter()
+                .filter_map(|p| p.liquidity_usd)
+                .max_by(f64::total_cmp)
+        })
+    }
+}
+
+/// Liquidity pool holding a token
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TokenPool {
+    /// DEX name (e.g. `uniswap_v3`)
+    pub dex: String,
+    /// Pool (pair) contract address
+    #[serde(alias = "pool_address")]
+    pub pair_address: String,
+    /// Pool liquidity in USD
+    pub liquidity_usd: Option<f64>,
+}
+
+/// Tokens sharing a symbol, ranked
+///
+/// Tokens on preferred chains come first (in preference order), then by
+/// liquidity, deepest first; tokens without liquidity data rank last.
+#[derive(Debug, Clone, Serialize)]
+pub struct SymbolMatches {
+    /// Symbol searched for
+    pub symbol: String,
+    /// Matching tokens, best first
+    pub candidates: Vec<TokenInfo>,
+}
+
+impl SymbolMatches {
+    /// Rank the tokens whose symbol matches (case-insensitively)
+    #[must_use]
+    pub fn rank(symbol: &str, tokens: Vec<TokenInfo>, preferred_chains: &[u64]) -> Self {
+        let preference = |token: &TokenInfo| {
+            preferred_chains
+                .iter()
+                .position(|id| i64::try_from(*id).is_ok_and(|id| id == token.chain_id))
+                .unwrap_or(preferred_chains.len())
+        };
+        let mut candidates: Vec<TokenInfo> = tokens
+            .into_iter()
+            .filter(|t| t.symbol.eq_ignore_ascii_case(symbol))
+            .collect();
+        candidates.sort_by(|a, b| {
+            preference(a)
+                .cmp(&preference(b))
+                .then_with(|| match (a.liquidity_usd(), b.liquidity_usd()) {
+                    (Some(a), Some(b)) => b.total_cmp(&a),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                })
+                .then_with(|| a.chain_id.cmp(&b.chain_id))
+                .then_with(|| a.address.cmp(&b.address))
+        });
+        Self {
+            symbol: symbol.to_string(),
+            candidates,
+        }
+    }
+
+    /// Whether another token on the top candidate's chain could be meant
+    ///
+    /// It could unless the top candidate has at least
+    /// [`LIQUIDITY_DOMINANCE`] times its liquidity. The same token on other
+    /// chains doesn't count.
+    #[must_use]
+    pub fn is_ambiguous(&self) -> bool {
+        let Some(top) = self.candidates.first() else {
+            return false;
+        };
+        self.candidates[1..]
+            .iter()
+            .filter(|t| t.chain_id == top.chain_id)
+            .any(|t| match (top.liquidity_usd(), t.liquidity_usd()) {
+                (Some(top), Some(other)) => top < other * LIQUIDITY_DOMINANCE,
+                (Some(_), None) => false,
+                (None, _) => true,
+            })
+    }
+
+    /// The top candidate, if the symbol resolves unambiguously
+    #[must_use]
+    pub fn best(&self) -> Option<&TokenInfo> {
+        if self.is_ambiguous() {
+            return None;
+        }
+        self.candidates.first()
+    }
+}
+
 /// Query options for token info
 #[derive(Debug, Clone)]
 pub struct TokenInfoOptions {
@@ -1,9 +1,9 @@
 //! Webhooks API endpoints (Beta)
 
 use super::types::{
-    AddressesListOptions, AddressesListResponse, CreateWebhookRequest, ReplaceAddressesRequest,
-    UpdateAddressesRequest, UpdateWebhookRequest, Webhook, WebhooksListOptions,
-    WebhooksListResponse,
+    AddressesListOptions, AddressesListResponse, CreateWebhookRequest, CreatedWebhook,
+    ReplaceAddressesRequest, UpdateAddressesRequest, UpdateWebhookRequest, Webhook,
+    WebhooksListOptions, WebhooksListResponse,
 };
 use crate::client::Client;
 use crate::error::Result;
@@ -37,7 +37,11 @@ impl<'a> WebhooksApi<'a> {
     }
 
     /// Create a webhook
-    pub async fn create(&self, request: &CreateWebhookRequest) -> Result<Webhook> {
+    ///
+    /// The response carries the webhook ID and, when the API issues one,
+    /// the secret for verifying deliveries; store it, it is not returned
+    /// again.
+    pub async fn create(&self, request: &CreateWebhookRequest) -> Result<CreatedWebhook> {
         self.client
             .post("/beta/evm/subscriptions/webhooks", request)
             .await
@@ -11,6 +11,172 @@ pub struct WebhooksListResponse {
     pub next_offset: Option<String>,
 }
 
+/// Kind of events a webhook delivers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookType {
+    /// Transactions sent or received by the watched addresses
+    Transactions,
+    /// Decoded address activity (transfers, swaps, mints, ...)
+    Activities,
+    /// Token balance changes
+    Balances,
+    /// Type not known to this client version; serializing it fails
+    #[serde(other, skip_serializing)]
+    Unknown,
+}
+
+/// Which side of a transaction the watched address is on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransactionDirection {
+    /// Address sent the transaction
+    Sender,
+    /// Address received the transaction
+    Receiver,
+    /// Value not known to this client version; it can't be sent back, so
+    /// serializing it fails
+    #[serde(other, skip_serializing)]
+    Unknown,
+}
+
+/// Activity kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ActivityType {
+    /// Token approval
+    Approve,
+    /// Token mint
+    Mint,
+    /// Token burn
+    Burn,
+    /// Incoming transfer
+    Receive,
+    /// Outgoing transfer
+    Send,
+    /// DEX swap
+    Swap,
+    /// Other contract call
+    Call,
+    /// Value not known to this client version; it can't be sent back, so
+    /// serializing it fails
+    #[serde(other, skip_serializing)]
+    Unknown,
+}
+
+/// Asset standard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AssetType {
+    /// Native gas token
+    Native,
+    /// ERC-20 token
+    Erc20,
+    /// ERC-721 NFT
+    Erc721,
+    /// ERC-1155 token
+    Erc1155,
+    /// Value not known to this client version; it can't be sent back, so
+    /// serializing it fails
+    #[serde(other, skip_serializing)]
+    Unknown,
+}
+
+/// Webhook type together with the filters that type supports
+///
+/// Serialized as the `type` field plus the filter fields, so a request can
+/// only carry filters valid for its webhook type. Webhooks of a type this
+/// client doesn't know read as [`WebhookFilter::Unknown`], which can't be
+/// serialized, so listing them works but they can't be re-sent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum WebhookFilter {
+    /// Transactions of the watched addresses
+    Transactions {
+        /// Only transactions where the address is the sender or receiver
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        transaction_type: Option<TransactionDirection>,
+        /// Only transactions with this counterparty address
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        counterparty: Option<String>,
+    },
+    /// Activity of the watched addresses (e.g. token transfers)
+    Activities {
+        /// Only this kind of activity
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        activity_type: Option<ActivityType>,
+        /// Only this asset standard
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        asset_type: Option<AssetType>,
+        /// Only this token
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        token_address: Option<String>,
+    },
+    /// Balance changes of the watched addresses
+    Balances {
+        /// Only this asset standard
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        asset_type: Option<AssetType>,
+        /// Only this token
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        token_address: Option<String>,
+    },
+    /// Webhook type not known to this client version
+    #[serde(other, skip_serializing)]
+    Unknown,
+}
+
+impl WebhookFilter {
+    /// All transactions
+    #[must_use]
+    pub fn transactions() -> Self {
+        Self::Transactions {
+            transaction_type: None,
+            counterparty: None,
+        }
+    }
+
+    /// All activity
+    #[must_use]
+    pub fn activities() -> Self {
+        Self::Activities {
+            activity_type: None,
+            asset_type: None,
+            token_address: None,
+        }
+    }
+
+    /// Transfers of one token, in or out
+    #[must_use]
+    pub fn token_transfers(token_address: impl Into<String>) -> Self {
+        Self::Activities {
+            activity_type: None,
+            asset_type: None,
+            token_address: Some(token_address.into()),
+        }
+    }
+
+    /// All balance changes
+    #[must_use]
+    pub fn balances() -> Self {
+        Self::Balances {
+            asset_type: None,
+            token_address: None,
+        }
+    }
+
+    /// The webhook type this filter belongs to
+    #[must_use]
+    pub fn webhook_type(&self) -> WebhookType {
+        match self {
+            Self::Transactions { .. } => WebhookType::Transactions,
+            Self::Activities { .. } => WebhookType::Activities,
+            Self::Balances { .. } => WebhookType::Balances,
+            Self::Unknown => WebhookType::Unknown,
+        }
+    }
+}
+
 /// Webhook
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Webhook {
@@ -22,29 +188,52 @@ pub struct Webhook {
     pub name: String,
     /// Callback URL
     pub url: String,
-    /// Webhook type (transactions, activities, balances)
-    #[serde(rename = "type")]
-    pub webhook_type: String,
+    /// Webhook type and filters
+    #[serde(flatten)]
+    pub filter: WebhookFilter,
     /// Is active
     pub active: bool,
     /// Chain IDs (null if not filtered)
     pub chain_ids: Option<Vec<i64>>,
-    /// Transaction type filter (sender, receiver)
-    pub transaction_type: Option<String>,
-    /// Counterparty address filter
-    pub counterparty: Option<String>,
-    /// Activity type filter
-    pub activity_type: Option<String>,
-    /// Asset type filter (native, erc20, erc721, erc1155)
-    pub asset_type: Option<String>,
-    /// Token address filter
-    pub token_address: Option<String>,
     /// Created at
     pub created_at: Option<String>,
     /// Updated at
     pub updated_at: Option<String>,
 }
 
+impl Webhook {
+    /// Webhook type
+    #[must_use]
+    pub fn webhook_type(&self) -> WebhookType {
+        self.filter.webhook_type()
+    }
+}
+
+/// Webhook returned by [`WebhooksApi::create`](super::WebhooksApi::create)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CreatedWebhook {
+    /// The created webhook
+    #[serde(flatten)]
+    pub webhook: Webhook,
+    /// Secret for verifying delivery signatures (only returned on create)
+    #[serde(default)]
+    pub secret: Option<String>,
+}
+
+impl CreatedWebhook {
+    /// Webhook ID
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.webhook.id
+    }
+
+    /// Delivery signing secret, if the API returned one
+    #[must_use]
+    pub fn secret(&self) -> Option<&str> {
+        self.secret.as_deref()
+    }
+}
+
 /// Create webhook request
 #[derive(Debug, Clone, Serialize)]
 pub struct CreateWebhookRequest {
@@ -52,85 +241,70 @@ pub struct CreateWebhookRequest {
     pub name: String,
     /// Callback URL
     pub url: String,
-    /// Webhook type (transactions, activities, balances)
-    #[serde(rename = "type")]
-    pub webhook_type: String,
+    /// Webhook type and filters
+    #[serde(flatten)]
+    pub filter: WebhookFilter,
     /// Addresses to watch
     pub addresses: Vec<String>,
     /// Chain IDs (optional)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub chain_ids: Option<Vec<i64>>,
-    /// Transaction type filter (sender, receiver) - transactions only
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub transaction_type: Option<String>,
-    /// Counterparty address filter - transactions only
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub counterparty: Option<String>,
-    /// Activity type filter - activities only
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub activity_type: Option<String>,
-    /// Asset type filter (native, erc20, erc721, erc1155)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub asset_type: Option<String>,
-    /// Token address filter
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub token_address: Option<String>,
 }
 
 impl CreateWebhookRequest {
-    /// Create a new webhook request for transactions
+    /// Create a webhook request with an explicit filter
     #[must_use]
-    pub fn transactions(name: &str, url: &str, addresses: Vec<String>) -> Self {
+    pub fn new(
+        name: impl Into<String>,
+        url: impl Into<String>,
+        addresses: Vec<String>,
+        filter: WebhookFilter,
+    ) -> Self {
         Self {
-            name: name.to_string(),
-            url: url.to_string(),
-            webhook_type: "transactions".to_string(),
+            name: name.into(),
+            url: url.into(),
+            filter,
             addresses,
             chain_ids: None,
-            transaction_type: None,
-            counterparty: None,
-            activity_type: None,
-            asset_type: None,
-            token_address: None,
         }
     }
 
+    /// Create a new webhook request for transactions
+    #[must_use]
+    pub fn transactions(name: &str, url: &str, addresses: Vec<String>) -> Self {
+        Self::new(name, url, addresses, WebhookFilter::transactions())
+    }
+
     /// Create a new webhook request for activities
     #[must_use]
     pub fn activities(name: &str, url: &str, addresses: Vec<String>) -> Self {
-        Self {
-            name: name.to_string(),
-            url: url.to_string(),
-            webhook_type: "activities".to_string(),
-            addresses,
-            chain_ids: None,
-            transaction_type: None,
-            counterparty: None,
-            activity_type: None,
-            asset_type: None,
-            token_address: None,
-        }
+        Self::new(name, url, addresses, WebhookFilter::activities())
     }
 
     /// Create a new webhook request for balances
     #[must_use]
     pub fn balances(name: &str, url: &str, addresses: Vec<String>) -> Self {
-        Self {
-            name: name.to_string(),
-            url: url.to_string(),
-            webhook_type: "balances".to_string(),
-            addresses,
-            chain_ids: None,
-            transaction_type: None,
-            counterparty: None,
-            activity_type: None,
-            asset_type: None,
-            token_address: None,
-        }
+        Self::new(name, url, addresses, WebhookFilter::balances())
+    }
+
+    /// Replace the webhook type and filters
+    #[must_use]
+    pub fn with_filter(mut self, filter: WebhookFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Only deliver events on these chains
+    #[must_use]
+    pub fn with_chain_ids(mut self, chain_ids: Vec<i64>) -> Self {
+        self.chain_ids = Some(chain_ids);
+        self
     }
 }
 
 /// Update webhook request
+///
+/// The webhook type cannot be changed; filters must belong to it.
 #[derive(Debug, Clone, Default, Serialize)]
 pub struct UpdateWebhookRequest {
     /// Webhook name
@@ -145,18 +319,18 @@ pub struct UpdateWebhookRequest {
     /// Chain IDs
     #[serde(skip_serializing_if = "Option::is_none")]
     pub chain_ids: Option<Vec<i64>>,
-    /// Transaction type filter (sender, receiver)
+    /// Transaction direction filter
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub transaction_type: Option<String>,
+    pub transaction_type: Option<TransactionDirection>,
     /// Counterparty address filter
     #[serde(skip_serializing_if = "Option::is_none")]
     pub counterparty: Option<String>,
     /// Activity type filter
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub activity_type: Option<String>,
+    pub activity_type: Option<ActivityType>,
     /// Asset type filter
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub asset_type: Option<String>,
+    pub asset_type: Option<AssetType>,
     /// Token address filter
     #[serde(skip_serializing_if = "Option::is_none")]
     pub token_address: Option<String>,
@@ -167,6 +341,70 @@ impl UpdateWebhookRequest {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Rename the webhook
+    #[must_use]
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Change the callback URL
+    #[must_use]
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Pause or resume deliveries
+    #[must_use]
+    pub fn with_active(mut self, active: bool) -> Self {
+        self.active = Some(active);
+        self
+    }
+
+    /// Only deliver events on these chains
+    #[must_use]
+    pub fn with_chain_ids(mut self, chain_ids: Vec<i64>) -> Self {
+        self.chain_ids = Some(chain_ids);
+        self
+    }
+
+    /// Set the filters from a [`WebhookFilter`]
+    ///
+    /// Only the filters the variant sets are sent; the variant must match
+    /// the webhook's existing type. [`WebhookFilter::Unknown`] changes
+    /// nothing.
+    #[must_use]
+    pub fn with_filter(mut self, filter: WebhookFilter) -> Self {
+        match filter {
+            WebhookFilter::Transactions {
+                transaction_type,
+                counterparty,
+            } => {
+                self.transaction_type = transaction_type;
+                self.counterparty = counterparty;
+            }
+            WebhookFilter::Activities {
+                activity_type,
+                asset_type,
+                token_address,
+            } => {
+                self.activity_type = activity_type;
+                self.asset_type = asset_type;
+                self.token_address = token_address;
+            }
+            WebhookFilter::Balances {
+                asset_type,
+                token_address,
+            } => {
+                self.asset_type = asset_type;
+                self.token_address = token_address;
+            }
+            WebhookFilter::Unknown => {}
+        }
+        self
+    }
 }
 
 /// Addresses list response
@@ -11,6 +11,7 @@
 //! - Position management (enter/exit strategies)
 //! - Support for lending, DEXs, yield farming
 //! - Gas-efficient batched transactions
+//! - Yield route discovery ([`Client::find_yield_routes`])
 //!
 //! # Quick Start
 //!
@@ -40,11 +41,18 @@
 
 pub mod error;
 pub mod types;
+pub mod yields;
 
 pub use error::{Error, Result};
 pub use types::{
-    ApiErrorResponse, BundleAction, BundleRequest, BundleResponse, Chain, RouteRequest,
-    RouteResponse, RouteStep, RoutingStrategy, TokenBalance, TokenPrice, TransactionData,
+    ApiErrorResponse, BundleAction, BundleRequest, BundleResponse, Chain, PageMeta, Protocol,
+    ProtocolChain, RouteRequest, RouteResponse, RouteStep, RoutingStrategy, TokenBalance,
+    TokenData, TokenPrice, TokenType, TokensRequest, TokensResponse, TransactionData,
+    UnderlyingToken,
+};
+pub use yields::{
+    ApyFuture, ApyProvider, ApySource, YieldFilter, YieldRouteFailure, YieldRouteOption,
+    YieldRoutes, QUOTE_ONLY_ADDRESS,
 };
 
 // Re-export common utilities
@@ -207,6 +215,32 @@ impl Client {
             .get::<Vec<TokenBalance>, _>(&path, &[] as &[(&str, &str)])
             .await
     }
+
+    /// List tokens, including protocol position tokens
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - Token filters and page
+    pub async fn get_tokens(&self, request: &TokensRequest) -> Result<TokensResponse> {
+        self.base
+            .get::<TokensResponse, _>("/api/v1/tokens", &request.to_query_params())
+            .await
+    }
+
+    /// List protocols Enso can route into
+    ///
+    /// # Arguments
+    ///
+    /// * `chain_id` - Only protocols on this chain
+    pub async fn get_protocols(&self, chain_id: Option<u64>) -> Result<Vec<Protocol>> {
+        let params: Vec<(&str, String)> = chain_id
+            .map(|id| ("chainId", id.to_string()))
+            .into_iter()
+            .collect();
+        self.base
+            .get::<Vec<Protocol>, _>("/api/v1/protocols", &params)
+            .await
+    }
 }
 
 #[cfg(test)]
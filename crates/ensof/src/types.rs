@@ -479,6 +479,261 @@ pub struct TokenBalance {
     pub usd_value: Option<f64>,
 }
 
+/// Kind of token in the tokens endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenType {
+    /// Plain tokens (ETH, USDC, ...)
+    Base,
+    /// Position tokens of a `DeFi` protocol (vault shares, aTokens, LP tokens)
+    Defi,
+}
+
+impl TokenType {
+    /// Get the type as used in the API
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Base => "base",
+            Self::Defi => "defi",
+        }
+    }
+}
+
+/// Query for the tokens endpoint
+#[derive(Debug, Clone, Default)]
+pub struct TokensRequest {
+    /// Chain ID
+    pub chain_id: Option<u64>,
+    /// Token type
+    pub token_type: Option<TokenType>,
+    /// Only tokens of this protocol
+    pub protocol_slug: Option<String>,
+    /// Only positions with this underlying token
+    pub underlying_token: Option<String>,
+    /// Only this token
+    pub address: Option<String>,
+    /// Include name, symbol and logos
+    pub include_metadata: Option<bool>,
+    /// Page number (1-based)
+    pub page: Option<u32>,
+}
+
+impl TokensRequest {
+    /// Create a query for tokens on a chain
+    #[must_use]
+    pub fn new(chain_id: u64) -> Self {
+        Self {
+            chain_id: Some(chain_id),
+            ..Self::default()
+        }
+    }
+
+    /// Only tokens of this type
+    #[must_use]
+    pub fn with_type(mut self, token_type: TokenType) -> Self {
+        self.token_type = Some(token_type);
+        self
+    }
+
+    /// Only tokens of this protocol
+    #[must_use]
+    pub fn with_protocol(mut self, slug: impl Into<String>) -> Self {
+        self.protocol_slug = Some(slug.into());
+        self
+    }
+
+    /// Only positions with this underlying token
+    #[must_use]
+    pub fn with_underlying_token(mut self, token: impl Into<String>) -> Self {
+        self.underlying_token = Some(token.into());
+        self
+    }
+
+    /// Only this token
+    #[must_use]
+    pub fn with_address(mut self, address: impl Into<String>) -> Self {
+        self.address = Some(address.into());
+        self
+    }
+
+    /// Include name, symbol and logos
+    #[must_use]
+    pub fn with_metadata(mut self, include: bool) -> Self {
+        self.include_metadata = Some(include);
+        self
+    }
+
+    /// Request a page (1-based)
+    #[must_use]
+    pub fn with_page(mut self, page: u32) -> Self {
+        self.page = Some(page);
+        self
+    }
+
+    /// Query parameters for the request
+    #[must_use]
+    pub fn to_query_params(&self) -> Vec<(&'static str, String)> {
+        let mut params = Vec::new();
+        if let Some(chain_id) = self.chain_id {
+            params.push(("chainId", chain_id.to_string()));
+        }
+        if let Some(token_type) = self.token_type {
+            params.push(("type", token_type.as_str().to_string()));
+        }
+        if let Some(slug) = &self.protocol_slug {
+            params.push(("protocolSlug", slug.clone()));
+        }
+        if let Some(token) = &self.underlying_token {
+            params.push(("underlyingTokens", token.clone()));
+        }
+        if let Some(address) = &self.address {
+            params.push(("address", address.clone()));
+        }
+        if let Some(include) = self.include_metadata {
+            params.push(("includeMetadata", include.to_string()));
+        }
+        if let Some(page) = self.page {
+            params.push(("page", page.to_string()));
+        }
+        params
+    }
+}
+
+/// A page of tokens
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokensResponse {
+    /// Tokens on this page
+    pub data: Vec<TokenData>,
+    /// Pagination
+    #[serde(default)]
+    pub meta: Option<PageMeta>,
+}
+
+impl TokensResponse {
+    /// The next page number, if there is one
+    #[must_use]
+    pub fn next_page(&self) -> Option<u32> {
+        self.meta.as_ref().and_then(|meta| meta.next)
+    }
+}
+
+/// Pagination of a paged response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageMeta {
+    /// Total number of items
+    #[serde(default)]
+    pub total: Option<u64>,
+    /// Last page number
+    #[serde(default)]
+    pub last_page: Option<u32>,
+    /// Current page number
+    #[serde(default)]
+    pub current_page: Option<u32>,
+    /// Items per page
+    #[serde(default)]
+    pub per_page: Option<u32>,
+    /// Previous page number
+    #[serde(default)]
+    pub prev: Option<u32>,
+    /// Next page number
+    #[serde(default)]
+    pub next: Option<u32>,
+}
+
+/// A token from the tokens endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenData {
+    /// Token address
+    pub address: String,
+    /// Chain ID
+    pub chain_id: u64,
+    /// Token type
+    #[serde(rename = "type")]
+    pub token_type: TokenType,
+    /// Token decimals
+    #[serde(default)]
+    pub decimals: Option<u8>,
+    /// Token symbol
+    #[serde(default)]
+    pub symbol: Option<String>,
+    /// Token name
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Protocol the position belongs to (defi tokens)
+    #[serde(default)]
+    pub protocol_slug: Option<String>,
+    /// Project the protocol belongs to (defi tokens)
+    #[serde(default)]
+    pub project: Option<String>,
+    /// Tokens the position holds (defi tokens)
+    #[serde(default)]
+    pub underlying_tokens: Vec<UnderlyingToken>,
+    /// APY in percent, where Enso has one
+    #[serde(default)]
+    pub apy: Option<f64>,
+    /// TVL in USD, where Enso has one
+    #[serde(default)]
+    pub tvl: Option<f64>,
+}
+
+/// Underlying token of a position
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnderlyingToken {
+    /// Token address
+    pub address: String,
+    /// Token symbol
+    #[serde(default)]
+    pub symbol: Option<String>,
+    /// Token decimals
+    #[serde(default)]
+    pub decimals: Option<u8>,
+}
+
+/// A protocol Enso can route into
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Protocol {
+    /// Protocol slug (used in `protocolSlug` filters and bundle actions)
+    pub slug: String,
+    /// Display name
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Project the protocol belongs to
+    #[serde(default)]
+    pub project: Option<String>,
+    /// Description
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Website
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Chains the protocol is supported on
+    #[serde(default)]
+    pub chains: Vec<ProtocolChain>,
+}
+
+impl Protocol {
+    /// Whether the protocol is supported on a chain
+    #[must_use]
+    pub fn supports_chain(&self, chain_id: u64) -> bool {
+        self.chains.iter().any(|chain| chain.id == chain_id)
+    }
+}
+
+/// A chain of a [`Protocol`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProtocolChain {
+    /// Chain ID
+    pub id: u64,
+    /// Chain name
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
 /// API error response
 #[derive(Debug, Clone, Deserialize)]
 pub struct ApiErrorResponse {
@@ -524,6 +779,49 @@ mod tests {
         assert_eq!(request.token_in.len(), 1);
     }
 
+    #[test]
+    fn test_tokens_request_params() {
+        let request = TokensRequest::new(1)
+            .with_type(TokenType::Defi)
+            .with_underlying_token("0xUsdc")
+            .with_page(2);
+        assert_eq!(
+            request.to_query_params(),
+            vec![
+                ("chainId", "1".to_string()),
+                ("type", "defi".to_string()),
+                ("underlyingTokens", "0xUsdc".to_string()),
+                ("page", "2".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokens_response() {
+        let json = r#"{
+            "data": [{
+                "address": "0xbeef",
+                "chainId": 1,
+                "type": "defi",
+                "decimals": 18,
+                "symbol": "yvUSDC",
+                "protocolSlug": "yearn-v3",
+                "underlyingTokens": [{"address": "0xusdc", "symbol": "USDC", "decimals": 6}],
+                "apy": 4.2,
+                "tvl": 1000000
+            }],
+            "meta": {"total": 120, "lastPage": 2, "currentPage": 1, "perPage": 100, "prev": null, "next": 2}
+        }"#;
+        let response: TokensResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.data[0].token_type, TokenType::Defi);
+        assert_eq!(
+            response.data[0].underlying_tokens[0].symbol.as_deref(),
+            Some("USDC")
+        );
+        assert_eq!(response.data[0].apy, Some(4.2));
+        assert_eq!(response.next_page(), Some(2));
+    }
+
     #[test]
     fn test_bundle_action() {
         let action = BundleAction::swap("0xIn", "0xOut", "1000");
@@ -0,0 +1,550 @@
+//! Yield route discovery
+//!
+//! Finds the best vault or lending position to route a token into: candidate
+//! position tokens come from the tokens endpoint, routes into the best
+//! candidates are fetched concurrently, and the results are ranked by APY.
+//!
+//! Enso's APY data is patchy, so ranking can use an injected
+//! [`ApyProvider`] (for example one backed by `DefiLlama` yields) instead.
+
+use crate::types::{RouteRequest, RouteResponse, TokenData, TokenType, TokensRequest};
+use crate::{Client, Error, Result};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// Sender used for routes when [`YieldFilter::from_address`] is not set
+///
+/// Routes quoted for it are estimates only; gas estimation is disabled.
+pub const QUOTE_ONLY_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+
+/// Future returned by [`ApyProvider::apy`]
+pub type ApyFuture<'a> = Pin<Box<dyn Future<Output = Option<f64>> + Send + 'a>>;
+
+/// Source of APY figures used to rank positions
+///
+/// ```
+/// use ensof::{ApyFuture, ApyProvider, TokenData};
+///
+/// struct Fixed;
+///
+/// impl ApyProvider for Fixed {
+///     fn apy<'a>(&'a self, _chain_id: u64, position: &'a TokenData) -> ApyFuture<'a> {
+///         Box::pin(async move { position.symbol.as_deref().map(|_| 5.0) })
+///     }
+/// }
+/// ```
+pub trait ApyProvider: Send + Sync {
+    /// APY of a position token in percent, or `None` if unknown
+    ///
+    /// Failures should be reported as `None`; a missing APY never fails
+    /// discovery.
+    fn apy<'a>(&'a self, chain_id: u64, position: &'a TokenData) -> ApyFuture<'a>;
+}
+
+/// Where position APYs come from
+#[derive(Clone, Default)]
+pub enum ApySource {
+    /// No APY: rank by expected output only
+    #[default]
+    None,
+    /// The `apy` field of the tokens endpoint
+    Enso,
+    /// An injected provider
+    Provider(Arc<dyn ApyProvider>),
+}
+
+impl ApySource {
+    /// Use an injected provider
+    pub fn provider(provider: impl ApyProvider + 'static) -> Self {
+        Self::Provider(Arc::new(provider))
+    }
+}
+
+impl fmt::Debug for ApySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => f.write_str("None"),
+            Self::Enso => f.write_str("Enso"),
+            Self::Provider(_) => f.write_str("Provider(..)"),
+        }
+    }
+}
+
+/// Options for [`Client::find_yield_routes`]
+#[derive(Debug, Clone)]
+pub struct YieldFilter {
+    /// Only positions of these protocol slugs (all protocols if empty)
+    pub protocols: Vec<String>,
+    /// Where APYs for ranking come from
+    pub apy_source: ApySource,
+    /// Drop positions with a lower (or unknown) APY, in percent
+    pub min_apy: Option<f64>,
+    /// Number of ranked options to return
+    pub max_positions: usize,
+    /// Number of candidates to fetch routes for
+    pub max_candidates: usize,
+    /// Maximum concurrent route (and APY) requests
+    pub concurrency: usize,
+    /// Maximum token pages to read per protocol
+    pub max_token_pages: u32,
+    /// Sender of the routes; quote-only if not set
+    pub from_address: Option<String>,
+    /// Slippage tolerance in basis points
+    pub slippage_bps: u16,
+}
+
+impl Default for YieldFilter {
+    fn default() -> Self {
+        Self {
+            protocols: Vec::new(),
+            apy_source: ApySource::None,
+            min_apy: None,
+            max_positions: 5,
+            max_candidates: 20,
+            concurrency: 4,
+            max_token_pages: 3,
+            from_address: None,
+            slippage_bps: 50,
+        }
+    }
+}
+
+impl YieldFilter {
+    /// Create a filter with default limits
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only positions of these protocols
+    #[must_use]
+    pub fn with_protocols(mut self, protocols: Vec<String>) -> Self {
+        self.protocols = protocols;
+        self
+    }
+
+    /// Set the APY source
+    #[must_use]
+    pub fn with_apy_source(mut self, source: ApySource) -> Self {
+        self.apy_source = source;
+        self
+    }
+
+    /// Drop positions below an APY, in percent
+    #[must_use]
+    pub fn with_min_apy(mut self, min_apy: f64) -> Self {
+        self.min_apy = Some(min_apy);
+        self
+    }
+
+    /// Set the number of ranked options to return
+    #[must_use]
+    pub fn with_max_positions(mut self, max: usize) -> Self {
+        self.max_positions = max;
+        self
+    }
+
+    /// Set the number of candidates to fetch routes for
+    #[must_use]
+    pub fn with_max_candidates(mut self, max: usize) -> Self {
+        self.max_candidates = max;
+        self
+    }
+
+    /// Set the maximum concurrent requests
+    #[must_use]
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Route from this address (makes the returned transactions executable)
+    #[must_use]
+    pub fn with_from_address(mut self, address: impl Into<String>) -> Self {
+        self.from_address = Some(address.into());
+        self
+    }
+
+    /// Set the slippage tolerance in basis points
+    #[must_use]
+    pub fn with_slippage_bps(mut self, slippage_bps: u16) -> Self {
+        self.slippage_bps = slippage_bps;
+        self
+    }
+}
+
+/// A ranked destination for a yield route
+#[derive(Debug, Clone)]
+pub struct YieldRouteOption {
+    /// Position token address
+    pub position_token: String,
+    /// Position token symbol
+    pub symbol: Option<String>,
+    /// Protocol slug
+    pub protocol: String,
+    /// APY in percent from the filter's [`ApySource`]
+    pub apy: Option<f64>,
+    /// Expected position tokens out (smallest units)
+    pub expected_out: String,
+    /// The route
+    pub route: RouteResponse,
+}
+
+/// A candidate whose route could not be fetched
+#[derive(Debug)]
+pub struct YieldRouteFailure {
+    /// Position token address
+    pub position_token: String,
+    /// Protocol slug
+    pub protocol: String,
+    /// Why the route failed
+    pub error: Error,
+}
+
+/// Result of [`Client::find_yield_routes`]
+#[derive(Debug, Default)]
+pub struct YieldRoutes {
+    /// Options, best first
+    pub options: Vec<YieldRouteOption>,
+    /// Candidates whose route failed
+    pub failures: Vec<YieldRouteFailure>,
+    /// Requested protocols Enso does not support on the chain
+    pub unsupported_protocols: Vec<String>,
+    /// Position tokens found before filtering
+    pub candidates: usize,
+}
+
+impl YieldRoutes {
+    /// The best option
+    #[must_use]
+    pub fn best(&self) -> Option<&YieldRouteOption> {
+        self.options.first()
+    }
+}
+
+/// A position token with its APY
+struct Candidate {
+    token: TokenData,
+    apy: Option<f64>,
+}
+
+impl Candidate {
+    fn protocol(&self) -> String {
+        self.token.protocol_slug.clone().unwrap_or_default()
+    }
+}
+
+impl Client {
+    /// Find the best yield-bearing positions to route a token into
+    ///
+    /// Reads `defi` tokens with `from_token` as underlying (per protocol when
+    /// [`YieldFilter::protocols`] is set), ranks them by APY, fetches routes
+    /// into the best [`YieldFilter::max_candidates`] concurrently, and
+    /// returns up to [`YieldFilter::max_positions`] options ranked by APY,
+    /// then expected output. A failed route is reported in
+    /// [`YieldRoutes::failures`] and does not fail the discovery; only a
+    /// failed tokens or protocols lookup does.
+    ///
+    /// Without an APY source, options are ranked by expected output alone,
+    /// which only compares like with like when positions have similar share
+    /// prices.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ensof::{ApySource, Client, YieldFilter};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), ensof::Error> {
+    ///     let client = Client::with_api_key("your-key")?;
+    ///     let filter = YieldFilter {
+    ///         protocols: vec!["aave-v3".into(), "morpho-blue".into()],
+    ///         apy_source: ApySource::Enso,
+    ///         max_positions: 3,
+    ///         ..YieldFilter::default()
+    ///     };
+    ///     let routes = client
+    ///         .find_yield_routes(
+    ///             1,
+    ///             "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+    ///             "1000000000",
+    ///             &filter,
+    ///         )
+    ///         .await?;
+    ///     for option in &routes.options {
+    ///         println!("{} {:?}% -> {}", option.protocol, option.apy, option.expected_out);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn find_yield_routes(
+        &self,
+        chain_id: u64,
+        from_token: &str,
+        amount: &str,
+        filter: &YieldFilter,
+    ) -> Result<YieldRoutes> {
+        let concurrency = filter.concurrency.max(1);
+        let mut result = YieldRoutes::default();
+
+        let protocols = if filter.protocols.is_empty() {
+            vec![None]
+        } else {
+            let supported = self.get_protocols(Some(chain_id)).await?;
+            let mut protocols = Vec::new();
+            for slug in &filter.protocols {
+                let known = supported.iter().any(|p| {
+                    p.slug.eq_ignore_ascii_case(slug)
+                        && (p.chains.is_empty() || p.supports_chain(chain_id))
+                });
+                if known {
+                    protocols.push(Some(slug.clone()));
+                } else {
+                    result.unsupported_protocols.push(slug.clone());
+                }
+            }
+            protocols
+        };
+
+        let mut tokens = Vec::new();
+        let mut seen = HashSet::new();
+        for protocol in protocols {
+            for token in self
+                .position_tokens(chain_id, from_token, protocol, filter.max_token_pages)
+                .await?
+            {
+                let address = token.address.to_lowercase();
+                if !address.eq_ignore_ascii_case(from_token) && seen.insert(address) {
+                    tokens.push(token);
+                }
+            }
+        }
+        result.candidates = tokens.len();
+
+        let mut candidates = with_apys(chain_id, tokens, &filter.apy_source, concurrency).await;
+        if let Some(min_apy) = filter.min_apy {
+            candidates.retain(|c| c.apy.is_some_and(|apy| apy >= min_apy));
+        }
+        candidates.sort_by(|a, b| {
+            cmp_apy(a.apy, b.apy).then_with(|| {
+                b.token
+                    .tvl
+                    .unwrap_or(0.0)
+                    .total_cmp(&a.token.tvl.unwrap_or(0.0))
+            })
+        });
+        candidates.truncate(filter.max_candidates);
+
+        let semaphore = Arc::new(Semaphore::new(concurrency));
+        let mut tasks = JoinSet::new();
+        for (index, candidate) in candidates.iter().enumerate() {
+            let request = RouteRequest::new(
+                chain_id,
+                filter.from_address.as_deref().unwrap_or(QUOTE_ONLY_ADDRESS),
+                from_token,
+                &candidate.token.address,
+                amount,
+                filter.slippage_bps,
+            );
+            let request = if filter.from_address.is_none() {
+                request.with_disable_estimate(true)
+            } else {
+                request
+            };
+            let client = self.clone();
+            let semaphore = Arc::clone(&semaphore);
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                (index, client.get_route(&request).await)
+            });
+        }
+
+        let mut routes = Vec::with_capacity(candidates.len());
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok(route) => routes.push(route),
+                Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+                Err(_) => {}
+            }
+        }
+        routes.sort_by_key(|(index, _)| *index);
+
+        let mut candidates: Vec<Option<Candidate>> = candidates.into_iter().map(Some).collect();
+        for (index, route) in routes {
+            let Some(candidate) = candidates[index].take() else {
+                continue;
+            };
+            match route {
+                Ok(route) => result.options.push(YieldRouteOption {
+                    protocol: candidate.protocol(),
+                    position_token: candidate.token.address,
+                    symbol: candidate.token.symbol,
+                    apy: candidate.apy,
+                    expected_out: route.amount_out.clone(),
+                    route,
+                }),
+                Err(error) => result.failures.push(YieldRouteFailure {
+                    protocol: candidate.protocol(),
+                    position_token: candidate.token.address,
+                    error,
+                }),
+            }
+        }
+
+        rank_options(&mut result.options);
+        result.options.truncate(filter.max_positions);
+        Ok(result)
+    }
+
+    /// `defi` tokens with `underlying` as an underlying token
+    async fn position_tokens(
+        &self,
+        chain_id: u64,
+        underlying: &str,
+        protocol: Option<String>,
+        max_pages: u32,
+    ) -> Result<Vec<TokenData>> {
+        let mut request = TokensRequest::new(chain_id)
+            .with_type(TokenType::Defi)
+            .with_underlying_token(underlying)
+            .with_metadata(true);
+        request.protocol_slug = protocol;
+
+        let mut tokens = Vec::new();
+        let mut page = 1;
+        for _ in 0..max_pages.max(1) {
+            let response = self.get_tokens(&request.clone().with_page(page)).await?;
+            let next = response.next_page();
+            tokens.extend(
+                response
+                    .data
+                    .into_iter()
+                    .filter(|t| t.token_type == TokenType::Defi),
+            );
+            match next {
+                Some(next) if next > page => page = next,
+                _ => break,
+            }
+        }
+        Ok(tokens)
+    }
+}
+
+/// Attach APYs from `source`, querying a provider `concurrency` at a time
+async fn with_apys(
+    chain_id: u64,
+    tokens: Vec<TokenData>,
+    source: &ApySource,
+    concurrency: usize,
+) -> Vec<Candidate> {
+    let provider = match source {
+        ApySource::None => {
+            return tokens
+                .into_iter()
+                .map(|token| Candidate { token, apy: None })
+                .collect()
+        }
+        ApySource::Enso => {
+            return tokens
+                .into_iter()
+                .map(|token| Candidate {
+                    apy: token.apy,
+                    token,
+                })
+                .collect()
+        }
+        ApySource::Provider(provider) => provider,
+    };
+
+    let semaphore = Arc::new(Semaphore::new(concurrency));
+    let mut tasks = JoinSet::new();
+    for (index, token) in tokens.into_iter().enumerate() {
+        let provider = Arc::clone(provider);
+        let semaphore = Arc::clone(&semaphore);
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            let apy = provider.apy(chain_id, &token).await;
+            (index, Candidate { token, apy })
+        });
+    }
+
+    let mut candidates = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok(candidate) => candidates.push(candidate),
+            Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+            Err(_) => {}
+        }
+    }
+    candidates.sort_by_key(|(index, _)| *index);
+    candidates
+        .into_iter()
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
+
+/// Order options by APY (unknown last), then expected output, best first
+fn rank_options(options: &mut [YieldRouteOption]) {
+    options.sort_by(|a, b| {
+        cmp_apy(a.apy, b.apy).then_with(|| cmp_amounts(&b.expected_out, &a.expected_out))
+    });
+}
+
+/// Higher APY first, unknown APY last
+fn cmp_apy(a: Option<f64>, b: Option<f64>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => b.total_cmp(&a),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// Compare decimal amount strings of any size numerically
+fn cmp_amounts(a: &str, b: &str) -> Ordering {
+    let a = a.trim_start_matches('0');
+    let b = b.trim_start_matches('0');
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cmp_amounts() {
+        assert_eq!(cmp_amounts("10", "9"), Ordering::Greater);
+        assert_eq!(cmp_amounts("0010", "10"), Ordering::Equal);
+        assert_eq!(
+            cmp_amounts("1000000000000000000000000000000000000000", "999"),
+            Ordering::Greater
+        );
+        assert_eq!(cmp_amounts("123", "124"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_cmp_apy_unknown_last() {
+        let mut apys = vec![None, Some(3.0), Some(7.5), None, Some(0.1)];
+        apys.sort_by(|a, b| cmp_apy(*a, *b));
+        assert_eq!(apys, vec![Some(7.5), Some(3.0), Some(0.1), None, None]);
+    }
+
+    #[test]
+    fn test_filter_defaults() {
+        let filter = YieldFilter {
+            protocols: vec!["aave-v3".into()],
+            apy_source: ApySource::Enso,
+            max_positions: 3,
+            ..YieldFilter::default()
+        };
+        assert_eq!(filter.max_candidates, 20);
+        assert_eq!(filter.concurrency, 4);
+        assert_eq!(format!("{:?}", filter.apy_source), "Enso");
+    }
+}
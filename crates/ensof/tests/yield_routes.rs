@@ -0,0 +1,315 @@
+//! `Client::find_yield_routes` against mocked Enso endpoints and APY providers
+//!
+//! USDC has five position tokens across two token pages. The route into
+//! `BROKEN` fails with a 500; everything else quotes.
+
+use ensof::{ApyFuture, ApyProvider, ApySource, Client, Config, TokenData, YieldFilter};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+const USDC: &str = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+const AAVE: &str = "0x98c23e9d8f34fefb1b7bd6a91b7ff122f4e16f5c";
+const YEARN: &str = "0xbe53a109b494e5c9f97b9cd39fe969be68bf6204";
+const MORPHO: &str = "0xd63070114470f685b75b74d60eec7c1113d33a3d";
+const COMPOUND: &str = "0xc3d688b66703497daa19211eedff47f25384cdc3";
+const BROKEN: &str = "0x00000000000000000000000000000000000000b0";
+
+fn token(address: &str, protocol: &str, symbol: &str, apy: Option<f64>) -> serde_json::Value {
+    serde_json::json!({
+        "address": address,
+        "chainId": 1,
+        "type": "defi",
+        "decimals": 6,
+        "symbol": symbol,
+        "protocolSlug": protocol,
+        "underlyingTokens": [{"address": USDC, "symbol": "USDC", "decimals": 6}],
+        "apy": apy,
+        "tvl": 1_000_000.0
+    })
+}
+
+struct Routes;
+
+impl Respond for Routes {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        let token_out = body["tokenOut"][0].as_str().unwrap().to_string();
+        let amount_out = match token_out.as_str() {
+            AAVE => "990000000",
+            YEARN => "950000000",
+            MORPHO => "980000000",
+            COMPOUND => "1000000000",
+            _ => {
+                return ResponseTemplate::new(500).set_body_json(serde_json::json!({
+                    "error": "no route"
+                }))
+            }
+        };
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "amountOut": amount_out,
+            "tx": {
+                "to": "0x80eba3855878739f4710233a8a19d89bdd2ffb8e",
+                "from": body["fromAddress"],
+                "data": "0x",
+                "value": "0"
+            },
+            "route": [{"protocol": "enso", "tokenIn": [USDC], "tokenOut": [token_out]}]
+        }))
+    }
+}
+
+async fn server() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/tokens"))
+        .and(query_param("page", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [
+                token(AAVE, "aave-v3", "aUSDC", Some(4.0)),
+                token(YEARN, "yearn-v3", "yvUSDC", Some(9.0)),
+                token(BROKEN, "yearn-v3", "brokenUSDC", Some(50.0)),
+                // The underlying token itself is never a destination
+                token(USDC, "aave-v3", "USDC", None),
+            ],
+            "meta": {"currentPage": 1, "lastPage": 2, "next": 2}
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/tokens"))
+        .and(query_param("page", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [
+                token(MORPHO, "morpho-blue", "mUSDC", Some(6.0)),
+                token(COMPOUND, "compound-v3", "cUSDCv3", None),
+                // Repeated across pages
+                token(AAVE, "aave-v3", "aUSDC", Some(4.0)),
+            ],
+            "meta": {"currentPage": 2, "lastPage": 2, "next": null}
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/shortcuts/route"))
+        .respond_with(Routes)
+        .mount(&server)
+        .await;
+    server
+}
+
+fn client(server: &MockServer) -> Client {
+    Client::with_config(Config::new(server.uri())).unwrap()
+}
+
+/// APYs from a table, tracking how many lookups run at once
+struct TableApy {
+    apys: HashMap<&'static str, f64>,
+    in_flight: AtomicUsize,
+    max_in_flight: AtomicUsize,
+    calls: AtomicUsize,
+}
+
+impl TableApy {
+    fn new(apys: &[(&'static str, f64)]) -> Arc<Self> {
+        Arc::new(Self {
+            apys: apys.iter().copied().collect(),
+            in_flight: AtomicUsize::new(0),
+            max_in_flight: AtomicUsize::new(0),
+            calls: AtomicUsize::new(0),
+        })
+    }
+}
+
+impl ApyProvider for TableApy {
+    fn apy<'a>(&'a self, chain_id: u64, position: &'a TokenData) -> ApyFuture<'a> {
+        Box::pin(async move {
+            assert_eq!(chain_id, 1);
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            self.apys.get(position.address.as_str()).copied()
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_ranks_by_provider_apy_and_isolates_failures() {
+    let server = server().await;
+    let provider = TableApy::new(&[(AAVE, 7.0), (YEARN, 3.0), (BROKEN, 99.0), (MORPHO, 5.5)]);
+    let filter = YieldFilter {
+        apy_source: ApySource::Provider(provider.clone()),
+        max_positions: 10,
+        ..YieldFilter::default()
+    };
+
+    let routes = client(&server)
+        .find_yield_routes(1, USDC, "1000000000", &filter)
+        .await
+        .unwrap();
+
+    assert_eq!(routes.candidates, 5);
+    let ranked: Vec<(&str, Option<f64>)> = routes
+        .options
+        .iter()
+        .map(|o| (o.position_token.as_str(), o.apy))
+        .collect();
+    // Provider APYs win over Enso's; unknown APY last
+    assert_eq!(
+        ranked,
+        vec![
+            (AAVE, Some(7.0)),
+            (MORPHO, Some(5.5)),
+            (YEARN, Some(3.0)),
+            (COMPOUND, None),
+        ]
+    );
+    let best = routes.best().unwrap();
+    assert_eq!(best.protocol, "aave-v3");
+    assert_eq!(best.symbol.as_deref(), Some("aUSDC"));
+    assert_eq!(best.expected_out, "990000000");
+    assert_eq!(best.route.tx.from, ensof::QUOTE_ONLY_ADDRESS);
+
+    // The best-APY candidate failed without failing discovery
+    assert_eq!(routes.failures.len(), 1);
+    assert_eq!(routes.failures[0].position_token, BROKEN);
+    assert_eq!(routes.failures[0].error.status_code(), Some(500));
+    assert_eq!(provider.calls.load(Ordering::SeqCst), 5);
+}
+
+#[tokio::test]
+async fn test_min_apy_and_limits() {
+    let server = server().await;
+    let filter = YieldFilter::new()
+        .with_apy_source(ApySource::Enso)
+        .with_min_apy(5.0)
+        .with_max_candidates(2)
+        .with_max_positions(1)
+        .with_from_address("0x00000000000000000000000000000000000000aa");
+
+    let routes = client(&server)
+        .find_yield_routes(1, USDC, "1000000000", &filter)
+        .await
+        .unwrap();
+
+    // Enso APYs >= 5: BROKEN (50), YEARN (9), MORPHO (6); only the top two
+    // are routed, and BROKEN fails
+    let route_requests: Vec<serde_json::Value> = server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .filter(|r| r.url.path() == "/api/v1/shortcuts/route")
+        .map(|r| serde_json::from_slice(&r.body).unwrap())
+        .collect();
+    assert_eq!(route_requests.len(), 2);
+    assert!(route_requests.iter().all(|r| r["fromAddress"]
+        == "0x00000000000000000000000000000000000000aa"
+        && r.get("disableEstimate").is_none()
+        && r["slippage"] == "50"));
+
+    assert_eq!(routes.options.len(), 1);
+    assert_eq!(routes.options[0].position_token, YEARN);
+    assert_eq!(routes.options[0].apy, Some(9.0));
+    assert_eq!(routes.failures.len(), 1);
+}
+
+#[tokio::test]
+async fn test_without_apy_ranks_by_expected_out() {
+    let server = server().await;
+    let routes = client(&server)
+        .find_yield_routes(1, USDC, "1000000000", &YieldFilter::default())
+        .await
+        .unwrap();
+
+    let ranked: Vec<&str> = routes
+        .options
+        .iter()
+        .map(|o| o.position_token.as_str())
+        .collect();
+    assert_eq!(ranked, vec![COMPOUND, AAVE, MORPHO, YEARN]);
+    assert!(routes.options.iter().all(|o| o.apy.is_none()));
+}
+
+#[tokio::test]
+async fn test_concurrency_is_bounded() {
+    let server = server().await;
+    let provider = TableApy::new(&[]);
+    let filter = YieldFilter::new()
+        .with_apy_source(ApySource::Provider(provider.clone()))
+        .with_concurrency(2);
+
+    client(&server)
+        .find_yield_routes(1, USDC, "1000000000", &filter)
+        .await
+        .unwrap();
+
+    assert_eq!(provider.calls.load(Ordering::SeqCst), 5);
+    assert_eq!(provider.max_in_flight.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_protocol_filter() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/protocols"))
+        .and(query_param("chainId", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {"slug": "aave-v3", "name": "Aave V3", "chains": [{"id": 1, "name": "mainnet"}]},
+            {"slug": "moonwell", "name": "Moonwell", "chains": [{"id": 8453, "name": "base"}]}
+        ])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/tokens"))
+        .and(query_param("protocolSlug", "aave-v3"))
+        .and(query_param("underlyingTokens", USDC))
+        .and(query_param("type", "defi"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [token(AAVE, "aave-v3", "aUSDC", Some(4.0))],
+            "meta": {"currentPage": 1, "lastPage": 1, "next": null}
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/shortcuts/route"))
+        .respond_with(Routes)
+        .mount(&server)
+        .await;
+
+    let filter = YieldFilter::new().with_protocols(vec![
+        "aave-v3".to_string(),
+        "moonwell".to_string(),
+        "nope".to_string(),
+    ]);
+    let routes = client(&server)
+        .find_yield_routes(1, USDC, "1000000000", &filter)
+        .await
+        .unwrap();
+
+    assert_eq!(routes.unsupported_protocols, vec!["moonwell", "nope"]);
+    assert_eq!(routes.options.len(), 1);
+    assert_eq!(routes.options[0].protocol, "aave-v3");
+}
+
+#[tokio::test]
+async fn test_tokens_failure_fails_discovery() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/tokens"))
+        .respond_with(ResponseTemplate::new(401).set_body_string("unauthorized"))
+        .mount(&server)
+        .await;
+
+    let err = client(&server)
+        .find_yield_routes(1, USDC, "1000000000", &YieldFilter::default())
+        .await
+        .unwrap_err();
+    assert_eq!(err.status_code(), Some(401));
+}
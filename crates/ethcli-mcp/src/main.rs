@@ -13,16 +13,23 @@
 #![deny(clippy::dbg_macro)]
 
 mod executor;
+mod policy;
 mod tools;
 mod types;
 
+use policy::Policy;
 use rmcp::{
-    handler::server::{router::tool::ToolRouter, wrapper::Parameters},
-    model::{Implementation, ServerCapabilities, ServerInfo},
-    tool, tool_handler, tool_router,
+    handler::server::{router::tool::ToolRouter, tool::ToolCallContext, wrapper::Parameters},
+    model::{
+        CallToolRequestParams, CallToolResult, Implementation, ListToolsResult,
+        PaginatedRequestParams, ServerCapabilities, ServerInfo,
+    },
+    service::RequestContext,
+    tool, tool_router,
     transport::stdio,
-    ServerHandler, ServiceExt,
+    ErrorData, RoleServer, ServerHandler, ServiceExt,
 };
+use std::sync::Arc;
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
 // Re-export all types for use in tool implementations
@@ -37,14 +44,28 @@ use types::*;
 #[derive(Clone)]
 pub struct EthcliMcpServer {
     tool_router: ToolRouter<Self>,
+    policy: Arc<Policy>,
 }
 
 impl EthcliMcpServer {
     pub fn new() -> Self {
+        Self::with_policy(Policy::default())
+    }
+
+    /// Create a server exposing only the tools `policy` allows
+    pub fn with_policy(policy: Policy) -> Self {
+        let mut tool_router = Self::tool_router();
+        policy.apply(&mut tool_router);
         Self {
-            tool_router: Self::tool_router(),
+            tool_router,
+            policy: Arc::new(policy),
         }
     }
+
+    /// Number of tools advertised under the active policy
+    pub fn tool_count(&self) -> usize {
+        self.tool_router.list_all().len()
+    }
 }
 
 impl Default for EthcliMcpServer {
@@ -79,7 +100,7 @@ impl EthcliMcpServer {
     // =========================================================================
 
     #[tool(
-        description = "Analyze an Ethereum transaction including decoded events, token transfers, and method calls"
+        description = "Analyze an Ethereum transaction including decoded events, token transfers, and method calls. Returns the structured analysis as JSON (labels with their source, failure reason, explorer URL, warnings)"
     )]
     async fn tx_analyze(&self, Parameters(input): Parameters<TxAnalyzeInput>) -> String {
         tools::tx_analyze(&input.hash, Some(&input.chain), input.trace)
@@ -177,23 +198,48 @@ impl EthcliMcpServer {
     // TOKEN
     // =========================================================================
 
-    #[tool(description = "Get token metadata (name, symbol, decimals, total supply)")]
+    #[tool(
+        description = "Get token metadata: standard (ERC-20/721/1155), name, symbol, decimals, total supply, and base/contract URI for NFTs"
+    )]
     async fn token_info(&self, Parameters(input): Parameters<TokenInfoInput>) -> String {
         tools::token_info(&input.address, Some(&input.chain))
             .await
             .to_response()
     }
 
-    #[tool(description = "Get top token holders")]
+    #[tool(
+        description = "Get top token holders. ERC-721/ERC-1155 holders are rebuilt from transfer logs; ERC-20 needs an Etherscan Pro plan"
+    )]
     async fn token_holders(&self, Parameters(input): Parameters<TokenHoldersInput>) -> String {
-        tools::token_holders(&input.address, Some(&input.chain), input.limit)
-            .await
-            .to_response()
+        tools::token_holders(
+            &input.address,
+            Some(&input.chain),
+            input.limit,
+            input.token_id.as_deref(),
+            input.from_block,
+            input.to_block,
+        )
+        .await
+        .to_response()
     }
 
-    #[tool(description = "Get token balance for a specific address")]
+    #[tool(
+        description = "Get token balance for a specific address. Pass token_id for ERC-1155 balances or ERC-721 ownership"
+    )]
     async fn token_balance(&self, Parameters(input): Parameters<TokenBalanceInput>) -> String {
-        tools::token_balance(&input.token, &input.address, Some(&input.chain))
+        tools::token_balance(
+            &input.token,
+            &input.address,
+            Some(&input.chain),
+            input.token_id.as_deref(),
+        )
+        .await
+        .to_response()
+    }
+
+    #[tool(description = "Get the owner of an ERC-721 token id")]
+    async fn token_owner(&self, Parameters(input): Parameters<TokenOwnerInput>) -> String {
+        tools::token_owner(&input.token, &input.token_id, Some(&input.chain))
             .await
             .to_response()
     }
@@ -219,6 +265,15 @@ impl EthcliMcpServer {
         .to_response()
     }
 
+    #[tool(
+        description = "Compare the USD cost of an operation (transfer, erc20-transfer, swap, nft-mint) across chains at current gas prices"
+    )]
+    async fn gas_compare(&self, Parameters(input): Parameters<GasCompareInput>) -> String {
+        tools::gas_compare(input.op.as_deref(), input.chains.as_deref())
+            .await
+            .to_response()
+    }
+
     // =========================================================================
     // SIGNATURE LOOKUP
     // =========================================================================
@@ -295,6 +350,18 @@ impl EthcliMcpServer {
             .to_response()
     }
 
+    #[tool(
+        description = "Decode calldata without an ABI: looks up the selector in 4byte.directory and decodes with every matching signature, best match first. Pass sig to force a signature"
+    )]
+    async fn cast_calldata_decode(
+        &self,
+        Parameters(input): Parameters<CastCalldataDecodeInput>,
+    ) -> String {
+        tools::cast_calldata_decode(&input.calldata, input.sig.as_deref())
+            .await
+            .to_response()
+    }
+
     // =========================================================================
     // RPC
     // =========================================================================
@@ -1427,9 +1494,19 @@ impl EthcliMcpServer {
     // KONG (Yearn)
     // =========================================================================
 
-    #[tool(description = "Get Yearn vaults via Kong")]
-    async fn kong_vaults(&self, Parameters(input): Parameters<KongChainInput>) -> String {
-        tools::kong_vaults(Some(&input.chain)).await.to_response()
+    #[tool(
+        description = "List Yearn vaults on a chain via Kong as a compact summary (address, name, symbol, chainId, tvl, apyNet), ranked by TVL and limited to 50 by default. Supports fields, min_tvl, limit and sort (tvl|apy). Use kong_reports for per-vault detail."
+    )]
+    async fn kong_vaults(&self, Parameters(input): Parameters<KongVaultsInput>) -> String {
+        tools::kong_vaults(
+            Some(&input.chain),
+            input.fields.as_deref(),
+            input.min_tvl,
+            input.limit,
+            input.sort.as_deref(),
+        )
+        .await
+        .to_response()
     }
 
     #[tool(description = "List all strategies via Kong")]
@@ -2005,20 +2082,51 @@ impl EthcliMcpServer {
 }
 
 // Server handler implementation
-#[tool_handler(router = self.tool_router)]
+//
+// Written out instead of using #[tool_handler] so every call is checked
+// against the policy before dispatch, even though denied tools are never
+// registered in the router.
 impl ServerHandler for EthcliMcpServer {
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Err(denial) = self
+            .policy
+            .check_call(&request.name, request.arguments.as_ref())
+        {
+            tracing::warn!(tool = %request.name, reason = denial.reason.as_str(), "tool call denied by policy");
+            return Err(denial.to_error_data());
+        }
+        let tcc = ToolCallContext::new(self, request, context);
+        self.tool_router.call(tcc).await
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, ErrorData> {
+        Ok(ListToolsResult {
+            tools: self.tool_router.list_all(),
+            meta: None,
+            next_cursor: None,
+        })
+    }
+
     fn get_info(&self) -> ServerInfo {
         ServerInfo {
             protocol_version: Default::default(),
             capabilities: ServerCapabilities::builder().enable_tools().build(),
             server_info: Implementation::from_build_env(),
-            instructions: Some(
+            instructions: Some(format!(
                 "Comprehensive Ethereum CLI tools: transaction analysis, account queries, \
                  contract interactions, ENS, gas prices, DEX quotes, oracles (Chainlink/Pyth), \
                  security checks (GoPlus/Solodit), portfolio tracking, and more. \
-                 Supports multiple chains: ethereum, polygon, arbitrum, optimism, base, etc."
-                    .to_string(),
-            ),
+                 Supports multiple chains: ethereum, polygon, arbitrum, optimism, base, etc. {}",
+                self.policy.describe()
+            )),
         }
     }
 }
@@ -2069,11 +2177,20 @@ async fn main() -> anyhow::Result<()> {
         }
     }
 
+    // Fail closed: a policy that can't be loaded must not fall back to full access
+    let policy = Policy::from_env()?;
+    tracing::info!(
+        mode = %policy.mode,
+        allow_paid_tools = policy.allow_paid_tools,
+        "policy loaded"
+    );
+
     // Create server and run with STDIO transport
-    let server = EthcliMcpServer::new();
+    let server = EthcliMcpServer::with_policy(policy);
+    let tools = server.tool_count();
     let service = server.serve(stdio()).await?;
 
-    tracing::info!(tools = 236, "ethcli-mcp server ready");
+    tracing::info!(tools, "ethcli-mcp server ready");
 
     // Wait for completion
     service.waiting().await?;
@@ -2082,3 +2199,273 @@ async fn main() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use policy::{MUTATING_TOOLS, PAID_TOOLS, PAID_TOOL_ARGUMENTS};
+
+    /// Tools that only read, locally or remotely, without spending credits
+    ///
+    /// Every router tool must be in exactly one of this list,
+    /// [`MUTATING_TOOLS`] or [`PAID_TOOLS`], so a new tool can't reach a
+    /// read-only or unpaid server unclassified.
+    const READ_ONLY_TOOLS: &[&str] = &[
+        "logs",
+        "tx_analyze",
+        "account_info",
+        "account_balance",
+        "account_txs",
+        "account_internal_txs",
+        "account_erc20",
+        "account_erc721",
+        "account_erc1155",
+        "contract_abi",
+        "contract_source",
+        "contract_creation",
+        "token_info",
+        "token_holders",
+        "token_balance",
+        "token_owner",
+        "gas_oracle",
+        "gas_estimate",
+        "gas_compare",
+        "sig_fn",
+        "sig_event",
+        "cast_to_wei",
+        "cast_from_wei",
+        "cast_to_hex",
+        "cast_to_dec",
+        "cast_keccak",
+        "cast_sig",
+        "cast_topic",
+        "cast_checksum",
+        "cast_abi_encode",
+        "cast_abi_decode",
+        "cast_calldata_decode",
+        "rpc_call",
+        "rpc_block",
+        "rpc_storage",
+        "rpc_code",
+        "rpc_nonce",
+        "rpc_receipt",
+        "rpc_block_number",
+        "rpc_chain_id",
+        "rpc_gas_price",
+        "ens_resolve",
+        "ens_lookup",
+        "ens_namehash",
+        "price",
+        "portfolio",
+        "nfts",
+        "yields",
+        "quote_best",
+        "quote_compare",
+        "chainlink_price",
+        "chainlink_feed",
+        "chainlink_oracles",
+        "goplus_token",
+        "goplus_address",
+        "goplus_nft",
+        "goplus_approval",
+        "solodit_search",
+        "solodit_get",
+        "uniswap_pool",
+        "uniswap_eth_price",
+        "uniswap_top_pools",
+        "uniswap_positions",
+        "curve_pools",
+        "curve_router_route",
+        "alchemy_portfolio",
+        "alchemy_transfers",
+        "gecko_coins_info",
+        "gecko_simple_price",
+        "llama_tvl",
+        "llama_yields",
+        "oneinch_quote",
+        "zerox_quote",
+        "openocean_quote",
+        "cowswap_quote",
+        "lifi_quote",
+        "pyth_price",
+        "pyth_search",
+        "ccxt_ticker",
+        "ccxt_orderbook",
+        "simulate_call",
+        "simulate_tx",
+        "doctor",
+        "account_mined_blocks",
+        "address_list",
+        "address_get",
+        "address_search",
+        "blacklist_list",
+        "blacklist_check",
+        "blacklist_scan",
+        "blacklist_scan_portfolio",
+        "blacklist_path",
+        "contract_call",
+        "sig_cache_stats",
+        "cast_compute_address",
+        "cast_create2",
+        "cast_concat",
+        "cast_to_bytes32",
+        "ens_resolver",
+        "simulate_list",
+        "simulate_get",
+        "simulate_info",
+        "tenderly_vnets",
+        "tenderly_wallets",
+        "tenderly_contracts",
+        "tenderly_alerts",
+        "tenderly_actions",
+        "tenderly_networks",
+        "tenderly_channels",
+        "alchemy_nft",
+        "alchemy_token",
+        "alchemy_prices",
+        "alchemy_debug",
+        "gecko_global",
+        "gecko_nfts",
+        "gecko_onchain",
+        "goplus_token_batch",
+        "goplus_chains",
+        "solodit_rate_limit",
+        "solodit_tags",
+        "solodit_firms",
+        "llama_coins",
+        "llama_volumes",
+        "llama_fees",
+        "llama_stablecoins",
+        "moralis_wallet",
+        "moralis_token",
+        "moralis_nft",
+        "moralis_resolve",
+        "moralis_market",
+        "dsim_chains",
+        "dsim_balances",
+        "dsim_collectibles",
+        "dsim_activity",
+        "dsim_token",
+        "dsim_holders",
+        "dsim_defi",
+        "dune_execution",
+        "curve_volumes",
+        "curve_lending",
+        "curve_tokens",
+        "curve_crvusd",
+        "curve_prices",
+        "curve_ohlc",
+        "curve_trades",
+        "curve_dao",
+        "quote_from",
+        "chainlink_streams",
+        "ccxt_tickers",
+        "ccxt_ohlcv",
+        "ccxt_trades",
+        "ccxt_markets",
+        "ccxt_compare",
+        "uniswap_liquidity",
+        "uniswap_swaps",
+        "uniswap_day_data",
+        "uniswap_balance",
+        "uniswap_addresses",
+        "kong_vaults",
+        "kong_strategies",
+        "kong_prices",
+        "kong_tvl",
+        "kong_reports",
+        "oneinch_swap",
+        "oneinch_tokens",
+        "oneinch_sources",
+        "oneinch_spender",
+        "oneinch_allowance",
+        "oneinch_approve",
+        "openocean_swap",
+        "openocean_reverse_quote",
+        "openocean_tokens",
+        "openocean_dexes",
+        "kyberswap_routes",
+        "kyberswap_route_data",
+        "kyberswap_build",
+        "zerox_price",
+        "zerox_sources",
+        "cowswap_order",
+        "cowswap_orders",
+        "cowswap_trades",
+        "cowswap_order_trades",
+        "cowswap_auction",
+        "cowswap_competition",
+        "cowswap_native_price",
+        "lifi_routes",
+        "lifi_best_route",
+        "lifi_status",
+        "lifi_chains",
+        "lifi_chain",
+        "lifi_tokens",
+        "lifi_tools",
+        "lifi_bridges",
+        "lifi_exchanges",
+        "lifi_gas",
+        "lifi_connections",
+        "velora_price",
+        "velora_transaction",
+        "velora_tokens",
+        "enso_route",
+        "enso_price",
+        "enso_balances",
+        "pyth_feeds",
+        "pyth_known_feeds",
+        "config_path",
+        "config_show",
+        "config_validate",
+        "endpoints_list",
+        "endpoints_health",
+        "endpoints_test",
+        "health",
+    ];
+
+    #[test]
+    fn test_every_tool_is_classified() {
+        let tools = EthcliMcpServer::tool_router().list_all();
+        let names: Vec<&str> = tools.iter().map(|tool| tool.name.as_ref()).collect();
+
+        for name in &names {
+            let classes = [READ_ONLY_TOOLS, MUTATING_TOOLS, PAID_TOOLS]
+                .iter()
+                .filter(|list| list.contains(name))
+                .count();
+            assert_eq!(
+                classes, 1,
+                "{name} must be in exactly one of READ_ONLY_TOOLS, MUTATING_TOOLS, PAID_TOOLS"
+            );
+        }
+        for name in READ_ONLY_TOOLS
+            .iter()
+            .chain(MUTATING_TOOLS)
+            .chain(PAID_TOOLS)
+        {
+            assert!(names.contains(name), "{name} is classified but not a tool");
+        }
+    }
+
+    #[test]
+    fn test_provider_arguments_are_classified() {
+        // A `via` argument picks a simulation backend, some of them billed
+        for tool in EthcliMcpServer::tool_router().list_all() {
+            let has_via = tool
+                .input_schema
+                .get("properties")
+                .and_then(|p| p.get("via"))
+                .is_some();
+            if has_via {
+                assert!(
+                    PAID_TOOL_ARGUMENTS
+                        .iter()
+                        .any(|(paid, argument, _)| *paid == tool.name && *argument == "via"),
+                    "{} takes `via` but has no PAID_TOOL_ARGUMENTS entry",
+                    tool.name
+                );
+            }
+        }
+    }
+}
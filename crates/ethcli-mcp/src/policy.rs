@@ -0,0 +1,564 @@
+//! Server policy: which tools are advertised and callable
+//!
+//! A [`Policy`] is built once at startup from a JSON file
+//! (`ETHCLI_MCP_POLICY`) and/or environment variables, then enforced twice:
+//! tools it denies are removed from the router so they are never advertised,
+//! and every call is checked again before dispatch.
+//!
+//! ```json
+//! {
+//!   "mode": "read-only",
+//!   "allow": ["cast_*", "rpc_*", "token_info"],
+//!   "deny": ["rpc_call"],
+//!   "allow_paid_tools": false
+//! }
+//! ```
+//!
+//! Environment variables override the file: `ETHCLI_MCP_MODE`,
+//! `ETHCLI_MCP_ALLOW` / `ETHCLI_MCP_DENY` (comma-separated, `prefix*`
+//! patterns allowed) and `ETHCLI_MCP_ALLOW_PAID_TOOLS`.
+
+use rmcp::handler::server::router::tool::ToolRouter;
+use rmcp::model::ErrorCode;
+use serde::Deserialize;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Tools that change local state (config, address book, blacklist,
+/// endpoints, caches, exported files) or sharing settings on remote services
+pub const MUTATING_TOOLS: &[&str] = &[
+    "config_init",
+    "config_set_etherscan_key",
+    "config_set_tenderly",
+    "config_set_moralis",
+    "config_set_alchemy",
+    "config_set_dune",
+    "config_set_dune_sim",
+    "config_set_solodit",
+    "config_set_chainlink",
+    "config_add_debug_rpc",
+    "config_remove_debug_rpc",
+    "address_add",
+    "address_remove",
+    "address_import",
+    "address_export",
+    "blacklist_add",
+    "blacklist_remove",
+    "endpoints_add",
+    "endpoints_remove",
+    "endpoints_enable",
+    "endpoints_disable",
+    "endpoints_optimize",
+    "sig_cache_clear",
+    "simulate_share",
+    "simulate_unshare",
+];
+
+/// Tools that spend API credits on every call
+pub const PAID_TOOLS: &[&str] = &[
+    "dune_query",
+    "dune_sql",
+    "tenderly_simulate",
+    "simulate_bundle",
+];
+
+/// Tools that spend API credits only with certain arguments, as
+/// `(tool, argument, value)`
+///
+/// `simulate_call` is free through cast or a node but bills Tenderly with
+/// `via: "tenderly"`. These tools stay advertised; the call is checked.
+pub const PAID_TOOL_ARGUMENTS: &[(&str, &str, &str)] = &[("simulate_call", "via", "tenderly")];
+
+/// Whether a call to `tool` with `arguments` spends API credits
+pub fn is_paid_call(tool: &str, arguments: Option<&rmcp::model::JsonObject>) -> bool {
+    PAID_TOOLS.contains(&tool)
+        || PAID_TOOL_ARGUMENTS.iter().any(|(paid, argument, value)| {
+            *paid == tool
+                && arguments
+                    .and_then(|args| args.get(*argument))
+                    .and_then(serde_json::Value::as_str)
+                    .is_some_and(|v| v.trim().eq_ignore_ascii_case(value))
+        })
+}
+
+/// JSON-RPC error code for calls rejected by the policy
+pub const POLICY_DENIED: ErrorCode = ErrorCode(-32003);
+
+/// Server mode
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Mode {
+    /// Every tool is available (subject to allow/deny lists)
+    #[default]
+    Full,
+    /// Tools in [`MUTATING_TOOLS`] are removed
+    ReadOnly,
+}
+
+impl Mode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Mode::Full => "full",
+            Mode::ReadOnly => "read-only",
+        }
+    }
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Mode {
+    type Err = PolicyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "full" => Ok(Mode::Full),
+            "read-only" | "read_only" | "readonly" => Ok(Mode::ReadOnly),
+            other => Err(PolicyError::Invalid(format!(
+                "unknown mode '{}' (expected 'full' or 'read-only')",
+                other
+            ))),
+        }
+    }
+}
+
+/// Errors loading a policy
+#[derive(Debug, thiserror::Error)]
+pub enum PolicyError {
+    #[error("Failed to read policy file {path}: {source}")]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("Invalid policy file {path}: {source}")]
+    Parse {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+
+    #[error("Invalid policy: {0}")]
+    Invalid(String),
+}
+
+/// Why a tool is not available
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DenyReason {
+    /// The tool mutates state and the server is read-only
+    ReadOnly,
+    /// An allowlist is set and the tool is not on it
+    NotAllowed,
+    /// The tool is on the denylist
+    Denied,
+    /// The tool spends credits and paid tools are disabled
+    PaidToolsDisabled,
+}
+
+impl DenyReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DenyReason::ReadOnly => "read_only",
+            DenyReason::NotAllowed => "not_allowlisted",
+            DenyReason::Denied => "denylisted",
+            DenyReason::PaidToolsDisabled => "paid_tools_disabled",
+        }
+    }
+}
+
+/// A tool call rejected by the policy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyDenial {
+    pub tool: String,
+    pub reason: DenyReason,
+    pub mode: Mode,
+}
+
+impl fmt::Display for PolicyDenial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let why = match self.reason {
+            DenyReason::ReadOnly => "it modifies local state and the server is read-only",
+            DenyReason::NotAllowed => "it is not on the tool allowlist",
+            DenyReason::Denied => "it is on the tool denylist",
+            DenyReason::PaidToolsDisabled => {
+                "it spends API credits and paid tools are disabled (allow_paid_tools)"
+            }
+        };
+        write!(
+            f,
+            "Tool '{}' is disabled by server policy: {}",
+            self.tool, why
+        )
+    }
+}
+
+impl PolicyDenial {
+    /// Structured MCP error for a denied call
+    pub fn to_error_data(&self) -> rmcp::ErrorData {
+        rmcp::ErrorData::new(
+            POLICY_DENIED,
+            self.to_string(),
+            Some(serde_json::json!({
+                "error": "policy_denied",
+                "tool": self.tool,
+                "reason": self.reason.as_str(),
+                "mode": self.mode.as_str(),
+            })),
+        )
+    }
+}
+
+/// Which tools the server exposes
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Policy {
+    /// Server mode
+    pub mode: Mode,
+    /// If set, only these tools (or `prefix*` patterns) are available
+    pub allow: Option<Vec<String>>,
+    /// Tools (or `prefix*` patterns) that are never available
+    pub deny: Vec<String>,
+    /// Enable tools in [`PAID_TOOLS`]
+    pub allow_paid_tools: bool,
+}
+
+impl Policy {
+    /// Load the policy from `ETHCLI_MCP_POLICY` and `ETHCLI_MCP_*` variables
+    pub fn from_env() -> Result<Self, PolicyError> {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    /// Load the policy using `var` to look up environment variables
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Self, PolicyError> {
+        let mut policy = match var("ETHCLI_MCP_POLICY").filter(|p| !p.trim().is_empty()) {
+            Some(path) => Self::from_file(path.trim())?,
+            None => Self::default(),
+        };
+
+        if let Some(mode) = var("ETHCLI_MCP_MODE") {
+            policy.mode = mode.parse()?;
+        }
+        if let Some(allow) = var("ETHCLI_MCP_ALLOW") {
+            policy.allow = Some(split_list(&allow));
+        }
+        if let Some(deny) = var("ETHCLI_MCP_DENY") {
+            policy.deny = split_list(&deny);
+        }
+        if let Some(paid) = var("ETHCLI_MCP_ALLOW_PAID_TOOLS") {
+            policy.allow_paid_tools = parse_bool(&paid).ok_or_else(|| {
+                PolicyError::Invalid(format!(
+                    "ETHCLI_MCP_ALLOW_PAID_TOOLS must be true or false, got '{}'",
+                    paid
+                ))
+            })?;
+        }
+        Ok(policy)
+    }
+
+    /// Load a JSON policy file
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, PolicyError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|source| PolicyError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        serde_json::from_str(&contents).map_err(|source| PolicyError::Parse {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    /// Check whether a tool may be advertised and called
+    pub fn check(&self, tool: &str) -> Result<(), PolicyDenial> {
+        let reason = if self.deny.iter().any(|p| matches_pattern(p, tool)) {
+            Some(DenyReason::Denied)
+        } else if self
+            .allow
+            .as_ref()
+            .is_some_and(|allow| !allow.iter().any(|p| matches_pattern(p, tool)))
+        {
+            Some(DenyReason::NotAllowed)
+        } else if self.mode == Mode::ReadOnly && MUTATING_TOOLS.contains(&tool) {
+            Some(DenyReason::ReadOnly)
+        } else if !self.allow_paid_tools && PAID_TOOLS.contains(&tool) {
+            Some(DenyReason::PaidToolsDisabled)
+        } else {
+            None
+        };
+
+        match reason {
+            Some(reason) => Err(PolicyDenial {
+                tool: tool.to_string(),
+                reason,
+                mode: self.mode,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Check a call, including arguments that make a free tool paid
+    pub fn check_call(
+        &self,
+        tool: &str,
+        arguments: Option<&rmcp::model::JsonObject>,
+    ) -> Result<(), PolicyDenial> {
+        self.check(tool)?;
+        if !self.allow_paid_tools && is_paid_call(tool, arguments) {
+            return Err(PolicyDenial {
+                tool: tool.to_string(),
+                reason: DenyReason::PaidToolsDisabled,
+                mode: self.mode,
+            });
+        }
+        Ok(())
+    }
+
+    /// Remove every tool the policy denies from a router
+    pub fn apply<S: Send + Sync + 'static>(&self, router: &mut ToolRouter<S>) {
+        let names: Vec<String> = router
+            .list_all()
+            .into_iter()
+            .map(|tool| tool.name.to_string())
+            .collect();
+
+        for pattern in self.allow.iter().flatten().chain(&self.deny) {
+            if !names.iter().any(|name| matches_pattern(pattern, name)) {
+                tracing::warn!(pattern = %pattern, "policy pattern matches no tool");
+            }
+        }
+
+        for name in &names {
+            if self.check(name).is_err() {
+                router.remove_route(name);
+            }
+        }
+    }
+
+    /// Summary of the policy for the server instructions
+    pub fn describe(&self) -> String {
+        let mut parts = vec![match self.mode {
+            Mode::Full => "Mode: full.".to_string(),
+            Mode::ReadOnly => "Mode: read-only; tools that change local configuration, \
+                               the address book, blacklist or endpoints are not available."
+                .to_string(),
+        }];
+        if let Some(allow) = &self.allow {
+            parts.push(format!(
+                "Only these tools are enabled: {}.",
+                allow.join(", ")
+            ));
+        }
+        if !self.deny.is_empty() {
+            parts.push(format!("Disabled tools: {}.", self.deny.join(", ")));
+        }
+        if !self.allow_paid_tools {
+            let by_argument: Vec<String> = PAID_TOOL_ARGUMENTS
+                .iter()
+                .map(|(tool, argument, value)| format!("{tool} with {argument}={value}"))
+                .collect();
+            parts.push(format!(
+                "Paid tools ({}) are disabled.",
+                PAID_TOOLS
+                    .iter()
+                    .map(|tool| tool.to_string())
+                    .chain(by_argument)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        parts.join(" ")
+    }
+}
+
+/// Exact tool name, or a prefix when the pattern ends with `*`
+fn matches_pattern(pattern: &str, tool: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => tool.starts_with(prefix),
+        None => pattern == tool,
+    }
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" | "" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn vars(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let map: HashMap<String, String> = pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| map.get(name).cloned()
+    }
+
+    #[test]
+    fn test_default_policy_disables_only_paid_tools() {
+        let policy = Policy::from_vars(vars(&[])).unwrap();
+        assert_eq!(policy.mode, Mode::Full);
+        assert!(policy.check("config_set_dune").is_ok());
+        assert!(policy.check("cast_to_wei").is_ok());
+        for tool in PAID_TOOLS {
+            let denial = policy.check(tool).unwrap_err();
+            assert_eq!(denial.reason, DenyReason::PaidToolsDisabled, "{tool}");
+        }
+        assert!(policy.check("simulate_list").is_ok());
+        assert!(policy.check("dune_execution").is_ok());
+    }
+
+    #[test]
+    fn test_paid_arguments() {
+        let args = |value: serde_json::Value| value.as_object().cloned();
+        let tenderly = args(serde_json::json!({"contract": "0x0", "via": "Tenderly"}));
+        let cast = args(serde_json::json!({"contract": "0x0", "via": "cast"}));
+
+        let policy = Policy::default();
+        assert!(policy.check("simulate_call").is_ok());
+        assert_eq!(
+            policy
+                .check_call("simulate_call", tenderly.as_ref())
+                .unwrap_err()
+                .reason,
+            DenyReason::PaidToolsDisabled
+        );
+        assert!(policy.check_call("simulate_call", cast.as_ref()).is_ok());
+        assert!(policy.check_call("simulate_call", None).is_ok());
+        // The same argument on another tool means nothing
+        assert!(policy.check_call("simulate_tx", tenderly.as_ref()).is_ok());
+
+        let paid = Policy {
+            allow_paid_tools: true,
+            ..Policy::default()
+        };
+        assert!(paid.check_call("simulate_call", tenderly.as_ref()).is_ok());
+        assert!(policy
+            .describe()
+            .contains("simulate_call with via=tenderly"));
+    }
+
+    #[test]
+    fn test_read_only_mode() {
+        let policy = Policy::from_vars(vars(&[("ETHCLI_MCP_MODE", "read-only")])).unwrap();
+        for tool in MUTATING_TOOLS {
+            assert_eq!(
+                policy.check(tool).unwrap_err().reason,
+                DenyReason::ReadOnly,
+                "{tool}"
+            );
+        }
+        assert!(policy.check("address_list").is_ok());
+        assert!(policy.check("endpoints_list").is_ok());
+        assert_eq!(
+            "READONLY".parse::<Mode>().unwrap(),
+            Mode::ReadOnly,
+            "mode names are case-insensitive"
+        );
+        assert!("write".parse::<Mode>().is_err());
+    }
+
+    #[test]
+    fn test_allow_and_deny_patterns() {
+        let policy = Policy::from_vars(vars(&[
+            ("ETHCLI_MCP_ALLOW", "cast_*, health,dune_sql"),
+            ("ETHCLI_MCP_DENY", "cast_keccak"),
+            ("ETHCLI_MCP_ALLOW_PAID_TOOLS", "true"),
+        ]))
+        .unwrap();
+        assert!(policy.check("cast_to_wei").is_ok());
+        assert!(policy.check("health").is_ok());
+        assert!(policy.check("dune_sql").is_ok());
+        assert_eq!(
+            policy.check("cast_keccak").unwrap_err().reason,
+            DenyReason::Denied
+        );
+        assert_eq!(
+            policy.check("rpc_call").unwrap_err().reason,
+            DenyReason::NotAllowed
+        );
+        // Allowlisting does not bypass read-only mode
+        let policy = Policy {
+            mode: Mode::ReadOnly,
+            allow: Some(vec!["config_*".into()]),
+            ..Policy::default()
+        };
+        assert!(policy.check("config_show").is_ok());
+        assert_eq!(
+            policy.check("config_init").unwrap_err().reason,
+            DenyReason::ReadOnly
+        );
+    }
+
+    #[test]
+    fn test_policy_file_with_env_override() {
+        let path =
+            std::env::temp_dir().join(format!("ethcli-mcp-policy-{}.json", std::process::id()));
+        std::fs::write(
+            &path,
+            r#"{"mode": "read-only", "deny": ["rpc_call"], "allow_paid_tools": true}"#,
+        )
+        .unwrap();
+        let file = path.to_string_lossy().to_string();
+
+        let policy = Policy::from_vars(vars(&[("ETHCLI_MCP_POLICY", &file)])).unwrap();
+        assert_eq!(policy.mode, Mode::ReadOnly);
+        assert_eq!(policy.deny, ["rpc_call"]);
+        assert!(policy.allow_paid_tools);
+
+        let policy = Policy::from_vars(vars(&[
+            ("ETHCLI_MCP_POLICY", &file),
+            ("ETHCLI_MCP_MODE", "full"),
+        ]))
+        .unwrap();
+        assert_eq!(policy.mode, Mode::Full);
+
+        std::fs::write(&path, r#"{"mode": "read-only", "alow": []}"#).unwrap();
+        let err = Policy::from_vars(vars(&[("ETHCLI_MCP_POLICY", &file)])).unwrap_err();
+        assert!(matches!(err, PolicyError::Parse { .. }), "{err}");
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(
+            Policy::from_vars(vars(&[("ETHCLI_MCP_POLICY", "/nonexistent/policy.json")])),
+            Err(PolicyError::Read { .. })
+        ));
+        assert!(Policy::from_vars(vars(&[("ETHCLI_MCP_ALLOW_PAID_TOOLS", "maybe")])).is_err());
+    }
+
+    #[test]
+    fn test_denial_error_data() {
+        let policy = Policy {
+            mode: Mode::ReadOnly,
+            ..Policy::default()
+        };
+        let error = policy.check("address_add").unwrap_err().to_error_data();
+        assert_eq!(error.code, POLICY_DENIED);
+        assert!(error.message.contains("read-only"));
+        assert_eq!(
+            error.data.unwrap(),
+            serde_json::json!({
+                "error": "policy_denied",
+                "tool": "address_add",
+                "reason": "read_only",
+                "mode": "read-only",
+            })
+        );
+    }
+}
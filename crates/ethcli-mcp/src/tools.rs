@@ -105,6 +105,7 @@ pub async fn tx_analyze(hash: &str, chain: Option<&str>, trace: bool) -> Result<
         .arg(hash)
         .chain(chain)
         .opt_flag("--trace", trace)
+        .opt("-o", Some("json"))
         .execute()
         .await
         .map_err(ToolError::from)
@@ -402,7 +403,7 @@ pub async fn contract_call(
 }
 
 // =============================================================================
-// TOKEN (3 subcommands)
+// TOKEN (4 subcommands)
 // =============================================================================
 
 pub async fn token_info(address: &str, chain: Option<&str>) -> Result<String, ToolError> {
@@ -419,15 +420,25 @@ pub async fn token_holders(
     address: &str,
     chain: Option<&str>,
     limit: Option<u32>,
+    token_id: Option<&str>,
+    from_block: Option<u64>,
+    to_block: Option<u64>,
 ) -> Result<String, ToolError> {
     let mut builder = ArgsBuilder::new("token")
         .subcommand("holders")
         .arg(address)
-        .chain(chain);
+        .chain(chain)
+        .opt("--token-id", token_id);
 
     if let Some(l) = limit {
         builder = builder.opt("--limit", Some(&l.to_string()));
     }
+    if let Some(b) = from_block {
+        builder = builder.opt("--from-block", Some(&b.to_string()));
+    }
+    if let Some(b) = to_block {
+        builder = builder.opt("--to-block", Some(&b.to_string()));
+    }
 
     builder.execute().await.map_err(ToolError::from)
 }
@@ -436,11 +447,28 @@ pub async fn token_balance(
     token: &str,
     holder: &str,
     chain: Option<&str>,
+    token_id: Option<&str>,
 ) -> Result<String, ToolError> {
     ArgsBuilder::new("token")
         .subcommand("balance")
         .arg(token)
         .opt("--holder", Some(holder))
+        .opt("--token-id", token_id)
+        .chain(chain)
+        .execute()
+        .await
+        .map_err(ToolError::from)
+}
+
+pub async fn token_owner(
+    token: &str,
+    token_id: &str,
+    chain: Option<&str>,
+) -> Result<String, ToolError> {
+    ArgsBuilder::new("token")
+        .subcommand("owner")
+        .arg(token)
+        .arg(token_id)
         .chain(chain)
         .execute()
         .await
@@ -448,7 +476,7 @@ pub async fn token_balance(
 }
 
 // =============================================================================
-// GAS (2 subcommands)
+// GAS (3 subcommands)
 // =============================================================================
 
 pub async fn gas_oracle(chain: Option<&str>) -> Result<String, ToolError> {
@@ -477,6 +505,17 @@ pub async fn gas_estimate(
         .map_err(ToolError::from)
 }
 
+pub async fn gas_compare(op: Option<&str>, chains: Option<&str>) -> Result<String, ToolError> {
+    ArgsBuilder::new("gas")
+        .subcommand("compare")
+        .opt("--op", op)
+        .opt("--chains", chains)
+        .opt("-o", Some("json"))
+        .execute()
+        .await
+        .map_err(ToolError::from)
+}
+
 // =============================================================================
 // SIG (4 subcommands) - Signature lookup
 // =============================================================================
@@ -649,6 +688,17 @@ pub async fn cast_abi_decode(sig: &str, data: &str) -> Result<String, ToolError>
         .map_err(ToolError::from)
 }
 
+pub async fn cast_calldata_decode(calldata: &str, sig: Option<&str>) -> Result<String, ToolError> {
+    ArgsBuilder::new("cast")
+        .subcommand("calldata-decode")
+        .arg(calldata)
+        .opt("--sig", sig)
+        .opt("-o", Some("json"))
+        .execute()
+        .await
+        .map_err(ToolError::from)
+}
+
 // =============================================================================
 // RPC (9 subcommands)
 // =============================================================================
@@ -1883,14 +1933,50 @@ pub async fn uniswap_addresses(chain: Option<&str>) -> Result<String, ToolError>
 // KONG (5 subcommands) - Yearn/Kong
 // =============================================================================
 
-pub async fn kong_vaults(chain: Option<&str>) -> Result<String, ToolError> {
-    ArgsBuilder::new("kong")
+/// Default number of vaults returned by `kong_vaults`, keeping the response compact
+const KONG_VAULTS_DEFAULT_LIMIT: u32 = 50;
+
+/// Map a chain name to the chain ID used by Kong
+fn kong_chain_id(chain: &str) -> Result<u64, ToolError> {
+    match chain.to_lowercase().as_str() {
+        "ethereum" | "mainnet" | "eth" => Ok(1),
+        "polygon" | "matic" => Ok(137),
+        "arbitrum" | "arb" => Ok(42161),
+        "optimism" | "op" => Ok(10),
+        "base" => Ok(8453),
+        "fantom" | "ftm" => Ok(250),
+        "gnosis" | "xdai" => Ok(100),
+        other => other.parse::<u64>().map_err(|_| {
+            ToolError::InvalidInput(format!(
+                "Unsupported Kong chain: {other}. Use ethereum, polygon, arbitrum, optimism, base, fantom, gnosis or a chain ID"
+            ))
+        }),
+    }
+}
+
+pub async fn kong_vaults(
+    chain: Option<&str>,
+    fields: Option<&str>,
+    min_tvl: Option<f64>,
+    limit: Option<u32>,
+    sort: Option<&str>,
+) -> Result<String, ToolError> {
+    let chain_id = chain.map(kong_chain_id).transpose()?;
+    let limit = limit.unwrap_or(KONG_VAULTS_DEFAULT_LIMIT);
+
+    let mut builder = ArgsBuilder::new("kong")
         .subcommand("vaults")
         .subcommand("list")
-        .chain(chain)
-        .execute()
-        .await
-        .map_err(ToolError::from)
+        .opt("--chain-id", chain_id.map(|c| c.to_string()).as_deref())
+        .opt("--fields", fields)
+        .opt("--limit", Some(&limit.to_string()))
+        .opt("--sort", Some(sort.unwrap_or("tvl")));
+
+    if let Some(min) = min_tvl {
+        builder = builder.opt("--min-tvl", Some(&min.to_string()));
+    }
+
+    builder.execute().await.map_err(ToolError::from)
 }
 
 pub async fn kong_strategies(chain: Option<&str>) -> Result<String, ToolError> {
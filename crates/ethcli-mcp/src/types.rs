@@ -99,6 +99,12 @@ pub struct TokenHoldersInput {
     pub chain: String,
     /// Maximum number of holders to return
     pub limit: Option<u32>,
+    /// Only holders of this token id (ERC-721/ERC-1155)
+    pub token_id: Option<String>,
+    /// First block to scan for NFT transfers (defaults to contract creation)
+    pub from_block: Option<u64>,
+    /// Last block to scan for NFT transfers (defaults to latest)
+    pub to_block: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -110,6 +116,19 @@ pub struct TokenBalanceInput {
     /// Chain name
     #[serde(default = "default_chain")]
     pub chain: String,
+    /// Token id (required for ERC-1155, ownership check for ERC-721)
+    pub token_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TokenOwnerInput {
+    /// ERC-721 contract address
+    pub token: String,
+    /// Token id
+    pub token_id: String,
+    /// Chain name
+    #[serde(default = "default_chain")]
+    pub chain: String,
 }
 
 // --- Gas ---
@@ -133,6 +152,14 @@ pub struct GasEstimateInput {
     pub chain: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GasCompareInput {
+    /// Operation to price: transfer, erc20-transfer, swap or nft-mint (default: transfer)
+    pub op: Option<String>,
+    /// Comma-separated chains (default: ethereum,base,arbitrum,optimism)
+    pub chains: Option<String>,
+}
+
 // --- Signature Lookup ---
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SigLookupInput {
@@ -187,6 +214,14 @@ pub struct CastAbiDecodeInput {
     pub data: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CastCalldataDecodeInput {
+    /// Calldata including the 4-byte selector
+    pub calldata: String,
+    /// Signature to decode with instead of looking the selector up
+    pub sig: Option<String>,
+}
+
 // --- RPC ---
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct RpcCallInput {
@@ -908,6 +943,21 @@ pub struct KongChainInput {
     pub chain: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct KongVaultsInput {
+    /// Chain name (ethereum, polygon, arbitrum, optimism, base, fantom, gnosis)
+    #[serde(default = "default_chain")]
+    pub chain: String,
+    /// Comma-separated fields to return (address, name, symbol, chain-id, tvl, apy)
+    pub fields: Option<String>,
+    /// Minimum TVL in USD
+    pub min_tvl: Option<f64>,
+    /// Maximum number of vaults to return (default: 50)
+    pub limit: Option<u32>,
+    /// Sort descending by "tvl" or "apy" (default: tvl)
+    pub sort: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct KongVaultInput {
     /// Vault address
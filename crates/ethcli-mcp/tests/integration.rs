@@ -25,6 +25,11 @@ struct McpClient {
 
 impl McpClient {
     fn new() -> Self {
+        Self::with_env(&[])
+    }
+
+    /// Spawn the server with extra environment variables (e.g. policy settings)
+    fn with_env(env: &[(&str, &str)]) -> Self {
         // Use CARGO_BIN_EXE_ethcli-mcp which cargo sets during `cargo test`
         // This ensures the binary is built and available
         let binary = std::path::PathBuf::from(
@@ -55,6 +60,7 @@ impl McpClient {
         );
 
         let proc = Command::new(&binary)
+            .envs(env.iter().copied())
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -252,6 +258,188 @@ fn test_mcp_list_tools() {
     );
 }
 
+// =============================================================================
+// Policy Tests (no network required)
+// =============================================================================
+
+const PAID: &[&str] = &[
+    "dune_query",
+    "dune_sql",
+    "tenderly_simulate",
+    "simulate_bundle",
+];
+
+const MUTATING: &[&str] = &[
+    "config_set_etherscan_key",
+    "config_set_dune",
+    "config_init",
+    "address_add",
+    "address_import",
+    "blacklist_add",
+    "endpoints_add",
+    "endpoints_optimize",
+    "sig_cache_clear",
+];
+
+fn tool_names(client: &mut McpClient) -> Vec<String> {
+    let response = client.list_tools();
+    response["result"]["tools"]
+        .as_array()
+        .expect("Should have tools array")
+        .iter()
+        .filter_map(|t| t["name"].as_str().map(String::from))
+        .collect()
+}
+
+fn instructions(client: &mut McpClient) -> String {
+    let response = client.send_request(
+        "initialize",
+        Some(json!({
+            "protocolVersion": "2024-11-05",
+            "capabilities": {},
+            "clientInfo": {"name": "ethcli-mcp-test", "version": "1.0.0"}
+        })),
+    );
+    client.send_notification("notifications/initialized", None);
+    response["result"]["instructions"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string()
+}
+
+#[test]
+fn test_policy_default_hides_paid_tools() {
+    let mut client = McpClient::with_env(&[]);
+    let text = instructions(&mut client);
+    assert!(text.contains("Mode: full."), "{text}");
+    assert!(text.contains(
+        "Paid tools (dune_query, dune_sql, tenderly_simulate, simulate_bundle, \
+         simulate_call with via=tenderly) are disabled"
+    ));
+
+    let names = tool_names(&mut client);
+    for tool in PAID {
+        assert!(!names.iter().any(|n| n == tool), "{tool} should be hidden");
+    }
+    assert!(names.iter().any(|n| n == "dune_execution"));
+    assert!(names.iter().any(|n| n == "simulate_list"));
+
+    // simulate_call stays listed, but not through Tenderly
+    assert!(names.iter().any(|n| n == "simulate_call"));
+    let response = client.call_tool(
+        "simulate_call",
+        json!({"contract": USDC, "sig": "totalSupply()", "args": [], "via": "tenderly"}),
+    );
+    assert_eq!(response["error"]["code"], -32003, "{response}");
+    assert_eq!(response["error"]["data"]["reason"], "paid_tools_disabled");
+    for tool in MUTATING {
+        assert!(names.iter().any(|n| n == tool), "{tool} should be listed");
+    }
+}
+
+#[test]
+fn test_policy_allow_paid_tools() {
+    let mut client = McpClient::with_env(&[("ETHCLI_MCP_ALLOW_PAID_TOOLS", "true")]);
+    assert!(client.initialize());
+    let names = tool_names(&mut client);
+    for tool in PAID {
+        assert!(names.iter().any(|n| n == tool), "{tool} should be listed");
+    }
+}
+
+#[test]
+fn test_policy_read_only_removes_mutating_tools() {
+    let mut client = McpClient::with_env(&[("ETHCLI_MCP_MODE", "read-only")]);
+    let text = instructions(&mut client);
+    assert!(text.contains("Mode: read-only"), "{text}");
+
+    let names = tool_names(&mut client);
+    for tool in MUTATING {
+        assert!(!names.iter().any(|n| n == tool), "{tool} should be hidden");
+    }
+    assert!(!names.iter().any(|n| n.starts_with("config_set_")));
+    for tool in [
+        "cast_to_wei",
+        "address_list",
+        "endpoints_list",
+        "config_show",
+    ] {
+        assert!(names.iter().any(|n| n == tool), "{tool} should be listed");
+    }
+
+    // Calling a hidden tool anyway gets a structured policy error
+    let response = client.call_tool(
+        "address_add",
+        json!({"address": VITALIK, "name": "vitalik"}),
+    );
+    assert_eq!(response["error"]["code"], -32003, "{response}");
+    assert_eq!(
+        response["error"]["data"],
+        json!({
+            "error": "policy_denied",
+            "tool": "address_add",
+            "reason": "read_only",
+            "mode": "read-only"
+        })
+    );
+
+    let response = client.call_tool("dune_sql", json!({"sql": "SELECT 1"}));
+    assert_eq!(response["error"]["data"]["reason"], "paid_tools_disabled");
+}
+
+#[test]
+fn test_policy_allowlist_and_denylist() {
+    let mut client = McpClient::with_env(&[
+        ("ETHCLI_MCP_ALLOW", "cast_*,health,rpc_block_number"),
+        ("ETHCLI_MCP_DENY", "cast_keccak"),
+    ]);
+    let text = instructions(&mut client);
+    assert!(text.contains("Only these tools are enabled"), "{text}");
+
+    let mut names = tool_names(&mut client);
+    names.sort();
+    assert!(names
+        .iter()
+        .all(|n| n.starts_with("cast_") || n == "health" || n == "rpc_block_number"));
+    assert!(names.iter().any(|n| n == "health"));
+    assert!(names.iter().any(|n| n == "cast_to_wei"));
+    assert!(!names.iter().any(|n| n == "cast_keccak"));
+
+    let response = client.call_tool("cast_keccak", json!({"data": "hello"}));
+    assert_eq!(response["error"]["data"]["reason"], "denylisted");
+    let response = client.call_tool("account_balance", json!({"address": VITALIK}));
+    assert_eq!(response["error"]["data"]["reason"], "not_allowlisted");
+}
+
+#[test]
+fn test_policy_file() {
+    let path = std::env::temp_dir().join(format!(
+        "ethcli-mcp-integration-policy-{}.json",
+        std::process::id()
+    ));
+    std::fs::write(
+        &path,
+        r#"{"mode": "read-only", "allow_paid_tools": true, "deny": ["rpc_*"]}"#,
+    )
+    .unwrap();
+
+    let mut client = McpClient::with_env(&[("ETHCLI_MCP_POLICY", path.to_str().unwrap())]);
+    assert!(client.initialize());
+    let names = tool_names(&mut client);
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(names.iter().any(|n| n == "dune_sql"));
+    assert!(!names.iter().any(|n| n.starts_with("rpc_")));
+    assert!(!names.iter().any(|n| n == "address_add"));
+}
+
+#[test]
+fn test_policy_invalid_mode_refuses_to_start() {
+    let mut client = McpClient::with_env(&[("ETHCLI_MCP_MODE", "yolo")]);
+    let status = client.proc.wait().expect("server should exit");
+    assert!(!status.success());
+}
+
 // =============================================================================
 // Input Validation Tests (no network required, not ignored)
 // =============================================================================
@@ -0,0 +1,411 @@
+//! Contract call encoding and return-data decoding
+//!
+//! Shared by `cast abi-encode`/`abi-decode` and `rpc call`. A [`ContractCall`]
+//! is built from a foundry-style signature with return types
+//! (`balanceOf(address)(uint256)`) or from a function in a JSON ABI. It
+//! encodes string arguments into calldata and renders return data for
+//! humans: single scalars print bare, arrays and tuples as JSON.
+
+use crate::error::{AbiError, Result};
+use alloy::dyn_abi::{DynSolType, DynSolValue, FunctionExt, JsonAbiExt, Specifier};
+use alloy::json_abi::{Function, JsonAbi, Param};
+use alloy::primitives::utils::format_units;
+use serde_json::{Map, Value};
+
+/// A function call that can be encoded and whose output can be decoded
+#[derive(Debug, Clone)]
+pub struct ContractCall {
+    function: Function,
+}
+
+impl ContractCall {
+    /// Parse a signature such as `balanceOf(address)(uint256)` or
+    /// `function balanceOf(address) returns (uint256)`
+    ///
+    /// Return types are optional; without them the output cannot be decoded.
+    pub fn from_signature(signature: &str) -> Result<Self> {
+        let function = Function::parse(signature.trim()).map_err(|e| {
+            AbiError::ParseError(format!("invalid signature '{}': {}", signature, e))
+        })?;
+        Ok(Self { function })
+    }
+
+    /// Pick function `name` from an ABI
+    ///
+    /// Overloads are told apart by argument count; if several overloads take
+    /// `arg_count` arguments, pass a full signature instead.
+    pub fn from_abi(abi: &JsonAbi, name: &str, arg_count: usize) -> Result<Self> {
+        let overloads = abi
+            .function(name)
+            .ok_or_else(|| AbiError::ParseError(format!("function '{}' not found in ABI", name)))?;
+        let mut matching = overloads.iter().filter(|f| f.inputs.len() == arg_count);
+        match (matching.next(), matching.next()) {
+            (Some(function), None) => Ok(Self {
+                function: function.clone(),
+            }),
+            (Some(_), Some(_)) => Err(AbiError::ParseError(format!(
+                "'{}' has several overloads taking {} arguments; use --sig with the full signature",
+                name, arg_count
+            ))
+            .into()),
+            (None, _) => Err(AbiError::ParseError(format!(
+                "no '{}' overload takes {} arguments (found: {})",
+                name,
+                arg_count,
+                overloads
+                    .iter()
+                    .map(|f| f.signature())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+            .into()),
+        }
+    }
+
+    /// The parsed function
+    pub fn function(&self) -> &Function {
+        &self.function
+    }
+
+    /// Whether return types are known
+    pub fn has_outputs(&self) -> bool {
+        !self.function.outputs.is_empty()
+    }
+
+    /// Encode the selector and arguments, parsing each argument as its
+    /// input type (`[1,2]` for arrays, `(a,b)` for tuples)
+    pub fn encode(&self, args: &[String]) -> Result<Vec<u8>> {
+        let inputs = &self.function.inputs;
+        if inputs.len() != args.len() {
+            return Err(AbiError::ParseError(format!(
+                "Expected {} arguments, got {}",
+                inputs.len(),
+                args.len()
+            ))
+            .into());
+        }
+        let values = inputs
+            .iter()
+            .zip(args)
+            .map(|(param, arg)| {
+                let ty = resolve(param)?;
+                ty.coerce_str(arg).map_err(|e| {
+                    AbiError::ParseError(format!(
+                        "Invalid value '{}' for type '{}': {}",
+                        arg,
+                        ty.sol_type_name(),
+                        e
+                    ))
+                    .into()
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.function.abi_encode_input(&values).map_err(|e| {
+            AbiError::ParseError(format!("{}: {}", self.function.signature(), e)).into()
+        })
+    }
+
+    /// Decode return data into one value per output
+    pub fn decode_output(&self, data: &[u8]) -> Result<Vec<DynSolValue>> {
+        self.function.abi_decode_output(data).map_err(|e| {
+            AbiError::DecodeError(format!(
+                "{} returned 0x{}: {}",
+                self.function.signature(),
+                hex::encode(data),
+                e
+            ))
+            .into()
+        })
+    }
+
+    /// Decode return data and render it for humans
+    ///
+    /// Integers are scaled by `decimals` when given.
+    pub fn format_output(&self, data: &[u8], decimals: Option<u8>) -> Result<String> {
+        let values = self.decode_output(data)?;
+        Ok(format_values(&values, &self.function.outputs, decimals))
+    }
+}
+
+/// Decode data as a type list such as `uint256`, `(address,uint256)` or
+/// `address,uint256[]` and render it like [`ContractCall::format_output`]
+pub fn decode_types(data: &[u8], types: &str, decimals: Option<u8>) -> Result<String> {
+    let types = types.trim();
+    let ty = match DynSolType::parse(types) {
+        Ok(ty @ DynSolType::Tuple(_)) => ty,
+        _ => DynSolType::parse(&format!("({})", types))
+            .map_err(|e| AbiError::ParseError(format!("invalid type '{}': {}", types, e)))?,
+    };
+    let value = ty
+        .abi_decode_params(data)
+        .map_err(|e| AbiError::DecodeError(format!("{}: {}", types, e)))?;
+    let values = match value {
+        DynSolValue::Tuple(values) => values,
+        other => vec![other],
+    };
+    Ok(format_values(&values, &[], decimals))
+}
+
+/// Render decoded values: a single scalar as bare text, anything else as
+/// pretty-printed JSON
+///
+/// Outputs whose ABI params are all named become a JSON object.
+pub fn format_values(values: &[DynSolValue], params: &[Param], decimals: Option<u8>) -> String {
+    let json = match values {
+        [] => return "()".to_string(),
+        [single] => value_to_json(single, param_components(params, 0), decimals),
+        _ => tuple_to_json(values, params, decimals),
+    };
+    match json {
+        Value::String(s) => s,
+        Value::Array(_) | Value::Object(_) => {
+            serde_json::to_string_pretty(&json).unwrap_or_else(|_| json.to_string())
+        }
+        scalar => scalar.to_string(),
+    }
+}
+
+/// Convert a decoded value to JSON
+///
+/// Integers are strings (they overflow JSON numbers), scaled by `decimals`
+/// when given; addresses and bytes are 0x-hex. Tuples become objects when
+/// every component is named, arrays otherwise.
+pub fn value_to_json(value: &DynSolValue, components: &[Param], decimals: Option<u8>) -> Value {
+    match value {
+        DynSolValue::Bool(b) => Value::Bool(*b),
+        DynSolValue::Int(i, _) => Value::String(scaled(*i, i.to_string(), decimals)),
+        DynSolValue::Uint(u, _) => Value::String(scaled(*u, u.to_string(), decimals)),
+        DynSolValue::FixedBytes(b, size) => {
+            Value::String(format!("0x{}", hex::encode(&b[..*size])))
+        }
+        DynSolValue::Address(a) => Value::String(format!("{:#x}", a)),
+        DynSolValue::Function(f) => Value::String(format!("0x{}", hex::encode(f))),
+        DynSolValue::Bytes(b) => Value::String(format!("0x{}", hex::encode(b))),
+        DynSolValue::String(s) => Value::String(s.clone()),
+        DynSolValue::Array(items) | DynSolValue::FixedArray(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| value_to_json(item, components, decimals))
+                .collect(),
+        ),
+        DynSolValue::Tuple(fields) => tuple_to_json(fields, components, decimals),
+    }
+}
+
+fn tuple_to_json(fields: &[DynSolValue], components: &[Param], decimals: Option<u8>) -> Value {
+    let named = components.len() == fields.len() && components.iter().all(|c| !c.name.is_empty());
+    if named {
+        let map: Map<String, Value> = components
+            .iter()
+            .zip(fields)
+            .map(|(c, f)| (c.name.clone(), value_to_json(f, &c.components, decimals)))
+            .collect();
+        Value::Object(map)
+    } else {
+        Value::Array(
+            fields
+                .iter()
+                .enumerate()
+                .map(|(i, f)| value_to_json(f, param_components(components, i), decimals))
+                .collect(),
+        )
+    }
+}
+
+fn param_components(params: &[Param], index: usize) -> &[Param] {
+    params.get(index).map_or(&[][..], |p| &p.components)
+}
+
+/// Format an integer with `decimals`, dropping trailing zeros
+fn scaled<T>(value: T, raw: String, decimals: Option<u8>) -> String
+where
+    T: Into<alloy::primitives::utils::ParseUnits>,
+{
+    let Some(decimals) = decimals else {
+        return raw;
+    };
+    match format_units(value, decimals) {
+        Ok(s) if s.contains('.') => s.trim_end_matches('0').trim_end_matches('.').to_string(),
+        Ok(s) => s,
+        Err(_) => raw,
+    }
+}
+
+fn resolve(param: &Param) -> Result<DynSolType> {
+    param.resolve().map_err(|e| {
+        AbiError::ParseError(format!("invalid type '{}': {}", param.selector_type(), e)).into()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(hex: &str) -> String {
+        format!("{:0>64}", hex)
+    }
+
+    fn call(signature: &str) -> ContractCall {
+        ContractCall::from_signature(signature).unwrap()
+    }
+
+    #[test]
+    fn test_encode_foundry_signature() {
+        let call = call("balanceOf(address)(uint256)");
+        assert!(call.has_outputs());
+        let data = call
+            .encode(&["0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string()])
+            .unwrap();
+        assert_eq!(
+            hex::encode(data),
+            format!(
+                "70a08231{}",
+                word("d8da6bf26964af9d7eed9e03e53415d37aa96045")
+            )
+        );
+    }
+
+    #[test]
+    fn test_encode_wrong_arg_count() {
+        let err = call("transfer(address,uint256)")
+            .encode(&["0x0000000000000000000000000000000000000001".to_string()])
+            .unwrap_err();
+        assert!(err.to_string().contains("Expected 2 arguments, got 1"));
+    }
+
+    #[test]
+    fn test_uint_with_decimals() {
+        let call = call("balanceOf(address)(uint256)");
+        let data = hex::decode(word("1e8480")).unwrap(); // 2_000_000
+        assert_eq!(call.format_output(&data, None).unwrap(), "2000000");
+        assert_eq!(call.format_output(&data, Some(6)).unwrap(), "2");
+        assert_eq!(call.format_output(&data, Some(8)).unwrap(), "0.02");
+    }
+
+    #[test]
+    fn test_negative_int_with_decimals() {
+        let call = call("latestAnswer()(int256)");
+        let data = hex::decode("f".repeat(56) + "fffe7960").unwrap(); // -100_000
+        assert_eq!(call.format_output(&data, Some(3)).unwrap(), "-100");
+    }
+
+    #[test]
+    fn test_bool_and_address() {
+        let data = hex::decode(word("1")).unwrap();
+        assert_eq!(
+            call("paused()(bool)").format_output(&data, None).unwrap(),
+            "true"
+        );
+
+        let data = hex::decode(word("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48")).unwrap();
+        assert_eq!(
+            call("token()(address)").format_output(&data, None).unwrap(),
+            "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"
+        );
+    }
+
+    #[test]
+    fn test_string_and_bytes() {
+        // string "USD Coin"
+        let data =
+            hex::decode(word("20") + &word("8") + &format!("{:0<64}", hex::encode("USD Coin")))
+                .unwrap();
+        assert_eq!(
+            call("name()(string)").format_output(&data, None).unwrap(),
+            "USD Coin"
+        );
+
+        // bytes 0xdeadbeef
+        let data = hex::decode(word("20") + &word("4") + &format!("{:0<64}", "deadbeef")).unwrap();
+        assert_eq!(
+            call("code()(bytes)").format_output(&data, None).unwrap(),
+            "0xdeadbeef"
+        );
+    }
+
+    #[test]
+    fn test_bool_array_as_json() {
+        let data =
+            hex::decode(word("20") + &word("3") + &word("1") + &word("0") + &word("1")).unwrap();
+        let out = call("flags()(bool[])").format_output(&data, None).unwrap();
+        let json: Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(json, serde_json::json!([true, false, true]));
+    }
+
+    #[test]
+    fn test_nested_tuple_as_json() {
+        // getReserve() returns ((uint112,uint112),address,bool)
+        let call = call("getReserve()(((uint112,uint112),address,bool))");
+        let data = hex::decode(
+            word("de0b6b3a7640000")
+                + &word("3b9aca00")
+                + &word("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48")
+                + &word("1"),
+        )
+        .unwrap();
+        let json: Value =
+            serde_json::from_str(&call.format_output(&data, Some(18)).unwrap()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([
+                ["1", "0.000000001"],
+                "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+                true
+            ])
+        );
+    }
+
+    #[test]
+    fn test_multiple_named_outputs_from_abi() {
+        let abi: JsonAbi = serde_json::from_str(
+            r#"[{
+                "type": "function",
+                "name": "getReserves",
+                "stateMutability": "view",
+                "inputs": [],
+                "outputs": [
+                    {"name": "reserve0", "type": "uint112"},
+                    {"name": "reserve1", "type": "uint112"},
+                    {"name": "blockTimestampLast", "type": "uint32"}
+                ]
+            }]"#,
+        )
+        .unwrap();
+        let call = ContractCall::from_abi(&abi, "getReserves", 0).unwrap();
+        assert_eq!(hex::encode(call.encode(&[]).unwrap()), "0902f1ac");
+
+        let data = hex::decode(word("64") + &word("c8") + &word("5")).unwrap();
+        let json: Value = serde_json::from_str(&call.format_output(&data, None).unwrap()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"reserve0": "100", "reserve1": "200", "blockTimestampLast": "5"})
+        );
+
+        assert!(ContractCall::from_abi(&abi, "getReserves", 1).is_err());
+        assert!(ContractCall::from_abi(&abi, "missing", 0).is_err());
+    }
+
+    #[test]
+    fn test_decode_types() {
+        let data = hex::decode(word("3e8")).unwrap();
+        assert_eq!(decode_types(&data, "uint256", None).unwrap(), "1000");
+
+        let data = hex::decode(word("1") + &word("2")).unwrap();
+        let json: Value =
+            serde_json::from_str(&decode_types(&data, "uint8,uint8", None).unwrap()).unwrap();
+        assert_eq!(json, serde_json::json!(["1", "2"]));
+        let json: Value =
+            serde_json::from_str(&decode_types(&data, "(uint8,uint8)", None).unwrap()).unwrap();
+        assert_eq!(json, serde_json::json!(["1", "2"]));
+
+        assert!(decode_types(&data, "invalid_type", None).is_err());
+    }
+
+    #[test]
+    fn test_output_decode_error() {
+        let err = call("balanceOf(address)(uint256)")
+            .format_output(&[0x01], None)
+            .unwrap_err();
+        assert!(err.to_string().contains("balanceOf(address)"));
+    }
+}
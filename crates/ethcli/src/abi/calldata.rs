@@ -0,0 +1,383 @@
+//! Function calldata decoding using an ABI or a bare signature
+
+use super::call::value_to_json;
+use crate::error::{AbiError, Result};
+use alloy::dyn_abi::JsonAbiExt;
+use alloy::json_abi::{Function, JsonAbi, Param};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Where the function definition used for decoding came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CalldataSource {
+    /// The contract's verified ABI
+    Abi,
+    /// A signature database lookup (4byte.directory)
+    Signature,
+}
+
+/// A decoded function argument
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecodedArg {
+    /// Parameter name (`param{i}` when the ABI leaves it blank)
+    pub name: String,
+    /// Canonical Solidity type
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// Decoded value; tuples become objects keyed by component name
+    pub value: Value,
+}
+
+/// Decoded function calldata
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecodedCalldata {
+    /// Function selector (4 bytes as hex)
+    pub selector: String,
+    /// Function name
+    pub name: String,
+    /// Canonical function signature
+    pub signature: String,
+    /// Where the function definition came from
+    pub source: CalldataSource,
+    /// Decoded arguments in order
+    pub args: Vec<DecodedArg>,
+}
+
+impl DecodedCalldata {
+    /// Decode calldata with the matching function from an ABI
+    ///
+    /// Returns `None` when the selector is not in the ABI.
+    pub fn from_abi(abi: &JsonAbi, calldata: &[u8]) -> Option<Result<Self>> {
+        let selector = calldata.get(..4)?;
+        abi.functions()
+            .find(|f| f.selector().as_slice() == selector)
+            .map(|f| Self::decode(f, calldata, CalldataSource::Abi))
+    }
+
+    /// Decode calldata with a text signature such as `transfer(address,uint256)`
+    pub fn from_signature(signature: &str, calldata: &[u8]) -> Result<Self> {
+        let func = Function::parse(signature).map_err(|e| {
+            AbiError::CalldataDecode(format!("invalid signature '{}': {}", signature, e))
+        })?;
+        Self::decode(&func, calldata, CalldataSource::Signature)
+    }
+
+    /// Decode calldata against a function definition
+    pub fn decode(func: &Function, calldata: &[u8], source: CalldataSource) -> Result<Self> {
+        if calldata.len() < 4 {
+            return Err(AbiError::CalldataDecode(format!(
+                "calldata is {} bytes, expected at least a 4-byte selector",
+                calldata.len()
+            ))
+            .into());
+        }
+        if func.selector().as_slice() != &calldata[..4] {
+            return Err(AbiError::CalldataDecode(format!(
+                "selector 0x{} does not match {}",
+                hex::encode(&calldata[..4]),
+                func.signature()
+            ))
+            .into());
+        }
+
+        let values = func
+            .abi_decode_input(&calldata[4..])
+            .map_err(|e| AbiError::CalldataDecode(format!("{}: {}", func.signature(), e)))?;
+
+        let args = func
+            .inputs
+            .iter()
+            .zip(&values)
+            .enumerate()
+            .map(|(i, (param, value))| DecodedArg {
+                name: param_name(param, i),
+                ty: param.selector_type().into_owned(),
+                value: value_to_json(value, &param.components, None),
+            })
+            .collect();
+
+        Ok(Self {
+            selector: format!("0x{}", hex::encode(&calldata[..4])),
+            name: func.name.clone(),
+            signature: func.signature(),
+            source,
+            args,
+        })
+    }
+
+    /// Render as indented text, one argument (or nested field) per line
+    pub fn format_text(&self) -> String {
+        let mut out = format!(
+            "Function:  {}\nSelector:  {}\nSource:    {}\n",
+            self.signature,
+            self.selector,
+            match self.source {
+                CalldataSource::Abi => "abi",
+                CalldataSource::Signature => "signature database",
+            }
+        );
+        if self.args.is_empty() {
+            out.push_str("Arguments: (none)\n");
+            return out;
+        }
+        out.push_str("Arguments:\n");
+        for arg in &self.args {
+            write_value(
+                &mut out,
+                &format!("{} ({})", arg.name, arg.ty),
+                &arg.value,
+                1,
+            );
+        }
+        out
+    }
+}
+
+/// A signature database match tried against calldata
+#[derive(Debug, Clone, Serialize)]
+pub struct CalldataCandidate {
+    /// Candidate function signature
+    pub signature: String,
+    /// Whether re-encoding the decoded arguments reproduces the calldata
+    /// byte for byte (no trailing data, canonical padding)
+    pub exact: bool,
+    /// Decoded calldata, or why it did not decode
+    #[serde(flatten, with = "candidate_result")]
+    pub decoded: std::result::Result<DecodedCalldata, String>,
+}
+
+impl CalldataCandidate {
+    /// Try each signature against the calldata, best match first
+    ///
+    /// Exact decodes come first, then looser decodes, then signatures that
+    /// do not decode at all. Within each group the input order (signature
+    /// database popularity) is kept.
+    pub fn rank(signatures: &[String], calldata: &[u8]) -> Vec<Self> {
+        let mut candidates: Vec<Self> = signatures
+            .iter()
+            .map(|signature| Self::try_signature(signature, calldata))
+            .collect();
+        candidates.sort_by_key(|c| match (&c.decoded, c.exact) {
+            (Ok(_), true) => 0,
+            (Ok(_), false) => 1,
+            (Err(_), _) => 2,
+        });
+        candidates
+    }
+
+    fn try_signature(signature: &str, calldata: &[u8]) -> Self {
+        let decoded =
+            DecodedCalldata::from_signature(signature, calldata).map_err(|e| e.to_string());
+        let exact = decoded.is_ok()
+            && Function::parse(signature)
+                .ok()
+                .and_then(|func| {
+                    let values = func.abi_decode_input(&calldata[4..]).ok()?;
+                    func.abi_encode_input(&values).ok()
+                })
+                .is_some_and(|encoded| encoded == calldata);
+        Self {
+            signature: signature.to_string(),
+            exact,
+            decoded,
+        }
+    }
+
+    /// Whether the signature decoded the calldata
+    pub fn is_decoded(&self) -> bool {
+        self.decoded.is_ok()
+    }
+}
+
+/// Serialize a candidate's decode result as `decoded` or `error`
+mod candidate_result {
+    use super::DecodedCalldata;
+    use serde::ser::SerializeMap;
+    use serde::Serializer;
+
+    pub fn serialize<S: Serializer>(
+        result: &Result<DecodedCalldata, String>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(1))?;
+        match result {
+            Ok(decoded) => map.serialize_entry("decoded", decoded)?,
+            Err(error) => map.serialize_entry("error", error)?,
+        }
+        map.end()
+    }
+}
+
+fn write_value(out: &mut String, label: &str, value: &Value, depth: usize) {
+    let indent = "  ".repeat(depth);
+    match value {
+        Value::Object(map) => {
+            out.push_str(&format!("{}{}:\n", indent, label));
+            for (key, field) in map {
+                write_value(out, key, field, depth + 1);
+            }
+        }
+        Value::Array(items) if items.iter().any(|v| v.is_object() || v.is_array()) => {
+            out.push_str(&format!("{}{}:\n", indent, label));
+            for (i, item) in items.iter().enumerate() {
+                write_value(out, &format!("[{}]", i), item, depth + 1);
+            }
+        }
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(scalar_text).collect();
+            out.push_str(&format!("{}{}: [{}]\n", indent, label, items.join(", ")));
+        }
+        scalar => out.push_str(&format!("{}{}: {}\n", indent, label, scalar_text(scalar))),
+    }
+}
+
+fn scalar_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn param_name(param: &Param, index: usize) -> String {
+    if param.name.is_empty() {
+        format!("param{}", index)
+    } else {
+        param.name.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::dyn_abi::DynSolValue;
+
+    const SWAP_ABI: &str = r#"[{
+        "type": "function",
+        "name": "swap",
+        "stateMutability": "nonpayable",
+        "inputs": [
+            {"name": "order", "type": "tuple", "components": [
+                {"name": "maker", "type": "address"},
+                {"name": "amounts", "type": "uint256[]"},
+                {"name": "fee", "type": "tuple", "components": [
+                    {"name": "bps", "type": "uint16"},
+                    {"name": "recipient", "type": "address"}
+                ]}
+            ]},
+            {"name": "", "type": "bytes"}
+        ],
+        "outputs": []
+    }]"#;
+
+    fn transfer_calldata() -> Vec<u8> {
+        hex::decode(concat!(
+            "a9059cbb",
+            "000000000000000000000000d8da6bf26964af9d7eed9e03e53415d37aa96045",
+            "00000000000000000000000000000000000000000000000000000000000f4240"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_decode_from_signature() {
+        let decoded =
+            DecodedCalldata::from_signature("transfer(address,uint256)", &transfer_calldata())
+                .unwrap();
+        assert_eq!(decoded.selector, "0xa9059cbb");
+        assert_eq!(decoded.name, "transfer");
+        assert_eq!(decoded.source, CalldataSource::Signature);
+        assert_eq!(decoded.args.len(), 2);
+        assert_eq!(decoded.args[0].name, "param0");
+        assert_eq!(decoded.args[0].ty, "address");
+        assert_eq!(
+            decoded.args[0].value,
+            "0xd8da6bf26964af9d7eed9e03e53415d37aa96045"
+        );
+        assert_eq!(decoded.args[1].value, "1000000");
+    }
+
+    #[test]
+    fn test_decode_selector_mismatch() {
+        let err = DecodedCalldata::from_signature("approve(address,uint256)", &transfer_calldata())
+            .unwrap_err();
+        assert!(err.to_string().contains("does not match"));
+    }
+
+    #[test]
+    fn test_decode_nested_tuple_from_abi() {
+        let abi: JsonAbi = serde_json::from_str(SWAP_ABI).unwrap();
+        let func = abi.functions().next().unwrap();
+        let order = DynSolValue::Tuple(vec![
+            DynSolValue::Address(alloy::primitives::Address::repeat_byte(0x11)),
+            DynSolValue::Array(vec![
+                DynSolValue::Uint(alloy::primitives::U256::from(5u64), 256),
+                DynSolValue::Uint(alloy::primitives::U256::from(7u64), 256),
+            ]),
+            DynSolValue::Tuple(vec![
+                DynSolValue::Uint(alloy::primitives::U256::from(30u64), 16),
+                DynSolValue::Address(alloy::primitives::Address::repeat_byte(0x22)),
+            ]),
+        ]);
+        let calldata = func
+            .abi_encode_input(&[order, DynSolValue::Bytes(vec![0xde, 0xad])])
+            .unwrap();
+
+        let decoded = DecodedCalldata::from_abi(&abi, &calldata)
+            .expect("selector in ABI")
+            .unwrap();
+        assert_eq!(decoded.source, CalldataSource::Abi);
+        assert_eq!(
+            decoded.signature,
+            "swap((address,uint256[],(uint16,address)),bytes)"
+        );
+        assert_eq!(decoded.args[0].ty, "(address,uint256[],(uint16,address))");
+        let order = &decoded.args[0].value;
+        assert_eq!(order["maker"], format!("0x{}", "11".repeat(20)));
+        assert_eq!(order["amounts"], serde_json::json!(["5", "7"]));
+        assert_eq!(order["fee"]["bps"], "30");
+        assert_eq!(decoded.args[1].name, "param1");
+        assert_eq!(decoded.args[1].value, "0xdead");
+
+        assert!(DecodedCalldata::from_abi(&abi, &transfer_calldata()).is_none());
+
+        let text = decoded.format_text();
+        assert!(text.contains("Source:    abi"));
+        assert!(text.contains("    amounts: [5, 7]\n"));
+        assert!(text.contains("    fee:\n      bps: 30\n"));
+        assert!(text.contains("  param1 (bytes): 0xdead\n"));
+    }
+
+    #[test]
+    fn test_rank_candidates_prefers_exact_decode() {
+        let mut padded = transfer_calldata();
+        padded.extend_from_slice(&[0u8; 32]);
+
+        let signatures = vec![
+            "approve(address,uint256)".to_string(),
+            "transfer(address,uint256)".to_string(),
+        ];
+        let candidates = CalldataCandidate::rank(&signatures, &transfer_calldata());
+        assert_eq!(candidates[0].signature, "transfer(address,uint256)");
+        assert!(candidates[0].exact);
+        // Selector mismatch sorts last
+        assert!(!candidates[1].is_decoded());
+
+        // Trailing data still decodes but is not an exact match
+        let candidates = CalldataCandidate::rank(&signatures[1..], &padded);
+        assert!(candidates[0].is_decoded());
+        assert!(!candidates[0].exact);
+    }
+
+    #[test]
+    fn test_candidate_json_shape() {
+        let candidates = CalldataCandidate::rank(
+            &["transfer(address,uint256)".to_string()],
+            &transfer_calldata(),
+        );
+        let json = serde_json::to_value(&candidates[0]).unwrap();
+        assert_eq!(json["exact"], true);
+        assert_eq!(json["decoded"]["name"], "transfer");
+        assert!(json.get("error").is_none());
+    }
+}
@@ -0,0 +1,513 @@
+//! Code generation for decoded event logs
+//!
+//! Maps ABI event definitions to the JSON shape of [`DecodedLog::params`]
+//! and renders that shape as Rust serde structs or JSON Schema documents.
+//! [`field_type`] is the shared type-mapping layer; new output targets only
+//! need to render [`FieldType`].
+//!
+//! [`DecodedLog::params`]: crate::abi::DecodedLog
+
+use crate::abi::LogDecoder;
+use crate::error::Result;
+use alloy::dyn_abi::DynSolType;
+use alloy::json_abi::{Event, JsonAbi};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, HashSet};
+
+/// JSON shape of a decoded parameter value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldType {
+    /// Lowercase 0x-prefixed address string
+    Address,
+    /// Decimal integer string
+    Integer {
+        /// Whether the integer is signed
+        signed: bool,
+        /// Bit width
+        bits: usize,
+    },
+    /// JSON boolean
+    Bool,
+    /// 0x-prefixed hex string, `len` bytes if fixed
+    Bytes {
+        /// Length for `bytesN`
+        len: Option<usize>,
+    },
+    /// UTF-8 string
+    String,
+    /// Keccak hash of an indexed dynamic value (0x-prefixed, 32 bytes)
+    TopicHash,
+    /// JSON array of items, exactly `len` if fixed
+    Array {
+        /// Item type
+        item: Box<FieldType>,
+        /// Length for `T[N]`
+        len: Option<usize>,
+    },
+    /// JSON array with one positional entry per component
+    Tuple(Vec<FieldType>),
+}
+
+/// Map a Solidity type to the JSON shape the log decoder produces for it
+///
+/// Indexed strings, bytes and dynamic arrays are only available as their
+/// topic hash.
+pub fn field_type(ty: &DynSolType, indexed: bool) -> FieldType {
+    if indexed
+        && matches!(
+            ty,
+            DynSolType::String | DynSolType::Bytes | DynSolType::Array(_)
+        )
+    {
+        return FieldType::TopicHash;
+    }
+    match ty {
+        DynSolType::Address => FieldType::Address,
+        DynSolType::Bool => FieldType::Bool,
+        DynSolType::Int(bits) => FieldType::Integer {
+            signed: true,
+            bits: *bits,
+        },
+        DynSolType::Uint(bits) => FieldType::Integer {
+            signed: false,
+            bits: *bits,
+        },
+        DynSolType::Bytes => FieldType::Bytes { len: None },
+        DynSolType::FixedBytes(len) => FieldType::Bytes { len: Some(*len) },
+        DynSolType::Function => FieldType::Bytes { len: Some(24) },
+        DynSolType::String => FieldType::String,
+        DynSolType::Array(item) => FieldType::Array {
+            item: Box::new(field_type(item, false)),
+            len: None,
+        },
+        DynSolType::FixedArray(item, len) => FieldType::Array {
+            item: Box::new(field_type(item, false)),
+            len: Some(*len),
+        },
+        DynSolType::Tuple(items) => {
+            FieldType::Tuple(items.iter().map(|t| field_type(t, false)).collect())
+        }
+    }
+}
+
+/// An event field as it appears in decoded logs
+#[derive(Debug, Clone)]
+pub struct EventField {
+    /// Key in the decoded `params` object
+    pub key: String,
+    /// Rust field name
+    pub ident: String,
+    /// Canonical Solidity type
+    pub solidity_type: String,
+    /// Whether the parameter is indexed
+    pub indexed: bool,
+    /// JSON shape of the value
+    pub ty: FieldType,
+}
+
+/// An event definition prepared for code generation
+#[derive(Debug, Clone)]
+pub struct EventDef {
+    /// Event name
+    pub name: String,
+    /// Rust struct name, unique within the ABI
+    pub struct_name: String,
+    /// Canonical signature
+    pub signature: String,
+    /// topic0, `None` for anonymous events
+    pub topic0: Option<String>,
+    /// Fields in ABI order
+    pub fields: Vec<EventField>,
+}
+
+/// Manifest entry listing an event and its topic0
+#[derive(Debug, Clone, Serialize)]
+pub struct ManifestEntry {
+    pub name: String,
+    #[serde(rename = "struct")]
+    pub struct_name: String,
+    pub signature: String,
+    pub topic0: Option<String>,
+}
+
+/// Extract every event of an ABI, sorted by name then signature
+///
+/// Unnamed parameters all decode under the `""` key, so only the first of
+/// them is kept.
+pub fn event_defs(abi: &JsonAbi) -> Result<Vec<EventDef>> {
+    let mut events: Vec<&Event> = abi.events().collect();
+    events.sort_by(|a, b| {
+        a.name
+            .cmp(&b.name)
+            .then_with(|| a.signature().cmp(&b.signature()))
+    });
+
+    let mut struct_names = HashSet::new();
+    let mut defs = Vec::with_capacity(events.len());
+    for event in events {
+        let struct_name = unique(pascal_case(&event.name), &mut struct_names);
+
+        let mut keys = HashSet::new();
+        let mut idents = HashSet::new();
+        let mut fields = Vec::with_capacity(event.inputs.len());
+        for (i, input) in event.inputs.iter().enumerate() {
+            if !keys.insert(input.name.clone()) {
+                continue;
+            }
+            let ty = LogDecoder::resolve_event_param_type(input)?;
+            let ident = match snake_case(&input.name) {
+                name if name.is_empty() => format!("param{i}"),
+                name => name,
+            };
+            fields.push(EventField {
+                key: input.name.clone(),
+                ident: unique(ident, &mut idents),
+                solidity_type: ty.sol_type_name().into_owned(),
+                indexed: input.indexed,
+                ty: field_type(&ty, input.indexed),
+            });
+        }
+
+        defs.push(EventDef {
+            name: event.name.clone(),
+            struct_name,
+            signature: event.signature(),
+            topic0: (!event.anonymous).then(|| format!("{:#x}", event.selector())),
+            fields,
+        });
+    }
+    Ok(defs)
+}
+
+/// Manifest listing each event's struct name and topic0
+pub fn manifest(defs: &[EventDef]) -> Vec<ManifestEntry> {
+    defs.iter()
+        .map(|def| ManifestEntry {
+            name: def.name.clone(),
+            struct_name: def.struct_name.clone(),
+            signature: def.signature.clone(),
+            topic0: def.topic0.clone(),
+        })
+        .collect()
+}
+
+/// Render a Rust module with one serde struct per event
+///
+/// Each struct deserializes the `params` object of a decoded log. A
+/// `TOPIC0` table maps topic0 to struct name.
+pub fn rust_module(defs: &[EventDef], source: &str) -> String {
+    let mut out = String::new();
+    out.push_str(&format!(
+        "//! Event params for {source}\n//!\n\
+         //! Generated by `ethcli contract events --codegen rust`. Each struct\n\
+         //! deserializes the `params` object of a decoded log.\n\n\
+         #![allow(dead_code)]\n\n\
+         use serde::{{Deserialize, Serialize}};\n"
+    ));
+
+    for def in defs {
+        out.push_str(&format!("\n/// `{}`\n", def.signature));
+        if let Some(topic0) = &def.topic0 {
+            out.push_str(&format!("///\n/// topic0: `{topic0}`\n"));
+        }
+        out.push_str(
+            "#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]\n\
+             #[serde(deny_unknown_fields)]\n",
+        );
+        if def.fields.is_empty() {
+            out.push_str(&format!("pub struct {} {{}}\n", def.struct_name));
+            continue;
+        }
+        out.push_str(&format!("pub struct {} {{\n", def.struct_name));
+        for field in &def.fields {
+            let indexed = if field.indexed { " (indexed)" } else { "" };
+            out.push_str(&format!("    /// `{}`{indexed}\n", field.solidity_type));
+            if field.key != field.ident {
+                out.push_str(&format!("    #[serde(rename = {:?})]\n", field.key));
+            }
+            out.push_str(&format!(
+                "    pub {}: {},\n",
+                field.ident,
+                rust_type(&field.ty)
+            ));
+        }
+        out.push_str("}\n");
+    }
+
+    out.push_str("\n/// `(topic0, struct name)` for every non-anonymous event\n");
+    out.push_str("pub const TOPIC0: &[(&str, &str)] = &[\n");
+    for def in defs {
+        if let Some(topic0) = &def.topic0 {
+            out.push_str(&format!("    ({topic0:?}, {:?}),\n", def.struct_name));
+        }
+    }
+    out.push_str("];\n");
+    out
+}
+
+/// Rust type for a decoded value
+pub fn rust_type(ty: &FieldType) -> String {
+    match ty {
+        FieldType::Bool => "bool".to_string(),
+        FieldType::Address
+        | FieldType::Integer { .. }
+        | FieldType::Bytes { .. }
+        | FieldType::String
+        | FieldType::TopicHash => "String".to_string(),
+        FieldType::Array { item, .. } => format!("Vec<{}>", rust_type(item)),
+        FieldType::Tuple(items) => match items.as_slice() {
+            [single] => format!("({},)", rust_type(single)),
+            items => format!(
+                "({})",
+                items.iter().map(rust_type).collect::<Vec<_>>().join(", ")
+            ),
+        },
+    }
+}
+
+/// JSON Schema (draft 2020-12) for an event's decoded params
+pub fn json_schema(def: &EventDef) -> Value {
+    let mut properties = Map::new();
+    for field in &def.fields {
+        let mut schema = schema_for(&field.ty);
+        schema["description"] = Value::String(if field.indexed {
+            format!("{} (indexed)", field.solidity_type)
+        } else {
+            field.solidity_type.clone()
+        });
+        properties.insert(field.key.clone(), schema);
+    }
+
+    let mut schema = json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": def.struct_name,
+        "description": def.signature,
+        "type": "object",
+        "properties": properties,
+        "required": def.fields.iter().map(|f| f.key.clone()).collect::<Vec<_>>(),
+        "additionalProperties": false,
+    });
+    if let Some(topic0) = &def.topic0 {
+        schema["x-topic0"] = Value::String(topic0.clone());
+    }
+    schema
+}
+
+/// JSON Schema for a decoded value
+pub fn schema_for(ty: &FieldType) -> Value {
+    match ty {
+        FieldType::Address => json!({ "type": "string", "pattern": "^0x[0-9a-f]{40}$" }),
+        FieldType::Integer { signed: true, .. } => {
+            json!({ "type": "string", "pattern": "^-?[0-9]+$" })
+        }
+        FieldType::Integer { signed: false, .. } => {
+            json!({ "type": "string", "pattern": "^[0-9]+$" })
+        }
+        FieldType::Bool => json!({ "type": "boolean" }),
+        FieldType::Bytes { len: Some(len) } => {
+            json!({ "type": "string", "pattern": format!("^0x[0-9a-f]{{{}}}$", len * 2) })
+        }
+        FieldType::Bytes { len: None } => json!({ "type": "string", "pattern": "^0x[0-9a-f]*$" }),
+        FieldType::String => json!({ "type": "string" }),
+        FieldType::TopicHash => json!({ "type": "string", "pattern": "^0x[0-9a-f]{64}$" }),
+        FieldType::Array { item, len } => {
+            let mut schema = json!({ "type": "array", "items": schema_for(item) });
+            if let Some(len) = len {
+                schema["minItems"] = json!(len);
+                schema["maxItems"] = json!(len);
+            }
+            schema
+        }
+        FieldType::Tuple(items) => json!({
+            "type": "array",
+            "prefixItems": items.iter().map(schema_for).collect::<Vec<_>>(),
+            "items": false,
+            "minItems": items.len(),
+        }),
+    }
+}
+
+/// JSON Schema documents keyed by struct name
+pub fn json_schemas(defs: &[EventDef]) -> BTreeMap<String, Value> {
+    defs.iter()
+        .map(|def| (def.struct_name.clone(), json_schema(def)))
+        .collect()
+}
+
+/// Rust keywords that can't be used as plain field names
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use",
+    "where", "while", "abstract", "become", "box", "do", "final", "gen", "macro", "override",
+    "priv", "try", "typeof", "unsized", "virtual", "yield",
+];
+
+/// Convert a Solidity identifier to a snake_case Rust field name
+fn snake_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 4);
+    let chars: Vec<char> = name.chars().collect();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() {
+            let prev_lower =
+                i > 0 && (chars[i - 1].is_ascii_lowercase() || chars[i - 1].is_ascii_digit());
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_ascii_lowercase());
+            let prev_upper = i > 0 && chars[i - 1].is_ascii_uppercase();
+            if !out.is_empty() && !out.ends_with('_') && (prev_lower || (prev_upper && next_lower))
+            {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else if c.is_ascii_alphanumeric() {
+            out.push(c);
+        } else {
+            out.push('_');
+        }
+    }
+    let out = out.trim_matches('_').to_string();
+    if RUST_KEYWORDS.contains(&out.as_str()) {
+        format!("{out}_")
+    } else {
+        out
+    }
+}
+
+/// Convert an event name to a PascalCase Rust type name
+fn pascal_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut upper = true;
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            out.push(if upper { c.to_ascii_uppercase() } else { c });
+            upper = false;
+        } else {
+            upper = true;
+        }
+    }
+    match out.chars().next() {
+        None => "Event".to_string(),
+        Some(c) if c.is_ascii_digit() => format!("Event{out}"),
+        Some(_) if out == "Self" => "SelfEvent".to_string(),
+        Some(_) => out,
+    }
+}
+
+/// Make `name` unique within `taken` by appending a counter
+fn unique(name: String, taken: &mut HashSet<String>) -> String {
+    if taken.insert(name.clone()) {
+        return name;
+    }
+    let name = (2..)
+        .map(|n| format!("{name}{n}"))
+        .find(|candidate| !taken.contains(candidate))
+        .expect("unbounded counter");
+    taken.insert(name.clone());
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn abi(json: &str) -> JsonAbi {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_field_type_mapping() {
+        let ty = DynSolType::parse("(address,uint256[],bytes32)[2]").unwrap();
+        assert_eq!(
+            field_type(&ty, false),
+            FieldType::Array {
+                item: Box::new(FieldType::Tuple(vec![
+                    FieldType::Address,
+                    FieldType::Array {
+                        item: Box::new(FieldType::Integer {
+                            signed: false,
+                            bits: 256
+                        }),
+                        len: None
+                    },
+                    FieldType::Bytes { len: Some(32) },
+                ])),
+                len: Some(2),
+            }
+        );
+        assert_eq!(field_type(&DynSolType::String, true), FieldType::TopicHash);
+        assert_eq!(
+            rust_type(&field_type(&ty, false)),
+            "Vec<(String, Vec<String>, String)>"
+        );
+        assert_eq!(
+            rust_type(&FieldType::Tuple(vec![FieldType::Bool])),
+            "(bool,)"
+        );
+    }
+
+    #[test]
+    fn test_identifiers() {
+        assert_eq!(snake_case("tokenId"), "token_id");
+        assert_eq!(snake_case("_from"), "from");
+        assert_eq!(snake_case("ERC20Token"), "erc20_token");
+        assert_eq!(snake_case("type"), "type_");
+        assert_eq!(snake_case("$value"), "value");
+        assert_eq!(pascal_case("transfer_single"), "TransferSingle");
+        assert_eq!(pascal_case("Self"), "SelfEvent");
+    }
+
+    #[test]
+    fn test_event_defs_overloads_and_unnamed() {
+        let abi = abi(r#"[
+            {"type":"event","name":"Deposit","anonymous":false,"inputs":[
+                {"name":"user","type":"address","indexed":true},
+                {"name":"","type":"uint256","indexed":false},
+                {"name":"","type":"uint256","indexed":false}]},
+            {"type":"event","name":"Deposit","anonymous":false,"inputs":[
+                {"name":"user","type":"address","indexed":true}]},
+            {"type":"event","name":"Note","anonymous":true,"inputs":[]}
+        ]"#);
+        let defs = event_defs(&abi).unwrap();
+        assert_eq!(defs.len(), 3);
+
+        let names: Vec<&str> = defs.iter().map(|d| d.struct_name.as_str()).collect();
+        assert_eq!(names, ["Deposit", "Deposit2", "Note"]);
+
+        let first = &defs[0];
+        assert_eq!(first.signature, "Deposit(address)");
+        let second = &defs[1];
+        assert_eq!(second.fields.len(), 2);
+        assert_eq!(second.fields[1].key, "");
+        assert_eq!(second.fields[1].ident, "param1");
+        assert!(defs[2].topic0.is_none());
+
+        let module = rust_module(&defs, "test");
+        assert!(module.contains("#[serde(rename = \"\")]"));
+        assert!(module.contains("pub struct Note {}"));
+        assert!(!module.contains("\"Note\")"));
+    }
+
+    #[test]
+    fn test_json_schema() {
+        let abi = abi(r#"[
+            {"type":"event","name":"Swap","anonymous":false,"inputs":[
+                {"name":"sender","type":"address","indexed":true},
+                {"name":"amount0","type":"int256","indexed":false},
+                {"name":"path","type":"bytes4[3]","indexed":false},
+                {"name":"memo","type":"string","indexed":true}]}
+        ]"#);
+        let defs = event_defs(&abi).unwrap();
+        let schema = json_schema(&defs[0]);
+        let props = &schema["properties"];
+        assert_eq!(props["sender"]["pattern"], "^0x[0-9a-f]{40}$");
+        assert_eq!(props["amount0"]["pattern"], "^-?[0-9]+$");
+        assert_eq!(props["path"]["maxItems"], 3);
+        assert_eq!(props["path"]["items"]["pattern"], "^0x[0-9a-f]{8}$");
+        assert_eq!(props["memo"]["pattern"], "^0x[0-9a-f]{64}$");
+        assert_eq!(schema["required"].as_array().unwrap().len(), 4);
+        assert_eq!(
+            schema["x-topic0"].as_str().unwrap(),
+            format!("{:#x}", abi.events().next().unwrap().selector())
+        );
+    }
+}
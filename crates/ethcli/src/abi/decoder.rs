@@ -9,6 +9,30 @@ use alloy::rpc::types::Log;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Which event definition decoded a log
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DecoderSource {
+    /// A user-supplied ABI (`--abi`/`--abi-str`) or event signature (`-e`)
+    #[default]
+    Explicit,
+    /// The emitting contract's verified ABI, fetched from Etherscan
+    Fetched,
+    /// A signature database lookup (4byte.directory), parameter names unknown
+    Signature,
+}
+
+impl DecoderSource {
+    /// Lowercase name, as used in CSV/SQLite output
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DecoderSource::Explicit => "explicit",
+            DecoderSource::Fetched => "fetched",
+            DecoderSource::Signature => "signature",
+        }
+    }
+}
+
 /// A decoded log with named parameters
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DecodedLog {
@@ -33,6 +57,9 @@ pub struct DecodedLog {
     pub topics: Vec<B256>,
     /// Raw data (for reference)
     pub data: Vec<u8>,
+    /// Which event definition decoded this log
+    #[serde(default)]
+    pub decoder: DecoderSource,
 }
 
 /// A decoded parameter value
@@ -77,9 +104,18 @@ impl DecodedValue {
 }
 
 /// Log decoder using ABI or event signatures
+///
+/// Events are looked up by topic0 in three layers, tried in order: the
+/// explicit events (user ABI and signatures, applied to every address), the
+/// verified ABI of the emitting contract, and signature-database candidates.
+/// The first definition whose layout fits the log wins.
 pub struct LogDecoder {
     /// Events indexed by topic0
     events: HashMap<B256, EventInfo>,
+    /// Per-contract events from fetched ABIs
+    contracts: HashMap<Address, HashMap<B256, EventInfo>>,
+    /// Signature database candidates per topic0 (names and `indexed` unknown)
+    fallback: HashMap<B256, Vec<EventInfo>>,
 }
 
 /// Event info for decoding
@@ -98,6 +134,25 @@ struct EventInfo {
     indexed_explicit: bool,
 }
 
+impl EventInfo {
+    /// Whether the log's topic count and data match this event's layout
+    fn fits(&self, log: &Log) -> bool {
+        let indexed = log.topics().len().saturating_sub(1);
+        let data_params = if self.indexed_explicit {
+            if indexed != self.indexed_types.len() {
+                return false;
+            }
+            self.data_types.len()
+        } else {
+            if indexed > self.all_types.len() {
+                return false;
+            }
+            self.all_types.len() - indexed
+        };
+        data_params == 0 || !log.data().data.is_empty()
+    }
+}
+
 impl Default for LogDecoder {
     fn default() -> Self {
         Self::new()
@@ -109,6 +164,8 @@ impl LogDecoder {
     pub fn new() -> Self {
         Self {
             events: HashMap::new(),
+            contracts: HashMap::new(),
+            fallback: HashMap::new(),
         }
     }
 
@@ -122,7 +179,19 @@ impl LogDecoder {
             events.insert(event.selector(), info);
         }
 
-        Ok(Self { events })
+        Ok(Self {
+            events,
+            ..Self::new()
+        })
+    }
+
+    /// Add every event of an ABI to the decoder
+    pub fn add_abi(&mut self, abi: &JsonAbi) -> Result<()> {
+        for event in abi.events() {
+            let info = Self::event_to_info(event)?;
+            self.events.insert(event.selector(), info);
+        }
+        Ok(())
     }
 
     /// Create a decoder from event signatures
@@ -134,7 +203,10 @@ impl LogDecoder {
             events.insert(sig.topic, info);
         }
 
-        Ok(Self { events })
+        Ok(Self {
+            events,
+            ..Self::new()
+        })
     }
 
     /// Create a decoder for a single event signature
@@ -149,6 +221,64 @@ impl LogDecoder {
         Ok(())
     }
 
+    /// Add a contract's ABI, used only for logs emitted by `address`
+    pub fn add_contract_abi(&mut self, address: Address, abi: &JsonAbi) -> Result<()> {
+        let events = self.contracts.entry(address).or_default();
+        for event in abi.events() {
+            events.insert(event.selector(), Self::event_to_info(event)?);
+        }
+        Ok(())
+    }
+
+    /// Whether an ABI has been added for `address`
+    pub fn has_contract(&self, address: &Address) -> bool {
+        self.contracts.contains_key(address)
+    }
+
+    /// Add a signature-database candidate, tried after the explicit and contract events
+    pub fn add_fallback_signature(&mut self, signature: &EventSignature) -> Result<()> {
+        let info = Self::signature_to_info(signature)?;
+        let candidates = self.fallback.entry(signature.topic).or_default();
+        if !candidates.iter().any(|c| c.canonical == info.canonical) {
+            candidates.push(info);
+        }
+        Ok(())
+    }
+
+    /// Event definitions for a log's topic0, in priority order
+    fn candidates(&self, address: &Address, topic0: &B256) -> Vec<(&EventInfo, DecoderSource)> {
+        let mut candidates = Vec::new();
+        if let Some(info) = self.events.get(topic0) {
+            candidates.push((info, DecoderSource::Explicit));
+        }
+        if let Some(info) = self.contracts.get(address).and_then(|e| e.get(topic0)) {
+            candidates.push((info, DecoderSource::Fetched));
+        }
+        if let Some(infos) = self.fallback.get(topic0) {
+            candidates.extend(infos.iter().map(|info| (info, DecoderSource::Signature)));
+        }
+        candidates
+    }
+
+    /// Whether the explicit events can't decode `log` and no ABI has been
+    /// added for its address yet
+    pub fn needs_contract_abi(&self, log: &Log) -> bool {
+        let Some(topic0) = log.topics().first() else {
+            return false;
+        };
+        let explicit_fits = self.events.get(topic0).is_some_and(|info| info.fits(log));
+        !explicit_fits && !self.has_contract(&log.address())
+    }
+
+    /// Whether some known event definition fits the log's topic count and data
+    pub fn fits(&self, log: &Log) -> bool {
+        log.topics().first().is_some_and(|topic0| {
+            self.candidates(&log.address(), topic0)
+                .iter()
+                .any(|(info, _)| info.fits(log))
+        })
+    }
+
     /// Convert an ABI Event to EventInfo
     fn event_to_info(event: &Event) -> Result<EventInfo> {
         let mut indexed_types = Vec::new();
@@ -194,7 +324,9 @@ impl LogDecoder {
     }
 
     /// Resolve an EventParam type, handling tuples with components
-    fn resolve_event_param_type(param: &alloy::json_abi::EventParam) -> Result<DynSolType> {
+    pub(crate) fn resolve_event_param_type(
+        param: &alloy::json_abi::EventParam,
+    ) -> Result<DynSolType> {
         use alloy::json_abi::Param;
 
         // Helper to resolve Param types recursively
@@ -359,6 +491,10 @@ impl LogDecoder {
     }
 
     /// Decode a log
+    ///
+    /// Tries each definition for the log's topic0 in priority order and uses
+    /// the first whose layout fits. If none fits, the highest-priority
+    /// definition decodes it as best it can.
     pub fn decode(&self, log: &Log) -> Result<DecodedLog> {
         // Get topic0 (event selector)
         let topic0 = log
@@ -366,12 +502,25 @@ impl LogDecoder {
             .first()
             .ok_or_else(|| AbiError::DecodeError("Log has no topics".to_string()))?;
 
-        // Find event info
-        let event_info = self
-            .events
-            .get(topic0)
-            .ok_or_else(|| AbiError::EventNotFound(format!("Unknown event: {:#x}", topic0)))?;
+        let candidates = self.candidates(&log.address(), topic0);
+        let Some(&(first, first_source)) = candidates.first() else {
+            return Err(AbiError::EventNotFound(format!("Unknown event: {:#x}", topic0)).into());
+        };
+
+        for &(info, source) in candidates.iter().filter(|(info, _)| info.fits(log)) {
+            match Self::decode_with(info, source, log) {
+                Ok(decoded) => return Ok(decoded),
+                Err(e) => {
+                    tracing::trace!("{} ({:?}) did not decode: {}", info.canonical, source, e)
+                }
+            }
+        }
+
+        Self::decode_with(first, first_source, log)
+    }
 
+    /// Decode a log with one event definition
+    fn decode_with(event_info: &EventInfo, source: DecoderSource, log: &Log) -> Result<DecodedLog> {
         // Number of indexed params (topics excluding topic0)
         let indexed_topics: Vec<_> = log.topics().iter().skip(1).collect();
         let actual_indexed_count = indexed_topics.len();
@@ -434,7 +583,7 @@ impl LogDecoder {
             } else {
                 let decoded = Self::decode_data(&data_types, &log.data().data)?;
 
-                for (name, value) in data_names.iter().zip(decoded.into_iter()) {
+                for (name, value) in data_names.iter().zip(decoded) {
                     params.insert(name.clone(), value);
                 }
             }
@@ -451,6 +600,7 @@ impl LogDecoder {
             params,
             topics: log.topics().to_vec(),
             data: log.data().data.to_vec(),
+            decoder: source,
         })
     }
 
@@ -474,9 +624,11 @@ impl LogDecoder {
 
     /// Decode non-indexed parameters from data
     fn decode_data(types: &[DynSolType], data: &[u8]) -> Result<Vec<DecodedValue>> {
+        // Event data is a parameter sequence, without the leading offset a
+        // single dynamic tuple value would have
         let tuple_type = DynSolType::Tuple(types.to_vec());
         let decoded = tuple_type
-            .abi_decode(data)
+            .abi_decode_sequence(data)
             .map_err(|e| AbiError::DecodeError(format!("Failed to decode data: {}", e)))?;
 
         match decoded {
@@ -492,7 +644,7 @@ impl LogDecoder {
     pub fn can_decode(&self, log: &Log) -> bool {
         log.topics()
             .first()
-            .map(|t| self.events.contains_key(t))
+            .map(|t| !self.candidates(&log.address(), t).is_empty())
             .unwrap_or(false)
     }
 
@@ -518,6 +670,242 @@ mod tests {
         assert_eq!(decoder.event_names(), vec!["Transfer"]);
     }
 
+    #[test]
+    fn test_decoder_human_abi_with_signatures() {
+        use alloy::primitives::{Bytes, LogData, U256};
+
+        let abi = crate::abi::parse_human_abi(
+            "event Filled(address indexed maker, (address token, uint256 amount) fill)",
+        )
+        .unwrap();
+        let mut decoder = LogDecoder::from_abi(&abi).unwrap();
+        decoder
+            .add_signature(&EventSignature::parse("Transfer(address,address,uint256)").unwrap())
+            .unwrap();
+
+        let mut names = decoder.event_names();
+        names.sort();
+        assert_eq!(names, vec!["Filled", "Transfer"]);
+
+        let maker = Address::repeat_byte(0x11);
+        let token = Address::repeat_byte(0x22);
+        let event = abi.events().next().unwrap();
+        let mut data = token.into_word().to_vec();
+        data.extend_from_slice(&U256::from(42u64).to_be_bytes::<32>());
+        let log = Log {
+            inner: alloy::primitives::Log {
+                address: Address::ZERO,
+                data: LogData::new_unchecked(
+                    vec![event.selector(), maker.into_word()],
+                    Bytes::from(data),
+                ),
+            },
+            ..Default::default()
+        };
+
+        let decoded = decoder.decode(&log).unwrap();
+        assert_eq!(decoded.event_name, "Filled");
+        assert_eq!(decoded.event_signature, "Filled(address,(address,uint256))");
+        match &decoded.params["fill"] {
+            DecodedValue::Tuple(values) => {
+                assert!(matches!(&values[1], DecodedValue::Uint(v) if v == "42"));
+            }
+            other => panic!("expected tuple, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_decode_dynamic_data() {
+        use alloy::primitives::{Bytes, LogData};
+
+        let abi = crate::abi::parse_human_abi(
+            "event Memo(address indexed from, string note, uint256 amount)",
+        )
+        .unwrap();
+        let decoder = LogDecoder::from_abi(&abi).unwrap();
+        let data = DynSolValue::Tuple(vec![
+            DynSolValue::String("gm".to_string()),
+            DynSolValue::Uint(alloy::primitives::U256::from(7u64), 256),
+        ])
+        .abi_encode_params();
+        let log = Log {
+            inner: alloy::primitives::Log {
+                address: Address::ZERO,
+                data: LogData::new_unchecked(
+                    vec![
+                        abi.events().next().unwrap().selector(),
+                        Address::repeat_byte(0x11).into_word(),
+                    ],
+                    Bytes::from(data),
+                ),
+            },
+            ..Default::default()
+        };
+
+        let decoded = decoder.decode(&log).unwrap();
+        assert!(matches!(&decoded.params["note"], DecodedValue::String(s) if s == "gm"));
+        assert!(matches!(&decoded.params["amount"], DecodedValue::Uint(v) if v == "7"));
+    }
+
+    fn log_at(address: Address, topics: Vec<B256>, data: Vec<u8>) -> Log {
+        use alloy::primitives::{Bytes, LogData};
+
+        Log {
+            inner: alloy::primitives::Log {
+                address,
+                data: LogData::new_unchecked(topics, Bytes::from(data)),
+            },
+            ..Default::default()
+        }
+    }
+
+    fn uint_word(value: u64) -> Vec<u8> {
+        alloy::primitives::U256::from(value)
+            .to_be_bytes::<32>()
+            .to_vec()
+    }
+
+    /// Two contracts emitting `Transfer` events that share topic0 but not layout
+    fn token_and_nft() -> (Address, JsonAbi, Address, JsonAbi) {
+        let token_abi = crate::abi::parse_human_abi(
+            "event Transfer(address indexed from, address indexed to, uint256 value)\n\
+             event Deposit(address indexed user, uint256 amount)",
+        )
+        .unwrap();
+        let nft_abi = crate::abi::parse_human_abi(
+            "event Transfer(address indexed owner, address indexed recipient, uint256 indexed tokenId)\n\
+             event Deposit(address indexed user, uint256 indexed poolId, uint256 amount, uint256 shares)",
+        )
+        .unwrap();
+        (
+            Address::repeat_byte(0xaa),
+            token_abi,
+            Address::repeat_byte(0xbb),
+            nft_abi,
+        )
+    }
+
+    fn selector(abi: &JsonAbi, name: &str) -> B256 {
+        abi.events().find(|e| e.name == name).unwrap().selector()
+    }
+
+    #[test]
+    fn test_routes_shared_event_name_by_address() {
+        let (token, token_abi, nft, nft_abi) = token_and_nft();
+        let mut decoder = LogDecoder::new();
+        decoder.add_contract_abi(token, &token_abi).unwrap();
+        decoder.add_contract_abi(nft, &nft_abi).unwrap();
+
+        let transfer = selector(&token_abi, "Transfer");
+        assert_eq!(transfer, selector(&nft_abi, "Transfer"));
+        let from = Address::repeat_byte(0x11).into_word();
+        let to = Address::repeat_byte(0x22).into_word();
+
+        let decoded = decoder
+            .decode(&log_at(token, vec![transfer, from, to], uint_word(100)))
+            .unwrap();
+        assert_eq!(decoded.decoder, DecoderSource::Fetched);
+        assert!(matches!(&decoded.params["value"], DecodedValue::Uint(v) if v == "100"));
+        assert!(decoded.params.contains_key("from"));
+
+        let token_id = B256::from(alloy::primitives::U256::from(7u64));
+        let decoded = decoder
+            .decode(&log_at(nft, vec![transfer, from, to, token_id], vec![]))
+            .unwrap();
+        assert!(matches!(&decoded.params["tokenId"], DecodedValue::Uint(v) if v == "7"));
+        assert!(decoded.params.contains_key("owner"));
+        assert!(!decoded.params.contains_key("value"));
+
+        // Same event name, different parameters: each address uses its own ABI
+        let mut data = uint_word(5);
+        data.extend(uint_word(4));
+        let decoded = decoder
+            .decode(&log_at(
+                nft,
+                vec![selector(&nft_abi, "Deposit"), from, token_id],
+                data,
+            ))
+            .unwrap();
+        assert_eq!(
+            decoded.event_signature,
+            "Deposit(address,uint256,uint256,uint256)"
+        );
+        assert!(matches!(&decoded.params["shares"], DecodedValue::Uint(v) if v == "4"));
+
+        let token_deposit = log_at(
+            token,
+            vec![selector(&token_abi, "Deposit"), from],
+            uint_word(9),
+        );
+        assert_eq!(
+            decoder.decode(&token_deposit).unwrap().event_signature,
+            "Deposit(address,uint256)"
+        );
+
+        // Events are not shared across addresses
+        let elsewhere = log_at(
+            Address::repeat_byte(0xcc),
+            vec![selector(&token_abi, "Deposit"), from],
+            uint_word(9),
+        );
+        assert!(!decoder.can_decode(&elsewhere));
+        assert!(decoder.decode(&elsewhere).is_err());
+    }
+
+    #[test]
+    fn test_explicit_abi_falls_through_to_contract_abi() {
+        let (_, token_abi, nft, nft_abi) = token_and_nft();
+        let mut decoder = LogDecoder::from_abi(&token_abi).unwrap();
+
+        let transfer = selector(&token_abi, "Transfer");
+        let from = Address::repeat_byte(0x11).into_word();
+        let to = Address::repeat_byte(0x22).into_word();
+        let token_id = B256::from(alloy::primitives::U256::from(7u64));
+        let nft_transfer = log_at(nft, vec![transfer, from, to, token_id], vec![]);
+
+        assert!(decoder.needs_contract_abi(&nft_transfer));
+        assert!(!decoder.fits(&nft_transfer));
+        decoder.add_contract_abi(nft, &nft_abi).unwrap();
+        assert!(!decoder.needs_contract_abi(&nft_transfer));
+        assert!(decoder.fits(&nft_transfer));
+
+        // The explicit ABI wins whenever its layout fits
+        let erc20_transfer = log_at(nft, vec![transfer, from, to], uint_word(100));
+        let decoded = decoder.decode(&erc20_transfer).unwrap();
+        assert_eq!(decoded.decoder, DecoderSource::Explicit);
+        assert!(decoded.params.contains_key("value"));
+
+        let decoded = decoder.decode(&nft_transfer).unwrap();
+        assert_eq!(decoded.decoder, DecoderSource::Fetched);
+        assert!(decoded.params.contains_key("tokenId"));
+    }
+
+    #[test]
+    fn test_fallback_signature_decode() {
+        let mut decoder = LogDecoder::new();
+        let sig = EventSignature::parse("Transfer(address,address,uint256)").unwrap();
+        let from = Address::repeat_byte(0x11).into_word();
+        let to = Address::repeat_byte(0x22).into_word();
+        let log = log_at(
+            Address::repeat_byte(0xcc),
+            vec![sig.topic, from, to],
+            uint_word(3),
+        );
+
+        assert!(decoder.needs_contract_abi(&log));
+        assert!(!decoder.can_decode(&log));
+        decoder.add_fallback_signature(&sig).unwrap();
+        decoder.add_fallback_signature(&sig).unwrap();
+
+        let decoded = decoder.decode(&log).unwrap();
+        assert_eq!(decoded.decoder, DecoderSource::Signature);
+        assert_eq!(decoded.event_name, "Transfer");
+        assert!(matches!(&decoded.params["param2"], DecodedValue::Uint(v) if v == "3"));
+
+        let json = serde_json::to_value(&decoded).unwrap();
+        assert_eq!(json["decoder"], "signature");
+    }
+
     #[test]
     fn test_decoded_value_json() {
         let value = DecodedValue::Address("0x1234567890123456789012345678901234567890".to_string());
@@ -2,6 +2,7 @@
 //!
 //! Uses the unified Etherscan API v2 endpoint which works for all supported chains.
 
+use crate::abi::parse_abi_source;
 use crate::config::Chain;
 use crate::error::{AbiError, Result};
 use crate::etherscan::SignatureCache;
@@ -92,6 +93,16 @@ impl AbiFetcher {
         })
     }
 
+    /// ABI for a contract from the on-disk cache, without a network request
+    ///
+    /// A corrupted cache entry is treated as a miss.
+    pub fn cached_abi(&self, chain: Chain, address: &str) -> Option<JsonAbi> {
+        let chain_id = chain.chain_id();
+        let (cached_abi, _) = self.cache.get_abi(chain_id, address)?;
+        tracing::debug!("Using cached ABI for {} on chain {}", address, chain_id);
+        serde_json::from_str(&cached_abi).ok()
+    }
+
     /// Fetch ABI from Etherscan API v2
     ///
     /// Works without an API key (rate limited to ~5 calls/sec)
@@ -102,12 +113,8 @@ impl AbiFetcher {
         let chain_id = chain.chain_id();
 
         // Check cache first
-        if let Some((cached_abi, _)) = self.cache.get_abi(chain_id, address) {
-            tracing::debug!("Using cached ABI for {} on chain {}", address, chain_id);
-            if let Ok(abi) = serde_json::from_str(&cached_abi) {
-                return Ok(abi);
-            }
-            // If cache is corrupted, fall through to fetch
+        if let Some(abi) = self.cached_abi(chain, address) {
+            return Ok(abi);
         }
 
         // URL-encode the address to prevent parameter injection
@@ -185,6 +192,9 @@ impl AbiFetcher {
     }
 
     /// Load ABI from a local file
+    ///
+    /// Accepts a JSON ABI, a compiler artifact with an `abi` field, or a
+    /// human-readable ABI with one signature per line.
     pub fn load_from_file(&self, path: &Path) -> Result<JsonAbi> {
         if !path.exists() {
             return Err(AbiError::FileNotFound(path.display().to_string()).into());
@@ -193,11 +203,28 @@ impl AbiFetcher {
         let content = std::fs::read_to_string(path)
             .map_err(|e| AbiError::FileNotFound(format!("{}: {}", path.display(), e)))?;
 
-        // Try to parse as JSON ABI
-        let abi: JsonAbi = serde_json::from_str(&content)
-            .map_err(|e| AbiError::ParseError(format!("Invalid ABI JSON: {}", e)))?;
+        parse_abi_source(&content).map_err(|e| match e {
+            crate::error::Error::Abi(AbiError::ParseError(msg)) => {
+                AbiError::ParseError(format!("{}: {}", path.display(), msg)).into()
+            }
+            e => e,
+        })
+    }
 
-        Ok(abi)
+    /// Load a local ABI from a file and/or inline source, merging both
+    ///
+    /// Returns `None` when neither is given. The inline source may be JSON
+    /// or human-readable, like [`load_from_file`](Self::load_from_file).
+    pub fn load_local(&self, path: Option<&Path>, inline: Option<&str>) -> Result<Option<JsonAbi>> {
+        let file = path.map(|p| self.load_from_file(p)).transpose()?;
+        let inline = inline.map(parse_abi_source).transpose()?;
+
+        Ok(match (file, inline) {
+            (Some(file), Some(inline)) => {
+                Some(file.into_items().chain(inline.into_items()).collect())
+            }
+            (file, inline) => file.or(inline),
+        })
     }
 
     /// Get events from an ABI
@@ -218,9 +245,11 @@ impl AbiFetcher {
 
     /// Get the full event signature string from an ABI event
     /// e.g., "Transfer(address,address,uint256)"
+    ///
+    /// Tuple parameters are expanded to their component types so the
+    /// signature hashes to the event topic.
     pub fn event_signature_string(event: &alloy::json_abi::Event) -> String {
-        let param_types: Vec<String> = event.inputs.iter().map(|p| p.ty.to_string()).collect();
-        format!("{}({})", event.name, param_types.join(","))
+        event.signature()
     }
 
     /// Resolve an event name to its full signature using the contract ABI
@@ -736,7 +765,7 @@ impl DecodedFunction {
 }
 
 /// Decode function parameters using the function definition
-fn decode_function_params(
+pub(crate) fn decode_function_params(
     func: &alloy::json_abi::Function,
     data: &[u8],
 ) -> Vec<(String, String, String)> {
@@ -0,0 +1,809 @@
+//! Human-readable ABI parsing
+//!
+//! Parses foundry/ethers-style ABI files with one signature per line:
+//!
+//! ```text
+//! // ERC20 subset
+//! event Transfer(address indexed from, address indexed to, uint256 value)
+//! function transfer(address,uint256) external returns (bool)
+//! error InsufficientBalance(uint256 available, uint256 required)
+//! ```
+//!
+//! Blank lines, `//` and `#` comments and trailing `;` are ignored. Tuples
+//! (`(uint256 a, address b)` or `tuple(...)`), fixed and dynamic arrays,
+//! `indexed` and named or unnamed params are supported. Errors name the
+//! offending line and column.
+
+use crate::error::{AbiError, Result};
+use alloy::json_abi::{
+    Constructor, Error as AbiErrorItem, Event, EventParam, Fallback, Function, JsonAbi, Param,
+    Receive, StateMutability,
+};
+
+/// Parse a human-readable ABI (one signature per line) into a [`JsonAbi`]
+pub fn parse_human_abi(source: &str) -> Result<JsonAbi> {
+    let mut abi = JsonAbi::new();
+
+    for (idx, raw) in source.lines().enumerate() {
+        let line_no = idx + 1;
+        let without_comment = strip_comment(raw);
+        let line = without_comment.trim_end().trim_end_matches(';').trim_end();
+        let indent = line.len() - line.trim_start().len();
+        let line = line.trim_start();
+        if line.is_empty() {
+            continue;
+        }
+
+        let item = LineParser::new(line).parse_item().map_err(|e| {
+            AbiError::ParseError(format!(
+                "line {}, column {}: {}\n  {} | {}\n  {} | {}^",
+                line_no,
+                indent + e.pos + 1,
+                e.message,
+                line_no,
+                line,
+                " ".repeat(line_no.to_string().len()),
+                " ".repeat(e.pos)
+            ))
+        })?;
+
+        match item {
+            Item::Event(event) => abi
+                .events
+                .entry(event.name.clone())
+                .or_default()
+                .push(event),
+            Item::Function(function) => abi
+                .functions
+                .entry(function.name.clone())
+                .or_default()
+                .push(function),
+            Item::Error(error) => abi
+                .errors
+                .entry(error.name.clone())
+                .or_default()
+                .push(error),
+            Item::Constructor(constructor) => abi.constructor = Some(constructor),
+            Item::Fallback(fallback) => abi.fallback = Some(fallback),
+            Item::Receive(receive) => abi.receive = Some(receive),
+        }
+    }
+
+    if abi.is_empty() {
+        return Err(
+            AbiError::ParseError("Human-readable ABI contains no signatures".to_string()).into(),
+        );
+    }
+
+    Ok(abi)
+}
+
+/// Parse ABI text that is either a JSON ABI or a human-readable ABI
+///
+/// JSON is detected by a leading `[` or `{`; a JSON object is accepted when it
+/// is a compiler artifact with an `abi` field.
+pub fn parse_abi_source(source: &str) -> Result<JsonAbi> {
+    let trimmed = source.trim_start();
+    if trimmed.starts_with('[') {
+        return serde_json::from_str(trimmed)
+            .map_err(|e| AbiError::ParseError(format!("Invalid ABI JSON: {}", e)).into());
+    }
+    if trimmed.starts_with('{') {
+        let value: serde_json::Value = serde_json::from_str(trimmed)
+            .map_err(|e| AbiError::ParseError(format!("Invalid ABI JSON: {}", e)))?;
+        let abi = value
+            .get("abi")
+            .cloned()
+            .ok_or_else(|| AbiError::ParseError("JSON object has no `abi` field".to_string()))?;
+        return serde_json::from_value(abi)
+            .map_err(|e| AbiError::ParseError(format!("Invalid ABI JSON: {}", e)).into());
+    }
+    parse_human_abi(source)
+}
+
+/// Strip a trailing `//` or `#` comment from a line
+fn strip_comment(line: &str) -> &str {
+    let end = [line.find("//"), line.find('#')]
+        .into_iter()
+        .flatten()
+        .min()
+        .unwrap_or(line.len());
+    &line[..end]
+}
+
+/// A parsed ABI item
+enum Item {
+    Event(Event),
+    Function(Function),
+    Error(AbiErrorItem),
+    Constructor(Constructor),
+    Fallback(Fallback),
+    Receive(Receive),
+}
+
+/// A parameter before it is specialised into an alloy `Param` or `EventParam`
+struct RawParam {
+    ty: String,
+    name: String,
+    indexed: bool,
+    components: Vec<Param>,
+}
+
+impl RawParam {
+    fn into_param(self) -> Param {
+        Param {
+            ty: self.ty,
+            name: self.name,
+            components: self.components,
+            internal_type: None,
+        }
+    }
+
+    fn into_event_param(self) -> EventParam {
+        EventParam {
+            ty: self.ty,
+            name: self.name,
+            indexed: self.indexed,
+            components: self.components,
+            internal_type: None,
+        }
+    }
+}
+
+/// Parse failure at a byte offset within the line
+struct ParseError {
+    pos: usize,
+    message: String,
+}
+
+/// Recursive-descent parser over a single signature line
+struct LineParser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> LineParser<'a> {
+    fn new(src: &'a str) -> Self {
+        Self { src, pos: 0 }
+    }
+
+    fn parse_item(&mut self) -> std::result::Result<Item, ParseError> {
+        let start = self.pos;
+        let keyword = self.ident().unwrap_or_default();
+        let item = match keyword {
+            "event" => {
+                let name = self.expect_ident("event name")?;
+                let inputs = self.param_list(true)?;
+                let anonymous = self.modifiers(&["anonymous"])?.contains(&"anonymous");
+                Item::Event(Event {
+                    name: name.to_string(),
+                    inputs: inputs.into_iter().map(RawParam::into_event_param).collect(),
+                    anonymous,
+                })
+            }
+            "function" => {
+                let name = self.expect_ident("function name")?;
+                let inputs = self.param_list(false)?;
+                let modifiers = self.modifiers(FUNCTION_MODIFIERS)?;
+                let outputs = if modifiers.contains(&"returns") {
+                    self.param_list(false)?
+                } else {
+                    Vec::new()
+                };
+                Item::Function(Function {
+                    name: name.to_string(),
+                    inputs: inputs.into_iter().map(RawParam::into_param).collect(),
+                    outputs: outputs.into_iter().map(RawParam::into_param).collect(),
+                    state_mutability: state_mutability(&modifiers),
+                })
+            }
+            "error" => {
+                let name = self.expect_ident("error name")?;
+                let inputs = self.param_list(false)?;
+                Item::Error(AbiErrorItem {
+                    name: name.to_string(),
+                    inputs: inputs.into_iter().map(RawParam::into_param).collect(),
+                })
+            }
+            "constructor" => {
+                let inputs = self.param_list(false)?;
+                let modifiers = self.modifiers(&["public", "payable"])?;
+                Item::Constructor(Constructor {
+                    inputs: inputs.into_iter().map(RawParam::into_param).collect(),
+                    state_mutability: state_mutability(&modifiers),
+                })
+            }
+            "receive" => {
+                self.empty_params()?;
+                self.modifiers(&["external", "payable", "virtual", "override"])?;
+                Item::Receive(Receive {
+                    state_mutability: StateMutability::Payable,
+                })
+            }
+            "fallback" => {
+                self.empty_params()?;
+                let modifiers = self.modifiers(&["external", "payable", "virtual", "override"])?;
+                Item::Fallback(Fallback {
+                    state_mutability: state_mutability(&modifiers),
+                })
+            }
+            _ => {
+                return Err(ParseError {
+                    pos: start,
+                    message: "expected `event`, `function`, `error`, `constructor`, \
+                              `receive` or `fallback`"
+                        .to_string(),
+                })
+            }
+        };
+
+        self.skip_ws();
+        if self.pos < self.src.len() {
+            return Err(self.error(format!("unexpected `{}`", &self.src[self.pos..])));
+        }
+        Ok(item)
+    }
+
+    /// Parse `(param, param, ...)`
+    fn param_list(
+        &mut self,
+        allow_indexed: bool,
+    ) -> std::result::Result<Vec<RawParam>, ParseError> {
+        self.expect('(')?;
+        let mut params = Vec::new();
+        if self.eat(')') {
+            return Ok(params);
+        }
+        loop {
+            params.push(self.param(allow_indexed)?);
+            if self.eat(',') {
+                continue;
+            }
+            self.expect(')')?;
+            return Ok(params);
+        }
+    }
+
+    /// Parse `type [indexed] [location] [name]`
+    fn param(&mut self, allow_indexed: bool) -> std::result::Result<RawParam, ParseError> {
+        let (ty, components) = self.ty()?;
+        let mut param = RawParam {
+            ty,
+            name: String::new(),
+            indexed: false,
+            components,
+        };
+
+        loop {
+            let start = self.pos;
+            let Some(word) = self.ident() else {
+                break;
+            };
+            match word {
+                "indexed" if param.name.is_empty() && !param.indexed => {
+                    if !allow_indexed {
+                        self.pos = start;
+                        return Err(self.error(
+                            "`indexed` is only allowed on top-level event parameters".to_string(),
+                        ));
+                    }
+                    param.indexed = true;
+                }
+                "memory" | "calldata" | "storage" if param.name.is_empty() => {}
+                "payable" if param.ty == "address" && param.name.is_empty() => {}
+                _ if param.name.is_empty() => param.name = word.to_string(),
+                _ => {
+                    self.pos = start;
+                    return Err(self.error(format!("unexpected `{}` after parameter name", word)));
+                }
+            }
+        }
+
+        Ok(param)
+    }
+
+    /// Parse a type with optional array suffixes, returning `(ty, components)`
+    fn ty(&mut self) -> std::result::Result<(String, Vec<Param>), ParseError> {
+        self.skip_ws();
+        let start = self.pos;
+        let (mut ty, components) = if self.peek() == Some('(') {
+            let params = self.param_list(false)?;
+            (
+                "tuple".to_string(),
+                params.into_iter().map(RawParam::into_param).collect(),
+            )
+        } else {
+            let Some(word) = self.ident() else {
+                return Err(self.error("expected a type".to_string()));
+            };
+            if word == "tuple" && self.peek_after_ws() == Some('(') {
+                let params = self.param_list(false)?;
+                (
+                    "tuple".to_string(),
+                    params.into_iter().map(RawParam::into_param).collect(),
+                )
+            } else {
+                let ty = elementary_type(word).ok_or_else(|| ParseError {
+                    pos: start,
+                    message: format!("unknown type `{}`", word),
+                })?;
+                (ty, Vec::new())
+            }
+        };
+
+        while self.eat('[') {
+            self.skip_ws();
+            let size_start = self.pos;
+            while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                self.pos += 1;
+            }
+            let size = &self.src[size_start..self.pos];
+            if size.starts_with('0') {
+                return Err(ParseError {
+                    pos: size_start,
+                    message: format!("invalid array size `{}`", size),
+                });
+            }
+            self.expect(']')?;
+            ty.push('[');
+            ty.push_str(size);
+            ty.push(']');
+        }
+
+        Ok((ty, components))
+    }
+
+    /// Parse `()`
+    fn empty_params(&mut self) -> std::result::Result<(), ParseError> {
+        self.expect('(')?;
+        self.expect(')')
+    }
+
+    /// Consume trailing modifier keywords, stopping after `returns`
+    fn modifiers(
+        &mut self,
+        allowed: &[&'static str],
+    ) -> std::result::Result<Vec<&'static str>, ParseError> {
+        let mut found = Vec::new();
+        loop {
+            let start = self.pos;
+            let Some(word) = self.ident() else {
+                return Ok(found);
+            };
+            match allowed.iter().find(|m| **m == word) {
+                Some(&"returns") => {
+                    found.push("returns");
+                    return Ok(found);
+                }
+                Some(m) => found.push(*m),
+                None => {
+                    self.pos = start;
+                    return Err(self.error(format!("unexpected `{}`", word)));
+                }
+            }
+        }
+    }
+
+    fn ident(&mut self) -> Option<&'a str> {
+        self.skip_ws();
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+        {
+            self.pos += 1;
+        }
+        (self.pos > start).then(|| &self.src[start..self.pos])
+    }
+
+    fn expect_ident(&mut self, what: &str) -> std::result::Result<&'a str, ParseError> {
+        self.ident()
+            .ok_or_else(|| self.error(format!("expected {}", what)))
+    }
+
+    fn expect(&mut self, c: char) -> std::result::Result<(), ParseError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected `{}`", c)))
+        }
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_ws();
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    fn peek_after_ws(&self) -> Option<char> {
+        self.src[self.pos..].trim_start().chars().next()
+    }
+
+    fn skip_ws(&mut self) {
+        while let Some(c) = self.peek().filter(|c| c.is_whitespace()) {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn error(&mut self, message: String) -> ParseError {
+        self.skip_ws();
+        ParseError {
+            pos: self.pos,
+            message,
+        }
+    }
+}
+
+const FUNCTION_MODIFIERS: &[&str] = &[
+    "external",
+    "public",
+    "internal",
+    "private",
+    "view",
+    "pure",
+    "payable",
+    "nonpayable",
+    "virtual",
+    "override",
+    "returns",
+];
+
+fn state_mutability(modifiers: &[&str]) -> StateMutability {
+    if modifiers.contains(&"pure") {
+        StateMutability::Pure
+    } else if modifiers.contains(&"view") {
+        StateMutability::View
+    } else if modifiers.contains(&"payable") {
+        StateMutability::Payable
+    } else {
+        StateMutability::NonPayable
+    }
+}
+
+/// Validate an elementary Solidity type, expanding `uint`/`int` aliases
+fn elementary_type(word: &str) -> Option<String> {
+    match word {
+        "address" | "bool" | "string" | "bytes" | "function" => return Some(word.to_string()),
+        "uint" => return Some("uint256".to_string()),
+        "int" => return Some("int256".to_string()),
+        _ => {}
+    }
+
+    let sized = |prefix: &str, valid: fn(u32) -> bool| {
+        word.strip_prefix(prefix)
+            .filter(|n| !n.starts_with('0'))
+            .and_then(|n| n.parse::<u32>().ok())
+            .is_some_and(valid)
+    };
+    let valid = sized("bytes", |n| (1..=32).contains(&n))
+        || sized("uint", |n| n % 8 == 0 && (8..=256).contains(&n))
+        || sized("int", |n| n % 8 == 0 && (8..=256).contains(&n));
+    valid.then(|| word.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::keccak256;
+
+    fn parse_err(source: &str) -> String {
+        parse_human_abi(source).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_event_with_indexed_and_names() {
+        let abi = parse_human_abi(
+            "event Transfer(address indexed from, address indexed to, uint256 value)",
+        )
+        .unwrap();
+        let event = &abi.events["Transfer"][0];
+
+        assert_eq!(event.inputs.len(), 3);
+        assert!(event.inputs[0].indexed);
+        assert!(event.inputs[1].indexed);
+        assert!(!event.inputs[2].indexed);
+        assert_eq!(event.inputs[0].name, "from");
+        assert_eq!(event.inputs[2].name, "value");
+        assert_eq!(
+            format!("{:#x}", event.selector()),
+            "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+        );
+    }
+
+    #[test]
+    fn test_event_unnamed_params() {
+        let abi =
+            parse_human_abi("event Approval(address indexed, address indexed, uint256)").unwrap();
+        let event = &abi.events["Approval"][0];
+
+        assert!(event.inputs.iter().all(|p| p.name.is_empty()));
+        assert!(event.inputs[0].indexed);
+        assert!(!event.inputs[2].indexed);
+        assert_eq!(event.signature(), "Approval(address,address,uint256)");
+    }
+
+    #[test]
+    fn test_unicode_whitespace() {
+        // NBSP and EM SPACE are multi-byte in UTF-8
+        let abi = parse_human_abi(
+            "event\u{a0}Transfer(address\u{2003}indexed from,\u{a0}address indexed to, uint256 value)",
+        )
+        .unwrap();
+        let event = &abi.events["Transfer"][0];
+        assert_eq!(event.inputs[0].name, "from");
+        assert_eq!(event.signature(), "Transfer(address,address,uint256)");
+
+        let err = parse_err("event Broken(address\u{a0}indexed\u{2003}");
+        assert!(err.contains("expected `)`"), "{err}");
+    }
+
+    #[test]
+    fn test_event_no_params_and_anonymous() {
+        let abi = parse_human_abi("event Paused()\nevent Ping(uint256 id) anonymous").unwrap();
+
+        assert!(abi.events["Paused"][0].inputs.is_empty());
+        assert!(!abi.events["Paused"][0].anonymous);
+        assert!(abi.events["Ping"][0].anonymous);
+    }
+
+    #[test]
+    fn test_function_unnamed_params() {
+        let abi = parse_human_abi("function transfer(address,uint256)").unwrap();
+        let function = &abi.functions["transfer"][0];
+
+        assert_eq!(function.inputs.len(), 2);
+        assert_eq!(function.selector().to_string(), "0xa9059cbb");
+        assert_eq!(function.state_mutability, StateMutability::NonPayable);
+    }
+
+    #[test]
+    fn test_function_modifiers_and_returns() {
+        let abi = parse_human_abi(
+            "function balanceOf(address owner) external view returns (uint256 balance)",
+        )
+        .unwrap();
+        let function = &abi.functions["balanceOf"][0];
+
+        assert_eq!(function.inputs[0].name, "owner");
+        assert_eq!(function.outputs.len(), 1);
+        assert_eq!(function.outputs[0].name, "balance");
+        assert_eq!(function.state_mutability, StateMutability::View);
+    }
+
+    #[test]
+    fn test_function_data_locations() {
+        let abi = parse_human_abi(
+            "function multicall(bytes[] calldata data) external payable returns (bytes[] memory results)",
+        )
+        .unwrap();
+        let function = &abi.functions["multicall"][0];
+
+        assert_eq!(function.inputs[0].name, "data");
+        assert_eq!(function.outputs[0].name, "results");
+        assert_eq!(function.state_mutability, StateMutability::Payable);
+        assert_eq!(function.selector().to_string(), "0xac9650d8");
+    }
+
+    #[test]
+    fn test_int_aliases_and_address_payable() {
+        let abi = parse_human_abi("function f(uint a, int b, address payable to)").unwrap();
+        assert_eq!(
+            abi.functions["f"][0].signature(),
+            "f(uint256,int256,address)"
+        );
+    }
+
+    #[test]
+    fn test_tuple_params() {
+        let abi = parse_human_abi(
+            "event Swapped((address tokenIn, uint256 amountIn) indexed order, (address,uint256)[] fills)",
+        )
+        .unwrap();
+        let event = &abi.events["Swapped"][0];
+
+        assert_eq!(event.inputs[0].ty, "tuple");
+        assert_eq!(event.inputs[0].components.len(), 2);
+        assert_eq!(event.inputs[0].components[0].name, "tokenIn");
+        assert_eq!(event.inputs[0].name, "order");
+        assert!(event.inputs[0].indexed);
+        assert_eq!(event.inputs[1].ty, "tuple[]");
+        assert!(event.inputs[1].components[1].name.is_empty());
+        assert_eq!(
+            event.signature(),
+            "Swapped((address,uint256),(address,uint256)[])"
+        );
+    }
+
+    #[test]
+    fn test_tuple_keyword() {
+        let abi =
+            parse_human_abi("function fill(tuple(address maker, uint256 amount) order)").unwrap();
+        let function = &abi.functions["fill"][0];
+
+        assert_eq!(function.inputs[0].ty, "tuple");
+        assert_eq!(function.inputs[0].components[0].name, "maker");
+        assert_eq!(function.signature(), "fill((address,uint256))");
+    }
+
+    #[test]
+    fn test_nested_tuples_and_arrays() {
+        let abi = parse_human_abi(
+            "function batch(((address,uint256)[2] legs, bytes data)[] calls, uint8[][] matrix)",
+        )
+        .unwrap();
+        let function = &abi.functions["batch"][0];
+        let signature = "batch(((address,uint256)[2],bytes)[],uint8[][])";
+
+        assert_eq!(function.signature(), signature);
+        assert_eq!(function.selector(), keccak256(signature)[..4]);
+        assert_eq!(function.inputs[0].components[0].ty, "tuple[2]");
+        assert_eq!(function.inputs[0].components[0].name, "legs");
+    }
+
+    #[test]
+    fn test_fixed_arrays() {
+        let abi =
+            parse_human_abi("event Snapshot(uint256[3] indexed values, bytes32[] ids)").unwrap();
+        let event = &abi.events["Snapshot"][0];
+
+        assert_eq!(event.inputs[0].ty, "uint256[3]");
+        assert!(event.inputs[0].indexed);
+        assert_eq!(event.inputs[1].ty, "bytes32[]");
+    }
+
+    #[test]
+    fn test_errors_and_constructor() {
+        let abi = parse_human_abi(
+            "error InsufficientBalance(uint256 available, uint256 required)\n\
+             constructor(address owner) payable",
+        )
+        .unwrap();
+
+        assert_eq!(abi.errors["InsufficientBalance"][0].inputs.len(), 2);
+        let constructor = abi.constructor.unwrap();
+        assert_eq!(constructor.inputs[0].name, "owner");
+        assert_eq!(constructor.state_mutability, StateMutability::Payable);
+    }
+
+    #[test]
+    fn test_receive_and_fallback() {
+        let abi = parse_human_abi("receive() external payable\nfallback() external").unwrap();
+
+        assert!(abi.receive.is_some());
+        assert_eq!(
+            abi.fallback.unwrap().state_mutability,
+            StateMutability::NonPayable
+        );
+    }
+
+    #[test]
+    fn test_comments_blanks_and_semicolons() {
+        let source = "\
+// ERC20 events
+# exported by cast interface
+
+    event Transfer(address indexed from, address indexed to, uint256 value); // transfers
+function transfer(address to, uint256 amount) external returns (bool);
+";
+        let abi = parse_human_abi(source).unwrap();
+
+        assert_eq!(abi.events().count(), 1);
+        assert_eq!(abi.functions().count(), 1);
+    }
+
+    #[test]
+    fn test_overloads_are_kept() {
+        let abi = parse_human_abi(
+            "function safeTransferFrom(address,address,uint256)\n\
+             function safeTransferFrom(address,address,uint256,bytes)",
+        )
+        .unwrap();
+
+        assert_eq!(abi.functions["safeTransferFrom"].len(), 2);
+    }
+
+    #[test]
+    fn test_error_points_at_line_and_column() {
+        let err = parse_err(
+            "event Transfer(address indexed from, address indexed to, uint256 value)\n\
+             \n\
+             event Broken(address indexed from",
+        );
+
+        assert!(err.contains("line 3, column 34: expected `)`"), "{err}");
+        assert!(
+            err.contains("3 | event Broken(address indexed from"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_error_missing_keyword() {
+        let err = parse_err("Transfer(address,address,uint256)");
+
+        assert!(err.contains("line 1, column 1"), "{err}");
+        assert!(err.contains("`event`"), "{err}");
+    }
+
+    #[test]
+    fn test_error_unknown_type() {
+        let err = parse_err("function f(uint256 a)\nevent E(adress indexed who)");
+        assert!(
+            err.contains("line 2, column 9: unknown type `adress`"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_error_invalid_sized_types() {
+        for ty in ["uint7", "uint264", "int0", "bytes33", "bytes0", "uint08"] {
+            let err = parse_err(&format!("function f({} x)", ty));
+            assert!(err.contains("unknown type"), "{ty}: {err}");
+        }
+    }
+
+    #[test]
+    fn test_error_indexed_outside_event() {
+        let err = parse_err("function f(address indexed who)");
+        assert!(err.contains("only allowed on top-level event"), "{err}");
+
+        let err = parse_err("event E((address indexed a, uint256 b) pair)");
+        assert!(err.contains("only allowed on top-level event"), "{err}");
+    }
+
+    #[test]
+    fn test_error_bad_array_size() {
+        let err = parse_err("function f(uint256[0] x)");
+        assert!(err.contains("invalid array size `0`"), "{err}");
+
+        let err = parse_err("function f(uint256[x] x)");
+        assert!(err.contains("expected `]`"), "{err}");
+    }
+
+    #[test]
+    fn test_error_trailing_garbage() {
+        let err = parse_err("function f(uint256) external view returns (uint256) extra");
+        assert!(err.contains("unexpected `extra`"), "{err}");
+
+        let err = parse_err("function f(uint256 a b)");
+        assert!(err.contains("unexpected `b` after parameter name"), "{err}");
+
+        let err = parse_err("event E(uint256) public");
+        assert!(err.contains("unexpected `public`"), "{err}");
+    }
+
+    #[test]
+    fn test_error_empty_source() {
+        let err = parse_err("// nothing here\n\n");
+        assert!(err.contains("no signatures"), "{err}");
+    }
+
+    #[test]
+    fn test_parse_abi_source_json() {
+        let json = r#"[{"type":"event","name":"Transfer","anonymous":false,"inputs":[
+            {"name":"from","type":"address","indexed":true},
+            {"name":"to","type":"address","indexed":true},
+            {"name":"value","type":"uint256","indexed":false}]}]"#;
+        let abi = parse_abi_source(json).unwrap();
+        assert_eq!(abi.events().count(), 1);
+
+        let artifact = format!(r#"{{"abi": {}, "bytecode": "0x"}}"#, json);
+        let abi = parse_abi_source(&artifact).unwrap();
+        assert_eq!(abi.events().count(), 1);
+    }
+
+    #[test]
+    fn test_parse_abi_source_human() {
+        let abi = parse_abi_source("\n  event Transfer(address indexed, address indexed, uint256)")
+            .unwrap();
+        assert_eq!(abi.events().count(), 1);
+    }
+}
@@ -0,0 +1,189 @@
+//! Signature files for bulk import into the signature cache
+//!
+//! Accepted formats:
+//!
+//! - A JSON ABI, or a compiler artifact with an `abi` field
+//! - A JSON array of signature strings
+//! - A JSON object mapping selectors/topics to signatures (as in a cache dump)
+//! - Plain text, one signature per line
+//!
+//! In string lists and plain text, a signature without a leading `function`,
+//! `event` or `error` keyword is read as a function.
+
+use crate::abi::{parse_abi_source, parse_human_abi};
+use crate::error::{AbiError, Result};
+use alloy::json_abi::JsonAbi;
+use alloy::primitives::keccak256;
+
+/// Function and event signatures read from an import file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SignatureImport {
+    /// `(selector, signature)` pairs, selector as `0x` + 8 hex chars
+    pub functions: Vec<(String, String)>,
+    /// `(topic0, signature)` pairs, topic as `0x` + 64 hex chars
+    pub events: Vec<(String, String)>,
+    /// Map entries whose key did not match the hash of their signature
+    pub mismatched: usize,
+}
+
+impl SignatureImport {
+    /// Total number of signatures read
+    pub fn len(&self) -> usize {
+        self.functions.len() + self.events.len()
+    }
+
+    /// Whether no signatures were read
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn from_abi(abi: &JsonAbi) -> Self {
+        let mut import = Self::default();
+        for function in abi.functions() {
+            import
+                .functions
+                .push((function.selector().to_string(), function.signature()));
+        }
+        for event in abi.events().filter(|e| !e.anonymous) {
+            import
+                .events
+                .push((event.selector().to_string(), event.signature()));
+        }
+        import
+    }
+}
+
+/// Parse a signature import file (see the module docs for formats)
+pub fn parse_signature_import(source: &str) -> Result<SignatureImport> {
+    let trimmed = source.trim_start();
+    if trimmed.starts_with('[') || trimmed.starts_with('{') {
+        let value: serde_json::Value = serde_json::from_str(trimmed)
+            .map_err(|e| AbiError::ParseError(format!("Invalid signature JSON: {}", e)))?;
+        match &value {
+            serde_json::Value::Array(items) if items.iter().all(|v| v.is_string()) => {
+                let lines: Vec<&str> = items.iter().filter_map(|v| v.as_str()).collect();
+                return parse_signature_lines(&lines);
+            }
+            serde_json::Value::Object(map) if !map.contains_key("abi") => {
+                return parse_signature_map(map);
+            }
+            _ => {}
+        }
+        return Ok(SignatureImport::from_abi(&parse_abi_source(trimmed)?));
+    }
+    let lines: Vec<&str> = source.lines().collect();
+    parse_signature_lines(&lines)
+}
+
+/// Parse bare or keyword-prefixed signatures, one per entry
+fn parse_signature_lines(lines: &[&str]) -> Result<SignatureImport> {
+    let source = lines
+        .iter()
+        .map(|line| {
+            let line = line.trim();
+            let has_keyword = ["function ", "event ", "error "]
+                .iter()
+                .any(|k| line.starts_with(k));
+            if line.is_empty() || has_keyword || line.starts_with("//") || line.starts_with('#') {
+                line.to_string()
+            } else {
+                format!("function {}", line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    Ok(SignatureImport::from_abi(&parse_human_abi(&source)?))
+}
+
+/// Parse a `{ selector_or_topic: signature }` map
+///
+/// Keys of 4 bytes are functions and keys of 32 bytes are events. Entries
+/// whose key is not the hash of the signature are skipped and counted.
+fn parse_signature_map(
+    map: &serde_json::Map<String, serde_json::Value>,
+) -> Result<SignatureImport> {
+    let mut import = SignatureImport::default();
+    for (key, value) in map {
+        let signature = value.as_str().ok_or_else(|| {
+            AbiError::ParseError(format!("Signature for {} is not a string", key))
+        })?;
+        let key = key.to_lowercase();
+        let hash = keccak256(signature.trim().as_bytes()).to_string();
+        match key.len() {
+            10 if hash.starts_with(&key) => {
+                import.functions.push((key, signature.trim().to_string()))
+            }
+            66 if hash == key => import.events.push((key, signature.trim().to_string())),
+            10 | 66 => import.mismatched += 1,
+            _ => {
+                return Err(AbiError::ParseError(format!(
+                    "Key {} is neither a 4-byte selector nor a 32-byte topic",
+                    key
+                ))
+                .into())
+            }
+        }
+    }
+    Ok(import)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRANSFER_FN: &str = "0xa9059cbb";
+    const TRANSFER_TOPIC: &str =
+        "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
+    #[test]
+    fn test_import_json_abi_and_artifact() {
+        let abi = r#"[
+            {"type":"function","name":"transfer","inputs":[{"name":"to","type":"address"},{"name":"amount","type":"uint256"}],"outputs":[{"type":"bool"}],"stateMutability":"nonpayable"},
+            {"type":"event","name":"Transfer","inputs":[{"name":"from","type":"address","indexed":true},{"name":"to","type":"address","indexed":true},{"name":"value","type":"uint256","indexed":false}],"anonymous":false}
+        ]"#;
+        let import = parse_signature_import(abi).unwrap();
+        assert_eq!(
+            import.functions,
+            vec![(
+                TRANSFER_FN.to_string(),
+                "transfer(address,uint256)".to_string()
+            )]
+        );
+        assert_eq!(
+            import.events,
+            vec![(
+                TRANSFER_TOPIC.to_string(),
+                "Transfer(address,address,uint256)".to_string()
+            )]
+        );
+
+        let artifact = format!(r#"{{"abi": {}, "bytecode": "0x"}}"#, abi);
+        assert_eq!(parse_signature_import(&artifact).unwrap(), import);
+    }
+
+    #[test]
+    fn test_import_flat_list_and_text() {
+        let list = r#"["transfer(address,uint256)", "event Transfer(address indexed from, address indexed to, uint256 value)"]"#;
+        let import = parse_signature_import(list).unwrap();
+        assert_eq!(import.functions[0].0, TRANSFER_FN);
+        assert_eq!(import.events[0].0, TRANSFER_TOPIC);
+
+        let text = "# ERC-20\ntransfer(address,uint256)\n\nevent Transfer(address indexed,address indexed,uint256)\n";
+        assert_eq!(parse_signature_import(text).unwrap(), import);
+    }
+
+    #[test]
+    fn test_import_selector_map() {
+        let map = format!(
+            r#"{{"{}": "transfer(address,uint256)", "{}": "Transfer(address,address,uint256)", "0x12345678": "transfer(address,uint256)"}}"#,
+            TRANSFER_FN.to_uppercase().replace("0X", "0x"),
+            TRANSFER_TOPIC
+        );
+        let import = parse_signature_import(&map).unwrap();
+        assert_eq!(import.functions.len(), 1);
+        assert_eq!(import.events.len(), 1);
+        assert_eq!(import.mismatched, 1);
+
+        assert!(parse_signature_import(r#"{"0x12": "f()"}"#).is_err());
+    }
+}
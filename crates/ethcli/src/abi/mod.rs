@@ -1,9 +1,21 @@
 //! ABI handling, event parsing, and log decoding
 
+mod call;
+mod calldata;
+pub mod codegen;
 mod decoder;
 mod fetcher;
+mod human;
+mod import;
 mod parser;
+mod router;
 
-pub use decoder::{DecodedLog, DecodedValue, LogDecoder};
+pub use call::{decode_types, format_values, value_to_json, ContractCall};
+pub use calldata::{CalldataCandidate, CalldataSource, DecodedArg, DecodedCalldata};
+pub use decoder::{DecodedLog, DecodedValue, DecoderSource, LogDecoder};
+pub(crate) use fetcher::decode_function_params;
 pub use fetcher::{AbiFetcher, ContractCreation, ContractMetadata, DecodedFunction};
+pub use human::{parse_abi_source, parse_human_abi};
+pub use import::{parse_signature_import, SignatureImport};
 pub use parser::{EventSignature, ParsedParam};
+pub use router::{AbiRouter, DEFAULT_AUTO_ABI_LIMIT};
@@ -4,6 +4,7 @@
 //! into structured form for topic matching and log decoding.
 
 use crate::error::{AbiError, Result};
+use alloy::json_abi::Event;
 use alloy::primitives::{keccak256, B256};
 
 /// A parsed event parameter
@@ -37,8 +38,23 @@ impl EventSignature {
     /// - "Transfer(address,address,uint256)"
     /// - "Transfer(address indexed from, address indexed to, uint256 value)"
     /// - "Transfer(address indexed, address indexed, uint256)"
+    /// - "event Swap((address,uint256) indexed order, uint256[] amounts)"
+    ///
+    /// A leading `event` keyword is optional. Signatures with tuple
+    /// parameters go through the human-readable ABI parser.
     pub fn parse(signature: &str) -> Result<Self> {
         let sig = signature.trim();
+        let sig = sig.strip_prefix("event ").map(str::trim).unwrap_or(sig);
+
+        if sig.find('(').is_some_and(|p| sig[p + 1..].contains('(')) {
+            let abi = crate::abi::parse_human_abi(&format!("event {}", sig))
+                .map_err(|e| AbiError::InvalidEventSignature(e.to_string()))?;
+            let event = abi
+                .events()
+                .next()
+                .ok_or_else(|| AbiError::InvalidEventSignature(format!("No event in: {}", sig)))?;
+            return Ok(Self::from_event(event));
+        }
 
         // Find the opening parenthesis
         let paren_pos = sig
@@ -76,6 +92,27 @@ impl EventSignature {
         })
     }
 
+    /// Build a signature from an ABI event
+    pub fn from_event(event: &Event) -> Self {
+        let params = event
+            .inputs
+            .iter()
+            .map(|p| ParsedParam {
+                name: p.name.clone(),
+                ty: p.selector_type().into_owned(),
+                indexed: p.indexed,
+            })
+            .collect();
+        let canonical = event.signature();
+
+        Self {
+            name: event.name.clone(),
+            params,
+            topic: keccak256(canonical.as_bytes()),
+            canonical,
+        }
+    }
+
     /// Parse parameter list
     fn parse_params(params_str: &str) -> Result<Vec<ParsedParam>> {
         if params_str.trim().is_empty() {
@@ -244,6 +281,33 @@ mod tests {
         assert_eq!(sig.params[2].name, "value");
     }
 
+    #[test]
+    fn test_parse_event_keyword() {
+        let sig =
+            EventSignature::parse("event Transfer(address indexed from, address to, uint256)")
+                .unwrap();
+
+        assert_eq!(sig.name, "Transfer");
+        assert!(sig.params[0].indexed);
+        assert_eq!(sig.canonical, "Transfer(address,address,uint256)");
+    }
+
+    #[test]
+    fn test_parse_tuple_signature() {
+        let sig = EventSignature::parse(
+            "Swapped((address tokenIn, uint256 amountIn)[] legs, address indexed sender)",
+        )
+        .unwrap();
+
+        assert_eq!(sig.params[0].ty, "(address,uint256)[]");
+        assert_eq!(sig.params[0].name, "legs");
+        assert!(sig.params[1].indexed);
+        assert_eq!(sig.canonical, "Swapped((address,uint256)[],address)");
+        assert_eq!(sig.topic, keccak256(sig.canonical.as_bytes()));
+
+        assert!(EventSignature::parse("Bad((address,uint256 x)").is_err());
+    }
+
     #[test]
     fn test_topic_calculation() {
         // Known topic for Transfer(address,address,uint256)
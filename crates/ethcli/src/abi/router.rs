@@ -0,0 +1,220 @@
+//! Per-address ABI resolution for log decoding
+//!
+//! Logs from factories or multi-contract queries come from addresses the
+//! configured ABI knows nothing about. [`AbiRouter`] fetches the verified ABI
+//! of each such address on first sight (up to a cap), and looks up unknown
+//! topics in the signature database when no ABI covers them.
+
+use crate::abi::{AbiFetcher, EventSignature, LogDecoder};
+use crate::config::Chain;
+use crate::error::{AbiError, Error, Result};
+use crate::etherscan::{Client as EtherscanClient, SignatureCache};
+use alloy::json_abi::JsonAbi;
+use alloy::primitives::{Address, B256};
+use alloy::rpc::types::Log;
+use parking_lot::RwLock;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Default maximum number of contract ABIs fetched per run
+pub const DEFAULT_AUTO_ABI_LIMIT: usize = 25;
+
+/// Minimum spacing between Etherscan requests without an API key (~5 calls/sec)
+const KEYLESS_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Minimum spacing between Etherscan requests with an API key
+const KEYED_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Retries after Etherscan reports its rate limit was hit
+const RATE_LIMIT_RETRIES: u32 = 3;
+
+/// Lazily fills a [`LogDecoder`] with the ABIs of the addresses emitting logs
+pub struct AbiRouter {
+    chain: Chain,
+    fetcher: AbiFetcher,
+    /// Signature database client (None if the chain isn't supported)
+    signatures: Option<EtherscanClient>,
+    /// Maximum number of contract ABIs to fetch
+    limit: usize,
+    /// Whether the limit-reached warning was shown
+    limit_warned: bool,
+    /// Addresses already tried, whether or not they had a verified ABI
+    attempted: HashSet<Address>,
+    /// Topics already looked up in the signature database
+    looked_up: HashSet<B256>,
+    /// Minimum spacing between Etherscan requests
+    interval: Duration,
+    /// When the last Etherscan request was sent
+    last_request: Option<Instant>,
+}
+
+impl AbiRouter {
+    /// Create a router fetching at most `limit` contract ABIs
+    pub fn new(chain: Chain, api_key: Option<String>, limit: usize) -> Result<Self> {
+        let interval = if api_key.is_some() {
+            KEYED_INTERVAL
+        } else {
+            KEYLESS_INTERVAL
+        };
+        let cache = Arc::new(SignatureCache::new());
+        let signatures = EtherscanClient::with_cache(chain, api_key.clone(), cache.clone())
+            .map_err(|e| tracing::debug!("Signature lookups disabled: {}", e))
+            .ok();
+
+        Ok(Self {
+            chain,
+            fetcher: AbiFetcher::with_cache(api_key, cache)?,
+            signatures,
+            limit,
+            limit_warned: false,
+            attempted: HashSet::new(),
+            looked_up: HashSet::new(),
+            interval,
+            last_request: None,
+        })
+    }
+
+    /// Mark an address as resolved so it is never fetched (e.g. the configured
+    /// contract, whose ABI was loaded up front)
+    pub fn mark_attempted(&mut self, address: Address) {
+        self.attempted.insert(address);
+    }
+
+    /// Fetch whatever the decoder is missing to decode `logs`
+    ///
+    /// Contract ABIs are fetched for addresses whose logs the explicit events
+    /// can't decode; topics still undecodable afterwards are looked up in the
+    /// signature database. Failures are logged and remembered, never retried.
+    pub async fn resolve(&mut self, decoder: &RwLock<LogDecoder>, logs: &[Log]) {
+        let addresses: Vec<Address> = {
+            let decoder = decoder.read();
+            let mut seen = HashSet::new();
+            logs.iter()
+                .filter(|log| decoder.needs_contract_abi(log))
+                .map(|log| log.address())
+                .filter(|address| !self.attempted.contains(address) && seen.insert(*address))
+                .collect()
+        };
+
+        for address in addresses {
+            if self.attempted.len() >= self.limit {
+                if !self.limit_warned {
+                    tracing::warn!(
+                        "Auto ABI limit of {} contracts reached; logs from {:#x} and other new \
+                         addresses fall back to the signature database",
+                        self.limit,
+                        address
+                    );
+                    self.limit_warned = true;
+                }
+                break;
+            }
+            self.attempted.insert(address);
+
+            match self.fetch_abi(address).await {
+                Ok(abi) => {
+                    if let Err(e) = decoder.write().add_contract_abi(address, &abi) {
+                        tracing::warn!("Unusable ABI for {:#x}: {}", address, e);
+                    }
+                }
+                Err(e) => tracing::debug!("No ABI for {:#x}: {}", address, e),
+            }
+        }
+
+        let Some(signatures) = &self.signatures else {
+            return;
+        };
+        let topics: Vec<B256> = {
+            let decoder = decoder.read();
+            logs.iter()
+                .filter(|log| !decoder.fits(log))
+                .filter_map(|log| log.topics().first().copied())
+                .filter(|topic| self.looked_up.insert(*topic))
+                .collect()
+        };
+
+        for topic in topics {
+            let Some(candidates) = signatures.lookup_event_all(&format!("{:#x}", topic)).await
+            else {
+                continue;
+            };
+            let mut decoder = decoder.write();
+            for candidate in candidates {
+                match EventSignature::parse(&candidate) {
+                    Ok(sig) if sig.topic == topic => {
+                        if let Err(e) = decoder.add_fallback_signature(&sig) {
+                            tracing::debug!("Skipping signature '{}': {}", candidate, e);
+                        }
+                    }
+                    Ok(_) => {
+                        tracing::debug!("Signature '{}' does not hash to {:#x}", candidate, topic)
+                    }
+                    Err(e) => tracing::debug!("Skipping signature '{}': {}", candidate, e),
+                }
+            }
+        }
+    }
+
+    /// Fetch a contract ABI, from the cache if possible, otherwise from
+    /// Etherscan with request spacing and rate-limit backoff
+    async fn fetch_abi(&mut self, address: Address) -> Result<JsonAbi> {
+        let address = format!("{:#x}", address);
+        if let Some(abi) = self.fetcher.cached_abi(self.chain, &address) {
+            return Ok(abi);
+        }
+
+        let mut attempt = 0;
+        loop {
+            if let Some(last) = self.last_request {
+                let elapsed = last.elapsed();
+                if elapsed < self.interval {
+                    tokio::time::sleep(self.interval - elapsed).await;
+                }
+            }
+            self.last_request = Some(Instant::now());
+
+            match self
+                .fetcher
+                .fetch_from_etherscan(self.chain, &address)
+                .await
+            {
+                Err(e) if is_rate_limited(&e) && attempt < RATE_LIMIT_RETRIES => {
+                    attempt += 1;
+                    let backoff = Duration::from_secs(1 << attempt);
+                    tracing::debug!("Etherscan rate limited, retrying in {:?}", backoff);
+                    tokio::time::sleep(backoff).await;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Whether an Etherscan error means the rate limit was hit
+fn is_rate_limited(error: &Error) -> bool {
+    match error {
+        Error::Abi(AbiError::EtherscanFetch(message)) => {
+            let message = message.to_lowercase();
+            message.contains("rate limit") || message.contains("429")
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_rate_limited() {
+        let limited: Error =
+            AbiError::EtherscanFetch("NOTOK: Max rate limit reached".into()).into();
+        assert!(is_rate_limited(&limited));
+        let http: Error =
+            AbiError::EtherscanFetch("HTTP error: 429 Too Many Requests".into()).into();
+        assert!(is_rate_limited(&http));
+        let unverified: Error = AbiError::ContractNotVerified("0xabc".into()).into();
+        assert!(!is_rate_limited(&unverified));
+    }
+}
@@ -0,0 +1,504 @@
+//! Gas cost comparison across chains
+//!
+//! Prices a representative operation (transfer, swap, ...) on several chains
+//! at current fees and converts the native cost to USD through the
+//! aggregated price path. Costs cover execution gas only; L1 data fees paid
+//! by rollups are not included.
+
+use super::get_cached_config;
+use super::price::fetch_prices_all;
+use crate::config::{Chain, EndpointConfig};
+use crate::rpc::Endpoint;
+use alloy::eips::BlockNumberOrTag;
+use alloy::providers::Provider;
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::future::Future;
+
+/// Operation priced by a gas comparison
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    clap::ValueEnum,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum GasOperation {
+    /// Native token transfer
+    Transfer,
+    /// ERC-20 `transfer`
+    Erc20Transfer,
+    /// Single-hop DEX swap
+    Swap,
+    /// ERC-721 mint
+    NftMint,
+}
+
+impl GasOperation {
+    /// All operations
+    pub const ALL: [Self; 4] = [
+        Self::Transfer,
+        Self::Erc20Transfer,
+        Self::Swap,
+        Self::NftMint,
+    ];
+
+    /// Name used on the command line and in config
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Transfer => "transfer",
+            Self::Erc20Transfer => "erc20-transfer",
+            Self::Swap => "swap",
+            Self::NftMint => "nft-mint",
+        }
+    }
+
+    /// Built-in representative gas units
+    pub fn default_gas(&self) -> u64 {
+        match self {
+            Self::Transfer => 21_000,
+            Self::Erc20Transfer => 65_000,
+            Self::Swap => 150_000,
+            Self::NftMint => 100_000,
+        }
+    }
+
+    /// Parse from its command line / config name
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|op| op.name() == name)
+    }
+}
+
+impl fmt::Display for GasOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Representative gas units per operation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GasTable {
+    gas: BTreeMap<GasOperation, u64>,
+}
+
+impl Default for GasTable {
+    fn default() -> Self {
+        Self {
+            gas: GasOperation::ALL
+                .into_iter()
+                .map(|op| (op, op.default_gas()))
+                .collect(),
+        }
+    }
+}
+
+impl GasTable {
+    /// Built-in defaults with `[gas.operations]` overrides from the config
+    /// file
+    ///
+    /// Returns the table and any override names that aren't known
+    /// operations, so the caller can warn about them.
+    pub fn from_config() -> (Self, Vec<String>) {
+        let mut table = Self::default();
+        let unknown = get_cached_config()
+            .as_ref()
+            .and_then(|c| c.gas.as_ref())
+            .map(|gas| table.apply_overrides(&gas.operations))
+            .unwrap_or_default();
+        (table, unknown)
+    }
+
+    /// Override gas units by operation name, returning unknown names
+    pub fn apply_overrides(&mut self, overrides: &BTreeMap<String, u64>) -> Vec<String> {
+        let mut unknown = Vec::new();
+        for (name, units) in overrides {
+            match GasOperation::from_name(name) {
+                Some(op) => self.set(op, *units),
+                None => unknown.push(name.clone()),
+            }
+        }
+        unknown
+    }
+
+    /// Set the gas units for an operation
+    pub fn set(&mut self, operation: GasOperation, units: u64) {
+        self.gas.insert(operation, units);
+    }
+
+    /// Gas units for an operation
+    pub fn gas_units(&self, operation: GasOperation) -> u64 {
+        self.gas
+            .get(&operation)
+            .copied()
+            .unwrap_or_else(|| operation.default_gas())
+    }
+}
+
+/// Fee and price data for a gas comparison
+pub trait GasPriceSource {
+    /// Current effective gas price on `chain` in wei (base fee plus
+    /// priority fee)
+    fn gas_price_wei(&self, chain: Chain) -> impl Future<Output = Result<u128, String>>;
+
+    /// USD price of a native token, by symbol
+    fn native_price_usd(&self, symbol: &str) -> impl Future<Output = Result<f64, String>>;
+}
+
+/// Live source backed by RPC endpoints and the aggregated price path
+///
+/// Fees come from the latest block's base fee plus the EIP-1559 priority fee
+/// estimate, falling back to `eth_gasPrice` on chains without a base fee.
+#[derive(Debug, Clone, Default)]
+pub struct LiveGasSource {
+    /// RPC URLs to use instead of the configured endpoints, per chain
+    pub rpc_urls: HashMap<Chain, String>,
+}
+
+impl LiveGasSource {
+    fn endpoint(&self, chain: Chain) -> Result<Endpoint, String> {
+        let config = match self.rpc_urls.get(&chain) {
+            Some(url) => EndpointConfig::new(url.clone()),
+            None => get_cached_config()
+                .as_ref()
+                .and_then(|c| c.endpoints.iter().find(|e| e.enabled && e.chain == chain))
+                .cloned()
+                .ok_or_else(|| format!("no RPC endpoint configured for {}", chain.name()))?,
+        };
+        Endpoint::new(config, 30, None).map_err(|e| e.to_string())
+    }
+}
+
+impl GasPriceSource for LiveGasSource {
+    async fn gas_price_wei(&self, chain: Chain) -> Result<u128, String> {
+        let endpoint = self.endpoint(chain)?;
+        let provider = endpoint.provider();
+
+        let block = provider
+            .get_block_by_number(BlockNumberOrTag::Latest)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        match block.and_then(|b| b.header.base_fee_per_gas) {
+            Some(base_fee) => {
+                let fees = provider
+                    .estimate_eip1559_fees()
+                    .await
+                    .map_err(|e| e.to_string())?;
+                Ok(u128::from(base_fee) + fees.max_priority_fee_per_gas)
+            }
+            None => provider.get_gas_price().await.map_err(|e| e.to_string()),
+        }
+    }
+
+    async fn native_price_usd(&self, symbol: &str) -> Result<f64, String> {
+        // Native tokens are priced by symbol, which the Ethereum-side sources
+        // cover for every supported chain
+        let result = fetch_prices_all(symbol, "ethereum").await;
+        if result.any_succeeded() && result.aggregated.median_usd > 0.0 {
+            Ok(result.aggregated.median_usd)
+        } else {
+            Err(format!("no USD price for {}", symbol))
+        }
+    }
+}
+
+/// Cost of an operation on one chain
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GasCost {
+    /// Chain name
+    pub chain: String,
+    /// Chain ID
+    pub chain_id: u64,
+    /// Gas units priced
+    pub gas_units: u64,
+    /// Effective gas price in gwei
+    pub gas_price_gwei: f64,
+    /// Native token symbol
+    pub native_symbol: String,
+    /// Cost in the native token
+    pub native_cost: f64,
+    /// Cost in USD, if the native token could be priced
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usd_cost: Option<f64>,
+    /// USD cost relative to the cheapest chain (1.0 = cheapest)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relative_to_cheapest: Option<f64>,
+}
+
+/// A chain left out of a comparison
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkippedChain {
+    /// Chain as given by the caller
+    pub chain: String,
+    /// Why it was skipped
+    pub reason: String,
+}
+
+/// Gas cost comparison for one operation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GasComparison {
+    /// Operation priced
+    pub operation: GasOperation,
+    /// Costs, cheapest (in USD) first; chains without a USD price come last
+    pub costs: Vec<GasCost>,
+    /// Chains that couldn't be compared
+    pub skipped: Vec<SkippedChain>,
+}
+
+/// Compare the cost of `operation` across `chains`
+///
+/// Chain names that don't parse, custom chain IDs and chains whose fees
+/// can't be fetched are skipped rather than failing the comparison. Fees
+/// and native prices are fetched concurrently, with each native token
+/// priced once.
+pub async fn compare_gas_costs<S: GasPriceSource>(
+    source: &S,
+    operation: GasOperation,
+    chains: &[impl AsRef<str>],
+    table: &GasTable,
+) -> GasComparison {
+    let mut skipped = Vec::new();
+    let mut known: Vec<Chain> = Vec::new();
+    for name in chains {
+        let name = name.as_ref().trim();
+        match Chain::from_str_or_id(name) {
+            Ok(Chain::Custom(_)) | Err(_) => skipped.push(SkippedChain {
+                chain: name.to_string(),
+                reason: "unknown chain".to_string(),
+            }),
+            Ok(chain) if !known.contains(&chain) => known.push(chain),
+            Ok(_) => {}
+        }
+    }
+
+    let mut symbols: Vec<&'static str> = known.iter().map(|c| c.native_symbol()).collect();
+    symbols.sort_unstable();
+    symbols.dedup();
+
+    let (fees, prices) = futures::join!(
+        join_all(known.iter().map(|chain| source.gas_price_wei(*chain))),
+        join_all(symbols.iter().map(|symbol| source.native_price_usd(symbol))),
+    );
+    let prices: HashMap<&str, f64> = symbols
+        .iter()
+        .zip(prices)
+        .filter_map(|(symbol, price)| Some((*symbol, price.ok()?)))
+        .collect();
+
+    let gas_units = table.gas_units(operation);
+    let mut costs = Vec::new();
+    for (chain, fee) in known.into_iter().zip(fees) {
+        let price_wei = match fee {
+            Ok(price) => price,
+            Err(e) => {
+                skipped.push(SkippedChain {
+                    chain: chain.name().to_string(),
+                    reason: e,
+                });
+                continue;
+            }
+        };
+        let native_cost = gas_units as f64 * price_wei as f64 / 1e18;
+        costs.push(GasCost {
+            chain: chain.name().to_string(),
+            chain_id: chain.chain_id(),
+            gas_units,
+            gas_price_gwei: price_wei as f64 / 1e9,
+            native_symbol: chain.native_symbol().to_string(),
+            native_cost,
+            usd_cost: prices
+                .get(chain.native_symbol())
+                .map(|price| native_cost * price),
+            relative_to_cheapest: None,
+        });
+    }
+
+    costs.sort_by(|a, b| match (a.usd_cost, b.usd_cost) {
+        (Some(a), Some(b)) => a.total_cmp(&b),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => a.native_cost.total_cmp(&b.native_cost),
+    });
+
+    if let Some(cheapest) = costs.first().and_then(|c| c.usd_cost) {
+        for cost in &mut costs {
+            cost.relative_to_cheapest = cost
+                .usd_cost
+                .filter(|_| cheapest > 0.0)
+                .map(|usd| usd / cheapest);
+        }
+    }
+
+    GasComparison {
+        operation,
+        costs,
+        skipped,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Fixed fees (gwei) and prices, recording price lookups
+    struct MockSource {
+        gwei: HashMap<Chain, f64>,
+        prices: HashMap<&'static str, f64>,
+        price_calls: Mutex<Vec<String>>,
+    }
+
+    impl MockSource {
+        fn new(gwei: &[(Chain, f64)], prices: &[(&'static str, f64)]) -> Self {
+            Self {
+                gwei: gwei.iter().copied().collect(),
+                prices: prices.iter().copied().collect(),
+                price_calls: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    impl GasPriceSource for MockSource {
+        async fn gas_price_wei(&self, chain: Chain) -> Result<u128, String> {
+            self.gwei
+                .get(&chain)
+                .map(|gwei| (gwei * 1e9) as u128)
+                .ok_or_else(|| "rpc unavailable".to_string())
+        }
+
+        async fn native_price_usd(&self, symbol: &str) -> Result<f64, String> {
+            self.price_calls.lock().unwrap().push(symbol.to_string());
+            self.prices
+                .get(symbol)
+                .copied()
+                .ok_or_else(|| "no price".to_string())
+        }
+    }
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9 * b.abs().max(1.0)
+    }
+
+    #[tokio::test]
+    async fn test_compare_math_and_ordering() {
+        let source = MockSource::new(
+            &[
+                (Chain::Ethereum, 20.0),
+                (Chain::Base, 0.01),
+                (Chain::Arbitrum, 0.1),
+                (Chain::Polygon, 50.0),
+            ],
+            &[("ETH", 3000.0), ("MATIC", 0.5)],
+        );
+        let comparison = compare_gas_costs(
+            &source,
+            GasOperation::Swap,
+            &["ethereum", "base", "arbitrum", "polygon"],
+            &GasTable::default(),
+        )
+        .await;
+
+        let order: Vec<_> = comparison.costs.iter().map(|c| c.chain.as_str()).collect();
+        assert_eq!(order, ["polygon", "base", "arbitrum", "ethereum"]);
+        assert!(comparison.skipped.is_empty());
+
+        // 150k gas at 20 gwei = 0.003 ETH = $9
+        let eth = &comparison.costs[3];
+        assert_eq!(eth.gas_units, 150_000);
+        assert!(approx(eth.gas_price_gwei, 20.0));
+        assert!(approx(eth.native_cost, 0.003));
+        assert!(approx(eth.usd_cost.unwrap(), 9.0));
+
+        // 150k gas at 50 gwei = 0.0075 MATIC = $0.00375
+        let polygon = &comparison.costs[0];
+        assert_eq!(polygon.native_symbol, "MATIC");
+        assert!(approx(polygon.usd_cost.unwrap(), 0.00375));
+        assert!(approx(polygon.relative_to_cheapest.unwrap(), 1.0));
+        assert!(approx(eth.relative_to_cheapest.unwrap(), 9.0 / 0.00375));
+
+        // ETH is priced once for three chains
+        let mut calls = source.price_calls.lock().unwrap().clone();
+        calls.sort();
+        assert_eq!(calls, ["ETH", "MATIC"]);
+    }
+
+    #[tokio::test]
+    async fn test_compare_skips_unknown_and_failing_chains() {
+        let source = MockSource::new(&[(Chain::Ethereum, 10.0)], &[("ETH", 2000.0)]);
+        let comparison = compare_gas_costs(
+            &source,
+            GasOperation::Transfer,
+            &["ethereum", "notachain", "999999", "optimism", "eth"],
+            &GasTable::default(),
+        )
+        .await;
+
+        assert_eq!(comparison.costs.len(), 1);
+        assert!(approx(comparison.costs[0].usd_cost.unwrap(), 0.42));
+        let skipped: Vec<_> = comparison
+            .skipped
+            .iter()
+            .map(|s| (s.chain.as_str(), s.reason.as_str()))
+            .collect();
+        assert_eq!(
+            skipped,
+            [
+                ("notachain", "unknown chain"),
+                ("999999", "unknown chain"),
+                ("optimism", "rpc unavailable"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_compare_unpriced_chains_sort_last() {
+        let source = MockSource::new(
+            &[(Chain::Bsc, 1.0), (Chain::Base, 0.05)],
+            &[("ETH", 3000.0)],
+        );
+        let comparison = compare_gas_costs(
+            &source,
+            GasOperation::Erc20Transfer,
+            &["bsc", "base"],
+            &GasTable::default(),
+        )
+        .await;
+
+        assert_eq!(comparison.costs[0].chain, "base");
+        assert_eq!(comparison.costs[1].chain, "bsc");
+        assert_eq!(comparison.costs[1].usd_cost, None);
+        assert_eq!(comparison.costs[1].relative_to_cheapest, None);
+        assert!(approx(comparison.costs[1].native_cost, 65_000.0 * 1e-9));
+    }
+
+    #[test]
+    fn test_gas_table_overrides() {
+        let mut table = GasTable::default();
+        let overrides = BTreeMap::from([("swap".to_string(), 250_000), ("bridge".to_string(), 1)]);
+        let unknown = table.apply_overrides(&overrides);
+
+        assert_eq!(unknown, ["bridge"]);
+        assert_eq!(table.gas_units(GasOperation::Swap), 250_000);
+        assert_eq!(table.gas_units(GasOperation::Transfer), 21_000);
+    }
+
+    #[test]
+    fn test_gas_operation_names() {
+        for op in GasOperation::ALL {
+            assert_eq!(GasOperation::from_name(op.name()), Some(op));
+            assert_eq!(
+                serde_json::to_value(op).unwrap(),
+                serde_json::json!(op.name())
+            );
+        }
+    }
+}
@@ -4,9 +4,11 @@
 //! - Core aggregation types (`SourceResult`, `AggregatedResult`)
 //! - Data normalization types (`NormalizedPrice`, `NormalizedBalance`, `NormalizedNft`)
 //! - Price aggregation from multiple sources
+//! - Gas cost comparison across chains
 //! - Chain name normalization across services
 
 pub mod chain_map;
+pub mod gas;
 pub mod nft;
 pub mod normalize;
 pub mod portfolio;
@@ -15,6 +17,7 @@ pub mod swap;
 pub mod yields;
 
 pub use chain_map::*;
+pub use gas::*;
 pub use nft::*;
 pub use normalize::*;
 pub use portfolio::*;
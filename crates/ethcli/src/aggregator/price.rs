@@ -450,6 +450,13 @@ async fn fetch_gecko_price(
                     )
                 }
             }
+            Err(cgko::error::ApiError::Domain(cgko::error::DomainError::UnknownCoinIds {
+                ..
+            })) => SourceResult::error(
+                "gecko",
+                format!("Coin ID '{}' not found (mapped from '{}')", coin_id, token),
+                measure.elapsed_ms(),
+            ),
             Err(e) => {
                 SourceResult::error("gecko", format!("API error: {}", e), measure.elapsed_ms())
             }
@@ -0,0 +1,228 @@
+//! ERC-20 approval tracking
+//!
+//! Folds `Approval` logs emitted for one owner into the latest approval per
+//! (token, spender) pair, for `token approvals`.
+
+use alloy::primitives::{Address, B256, U256};
+use alloy::rpc::types::Log;
+use alloy::sol_types::SolEvent;
+use std::collections::BTreeMap;
+
+/// Approval events
+pub mod events {
+    alloy::sol! {
+        /// ERC-20 approval (ERC-721 shares the topic but indexes the token id)
+        event Approval(address indexed owner, address indexed spender, uint256 value);
+    }
+}
+
+/// `type(uint96).max`, the cap tokens such as UNI and COMP use for
+/// "infinite" approvals
+pub const UINT96_MAX: U256 = U256::from_limbs([u64::MAX, (1 << 32) - 1, 0, 0]);
+
+/// Allowances at or above this are what's left of a `type(uint256).max`
+/// approval after `transferFrom` spent some of it
+///
+/// Only the top 64 bits have to be set, so up to 2^192 units can have been
+/// spent.
+pub const NEAR_MAX_THRESHOLD: U256 = U256::from_limbs([0, 0, 0, u64::MAX]);
+
+/// Topic of the ERC-20 `Approval` event, for log filters
+pub fn approval_topic() -> B256 {
+    events::Approval::SIGNATURE_HASH
+}
+
+/// Whether `amount` counts as an unlimited approval
+///
+/// An amount is unlimited when it is at least [`NEAR_MAX_THRESHOLD`],
+/// exactly [`UINT96_MAX`], or covers the token's whole `total_supply`.
+/// A fixed threshold alone is not enough: SHIB and PEPE supplies exceed
+/// `type(uint96).max`, so finite approvals for them can too.
+pub fn is_unlimited(amount: U256, total_supply: Option<U256>) -> bool {
+    amount >= NEAR_MAX_THRESHOLD
+        || amount == UINT96_MAX
+        || total_supply.is_some_and(|supply| !supply.is_zero() && amount >= supply)
+}
+
+/// The most recent approval `owner` gave `spender` on `token`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Approval {
+    /// Token contract that emitted the event
+    pub token: Address,
+    /// Approved spender
+    pub spender: Address,
+    /// Approved amount in the token's smallest unit
+    pub amount: U256,
+    /// Block the approval was set in
+    pub block_number: Option<u64>,
+    /// Transaction that set the approval
+    pub transaction_hash: Option<B256>,
+}
+
+impl Approval {
+    /// Whether this is an unlimited approval for a token with `total_supply`
+    pub fn is_unlimited(&self, total_supply: Option<U256>) -> bool {
+        is_unlimited(self.amount, total_supply)
+    }
+}
+
+/// Latest approval per (token, spender) from `owner`'s `Approval` logs
+///
+/// Logs are applied in chain order, so a later approval replaces an earlier
+/// one and an approval of zero revokes it. ERC-721 `Approval` logs (four
+/// topics) and logs for other owners are skipped. The result is sorted by
+/// token, then spender, and excludes revoked pairs.
+///
+/// Amounts are what the events said; `transferFrom` usually lowers an
+/// allowance without emitting `Approval`, so read `allowance()` for the
+/// live figure.
+pub fn latest_approvals<'a>(
+    owner: Address,
+    logs: impl IntoIterator<Item = &'a Log>,
+) -> Vec<Approval> {
+    let mut logs: Vec<&Log> = logs.into_iter().collect();
+    // Pending logs have no position; apply them last
+    logs.sort_by_key(|log| {
+        (
+            log.block_number.unwrap_or(u64::MAX),
+            log.log_index.unwrap_or(u64::MAX),
+        )
+    });
+
+    let mut latest: BTreeMap<(Address, Address), Approval> = BTreeMap::new();
+    for log in logs {
+        let data = log.data();
+        if data.topics().len() != 3 || data.topics()[0] != events::Approval::SIGNATURE_HASH {
+            continue;
+        }
+        let Ok(event) = events::Approval::decode_log_data(data) else {
+            continue;
+        };
+        if event.owner != owner {
+            continue;
+        }
+        let token = log.address();
+        latest.insert(
+            (token, event.spender),
+            Approval {
+                token,
+                spender: event.spender,
+                amount: event.value,
+                block_number: log.block_number,
+                transaction_hash: log.transaction_hash,
+            },
+        );
+    }
+
+    latest
+        .into_values()
+        .filter(|approval| !approval.amount.is_zero())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{address, LogData};
+
+    const OWNER: Address = address!("00000000000000000000000000000000000000aa");
+    const SPENDER: Address = address!("00000000000000000000000000000000000000bb");
+    const TOKEN_A: Address = address!("000000000000000000000000000000000000000a");
+    const TOKEN_B: Address = address!("000000000000000000000000000000000000000b");
+
+    fn approval_log(
+        token: Address,
+        owner: Address,
+        spender: Address,
+        amount: U256,
+        block: u64,
+        log_index: u64,
+    ) -> Log {
+        Log {
+            inner: alloy::primitives::Log {
+                address: token,
+                data: LogData::new_unchecked(
+                    vec![approval_topic(), owner.into_word(), spender.into_word()],
+                    amount.to_be_bytes_vec().into(),
+                ),
+            },
+            block_number: Some(block),
+            log_index: Some(log_index),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_approval_topic() {
+        assert_eq!(
+            format!("{:#x}", approval_topic()),
+            "0x8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925"
+        );
+    }
+
+    #[test]
+    fn test_latest_approval_wins_in_chain_order() {
+        let logs = vec![
+            // Out of order on purpose: block 20 replaces block 10
+            approval_log(TOKEN_A, OWNER, SPENDER, U256::from(5), 20, 0),
+            approval_log(TOKEN_A, OWNER, SPENDER, U256::MAX, 10, 3),
+            approval_log(TOKEN_B, OWNER, SPENDER, U256::MAX, 10, 1),
+        ];
+        let approvals = latest_approvals(OWNER, &logs);
+        assert_eq!(approvals.len(), 2);
+        assert_eq!(approvals[0].token, TOKEN_A);
+        assert_eq!(approvals[0].amount, U256::from(5));
+        assert_eq!(approvals[0].block_number, Some(20));
+        assert!(!approvals[0].is_unlimited(None));
+        assert_eq!(approvals[1].token, TOKEN_B);
+        assert!(approvals[1].is_unlimited(None));
+    }
+
+    #[test]
+    fn test_revoked_and_foreign_approvals_dropped() {
+        let mut erc721 = approval_log(TOKEN_B, OWNER, SPENDER, U256::ZERO, 5, 0);
+        erc721.inner.data = LogData::new_unchecked(
+            vec![
+                approval_topic(),
+                OWNER.into_word(),
+                SPENDER.into_word(),
+                B256::with_last_byte(7),
+            ],
+            Default::default(),
+        );
+        let logs = vec![
+            approval_log(TOKEN_A, OWNER, SPENDER, U256::MAX, 1, 0),
+            approval_log(TOKEN_A, OWNER, SPENDER, U256::ZERO, 2, 0),
+            approval_log(TOKEN_A, SPENDER, OWNER, U256::MAX, 3, 0),
+            erc721,
+        ];
+        assert!(latest_approvals(OWNER, &logs).is_empty());
+    }
+
+    #[test]
+    fn test_unlimited_threshold() {
+        let uint96_max = (U256::from(1) << 96) - U256::from(1);
+        assert_eq!(UINT96_MAX, uint96_max);
+        assert!(is_unlimited(U256::MAX, None));
+        assert!(is_unlimited(U256::MAX - U256::from(10u64.pow(18)), None));
+        assert!(is_unlimited(uint96_max, None));
+        assert!(!is_unlimited(uint96_max - U256::from(1), None));
+        assert!(!is_unlimited(uint96_max + U256::from(1), None));
+    }
+
+    #[test]
+    fn test_unlimited_against_total_supply() {
+        // ~589 trillion SHIB, 18 decimals: above type(uint96).max
+        let supply = U256::from(589_735_030_408_323u64) * U256::from(10u64.pow(18));
+        assert!(supply > UINT96_MAX);
+
+        // A trillion SHIB is a large but finite approval
+        let trillion = U256::from(10u64.pow(12)) * U256::from(10u64.pow(18));
+        assert!(!is_unlimited(trillion, Some(supply)));
+        assert!(is_unlimited(supply, Some(supply)));
+        assert!(is_unlimited(U256::MAX, Some(supply)));
+
+        // A zero supply (e.g. a failed call decoded as 0) says nothing
+        assert!(!is_unlimited(U256::from(1), Some(U256::ZERO)));
+    }
+}
@@ -1,13 +1,58 @@
 //! Checkpoint system for resumable fetching
 
 use crate::error::{CheckpointError, Result};
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Current checkpoint format version
 const CHECKPOINT_VERSION: u32 = 1;
 
+/// Current multi-chain checkpoint file format version
+const CHECKPOINT_SET_VERSION: u32 = 2;
+
+/// Extension of checkpoint files
+pub const CHECKPOINT_EXTENSION: &str = "checkpoint";
+
+/// Directory default checkpoints are written to
+///
+/// `configured` (`settings.checkpoint_dir`) wins; otherwise `checkpoints/`
+/// under the config directory, which honours `ETHCLI_CONFIG_DIR`.
+pub fn checkpoint_dir(configured: Option<&Path>) -> PathBuf {
+    if let Some(dir) = configured {
+        return dir.to_path_buf();
+    }
+    if let Ok(config_dir) = std::env::var("ETHCLI_CONFIG_DIR") {
+        return PathBuf::from(config_dir).join("checkpoints");
+    }
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("ethcli")
+        .join("checkpoints")
+}
+
+/// Default checkpoint path for a resumable `logs` fetch of `contract`
+///
+/// Checkpoints used to be written to the current directory; a file with the
+/// same name there is still picked up so interrupted fetches resume.
+/// Otherwise the path is inside `dir`.
+pub fn default_checkpoint_path(contract: &str, multichain: bool, dir: &Path) -> PathBuf {
+    let file_name = format!(
+        ".eth-log-fetch-{}{}.{}",
+        &contract[..8.min(contract.len())],
+        if multichain { ".multi" } else { "" },
+        CHECKPOINT_EXTENSION
+    );
+    let legacy = PathBuf::from(&file_name);
+    if legacy.is_file() {
+        return legacy;
+    }
+    dir.join(file_name)
+}
+
 /// Checkpoint data for resuming interrupted fetches
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Checkpoint {
@@ -25,6 +70,9 @@ pub struct Checkpoint {
     pub end_block: Option<u64>,
     /// Completed block ranges (start, end) inclusive
     pub completed_ranges: Vec<(u64, u64)>,
+    /// Block ranges whose last fetch attempt failed (start, end) inclusive
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failed_ranges: Vec<(u64, u64)>,
     /// Last processed block
     pub last_processed_block: u64,
     /// Total logs fetched so far
@@ -52,6 +100,7 @@ impl Checkpoint {
             start_block,
             end_block,
             completed_ranges: Vec::new(),
+            failed_ranges: Vec::new(),
             last_processed_block: start_block,
             total_logs: 0,
             last_updated: current_timestamp(),
@@ -81,23 +130,7 @@ impl Checkpoint {
 
     /// Save to file (atomic write)
     pub fn save(&self, path: &Path) -> Result<()> {
-        let content = serde_json::to_string_pretty(self)
-            .map_err(|e| CheckpointError::WriteError(format!("Serialization failed: {}", e)))?;
-
-        // Write to temp file first with unique name to avoid TOCTOU race
-        let temp_path = path.with_extension(format!("tmp.{}", std::process::id()));
-
-        fs::write(&temp_path, &content)
-            .map_err(|e| CheckpointError::WriteError(format!("Write failed: {}", e)))?;
-
-        // Atomic rename
-        if let Err(e) = fs::rename(&temp_path, path) {
-            // Clean up temp file on failure
-            let _ = fs::remove_file(&temp_path);
-            return Err(CheckpointError::WriteError(format!("Rename failed: {}", e)).into());
-        }
-
-        Ok(())
+        write_atomic(path, self)
     }
 
     /// Mark a block range as completed
@@ -112,34 +145,89 @@ impl Checkpoint {
         self.last_updated = current_timestamp();
 
         // Merge adjacent ranges to keep the list compact
-        self.merge_ranges();
+        self.completed_ranges = normalize_ranges(std::mem::take(&mut self.completed_ranges));
+        if !self.failed_ranges.is_empty() {
+            self.failed_ranges = subtract_range(&self.failed_ranges, (from, to));
+        }
     }
 
-    /// Merge adjacent/overlapping completed ranges
-    fn merge_ranges(&mut self) {
-        if self.completed_ranges.len() < 2 {
-            return;
-        }
+    /// Record a block range whose fetch failed
+    ///
+    /// The range stays in [`remaining_ranges`](Self::remaining_ranges) and is
+    /// dropped from the failed set once a later run completes it.
+    pub fn mark_failed(&mut self, from: u64, to: u64) {
+        self.failed_ranges.push((from, to));
+        self.failed_ranges = normalize_ranges(std::mem::take(&mut self.failed_ranges));
+        self.last_updated = current_timestamp();
+    }
 
-        // Sort by start block
-        self.completed_ranges.sort_by_key(|r| r.0);
+    /// Last block this fetch targets
+    ///
+    /// The end block when one was fixed, otherwise the furthest block seen
+    /// so far (the fetch ran up to "latest" at the time).
+    pub fn target_end(&self) -> u64 {
+        self.end_block.unwrap_or_else(|| {
+            let furthest_completed = self.completed_ranges.last().map_or(0, |r| r.1);
+            self.last_processed_block.max(furthest_completed)
+        })
+    }
 
-        let mut merged = Vec::new();
-        let mut current = self.completed_ranges[0];
+    /// How much of `[start_block, target_end]` the completed ranges cover
+    pub fn coverage(&self) -> Coverage {
+        let (start, end) = (self.start_block, self.target_end());
+        Coverage {
+            start,
+            end,
+            covered_blocks: covered_blocks(&self.completed_ranges, start, end),
+            total_blocks: end.saturating_sub(start).saturating_add(1),
+            gaps: range_gaps(&self.completed_ranges, start, end),
+        }
+    }
 
-        for &(start, end) in self.completed_ranges.iter().skip(1) {
-            if start <= current.1 + 1 {
-                // Overlapping or adjacent - extend current
-                current.1 = current.1.max(end);
-            } else {
-                // Gap - save current and start new
-                merged.push(current);
-                current = (start, end);
-            }
+    /// Fold another checkpoint of the same query into this one
+    ///
+    /// Contract, chain and event filter must match. The block range widens
+    /// to cover both, completed ranges are unioned and failed ranges keep
+    /// only what neither side completed. Returns the number of blocks both
+    /// had completed; when non-zero, `total_logs` counts those logs twice.
+    pub fn merge(&mut self, other: &Checkpoint) -> Result<u64> {
+        self.validate(
+            &other.contract,
+            other.chain_id,
+            other.event_signature.as_deref(),
+        )?;
+
+        let overlap = overlap_blocks(&self.completed_ranges, &other.completed_ranges);
+
+        self.start_block = self.start_block.min(other.start_block);
+        self.end_block = match (self.end_block, other.end_block) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            _ => None,
+        };
+        self.completed_ranges = normalize_ranges(
+            self.completed_ranges
+                .iter()
+                .chain(&other.completed_ranges)
+                .copied(),
+        );
+        let mut failed = normalize_ranges(
+            self.failed_ranges
+                .iter()
+                .chain(&other.failed_ranges)
+                .copied(),
+        );
+        for &completed in &self.completed_ranges {
+            failed = subtract_range(&failed, completed);
+        }
+        self.failed_ranges = failed;
+        self.last_processed_block = self.last_processed_block.max(other.last_processed_block);
+        self.total_logs = self.total_logs.saturating_add(other.total_logs);
+        self.last_updated = self.last_updated.max(other.last_updated);
+        if self.output_path.is_none() {
+            self.output_path.clone_from(&other.output_path);
         }
-        merged.push(current);
 
-        self.completed_ranges = merged;
+        Ok(overlap)
     }
 
     /// Get remaining block ranges to fetch
@@ -235,12 +323,363 @@ impl Checkpoint {
     }
 }
 
+/// Coverage of a checkpoint's target block range
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Coverage {
+    /// First block of the target range
+    pub start: u64,
+    /// Last block of the target range
+    pub end: u64,
+    /// Blocks in the range that have been fetched
+    pub covered_blocks: u64,
+    /// Blocks in the range
+    pub total_blocks: u64,
+    /// Parts of the range not fetched yet
+    pub gaps: Vec<(u64, u64)>,
+}
+
+impl Coverage {
+    /// Fetched share of the range in percent
+    pub fn percent(&self) -> f64 {
+        if self.total_blocks == 0 {
+            return 100.0;
+        }
+        self.covered_blocks as f64 / self.total_blocks as f64 * 100.0
+    }
+
+    /// Whether every block in the range has been fetched
+    pub fn is_complete(&self) -> bool {
+        self.gaps.is_empty()
+    }
+}
+
+/// Sort inclusive block ranges and merge overlapping or adjacent ones
+pub fn normalize_ranges(ranges: impl IntoIterator<Item = (u64, u64)>) -> Vec<(u64, u64)> {
+    let mut ranges: Vec<(u64, u64)> = ranges.into_iter().filter(|(s, e)| s <= e).collect();
+    ranges.sort_unstable();
+
+    let mut merged: Vec<(u64, u64)> = Vec::with_capacity(ranges.len());
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1.saturating_add(1) => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// Remove `cut` from normalized `ranges`
+pub fn subtract_range(ranges: &[(u64, u64)], cut: (u64, u64)) -> Vec<(u64, u64)> {
+    let (cut_start, cut_end) = cut;
+    let mut out = Vec::with_capacity(ranges.len() + 1);
+    for &(start, end) in ranges {
+        if end < cut_start || start > cut_end {
+            out.push((start, end));
+            continue;
+        }
+        if start < cut_start {
+            out.push((start, cut_start - 1));
+        }
+        if end > cut_end {
+            out.push((cut_end + 1, end));
+        }
+    }
+    out
+}
+
+/// Blocks of normalized `ranges` that fall inside `[start, end]`
+pub fn covered_blocks(ranges: &[(u64, u64)], start: u64, end: u64) -> u64 {
+    ranges
+        .iter()
+        .filter_map(|&(s, e)| {
+            let (s, e) = (s.max(start), e.min(end));
+            (s <= e).then(|| e - s + 1)
+        })
+        .fold(0u64, u64::saturating_add)
+}
+
+/// Parts of `[start, end]` not covered by normalized `ranges`
+pub fn range_gaps(ranges: &[(u64, u64)], start: u64, end: u64) -> Vec<(u64, u64)> {
+    if start > end {
+        return Vec::new();
+    }
+    ranges.iter().fold(vec![(start, end)], |gaps, &range| {
+        let mut next = Vec::with_capacity(gaps.len() + 1);
+        for gap in gaps {
+            next.extend(subtract_range(&[gap], range));
+        }
+        next
+    })
+}
+
+/// Blocks covered by both normalized range sets
+pub fn overlap_blocks(a: &[(u64, u64)], b: &[(u64, u64)]) -> u64 {
+    a.iter()
+        .map(|&(s, e)| covered_blocks(b, s, e))
+        .fold(0u64, u64::saturating_add)
+}
+
+/// Per-chain checkpoints stored in a single file
+///
+/// Used when one invocation fetches the same contract on several chains.
+/// Entries are keyed by chain ID.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointSet {
+    /// Format version
+    pub version: u32,
+    /// Checkpoint for each chain, by chain ID
+    pub chains: BTreeMap<u64, Checkpoint>,
+}
+
+impl Default for CheckpointSet {
+    fn default() -> Self {
+        Self {
+            version: CHECKPOINT_SET_VERSION,
+            chains: BTreeMap::new(),
+        }
+    }
+}
+
+impl CheckpointSet {
+    /// Load from file
+    ///
+    /// A single-chain (version 1) checkpoint is accepted and becomes the
+    /// entry for its chain.
+    pub fn load(path: &Path) -> Result<Self> {
+        Self::load_versioned(path).map(|(set, _)| set)
+    }
+
+    /// Load from file, also returning the file's format version
+    fn load_versioned(path: &Path) -> Result<(Self, u32)> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| CheckpointError::ReadError(format!("{}: {}", path.display(), e)))?;
+
+        let value: serde_json::Value = serde_json::from_str(&content)
+            .map_err(|e| CheckpointError::Corrupted(format!("Invalid JSON: {}", e)))?;
+        let version = value
+            .get("version")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(0) as u32;
+
+        match version {
+            CHECKPOINT_VERSION => {
+                let checkpoint: Checkpoint = serde_json::from_value(value)
+                    .map_err(|e| CheckpointError::Corrupted(format!("Invalid JSON: {}", e)))?;
+                let mut set = Self::default();
+                set.chains.insert(checkpoint.chain_id, checkpoint);
+                Ok((set, version))
+            }
+            CHECKPOINT_SET_VERSION => Ok((
+                serde_json::from_value(value)
+                    .map_err(|e| CheckpointError::Corrupted(format!("Invalid JSON: {}", e)))?,
+                version,
+            )),
+            found => Err(CheckpointError::VersionMismatch {
+                expected: CHECKPOINT_SET_VERSION,
+                found,
+            }
+            .into()),
+        }
+    }
+
+    /// Save to file (atomic write)
+    pub fn save(&self, path: &Path) -> Result<()> {
+        write_atomic(path, self)
+    }
+
+    /// Most recent update across all chains
+    pub fn last_updated(&self) -> Option<u64> {
+        self.chains.values().map(|c| c.last_updated).max()
+    }
+
+    /// Fold another set for the same query into this one, chain by chain
+    ///
+    /// Chains present in both are merged with [`Checkpoint::merge`]; chains
+    /// only in `other` are added, provided they fetch the same contract and
+    /// events as this set. Returns the number of blocks completed in both.
+    pub fn merge(&mut self, other: &CheckpointSet) -> Result<u64> {
+        let reference = self.chains.values().next().cloned();
+        let mut overlap = 0u64;
+        for (chain_id, checkpoint) in &other.chains {
+            match self.chains.get_mut(chain_id) {
+                Some(existing) => overlap = overlap.saturating_add(existing.merge(checkpoint)?),
+                None => {
+                    if let Some(reference) = &reference {
+                        reference.validate(
+                            &checkpoint.contract,
+                            reference.chain_id,
+                            checkpoint.event_signature.as_deref(),
+                        )?;
+                    }
+                    self.chains.insert(*chain_id, checkpoint.clone());
+                }
+            }
+        }
+        Ok(overlap)
+    }
+}
+
+/// Result of [`merge_checkpoint_files`]
+#[derive(Debug, Clone)]
+pub struct MergeSummary {
+    /// Merged checkpoints
+    pub set: CheckpointSet,
+    /// Blocks both inputs had completed (their logs are counted twice)
+    pub overlap_blocks: u64,
+    /// Whether the output was written in the single-chain format
+    pub single_chain: bool,
+}
+
+/// Merge checkpoint file `b` into `a` and write the result to `output`
+///
+/// When both inputs are single-chain files for the same chain the result
+/// stays single-chain, so a plain `logs --resume` can keep using it;
+/// otherwise it is written as a multi-chain set.
+pub fn merge_checkpoint_files(a: &Path, b: &Path, output: &Path) -> Result<MergeSummary> {
+    let (mut set, version_a) = CheckpointSet::load_versioned(a)?;
+    let (other, version_b) = CheckpointSet::load_versioned(b)?;
+    let overlap_blocks = set.merge(&other)?;
+
+    let single_chain =
+        version_a == CHECKPOINT_VERSION && version_b == CHECKPOINT_VERSION && set.chains.len() == 1;
+    match set.chains.values().next() {
+        Some(checkpoint) if single_chain => checkpoint.save(output)?,
+        _ => set.save(output)?,
+    }
+
+    Ok(MergeSummary {
+        set,
+        overlap_blocks,
+        single_chain,
+    })
+}
+
+/// A checkpoint file found by [`discover_checkpoints`]
+#[derive(Debug)]
+pub struct CheckpointFile {
+    /// File path
+    pub path: PathBuf,
+    /// Parsed contents, or why they could not be read
+    pub contents: std::result::Result<CheckpointSet, String>,
+    /// File modification time (Unix seconds)
+    pub modified: Option<u64>,
+}
+
+impl CheckpointFile {
+    /// Last update recorded in the file, falling back to its mtime
+    pub fn last_updated(&self) -> Option<u64> {
+        self.contents
+            .as_ref()
+            .ok()
+            .and_then(CheckpointSet::last_updated)
+            .or(self.modified)
+    }
+
+    /// Whether this is an ethcli checkpoint last updated before `cutoff`
+    ///
+    /// Files that don't parse are never stale: a `.checkpoint` file ethcli
+    /// can't read may belong to another tool.
+    pub fn is_stale(&self, cutoff: u64) -> bool {
+        self.contents.is_ok() && self.last_updated().is_some_and(|t| t < cutoff)
+    }
+}
+
+/// Find checkpoint files (`*.checkpoint`) directly inside `dirs`
+///
+/// Missing directories are skipped; a path reachable through several of
+/// the directories is listed once.
+pub fn discover_checkpoints(dirs: &[PathBuf]) -> Vec<CheckpointFile> {
+    let mut seen = std::collections::HashSet::new();
+    let mut files = Vec::new();
+    for dir in dirs {
+        let Ok(entries) = fs::read_dir(dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let is_checkpoint = path.is_file()
+                && path.extension().and_then(|e| e.to_str()) == Some(CHECKPOINT_EXTENSION);
+            if !is_checkpoint {
+                continue;
+            }
+            let key = fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+            if !seen.insert(key) {
+                continue;
+            }
+            let modified = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_secs());
+            files.push(CheckpointFile {
+                contents: CheckpointSet::load(&path).map_err(|e| e.to_string()),
+                path,
+                modified,
+            });
+        }
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    files
+}
+
+/// A [`CheckpointSet`] file shared by concurrent per-chain fetchers
+///
+/// Every update rewrites the whole file, so each chain's progress survives
+/// an interruption of any other.
+#[derive(Debug, Clone)]
+pub struct SharedCheckpoints {
+    path: PathBuf,
+    set: Arc<Mutex<CheckpointSet>>,
+}
+
+impl SharedCheckpoints {
+    /// Open the file at `path`, or start an empty set if it does not exist
+    pub fn open(path: &Path) -> Result<Self> {
+        let set = if path.exists() {
+            CheckpointSet::load(path)?
+        } else {
+            CheckpointSet::default()
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            set: Arc::new(Mutex::new(set)),
+        })
+    }
+
+    /// File path
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Checkpoint for a chain, if one has been saved
+    pub fn get(&self, chain_id: u64) -> Option<Checkpoint> {
+        self.set.lock().chains.get(&chain_id).cloned()
+    }
+
+    /// Store a chain's checkpoint and save the file
+    pub fn update(&self, checkpoint: &Checkpoint) -> Result<()> {
+        let mut set = self.set.lock();
+        set.chains.insert(checkpoint.chain_id, checkpoint.clone());
+        set.save(&self.path)
+    }
+
+    /// Drop a chain's checkpoint and save the file
+    pub fn remove(&self, chain_id: u64) -> Result<()> {
+        let mut set = self.set.lock();
+        set.chains.remove(&chain_id);
+        set.save(&self.path)
+    }
+}
+
 /// Checkpoint manager for automatic saving
 pub struct CheckpointManager {
     /// Current checkpoint
     checkpoint: Checkpoint,
     /// File path
     path: PathBuf,
+    /// Shared multi-chain file (saves go here instead of `path`)
+    shared: Option<SharedCheckpoints>,
     /// Save interval (number of ranges)
     save_interval: usize,
     /// Ranges since last save
@@ -253,6 +692,7 @@ impl CheckpointManager {
         Self {
             checkpoint,
             path,
+            shared: None,
             save_interval: 10,
             ranges_since_save: 0,
         }
@@ -284,6 +724,34 @@ impl CheckpointManager {
         Ok(Self::new(checkpoint, path.to_path_buf()))
     }
 
+    /// Load or create this chain's entry in a shared checkpoint file
+    pub fn load_or_create_shared(
+        shared: &SharedCheckpoints,
+        contract: &str,
+        chain_id: u64,
+        event_signature: Option<&str>,
+        start_block: u64,
+        end_block: Option<u64>,
+    ) -> Result<Self> {
+        let checkpoint = match shared.get(chain_id) {
+            Some(loaded) => {
+                loaded.validate(contract, chain_id, event_signature)?;
+                loaded
+            }
+            None => Checkpoint::new(
+                contract.to_string(),
+                chain_id,
+                event_signature.map(String::from),
+                start_block,
+                end_block,
+            ),
+        };
+
+        let mut manager = Self::new(checkpoint, shared.path().to_path_buf());
+        manager.shared = Some(shared.clone());
+        Ok(manager)
+    }
+
     /// Mark range as completed
     pub fn mark_completed(&mut self, from: u64, to: u64, logs_count: u64) -> Result<()> {
         self.checkpoint.mark_completed(from, to, logs_count);
@@ -297,9 +765,17 @@ impl CheckpointManager {
         Ok(())
     }
 
+    /// Record a failed range (saved with the next save)
+    pub fn mark_failed(&mut self, from: u64, to: u64) {
+        self.checkpoint.mark_failed(from, to);
+    }
+
     /// Force save
     pub fn save(&mut self) -> Result<()> {
-        self.checkpoint.save(&self.path)?;
+        match &self.shared {
+            Some(shared) => shared.update(&self.checkpoint)?,
+            None => self.checkpoint.save(&self.path)?,
+        }
         self.ranges_since_save = 0;
         Ok(())
     }
@@ -334,8 +810,11 @@ impl CheckpointManager {
         &self.checkpoint
     }
 
-    /// Delete checkpoint file
+    /// Delete checkpoint file (or this chain's entry in a shared file)
     pub fn delete(&self) -> Result<()> {
+        if let Some(shared) = &self.shared {
+            return shared.remove(self.checkpoint.chain_id);
+        }
         if self.path.exists() {
             fs::remove_file(&self.path)
                 .map_err(|e| CheckpointError::WriteError(format!("Delete failed: {}", e)))?;
@@ -344,6 +823,33 @@ impl CheckpointManager {
     }
 }
 
+/// Serialize `value` and write it to `path` atomically
+fn write_atomic<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let content = serde_json::to_string_pretty(value)
+        .map_err(|e| CheckpointError::WriteError(format!("Serialization failed: {}", e)))?;
+
+    // The default checkpoint directory may not exist yet
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .map_err(|e| CheckpointError::WriteError(format!("{}: {}", parent.display(), e)))?;
+    }
+
+    // Write to temp file first with unique name to avoid TOCTOU race
+    let temp_path = path.with_extension(format!("tmp.{}", std::process::id()));
+
+    fs::write(&temp_path, &content)
+        .map_err(|e| CheckpointError::WriteError(format!("Write failed: {}", e)))?;
+
+    // Atomic rename
+    if let Err(e) = fs::rename(&temp_path, path) {
+        // Clean up temp file on failure
+        let _ = fs::remove_file(&temp_path);
+        return Err(CheckpointError::WriteError(format!("Rename failed: {}", e)).into());
+    }
+
+    Ok(())
+}
+
 /// Get current timestamp (delegated to utils for consistency)
 fn current_timestamp() -> u64 {
     crate::utils::unix_timestamp_secs()
@@ -429,4 +935,296 @@ mod tests {
         assert_eq!(loaded.total_logs, 50);
         assert_eq!(loaded.completed_ranges, vec![(0, 100)]);
     }
+
+    #[test]
+    fn test_shared_checkpoints_per_chain() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("multi.checkpoint");
+        let shared = SharedCheckpoints::open(&path).unwrap();
+
+        let mut eth =
+            CheckpointManager::load_or_create_shared(&shared, "0x1234", 1, None, 0, Some(1000))
+                .unwrap();
+        let mut arb =
+            CheckpointManager::load_or_create_shared(&shared, "0x1234", 42161, None, 500, None)
+                .unwrap();
+        eth.mark_completed(0, 100, 5).unwrap();
+        eth.save().unwrap();
+        arb.mark_completed(500, 600, 7).unwrap();
+        arb.save().unwrap();
+
+        let set = CheckpointSet::load(&path).unwrap();
+        assert_eq!(set.version, CHECKPOINT_SET_VERSION);
+        assert_eq!(set.chains[&1].completed_ranges, vec![(0, 100)]);
+        assert_eq!(set.chains[&42161].total_logs, 7);
+
+        // Reopening resumes each chain independently
+        let reopened = SharedCheckpoints::open(&path).unwrap();
+        let eth =
+            CheckpointManager::load_or_create_shared(&reopened, "0x1234", 1, None, 0, Some(1000))
+                .unwrap();
+        assert_eq!(eth.remaining_ranges(1000), vec![(101, 1000)]);
+        assert!(
+            CheckpointManager::load_or_create_shared(&reopened, "0xabcd", 1, None, 0, None)
+                .is_err()
+        );
+
+        eth.delete().unwrap();
+        assert!(!CheckpointSet::load(&path).unwrap().chains.contains_key(&1));
+    }
+
+    #[test]
+    fn test_checkpoint_set_migrates_single_chain_file() {
+        let mut cp = Checkpoint::new("0x1234".to_string(), 10, None, 0, None);
+        cp.mark_completed(0, 50, 3);
+
+        let temp = NamedTempFile::new().unwrap();
+        cp.save(temp.path()).unwrap();
+
+        let set = CheckpointSet::load(temp.path()).unwrap();
+        assert_eq!(set.chains[&10].total_logs, 3);
+
+        fs::write(temp.path(), r#"{"version": 9, "chains": {}}"#).unwrap();
+        assert!(CheckpointSet::load(temp.path()).is_err());
+    }
+
+    #[test]
+    fn test_normalize_ranges() {
+        assert_eq!(normalize_ranges([]), Vec::<(u64, u64)>::new());
+        assert_eq!(
+            normalize_ranges([(50, 60), (0, 10), (11, 20), (5, 8), (30, 40)]),
+            vec![(0, 20), (30, 40), (50, 60)]
+        );
+        // Inverted ranges are dropped, u64::MAX does not overflow
+        assert_eq!(
+            normalize_ranges([(10, 5), (u64::MAX - 1, u64::MAX), (0, u64::MAX - 2)]),
+            vec![(0, u64::MAX)]
+        );
+    }
+
+    #[test]
+    fn test_subtract_range() {
+        let ranges = [(0, 100), (200, 300)];
+        assert_eq!(
+            subtract_range(&ranges, (50, 250)),
+            vec![(0, 49), (251, 300)]
+        );
+        assert_eq!(
+            subtract_range(&ranges, (10, 20)),
+            vec![(0, 9), (21, 100), (200, 300)]
+        );
+        assert_eq!(subtract_range(&ranges, (0, 300)), vec![]);
+        assert_eq!(subtract_range(&ranges, (101, 199)), ranges.to_vec());
+        assert_eq!(subtract_range(&[(0, 0)], (0, 0)), vec![]);
+    }
+
+    #[test]
+    fn test_coverage_and_gaps() {
+        let ranges = [(100, 199), (300, 399)];
+        assert_eq!(covered_blocks(&ranges, 0, 999), 200);
+        assert_eq!(covered_blocks(&ranges, 150, 349), 100);
+        assert_eq!(covered_blocks(&ranges, 200, 299), 0);
+        assert_eq!(
+            range_gaps(&ranges, 0, 999),
+            vec![(0, 99), (200, 299), (400, 999)]
+        );
+        assert_eq!(range_gaps(&ranges, 100, 199), vec![]);
+        assert_eq!(range_gaps(&ranges, 10, 5), vec![]);
+
+        let mut cp = Checkpoint::new("0x1234".to_string(), 1, None, 0, Some(999));
+        cp.mark_completed(0, 499, 1);
+        let coverage = cp.coverage();
+        assert_eq!(coverage.total_blocks, 1000);
+        assert_eq!(coverage.covered_blocks, 500);
+        assert_eq!(coverage.gaps, vec![(500, 999)]);
+        assert!((coverage.percent() - 50.0).abs() < f64::EPSILON);
+        assert!(!coverage.is_complete());
+
+        cp.mark_completed(500, 999, 1);
+        assert!(cp.coverage().is_complete());
+    }
+
+    #[test]
+    fn test_coverage_open_ended_uses_furthest_block() {
+        let mut cp = Checkpoint::new("0x1234".to_string(), 1, None, 100, None);
+        cp.mark_completed(100, 199, 1);
+        cp.mark_completed(300, 399, 1);
+
+        let coverage = cp.coverage();
+        assert_eq!((coverage.start, coverage.end), (100, 399));
+        assert_eq!(coverage.gaps, vec![(200, 299)]);
+    }
+
+    #[test]
+    fn test_overlap_blocks() {
+        assert_eq!(overlap_blocks(&[(0, 100)], &[(50, 150)]), 51);
+        assert_eq!(overlap_blocks(&[(0, 10), (20, 30)], &[(5, 25)]), 12);
+        assert_eq!(overlap_blocks(&[(0, 10)], &[(11, 20)]), 0);
+    }
+
+    #[test]
+    fn test_failed_ranges_cleared_by_completion() {
+        let mut cp = Checkpoint::new("0x1234".to_string(), 1, None, 0, Some(1000));
+        cp.mark_failed(100, 199);
+        cp.mark_failed(200, 299);
+        assert_eq!(cp.failed_ranges, vec![(100, 299)]);
+
+        cp.mark_completed(150, 249, 4);
+        assert_eq!(cp.failed_ranges, vec![(100, 149), (250, 299)]);
+    }
+
+    #[test]
+    fn test_failed_ranges_omitted_when_empty() {
+        let cp = Checkpoint::new("0x1234".to_string(), 1, None, 0, None);
+        let json = serde_json::to_string(&cp).unwrap();
+        assert!(!json.contains("failed_ranges"));
+
+        // Files written before failed ranges were tracked still load
+        let loaded: Checkpoint = serde_json::from_str(&json).unwrap();
+        assert!(loaded.failed_ranges.is_empty());
+    }
+
+    #[test]
+    fn test_checkpoint_merge() {
+        let mut a = Checkpoint::new("0xABCD".to_string(), 1, None, 0, Some(1000));
+        a.mark_completed(0, 400, 10);
+        a.mark_failed(401, 500);
+
+        let mut b = Checkpoint::new("0xabcd".to_string(), 1, None, 300, Some(2000));
+        b.mark_completed(300, 600, 5);
+        b.mark_failed(601, 700);
+
+        let overlap = a.merge(&b).unwrap();
+        assert_eq!(overlap, 101);
+        assert_eq!((a.start_block, a.end_block), (0, Some(2000)));
+        assert_eq!(a.completed_ranges, vec![(0, 600)]);
+        assert_eq!(a.failed_ranges, vec![(601, 700)]);
+        assert_eq!(a.total_logs, 15);
+
+        let open_ended = Checkpoint::new("0xabcd".to_string(), 1, None, 0, None);
+        a.merge(&open_ended).unwrap();
+        assert_eq!(a.end_block, None);
+    }
+
+    #[test]
+    fn test_checkpoint_merge_rejects_different_query() {
+        let mut a = Checkpoint::new("0x1234".to_string(), 1, None, 0, None);
+        let other_contract = Checkpoint::new("0x5678".to_string(), 1, None, 0, None);
+        let other_chain = Checkpoint::new("0x1234".to_string(), 10, None, 0, None);
+        let other_event = Checkpoint::new(
+            "0x1234".to_string(),
+            1,
+            Some("Transfer(address,address,uint256)".to_string()),
+            0,
+            None,
+        );
+
+        assert!(a.merge(&other_contract).is_err());
+        assert!(a.merge(&other_chain).is_err());
+        assert!(a.merge(&other_event).is_err());
+    }
+
+    #[test]
+    fn test_merge_checkpoint_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let (a, b, c, out) = (
+            dir.path().join("a.checkpoint"),
+            dir.path().join("b.checkpoint"),
+            dir.path().join("c.checkpoint"),
+            dir.path().join("out.checkpoint"),
+        );
+
+        let mut cp_a = Checkpoint::new("0x1234".to_string(), 1, None, 0, Some(1000));
+        cp_a.mark_completed(0, 499, 2);
+        cp_a.save(&a).unwrap();
+        let mut cp_b = Checkpoint::new("0x1234".to_string(), 1, None, 0, Some(1000));
+        cp_b.mark_completed(500, 1000, 3);
+        cp_b.save(&b).unwrap();
+
+        let summary = merge_checkpoint_files(&a, &b, &out).unwrap();
+        assert!(summary.single_chain);
+        assert_eq!(summary.overlap_blocks, 0);
+        let merged = Checkpoint::load(&out).unwrap();
+        assert!(merged.coverage().is_complete());
+        assert_eq!(merged.total_logs, 5);
+
+        // A second chain turns the result into a multi-chain set
+        let mut set = CheckpointSet::default();
+        set.chains
+            .insert(10, Checkpoint::new("0x1234".to_string(), 10, None, 0, None));
+        set.save(&c).unwrap();
+        let summary = merge_checkpoint_files(&a, &c, &out).unwrap();
+        assert!(!summary.single_chain);
+        assert_eq!(CheckpointSet::load(&out).unwrap().chains.len(), 2);
+
+        // Different contract on a new chain is still rejected
+        set.chains.insert(
+            137,
+            Checkpoint::new("0x9999".to_string(), 137, None, 0, None),
+        );
+        set.save(&c).unwrap();
+        assert!(merge_checkpoint_files(&a, &c, &out).is_err());
+    }
+
+    #[test]
+    fn test_discover_checkpoints() {
+        let dir = tempfile::tempdir().unwrap();
+        Checkpoint::new("0x1234".to_string(), 1, None, 0, None)
+            .save(&dir.path().join("b.checkpoint"))
+            .unwrap();
+        fs::write(dir.path().join("a.checkpoint"), "not json").unwrap();
+        fs::write(dir.path().join("notes.txt"), "").unwrap();
+
+        let dirs = vec![
+            dir.path().to_path_buf(),
+            dir.path().join("."),
+            dir.path().join("missing"),
+        ];
+        let files = discover_checkpoints(&dirs);
+        assert_eq!(files.len(), 2);
+        assert!(files[0].contents.is_err());
+        assert!(files[0].last_updated().is_some());
+        assert_eq!(files[1].contents.as_ref().unwrap().chains.len(), 1);
+    }
+
+    #[test]
+    fn test_stale_checkpoints_skip_foreign_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut old = Checkpoint::new("0x1234".to_string(), 1, None, 0, None);
+        old.last_updated = 1_000;
+        old.save(&dir.path().join("old.checkpoint")).unwrap();
+        let mut fresh = Checkpoint::new("0x1234".to_string(), 1, None, 0, None);
+        fresh.last_updated = 5_000;
+        fresh.save(&dir.path().join("fresh.checkpoint")).unwrap();
+        fs::write(dir.path().join("foreign.checkpoint"), "not json").unwrap();
+
+        let stale: Vec<PathBuf> = discover_checkpoints(&[dir.path().to_path_buf()])
+            .into_iter()
+            .filter(|f| f.is_stale(u64::MAX))
+            .map(|f| f.path)
+            .collect();
+        assert_eq!(stale.len(), 2);
+        assert!(stale.iter().all(|p| !p.ends_with("foreign.checkpoint")));
+
+        let stale: Vec<PathBuf> = discover_checkpoints(&[dir.path().to_path_buf()])
+            .into_iter()
+            .filter(|f| f.is_stale(2_000))
+            .map(|f| f.path)
+            .collect();
+        assert_eq!(stale, [dir.path().join("old.checkpoint")]);
+    }
+
+    #[test]
+    fn test_default_checkpoint_path() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(
+            default_checkpoint_path("0x1234567890", false, dir.path()),
+            dir.path().join(".eth-log-fetch-0x123456.checkpoint")
+        );
+        assert_eq!(
+            default_checkpoint_path("0x12", true, dir.path()),
+            dir.path().join(".eth-log-fetch-0x12.multi.checkpoint")
+        );
+        assert_eq!(checkpoint_dir(Some(dir.path())), dir.path().to_path_buf());
+    }
 }
@@ -1,19 +1,35 @@
 //! Account-related commands
 //!
 //! Query balances, transactions, and token transfers for addresses
+//!
+//! `txs` and `erc20` produce [`TxRow`]s and [`Erc20TransferRow`]s and write
+//! them through [`RowWriter`], page by page.
 
 use super::OutputFormat;
-use crate::config::Chain;
-use crate::etherscan::Client;
+use crate::config::{AddressBook, Chain};
+use crate::etherscan::{empty_page_ok, Client, PageCursor};
+use crate::output::{parse_output_path, Row, RowFormat, RowWriter};
 use crate::rpc::get_rpc_endpoint;
 use crate::rpc::multicall::{selectors, MulticallBuilder, MULTICALL3_ADDRESS};
 use crate::utils::address::{is_ens_name, resolve_from_book};
 use crate::utils::format::format_wei_to_eth;
-use alloy::primitives::Address;
+use crate::utils::parse_duration_string;
+use crate::watch::{
+    parse_native_amount, parse_token_amount, AccountWatch, Backoff, Snapshot, WatchState,
+    WatchedToken, WatchedTx,
+};
+use alloy::consensus::Transaction as _;
+use alloy::eips::BlockId;
+use alloy::primitives::utils::format_units;
+use alloy::primitives::{Address, U256};
 use alloy::providers::Provider;
-use clap::Subcommand;
+use clap::{Args, Subcommand, ValueEnum};
+use foundry_block_explorers::account::{ERC20TokenTransferEvent, NormalTransaction, Sort};
+use serde::Serialize;
 use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::time::Duration;
 
 /// Resolve an address from label, ENS name, or raw address
 /// Checks address book first, then tries ENS resolution
@@ -76,6 +92,11 @@ pub enum AccountCommands {
     },
 
     /// Get transaction history for an address
+    ///
+    /// Columns: hash, blockNumber, timeStamp, dateTime, from, to, value,
+    /// valueEth, gas, gasPrice, gasPriceGwei, gasUsed, methodId,
+    /// functionName, contractAddress, isError, txreceipt_status,
+    /// confirmations, input
     Txs {
         /// Address to query (hex, ENS name, or address book label)
         #[arg(value_name = "ADDRESS")]
@@ -89,13 +110,21 @@ pub enum AccountCommands {
         #[arg(long, default_value = "50")]
         limit: u64,
 
+        /// Fetch every page from --page on, writing each as it arrives
+        #[arg(long)]
+        all: bool,
+
         /// Sort order (asc, desc)
         #[arg(long, default_value = "desc")]
         sort: String,
 
-        /// Output format (json, table/pretty)
-        #[arg(long, short, value_enum, default_value = "table")]
-        output: OutputFormat,
+        /// Output format (table, json, ndjson, csv)
+        #[arg(long, short = 'o', value_enum, default_value = "table")]
+        format: RowFormat,
+
+        /// Output file path (stdout if not specified)
+        #[arg(long, short = 'O', value_name = "FILE", value_parser = parse_output_path)]
+        output: Option<PathBuf>,
     },
 
     /// Get internal transactions for an address
@@ -117,6 +146,10 @@ pub enum AccountCommands {
     },
 
     /// Get ERC20 token transfers for an address
+    ///
+    /// Columns: hash, blockNumber, timeStamp, dateTime, from, to,
+    /// tokenAddress, tokenName, tokenSymbol, tokenDecimal, value,
+    /// valueFormatted
     Erc20 {
         /// Address to query
         address: String,
@@ -133,9 +166,17 @@ pub enum AccountCommands {
         #[arg(long, default_value = "50")]
         limit: u64,
 
-        /// Output format (json, table/pretty)
-        #[arg(long, short, value_enum, default_value = "table")]
-        output: OutputFormat,
+        /// Fetch every page from --page on, writing each as it arrives
+        #[arg(long)]
+        all: bool,
+
+        /// Output format (table, json, ndjson, csv)
+        #[arg(long, short = 'o', value_enum, default_value = "table")]
+        format: RowFormat,
+
+        /// Output file path (stdout if not specified)
+        #[arg(long, short = 'O', value_name = "FILE", value_parser = parse_output_path)]
+        output: Option<PathBuf>,
     },
 
     /// Get ERC721 (NFT) transfers for an address
@@ -182,6 +223,30 @@ pub enum AccountCommands {
         output: OutputFormat,
     },
 
+    /// Look up the address book label for an address
+    Label {
+        /// Address to look up (0x...)
+        #[arg(value_name = "ADDRESS")]
+        address: String,
+
+        /// Output format (json, table/pretty)
+        #[arg(long, short, value_enum, default_value = "table")]
+        output: OutputFormat,
+    },
+
+    /// Watch balances and transactions, printing changes as NDJSON
+    ///
+    /// Polls the native balance, the --token balances and new transactions,
+    /// and prints one JSON line per change. Changes that cross a threshold
+    /// are alerts: they are sent to --notify-cmd as JSON on stdin.
+    /// Thresholds are measured from the balance at the last alert, and state
+    /// is saved between polls so a restart doesn't alert again.
+    ///
+    /// Examples:
+    ///   ethcli account watch 0x... --interval 30s --min-native-change 0.5eth --notify-cmd ./alert.sh
+    ///   ethcli account watch treasury --token 0xA0b8...eB48 --min-change 1000 --tx-source etherscan
+    Watch(WatchArgs),
+
     /// Get blocks mined/validated by an address
     MinedBlocks {
         /// Address to query
@@ -201,14 +266,78 @@ pub enum AccountCommands {
     },
 }
 
+/// Arguments for `account watch`
+#[derive(Args)]
+pub struct WatchArgs {
+    /// Address to watch (hex, ENS name, or address book label)
+    #[arg(value_name = "ADDRESS")]
+    pub address: String,
+
+    /// Time between polls (e.g., 30s, 5m)
+    #[arg(long, default_value = "30s", value_name = "DURATION")]
+    pub interval: String,
+
+    /// Native balance change that alerts (e.g., 0.5eth, 0.5, 100gwei)
+    #[arg(long, value_name = "AMOUNT", value_parser = parse_native_amount)]
+    pub min_native_change: Option<U256>,
+
+    /// ERC20 token to watch (can be repeated)
+    #[arg(long = "token", value_name = "ADDRESS")]
+    pub tokens: Vec<Address>,
+
+    /// Token balance change that alerts, in whole tokens (can be repeated)
+    ///
+    /// Given once, it applies to every --token; given several times, the
+    /// Nth value belongs to the Nth --token. Tokens without one alert on
+    /// any change.
+    #[arg(long = "min-change", value_name = "AMOUNT", requires = "tokens")]
+    pub min_changes: Vec<String>,
+
+    /// Where new transactions come from
+    #[arg(long, value_enum, default_value = "blocks")]
+    pub tx_source: WatchTxSource,
+
+    /// Command run for each alert, with the event JSON on stdin (via `sh -c`)
+    #[arg(long, value_name = "COMMAND")]
+    pub notify_cmd: Option<String>,
+
+    /// State file (defaults to account_watch.json in the config directory)
+    #[arg(long, value_name = "FILE")]
+    pub state: Option<PathBuf>,
+
+    /// Stop after this many polls (runs until interrupted by default)
+    #[arg(long, value_name = "COUNT")]
+    pub cycles: Option<u64>,
+}
+
+/// Transaction source for `account watch`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum WatchTxSource {
+    /// Scan each new block over RPC (up to the last 100 blocks per poll)
+    Blocks,
+    /// Etherscan's transaction list (fewer RPC calls; may lag a few blocks)
+    Etherscan,
+    /// Don't watch transactions
+    None,
+}
+
 pub async fn handle(
     action: &AccountCommands,
     chain: Chain,
     api_key: Option<String>,
     quiet: bool,
 ) -> anyhow::Result<()> {
+    // Address book lookups are local and need no Etherscan client
+    if let AccountCommands::Label { address, output } = action {
+        return handle_label(address, *output);
+    }
+
     let client = Client::new(chain, api_key)?;
 
+    if let AccountCommands::Watch(args) = action {
+        return handle_watch(args, chain, &client, quiet).await;
+    }
+
     match action {
         AccountCommands::Info { address, output } => {
             let addr = Address::from_str(address)
@@ -452,7 +581,9 @@ pub async fn handle(
             address,
             page,
             limit,
+            all,
             sort,
+            format,
             output,
         } => {
             let addr = Address::from_str(address)
@@ -463,79 +594,19 @@ pub async fn handle(
                 let _ = std::io::stderr().flush();
             }
 
-            let params = foundry_block_explorers::account::TxListParams {
-                start_block: 0,
-                end_block: 99999999,
-                page: *page,
-                offset: *limit,
-                sort: if sort == "asc" {
-                    foundry_block_explorers::account::Sort::Asc
-                } else {
-                    foundry_block_explorers::account::Sort::Desc
-                },
-            };
-
-            let txs = client.get_transactions(&addr, Some(params)).await?;
+            let sort = if sort == "asc" { Sort::Asc } else { Sort::Desc };
+            let mut writer = RowWriter::<TxRow>::new(*format, output.as_deref())?;
+            client
+                .stream_transactions(&addr, page_cursor(*page, *limit, *all), sort, |txs| {
+                    let rows: Vec<TxRow> = txs.iter().map(TxRow::from).collect();
+                    writer.write_rows(&rows)?;
+                    writer.flush()
+                })
+                .await?;
+            writer.finalize()?;
 
-            if output.is_json() {
-                // Manually construct JSON to avoid GenesisOption serialization issues
-                let json_txs: Vec<serde_json::Value> = txs
-                    .iter()
-                    .map(|tx| {
-                        serde_json::json!({
-                            "hash": format!("{:#x}", tx.hash.value().unwrap_or_default()),
-                            "blockNumber": tx.block_number.as_number().map(|n| n.to::<u64>()),
-                            "timeStamp": tx.time_stamp,
-                            "dateTime": format_timestamp(&tx.time_stamp),
-                            "from": tx.from.value().map(|a| format!("{:#x}", a)),
-                            "to": tx.to.map(|a| format!("{:#x}", a)),
-                            "value": tx.value.to_string(),
-                            "valueEth": format_wei_to_eth(&tx.value.to_string()),
-                            "gas": tx.gas.to_string(),
-                            "gasPrice": tx.gas_price.map(|p| p.to_string()),
-                            "gasPriceGwei": tx.gas_price.map(|p| format_wei_to_gwei(&p.to_string())),
-                            "gasUsed": tx.gas_used.to_string(),
-                            "input": &tx.input,
-                            "methodId": tx.method_id.as_ref(),
-                            "functionName": tx.function_name.as_ref(),
-                            "contractAddress": tx.contract_address.map(|a| format!("{:#x}", a)),
-                            "isError": &tx.is_error,
-                            "txreceipt_status": &tx.tx_receipt_status,
-                            "confirmations": tx.confirmations,
-                        })
-                    })
-                    .collect();
-                println!("{}", serde_json::to_string_pretty(&json_txs)?);
-            } else {
-                println!("Transactions for {}", address);
-                println!("{}", "─".repeat(80));
-                for tx in txs.iter().take(20) {
-                    let value_eth = format_wei_to_eth(&tx.value.to_string());
-                    let status = if tx.is_error == "0" { "✓" } else { "✗" };
-                    let to_addr = tx
-                        .to
-                        .map(|a| format!("{:#x}", a))
-                        .unwrap_or_else(|| "contract creation".to_string());
-                    let hash_str = format!("{:#x}", tx.hash.value().unwrap_or_default());
-                    let block_num = tx
-                        .block_number
-                        .as_number()
-                        .map(|n| n.to::<u64>())
-                        .unwrap_or(0);
-                    println!(
-                        "{} {} | Block {} | {} {} → {}",
-                        status,
-                        &hash_str[..hash_str.len().min(12)],
-                        block_num,
-                        value_eth,
-                        chain.native_symbol(),
-                        truncate_addr(&to_addr)
-                    );
-                }
-                if txs.len() > 20 {
-                    println!("... and {} more", txs.len() - 20);
-                }
-                // Add explorer link
+            finish_rows(&writer, output.as_deref(), "transactions", quiet);
+            if *format == RowFormat::Table && output.is_none() {
                 if let Some(explorer) = chain.explorer_url() {
                     println!("\nExplorer: {}/address/{}", explorer, address);
                 }
@@ -605,6 +676,8 @@ pub async fn handle(
             token,
             page,
             limit,
+            all,
+            format,
             output,
         } => {
             let addr = Address::from_str(address)
@@ -625,48 +698,23 @@ pub async fn handle(
                 foundry_block_explorers::account::TokenQueryOption::ByAddress(addr)
             };
 
-            let params = foundry_block_explorers::account::TxListParams {
-                start_block: 0,
-                end_block: 99999999,
-                page: *page,
-                offset: *limit,
-                sort: foundry_block_explorers::account::Sort::Desc,
-            };
-
-            let transfers = client
-                .get_erc20_token_transfer_events(query, Some(params))
+            let mut writer = RowWriter::<Erc20TransferRow>::new(*format, output.as_deref())?;
+            client
+                .stream_erc20_transfers(
+                    query,
+                    page_cursor(*page, *limit, *all),
+                    Sort::Desc,
+                    |transfers| {
+                        let rows: Vec<Erc20TransferRow> =
+                            transfers.iter().map(Erc20TransferRow::from).collect();
+                        writer.write_rows(&rows)?;
+                        writer.flush()
+                    },
+                )
                 .await?;
+            writer.finalize()?;
 
-            if output.is_json() {
-                println!("{}", serde_json::to_string_pretty(&transfers)?);
-            } else {
-                println!("ERC20 Transfers for {}", address);
-                println!("{}", "─".repeat(80));
-                for tx in transfers.iter().take(20) {
-                    let symbol = &tx.token_symbol;
-                    let to_addr = tx
-                        .to
-                        .as_ref()
-                        .map(|a| format!("{:#x}", a))
-                        .unwrap_or_else(|| "n/a".to_string());
-                    let block_num = tx
-                        .block_number
-                        .as_number()
-                        .map(|n| n.to::<u64>())
-                        .unwrap_or(0);
-                    println!(
-                        "Block {} | {} {} | {} → {}",
-                        block_num,
-                        tx.value,
-                        symbol,
-                        truncate_addr(&format!("{:#x}", tx.from)),
-                        truncate_addr(&to_addr)
-                    );
-                }
-                if transfers.len() > 20 {
-                    println!("... and {} more", transfers.len() - 20);
-                }
-            }
+            finish_rows(&writer, output.as_deref(), "transfers", quiet);
         }
 
         AccountCommands::Erc721 {
@@ -848,11 +896,333 @@ pub async fn handle(
                 }
             }
         }
+
+        // Handled before the Etherscan client is created
+        AccountCommands::Label { .. } => {}
+
+        // Handled before dispatch
+        AccountCommands::Watch(_) => {}
+    }
+
+    Ok(())
+}
+
+/// Print the address book entry saved for an address
+fn handle_label(address: &str, output: OutputFormat) -> anyhow::Result<()> {
+    let addr = Address::from_str(address).map_err(|e| anyhow::anyhow!("Invalid address: {}", e))?;
+
+    let book = AddressBook::load_default();
+    let (label, entry) = book.find_by_address(&addr).ok_or_else(|| {
+        anyhow::anyhow!(
+            "No address book label for {:#x}. Use 'ethcli address add' to save one.",
+            addr
+        )
+    })?;
+
+    if output.is_json() {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "address": format!("{:#x}", addr),
+                "label": label,
+                "description": entry.description,
+                "tags": entry.tags,
+                "chain": entry.chain,
+            }))?
+        );
+    } else {
+        super::address::print_entry(label, entry);
+    }
+
+    Ok(())
+}
+
+/// Blocks scanned per poll by `--tx-source blocks`
+const WATCH_MAX_SCAN_BLOCKS: u64 = 100;
+
+/// How long a notify command may run
+const NOTIFY_TIMEOUT: Duration = Duration::from_secs(30);
+
+async fn handle_watch(
+    args: &WatchArgs,
+    chain: Chain,
+    client: &Client,
+    quiet: bool,
+) -> anyhow::Result<()> {
+    let (address, label) = resolve_address(&args.address, chain, quiet).await?;
+    let interval_secs = parse_duration_string(&args.interval)?;
+    if interval_secs <= 0.0 {
+        anyhow::bail!("--interval must be positive");
+    }
+    let interval = Duration::from_secs_f64(interval_secs);
+    if args.min_changes.len() > 1 && args.min_changes.len() != args.tokens.len() {
+        anyhow::bail!(
+            "Got {} --min-change values for {} --token(s); give one for all tokens or one per token",
+            args.min_changes.len(),
+            args.tokens.len()
+        );
+    }
+
+    let endpoint = get_rpc_endpoint(chain)?;
+    let provider = endpoint.provider();
+    let watch = AccountWatch {
+        chain,
+        address,
+        min_native_change: args.min_native_change.unwrap_or_default(),
+        tokens: watched_tokens(&provider, &args.tokens, &args.min_changes).await?,
+    };
+
+    let state_path = args.state.clone().unwrap_or_else(WatchState::default_path);
+    let mut state = WatchState::load(&state_path)?;
+    let mut backoff = Backoff::new(interval);
+
+    if !quiet {
+        eprintln!(
+            "Watching {}{:#x} on {} every {} (state in {})",
+            label.map(|l| format!("{} ", l)).unwrap_or_default(),
+            address,
+            chain.display_name(),
+            args.interval,
+            state_path.display()
+        );
+    }
+
+    let mut cycle = 0u64;
+    loop {
+        cycle += 1;
+        let last_block = state.account(chain, address).block;
+        let delay = match poll_account(&provider, client, &watch, last_block, args.tx_source).await
+        {
+            Ok((snapshot, txs)) => {
+                backoff.success();
+                let events = watch.observe(state.account(chain, address), &snapshot, &txs);
+                if let Err(e) = state.save(&state_path) {
+                    eprintln!("Warning: failed to save watch state: {}", e);
+                }
+                for event in &events {
+                    let line = serde_json::to_string(event)?;
+                    println!("{}", line);
+                    if let (true, Some(cmd)) = (event.alert, &args.notify_cmd) {
+                        if let Err(e) = notify(cmd, &line).await {
+                            eprintln!("Warning: notify command failed: {}", e);
+                        }
+                    }
+                }
+                interval
+            }
+            Err(e) => {
+                let delay = backoff.failure();
+                eprintln!(
+                    "Poll failed ({} in a row): {:#}; retrying in {}s",
+                    backoff.failures(),
+                    e,
+                    delay.as_secs()
+                );
+                delay
+            }
+        };
+
+        if args.cycles.is_some_and(|max| cycle >= max) {
+            return Ok(());
+        }
+        tokio::time::sleep(delay).await;
     }
+}
+
+/// Look up symbol and decimals of the watched tokens and parse their thresholds
+async fn watched_tokens<P: Provider>(
+    provider: &P,
+    tokens: &[Address],
+    min_changes: &[String],
+) -> anyhow::Result<Vec<WatchedToken>> {
+    if tokens.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut multicall = MulticallBuilder::new();
+    for token in tokens {
+        multicall = multicall
+            .add_call_allow_failure(*token, selectors::symbol())
+            .add_call_allow_failure(*token, selectors::decimals());
+    }
+    let results = multicall.execute_with_retry(provider, 3).await?;
+
+    tokens
+        .iter()
+        .enumerate()
+        .map(|(i, token)| {
+            let decimals = results
+                .get(2 * i + 1)
+                .and_then(|r| r.decode_uint8())
+                .ok_or_else(|| {
+                    anyhow::anyhow!("{:#x} is not an ERC20 token (decimals() failed)", token)
+                })?;
+            let symbol = results
+                .get(2 * i)
+                .and_then(|r| r.decode_string())
+                .unwrap_or_else(|| format!("{:#x}", token));
+            let min_change = match min_changes.get(i).or(min_changes.first()) {
+                Some(amount) => parse_token_amount(amount, decimals)
+                    .map_err(|e| anyhow::anyhow!("--min-change for {}: {}", symbol, e))?,
+                None => U256::ZERO,
+            };
+            Ok(WatchedToken {
+                address: *token,
+                symbol,
+                decimals,
+                min_change,
+            })
+        })
+        .collect()
+}
+
+/// Read balances at the head block and the transactions since `last_block`
+async fn poll_account<P: Provider>(
+    provider: &P,
+    client: &Client,
+    watch: &AccountWatch,
+    last_block: Option<u64>,
+    tx_source: WatchTxSource,
+) -> anyhow::Result<(Snapshot, Vec<WatchedTx>)> {
+    let address = watch.address;
+    let block = provider.get_block_number().await?;
+
+    let mut multicall = MulticallBuilder::new()
+        .at_block(BlockId::number(block))
+        .add_call(
+            MULTICALL3_ADDRESS,
+            selectors::get_eth_balance(address),
+            false,
+        );
+    for token in &watch.tokens {
+        multicall = multicall.add_call_allow_failure(token.address, selectors::balance_of(address));
+    }
+    let results = multicall.execute(provider).await?;
+    let native = results
+        .first()
+        .and_then(|r| r.decode_uint256())
+        .ok_or_else(|| anyhow::anyhow!("Failed to read native balance"))?;
+    // A token whose balanceOf fails is skipped for this poll
+    let tokens = watch
+        .tokens
+        .iter()
+        .zip(results.iter().skip(1))
+        .filter_map(|(token, result)| Some((token.address, result.decode_uint256()?)))
+        .collect();
+    let snapshot = Snapshot {
+        block,
+        native,
+        tokens,
+    };
+
+    let Some(from) = last_block.map(|b| b + 1).filter(|from| *from <= block) else {
+        return Ok((snapshot, Vec::new()));
+    };
+    let txs = match tx_source {
+        WatchTxSource::None => Vec::new(),
+        WatchTxSource::Blocks => {
+            let from = from.max(block.saturating_sub(WATCH_MAX_SCAN_BLOCKS - 1));
+            if from > last_block.unwrap_or_default() + 1 {
+                eprintln!(
+                    "Warning: {} blocks behind, scanning only the last {}",
+                    block - last_block.unwrap_or_default(),
+                    WATCH_MAX_SCAN_BLOCKS
+                );
+            }
+            let blocks = futures::future::join_all(
+                (from..=block)
+                    .map(|n| async move { provider.get_block_by_number(n.into()).full().await }),
+            )
+            .await;
+            let mut txs = Vec::new();
+            for (n, result) in (from..=block).zip(blocks) {
+                let block = result?.ok_or_else(|| anyhow::anyhow!("Block {} not found", n))?;
+                txs.extend(
+                    block
+                        .transactions
+                        .txns()
+                        .filter(|tx| tx.inner.signer() == address || tx.inner.to() == Some(address))
+                        .map(|tx| WatchedTx {
+                            hash: format!("{:#x}", tx.inner.tx_hash()),
+                            block: n,
+                            from: tx.inner.signer(),
+                            to: tx.inner.to(),
+                            value: tx.inner.value(),
+                        }),
+                );
+            }
+            txs
+        }
+        WatchTxSource::Etherscan => {
+            let params = foundry_block_explorers::account::TxListParams {
+                start_block: from,
+                end_block: block,
+                page: 1,
+                offset: 1000,
+                sort: Sort::Asc,
+            };
+            empty_page_ok(client.get_transactions(&address, Some(params)).await)?
+                .iter()
+                .filter_map(|tx| {
+                    Some(WatchedTx {
+                        hash: format!("{:#x}", tx.hash.value()?),
+                        block: tx.block_number.as_number()?.to::<u64>(),
+                        from: *tx.from.value()?,
+                        to: tx.to,
+                        value: tx.value,
+                    })
+                })
+                .collect()
+        }
+    };
+    Ok((snapshot, txs))
+}
+
+/// Run the notify command with an event on stdin
+async fn notify(cmd: &str, event: &str) -> anyhow::Result<()> {
+    use tokio::io::AsyncWriteExt;
 
+    let mut command = if cfg!(windows) {
+        let mut command = tokio::process::Command::new("cmd");
+        command.arg("/C").arg(cmd);
+        command
+    } else {
+        let mut command = tokio::process::Command::new("sh");
+        command.arg("-c").arg(cmd);
+        command
+    };
+    let mut child = command
+        .stdin(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(event.as_bytes()).await?;
+        stdin.write_all(b"\n").await?;
+    }
+    let status = tokio::time::timeout(NOTIFY_TIMEOUT, child.wait())
+        .await
+        .map_err(|_| anyhow::anyhow!("timed out after {}s", NOTIFY_TIMEOUT.as_secs()))??;
+    if !status.success() {
+        anyhow::bail!("exited with {}", status);
+    }
     Ok(())
 }
 
+/// Cursor for `--page`/`--limit`, walking on to later pages with `--all`
+fn page_cursor(page: u64, limit: u64, all: bool) -> PageCursor {
+    if all {
+        PageCursor::from_page(page, limit)
+    } else {
+        PageCursor::single(page, limit)
+    }
+}
+
+/// Report where rows went once a file has been written
+fn finish_rows<T: Row>(writer: &RowWriter<T>, output: Option<&Path>, noun: &str, quiet: bool) {
+    if let (Some(path), false) = (output, quiet) {
+        eprintln!("Wrote {} {} to {}", writer.count(), noun, path.display());
+    }
+}
+
 /// Truncate address for display
 fn truncate_addr(addr: &str) -> String {
     if addr.len() > 12 {
@@ -862,6 +1232,250 @@ fn truncate_addr(addr: &str) -> String {
     }
 }
 
+/// One transaction from `account txs`
+///
+/// Field names are the output columns (JSON keys and CSV header), in order.
+/// Amounts are decimal strings in wei; missing values are `null` in JSON and
+/// empty in CSV.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxRow {
+    pub hash: String,
+    pub block_number: Option<u64>,
+    /// Unix timestamp, as Etherscan returns it
+    pub time_stamp: String,
+    /// `time_stamp` as `DD/MM/YYYY HH:MM:SS UTC`
+    pub date_time: String,
+    pub from: Option<String>,
+    /// `None` for contract creations
+    pub to: Option<String>,
+    pub value: String,
+    pub value_eth: String,
+    pub gas: String,
+    pub gas_price: Option<String>,
+    pub gas_price_gwei: Option<String>,
+    pub gas_used: String,
+    pub method_id: Option<String>,
+    pub function_name: Option<String>,
+    pub contract_address: Option<String>,
+    /// `"0"` for success, `"1"` for a reverted transaction
+    pub is_error: String,
+    #[serde(rename = "txreceipt_status")]
+    pub tx_receipt_status: String,
+    pub confirmations: u64,
+    /// Calldata, last since it can be long
+    pub input: String,
+}
+
+impl From<&NormalTransaction> for TxRow {
+    fn from(tx: &NormalTransaction) -> Self {
+        Self {
+            hash: format!("{:#x}", tx.hash.value().copied().unwrap_or_default()),
+            block_number: tx.block_number.as_number().map(|n| n.to::<u64>()),
+            time_stamp: tx.time_stamp.clone(),
+            date_time: format_timestamp(&tx.time_stamp),
+            from: tx.from.value().map(|a| format!("{:#x}", a)),
+            to: tx.to.map(|a| format!("{:#x}", a)),
+            value: tx.value.to_string(),
+            value_eth: format_wei_to_eth(&tx.value.to_string()),
+            gas: tx.gas.to_string(),
+            gas_price: tx.gas_price.map(|p| p.to_string()),
+            gas_price_gwei: tx.gas_price.map(|p| format_wei_to_gwei(&p.to_string())),
+            gas_used: tx.gas_used.to_string(),
+            method_id: tx.method_id.as_ref().map(|m| m.to_string()),
+            function_name: tx.function_name.clone(),
+            contract_address: tx.contract_address.map(|a| format!("{:#x}", a)),
+            is_error: tx.is_error.clone(),
+            tx_receipt_status: tx.tx_receipt_status.clone(),
+            confirmations: tx.confirmations,
+            input: tx.input.to_string(),
+        }
+    }
+}
+
+impl Row for TxRow {
+    const COLUMNS: &'static [&'static str] = &[
+        "hash",
+        "blockNumber",
+        "timeStamp",
+        "dateTime",
+        "from",
+        "to",
+        "value",
+        "valueEth",
+        "gas",
+        "gasPrice",
+        "gasPriceGwei",
+        "gasUsed",
+        "methodId",
+        "functionName",
+        "contractAddress",
+        "isError",
+        "txreceipt_status",
+        "confirmations",
+        "input",
+    ];
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.hash.clone(),
+            opt_cell(self.block_number),
+            self.time_stamp.clone(),
+            self.date_time.clone(),
+            opt_cell(self.from.as_ref()),
+            opt_cell(self.to.as_ref()),
+            self.value.clone(),
+            self.value_eth.clone(),
+            self.gas.clone(),
+            opt_cell(self.gas_price.as_ref()),
+            opt_cell(self.gas_price_gwei.as_ref()),
+            self.gas_used.clone(),
+            opt_cell(self.method_id.as_ref()),
+            opt_cell(self.function_name.as_ref()),
+            opt_cell(self.contract_address.as_ref()),
+            self.is_error.clone(),
+            self.tx_receipt_status.clone(),
+            self.confirmations.to_string(),
+            self.input.clone(),
+        ]
+    }
+
+    fn table_columns() -> &'static [&'static str] {
+        &["", "Hash", "Block", "Time", "Value (ETH)", "From", "To"]
+    }
+
+    fn table_cells(&self) -> Vec<String> {
+        let status = if self.is_error == "0" { "✓" } else { "✗" };
+        vec![
+            status.to_string(),
+            truncate_addr(&self.hash),
+            opt_cell(self.block_number),
+            self.date_time.clone(),
+            self.value_eth.clone(),
+            self.from.as_deref().map(truncate_addr).unwrap_or_default(),
+            self.to
+                .as_deref()
+                .map(truncate_addr)
+                .unwrap_or_else(|| "contract creation".to_string()),
+        ]
+    }
+}
+
+/// One ERC20 transfer from `account erc20`
+///
+/// Field names are the output columns (JSON keys and CSV header), in order.
+/// `value` is the raw amount; `valueFormatted` applies `tokenDecimal`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Erc20TransferRow {
+    pub hash: String,
+    pub block_number: Option<u64>,
+    /// Unix timestamp, as Etherscan returns it
+    pub time_stamp: String,
+    /// `time_stamp` as `DD/MM/YYYY HH:MM:SS UTC`
+    pub date_time: String,
+    pub from: String,
+    pub to: Option<String>,
+    pub token_address: String,
+    pub token_name: String,
+    pub token_symbol: String,
+    pub token_decimal: String,
+    pub value: String,
+    pub value_formatted: String,
+}
+
+impl From<&ERC20TokenTransferEvent> for Erc20TransferRow {
+    fn from(tx: &ERC20TokenTransferEvent) -> Self {
+        let value = tx.value.to_string();
+        // Plain decimal (no grouping) so the column stays machine-readable
+        let value_formatted = tx
+            .token_decimal
+            .parse::<u8>()
+            .ok()
+            .and_then(|decimals| format_units(tx.value, decimals).ok())
+            .map(|s| {
+                if s.contains('.') {
+                    s.trim_end_matches('0').trim_end_matches('.').to_string()
+                } else {
+                    s
+                }
+            })
+            .unwrap_or_else(|| value.clone());
+        Self {
+            hash: format!("{:#x}", tx.hash),
+            block_number: tx.block_number.as_number().map(|n| n.to::<u64>()),
+            time_stamp: tx.time_stamp.clone(),
+            date_time: format_timestamp(&tx.time_stamp),
+            from: format!("{:#x}", tx.from),
+            to: tx.to.map(|a| format!("{:#x}", a)),
+            token_address: format!("{:#x}", tx.contract_address),
+            token_name: tx.token_name.clone(),
+            token_symbol: tx.token_symbol.clone(),
+            token_decimal: tx.token_decimal.clone(),
+            value,
+            value_formatted,
+        }
+    }
+}
+
+impl Row for Erc20TransferRow {
+    const COLUMNS: &'static [&'static str] = &[
+        "hash",
+        "blockNumber",
+        "timeStamp",
+        "dateTime",
+        "from",
+        "to",
+        "tokenAddress",
+        "tokenName",
+        "tokenSymbol",
+        "tokenDecimal",
+        "value",
+        "valueFormatted",
+    ];
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.hash.clone(),
+            opt_cell(self.block_number),
+            self.time_stamp.clone(),
+            self.date_time.clone(),
+            self.from.clone(),
+            opt_cell(self.to.as_ref()),
+            self.token_address.clone(),
+            self.token_name.clone(),
+            self.token_symbol.clone(),
+            self.token_decimal.clone(),
+            self.value.clone(),
+            self.value_formatted.clone(),
+        ]
+    }
+
+    fn table_columns() -> &'static [&'static str] {
+        &["Hash", "Block", "Time", "Amount", "Token", "From", "To"]
+    }
+
+    fn table_cells(&self) -> Vec<String> {
+        vec![
+            truncate_addr(&self.hash),
+            opt_cell(self.block_number),
+            self.date_time.clone(),
+            self.value_formatted.clone(),
+            self.token_symbol.clone(),
+            truncate_addr(&self.from),
+            self.to
+                .as_deref()
+                .map(truncate_addr)
+                .unwrap_or_else(|| "n/a".to_string()),
+        ]
+    }
+}
+
+/// CSV cell for an optional value (empty when missing)
+fn opt_cell(value: Option<impl ToString>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
 /// Format wei to gwei string (shows up to 2 decimal places)
 fn format_wei_to_gwei(wei: &str) -> String {
     // Parse wei and divide by 1e9 to get gwei
@@ -301,7 +301,7 @@ pub fn handle(action: &AddressCommands, quiet: bool) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn print_entry(label: &str, entry: &AddressEntry) {
+pub(crate) fn print_entry(label: &str, entry: &AddressEntry) {
     println!("{:<15} {}", label, entry.address);
 
     if let Some(desc) = &entry.description {
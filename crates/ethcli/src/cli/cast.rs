@@ -2,6 +2,10 @@
 //!
 //! Similar to Foundry's cast utility
 
+use super::OutputFormat;
+use crate::abi::{decode_types, CalldataCandidate, ContractCall};
+use crate::config::Chain;
+use crate::etherscan::Client;
 use alloy::primitives::{keccak256, utils::parse_units, Address, B256, U256};
 use clap::Subcommand;
 use std::str::FromStr;
@@ -109,9 +113,33 @@ pub enum CastCommands {
         /// Hex data to decode
         data: String,
     },
+
+    /// Decode calldata without an ABI, looking up the selector in the
+    /// signature cache / 4byte.directory
+    ///
+    /// When several signatures share the selector, every candidate is
+    /// decoded and the best match (one that re-encodes to the exact
+    /// calldata) is listed first.
+    CalldataDecode {
+        /// Calldata (0x-prefixed hex, selector included)
+        calldata: String,
+
+        /// Decode with this signature instead of looking the selector up
+        #[arg(long, value_name = "SIGNATURE")]
+        sig: Option<String>,
+
+        /// Output format (json, ndjson with one candidate per line, table/pretty)
+        #[arg(long, short, value_enum, default_value = "table")]
+        output: OutputFormat,
+    },
 }
 
-pub fn handle(action: &CastCommands) -> anyhow::Result<()> {
+pub async fn handle(
+    action: &CastCommands,
+    chain: Chain,
+    api_key: Option<String>,
+    quiet: bool,
+) -> anyhow::Result<()> {
     match action {
         CastCommands::ToWei { value, unit } => {
             let result = to_wei(value, unit)?;
@@ -214,11 +242,99 @@ pub fn handle(action: &CastCommands) -> anyhow::Result<()> {
             let result = abi_decode(signature, data)?;
             println!("{}", result);
         }
+
+        CastCommands::CalldataDecode {
+            calldata,
+            sig,
+            output,
+        } => {
+            let data = hex::decode(calldata.trim().trim_start_matches("0x"))
+                .map_err(|e| anyhow::anyhow!("Invalid calldata hex: {}", e))?;
+            if data.len() < 4 {
+                return Err(anyhow::anyhow!(
+                    "Calldata must be at least 4 bytes (function selector)"
+                ));
+            }
+            let selector = format!("0x{}", hex::encode(&data[..4]));
+
+            let signatures = match sig {
+                Some(sig) => vec![sig.clone()],
+                None => {
+                    if !quiet {
+                        eprintln!("Looking up selector {}...", selector);
+                    }
+                    Client::new(chain, api_key)?
+                        .lookup_selector_all(&selector)
+                        .await
+                        .ok_or_else(|| {
+                            anyhow::anyhow!(
+                                "No signature found for selector {}; pass --sig to decode with a known signature",
+                                selector
+                            )
+                        })?
+                }
+            };
+
+            let candidates = CalldataCandidate::rank(&signatures, &data);
+            match output {
+                OutputFormat::Json => {
+                    println!("{}", serde_json::to_string_pretty(&candidates)?);
+                }
+                OutputFormat::Ndjson => {
+                    for candidate in &candidates {
+                        println!("{}", serde_json::to_string(candidate)?);
+                    }
+                }
+                OutputFormat::Table => print_candidates(&selector, &candidates),
+            }
+
+            if !candidates.iter().any(CalldataCandidate::is_decoded) {
+                return Err(anyhow::anyhow!(
+                    "None of the {} signature(s) for {} decode this calldata",
+                    candidates.len(),
+                    selector
+                ));
+            }
+        }
     }
 
     Ok(())
 }
 
+fn print_candidates(selector: &str, candidates: &[CalldataCandidate]) {
+    if let [candidate] = candidates {
+        match &candidate.decoded {
+            Ok(decoded) => print!("{}", decoded.format_text()),
+            Err(e) => eprintln!("{}: {}", candidate.signature, e),
+        }
+        return;
+    }
+
+    println!(
+        "Found {} candidate signatures for {}:",
+        candidates.len(),
+        selector
+    );
+    for (i, candidate) in candidates.iter().enumerate() {
+        println!();
+        let marker = match (i, &candidate.decoded, candidate.exact) {
+            (0, Ok(_), true) => " (best match)",
+            (_, Ok(_), false) => " (loose decode)",
+            (_, Err(_), _) => " (does not decode)",
+            _ => "",
+        };
+        println!("{}. {}{}", i + 1, candidate.signature, marker);
+        match &candidate.decoded {
+            Ok(decoded) => {
+                for line in decoded.format_text().lines() {
+                    println!("   {}", line);
+                }
+            }
+            Err(e) => println!("   {}", e),
+        }
+    }
+}
+
 fn to_wei(value: &str, unit: &str) -> anyhow::Result<String> {
     // Use alloy's parse_units for robust decimal handling
     let unit_str = match unit.to_lowercase().as_str() {
@@ -273,58 +389,15 @@ fn from_wei(value: &str, unit: &str) -> anyhow::Result<String> {
 }
 
 /// Encode a function call with signature and arguments
+///
+/// Return types in the signature (`balanceOf(address)(uint256)`) are
+/// accepted and ignored.
 pub fn abi_encode(signature: &str, args: &[String]) -> anyhow::Result<String> {
-    use alloy::dyn_abi::{DynSolType, DynSolValue};
-
-    // Parse signature to get types
-    let sig = signature.trim();
-    let types_start = sig
-        .find('(')
-        .ok_or_else(|| anyhow::anyhow!("Invalid signature: missing '('"))?;
-    let types_end = sig
-        .rfind(')')
-        .ok_or_else(|| anyhow::anyhow!("Invalid signature: missing ')'"))?;
-
-    let types_str = &sig[types_start + 1..types_end];
-
-    // Get function selector
-    let selector = keccak256(sig.as_bytes());
-    let selector_hex = hex::encode(&selector[..4]);
-
-    if types_str.is_empty() {
-        return Ok(format!("0x{}", selector_hex));
-    }
-
-    // Parse types and encode values
-    let type_strs: Vec<&str> = split_types(types_str);
-
-    if type_strs.len() != args.len() {
-        return Err(anyhow::anyhow!(
-            "Expected {} arguments, got {}",
-            type_strs.len(),
-            args.len()
-        ));
-    }
-
-    let mut values = Vec::new();
-    for (type_str, arg) in type_strs.iter().zip(args.iter()) {
-        let ty = DynSolType::parse(type_str)
-            .map_err(|e| anyhow::anyhow!("Invalid type '{}': {}", type_str, e))?;
-        let val = ty.coerce_str(arg).map_err(|e| {
-            anyhow::anyhow!("Invalid value '{}' for type '{}': {}", arg, type_str, e)
-        })?;
-        values.push(val);
-    }
-
-    // Wrap values in a tuple and encode as parameters
-    let tuple = DynSolValue::Tuple(values);
-    let encoded = tuple.abi_encode_params();
-    Ok(format!("0x{}{}", selector_hex, hex::encode(encoded)))
+    let encoded = ContractCall::from_signature(signature)?.encode(args)?;
+    Ok(format!("0x{}", hex::encode(encoded)))
 }
 
 fn abi_decode(signature: &str, data: &str) -> anyhow::Result<String> {
-    use alloy::dyn_abi::DynSolType;
-
     let data_hex = data.strip_prefix("0x").unwrap_or(data);
     let data_bytes =
         hex::decode(data_hex).map_err(|e| anyhow::anyhow!("Invalid hex data: {}", e))?;
@@ -362,16 +435,7 @@ fn abi_decode(signature: &str, data: &str) -> anyhow::Result<String> {
     }
 
     let type_strs: Vec<&str> = split_types(types_str);
-    let tuple_type = format!("({})", type_strs.join(","));
-
-    let ty = DynSolType::parse(&tuple_type)
-        .map_err(|e| anyhow::anyhow!("Invalid type signature: {}", e))?;
-
-    let decoded = ty
-        .abi_decode(data_to_decode)
-        .map_err(|e| anyhow::anyhow!("Failed to decode: {}", e))?;
-
-    Ok(format!("{:?}", decoded))
+    Ok(decode_types(data_to_decode, &type_strs.join(","), None)?)
 }
 
 /// Split comma-separated types, handling nested parentheses
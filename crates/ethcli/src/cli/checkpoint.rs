@@ -0,0 +1,374 @@
+//! Checkpoint management commands
+//!
+//! List, inspect, clean up and merge the checkpoint files written by
+//! `ethcli logs --resume`.
+
+use super::OutputFormat;
+use crate::checkpoint::{
+    checkpoint_dir, discover_checkpoints, merge_checkpoint_files, Checkpoint, CheckpointFile,
+    CheckpointSet,
+};
+use crate::config::{Chain, ConfigFile};
+use crate::utils::{parse_duration_string, unix_timestamp_secs, Alignment, Table};
+use clap::Subcommand;
+use std::path::PathBuf;
+
+#[derive(Subcommand)]
+pub enum CheckpointCommands {
+    /// List checkpoints in the current directory and the checkpoint directory
+    List {
+        /// Additional directories to scan (can be repeated)
+        #[arg(long, short, value_name = "DIR")]
+        dir: Vec<PathBuf>,
+
+        /// Output format (json, table/pretty)
+        #[arg(long, short, value_enum, default_value = "table")]
+        output: OutputFormat,
+    },
+
+    /// Show a checkpoint in full, including gaps and failed ranges
+    Inspect {
+        /// Checkpoint file
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+
+        /// Output format (json, table/pretty)
+        #[arg(long, short, value_enum, default_value = "table")]
+        output: OutputFormat,
+    },
+
+    /// Delete checkpoints that have not been updated for a while
+    Clean {
+        /// Minimum age of deleted checkpoints (e.g., "30d", "12h", "2w")
+        #[arg(long, value_name = "AGE")]
+        older_than: String,
+
+        /// Only print what would be deleted
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Additional directories to scan (can be repeated)
+        #[arg(long, short, value_name = "DIR")]
+        dir: Vec<PathBuf>,
+    },
+
+    /// Merge two checkpoints of the same query
+    ///
+    /// Contract, chain and event filter must match. Completed block ranges
+    /// are combined so a resumed fetch only re-fetches what neither covered.
+    Merge {
+        /// First checkpoint
+        #[arg(value_name = "A")]
+        a: PathBuf,
+
+        /// Second checkpoint
+        #[arg(value_name = "B")]
+        b: PathBuf,
+
+        /// Output file (defaults to overwriting A)
+        #[arg(long, short, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+}
+
+pub fn handle(action: &CheckpointCommands, quiet: bool) -> anyhow::Result<()> {
+    match action {
+        CheckpointCommands::List { dir, output } => {
+            let files = discover_checkpoints(&scan_dirs(dir));
+            let now = unix_timestamp_secs();
+
+            if output.is_json() {
+                let entries: Vec<_> = files.iter().map(|f| file_json(f, now)).collect();
+                println!("{}", serde_json::to_string_pretty(&entries)?);
+                return Ok(());
+            }
+
+            if files.is_empty() {
+                if !quiet {
+                    println!("No checkpoints found");
+                }
+                return Ok(());
+            }
+
+            let mut table = Table::new([
+                "File", "Contract", "Chain", "Blocks", "Covered", "Status", "Age",
+            ])
+            .with_alignments([
+                Alignment::Left,
+                Alignment::Left,
+                Alignment::Left,
+                Alignment::Left,
+                Alignment::Right,
+                Alignment::Left,
+                Alignment::Right,
+            ]);
+            for file in &files {
+                let age = file
+                    .last_updated()
+                    .map_or_else(|| "-".to_string(), |t| format_age(now.saturating_sub(t)));
+                match &file.contents {
+                    Ok(set) => {
+                        for checkpoint in set.chains.values() {
+                            let coverage = checkpoint.coverage();
+                            table.add_row([
+                                file.path.display().to_string(),
+                                checkpoint.contract.clone(),
+                                chain_name(checkpoint.chain_id),
+                                format!("{}-{}", coverage.start, coverage.end),
+                                format!("{:.1}%", coverage.percent()),
+                                status(checkpoint).to_string(),
+                                age.clone(),
+                            ]);
+                        }
+                    }
+                    Err(e) => table.add_row([
+                        file.path.display().to_string(),
+                        "-".to_string(),
+                        "-".to_string(),
+                        "-".to_string(),
+                        "-".to_string(),
+                        format!("unreadable: {}", e),
+                        age,
+                    ]),
+                }
+            }
+            table.print();
+        }
+
+        CheckpointCommands::Inspect { file, output } => {
+            let set = CheckpointSet::load(file)?;
+
+            if output.is_json() {
+                let chains: Vec<_> = set.chains.values().map(checkpoint_json).collect();
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "path": file,
+                        "chains": chains,
+                    }))?
+                );
+                return Ok(());
+            }
+
+            println!("Checkpoint: {}", file.display());
+            let now = unix_timestamp_secs();
+            for checkpoint in set.chains.values() {
+                print_checkpoint(checkpoint, now);
+            }
+        }
+
+        CheckpointCommands::Clean {
+            older_than,
+            dry_run,
+            dir,
+        } => {
+            let max_age = parse_max_age(older_than)?;
+            let cutoff = unix_timestamp_secs().saturating_sub(max_age);
+
+            let stale: Vec<_> = discover_checkpoints(&scan_dirs(dir))
+                .into_iter()
+                .filter(|f| f.is_stale(cutoff))
+                .collect();
+
+            for file in &stale {
+                if !*dry_run {
+                    std::fs::remove_file(&file.path)?;
+                }
+                if !quiet {
+                    let verb = if *dry_run { "Would remove" } else { "Removed" };
+                    println!("{} {}", verb, file.path.display());
+                }
+            }
+            if !quiet && stale.is_empty() {
+                println!("No checkpoints older than {}", older_than);
+            }
+        }
+
+        CheckpointCommands::Merge { a, b, output } => {
+            let output = output.as_deref().unwrap_or(a);
+            let summary = merge_checkpoint_files(a, b, output)?;
+
+            if !quiet {
+                println!(
+                    "Merged {} and {} into {}",
+                    a.display(),
+                    b.display(),
+                    output.display()
+                );
+                if summary.overlap_blocks > 0 {
+                    eprintln!(
+                        "Warning: {} blocks were completed in both; their logs are counted twice in total_logs",
+                        summary.overlap_blocks
+                    );
+                }
+                for checkpoint in summary.set.chains.values() {
+                    let coverage = checkpoint.coverage();
+                    println!(
+                        "  {}: {:.1}% of blocks {}-{} ({})",
+                        chain_name(checkpoint.chain_id),
+                        coverage.percent(),
+                        coverage.start,
+                        coverage.end,
+                        status(checkpoint)
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse `--older-than` into seconds, rejecting ages that would select
+/// every checkpoint
+fn parse_max_age(older_than: &str) -> anyhow::Result<u64> {
+    let seconds = parse_duration_string(older_than)?;
+    if !seconds.is_finite() || seconds <= 0.0 {
+        return Err(anyhow::anyhow!(
+            "--older-than must be a positive duration, got '{}'",
+            older_than
+        ));
+    }
+    Ok(seconds as u64)
+}
+
+/// Current directory, the checkpoint directory and any extra `--dir`s
+fn scan_dirs(extra: &[PathBuf]) -> Vec<PathBuf> {
+    let settings = ConfigFile::load_default()
+        .ok()
+        .flatten()
+        .map(|c| c.settings);
+    let configured = settings.as_ref().and_then(|s| s.checkpoint_dir.as_deref());
+
+    let mut dirs = vec![PathBuf::from("."), checkpoint_dir(configured)];
+    dirs.extend(extra.iter().cloned());
+    dirs
+}
+
+fn chain_name(chain_id: u64) -> String {
+    match Chain::from_chain_id(chain_id) {
+        Chain::Custom(id) => id.to_string(),
+        chain => chain.name().to_string(),
+    }
+}
+
+fn status(checkpoint: &Checkpoint) -> &'static str {
+    if !checkpoint.failed_ranges.is_empty() {
+        "failed ranges"
+    } else if !checkpoint.coverage().is_complete() {
+        "partial"
+    } else if checkpoint.end_block.is_none() {
+        // Caught up to the head seen last time; resuming continues from there
+        "open-ended"
+    } else {
+        "complete"
+    }
+}
+
+fn format_age(secs: u64) -> String {
+    match secs {
+        s if s < 60 => format!("{}s", s),
+        s if s < 3600 => format!("{}m", s / 60),
+        s if s < 86400 => format!("{}h", s / 3600),
+        s => format!("{}d", s / 86400),
+    }
+}
+
+fn format_ranges(ranges: &[(u64, u64)]) -> String {
+    if ranges.is_empty() {
+        return "none".to_string();
+    }
+    ranges
+        .iter()
+        .map(|(from, to)| format!("{}-{}", from, to))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn checkpoint_json(checkpoint: &Checkpoint) -> serde_json::Value {
+    serde_json::json!({
+        "contract": checkpoint.contract,
+        "chain_id": checkpoint.chain_id,
+        "chain": chain_name(checkpoint.chain_id),
+        "event_signature": checkpoint.event_signature,
+        "start_block": checkpoint.start_block,
+        "end_block": checkpoint.end_block,
+        "completed_ranges": checkpoint.completed_ranges,
+        "failed_ranges": checkpoint.failed_ranges,
+        "coverage": checkpoint.coverage(),
+        "coverage_percent": checkpoint.coverage().percent(),
+        "status": status(checkpoint),
+        "total_logs": checkpoint.total_logs,
+        "last_updated": checkpoint.last_updated,
+        "output_path": checkpoint.output_path,
+    })
+}
+
+fn file_json(file: &CheckpointFile, now: u64) -> serde_json::Value {
+    let age_seconds = file.last_updated().map(|t| now.saturating_sub(t));
+    match &file.contents {
+        Ok(set) => serde_json::json!({
+            "path": file.path,
+            "age_seconds": age_seconds,
+            "chains": set.chains.values().map(checkpoint_json).collect::<Vec<_>>(),
+        }),
+        Err(e) => serde_json::json!({
+            "path": file.path,
+            "age_seconds": age_seconds,
+            "error": e,
+        }),
+    }
+}
+
+fn print_checkpoint(checkpoint: &Checkpoint, now: u64) {
+    let coverage = checkpoint.coverage();
+    println!();
+    println!(
+        "{} on {}",
+        checkpoint.contract,
+        chain_name(checkpoint.chain_id)
+    );
+    println!("{}", "─".repeat(60));
+    if let Some(event) = &checkpoint.event_signature {
+        println!("  Event:      {}", event);
+    }
+    let end = checkpoint
+        .end_block
+        .map_or_else(|| "latest".to_string(), |b| b.to_string());
+    println!("  Blocks:     {}-{}", checkpoint.start_block, end);
+    println!(
+        "  Covered:    {} of {} blocks ({:.1}%)",
+        coverage.covered_blocks,
+        coverage.total_blocks,
+        coverage.percent()
+    );
+    println!("  Status:     {}", status(checkpoint));
+    println!(
+        "  Completed:  {}",
+        format_ranges(&checkpoint.completed_ranges)
+    );
+    println!("  Gaps:       {}", format_ranges(&coverage.gaps));
+    println!("  Failed:     {}", format_ranges(&checkpoint.failed_ranges));
+    println!("  Logs:       {}", checkpoint.total_logs);
+    println!(
+        "  Updated:    {} ago",
+        format_age(now.saturating_sub(checkpoint.last_updated))
+    );
+    if let Some(path) = &checkpoint.output_path {
+        println!("  Output:     {}", path.display());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_max_age() {
+        assert_eq!(parse_max_age("2d").unwrap(), 172_800);
+        assert_eq!(parse_max_age("90m").unwrap(), 5_400);
+        assert!(parse_max_age("-1d").is_err());
+        assert!(parse_max_age("0h").is_err());
+        assert!(parse_max_age("abc").is_err());
+    }
+}
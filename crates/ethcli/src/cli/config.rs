@@ -178,4 +178,14 @@ pub enum ConfigCommands {
 
     /// Validate config file syntax and structure
     Validate,
+
+    /// Upgrade an older config file to the current schema
+    #[command(
+        after_help = "The original file is kept as config.toml.v<N>.bak next to it.\nComments are not carried over to the migrated file."
+    )]
+    Migrate {
+        /// Show what would change without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
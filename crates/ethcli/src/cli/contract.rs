@@ -3,14 +3,20 @@
 //! Fetch ABI, source code, and creation info for contracts
 
 use super::OutputFormat;
+use crate::abi::{codegen, AbiFetcher};
 use crate::config::{Chain, ConfigFile, EndpointConfig};
+use crate::error::{AbiError, Error};
 use crate::etherscan::{Client, SignatureCache};
 use crate::rpc::Endpoint;
+use crate::source_diff::{
+    diff_bundles, DiffOptions, FileStatus, MatchMode, NormalizeOptions, SourceBundle,
+};
 use crate::utils::format::with_thousands_sep;
+use crate::utils::Table;
 use alloy::dyn_abi::{DynSolType, DynSolValue, FunctionExt, JsonAbiExt};
 use alloy::primitives::{Address, B256};
 use alloy::providers::Provider;
-use clap::Subcommand;
+use clap::{Args, Subcommand, ValueEnum};
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -36,6 +42,26 @@ fn eip1967_beacon_slot() -> B256 {
     })
 }
 
+/// Get EIP-1967 admin slot (parsed once)
+fn eip1967_admin_slot() -> B256 {
+    static SLOT: OnceLock<B256> = OnceLock::new();
+    *SLOT.get_or_init(|| {
+        "0xb53127684a568b3173ae13b9f8a6016e243e63b6e8ee1178d6a717850b5d6103"
+            .parse()
+            .expect("valid EIP-1967 admin slot")
+    })
+}
+
+/// Get EIP-1822 (UUPS) PROXIABLE slot (parsed once)
+fn eip1822_slot() -> B256 {
+    static SLOT: OnceLock<B256> = OnceLock::new();
+    *SLOT.get_or_init(|| {
+        "0xc5f16f0fcc639fa48a6947836d9850f504798523bf8c9a3a87d5876cf622bcf7"
+            .parse()
+            .expect("valid EIP-1822 PROXIABLE slot")
+    })
+}
+
 /// Get OpenZeppelin AdminUpgradeabilityProxy slot (parsed once)
 fn oz_impl_slot() -> B256 {
     static SLOT: OnceLock<B256> = OnceLock::new();
@@ -46,64 +72,193 @@ fn oz_impl_slot() -> B256 {
     })
 }
 
-/// Try to detect if a contract is a proxy and return the implementation address
-async fn detect_proxy_implementation<P: Provider>(
+/// EIP-1167 minimal proxy runtime code before the implementation address
+const MINIMAL_PROXY_PREFIX: &str = "363d3d373d3d3d363d73";
+/// EIP-1167 minimal proxy runtime code after the implementation address
+const MINIMAL_PROXY_SUFFIX: &str = "5af43d82803e903d91602b57fd5bf3";
+
+/// Proxy pattern detected for a contract
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProxyPattern {
+    /// EIP-1967 implementation slot with an admin set (OpenZeppelin transparent proxy)
+    Transparent,
+    /// EIP-1967 implementation slot without an admin (typically UUPS)
+    Eip1967,
+    /// EIP-1822 PROXIABLE slot
+    Eip1822,
+    /// EIP-1967 beacon slot; the implementation comes from the beacon
+    Beacon,
+    /// Legacy OpenZeppelin AdminUpgradeabilityProxy slot (e.g. USDC)
+    OzLegacy,
+    /// EIP-1167 minimal proxy (clone)
+    Minimal,
+}
+
+impl std::fmt::Display for ProxyPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Transparent => "EIP-1967 transparent",
+            Self::Eip1967 => "EIP-1967",
+            Self::Eip1822 => "EIP-1822 (UUPS)",
+            Self::Beacon => "EIP-1967 beacon",
+            Self::OzLegacy => "OpenZeppelin legacy",
+            Self::Minimal => "EIP-1167 minimal",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A detected proxy and where it delegates to
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ProxyInfo {
+    pub pattern: ProxyPattern,
+    pub implementation: Address,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub admin: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beacon: Option<Address>,
+}
+
+/// Read a storage slot as an address, `None` if the slot is unset
+async fn slot_address<P: Provider>(
     provider: &P,
     address: Address,
-) -> Option<Address> {
-    // Try EIP-1967 implementation slot first
-    if let Ok(storage) = provider
-        .get_storage_at(address, eip1967_impl_slot().into())
+    slot: B256,
+) -> anyhow::Result<Option<Address>> {
+    let storage = provider
+        .get_storage_at(address, slot.into())
         .await
-    {
-        let impl_addr = Address::from_slice(&storage.to_be_bytes::<32>()[12..]);
-        if !impl_addr.is_zero() {
-            return Some(impl_addr);
-        }
+        .map_err(|e| anyhow::anyhow!("Failed to read storage slot {slot:#x}: {e}"))?;
+    let addr = Address::from_slice(&storage.to_be_bytes::<32>()[12..]);
+    Ok((!addr.is_zero()).then_some(addr))
+}
+
+/// Extract the implementation from EIP-1167 minimal proxy runtime code
+fn minimal_proxy_target(code: &[u8]) -> Option<Address> {
+    let code = hex::encode(code);
+    let rest = code.strip_prefix(MINIMAL_PROXY_PREFIX)?;
+    let target = rest.strip_suffix(MINIMAL_PROXY_SUFFIX)?;
+    if target.len() != 40 {
+        return None;
+    }
+    Address::from_str(target).ok()
+}
+
+/// Detect the proxy pattern of a contract, `None` if it is not a proxy
+///
+/// RPC failures are errors rather than "not a proxy".
+pub async fn detect_proxy<P: Provider>(
+    provider: &P,
+    address: Address,
+) -> anyhow::Result<Option<ProxyInfo>> {
+    // Try EIP-1967 implementation slot first
+    if let Some(implementation) = slot_address(provider, address, eip1967_impl_slot()).await? {
+        let admin = slot_address(provider, address, eip1967_admin_slot()).await?;
+        return Ok(Some(ProxyInfo {
+            pattern: if admin.is_some() {
+                ProxyPattern::Transparent
+            } else {
+                ProxyPattern::Eip1967
+            },
+            implementation,
+            admin,
+            beacon: None,
+        }));
     }
 
     // Try OpenZeppelin AdminUpgradeabilityProxy slot (used by USDC, etc.)
-    if let Ok(storage) = provider
-        .get_storage_at(address, oz_impl_slot().into())
-        .await
-    {
-        let impl_addr = Address::from_slice(&storage.to_be_bytes::<32>()[12..]);
-        if !impl_addr.is_zero() {
-            return Some(impl_addr);
-        }
+    if let Some(implementation) = slot_address(provider, address, oz_impl_slot()).await? {
+        return Ok(Some(ProxyInfo {
+            pattern: ProxyPattern::OzLegacy,
+            implementation,
+            admin: None,
+            beacon: None,
+        }));
     }
 
     // Try EIP-1967 beacon slot
-    if let Ok(storage) = provider
-        .get_storage_at(address, eip1967_beacon_slot().into())
-        .await
-    {
-        let beacon_addr = Address::from_slice(&storage.to_be_bytes::<32>()[12..]);
-        if !beacon_addr.is_zero() {
-            // Call beacon.implementation() to get the actual implementation
-            // implementation() selector = 0x5c60da1b
-            let calldata = hex::decode("5c60da1b").ok()?;
-            let tx = alloy::rpc::types::TransactionRequest::default()
-                .to(beacon_addr)
-                .input(calldata.into());
-            if let Ok(result) = provider.call(tx).await {
-                if result.len() >= 32 {
-                    let impl_addr = Address::from_slice(&result[12..32]);
-                    if !impl_addr.is_zero() {
-                        return Some(impl_addr);
-                    }
+    if let Some(beacon) = slot_address(provider, address, eip1967_beacon_slot()).await? {
+        // Call beacon.implementation() to get the actual implementation
+        // implementation() selector = 0x5c60da1b
+        let calldata = hex::decode("5c60da1b").expect("valid selector hex");
+        let tx = alloy::rpc::types::TransactionRequest::default()
+            .to(beacon)
+            .input(calldata.into());
+        if let Ok(result) = provider.call(tx).await {
+            if result.len() >= 32 {
+                let implementation = Address::from_slice(&result[12..32]);
+                if !implementation.is_zero() {
+                    return Ok(Some(ProxyInfo {
+                        pattern: ProxyPattern::Beacon,
+                        implementation,
+                        admin: None,
+                        beacon: Some(beacon),
+                    }));
                 }
             }
         }
     }
 
-    None
+    // Try EIP-1822 PROXIABLE slot
+    if let Some(implementation) = slot_address(provider, address, eip1822_slot()).await? {
+        return Ok(Some(ProxyInfo {
+            pattern: ProxyPattern::Eip1822,
+            implementation,
+            admin: None,
+            beacon: None,
+        }));
+    }
+
+    // Try EIP-1167 minimal proxy bytecode
+    let code = provider
+        .get_code_at(address)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to fetch code: {e}"))?;
+    Ok(minimal_proxy_target(&code).map(|implementation| ProxyInfo {
+        pattern: ProxyPattern::Minimal,
+        implementation,
+        admin: None,
+        beacon: None,
+    }))
+}
+
+/// Try to detect if a contract is a proxy and return the implementation address
+async fn detect_proxy_implementation<P: Provider>(
+    provider: &P,
+    address: Address,
+) -> anyhow::Result<Option<Address>> {
+    Ok(detect_proxy(provider, address)
+        .await?
+        .map(|info| info.implementation))
+}
+
+/// Get an RPC endpoint for the chain, from `--rpc-url` or the config file
+fn rpc_endpoint(chain: Chain, rpc_url: Option<&str>) -> anyhow::Result<Endpoint> {
+    if let Some(url) = rpc_url {
+        return Ok(Endpoint::new(EndpointConfig::new(url), 30, None)?);
+    }
+
+    let config = ConfigFile::load_default()
+        .map_err(|e| anyhow::anyhow!("Failed to load config: {}", e))?
+        .unwrap_or_default();
+
+    let endpoint = config
+        .endpoints
+        .into_iter()
+        .find(|e| e.enabled && e.chain == chain)
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "No RPC endpoints configured for {}. Add one with: ethcli endpoints add <url>",
+                chain.display_name()
+            )
+        })?;
+    Ok(Endpoint::new(endpoint, 30, None)?)
 }
 
 #[derive(Subcommand)]
 pub enum ContractCommands {
     /// Get verified contract ABI
-    #[command(visible_alias = "abi")]
     Abi {
         /// Contract address
         #[arg(value_name = "ADDRESS")]
@@ -112,6 +267,40 @@ pub enum ContractCommands {
         /// Save to file instead of stdout
         #[arg(long, short, value_name = "FILE")]
         output: Option<PathBuf>,
+
+        /// If the contract is a proxy, fetch the implementation's ABI instead
+        #[arg(long)]
+        follow_proxy: bool,
+
+        /// Custom RPC URL for proxy detection (overrides config)
+        #[arg(long, value_name = "URL", requires = "follow_proxy")]
+        rpc_url: Option<String>,
+    },
+
+    /// Detect the proxy pattern of a contract and resolve its implementation
+    ///
+    /// Checks EIP-1967 (transparent, UUPS, beacon), EIP-1822, the legacy
+    /// OpenZeppelin slot and EIP-1167 minimal proxies.
+    ///
+    /// Examples:
+    ///   ethcli contract proxy-resolve 0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48
+    ///   ethcli contract proxy-resolve 0x... --abi --format json
+    ProxyResolve {
+        /// Contract address
+        #[arg(value_name = "ADDRESS")]
+        address: String,
+
+        /// Also fetch the implementation's verified ABI
+        #[arg(long)]
+        abi: bool,
+
+        /// Custom RPC URL (overrides config)
+        #[arg(long, value_name = "URL")]
+        rpc_url: Option<String>,
+
+        /// Output format (json, table/pretty)
+        #[arg(long, short = 'o', value_enum, default_value = "table")]
+        format: OutputFormat,
     },
 
     /// Get verified source code
@@ -138,6 +327,29 @@ pub enum ContractCommands {
         format: OutputFormat,
     },
 
+    /// List the events a contract emits (name, signature, topic0)
+    ///
+    /// Examples:
+    ///   ethcli contract events 0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48
+    ///   ethcli contract events 0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48 --format json
+    ///   ethcli contract events 0x... --abi ./MyContract.json
+    ///   ethcli contract events 0x... --follow-proxy --codegen rust > events.rs
+    ///   ethcli contract events 0x... --codegen json-schema --out-dir ./schemas
+    Events(EventsArgs),
+
+    /// Compare the verified sources of two contracts, on one chain or two
+    ///
+    /// Sources are normalized first: line endings and IPFS/Swarm metadata
+    /// hashes are ignored by default. Compiler settings and constructor
+    /// arguments are compared too.
+    ///
+    /// Examples:
+    ///   ethcli contract diff 0xAAA... 0xBBB...
+    ///   ethcli contract diff 0xAAA... 0xAAA... --chain-a ethereum --chain-b base
+    ///   ethcli contract diff 0xAAA... 0xBBB... --match-mode content --ignore-pragma
+    ///   ethcli contract diff 0xAAA... 0xBBB... --format json --fail-on-diff
+    Diff(DiffArgs),
+
     /// Call a contract function (auto-fetches ABI)
     ///
     /// Examples:
@@ -171,19 +383,136 @@ pub enum ContractCommands {
     },
 }
 
+/// Arguments for `contract events`
+#[derive(Args)]
+pub struct EventsArgs {
+    /// Contract address
+    #[arg(value_name = "ADDRESS")]
+    pub address: String,
+
+    /// Path to ABI JSON file (skips Etherscan, for unverified contracts)
+    #[arg(long, value_name = "FILE")]
+    pub abi: Option<PathBuf>,
+
+    /// Output format (json, table/pretty)
+    #[arg(long, short = 'o', value_enum, default_value = "table")]
+    pub format: OutputFormat,
+
+    /// Generate types for the decoded log `params` instead of listing events
+    #[arg(long, value_enum, value_name = "TARGET")]
+    pub codegen: Option<CodegenTarget>,
+
+    /// Write generated files and manifest.json to this directory instead of stdout
+    #[arg(long, value_name = "DIR", requires = "codegen")]
+    pub out_dir: Option<PathBuf>,
+
+    /// If the contract is a proxy, include the implementation's events
+    #[arg(long, conflicts_with = "abi")]
+    pub follow_proxy: bool,
+
+    /// Custom RPC URL for proxy detection (overrides config)
+    #[arg(long, value_name = "URL", requires = "follow_proxy")]
+    pub rpc_url: Option<String>,
+}
+
+/// Arguments for `contract diff`
+#[derive(Args)]
+pub struct DiffArgs {
+    /// First contract address (A)
+    #[arg(value_name = "ADDRESS_A")]
+    pub address_a: String,
+
+    /// Second contract address (B)
+    #[arg(value_name = "ADDRESS_B")]
+    pub address_b: String,
+
+    /// Chain of A (defaults to --chain)
+    #[arg(long, value_name = "CHAIN")]
+    pub chain_a: Option<Chain>,
+
+    /// Chain of B (defaults to --chain)
+    #[arg(long, value_name = "CHAIN")]
+    pub chain_b: Option<Chain>,
+
+    /// Compare the implementations of proxies instead of the proxies
+    #[arg(long)]
+    pub follow_proxy: bool,
+
+    /// How files of A and B are paired
+    #[arg(long, value_enum, default_value = "path")]
+    pub match_mode: MatchMode,
+
+    /// Also ignore SPDX license and pragma lines
+    #[arg(long)]
+    pub ignore_pragma: bool,
+
+    /// Compare sources byte for byte (no normalization)
+    #[arg(long, conflicts_with = "ignore_pragma")]
+    pub raw: bool,
+
+    /// Context lines in diffs
+    #[arg(long, short = 'U', default_value = "3", value_name = "LINES")]
+    pub context: usize,
+
+    /// Output format (json, table/pretty)
+    #[arg(long, short = 'o', value_enum, default_value = "table")]
+    pub format: OutputFormat,
+
+    /// Exit with status 1 if sources or settings differ
+    #[arg(long)]
+    pub fail_on_diff: bool,
+}
+
+/// Code generation target for `contract events --codegen`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CodegenTarget {
+    /// Rust module with serde structs
+    Rust,
+    /// JSON Schema (draft 2020-12) per event
+    JsonSchema,
+}
+
 pub async fn handle(
     action: &ContractCommands,
     chain: Chain,
     api_key: Option<String>,
     quiet: bool,
 ) -> anyhow::Result<()> {
-    let client = Client::new(chain, api_key)?;
+    let client = Client::new(chain, api_key.clone())?;
 
     match action {
-        ContractCommands::Abi { address, output } => {
-            let addr = Address::from_str(address)
+        ContractCommands::Abi {
+            address,
+            output,
+            follow_proxy,
+            rpc_url,
+        } => {
+            let mut addr = Address::from_str(address)
                 .map_err(|e| anyhow::anyhow!("Invalid address: {}", e))?;
 
+            let mut resolved = None;
+            if *follow_proxy {
+                let endpoint = rpc_endpoint(chain, rpc_url.as_deref())?;
+                match detect_proxy(endpoint.provider(), addr).await? {
+                    Some(info) => {
+                        if !quiet {
+                            eprintln!(
+                                "Detected {} proxy, using implementation {:#x}",
+                                info.pattern, info.implementation
+                            );
+                        }
+                        addr = info.implementation;
+                        resolved = Some(format!("{:#x}", addr));
+                    }
+                    None => {
+                        if !quiet {
+                            eprintln!("{} is not a proxy", address);
+                        }
+                    }
+                }
+            }
+            let address = resolved.as_ref().unwrap_or(address);
+
             let cache = Arc::new(SignatureCache::new());
             let chain_id = chain.chain_id();
 
@@ -367,6 +696,83 @@ pub async fn handle(
             }
         }
 
+        ContractCommands::ProxyResolve {
+            address,
+            abi,
+            rpc_url,
+            format,
+        } => {
+            let addr = Address::from_str(address)
+                .map_err(|e| anyhow::anyhow!("Invalid address: {}", e))?;
+            let endpoint = rpc_endpoint(chain, rpc_url.as_deref())?;
+
+            if !quiet {
+                eprintln!("Checking proxy slots for {}...", address);
+            }
+            let info = detect_proxy(endpoint.provider(), addr).await?;
+
+            let implementation_abi = match (&info, abi) {
+                (Some(info), true) => {
+                    if !quiet {
+                        eprintln!(
+                            "Fetching implementation ABI from {:#x}...",
+                            info.implementation
+                        );
+                    }
+                    Some(
+                        client
+                            .contract_abi(info.implementation)
+                            .await
+                            .map_err(|e| anyhow::anyhow!("Failed to fetch ABI: {}", e))?,
+                    )
+                }
+                _ => None,
+            };
+
+            if format.is_json() {
+                let mut json = serde_json::json!({
+                    "address": format!("{:#x}", addr),
+                    "is_proxy": info.is_some(),
+                });
+                if let Some(info) = &info {
+                    json["proxy"] = serde_json::to_value(info)?;
+                }
+                if let Some(abi) = &implementation_abi {
+                    json["implementation_abi"] = serde_json::to_value(abi)?;
+                }
+                println!("{}", serde_json::to_string_pretty(&json)?);
+                return Ok(());
+            }
+
+            let Some(info) = info else {
+                println!("{:#x} is not a proxy", addr);
+                return Ok(());
+            };
+
+            println!("Proxy Resolution");
+            println!("{}", "─".repeat(50));
+            println!("Proxy:          {:#x}", addr);
+            println!("Pattern:        {}", info.pattern);
+            println!("Implementation: {:#x}", info.implementation);
+            if let Some(beacon) = info.beacon {
+                println!("Beacon:         {:#x}", beacon);
+            }
+            if let Some(admin) = info.admin {
+                println!("Admin:          {:#x}", admin);
+            }
+            if let Some(abi) = implementation_abi {
+                println!("\n{}", serde_json::to_string_pretty(&abi)?);
+            }
+        }
+
+        ContractCommands::Events(args) => {
+            handle_events(args, chain, api_key, quiet).await?;
+        }
+
+        ContractCommands::Diff(args) => {
+            handle_diff(args, chain, api_key, quiet).await?;
+        }
+
         ContractCommands::Call {
             address,
             function,
@@ -379,33 +785,13 @@ pub async fn handle(
                 .map_err(|e| anyhow::anyhow!("Invalid address: {}", e))?;
 
             // Get RPC endpoint first (needed for proxy detection)
-            let endpoint = if let Some(url) = rpc_url {
-                Endpoint::new(EndpointConfig::new(url.clone()), 30, None)?
-            } else {
-                let config = ConfigFile::load_default()
-                    .map_err(|e| anyhow::anyhow!("Failed to load config: {}", e))?
-                    .unwrap_or_default();
-
-                let chain_endpoints: Vec<_> = config
-                    .endpoints
-                    .into_iter()
-                    .filter(|e| e.enabled && e.chain == chain)
-                    .collect();
-
-                if chain_endpoints.is_empty() {
-                    return Err(anyhow::anyhow!(
-                        "No RPC endpoints configured for {}. Add one with: ethcli endpoints add <url>",
-                        chain.display_name()
-                    ));
-                }
-                Endpoint::new(chain_endpoints[0].clone(), 30, None)?
-            };
+            let endpoint = rpc_endpoint(chain, rpc_url.as_deref())?;
 
             let provider = endpoint.provider();
 
             // Check if this is a proxy contract
             let abi_address =
-                if let Some(impl_addr) = detect_proxy_implementation(provider, addr).await {
+                if let Some(impl_addr) = detect_proxy_implementation(provider, addr).await? {
                     if !quiet {
                         eprintln!(
                             "Detected proxy contract, fetching implementation ABI from {:#x}...",
@@ -586,6 +972,237 @@ pub async fn handle(
     Ok(())
 }
 
+/// Event schema entry printed by `contract events`
+#[derive(Debug, serde::Serialize)]
+struct EventSchema {
+    name: String,
+    signature: String,
+    /// None for anonymous events, which have no topic0
+    topic0: Option<String>,
+    indexed_params: Vec<EventParam>,
+}
+
+/// Indexed event parameter
+#[derive(Debug, serde::Serialize)]
+struct EventParam {
+    name: String,
+    #[serde(rename = "type")]
+    ty: String,
+}
+
+/// Build the event schema list for an ABI, sorted by name then signature
+fn event_schemas(abi: &alloy::json_abi::JsonAbi) -> Vec<EventSchema> {
+    let mut events: Vec<EventSchema> = AbiFetcher::get_events(abi)
+        .into_iter()
+        .map(|event| EventSchema {
+            name: event.name.clone(),
+            signature: AbiFetcher::event_signature_string(event),
+            topic0: (!event.anonymous).then(|| format!("{:#x}", AbiFetcher::event_selector(event))),
+            indexed_params: event
+                .inputs
+                .iter()
+                .filter(|p| p.indexed)
+                .map(|p| EventParam {
+                    name: p.name.clone(),
+                    ty: p.ty.to_string(),
+                })
+                .collect(),
+        })
+        .collect();
+    events.sort_by(|a, b| {
+        a.name
+            .cmp(&b.name)
+            .then_with(|| a.signature.cmp(&b.signature))
+    });
+    events
+}
+
+/// Handle `contract events`
+async fn handle_events(
+    args: &EventsArgs,
+    chain: Chain,
+    api_key: Option<String>,
+    quiet: bool,
+) -> anyhow::Result<()> {
+    let address = &args.address;
+    let fetcher = AbiFetcher::new(api_key)?;
+
+    let abi = if let Some(path) = &args.abi {
+        fetcher.load_from_file(path)?
+    } else {
+        let addr =
+            Address::from_str(address).map_err(|e| anyhow::anyhow!("Invalid address: {}", e))?;
+
+        let proxy = if args.follow_proxy {
+            let endpoint = rpc_endpoint(chain, args.rpc_url.as_deref())?;
+            detect_proxy(endpoint.provider(), addr).await?
+        } else {
+            None
+        };
+
+        match proxy {
+            Some(info) => {
+                if !quiet {
+                    eprintln!(
+                        "Detected {} proxy, fetching implementation ABI from {:#x}...",
+                        info.pattern, info.implementation
+                    );
+                }
+                let mut abi =
+                    fetch_events_abi(&fetcher, chain, &format!("{:#x}", info.implementation))
+                        .await?;
+                // Proxies emit their own events (Upgraded, AdminChanged, ...) too
+                match fetcher.fetch_from_etherscan(chain, address).await {
+                    Ok(proxy_abi) => {
+                        for event in proxy_abi.events() {
+                            if !abi.events().any(|e| e.selector() == event.selector()) {
+                                abi.events
+                                    .entry(event.name.clone())
+                                    .or_default()
+                                    .push(event.clone());
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        if !quiet {
+                            eprintln!("Warning: skipping proxy's own events: {}", e);
+                        }
+                    }
+                }
+                abi
+            }
+            None => {
+                if args.follow_proxy && !quiet {
+                    eprintln!("{} is not a proxy", address);
+                }
+                if !quiet {
+                    eprintln!("Fetching ABI for {}...", address);
+                }
+                fetch_events_abi(&fetcher, chain, address).await?
+            }
+        }
+    };
+
+    if let Some(target) = args.codegen {
+        return write_codegen(&abi, target, address, args.out_dir.as_deref(), quiet);
+    }
+
+    let format = &args.format;
+    let events = event_schemas(&abi);
+
+    if format.is_json() {
+        println!("{}", serde_json::to_string_pretty(&events)?);
+        return Ok(());
+    }
+
+    if events.is_empty() {
+        println!("No events found in ABI for {}", address);
+        return Ok(());
+    }
+
+    println!("Events for {} ({})", address, events.len());
+    println!("{}", "─".repeat(80));
+    for event in &events {
+        println!("{}", event.name);
+        println!("  Signature: {}", event.signature);
+        match &event.topic0 {
+            Some(topic0) => println!("  Topic0:    {}", topic0),
+            None => println!("  Topic0:    (anonymous)"),
+        }
+        if !event.indexed_params.is_empty() {
+            let indexed: Vec<String> = event
+                .indexed_params
+                .iter()
+                .map(|p| format!("{} {}", p.ty, p.name).trim().to_string())
+                .collect();
+            println!("  Indexed:   {}", indexed.join(", "));
+        }
+    }
+
+    if let Some(first) = events.first() {
+        println!(
+            "\nFilter logs with: ethcli logs -c {} -e \"{}\"",
+            address, first.signature
+        );
+    }
+
+    Ok(())
+}
+
+/// Fetch an ABI from Etherscan with a hint for unverified contracts
+async fn fetch_events_abi(
+    fetcher: &AbiFetcher,
+    chain: Chain,
+    address: &str,
+) -> anyhow::Result<alloy::json_abi::JsonAbi> {
+    match fetcher.fetch_from_etherscan(chain, address).await {
+        Ok(abi) => Ok(abi),
+        Err(Error::Abi(AbiError::ContractNotVerified(_))) => Err(anyhow::anyhow!(
+            "Contract {} is not verified on {}. Provide the ABI manually with: \
+             ethcli contract events {} --abi <FILE>",
+            address,
+            chain.display_name(),
+            address
+        )),
+        Err(e) => Err(anyhow::anyhow!("Failed to fetch ABI: {}", e)),
+    }
+}
+
+/// Emit generated types for an ABI's events to stdout or `out_dir`
+fn write_codegen(
+    abi: &alloy::json_abi::JsonAbi,
+    target: CodegenTarget,
+    source: &str,
+    out_dir: Option<&std::path::Path>,
+    quiet: bool,
+) -> anyhow::Result<()> {
+    let defs = codegen::event_defs(abi)?;
+    let manifest = codegen::manifest(&defs);
+
+    let Some(dir) = out_dir else {
+        match target {
+            CodegenTarget::Rust => print!("{}", codegen::rust_module(&defs, source)),
+            CodegenTarget::JsonSchema => {
+                let json = serde_json::json!({
+                    "source": source,
+                    "events": manifest,
+                    "schemas": codegen::json_schemas(&defs),
+                });
+                println!("{}", serde_json::to_string_pretty(&json)?);
+            }
+        }
+        return Ok(());
+    };
+
+    std::fs::create_dir_all(dir)?;
+    match target {
+        CodegenTarget::Rust => {
+            std::fs::write(dir.join("events.rs"), codegen::rust_module(&defs, source))?;
+        }
+        CodegenTarget::JsonSchema => {
+            // Struct names are sanitized identifiers, safe to use as file names
+            for (name, schema) in codegen::json_schemas(&defs) {
+                std::fs::write(
+                    dir.join(format!("{}.schema.json", name)),
+                    serde_json::to_string_pretty(&schema)?,
+                )?;
+            }
+        }
+    }
+    std::fs::write(
+        dir.join("manifest.json"),
+        serde_json::to_string_pretty(&manifest)?,
+    )?;
+    if !quiet {
+        eprintln!(
+            "Generated types for {} events in {}",
+            defs.len(),
+            dir.display()
+        );
+    }
+    Ok(())
+}
+
 /// Try to get token decimals by calling decimals() on the contract
 async fn get_token_decimals<P: Provider>(provider: &P, address: Address) -> Option<u8> {
     // decimals() selector = 0x313ce567
@@ -694,3 +1311,254 @@ fn format_with_decimals(value: &alloy::primitives::U256, decimals: u8) -> String
         format!("{}.{}", with_thousands_sep(integer), fraction_trimmed)
     }
 }
+
+/// One side of `contract diff`
+struct DiffSide {
+    address: String,
+    chain: Chain,
+    /// Implementation compared instead of `address`
+    implementation: Option<String>,
+    bundle: SourceBundle,
+}
+
+impl DiffSide {
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "address": self.address,
+            "chain": self.chain.name(),
+            "implementation": self.implementation,
+            "contract_name": self.bundle.contract_name,
+        })
+    }
+
+    fn label(&self) -> String {
+        let mut label = format!(
+            "{} on {} ({})",
+            self.address,
+            self.chain.display_name(),
+            self.bundle.contract_name
+        );
+        if let Some(implementation) = &self.implementation {
+            label.push_str(&format!(", implementation {}", implementation));
+        }
+        label
+    }
+}
+
+async fn fetch_diff_side(
+    address: &str,
+    chain: Chain,
+    api_key: Option<String>,
+    follow_proxy: bool,
+    quiet: bool,
+) -> anyhow::Result<DiffSide> {
+    let mut addr =
+        Address::from_str(address).map_err(|e| anyhow::anyhow!("Invalid address: {}", e))?;
+
+    let mut implementation = None;
+    if follow_proxy {
+        let endpoint = rpc_endpoint(chain, None)?;
+        if let Some(info) = detect_proxy(endpoint.provider(), addr).await? {
+            if !quiet {
+                eprintln!(
+                    "{} is a {} proxy, comparing implementation {:#x}",
+                    address, info.pattern, info.implementation
+                );
+            }
+            addr = info.implementation;
+            implementation = Some(format!("{:#x}", addr));
+        }
+    }
+
+    if !quiet {
+        eprintln!("Fetching source code for {:#x} on {}...", addr, chain);
+    }
+    let client = Client::new(chain, api_key)?;
+    let metadata = client
+        .contract_source_code(addr)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to fetch source for {:#x}: {}", addr, e))?;
+    let item = metadata
+        .items
+        .first()
+        .filter(|item| !item.source_code().is_empty())
+        .ok_or_else(|| anyhow::anyhow!("{:#x} on {} is not verified", addr, chain))?;
+
+    Ok(DiffSide {
+        address: address.to_string(),
+        chain,
+        implementation,
+        bundle: SourceBundle::from_metadata(item),
+    })
+}
+
+async fn handle_diff(
+    args: &DiffArgs,
+    chain: Chain,
+    api_key: Option<String>,
+    quiet: bool,
+) -> anyhow::Result<()> {
+    let (a, b) = tokio::try_join!(
+        fetch_diff_side(
+            &args.address_a,
+            args.chain_a.unwrap_or(chain),
+            api_key.clone(),
+            args.follow_proxy,
+            quiet,
+        ),
+        fetch_diff_side(
+            &args.address_b,
+            args.chain_b.unwrap_or(chain),
+            api_key,
+            args.follow_proxy,
+            quiet,
+        ),
+    )?;
+
+    let normalize = if args.raw {
+        NormalizeOptions::raw()
+    } else {
+        NormalizeOptions {
+            license_and_pragma: args.ignore_pragma,
+            ..NormalizeOptions::default()
+        }
+    };
+    let diff = diff_bundles(
+        &a.bundle,
+        &b.bundle,
+        &DiffOptions {
+            normalize,
+            match_mode: args.match_mode,
+            context: args.context,
+        },
+    );
+
+    if args.format.is_json() {
+        let output = serde_json::json!({
+            "a": a.to_json(),
+            "b": b.to_json(),
+            "identical": diff.is_identical(),
+            "summary": diff.summary(),
+            "identical_files": diff.identical_files(),
+            "differing_files": diff.differing_files(),
+            "only_in_a": diff.only_in_a(),
+            "only_in_b": diff.only_in_b(),
+            "files": diff.files,
+            "settings": diff.settings,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        println!("A: {}", a.label());
+        println!("B: {}", b.label());
+        println!();
+
+        for file in &diff.files {
+            let path = match (&file.path_a, &file.path_b) {
+                (Some(path_a), Some(path_b)) if path_a != path_b => {
+                    format!("{} -> {}", path_a, path_b)
+                }
+                (Some(path), _) | (None, Some(path)) => path.clone(),
+                (None, None) => continue,
+            };
+            let status = match file.status {
+                FileStatus::Identical => "identical",
+                FileStatus::Differs => "differs",
+                FileStatus::OnlyInA => "only in A",
+                FileStatus::OnlyInB => "only in B",
+            };
+            println!("  {:<10} {}", status, path);
+        }
+
+        if !diff.settings.is_empty() {
+            println!();
+            let mut table = Table::new(["Setting", "A", "B"]);
+            for setting in &diff.settings {
+                table.add_row([
+                    setting.setting.clone(),
+                    setting.a.clone().unwrap_or_else(|| "-".to_string()),
+                    setting.b.clone().unwrap_or_else(|| "-".to_string()),
+                ]);
+            }
+            table.print();
+        }
+
+        for patch in diff.files.iter().filter_map(|f| f.diff.as_deref()) {
+            println!();
+            print!("{}", patch);
+        }
+
+        println!();
+        println!("{}", diff.summary());
+    }
+
+    if args.fail_on_diff && !diff.is_identical() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minimal_proxy_target() {
+        let implementation = "bebc44782c7db0a1a60cb6fe97d0b483032ff1c7";
+        let code = hex::decode(format!(
+            "{MINIMAL_PROXY_PREFIX}{implementation}{MINIMAL_PROXY_SUFFIX}"
+        ))
+        .unwrap();
+        assert_eq!(
+            minimal_proxy_target(&code),
+            Some(Address::from_str(implementation).unwrap())
+        );
+
+        // Regular contracts and truncated clones are not minimal proxies
+        assert_eq!(minimal_proxy_target(&code[..code.len() - 1]), None);
+        assert_eq!(
+            minimal_proxy_target(&hex::decode("6080604052").unwrap()),
+            None
+        );
+        assert_eq!(minimal_proxy_target(&[]), None);
+    }
+
+    #[test]
+    fn test_proxy_info_json() {
+        let info = ProxyInfo {
+            pattern: ProxyPattern::Beacon,
+            implementation: Address::repeat_byte(0x11),
+            admin: None,
+            beacon: Some(Address::repeat_byte(0x22)),
+        };
+        let json = serde_json::to_value(&info).unwrap();
+        assert_eq!(json["pattern"], "beacon");
+        assert_eq!(
+            json["implementation"],
+            "0x1111111111111111111111111111111111111111"
+        );
+        assert!(json.get("admin").is_none());
+        assert_eq!(ProxyPattern::Eip1822.to_string(), "EIP-1822 (UUPS)");
+    }
+
+    #[tokio::test]
+    async fn test_detect_proxy_propagates_rpc_errors() {
+        use alloy::providers::ProviderBuilder;
+        use alloy::transports::mock::Asserter;
+
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+        let address = Address::repeat_byte(0x33);
+
+        // Empty slots and plain code: not a proxy
+        for _ in 0..4 {
+            asserter.push_success(&B256::ZERO);
+        }
+        asserter.push_success(&"0x6080604052");
+        assert!(detect_proxy(&provider, address).await.unwrap().is_none());
+
+        // A failed slot read is an error, not "no proxy"
+        asserter.push_failure_msg("connection reset");
+        let err = detect_proxy(&provider, address).await.unwrap_err();
+        assert!(err.to_string().contains("connection reset"), "{err}");
+    }
+}
@@ -0,0 +1,266 @@
+//! SQLite database commands
+//!
+//! Read-only queries, saved reports and schema listing for databases
+//! written by `logs -o sqlite` and `tx --sqlite`. Queries are validated as a
+//! single SELECT, run on a read-only connection, and take values only as
+//! bound parameters.
+
+use super::OutputFormat;
+use crate::config::{ConfigFile, SavedQuery};
+use crate::output::{
+    parse_output_path, read_schema, run_query, QueryParam, QueryRow, RowFormat, RowWriter,
+};
+use crate::utils::Table;
+use clap::Subcommand;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+#[derive(Subcommand)]
+pub enum DbCommands {
+    /// Run a read-only SQL query
+    ///
+    /// Only a single SELECT (or WITH ... SELECT) is accepted. Pass values
+    /// with --param instead of splicing them into the SQL:
+    ///
+    ///   ethcli db query logs.db --sql "SELECT * FROM events WHERE address = :token" -p token=0xa0b8...
+    Query {
+        /// SQLite database file
+        #[arg(value_name = "DATABASE")]
+        database: PathBuf,
+
+        /// SQL query
+        #[arg(long, value_name = "SQL")]
+        sql: String,
+
+        /// Query parameter as NAME=VALUE or NAME:TYPE=VALUE (can be repeated)
+        ///
+        /// NAME matches :NAME, @NAME or $NAME; a number N matches ?N or the
+        /// Nth bare ?. TYPE is int, real, text, blob (0x hex) or null;
+        /// without it integers and decimals bind as numbers and anything
+        /// else as text.
+        #[arg(long = "param", short = 'p', value_name = "NAME=VALUE")]
+        params: Vec<QueryParam>,
+
+        /// Output format (table, json, ndjson, csv)
+        #[arg(long, short = 'o', value_enum, default_value = "table")]
+        format: RowFormat,
+
+        /// Output file path (stdout if not specified)
+        #[arg(long, short = 'O', value_name = "FILE", value_parser = parse_output_path)]
+        output: Option<PathBuf>,
+    },
+
+    /// Run a saved query from the `[queries]` section of the config
+    Report {
+        /// Saved query name
+        #[arg(value_name = "NAME")]
+        name: String,
+
+        /// SQLite database file
+        #[arg(long, value_name = "DATABASE")]
+        db: PathBuf,
+
+        /// Override a default parameter, as NAME=VALUE or NAME:TYPE=VALUE
+        /// (can be repeated)
+        #[arg(long = "param", short = 'p', value_name = "NAME=VALUE")]
+        params: Vec<QueryParam>,
+
+        /// Output format (table, json, ndjson, csv)
+        #[arg(long, short = 'o', value_enum, default_value = "table")]
+        format: RowFormat,
+
+        /// Output file path (stdout if not specified)
+        #[arg(long, short = 'O', value_name = "FILE", value_parser = parse_output_path)]
+        output: Option<PathBuf>,
+    },
+
+    /// List saved queries
+    Reports {
+        /// Output format (json, table/pretty)
+        #[arg(long, short, value_enum, default_value = "table")]
+        output: OutputFormat,
+    },
+
+    /// Show tables, columns and indexes
+    Schema {
+        /// SQLite database file
+        #[arg(value_name = "DATABASE")]
+        database: PathBuf,
+
+        /// Output format (json, table/pretty)
+        #[arg(long, short, value_enum, default_value = "table")]
+        output: OutputFormat,
+    },
+}
+
+pub fn handle(action: &DbCommands, quiet: bool) -> anyhow::Result<()> {
+    match action {
+        DbCommands::Query {
+            database,
+            sql,
+            params,
+            format,
+            output,
+        } => query(database, sql, params, *format, output.as_deref(), quiet),
+
+        DbCommands::Report {
+            name,
+            db,
+            params,
+            format,
+            output,
+        } => {
+            let queries = saved_queries()?;
+            let saved = queries.get(name).ok_or_else(|| {
+                let available = if queries.is_empty() {
+                    "none configured; add a [queries.<name>] section to the config".to_string()
+                } else {
+                    queries.keys().cloned().collect::<Vec<_>>().join(", ")
+                };
+                anyhow::anyhow!("Unknown saved query '{}' (available: {})", name, available)
+            })?;
+            let params = report_params(name, saved, params)?;
+            query(db, &saved.sql, &params, *format, output.as_deref(), quiet)
+        }
+
+        DbCommands::Reports { output } => {
+            let queries = saved_queries()?;
+            if output.is_json() {
+                println!("{}", serde_json::to_string_pretty(&queries)?);
+                return Ok(());
+            }
+            if queries.is_empty() {
+                if !quiet {
+                    println!("No saved queries; add a [queries.<name>] section to the config");
+                }
+                return Ok(());
+            }
+            let mut table = Table::new(["Name", "Params", "Description"]);
+            for (name, saved) in &queries {
+                let params: Vec<&str> = saved.params.keys().map(String::as_str).collect();
+                table.add_row([
+                    name.clone(),
+                    params.join(", "),
+                    saved.description.clone().unwrap_or_default(),
+                ]);
+            }
+            table.print();
+            Ok(())
+        }
+
+        DbCommands::Schema { database, output } => {
+            ensure_exists(database)?;
+            let schema = read_schema(database)?;
+            if output.is_json() {
+                println!("{}", serde_json::to_string_pretty(&schema)?);
+                return Ok(());
+            }
+            if schema.tables.is_empty() {
+                if !quiet {
+                    println!("No tables");
+                }
+                return Ok(());
+            }
+            for (i, table) in schema.tables.iter().enumerate() {
+                if i > 0 {
+                    println!();
+                }
+                println!("{} ({})", table.name, table.kind);
+                let mut columns = Table::new(["Column", "Type", "Constraints", "Default"]);
+                for column in &table.columns {
+                    let mut constraints = Vec::new();
+                    if column.primary_key {
+                        constraints.push("PRIMARY KEY");
+                    }
+                    if column.not_null {
+                        constraints.push("NOT NULL");
+                    }
+                    columns.add_row([
+                        column.name.clone(),
+                        column.decl_type.clone(),
+                        constraints.join(" "),
+                        column.default.clone().unwrap_or_default(),
+                    ]);
+                }
+                columns.print();
+                for index in &table.indexes {
+                    println!(
+                        "  {}index {} ({})",
+                        if index.unique { "unique " } else { "" },
+                        index.name,
+                        index.columns.join(", ")
+                    );
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Run a read-only query and write the rows
+///
+/// Shared by `db query`, `db report` and `tx query`.
+pub fn query(
+    database: &Path,
+    sql: &str,
+    params: &[QueryParam],
+    format: RowFormat,
+    output: Option<&Path>,
+    quiet: bool,
+) -> anyhow::Result<()> {
+    ensure_exists(database)?;
+    let result = run_query(database, sql, params)?;
+    let columns = result.columns.clone();
+    let rows = result.into_rows();
+
+    if format == RowFormat::Table && rows.is_empty() && output.is_none() {
+        if !quiet {
+            println!("No rows");
+        }
+        return Ok(());
+    }
+    let mut writer = RowWriter::<QueryRow>::new(format, output)?.with_columns(columns);
+    writer.write_rows(&rows)?;
+    writer.finalize()?;
+
+    if let Some(path) = output {
+        if !quiet {
+            eprintln!("Wrote {} row(s) to {}", rows.len(), path.display());
+        }
+    }
+    Ok(())
+}
+
+/// A saved query's default parameters, overridden by `--param`
+fn report_params(
+    name: &str,
+    saved: &SavedQuery,
+    overrides: &[QueryParam],
+) -> anyhow::Result<Vec<QueryParam>> {
+    let mut params = Vec::with_capacity(saved.params.len() + overrides.len());
+    for (key, value) in &saved.params {
+        let param: QueryParam = format!("{}={}", key, value)
+            .parse()
+            .map_err(|e| anyhow::anyhow!("Saved query '{}': {}", name, e))?;
+        if !overrides.iter().any(|o| o.name == param.name) {
+            params.push(param);
+        }
+    }
+    params.extend(overrides.iter().cloned());
+    Ok(params)
+}
+
+fn saved_queries() -> anyhow::Result<BTreeMap<String, SavedQuery>> {
+    Ok(ConfigFile::load_default()
+        .map_err(|e| anyhow::anyhow!("Failed to load config: {}", e))?
+        .unwrap_or_default()
+        .queries)
+}
+
+/// Fail early: opening a missing file read-only gives an unhelpful error
+fn ensure_exists(database: &Path) -> anyhow::Result<()> {
+    if !database.is_file() {
+        anyhow::bail!("Database not found: {}", database.display());
+    }
+    Ok(())
+}
@@ -99,4 +99,51 @@ pub enum EndpointCommands {
         #[arg(long, default_value = "3", value_name = "COUNT")]
         probes: u32,
     },
+
+    /// Probe endpoints on a schedule and auto-tune priorities (long-running)
+    ///
+    /// Keeps a rolling probe history in endpoint_monitor.json next to the
+    /// config. Endpoints with `pinned = true` keep their priority, and no
+    /// other endpoint on the same chain is promoted above them.
+    #[command(after_help = r#"Examples:
+  ethcli endpoints monitor --dry-run              # Probe once, print proposed changes
+  ethcli endpoints monitor --interval 6h --apply  # Run forever, write changes to config
+  ethcli endpoints monitor --chain polygon --disable-after 5 --apply"#)]
+    Monitor {
+        /// Time between probe cycles (e.g., 30m, 6h, 1d)
+        #[arg(long, default_value = "6h", value_name = "DURATION")]
+        interval: String,
+
+        /// Write priority and enable/disable changes to the config
+        #[arg(long, conflicts_with = "dry_run")]
+        apply: bool,
+
+        /// Probe once and print proposed changes without saving anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Only monitor endpoints for this chain
+        #[arg(long, value_name = "CHAIN")]
+        chain: Option<String>,
+
+        /// Lowest priority auto-tuning assigns
+        #[arg(long, default_value = "1", value_name = "PRIORITY")]
+        min_priority: u8,
+
+        /// Highest priority auto-tuning assigns
+        #[arg(long, default_value = "10", value_name = "PRIORITY")]
+        max_priority: u8,
+
+        /// Disable an endpoint after this many consecutive failed probes
+        #[arg(long, default_value = "3", value_name = "COUNT")]
+        disable_after: u32,
+
+        /// Timeout for each test in seconds
+        #[arg(long, default_value = "10", value_name = "SECONDS")]
+        timeout: u64,
+
+        /// Stop after this many cycles (runs until interrupted by default)
+        #[arg(long, value_name = "COUNT")]
+        cycles: Option<u64>,
+    },
 }
@@ -1,8 +1,12 @@
 //! Gas oracle and estimation commands
 
 use super::OutputFormat;
+use crate::aggregator::gas::{
+    compare_gas_costs, GasComparison, GasOperation, GasTable, LiveGasSource,
+};
 use crate::config::Chain;
 use crate::etherscan::Client;
+use crate::utils::{format_usd, Alignment, Table};
 use alloy::primitives::U256;
 use clap::Subcommand;
 
@@ -21,6 +25,29 @@ pub enum GasCommands {
         #[arg(value_name = "GWEI")]
         gwei: u64,
     },
+
+    /// Compare the cost of an operation across chains at current fees
+    ///
+    /// Gas units per operation can be overridden in the config file under
+    /// `[gas.operations]`. Chains that are unknown or have no reachable RPC
+    /// endpoint are skipped with a warning.
+    Compare {
+        /// Operation to price
+        #[arg(long, value_enum, default_value = "transfer")]
+        op: GasOperation,
+
+        /// Chains to compare (comma-separated)
+        #[arg(
+            long,
+            value_delimiter = ',',
+            default_value = "ethereum,base,arbitrum,optimism"
+        )]
+        chains: Vec<String>,
+
+        /// Output format (json, table/pretty)
+        #[arg(long, short, value_enum, default_value = "table")]
+        output: OutputFormat,
+    },
 }
 
 pub async fn handle(
@@ -29,10 +56,9 @@ pub async fn handle(
     api_key: Option<String>,
     quiet: bool,
 ) -> anyhow::Result<()> {
-    let client = Client::new(chain, api_key)?;
-
     match action {
         GasCommands::Oracle { output } => {
+            let client = Client::new(chain, api_key)?;
             if !quiet {
                 eprintln!("Fetching gas oracle for {}...", chain.display_name());
             }
@@ -76,7 +102,12 @@ pub async fn handle(
             }
         }
 
+        GasCommands::Compare { op, chains, output } => {
+            compare(*op, chains, *output, quiet).await?;
+        }
+
         GasCommands::Estimate { gwei } => {
+            let client = Client::new(chain, api_key)?;
             if !quiet {
                 eprintln!(
                     "Estimating confirmation time for {} gwei on {}...",
@@ -105,3 +136,72 @@ pub async fn handle(
 
     Ok(())
 }
+
+async fn compare(
+    op: GasOperation,
+    chains: &[String],
+    output: OutputFormat,
+    quiet: bool,
+) -> anyhow::Result<()> {
+    let (table, unknown_ops) = GasTable::from_config();
+    if !quiet {
+        for name in &unknown_ops {
+            eprintln!(
+                "Warning: ignoring unknown operation '{}' in [gas.operations]",
+                name
+            );
+        }
+        eprintln!("Comparing {} cost across {} chains...", op, chains.len());
+    }
+
+    let comparison = compare_gas_costs(&LiveGasSource::default(), op, chains, &table).await;
+    if !quiet {
+        for skipped in &comparison.skipped {
+            eprintln!("Warning: skipping {}: {}", skipped.chain, skipped.reason);
+        }
+    }
+
+    if output.is_json() {
+        println!("{}", serde_json::to_string_pretty(&comparison)?);
+    } else {
+        print_comparison(&comparison);
+    }
+
+    if comparison.costs.is_empty() {
+        anyhow::bail!("No chains could be compared");
+    }
+    Ok(())
+}
+
+fn print_comparison(comparison: &GasComparison) {
+    println!("Gas cost: {}", comparison.operation);
+    let mut table = Table::new([
+        "Chain",
+        "Gas",
+        "Gwei",
+        "Native Cost",
+        "USD Cost",
+        "vs Cheapest",
+    ])
+    .with_alignments([
+        Alignment::Left,
+        Alignment::Right,
+        Alignment::Right,
+        Alignment::Right,
+        Alignment::Right,
+        Alignment::Right,
+    ]);
+    for cost in &comparison.costs {
+        table.add_row([
+            cost.chain.clone(),
+            cost.gas_units.to_string(),
+            format!("{:.4}", cost.gas_price_gwei),
+            format!("{:.8} {}", cost.native_cost, cost.native_symbol),
+            cost.usd_cost.map(format_usd).unwrap_or_else(|| "-".into()),
+            cost.relative_to_cheapest
+                .map(|r| format!("{:.2}x", r))
+                .unwrap_or_else(|| "-".into()),
+        ]);
+    }
+    table.print();
+}
@@ -110,10 +110,18 @@ pub enum CoinsCommands {
         id: String,
     },
 
-    /// Get coin tickers
+    /// Get coin tickers (all pages)
     Tickers {
         /// Coin ID
         id: String,
+
+        /// Only include these exchanges (comma-separated exchange IDs)
+        #[arg(long, value_delimiter = ',')]
+        exchanges: Vec<String>,
+
+        /// Drop anomalous, stale and red trust score tickers
+        #[arg(long)]
+        trusted: bool,
     },
 
     /// Get coin market chart
@@ -370,7 +378,17 @@ async fn handle_simple(
             }
             let id_list: Vec<&str> = ids.split(',').map(|s| s.trim()).collect();
             let vs_list: Vec<&str> = vs.split(',').map(|s| s.trim()).collect();
-            let response = client.simple().price(&id_list, &vs_list).await?;
+            let response = match client.simple().price(&id_list, &vs_list).await {
+                // Print the coins that were found and report the rest
+                Err(cgko::error::ApiError::Domain(cgko::error::DomainError::UnknownCoinIds {
+                    ids,
+                    prices,
+                })) if !prices.is_empty() => {
+                    eprintln!("Warning: unknown coin ids: {}", ids.join(", "));
+                    prices
+                }
+                other => other?,
+            };
             print_output(&response, args.format)?;
         }
         SimpleCommands::TokenPrice {
@@ -432,12 +450,21 @@ async fn handle_coins(
             let response = client.coins().get(id).await?;
             print_output(&response, args.format)?;
         }
-        CoinsCommands::Tickers { id } => {
+        CoinsCommands::Tickers {
+            id,
+            exchanges,
+            trusted,
+        } => {
             if !quiet {
                 eprintln!("Fetching tickers for {}...", id);
             }
-            let response = client.coins().tickers(id).await?;
-            print_output(&response, args.format)?;
+            let exchange_ids: Vec<&str> = exchanges.iter().map(String::as_str).collect();
+            let filter = (!exchange_ids.is_empty()).then_some(exchange_ids.as_slice());
+            let mut tickers = client.coins().tickers(id, filter, false).await?;
+            if *trusted {
+                tickers.retain(|t| t.is_trustworthy());
+            }
+            print_output(&tickers, args.format)?;
         }
         CoinsCommands::Chart { id, vs, days } => {
             if !quiet {
@@ -110,6 +110,8 @@ pub enum KongCommands {
   ethcli kong vaults list                              # List all vaults
   ethcli kong vaults list --chain-id 1 --yearn         # Official Yearn vaults on mainnet
   ethcli kong vaults list --v3 --erc4626               # V3 ERC4626 vaults
+  ethcli kong vaults list -c 1 --sort tvl --limit 20   # Top 20 mainnet vaults by TVL
+  ethcli kong vaults list -c 1 --min-tvl 1000000 --fields address,name,apy
   ethcli kong vaults get -c 1 0x7B5A...                # Get specific vault details
   ethcli kong vaults accounts -c 1 0xd8dA...           # Get user positions")]
 #[non_exhaustive]
@@ -131,6 +133,22 @@ pub enum VaultCommands {
         /// Filter ERC4626 compliant vaults
         #[arg(long)]
         erc4626: bool,
+
+        /// Comma-separated summary fields to output (address, name, symbol, chain-id, tvl, apy)
+        #[arg(long, value_enum, value_delimiter = ',')]
+        fields: Vec<VaultFieldArg>,
+
+        /// Only include vaults with at least this TVL in USD
+        #[arg(long)]
+        min_tvl: Option<f64>,
+
+        /// Maximum number of vaults to return
+        #[arg(long)]
+        limit: Option<usize>,
+
+        /// Sort vaults descending by TVL or net APY
+        #[arg(long, value_enum)]
+        sort: Option<VaultSortArg>,
     },
 
     /// Get a specific vault by address
@@ -154,6 +172,90 @@ pub enum VaultCommands {
     },
 }
 
+/// Field of a vault summary
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum VaultFieldArg {
+    /// Vault address
+    Address,
+    /// Vault name
+    Name,
+    /// Vault symbol
+    Symbol,
+    /// Chain ID
+    ChainId,
+    /// TVL in USD
+    Tvl,
+    /// Net APY
+    Apy,
+}
+
+impl VaultFieldArg {
+    /// Key of this field in the serialized `VaultSummary`
+    fn key(self) -> &'static str {
+        match self {
+            Self::Address => "address",
+            Self::Name => "name",
+            Self::Symbol => "symbol",
+            Self::ChainId => "chainId",
+            Self::Tvl => "tvl",
+            Self::Apy => "apyNet",
+        }
+    }
+}
+
+/// Sort order for vault summaries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum VaultSortArg {
+    /// Highest TVL first
+    Tvl,
+    /// Highest net APY first
+    Apy,
+}
+
+/// Filter, sort and truncate vault summaries
+fn rank_vault_summaries(
+    mut summaries: Vec<ykong::VaultSummary>,
+    min_tvl: Option<f64>,
+    sort: Option<VaultSortArg>,
+    limit: Option<usize>,
+) -> Vec<ykong::VaultSummary> {
+    if let Some(min) = min_tvl {
+        summaries.retain(|s| s.tvl.unwrap_or(0.0) >= min);
+    }
+    match sort {
+        Some(VaultSortArg::Tvl) => {
+            summaries.sort_by(|a, b| b.tvl.unwrap_or(0.0).total_cmp(&a.tvl.unwrap_or(0.0)))
+        }
+        Some(VaultSortArg::Apy) => summaries.sort_by(|a, b| {
+            b.apy_net
+                .unwrap_or(0.0)
+                .total_cmp(&a.apy_net.unwrap_or(0.0))
+        }),
+        None => {}
+    }
+    if let Some(limit) = limit {
+        summaries.truncate(limit);
+    }
+    summaries
+}
+
+/// Keep only the requested fields of each summary (all fields if none given)
+fn project_vault_summaries(
+    summaries: &[ykong::VaultSummary],
+    fields: &[VaultFieldArg],
+) -> anyhow::Result<Vec<serde_json::Value>> {
+    summaries
+        .iter()
+        .map(|summary| {
+            let mut value = serde_json::to_value(summary)?;
+            if let (false, Some(map)) = (fields.is_empty(), value.as_object_mut()) {
+                map.retain(|key, _| fields.iter().any(|f| f.key() == key));
+            }
+            Ok(value)
+        })
+        .collect()
+}
+
 #[derive(Subcommand)]
 #[non_exhaustive]
 pub enum StrategyCommands {
@@ -317,6 +419,10 @@ async fn handle_vaults(
             v3,
             yearn,
             erc4626,
+            fields,
+            min_tvl,
+            limit,
+            sort,
         } => {
             if !quiet {
                 eprintln!("Fetching vaults...");
@@ -337,11 +443,24 @@ async fn handle_vaults(
                 filter = filter.erc4626(true);
             }
 
-            let vaults = client.vaults().list(Some(filter)).await?;
-            if !quiet {
-                eprintln!("Found {} vaults", vaults.len());
+            // Any ranking/projection option switches to the lightweight summary query
+            let summary_mode =
+                !fields.is_empty() || min_tvl.is_some() || limit.is_some() || sort.is_some();
+            if summary_mode {
+                let summaries = client.vaults().list_summaries(Some(filter)).await?;
+                let total = summaries.len();
+                let ranked = rank_vault_summaries(summaries, *min_tvl, *sort, *limit);
+                if !quiet {
+                    eprintln!("Found {} vaults, showing {}", total, ranked.len());
+                }
+                print_output(&project_vault_summaries(&ranked, fields)?, args.format)?;
+            } else {
+                let vaults = client.vaults().list(Some(filter)).await?;
+                if !quiet {
+                    eprintln!("Found {} vaults", vaults.len());
+                }
+                print_output(&vaults, args.format)?;
             }
-            print_output(&vaults, args.format)?;
         }
         VaultCommands::Get { chain_id, address } => {
             validate_address(address)?;
@@ -579,3 +698,54 @@ fn print_output<T: serde::Serialize>(data: &T, format: OutputFormat) -> anyhow::
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(address: &str, tvl: Option<f64>, apy_net: Option<f64>) -> ykong::VaultSummary {
+        ykong::VaultSummary {
+            address: address.to_string(),
+            name: Some(format!("{address} vault")),
+            symbol: None,
+            chain_id: 1,
+            tvl,
+            apy_net,
+        }
+    }
+
+    #[test]
+    fn test_rank_vault_summaries() {
+        let summaries = vec![
+            summary("a", Some(5_000.0), Some(0.10)),
+            summary("b", Some(2_000_000.0), Some(0.03)),
+            summary("c", None, Some(0.50)),
+            summary("d", Some(800_000.0), Some(0.07)),
+        ];
+
+        let by_tvl =
+            rank_vault_summaries(summaries.clone(), None, Some(VaultSortArg::Tvl), Some(2));
+        let ids: Vec<&str> = by_tvl.iter().map(|s| s.address.as_str()).collect();
+        assert_eq!(ids, vec!["b", "d"]);
+
+        let by_apy = rank_vault_summaries(summaries, Some(10_000.0), Some(VaultSortArg::Apy), None);
+        let ids: Vec<&str> = by_apy.iter().map(|s| s.address.as_str()).collect();
+        assert_eq!(ids, vec!["d", "b"]);
+    }
+
+    #[test]
+    fn test_project_vault_summaries() {
+        let summaries = vec![summary("a", Some(1.0), Some(0.1))];
+
+        let projected =
+            project_vault_summaries(&summaries, &[VaultFieldArg::Address, VaultFieldArg::Apy])
+                .unwrap();
+        assert_eq!(
+            projected[0],
+            serde_json::json!({"address": "a", "apyNet": 0.1})
+        );
+
+        let full = project_vault_summaries(&summaries, &[]).unwrap();
+        assert_eq!(full[0].as_object().unwrap().len(), 6);
+    }
+}
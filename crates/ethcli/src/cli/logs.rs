@@ -19,8 +19,25 @@ use std::path::PathBuf;
   # Multiple events with resume support
   ethcli logs -c 0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D -e "Swap" -e "Sync" -f 19000000 --resume
 
+  # Decode with an inline human-readable ABI (one signature per line)
+  ethcli logs -c 0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48 -e Transfer -f 21500000 -t 21500010 \
+    --abi-str "event Transfer(address indexed from, address indexed to, uint256 value)"
+
+  # Skip the signature-database fallback for events the contract ABI lacks
+  ethcli logs -c 0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48 -f 21500000 -t 21500010 --no-auto-abi
+
   # Raw logs without decoding
-  ethcli logs -c 0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48 -f 21500000 -t 21500100 --raw"#)]
+  ethcli logs -c 0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48 -f 21500000 -t 21500100 --raw
+
+  # Follow new Transfer events as they arrive (Ctrl-C to stop)
+  ethcli logs -c 0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48 -e Transfer --since 10m --watch -o ndjson
+
+  # Project request count and duration before a long fetch (nothing is written)
+  ethcli logs -c 0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48 -e Transfer -f 16000000 -t 21000000 --estimate
+
+  # Same contract on several chains in one run (rows gain chain/chain_id columns)
+  ethcli logs --chain ethereum,arbitrum,base -c 0x000000000022D473030F116dDEE9F6B43aC78BA3 \
+    -e "Approval(address,address,address,uint160,uint48)" --since 1d -o ndjson"#)]
 pub struct LogsArgs {
     /// Contract address to fetch logs from
     #[arg(short, long, value_name = "ADDRESS")]
@@ -30,10 +47,15 @@ pub struct LogsArgs {
     #[arg(short, long, action = clap::ArgAction::Append, value_name = "EVENT")]
     pub event: Vec<String>,
 
-    /// Path to ABI JSON file
+    /// Path to ABI file (JSON ABI, or human-readable with one signature per line)
     #[arg(long, value_name = "FILE")]
     pub abi: Option<PathBuf>,
 
+    /// Inline human-readable ABI, e.g. "event Transfer(address indexed from, address indexed to, uint256 value)"
+    /// (separate multiple signatures with newlines)
+    #[arg(long, value_name = "SIGNATURES")]
+    pub abi_str: Option<String>,
+
     /// Start block number (omit or use "auto" to start from contract creation)
     #[arg(short = 'f', long, conflicts_with = "since", value_name = "BLOCK")]
     pub from_block: Option<String>,
@@ -51,14 +73,20 @@ pub struct LogsArgs {
     #[arg(short = 'O', long, value_name = "FILE")]
     pub output: Option<PathBuf>,
 
-    /// Output format (json, ndjson, csv, sqlite)
-    #[arg(short = 'o', long, default_value = "json", value_name = "FORMAT")]
-    pub format: String,
+    /// Output format (json, ndjson, csv, sqlite) [default: json].
+    /// With --estimate, json prints the estimate as JSON
+    #[arg(short = 'o', long, value_name = "FORMAT")]
+    pub format: Option<String>,
 
     /// Fetch raw logs without decoding
     #[arg(long)]
     pub raw: bool,
 
+    /// Decode only with --abi/--abi-str/-e or the contract's ABI: don't fetch
+    /// ABIs per emitting address or fall back to the signature database
+    #[arg(long, conflicts_with = "raw")]
+    pub no_auto_abi: bool,
+
     /// Include block timestamps in output (requires extra RPC calls)
     #[arg(long)]
     pub timestamps: bool,
@@ -75,7 +103,8 @@ pub struct LogsArgs {
     #[arg(long, value_name = "FILE")]
     pub checkpoint: Option<PathBuf>,
 
-    /// Fail if any chunk fails (default: warn and continue)
+    /// Fail if any chunk fails (default: warn and continue).
+    /// With several chains, one chain failing also stops the others
     #[arg(long)]
     pub strict: bool,
 
@@ -83,6 +112,28 @@ pub struct LogsArgs {
     #[arg(long, value_name = "SIZE")]
     pub chunk_size: Option<u64>,
 
+    /// After catching up, keep polling for new blocks and print new events
+    /// as they arrive (json/ndjson only, single chain; Ctrl-C to stop)
+    #[arg(long, conflicts_with = "to_block")]
+    pub watch: bool,
+
+    /// Seconds between block number polls in --watch mode
+    #[arg(long, default_value = "2", value_name = "SECONDS", requires = "watch")]
+    pub poll_interval: u64,
+
+    /// Trailing blocks re-checked for reorgs on every --watch poll
+    #[arg(long, default_value = "3", value_name = "BLOCKS", requires = "watch")]
+    pub reorg_depth: u64,
+
+    /// Sample log density and project requests, duration and limit problems
+    /// instead of fetching (writes no output or checkpoint)
+    #[arg(long, conflicts_with_all = ["watch", "resume", "output", "checkpoint"])]
+    pub estimate: bool,
+
+    /// Block ranges sampled by --estimate
+    #[arg(long, default_value = "8", value_name = "N", requires = "estimate")]
+    pub samples: usize,
+
     #[command(flatten)]
     pub rpc: RpcArgs,
 
@@ -9,10 +9,12 @@ pub mod blacklist;
 pub mod cast;
 pub mod ccxt;
 pub mod chainlink;
+pub mod checkpoint;
 pub mod config;
 pub mod contract;
 pub mod cowswap;
 pub mod curve;
+pub mod db;
 pub mod doctor;
 pub mod dsim;
 pub mod dune_cli;
@@ -137,7 +139,7 @@ pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
 
-    /// Chain to query
+    /// Chain to query (`logs` also accepts a comma-separated list)
     #[arg(long, default_value = "ethereum", global = true)]
     pub chain: String,
 
@@ -178,6 +180,19 @@ pub enum Commands {
         action: address::AddressCommands,
     },
 
+    /// Manage `logs --resume` checkpoints (list, inspect, clean, merge)
+    #[command(visible_alias = "ckpt")]
+    Checkpoint {
+        #[command(subcommand)]
+        action: checkpoint::CheckpointCommands,
+    },
+
+    /// Query SQLite output databases (read-only SQL, saved reports, schema)
+    Db {
+        #[command(subcommand)]
+        action: db::DbCommands,
+    },
+
     /// Token blacklist (exclude spam/scam tokens from portfolio)
     #[command(visible_alias = "bl")]
     Blacklist(blacklist::BlacklistArgs),
@@ -274,10 +289,21 @@ pub enum Commands {
     Nfts(nfts::NftsArgs),
 
     /// Check for updates and optionally install latest version
+    ///
+    /// Installs are verified against the release signing key; the replaced
+    /// binary is kept as `ethcli.bak` for `--rollback`.
     Update {
-        /// Automatically download and install the update
+        /// Automatically download, verify and install the update
         #[arg(long)]
         install: bool,
+
+        /// Restore the binary replaced by the last install
+        #[arg(long, conflicts_with_all = ["install", "channel"])]
+        rollback: bool,
+
+        /// Release channel to check (nightly includes pre-releases)
+        #[arg(long, value_enum, default_value = "stable")]
+        channel: update::Channel,
     },
 
     /// Check configuration and endpoint health
@@ -2,12 +2,15 @@
 //!
 //! Commands for reading blockchain state
 
+use crate::abi::{decode_types, parse_abi_source, ContractCall};
 use crate::cli::OutputFormat;
-use crate::config::{Chain, ConfigFile, EndpointConfig};
-use crate::rpc::Endpoint;
-use alloy::primitives::{Address, B256, U256};
+use crate::config::{Chain, ConfigFile, EndpointConfig, NodeType};
+use crate::rpc::{Endpoint, MulticallBuilder, MulticallResult};
+use alloy::primitives::{Address, Bytes, B256, U256};
 use alloy::providers::Provider;
 use clap::Subcommand;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 use std::str::FromStr;
 
 #[derive(Subcommand)]
@@ -15,8 +18,11 @@ use std::str::FromStr;
   # Call a contract with function signature
   ethcli rpc call 0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48 -s "totalSupply()" -d uint256
 
-  # Call with arguments and decode output
-  ethcli rpc call 0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48 -s "balanceOf(address)" -a 0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045 -d uint256
+  # Call with arguments and decode the return value (foundry-style signature)
+  ethcli rpc call 0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48 -s "balanceOf(address)(uint256)" -a 0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045 --decimals 6
+
+  # Call a function from an ABI file at a past block (needs an archive endpoint)
+  ethcli rpc call 0xB4e16d0168e52d35CaCD2c6185b44281Ec28C9Dc --abi pair.json --fn getReserves -b 15000000
 
   # Get block info
   ethcli rpc block latest
@@ -25,6 +31,9 @@ use std::str::FromStr;
   # Read storage slot
   ethcli rpc storage 0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48 0
 
+  # Batch many calls into one Multicall3 eth_call
+  ethcli rpc multicall --file calls.json --format json
+
   # Get transaction receipt
   ethcli rpc receipt 0x123abc..."#)]
 pub enum RpcCommands {
@@ -33,7 +42,12 @@ pub enum RpcCommands {
     /// Examples:
     ///   ethcli rpc call 0x... 0xa9059cbb...              # Raw calldata
     ///   ethcli rpc call 0x... -s "balanceOf(address)" -a 0xabc...  # With signature
-    ///   ethcli rpc call 0x... -s "totalSupply()" -d uint256        # With decode
+    ///   ethcli rpc call 0x... -s "totalSupply()(uint256)"          # Decode return value
+    ///   ethcli rpc call 0x... --abi token.json --fn balanceOf -a 0xabc...
+    ///
+    /// Return values print bare when there is one scalar, and as JSON for
+    /// arrays, tuples and multiple outputs. Calls at an old block are
+    /// checked against the endpoint's node type (see `endpoints optimize`).
     Call {
         /// Contract address
         #[arg(value_name = "ADDRESS")]
@@ -43,11 +57,25 @@ pub enum RpcCommands {
         #[arg(value_name = "DATA")]
         data: Option<String>,
 
-        /// Function signature (e.g., "balanceOf(address)")
-        #[arg(long, short, conflicts_with = "data", value_name = "SIG")]
+        /// Function signature, optionally with return types
+        /// (e.g., "balanceOf(address)(uint256)")
+        #[arg(long, short, conflicts_with_all = ["data", "abi"], value_name = "SIG")]
         sig: Option<String>,
 
-        /// Function arguments (use with --sig)
+        /// JSON ABI file to take the function from (use with --fn)
+        #[arg(
+            long,
+            conflicts_with = "data",
+            requires = "function",
+            value_name = "FILE"
+        )]
+        abi: Option<PathBuf>,
+
+        /// Function name in the --abi file
+        #[arg(long = "fn", requires = "abi", value_name = "NAME")]
+        function: Option<String>,
+
+        /// Function arguments (use with --sig or --abi)
         #[arg(long, short = 'a', num_args = 1.., value_delimiter = ' ', value_name = "ARG")]
         args: Option<Vec<String>>,
 
@@ -55,9 +83,42 @@ pub enum RpcCommands {
         #[arg(long, short, default_value = "latest", value_name = "BLOCK")]
         block: String,
 
-        /// Decode output as type (e.g., "uint256", "(address,uint256)")
+        /// Decode output as type (e.g., "uint256", "(address,uint256)");
+        /// overrides return types from --sig or --abi
         #[arg(long, short, value_name = "TYPE")]
         decode: Option<String>,
+
+        /// Scale decoded integers by this many decimals (e.g., 6 for USDC)
+        #[arg(long, value_name = "N")]
+        decimals: Option<u8>,
+    },
+
+    /// Batch eth_calls into a single Multicall3 call
+    ///
+    /// The file holds a JSON array of calls; `allowFailure` and `decode`
+    /// are optional:
+    ///   [{"to": "0x...", "data": "0x18160ddd", "decode": "uint256"},
+    ///    {"to": "0x...", "data": "0x...", "allowFailure": false}]
+    ///
+    /// Calls may fail individually (their success flag is reported) unless
+    /// `allowFailure` is false or --strict is set, in which case one
+    /// failure reverts the whole batch.
+    Multicall {
+        /// JSON file with the calls ("-" for stdin)
+        #[arg(long, short, value_name = "FILE")]
+        file: String,
+
+        /// Revert the whole batch if any call fails
+        #[arg(long)]
+        strict: bool,
+
+        /// Block number or "latest" (default: latest)
+        #[arg(long, short, default_value = "latest", value_name = "BLOCK")]
+        block: String,
+
+        /// Output format
+        #[arg(long, short = 'o', default_value = "table")]
+        format: OutputFormat,
     },
 
     /// Get block information
@@ -139,9 +200,15 @@ pub async fn handle(
     rpc_url: Option<String>,
     quiet: bool,
 ) -> anyhow::Result<()> {
+    // A historical call prefers an endpoint known to hold that block's state
+    let historical_block = match action {
+        RpcCommands::Call { block, .. } => block_number(&parse_block_id(block)?),
+        _ => None,
+    };
+
     // Get RPC endpoint
-    let endpoint = if let Some(url) = rpc_url {
-        Endpoint::new(EndpointConfig::new(url), 30, None)?
+    let endpoint_config = if let Some(url) = rpc_url {
+        EndpointConfig::new(url)
     } else {
         // Use config endpoints
         let config = ConfigFile::load_default()
@@ -160,8 +227,16 @@ pub async fn handle(
                 chain.display_name()
             ));
         }
-        Endpoint::new(chain_endpoints[0].clone(), 30, None)?
+        historical_block
+            .and_then(|block| {
+                chain_endpoints
+                    .iter()
+                    .find(|e| e.is_archive() && e.can_serve_block(block))
+            })
+            .unwrap_or(&chain_endpoints[0])
+            .clone()
     };
+    let endpoint = Endpoint::new(endpoint_config.clone(), 30, None)?;
 
     let provider = endpoint.provider();
 
@@ -170,31 +245,48 @@ pub async fn handle(
             to,
             data,
             sig,
+            abi,
+            function,
             args,
             block,
             decode,
+            decimals,
         } => {
             let to_addr =
                 Address::from_str(to).map_err(|e| anyhow::anyhow!("Invalid address: {}", e))?;
-
-            // Build calldata from either raw data or signature + args
-            let calldata = if let Some(signature) = sig {
-                // Encode from function signature
-                let func_args = args.as_deref().unwrap_or(&[]);
-                let encoded = super::cast::abi_encode(signature, func_args)?;
-                let hex_str = encoded.strip_prefix("0x").unwrap_or(&encoded);
-                hex::decode(hex_str).map_err(|e| anyhow::anyhow!("Encoding error: {}", e))?
+            let func_args = args.as_deref().unwrap_or(&[]);
+
+            // Build calldata from raw data, a signature, or an ABI function
+            let call = if let Some(signature) = sig {
+                Some(ContractCall::from_signature(signature)?)
+            } else if let (Some(path), Some(name)) = (abi, function) {
+                let contents = std::fs::read_to_string(path)
+                    .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+                let json_abi = parse_abi_source(&contents)?;
+                Some(ContractCall::from_abi(&json_abi, name, func_args.len())?)
+            } else {
+                None
+            };
+            let calldata = if let Some(call) = &call {
+                call.encode(func_args)?
             } else if let Some(data) = data {
                 // Use raw calldata
                 let data_hex = data.strip_prefix("0x").unwrap_or(data);
                 hex::decode(data_hex).map_err(|e| anyhow::anyhow!("Invalid calldata: {}", e))?
             } else {
                 return Err(anyhow::anyhow!(
-                    "Calldata or --sig required. Use: rpc call <addr> <calldata> OR rpc call <addr> -s \"func()\""
+                    "Calldata, --sig or --abi required. Use: rpc call <addr> <calldata> OR rpc call <addr> -s \"func()\""
                 ));
             };
 
             let block_id = parse_block_id(block)?;
+            if let Some(number) = block_number(&block_id) {
+                let latest = match endpoint_config.node_type {
+                    NodeType::Full => Some(provider.get_block_number().await?),
+                    _ => None,
+                };
+                check_historical_block(&endpoint_config, number, latest)?;
+            }
 
             let tx = alloy::rpc::types::TransactionRequest::default()
                 .to(to_addr)
@@ -204,16 +296,72 @@ pub async fn handle(
                 .call(tx)
                 .block(block_id)
                 .await
-                .map_err(|e| anyhow::anyhow!("Call failed: {}", e))?;
+                .map_err(|e| call_error(e, block_number(&block_id)))?;
 
             if let Some(type_sig) = decode {
-                let decoded = decode_output(&result, type_sig)?;
-                println!("{}", decoded);
+                println!("{}", decode_types(&result, type_sig, *decimals)?);
+            } else if let Some(call) = call.as_ref().filter(|c| c.has_outputs()) {
+                println!("{}", call.format_output(&result, *decimals)?);
             } else {
                 println!("0x{}", hex::encode(&result));
             }
         }
 
+        RpcCommands::Multicall {
+            file,
+            strict,
+            block,
+            format,
+        } => {
+            let contents = if file == "-" {
+                std::io::read_to_string(std::io::stdin())
+                    .map_err(|e| anyhow::anyhow!("Failed to read stdin: {}", e))?
+            } else {
+                std::fs::read_to_string(file)
+                    .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", file, e))?
+            };
+            let calls = parse_multicall_calls(&contents, !strict)?;
+            if calls.is_empty() {
+                return Err(anyhow::anyhow!("No calls in {}", file));
+            }
+
+            let builder = calls
+                .iter()
+                .fold(MulticallBuilder::new(), |builder, call| {
+                    builder.add_call(call.to, call.data.clone(), call.allow_failure)
+                })
+                .at_block(parse_block_id(block)?);
+            let results = builder.execute_with_retry(&provider, 2).await?;
+            let rows = multicall_rows(&calls, &results);
+
+            if matches!(format, OutputFormat::Json) {
+                println!("{}", serde_json::to_string_pretty(&rows)?);
+            } else if matches!(format, OutputFormat::Ndjson) {
+                for row in &rows {
+                    println!("{}", serde_json::to_string(row)?);
+                }
+            } else {
+                for row in &rows {
+                    let status = if row.success { "ok  " } else { "FAIL" };
+                    let value = row.decoded.as_deref().unwrap_or(&row.return_data);
+                    println!("[{}] {} {}  {}", row.index, status, row.to, value);
+                    if let Some(err) = &row.decode_error {
+                        println!("         decode error: {}", err);
+                    }
+                }
+            }
+
+            if !quiet {
+                let failed = rows.iter().filter(|r| !r.success).count();
+                eprintln!(
+                    "\n{} calls, {} succeeded, {} failed",
+                    rows.len(),
+                    rows.len() - failed,
+                    failed
+                );
+            }
+        }
+
         RpcCommands::Block {
             block,
             full,
@@ -420,16 +568,182 @@ pub fn parse_block_id(block: &str) -> anyhow::Result<alloy::eips::BlockId> {
     }
 }
 
-fn decode_output(data: &[u8], type_sig: &str) -> anyhow::Result<String> {
+/// One entry of a `rpc multicall` file
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MulticallEntry {
+    #[serde(alias = "target")]
+    to: String,
+    #[serde(alias = "callData", alias = "calldata")]
+    data: String,
+    allow_failure: Option<bool>,
+    decode: Option<String>,
+}
+
+/// A validated call ready to be batched
+#[derive(Debug, Clone, PartialEq)]
+pub struct MulticallCall {
+    pub to: Address,
+    pub data: Bytes,
+    pub allow_failure: bool,
+    /// Output type to decode successful results as
+    pub decode: Option<String>,
+}
+
+/// Parse and validate a JSON array of `{to, data, allowFailure?, decode?}`
+///
+/// `allow_failure` is used for entries that don't set `allowFailure`.
+pub fn parse_multicall_calls(
+    json: &str,
+    allow_failure: bool,
+) -> anyhow::Result<Vec<MulticallCall>> {
     use alloy::dyn_abi::DynSolType;
 
-    let ty = DynSolType::parse(type_sig).map_err(|e| anyhow::anyhow!("Invalid type: {}", e))?;
+    let entries: Vec<MulticallEntry> =
+        serde_json::from_str(json).map_err(|e| anyhow::anyhow!("Invalid calls file: {}", e))?;
+
+    entries
+        .into_iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let to = Address::from_str(&entry.to)
+                .map_err(|e| anyhow::anyhow!("Call {}: invalid address: {}", i, e))?;
+            let data_hex = entry.data.strip_prefix("0x").unwrap_or(&entry.data);
+            let data = hex::decode(data_hex)
+                .map_err(|e| anyhow::anyhow!("Call {}: invalid calldata: {}", i, e))?;
+            if let Some(type_sig) = &entry.decode {
+                DynSolType::parse(type_sig)
+                    .map_err(|e| anyhow::anyhow!("Call {}: invalid decode type: {}", i, e))?;
+            }
+            Ok(MulticallCall {
+                to,
+                data: data.into(),
+                allow_failure: entry.allow_failure.unwrap_or(allow_failure),
+                decode: entry.decode,
+            })
+        })
+        .collect()
+}
+
+/// Result of one call in `rpc multicall` output
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MulticallRow {
+    pub index: usize,
+    pub to: String,
+    pub success: bool,
+    /// Raw return data (revert data for failed calls)
+    pub return_data: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decoded: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decode_error: Option<String>,
+}
 
-    let decoded = ty
-        .abi_decode(data)
-        .map_err(|e| anyhow::anyhow!("Failed to decode: {}", e))?;
+/// Pair batched calls with their results, decoding successful ones
+pub fn multicall_rows(calls: &[MulticallCall], results: &[MulticallResult]) -> Vec<MulticallRow> {
+    calls
+        .iter()
+        .zip(results)
+        .enumerate()
+        .map(|(index, (call, result))| {
+            let (decoded, decode_error) = match &call.decode {
+                Some(type_sig) if result.success => match decode_output(&result.data, type_sig) {
+                    Ok(value) => (Some(value), None),
+                    Err(e) => (None, Some(e.to_string())),
+                },
+                _ => (None, None),
+            };
+            MulticallRow {
+                index,
+                to: call.to.to_checksum(None),
+                success: result.success,
+                return_data: format!("0x{}", hex::encode(&result.data)),
+                decoded,
+                decode_error,
+            }
+        })
+        .collect()
+}
+
+fn decode_output(data: &[u8], type_sig: &str) -> anyhow::Result<String> {
+    Ok(decode_types(data, type_sig, None)?)
+}
+
+/// Blocks of recent state a full (non-archive) node keeps
+pub const FULL_NODE_STATE_BLOCKS: u64 = 128;
+
+/// Block number of a numbered block id (tags and hashes give `None`)
+fn block_number(block_id: &alloy::eips::BlockId) -> Option<u64> {
+    match block_id {
+        alloy::eips::BlockId::Number(alloy::eips::BlockNumberOrTag::Number(n)) => Some(*n),
+        _ => None,
+    }
+}
+
+/// Check from the endpoint's recorded node type that it can serve state at
+/// `block`
+///
+/// `latest` is the chain head, needed to judge a full node's recent-state
+/// window; endpoints of unknown type are given the benefit of the doubt.
+pub fn check_historical_block(
+    endpoint: &EndpointConfig,
+    block: u64,
+    latest: Option<u64>,
+) -> anyhow::Result<()> {
+    let url = crate::error::sanitize_error_message(&endpoint.url);
+    match endpoint.node_type {
+        NodeType::Archive => match endpoint.archive_from_block {
+            Some(from) if block < from => Err(anyhow::anyhow!(
+                "Endpoint {} only has state from block {}; block {} needs a full archive endpoint.\n\
+                 Add one with: ethcli endpoints add <archive-url>",
+                url,
+                from,
+                block
+            )),
+            _ => Ok(()),
+        },
+        NodeType::Full => {
+            let age = latest.map_or(0, |head| head.saturating_sub(block));
+            if age > FULL_NODE_STATE_BLOCKS {
+                Err(anyhow::anyhow!(
+                    "Endpoint {} is a full node and keeps state for about the last {} blocks; \
+                     block {} is {} blocks old and needs an archive endpoint.\n\
+                     Add one with: ethcli endpoints add <archive-url>",
+                    url,
+                    FULL_NODE_STATE_BLOCKS,
+                    block,
+                    age
+                ))
+            } else {
+                Ok(())
+            }
+        }
+        NodeType::Unknown => Ok(()),
+    }
+}
 
-    Ok(format!("{:?}", decoded))
+/// Explain pruned-state failures of historical calls
+fn call_error(error: impl std::fmt::Display, block: Option<u64>) -> anyhow::Error {
+    let message = error.to_string();
+    let lower = message.to_lowercase();
+    let pruned = [
+        "missing trie node",
+        "pruned",
+        "state is not available",
+        "header not found",
+    ]
+    .iter()
+    .any(|needle| lower.contains(needle));
+    match block {
+        Some(block) if pruned => anyhow::anyhow!(
+            "Call failed: {}\nThe endpoint has no state for block {}; it is probably not an archive node. \
+             Run `ethcli endpoints optimize <url>` to record its node type, or use an archive endpoint.",
+            message,
+            block
+        ),
+        _ => anyhow::anyhow!("Call failed: {}", message),
+    }
 }
 
 #[cfg(test)]
@@ -515,6 +829,111 @@ mod tests {
         assert!(result.is_err());
     }
 
+    // ==================== multicall tests ====================
+
+    const USDC: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+
+    #[test]
+    fn test_parse_multicall_calls() {
+        let json = format!(
+            r#"[
+                {{"to": "{USDC}", "data": "0x18160ddd", "decode": "uint256"}},
+                {{"target": "{USDC}", "callData": "313ce567", "allowFailure": false}}
+            ]"#
+        );
+        let calls = parse_multicall_calls(&json, true).unwrap();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].to, Address::from_str(USDC).unwrap());
+        assert_eq!(calls[0].data.as_ref(), [0x18, 0x16, 0x0d, 0xdd]);
+        assert!(calls[0].allow_failure);
+        assert_eq!(calls[0].decode.as_deref(), Some("uint256"));
+        assert!(!calls[1].allow_failure);
+        assert_eq!(calls[1].decode, None);
+
+        // --strict flips the default but not explicit entries
+        let json = format!(
+            r#"[{{"to": "{USDC}", "data": "0x", "allowFailure": true}}, {{"to": "{USDC}", "data": "0x"}}]"#
+        );
+        let calls = parse_multicall_calls(&json, false).unwrap();
+        assert!(calls[0].allow_failure);
+        assert!(!calls[1].allow_failure);
+    }
+
+    #[test]
+    fn test_parse_multicall_calls_errors_name_entry() {
+        let json = format!(r#"[{{"to": "{USDC}", "data": "0x"}}, {{"to": "0x12", "data": "0x"}}]"#);
+        let err = parse_multicall_calls(&json, true).unwrap_err();
+        assert!(
+            err.to_string().starts_with("Call 1: invalid address"),
+            "{err}"
+        );
+
+        let json = format!(r#"[{{"to": "{USDC}", "data": "0xzz"}}]"#);
+        let err = parse_multicall_calls(&json, true).unwrap_err();
+        assert!(
+            err.to_string().starts_with("Call 0: invalid calldata"),
+            "{err}"
+        );
+
+        let json = format!(r#"[{{"to": "{USDC}", "data": "0x", "decode": "uint257"}}]"#);
+        let err = parse_multicall_calls(&json, true).unwrap_err();
+        assert!(
+            err.to_string().starts_with("Call 0: invalid decode type"),
+            "{err}"
+        );
+
+        assert!(parse_multicall_calls("{}", true).is_err());
+    }
+
+    #[test]
+    fn test_multicall_rows() {
+        let json = format!(
+            r#"[
+                {{"to": "{USDC}", "data": "0x18160ddd", "decode": "uint256"}},
+                {{"to": "{USDC}", "data": "0x12345678", "decode": "uint256"}},
+                {{"to": "{USDC}", "data": "0x313ce567", "decode": "uint256"}}
+            ]"#
+        );
+        let calls = parse_multicall_calls(&json, true).unwrap();
+        let mut word = [0u8; 32];
+        word[31] = 6;
+        let results = [
+            MulticallResult {
+                success: true,
+                data: Bytes::copy_from_slice(&word),
+            },
+            MulticallResult {
+                success: false,
+                data: Bytes::new(),
+            },
+            MulticallResult {
+                success: true,
+                data: Bytes::from_static(&[1, 2]),
+            },
+        ];
+
+        let rows = multicall_rows(&calls, &results);
+        assert_eq!(rows[0].to, USDC);
+        assert!(rows[0].success);
+        assert!(rows[0].decoded.as_deref().unwrap().contains('6'));
+        // Failed calls are not decoded
+        assert!(!rows[1].success);
+        assert_eq!(rows[1].return_data, "0x");
+        assert_eq!(
+            (rows[1].decoded.clone(), rows[1].decode_error.clone()),
+            (None, None)
+        );
+        // Undecodable data keeps the raw bytes and reports why
+        assert_eq!(rows[2].return_data, "0x0102");
+        assert!(rows[2].decode_error.is_some());
+
+        let json = serde_json::to_value(&rows[1]).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"index": 1, "to": USDC, "success": false, "returnData": "0x"})
+        );
+    }
+
     // ==================== decode_output tests ====================
 
     #[test]
@@ -556,6 +975,41 @@ mod tests {
             .contains("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"));
     }
 
+    // ==================== historical block checks ====================
+
+    #[test]
+    fn test_check_historical_block() {
+        let unknown = EndpointConfig::new("https://rpc.example.com");
+        assert!(check_historical_block(&unknown, 1, Some(21_000_000)).is_ok());
+
+        let archive =
+            EndpointConfig::new("https://archive.example.com").with_node_type(NodeType::Archive);
+        assert!(check_historical_block(&archive, 1, Some(21_000_000)).is_ok());
+
+        let partial = archive.clone().with_archive_from_block(15_000_000);
+        let err = check_historical_block(&partial, 14_000_000, None).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("only has state from block 15000000"));
+        assert!(check_historical_block(&partial, 15_000_000, None).is_ok());
+
+        let full = EndpointConfig::new("https://full.example.com").with_node_type(NodeType::Full);
+        assert!(check_historical_block(&full, 20_999_900, Some(21_000_000)).is_ok());
+        let err = check_historical_block(&full, 20_000_000, Some(21_000_000)).unwrap_err();
+        assert!(err.to_string().contains("full node"));
+        assert!(err.to_string().contains("1000000 blocks old"));
+    }
+
+    #[test]
+    fn test_call_error_hint() {
+        let err = call_error("missing trie node abc", Some(100));
+        assert!(err.to_string().contains("no state for block 100"));
+        let err = call_error("missing trie node abc", None);
+        assert!(!err.to_string().contains("archive"));
+        let err = call_error("execution reverted", Some(100));
+        assert_eq!(err.to_string(), "Call failed: execution reverted");
+    }
+
     #[test]
     fn test_decode_output_invalid_type() {
         let data = vec![0u8; 32];
@@ -2,10 +2,12 @@
 //!
 //! Look up function selectors and event topics from 4byte.directory
 
+use crate::abi::parse_signature_import;
 use crate::config::Chain;
 use crate::etherscan::Client;
 use clap::Subcommand;
 use std::io::Write;
+use std::path::PathBuf;
 
 #[derive(Subcommand)]
 pub enum SigCommands {
@@ -29,6 +31,18 @@ pub enum SigCommands {
 
     /// Clear the signature cache
     CacheClear,
+
+    /// Import signatures into the cache for offline decoding
+    ///
+    /// Accepts a JSON ABI or compiler artifact, a JSON array of signatures,
+    /// a JSON object of selector/topic -> signature, or plain text with one
+    /// signature per line. Imported signatures never expire and are used
+    /// before querying 4byte.directory.
+    Import {
+        /// Signature or ABI file
+        #[arg(long, short)]
+        file: PathBuf,
+    },
 }
 
 pub async fn handle(
@@ -112,6 +126,12 @@ pub async fn handle(
                 "ABIs:      {} cached ({} valid)",
                 stats.total_abis, stats.valid_abis
             );
+            if stats.imported_functions + stats.imported_events > 0 {
+                println!(
+                    "Imported:  {} functions, {} events",
+                    stats.imported_functions, stats.imported_events
+                );
+            }
             println!("Path:      {}", stats.cache_path.display());
         }
 
@@ -120,6 +140,34 @@ pub async fn handle(
             client.cache().clear();
             println!("Signature cache cleared");
         }
+
+        SigCommands::Import { file } => {
+            let content = std::fs::read_to_string(file)
+                .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", file.display(), e))?;
+            let import = parse_signature_import(&content)?;
+            if import.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "No function or event signatures found in {}",
+                    file.display()
+                ));
+            }
+
+            let client = Client::new(chain, api_key)?;
+            let stats = client
+                .cache()
+                .import_signatures(&import.functions, &import.events)?;
+
+            println!(
+                "Imported {} function and {} event signatures ({} already cached)",
+                stats.functions_added, stats.events_added, stats.already_cached
+            );
+            if import.mismatched > 0 && !quiet {
+                eprintln!(
+                    "Skipped {} entries whose selector did not match their signature",
+                    import.mismatched
+                );
+            }
+        }
     }
 
     Ok(())
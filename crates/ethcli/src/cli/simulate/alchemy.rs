@@ -1,12 +1,14 @@
 //! Alchemy Simulation API handlers
 
+use super::outcome::SimulationOutcome;
 use super::{build_calldata, AlchemyArgs};
 use alcmy::simulation::{ExecutionFormat, SimulationTransaction};
 
 /// Simulate a transaction via Alchemy's simulateAssetChanges API
 ///
 /// Returns a list of asset changes (transfers, approvals, etc.) that would occur
-/// if the transaction were executed.
+/// if the transaction were executed. With `collect`, the normalized outcome
+/// is returned instead of printing the changes.
 #[allow(clippy::too_many_arguments)]
 pub async fn simulate_via_alchemy(
     to: &str,
@@ -19,7 +21,8 @@ pub async fn simulate_via_alchemy(
     gas_price: Option<u64>,
     alchemy: &AlchemyArgs,
     quiet: bool,
-) -> anyhow::Result<()> {
+    collect: bool,
+) -> anyhow::Result<Option<SimulationOutcome>> {
     let client = alchemy.create_client()?;
 
     // Build calldata from signature or raw data
@@ -58,13 +61,17 @@ pub async fn simulate_via_alchemy(
         .await
         .map_err(|e| anyhow::anyhow!("Alchemy simulation failed: {}", e))?;
 
+    if collect {
+        return Ok(Some(SimulationOutcome::from_alchemy(&response)));
+    }
+
     // Check for errors
     if let Some(ref err) = response.error {
         eprintln!("Simulation Error: {}", err.message);
         if let Some(ref reason) = err.revert_reason {
             eprintln!("Revert Reason: {}", reason);
         }
-        return Ok(());
+        return Ok(None);
     }
 
     // Print results
@@ -107,7 +114,7 @@ pub async fn simulate_via_alchemy(
         println!("\nGas Used: {}", gas_used);
     }
 
-    Ok(())
+    Ok(None)
 }
 
 /// Simulate a transaction with full execution trace via Alchemy
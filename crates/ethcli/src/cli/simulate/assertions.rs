@@ -0,0 +1,982 @@
+//! Declarative checks on a simulation result, for use in CI
+//!
+//! `ethcli simulate call ... --assert-file expectations.toml` runs the
+//! simulation, checks it against the file and exits non-zero if any check
+//! fails. Addresses may be address-book labels.
+//!
+//! ```toml
+//! success = true                  # the call must not revert
+//! max_gas = 250000
+//!
+//! [[event]]
+//! address = "usdc"
+//! signature = "Transfer(address indexed from, address indexed to, uint256 value)"
+//! args = { to = "treasury", value = "1000000" }
+//!
+//! [[storage]]
+//! address = "vault"
+//! slot = "0x5"
+//! equals = "0x1"
+//!
+//! [[balance]]
+//! holder = "treasury"
+//! token = "usdc"                  # omit for ETH
+//! min = "1000000"                 # net change in raw units, inclusive
+//! ```
+//!
+//! Events match on `signature` (decoded from raw logs, so any backend that
+//! reports logs works) or on `name` (needs a backend that decodes logs, such
+//! as Tenderly). `count` requires an exact number of matches; without it at
+//! least one must match. Storage checks see only slots the call wrote; which
+//! backends report which effects is listed in [`super::outcome`].
+
+use super::outcome::{
+    normalize_word, parse_i256, sol_value_string, SimulatedLog, SimulationOutcome,
+};
+use crate::config::AddressBook;
+use alloy::dyn_abi::EventExt;
+use alloy::json_abi::Event;
+use alloy::primitives::{B256, I256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Contents of an assertion file
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AssertionSpec {
+    /// Require the call to succeed (`true`) or revert (`false`)
+    pub success: Option<bool>,
+    /// Require a revert whose reason contains this text
+    pub revert_reason: Option<String>,
+    /// Maximum gas used
+    pub max_gas: Option<u64>,
+    /// Expected events
+    #[serde(default, rename = "event")]
+    pub events: Vec<EventSpec>,
+    /// Expected storage values
+    #[serde(default)]
+    pub storage: Vec<StorageSpec>,
+    /// Expected balance changes
+    #[serde(default, rename = "balance")]
+    pub balances: Vec<BalanceSpec>,
+}
+
+/// `[[event]]` entry
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EventSpec {
+    /// Emitting contract
+    pub address: Option<String>,
+    /// Event name as decoded by the backend
+    pub name: Option<String>,
+    /// Event signature, optionally with `indexed` markers and parameter names
+    pub signature: Option<String>,
+    /// Expected argument values by parameter name
+    #[serde(default)]
+    pub args: BTreeMap<String, Scalar>,
+    /// Exact number of matching logs
+    pub count: Option<usize>,
+}
+
+/// `[[storage]]` entry
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StorageSpec {
+    /// Contract address
+    pub address: String,
+    /// Storage slot
+    pub slot: Scalar,
+    /// Value the call must leave in the slot
+    pub equals: Scalar,
+}
+
+/// `[[balance]]` entry
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BalanceSpec {
+    /// Account whose balance changes
+    pub holder: String,
+    /// Token contract, ETH if omitted
+    pub token: Option<String>,
+    /// Minimum net change, inclusive
+    pub min: Option<Scalar>,
+    /// Maximum net change, inclusive
+    pub max: Option<Scalar>,
+    /// Exact net change
+    pub equals: Option<Scalar>,
+}
+
+/// A TOML string, integer or boolean, compared as text
+///
+/// Integers beyond `i64` must be written as strings.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum Scalar {
+    /// Quoted value
+    Text(String),
+    /// Bare integer
+    Integer(i64),
+    /// Bare boolean
+    Bool(bool),
+}
+
+impl fmt::Display for Scalar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Scalar::Text(s) => f.write_str(s),
+            Scalar::Integer(i) => write!(f, "{}", i),
+            Scalar::Bool(b) => write!(f, "{}", b),
+        }
+    }
+}
+
+/// One check, with addresses resolved and values parsed
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assertion {
+    /// Short description for the report
+    pub label: String,
+    /// What to check
+    pub check: Check,
+}
+
+/// The condition an [`Assertion`] checks
+#[derive(Debug, Clone, PartialEq)]
+pub enum Check {
+    /// Call succeeds (`true`) or reverts (`false`)
+    Success(bool),
+    /// Call reverts with a reason containing this text
+    RevertReason(String),
+    /// Gas used is at most this much
+    MaxGas(u64),
+    /// Matching logs were emitted
+    Event(EventCheck),
+    /// Slot was written with this value
+    Storage {
+        /// Contract (lowercase)
+        address: String,
+        /// 32-byte slot
+        slot: String,
+        /// Expected 32-byte value
+        equals: String,
+    },
+    /// Net balance change lies within bounds
+    Balance {
+        /// Holder (lowercase)
+        holder: String,
+        /// Token (lowercase), `None` for ETH
+        token: Option<String>,
+        /// Inclusive lower bound
+        min: Option<I256>,
+        /// Inclusive upper bound
+        max: Option<I256>,
+    },
+}
+
+/// Log filter for [`Check::Event`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventCheck {
+    /// Emitting contract (lowercase)
+    pub address: Option<String>,
+    /// Decoded event name
+    pub name: Option<String>,
+    /// Parsed signature, used to match topic0 and decode arguments
+    pub event: Option<Event>,
+    /// Expected arguments, labels resolved
+    pub args: BTreeMap<String, String>,
+    /// Exact match count
+    pub count: Option<usize>,
+}
+
+impl AssertionSpec {
+    /// Parse an assertion file
+    pub fn from_toml(source: &str) -> anyhow::Result<Self> {
+        toml::from_str(source).map_err(|e| anyhow::anyhow!("Invalid assertion file: {}", e))
+    }
+
+    /// Resolve labels, parse values and check the spec is consistent
+    pub fn compile(&self, book: &AddressBook) -> anyhow::Result<Vec<Assertion>> {
+        let mut assertions = Vec::new();
+
+        if self.revert_reason.is_some() && self.success == Some(true) {
+            anyhow::bail!("`revert_reason` conflicts with `success = true`");
+        }
+        if let Some(expected) = self.success {
+            let label = if expected {
+                "call succeeds"
+            } else {
+                "call reverts"
+            };
+            assertions.push(Assertion {
+                label: label.to_string(),
+                check: Check::Success(expected),
+            });
+        }
+        if let Some(reason) = &self.revert_reason {
+            assertions.push(Assertion {
+                label: format!("reverts with \"{}\"", reason),
+                check: Check::RevertReason(reason.clone()),
+            });
+        }
+        if let Some(cap) = self.max_gas {
+            assertions.push(Assertion {
+                label: format!("gas used <= {}", cap),
+                check: Check::MaxGas(cap),
+            });
+        }
+
+        for (i, spec) in self.events.iter().enumerate() {
+            assertions.push(
+                compile_event(spec, book)
+                    .map_err(|e| anyhow::anyhow!("event #{}: {}", i + 1, e))?,
+            );
+        }
+        for (i, spec) in self.storage.iter().enumerate() {
+            assertions.push(
+                compile_storage(spec, book)
+                    .map_err(|e| anyhow::anyhow!("storage #{}: {}", i + 1, e))?,
+            );
+        }
+        for (i, spec) in self.balances.iter().enumerate() {
+            assertions.push(
+                compile_balance(spec, book)
+                    .map_err(|e| anyhow::anyhow!("balance #{}: {}", i + 1, e))?,
+            );
+        }
+
+        if assertions.is_empty() {
+            anyhow::bail!("Assertion file contains no assertions");
+        }
+        Ok(assertions)
+    }
+}
+
+fn compile_event(spec: &EventSpec, book: &AddressBook) -> anyhow::Result<Assertion> {
+    let event = spec
+        .signature
+        .as_deref()
+        .map(|sig| {
+            let sig = sig.trim();
+            let sig = sig.strip_prefix("event ").unwrap_or(sig);
+            Event::parse(sig).map_err(|e| anyhow::anyhow!("invalid signature '{}': {}", sig, e))
+        })
+        .transpose()?;
+    if event.as_ref().is_some_and(|e| e.anonymous) {
+        anyhow::bail!("anonymous events cannot be matched");
+    }
+    if spec.name.is_none() && event.is_none() && spec.address.is_none() {
+        anyhow::bail!("needs at least one of `address`, `name` or `signature`");
+    }
+    if let (Some(event), false) = (&event, spec.args.is_empty()) {
+        for arg in spec.args.keys() {
+            if !event.inputs.iter().any(|p| &p.name == arg) {
+                anyhow::bail!("signature has no parameter named `{}`", arg);
+            }
+        }
+    }
+
+    let address = spec
+        .address
+        .as_deref()
+        .map(|a| resolve_address(book, a))
+        .transpose()?;
+    let args = spec
+        .args
+        .iter()
+        .map(|(k, v)| (k.clone(), resolve_value(book, &v.to_string())))
+        .collect();
+
+    let what = spec
+        .name
+        .clone()
+        .or_else(|| event.as_ref().map(|e| e.name.clone()))
+        .unwrap_or_else(|| "any log".to_string());
+    let mut label = format!("event {}", what);
+    if let Some(address) = &spec.address {
+        label.push_str(&format!(" from {}", address));
+    }
+    if !spec.args.is_empty() {
+        let args: Vec<String> = spec
+            .args
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect();
+        label.push_str(&format!(" ({})", args.join(", ")));
+    }
+    if let Some(count) = spec.count {
+        label.push_str(&format!(" x{}", count));
+    }
+
+    Ok(Assertion {
+        label,
+        check: Check::Event(EventCheck {
+            address,
+            name: spec.name.clone(),
+            event,
+            args,
+            count: spec.count,
+        }),
+    })
+}
+
+fn compile_storage(spec: &StorageSpec, book: &AddressBook) -> anyhow::Result<Assertion> {
+    let word = |value: &Scalar, what: &str| {
+        normalize_word(&value.to_string())
+            .ok_or_else(|| anyhow::anyhow!("invalid {} '{}'", what, value))
+    };
+    Ok(Assertion {
+        label: format!("storage {}[{}] == {}", spec.address, spec.slot, spec.equals),
+        check: Check::Storage {
+            address: resolve_address(book, &spec.address)?,
+            slot: word(&spec.slot, "slot")?,
+            equals: word(&spec.equals, "value")?,
+        },
+    })
+}
+
+fn compile_balance(spec: &BalanceSpec, book: &AddressBook) -> anyhow::Result<Assertion> {
+    let amount = |value: &Scalar| {
+        parse_i256(&value.to_string()).ok_or_else(|| anyhow::anyhow!("invalid amount '{}'", value))
+    };
+    let (min, max) = match (&spec.equals, &spec.min, &spec.max) {
+        (Some(_), Some(_), _) | (Some(_), _, Some(_)) => {
+            anyhow::bail!("`equals` cannot be combined with `min`/`max`")
+        }
+        (Some(exact), None, None) => {
+            let exact = amount(exact)?;
+            (Some(exact), Some(exact))
+        }
+        (None, None, None) => anyhow::bail!("needs `min`, `max` or `equals`"),
+        (None, min, max) => (
+            min.as_ref().map(amount).transpose()?,
+            max.as_ref().map(amount).transpose()?,
+        ),
+    };
+    if let (Some(min), Some(max)) = (min, max) {
+        if min > max {
+            anyhow::bail!("`min` {} is greater than `max` {}", min, max);
+        }
+    }
+
+    let asset = spec.token.as_deref().unwrap_or("ETH");
+    let bounds = match (min, max) {
+        (Some(min), Some(max)) if min == max => format!("== {}", min),
+        (Some(min), Some(max)) => format!("in [{}, {}]", min, max),
+        (Some(min), None) => format!(">= {}", min),
+        (None, Some(max)) => format!("<= {}", max),
+        (None, None) => unreachable!("bounds checked above"),
+    };
+
+    Ok(Assertion {
+        label: format!("{} balance of {} changes {}", asset, spec.holder, bounds),
+        check: Check::Balance {
+            holder: resolve_address(book, &spec.holder)?,
+            token: spec
+                .token
+                .as_deref()
+                .map(|t| resolve_address(book, t))
+                .transpose()?,
+            min,
+            max,
+        },
+    })
+}
+
+/// Resolve a label or hex address to a lowercase address
+fn resolve_address(book: &AddressBook, input: &str) -> anyhow::Result<String> {
+    book.resolve(input)
+        .filter(|a| crate::utils::address::is_hex_address(a))
+        .map(|a| a.to_lowercase())
+        .ok_or_else(|| anyhow::anyhow!("unknown address or label '{}'", input))
+}
+
+/// Replace an address-book label with its address; other values pass through
+fn resolve_value(book: &AddressBook, value: &str) -> String {
+    match book.get(value) {
+        Some(entry) => entry.address.to_lowercase(),
+        None => value.to_string(),
+    }
+}
+
+/// Compare values numerically when both are integers, otherwise as text
+fn values_match(actual: &str, expected: &str) -> bool {
+    match (parse_i256(actual), parse_i256(expected)) {
+        (Some(a), Some(b)) => a == b,
+        _ => actual.trim().eq_ignore_ascii_case(expected.trim()),
+    }
+}
+
+/// Outcome of one assertion
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AssertionResult {
+    /// The assertion's label
+    pub assertion: String,
+    /// Whether it held
+    pub passed: bool,
+    /// What was observed
+    pub detail: String,
+}
+
+/// Results of evaluating an assertion file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AssertionReport {
+    /// Number of assertions that held
+    pub passed: usize,
+    /// Number of assertions that failed
+    pub failed: usize,
+    /// Per-assertion results, in file order
+    pub results: Vec<AssertionResult>,
+}
+
+impl AssertionReport {
+    /// Evaluate assertions against a simulation outcome
+    pub fn evaluate(assertions: &[Assertion], outcome: &SimulationOutcome) -> Self {
+        let results: Vec<AssertionResult> = assertions
+            .iter()
+            .map(|a| {
+                let (passed, detail) = evaluate(&a.check, outcome);
+                AssertionResult {
+                    assertion: a.label.clone(),
+                    passed,
+                    detail,
+                }
+            })
+            .collect();
+        let passed = results.iter().filter(|r| r.passed).count();
+        Self {
+            passed,
+            failed: results.len() - passed,
+            results,
+        }
+    }
+
+    /// Whether every assertion held
+    pub fn all_passed(&self) -> bool {
+        self.failed == 0
+    }
+}
+
+impl fmt::Display for AssertionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for result in &self.results {
+            let status = if result.passed { "PASS" } else { "FAIL" };
+            writeln!(f, "{}  {}", status, result.assertion)?;
+            if !result.passed {
+                writeln!(f, "      {}", result.detail)?;
+            }
+        }
+        write!(f, "\n{} passed, {} failed", self.passed, self.failed)
+    }
+}
+
+fn evaluate(check: &Check, outcome: &SimulationOutcome) -> (bool, String) {
+    let status = match (&outcome.success, &outcome.revert_reason) {
+        (true, _) => "call succeeded".to_string(),
+        (false, Some(reason)) => format!("call reverted: {}", reason),
+        (false, None) => "call reverted".to_string(),
+    };
+
+    match check {
+        Check::Success(expected) => (outcome.success == *expected, status),
+        Check::RevertReason(text) => {
+            let matched = !outcome.success
+                && outcome
+                    .revert_reason
+                    .as_deref()
+                    .is_some_and(|r| r.contains(text.as_str()));
+            (matched, status)
+        }
+        Check::MaxGas(cap) => match outcome.gas_used {
+            Some(used) => (used <= *cap, format!("gas used {}", used)),
+            None => (false, "backend did not report gas used".to_string()),
+        },
+        Check::Event(check) => match &outcome.logs {
+            Some(logs) => evaluate_event(check, logs),
+            None => (false, "backend does not report logs".to_string()),
+        },
+        Check::Storage {
+            address,
+            slot,
+            equals,
+        } => match &outcome.storage_changes {
+            Some(changes) => {
+                match changes
+                    .iter()
+                    .find(|c| &c.address == address && &c.slot == slot)
+                {
+                    Some(change) if &change.after == equals => (true, "slot matches".to_string()),
+                    Some(change) => (false, format!("slot was set to {}", change.after)),
+                    None => (false, "slot was not written by the call".to_string()),
+                }
+            }
+            None => (false, "backend does not report storage changes".to_string()),
+        },
+        Check::Balance {
+            holder,
+            token,
+            min,
+            max,
+        } => match &outcome.balance_changes {
+            Some(changes) => {
+                let delta = changes
+                    .iter()
+                    .find(|c| &c.holder == holder && &c.token == token)
+                    .map(|c| c.delta)
+                    .unwrap_or_default();
+                let in_range =
+                    min.is_none_or(|min| delta >= min) && max.is_none_or(|max| delta <= max);
+                (in_range, format!("balance changed by {}", delta))
+            }
+            None => (false, "backend does not report balance changes".to_string()),
+        },
+    }
+}
+
+fn evaluate_event(check: &EventCheck, logs: &[SimulatedLog]) -> (bool, String) {
+    // Logs that match everything but the arguments, kept for the failure message
+    let mut candidates = Vec::new();
+    let mut matched = 0;
+    for log in logs {
+        if check.address.as_ref().is_some_and(|a| a != &log.address) {
+            continue;
+        }
+        if check
+            .name
+            .as_ref()
+            .is_some_and(|n| log.name.as_ref() != Some(n))
+        {
+            continue;
+        }
+        let args = match &check.event {
+            Some(event) => match decode_log_args(event, log) {
+                Some(args) => args,
+                None => continue,
+            },
+            None => log.args.clone(),
+        };
+        let args_match = check.args.iter().all(|(name, expected)| {
+            args.get(name)
+                .is_some_and(|actual| values_match(actual, expected))
+        });
+        if args_match {
+            matched += 1;
+        } else {
+            candidates.push(args);
+        }
+    }
+
+    let passed = match check.count {
+        Some(count) => matched == count,
+        None => matched > 0,
+    };
+    let mut detail = format!("{} matching of {} logs", matched, logs.len());
+    if !passed {
+        if let Some(args) = candidates.first() {
+            let shown: Vec<String> = check
+                .args
+                .keys()
+                .map(|k| format!("{}={}", k, args.get(k).map_or("?", String::as_str)))
+                .collect();
+            detail.push_str(&format!(
+                "; {} with other args, e.g. {}",
+                candidates.len(),
+                shown.join(", ")
+            ));
+        }
+    }
+    (passed, detail)
+}
+
+/// Decode a raw log against an event signature, `None` if it does not match
+fn decode_log_args(event: &Event, log: &SimulatedLog) -> Option<BTreeMap<String, String>> {
+    let topics: Vec<B256> = log
+        .topics
+        .iter()
+        .map(|t| B256::from_str(t).ok())
+        .collect::<Option<_>>()?;
+    if topics.first() != Some(&event.selector()) {
+        return None;
+    }
+    let data = hex::decode(log.data.trim_start_matches("0x")).ok()?;
+    let decoded = event.decode_log_parts(topics, &data).ok()?;
+
+    let mut indexed = decoded.indexed.iter();
+    let mut body = decoded.body.iter();
+    let mut args = BTreeMap::new();
+    for param in &event.inputs {
+        let value = if param.indexed {
+            indexed.next()
+        } else {
+            body.next()
+        }?;
+        if !param.name.is_empty() {
+            args.insert(param.name.clone(), sol_value_string(value));
+        }
+    }
+    Some(args)
+}
+
+/// Read an assertion file and compile it against the default address book
+pub fn load_assertions(path: &str) -> anyhow::Result<Vec<Assertion>> {
+    let source = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read assertion file {}: {}", path, e))?;
+    AssertionSpec::from_toml(&source)?.compile(&AddressBook::load_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::simulate::outcome::{BalanceChange, StorageChange};
+    use crate::config::AddressEntry;
+    use alloy::primitives::{keccak256, U256};
+
+    const USDC: &str = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+    const TREASURY: &str = "0x00000000000000000000000000000000000000a1";
+    const SENDER: &str = "0x00000000000000000000000000000000000000b0";
+
+    fn book() -> AddressBook {
+        let mut book = AddressBook::default();
+        for (label, address) in [
+            ("usdc", "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"),
+            ("treasury", TREASURY),
+        ] {
+            book.entries.insert(
+                label.to_string(),
+                AddressEntry {
+                    address: address.to_string(),
+                    description: None,
+                    tags: Vec::new(),
+                    chain: None,
+                },
+            );
+        }
+        book
+    }
+
+    fn word(value: u64) -> String {
+        format!("0x{:064x}", U256::from(value))
+    }
+
+    fn address_topic(address: &str) -> String {
+        format!("0x{:0>64}", address.trim_start_matches("0x"))
+    }
+
+    /// An undecoded USDC Transfer log, as the debug backend reports it
+    fn transfer_log(from: &str, to: &str, amount: u64) -> SimulatedLog {
+        SimulatedLog {
+            address: USDC.to_string(),
+            topics: vec![
+                keccak256("Transfer(address,address,uint256)").to_string(),
+                address_topic(from),
+                address_topic(to),
+            ],
+            data: word(amount),
+            name: None,
+            args: BTreeMap::new(),
+        }
+    }
+
+    fn outcome() -> SimulationOutcome {
+        SimulationOutcome {
+            success: true,
+            revert_reason: None,
+            gas_used: Some(90_000),
+            logs: Some(vec![
+                transfer_log(SENDER, TREASURY, 1_000_000),
+                transfer_log(SENDER, SENDER, 5),
+            ]),
+            storage_changes: Some(vec![StorageChange {
+                address: USDC.to_string(),
+                slot: word(5),
+                before: Some(word(0)),
+                after: word(1),
+            }]),
+            balance_changes: Some(vec![
+                BalanceChange {
+                    holder: TREASURY.to_string(),
+                    token: Some(USDC.to_string()),
+                    delta: I256::try_from(1_000_000).unwrap(),
+                },
+                BalanceChange {
+                    holder: SENDER.to_string(),
+                    token: None,
+                    delta: I256::try_from(-21_000).unwrap(),
+                },
+            ]),
+        }
+    }
+
+    fn run(spec: &str, outcome: &SimulationOutcome) -> AssertionReport {
+        let assertions = AssertionSpec::from_toml(spec)
+            .unwrap()
+            .compile(&book())
+            .unwrap();
+        AssertionReport::evaluate(&assertions, outcome)
+    }
+
+    fn compile_error(spec: &str) -> String {
+        AssertionSpec::from_toml(spec)
+            .and_then(|s| s.compile(&book()))
+            .unwrap_err()
+            .to_string()
+    }
+
+    #[test]
+    fn test_parse_full_spec() {
+        let spec = AssertionSpec::from_toml(
+            r#"
+            success = true
+            max_gas = 250000
+
+            [[event]]
+            address = "usdc"
+            signature = "Transfer(address indexed from, address indexed to, uint256 value)"
+            args = { to = "treasury", value = 1000000 }
+            count = 1
+
+            [[storage]]
+            address = "usdc"
+            slot = 5
+            equals = "0x1"
+
+            [[balance]]
+            holder = "treasury"
+            token = "usdc"
+            min = "1000000"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(spec.success, Some(true));
+        assert_eq!(spec.max_gas, Some(250_000));
+        assert_eq!(spec.events.len(), 1);
+        assert_eq!(spec.events[0].args["value"], Scalar::Integer(1_000_000));
+        assert_eq!(spec.storage[0].slot, Scalar::Integer(5));
+        assert_eq!(spec.balances[0].max, None);
+
+        let assertions = spec.compile(&book()).unwrap();
+        assert_eq!(assertions.len(), 5);
+        match &assertions[2].check {
+            Check::Event(check) => {
+                assert_eq!(check.address.as_deref(), Some(USDC));
+                assert_eq!(check.args["to"], TREASURY);
+                assert_eq!(check.args["value"], "1000000");
+            }
+            other => panic!("expected event check, got {other:?}"),
+        }
+        match &assertions[3].check {
+            Check::Storage { slot, equals, .. } => {
+                assert_eq!(slot, &word(5));
+                assert_eq!(equals, &word(1));
+            }
+            other => panic!("expected storage check, got {other:?}"),
+        }
+        assert_eq!(
+            assertions[4].label,
+            "usdc balance of treasury changes >= 1000000"
+        );
+    }
+
+    #[test]
+    fn test_schema_errors() {
+        assert!(compile_error("sucess = true").contains("unknown field"));
+        assert!(compile_error("[[event]]\nadress = \"usdc\"").contains("unknown field"));
+        assert!(compile_error("").contains("no assertions"));
+        assert!(compile_error("success = true\nrevert_reason = \"x\"").contains("conflicts"));
+        assert!(compile_error("[[event]]\ncount = 1").contains("event #1"));
+        assert!(compile_error("[[event]]\nsignature = \"Transfer(\"").contains("invalid signature"));
+        assert!(compile_error(
+            "[[event]]\nsignature = \"Transfer(address indexed from)\"\nargs = { to = \"x\" }"
+        )
+        .contains("no parameter named `to`"));
+        assert!(
+            compile_error("[[storage]]\naddress = \"nobody\"\nslot = 0\nequals = 0")
+                .contains("unknown address or label 'nobody'")
+        );
+        assert!(
+            compile_error("[[storage]]\naddress = \"usdc\"\nslot = \"zz\"\nequals = 0")
+                .contains("invalid slot")
+        );
+        assert!(compile_error("[[balance]]\nholder = \"treasury\"").contains("needs `min`"));
+        assert!(
+            compile_error("[[balance]]\nholder = \"treasury\"\nequals = 1\nmin = 0")
+                .contains("cannot be combined")
+        );
+        assert!(
+            compile_error("[[balance]]\nholder = \"treasury\"\nmin = 5\nmax = 1")
+                .contains("greater than")
+        );
+    }
+
+    #[test]
+    fn test_status_and_gas() {
+        let report = run("success = true\nmax_gas = 100000", &outcome());
+        assert!(report.all_passed());
+
+        let report = run("max_gas = 50000", &outcome());
+        assert_eq!(report.failed, 1);
+        assert_eq!(report.results[0].detail, "gas used 90000");
+
+        let mut reverted = outcome();
+        reverted.success = false;
+        reverted.revert_reason = Some("ERC20: transfer amount exceeds balance".to_string());
+        let report = run("success = true", &reverted);
+        assert!(!report.all_passed());
+        assert!(report.results[0].detail.contains("exceeds balance"));
+
+        assert!(run("revert_reason = \"exceeds balance\"", &reverted).all_passed());
+        assert!(!run("revert_reason = \"paused\"", &reverted).all_passed());
+        assert!(!run("revert_reason = \"exceeds balance\"", &outcome()).all_passed());
+
+        reverted.gas_used = None;
+        let report = run("max_gas = 1", &reverted);
+        assert_eq!(report.results[0].detail, "backend did not report gas used");
+    }
+
+    #[test]
+    fn test_event_by_signature_decodes_raw_logs() {
+        let signature = "Transfer(address indexed from, address indexed to, uint256 value)";
+        let spec = |args: &str, extra: &str| {
+            format!(
+                "[[event]]\naddress = \"usdc\"\nsignature = \"{}\"\nargs = {{ {} }}\n{}",
+                signature, args, extra
+            )
+        };
+
+        assert!(run(&spec("to = \"treasury\", value = 1000000", ""), &outcome()).all_passed());
+        assert!(run(&spec("value = \"0xf4240\"", "count = 1"), &outcome()).all_passed());
+        assert!(run(&spec("", "count = 2"), &outcome()).all_passed());
+        assert!(!run(&spec("", "count = 1"), &outcome()).all_passed());
+
+        let report = run(&spec("to = \"treasury\", value = 7", ""), &outcome());
+        assert!(!report.all_passed());
+        assert_eq!(
+            report.results[0].detail,
+            format!(
+                "0 matching of 2 logs; 2 with other args, e.g. to={}, value=1000000",
+                TREASURY
+            )
+        );
+
+        // Same event from another contract does not match
+        let mut elsewhere = outcome();
+        for log in elsewhere.logs.as_mut().unwrap() {
+            log.address = SENDER.to_string();
+        }
+        assert!(!run(&spec("", ""), &elsewhere).all_passed());
+    }
+
+    #[test]
+    fn test_event_by_decoded_name() {
+        let mut decoded = outcome();
+        let log = &mut decoded.logs.as_mut().unwrap()[0];
+        log.name = Some("Transfer".to_string());
+        log.args.insert(
+            "to".to_string(),
+            TREASURY.to_uppercase().replace("0X", "0x"),
+        );
+
+        assert!(run(
+            "[[event]]\nname = \"Transfer\"\nargs = { to = \"treasury\" }",
+            &decoded
+        )
+        .all_passed());
+        assert!(!run("[[event]]\nname = \"Approval\"", &decoded).all_passed());
+        // Undecoded logs have no name to match
+        assert!(!run("[[event]]\nname = \"Transfer\"", &outcome()).all_passed());
+
+        let mut no_logs = outcome();
+        no_logs.logs = None;
+        let report = run("[[event]]\nname = \"Transfer\"", &no_logs);
+        assert_eq!(report.results[0].detail, "backend does not report logs");
+    }
+
+    #[test]
+    fn test_storage() {
+        let check = |slot: &str, equals: &str| {
+            run(
+                &format!(
+                    "[[storage]]\naddress = \"usdc\"\nslot = {}\nequals = {}",
+                    slot, equals
+                ),
+                &outcome(),
+            )
+        };
+        assert!(check("5", "1").all_passed());
+        assert!(check(
+            "\"0x05\"",
+            "\"0x0000000000000000000000000000000000000000000000000000000000000001\""
+        )
+        .all_passed());
+
+        let report = check("5", "2");
+        assert_eq!(
+            report.results[0].detail,
+            format!("slot was set to {}", word(1))
+        );
+        let report = check("6", "1");
+        assert_eq!(report.results[0].detail, "slot was not written by the call");
+
+        let mut unsupported = outcome();
+        unsupported.storage_changes = None;
+        let report = run(
+            "[[storage]]\naddress = \"usdc\"\nslot = 5\nequals = 1",
+            &unsupported,
+        );
+        assert_eq!(
+            report.results[0].detail,
+            "backend does not report storage changes"
+        );
+    }
+
+    #[test]
+    fn test_balance_ranges() {
+        let check = |holder: &str, token: &str, bounds: &str| {
+            run(
+                &format!(
+                    "[[balance]]\nholder = \"{}\"\n{}\n{}",
+                    holder, token, bounds
+                ),
+                &outcome(),
+            )
+            .all_passed()
+        };
+        let usdc = "token = \"usdc\"";
+        assert!(check("treasury", usdc, "min = 1000000"));
+        assert!(check("treasury", usdc, "equals = \"1000000\""));
+        assert!(check("treasury", usdc, "min = 1\nmax = 2000000"));
+        assert!(!check("treasury", usdc, "max = 999999"));
+        assert!(check(SENDER, "", "max = -20000"));
+        assert!(check(SENDER, "", "min = \"-21000\"\nmax = \"-21000\""));
+        assert!(!check(SENDER, "", "min = 0"));
+        // Untouched balances changed by zero
+        assert!(check(SENDER, usdc, "equals = 0"));
+        assert!(!check(SENDER, usdc, "min = 1"));
+    }
+
+    #[test]
+    fn test_report_counts_and_output() {
+        let report = run("success = true\nmax_gas = 1", &outcome());
+        assert_eq!((report.passed, report.failed), (1, 1));
+        let text = report.to_string();
+        assert!(text.contains("PASS  call succeeds"));
+        assert!(text.contains("FAIL  gas used <= 1\n      gas used 90000"));
+        assert!(text.ends_with("1 passed, 1 failed"));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["failed"], 1);
+        assert_eq!(json["results"][1]["passed"], false);
+    }
+
+    #[test]
+    fn test_values_match() {
+        assert!(values_match("1000", "0x3e8"));
+        assert!(values_match(
+            "0xABC0000000000000000000000000000000000000",
+            "0xabc0000000000000000000000000000000000000"
+        ));
+        assert!(values_match("-1", "-1"));
+        assert!(!values_match("1", "2"));
+        assert!(values_match("hello", "hello"));
+    }
+}
@@ -1,6 +1,9 @@
 pub mod alchemy;
 pub mod anvil;
+pub mod assertions;
 pub mod cast;
+pub mod outcome;
+pub mod replay;
 pub mod rpc;
 pub mod tenderly;
 pub mod types;
@@ -8,14 +11,19 @@ pub mod utils;
 
 pub use alchemy::*;
 pub use anvil::*;
+pub use assertions::*;
 pub use cast::*;
+pub use outcome::*;
+pub use replay::*;
 pub use rpc::*;
 pub use tenderly::*;
 pub use types::*;
 pub use utils::*;
 
+use crate::cli::OutputFormat;
 use crate::config::Chain;
 use clap::Subcommand;
+use std::path::PathBuf;
 
 #[derive(Subcommand)]
 #[allow(clippy::large_enum_variant)]
@@ -33,7 +41,25 @@ use clap::Subcommand;
   ethcli simulate tx 0x123abc... --via tenderly
 
   # Simulate using Anvil fork
-  ethcli simulate call 0x... --sig "foo()" --via anvil"#)]
+  ethcli simulate call 0x... --sig "foo()" --via anvil
+
+  # Check a simulation against expectations in CI (exits non-zero on failure)
+  ethcli simulate call 0x... --sig "deposit(uint256)" 1000 --via tenderly --assert-file expectations.toml
+
+  # Compare two saved Tenderly simulations
+  ethcli simulate diff <simulation-id-1> <simulation-id-2>
+
+  # Export saved Tenderly simulations to a portable bundle file
+  ethcli simulate export <simulation-id-1>,<simulation-id-2> --output bundle.json
+
+  # Save a bundle file's transactions as Tenderly simulations
+  ethcli simulate import bundle.json
+
+  # Replay a bundle on an anvil fork at a later block (exits non-zero on regressions)
+  ethcli simulate replay bundle.json --at-block 19500000 --gas-tolerance 10 -o json
+
+  # Replay saved Tenderly simulations directly
+  ethcli simulate replay --ids <simulation-id-1>,<simulation-id-2> --backend tenderly"#)]
 pub enum SimulateCommands {
     /// Simulate a transaction call (without sending)
     Call {
@@ -186,6 +212,15 @@ pub enum SimulateCommands {
         /// Show API keys in dry-run output (default: masked with env var placeholders)
         #[arg(long)]
         show_secrets: bool,
+
+        /// Check the result against a TOML assertion file, failing if any assertion fails
+        /// (tenderly, alchemy, debug and trace backends)
+        #[arg(long)]
+        assert_file: Option<String>,
+
+        /// Output format for the assertion report
+        #[arg(long, short = 'o', default_value = "table")]
+        format: OutputFormat,
     },
 
     /// Trace an existing transaction
@@ -248,6 +283,10 @@ pub enum SimulateCommands {
         /// Save simulation bundle to Tenderly
         #[arg(long)]
         save: bool,
+
+        /// Also write the results to a portable bundle file for `simulate replay`
+        #[arg(long, value_name = "FILE")]
+        export: Option<String>,
     },
 
     /// List saved simulations (Tenderly only)
@@ -305,6 +344,99 @@ pub enum SimulateCommands {
         #[command(flatten)]
         tenderly: TenderlyArgs,
     },
+
+    /// Compare two saved simulations: status, gas, logs, storage and balances (Tenderly only)
+    Diff {
+        /// First simulation ID
+        id1: String,
+
+        /// Second simulation ID
+        id2: String,
+
+        /// Output format
+        #[arg(long, short = 'o', default_value = "table")]
+        format: OutputFormat,
+
+        /// Tenderly credentials
+        #[command(flatten)]
+        tenderly: TenderlyArgs,
+    },
+
+    /// Export saved simulations to a portable bundle file (Tenderly only)
+    Export {
+        /// Simulation IDs in execution order (comma-separated)
+        #[arg(value_delimiter = ',', required = true)]
+        ids: Vec<String>,
+
+        /// Bundle file to write
+        #[arg(long, short = 'O', value_name = "FILE")]
+        output: String,
+
+        /// Tenderly credentials
+        #[command(flatten)]
+        tenderly: TenderlyArgs,
+    },
+
+    /// Save a bundle file's transactions as Tenderly simulations (Tenderly only)
+    ///
+    /// Prints the new simulation IDs, comma-separated.
+    Import {
+        /// Bundle file written by `simulate export`
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+
+        /// Block to simulate at (default: the block the bundle was saved at)
+        #[arg(long)]
+        at_block: Option<u64>,
+
+        /// Tenderly credentials
+        #[command(flatten)]
+        tenderly: TenderlyArgs,
+    },
+
+    /// Re-run a saved bundle at another block and report regressions against its saved outcomes
+    Replay {
+        /// Bundle file
+        #[arg(value_name = "FILE", required_unless_present = "ids")]
+        file: Option<PathBuf>,
+
+        /// Saved Tenderly simulation IDs in execution order, instead of a file (comma-separated)
+        #[arg(long, value_delimiter = ',', conflicts_with = "file")]
+        ids: Vec<String>,
+
+        /// Block to replay at (default: the block the bundle was saved at)
+        #[arg(long)]
+        at_block: Option<u64>,
+
+        /// Replay backend
+        #[arg(long, value_enum, default_value = "anvil")]
+        backend: ReplayBackend,
+
+        /// Allowed gas change in percent of the saved gas used
+        #[arg(long, default_value = "5.0")]
+        gas_tolerance: f64,
+
+        /// Asset whose balance changes must match: token address or "eth"
+        /// (can repeat; default: every ERC-20 token)
+        #[arg(long = "asset", action = clap::ArgAction::Append)]
+        assets: Vec<String>,
+
+        /// RPC URL to fork from (anvil backend; default: configured endpoint for the bundle's chain)
+        #[arg(long)]
+        rpc_url: Option<String>,
+
+        /// Use a running anvil node instead of spawning one; it is reset to the replay block
+        #[arg(long)]
+        anvil_url: Option<String>,
+
+        /// Output format for the replay report
+        #[arg(long, short = 'o', default_value = "table")]
+        format: OutputFormat,
+
+        /// Tenderly credentials
+        #[command(flatten)]
+        tenderly: TenderlyArgs,
+    },
 }
 
 pub async fn handle(action: &SimulateCommands, chain: Chain, quiet: bool) -> anyhow::Result<()> {
@@ -346,8 +478,26 @@ pub async fn handle(action: &SimulateCommands, chain: Chain, quiet: bool) -> any
             block_coinbase,
             block_difficulty,
             block_base_fee,
+            assert_file,
+            format,
             ..
         } => {
+            // Load assertions before simulating so a bad file fails fast
+            let assertions = assert_file.as_deref().map(load_assertions).transpose()?;
+            let collect = assertions.is_some();
+            if collect {
+                if matches!(via, SimulateVia::Cast | SimulateVia::Anvil) {
+                    return Err(anyhow::anyhow!(
+                        "--assert-file needs structured results. Use --via tenderly, alchemy, debug, or trace"
+                    ));
+                }
+                if dry_run.is_some() {
+                    return Err(anyhow::anyhow!(
+                        "--assert-file cannot be combined with --dry-run"
+                    ));
+                }
+            }
+
             // Warn if Tenderly-exclusive flags are used with non-Tenderly backends
             if !matches!(via, SimulateVia::Tenderly) {
                 let mut tenderly_only = Vec::new();
@@ -421,7 +571,7 @@ pub async fn handle(action: &SimulateCommands, chain: Chain, quiet: bool) -> any
                 }
             }
 
-            match via {
+            let outcome = match via {
                 SimulateVia::Cast => {
                     if dry_run.is_some() {
                         return Err(anyhow::anyhow!("--dry-run not supported for cast backend. Use --via tenderly, debug, or trace"));
@@ -429,13 +579,15 @@ pub async fn handle(action: &SimulateCommands, chain: Chain, quiet: bool) -> any
                     simulate_via_cast(
                         to, sig, data, args, from, value, block, rpc_url, *trace, quiet,
                     )
-                    .await
+                    .await?;
+                    None
                 }
                 SimulateVia::Anvil => {
                     if dry_run.is_some() {
                         return Err(anyhow::anyhow!("--dry-run not supported for anvil backend. Use --via tenderly, debug, or trace"));
                     }
-                    simulate_via_anvil(to, sig, data, args, from, value, rpc_url, quiet).await
+                    simulate_via_anvil(to, sig, data, args, from, value, rpc_url, quiet).await?;
+                    None
                 }
                 SimulateVia::Tenderly => {
                     simulate_via_tenderly(
@@ -472,8 +624,9 @@ pub async fn handle(action: &SimulateCommands, chain: Chain, quiet: bool) -> any
                         block_coinbase,
                         *block_difficulty,
                         *block_base_fee,
+                        collect,
                     )
-                    .await
+                    .await?
                 }
                 SimulateVia::Debug => {
                     simulate_via_debug_rpc(
@@ -492,8 +645,9 @@ pub async fn handle(action: &SimulateCommands, chain: Chain, quiet: bool) -> any
                         *dry_run,
                         *show_secrets,
                         quiet,
+                        collect,
                     )
-                    .await
+                    .await?
                 }
                 SimulateVia::Trace => {
                     simulate_via_trace_rpc(
@@ -512,8 +666,9 @@ pub async fn handle(action: &SimulateCommands, chain: Chain, quiet: bool) -> any
                         *dry_run,
                         *show_secrets,
                         quiet,
+                        collect,
                     )
-                    .await
+                    .await?
                 }
                 SimulateVia::Alchemy => {
                     if dry_run.is_some() {
@@ -522,11 +677,30 @@ pub async fn handle(action: &SimulateCommands, chain: Chain, quiet: bool) -> any
                         ));
                     }
                     simulate_via_alchemy(
-                        to, sig, data, args, from, value, *gas, *gas_price, alchemy, quiet,
+                        to, sig, data, args, from, value, *gas, *gas_price, alchemy, quiet, collect,
                     )
-                    .await
+                    .await?
+                }
+            };
+
+            let (Some(assertions), Some(outcome)) = (assertions, outcome) else {
+                return Ok(());
+            };
+            let report = AssertionReport::evaluate(&assertions, &outcome);
+            match format {
+                OutputFormat::Json | OutputFormat::Ndjson => {
+                    println!("{}", serde_json::to_string_pretty(&report)?)
                 }
+                OutputFormat::Table => println!("{}", report),
+            }
+            if !report.all_passed() {
+                return Err(anyhow::anyhow!(
+                    "{} of {} assertions failed",
+                    report.failed,
+                    report.results.len()
+                ));
             }
+            Ok(())
         }
 
         SimulateCommands::Tx {
@@ -555,6 +729,7 @@ pub async fn handle(action: &SimulateCommands, chain: Chain, quiet: bool) -> any
             code_overrides,
             tenderly,
             save,
+            export,
         } => {
             simulate_bundle_tenderly(
                 txs,
@@ -563,6 +738,7 @@ pub async fn handle(action: &SimulateCommands, chain: Chain, quiet: bool) -> any
                 storage_overrides,
                 code_overrides,
                 *save,
+                export,
                 tenderly,
                 quiet,
             )
@@ -590,5 +766,69 @@ pub async fn handle(action: &SimulateCommands, chain: Chain, quiet: bool) -> any
         SimulateCommands::Unshare { id, tenderly } => {
             unshare_simulation_tenderly(id, tenderly, quiet).await
         }
+
+        SimulateCommands::Diff {
+            id1,
+            id2,
+            format,
+            tenderly,
+        } => diff_simulations_tenderly(id1, id2, *format, tenderly, quiet).await,
+
+        SimulateCommands::Export {
+            ids,
+            output,
+            tenderly,
+        } => export_bundle_tenderly(ids, std::path::Path::new(output), tenderly, quiet).await,
+
+        SimulateCommands::Import {
+            file,
+            at_block,
+            tenderly,
+        } => import_bundle_tenderly(file, *at_block, tenderly, quiet).await,
+
+        SimulateCommands::Replay {
+            file,
+            ids,
+            at_block,
+            backend,
+            gas_tolerance,
+            assets,
+            rpc_url,
+            anvil_url,
+            format,
+            tenderly,
+        } => {
+            if !gas_tolerance.is_finite() || *gas_tolerance < 0.0 {
+                return Err(anyhow::anyhow!(
+                    "--gas-tolerance must be a non-negative percentage"
+                ));
+            }
+            let tolerances = ReplayTolerances {
+                gas_pct: *gas_tolerance,
+                assets: assets
+                    .iter()
+                    .map(|a| AssetFilter::parse(a))
+                    .collect::<anyhow::Result<_>>()?,
+            };
+            let anvil = AnvilTarget {
+                fork_url: rpc_url.clone(),
+                anvil_url: anvil_url.clone(),
+            };
+            let source = match file {
+                Some(path) => BundleSource::File(path.clone()),
+                None => BundleSource::TenderlyIds(ids.clone()),
+            };
+            replay_bundle(
+                &source,
+                *at_block,
+                *backend,
+                &tolerances,
+                &anvil,
+                *format,
+                tenderly,
+                quiet,
+            )
+            .await
+        }
     }
 }
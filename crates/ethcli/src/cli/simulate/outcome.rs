@@ -0,0 +1,1029 @@
+//! Backend-independent view of a simulation result
+//!
+//! Each backend reports a different subset of what a call did. Anything a
+//! backend cannot report is `None`, so checks against it fail instead of
+//! passing on an empty list:
+//!
+//! | Backend    | Revert | Gas | Logs | Storage | Balance changes      |
+//! |------------|--------|-----|------|---------|----------------------|
+//! | `tenderly` | yes    | yes | yes  | yes     | ETH and ERC-20       |
+//! | `alchemy`  | yes    | yes | -    | -       | ETH and ERC-20       |
+//! | `debug`    | yes    | yes | yes  | -       | ETH (call values)    |
+//! | `trace`    | yes    | yes | -    | yes     | ETH (state diff)     |
+//! | receipt    | yes*   | yes | yes  | -       | ETH (tx value), ERC-20 transfers |
+//!
+//! Receipts (used by `simulate replay` on anvil) carry no revert reason.
+//! `cast` and `anvil` calls print cast's text output and are not normalized.
+
+use alloy::dyn_abi::DynSolValue;
+use alloy::primitives::{I256, U256};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Normalized result of one simulated call
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SimulationOutcome {
+    /// Whether the call completed without reverting
+    pub success: bool,
+    /// Revert reason or error message, if the call reverted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revert_reason: Option<String>,
+    /// Gas used, if reported
+    pub gas_used: Option<u64>,
+    /// Emitted logs, if the backend reports them
+    pub logs: Option<Vec<SimulatedLog>>,
+    /// Storage slots written, if the backend reports them
+    pub storage_changes: Option<Vec<StorageChange>>,
+    /// Net balance changes, if the backend reports them
+    pub balance_changes: Option<Vec<BalanceChange>>,
+}
+
+/// A log emitted during simulation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulatedLog {
+    /// Emitting contract (lowercase)
+    pub address: String,
+    /// Topics as 32-byte hex words
+    pub topics: Vec<String>,
+    /// Non-indexed data as hex
+    pub data: String,
+    /// Event name, when the backend decoded the log
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Decoded arguments by parameter name, when the backend decoded the log
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub args: BTreeMap<String, String>,
+}
+
+/// A storage slot written during simulation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageChange {
+    /// Contract address (lowercase)
+    pub address: String,
+    /// Slot as a 32-byte hex word
+    pub slot: String,
+    /// Value before the call, if reported
+    pub before: Option<String>,
+    /// Value after the call
+    pub after: String,
+}
+
+/// Net change of one holder's balance of one asset
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceChange {
+    /// Holder address (lowercase)
+    pub holder: String,
+    /// Token contract (lowercase), `None` for ETH
+    pub token: Option<String>,
+    /// Change in raw units (wei or token base units)
+    #[serde(
+        serialize_with = "serialize_display",
+        deserialize_with = "deserialize_i256"
+    )]
+    pub delta: I256,
+}
+
+impl SimulationOutcome {
+    /// Normalize a Tenderly simulation response
+    ///
+    /// Logs, storage and balance changes come from `transaction_info`, which
+    /// Tenderly omits for `quick` simulations.
+    pub fn from_tenderly(response: &tndrly::simulation::SimulationResponse) -> Self {
+        let tx = response.transaction.as_ref();
+        let info = tx.and_then(|t| t.transaction_info.as_ref());
+        let success = tx
+            .and_then(|t| t.status)
+            .unwrap_or(response.simulation.status);
+        let gas_used = tx
+            .and_then(|t| t.gas_used)
+            .or(Some(response.simulation.gas_used));
+
+        let revert_reason = if success {
+            None
+        } else {
+            info.and_then(|i| {
+                let trace = &i["call_trace"];
+                trace["error_reason"]
+                    .as_str()
+                    .or_else(|| trace["error"].as_str())
+                    .map(str::to_string)
+            })
+        };
+
+        let raw_logs = info
+            .and_then(|i| i["logs"].as_array())
+            .or_else(|| tx.and_then(|t| t.logs.as_ref()));
+        let logs = raw_logs
+            .map(|logs| logs.iter().filter_map(tenderly_log).collect())
+            .or_else(|| info.map(|_| Vec::new()));
+
+        let storage_changes = info.map(|i| {
+            json_array(&i["state_diff"])
+                .iter()
+                .flat_map(|diff| json_array(&diff["raw"]))
+                .filter_map(|raw| {
+                    Some(StorageChange {
+                        address: raw["address"].as_str()?.to_lowercase(),
+                        slot: normalize_word(raw["key"].as_str()?)?,
+                        before: raw["original"].as_str().and_then(normalize_word),
+                        after: normalize_word(raw["dirty"].as_str()?)?,
+                    })
+                })
+                .collect()
+        });
+
+        let balance_changes = info.map(|i| {
+            let mut balances = BalanceLedger::default();
+            for diff in json_array(&i["balance_diff"]) {
+                if let (Some(holder), Some(before), Some(after)) = (
+                    diff["address"].as_str(),
+                    diff["original"].as_str().and_then(parse_i256),
+                    diff["dirty"].as_str().and_then(parse_i256),
+                ) {
+                    balances.add(holder, None, after - before);
+                }
+            }
+            // ETH is covered by balance_diff; NFTs have no fungible amount
+            for change in json_array(&i["asset_changes"]) {
+                let token = &change["token_info"];
+                if token["standard"].as_str() != Some("ERC20") {
+                    continue;
+                }
+                let amount = change["raw_amount"].as_str().and_then(parse_i256);
+                if let (Some(contract), Some(amount)) = (token["contract_address"].as_str(), amount)
+                {
+                    balances.transfer(
+                        change["from"].as_str(),
+                        change["to"].as_str(),
+                        Some(contract),
+                        amount,
+                    );
+                }
+            }
+            balances.into_changes()
+        });
+
+        Self {
+            success,
+            revert_reason,
+            gas_used,
+            logs,
+            storage_changes,
+            balance_changes,
+        }
+    }
+
+    /// Normalize an Alchemy `simulateAssetChanges` response
+    pub fn from_alchemy(response: &alcmy::simulation::SimulateAssetChangesResponse) -> Self {
+        let mut balances = BalanceLedger::default();
+        for change in &response.changes {
+            let token = match change.asset_type.to_uppercase().as_str() {
+                "NATIVE" => None,
+                "ERC20" => match change.contract_address.as_deref() {
+                    Some(contract) => Some(contract),
+                    None => continue,
+                },
+                _ => continue,
+            };
+            if !change.change_type.eq_ignore_ascii_case("transfer") {
+                continue;
+            }
+            if let Some(amount) = change.raw_amount.as_deref().and_then(parse_i256) {
+                balances.transfer(Some(&change.from), Some(&change.to), token, amount);
+            }
+        }
+
+        Self {
+            success: response.error.is_none(),
+            revert_reason: response
+                .error
+                .as_ref()
+                .map(|e| e.revert_reason.clone().unwrap_or_else(|| e.message.clone())),
+            gas_used: response.gas_used.as_deref().and_then(parse_u64),
+            logs: None,
+            storage_changes: None,
+            balance_changes: Some(balances.into_changes()),
+        }
+    }
+
+    /// Normalize a `debug_traceCall` result from `callTracer` with `withLog`
+    ///
+    /// Logs and value transfers inside reverted frames are dropped, since
+    /// the revert undoes them.
+    pub fn from_call_trace(trace: &Value) -> Self {
+        let success = trace["error"].is_null();
+        let revert_reason = if success {
+            None
+        } else {
+            trace["revertReason"]
+                .as_str()
+                .or_else(|| trace["error"].as_str())
+                .map(str::to_string)
+        };
+
+        let mut logs = Vec::new();
+        let mut balances = BalanceLedger::default();
+        collect_call_frame(trace, &mut logs, &mut balances);
+
+        Self {
+            success,
+            revert_reason,
+            gas_used: trace["gasUsed"].as_str().and_then(parse_u64),
+            logs: Some(logs),
+            storage_changes: None,
+            balance_changes: Some(balances.into_changes()),
+        }
+    }
+
+    /// Normalize a `trace_call` result
+    ///
+    /// Storage and balance changes are only available when `stateDiff` was
+    /// among the requested trace types.
+    pub fn from_parity_trace(result: &Value) -> Self {
+        let root = &result["trace"][0];
+        let success = root["error"].is_null();
+
+        let state_diff = result["stateDiff"].as_object();
+        let storage_changes = state_diff.map(|accounts| {
+            let mut changes = Vec::new();
+            for (address, account) in accounts {
+                let Some(slots) = account["storage"].as_object() else {
+                    continue;
+                };
+                for (slot, delta) in slots {
+                    if let (Some(slot), Some((before, Some(after)))) =
+                        (normalize_word(slot), parity_delta(delta))
+                    {
+                        changes.push(StorageChange {
+                            address: address.to_lowercase(),
+                            slot,
+                            before: before.as_deref().and_then(normalize_word),
+                            after: normalize_word(&after).unwrap_or(after),
+                        });
+                    }
+                }
+            }
+            changes
+        });
+        let balance_changes = state_diff.map(|accounts| {
+            let mut balances = BalanceLedger::default();
+            for (address, account) in accounts {
+                if let Some((before, after)) = parity_delta(&account["balance"]) {
+                    let before = before.as_deref().and_then(parse_i256).unwrap_or_default();
+                    let after = after.as_deref().and_then(parse_i256).unwrap_or_default();
+                    balances.add(address, None, after - before);
+                }
+            }
+            balances.into_changes()
+        });
+
+        Self {
+            success,
+            revert_reason: root["error"].as_str().map(str::to_string),
+            gas_used: root["result"]["gasUsed"].as_str().and_then(parse_u64),
+            logs: None,
+            storage_changes,
+            balance_changes,
+        }
+    }
+
+    /// Normalize a mined transaction receipt
+    ///
+    /// `value`, `from` and `to` come from the transaction itself. Balance
+    /// changes cover the ETH value sent and ERC-20 `Transfer` events, but
+    /// not gas fees.
+    pub fn from_receipt(receipt: &Value, from: &str, to: &str, value: Option<&str>) -> Self {
+        let success = receipt["status"].as_str().and_then(parse_u64) == Some(1);
+        let logs: Vec<SimulatedLog> = json_array(&receipt["logs"])
+            .iter()
+            .filter_map(raw_log)
+            .collect();
+
+        let mut balances = BalanceLedger::default();
+        if success {
+            if let Some(value) = value.and_then(parse_i256).filter(|v| !v.is_zero()) {
+                balances.transfer(Some(from), Some(to), None, value);
+            }
+        }
+        for log in &logs {
+            // ERC-721 Transfer shares the topic but indexes the token id instead of
+            // carrying an amount in data
+            if log.topics.len() != 3
+                || log.topics[0] != ERC20_TRANSFER_TOPIC
+                || log.data.len() != 66
+                || log.topics[1..]
+                    .iter()
+                    .any(|t| t.len() != 66 || !t.is_ascii())
+            {
+                continue;
+            }
+            let holder = |topic: &str| format!("0x{}", &topic[26..]);
+            if let Some(amount) = parse_i256(&log.data) {
+                balances.transfer(
+                    Some(&holder(&log.topics[1])),
+                    Some(&holder(&log.topics[2])),
+                    Some(&log.address),
+                    amount,
+                );
+            }
+        }
+
+        Self {
+            success,
+            revert_reason: None,
+            gas_used: receipt["gasUsed"].as_str().and_then(parse_u64),
+            logs: Some(logs),
+            storage_changes: None,
+            balance_changes: Some(balances.into_changes()),
+        }
+    }
+}
+
+/// `Transfer(address,address,uint256)` topic
+const ERC20_TRANSFER_TOPIC: &str =
+    "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
+/// Differences between two simulation outcomes
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SimulationDiff {
+    /// Success of each side, if they differ
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub success: Option<(bool, bool)>,
+    /// Gas used by each side, if it differs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_used: Option<(Option<u64>, Option<u64>)>,
+    /// Logs emitted only by the first simulation
+    pub logs_only_in_first: Vec<SimulatedLog>,
+    /// Logs emitted only by the second simulation
+    pub logs_only_in_second: Vec<SimulatedLog>,
+    /// Slots whose final value differs
+    pub storage: Vec<StorageDiff>,
+    /// Balances whose net change differs
+    pub balances: Vec<BalanceDiff>,
+}
+
+/// A slot whose final value differs between two simulations
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StorageDiff {
+    /// Contract address
+    pub address: String,
+    /// Slot
+    pub slot: String,
+    /// Value written by the first simulation (`None` if it left the slot alone)
+    pub first: Option<String>,
+    /// Value written by the second simulation (`None` if it left the slot alone)
+    pub second: Option<String>,
+}
+
+/// A balance whose net change differs between two simulations
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BalanceDiff {
+    /// Holder address
+    pub holder: String,
+    /// Token contract, `None` for ETH
+    pub token: Option<String>,
+    /// Change in the first simulation
+    #[serde(serialize_with = "serialize_display")]
+    pub first: I256,
+    /// Change in the second simulation
+    #[serde(serialize_with = "serialize_display")]
+    pub second: I256,
+}
+
+impl SimulationDiff {
+    /// Compare two outcomes
+    ///
+    /// Logs are compared as multisets of `(address, topics, data)`, so a log
+    /// emitted twice by one side and once by the other shows up once.
+    pub fn between(first: &SimulationOutcome, second: &SimulationOutcome) -> Self {
+        let mut diff = Self::default();
+        if first.success != second.success {
+            diff.success = Some((first.success, second.success));
+        }
+        if first.gas_used != second.gas_used {
+            diff.gas_used = Some((first.gas_used, second.gas_used));
+        }
+
+        let first_logs = first.logs.as_deref().unwrap_or_default();
+        let mut unmatched: Vec<&SimulatedLog> =
+            second.logs.as_deref().unwrap_or_default().iter().collect();
+        for log in first_logs {
+            let same = |other: &&SimulatedLog| {
+                other.address == log.address && other.topics == log.topics && other.data == log.data
+            };
+            match unmatched.iter().position(same) {
+                Some(i) => {
+                    unmatched.remove(i);
+                }
+                None => diff.logs_only_in_first.push(log.clone()),
+            }
+        }
+        diff.logs_only_in_second = unmatched.into_iter().cloned().collect();
+
+        let mut slots: BTreeMap<(String, String), (Option<String>, Option<String>)> =
+            BTreeMap::new();
+        for change in first.storage_changes.as_deref().unwrap_or_default() {
+            slots
+                .entry((change.address.clone(), change.slot.clone()))
+                .or_default()
+                .0 = Some(change.after.clone());
+        }
+        for change in second.storage_changes.as_deref().unwrap_or_default() {
+            slots
+                .entry((change.address.clone(), change.slot.clone()))
+                .or_default()
+                .1 = Some(change.after.clone());
+        }
+        diff.storage = slots
+            .into_iter()
+            .filter(|(_, (a, b))| a != b)
+            .map(|((address, slot), (first, second))| StorageDiff {
+                address,
+                slot,
+                first,
+                second,
+            })
+            .collect();
+
+        let mut deltas: BTreeMap<(String, Option<String>), (I256, I256)> = BTreeMap::new();
+        for change in first.balance_changes.as_deref().unwrap_or_default() {
+            deltas
+                .entry((change.holder.clone(), change.token.clone()))
+                .or_default()
+                .0 = change.delta;
+        }
+        for change in second.balance_changes.as_deref().unwrap_or_default() {
+            deltas
+                .entry((change.holder.clone(), change.token.clone()))
+                .or_default()
+                .1 = change.delta;
+        }
+        diff.balances = deltas
+            .into_iter()
+            .filter(|(_, (a, b))| a != b)
+            .map(|((holder, token), (first, second))| BalanceDiff {
+                holder,
+                token,
+                first,
+                second,
+            })
+            .collect();
+
+        diff
+    }
+
+    /// Whether the two simulations had the same effects
+    pub fn is_empty(&self) -> bool {
+        self.success.is_none()
+            && self.gas_used.is_none()
+            && self.logs_only_in_first.is_empty()
+            && self.logs_only_in_second.is_empty()
+            && self.storage.is_empty()
+            && self.balances.is_empty()
+    }
+}
+
+impl fmt::Display for SimulationDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "No differences");
+        }
+        let status = |ok: bool| if ok { "success" } else { "reverted" };
+        let or_dash = |v: &Option<String>| v.clone().unwrap_or_else(|| "-".to_string());
+
+        if let Some((first, second)) = self.success {
+            writeln!(f, "Status:   {} -> {}", status(first), status(second))?;
+        }
+        if let Some((first, second)) = self.gas_used {
+            let change = match (first, second) {
+                (Some(a), Some(b)) => format!(" ({:+})", i128::from(b) - i128::from(a)),
+                _ => String::new(),
+            };
+            writeln!(
+                f,
+                "Gas used: {} -> {}{}",
+                or_dash(&first.map(|g| g.to_string())),
+                or_dash(&second.map(|g| g.to_string())),
+                change
+            )?;
+        }
+        for (title, logs) in [
+            ("Logs only in first", &self.logs_only_in_first),
+            ("Logs only in second", &self.logs_only_in_second),
+        ] {
+            if logs.is_empty() {
+                continue;
+            }
+            writeln!(f, "{}:", title)?;
+            for log in logs {
+                let event = log
+                    .name
+                    .clone()
+                    .or_else(|| log.topics.first().cloned())
+                    .unwrap_or_else(|| "anonymous".to_string());
+                writeln!(f, "  {} {}", log.address, event)?;
+            }
+        }
+        if !self.storage.is_empty() {
+            writeln!(f, "Storage:")?;
+            for slot in &self.storage {
+                writeln!(
+                    f,
+                    "  {} [{}]: {} -> {}",
+                    slot.address,
+                    slot.slot,
+                    or_dash(&slot.first),
+                    or_dash(&slot.second)
+                )?;
+            }
+        }
+        if !self.balances.is_empty() {
+            writeln!(f, "Balance changes:")?;
+            for balance in &self.balances {
+                writeln!(
+                    f,
+                    "  {} {}: {} -> {}",
+                    balance.holder,
+                    balance.token.as_deref().unwrap_or("ETH"),
+                    balance.first,
+                    balance.second
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Accumulates signed balance deltas per `(holder, token)`
+#[derive(Default)]
+struct BalanceLedger(BTreeMap<(String, Option<String>), I256>);
+
+impl BalanceLedger {
+    fn add(&mut self, holder: &str, token: Option<&str>, delta: I256) {
+        let key = (holder.to_lowercase(), token.map(str::to_lowercase));
+        let entry = self.0.entry(key).or_default();
+        *entry = entry.saturating_add(delta);
+    }
+
+    fn transfer(
+        &mut self,
+        from: Option<&str>,
+        to: Option<&str>,
+        token: Option<&str>,
+        amount: I256,
+    ) {
+        if let Some(from) = from {
+            self.add(from, token, -amount);
+        }
+        if let Some(to) = to {
+            self.add(to, token, amount);
+        }
+    }
+
+    fn into_changes(self) -> Vec<BalanceChange> {
+        self.0
+            .into_iter()
+            .filter(|(_, delta)| !delta.is_zero())
+            .map(|((holder, token), delta)| BalanceChange {
+                holder,
+                token,
+                delta,
+            })
+            .collect()
+    }
+}
+
+/// Walk a `callTracer` frame, skipping reverted subtrees
+fn collect_call_frame(frame: &Value, logs: &mut Vec<SimulatedLog>, balances: &mut BalanceLedger) {
+    if !frame["error"].is_null() {
+        return;
+    }
+    let moves_value = !matches!(
+        frame["type"].as_str(),
+        Some("DELEGATECALL") | Some("STATICCALL")
+    );
+    if moves_value {
+        if let Some(value) = frame["value"].as_str().and_then(parse_i256) {
+            if !value.is_zero() {
+                balances.transfer(frame["from"].as_str(), frame["to"].as_str(), None, value);
+            }
+        }
+    }
+    for log in json_array(&frame["logs"]) {
+        if let Some(log) = raw_log(log) {
+            logs.push(log);
+        }
+    }
+    for call in json_array(&frame["calls"]) {
+        collect_call_frame(call, logs, balances);
+    }
+}
+
+/// Read a log with `address`, `topics` and `data` fields
+fn raw_log(log: &Value) -> Option<SimulatedLog> {
+    Some(SimulatedLog {
+        address: log["address"].as_str()?.to_lowercase(),
+        topics: json_array(&log["topics"])
+            .iter()
+            .filter_map(|t| t.as_str().map(str::to_lowercase))
+            .collect(),
+        data: log["data"].as_str().unwrap_or("0x").to_lowercase(),
+        name: None,
+        args: BTreeMap::new(),
+    })
+}
+
+/// Read a Tenderly log: raw fields plus the decoded name and inputs
+fn tenderly_log(log: &Value) -> Option<SimulatedLog> {
+    let mut parsed = raw_log(&log["raw"])?;
+    parsed.name = log["name"].as_str().map(str::to_string);
+    for input in json_array(&log["inputs"]) {
+        if let Some(name) = input["soltype"]["name"].as_str().filter(|n| !n.is_empty()) {
+            parsed
+                .args
+                .insert(name.to_string(), json_scalar(&input["value"]));
+        }
+    }
+    Some(parsed)
+}
+
+/// `(before, after)` of a parity state-diff entry, `None` when unchanged
+///
+/// Entries are `"="`, `{"+": to}`, `{"-": from}` or `{"*": {"from", "to"}}`.
+fn parity_delta(delta: &Value) -> Option<(Option<String>, Option<String>)> {
+    let text = |v: &Value| v.as_str().map(str::to_string);
+    let entry = delta.as_object()?;
+    if let Some(to) = entry.get("+") {
+        Some((None, text(to)))
+    } else if let Some(from) = entry.get("-") {
+        Some((text(from), None))
+    } else {
+        let changed = entry.get("*")?;
+        Some((text(&changed["from"]), text(&changed["to"])))
+    }
+}
+
+fn json_array(value: &Value) -> &[Value] {
+    value.as_array().map(Vec::as_slice).unwrap_or_default()
+}
+
+/// Render a JSON scalar the way values appear in assertion files
+pub(crate) fn json_scalar(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// Render a decoded ABI value the way values appear in assertion files
+pub(crate) fn sol_value_string(value: &DynSolValue) -> String {
+    match value {
+        DynSolValue::Bool(b) => b.to_string(),
+        DynSolValue::Int(i, _) => i.to_string(),
+        DynSolValue::Uint(u, _) => u.to_string(),
+        DynSolValue::FixedBytes(b, size) => format!("0x{}", hex::encode(&b[..*size])),
+        DynSolValue::Address(a) => format!("{:#x}", a),
+        DynSolValue::Function(f) => format!("0x{}", hex::encode(f)),
+        DynSolValue::Bytes(b) => format!("0x{}", hex::encode(b)),
+        DynSolValue::String(s) => s.clone(),
+        DynSolValue::Array(items) | DynSolValue::FixedArray(items) => format!(
+            "[{}]",
+            items
+                .iter()
+                .map(sol_value_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        ),
+        DynSolValue::Tuple(items) => format!(
+            "({})",
+            items
+                .iter()
+                .map(sol_value_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        ),
+    }
+}
+
+/// Parse a decimal or `0x` hex unsigned integer
+pub(crate) fn parse_u256(s: &str) -> Option<U256> {
+    let s = s.trim();
+    match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some("") => Some(U256::ZERO),
+        Some(hex) => U256::from_str_radix(hex, 16).ok(),
+        None => U256::from_str_radix(s, 10).ok(),
+    }
+}
+
+/// Parse a decimal (optionally negative) or `0x` hex integer
+pub(crate) fn parse_i256(s: &str) -> Option<I256> {
+    let s = s.trim();
+    match s.strip_prefix('-') {
+        Some(rest) => parse_u256(rest)
+            .and_then(|u| I256::try_from(u).ok())
+            .map(|i| -i),
+        None => parse_u256(s).and_then(|u| I256::try_from(u).ok()),
+    }
+}
+
+fn parse_u64(s: &str) -> Option<u64> {
+    parse_u256(s).and_then(|u| u64::try_from(u).ok())
+}
+
+/// Normalize a slot or storage value to a 32-byte lowercase hex word
+pub(crate) fn normalize_word(s: &str) -> Option<String> {
+    parse_u256(s).map(|u| format!("0x{:064x}", u))
+}
+
+fn serialize_display<T: std::fmt::Display, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+fn deserialize_i256<'de, D: Deserializer<'de>>(deserializer: D) -> Result<I256, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    parse_i256(&text).ok_or_else(|| serde::de::Error::custom(format!("invalid integer '{}'", text)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const ALICE: &str = "0x00000000000000000000000000000000000000a1";
+    const BOB: &str = "0x00000000000000000000000000000000000000b0";
+    const TOKEN: &str = "0x00000000000000000000000000000000000000cc";
+
+    fn tenderly_response(status: bool) -> tndrly::simulation::SimulationResponse {
+        serde_json::from_value(json!({
+            "simulation": {
+                "id": "sim-1", "network_id": "1", "block_number": 1, "from": ALICE,
+                "to": TOKEN, "input": "0x", "gas": 100000, "gas_used": 51000,
+                "value": "0", "status": status
+            },
+            "transaction": {
+                "gas_used": 52000,
+                "status": status,
+                "transaction_info": {
+                    "call_trace": { "error": "execution reverted", "error_reason": "ERC20: insufficient" },
+                    "logs": [{
+                        "name": "Transfer",
+                        "inputs": [
+                            { "soltype": { "name": "from" }, "value": ALICE },
+                            { "soltype": { "name": "to" }, "value": BOB },
+                            { "soltype": { "name": "value" }, "value": "1000" }
+                        ],
+                        "raw": { "address": "0x00000000000000000000000000000000000000CC", "topics": ["0xDDF2"], "data": "0x03e8" }
+                    }],
+                    "state_diff": [{ "raw": [{ "address": TOKEN, "key": "0x1", "original": "0x0", "dirty": "0x3e8" }] }],
+                    "balance_diff": [{ "address": ALICE, "original": "5000", "dirty": "4000" }],
+                    "asset_changes": [
+                        { "token_info": { "standard": "ERC20", "contract_address": TOKEN }, "from": ALICE, "to": BOB, "raw_amount": "1000" },
+                        { "token_info": { "standard": "ERC721", "contract_address": TOKEN }, "from": ALICE, "to": BOB, "raw_amount": "1" }
+                    ]
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_from_tenderly() {
+        let outcome = SimulationOutcome::from_tenderly(&tenderly_response(true));
+        assert!(outcome.success);
+        assert_eq!(outcome.revert_reason, None);
+        assert_eq!(outcome.gas_used, Some(52000));
+
+        let logs = outcome.logs.unwrap();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].address, TOKEN);
+        assert_eq!(logs[0].name.as_deref(), Some("Transfer"));
+        assert_eq!(logs[0].args["value"], "1000");
+
+        let storage = outcome.storage_changes.unwrap();
+        assert_eq!(storage[0].slot, format!("0x{:064x}", 1));
+        assert_eq!(storage[0].after, format!("0x{:064x}", 1000));
+
+        let balances = outcome.balance_changes.unwrap();
+        let delta = |holder: &str, token: Option<&str>| {
+            balances
+                .iter()
+                .find(|b| b.holder == holder && b.token.as_deref() == token)
+                .map(|b| b.delta.to_string())
+        };
+        assert_eq!(delta(ALICE, None).as_deref(), Some("-1000"));
+        assert_eq!(delta(ALICE, Some(TOKEN)).as_deref(), Some("-1000"));
+        assert_eq!(delta(BOB, Some(TOKEN)).as_deref(), Some("1000"));
+        assert_eq!(balances.len(), 3);
+
+        let reverted = SimulationOutcome::from_tenderly(&tenderly_response(false));
+        assert!(!reverted.success);
+        assert_eq!(
+            reverted.revert_reason.as_deref(),
+            Some("ERC20: insufficient")
+        );
+    }
+
+    #[test]
+    fn test_from_tenderly_without_transaction_info() {
+        let mut response = tenderly_response(true);
+        response.transaction = None;
+        let outcome = SimulationOutcome::from_tenderly(&response);
+        assert_eq!(outcome.gas_used, Some(51000));
+        assert_eq!(outcome.logs, None);
+        assert_eq!(outcome.storage_changes, None);
+        assert_eq!(outcome.balance_changes, None);
+    }
+
+    #[test]
+    fn test_from_call_trace_drops_reverted_frames() {
+        let trace = json!({
+            "type": "CALL", "from": ALICE, "to": TOKEN, "value": "0x64", "gasUsed": "0x5208",
+            "logs": [{ "address": TOKEN, "topics": ["0xaa"], "data": "0x" }],
+            "calls": [
+                { "type": "CALL", "from": TOKEN, "to": BOB, "value": "0xa",
+                  "logs": [{ "address": BOB, "topics": [], "data": "0x01" }] },
+                { "type": "CALL", "from": TOKEN, "to": BOB, "value": "0x5", "error": "execution reverted",
+                  "logs": [{ "address": BOB, "topics": ["0xbb"], "data": "0x" }] },
+                { "type": "DELEGATECALL", "from": TOKEN, "to": BOB, "value": "0x64" }
+            ]
+        });
+        let outcome = SimulationOutcome::from_call_trace(&trace);
+        assert!(outcome.success);
+        assert_eq!(outcome.gas_used, Some(21000));
+        assert_eq!(outcome.logs.as_ref().unwrap().len(), 2);
+        assert_eq!(outcome.storage_changes, None);
+
+        let balances = outcome.balance_changes.unwrap();
+        let delta = |holder: &str| {
+            balances
+                .iter()
+                .find(|b| b.holder == holder)
+                .map(|b| b.delta.to_string())
+        };
+        assert_eq!(delta(ALICE).as_deref(), Some("-100"));
+        assert_eq!(delta(TOKEN).as_deref(), Some("90"));
+        assert_eq!(delta(BOB).as_deref(), Some("10"));
+
+        let reverted = SimulationOutcome::from_call_trace(&json!({
+            "error": "execution reverted", "revertReason": "paused", "gasUsed": "0x10"
+        }));
+        assert!(!reverted.success);
+        assert_eq!(reverted.revert_reason.as_deref(), Some("paused"));
+        assert_eq!(reverted.logs, Some(Vec::new()));
+    }
+
+    #[test]
+    fn test_from_parity_trace() {
+        let result = json!({
+            "output": "0x",
+            "trace": [{ "result": { "gasUsed": "0x100" } }],
+            "stateDiff": {
+                TOKEN: {
+                    "balance": "=",
+                    "storage": {
+                        "0x02": { "*": { "from": "0x01", "to": "0x02" } },
+                        "0x03": "="
+                    }
+                },
+                ALICE: { "balance": { "*": { "from": "0x10", "to": "0x0" } }, "storage": {} }
+            }
+        });
+        let outcome = SimulationOutcome::from_parity_trace(&result);
+        assert!(outcome.success);
+        assert_eq!(outcome.gas_used, Some(256));
+        assert_eq!(outcome.logs, None);
+
+        let storage = outcome.storage_changes.unwrap();
+        assert_eq!(storage.len(), 1);
+        assert_eq!(storage[0].before, normalize_word("1"));
+        assert_eq!(storage[0].after, normalize_word("2").unwrap());
+
+        let balances = outcome.balance_changes.unwrap();
+        assert_eq!(balances.len(), 1);
+        assert_eq!(balances[0].delta.to_string(), "-16");
+
+        let without_diff = SimulationOutcome::from_parity_trace(&json!({
+            "trace": [{ "error": "Reverted" }]
+        }));
+        assert!(!without_diff.success);
+        assert_eq!(without_diff.revert_reason.as_deref(), Some("Reverted"));
+        assert_eq!(without_diff.storage_changes, None);
+    }
+
+    #[test]
+    fn test_diff() {
+        let first = SimulationOutcome::from_tenderly(&tenderly_response(true));
+        assert!(SimulationDiff::between(&first, &first).is_empty());
+
+        let mut second = first.clone();
+        second.gas_used = Some(60000);
+        second.logs.as_mut().unwrap().push(SimulatedLog {
+            address: BOB.to_string(),
+            topics: Vec::new(),
+            data: "0x".to_string(),
+            name: None,
+            args: BTreeMap::new(),
+        });
+        second.storage_changes.as_mut().unwrap()[0].after = normalize_word("7").unwrap();
+        second.balance_changes.as_mut().unwrap().remove(0);
+
+        let diff = SimulationDiff::between(&first, &second);
+        assert_eq!(diff.success, None);
+        assert_eq!(diff.gas_used, Some((Some(52000), Some(60000))));
+        assert!(diff.logs_only_in_first.is_empty());
+        assert_eq!(diff.logs_only_in_second.len(), 1);
+        assert_eq!(diff.storage.len(), 1);
+        assert_eq!(diff.storage[0].second, normalize_word("7"));
+        assert_eq!(diff.balances.len(), 1);
+        assert_eq!(diff.balances[0].second, I256::ZERO);
+
+        let text = diff.to_string();
+        assert!(text.contains("Gas used: 52000 -> 60000 (+8000)"));
+        assert!(text.contains("Logs only in second:"));
+        assert!(text.contains(&format!("{} ETH: -1000 -> 0", ALICE)));
+        assert_eq!(SimulationDiff::default().to_string(), "No differences");
+    }
+
+    #[test]
+    fn test_from_receipt() {
+        let word = |addr: &str| format!("0x{:0>64}", &addr[2..]);
+        let receipt = json!({
+            "status": "0x1",
+            "gasUsed": "0xcb20",
+            "logs": [
+                {
+                    "address": TOKEN,
+                    "topics": [ERC20_TRANSFER_TOPIC, word(ALICE), word(BOB)],
+                    "data": format!("0x{:064x}", 250),
+                },
+                // ERC-721 Transfer: token id is indexed, no amount
+                {
+                    "address": BOB,
+                    "topics": [ERC20_TRANSFER_TOPIC, word(ALICE), word(BOB), word(TOKEN)],
+                    "data": "0x",
+                },
+                // Malformed topics are skipped rather than sliced
+                {
+                    "address": TOKEN,
+                    "topics": [ERC20_TRANSFER_TOPIC, "0xa1", word(BOB)],
+                    "data": format!("0x{:064x}", 1),
+                },
+                {
+                    "address": TOKEN,
+                    "topics": [ERC20_TRANSFER_TOPIC, format!("0x{}", "é".repeat(32)), word(BOB)],
+                    "data": format!("0x{:064x}", 1),
+                }
+            ]
+        });
+        let outcome = SimulationOutcome::from_receipt(&receipt, ALICE, BOB, Some("0x64"));
+        assert!(outcome.success);
+        assert_eq!(outcome.gas_used, Some(52000));
+        assert_eq!(outcome.logs.as_ref().map(Vec::len), Some(4));
+        let changes = outcome.balance_changes.unwrap();
+        let delta = |holder: &str, token: Option<&str>| {
+            changes
+                .iter()
+                .find(|c| c.holder == holder && c.token.as_deref() == token)
+                .map(|c| c.delta.to_string())
+        };
+        assert_eq!(changes.len(), 4);
+        assert_eq!(delta(ALICE, None).as_deref(), Some("-100"));
+        assert_eq!(delta(BOB, Some(TOKEN)).as_deref(), Some("250"));
+
+        // A reverted transaction moves no value
+        let reverted = json!({"status": "0x0", "gasUsed": "0x5208", "logs": []});
+        let outcome = SimulationOutcome::from_receipt(&reverted, ALICE, BOB, Some("100"));
+        assert!(!outcome.success);
+        assert_eq!(outcome.balance_changes, Some(Vec::new()));
+    }
+
+    #[test]
+    fn test_outcome_round_trip() {
+        let outcome = SimulationOutcome::from_tenderly(&tenderly_response(true));
+        let json = serde_json::to_string(&outcome).unwrap();
+        let parsed: SimulationOutcome = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, outcome);
+
+        let bad = json!({"holder": ALICE, "token": null, "delta": "lots"});
+        assert!(serde_json::from_value::<BalanceChange>(bad).is_err());
+    }
+
+    #[test]
+    fn test_parse_numbers() {
+        assert_eq!(parse_u256("0x10"), Some(U256::from(16)));
+        assert_eq!(parse_u256("16"), Some(U256::from(16)));
+        assert_eq!(parse_u256("0x"), Some(U256::ZERO));
+        assert_eq!(parse_u256("abc"), None);
+        assert_eq!(
+            parse_i256("-5").map(|i| i.to_string()).as_deref(),
+            Some("-5")
+        );
+        assert_eq!(normalize_word("0x01"), normalize_word("1"));
+    }
+}
@@ -0,0 +1,1006 @@
+//! Portable simulation bundles and regression replay
+//!
+//! A [`PortableBundle`] records the transactions of a simulation bundle
+//! together with the outcome each one had when it was saved. `simulate
+//! replay` re-executes the transactions at a later block, on an anvil fork
+//! or on Tenderly, and reports where the new outcomes drifted from the saved
+//! ones. `simulate import` turns a bundle file back into saved Tenderly
+//! simulations.
+
+use super::outcome::{parse_u256, BalanceChange, SimulationOutcome};
+use super::types::TenderlyArgs;
+use super::utils::create_tenderly_client;
+use crate::cli::OutputFormat;
+use crate::config::Chain;
+use crate::rpc::{get_rpc_url, RawRpc};
+use alloy::primitives::I256;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::time::Duration;
+use tndrly::simulation::{BundleSimulationRequest, SimulationRequest, SimulationResponse};
+
+/// Current portable bundle format version
+///
+/// Bump when a change would make older ethcli versions misread a bundle.
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// How long to wait for a spawned anvil to answer requests
+const ANVIL_STARTUP_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// A simulation bundle with its saved outcomes, independent of any backend
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortableBundle {
+    /// Format version, see [`BUNDLE_VERSION`]
+    pub version: u32,
+    /// Chain ID the bundle was simulated on
+    pub network_id: String,
+    /// Block the saved outcomes were simulated at
+    pub block_number: Option<u64>,
+    /// Tenderly simulation IDs the bundle was exported from
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub simulation_ids: Vec<String>,
+    /// Transactions in execution order
+    pub transactions: Vec<BundleTransaction>,
+}
+
+/// One transaction of a portable bundle
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleTransaction {
+    /// Sender address
+    pub from: String,
+    /// Target address
+    pub to: String,
+    /// Calldata as hex
+    #[serde(default = "empty_input")]
+    pub input: String,
+    /// Value in wei (decimal or `0x` hex)
+    #[serde(default = "zero_value")]
+    pub value: String,
+    /// Gas limit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas: Option<u64>,
+    /// Outcome when the bundle was saved
+    pub outcome: SimulationOutcome,
+}
+
+fn empty_input() -> String {
+    "0x".to_string()
+}
+
+fn zero_value() -> String {
+    "0".to_string()
+}
+
+impl PortableBundle {
+    /// Build a bundle from Tenderly simulation results, in execution order
+    pub fn from_tenderly(results: &[SimulationResponse]) -> anyhow::Result<Self> {
+        let first = results
+            .first()
+            .ok_or_else(|| anyhow::anyhow!("Cannot export an empty bundle"))?;
+        if let Some(other) = results
+            .iter()
+            .find(|r| r.simulation.network_id != first.simulation.network_id)
+        {
+            return Err(anyhow::anyhow!(
+                "Simulation {} is on network {}, but {} is on network {}",
+                other.simulation.id,
+                other.simulation.network_id,
+                first.simulation.id,
+                first.simulation.network_id
+            ));
+        }
+
+        Ok(Self {
+            version: BUNDLE_VERSION,
+            network_id: first.simulation.network_id.clone(),
+            block_number: Some(first.simulation.block_number),
+            simulation_ids: results
+                .iter()
+                .map(|r| r.simulation.id.clone())
+                .filter(|id| !id.is_empty())
+                .collect(),
+            transactions: results
+                .iter()
+                .map(|r| BundleTransaction {
+                    from: r.simulation.from.to_lowercase(),
+                    to: r.simulation.to.to_lowercase(),
+                    input: r.simulation.input.clone(),
+                    value: r.simulation.value.clone(),
+                    gas: Some(r.simulation.gas).filter(|g| *g > 0),
+                    outcome: SimulationOutcome::from_tenderly(r),
+                })
+                .collect(),
+        })
+    }
+
+    /// Parse a bundle, rejecting versions this ethcli doesn't understand
+    pub fn from_json(text: &str) -> anyhow::Result<Self> {
+        let value: Value = serde_json::from_str(text)
+            .map_err(|e| anyhow::anyhow!("Invalid bundle JSON: {}", e))?;
+        let version = value["version"]
+            .as_u64()
+            .ok_or_else(|| anyhow::anyhow!("Bundle has no format version"))?;
+        if version == 0 || version > u64::from(BUNDLE_VERSION) {
+            return Err(anyhow::anyhow!(
+                "Bundle format version {} is not supported (this ethcli reads up to version {})",
+                version,
+                BUNDLE_VERSION
+            ));
+        }
+
+        let bundle: Self =
+            serde_json::from_value(value).map_err(|e| anyhow::anyhow!("Invalid bundle: {}", e))?;
+        if bundle.transactions.is_empty() {
+            return Err(anyhow::anyhow!("Bundle has no transactions"));
+        }
+        Ok(bundle)
+    }
+
+    /// Read a bundle file
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read bundle file {}: {}", path.display(), e))?;
+        Self::from_json(&text)
+    }
+
+    /// Write the bundle as pretty-printed JSON
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .map_err(|e| anyhow::anyhow!("Failed to write bundle file {}: {}", path.display(), e))
+    }
+
+    /// Tenderly simulation requests for the transactions, in order
+    fn tenderly_requests(&self, block_number: Option<u64>) -> Vec<SimulationRequest> {
+        self.transactions
+            .iter()
+            .map(|tx| {
+                let mut request = SimulationRequest::new(&tx.from, &tx.to, &tx.input)
+                    .network_id(&self.network_id)
+                    .value(&tx.value);
+                if let Some(gas) = tx.gas {
+                    request = request.gas(gas);
+                }
+                if let Some(block) = block_number {
+                    request = request.block_number(block);
+                }
+                request
+            })
+            .collect()
+    }
+
+    /// Chain the bundle was simulated on
+    pub fn chain(&self) -> anyhow::Result<Chain> {
+        let id = self
+            .network_id
+            .parse()
+            .map_err(|_| anyhow::anyhow!("Invalid network ID '{}' in bundle", self.network_id))?;
+        Ok(Chain::from_chain_id(id))
+    }
+}
+
+/// Backend that re-executes a bundle
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ReplayBackend {
+    /// Send the transactions to an anvil fork
+    #[default]
+    Anvil,
+    /// Simulate the bundle via the Tenderly API
+    Tenderly,
+}
+
+impl fmt::Display for ReplayBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Anvil => write!(f, "anvil"),
+            Self::Tenderly => write!(f, "tenderly"),
+        }
+    }
+}
+
+/// Asset whose balance changes are compared on replay
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssetFilter {
+    /// Native ETH
+    Eth,
+    /// An ERC-20 token contract (lowercase)
+    Token(String),
+}
+
+impl AssetFilter {
+    /// Parse `eth` or a token address
+    pub fn parse(s: &str) -> anyhow::Result<Self> {
+        if s.eq_ignore_ascii_case("eth") {
+            return Ok(Self::Eth);
+        }
+        let hex = s.strip_prefix("0x").unwrap_or_default();
+        if hex.len() != 40 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(anyhow::anyhow!(
+                "Invalid asset '{}': expected 'eth' or a token address",
+                s
+            ));
+        }
+        Ok(Self::Token(s.to_lowercase()))
+    }
+
+    fn matches(&self, token: Option<&str>) -> bool {
+        match self {
+            Self::Eth => token.is_none(),
+            Self::Token(address) => token == Some(address.as_str()),
+        }
+    }
+}
+
+/// How far a replay may drift from the saved outcomes
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayTolerances {
+    /// Allowed gas change, in percent of the saved gas used
+    pub gas_pct: f64,
+    /// Assets whose balance changes must match; empty compares every ERC-20
+    /// token. ETH is only compared when listed, since backends disagree on
+    /// whether gas fees count.
+    pub assets: Vec<AssetFilter>,
+}
+
+impl Default for ReplayTolerances {
+    fn default() -> Self {
+        Self {
+            gas_pct: 5.0,
+            assets: Vec::new(),
+        }
+    }
+}
+
+impl ReplayTolerances {
+    fn selects(&self, token: Option<&str>) -> bool {
+        if self.assets.is_empty() {
+            token.is_some()
+        } else {
+            self.assets.iter().any(|a| a.matches(token))
+        }
+    }
+}
+
+/// Result of one replay check
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReplayCheck {
+    /// Index of the transaction in the bundle
+    pub transaction: usize,
+    /// What was compared (`status`, `gas`, `logs` or `assets`)
+    pub check: String,
+    /// Whether the replay stayed within tolerance
+    pub passed: bool,
+    /// Saved and replayed values
+    pub detail: String,
+}
+
+/// Comparison of a replay against the saved outcomes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReplayReport {
+    /// Backend the bundle was replayed on
+    pub backend: String,
+    /// Block the bundle was replayed at, if pinned
+    pub block_number: Option<u64>,
+    /// Number of checks within tolerance
+    pub passed: usize,
+    /// Number of regressions
+    pub failed: usize,
+    /// Per-transaction checks, in bundle order
+    pub results: Vec<ReplayCheck>,
+}
+
+impl ReplayReport {
+    /// Compare replayed outcomes with the outcomes saved in `bundle`
+    pub fn compare(
+        bundle: &PortableBundle,
+        replayed: &[SimulationOutcome],
+        tolerances: &ReplayTolerances,
+        backend: ReplayBackend,
+        block_number: Option<u64>,
+    ) -> Self {
+        let mut results = Vec::new();
+        for (index, tx) in bundle.transactions.iter().enumerate() {
+            let Some(new) = replayed.get(index) else {
+                results.push(ReplayCheck {
+                    transaction: index,
+                    check: "status".to_string(),
+                    passed: false,
+                    detail: "not replayed".to_string(),
+                });
+                continue;
+            };
+            compare_outcome(index, &tx.outcome, new, tolerances, &mut results);
+        }
+
+        let passed = results.iter().filter(|r| r.passed).count();
+        Self {
+            backend: backend.to_string(),
+            block_number,
+            passed,
+            failed: results.len() - passed,
+            results,
+        }
+    }
+
+    /// Whether every check passed
+    pub fn all_passed(&self) -> bool {
+        self.failed == 0
+    }
+}
+
+impl fmt::Display for ReplayReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for result in &self.results {
+            let status = if result.passed { "PASS" } else { "FAIL" };
+            writeln!(f, "{}  tx {} {}", status, result.transaction, result.check)?;
+            if !result.passed {
+                writeln!(f, "      {}", result.detail)?;
+            }
+        }
+        write!(f, "\n{} passed, {} failed", self.passed, self.failed)
+    }
+}
+
+/// Compare one transaction's outcomes
+///
+/// Checks the saved outcome has no data for are skipped; data the replay
+/// fails to report where the saved outcome had it counts as a regression.
+fn compare_outcome(
+    index: usize,
+    saved: &SimulationOutcome,
+    new: &SimulationOutcome,
+    tolerances: &ReplayTolerances,
+    results: &mut Vec<ReplayCheck>,
+) {
+    let mut push = |check: &str, passed: bool, detail: String| {
+        results.push(ReplayCheck {
+            transaction: index,
+            check: check.to_string(),
+            passed,
+            detail,
+        })
+    };
+
+    let status = |outcome: &SimulationOutcome| match (outcome.success, &outcome.revert_reason) {
+        (true, _) => "success".to_string(),
+        (false, Some(reason)) => format!("reverted: {}", reason),
+        (false, None) => "reverted".to_string(),
+    };
+    push(
+        "status",
+        saved.success == new.success,
+        format!("{} -> {}", status(saved), status(new)),
+    );
+
+    if let Some(before) = saved.gas_used {
+        match new.gas_used {
+            Some(after) => {
+                let change = gas_change_pct(before, after);
+                push(
+                    "gas",
+                    change.abs() <= tolerances.gas_pct,
+                    format!(
+                        "{} -> {} ({:+.2}%, tolerance {}%)",
+                        before, after, change, tolerances.gas_pct
+                    ),
+                );
+            }
+            None => push("gas", false, format!("{} -> not reported", before)),
+        }
+    }
+
+    if let Some(before) = &saved.logs {
+        match &new.logs {
+            Some(after) => push(
+                "logs",
+                before.len() == after.len(),
+                format!("{} -> {} logs", before.len(), after.len()),
+            ),
+            None => push(
+                "logs",
+                false,
+                format!("{} logs -> not reported", before.len()),
+            ),
+        }
+    }
+
+    if let Some(before) = &saved.balance_changes {
+        match &new.balance_changes {
+            Some(after) => {
+                let drifted = asset_drift(before, after, tolerances);
+                let detail = if drifted.is_empty() {
+                    "selected balance changes match".to_string()
+                } else {
+                    drifted.join("; ")
+                };
+                push("assets", drifted.is_empty(), detail);
+            }
+            None => push("assets", false, "balance changes not reported".to_string()),
+        }
+    }
+}
+
+/// Gas change in percent of `before` (0 when both are zero)
+fn gas_change_pct(before: u64, after: u64) -> f64 {
+    if before == 0 {
+        return if after == 0 { 0.0 } else { f64::INFINITY };
+    }
+    (after as f64 - before as f64) / before as f64 * 100.0
+}
+
+/// Selected `(holder, asset)` pairs whose net change differs
+fn asset_drift(
+    before: &[BalanceChange],
+    after: &[BalanceChange],
+    tolerances: &ReplayTolerances,
+) -> Vec<String> {
+    let mut deltas: BTreeMap<(&str, Option<&str>), (I256, I256)> = BTreeMap::new();
+    for change in before
+        .iter()
+        .filter(|c| tolerances.selects(c.token.as_deref()))
+    {
+        deltas
+            .entry((&change.holder, change.token.as_deref()))
+            .or_default()
+            .0 = change.delta;
+    }
+    for change in after
+        .iter()
+        .filter(|c| tolerances.selects(c.token.as_deref()))
+    {
+        deltas
+            .entry((&change.holder, change.token.as_deref()))
+            .or_default()
+            .1 = change.delta;
+    }
+    deltas
+        .into_iter()
+        .filter(|(_, (a, b))| a != b)
+        .map(|((holder, token), (a, b))| {
+            format!("{} {}: {} -> {}", holder, token.unwrap_or("ETH"), a, b)
+        })
+        .collect()
+}
+
+/// Where `simulate replay` finds an anvil node
+pub struct AnvilTarget {
+    /// Fork source for a spawned anvil, or for resetting an existing one
+    pub fork_url: Option<String>,
+    /// Existing anvil node to reset and use instead of spawning one
+    pub anvil_url: Option<String>,
+}
+
+/// Re-execute a bundle on an anvil fork at `block_number`
+///
+/// Each transaction is sent from its impersonated sender with a zero gas
+/// price, matching Tenderly's default, so senders need no ETH for gas.
+pub async fn replay_via_anvil(
+    bundle: &PortableBundle,
+    block_number: Option<u64>,
+    target: &AnvilTarget,
+    quiet: bool,
+) -> anyhow::Result<Vec<SimulationOutcome>> {
+    // Kept alive until the replay is done; dropping it stops anvil
+    let mut _spawned = None;
+    let rpc = match &target.anvil_url {
+        Some(url) => {
+            let mut forking = json!({});
+            if let Some(fork_url) = &target.fork_url {
+                forking["jsonRpcUrl"] = json!(fork_url);
+            }
+            if let Some(block) = block_number {
+                forking["blockNumber"] = json!(block);
+            }
+            if !quiet {
+                eprintln!("Resetting anvil at {}...", url);
+            }
+            let rpc = RawRpc::new(url)?;
+            rpc.request("anvil_reset", json!([{ "forking": forking }]))
+                .await?;
+            rpc
+        }
+        None => {
+            let fork_url = match &target.fork_url {
+                Some(url) => url.clone(),
+                None => get_rpc_url(bundle.chain()?)?,
+            };
+            if !quiet {
+                eprintln!("Starting Anvil fork of {}...", fork_url);
+            }
+            let port = free_port()?;
+            let mut cmd = Command::new("anvil");
+            cmd.arg("--fork-url")
+                .arg(&fork_url)
+                .arg("--port")
+                .arg(port.to_string())
+                .arg("--silent")
+                .stdout(Stdio::null())
+                .stderr(Stdio::null());
+            if let Some(block) = block_number {
+                cmd.arg("--fork-block-number").arg(block.to_string());
+            }
+            let mut anvil = AnvilProcess(cmd.spawn()?);
+            let rpc = RawRpc::new(&format!("http://127.0.0.1:{}", port))?;
+            wait_for_node(&rpc, &mut anvil).await?;
+            _spawned = Some(anvil);
+            rpc
+        }
+    };
+
+    let mut outcomes = Vec::with_capacity(bundle.transactions.len());
+    for (index, tx) in bundle.transactions.iter().enumerate() {
+        if !quiet {
+            eprintln!(
+                "Replaying transaction {} of {}...",
+                index + 1,
+                bundle.transactions.len()
+            );
+        }
+        outcomes.push(send_on_anvil(&rpc, tx).await?);
+    }
+    Ok(outcomes)
+}
+
+/// Send one transaction to anvil and normalize its receipt
+async fn send_on_anvil(rpc: &RawRpc, tx: &BundleTransaction) -> anyhow::Result<SimulationOutcome> {
+    let value = parse_u256(&tx.value)
+        .ok_or_else(|| anyhow::anyhow!("Invalid value '{}' in bundle", tx.value))?;
+    let mut request = json!({
+        "from": tx.from,
+        "to": tx.to,
+        "data": tx.input,
+        "value": format!("0x{:x}", value),
+        "gasPrice": "0x0",
+    });
+    if let Some(gas) = tx.gas {
+        request["gas"] = json!(format!("0x{:x}", gas));
+    }
+
+    rpc.request("anvil_impersonateAccount", json!([tx.from]))
+        .await?;
+    rpc.request("anvil_setNextBlockBaseFeePerGas", json!(["0x0"]))
+        .await?;
+    let response = rpc
+        .try_request("eth_sendTransaction", json!([request]))
+        .await?;
+    rpc.request("anvil_stopImpersonatingAccount", json!([tx.from]))
+        .await?;
+
+    // Without a gas limit anvil estimates first, and a revert surfaces as an error
+    let hash = match response {
+        Ok(hash) => hash
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("eth_sendTransaction returned no hash"))?
+            .to_string(),
+        Err(message) => {
+            return Ok(SimulationOutcome {
+                success: false,
+                revert_reason: Some(message),
+                ..Default::default()
+            })
+        }
+    };
+
+    let mut receipt = rpc
+        .request("eth_getTransactionReceipt", json!([hash]))
+        .await?;
+    if receipt.is_null() {
+        // Automine is off; mine the transaction ourselves
+        rpc.request("evm_mine", json!([])).await?;
+        receipt = rpc
+            .request("eth_getTransactionReceipt", json!([hash]))
+            .await?;
+    }
+    if receipt.is_null() {
+        return Err(anyhow::anyhow!(
+            "No receipt for replayed transaction {}",
+            hash
+        ));
+    }
+    Ok(SimulationOutcome::from_receipt(
+        &receipt,
+        &tx.from,
+        &tx.to,
+        Some(&tx.value),
+    ))
+}
+
+/// Re-execute a bundle via the Tenderly simulate-bundle API
+pub async fn replay_via_tenderly(
+    bundle: &PortableBundle,
+    block_number: Option<u64>,
+    tenderly_args: &TenderlyArgs,
+    quiet: bool,
+) -> anyhow::Result<Vec<SimulationOutcome>> {
+    let simulations = bundle.tenderly_requests(block_number);
+
+    if !quiet {
+        eprintln!(
+            "Replaying bundle of {} transactions via Tenderly...",
+            bundle.transactions.len()
+        );
+    }
+    let client = create_tenderly_client(tenderly_args)?;
+    let response = client
+        .simulation()
+        .simulate_bundle(&BundleSimulationRequest::new(simulations))
+        .await
+        .map_err(|e| anyhow::anyhow!("Tenderly API error: {}", e))?;
+    Ok(response
+        .simulation_results
+        .iter()
+        .map(SimulationOutcome::from_tenderly)
+        .collect())
+}
+
+/// Fetch saved Tenderly simulations, in the given order, as a portable bundle
+pub async fn fetch_tenderly_bundle(
+    ids: &[String],
+    tenderly_args: &TenderlyArgs,
+    quiet: bool,
+) -> anyhow::Result<PortableBundle> {
+    if !quiet {
+        eprintln!("Fetching {} Tenderly simulation(s)...", ids.len());
+    }
+    let client = create_tenderly_client(tenderly_args)?;
+    let mut results = Vec::with_capacity(ids.len());
+    for id in ids {
+        results.push(
+            client
+                .simulation()
+                .get_full(id)
+                .await
+                .map_err(|e| anyhow::anyhow!("Tenderly API error for {}: {}", id, e))?,
+        );
+    }
+    PortableBundle::from_tenderly(&results)
+}
+
+/// Export saved Tenderly simulations to a portable bundle file
+pub async fn export_bundle_tenderly(
+    ids: &[String],
+    output: &Path,
+    tenderly_args: &TenderlyArgs,
+    quiet: bool,
+) -> anyhow::Result<()> {
+    let bundle = fetch_tenderly_bundle(ids, tenderly_args, quiet).await?;
+    bundle.save(output)?;
+    if !quiet {
+        eprintln!(
+            "Exported {} transaction(s) to {}",
+            bundle.transactions.len(),
+            output.display()
+        );
+    }
+    Ok(())
+}
+
+/// Save a bundle's transactions as Tenderly simulations, returning their IDs
+///
+/// The transactions run as one Tenderly bundle, so each sees the state left
+/// by the ones before it, and are saved even if they revert.
+pub async fn import_bundle(
+    client: &tndrly::Client,
+    bundle: &PortableBundle,
+    block_number: Option<u64>,
+) -> anyhow::Result<Vec<String>> {
+    let simulations = bundle
+        .tenderly_requests(block_number)
+        .into_iter()
+        .map(SimulationRequest::save_always)
+        .collect();
+    let response = client
+        .simulation()
+        .simulate_bundle(&BundleSimulationRequest::new(simulations))
+        .await
+        .map_err(|e| anyhow::anyhow!("Tenderly API error: {}", e))?;
+
+    let ids: Vec<String> = response
+        .simulation_results
+        .iter()
+        .map(|r| r.simulation.id.clone())
+        .collect();
+    if ids.len() != bundle.transactions.len() || ids.iter().any(String::is_empty) {
+        return Err(anyhow::anyhow!(
+            "Tenderly saved {} of {} transactions",
+            ids.iter().filter(|id| !id.is_empty()).count(),
+            bundle.transactions.len()
+        ));
+    }
+    Ok(ids)
+}
+
+/// Import a bundle file into the saved Tenderly simulations
+///
+/// Prints the new simulation IDs comma-separated, ready for `simulate
+/// export` or `simulate replay --ids`. Without `at_block`, the transactions
+/// run at the block the bundle was saved at.
+pub async fn import_bundle_tenderly(
+    path: &Path,
+    at_block: Option<u64>,
+    tenderly_args: &TenderlyArgs,
+    quiet: bool,
+) -> anyhow::Result<()> {
+    let bundle = PortableBundle::load(path)?;
+    if !quiet {
+        eprintln!(
+            "Saving {} transaction(s) as Tenderly simulations...",
+            bundle.transactions.len()
+        );
+    }
+    let client = create_tenderly_client(tenderly_args)?;
+    let ids = import_bundle(&client, &bundle, at_block.or(bundle.block_number)).await?;
+    println!("{}", ids.join(","));
+    Ok(())
+}
+
+/// Where `simulate replay` reads its bundle from
+#[derive(Debug, Clone)]
+pub enum BundleSource {
+    /// A portable bundle file
+    File(PathBuf),
+    /// Saved Tenderly simulations, in execution order
+    TenderlyIds(Vec<String>),
+}
+
+/// Replay a bundle file or saved Tenderly simulations and report regressions
+///
+/// Without `at_block`, the bundle is replayed at the block it was saved at.
+#[allow(clippy::too_many_arguments)]
+pub async fn replay_bundle(
+    source: &BundleSource,
+    at_block: Option<u64>,
+    backend: ReplayBackend,
+    tolerances: &ReplayTolerances,
+    anvil: &AnvilTarget,
+    format: OutputFormat,
+    tenderly_args: &TenderlyArgs,
+    quiet: bool,
+) -> anyhow::Result<()> {
+    let bundle = match source {
+        BundleSource::File(path) => PortableBundle::load(path)?,
+        BundleSource::TenderlyIds(ids) => fetch_tenderly_bundle(ids, tenderly_args, quiet).await?,
+    };
+
+    let block_number = at_block.or(bundle.block_number);
+    let replayed = match backend {
+        ReplayBackend::Anvil => replay_via_anvil(&bundle, block_number, anvil, quiet).await?,
+        ReplayBackend::Tenderly => {
+            replay_via_tenderly(&bundle, block_number, tenderly_args, quiet).await?
+        }
+    };
+
+    let report = ReplayReport::compare(&bundle, &replayed, tolerances, backend, block_number);
+    match format {
+        OutputFormat::Json | OutputFormat::Ndjson => {
+            println!("{}", serde_json::to_string_pretty(&report)?)
+        }
+        OutputFormat::Table => println!("{}", report),
+    }
+    if !report.all_passed() {
+        return Err(anyhow::anyhow!(
+            "{} of {} replay checks regressed",
+            report.failed,
+            report.results.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Kills the spawned anvil when dropped, including on early error returns
+struct AnvilProcess(Child);
+
+impl Drop for AnvilProcess {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+/// Poll until a freshly spawned node answers `eth_blockNumber`
+///
+/// Fails as soon as the process exits, e.g. when the port was taken.
+async fn wait_for_node(rpc: &RawRpc, anvil: &mut AnvilProcess) -> anyhow::Result<()> {
+    let started = std::time::Instant::now();
+    loop {
+        if rpc.request("eth_blockNumber", json!([])).await.is_ok() {
+            return Ok(());
+        }
+        if let Some(status) = anvil.0.try_wait()? {
+            return Err(anyhow::anyhow!("Anvil exited during startup ({})", status));
+        }
+        if started.elapsed() > ANVIL_STARTUP_TIMEOUT {
+            return Err(anyhow::anyhow!(
+                "Anvil did not start within {:?}",
+                ANVIL_STARTUP_TIMEOUT
+            ));
+        }
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+}
+
+/// A local port nothing is listening on
+fn free_port() -> anyhow::Result<u16> {
+    Ok(std::net::TcpListener::bind("127.0.0.1:0")?
+        .local_addr()?
+        .port())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN: &str = "0x00000000000000000000000000000000000000cc";
+
+    fn bundle() -> PortableBundle {
+        PortableBundle::from_json(include_str!(
+            "../../../tests/fixtures/replay/bundle_v1.json"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_bundle_versions() {
+        let bundle = bundle();
+        assert_eq!(bundle.version, BUNDLE_VERSION);
+        assert_eq!(bundle.chain().unwrap(), Chain::Ethereum);
+        assert_eq!(bundle.transactions.len(), 2);
+        assert_eq!(bundle.transactions[1].outcome.gas_used, Some(21000));
+
+        // Round trip through the writer
+        let written = serde_json::to_string(&bundle).unwrap();
+        assert_eq!(PortableBundle::from_json(&written).unwrap(), bundle);
+
+        let mut value: Value = serde_json::from_str(&written).unwrap();
+        value["version"] = json!(BUNDLE_VERSION + 1);
+        let err = PortableBundle::from_json(&value.to_string()).unwrap_err();
+        assert!(err.to_string().contains("not supported"));
+        value.as_object_mut().unwrap().remove("version");
+        let err = PortableBundle::from_json(&value.to_string()).unwrap_err();
+        assert!(err.to_string().contains("no format version"));
+        value["version"] = json!(1);
+        value["transactions"] = json!([]);
+        assert!(PortableBundle::from_json(&value.to_string()).is_err());
+    }
+
+    #[test]
+    fn test_compare_within_tolerance() {
+        let bundle = bundle();
+        let mut replayed: Vec<SimulationOutcome> = bundle
+            .transactions
+            .iter()
+            .map(|tx| tx.outcome.clone())
+            .collect();
+        // +4% gas and a different ETH change (not selected by default)
+        replayed[0].gas_used = Some(54080);
+        replayed[1].balance_changes = Some(Vec::new());
+
+        let tolerances = ReplayTolerances::default();
+        let report = ReplayReport::compare(
+            &bundle,
+            &replayed,
+            &tolerances,
+            ReplayBackend::Anvil,
+            Some(19_000_000),
+        );
+        assert!(report.all_passed(), "{}", report);
+        assert_eq!(report.passed, 8);
+
+        // Selecting ETH catches the missing transfer
+        let tolerances = ReplayTolerances {
+            assets: vec![AssetFilter::Eth],
+            ..Default::default()
+        };
+        let report =
+            ReplayReport::compare(&bundle, &replayed, &tolerances, ReplayBackend::Anvil, None);
+        assert_eq!(report.failed, 1);
+        assert_eq!(report.results[7].check, "assets");
+        assert!(report.results[7]
+            .detail
+            .contains("ETH: -1000000000000000000 -> 0"));
+    }
+
+    #[test]
+    fn test_compare_regressions() {
+        let bundle = bundle();
+        let mut replayed: Vec<SimulationOutcome> = bundle
+            .transactions
+            .iter()
+            .map(|tx| tx.outcome.clone())
+            .collect();
+        replayed[0].success = false;
+        replayed[0].revert_reason = Some("paused".to_string());
+        replayed[0].gas_used = Some(60000);
+        replayed[0].logs = Some(Vec::new());
+        replayed[0].balance_changes = Some(Vec::new());
+        replayed.pop();
+
+        let report = ReplayReport::compare(
+            &bundle,
+            &replayed,
+            &ReplayTolerances::default(),
+            ReplayBackend::Tenderly,
+            None,
+        );
+        assert!(!report.all_passed());
+        let failed: Vec<(usize, &str)> = report
+            .results
+            .iter()
+            .filter(|r| !r.passed)
+            .map(|r| (r.transaction, r.check.as_str()))
+            .collect();
+        assert_eq!(
+            failed,
+            [
+                (0, "status"),
+                (0, "gas"),
+                (0, "logs"),
+                (0, "assets"),
+                (1, "status")
+            ]
+        );
+        assert_eq!(report.results[0].detail, "success -> reverted: paused");
+        assert!(report.results[3].detail.contains(TOKEN));
+
+        let text = report.to_string();
+        assert!(text.contains("FAIL  tx 0 gas"));
+        assert!(text.contains("+15.38%, tolerance 5%"));
+        assert!(text.ends_with("0 passed, 5 failed"));
+    }
+
+    #[test]
+    fn test_asset_filter() {
+        assert_eq!(AssetFilter::parse("ETH").unwrap(), AssetFilter::Eth);
+        assert_eq!(
+            AssetFilter::parse("0x00000000000000000000000000000000000000CC").unwrap(),
+            AssetFilter::Token(TOKEN.to_string())
+        );
+        assert!(AssetFilter::parse("usdc").is_err());
+        assert_eq!(gas_change_pct(0, 0), 0.0);
+        assert!(gas_change_pct(0, 1).is_infinite());
+    }
+
+    #[tokio::test]
+    async fn test_import_saves_bundle_in_order() {
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let bundle = bundle();
+        let saved = |id: &str, tx: &BundleTransaction| {
+            json!({ "simulation": {
+                "id": id, "network_id": "1", "block_number": 19_600_000,
+                "from": tx.from, "to": tx.to, "input": tx.input, "gas": 100000,
+                "gas_used": 21000, "value": tx.value, "status": true
+            }})
+        };
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/account/acct/project/proj/simulate-bundle"))
+            .and(body_partial_json(json!({ "simulations": [
+                { "from": bundle.transactions[0].from, "block_number": 19_600_000, "save": true, "save_if_fails": true },
+                { "from": bundle.transactions[1].from, "block_number": 19_600_000, "save": true, "save_if_fails": true }
+            ]})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "simulation_results": [
+                    saved("sim-a", &bundle.transactions[0]),
+                    saved("sim-b", &bundle.transactions[1])
+                ]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = tndrly::Client::new(
+            tndrly::Config::new("key", "acct", "proj").with_base_url(server.uri()),
+        )
+        .unwrap();
+        let ids = import_bundle(&client, &bundle, Some(19_600_000))
+            .await
+            .unwrap();
+        assert_eq!(ids, ["sim-a", "sim-b"]);
+    }
+}
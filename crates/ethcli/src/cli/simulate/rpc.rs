@@ -1,3 +1,4 @@
+use super::outcome::SimulationOutcome;
 use super::types::DryRunFormat;
 use super::utils::{
     block_to_param, build_calldata, build_state_overrides, format_request, get_debug_rpc_url,
@@ -7,6 +8,9 @@ use crate::config::Chain;
 use crate::utils::address::resolve_label;
 
 /// Simulate using debug_traceCall RPC
+///
+/// With `collect`, the normalized outcome is returned instead of printing
+/// the raw trace.
 #[allow(clippy::too_many_arguments)]
 pub async fn simulate_via_debug_rpc(
     to: &str,
@@ -24,7 +28,8 @@ pub async fn simulate_via_debug_rpc(
     dry_run: Option<DryRunFormat>,
     show_secrets: bool,
     quiet: bool,
-) -> anyhow::Result<()> {
+    collect: bool,
+) -> anyhow::Result<Option<SimulationOutcome>> {
     let rpc = get_debug_rpc_url(rpc_url, chain)
         .ok_or_else(|| anyhow::anyhow!(
             "Debug RPC URL required. Set via --rpc-url, add an endpoint with has_debug: true, or use 'config add-debug-rpc'"
@@ -79,7 +84,7 @@ pub async fn simulate_via_debug_rpc(
         let headers = vec![("Content-Type", "application/json")];
         let output = format_request(&rpc, "POST", &headers, &request, format, show_secrets);
         println!("{}", output);
-        return Ok(());
+        return Ok(None);
     }
 
     if !quiet {
@@ -100,13 +105,18 @@ pub async fn simulate_via_debug_rpc(
         return Err(anyhow::anyhow!("RPC error: {}", error));
     }
 
+    if collect {
+        let trace = result.get("result").unwrap_or(&serde_json::Value::Null);
+        return Ok(Some(SimulationOutcome::from_call_trace(trace)));
+    }
+
     if let Some(trace) = result.get("result") {
         println!("{}", serde_json::to_string_pretty(trace)?);
     } else {
         println!("{}", serde_json::to_string_pretty(&result)?);
     }
 
-    Ok(())
+    Ok(None)
 }
 
 /// Trace existing tx via debug_traceTransaction
@@ -164,6 +174,9 @@ pub async fn trace_tx_via_debug_rpc(
 }
 
 /// Simulate using trace_call RPC (Parity/Erigon style)
+///
+/// With `collect`, `stateDiff` is also requested and the normalized outcome
+/// is returned instead of printing the raw trace.
 #[allow(clippy::too_many_arguments)]
 pub async fn simulate_via_trace_rpc(
     to: &str,
@@ -181,7 +194,8 @@ pub async fn simulate_via_trace_rpc(
     dry_run: Option<DryRunFormat>,
     show_secrets: bool,
     quiet: bool,
-) -> anyhow::Result<()> {
+    collect: bool,
+) -> anyhow::Result<Option<SimulationOutcome>> {
     let rpc = get_trace_rpc_url(rpc_url, chain).ok_or_else(|| {
         anyhow::anyhow!(
             "Trace RPC URL required. Set via --rpc-url or add an endpoint with has_trace: true"
@@ -216,6 +230,10 @@ pub async fn simulate_via_trace_rpc(
         block_param
     ]);
 
+    if collect {
+        params[1] = serde_json::json!(["trace", "vmTrace", "stateDiff"]);
+    }
+
     // Add state overrides as 4th parameter if any are provided
     if !state_overrides.is_empty() {
         params
@@ -236,7 +254,7 @@ pub async fn simulate_via_trace_rpc(
         let headers = vec![("Content-Type", "application/json")];
         let output = format_request(&rpc, "POST", &headers, &request, format, show_secrets);
         println!("{}", output);
-        return Ok(());
+        return Ok(None);
     }
 
     if !quiet {
@@ -257,13 +275,18 @@ pub async fn simulate_via_trace_rpc(
         return Err(anyhow::anyhow!("RPC error: {}", error));
     }
 
+    if collect {
+        let trace = result.get("result").unwrap_or(&serde_json::Value::Null);
+        return Ok(Some(SimulationOutcome::from_parity_trace(trace)));
+    }
+
     if let Some(trace) = result.get("result") {
         println!("{}", serde_json::to_string_pretty(trace)?);
     } else {
         println!("{}", serde_json::to_string_pretty(&result)?);
     }
 
-    Ok(())
+    Ok(None)
 }
 
 /// Trace existing tx via trace_transaction (Parity/Erigon style)
@@ -1,13 +1,19 @@
+use super::outcome::{SimulationDiff, SimulationOutcome};
+use super::replay::PortableBundle;
 use super::types::{DryRunFormat, SimulationType, TenderlyArgs};
 use super::utils::{
     build_calldata, create_tenderly_client, format_request, get_tenderly_credentials, value_to_hex,
 };
+use crate::cli::OutputFormat;
 use crate::utils::address::resolve_label;
 use tndrly::simulation::{
     AccessListEntry, BlockHeaderOverride, BundleSimulationRequest, SimulationRequest, StateOverride,
 };
 
 /// Simulate using Tenderly API
+///
+/// With `collect`, the normalized outcome is returned instead of printing
+/// the raw response.
 #[allow(clippy::too_many_arguments)]
 pub async fn simulate_via_tenderly(
     to: &str,
@@ -43,7 +49,8 @@ pub async fn simulate_via_tenderly(
     block_coinbase: &Option<String>,
     block_difficulty: Option<u64>,
     block_base_fee: Option<u64>,
-) -> anyhow::Result<()> {
+    collect: bool,
+) -> anyhow::Result<Option<SimulationOutcome>> {
     // Resolve target address
     let resolved_to = resolve_label(to);
     let calldata = build_calldata(sig, data, args)?;
@@ -215,7 +222,7 @@ pub async fn simulate_via_tenderly(
         ];
         let output = format_request(&url, "POST", &headers, &json_request, format, show_secrets);
         println!("{}", output);
-        return Ok(());
+        return Ok(None);
     }
 
     let has_state_overrides = !balance_overrides.is_empty()
@@ -264,11 +271,15 @@ pub async fn simulate_via_tenderly(
         }
     }
 
+    if collect {
+        return Ok(Some(SimulationOutcome::from_tenderly(&result)));
+    }
+
     // Pretty print the result
     let json_result = serde_json::to_value(&result)?;
     println!("{}", serde_json::to_string_pretty(&json_result)?);
 
-    Ok(())
+    Ok(None)
 }
 
 /// Trace existing tx via Tenderly
@@ -302,6 +313,7 @@ pub async fn simulate_bundle_tenderly(
     storage_overrides: &[String],
     code_overrides: &[String],
     save: bool,
+    export: &Option<String>,
     tenderly_args: &TenderlyArgs,
     quiet: bool,
 ) -> anyhow::Result<()> {
@@ -436,6 +448,14 @@ pub async fn simulate_bundle_tenderly(
 
     println!("{}", serde_json::to_string_pretty(&result)?);
 
+    if let Some(path) = export {
+        PortableBundle::from_tenderly(&result.simulation_results)?
+            .save(std::path::Path::new(path))?;
+        if !quiet {
+            eprintln!("Exported bundle to {}", path);
+        }
+    }
+
     Ok(())
 }
 
@@ -509,3 +529,38 @@ pub async fn unshare_simulation_tenderly(
     println!("Simulation {} is now private.", id);
     Ok(())
 }
+
+/// Compare two saved Tenderly simulations
+pub async fn diff_simulations_tenderly(
+    first_id: &str,
+    second_id: &str,
+    format: OutputFormat,
+    tenderly_args: &TenderlyArgs,
+    quiet: bool,
+) -> anyhow::Result<()> {
+    if !quiet {
+        eprintln!(
+            "Fetching Tenderly simulations {} and {}...",
+            first_id, second_id
+        );
+    }
+    let client = create_tenderly_client(tenderly_args)?;
+    let simulations = client.simulation();
+    let (first, second) = tokio::try_join!(
+        simulations.get_full(first_id),
+        simulations.get_full(second_id)
+    )
+    .map_err(|e| anyhow::anyhow!("Tenderly API error: {}", e))?;
+
+    let diff = SimulationDiff::between(
+        &SimulationOutcome::from_tenderly(&first),
+        &SimulationOutcome::from_tenderly(&second),
+    );
+    match format {
+        OutputFormat::Json | OutputFormat::Ndjson => {
+            println!("{}", serde_json::to_string_pretty(&diff)?)
+        }
+        OutputFormat::Table => println!("{}", diff),
+    }
+    Ok(())
+}
@@ -1,17 +1,33 @@
 //! Token-related commands
 //!
-//! Get token info, holders, and balances
+//! Get token info, holders, and balances for ERC-20, ERC-721 and ERC-1155
+//! tokens, and a wallet's outstanding ERC-20 approvals
+//!
+//! `holders` produces [`HolderRow`]s and `approvals` [`ApprovalRow`]s, both
+//! written through [`RowWriter`].
 
 use super::OutputFormat;
-use crate::config::{AddressBook, Chain};
+use crate::approvals::{approval_topic, is_unlimited, latest_approvals, Approval};
+use crate::config::{build_default_rpc_config, AddressBook, Chain, Config, ConfigFile, RpcConfig};
 use crate::etherscan::TokenMetadataCache;
+use crate::fetcher::{FetchLogs, LogFetcher};
+use crate::nft::{
+    add_probe_calls, detect_standard, standard_from_probe, transfer_topics, NftHolder, NftHolders,
+    TokenStandard, PROBE_CALLS,
+};
+use crate::output::{parse_output_path, Row, RowFormat, RowWriter};
 use crate::rpc::get_rpc_endpoint;
-use crate::rpc::multicall::{selectors, MulticallBuilder};
+use crate::rpc::multicall::{selectors, MulticallBuilder, MulticallResult};
 use crate::utils::address::resolve_from_book;
 use crate::utils::format::format_token_amount;
-use alloy::primitives::Address;
+use crate::utils::Alignment;
+use alloy::primitives::{Address, U256};
 use alloy::providers::Provider;
 use clap::Subcommand;
+use futures::stream::{self, StreamExt};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::OnceLock;
 
@@ -24,18 +40,28 @@ fn get_token_cache() -> &'static TokenMetadataCache {
 
 #[derive(Subcommand)]
 pub enum TokenCommands {
-    /// Get token info (name, symbol, decimals, supply)
+    /// Get token info (name, symbol, decimals, supply; URIs for NFTs)
     Info {
         /// Token contract address
         #[arg(value_name = "ADDRESS")]
         address: String,
 
+        /// Token standard (detected via ERC-165 by default)
+        #[arg(long, value_enum)]
+        standard: Option<TokenStandard>,
+
         /// Output format (json, table/pretty)
         #[arg(long, short, value_enum, default_value = "table")]
         output: OutputFormat,
     },
 
-    /// Get top token holders (requires Etherscan Pro plan)
+    /// Get top token holders
+    ///
+    /// ERC-721 and ERC-1155 holders are reconstructed from the contract's
+    /// transfer logs. ERC-20 holders require an Etherscan Pro plan.
+    ///
+    /// Columns: rank, address, balance, tokens. JSON output wraps the rows
+    /// in a summary object; NDJSON and CSV write the rows alone.
     Holders {
         /// Token contract address
         #[arg(value_name = "ADDRESS")]
@@ -45,9 +71,29 @@ pub enum TokenCommands {
         #[arg(long, default_value = "100", value_name = "N")]
         limit: u32,
 
-        /// Output format (json, table/pretty)
-        #[arg(long, short, value_enum, default_value = "table")]
-        output: OutputFormat,
+        /// Only holders of this token id
+        #[arg(long, value_name = "ID")]
+        token_id: Option<U256>,
+
+        /// First block to scan (defaults to the contract creation block)
+        #[arg(long, value_name = "BLOCK")]
+        from_block: Option<u64>,
+
+        /// Last block to scan (defaults to latest)
+        #[arg(long, value_name = "BLOCK")]
+        to_block: Option<u64>,
+
+        /// Token standard (detected via ERC-165 by default)
+        #[arg(long, value_enum)]
+        standard: Option<TokenStandard>,
+
+        /// Output format (table, json, ndjson, csv)
+        #[arg(long, short = 'o', value_enum, default_value = "table")]
+        format: RowFormat,
+
+        /// Output file path (stdout if not specified)
+        #[arg(long, short = 'O', value_name = "FILE", value_parser = parse_output_path)]
+        output: Option<PathBuf>,
     },
 
     /// Get token balance for holder(s)
@@ -68,6 +114,66 @@ pub enum TokenCommands {
         #[arg(long)]
         show_zero: bool,
 
+        /// Token id, for ERC-1155 balances and ERC-721 ownership checks
+        #[arg(long, value_name = "ID")]
+        token_id: Option<U256>,
+
+        /// Token standard (detected via ERC-165 by default)
+        #[arg(long, value_enum)]
+        standard: Option<TokenStandard>,
+
+        /// Output format (json, table/pretty)
+        #[arg(long, short, value_enum, default_value = "table")]
+        output: OutputFormat,
+    },
+
+    /// List a wallet's outstanding ERC-20 approvals
+    ///
+    /// Rebuilds approvals from the wallet's `Approval` logs on every
+    /// contract, keeps the latest per token and spender, and reads the live
+    /// `allowance()` for each. Unlimited approvals are listed first. With
+    /// GOPLUS_APP_KEY and GOPLUS_APP_SECRET set, spenders are also checked
+    /// against GoPlus' malicious address data.
+    ///
+    /// Columns: token, symbol, spender, allowance, unlimited, block, risk.
+    /// JSON output wraps the rows in a summary object; NDJSON and CSV write
+    /// the rows alone.
+    Approvals {
+        /// Wallet address or label
+        #[arg(value_name = "WALLET")]
+        wallet: String,
+
+        /// First block to scan (defaults to genesis; set it to speed up the scan)
+        #[arg(long, value_name = "BLOCK")]
+        from_block: Option<u64>,
+
+        /// Last block to scan (defaults to latest)
+        #[arg(long, value_name = "BLOCK")]
+        to_block: Option<u64>,
+
+        /// Skip the GoPlus spender check even when credentials are set
+        #[arg(long)]
+        no_risk: bool,
+
+        /// Output format (table, json, ndjson, csv)
+        #[arg(long, short = 'o', value_enum, default_value = "table")]
+        format: RowFormat,
+
+        /// Output file path (stdout if not specified)
+        #[arg(long, short = 'O', value_name = "FILE", value_parser = parse_output_path)]
+        output: Option<PathBuf>,
+    },
+
+    /// Get the owner of an ERC-721 token
+    Owner {
+        /// Token contract address
+        #[arg(value_name = "TOKEN")]
+        token: String,
+
+        /// Token id
+        #[arg(value_name = "ID")]
+        token_id: U256,
+
         /// Output format (json, table/pretty)
         #[arg(long, short, value_enum, default_value = "table")]
         output: OutputFormat,
@@ -77,11 +183,15 @@ pub enum TokenCommands {
 pub async fn handle(
     action: &TokenCommands,
     chain: Chain,
-    _api_key: Option<String>,
+    api_key: Option<String>,
     quiet: bool,
 ) -> anyhow::Result<()> {
     match action {
-        TokenCommands::Info { address, output } => {
+        TokenCommands::Info {
+            address,
+            standard,
+            output,
+        } => {
             let (token_addr, label) = resolve_from_book(address)?;
             let addr_str = format!("{:#x}", token_addr);
             let display = label.as_ref().unwrap_or(&addr_str);
@@ -89,8 +199,12 @@ pub async fn handle(
             let cache = get_token_cache();
             let chain_name = chain.name();
 
-            // Check cache first
-            if let Some(cached) = cache.get(chain_name, &addr_str) {
+            // Check cache first (only ERC-20 metadata is cached)
+            let cached = cache
+                .get(chain_name, &addr_str)
+                .filter(|c| c.decimals.is_some())
+                .filter(|_| standard.is_none_or(|s| s == TokenStandard::Erc20));
+            if let Some(cached) = cached {
                 if !quiet {
                     eprintln!("Using cached token info for {}...", display);
                 }
@@ -107,6 +221,7 @@ pub async fn handle(
                         serde_json::json!({
                             "address": addr_str,
                             "label": label,
+                            "standard": TokenStandard::Erc20.as_str(),
                             "name": cached.name,
                             "symbol": cached.symbol,
                             "decimals": cached.decimals,
@@ -122,6 +237,7 @@ pub async fn handle(
                         println!("Label:    {}", lbl);
                     }
                     println!("Address:  {}", addr_str);
+                    println!("Standard: {}", TokenStandard::Erc20);
                     println!(
                         "Name:     {}",
                         cached.name.as_deref().unwrap_or("(unknown)")
@@ -154,20 +270,41 @@ pub async fn handle(
             let endpoint = get_rpc_endpoint(chain)?;
             let provider = endpoint.provider();
 
+            // Standard detection and NFT metadata ride along in the same batch
             let multicall = MulticallBuilder::new()
                 .add_call_allow_failure(token_addr, selectors::name())
                 .add_call_allow_failure(token_addr, selectors::symbol())
                 .add_call_allow_failure(token_addr, selectors::decimals())
-                .add_call_allow_failure(token_addr, selectors::total_supply());
+                .add_call_allow_failure(token_addr, selectors::total_supply())
+                .add_call_allow_failure(token_addr, selectors::base_uri())
+                .add_call_allow_failure(token_addr, selectors::contract_uri())
+                .add_call_allow_failure(token_addr, selectors::uri(U256::ZERO));
+            let multicall = add_probe_calls(multicall, token_addr);
 
             // Execute with retry (up to 3 retries with exponential backoff)
             let results = multicall.execute_with_retry(provider, 3).await?;
 
+            let standard = standard.unwrap_or_else(|| probe_standard(&results, 7));
             let name = results.first().and_then(|r| r.decode_string());
             let symbol = results.get(1).and_then(|r| r.decode_string());
-            let decimals = results.get(2).and_then(|r| r.decode_uint8());
             let total_supply = results.get(3).and_then(|r| r.decode_uint256());
 
+            if standard.is_nft() {
+                let info = NftInfo {
+                    standard,
+                    name,
+                    symbol,
+                    total_supply,
+                    base_uri: results.get(4).and_then(|r| r.decode_string()),
+                    contract_uri: results.get(5).and_then(|r| r.decode_string()),
+                    uri: results.get(6).and_then(|r| r.decode_string()),
+                };
+                print_nft_info(&info, &addr_str, label.as_deref(), chain, output);
+                return Ok(());
+            }
+
+            let decimals = results.get(2).and_then(|r| r.decode_uint8());
+
             // Cache the result (token metadata is immutable)
             cache.set(
                 chain_name,
@@ -190,6 +327,7 @@ pub async fn handle(
                     serde_json::json!({
                         "address": addr_str,
                         "label": label,
+                        "standard": standard.as_str(),
                         "name": name,
                         "symbol": symbol,
                         "decimals": decimals,
@@ -204,6 +342,7 @@ pub async fn handle(
                     println!("Label:    {}", lbl);
                 }
                 println!("Address:  {}", addr_str);
+                println!("Standard: {}", standard);
                 println!("Name:     {}", name.as_deref().unwrap_or("(unknown)"));
                 println!("Symbol:   {}", symbol.as_deref().unwrap_or("(unknown)"));
                 println!(
@@ -220,12 +359,220 @@ pub async fn handle(
             }
         }
 
-        TokenCommands::Holders { .. } => {
-            return Err(anyhow::anyhow!(
-                "Token holders endpoint requires an Etherscan Pro plan.\n\
-                 See: https://docs.etherscan.io/resources/pro-endpoints\n\
-                 Alternative: View holders on Etherscan website directly."
-            ));
+        TokenCommands::Holders {
+            address,
+            limit,
+            token_id,
+            from_block,
+            to_block,
+            standard,
+            format,
+            output,
+        } => {
+            let (token_addr, label) = resolve_from_book(address)?;
+            let addr_str = format!("{:#x}", token_addr);
+            let display = label.as_ref().unwrap_or(&addr_str);
+
+            let standard = match standard {
+                Some(s) => *s,
+                None => {
+                    let endpoint = get_rpc_endpoint(chain)?;
+                    detect_standard(endpoint.provider(), token_addr).await?
+                }
+            };
+
+            if !standard.is_nft() {
+                return Err(anyhow::anyhow!(
+                    "Token holders endpoint requires an Etherscan Pro plan.\n\
+                     See: https://docs.etherscan.io/resources/pro-endpoints\n\
+                     Alternative: View holders on Etherscan website directly."
+                ));
+            }
+
+            if !quiet {
+                eprintln!(
+                    "Reconstructing {} holders of {} from transfer logs...",
+                    standard, display
+                );
+            }
+
+            let nft_holders =
+                fetch_nft_holders(chain, token_addr, *from_block, *to_block, api_key, quiet)
+                    .await?;
+            let mut holders = match token_id {
+                Some(id) => nft_holders.holders_of(*id),
+                None => nft_holders.holders(),
+            };
+            let holder_count = holders.len();
+            holders.truncate(*limit as usize);
+
+            let rows: Vec<HolderRow> = holders
+                .iter()
+                .enumerate()
+                .map(|(i, h)| HolderRow::new(i + 1, h, standard))
+                .collect();
+
+            match format {
+                RowFormat::Json => {
+                    let json = serde_json::to_string_pretty(&serde_json::json!({
+                        "token": addr_str,
+                        "label": label,
+                        "standard": standard.as_str(),
+                        "tokenId": token_id.map(|id| id.to_string()),
+                        "transfers": nft_holders.transfer_count(),
+                        "holderCount": holder_count,
+                        "holders": rows,
+                    }))?;
+                    match output {
+                        Some(path) => std::fs::write(path, json + "\n")?,
+                        None => println!("{}", json),
+                    }
+                }
+                RowFormat::Table if rows.is_empty() && output.is_none() => {
+                    println!("No holders found");
+                }
+                _ => {
+                    let mut writer = RowWriter::<HolderRow>::new(*format, output.as_deref())?;
+                    writer.write_rows(&rows)?;
+                    writer.finalize()?;
+                }
+            }
+
+            if let Some(path) = output {
+                if !quiet {
+                    eprintln!("Wrote {} holders to {}", rows.len(), path.display());
+                }
+            } else if *format == RowFormat::Table && holder_count > rows.len() {
+                println!("\nShowing {} of {} holders", rows.len(), holder_count);
+            }
+        }
+
+        TokenCommands::Approvals {
+            wallet,
+            from_block,
+            to_block,
+            no_risk,
+            format,
+            output,
+        } => {
+            let (owner, label) = resolve_from_book(wallet)?;
+            let owner_str = format!("{:#x}", owner);
+            let display = label.as_ref().unwrap_or(&owner_str);
+
+            if !quiet {
+                eprintln!("Scanning Approval logs for {}...", display);
+            }
+            let approvals = fetch_approvals(chain, owner, *from_block, *to_block, quiet).await?;
+
+            let endpoint = get_rpc_endpoint(chain)?;
+            let mut rows = approval_rows(endpoint.provider(), owner, &approvals).await?;
+
+            if !*no_risk {
+                let client = gplus::Client::from_env()?;
+                if client.is_authenticated() {
+                    if !quiet {
+                        eprintln!("Checking spenders with GoPlus...");
+                    }
+                    let risks = spender_risks(&client, chain, &rows, quiet).await;
+                    for row in &mut rows {
+                        row.risk = risks.get(&row.spender).cloned();
+                    }
+                }
+            }
+
+            let unlimited = rows.iter().filter(|r| r.unlimited).count();
+            match format {
+                RowFormat::Json => {
+                    let json = serde_json::to_string_pretty(&serde_json::json!({
+                        "owner": owner_str,
+                        "label": label,
+                        "chain": chain.name(),
+                        "approvalCount": rows.len(),
+                        "unlimitedCount": unlimited,
+                        "approvals": rows,
+                    }))?;
+                    match output {
+                        Some(path) => std::fs::write(path, json + "\n")?,
+                        None => println!("{}", json),
+                    }
+                }
+                RowFormat::Table if rows.is_empty() && output.is_none() => {
+                    println!("No outstanding approvals");
+                }
+                _ => {
+                    let mut writer = RowWriter::<ApprovalRow>::new(*format, output.as_deref())?;
+                    writer.write_rows(&rows)?;
+                    writer.finalize()?;
+                }
+            }
+
+            if let Some(path) = output {
+                if !quiet {
+                    eprintln!("Wrote {} approvals to {}", rows.len(), path.display());
+                }
+            } else if *format == RowFormat::Table && !rows.is_empty() {
+                println!(
+                    "\n{} outstanding approval(s), {} unlimited",
+                    rows.len(),
+                    unlimited
+                );
+            }
+        }
+
+        TokenCommands::Owner {
+            token,
+            token_id,
+            output,
+        } => {
+            let (token_addr, token_label) = resolve_from_book(token)?;
+            let token_str = format!("{:#x}", token_addr);
+            let token_display = token_label.as_ref().unwrap_or(&token_str);
+
+            let endpoint = get_rpc_endpoint(chain)?;
+            let multicall = MulticallBuilder::new()
+                .add_call_allow_failure(token_addr, selectors::owner_of(*token_id));
+            let results = add_probe_calls(multicall, token_addr)
+                .execute_with_retry(endpoint.provider(), 3)
+                .await?;
+
+            let owner = match results.first().and_then(|r| r.decode_address()) {
+                Some(owner) => owner,
+                None => {
+                    return Err(match probe_standard(&results, 1) {
+                        TokenStandard::Erc1155 => anyhow::anyhow!(
+                            "{} is an ERC-1155 token, which has no single owner per id.\n\
+                             Use: ethcli token holders {} --token-id {}",
+                            token_display,
+                            token,
+                            token_id
+                        ),
+                        TokenStandard::Erc721 => anyhow::anyhow!(
+                            "ownerOf({}) reverted: the token does not exist or was burned",
+                            token_id
+                        ),
+                        other => anyhow::anyhow!(
+                            "{} does not look like an ERC-721 token (detected: {})",
+                            token_display,
+                            other
+                        ),
+                    });
+                }
+            };
+            let owner_str = format!("{:#x}", owner);
+
+            if output.is_json() {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "token": token_str,
+                        "tokenLabel": token_label,
+                        "tokenId": token_id.to_string(),
+                        "owner": owner_str,
+                    }))?
+                );
+            } else {
+                println!("{}", owner_str);
+            }
         }
 
         TokenCommands::Balance {
@@ -233,6 +580,8 @@ pub async fn handle(
             holder,
             tag,
             show_zero,
+            token_id,
+            standard,
             output,
         } => {
             // Build list of holders from --holder args and --tag
@@ -282,6 +631,10 @@ pub async fn handle(
                     println!();
                 }
 
+                if is_eth && token_id.is_some() {
+                    return Err(anyhow::anyhow!("--token-id does not apply to native ETH"));
+                }
+
                 if is_eth {
                     // Native ETH balance
                     let symbol = "ETH";
@@ -322,32 +675,41 @@ pub async fn handle(
                         }
                     }
                 } else {
-                    // ERC20 token
+                    // ERC20, ERC721 or ERC1155 token
                     let (token_addr, token_label) = resolve_from_book(token)?;
                     let token_str = format!("{:#x}", token_addr);
                     let token_display = token_label.as_ref().unwrap_or(&token_str);
 
-                    // Get token decimals and symbol
+                    // Get token decimals, symbol and standard
                     let meta_multicall = MulticallBuilder::new()
                         .add_call_allow_failure(token_addr, selectors::decimals())
                         .add_call_allow_failure(token_addr, selectors::symbol());
-                    let meta_results = meta_multicall.execute_with_retry(&provider, 3).await?;
-                    let decimals = meta_results
-                        .first()
-                        .and_then(|r| r.decode_uint8())
-                        .unwrap_or(18);
+                    let meta_results = add_probe_calls(meta_multicall, token_addr)
+                        .execute_with_retry(&provider, 3)
+                        .await?;
+                    let token_standard =
+                        standard.unwrap_or_else(|| probe_standard(&meta_results, 2));
+                    let decimals = if token_standard.is_nft() {
+                        0
+                    } else {
+                        meta_results
+                            .first()
+                            .and_then(|r| r.decode_uint8())
+                            .unwrap_or(18)
+                    };
                     let symbol = meta_results
                         .get(1)
                         .and_then(|r| r.decode_string())
                         .unwrap_or_else(|| "???".to_string());
 
+                    let balance_calls =
+                        balance_calls(token_standard, *token_id, &holders, token_display)?;
+
                     // Build multicall for all holder balances at once
                     let mut balance_multicall = MulticallBuilder::new();
-                    for (holder_addr, _) in &holders {
-                        balance_multicall = balance_multicall.add_call_allow_failure(
-                            token_addr,
-                            selectors::balance_of(*holder_addr),
-                        );
+                    for calldata in &balance_calls.calldata {
+                        balance_multicall =
+                            balance_multicall.add_call_allow_failure(token_addr, calldata.clone());
                     }
 
                     if !quiet {
@@ -370,10 +732,7 @@ pub async fn handle(
                         let holder_str = format!("{:#x}", holder_addr);
                         let holder_display = holder_label.as_ref().unwrap_or(&holder_str);
 
-                        let balance = balance_results
-                            .get(i)
-                            .and_then(|r| r.decode_uint256())
-                            .unwrap_or_default();
+                        let balance = balance_calls.decode(&balance_results, i, *holder_addr);
 
                         let formatted = format_token_amount(&balance.to_string(), decimals);
 
@@ -386,7 +745,9 @@ pub async fn handle(
                                 "balance": balance.to_string(),
                                 "balanceFormatted": formatted,
                                 "decimals": decimals,
-                                "symbol": symbol
+                                "symbol": symbol,
+                                "standard": token_standard.as_str(),
+                                "tokenId": token_id.map(|id| id.to_string())
                             }));
                         } else {
                             let should_show = *show_zero || !balance.is_zero() || !multiple_holders;
@@ -406,3 +767,571 @@ pub async fn handle(
 
     Ok(())
 }
+
+/// Standard from the probe results appended at `offset` by [`add_probe_calls`]
+fn probe_standard(results: &[MulticallResult], offset: usize) -> TokenStandard {
+    standard_from_probe(
+        results
+            .get(offset..offset + PROBE_CALLS)
+            .unwrap_or_default(),
+    )
+}
+
+/// Per-holder balance calls for one token
+struct BalanceCalls {
+    /// Calldata to batch, in holder order (a single `ownerOf` for ERC-721 ids)
+    calldata: Vec<alloy::primitives::Bytes>,
+    /// Whether the single call is `ownerOf(id)` rather than one call per holder
+    owner_of: bool,
+}
+
+impl BalanceCalls {
+    /// Balance of the `index`-th holder from the batch results
+    fn decode(&self, results: &[MulticallResult], index: usize, holder: Address) -> U256 {
+        if self.owner_of {
+            let owns = results
+                .first()
+                .and_then(|r| r.decode_address())
+                .is_some_and(|owner| owner == holder);
+            U256::from(u8::from(owns))
+        } else {
+            results
+                .get(index)
+                .and_then(|r| r.decode_uint256())
+                .unwrap_or_default()
+        }
+    }
+}
+
+/// Choose `balanceOf(address)`, `balanceOf(address,id)` or `ownerOf(id)`
+fn balance_calls(
+    standard: TokenStandard,
+    token_id: Option<U256>,
+    holders: &[(Address, Option<String>)],
+    token_display: &str,
+) -> anyhow::Result<BalanceCalls> {
+    let per_holder = |call: &dyn Fn(Address) -> alloy::primitives::Bytes| BalanceCalls {
+        calldata: holders.iter().map(|(addr, _)| call(*addr)).collect(),
+        owner_of: false,
+    };
+
+    match (standard, token_id) {
+        (TokenStandard::Erc1155, Some(id)) => {
+            Ok(per_holder(&|addr| selectors::balance_of_id(addr, id)))
+        }
+        (TokenStandard::Erc1155, None) => Err(anyhow::anyhow!(
+            "{} is an ERC-1155 token; balances are per token id, pass --token-id",
+            token_display
+        )),
+        (TokenStandard::Erc721, Some(id)) => Ok(BalanceCalls {
+            calldata: vec![selectors::owner_of(id)],
+            owner_of: true,
+        }),
+        (TokenStandard::Erc20 | TokenStandard::Unknown, Some(_)) => Err(anyhow::anyhow!(
+            "--token-id only applies to ERC-721 and ERC-1155 tokens ({} detected as {}); \
+             pass --standard to override detection",
+            token_display,
+            standard
+        )),
+        // ERC-20 amount or ERC-721 token count
+        (_, None) => Ok(per_holder(&selectors::balance_of)),
+    }
+}
+
+/// Metadata shown by `token info` for ERC-721 and ERC-1155 contracts
+struct NftInfo {
+    standard: TokenStandard,
+    name: Option<String>,
+    symbol: Option<String>,
+    total_supply: Option<U256>,
+    base_uri: Option<String>,
+    contract_uri: Option<String>,
+    /// ERC-1155 `uri(0)`, usually the `{id}` template shared by all ids
+    uri: Option<String>,
+}
+
+fn print_nft_info(
+    info: &NftInfo,
+    addr_str: &str,
+    label: Option<&str>,
+    chain: Chain,
+    output: &OutputFormat,
+) {
+    let uri = info
+        .uri
+        .as_ref()
+        .filter(|_| info.standard == TokenStandard::Erc1155);
+
+    if output.is_json() {
+        println!(
+            "{}",
+            serde_json::json!({
+                "address": addr_str,
+                "label": label,
+                "standard": info.standard.as_str(),
+                "name": info.name,
+                "symbol": info.symbol,
+                "totalSupply": info.total_supply.map(|s| s.to_string()),
+                "baseUri": info.base_uri,
+                "contractUri": info.contract_uri,
+                "uri": uri,
+            })
+        );
+        return;
+    }
+
+    let unknown = || "(unknown)".to_string();
+    println!("Token Info");
+    println!("{}", "─".repeat(40));
+    if let Some(lbl) = label {
+        println!("Label:        {}", lbl);
+    }
+    println!("Address:      {}", addr_str);
+    println!("Standard:     {}", info.standard);
+    println!(
+        "Name:         {}",
+        info.name.clone().unwrap_or_else(unknown)
+    );
+    println!(
+        "Symbol:       {}",
+        info.symbol.clone().unwrap_or_else(unknown)
+    );
+    println!(
+        "Supply:       {}",
+        info.total_supply
+            .map(|s| s.to_string())
+            .unwrap_or_else(unknown)
+    );
+    if let Some(base_uri) = &info.base_uri {
+        println!("Base URI:     {}", base_uri);
+    }
+    if let Some(uri) = uri {
+        println!("URI:          {}", uri);
+    }
+    if let Some(contract_uri) = &info.contract_uri {
+        println!("Contract URI: {}", contract_uri);
+    }
+
+    if let Some(explorer) = chain.explorer_url() {
+        println!("\nExplorer: {}/token/{}", explorer, addr_str);
+    }
+}
+
+/// Token ids shown per holder in table output
+const MAX_TABLE_TOKEN_IDS: usize = 5;
+
+/// One holder from `token holders`
+///
+/// Field names are the output columns (JSON keys and CSV header), in order.
+/// In CSV, `tokens` is a space-separated list of `tokenId:balance` pairs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HolderRow {
+    /// 1-based position by descending balance
+    pub rank: usize,
+    pub address: String,
+    /// Total balance across `tokens`
+    pub balance: String,
+    /// Token ids held, by ascending id
+    pub tokens: Vec<HolderToken>,
+    /// Standard of the token, for the table view
+    #[serde(skip)]
+    standard: TokenStandard,
+}
+
+/// A token id held by a [`HolderRow`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HolderToken {
+    pub token_id: String,
+    pub balance: String,
+}
+
+impl HolderRow {
+    /// Row for `holder` at 1-based position `rank`
+    pub fn new(rank: usize, holder: &NftHolder, standard: TokenStandard) -> Self {
+        Self {
+            rank,
+            address: format!("{:#x}", holder.address),
+            balance: holder.total.to_string(),
+            tokens: holder
+                .tokens
+                .iter()
+                .map(|(id, balance)| HolderToken {
+                    token_id: id.to_string(),
+                    balance: balance.to_string(),
+                })
+                .collect(),
+            standard,
+        }
+    }
+}
+
+impl Row for HolderRow {
+    const COLUMNS: &'static [&'static str] = &["rank", "address", "balance", "tokens"];
+
+    fn cells(&self) -> Vec<String> {
+        let tokens: Vec<String> = self
+            .tokens
+            .iter()
+            .map(|t| format!("{}:{}", t.token_id, t.balance))
+            .collect();
+        vec![
+            self.rank.to_string(),
+            self.address.clone(),
+            self.balance.clone(),
+            tokens.join(" "),
+        ]
+    }
+
+    fn table_columns() -> &'static [&'static str] {
+        &["#", "Holder", "Balance", "Token IDs"]
+    }
+
+    fn table_alignments() -> &'static [Alignment] {
+        &[
+            Alignment::Right,
+            Alignment::Left,
+            Alignment::Right,
+            Alignment::Left,
+        ]
+    }
+
+    fn table_cells(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self
+            .tokens
+            .iter()
+            .take(MAX_TABLE_TOKEN_IDS)
+            .map(|t| match self.standard {
+                TokenStandard::Erc1155 => format!("{}:{}", t.token_id, t.balance),
+                _ => t.token_id.clone(),
+            })
+            .collect();
+        if self.tokens.len() > MAX_TABLE_TOKEN_IDS {
+            ids.push(format!("+{} more", self.tokens.len() - MAX_TABLE_TOKEN_IDS));
+        }
+        vec![
+            self.rank.to_string(),
+            self.address.clone(),
+            self.balance.clone(),
+            ids.join(", "),
+        ]
+    }
+}
+
+/// Log fetcher RPC settings from the config file
+fn rpc_config_from_file() -> anyhow::Result<RpcConfig> {
+    let config_file =
+        ConfigFile::load_default().map_err(|e| anyhow::anyhow!("Failed to load config: {}", e))?;
+    Ok(build_default_rpc_config(&config_file))
+}
+
+/// Rebuild NFT balances from the token's transfer logs
+///
+/// Uses the RPC endpoints from the config file. Without `from_block` the
+/// scan starts at the contract creation block (looked up on Etherscan).
+async fn fetch_nft_holders(
+    chain: Chain,
+    token: Address,
+    from_block: Option<u64>,
+    to_block: Option<u64>,
+    api_key: Option<String>,
+    quiet: bool,
+) -> anyhow::Result<NftHolders> {
+    let rpc_config = rpc_config_from_file()?;
+
+    let topics = transfer_topics()
+        .iter()
+        .map(|topic| format!("{:#x}", topic))
+        .collect();
+    let mut builder = Config::builder()
+        .chain(chain)
+        .contract(format!("{:#x}", token))
+        .events(topics)
+        .from_block(from_block.unwrap_or(0))
+        .auto_from_block(from_block.is_none())
+        .raw(true)
+        .quiet(quiet)
+        .rpc_config(rpc_config);
+    builder = match to_block {
+        Some(block) => builder.to_block_number(block),
+        None => builder.to_latest(),
+    };
+    if let Some(key) = api_key {
+        builder = builder.etherscan_key(key);
+    }
+
+    let result = LogFetcher::new(builder.build()?).await?.fetch_all().await?;
+    if !result.is_complete() {
+        return Err(anyhow::anyhow!(
+            "{} block range(s) failed to fetch; holder balances would be incomplete",
+            result.failed_ranges().len()
+        ));
+    }
+
+    match &result.logs {
+        FetchLogs::Raw(logs) => Ok(NftHolders::from_logs(logs)),
+        FetchLogs::Decoded(_) => Err(anyhow::anyhow!("Expected raw transfer logs")),
+    }
+}
+
+/// Latest approval per token and spender from `owner`'s `Approval` logs
+///
+/// Scans every contract with the RPC endpoints from the config file,
+/// filtering on the owner topic so only the wallet's own logs come back.
+async fn fetch_approvals(
+    chain: Chain,
+    owner: Address,
+    from_block: Option<u64>,
+    to_block: Option<u64>,
+    quiet: bool,
+) -> anyhow::Result<Vec<Approval>> {
+    let mut builder = Config::builder()
+        .chain(chain)
+        .any_contract()
+        .event(format!("{:#x}", approval_topic()))
+        .indexed_topic(1, owner.into_word())
+        .from_block(from_block.unwrap_or(0))
+        .raw(true)
+        .quiet(quiet)
+        .rpc_config(rpc_config_from_file()?);
+    builder = match to_block {
+        Some(block) => builder.to_block_number(block),
+        None => builder.to_latest(),
+    };
+
+    let result = LogFetcher::new(builder.build()?).await?.fetch_all().await?;
+    if !result.is_complete() {
+        return Err(anyhow::anyhow!(
+            "{} block range(s) failed to fetch; the approval list would be incomplete",
+            result.failed_ranges().len()
+        ));
+    }
+
+    match &result.logs {
+        FetchLogs::Raw(logs) => Ok(latest_approvals(owner, logs)),
+        FetchLogs::Decoded(_) => Err(anyhow::anyhow!("Expected raw approval logs")),
+    }
+}
+
+/// Calls per multicall in [`approval_rows`], to stay under RPC call limits
+const APPROVAL_MULTICALL_BATCH: usize = 200;
+
+/// Rows for `approvals` with live allowances, unlimited approvals first
+///
+/// Approvals whose live allowance is zero (spent or revoked without an
+/// event) are dropped. Where `allowance()` reverts, the event amount stands.
+async fn approval_rows<P: Provider>(
+    provider: &P,
+    owner: Address,
+    approvals: &[Approval],
+) -> anyhow::Result<Vec<ApprovalRow>> {
+    let tokens: Vec<Address> = approvals
+        .iter()
+        .map(|a| a.token)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    let calls: Vec<(Address, alloy::primitives::Bytes)> = approvals
+        .iter()
+        .map(|approval| {
+            (
+                approval.token,
+                selectors::allowance(owner, approval.spender),
+            )
+        })
+        .chain(tokens.iter().flat_map(|token| {
+            [
+                (*token, selectors::symbol()),
+                (*token, selectors::decimals()),
+                (*token, selectors::total_supply()),
+            ]
+        }))
+        .collect();
+    let mut results = Vec::with_capacity(calls.len());
+    for batch in calls.chunks(APPROVAL_MULTICALL_BATCH) {
+        let multicall =
+            batch
+                .iter()
+                .fold(MulticallBuilder::new(), |multicall, (target, calldata)| {
+                    multicall.add_call_allow_failure(*target, calldata.clone())
+                });
+        results.extend(multicall.execute_with_retry(provider, 3).await?);
+    }
+
+    let metadata: BTreeMap<Address, TokenMeta> = tokens
+        .iter()
+        .enumerate()
+        .map(|(i, token)| {
+            let base = approvals.len() + i * 3;
+            let meta = TokenMeta {
+                symbol: results.get(base).and_then(|r| r.decode_string()),
+                decimals: results.get(base + 1).and_then(|r| r.decode_uint8()),
+                total_supply: results.get(base + 2).and_then(|r| r.decode_uint256()),
+            };
+            (*token, meta)
+        })
+        .collect();
+
+    let mut rows: Vec<ApprovalRow> = approvals
+        .iter()
+        .enumerate()
+        .filter_map(|(i, approval)| {
+            let amount = results
+                .get(i)
+                .and_then(|r| r.decode_uint256())
+                .unwrap_or(approval.amount);
+            if amount.is_zero() {
+                return None;
+            }
+            let meta = metadata.get(&approval.token).cloned().unwrap_or_default();
+            Some(ApprovalRow::new(approval, amount, meta))
+        })
+        .collect();
+    rows.sort_by(|a, b| {
+        b.unlimited
+            .cmp(&a.unlimited)
+            .then_with(|| a.token.cmp(&b.token))
+            .then_with(|| a.spender.cmp(&b.spender))
+    });
+    Ok(rows)
+}
+
+/// GoPlus issues per spender; spenders that fail to look up are left out
+async fn spender_risks(
+    client: &gplus::Client,
+    chain: Chain,
+    rows: &[ApprovalRow],
+    quiet: bool,
+) -> BTreeMap<String, Vec<String>> {
+    let spenders: BTreeSet<&str> = rows.iter().map(|r| r.spender.as_str()).collect();
+    stream::iter(spenders)
+        .map(|spender| async move {
+            match client.address_security(chain.chain_id(), spender).await {
+                Ok(security) => Some((spender.to_string(), security.get_issues())),
+                Err(e) => {
+                    if !quiet {
+                        eprintln!("GoPlus check failed for {}: {}", spender, e);
+                    }
+                    None
+                }
+            }
+        })
+        .buffer_unordered(4)
+        .filter_map(|risk| async move { risk })
+        .collect()
+        .await
+}
+
+/// Token details read alongside allowances for `token approvals`
+#[derive(Debug, Clone, Default)]
+pub struct TokenMeta {
+    pub symbol: Option<String>,
+    pub decimals: Option<u8>,
+    /// Used to tell large finite approvals from unlimited ones
+    pub total_supply: Option<U256>,
+}
+
+/// One outstanding approval from `token approvals`
+///
+/// Field names are the output columns (JSON keys and CSV header), in order.
+/// In CSV, `risk` is a `; `-separated list, empty when clean or unchecked.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalRow {
+    pub token: String,
+    pub symbol: Option<String>,
+    pub spender: String,
+    /// Live allowance in the token's smallest unit
+    pub allowance: String,
+    /// Whether the allowance counts as unlimited
+    pub unlimited: bool,
+    /// Block of the latest `Approval` event
+    pub block: Option<u64>,
+    /// GoPlus issues for the spender; `None` when not checked
+    pub risk: Option<Vec<String>>,
+    /// Allowance in whole tokens, for the table view
+    #[serde(skip)]
+    formatted: String,
+}
+
+impl ApprovalRow {
+    /// Row for `approval` with its live `allowance`
+    pub fn new(approval: &Approval, allowance: U256, meta: TokenMeta) -> Self {
+        let TokenMeta {
+            symbol,
+            decimals,
+            total_supply,
+        } = meta;
+        let unlimited = is_unlimited(allowance, total_supply);
+        let formatted = match decimals {
+            _ if unlimited => "UNLIMITED".to_string(),
+            Some(decimals) => format_token_amount(&allowance.to_string(), decimals),
+            None => allowance.to_string(),
+        };
+        Self {
+            token: format!("{:#x}", approval.token),
+            symbol,
+            spender: format!("{:#x}", approval.spender),
+            allowance: allowance.to_string(),
+            unlimited,
+            block: approval.block_number,
+            risk: None,
+            formatted,
+        }
+    }
+}
+
+impl Row for ApprovalRow {
+    const COLUMNS: &'static [&'static str] = &[
+        "token",
+        "symbol",
+        "spender",
+        "allowance",
+        "unlimited",
+        "block",
+        "risk",
+    ];
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.token.clone(),
+            self.symbol.clone().unwrap_or_default(),
+            self.spender.clone(),
+            self.allowance.clone(),
+            self.unlimited.to_string(),
+            self.block.map(|b| b.to_string()).unwrap_or_default(),
+            self.risk.as_deref().unwrap_or_default().join("; "),
+        ]
+    }
+
+    fn table_columns() -> &'static [&'static str] {
+        &["Token", "Spender", "Allowance", "Block", "Risk"]
+    }
+
+    fn table_alignments() -> &'static [Alignment] {
+        &[
+            Alignment::Left,
+            Alignment::Left,
+            Alignment::Right,
+            Alignment::Right,
+            Alignment::Left,
+        ]
+    }
+
+    fn table_cells(&self) -> Vec<String> {
+        let risk = match self.risk.as_deref() {
+            None => String::new(),
+            Some([]) => "ok".to_string(),
+            Some(issues) => format!("⚠ {}", issues.join(", ")),
+        };
+        vec![
+            self.symbol.clone().unwrap_or_else(|| self.token.clone()),
+            self.spender.clone(),
+            self.formatted.clone(),
+            self.block.map(|b| b.to_string()).unwrap_or_default(),
+            risk,
+        ]
+    }
+}
@@ -1,11 +1,39 @@
 //! Transaction analysis command
 
 use super::OutputFormat;
-use clap::Args;
+use crate::output::{QueryParam, RowFormat};
+use clap::{Args, Subcommand};
 use std::path::PathBuf;
 
 #[derive(Args)]
+#[command(
+    args_conflicts_with_subcommands = true,
+    after_help = r#"Examples:
+  # Analyze a transaction
+  ethcli tx 0x...
+
+  # Export many transactions to SQLite (re-run to resume)
+  ethcli tx --file hashes.txt --sqlite txs.db
+
+  # Query the export
+  ethcli tx query txs.db "SELECT method, COUNT(*) AS n FROM transactions GROUP BY method"
+
+  # Decode calls and events with a local human-readable ABI
+  ethcli tx 0x... --abi-str "event Filled(address indexed maker, (address token, uint256 amount) fill)"
+
+  # Skip address book labels in the output
+  ethcli tx 0x... --no-labels
+
+  # Decode raw calldata against a contract's ABI
+  ethcli tx decode-input 0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48 0xa9059cbb...
+
+  # Gas by opcode category (needs a debug_traceTransaction endpoint)
+  ethcli tx gas-breakdown 0x... --format json"#
+)]
 pub struct TxArgs {
+    #[command(subcommand)]
+    pub command: Option<TxCommands>,
+
     /// Transaction hash(es) (with or without 0x prefix)
     #[arg(value_name = "TX_HASH")]
     pub hashes: Vec<String>,
@@ -29,4 +57,86 @@ pub struct TxArgs {
     /// Enrich with Etherscan data (contract names, token symbols, function decoding)
     #[arg(long)]
     pub enrich: bool,
+
+    /// ABI file used to decode calls and events before Etherscan/4byte
+    /// (JSON ABI, or human-readable with one signature per line)
+    #[arg(long, value_name = "FILE")]
+    pub abi: Option<PathBuf>,
+
+    /// Inline human-readable ABI (newline-separated signatures)
+    #[arg(long, value_name = "SIGNATURES")]
+    pub abi_str: Option<String>,
+
+    /// Don't annotate addresses with address book labels
+    #[arg(long)]
+    pub no_labels: bool,
+
+    /// Write results to a SQLite database (transactions, events, token_flows tables)
+    /// instead of stdout. Transactions already in the database are skipped.
+    #[arg(long, value_name = "FILE")]
+    pub sqlite: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+pub enum TxCommands {
+    /// Run a read-only SQL query against a `--sqlite` export
+    ///
+    /// Same as `ethcli db query`: only a single SELECT is accepted and
+    /// values are passed with --param.
+    Query {
+        /// SQLite database file
+        #[arg(value_name = "DB")]
+        db: PathBuf,
+
+        /// SQL query
+        #[arg(value_name = "SQL")]
+        sql: String,
+
+        /// Query parameter as NAME=VALUE or NAME:TYPE=VALUE (can be repeated)
+        #[arg(long = "param", short = 'p', value_name = "NAME=VALUE")]
+        params: Vec<QueryParam>,
+
+        /// Output format (table, json, ndjson, csv)
+        #[arg(long, short, value_enum, default_value = "table")]
+        output: RowFormat,
+    },
+
+    /// Decode function calldata without fetching a transaction
+    ///
+    /// Uses the contract's verified ABI, falling back to the 4byte
+    /// signature database when the selector is not in the ABI.
+    DecodeInput {
+        /// Contract address the calldata targets
+        #[arg(value_name = "ADDRESS")]
+        address: String,
+
+        /// Calldata as hex (with or without 0x prefix)
+        #[arg(value_name = "CALLDATA")]
+        calldata: String,
+
+        /// Output format (json, table/pretty, ndjson)
+        #[arg(long, short = 'o', value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+
+    /// Show where a transaction's gas went: storage writes and reads,
+    /// call overhead, log emission, computation
+    ///
+    /// Replays the transaction with debug_traceTransaction, so it needs a
+    /// debug-capable endpoint (an endpoint with has_debug = true, a
+    /// configured debug RPC, or --rpc-url). Gas spent inside a called
+    /// contract counts toward that contract's opcodes, not the CALL.
+    GasBreakdown {
+        /// Transaction hash
+        #[arg(value_name = "TX_HASH")]
+        hash: String,
+
+        /// Debug-capable RPC URL (overrides configured endpoints)
+        #[arg(long, value_name = "URL")]
+        rpc_url: Option<String>,
+
+        /// Output format (json, table/pretty, ndjson)
+        #[arg(long, short = 'o', value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
 }
@@ -0,0 +1,183 @@
+//! In-process extraction of the ethcli binary from release archives
+//!
+//! Only the executable is read out of the archive; nothing else is written
+//! to disk. Entries with absolute paths or `..` components are rejected
+//! outright, so a crafted archive cannot write outside the target.
+
+use std::io::{Cursor, Read};
+use std::path::{Component, Path};
+
+/// Maximum directory depth the binary may sit at (`ethcli` or `dir/ethcli`)
+const MAX_DEPTH: usize = 2;
+
+/// Read the `binary_name` executable out of a `.tar.gz` or `.zip` archive
+///
+/// The binary may be at the archive root or inside a single top-level
+/// directory. Exactly one match is required.
+pub fn extract_binary(
+    archive_name: &str,
+    bytes: &[u8],
+    binary_name: &str,
+) -> anyhow::Result<Vec<u8>> {
+    if archive_name.ends_with(".tar.gz") || archive_name.ends_with(".tgz") {
+        extract_from_tar_gz(bytes, binary_name)
+    } else if archive_name.ends_with(".zip") {
+        extract_from_zip(bytes, binary_name)
+    } else {
+        anyhow::bail!("Unknown archive format: {}", archive_name)
+    }
+}
+
+/// Whether `path` names the binary; errors if the path is unsafe
+fn is_binary_entry(path: &Path, binary_name: &str) -> anyhow::Result<bool> {
+    let mut depth = 0;
+    for component in path.components() {
+        match component {
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {}
+            _ => anyhow::bail!(
+                "Security: archive entry '{}' escapes the extraction directory",
+                path.display()
+            ),
+        }
+    }
+    Ok(depth <= MAX_DEPTH && path.file_name().is_some_and(|n| n == binary_name))
+}
+
+fn single_match(found: Option<Vec<u8>>, binary_name: &str) -> anyhow::Result<Vec<u8>> {
+    found.ok_or_else(|| anyhow::anyhow!("Binary '{}' not found in archive", binary_name))
+}
+
+fn extract_from_tar_gz(bytes: &[u8], binary_name: &str) -> anyhow::Result<Vec<u8>> {
+    let decoder = flate2::read::GzDecoder::new(Cursor::new(bytes));
+    let mut archive = tar::Archive::new(decoder);
+    let mut found = None;
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.into_owned();
+        if !is_binary_entry(&path, binary_name)? {
+            continue;
+        }
+        if !entry.header().entry_type().is_file() {
+            anyhow::bail!(
+                "Security: '{}' in archive is not a regular file",
+                path.display()
+            );
+        }
+        if found.is_some() {
+            anyhow::bail!("Archive contains more than one '{}'", binary_name);
+        }
+        let mut contents = Vec::with_capacity(entry.size() as usize);
+        entry.read_to_end(&mut contents)?;
+        found = Some(contents);
+    }
+
+    single_match(found, binary_name)
+}
+
+fn extract_from_zip(bytes: &[u8], binary_name: &str) -> anyhow::Result<Vec<u8>> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes))?;
+    let mut found = None;
+
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index)?;
+        // `enclosed_name` is `None` for absolute or `..` paths
+        let path = file.enclosed_name().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Security: archive entry '{}' escapes the extraction directory",
+                file.name()
+            )
+        })?;
+        if !is_binary_entry(&path, binary_name)? {
+            continue;
+        }
+        if !file.is_file() || file.is_symlink() {
+            anyhow::bail!(
+                "Security: '{}' in archive is not a regular file",
+                path.display()
+            );
+        }
+        if found.is_some() {
+            anyhow::bail!("Archive contains more than one '{}'", binary_name);
+        }
+        let mut contents = Vec::with_capacity(file.size() as usize);
+        file.read_to_end(&mut contents)?;
+        found = Some(contents);
+    }
+
+    single_match(found, binary_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/update");
+
+    fn fixture(name: &str) -> Vec<u8> {
+        std::fs::read(format!("{}/{}", FIXTURES, name)).unwrap()
+    }
+
+    #[test]
+    fn test_extract_tar_gz() {
+        let binary = extract_binary(
+            "ethcli-linux-x86_64.tar.gz",
+            &fixture("ethcli-linux-x86_64.tar.gz"),
+            "ethcli",
+        )
+        .unwrap();
+        assert_eq!(binary, b"#!/bin/sh\necho 'ethcli 99.0.0'\n");
+    }
+
+    #[test]
+    fn test_extract_zip() {
+        let binary = extract_binary(
+            "ethcli-windows-x86_64.zip",
+            &fixture("ethcli-windows-x86_64.zip"),
+            "ethcli.exe",
+        )
+        .unwrap();
+        assert_eq!(binary, b"MZ fake ethcli 99.0.0\r\n");
+    }
+
+    #[test]
+    fn test_extract_nested_binary() {
+        let binary = extract_binary("nested.tar.gz", &fixture("nested.tar.gz"), "ethcli").unwrap();
+        assert_eq!(binary, b"#!/bin/sh\necho 'ethcli 99.0.0'\n");
+    }
+
+    #[test]
+    fn test_rejects_path_traversal() {
+        let err = extract_binary("evil.tar.gz", &fixture("traversal.tar.gz"), "ethcli")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("escapes"), "{}", err);
+
+        let err = extract_binary("evil.zip", &fixture("traversal.zip"), "ethcli")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("escapes"), "{}", err);
+    }
+
+    #[test]
+    fn test_rejects_symlink_binary() {
+        let err = extract_binary("link.tar.gz", &fixture("symlink.tar.gz"), "ethcli")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("not a regular file"), "{}", err);
+    }
+
+    #[test]
+    fn test_missing_binary() {
+        let err = extract_binary(
+            "ethcli-linux-x86_64.tar.gz",
+            &fixture("ethcli-linux-x86_64.tar.gz"),
+            "ethcli.exe",
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("not found"), "{}", err);
+        assert!(extract_binary("ethcli.rar", b"", "ethcli").is_err());
+    }
+}
@@ -0,0 +1,419 @@
+//! Update command - check for and install updates from GitHub releases
+//!
+//! Installs only proceed when the downloaded archive carries a valid
+//! minisign signature from the release key (see [`verify`]). The replaced
+//! binary is kept next to the new one as `ethcli.bak` so `--rollback` can
+//! restore it.
+
+mod archive;
+mod platform;
+mod verify;
+
+pub use archive::extract_binary;
+pub use platform::Platform;
+pub use verify::{
+    checksum_for, resolve_public_key, verify_checksum, verify_signature, CHECKSUMS_ASSET,
+    EMBEDDED_PUBLIC_KEY, SIGNATURE_SUFFIX,
+};
+
+use crate::config::ConfigFile;
+use clap::ValueEnum;
+use semver::Version;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use yldfi_common::http::{build_client, HttpClientConfig};
+
+#[derive(Debug, Deserialize)]
+pub struct GitHubRelease {
+    pub tag_name: String,
+    pub html_url: String,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub prerelease: bool,
+    #[serde(default)]
+    pub assets: Vec<GitHubAsset>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GitHubAsset {
+    pub name: String,
+    pub browser_download_url: String,
+}
+
+impl GitHubRelease {
+    fn asset(&self, name: &str) -> Option<&GitHubAsset> {
+        self.assets.iter().find(|a| a.name == name)
+    }
+}
+
+/// Release channel to follow
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Channel {
+    /// Tagged releases only
+    #[default]
+    Stable,
+    /// Also consider GitHub pre-releases and semver pre-release versions
+    Nightly,
+}
+
+const REPO: &str = "yldfi/yldfi-rs";
+const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+const RELEASE_TAG_PREFIX: &str = "ethcli-v";
+/// Proxy source name for `[proxy] sources` / `exclude_sources`
+const PROXY_SOURCE: &str = "github";
+
+/// Newest ethcli release on `channel`
+///
+/// The releases list is shared with other crates in the repository, so
+/// only `ethcli-v*` tags are considered. Drafts are always skipped.
+pub fn select_release(
+    releases: &[GitHubRelease],
+    channel: Channel,
+) -> Option<(&GitHubRelease, Version)> {
+    releases
+        .iter()
+        .filter(|r| !r.draft)
+        .filter_map(|r| {
+            let version = r.tag_name.strip_prefix(RELEASE_TAG_PREFIX)?;
+            Some((r, Version::parse(version).ok()?))
+        })
+        .filter(|(r, v)| match channel {
+            Channel::Stable => !r.prerelease && v.pre.is_empty(),
+            Channel::Nightly => true,
+        })
+        .max_by(|(_, a), (_, b)| a.cmp(b))
+}
+
+/// Where the previous binary is kept after an install
+pub fn backup_path(install_path: &Path) -> PathBuf {
+    install_path.with_file_name("ethcli.bak")
+}
+
+/// Replace the binary at `install_path`, keeping the old one as a backup
+///
+/// The new binary is staged next to the target and moved into place with
+/// renames, which also works while the old binary is running (including on
+/// Windows, where a running executable can be renamed but not overwritten).
+pub fn install_binary(new_binary: &[u8], install_path: &Path) -> anyhow::Result<PathBuf> {
+    let backup = backup_path(install_path);
+    let staged = install_path.with_file_name("ethcli.new");
+
+    std::fs::write(&staged, new_binary)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o755))?;
+    }
+
+    if backup.exists() {
+        std::fs::remove_file(&backup)?;
+    }
+    if let Err(e) = std::fs::rename(install_path, &backup) {
+        let _ = std::fs::remove_file(&staged);
+        return Err(e.into());
+    }
+    if let Err(e) = std::fs::rename(&staged, install_path) {
+        // Put the old binary back so the install is never left empty
+        let _ = std::fs::rename(&backup, install_path);
+        let _ = std::fs::remove_file(&staged);
+        return Err(e.into());
+    }
+
+    Ok(backup)
+}
+
+/// Swap the binary at `install_path` with its backup
+///
+/// The binary being rolled back from becomes the new backup, so a second
+/// rollback undoes the first.
+pub fn rollback_binary(install_path: &Path) -> anyhow::Result<PathBuf> {
+    let backup = backup_path(install_path);
+    if !backup.is_file() {
+        anyhow::bail!(
+            "No previous version to roll back to ({} not found)",
+            backup.display()
+        );
+    }
+
+    let swap = install_path.with_file_name("ethcli.rollback");
+    std::fs::rename(install_path, &swap)?;
+    if let Err(e) = std::fs::rename(&backup, install_path) {
+        let _ = std::fs::rename(&swap, install_path);
+        return Err(e.into());
+    }
+    std::fs::rename(&swap, &backup)?;
+
+    Ok(backup)
+}
+
+async fn download(client: &reqwest::Client, url: &str) -> anyhow::Result<Vec<u8>> {
+    let response = client.get(url).send().await?;
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to download {}: {}", url, response.status());
+    }
+    Ok(response.bytes().await?.to_vec())
+}
+
+async fn download_text(client: &reqwest::Client, url: &str) -> anyhow::Result<String> {
+    String::from_utf8(download(client, url).await?)
+        .map_err(|_| anyhow::anyhow!("{} is not valid UTF-8", url))
+}
+
+/// Verify a downloaded asset against the release's signatures
+///
+/// Prefers a per-asset `.minisig`; otherwise requires a signed `SHA256SUMS`
+/// that lists the asset. Anything else is refused.
+async fn verify_asset(
+    client: &reqwest::Client,
+    release: &GitHubRelease,
+    asset: &GitHubAsset,
+    bytes: &[u8],
+    public_key: &str,
+) -> anyhow::Result<()> {
+    let asset_sig = format!("{}{}", asset.name, SIGNATURE_SUFFIX);
+    if let Some(sig) = release.asset(&asset_sig) {
+        let signature = download_text(client, &sig.browser_download_url).await?;
+        return verify_signature(bytes, &signature, public_key);
+    }
+
+    let sums_sig = format!("{}{}", CHECKSUMS_ASSET, SIGNATURE_SUFFIX);
+    match (release.asset(CHECKSUMS_ASSET), release.asset(&sums_sig)) {
+        (Some(sums), Some(sig)) => {
+            let sums = download_text(client, &sums.browser_download_url).await?;
+            let signature = download_text(client, &sig.browser_download_url).await?;
+            verify_signature(sums.as_bytes(), &signature, public_key)?;
+            verify_checksum(&sums, &asset.name, bytes)
+        }
+        _ => anyhow::bail!(
+            "Security: {} is not signed (no {} or {}).\n\
+             Refusing to install an unverified update.\n\
+             Please download manually from: {}",
+            release.tag_name,
+            asset_sig,
+            sums_sig,
+            release.html_url
+        ),
+    }
+}
+
+/// Restore the binary that was replaced by the last `update --install`
+pub fn handle_rollback(quiet: bool) -> anyhow::Result<()> {
+    let install_path = std::env::current_exe()?;
+    if !quiet {
+        eprintln!(
+            "Restoring previous version of {}...",
+            install_path.display()
+        );
+    }
+    let backup = rollback_binary(&install_path)?;
+    println!("\n✓ Rolled back to the previous version.");
+    println!(
+        "  The replaced binary was kept as {} (run --rollback again to undo)",
+        backup.display()
+    );
+    Ok(())
+}
+
+/// Check for updates and optionally install them
+pub async fn handle(
+    install: bool,
+    channel: Channel,
+    config: Option<&ConfigFile>,
+    quiet: bool,
+) -> anyhow::Result<()> {
+    if !quiet {
+        eprintln!("Checking for updates...");
+    }
+
+    let proxy = config.and_then(|c| c.proxy_for_source(PROXY_SOURCE));
+    let client = build_client(
+        &HttpClientConfig::new()
+            .with_timeout_secs(30)
+            .with_user_agent("ethcli")
+            .with_optional_proxy(proxy),
+    )?;
+
+    // Fetch releases from GitHub (not /latest, as that returns any crate's release)
+    let url = format!("https://api.github.com/repos/{}/releases?per_page=50", REPO);
+    let response = client.get(&url).send().await?;
+
+    if !response.status().is_success() {
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            anyhow::bail!(
+                "Could not fetch release info. The repository may be private.\n\
+                 Check manually: https://github.com/{}/releases",
+                REPO
+            );
+        }
+        anyhow::bail!("Failed to check for updates: {}", response.status());
+    }
+
+    let releases: Vec<GitHubRelease> = response.json().await?;
+    let (release, latest) = select_release(&releases, channel).ok_or_else(|| {
+        anyhow::anyhow!(
+            "No ethcli releases found on the {:?} channel. Check manually: https://github.com/{}/releases",
+            channel,
+            REPO
+        )
+    })?;
+
+    println!("Current version: v{}", CURRENT_VERSION);
+    println!("Latest version:  {}", release.tag_name);
+
+    let current = Version::parse(CURRENT_VERSION)
+        .map_err(|e| anyhow::anyhow!("Failed to parse current version: {}", e))?;
+
+    if current >= latest {
+        println!("\n✓ You're on the latest version!");
+        return Ok(());
+    }
+
+    let platform = Platform::current();
+    let asset = platform
+        .asset_candidates()
+        .iter()
+        .find_map(|name| release.asset(name));
+
+    if !install {
+        println!("\nUpdate available!");
+        println!("Download from: {}", release.html_url);
+        if asset.is_some() {
+            let flag = match channel {
+                Channel::Stable => "",
+                Channel::Nightly => " --channel nightly",
+            };
+            println!("\nOr run: ethcli update --install{}", flag);
+        }
+        return Ok(());
+    }
+
+    let asset = asset.ok_or_else(|| {
+        anyhow::anyhow!(
+            "No binary available for your platform ({}). Download manually from: {}",
+            platform,
+            release.html_url
+        )
+    })?;
+
+    // Resolve the signing key before downloading anything we couldn't verify
+    let configured_key = config
+        .and_then(|c| c.update.as_ref())
+        .and_then(|u| u.public_key.as_deref());
+    let public_key = resolve_public_key(configured_key).ok_or_else(|| {
+        anyhow::anyhow!(
+            "This build has no release signing key, so updates cannot be verified.\n\
+             Set `public_key` under [update] in the config file, or download manually from: {}",
+            release.html_url
+        )
+    })?;
+
+    if !quiet {
+        eprintln!("Downloading {}...", asset.name);
+    }
+    let bytes = download(&client, &asset.browser_download_url).await?;
+
+    if !quiet {
+        eprintln!("Verifying signature...");
+    }
+    verify_asset(&client, release, asset, &bytes, &public_key).await?;
+    if !quiet {
+        eprintln!("Signature verified.");
+    }
+
+    let binary = extract_binary(&asset.name, &bytes, platform.binary_name())?;
+
+    let install_path = std::env::current_exe()?;
+    if !quiet {
+        eprintln!("Installing to {}...", install_path.display());
+    }
+    let backup = install_binary(&binary, &install_path)?;
+
+    println!("\n✓ Updated to {}!", release.tag_name);
+    println!(
+        "  Previous version saved as {} (undo with: ethcli update --rollback)",
+        backup.display()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(tag: &str, prerelease: bool) -> GitHubRelease {
+        GitHubRelease {
+            tag_name: tag.to_string(),
+            html_url: String::new(),
+            draft: false,
+            prerelease,
+            assets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_select_release_by_channel() {
+        let releases = vec![
+            release("ykong-v9.0.0", false),
+            release("ethcli-v0.30.0-nightly.20261001", true),
+            release("ethcli-v0.29.1", false),
+            release("ethcli-v0.29.0", false),
+            release("ethcli-v0.31.0-rc.1", false),
+            GitHubRelease {
+                draft: true,
+                ..release("ethcli-v1.0.0", false)
+            },
+        ];
+
+        let (stable, version) = select_release(&releases, Channel::Stable).unwrap();
+        assert_eq!(stable.tag_name, "ethcli-v0.29.1");
+        assert_eq!(version, Version::new(0, 29, 1));
+
+        let (nightly, _) = select_release(&releases, Channel::Nightly).unwrap();
+        assert_eq!(nightly.tag_name, "ethcli-v0.31.0-rc.1");
+
+        assert!(select_release(&releases[..1], Channel::Nightly).is_none());
+    }
+
+    #[test]
+    fn test_install_and_rollback() {
+        let dir = tempfile::tempdir().unwrap();
+        let install_path = dir.path().join("ethcli");
+        std::fs::write(&install_path, b"old").unwrap();
+
+        let backup = install_binary(b"new", &install_path).unwrap();
+        assert_eq!(backup, dir.path().join("ethcli.bak"));
+        assert_eq!(std::fs::read(&install_path).unwrap(), b"new");
+        assert_eq!(std::fs::read(&backup).unwrap(), b"old");
+        assert!(!dir.path().join("ethcli.new").exists());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&install_path)
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o755);
+        }
+
+        rollback_binary(&install_path).unwrap();
+        assert_eq!(std::fs::read(&install_path).unwrap(), b"old");
+        assert_eq!(std::fs::read(&backup).unwrap(), b"new");
+
+        // Second rollback undoes the first
+        rollback_binary(&install_path).unwrap();
+        assert_eq!(std::fs::read(&install_path).unwrap(), b"new");
+    }
+
+    #[test]
+    fn test_rollback_without_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let install_path = dir.path().join("ethcli");
+        std::fs::write(&install_path, b"current").unwrap();
+
+        assert!(rollback_binary(&install_path).is_err());
+        assert_eq!(std::fs::read(&install_path).unwrap(), b"current");
+    }
+}
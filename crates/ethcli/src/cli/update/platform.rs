@@ -0,0 +1,142 @@
+//! Release asset naming for the running platform
+
+/// Target a release binary is built for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Platform {
+    /// Operating system (`linux`, `macos`, `windows`)
+    pub os: &'static str,
+    /// CPU architecture (`x86_64`, `aarch64`)
+    pub arch: &'static str,
+    /// Statically linked against musl instead of glibc (Linux only)
+    pub musl: bool,
+}
+
+impl Platform {
+    /// Platform of the running binary
+    pub fn current() -> Self {
+        Self::new(
+            std::env::consts::OS,
+            std::env::consts::ARCH,
+            cfg!(target_env = "musl"),
+        )
+    }
+
+    /// Build a platform, normalizing common architecture aliases
+    /// (`arm64` → `aarch64`, `amd64`/`x64` → `x86_64`)
+    pub fn new(os: &'static str, arch: &'static str, musl: bool) -> Self {
+        let arch = match arch {
+            "arm64" => "aarch64",
+            "amd64" | "x64" => "x86_64",
+            other => other,
+        };
+        Self {
+            os,
+            arch,
+            musl: musl && os == "linux",
+        }
+    }
+
+    /// Archive extension used for this platform's release assets
+    pub fn archive_extension(&self) -> &'static str {
+        if self.os == "windows" {
+            "zip"
+        } else {
+            "tar.gz"
+        }
+    }
+
+    /// Name of the executable inside the release archive
+    pub fn binary_name(&self) -> &'static str {
+        if self.os == "windows" {
+            "ethcli.exe"
+        } else {
+            "ethcli"
+        }
+    }
+
+    /// Release asset name, e.g. `ethcli-linux-aarch64-musl.tar.gz`
+    pub fn asset_name(&self) -> String {
+        let libc = if self.musl { "-musl" } else { "" };
+        format!(
+            "ethcli-{}-{}{}.{}",
+            self.os,
+            self.arch,
+            libc,
+            self.archive_extension()
+        )
+    }
+
+    /// Asset names to look for, most specific first
+    ///
+    /// A glibc Linux build can also run the static musl binary, so it is
+    /// offered as a fallback. The reverse does not hold: a musl system
+    /// (e.g. Alpine) has no glibc to run the dynamic build.
+    pub fn asset_candidates(&self) -> Vec<String> {
+        let mut candidates = vec![self.asset_name()];
+        if self.os == "linux" && !self.musl {
+            candidates.push(
+                Self {
+                    musl: true,
+                    ..*self
+                }
+                .asset_name(),
+            );
+        }
+        candidates
+    }
+}
+
+impl std::fmt::Display for Platform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.os, self.arch)?;
+        if self.musl {
+            write!(f, "-musl")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_asset_names() {
+        assert_eq!(
+            Platform::new("macos", "arm64", false).asset_name(),
+            "ethcli-macos-aarch64.tar.gz"
+        );
+        assert_eq!(
+            Platform::new("windows", "aarch64", false).asset_name(),
+            "ethcli-windows-aarch64.zip"
+        );
+        assert_eq!(
+            Platform::new("linux", "aarch64", true).asset_name(),
+            "ethcli-linux-aarch64-musl.tar.gz"
+        );
+        // musl is meaningless outside Linux
+        assert_eq!(
+            Platform::new("macos", "x86_64", true).asset_name(),
+            "ethcli-macos-x86_64.tar.gz"
+        );
+    }
+
+    #[test]
+    fn test_asset_candidates() {
+        assert_eq!(
+            Platform::new("linux", "x86_64", false).asset_candidates(),
+            vec![
+                "ethcli-linux-x86_64.tar.gz",
+                "ethcli-linux-x86_64-musl.tar.gz"
+            ]
+        );
+        assert_eq!(
+            Platform::new("linux", "x86_64", true).asset_candidates(),
+            vec!["ethcli-linux-x86_64-musl.tar.gz"]
+        );
+        assert_eq!(
+            Platform::new("windows", "x86_64", false).asset_candidates(),
+            vec!["ethcli-windows-x86_64.zip"]
+        );
+    }
+}
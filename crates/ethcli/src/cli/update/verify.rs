@@ -0,0 +1,170 @@
+//! Release signature and checksum verification
+//!
+//! Releases are signed with [minisign](https://jedisct1.github.io/minisign/).
+//! An asset is trusted if either its own `<asset>.minisig` verifies, or the
+//! release's `SHA256SUMS` manifest verifies against `SHA256SUMS.minisig` and
+//! lists the asset's digest.
+
+use minisign_verify::{PublicKey, Signature};
+use sha2::{Digest, Sha256};
+
+/// Release signing key embedded at build time
+///
+/// Release builds set `ETHCLI_UPDATE_PUBLIC_KEY`; local builds without it
+/// need `[update] public_key` in the config file to install updates.
+pub const EMBEDDED_PUBLIC_KEY: Option<&str> = option_env!("ETHCLI_UPDATE_PUBLIC_KEY");
+
+/// Checksum manifest published with each release
+pub const CHECKSUMS_ASSET: &str = "SHA256SUMS";
+
+/// Suffix of detached minisign signatures
+pub const SIGNATURE_SUFFIX: &str = ".minisig";
+
+/// Pick the public key to verify against: config override, then embedded key
+pub fn resolve_public_key(configured: Option<&str>) -> Option<String> {
+    configured
+        .map(str::trim)
+        .filter(|k| !k.is_empty())
+        .or(EMBEDDED_PUBLIC_KEY.map(str::trim).filter(|k| !k.is_empty()))
+        .map(str::to_string)
+}
+
+/// Parse a public key given either as the bare base64 key or as the
+/// contents of a `minisign.pub` file
+fn parse_public_key(public_key: &str) -> anyhow::Result<PublicKey> {
+    let trimmed = public_key.trim();
+    let parsed = if trimmed.contains('\n') {
+        PublicKey::decode(trimmed)
+    } else {
+        PublicKey::from_base64(trimmed)
+    };
+    parsed.map_err(|e| anyhow::anyhow!("Invalid update public key: {}", e))
+}
+
+/// Verify a detached minisign signature over `data`
+///
+/// Only prehashed (BLAKE2b) signatures, the minisign default, are accepted.
+pub fn verify_signature(data: &[u8], signature: &str, public_key: &str) -> anyhow::Result<()> {
+    let public_key = parse_public_key(public_key)?;
+    let signature = Signature::decode(signature)
+        .map_err(|e| anyhow::anyhow!("Malformed release signature: {}", e))?;
+    public_key.verify(data, &signature, false).map_err(|e| {
+        anyhow::anyhow!(
+            "Signature verification failed: {}\n\
+             The download may be corrupted or tampered with.",
+            e
+        )
+    })
+}
+
+/// Hex SHA-256 digest listed for `asset_name` in a `sha256sum`-style manifest
+///
+/// Lines look like `<hex>  <name>` (text mode) or `<hex> *<name>` (binary mode).
+pub fn checksum_for<'a>(sums: &'a str, asset_name: &str) -> Option<&'a str> {
+    sums.lines().find_map(|line| {
+        let (hash, name) = line.trim().split_once(char::is_whitespace)?;
+        let name = name.trim_start();
+        let name = name.strip_prefix('*').unwrap_or(name);
+        (name == asset_name).then_some(hash)
+    })
+}
+
+/// Check `bytes` against the digest listed for `asset_name` in `sums`
+pub fn verify_checksum(sums: &str, asset_name: &str, bytes: &[u8]) -> anyhow::Result<()> {
+    let expected = checksum_for(sums, asset_name)
+        .ok_or_else(|| anyhow::anyhow!("{} is not listed in {}", asset_name, CHECKSUMS_ASSET))?
+        .to_lowercase();
+    let actual = format!("{:x}", Sha256::digest(bytes));
+    if actual != expected {
+        anyhow::bail!(
+            "Checksum verification failed!\nExpected: {}\nActual:   {}\n\n\
+             The downloaded file may be corrupted or tampered with.",
+            expected,
+            actual
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/update");
+
+    fn fixture(name: &str) -> Vec<u8> {
+        std::fs::read(format!("{}/{}", FIXTURES, name)).unwrap()
+    }
+
+    fn fixture_str(name: &str) -> String {
+        String::from_utf8(fixture(name)).unwrap()
+    }
+
+    #[test]
+    fn test_verify_asset_signature() {
+        let key = fixture_str("minisign.pub");
+        let asset = fixture("ethcli-linux-x86_64.tar.gz");
+        let signature = fixture_str("ethcli-linux-x86_64.tar.gz.minisig");
+
+        verify_signature(&asset, &signature, &key).unwrap();
+
+        // Bare base64 key works too
+        let bare = key.lines().nth(1).unwrap();
+        verify_signature(&asset, &signature, bare).unwrap();
+
+        // Tampered payload
+        let mut tampered = asset.clone();
+        tampered[0] ^= 0xff;
+        assert!(verify_signature(&tampered, &signature, &key).is_err());
+
+        // Signature from a different key
+        let other = fixture_str("other.pub");
+        assert!(verify_signature(&asset, &signature, &other).is_err());
+    }
+
+    #[test]
+    fn test_verify_signed_checksums() {
+        let key = fixture_str("minisign.pub");
+        let sums = fixture_str("SHA256SUMS");
+        let signature = fixture_str("SHA256SUMS.minisig");
+        verify_signature(sums.as_bytes(), &signature, &key).unwrap();
+
+        let asset = fixture("ethcli-windows-x86_64.zip");
+        verify_checksum(&sums, "ethcli-windows-x86_64.zip", &asset).unwrap();
+        assert!(verify_checksum(&sums, "ethcli-linux-x86_64.tar.gz", &asset).is_err());
+        assert!(verify_checksum(&sums, "ethcli-freebsd-x86_64.tar.gz", &asset).is_err());
+
+        // Editing the manifest invalidates its signature
+        let edited = sums.replacen('0', "1", 1);
+        assert!(verify_signature(edited.as_bytes(), &signature, &key).is_err());
+    }
+
+    #[test]
+    fn test_checksum_for() {
+        let sums = "abc123  ethcli-linux-x86_64.tar.gz\nDEF456 *ethcli-windows-x86_64.zip\n";
+        assert_eq!(
+            checksum_for(sums, "ethcli-linux-x86_64.tar.gz"),
+            Some("abc123")
+        );
+        assert_eq!(
+            checksum_for(sums, "ethcli-windows-x86_64.zip"),
+            Some("DEF456")
+        );
+        assert_eq!(checksum_for(sums, "ethcli-linux-x86_64"), None);
+    }
+
+    #[test]
+    fn test_resolve_public_key() {
+        assert_eq!(
+            resolve_public_key(Some("  RWQkey  ")),
+            Some("RWQkey".to_string())
+        );
+        assert_eq!(
+            resolve_public_key(Some("")),
+            EMBEDDED_PUBLIC_KEY
+                .map(str::trim)
+                .filter(|k| !k.is_empty())
+                .map(str::to_string)
+        );
+    }
+}
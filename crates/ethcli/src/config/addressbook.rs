@@ -157,6 +157,23 @@ impl AddressBook {
         self.get(label_or_address).map(|e| e.address.clone())
     }
 
+    /// Find the entry saved for an address (reverse lookup)
+    ///
+    /// Matching ignores checksum casing. If several labels point at the same
+    /// address, the alphabetically first label wins.
+    pub fn find_by_address(&self, address: &Address) -> Option<(&String, &AddressEntry)> {
+        self.entries
+            .iter()
+            .filter(|(_, entry)| Address::from_str(&entry.address).is_ok_and(|a| a == *address))
+            .min_by(|a, b| a.0.cmp(b.0))
+    }
+
+    /// Label saved for an address, if any
+    pub fn label_for(&self, address: &Address) -> Option<&str> {
+        self.find_by_address(address)
+            .map(|(label, _)| label.as_str())
+    }
+
     /// List all entries, optionally filtered by tag
     pub fn list(&self, tag_filter: Option<&str>) -> Vec<(&String, &AddressEntry)> {
         let mut entries: Vec<_> = self
@@ -280,4 +297,24 @@ mod tests {
         assert_eq!(book.list(Some("whale")).len(), 1);
         assert_eq!(book.list(Some("unknown")).len(), 0);
     }
+
+    #[test]
+    fn test_label_for() {
+        let mut book = AddressBook::default();
+        for label in ["usdc", "circle-usd"] {
+            book.entries.insert(
+                label.to_string(),
+                AddressEntry {
+                    address: "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".to_string(),
+                    description: None,
+                    tags: Vec::new(),
+                    chain: None,
+                },
+            );
+        }
+
+        let usdc = Address::from_str("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48").unwrap();
+        assert_eq!(book.label_for(&usdc), Some("circle-usd"));
+        assert_eq!(book.label_for(&Address::ZERO), None);
+    }
 }
@@ -85,6 +85,10 @@ pub struct EndpointConfig {
     /// ISO timestamp of last optimization/test
     #[serde(default)]
     pub last_tested: Option<String>,
+    /// User-pinned priority: `endpoints monitor` never changes it and never
+    /// promotes other endpoints on the same chain above it
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pinned: bool,
 }
 
 fn default_max_block_range() -> u64 {
@@ -120,6 +124,7 @@ impl EndpointConfig {
             has_trace: false,
             chain: Chain::default(),
             last_tested: None,
+            pinned: false,
         }
     }
 
@@ -189,6 +194,12 @@ impl EndpointConfig {
         self
     }
 
+    /// Builder-style setter for pinned
+    pub fn with_pinned(mut self, pinned: bool) -> Self {
+        self.pinned = pinned;
+        self
+    }
+
     /// Check if endpoint is suitable for a given block range
     pub fn can_handle_range(&self, range: u64) -> bool {
         self.max_block_range == 0 || range <= self.max_block_range
@@ -1,9 +1,11 @@
 //! Configuration file handling
 
+use super::migrate::CURRENT_CONFIG_VERSION;
 use super::{EndpointConfig, ProxyConfig};
 use crate::error::{ConfigError, Result};
 use fs2::FileExt;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
 use std::fs::OpenOptions;
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -57,8 +59,13 @@ where
 }
 
 /// Configuration file structure
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConfigFile {
+    /// Schema version. Files written before versioning existed have none and
+    /// are treated as version 0; `ethcli config migrate` upgrades them.
+    #[serde(default)]
+    pub version: u32,
+
     /// Global settings
     #[serde(default)]
     pub settings: Settings,
@@ -135,9 +142,51 @@ pub struct ConfigFile {
     #[serde(default)]
     pub solodit: Option<SoloditConfig>,
 
+    /// Self-update settings
+    #[serde(default)]
+    pub update: Option<UpdateConfig>,
+
+    /// Gas cost comparison settings
+    #[serde(default)]
+    pub gas: Option<GasConfig>,
+
     /// Debug-capable RPC endpoints (for debug_traceCall, etc.)
     #[serde(default)]
     pub debug_rpc_urls: Vec<String>,
+
+    /// Saved queries for `ethcli db report`, keyed by name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub queries: BTreeMap<String, SavedQuery>,
+}
+
+impl Default for ConfigFile {
+    fn default() -> Self {
+        Self {
+            version: CURRENT_CONFIG_VERSION,
+            settings: Settings::default(),
+            endpoints: Vec::new(),
+            disabled_endpoints: DisabledEndpoints::default(),
+            proxy: None,
+            etherscan_api_key: None,
+            tenderly: None,
+            alchemy: None,
+            coingecko: None,
+            defillama: None,
+            moralis: None,
+            dune: None,
+            dune_sim: None,
+            chainlink: None,
+            zerox: None,
+            oneinch: None,
+            enso: None,
+            thegraph: None,
+            solodit: None,
+            update: None,
+            gas: None,
+            debug_rpc_urls: Vec::new(),
+            queries: BTreeMap::new(),
+        }
+    }
 }
 
 /// Tenderly API configuration
@@ -307,6 +356,48 @@ pub struct SoloditConfig {
     pub api_key: SecretString,
 }
 
+/// Self-update configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateConfig {
+    /// Minisign public key used to verify releases (overrides the key
+    /// embedded at build time). Either the bare base64 key or the full
+    /// contents of a `minisign.pub` file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+}
+
+/// Gas cost comparison configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GasConfig {
+    /// Representative gas units per operation, overriding the built-in
+    /// defaults (`transfer`, `erc20-transfer`, `swap`, `nft-mint`)
+    #[serde(default)]
+    pub operations: BTreeMap<String, u64>,
+}
+
+/// A named read-only query run by `ethcli db report`
+///
+/// ```toml
+/// [queries.daily_volume]
+/// sql = "SELECT block_number / 7200 AS day, count(*) FROM events WHERE address = :token GROUP BY day"
+/// description = "Transfers per day"
+/// params = { token = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48" }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedQuery {
+    /// SQL to run; must be a single SELECT
+    pub sql: String,
+
+    /// Shown by `ethcli db reports`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Default parameter values (`NAME` or `NAME:TYPE` keys); `--param`
+    /// overrides them
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub params: BTreeMap<String, String>,
+}
+
 /// Global settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Settings {
@@ -325,6 +416,11 @@ pub struct Settings {
     /// Checkpoint save interval (blocks)
     #[serde(default = "default_checkpoint_interval")]
     pub checkpoint_interval: u64,
+
+    /// Directory for default checkpoint files (defaults to `checkpoints/`
+    /// under the config directory)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checkpoint_dir: Option<PathBuf>,
 }
 
 fn default_concurrency() -> usize {
@@ -350,6 +446,7 @@ impl Default for Settings {
             timeout_seconds: default_timeout(),
             retry_attempts: default_retries(),
             checkpoint_interval: default_checkpoint_interval(),
+            checkpoint_dir: None,
         }
     }
 }
@@ -446,110 +543,11 @@ impl ConfigFile {
         Ok(config)
     }
 
-    /// Save to a specific path
-    ///
-    /// MED-003/HIGH-001 fix: Uses file-level locking with atomic write pattern
-    /// to prevent TOCTOU race conditions and data loss when multiple CLI
-    /// instances run simultaneously.
-    ///
-    /// Uses write-to-temp-and-rename pattern for atomic writes:
-    /// 1. Write to a unique temporary file with exclusive lock
-    /// 2. Set restrictive permissions (before rename to avoid race)
-    /// 3. Sync to disk
-    /// 4. Rename atomically to target path
+    /// Save to a specific path (see [`write_config`] for the atomic write)
     pub fn save(&self, path: &Path) -> Result<()> {
-        use std::io::{Seek, SeekFrom};
-
-        // Ensure parent directory exists
-        if let Some(parent) = path.parent() {
-            std::fs::create_dir_all(parent).map_err(|e| {
-                ConfigError::InvalidFile(format!("Failed to create directory: {}", e))
-            })?;
-        }
-
         let content = toml::to_string_pretty(self)
             .map_err(|e| ConfigError::InvalidFile(format!("Failed to serialize config: {}", e)))?;
-
-        // CFG-001 fix: Use unique temp filename to prevent race conditions
-        // Include PID and timestamp for uniqueness across concurrent processes
-        let unique_suffix = format!(
-            "{}.{}.tmp",
-            std::process::id(),
-            std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .map(|d| d.as_nanos())
-                .unwrap_or(0)
-        );
-        let temp_path = path.with_extension(unique_suffix);
-
-        // CFG-003 fix: RAII guard to clean up temp file on failure
-        struct TempFileGuard<'a> {
-            path: &'a Path,
-            keep: bool,
-        }
-        impl Drop for TempFileGuard<'_> {
-            fn drop(&mut self) {
-                if !self.keep {
-                    let _ = std::fs::remove_file(self.path);
-                }
-            }
-        }
-        let mut guard = TempFileGuard {
-            path: &temp_path,
-            keep: false,
-        };
-
-        // Open temp file WITHOUT truncate - we'll truncate AFTER acquiring lock
-        let mut file = OpenOptions::new()
-            .write(true)
-            .create(true)
-            .truncate(false) // Intentional: truncate after acquiring lock for atomicity
-            .open(&temp_path)
-            .map_err(|e| {
-                ConfigError::InvalidFile(format!("Failed to create temp config file: {}", e))
-            })?;
-
-        // CFG-002 fix: Set restrictive permissions BEFORE writing content
-        // This prevents a window where the file has default permissions
-        #[cfg(unix)]
-        {
-            let permissions = std::fs::Permissions::from_mode(0o600);
-            std::fs::set_permissions(&temp_path, permissions).map_err(|e| {
-                ConfigError::InvalidFile(format!("Failed to set temp file permissions: {}", e))
-            })?;
-        }
-
-        // Acquire exclusive lock FIRST (blocks until available)
-        file.lock_exclusive()
-            .map_err(|e| ConfigError::InvalidFile(format!("Failed to acquire file lock: {}", e)))?;
-
-        // Truncate after we have the lock
-        file.set_len(0).map_err(|e| {
-            ConfigError::InvalidFile(format!("Failed to truncate temp file: {}", e))
-        })?;
-        file.seek(SeekFrom::Start(0))
-            .map_err(|e| ConfigError::InvalidFile(format!("Failed to seek temp file: {}", e)))?;
-
-        // Write content
-        file.write_all(content.as_bytes())
-            .map_err(|e| ConfigError::InvalidFile(format!("Failed to write config: {}", e)))?;
-
-        // Sync to disk before rename for durability
-        file.sync_all()
-            .map_err(|e| ConfigError::InvalidFile(format!("Failed to sync config file: {}", e)))?;
-
-        // Lock is released when file is dropped, but rename is still atomic
-        drop(file);
-
-        // Atomic rename (same filesystem guaranteed since temp is in same dir)
-        std::fs::rename(&temp_path, path).map_err(|e| {
-            ConfigError::InvalidFile(format!("Failed to rename config file: {}", e))
-        })?;
-
-        // Success - don't delete the temp file (it's been renamed)
-        guard.keep = true;
-
-        Ok(())
+        write_config(path, &content)
     }
 
     /// Save to default path
@@ -693,6 +691,106 @@ impl ConfigFile {
     }
 }
 
+/// Write config content to `path` atomically with owner-only permissions
+///
+/// MED-003/HIGH-001 fix: Uses file-level locking with atomic write pattern
+/// to prevent TOCTOU race conditions and data loss when multiple CLI
+/// instances run simultaneously.
+///
+/// Uses write-to-temp-and-rename pattern for atomic writes:
+/// 1. Write to a unique temporary file with exclusive lock
+/// 2. Set restrictive permissions (before rename to avoid race)
+/// 3. Sync to disk
+/// 4. Rename atomically to target path
+pub(crate) fn write_config(path: &Path, content: &str) -> Result<()> {
+    use std::io::{Seek, SeekFrom};
+
+    // Ensure parent directory exists
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| ConfigError::InvalidFile(format!("Failed to create directory: {}", e)))?;
+    }
+
+    // CFG-001 fix: Use unique temp filename to prevent race conditions
+    // Include PID and timestamp for uniqueness across concurrent processes
+    let unique_suffix = format!(
+        "{}.{}.tmp",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0)
+    );
+    let temp_path = path.with_extension(unique_suffix);
+
+    // CFG-003 fix: RAII guard to clean up temp file on failure
+    struct TempFileGuard<'a> {
+        path: &'a Path,
+        keep: bool,
+    }
+    impl Drop for TempFileGuard<'_> {
+        fn drop(&mut self) {
+            if !self.keep {
+                let _ = std::fs::remove_file(self.path);
+            }
+        }
+    }
+    let mut guard = TempFileGuard {
+        path: &temp_path,
+        keep: false,
+    };
+
+    // Open temp file WITHOUT truncate - we'll truncate AFTER acquiring lock
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false) // Intentional: truncate after acquiring lock for atomicity
+        .open(&temp_path)
+        .map_err(|e| {
+            ConfigError::InvalidFile(format!("Failed to create temp config file: {}", e))
+        })?;
+
+    // CFG-002 fix: Set restrictive permissions BEFORE writing content
+    // This prevents a window where the file has default permissions
+    #[cfg(unix)]
+    {
+        let permissions = std::fs::Permissions::from_mode(0o600);
+        std::fs::set_permissions(&temp_path, permissions).map_err(|e| {
+            ConfigError::InvalidFile(format!("Failed to set temp file permissions: {}", e))
+        })?;
+    }
+
+    // Acquire exclusive lock FIRST (blocks until available)
+    file.lock_exclusive()
+        .map_err(|e| ConfigError::InvalidFile(format!("Failed to acquire file lock: {}", e)))?;
+
+    // Truncate after we have the lock
+    file.set_len(0)
+        .map_err(|e| ConfigError::InvalidFile(format!("Failed to truncate temp file: {}", e)))?;
+    file.seek(SeekFrom::Start(0))
+        .map_err(|e| ConfigError::InvalidFile(format!("Failed to seek temp file: {}", e)))?;
+
+    // Write content
+    file.write_all(content.as_bytes())
+        .map_err(|e| ConfigError::InvalidFile(format!("Failed to write config: {}", e)))?;
+
+    // Sync to disk before rename for durability
+    file.sync_all()
+        .map_err(|e| ConfigError::InvalidFile(format!("Failed to sync config file: {}", e)))?;
+
+    // Lock is released when file is dropped, but rename is still atomic
+    drop(file);
+
+    // Atomic rename (same filesystem guaranteed since temp is in same dir)
+    std::fs::rename(&temp_path, path)
+        .map_err(|e| ConfigError::InvalidFile(format!("Failed to rename config file: {}", e)))?;
+
+    // Success - don't delete the temp file (it's been renamed)
+    guard.keep = true;
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -930,13 +1028,35 @@ concurrency = 20
         assert_eq!(config.settings.retry_attempts, 3); // default
     }
 
+    #[test]
+    fn test_load_gas_operations() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let config_path = temp_dir.path().join("config.toml");
+        std::fs::write(
+            &config_path,
+            r#"
+[gas.operations]
+swap = 180000
+nft-mint = 90000
+"#,
+        )
+        .expect("Failed to write config");
+
+        let config = ConfigFile::load(&config_path).expect("Failed to load config");
+        let gas = config.gas.expect("gas section");
+        assert_eq!(gas.operations.get("swap"), Some(&180000));
+        assert_eq!(gas.operations.get("nft-mint"), Some(&90000));
+    }
+
     #[test]
     fn test_save_and_load_roundtrip() {
         let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
         let config_path = temp_dir.path().join("config.toml");
 
-        let mut config = ConfigFile::default();
-        config.etherscan_api_key = Some(SecretString::new("test_key_123".into()));
+        let mut config = ConfigFile {
+            etherscan_api_key: Some(SecretString::new("test_key_123".into())),
+            ..Default::default()
+        };
         config.settings.concurrency = 15;
         config
             .endpoints
@@ -0,0 +1,553 @@
+//! Config schema versioning and migrations
+//!
+//! Config files written before the `version` field existed are treated as
+//! version 0. Each migration rewrites the raw TOML from one version to the
+//! next, so an old file is upgraded by running every step from its version
+//! onward. The result must deserialize as a current [`ConfigFile`] before
+//! anything is written, and the original file is backed up first. Keys the
+//! current schema does not know are carried over unchanged.
+
+use super::file::write_config;
+use super::ConfigFile;
+use crate::error::{ConfigError, Result};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+#[cfg(unix)]
+use std::os::unix::fs::OpenOptionsExt;
+
+/// Schema version written by this build of ethcli
+pub const CURRENT_CONFIG_VERSION: u32 = 1;
+
+/// A single upgrade step from `from` to `from + 1`
+struct Migration {
+    from: u32,
+    apply: fn(&mut Table, &mut Vec<String>),
+}
+
+const MIGRATIONS: &[Migration] = &[Migration {
+    from: 0,
+    apply: migrate_v0_to_v1,
+}];
+
+/// What a migration changed (or would change, for a dry run)
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationReport {
+    /// Config file that was migrated
+    pub path: PathBuf,
+    /// Schema version found in the file
+    pub from_version: u32,
+    /// Schema version after migration
+    pub to_version: u32,
+    /// Human-readable list of changes, in the order they were applied
+    pub changes: Vec<String>,
+    /// Copy of the original file, if one was written
+    pub backup: Option<PathBuf>,
+}
+
+impl MigrationReport {
+    /// Whether the file was already at the current schema version
+    pub fn is_current(&self) -> bool {
+        self.from_version == self.to_version
+    }
+}
+
+/// Read the schema version from raw config TOML (missing means version 0)
+pub fn schema_version(table: &Table) -> Result<u32> {
+    match table.get("version") {
+        None => Ok(0),
+        Some(Value::Integer(v)) => u32::try_from(*v)
+            .map_err(|_| ConfigError::InvalidFile(format!("invalid schema version: {v}")).into()),
+        Some(other) => Err(ConfigError::InvalidFile(format!(
+            "schema version must be an integer, got {}",
+            other.type_str()
+        ))
+        .into()),
+    }
+}
+
+/// A config upgraded to the current schema
+struct Migrated {
+    /// What gets written: the serialized config plus any unknown keys
+    table: Table,
+    /// Schema version found in the original file
+    from: u32,
+    /// Changes, in the order they were applied
+    changes: Vec<String>,
+}
+
+/// Upgrade raw config TOML to the current schema
+///
+/// Fails without side effects if the file is from a newer ethcli or the
+/// migrated result does not match the current schema.
+fn migrate_str(content: &str) -> Result<Migrated> {
+    let mut table: Table = content.parse().map_err(ConfigError::from)?;
+    let from = schema_version(&table)?;
+    let mut changes = run_migrations(&mut table, MIGRATIONS, CURRENT_CONFIG_VERSION)?;
+    let before = table.clone();
+
+    let config: ConfigFile = Value::Table(table).try_into().map_err(|e| {
+        ConfigError::InvalidFile(format!(
+            "migrated config does not match the current schema: {e}"
+        ))
+    })?;
+    let Value::Table(mut after) = Value::try_from(&config)
+        .map_err(|e| ConfigError::InvalidFile(format!("Failed to serialize config: {e}")))?
+    else {
+        return Err(ConfigError::InvalidFile("config did not serialize to a table".into()).into());
+    };
+
+    if from < CURRENT_CONFIG_VERSION {
+        changes.extend(schema_diff(&before, &after));
+    }
+    restore_unknown(&before, &mut after);
+    Ok(Migrated {
+        table: after,
+        from,
+        changes,
+    })
+}
+
+/// Migrate the config file at `path` in place
+///
+/// The original is copied to a backup next to it before the new file is
+/// written; if the backup cannot be created the file is left untouched. With
+/// `dry_run` nothing is written and the report lists what would change.
+pub fn migrate_file(path: &Path, dry_run: bool) -> Result<MigrationReport> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| ConfigError::InvalidFile(format!("{}: {}", path.display(), e)))?;
+    let migrated = migrate_str(&content)?;
+
+    let mut report = MigrationReport {
+        path: path.to_path_buf(),
+        from_version: migrated.from,
+        to_version: CURRENT_CONFIG_VERSION,
+        changes: migrated.changes,
+        backup: None,
+    };
+    if dry_run || report.is_current() {
+        return Ok(report);
+    }
+
+    let output = toml::to_string_pretty(&migrated.table)
+        .map_err(|e| ConfigError::InvalidFile(format!("Failed to serialize config: {e}")))?;
+    let backup = backup_path(path, migrated.from);
+    write_backup(&backup, content.as_bytes())?;
+    report.backup = Some(backup);
+    write_config(path, &output)?;
+    Ok(report)
+}
+
+/// Backup location for a config at `version`, e.g. `config.toml.v0.bak`
+pub fn backup_path(path: &Path, version: u32) -> PathBuf {
+    let mut name = path
+        .file_name()
+        .map(|n| n.to_os_string())
+        .unwrap_or_else(|| "config.toml".into());
+    name.push(format!(".v{version}.bak"));
+    path.with_file_name(name)
+}
+
+/// Write the backup, refusing to replace an existing one
+fn write_backup(backup: &Path, content: &[u8]) -> Result<()> {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    // The config holds API keys, so the backup gets the same permissions
+    #[cfg(unix)]
+    options.mode(0o600);
+
+    let mut file = options.open(backup).map_err(|e| {
+        let hint = if e.kind() == std::io::ErrorKind::AlreadyExists {
+            " (move the existing backup aside and retry)"
+        } else {
+            ""
+        };
+        ConfigError::InvalidFile(format!(
+            "Failed to create backup {}: {}{}",
+            backup.display(),
+            e,
+            hint
+        ))
+    })?;
+    file.write_all(content)
+        .and_then(|_| file.sync_all())
+        .map_err(|e| {
+            ConfigError::InvalidFile(format!(
+                "Failed to write backup {}: {}",
+                backup.display(),
+                e
+            ))
+        })?;
+    Ok(())
+}
+
+fn run_migrations(table: &mut Table, steps: &[Migration], target: u32) -> Result<Vec<String>> {
+    let mut version = schema_version(table)?;
+    if version > target {
+        return Err(ConfigError::UnsupportedVersion {
+            found: version,
+            supported: target,
+        }
+        .into());
+    }
+
+    let mut changes = Vec::new();
+    while version < target {
+        let step = steps.iter().find(|m| m.from == version).ok_or_else(|| {
+            ConfigError::InvalidFile(format!("no migration from schema version {version}"))
+        })?;
+        (step.apply)(table, &mut changes);
+        version += 1;
+        table.insert("version".to_string(), Value::Integer(version.into()));
+        changes.push(format!("set version = {version}"));
+    }
+    Ok(changes)
+}
+
+/// Move `old` to `new` within a table, keeping `new` if both are present
+fn rename(table: &mut Table, prefix: &str, old: &str, new: &str, changes: &mut Vec<String>) {
+    let Some(value) = table.remove(old) else {
+        return;
+    };
+    if table.contains_key(new) {
+        changes.push(format!(
+            "dropped {prefix}{old} (superseded by {prefix}{new})"
+        ));
+    } else {
+        table.insert(new.to_string(), value);
+        changes.push(format!("renamed {prefix}{old} to {prefix}{new}"));
+    }
+}
+
+/// Unversioned configs from before the `version` field
+///
+/// The only key renamed in a shipped release is the Etherscan key, which
+/// early ethcli wrote as `etherscan_key` until every command was switched to
+/// read `etherscan_api_key` (CHANGELOG, commit acf568f). Other v0 keys
+/// already match the current schema.
+fn migrate_v0_to_v1(table: &mut Table, changes: &mut Vec<String>) {
+    rename(table, "", "etherscan_key", "etherscan_api_key", changes);
+}
+
+/// Keys filled in from defaults and keys the current schema does not know
+///
+/// Array indices are collapsed to `[*]` so a default applied to every
+/// endpoint is reported once.
+fn schema_diff(before: &Table, after: &Table) -> Vec<String> {
+    let mut added = BTreeSet::new();
+    let mut dropped = BTreeSet::new();
+    diff_tables(before, after, "", &mut added, &mut dropped);
+
+    let mut changes: Vec<String> = added
+        .into_iter()
+        .map(|key| format!("added default {key}"))
+        .collect();
+    changes.extend(
+        dropped
+            .into_iter()
+            .map(|key| format!("kept unrecognized key {key}")),
+    );
+    changes
+}
+
+fn diff_tables(
+    before: &Table,
+    after: &Table,
+    prefix: &str,
+    added: &mut BTreeSet<String>,
+    dropped: &mut BTreeSet<String>,
+) {
+    for (key, value) in before {
+        let path = format!("{prefix}{key}");
+        match (value, after.get(key)) {
+            (_, None) => {
+                dropped.insert(path);
+            }
+            (Value::Table(b), Some(Value::Table(a))) => {
+                diff_tables(b, a, &format!("{path}."), added, dropped);
+            }
+            (Value::Array(b), Some(Value::Array(a))) => {
+                for (b, a) in b.iter().zip(a) {
+                    if let (Value::Table(b), Value::Table(a)) = (b, a) {
+                        diff_tables(b, a, &format!("{path}[*]."), added, dropped);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    for (key, value) in after {
+        if before.contains_key(key) {
+            continue;
+        }
+        if !is_empty(value) {
+            added.insert(format!("{prefix}{key}"));
+        }
+    }
+}
+
+/// Copy keys the current schema does not know from `before` into `after`
+///
+/// Mirrors [`diff_tables`], so exactly the keys reported as unrecognized are
+/// written back.
+fn restore_unknown(before: &Table, after: &mut Table) {
+    for (key, value) in before {
+        match (value, after.get_mut(key)) {
+            (_, None) => {
+                after.insert(key.clone(), value.clone());
+            }
+            (Value::Table(b), Some(Value::Table(a))) => restore_unknown(b, a),
+            (Value::Array(b), Some(Value::Array(a))) => {
+                for (b, a) in b.iter().zip(a.iter_mut()) {
+                    if let (Value::Table(b), Value::Table(a)) = (b, a) {
+                        restore_unknown(b, a);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Empty lists and sections are serialization noise, not real defaults
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Table(t) => t.values().all(is_empty),
+        Value::Array(a) => a.is_empty(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::NodeType;
+    use secrecy::ExposeSecret;
+
+    impl Migrated {
+        fn config(&self) -> ConfigFile {
+            Value::Table(self.table.clone()).try_into().unwrap()
+        }
+    }
+
+    const LEGACY: &str = r#"
+etherscan_key = "abc"
+colour = "blue"
+
+[settings]
+concurrency = 8
+timeout_seconds = 45
+theme = "dark"
+
+[[endpoints]]
+url = "https://a.example.com"
+max_block_range = 2000
+node_type = "archive"
+label = "primary"
+
+[[endpoints]]
+url = "https://b.example.com"
+"#;
+
+    #[test]
+    fn test_schema_version() {
+        assert_eq!(schema_version(&Table::new()).unwrap(), 0);
+        let table: Table = "version = 3".parse().unwrap();
+        assert_eq!(schema_version(&table).unwrap(), 3);
+        let table: Table = "version = \"1\"".parse().unwrap();
+        assert!(schema_version(&table).is_err());
+        let table: Table = "version = -1".parse().unwrap();
+        assert!(schema_version(&table).is_err());
+    }
+
+    #[test]
+    fn test_migrate_legacy_layout() {
+        let migrated = migrate_str(LEGACY).unwrap();
+        let config = migrated.config();
+        assert_eq!(migrated.from, 0);
+        assert_eq!(config.version, CURRENT_CONFIG_VERSION);
+        assert_eq!(
+            config.etherscan_api_key.as_ref().map(|k| k.expose_secret()),
+            Some("abc")
+        );
+        assert_eq!(config.settings.concurrency, 8);
+        assert_eq!(config.settings.timeout_seconds, 45);
+        assert_eq!(config.endpoints[0].max_block_range, 2000);
+        assert_eq!(config.endpoints[0].node_type, NodeType::Archive);
+
+        let changes = &migrated.changes;
+        for expected in [
+            "renamed etherscan_key to etherscan_api_key",
+            "set version = 1",
+            "added default settings.checkpoint_interval",
+            "added default endpoints[*].max_logs",
+            "kept unrecognized key colour",
+            "kept unrecognized key settings.theme",
+            "kept unrecognized key endpoints[*].label",
+        ] {
+            assert!(
+                changes.iter().any(|c| c == expected),
+                "missing {expected:?} in {changes:#?}"
+            );
+        }
+        assert!(!changes.iter().any(|c| c.contains("debug_rpc_urls")));
+        // Defaults on array entries are reported once
+        assert_eq!(
+            changes
+                .iter()
+                .filter(|c| c.as_str() == "added default endpoints[*].priority")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_unknown_keys_are_written_back() {
+        let table = migrate_str(LEGACY).unwrap().table;
+        assert_eq!(table["colour"].as_str(), Some("blue"));
+        assert_eq!(table["settings"]["theme"].as_str(), Some("dark"));
+        assert_eq!(table["endpoints"][0]["label"].as_str(), Some("primary"));
+        assert!(!table.contains_key("etherscan_key"));
+    }
+
+    #[test]
+    fn test_new_key_wins_over_legacy_key() {
+        let migrated =
+            migrate_str("etherscan_key = \"old\"\netherscan_api_key = \"new\"\n").unwrap();
+        assert_eq!(
+            migrated
+                .config()
+                .etherscan_api_key
+                .as_ref()
+                .map(|k| k.expose_secret()),
+            Some("new")
+        );
+        assert!(migrated
+            .changes
+            .contains(&"dropped etherscan_key (superseded by etherscan_api_key)".into()));
+    }
+
+    #[test]
+    fn test_current_version_is_unchanged() {
+        let migrated = migrate_str("version = 1\n[settings]\n").unwrap();
+        assert_eq!(migrated.from, CURRENT_CONFIG_VERSION);
+        assert!(migrated.changes.is_empty());
+    }
+
+    #[test]
+    fn test_newer_version_is_rejected() {
+        let err = migrate_str("version = 99\n").err().unwrap();
+        assert!(err.to_string().contains("newer"), "{err}");
+    }
+
+    #[test]
+    fn test_migrations_run_in_sequence() {
+        fn step_a(table: &mut Table, changes: &mut Vec<String>) {
+            table.insert("a".into(), Value::Boolean(true));
+            changes.push("a".into());
+        }
+        fn step_b(table: &mut Table, changes: &mut Vec<String>) {
+            assert!(table.contains_key("a"));
+            table.insert("b".into(), Value::Boolean(true));
+            changes.push("b".into());
+        }
+        let steps = [
+            Migration {
+                from: 1,
+                apply: step_b,
+            },
+            Migration {
+                from: 0,
+                apply: step_a,
+            },
+        ];
+
+        let mut table = Table::new();
+        let changes = run_migrations(&mut table, &steps, 2).unwrap();
+        assert_eq!(
+            changes,
+            vec!["a", "set version = 1", "b", "set version = 2"]
+        );
+
+        // Starting part-way only runs the remaining steps
+        let mut table: Table = "version = 1\na = true".parse().unwrap();
+        let changes = run_migrations(&mut table, &steps, 2).unwrap();
+        assert_eq!(changes, vec!["b", "set version = 2"]);
+
+        // A gap in the chain is an error, not a silent skip
+        let mut table = Table::new();
+        assert!(run_migrations(&mut table, &steps[..1], 2).is_err());
+    }
+
+    #[test]
+    fn test_migrate_file_writes_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, LEGACY).unwrap();
+
+        let dry = migrate_file(&path, true).unwrap();
+        assert!(dry.backup.is_none());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), LEGACY);
+
+        let report = migrate_file(&path, false).unwrap();
+        let backup = report.backup.unwrap();
+        assert_eq!(backup, dir.path().join("config.toml.v0.bak"));
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), LEGACY);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&backup).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        let migrated = ConfigFile::load(&path).unwrap();
+        assert_eq!(migrated.version, CURRENT_CONFIG_VERSION);
+        assert_eq!(migrated.settings.timeout_seconds, 45);
+        let written: Table = std::fs::read_to_string(&path).unwrap().parse().unwrap();
+        assert_eq!(written["colour"].as_str(), Some("blue"));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        // Running again is a no-op
+        let again = migrate_file(&path, false).unwrap();
+        assert!(again.is_current());
+        assert!(again.backup.is_none());
+    }
+
+    #[test]
+    fn test_existing_backup_is_never_replaced() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, LEGACY).unwrap();
+        std::fs::write(dir.path().join("config.toml.v0.bak"), "keep me").unwrap();
+
+        let err = migrate_file(&path, false).unwrap_err();
+        assert!(err.to_string().contains("backup"), "{err}");
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), LEGACY);
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("config.toml.v0.bak")).unwrap(),
+            "keep me"
+        );
+    }
+
+    #[test]
+    fn test_invalid_result_is_not_written() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let content = "[settings]\nconcurrency = \"lots\"\n";
+        std::fs::write(&path, content).unwrap();
+
+        let err = migrate_file(&path, false).unwrap_err();
+        assert!(err.to_string().contains("current schema"), "{err}");
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), content);
+        assert!(!dir.path().join("config.toml.v0.bak").exists());
+    }
+}
@@ -5,6 +5,7 @@ mod blacklist;
 mod chain;
 mod endpoint;
 mod file;
+mod migrate;
 
 pub use addressbook::{AddressBook, AddressEntry};
 pub use blacklist::{BlacklistEntry, TokenBlacklist};
@@ -14,11 +15,14 @@ pub use endpoint::{
     MIN_TX_FETCH_CONCURRENCY,
 };
 pub use file::{
-    AlchemyConfig, ChainlinkConfig, ConfigFile, DuneConfig, DuneSimConfig, GeckoConfig,
-    LlamaConfig, MoralisConfig, OneInchConfig, SoloditConfig, TenderlyConfig, ZeroxConfig,
+    AlchemyConfig, ChainlinkConfig, ConfigFile, DuneConfig, DuneSimConfig, GasConfig, GeckoConfig,
+    LlamaConfig, MoralisConfig, OneInchConfig, SavedQuery, SoloditConfig, TenderlyConfig,
+    UpdateConfig, ZeroxConfig,
 };
+pub use migrate::{migrate_file, MigrationReport, CURRENT_CONFIG_VERSION};
 
 use crate::error::{ConfigError, Result};
+use alloy::primitives::B256;
 use std::path::PathBuf;
 
 /// Main configuration for the log fetcher
@@ -26,12 +30,16 @@ use std::path::PathBuf;
 pub struct Config {
     /// Chain to query
     pub chain: Chain,
-    /// Contract address
+    /// Contract address (empty = logs from any contract)
     pub contract: String,
     /// Event filters (names, signatures, or topic hashes). Empty = all events
     pub events: Vec<String>,
+    /// Required values for topics 1 to 3 (the indexed event arguments)
+    pub indexed_topics: [Option<B256>; 3],
     /// ABI file path (optional)
     pub abi_path: Option<PathBuf>,
+    /// Inline human-readable ABI (optional, one signature per line)
+    pub abi_str: Option<String>,
     /// Block range to fetch
     pub block_range: BlockRange,
     /// Output configuration
@@ -52,6 +60,11 @@ pub struct Config {
     pub raw: bool,
     /// Auto-detect from_block from contract creation
     pub auto_from_block: bool,
+    /// Fetch the ABI of each emitting contract and fall back to the signature
+    /// database for events no ABI covers
+    pub auto_abi: bool,
+    /// Maximum number of contract ABIs fetched when `auto_abi` is set
+    pub auto_abi_limit: usize,
 }
 
 /// Block range specification
@@ -161,6 +174,37 @@ impl Default for RpcConfig {
     }
 }
 
+/// Build the RPC config from config file settings, for commands without RPC args
+pub fn build_default_rpc_config(config_file: &Option<ConfigFile>) -> RpcConfig {
+    let mut rpc_config = RpcConfig::default();
+
+    // Add custom endpoints from config file
+    if let Some(cf) = config_file {
+        for endpoint in &cf.endpoints {
+            if !rpc_config.endpoints.iter().any(|e| e.url == endpoint.url) {
+                rpc_config.endpoints.push(endpoint.clone());
+            }
+        }
+    }
+
+    // Add disabled endpoints from config file
+    if let Some(cf) = config_file {
+        rpc_config
+            .exclude_endpoints
+            .extend(cf.disabled_endpoints.urls.clone());
+    }
+
+    // Apply config file defaults
+    if let Some(cf) = config_file {
+        rpc_config.timeout_secs = cf.settings.timeout_seconds;
+        rpc_config.max_retries = cf.settings.retry_attempts;
+        rpc_config.concurrency = cf.settings.concurrency;
+        rpc_config.proxy = cf.proxy_config();
+    }
+
+    rpc_config
+}
+
 /// Proxy configuration
 #[derive(Debug, Clone)]
 pub struct ProxyConfig {
@@ -219,7 +263,9 @@ pub struct ConfigBuilder {
     chain: Option<Chain>,
     contract: Option<String>,
     events: Vec<String>,
+    indexed_topics: [Option<B256>; 3],
     abi_path: Option<PathBuf>,
+    abi_str: Option<String>,
     from_block: Option<u64>,
     to_block: Option<BlockNumber>,
     output_path: Option<PathBuf>,
@@ -232,6 +278,8 @@ pub struct ConfigBuilder {
     quiet: bool,
     raw: bool,
     auto_from_block: bool,
+    auto_abi: Option<bool>,
+    auto_abi_limit: Option<usize>,
 }
 
 impl ConfigBuilder {
@@ -249,6 +297,26 @@ impl ConfigBuilder {
         self
     }
 
+    /// Match logs from every contract instead of one
+    ///
+    /// Needs explicit events, since there is no contract ABI to resolve
+    /// names from or decode against.
+    pub fn any_contract(mut self) -> Self {
+        self.contract = Some(String::new());
+        self
+    }
+
+    /// Require topic `position` (1 to 3) to equal `topic`
+    pub fn indexed_topic(mut self, position: usize, topic: B256) -> Self {
+        if let Some(slot) = position
+            .checked_sub(1)
+            .and_then(|i| self.indexed_topics.get_mut(i))
+        {
+            *slot = Some(topic);
+        }
+        self
+    }
+
     pub fn event(mut self, signature: impl Into<String>) -> Self {
         self.events.push(signature.into());
         self
@@ -264,6 +332,11 @@ impl ConfigBuilder {
         self
     }
 
+    pub fn abi_str(mut self, abi: impl Into<String>) -> Self {
+        self.abi_str = Some(abi.into());
+        self
+    }
+
     pub fn from_block(mut self, block: u64) -> Self {
         self.from_block = Some(block);
         self
@@ -339,6 +412,18 @@ impl ConfigBuilder {
         self
     }
 
+    /// Resolve ABIs per emitting address (default: on)
+    pub fn auto_abi(mut self, auto: bool) -> Self {
+        self.auto_abi = Some(auto);
+        self
+    }
+
+    /// Cap the number of contract ABIs fetched by `auto_abi`
+    pub fn auto_abi_limit(mut self, limit: usize) -> Self {
+        self.auto_abi_limit = Some(limit);
+        self
+    }
+
     pub fn rpc_config(mut self, rpc: RpcConfig) -> Self {
         self.rpc = rpc;
         self
@@ -348,6 +433,12 @@ impl ConfigBuilder {
         let contract = self
             .contract
             .ok_or_else(|| ConfigError::MissingField("contract".to_string()))?;
+        if contract.is_empty() && self.events.is_empty() {
+            return Err(ConfigError::MissingField(
+                "events (required when matching any contract)".to_string(),
+            )
+            .into());
+        }
 
         let from_block = self.from_block.unwrap_or(0);
         let to_block = self.to_block.unwrap_or(BlockNumber::Latest);
@@ -379,7 +470,9 @@ impl ConfigBuilder {
             chain: self.chain.unwrap_or_default(),
             contract,
             events: self.events,
+            indexed_topics: self.indexed_topics,
             abi_path: self.abi_path,
+            abi_str: self.abi_str,
             block_range,
             output: OutputConfig {
                 path: self.output_path,
@@ -395,6 +488,10 @@ impl ConfigBuilder {
             quiet: self.quiet,
             raw: self.raw,
             auto_from_block: self.auto_from_block,
+            auto_abi: self.auto_abi.unwrap_or(true),
+            auto_abi_limit: self
+                .auto_abi_limit
+                .unwrap_or(crate::abi::DEFAULT_AUTO_ABI_LIMIT),
         })
     }
 }
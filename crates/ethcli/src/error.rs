@@ -184,6 +184,9 @@ pub enum AbiError {
     #[error("Failed to decode log: {0}")]
     DecodeError(String),
 
+    #[error("Failed to decode calldata: {0}")]
+    CalldataDecode(String),
+
     #[error("ABI file not found: {0}")]
     FileNotFound(String),
 
@@ -210,8 +213,14 @@ pub enum ConfigError {
     #[error("Missing required field: {0}")]
     MissingField(String),
 
+    #[error("Invalid amount: {0}")]
+    InvalidAmount(String),
+
     #[error("Config file parse error: {0}")]
     ParseError(#[from] toml::de::Error),
+
+    #[error("Config schema version {found} is newer than this ethcli supports ({supported}); upgrade ethcli")]
+    UnsupportedVersion { found: u32, supported: u32 },
 }
 
 /// Output-related errors
@@ -232,6 +241,15 @@ pub enum OutputError {
 
     #[error("Unsupported format: {0}")]
     UnsupportedFormat(String),
+
+    #[error("Incompatible database schema: {0}")]
+    Schema(String),
+
+    #[error("Rejected query: {0}")]
+    InvalidQuery(String),
+
+    #[error("Invalid query parameter: {0}")]
+    InvalidParam(String),
 }
 
 /// Checkpoint-related errors
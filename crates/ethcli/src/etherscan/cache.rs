@@ -43,6 +43,20 @@ pub struct CacheEntry {
     pub signature: String,
     /// When this entry was added (Unix timestamp)
     pub timestamp: u64,
+    /// Imported by the user: never expires and is preferred over 4byte
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pinned: bool,
+}
+
+/// Result of [`SignatureCache::import_signatures`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportStats {
+    /// Function signatures added or changed
+    pub functions_added: usize,
+    /// Event signatures added or changed
+    pub events_added: usize,
+    /// Signatures that were already cached as given
+    pub already_cached: usize,
 }
 
 /// Signature cache data structure
@@ -179,9 +193,9 @@ impl SignatureCache {
         let negative_ttl_secs = NEGATIVE_CACHE_TTL_SECS;
 
         data.events
-            .retain(|_, e| now.saturating_sub(e.timestamp) <= ttl_secs);
+            .retain(|_, e| e.pinned || now.saturating_sub(e.timestamp) <= ttl_secs);
         data.functions
-            .retain(|_, e| now.saturating_sub(e.timestamp) <= ttl_secs);
+            .retain(|_, e| e.pinned || now.saturating_sub(e.timestamp) <= ttl_secs);
         data.abis
             .retain(|_, e| now.saturating_sub(e.timestamp) <= abi_ttl_secs);
         data.not_found
@@ -198,6 +212,9 @@ impl SignatureCache {
 
     /// Check if an entry is expired
     fn is_expired(&self, entry: &CacheEntry) -> bool {
+        if entry.pinned {
+            return false;
+        }
         let now = Self::now();
         let age = now.saturating_sub(entry.timestamp);
         age > self.ttl.as_secs()
@@ -226,6 +243,7 @@ impl SignatureCache {
                 CacheEntry {
                     signature: signature.to_string(),
                     timestamp: Self::now(),
+                    pinned: false,
                 },
             );
         }
@@ -256,6 +274,7 @@ impl SignatureCache {
                 CacheEntry {
                     signature: signature.to_string(),
                     timestamp: Self::now(),
+                    pinned: false,
                 },
             );
         }
@@ -274,6 +293,7 @@ impl SignatureCache {
                     CacheEntry {
                         signature: signature.clone(),
                         timestamp: now,
+                        pinned: false,
                     },
                 );
             }
@@ -292,6 +312,7 @@ impl SignatureCache {
                     CacheEntry {
                         signature: signature.clone(),
                         timestamp: now,
+                        pinned: false,
                     },
                 );
             }
@@ -299,6 +320,96 @@ impl SignatureCache {
         self.maybe_save();
     }
 
+    /// Get an imported function signature by 4-byte selector
+    pub fn get_imported_function(&self, selector: &str) -> Option<String> {
+        let data = self.data.read();
+        data.functions
+            .get(&selector.to_lowercase())
+            .filter(|e| e.pinned)
+            .map(|e| e.signature.clone())
+    }
+
+    /// Get an imported event signature by topic0 hash
+    pub fn get_imported_event(&self, topic0: &str) -> Option<String> {
+        let data = self.data.read();
+        data.events
+            .get(&topic0.to_lowercase())
+            .filter(|e| e.pinned)
+            .map(|e| e.signature.clone())
+    }
+
+    /// Import function and event signatures and write the cache to disk
+    ///
+    /// Imported entries are pinned: they never expire and lookups use them
+    /// without querying 4byte.directory. An entry already cached with the
+    /// same signature is pinned and counted as already cached; a different
+    /// signature for the same key is replaced. Imported keys are removed
+    /// from the negative cache.
+    pub fn import_signatures(
+        &self,
+        functions: &[(String, String)],
+        events: &[(String, String)],
+    ) -> std::io::Result<ImportStats> {
+        let mut stats = ImportStats::default();
+        {
+            let mut data = self.data.write();
+            let now = Self::now();
+            let data = &mut *data;
+            for (selector, signature) in functions {
+                if Self::import_entry(
+                    &mut data.functions,
+                    &mut data.not_found,
+                    selector,
+                    signature,
+                    now,
+                ) {
+                    stats.functions_added += 1;
+                } else {
+                    stats.already_cached += 1;
+                }
+            }
+            for (topic, signature) in events {
+                if Self::import_entry(&mut data.events, &mut data.not_found, topic, signature, now)
+                {
+                    stats.events_added += 1;
+                } else {
+                    stats.already_cached += 1;
+                }
+            }
+        }
+        self.save_to_file()?;
+        Ok(stats)
+    }
+
+    /// Pin one imported entry; returns whether it was added or changed
+    fn import_entry(
+        map: &mut HashMap<String, CacheEntry>,
+        not_found: &mut HashMap<String, u64>,
+        key: &str,
+        signature: &str,
+        now: u64,
+    ) -> bool {
+        let key = key.to_lowercase();
+        not_found.remove(&key);
+        match map.get_mut(&key) {
+            Some(entry) if entry.signature == signature => {
+                entry.pinned = true;
+                false
+            }
+            _ => {
+                map.insert(
+                    key,
+                    CacheEntry {
+                        signature: signature.to_string(),
+                        timestamp: now,
+                        pinned: true,
+                    },
+                );
+                true
+            }
+        }
+    }
+
     // ========================================================================
     // Negative Cache Methods
     // ========================================================================
@@ -454,12 +565,12 @@ impl SignatureCache {
         let valid_events = data
             .events
             .values()
-            .filter(|e| now.saturating_sub(e.timestamp) <= ttl_secs)
+            .filter(|e| e.pinned || now.saturating_sub(e.timestamp) <= ttl_secs)
             .count();
         let valid_functions = data
             .functions
             .values()
-            .filter(|e| now.saturating_sub(e.timestamp) <= ttl_secs)
+            .filter(|e| e.pinned || now.saturating_sub(e.timestamp) <= ttl_secs)
             .count();
         let valid_abis = data
             .abis
@@ -472,6 +583,8 @@ impl SignatureCache {
             valid_events,
             total_functions: data.functions.len(),
             valid_functions,
+            imported_events: data.events.values().filter(|e| e.pinned).count(),
+            imported_functions: data.functions.values().filter(|e| e.pinned).count(),
             total_abis: data.abis.len(),
             valid_abis,
             cache_path: self.path.clone(),
@@ -526,6 +639,8 @@ pub struct CacheStats {
     pub valid_events: usize,
     pub total_functions: usize,
     pub valid_functions: usize,
+    pub imported_events: usize,
+    pub imported_functions: usize,
     pub total_abis: usize,
     pub valid_abis: usize,
     pub cache_path: PathBuf,
@@ -586,6 +701,48 @@ mod tests {
         assert_eq!(sig, Some("transfer(address,uint256)".to_string()));
     }
 
+    #[test]
+    fn test_import_signatures() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("test_cache.json");
+        let cache = SignatureCache::with_path(path.clone());
+        cache.set_function("0xa9059cbb", "transfer(address,uint256)");
+        cache.set_not_found("0x12345678");
+
+        let functions = vec![
+            (
+                "0xA9059CBB".to_string(),
+                "transfer(address,uint256)".to_string(),
+            ),
+            ("0x12345678".to_string(), "custom(uint256)".to_string()),
+        ];
+        let events = vec![("0xabc123".to_string(), "TestEvent(uint256)".to_string())];
+        let stats = cache.import_signatures(&functions, &events).unwrap();
+        assert_eq!(
+            stats,
+            ImportStats {
+                functions_added: 1,
+                events_added: 1,
+                already_cached: 1,
+            }
+        );
+        assert!(!cache.is_not_found("0x12345678"));
+        assert_eq!(
+            cache.get_imported_function("0xa9059cbb"),
+            Some("transfer(address,uint256)".to_string())
+        );
+
+        // Re-importing adds nothing; pinned entries survive a reload
+        let stats = cache.import_signatures(&functions, &events).unwrap();
+        assert_eq!(stats.already_cached, 3);
+        let reloaded = SignatureCache::with_path(path);
+        assert_eq!(
+            reloaded.get_imported_event("0xabc123"),
+            Some("TestEvent(uint256)".to_string())
+        );
+        assert_eq!(reloaded.stats().imported_functions, 2);
+    }
+
     #[test]
     fn test_persistence() {
         let dir = tempdir().unwrap();
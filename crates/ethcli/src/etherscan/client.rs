@@ -148,13 +148,18 @@ fn normalize_hex_selector(selector: &str) -> Cow<'_, str> {
     }
 }
 
-use crate::error::{AbiError, Result};
+use crate::error::{AbiError, Error, Result};
+use crate::etherscan::pages::{empty_page_ok, PageCursor};
 use crate::etherscan::SignatureCache;
 use crate::utils::{
     decode_string_from_hex, decode_uint8_from_hex, get_shared_http_client, urlencoding_encode,
     TokenMetadata,
 };
+use alloy::primitives::Address;
 use alloy_chains::Chain as AlloyChain;
+use foundry_block_explorers::account::{
+    ERC20TokenTransferEvent, NormalTransaction, Sort, TokenQueryOption,
+};
 use foundry_block_explorers::Client as EtherscanClient;
 use std::borrow::Cow;
 use std::sync::Arc;
@@ -256,6 +261,11 @@ impl Client {
         // PERF-009 fix: avoid allocation if already normalized
         let normalized = normalize_hex_selector(selector);
 
+        // Imported signatures are authoritative and work offline
+        if let Some(sig) = self.cache.get_imported_function(&normalized) {
+            return Some(vec![sig]);
+        }
+
         // Check negative cache first - skip lookup if we recently found nothing
         if self.cache.is_not_found(&normalized) {
             tracing::debug!("Selector {} in negative cache, skipping lookup", normalized);
@@ -332,6 +342,11 @@ impl Client {
         // PERF-009 fix: avoid allocation if already normalized
         let normalized = normalize_hex_selector(topic0);
 
+        // Imported signatures are authoritative and work offline
+        if let Some(sig) = self.cache.get_imported_event(&normalized) {
+            return Some(vec![sig]);
+        }
+
         // Check negative cache first - skip lookup if we recently found nothing
         if self.cache.is_not_found(&normalized) {
             tracing::debug!("Event {} in negative cache, skipping lookup", normalized);
@@ -387,6 +402,58 @@ impl Client {
         None
     }
 
+    // ========================================================================
+    // Paginated list endpoints
+    // ========================================================================
+
+    /// Fetch an address's transactions page by page
+    ///
+    /// Each page is handed to `on_page` before the next one is requested, so
+    /// callers can write results out as they arrive. Returns the number of
+    /// transactions fetched.
+    pub async fn stream_transactions(
+        &self,
+        address: &Address,
+        mut cursor: PageCursor,
+        sort: Sort,
+        mut on_page: impl FnMut(Vec<NormalTransaction>) -> Result<()>,
+    ) -> Result<usize> {
+        let mut total = 0;
+        while let Some(params) = cursor.next_params(sort) {
+            let page = empty_page_ok(self.inner.get_transactions(address, Some(params)).await)
+                .map_err(|e| Error::Other(format!("Etherscan request failed: {}", e)))?;
+            cursor.advance(page.len());
+            total += page.len();
+            on_page(page)?;
+        }
+        Ok(total)
+    }
+
+    /// Fetch ERC20 transfer events page by page
+    ///
+    /// See [`Client::stream_transactions`].
+    pub async fn stream_erc20_transfers(
+        &self,
+        query: TokenQueryOption,
+        mut cursor: PageCursor,
+        sort: Sort,
+        mut on_page: impl FnMut(Vec<ERC20TokenTransferEvent>) -> Result<()>,
+    ) -> Result<usize> {
+        let mut total = 0;
+        while let Some(params) = cursor.next_params(sort) {
+            let page = empty_page_ok(
+                self.inner
+                    .get_erc20_token_transfer_events(query.clone(), Some(params))
+                    .await,
+            )
+            .map_err(|e| Error::Other(format!("Etherscan request failed: {}", e)))?;
+            cursor.advance(page.len());
+            total += page.len();
+            on_page(page)?;
+        }
+        Ok(total)
+    }
+
     // ========================================================================
     // Token metadata via eth_call (our unique value-add)
     // ========================================================================
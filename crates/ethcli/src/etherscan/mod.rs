@@ -4,11 +4,15 @@
 //! - Signature cache for function selectors and event topics
 //! - 4byte.directory integration for signature lookups
 //! - Token metadata via eth_call
+//! - Page-by-page iteration over list endpoints
 
 pub mod cache;
 mod client;
+mod pages;
 
 pub use cache::{
-    CacheData, CacheEntry, CacheStats, SignatureCache, TokenCacheEntry, TokenMetadataCache,
+    CacheData, CacheEntry, CacheStats, ImportStats, SignatureCache, TokenCacheEntry,
+    TokenMetadataCache,
 };
 pub use client::Client;
+pub use pages::{empty_page_ok, PageCursor, MAX_RESULT_WINDOW};
@@ -0,0 +1,140 @@
+//! Pagination over Etherscan list endpoints
+//!
+//! `txlist`, `tokentx` and friends are paged with `page`/`offset` and stop
+//! serving results once `page * offset` passes 10,000. [`PageCursor`] walks
+//! those pages so callers can write each one out before fetching the next.
+
+use foundry_block_explorers::account::{Sort, TxListParams};
+use foundry_block_explorers::errors::EtherscanError;
+
+/// Largest `page * offset` window Etherscan serves for list endpoints
+pub const MAX_RESULT_WINDOW: u64 = 10_000;
+
+/// Cursor over consecutive pages of an Etherscan list endpoint
+#[derive(Debug, Clone)]
+pub struct PageCursor {
+    /// Next page to fetch (1-indexed)
+    page: u64,
+    /// Results per page
+    page_size: u64,
+    /// Last page to fetch, or `None` to continue until the results run out
+    last_page: Option<u64>,
+    /// Whether the last fetched page was short (no more results)
+    exhausted: bool,
+}
+
+impl PageCursor {
+    /// Fetch only `page`
+    pub fn single(page: u64, page_size: u64) -> Self {
+        Self {
+            page: page.max(1),
+            page_size,
+            last_page: Some(page.max(1)),
+            exhausted: false,
+        }
+    }
+
+    /// Fetch every page from `first_page` until the results run out
+    pub fn from_page(first_page: u64, page_size: u64) -> Self {
+        Self {
+            last_page: None,
+            ..Self::single(first_page, page_size)
+        }
+    }
+
+    /// Request parameters for the next page, or `None` when done
+    pub fn next_params(&self, sort: Sort) -> Option<TxListParams> {
+        if self.is_done() {
+            return None;
+        }
+        Some(TxListParams {
+            start_block: 0,
+            end_block: 99999999,
+            page: self.page,
+            offset: self.page_size,
+            sort,
+        })
+    }
+
+    /// Record that a page with `len` results was fetched
+    pub fn advance(&mut self, len: usize) {
+        self.exhausted = (len as u64) < self.page_size;
+        self.page += 1;
+    }
+
+    /// Next page number
+    pub fn page(&self) -> u64 {
+        self.page
+    }
+
+    /// Whether there is nothing left to fetch
+    pub fn is_done(&self) -> bool {
+        self.exhausted
+            || self.page_size == 0
+            || self.last_page.is_some_and(|last| self.page > last)
+            || self.page.saturating_mul(self.page_size) > MAX_RESULT_WINDOW
+    }
+}
+
+/// Treat Etherscan's "No transactions found" reply as an empty page
+///
+/// List endpoints answer a page past the end with an error status rather
+/// than an empty result.
+pub fn empty_page_ok<T>(
+    result: std::result::Result<Vec<T>, EtherscanError>,
+) -> std::result::Result<Vec<T>, EtherscanError> {
+    match result {
+        Err(EtherscanError::ErrorResponse { ref message, .. })
+            if message.starts_with("No transactions found")
+                || message.starts_with("No token transfers found") =>
+        {
+            Ok(Vec::new())
+        }
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_page() {
+        let mut cursor = PageCursor::single(3, 50);
+        let params = cursor.next_params(Sort::Desc).unwrap();
+        assert_eq!((params.page, params.offset), (3, 50));
+        cursor.advance(50);
+        assert!(cursor.next_params(Sort::Desc).is_none());
+    }
+
+    #[test]
+    fn test_stops_on_short_page() {
+        let mut cursor = PageCursor::from_page(1, 100);
+        cursor.advance(100);
+        assert_eq!(cursor.next_params(Sort::Asc).unwrap().page, 2);
+        cursor.advance(7);
+        assert!(cursor.is_done());
+    }
+
+    #[test]
+    fn test_stops_at_result_window() {
+        let mut cursor = PageCursor::from_page(1, 5_000);
+        cursor.advance(5_000);
+        cursor.advance(5_000);
+        assert_eq!(cursor.page(), 3);
+        assert!(cursor.next_params(Sort::Desc).is_none());
+    }
+
+    #[test]
+    fn test_empty_page_ok() {
+        let no_results: std::result::Result<Vec<u8>, _> = Err(EtherscanError::ErrorResponse {
+            status: "0".to_string(),
+            message: "No transactions found".to_string(),
+            result: Some("[]".to_string()),
+        });
+        assert!(empty_page_ok(no_results).unwrap().is_empty());
+
+        let rate_limited: std::result::Result<Vec<u8>, _> = Err(EtherscanError::RateLimitExceeded);
+        assert!(empty_page_ok(rate_limited).is_err());
+    }
+}
@@ -1,16 +1,20 @@
 //! Main log fetcher coordinator
 
-use crate::abi::{AbiFetcher, DecodedLog, EventSignature, LogDecoder};
-use crate::checkpoint::CheckpointManager;
-use crate::config::{BlockNumber, Config};
+use crate::abi::{AbiFetcher, AbiRouter, DecodedLog, EventSignature, LogDecoder};
+use crate::checkpoint::{CheckpointManager, SharedCheckpoints};
+use crate::config::{BlockNumber, Chain, Config};
 use crate::error::{AbiError, Error, Result, RpcError};
 use crate::rpc::RpcPool;
+use alloy::json_abi::JsonAbi;
 use alloy::primitives::{Address, B256};
 use alloy::rpc::types::{Filter, Log};
 use futures::stream::{self, StreamExt};
+use parking_lot::RwLock;
+use std::collections::{BTreeMap, HashSet};
 use std::path::Path;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
 
 /// Parse event strings into topic hashes
@@ -73,6 +77,9 @@ pub struct FetchResult {
     pub logs: FetchLogs,
     /// Statistics about the fetch operation
     pub stats: FetchStats,
+    /// Chain the logs came from, set for multi-chain fetches so writers add
+    /// `chain`/`chain_id` columns
+    pub chain: Option<Chain>,
 }
 
 /// The actual log data
@@ -132,7 +139,9 @@ pub struct LogFetcher {
     /// RPC pool
     pool: RpcPool,
     /// Log decoder (if not raw mode)
-    decoder: Option<LogDecoder>,
+    decoder: Option<RwLock<LogDecoder>>,
+    /// Fetches ABIs for new emitting addresses (if auto ABI is on)
+    abi_router: Option<tokio::sync::Mutex<AbiRouter>>,
     /// Progress callback
     progress_callback: Option<ProgressCallback>,
     /// Resolved event signatures/topics for filtering (empty = all events)
@@ -146,7 +155,10 @@ impl LogFetcher {
         let pool = RpcPool::new(config.chain, &config.rpc)?;
 
         // Auto-detect from_block if needed
-        if config.auto_from_block && config.block_range.from_block() == 0 {
+        if config.auto_from_block
+            && config.block_range.from_block() == 0
+            && !config.contract.is_empty()
+        {
             tracing::info!("Looking up contract creation block from Etherscan...");
             let fetcher = AbiFetcher::new(config.etherscan_key.clone())?;
             match fetcher
@@ -175,6 +187,7 @@ impl LogFetcher {
         }
 
         // Resolve event filters (names, signatures, or topic hashes)
+        let user_abi = Self::load_user_abi(&config)?;
         let mut resolved_events = Vec::new();
         if !config.events.is_empty() {
             let fetcher = AbiFetcher::new(config.etherscan_key.clone())?;
@@ -189,6 +202,14 @@ impl LogFetcher {
                     // It's a full signature
                     tracing::debug!("Using event signature: {}", event_str);
                     resolved_events.push(event_str.clone());
+                } else if let Some(event) = user_abi
+                    .as_ref()
+                    .and_then(|abi| AbiFetcher::find_event(abi, event_str))
+                {
+                    // Event name found in the user-supplied ABI
+                    let resolved = AbiFetcher::event_signature_string(event);
+                    tracing::debug!("Resolved '{}' from local ABI: {}", event_str, resolved);
+                    resolved_events.push(resolved);
                 } else {
                     // It's just an event name - resolve from Etherscan ABI
                     tracing::info!("Resolving event name '{}' from contract ABI...", event_str);
@@ -205,20 +226,40 @@ impl LogFetcher {
         let decoder = if config.raw {
             None
         } else {
-            Some(Self::setup_decoder(&config, &resolved_events).await?)
+            Some(Self::setup_decoder(&config, user_abi.as_ref(), &resolved_events).await?)
+        };
+
+        let abi_router = if decoder.is_some() && config.auto_abi {
+            let mut router = AbiRouter::new(
+                config.chain,
+                config.etherscan_key.clone(),
+                config.auto_abi_limit,
+            )?;
+            if let Ok(address) = config.contract.parse::<Address>() {
+                // Its ABI, if any, was loaded by setup_decoder
+                router.mark_attempted(address);
+            }
+            Some(tokio::sync::Mutex::new(router))
+        } else {
+            None
         };
 
         Ok(Self {
             config,
             pool,
-            decoder,
+            decoder: decoder.map(RwLock::new),
+            abi_router,
             progress_callback: None,
             resolved_events,
         })
     }
 
     /// Set up the log decoder
-    async fn setup_decoder(config: &Config, resolved_events: &[String]) -> Result<LogDecoder> {
+    async fn setup_decoder(
+        config: &Config,
+        user_abi: Option<&JsonAbi>,
+        resolved_events: &[String],
+    ) -> Result<LogDecoder> {
         // Filter out topic hashes (they're not signatures we can decode)
         let signatures: Vec<&str> = resolved_events
             .iter()
@@ -226,29 +267,55 @@ impl LogFetcher {
             .map(|s| s.as_str())
             .collect();
 
-        // If we have resolved event signatures, use them
-        if !signatures.is_empty() {
+        // A local ABI and event signatures are combined into one decoder.
+        // The ABI goes last so its `indexed` flags win for shared events.
+        if user_abi.is_some() || !signatures.is_empty() {
             let mut decoder = LogDecoder::new();
             for sig_str in signatures {
                 let sig = EventSignature::parse(sig_str)?;
                 decoder.add_signature(&sig)?;
             }
+            if let Some(abi) = user_abi {
+                decoder.add_abi(abi)?;
+            }
             return Ok(decoder);
         }
 
-        // If ABI file provided, load it
-        if let Some(abi_path) = &config.abi_path {
-            let fetcher = AbiFetcher::new(None)?;
-            let abi = fetcher.load_from_file(abi_path)?;
+        if !config.auto_abi {
+            // Try to fetch ABI from Etherscan
+            let fetcher = AbiFetcher::new(config.etherscan_key.clone())?;
+            let abi = fetcher
+                .fetch_from_etherscan(config.chain, &config.contract)
+                .await?;
             return LogDecoder::from_abi(&abi);
         }
 
-        // Try to fetch ABI from Etherscan
-        let fetcher = AbiFetcher::new(config.etherscan_key.clone())?;
-        let abi = fetcher
-            .fetch_from_etherscan(config.chain, &config.contract)
-            .await?;
-        LogDecoder::from_abi(&abi)
+        // With auto ABI, the contract's ABI only applies to its own logs;
+        // other emitters are resolved as their logs arrive
+        let mut decoder = LogDecoder::new();
+        if let Ok(address) = config.contract.parse::<Address>() {
+            let fetcher = AbiFetcher::new(config.etherscan_key.clone())?;
+            match fetcher
+                .fetch_from_etherscan(config.chain, &config.contract)
+                .await
+            {
+                Ok(abi) => decoder.add_contract_abi(address, &abi)?,
+                Err(e) => tracing::warn!(
+                    "No ABI for {}: {}. Falling back to the signature database.",
+                    config.contract,
+                    e
+                ),
+            }
+        }
+        Ok(decoder)
+    }
+
+    /// Load the ABI given by `--abi` and/or `--abi-str`, if any
+    fn load_user_abi(config: &Config) -> Result<Option<JsonAbi>> {
+        if config.abi_path.is_none() && config.abi_str.is_none() {
+            return Ok(None);
+        }
+        AbiFetcher::new(None)?.load_local(config.abi_path.as_deref(), config.abi_str.as_deref())
     }
 
     /// Set progress callback
@@ -281,21 +348,7 @@ impl LogFetcher {
             chunks.len()
         );
 
-        // Build base filter
-        let address: Address = self
-            .config
-            .contract
-            .parse()
-            .map_err(|_| Error::from("Invalid contract address"))?;
-
-        let mut base_filter = Filter::new().address(address);
-
-        // Add event topics if we have specific events (works in both raw and decoded modes)
-        // Multiple topics create an OR filter (matches any of the specified events)
-        if !self.resolved_events.is_empty() {
-            let topics = parse_event_topics(&self.resolved_events)?;
-            base_filter = base_filter.event_signature(topics);
-        }
+        let base_filter = self.base_filter()?;
 
         // Fetch chunks in parallel
         let concurrency = self.config.rpc.concurrency;
@@ -414,25 +467,89 @@ impl LogFetcher {
             });
         }
 
-        // Decode if needed
-        let logs = if let Some(decoder) = &self.decoder {
-            let decoded: Vec<DecodedLog> = all_logs
-                .iter()
-                .filter_map(|log| match decoder.decode(log) {
-                    Ok(decoded) => Some(decoded),
-                    Err(e) => {
-                        tracing::debug!("Failed to decode log: {}", e);
-                        None
-                    }
-                })
-                .collect();
+        self.resolve_abis(&all_logs).await;
+        Ok(FetchResult {
+            logs: self.decode_logs(all_logs),
+            stats,
+            chain: None,
+        })
+    }
 
-            FetchLogs::Decoded(decoded)
-        } else {
-            FetchLogs::Raw(all_logs)
-        };
+    /// Base filter: contract address plus event and indexed topics, if any
+    fn base_filter(&self) -> Result<Filter> {
+        let mut filter = Filter::new();
+        if !self.config.contract.is_empty() {
+            let address: Address = self
+                .config
+                .contract
+                .parse()
+                .map_err(|_| Error::from("Invalid contract address"))?;
+            filter = filter.address(address);
+        }
+
+        // Add event topics if we have specific events (works in both raw and decoded modes)
+        // Multiple topics create an OR filter (matches any of the specified events)
+        if !self.resolved_events.is_empty() {
+            let topics = parse_event_topics(&self.resolved_events)?;
+            filter = filter.event_signature(topics);
+        }
+        let [topic1, topic2, topic3] = self.config.indexed_topics;
+        if let Some(topic) = topic1 {
+            filter = filter.topic1(topic);
+        }
+        if let Some(topic) = topic2 {
+            filter = filter.topic2(topic);
+        }
+        if let Some(topic) = topic3 {
+            filter = filter.topic3(topic);
+        }
+
+        Ok(filter)
+    }
 
-        Ok(FetchResult { logs, stats })
+    /// Fetch the ABIs and signatures needed to decode `logs`, if auto ABI is on
+    async fn resolve_abis(&self, logs: &[Log]) {
+        if let (Some(decoder), Some(router)) = (&self.decoder, &self.abi_router) {
+            router.lock().await.resolve(decoder, logs).await;
+        }
+    }
+
+    /// Decode logs if not in raw mode, dropping logs that fail to decode
+    fn decode_logs(&self, logs: Vec<Log>) -> FetchLogs {
+        match &self.decoder {
+            Some(decoder) => FetchLogs::Decoded(
+                logs.iter()
+                    .filter_map(|log| match decoder.read().decode(log) {
+                        Ok(decoded) => Some(decoded),
+                        Err(e) => {
+                            tracing::debug!("Failed to decode log: {}", e);
+                            None
+                        }
+                    })
+                    .collect(),
+            ),
+            None => FetchLogs::Raw(logs),
+        }
+    }
+
+    /// Fetch raw logs for a block range, chunk by chunk in block order
+    async fn fetch_range(&self, from: u64, to: u64) -> Result<Vec<Log>> {
+        let base_filter = self.base_filter()?;
+        let mut logs = Vec::new();
+        for (chunk_from, chunk_to) in Self::calculate_chunks(from, to, self.pool.max_block_range())
+        {
+            logs.extend(
+                Self::fetch_chunk_with_retry(
+                    &self.pool,
+                    &base_filter,
+                    chunk_from,
+                    chunk_to,
+                    self.config.rpc.max_retries,
+                )
+                .await?,
+            );
+        }
+        Ok(logs)
     }
 
     /// Fetch a single chunk with retry and adaptive splitting
@@ -442,6 +559,32 @@ impl LogFetcher {
         from: u64,
         to: u64,
         max_retries: u32,
+    ) -> Result<Vec<Log>> {
+        Self::fetch_chunk_counted(
+            pool,
+            filter,
+            from,
+            to,
+            max_retries,
+            0,
+            &mut ChunkStats::default(),
+        )
+        .await
+    }
+
+    /// [`Self::fetch_chunk_with_retry`], recording each request in `stats`
+    ///
+    /// A response holding `full_at` or more logs (0 = never) is treated as
+    /// possibly cut short by the provider and its range is split and
+    /// fetched again, down to single blocks.
+    async fn fetch_chunk_counted(
+        pool: &RpcPool,
+        filter: &Filter,
+        from: u64,
+        to: u64,
+        max_retries: u32,
+        full_at: usize,
+        stats: &mut ChunkStats,
     ) -> Result<Vec<Log>> {
         let mut current_from = from;
         let mut current_to = to;
@@ -451,7 +594,21 @@ impl LogFetcher {
         while current_from <= to {
             let chunk_filter = filter.clone().from_block(current_from).to_block(current_to);
 
-            match pool.get_logs(&chunk_filter).await {
+            let start = std::time::Instant::now();
+            let result = pool.get_logs(&chunk_filter).await;
+            stats.requests += 1;
+            stats.rpc_time += start.elapsed();
+
+            match result {
+                Ok(logs) if full_at > 0 && logs.len() >= full_at && current_to > current_from => {
+                    current_to = (current_from + current_to) / 2;
+                    tracing::debug!(
+                        "Response at the {} log limit, splitting: {} - {}",
+                        full_at,
+                        current_from,
+                        current_to
+                    );
+                }
                 Ok(logs) => {
                     all_logs.extend(logs);
                     current_from = current_to + 1;
@@ -533,6 +690,73 @@ impl LogFetcher {
         chunks
     }
 
+    /// Dry run: sample log density and project what a full fetch would cost
+    ///
+    /// Fetches `sample_count` evenly spaced ranges of up to
+    /// [`ESTIMATE_SAMPLE_WIDTH`] blocks and feeds their log counts and
+    /// per-request latency to [`FetchEstimate::project`]. A sample response
+    /// that reaches the smallest `max_logs` is split and fetched again, so
+    /// a provider that truncates instead of erroring does not hide logs.
+    /// Nothing is written.
+    pub async fn estimate(&self, sample_count: usize) -> Result<FetchEstimate> {
+        let end_block = self.resolve_end_block().await?;
+        let from_block = self.config.block_range.from_block();
+        let chunk_size = self.pool.max_block_range();
+        let width = match chunk_size {
+            0 => ESTIMATE_SAMPLE_WIDTH,
+            size => size.min(ESTIMATE_SAMPLE_WIDTH),
+        };
+        let base_filter = self.base_filter()?;
+
+        let endpoints: Vec<EndpointLimits> = self
+            .pool
+            .endpoint_limits()
+            .into_iter()
+            .map(|(url, max_block_range, max_logs)| EndpointLimits {
+                url: url.to_string(),
+                max_block_range,
+                max_logs,
+            })
+            .collect();
+        let full_at = endpoints
+            .iter()
+            .map(|e| e.max_logs)
+            .filter(|&m| m > 0)
+            .min()
+            .unwrap_or(0);
+
+        let mut samples = Vec::new();
+        let mut stats = ChunkStats::default();
+        for (from, to) in sample_ranges(from_block, end_block, sample_count, width) {
+            let logs = Self::fetch_chunk_counted(
+                &self.pool,
+                &base_filter,
+                from,
+                to,
+                self.config.rpc.max_retries,
+                full_at,
+                &mut stats,
+            )
+            .await?;
+            samples.push(DensitySample {
+                from,
+                to,
+                logs: logs.len() as u64,
+            });
+        }
+        let latency = stats.rpc_time / stats.requests.max(1);
+
+        Ok(FetchEstimate::project(
+            from_block,
+            end_block,
+            &samples,
+            &endpoints,
+            chunk_size,
+            self.config.rpc.concurrency,
+            latency,
+        ))
+    }
+
     /// Get the RPC pool
     pub fn pool(&self) -> &RpcPool {
         &self.pool
@@ -563,28 +787,53 @@ impl StreamingFetcher {
     /// Enable checkpointing
     pub fn with_checkpoint(mut self, path: &Path) -> Result<Self> {
         let config = &self.fetcher.config;
-        // For checkpoint, join multiple events with comma
-        let event_filter = if config.events.is_empty() {
-            None
-        } else {
-            Some(config.events.join(","))
-        };
+        let event_filter = Self::checkpoint_event_filter(config);
         let manager = CheckpointManager::load_or_create(
             path,
             &config.contract,
             config.chain.chain_id(),
             event_filter.as_deref(),
             config.block_range.from_block(),
-            match config.block_range.to_block() {
-                BlockNumber::Number(n) => Some(n),
-                BlockNumber::Latest => None,
-            },
+            Self::checkpoint_end_block(config),
         )?;
 
         self.checkpoint_manager = Some(std::sync::Arc::new(parking_lot::Mutex::new(manager)));
         Ok(self)
     }
 
+    /// Enable checkpointing into this chain's entry of a shared multi-chain file
+    pub fn with_shared_checkpoint(mut self, shared: &SharedCheckpoints) -> Result<Self> {
+        let config = &self.fetcher.config;
+        let event_filter = Self::checkpoint_event_filter(config);
+        let manager = CheckpointManager::load_or_create_shared(
+            shared,
+            &config.contract,
+            config.chain.chain_id(),
+            event_filter.as_deref(),
+            config.block_range.from_block(),
+            Self::checkpoint_end_block(config),
+        )?;
+
+        self.checkpoint_manager = Some(std::sync::Arc::new(parking_lot::Mutex::new(manager)));
+        Ok(self)
+    }
+
+    /// Event filter recorded in checkpoints (multiple events joined with comma)
+    fn checkpoint_event_filter(config: &Config) -> Option<String> {
+        if config.events.is_empty() {
+            None
+        } else {
+            Some(config.events.join(","))
+        }
+    }
+
+    fn checkpoint_end_block(config: &Config) -> Option<u64> {
+        match config.block_range.to_block() {
+            BlockNumber::Number(n) => Some(n),
+            BlockNumber::Latest => None,
+        }
+    }
+
     /// Get the RPC pool
     pub fn pool(&self) -> &RpcPool {
         &self.fetcher.pool
@@ -647,22 +896,7 @@ impl StreamingFetcher {
             failed_ranges: Vec::new(),
         };
 
-        // Build base filter
-        let address: Address = self
-            .fetcher
-            .config
-            .contract
-            .parse()
-            .map_err(|_| Error::from("Invalid contract address"))?;
-
-        let mut base_filter = Filter::new().address(address);
-
-        // Add event topics if we have specific events (use resolved events for filtering)
-        // Multiple topics create an OR filter (matches any of the specified events)
-        if !self.fetcher.resolved_events.is_empty() {
-            let topics = parse_event_topics(&self.fetcher.resolved_events)?;
-            base_filter = base_filter.event_signature(topics);
-        }
+        let base_filter = self.fetcher.base_filter()?;
 
         let concurrency = self.fetcher.config.rpc.concurrency;
         let max_retries = self.fetcher.config.rpc.max_retries;
@@ -696,7 +930,9 @@ impl StreamingFetcher {
                 Ok(logs) => {
                     let logs_count = logs.len() as u64;
 
+                    self.fetcher.resolve_abis(&logs).await;
                     let fetch_logs = if let Some(decoder) = &self.fetcher.decoder {
+                        let decoder = decoder.read();
                         let mut decode_errors = 0u64;
                         let decoded: Vec<DecodedLog> = logs
                             .iter()
@@ -736,6 +972,7 @@ impl StreamingFetcher {
                             chunks_failed: 0,
                             failed_ranges: Vec::new(),
                         },
+                        chain: None,
                     };
 
                     // Call handler to process/write the chunk
@@ -764,6 +1001,9 @@ impl StreamingFetcher {
                     stats
                         .failed_ranges
                         .push((chunk_from, chunk_to, e.to_string()));
+                    if let Some(ref manager) = self.checkpoint_manager {
+                        manager.lock().mark_failed(chunk_from, chunk_to);
+                    }
                 }
             }
         }
@@ -778,6 +1018,77 @@ impl StreamingFetcher {
         Ok(stats)
     }
 
+    /// Follow the chain head, like `tail -f`
+    ///
+    /// Polls the block number every `options.poll_interval` and passes logs
+    /// from new blocks to `handler`, starting at `start_block`. Each poll
+    /// re-fetches the trailing `options.reorg_depth` blocks so logs replaced
+    /// by a reorg are emitted again (see [`WatchState`]). A failed poll is
+    /// logged and retried on the next tick. Runs until `shutdown` resolves
+    /// and returns the number of logs emitted.
+    pub async fn watch<F, S>(
+        &mut self,
+        start_block: u64,
+        options: &WatchOptions,
+        shutdown: S,
+        mut handler: F,
+    ) -> Result<u64>
+    where
+        F: FnMut(FetchResult) -> Result<()>,
+        S: std::future::Future<Output = ()>,
+    {
+        let mut state = WatchState::new(start_block, options.reorg_depth);
+        let mut emitted = 0u64;
+        tokio::pin!(shutdown);
+
+        loop {
+            let poll = async {
+                let head = self.fetcher.pool.get_block_number().await?;
+                let Some((from, to)) = state.poll_range(head) else {
+                    return Ok(None);
+                };
+                let logs = self.fetcher.fetch_range(from, to).await?;
+                Ok::<_, Error>(Some(state.record(from, to, logs)))
+            };
+
+            let update = tokio::select! {
+                _ = &mut shutdown => break,
+                update = poll => update,
+            };
+
+            match update {
+                Ok(Some(update)) => {
+                    for block in &update.reorged_blocks {
+                        tracing::warn!("Reorg detected at block {}; re-emitting its logs", block);
+                    }
+                    if !update.logs.is_empty() {
+                        emitted += update.logs.len() as u64;
+                        self.fetcher.resolve_abis(&update.logs).await;
+                        handler(FetchResult {
+                            logs: self.fetcher.decode_logs(update.logs),
+                            stats: FetchStats {
+                                chunks_total: 1,
+                                chunks_succeeded: 1,
+                                chunks_failed: 0,
+                                failed_ranges: Vec::new(),
+                            },
+                            chain: None,
+                        })?;
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Watch poll failed, retrying: {}", e),
+            }
+
+            tokio::select! {
+                _ = &mut shutdown => break,
+                _ = tokio::time::sleep(options.poll_interval) => {}
+            }
+        }
+
+        Ok(emitted)
+    }
+
     /// Stream logs through a bounded channel (for async consumers)
     ///
     /// Note: Uses `try_send` to avoid potential deadlock with `blocking_send`.
@@ -810,10 +1121,541 @@ impl StreamingFetcher {
     }
 }
 
+/// Options for [`StreamingFetcher::watch`]
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+    /// Delay between block number polls
+    pub poll_interval: Duration,
+    /// Trailing blocks re-checked on every poll
+    pub reorg_depth: u64,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(2),
+            reorg_depth: 3,
+        }
+    }
+}
+
+/// RPC requests made while fetching one or more chunks
+#[derive(Debug, Clone, Copy, Default)]
+struct ChunkStats {
+    /// `eth_getLogs` calls, including retries and split halves
+    requests: u32,
+    /// Time spent waiting on those calls, without retry backoff
+    rpc_time: Duration,
+}
+
+/// Widest block range sampled by [`LogFetcher::estimate`]
+pub const ESTIMATE_SAMPLE_WIDTH: u64 = 1_000;
+
+/// Headroom kept below `max_logs` when suggesting a chunk size
+const SUGGESTED_CHUNK_FILL: f64 = 0.8;
+
+/// Up to `count` ranges of `width` blocks spread evenly over `from..=to`
+///
+/// A range no wider than `count * width` is covered completely.
+pub fn sample_ranges(from: u64, to: u64, count: usize, width: u64) -> Vec<(u64, u64)> {
+    if to < from || count == 0 || width == 0 {
+        return Vec::new();
+    }
+    let blocks = to - from + 1;
+    let count = count as u64;
+    if blocks <= count.saturating_mul(width) {
+        return LogFetcher::calculate_chunks(from, to, width);
+    }
+    (0..count)
+        .map(|i| {
+            let center = from + (blocks * (2 * i + 1)) / (2 * count);
+            let start = center.saturating_sub(width / 2).max(from);
+            let end = (start + width - 1).min(to);
+            (start, end)
+        })
+        .collect()
+}
+
+/// Logs found in one sampled block range
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+pub struct DensitySample {
+    /// First block
+    pub from: u64,
+    /// Last block (inclusive)
+    pub to: u64,
+    /// Matching logs in the range
+    pub logs: u64,
+}
+
+impl DensitySample {
+    /// Number of blocks sampled
+    pub fn blocks(&self) -> u64 {
+        self.to - self.from + 1
+    }
+
+    /// Average logs per block
+    pub fn density(&self) -> f64 {
+        self.logs as f64 / self.blocks() as f64
+    }
+}
+
+/// Per-request limits of one endpoint
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct EndpointLimits {
+    /// Endpoint URL
+    pub url: String,
+    /// Widest block range per request (0 = unlimited)
+    pub max_block_range: u64,
+    /// Most logs per response (0 = unlimited)
+    pub max_logs: usize,
+}
+
+/// A stretch of blocks whose chunks are expected to exceed `max_logs`
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct HotRange {
+    /// First block
+    pub from: u64,
+    /// Last block (inclusive)
+    pub to: u64,
+    /// Sampled logs per block
+    pub logs_per_block: f64,
+    /// Expected logs in one full chunk
+    pub logs_per_chunk: u64,
+}
+
+/// Projected cost of a logs fetch, from [`LogFetcher::estimate`]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct FetchEstimate {
+    /// First block of the fetch
+    pub from_block: u64,
+    /// Last block of the fetch
+    pub to_block: u64,
+    /// Blocks covered
+    pub blocks: u64,
+    /// Sampled ranges
+    pub samples: Vec<DensitySample>,
+    /// Projected matching logs
+    pub estimated_logs: u64,
+    /// Blocks per request the fetch will start with (0 = whole range)
+    pub chunk_size: u64,
+    /// Chunks at that size
+    pub chunks: u64,
+    /// Requests including the retries of chunks split for `max_logs`
+    pub requests: u64,
+    /// Parallel requests
+    pub concurrency: usize,
+    /// Average sampled request latency, in milliseconds
+    pub latency_ms: u64,
+    /// Projected wall time, in seconds
+    pub expected_duration_secs: f64,
+    /// Smallest `max_logs` across endpoints (0 = unlimited)
+    pub max_logs: usize,
+    /// Endpoint limits the projection used
+    pub endpoints: Vec<EndpointLimits>,
+    /// Ranges whose chunks will be split to fit `max_logs`
+    pub hot_ranges: Vec<HotRange>,
+    /// Chunk size that keeps the densest sample under `max_logs`
+    pub suggested_chunk_size: Option<u64>,
+    /// False if a single block is expected to exceed `max_logs`
+    pub feasible: bool,
+    /// Warnings about the setup
+    pub warnings: Vec<String>,
+}
+
+impl FetchEstimate {
+    /// Project a fetch of `from..=to` from sampled densities
+    ///
+    /// Each sample stands for the blocks closer to it than to its
+    /// neighbours. A chunk expected to hold more than the smallest
+    /// `max_logs` is split in half until it fits, as the fetcher does, and
+    /// each failed attempt counts as a request.
+    pub fn project(
+        from: u64,
+        to: u64,
+        samples: &[DensitySample],
+        endpoints: &[EndpointLimits],
+        chunk_size: u64,
+        concurrency: usize,
+        latency: Duration,
+    ) -> Self {
+        let blocks = if to >= from { to - from + 1 } else { 0 };
+        let chunk = if chunk_size == 0 {
+            blocks.max(1)
+        } else {
+            chunk_size
+        };
+        let max_logs = endpoints
+            .iter()
+            .map(|e| e.max_logs)
+            .filter(|&m| m > 0)
+            .min()
+            .unwrap_or(0);
+
+        let mut samples = samples.to_vec();
+        samples.sort_by_key(|s| s.from);
+
+        let mut estimated_logs = 0f64;
+        // Requests beyond one per chunk, from splitting dense chunks
+        let mut split_requests = 0f64;
+        let mut hot_ranges = Vec::new();
+        for (i, sample) in samples.iter().enumerate() {
+            let seg_from = if i == 0 {
+                from
+            } else {
+                midpoint(&samples[i - 1], sample) + 1
+            };
+            let seg_to = match samples.get(i + 1) {
+                Some(next) => midpoint(sample, next),
+                None => to,
+            };
+            if seg_to < seg_from {
+                continue;
+            }
+            let seg_blocks = seg_to - seg_from + 1;
+            let density = sample.density();
+            estimated_logs += density * seg_blocks as f64;
+
+            let logs_per_chunk = (density * chunk.min(seg_blocks) as f64).ceil() as u64;
+            let mut per_chunk = 1;
+            if max_logs > 0 && logs_per_chunk > max_logs as u64 {
+                let splits = (logs_per_chunk as f64 / max_logs as f64).log2().ceil() as u32;
+                per_chunk = 2u64.saturating_pow(splits + 1) - 1;
+                hot_ranges.push(HotRange {
+                    from: seg_from,
+                    to: seg_to,
+                    logs_per_block: density,
+                    logs_per_chunk,
+                });
+            }
+            split_requests += seg_blocks as f64 / chunk as f64 * (per_chunk - 1) as f64;
+        }
+        let chunks = blocks.div_ceil(chunk);
+        let requests = chunks + split_requests.ceil() as u64;
+
+        let peak_density = samples
+            .iter()
+            .map(DensitySample::density)
+            .fold(0.0, f64::max);
+        let suggested_chunk_size = (!hot_ranges.is_empty()).then(|| {
+            ((max_logs as f64 * SUGGESTED_CHUNK_FILL / peak_density).floor() as u64).max(1)
+        });
+        let feasible = max_logs == 0 || peak_density <= max_logs as f64;
+
+        let mut warnings = Vec::new();
+        if !feasible {
+            warnings.push(format!(
+                "Sampled blocks average {peak_density:.0} logs each, above the {max_logs} log limit; \
+                 those blocks cannot be split further"
+            ));
+        }
+        for endpoint in endpoints {
+            if chunk_size > 0
+                && endpoint.max_block_range > 0
+                && endpoint.max_block_range < chunk_size
+            {
+                warnings.push(format!(
+                    "{} allows {} blocks per request; {}-block chunks sent there will be split",
+                    endpoint.url, endpoint.max_block_range, chunk_size
+                ));
+            }
+        }
+
+        let concurrency = concurrency.max(1);
+        let expected_duration_secs = requests as f64 * latency.as_secs_f64() / concurrency as f64;
+
+        Self {
+            from_block: from,
+            to_block: to,
+            blocks,
+            samples,
+            estimated_logs: estimated_logs.round() as u64,
+            chunk_size,
+            chunks,
+            requests,
+            concurrency,
+            latency_ms: latency.as_millis() as u64,
+            expected_duration_secs,
+            max_logs,
+            endpoints: endpoints.to_vec(),
+            hot_ranges,
+            suggested_chunk_size,
+            feasible,
+            warnings,
+        }
+    }
+}
+
+/// Last block belonging to `a` when splitting the gap between two samples
+fn midpoint(a: &DensitySample, b: &DensitySample) -> u64 {
+    let a_center = a.from + (a.to - a.from) / 2;
+    let b_center = b.from + (b.to - b.from) / 2;
+    a_center + (b_center.saturating_sub(a_center)) / 2
+}
+
+/// Logs already emitted for one block
+#[derive(Debug, Default)]
+struct WatchedBlock {
+    hash: Option<B256>,
+    logs: HashSet<(B256, u64)>,
+}
+
+/// Logs from a watch poll that weren't emitted before
+#[derive(Debug, Default)]
+pub struct WatchUpdate {
+    /// New logs, in block order
+    pub logs: Vec<Log>,
+    /// Blocks whose logs changed since the last poll
+    pub reorged_blocks: Vec<u64>,
+}
+
+/// Tracks emitted logs across watch polls
+///
+/// Every poll re-fetches the trailing `reorg_depth` blocks. Logs are keyed by
+/// transaction hash and log index within their block, so re-fetched logs are
+/// skipped. A block whose hash changed, or whose logs disappeared, is treated
+/// as reorged and its current logs are emitted again. Blocks before the
+/// start block are never fetched.
+#[derive(Debug)]
+pub struct WatchState {
+    reorg_depth: u64,
+    start_block: u64,
+    next_block: u64,
+    blocks: BTreeMap<u64, WatchedBlock>,
+}
+
+impl WatchState {
+    /// Start watching at `start_block`
+    pub fn new(start_block: u64, reorg_depth: u64) -> Self {
+        Self {
+            reorg_depth,
+            start_block,
+            next_block: start_block,
+            blocks: BTreeMap::new(),
+        }
+    }
+
+    /// First block not fetched yet
+    pub fn next_block(&self) -> u64 {
+        self.next_block
+    }
+
+    /// Block range to fetch given the chain head, `None` if no new blocks
+    pub fn poll_range(&self, head: u64) -> Option<(u64, u64)> {
+        if head < self.next_block {
+            return None;
+        }
+        let from = self
+            .next_block
+            .saturating_sub(self.reorg_depth)
+            .max(self.start_block);
+        Some((from, head))
+    }
+
+    /// Record the logs fetched for `from..=to` and return the new ones
+    pub fn record(&mut self, from: u64, to: u64, logs: Vec<Log>) -> WatchUpdate {
+        let mut fetched: BTreeMap<u64, (Option<B256>, Vec<Log>)> = BTreeMap::new();
+        for log in logs {
+            let Some(block) = log.block_number else {
+                continue;
+            };
+            let entry = fetched.entry(block).or_insert((log.block_hash, Vec::new()));
+            entry.1.push(log);
+        }
+
+        let reorged_blocks: Vec<u64> = self
+            .blocks
+            .range(from..=to)
+            .filter(|(block, seen)| match fetched.get(block) {
+                Some((hash, _)) => seen.hash.is_some() && *hash != seen.hash,
+                None => true,
+            })
+            .map(|(block, _)| *block)
+            .collect();
+        for block in &reorged_blocks {
+            self.blocks.remove(block);
+        }
+
+        let mut new_logs = Vec::new();
+        for (block, (hash, logs)) in fetched {
+            let seen = self.blocks.entry(block).or_default();
+            seen.hash = hash.or(seen.hash);
+            for log in logs {
+                let key = (
+                    log.transaction_hash.unwrap_or_default(),
+                    log.log_index.unwrap_or_default(),
+                );
+                if seen.logs.insert(key) {
+                    new_logs.push(log);
+                }
+            }
+        }
+
+        self.next_block = self.next_block.max(to + 1);
+        let keep_from = self.next_block.saturating_sub(self.reorg_depth);
+        self.blocks = self.blocks.split_off(&keep_from);
+
+        WatchUpdate {
+            logs: new_logs,
+            reorged_blocks,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Sample `from..=to` at `count` points from a density profile
+    fn sample_profile(
+        from: u64,
+        to: u64,
+        count: usize,
+        width: u64,
+        density: impl Fn(u64) -> f64,
+    ) -> Vec<DensitySample> {
+        sample_ranges(from, to, count, width)
+            .into_iter()
+            .map(|(from, to)| DensitySample {
+                from,
+                to,
+                logs: (from..=to).map(&density).sum::<f64>().round() as u64,
+            })
+            .collect()
+    }
+
+    fn limits(max_block_range: u64, max_logs: usize) -> EndpointLimits {
+        EndpointLimits {
+            url: "https://rpc.example".to_string(),
+            max_block_range,
+            max_logs,
+        }
+    }
+
+    #[test]
+    fn test_sample_ranges() {
+        // Spread evenly, each `width` wide
+        let ranges = sample_ranges(0, 999_999, 4, 100);
+        assert_eq!(
+            ranges,
+            vec![
+                (124_950, 125_049),
+                (374_950, 375_049),
+                (624_950, 625_049),
+                (874_950, 875_049)
+            ]
+        );
+        // A short range is covered completely
+        assert_eq!(sample_ranges(10, 25, 4, 10), vec![(10, 19), (20, 25)]);
+        assert!(sample_ranges(10, 5, 4, 10).is_empty());
+        assert!(sample_ranges(0, 100, 0, 10).is_empty());
+    }
+
+    #[test]
+    fn test_estimate_uniform_density() {
+        let samples = sample_profile(0, 4_999_999, 8, 1_000, |_| 0.5);
+        let estimate = FetchEstimate::project(
+            0,
+            4_999_999,
+            &samples,
+            &[limits(10_000, 10_000)],
+            10_000,
+            5,
+            Duration::from_millis(200),
+        );
+        assert_eq!(estimate.blocks, 5_000_000);
+        assert_eq!(estimate.estimated_logs, 2_500_000);
+        assert_eq!(estimate.chunks, 500);
+        assert_eq!(estimate.requests, 500);
+        assert!(estimate.hot_ranges.is_empty());
+        assert_eq!(estimate.suggested_chunk_size, None);
+        assert!(estimate.feasible);
+        // 500 requests * 0.2s / 5 in parallel
+        assert!((estimate.expected_duration_secs - 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_estimate_flags_hot_region() {
+        // Quiet except for a busy stretch in the middle
+        let samples = sample_profile(0, 999_999, 10, 1_000, |block| {
+            if (400_000..600_000).contains(&block) {
+                4.0
+            } else {
+                0.1
+            }
+        });
+        let estimate = FetchEstimate::project(
+            0,
+            999_999,
+            &samples,
+            &[limits(10_000, 10_000)],
+            10_000,
+            4,
+            Duration::from_millis(100),
+        );
+
+        // Only the busy samples' segments go over 10k logs per 10k-block chunk
+        assert_eq!(estimate.hot_ranges.len(), 2);
+        assert!(estimate
+            .hot_ranges
+            .iter()
+            .all(|r| r.from >= 350_000 && r.to < 650_000 && r.logs_per_chunk == 40_000));
+        // 40k logs per chunk: split twice, 1 + 2 + 4 requests for each of
+        // the 20 busy chunks
+        let hot_blocks: u64 = estimate.hot_ranges.iter().map(|r| r.to - r.from + 1).sum();
+        assert_eq!(hot_blocks, 200_000);
+        assert_eq!(estimate.chunks, 100);
+        assert_eq!(estimate.requests, 100 + 20 * 6);
+        // 10k * 0.8 / 4 logs per block
+        assert_eq!(estimate.suggested_chunk_size, Some(2_000));
+        assert!(estimate.feasible);
+    }
+
+    #[test]
+    fn test_estimate_sparse_and_unlimited() {
+        let samples = sample_profile(0, 99_999, 5, 1_000, |_| 0.0);
+        let estimate = FetchEstimate::project(
+            0,
+            99_999,
+            &samples,
+            &[limits(0, 0)],
+            0,
+            5,
+            Duration::from_millis(50),
+        );
+        assert_eq!(estimate.estimated_logs, 0);
+        // Unlimited range: one request for everything
+        assert_eq!(estimate.chunks, 1);
+        assert_eq!(estimate.requests, 1);
+        assert_eq!(estimate.max_logs, 0);
+        assert!(estimate.feasible);
+    }
+
+    #[test]
+    fn test_estimate_infeasible_block_density() {
+        let samples = sample_profile(0, 9_999, 2, 100, |_| 2_000.0);
+        let estimate = FetchEstimate::project(
+            0,
+            9_999,
+            &samples,
+            &[limits(2_000, 1_000), limits(500, 5_000)],
+            2_000,
+            2,
+            Duration::from_millis(100),
+        );
+        // The smallest max_logs wins
+        assert_eq!(estimate.max_logs, 1_000);
+        assert!(!estimate.feasible);
+        assert_eq!(estimate.suggested_chunk_size, Some(1));
+        assert!(estimate
+            .warnings
+            .iter()
+            .any(|w| w.contains("cannot be split")));
+        assert!(estimate
+            .warnings
+            .iter()
+            .any(|w| w.contains("allows 500 blocks per request")));
+    }
+
     #[test]
     fn test_calculate_chunks() {
         let chunks = LogFetcher::calculate_chunks(0, 100, 30);
@@ -826,11 +1668,95 @@ mod tests {
         assert_eq!(chunks, vec![(50, 50)]);
     }
 
+    fn watched_log(block: u64, block_hash: u8, tx: u8, index: u64) -> Log {
+        Log {
+            block_number: Some(block),
+            block_hash: Some(B256::repeat_byte(block_hash)),
+            transaction_hash: Some(B256::repeat_byte(tx)),
+            log_index: Some(index),
+            ..Default::default()
+        }
+    }
+
+    fn keys(update: &WatchUpdate) -> Vec<(u64, u64)> {
+        update
+            .logs
+            .iter()
+            .map(|l| (l.block_number.unwrap(), l.log_index.unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_watch_poll_range() {
+        let mut state = WatchState::new(100, 3);
+        assert_eq!(state.poll_range(99), None);
+        assert_eq!(state.poll_range(105), Some((100, 105)));
+
+        state.record(100, 105, vec![]);
+        assert_eq!(state.next_block(), 106);
+        assert_eq!(state.poll_range(105), None);
+        // Trailing blocks are re-checked once new blocks arrive
+        assert_eq!(state.poll_range(107), Some((103, 107)));
+    }
+
+    #[test]
+    fn test_watch_dedupes_across_polls() {
+        let mut state = WatchState::new(100, 3);
+        let update = state.record(
+            100,
+            102,
+            vec![watched_log(101, 1, 1, 0), watched_log(102, 2, 2, 1)],
+        );
+        assert_eq!(keys(&update), [(101, 0), (102, 1)]);
+
+        // Overlapping window: only the log in the new block is emitted
+        let update = state.record(
+            100,
+            104,
+            vec![
+                watched_log(101, 1, 1, 0),
+                watched_log(102, 2, 2, 1),
+                watched_log(104, 4, 4, 0),
+            ],
+        );
+        assert_eq!(keys(&update), [(104, 0)]);
+        assert!(update.reorged_blocks.is_empty());
+    }
+
+    #[test]
+    fn test_watch_reemits_reorged_blocks() {
+        let mut state = WatchState::new(100, 3);
+        state.record(
+            100,
+            103,
+            vec![watched_log(102, 2, 2, 0), watched_log(103, 3, 3, 0)],
+        );
+
+        // Block 102 replaced (new hash, same tx), block 103's log gone
+        let update = state.record(
+            101,
+            104,
+            vec![watched_log(102, 0xaa, 2, 0), watched_log(104, 4, 4, 0)],
+        );
+        assert_eq!(update.reorged_blocks, [102, 103]);
+        assert_eq!(keys(&update), [(102, 0), (104, 0)]);
+
+        // The replacement is now the known version
+        let update = state.record(
+            102,
+            105,
+            vec![watched_log(102, 0xaa, 2, 0), watched_log(104, 4, 4, 0)],
+        );
+        assert!(update.logs.is_empty());
+        assert!(update.reorged_blocks.is_empty());
+    }
+
     #[test]
     fn test_fetch_result_len() {
         let result = FetchResult {
             logs: FetchLogs::Raw(vec![]),
             stats: FetchStats::default(),
+            chain: None,
         };
         assert!(result.is_empty());
         assert_eq!(result.len(), 0);
@@ -29,6 +29,7 @@
 
 pub mod abi;
 pub mod aggregator;
+pub mod approvals;
 pub mod chainlink;
 pub mod checkpoint;
 pub mod cli;
@@ -36,11 +37,15 @@ pub mod config;
 pub mod error;
 pub mod etherscan;
 pub mod fetcher;
+pub mod multichain;
+pub mod nft;
 pub mod output;
 pub mod proxy;
 pub mod rpc;
+pub mod source_diff;
 pub mod tx;
 pub mod utils;
+pub mod watch;
 
 // Legacy alias for cache module (now in etherscan::cache)
 pub mod cache {
@@ -48,18 +53,23 @@ pub mod cache {
 }
 
 // Re-exports for convenience
-pub use abi::{AbiFetcher, DecodedLog, EventSignature, LogDecoder};
-pub use checkpoint::{Checkpoint, CheckpointManager};
+pub use abi::{AbiFetcher, DecodedCalldata, DecodedLog, DecoderSource, EventSignature, LogDecoder};
+pub use checkpoint::{Checkpoint, CheckpointManager, CheckpointSet, SharedCheckpoints};
 pub use config::{
-    BlockNumber, BlockRange, Chain, ChainId, Config, ConfigBuilder, ConfigFile, EndpointConfig,
-    NodeType, OutputConfig, OutputFormat, ProxyConfig, RpcConfig,
+    build_default_rpc_config, BlockNumber, BlockRange, Chain, ChainId, Config, ConfigBuilder,
+    ConfigFile, EndpointConfig, NodeType, OutputConfig, OutputFormat, ProxyConfig, RpcConfig,
 };
 pub use error::{AbiError, CheckpointError, ConfigError, Error, OutputError, Result, RpcError};
 pub use etherscan::{CacheStats, Client as EtherscanClient, SignatureCache};
 pub use fetcher::{
-    FetchLogs, FetchProgress, FetchResult, FetchStats, LogFetcher, StreamingFetcher,
+    DensitySample, EndpointLimits, FetchEstimate, FetchLogs, FetchProgress, FetchResult,
+    FetchStats, HotRange, LogFetcher, StreamingFetcher, WatchOptions, WatchState, WatchUpdate,
+};
+pub use multichain::{ChainFetchSummary, MultiChainFetcher};
+pub use output::{
+    create_writer, query_rows, CsvWriter, JsonWriter, OutputWriter, QueryResult, SqliteWriter,
+    TxSqliteWriter,
 };
-pub use output::{create_writer, CsvWriter, JsonWriter, OutputWriter, SqliteWriter};
 pub use proxy::{validate_proxy_url, ProxyRotator, RotationMode};
 pub use rpc::{
     optimize_endpoint, test_connectivity, Endpoint, EndpointHealth, HealthTracker,
@@ -7,61 +7,25 @@ use ethcli::cli::{
     endpoints::EndpointCommands,
     logs::{LogsArgs, ProxyArgs, RpcArgs},
     schema::{get_subcommand_schema, CliSchema},
-    tx::TxArgs,
+    tx::{TxArgs, TxCommands},
     Cli, Commands,
 };
+use ethcli::utils::parse_duration_string;
 use ethcli::{
-    format_analysis, Chain, Config, ConfigFile, DecodedLog, Endpoint, EndpointConfig, FetchLogs,
-    FetchProgress, FetchStats, LogFetcher, OutputFormat, OutputWriter, ProxyConfig, RpcConfig,
-    RpcPool, StreamingFetcher, TxAnalyzer,
+    build_default_rpc_config, format_analysis, Chain, ChainFetchSummary, Config, ConfigFile,
+    DecodedLog, Endpoint, EndpointConfig, FetchLogs, FetchProgress, FetchStats, LogFetcher,
+    MultiChainFetcher, OutputFormat, OutputWriter, ProxyConfig, RpcConfig, RpcPool,
+    StreamingFetcher, TxAnalyzer, WatchOptions,
 };
 use indicatif::{ProgressBar, ProgressStyle};
 use secrecy::ExposeSecret;
 use std::collections::HashMap;
 use std::io::IsTerminal;
-use std::path::PathBuf;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Instant;
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
-/// Parse a duration string like "30d", "6h", "2w", "90m" into seconds
-///
-/// Supported units:
-/// - m, min, minutes: minutes
-/// - h, hr, hours: hours
-/// - d, days: days
-/// - w, weeks: weeks
-fn parse_duration_string(s: &str) -> anyhow::Result<f64> {
-    let s = s.trim().to_lowercase();
-
-    // Try to find where the number ends and unit begins
-    let (num_str, unit) = if let Some(pos) = s.find(|c: char| c.is_alphabetic()) {
-        (&s[..pos], s[pos..].trim())
-    } else {
-        // No unit found, assume days for backwards compatibility
-        (s.as_str(), "d")
-    };
-
-    let value: f64 = num_str
-        .trim()
-        .parse()
-        .map_err(|_| anyhow::anyhow!("Invalid duration number: '{num_str}'"))?;
-
-    let seconds = match unit {
-        "m" | "min" | "mins" | "minute" | "minutes" => value * 60.0,
-        "h" | "hr" | "hrs" | "hour" | "hours" => value * 3600.0,
-        "d" | "day" | "days" => value * 86400.0,
-        "w" | "wk" | "wks" | "week" | "weeks" => value * 604800.0,
-        _ => {
-            return Err(anyhow::anyhow!(
-                "Unknown duration unit: '{unit}'. Use m/h/d/w (e.g., 30d, 6h, 2w, 90m)"
-            ))
-        }
-    };
-
-    Ok(seconds)
-}
-
 /// Load config file with proper error reporting
 ///
 /// Returns None if file doesn't exist, but warns on parse errors
@@ -71,6 +35,7 @@ fn load_config_with_warning() -> Option<ConfigFile> {
         Err(e) => {
             eprintln!("Warning: Failed to load config file: {e}");
             eprintln!("Using default settings. Fix the config or run: ethcli config path");
+            eprintln!("If the file is from an older ethcli, try: ethcli config migrate");
             None
         }
     }
@@ -108,8 +73,10 @@ async fn main() -> anyhow::Result<()> {
         .with(EnvFilter::new(filter))
         .init();
 
-    // Parse chain once for use in handlers
-    let chain: Chain = cli.chain.parse()?;
+    // Parse chain once for use in handlers. Logs accepts a comma-separated
+    // list and parses --chain itself, so a parse error only surfaces in the
+    // handlers that take a single chain.
+    let chain = cli.chain.parse::<Chain>();
 
     // Load config file and merge etherscan key (CLI takes precedence over config)
     let config_file = load_config_with_warning();
@@ -123,34 +90,42 @@ async fn main() -> anyhow::Result<()> {
     // Handle subcommands
     match &cli.command {
         Commands::Logs(args) => {
-            return run_logs(args, &cli).await;
+            return run_logs(args, &cli, &config_file, etherscan_key.clone()).await;
         }
         Commands::Tx(args) => {
-            return handle_tx(args, &cli).await;
+            return handle_tx(args, &cli, etherscan_key.clone()).await;
         }
         Commands::Account { action } => {
-            return ethcli::cli::account::handle(action, chain, etherscan_key.clone(), cli.quiet)
+            return ethcli::cli::account::handle(action, chain?, etherscan_key.clone(), cli.quiet)
                 .await;
         }
         Commands::Address { action } => {
             return ethcli::cli::address::handle(action, cli.quiet);
         }
+        Commands::Checkpoint { action } => {
+            return ethcli::cli::checkpoint::handle(action, cli.quiet);
+        }
+        Commands::Db { action } => {
+            return ethcli::cli::db::handle(action, cli.quiet);
+        }
         Commands::Blacklist(args) => {
             return ethcli::cli::blacklist::execute(args).await;
         }
         Commands::Contract { action } => {
-            return ethcli::cli::contract::handle(action, chain, etherscan_key.clone(), cli.quiet)
+            return ethcli::cli::contract::handle(action, chain?, etherscan_key.clone(), cli.quiet)
                 .await;
         }
         Commands::Token { action } => {
-            return ethcli::cli::token::handle(action, chain, etherscan_key.clone(), cli.quiet)
+            return ethcli::cli::token::handle(action, chain?, etherscan_key.clone(), cli.quiet)
                 .await;
         }
         Commands::Gas { action } => {
-            return ethcli::cli::gas::handle(action, chain, etherscan_key.clone(), cli.quiet).await;
+            return ethcli::cli::gas::handle(action, chain?, etherscan_key.clone(), cli.quiet)
+                .await;
         }
         Commands::Sig { action } => {
-            return ethcli::cli::sig::handle(action, chain, etherscan_key.clone(), cli.quiet).await;
+            return ethcli::cli::sig::handle(action, chain?, etherscan_key.clone(), cli.quiet)
+                .await;
         }
         Commands::Endpoints { action } => {
             return handle_endpoints(action, &cli).await;
@@ -159,19 +134,20 @@ async fn main() -> anyhow::Result<()> {
             return handle_config(action).await;
         }
         Commands::Cast { action } => {
-            return ethcli::cli::cast::handle(action);
+            return ethcli::cli::cast::handle(action, chain?, etherscan_key.clone(), cli.quiet)
+                .await;
         }
         Commands::Rpc { action, rpc_url } => {
-            return ethcli::cli::rpc::handle(action, chain, rpc_url.clone(), cli.quiet).await;
+            return ethcli::cli::rpc::handle(action, chain?, rpc_url.clone(), cli.quiet).await;
         }
         Commands::Ens { action, rpc_url } => {
-            return ethcli::cli::ens::handle(action, chain, rpc_url.clone(), cli.quiet).await;
+            return ethcli::cli::ens::handle(action, chain?, rpc_url.clone(), cli.quiet).await;
         }
         Commands::Simulate { action } => {
-            return ethcli::cli::simulate::handle(action, chain, cli.quiet).await;
+            return ethcli::cli::simulate::handle(action, chain?, cli.quiet).await;
         }
         Commands::Tenderly { action } => {
-            return ethcli::cli::tenderly::handle(action, chain, cli.quiet).await;
+            return ethcli::cli::tenderly::handle(action, chain?, cli.quiet).await;
         }
 
         Commands::Price(args) => {
@@ -186,8 +162,21 @@ async fn main() -> anyhow::Result<()> {
             return ethcli::cli::nfts::execute(args, cli.quiet).await;
         }
 
-        Commands::Update { install } => {
-            return ethcli::cli::update::handle(*install, cli.quiet).await;
+        Commands::Update {
+            install,
+            rollback,
+            channel,
+        } => {
+            if *rollback {
+                return ethcli::cli::update::handle_rollback(cli.quiet);
+            }
+            return ethcli::cli::update::handle(
+                *install,
+                *channel,
+                config_file.as_ref(),
+                cli.quiet,
+            )
+            .await;
         }
 
         Commands::Doctor => {
@@ -298,14 +287,20 @@ async fn main() -> anyhow::Result<()> {
 }
 
 /// Run logs command with `LogsArgs`
-async fn run_logs(args: &LogsArgs, cli: &Cli) -> anyhow::Result<()> {
+async fn run_logs(
+    args: &LogsArgs,
+    cli: &Cli,
+    config_file: &Option<ConfigFile>,
+    etherscan_key: Option<String>,
+) -> anyhow::Result<()> {
     let contract = &args.contract;
 
-    // Parse chain
-    let chain: Chain = cli.chain.parse()?;
+    // Parse chain (a comma-separated list fetches every chain in one run)
+    let chains = ethcli::multichain::parse_chain_list(&cli.chain)?;
+    let chain = chains[0];
 
     // Parse output format
-    let format: OutputFormat = args.format.parse()?;
+    let format: OutputFormat = args.format.as_deref().unwrap_or("json").parse()?;
 
     // Parse to_block
     let to_block = if args.to_block.to_lowercase() == "latest" {
@@ -314,16 +309,12 @@ async fn run_logs(args: &LogsArgs, cli: &Cli) -> anyhow::Result<()> {
         ethcli::BlockNumber::Number(args.to_block.parse()?)
     };
 
-    // Load config file for additional settings
-    let config_file = load_config_with_warning();
-
-    // Get Etherscan API key
-    let etherscan_key = cli.etherscan_key.clone().or_else(|| {
+    // Default checkpoints live under the config dir (or settings.checkpoint_dir)
+    let checkpoint_dir = ethcli::checkpoint::checkpoint_dir(
         config_file
             .as_ref()
-            .and_then(|c| c.etherscan_api_key.as_ref())
-            .map(|s| s.expose_secret().to_string())
-    });
+            .and_then(|c| c.settings.checkpoint_dir.as_deref()),
+    );
 
     // Apply defaults: CLI > config file > hardcoded defaults
     let concurrency = args
@@ -334,37 +325,36 @@ async fn run_logs(args: &LogsArgs, cli: &Cli) -> anyhow::Result<()> {
     let rpc_config = build_rpc_config_from_logs_args_full(
         &args.rpc,
         &args.proxy,
-        &config_file,
+        config_file,
         concurrency,
         args.chunk_size,
     )?;
 
-    // Parse from_block (can be number, "auto", --since, or omitted for auto-detect)
-    let (from_block, auto_from_block) = if let Some(since_str) = &args.since {
-        // Parse the duration string and calculate from_block
-        let duration_secs = parse_duration_string(since_str)?;
-        let blocks_back = chain.blocks_for_duration(duration_secs);
-
-        // Get current block number via a quick RPC call
-        let quick_pool = RpcPool::new(chain, &rpc_config)?;
-        let current_block = quick_pool.get_block_number().await?;
-
-        let target_block = current_block.saturating_sub(blocks_back);
-
-        if !cli.quiet {
-            eprintln!(
-                "Using --since {since_str}: ~{blocks_back} blocks back from {current_block} to block {target_block}"
-            );
+    if args.watch {
+        if chains.len() > 1 {
+            anyhow::bail!("--watch follows a single chain");
         }
-
-        (target_block, false)
-    } else {
-        match &args.from_block {
-            Some(s) if s.to_lowercase() == "auto" => (0, true),
-            Some(s) => (s.parse::<u64>()?, false),
-            None => (0, true), // Default to auto-detect from contract creation
+        if !matches!(format, OutputFormat::Json | OutputFormat::NdJson) {
+            anyhow::bail!("--watch supports json and ndjson output only");
         }
-    };
+    }
+
+    // Parse from_block (can be number, "auto", --since, or omitted for auto-detect)
+    let (from_block, auto_from_block) =
+        resolve_from_block(args, chain, &rpc_config, cli.quiet).await?;
+
+    // With --watch, catch up to the last block outside the reorg window and
+    // let the watch loop take over from the next one
+    let mut to_block = to_block;
+    let mut watch_start = None;
+    let mut skip_catch_up = false;
+    if args.watch {
+        let head = RpcPool::new(chain, &rpc_config)?.get_block_number().await?;
+        let catch_up_end = head.saturating_sub(args.reorg_depth);
+        skip_catch_up = !auto_from_block && from_block > catch_up_end;
+        to_block = ethcli::BlockNumber::Number(catch_up_end);
+        watch_start = Some(from_block.max(catch_up_end + 1));
+    }
 
     // Build main config
     let mut builder = Config::builder()
@@ -375,6 +365,7 @@ async fn run_logs(args: &LogsArgs, cli: &Cli) -> anyhow::Result<()> {
         .output_format(format)
         .concurrency(concurrency)
         .raw(args.raw)
+        .auto_abi(!args.no_auto_abi)
         .resume(args.resume)
         .quiet(cli.quiet)
         .verbosity(cli.verbose)
@@ -390,6 +381,10 @@ async fn run_logs(args: &LogsArgs, cli: &Cli) -> anyhow::Result<()> {
         builder = builder.abi_path(abi);
     }
 
+    if let Some(abi) = &args.abi_str {
+        builder = builder.abi_str(abi);
+    }
+
     if let Some(output) = &args.output {
         builder = builder.output_path(output);
     }
@@ -402,26 +397,50 @@ async fn run_logs(args: &LogsArgs, cli: &Cli) -> anyhow::Result<()> {
         builder = builder.etherscan_key(key);
     }
 
+    if args.estimate {
+        if chains.len() > 1 {
+            anyhow::bail!("--estimate takes a single chain");
+        }
+        // Only log counts are needed, so skip ABI lookup and decoding
+        let config = builder.raw(true).build()?;
+        return run_logs_estimate(args, cli, config).await;
+    }
+
     let config = builder.build()?;
 
     // Create output writer early for streaming mode
     let mut writer = ethcli::create_writer(format, args.output.as_deref())?;
 
+    if chains.len() > 1 {
+        return run_multichain_logs(args, cli, config, &chains, &checkpoint_dir, &mut writer).await;
+    }
+
     if !cli.quiet {
         eprintln!("Connecting to {} endpoints...", chain.display_name());
     }
 
+    // The watch loop builds its own fetcher from the same settings
+    let watch_config = watch_start.map(|_| {
+        let mut config = config.clone();
+        config.auto_from_block = false;
+        config
+    });
+
     let start = Instant::now();
-    let (total_logs, stats) = if args.resume {
+    let (total_logs, stats) = if skip_catch_up {
+        (0, FetchStats::default())
+    } else if args.resume {
         // Use streaming mode with checkpoint support
-        run_streaming_fetch(args, cli, config, &mut writer).await?
+        run_streaming_fetch(args, cli, config, &checkpoint_dir, &mut writer).await?
     } else {
         // Use batch mode (faster for smaller queries)
         run_batch_fetch_logs(args, cli, config, &mut writer).await?
     };
     let elapsed = start.elapsed();
 
-    writer.finalize()?;
+    if watch_start.is_none() {
+        writer.finalize()?;
+    }
 
     // Report failures
     if !stats.is_complete() {
@@ -467,6 +486,326 @@ async fn run_logs(args: &LogsArgs, cli: &Cli) -> anyhow::Result<()> {
         );
     }
 
+    if let (Some(start_block), Some(config)) = (watch_start, watch_config) {
+        writer.flush()?;
+        let watched = run_watch(args, cli, config, start_block, &mut writer).await;
+        // Close the output (e.g. the JSON array) even if watching failed
+        writer.finalize()?;
+        let watched = watched?;
+        if !cli.quiet {
+            eprintln!("Stopped watching: {} new logs", watched);
+        }
+    }
+
+    Ok(())
+}
+
+/// Sample log density and print the projected cost of the fetch
+async fn run_logs_estimate(args: &LogsArgs, cli: &Cli, config: Config) -> anyhow::Result<()> {
+    let json = match args.format.as_deref() {
+        None => false,
+        Some(f) if f.eq_ignore_ascii_case("json") => true,
+        Some(f) => anyhow::bail!("--estimate prints a summary or --format json, not {f}"),
+    };
+
+    if !cli.quiet {
+        eprintln!(
+            "Sampling {} block ranges on {}...",
+            args.samples,
+            config.chain.display_name()
+        );
+    }
+    let fetcher = LogFetcher::new(config).await?;
+    let estimate = fetcher.estimate(args.samples.max(1)).await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&estimate)?);
+        return Ok(());
+    }
+
+    let density = if estimate.blocks > 0 {
+        estimate.estimated_logs as f64 / estimate.blocks as f64
+    } else {
+        0.0
+    };
+    println!(
+        "Blocks:        {} - {} ({} blocks)",
+        estimate.from_block,
+        estimate.to_block,
+        format_thousands(estimate.blocks)
+    );
+    println!(
+        "Sampled:       {} ranges, {:.3} logs/block",
+        estimate.samples.len(),
+        density
+    );
+    println!(
+        "Logs:          ~{}",
+        format_thousands(estimate.estimated_logs)
+    );
+    let chunk_size = match estimate.chunk_size {
+        0 => "whole range".to_string(),
+        size => format!("{} blocks", format_thousands(size)),
+    };
+    println!(
+        "Chunks:        {} of {}",
+        format_thousands(estimate.chunks),
+        chunk_size
+    );
+    println!(
+        "Requests:      ~{} at concurrency {}",
+        format_thousands(estimate.requests),
+        estimate.concurrency
+    );
+    println!(
+        "Duration:      ~{:.0}s ({} ms per request)",
+        estimate.expected_duration_secs, estimate.latency_ms
+    );
+    for range in &estimate.hot_ranges {
+        println!(
+            "Dense range:   {} - {}: {:.1} logs/block, ~{} per chunk (limit {})",
+            range.from, range.to, range.logs_per_block, range.logs_per_chunk, estimate.max_logs
+        );
+    }
+    if let Some(size) = estimate.suggested_chunk_size {
+        println!("Suggestion:    --chunk-size {size}");
+    }
+    for warning in &estimate.warnings {
+        println!("Warning:       {warning}");
+    }
+    println!(
+        "Feasible:      {}",
+        if estimate.feasible { "yes" } else { "no" }
+    );
+    Ok(())
+}
+
+/// Follow the chain head for `logs --watch` until Ctrl-C
+///
+/// Returns the number of logs written.
+async fn run_watch(
+    args: &LogsArgs,
+    cli: &Cli,
+    config: Config,
+    start_block: u64,
+    writer: &mut Box<dyn OutputWriter>,
+) -> anyhow::Result<u64> {
+    let mut fetcher = StreamingFetcher::new(config).await?;
+    let endpoint_for_timestamps = args
+        .timestamps
+        .then(|| fetcher.pool().select_archive_endpoints(1));
+    let options = WatchOptions {
+        poll_interval: std::time::Duration::from_secs(args.poll_interval.max(1)),
+        reorg_depth: args.reorg_depth,
+    };
+
+    if !cli.quiet {
+        eprintln!(
+            "Watching for new logs from block {} (Ctrl-C to stop)...",
+            start_block
+        );
+    }
+
+    let shutdown = async {
+        let _ = tokio::signal::ctrl_c().await;
+    };
+    let watched = fetcher
+        .watch(start_block, &options, shutdown, |mut result| {
+            if let Some(ref endpoints) = endpoint_for_timestamps {
+                if let FetchLogs::Decoded(ref mut logs) = result.logs {
+                    // Same approach as streaming mode: run async code from the sync callback
+                    tokio::task::block_in_place(|| {
+                        let rt = tokio::runtime::Handle::current();
+                        rt.block_on(async {
+                            if let Err(e) = add_timestamps_to_logs(logs, endpoints).await {
+                                eprintln!("Warning: Failed to fetch timestamps: {e}");
+                            }
+                        });
+                    });
+                }
+            }
+
+            writer.write_logs(&result)?;
+            writer.flush()
+        })
+        .await?;
+
+    Ok(watched)
+}
+
+/// Resolve the start block for one chain from `--since`, `--from-block` or auto-detect
+///
+/// Returns `(from_block, auto_from_block)`.
+async fn resolve_from_block(
+    args: &LogsArgs,
+    chain: Chain,
+    rpc_config: &RpcConfig,
+    quiet: bool,
+) -> anyhow::Result<(u64, bool)> {
+    if let Some(since_str) = &args.since {
+        // Parse the duration string and calculate from_block
+        let duration_secs = parse_duration_string(since_str)?;
+        let blocks_back = chain.blocks_for_duration(duration_secs);
+
+        // Get current block number via a quick RPC call
+        let quick_pool = RpcPool::new(chain, rpc_config)?;
+        let current_block = quick_pool.get_block_number().await?;
+
+        let target_block = current_block.saturating_sub(blocks_back);
+
+        if !quiet {
+            eprintln!(
+                "Using --since {since_str} on {}: ~{blocks_back} blocks back from {current_block} to block {target_block}",
+                chain.name()
+            );
+        }
+
+        return Ok((target_block, false));
+    }
+
+    Ok(match &args.from_block {
+        Some(s) if s.to_lowercase() == "auto" => (0, true),
+        Some(s) => (s.parse::<u64>()?, false),
+        None => (0, true), // Default to auto-detect from contract creation
+    })
+}
+
+/// Run the logs command on several chains concurrently into one writer
+async fn run_multichain_logs(
+    args: &LogsArgs,
+    cli: &Cli,
+    config: Config,
+    chains: &[Chain],
+    checkpoint_dir: &Path,
+    writer: &mut Box<dyn OutputWriter>,
+) -> anyhow::Result<()> {
+    let mut configs = ethcli::multichain::fan_out(&config, chains);
+    // The template already holds the first chain's start block
+    for config in configs.iter_mut().skip(1) {
+        let (from_block, auto_from_block) =
+            resolve_from_block(args, config.chain, &config.rpc, cli.quiet).await?;
+        config.block_range = ethcli::BlockRange::Range {
+            from: from_block,
+            to: config.block_range.to_block(),
+        };
+        config.auto_from_block = auto_from_block;
+    }
+
+    // One archive endpoint per chain for timestamp lookups
+    let mut timestamp_endpoints: HashMap<u64, Vec<Arc<Endpoint>>> = HashMap::new();
+    if args.timestamps {
+        for config in &configs {
+            if let Ok(pool) = RpcPool::new(config.chain, &config.rpc) {
+                timestamp_endpoints
+                    .insert(config.chain.chain_id(), pool.select_archive_endpoints(1));
+            }
+        }
+    }
+
+    let mut fetcher = MultiChainFetcher::new(configs).strict(args.strict);
+    if args.resume {
+        let checkpoint_path = args.checkpoint.clone().unwrap_or_else(|| {
+            ethcli::checkpoint::default_checkpoint_path(&args.contract, true, checkpoint_dir)
+        });
+        fetcher = fetcher.with_checkpoint(&checkpoint_path)?;
+        if !cli.quiet {
+            eprintln!("Checkpoint: {}", checkpoint_path.display());
+        }
+    }
+
+    if !cli.quiet {
+        let names: Vec<&str> = chains.iter().map(|c| c.name()).collect();
+        eprintln!(
+            "Fetching from {} chains: {}",
+            chains.len(),
+            names.join(", ")
+        );
+    }
+
+    let start = Instant::now();
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<ethcli::FetchResult>();
+    let write = async {
+        while let Some(mut result) = rx.recv().await {
+            if let (Some(chain), FetchLogs::Decoded(logs)) = (result.chain, &mut result.logs) {
+                if let Some(endpoints) = timestamp_endpoints.get(&chain.chain_id()) {
+                    if let Err(e) = add_timestamps_to_logs(logs, endpoints).await {
+                        eprintln!(
+                            "Warning: Failed to fetch timestamps on {}: {e}",
+                            chain.name()
+                        );
+                    }
+                }
+            }
+            writer.write_logs(&result)?;
+        }
+        anyhow::Ok(())
+    };
+    let (summaries, written) = tokio::join!(fetcher.fetch(tx), write);
+    written?;
+    let summaries = summaries?;
+    let elapsed = start.elapsed();
+
+    writer.finalize()?;
+
+    report_multichain_summaries(&summaries, args.strict, cli, elapsed)
+}
+
+/// Print the per-chain summary and apply `--strict`
+fn report_multichain_summaries(
+    summaries: &[ChainFetchSummary],
+    strict: bool,
+    cli: &Cli,
+    elapsed: std::time::Duration,
+) -> anyhow::Result<()> {
+    for summary in summaries {
+        let chain = summary.chain;
+        if let Some(err) = &summary.error {
+            eprintln!("Error: {err}");
+        } else if !summary.stats.is_complete() {
+            eprintln!(
+                "Warning: {} ({}): {} of {} chunks failed",
+                chain.name(),
+                chain.chain_id(),
+                summary.stats.chunks_failed,
+                summary.stats.chunks_total
+            );
+            if cli.verbose > 0 || strict {
+                for (from, to, err) in &summary.stats.failed_ranges {
+                    eprintln!("  - Blocks {from}-{to}: {err}");
+                }
+            }
+        }
+    }
+
+    if !cli.quiet {
+        for summary in summaries.iter().filter(|s| s.error.is_none()) {
+            eprintln!(
+                "  {} ({}): {} logs, {}/{} chunks",
+                summary.chain.name(),
+                summary.chain.chain_id(),
+                format_thousands(summary.logs as u64),
+                summary.stats.chunks_succeeded,
+                summary.stats.chunks_total
+            );
+        }
+        let total: usize = summaries.iter().map(|s| s.logs).sum();
+        eprintln!(
+            "Fetched {} logs from {} chains in {:.2}s",
+            total,
+            summaries.len(),
+            elapsed.as_secs_f64()
+        );
+    }
+
+    let incomplete = summaries.iter().filter(|s| !s.is_complete()).count();
+    if strict && incomplete > 0 {
+        return Err(anyhow::anyhow!(
+            "Fetch incomplete on {incomplete} chain(s) (--strict mode)"
+        ));
+    }
+    if summaries.iter().all(|s| s.error.is_some()) {
+        return Err(anyhow::anyhow!("All {} chains failed", summaries.len()));
+    }
     Ok(())
 }
 
@@ -619,16 +958,14 @@ async fn run_streaming_fetch(
     args: &LogsArgs,
     cli: &Cli,
     config: Config,
+    checkpoint_dir: &Path,
     writer: &mut Box<dyn OutputWriter>,
 ) -> anyhow::Result<(usize, FetchStats)> {
     let fetcher = StreamingFetcher::new(config.clone()).await?;
 
     // Enable checkpointing if path specified or use default
     let checkpoint_path = args.checkpoint.clone().unwrap_or_else(|| {
-        PathBuf::from(format!(
-            ".eth-log-fetch-{}.checkpoint",
-            &args.contract[..8.min(args.contract.len())]
-        ))
+        ethcli::checkpoint::default_checkpoint_path(&args.contract, false, checkpoint_dir)
     });
 
     // Get an endpoint for timestamp fetching before moving the fetcher
@@ -810,37 +1147,6 @@ fn build_rpc_config_from_logs_args_full(
     Ok(rpc_config)
 }
 
-/// Build default RPC config (for tx command that doesn't have RPC args)
-fn build_default_rpc_config(config_file: &Option<ConfigFile>) -> anyhow::Result<RpcConfig> {
-    let mut rpc_config = RpcConfig::default();
-
-    // Add custom endpoints from config file
-    if let Some(cf) = config_file {
-        for endpoint in &cf.endpoints {
-            if !rpc_config.endpoints.iter().any(|e| e.url == endpoint.url) {
-                rpc_config.endpoints.push(endpoint.clone());
-            }
-        }
-    }
-
-    // Add disabled endpoints from config file
-    if let Some(cf) = config_file {
-        rpc_config
-            .exclude_endpoints
-            .extend(cf.disabled_endpoints.urls.clone());
-    }
-
-    // Apply config file defaults
-    if let Some(cf) = config_file {
-        rpc_config.timeout_secs = cf.settings.timeout_seconds;
-        rpc_config.max_retries = cf.settings.retry_attempts;
-        rpc_config.concurrency = cf.settings.concurrency;
-        rpc_config.proxy = cf.proxy_config();
-    }
-
-    Ok(rpc_config)
-}
-
 async fn handle_endpoints(action: &EndpointCommands, cli: &Cli) -> anyhow::Result<()> {
     use ethcli::{optimize_endpoint, NodeType};
 
@@ -905,7 +1211,7 @@ async fn handle_endpoints(action: &EndpointCommands, cli: &Cli) -> anyhow::Resul
 
             for (chain_name, mut eps) in by_chain {
                 // Sort by priority descending
-                eps.sort_by(|a, b| b.priority.cmp(&a.priority));
+                eps.sort_by_key(|e| std::cmp::Reverse(e.priority));
 
                 println!("=== {} ({}) ===", chain_name.to_uppercase(), eps.len());
                 for ep in eps {
@@ -941,6 +1247,9 @@ async fn handle_endpoints(action: &EndpointCommands, cli: &Cli) -> anyhow::Resul
                         if let Some(tested) = &ep.last_tested {
                             println!("      Last tested: {tested}");
                         }
+                        if ep.pinned {
+                            println!("      Priority pinned");
+                        }
                     }
                 }
                 println!();
@@ -1097,7 +1406,12 @@ async fn handle_endpoints(action: &EndpointCommands, cli: &Cli) -> anyhow::Resul
                                 format_thousands(result.config.max_block_range),
                                 format_thousands(result.config.max_logs as u64)
                             );
-                            config.endpoints[idx] = result.config;
+                            let mut updated = result.config;
+                            if config.endpoints[idx].pinned {
+                                updated.pinned = true;
+                                updated.priority = config.endpoints[idx].priority;
+                            }
+                            config.endpoints[idx] = updated;
                         } else {
                             println!(
                                 "FAILED: {}",
@@ -1394,6 +1708,96 @@ async fn handle_endpoints(action: &EndpointCommands, cli: &Cli) -> anyhow::Resul
                 );
             }
         }
+
+        EndpointCommands::Monitor {
+            interval,
+            apply,
+            dry_run,
+            chain: chain_filter,
+            min_priority,
+            max_priority,
+            disable_after,
+            timeout,
+            cycles,
+        } => {
+            use ethcli::rpc::monitor::{
+                ConfigFileStore, EndpointMonitor, MonitorOptions, MonitorPolicy, MonitorState,
+                OptimizerProber,
+            };
+
+            if min_priority > max_priority {
+                anyhow::bail!(
+                    "--min-priority ({}) must not exceed --max-priority ({})",
+                    min_priority,
+                    max_priority
+                );
+            }
+            let interval_secs = parse_duration_string(interval)?;
+            if interval_secs <= 0.0 {
+                anyhow::bail!("--interval must be positive");
+            }
+
+            let state_path = MonitorState::default_path();
+            let mut monitor = EndpointMonitor {
+                state: MonitorState::load(&state_path)?,
+                policy: MonitorPolicy {
+                    min_priority: *min_priority,
+                    max_priority: *max_priority,
+                    failure_threshold: *disable_after,
+                    ..Default::default()
+                },
+                chain: chain_filter.as_ref().map(|c| c.parse()).transpose()?,
+                state_path: (!dry_run).then(|| state_path.clone()),
+            };
+            let options = MonitorOptions {
+                interval: std::time::Duration::from_secs_f64(interval_secs),
+                max_cycles: if *dry_run { Some(1) } else { *cycles },
+                apply: *apply,
+            };
+            let prober = OptimizerProber {
+                timeout_secs: *timeout,
+            };
+            let mut store = ConfigFileStore::default_paths();
+            let changelog_path = store.changelog_path.clone();
+
+            if !dry_run {
+                println!(
+                    "Monitoring endpoints every {} ({}; history in {})",
+                    interval,
+                    if *apply {
+                        "applying changes"
+                    } else {
+                        "proposals only, pass --apply to write"
+                    },
+                    state_path.display()
+                );
+            }
+
+            monitor
+                .run(&prober, &mut store, &options, |report| {
+                    println!(
+                        "\n[cycle {}] probed {} endpoint(s), {} failed",
+                        report.cycle, report.probed, report.failed
+                    );
+                    if report.changes.is_empty() {
+                        println!("  No changes");
+                        return;
+                    }
+                    let verb = if report.applied {
+                        "Applied"
+                    } else {
+                        "Proposed"
+                    };
+                    println!("  {} changes:", verb);
+                    for change in &report.changes {
+                        println!("    {}", change);
+                    }
+                    if report.applied {
+                        println!("  Logged to {}", changelog_path.display());
+                    }
+                })
+                .await?;
+        }
     }
 
     Ok(())
@@ -1778,6 +2182,48 @@ async fn handle_config(action: &ConfigCommands) -> anyhow::Result<()> {
             }
             println!("Config validation passed.");
         }
+
+        ConfigCommands::Migrate { dry_run } => {
+            let path = ConfigFile::default_path();
+            if !path.exists() {
+                println!("No config file found at: {}", path.display());
+                println!("Run 'ethcli config init' to create one.");
+                return Ok(());
+            }
+
+            let report = ethcli::config::migrate_file(&path, *dry_run)?;
+            if report.is_current() {
+                println!(
+                    "{} is already at schema version {}; nothing to migrate.",
+                    path.display(),
+                    report.to_version
+                );
+                return Ok(());
+            }
+
+            println!(
+                "{} {} from schema version {} to {}:",
+                if *dry_run {
+                    "Would migrate"
+                } else {
+                    "Migrated"
+                },
+                path.display(),
+                report.from_version,
+                report.to_version
+            );
+            for change in &report.changes {
+                println!("  - {change}");
+            }
+            println!();
+            match &report.backup {
+                Some(backup) => {
+                    println!("Original saved to: {}", backup.display());
+                    println!("Comments were not carried over; copy any you need from the backup.");
+                }
+                None => println!("Dry run: no files were written."),
+            }
+        }
     }
 
     Ok(())
@@ -1803,6 +2249,9 @@ retry_attempts = 3
 # Save checkpoint every N blocks when fetching logs (default: 1000)
 checkpoint_interval = 1000
 
+# Directory for `logs --resume` checkpoints (default: <config dir>/checkpoints)
+# checkpoint_dir = "/var/lib/ethcli/checkpoints"
+
 # =============================================================================
 # API Keys (optional but recommended)
 # =============================================================================
@@ -1822,6 +2271,15 @@ checkpoint_interval = 1000
 # account = "your-account-slug"
 # project = "your-project-slug"
 
+# =============================================================================
+# Self-update (optional)
+# =============================================================================
+# Minisign public key that `ethcli update --install` verifies releases against.
+# Only needed to override the key built into release binaries.
+
+# [update]
+# public_key = "RW..."
+
 # =============================================================================
 # Debug RPC URLs (optional)
 # =============================================================================
@@ -1958,9 +2416,45 @@ urls = []
     .to_string()
 }
 
-async fn handle_tx(args: &TxArgs, cli: &Cli) -> anyhow::Result<()> {
+async fn handle_tx(args: &TxArgs, cli: &Cli, etherscan_key: Option<String>) -> anyhow::Result<()> {
     use std::io::BufRead;
 
+    match &args.command {
+        Some(TxCommands::Query {
+            db,
+            sql,
+            params,
+            output,
+        }) => {
+            return ethcli::cli::db::query(db, sql, params, *output, None, cli.quiet);
+        }
+        Some(TxCommands::GasBreakdown {
+            hash,
+            rpc_url,
+            format,
+        }) => {
+            let chain: Chain = cli.chain.parse()?;
+            return handle_tx_gas_breakdown(hash, rpc_url, *format, chain, cli.quiet).await;
+        }
+        Some(TxCommands::DecodeInput {
+            address,
+            calldata,
+            format,
+        }) => {
+            let chain: Chain = cli.chain.parse()?;
+            return handle_tx_decode_input(
+                address,
+                calldata,
+                *format,
+                chain,
+                etherscan_key,
+                cli.quiet,
+            )
+            .await;
+        }
+        None => {}
+    }
+
     // Collect all hashes from various sources
     let mut all_hashes: Vec<String> = args.hashes.clone();
 
@@ -1996,6 +2490,41 @@ async fn handle_tx(args: &TxArgs, cli: &Cli) -> anyhow::Result<()> {
         ));
     }
 
+    // Open the SQLite export and skip transactions a previous run already wrote
+    let mut sqlite_writer = match &args.sqlite {
+        Some(path) => {
+            let writer = ethcli::TxSqliteWriter::new(path)?;
+            let existing = writer.existing_hashes(&all_hashes)?;
+            if !existing.is_empty() {
+                let before = all_hashes.len();
+                all_hashes.retain(|h| {
+                    let hex = h.trim_start_matches("0x").trim_start_matches("0X");
+                    !existing.contains(&format!("0x{}", hex.to_lowercase()))
+                });
+                if !cli.quiet {
+                    eprintln!(
+                        "Skipping {} transaction{} already in {}",
+                        before - all_hashes.len(),
+                        if before - all_hashes.len() == 1 {
+                            ""
+                        } else {
+                            "s"
+                        },
+                        path.display()
+                    );
+                }
+            }
+            if all_hashes.is_empty() {
+                if !cli.quiet {
+                    eprintln!("Nothing to do: all transactions already exported");
+                }
+                return Ok(());
+            }
+            Some(writer)
+        }
+        None => None,
+    };
+
     // Parse chain
     let chain: Chain = cli.chain.parse()?;
 
@@ -2003,7 +2532,7 @@ async fn handle_tx(args: &TxArgs, cli: &Cli) -> anyhow::Result<()> {
     let config_file = load_config_with_warning();
 
     // Build RPC config with defaults
-    let rpc_config = build_default_rpc_config(&config_file)?;
+    let rpc_config = build_default_rpc_config(&config_file);
 
     // Create RPC pool
     let pool = RpcPool::new(chain, &rpc_config)?;
@@ -2021,14 +2550,24 @@ async fn handle_tx(args: &TxArgs, cli: &Cli) -> anyhow::Result<()> {
         );
     }
 
-    // Create analyzer
-    let analyzer = std::sync::Arc::new(TxAnalyzer::new(pool, chain)?);
+    // Create analyzer, decoding with the local ABI first if one was given
+    let mut analyzer = TxAnalyzer::new(pool, chain)?;
+    let user_abi =
+        ethcli::AbiFetcher::new(None)?.load_local(args.abi.as_deref(), args.abi_str.as_deref())?;
+    if let Some(abi) = user_abi {
+        analyzer = analyzer.with_abi(abi)?;
+    }
+    if !args.no_labels {
+        analyzer = analyzer.with_address_book(ethcli::config::AddressBook::load_default());
+    }
+    let analyzer = std::sync::Arc::new(analyzer);
 
     let start = Instant::now();
     let mut all_analyses = Vec::new();
     let mut total_events = 0;
     let mut total_transfers = 0;
     let mut failed_count = 0;
+    let mut written_count = 0;
 
     // Process in batches for parallelism
     for (batch_idx, batch) in all_hashes.chunks(args.batch_size).enumerate() {
@@ -2069,13 +2608,14 @@ async fn handle_tx(args: &TxArgs, cli: &Cli) -> anyhow::Result<()> {
             .collect();
 
         let results = futures::future::join_all(futures).await;
+        let mut batch_analyses = Vec::new();
 
         for (idx, hash, result) in results {
             match result {
                 Ok(analysis) => {
                     total_events += analysis.events.len();
                     total_transfers += analysis.token_flows.len();
-                    all_analyses.push((idx, analysis));
+                    batch_analyses.push((idx, analysis));
                 }
                 Err(e) => {
                     failed_count += 1;
@@ -2090,6 +2630,15 @@ async fn handle_tx(args: &TxArgs, cli: &Cli) -> anyhow::Result<()> {
                 }
             }
         }
+
+        // SQLite exports are written per batch so large runs stay out of memory
+        if let Some(writer) = sqlite_writer.as_mut() {
+            batch_analyses.sort_by_key(|(idx, _)| *idx);
+            let batch: Vec<_> = batch_analyses.into_iter().map(|(_, a)| a).collect();
+            written_count += writer.write_batch(&batch)?;
+        } else {
+            all_analyses.extend(batch_analyses);
+        }
     }
 
     // Sort by original index to maintain order
@@ -2103,7 +2652,17 @@ async fn handle_tx(args: &TxArgs, cli: &Cli) -> anyhow::Result<()> {
     let elapsed = start.elapsed();
 
     // Output
-    if args.output.is_json() {
+    if let (Some(writer), Some(path)) = (sqlite_writer.as_mut(), &args.sqlite) {
+        writer.finalize()?;
+        if !cli.quiet {
+            eprintln!(
+                "Wrote {} transaction{} to {}",
+                written_count,
+                if written_count == 1 { "" } else { "s" },
+                path.display()
+            );
+        }
+    } else if args.output.is_json() {
         if analyses.len() == 1 {
             let json = serde_json::to_string_pretty(&analyses[0])?;
             println!("{json}");
@@ -2125,23 +2684,25 @@ async fn handle_tx(args: &TxArgs, cli: &Cli) -> anyhow::Result<()> {
                 println!();
             }
             println!("{}", format_analysis(analysis));
-            // Add explorer link
-            if let Some(explorer) = chain.explorer_url() {
-                println!("\nExplorer: {}/tx/{:#x}", explorer, analysis.hash);
-            }
         }
     }
 
     if !cli.quiet {
+        for analysis in &analyses {
+            for warning in &analysis.warnings {
+                eprintln!("Warning: {:#x}: {}", analysis.hash, warning);
+            }
+        }
         let failed_msg = if failed_count > 0 {
             format!(", {failed_count} failed")
         } else {
             String::new()
         };
+        let analyzed = analyses.len().max(written_count);
         eprintln!(
             "\nAnalyzed {} transaction{} in {:.2}s ({} events, {} transfers{})",
-            analyses.len(),
-            if analyses.len() == 1 { "" } else { "s" },
+            analyzed,
+            if analyzed == 1 { "" } else { "s" },
             elapsed.as_secs_f64(),
             total_events,
             total_transfers,
@@ -2150,7 +2711,7 @@ async fn handle_tx(args: &TxArgs, cli: &Cli) -> anyhow::Result<()> {
         if tx_count > 10 {
             eprintln!(
                 "Throughput: {:.1} tx/s",
-                analyses.len() as f64 / elapsed.as_secs_f64()
+                analyzed as f64 / elapsed.as_secs_f64()
             );
         }
     }
@@ -2158,6 +2719,120 @@ async fn handle_tx(args: &TxArgs, cli: &Cli) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Decode calldata with the contract ABI, falling back to the 4byte database
+async fn handle_tx_decode_input(
+    address: &str,
+    calldata: &str,
+    format: ethcli::cli::OutputFormat,
+    chain: Chain,
+    etherscan_key: Option<String>,
+    quiet: bool,
+) -> anyhow::Result<()> {
+    use ethcli::DecodedCalldata;
+    use std::str::FromStr;
+
+    alloy::primitives::Address::from_str(address)
+        .map_err(|e| anyhow::anyhow!("Invalid address: {}", e))?;
+    let data = hex::decode(calldata.trim().trim_start_matches("0x"))
+        .map_err(|e| anyhow::anyhow!("Invalid calldata hex: {}", e))?;
+    if data.len() < 4 {
+        return Err(anyhow::anyhow!(
+            "Calldata must be at least 4 bytes (function selector)"
+        ));
+    }
+    let selector = format!("0x{}", hex::encode(&data[..4]));
+
+    let fetcher = ethcli::AbiFetcher::new(etherscan_key)?;
+    if !quiet {
+        eprintln!("Fetching ABI for {}...", address);
+    }
+    let from_abi = match fetcher.fetch_from_etherscan(chain, address).await {
+        Ok(abi) => DecodedCalldata::from_abi(&abi, &data).transpose()?,
+        Err(e) => {
+            if !quiet {
+                eprintln!("ABI unavailable ({}), trying signature database", e);
+            }
+            None
+        }
+    };
+
+    let decoded = match from_abi {
+        Some(decoded) => decoded,
+        None => {
+            let signature = fetcher.lookup_selector(&selector).await.ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Selector {} not found in the contract ABI or signature database",
+                    selector
+                )
+            })?;
+            DecodedCalldata::from_signature(&signature, &data)?
+        }
+    };
+
+    if format.is_json() {
+        println!("{}", serde_json::to_string_pretty(&decoded)?);
+    } else if format.is_ndjson() {
+        println!("{}", serde_json::to_string(&decoded)?);
+    } else {
+        print!("{}", decoded.format_text());
+    }
+
+    Ok(())
+}
+
+async fn handle_tx_gas_breakdown(
+    hash: &str,
+    rpc_url: &Option<String>,
+    format: ethcli::cli::OutputFormat,
+    chain: Chain,
+    quiet: bool,
+) -> anyhow::Result<()> {
+    use ethcli::tx::gas::trace_gas_breakdown;
+    use ethcli::utils::{Alignment, Table};
+
+    let hash = format!("0x{}", hash.trim().trim_start_matches("0x"));
+    let rpc = ethcli::cli::simulate::utils::get_debug_rpc_url(rpc_url, chain).ok_or_else(|| {
+        anyhow::anyhow!(
+            "gas-breakdown needs a debug-capable endpoint for {}. Pass --rpc-url, set \
+             has_debug = true on an endpoint, or use 'config add-debug-rpc'",
+            chain.display_name()
+        )
+    })?;
+    if !quiet {
+        eprintln!(
+            "Tracing {} on {}...",
+            hash,
+            ethcli::error::sanitize_error_message(&rpc)
+        );
+    }
+
+    let breakdown = trace_gas_breakdown(&rpc, &hash).await?;
+
+    if format.is_json() {
+        println!("{}", serde_json::to_string_pretty(&breakdown)?);
+    } else if format.is_ndjson() {
+        println!("{}", serde_json::to_string(&breakdown)?);
+    } else {
+        let status = if breakdown.failed { " (reverted)" } else { "" };
+        println!("Gas used: {}{}", breakdown.gas_used, status);
+        let mut table = Table::new(["Category", "Gas", "Percent"]).with_alignments([
+            Alignment::Left,
+            Alignment::Right,
+            Alignment::Right,
+        ]);
+        for share in &breakdown.categories {
+            table.add_row([
+                share.category.label().to_string(),
+                share.gas.to_string(),
+                format!("{:.2}%", share.percent),
+            ]);
+        }
+        table.print();
+    }
+
+    Ok(())
+}
+
 /// Handle --help-json flag
 ///
 /// Outputs JSON schema for the CLI or a specific subcommand.
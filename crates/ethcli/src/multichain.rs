@@ -0,0 +1,245 @@
+//! Fetch logs for the same contract on several chains in one run
+//!
+//! Contracts deployed at the same address on many chains (e.g. via CREATE2)
+//! can be indexed with a single invocation. Each chain gets its own
+//! [`Config`] and RPC pool; results are tagged with their chain so the
+//! output writers can add `chain`/`chain_id` columns.
+
+use crate::checkpoint::SharedCheckpoints;
+use crate::config::{Chain, Config};
+use crate::error::{ConfigError, Error, Result};
+use crate::fetcher::{FetchResult, FetchStats, LogFetcher, StreamingFetcher};
+use futures::future;
+use std::path::Path;
+use tokio::sync::mpsc;
+
+/// Parse a comma-separated chain list (names or chain IDs)
+///
+/// Duplicates are dropped, keeping the first occurrence.
+pub fn parse_chain_list(s: &str) -> Result<Vec<Chain>> {
+    let mut chains: Vec<Chain> = Vec::new();
+    for part in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let chain: Chain = part.parse()?;
+        if !chains.iter().any(|c| c.chain_id() == chain.chain_id()) {
+            chains.push(chain);
+        }
+    }
+    if chains.is_empty() {
+        return Err(ConfigError::InvalidChain(s.to_string()).into());
+    }
+    Ok(chains)
+}
+
+/// Split a global concurrency budget across `chains` fetchers
+///
+/// Every chain gets at least one slot; any remainder goes to the first chains.
+pub fn split_concurrency(total: usize, chains: usize) -> Vec<usize> {
+    if chains == 0 {
+        return Vec::new();
+    }
+    let base = total / chains;
+    let extra = total % chains;
+    (0..chains)
+        .map(|i| (base + usize::from(i < extra)).max(1))
+        .collect()
+}
+
+/// Build one config per chain from a template
+///
+/// The template's RPC concurrency is treated as the global budget and split
+/// with [`split_concurrency`].
+pub fn fan_out(template: &Config, chains: &[Chain]) -> Vec<Config> {
+    split_concurrency(template.rpc.concurrency, chains.len())
+        .into_iter()
+        .zip(chains)
+        .map(|(concurrency, &chain)| {
+            let mut config = template.clone();
+            config.chain = chain;
+            config.rpc.concurrency = concurrency;
+            config
+        })
+        .collect()
+}
+
+/// Outcome of fetching one chain
+#[derive(Debug, Clone)]
+pub struct ChainFetchSummary {
+    /// Chain fetched
+    pub chain: Chain,
+    /// Logs fetched
+    pub logs: usize,
+    /// Chunk statistics
+    pub stats: FetchStats,
+    /// Error that stopped this chain, if any
+    pub error: Option<String>,
+}
+
+impl ChainFetchSummary {
+    /// Whether the chain finished with no failed chunks
+    pub fn is_complete(&self) -> bool {
+        self.error.is_none() && self.stats.is_complete()
+    }
+}
+
+/// Runs one fetcher per chain concurrently
+pub struct MultiChainFetcher {
+    configs: Vec<Config>,
+    checkpoints: Option<SharedCheckpoints>,
+    strict: bool,
+}
+
+impl MultiChainFetcher {
+    /// Create a fetcher for per-chain configs (see [`fan_out`])
+    pub fn new(configs: Vec<Config>) -> Self {
+        Self {
+            configs,
+            checkpoints: None,
+            strict: false,
+        }
+    }
+
+    /// Stream each chain with checkpoints kept in one shared file
+    pub fn with_checkpoint(mut self, path: &Path) -> Result<Self> {
+        self.checkpoints = Some(SharedCheckpoints::open(path)?);
+        Ok(self)
+    }
+
+    /// Abort every chain as soon as one fails (default: keep going)
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Fetch every chain, sending chain-tagged results to `tx`
+    ///
+    /// Returns one summary per chain in config order. Without
+    /// [`strict`](Self::strict) a failing chain is recorded in its summary
+    /// and the others continue; in strict mode the first error is returned.
+    pub async fn fetch(
+        &self,
+        tx: mpsc::UnboundedSender<FetchResult>,
+    ) -> Result<Vec<ChainFetchSummary>> {
+        let tasks: Vec<_> = self
+            .configs
+            .iter()
+            .cloned()
+            .map(|config| {
+                let tx = tx.clone();
+                let checkpoints = self.checkpoints.clone();
+                async move {
+                    let chain = config.chain;
+                    let result = Self::fetch_chain(config, checkpoints.as_ref(), &tx)
+                        .await
+                        .map_err(|e| {
+                            Error::Other(format!("{} ({}): {}", chain.name(), chain.chain_id(), e))
+                        });
+                    (chain, result)
+                }
+            })
+            .collect();
+        drop(tx);
+
+        let summary = |chain: Chain, result: Result<(usize, FetchStats)>| match result {
+            Ok((logs, stats)) => ChainFetchSummary {
+                chain,
+                logs,
+                stats,
+                error: None,
+            },
+            Err(e) => ChainFetchSummary {
+                chain,
+                logs: 0,
+                stats: FetchStats::default(),
+                error: Some(e.to_string()),
+            },
+        };
+
+        if self.strict {
+            let results = future::try_join_all(tasks.into_iter().map(|task| async move {
+                let (chain, result) = task.await;
+                result.map(|ok| (chain, ok))
+            }))
+            .await?;
+            Ok(results
+                .into_iter()
+                .map(|(chain, ok)| summary(chain, Ok(ok)))
+                .collect())
+        } else {
+            Ok(future::join_all(tasks)
+                .await
+                .into_iter()
+                .map(|(chain, result)| summary(chain, result))
+                .collect())
+        }
+    }
+
+    async fn fetch_chain(
+        config: Config,
+        checkpoints: Option<&SharedCheckpoints>,
+        tx: &mpsc::UnboundedSender<FetchResult>,
+    ) -> Result<(usize, FetchStats)> {
+        let chain = config.chain;
+        let send = |mut result: FetchResult| {
+            result.chain = Some(chain);
+            tx.send(result)
+                .map_err(|_| Error::Other("Output channel closed".to_string()))
+        };
+
+        match checkpoints {
+            Some(checkpoints) => {
+                let mut fetcher = StreamingFetcher::new(config)
+                    .await?
+                    .with_shared_checkpoint(checkpoints)?;
+                let mut logs = 0;
+                let stats = fetcher
+                    .fetch_streaming(|result| {
+                        logs += result.len();
+                        send(result)
+                    })
+                    .await?;
+                Ok((logs, stats))
+            }
+            None => {
+                let result = LogFetcher::new(config).await?.fetch_all().await?;
+                let logs = result.len();
+                let stats = result.stats.clone();
+                send(result)?;
+                Ok((logs, stats))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_chain_list() {
+        let chains = parse_chain_list("ethereum, arbitrum,eth,8453").unwrap();
+        assert_eq!(chains, vec![Chain::Ethereum, Chain::Arbitrum, Chain::Base]);
+        assert!(parse_chain_list(" , ").is_err());
+        assert!(parse_chain_list("ethereum,nope").is_err());
+    }
+
+    #[test]
+    fn test_split_concurrency() {
+        assert_eq!(split_concurrency(10, 4), vec![3, 3, 2, 2]);
+        assert_eq!(split_concurrency(2, 4), vec![1, 1, 1, 1]);
+        assert!(split_concurrency(5, 0).is_empty());
+    }
+
+    #[test]
+    fn test_fan_out() {
+        let template = Config::builder()
+            .contract("0x0000000000000000000000000000000000000001")
+            .concurrency(5)
+            .build()
+            .unwrap();
+        let configs = fan_out(&template, &[Chain::Ethereum, Chain::Polygon]);
+        assert_eq!(configs[0].chain, Chain::Ethereum);
+        assert_eq!(configs[1].chain, Chain::Polygon);
+        assert_eq!(configs[0].rpc.concurrency, 3);
+        assert_eq!(configs[1].rpc.concurrency, 2);
+    }
+}
@@ -0,0 +1,525 @@
+//! ERC-721 and ERC-1155 support
+//!
+//! Detects which token standard a contract implements (ERC-165 probing with
+//! fallbacks) and reconstructs NFT ownership from `Transfer`,
+//! `TransferSingle` and `TransferBatch` logs.
+
+use crate::rpc::multicall::{selectors, MulticallBuilder, MulticallResult};
+use alloy::primitives::{Address, B256, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::Log;
+use alloy::sol_types::SolEvent;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// ERC-165 interface id of ERC-721
+pub const ERC721_INTERFACE_ID: [u8; 4] = [0x80, 0xac, 0x58, 0xcd];
+
+/// Interface id reported by pre-final ERC-721 drafts (e.g. CryptoKitties)
+pub const ERC721_DRAFT_INTERFACE_ID: [u8; 4] = [0x9a, 0x20, 0x48, 0x3d];
+
+/// ERC-165 interface id of ERC-1155
+pub const ERC1155_INTERFACE_ID: [u8; 4] = [0xd9, 0xb6, 0x7a, 0x26];
+
+/// ERC-165 requires this id to be reported as unsupported
+const INVALID_INTERFACE_ID: [u8; 4] = [0xff; 4];
+
+/// NFT transfer events
+pub mod events {
+    alloy::sol! {
+        /// ERC-721 transfer (same topic as ERC-20 `Transfer`, but 4 topics)
+        event Transfer(address indexed from, address indexed to, uint256 indexed tokenId);
+
+        /// ERC-1155 single transfer
+        event TransferSingle(
+            address indexed operator,
+            address indexed from,
+            address indexed to,
+            uint256 id,
+            uint256 value
+        );
+
+        /// ERC-1155 batch transfer
+        event TransferBatch(
+            address indexed operator,
+            address indexed from,
+            address indexed to,
+            uint256[] ids,
+            uint256[] values
+        );
+    }
+}
+
+/// Token standard implemented by a contract
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, clap::ValueEnum)]
+pub enum TokenStandard {
+    /// Fungible token
+    #[value(name = "erc20")]
+    Erc20,
+    /// Non-fungible token
+    #[value(name = "erc721")]
+    Erc721,
+    /// Multi-token
+    #[value(name = "erc1155")]
+    Erc1155,
+    /// Could not be determined
+    #[value(skip)]
+    Unknown,
+}
+
+impl TokenStandard {
+    /// Whether this is ERC-721 or ERC-1155
+    pub fn is_nft(&self) -> bool {
+        matches!(self, Self::Erc721 | Self::Erc1155)
+    }
+
+    /// Lowercase identifier used in JSON output
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Erc20 => "erc20",
+            Self::Erc721 => "erc721",
+            Self::Erc1155 => "erc1155",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+impl fmt::Display for TokenStandard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Erc20 => "ERC-20",
+            Self::Erc721 => "ERC-721",
+            Self::Erc1155 => "ERC-1155",
+            Self::Unknown => "unknown",
+        })
+    }
+}
+
+/// Number of calls appended by [`add_probe_calls`]
+pub const PROBE_CALLS: usize = 5;
+
+/// Append the standard detection calls for `token` to a multicall
+///
+/// Pass the [`PROBE_CALLS`] results they produce to [`standard_from_probe`].
+/// Appending them to an existing batch keeps detection to one round trip.
+pub fn add_probe_calls(builder: MulticallBuilder, token: Address) -> MulticallBuilder {
+    builder
+        .add_call_allow_failure(token, selectors::supports_interface(INVALID_INTERFACE_ID))
+        .add_call_allow_failure(token, selectors::supports_interface(ERC1155_INTERFACE_ID))
+        .add_call_allow_failure(token, selectors::supports_interface(ERC721_INTERFACE_ID))
+        .add_call_allow_failure(
+            token,
+            selectors::supports_interface(ERC721_DRAFT_INTERFACE_ID),
+        )
+        .add_call_allow_failure(token, selectors::decimals())
+}
+
+/// Decide the standard from the results of [`add_probe_calls`]
+///
+/// ERC-165 answers are only trusted when the contract reports the invalid id
+/// `0xffffffff` as unsupported; contracts whose fallback answers `true` to
+/// everything are otherwise mistaken for NFTs. Without a usable ERC-165
+/// answer, a contract with `decimals()` is taken to be ERC-20.
+pub fn standard_from_probe(results: &[MulticallResult]) -> TokenStandard {
+    let supports = |i: usize| results.get(i).and_then(MulticallResult::decode_bool);
+
+    if supports(0) == Some(false) {
+        if supports(1) == Some(true) {
+            return TokenStandard::Erc1155;
+        }
+        if supports(2) == Some(true) || supports(3) == Some(true) {
+            return TokenStandard::Erc721;
+        }
+    }
+
+    let has_decimals = results
+        .get(4)
+        .and_then(MulticallResult::decode_uint256)
+        .is_some_and(|d| d <= U256::from(u8::MAX));
+    if has_decimals {
+        TokenStandard::Erc20
+    } else {
+        TokenStandard::Unknown
+    }
+}
+
+/// Detect the standard implemented by `token` with a single multicall
+pub async fn detect_standard<P: Provider>(
+    provider: &P,
+    token: Address,
+) -> anyhow::Result<TokenStandard> {
+    let results = add_probe_calls(MulticallBuilder::new(), token)
+        .execute_with_retry(provider, 3)
+        .await?;
+    Ok(standard_from_probe(&results))
+}
+
+/// Topics of the events that move NFTs, for log filters
+pub fn transfer_topics() -> [B256; 3] {
+    [
+        events::Transfer::SIGNATURE_HASH,
+        events::TransferSingle::SIGNATURE_HASH,
+        events::TransferBatch::SIGNATURE_HASH,
+    ]
+}
+
+/// One token movement decoded from a transfer log
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NftTransfer {
+    /// Block the log was emitted in
+    pub block_number: Option<u64>,
+    /// Position of the log in its block
+    pub log_index: Option<u64>,
+    /// Transaction that emitted the log
+    pub transaction_hash: Option<B256>,
+    /// Approved operator that moved the tokens (ERC-1155 only)
+    pub operator: Option<Address>,
+    /// Previous holder (zero address for mints)
+    pub from: Address,
+    /// New holder (zero address for burns)
+    pub to: Address,
+    /// Token id
+    pub token_id: U256,
+    /// Amount moved (always 1 for ERC-721)
+    pub amount: U256,
+}
+
+/// Decode an NFT transfer log into one row per token id moved
+///
+/// `TransferBatch` yields one row per id. ERC-20 `Transfer` logs (three
+/// topics), other events and malformed logs yield nothing.
+pub fn decode_transfers(log: &Log) -> Vec<NftTransfer> {
+    let row = |operator, from, to, token_id, amount| NftTransfer {
+        block_number: log.block_number,
+        log_index: log.log_index,
+        transaction_hash: log.transaction_hash,
+        operator,
+        from,
+        to,
+        token_id,
+        amount,
+    };
+
+    let data = log.data();
+    match data.topics().first() {
+        Some(&events::Transfer::SIGNATURE_HASH) if data.topics().len() == 4 => {
+            events::Transfer::decode_log_data(data)
+                .map(|e| vec![row(None, e.from, e.to, e.tokenId, U256::from(1))])
+                .unwrap_or_default()
+        }
+        Some(&events::TransferSingle::SIGNATURE_HASH) => {
+            events::TransferSingle::decode_log_data(data)
+                .map(|e| vec![row(Some(e.operator), e.from, e.to, e.id, e.value)])
+                .unwrap_or_default()
+        }
+        Some(&events::TransferBatch::SIGNATURE_HASH) => {
+            match events::TransferBatch::decode_log_data(data) {
+                Ok(e) if e.ids.len() == e.values.len() => e
+                    .ids
+                    .iter()
+                    .zip(&e.values)
+                    .map(|(id, value)| row(Some(e.operator), e.from, e.to, *id, *value))
+                    .collect(),
+                _ => Vec::new(),
+            }
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// A holder and the tokens they hold
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NftHolder {
+    /// Holder address
+    pub address: Address,
+    /// Sum of the balances in `tokens`
+    pub total: U256,
+    /// Token ids held with their balance, by ascending id
+    pub tokens: Vec<(U256, U256)>,
+}
+
+/// Token balances reconstructed from transfer logs
+///
+/// Only as complete as the logs applied: transfers before the first block
+/// fetched are missing, so fetch from the contract's deployment block.
+#[derive(Debug, Clone, Default)]
+pub struct NftHolders {
+    /// token id -> holder -> balance (zero balances removed)
+    balances: BTreeMap<U256, BTreeMap<Address, U256>>,
+    /// Number of transfer rows applied
+    transfers: usize,
+}
+
+impl NftHolders {
+    /// Create an empty set of balances
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decode `logs` and apply their transfers in chain order
+    pub fn from_logs<'a>(logs: impl IntoIterator<Item = &'a Log>) -> Self {
+        let mut transfers: Vec<NftTransfer> = logs.into_iter().flat_map(decode_transfers).collect();
+        // Pending logs have no position; apply them last
+        transfers.sort_by_key(|t| {
+            (
+                t.block_number.unwrap_or(u64::MAX),
+                t.log_index.unwrap_or(u64::MAX),
+            )
+        });
+
+        let mut holders = Self::new();
+        for transfer in &transfers {
+            holders.apply(transfer);
+        }
+        holders
+    }
+
+    /// Apply a single transfer
+    ///
+    /// Balances never go below zero, so starting mid-history under-counts
+    /// rather than failing.
+    pub fn apply(&mut self, transfer: &NftTransfer) {
+        self.transfers += 1;
+        let owners = self.balances.entry(transfer.token_id).or_default();
+
+        if !transfer.from.is_zero() {
+            if let Some(balance) = owners.get_mut(&transfer.from) {
+                *balance = balance.saturating_sub(transfer.amount);
+                if balance.is_zero() {
+                    owners.remove(&transfer.from);
+                }
+            }
+        }
+        if !transfer.to.is_zero() && !transfer.amount.is_zero() {
+            *owners.entry(transfer.to).or_default() += transfer.amount;
+        }
+
+        if owners.is_empty() {
+            self.balances.remove(&transfer.token_id);
+        }
+    }
+
+    /// Number of transfer rows applied
+    pub fn transfer_count(&self) -> usize {
+        self.transfers
+    }
+
+    /// Balance of `holder` for token `id`
+    pub fn balance_of(&self, holder: Address, id: U256) -> U256 {
+        self.balances
+            .get(&id)
+            .and_then(|owners| owners.get(&holder))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Sole owner of token `id`, if exactly one address holds it
+    pub fn owner_of(&self, id: U256) -> Option<Address> {
+        let owners = self.balances.get(&id)?;
+        if owners.len() == 1 {
+            owners.keys().next().copied()
+        } else {
+            None
+        }
+    }
+
+    /// Token ids with at least one holder, ascending
+    pub fn token_ids(&self) -> impl Iterator<Item = U256> + '_ {
+        self.balances.keys().copied()
+    }
+
+    /// All holders, largest total balance first
+    pub fn holders(&self) -> Vec<NftHolder> {
+        self.collect_holders(|_| true)
+    }
+
+    /// Holders of token `id`, largest balance first
+    pub fn holders_of(&self, id: U256) -> Vec<NftHolder> {
+        self.collect_holders(|token_id| token_id == id)
+    }
+
+    fn collect_holders(&self, include: impl Fn(U256) -> bool) -> Vec<NftHolder> {
+        let mut by_holder: BTreeMap<Address, NftHolder> = BTreeMap::new();
+        for (&id, owners) in self.balances.iter().filter(|(id, _)| include(**id)) {
+            for (&address, &balance) in owners {
+                let holder = by_holder.entry(address).or_insert_with(|| NftHolder {
+                    address,
+                    total: U256::ZERO,
+                    tokens: Vec::new(),
+                });
+                holder.total = holder.total.saturating_add(balance);
+                holder.tokens.push((id, balance));
+            }
+        }
+
+        let mut holders: Vec<NftHolder> = by_holder.into_values().collect();
+        // Ties keep address order from the map (sort is stable)
+        holders.sort_by_key(|h| std::cmp::Reverse(h.total));
+        holders
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{address, keccak256, Bytes, LogData};
+
+    fn selector(signature: &str) -> [u8; 4] {
+        keccak256(signature)[..4].try_into().unwrap()
+    }
+
+    fn ok(data: Vec<u8>) -> MulticallResult {
+        MulticallResult {
+            success: true,
+            data: Bytes::from(data),
+        }
+    }
+
+    fn bool_word(value: bool) -> MulticallResult {
+        let mut word = vec![0u8; 32];
+        word[31] = u8::from(value);
+        ok(word)
+    }
+
+    fn failed() -> MulticallResult {
+        MulticallResult {
+            success: false,
+            data: Bytes::new(),
+        }
+    }
+
+    #[test]
+    fn test_selectors_match_signatures() {
+        assert_eq!(
+            selectors::supports_interface([0; 4])[..4],
+            selector("supportsInterface(bytes4)")
+        );
+        assert_eq!(
+            selectors::owner_of(U256::ZERO)[..4],
+            selector("ownerOf(uint256)")
+        );
+        assert_eq!(
+            selectors::balance_of_id(Address::ZERO, U256::ZERO)[..4],
+            selector("balanceOf(address,uint256)")
+        );
+        assert_eq!(selectors::uri(U256::ZERO)[..4], selector("uri(uint256)"));
+        assert_eq!(selectors::base_uri()[..], selector("baseURI()"));
+        assert_eq!(selectors::contract_uri()[..], selector("contractURI()"));
+        assert_eq!(
+            selectors::balance_of_id(Address::ZERO, U256::ZERO).len(),
+            68
+        );
+    }
+
+    #[test]
+    fn test_standard_from_probe() {
+        let probe = |invalid, erc1155, erc721, draft, decimals| {
+            standard_from_probe(&[invalid, erc1155, erc721, draft, decimals])
+        };
+
+        assert_eq!(
+            probe(
+                bool_word(false),
+                bool_word(true),
+                bool_word(false),
+                bool_word(false),
+                failed()
+            ),
+            TokenStandard::Erc1155
+        );
+        assert_eq!(
+            probe(
+                bool_word(false),
+                bool_word(false),
+                bool_word(true),
+                bool_word(false),
+                failed()
+            ),
+            TokenStandard::Erc721
+        );
+        // Draft interface id only
+        assert_eq!(
+            probe(
+                bool_word(false),
+                bool_word(false),
+                bool_word(false),
+                bool_word(true),
+                failed()
+            ),
+            TokenStandard::Erc721
+        );
+        // No ERC-165, has decimals
+        assert_eq!(
+            probe(
+                failed(),
+                failed(),
+                failed(),
+                failed(),
+                ok(U256::from(18).to_be_bytes_vec())
+            ),
+            TokenStandard::Erc20
+        );
+        // Answers true to everything: ERC-165 ignored
+        assert_eq!(
+            probe(
+                bool_word(true),
+                bool_word(true),
+                bool_word(true),
+                bool_word(true),
+                ok(U256::from(6).to_be_bytes_vec())
+            ),
+            TokenStandard::Erc20
+        );
+        // Fallback returning non-bool data, no decimals
+        assert_eq!(
+            probe(
+                ok(vec![0xff; 32]),
+                ok(vec![0xff; 32]),
+                ok(vec![]),
+                ok(vec![]),
+                ok(vec![])
+            ),
+            TokenStandard::Unknown
+        );
+    }
+
+    #[test]
+    fn test_decode_erc20_transfer_is_ignored() {
+        let a = address!("00000000000000000000000000000000000000aa");
+        let b = address!("00000000000000000000000000000000000000bb");
+        let topics = vec![
+            events::Transfer::SIGNATURE_HASH,
+            a.into_word(),
+            b.into_word(),
+        ];
+        let log = Log {
+            inner: alloy::primitives::Log {
+                address: Address::ZERO,
+                data: LogData::new_unchecked(topics, U256::from(5).to_be_bytes_vec().into()),
+            },
+            ..Default::default()
+        };
+        assert!(decode_transfers(&log).is_empty());
+    }
+
+    #[test]
+    fn test_apply_never_underflows() {
+        let a = address!("00000000000000000000000000000000000000aa");
+        let b = address!("00000000000000000000000000000000000000bb");
+        let mut holders = NftHolders::new();
+        // Transfer out of a balance minted before the logs start
+        holders.apply(&NftTransfer {
+            block_number: Some(1),
+            log_index: Some(0),
+            transaction_hash: None,
+            operator: None,
+            from: a,
+            to: b,
+            token_id: U256::from(7),
+            amount: U256::from(1),
+        });
+        assert_eq!(holders.balance_of(a, U256::from(7)), U256::ZERO);
+        assert_eq!(holders.owner_of(U256::from(7)), Some(b));
+        assert_eq!(holders.transfer_count(), 1);
+    }
+}
@@ -1,6 +1,7 @@
 //! CSV output writer
 
 use crate::abi::{DecodedLog, DecodedValue};
+use crate::config::Chain;
 use crate::error::{OutputError, Result};
 use crate::fetcher::{FetchLogs, FetchResult};
 use crate::output::OutputWriter;
@@ -21,11 +22,13 @@ pub struct CsvWriter {
     /// Whether header has been written
     header_written: bool,
     /// Buffered rows (before header is determined)
-    buffer: Vec<DecodedLog>,
+    buffer: Vec<(Option<Chain>, DecodedLog)>,
     /// Max rows to buffer before writing header
     max_buffer: usize,
     /// Columns that appeared after header was written (with count of dropped values)
     dropped_columns: HashMap<String, usize>,
+    /// Whether rows lead with `chain`/`chain_id` (decided by the first batch)
+    chain_columns: Option<bool>,
 }
 
 impl CsvWriter {
@@ -49,6 +52,7 @@ impl CsvWriter {
             buffer: Vec::new(),
             max_buffer: 1000, // Buffer more rows to determine schema
             dropped_columns: HashMap::new(),
+            chain_columns: None,
         })
     }
 
@@ -68,14 +72,17 @@ impl CsvWriter {
         self.columns.sort();
 
         // Build header
-        let mut header = vec![
+        let mut header = self.chain_header();
+        header.extend([
             "block_number".to_string(),
             "transaction_hash".to_string(),
             "log_index".to_string(),
             "address".to_string(),
             "event_name".to_string(),
-        ];
+        ]);
         header.extend(self.columns.clone());
+        // Last, so columns that existed before it keep their positions
+        header.push("decoder".to_string());
 
         self.writer
             .write_record(&header)
@@ -85,15 +92,36 @@ impl CsvWriter {
         Ok(())
     }
 
+    /// Leading header columns for chain-tagged output
+    fn chain_header(&self) -> Vec<String> {
+        if self.chain_columns == Some(true) {
+            vec!["chain".to_string(), "chain_id".to_string()]
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Leading row values for chain-tagged output
+    fn chain_values(&self, chain: Option<Chain>) -> Vec<String> {
+        if self.chain_columns != Some(true) {
+            return Vec::new();
+        }
+        match chain {
+            Some(chain) => vec![chain.name().to_string(), chain.chain_id().to_string()],
+            None => vec![String::new(), String::new()],
+        }
+    }
+
     /// Write a single decoded log as a row
-    fn write_row(&mut self, log: &DecodedLog) -> Result<()> {
-        let mut row = vec![
+    fn write_row(&mut self, chain: Option<Chain>, log: &DecodedLog) -> Result<()> {
+        let mut row = self.chain_values(chain);
+        row.extend([
             log.block_number.to_string(),
             format!("{:#x}", log.transaction_hash),
             log.log_index.to_string(),
             format!("{:#x}", log.address),
             log.event_name.clone(),
-        ];
+        ]);
 
         // Add parameter values in column order
         for col in &self.columns {
@@ -104,6 +132,7 @@ impl CsvWriter {
                 .unwrap_or_default();
             row.push(value);
         }
+        row.push(log.decoder.as_str().to_string());
 
         self.writer
             .write_record(&row)
@@ -165,7 +194,7 @@ impl CsvWriter {
         }
 
         // Collect columns from all buffered rows
-        for log in &self.buffer {
+        for (_, log) in &self.buffer {
             for key in log.params.keys() {
                 if !self.column_set.contains(key) {
                     self.column_set.insert(key.clone());
@@ -179,40 +208,47 @@ impl CsvWriter {
 
         // Write buffered rows
         let buffer = std::mem::take(&mut self.buffer);
-        for log in buffer {
-            self.write_row(&log)?;
+        for (chain, log) in buffer {
+            self.write_row(chain, &log)?;
         }
 
         Ok(())
     }
 
     /// Write raw log (limited CSV support)
-    fn write_raw_log(&mut self, log: &Log) -> Result<()> {
+    fn write_raw_log(&mut self, chain: Option<Chain>, log: &Log) -> Result<()> {
         if !self.header_written {
-            self.writer
-                .write_record([
+            let mut header = self.chain_header();
+            header.extend(
+                [
                     "block_number",
                     "transaction_hash",
                     "log_index",
                     "address",
                     "topics",
                     "data",
-                ])
+                ]
+                .map(String::from),
+            );
+            self.writer
+                .write_record(&header)
                 .map_err(|e| OutputError::CsvWrite(e.to_string()))?;
             self.header_written = true;
         }
 
         let topics: Vec<String> = log.topics().iter().map(|t| format!("{:#x}", t)).collect();
 
+        let mut row = self.chain_values(chain);
+        row.extend([
+            log.block_number.unwrap_or(0).to_string(),
+            format!("{:#x}", log.transaction_hash.unwrap_or_default()),
+            log.log_index.unwrap_or(0).to_string(),
+            format!("{:#x}", log.address()),
+            topics.join(";"),
+            format!("0x{}", hex::encode(&log.data().data)),
+        ]);
         self.writer
-            .write_record(&[
-                log.block_number.unwrap_or(0).to_string(),
-                format!("{:#x}", log.transaction_hash.unwrap_or_default()),
-                log.log_index.unwrap_or(0).to_string(),
-                format!("{:#x}", log.address()),
-                topics.join(";"),
-                format!("0x{}", hex::encode(&log.data().data)),
-            ])
+            .write_record(&row)
             .map_err(|e| OutputError::CsvWrite(e.to_string()))?;
 
         Ok(())
@@ -221,13 +257,16 @@ impl CsvWriter {
 
 impl OutputWriter for CsvWriter {
     fn write_logs(&mut self, result: &FetchResult) -> Result<()> {
+        if self.chain_columns.is_none() {
+            self.chain_columns = Some(result.chain.is_some());
+        }
         match &result.logs {
             FetchLogs::Decoded(logs) => {
                 for log in logs {
                     if !self.header_written {
                         // Buffer rows until we have enough to determine columns
                         self.collect_columns(log);
-                        self.buffer.push(log.clone());
+                        self.buffer.push((result.chain, log.clone()));
 
                         if self.buffer.len() >= self.max_buffer {
                             self.flush_buffer()?;
@@ -249,13 +288,13 @@ impl OutputWriter for CsvWriter {
                             }
                         }
 
-                        self.write_row(log)?;
+                        self.write_row(result.chain, log)?;
                     }
                 }
             }
             FetchLogs::Raw(logs) => {
                 for log in logs {
-                    self.write_raw_log(log)?;
+                    self.write_raw_log(result.chain, log)?;
                 }
             }
         }
@@ -295,6 +334,7 @@ impl OutputWriter for CsvWriter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::abi::DecoderSource;
 
     #[test]
     fn test_value_to_string() {
@@ -316,4 +356,80 @@ mod tests {
             "[1,2]"
         );
     }
+
+    #[test]
+    fn test_chain_columns() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.csv");
+        let mut writer = CsvWriter::new(Some(&path)).unwrap();
+
+        let log = DecodedLog {
+            block_number: 7,
+            timestamp: None,
+            transaction_hash: Default::default(),
+            log_index: 0,
+            address: Default::default(),
+            event_name: "Transfer".to_string(),
+            event_signature: "Transfer(address,address,uint256)".to_string(),
+            params: HashMap::new(),
+            topics: vec![],
+            data: vec![],
+            decoder: Default::default(),
+        };
+        for chain in [Chain::Ethereum, Chain::Arbitrum] {
+            writer
+                .write_logs(&FetchResult {
+                    logs: FetchLogs::Decoded(vec![log.clone()]),
+                    stats: Default::default(),
+                    chain: Some(chain),
+                })
+                .unwrap();
+        }
+        writer.finalize().unwrap();
+        drop(writer);
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert!(lines[0].starts_with("chain,chain_id,block_number,"));
+        assert!(lines[1].starts_with("ethereum,1,7,"));
+        assert!(lines[2].starts_with("arbitrum,42161,7,"));
+    }
+
+    #[test]
+    fn test_decoder_column_comes_last() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.csv");
+        let mut writer = CsvWriter::new(Some(&path)).unwrap();
+
+        let log = DecodedLog {
+            block_number: 7,
+            timestamp: None,
+            transaction_hash: Default::default(),
+            log_index: 0,
+            address: Default::default(),
+            event_name: "Transfer".to_string(),
+            event_signature: "Transfer(address,address,uint256)".to_string(),
+            params: HashMap::from([("value".to_string(), DecodedValue::Bool(true))]),
+            topics: vec![],
+            data: vec![],
+            decoder: DecoderSource::Signature,
+        };
+        writer
+            .write_logs(&FetchResult {
+                logs: FetchLogs::Decoded(vec![log]),
+                stats: Default::default(),
+                chain: None,
+            })
+            .unwrap();
+        writer.finalize().unwrap();
+        drop(writer);
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(
+            lines[0],
+            "block_number,transaction_hash,log_index,address,event_name,value,decoder"
+        );
+        assert!(lines[1].ends_with(",true,signature"), "{}", lines[1]);
+    }
 }
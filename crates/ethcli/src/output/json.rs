@@ -1,14 +1,25 @@
 //! JSON output writer
 
 use crate::abi::DecodedLog;
+use crate::config::Chain;
 use crate::error::{OutputError, Result};
 use crate::fetcher::{FetchLogs, FetchResult};
 use crate::output::OutputWriter;
 use alloy::rpc::types::Log;
+use serde::Serialize;
 use std::fs::File;
 use std::io::{self, BufWriter, Write};
 use std::path::Path;
 
+/// A log with the chain it came from as leading fields
+#[derive(Serialize)]
+struct ChainTagged<'a, T> {
+    chain: &'static str,
+    chain_id: u64,
+    #[serde(flatten)]
+    log: &'a T,
+}
+
 /// JSON output writer
 pub struct JsonWriter {
     /// Output destination
@@ -48,8 +59,8 @@ impl JsonWriter {
     }
 
     /// Write a single decoded log
-    fn write_decoded(&mut self, log: &DecodedLog) -> Result<()> {
-        let json = serde_json::to_string(log).map_err(|e| OutputError::JsonWrite(e.to_string()))?;
+    fn write_decoded(&mut self, chain: Option<Chain>, log: &DecodedLog) -> Result<()> {
+        let json = Self::to_json(chain, log)?;
 
         if self.ndjson {
             writeln!(self.writer, "{}", json).map_err(|e| OutputError::JsonWrite(e.to_string()))?;
@@ -66,8 +77,8 @@ impl JsonWriter {
     }
 
     /// Write a single raw log
-    fn write_raw(&mut self, log: &Log) -> Result<()> {
-        let json = serde_json::to_string(log).map_err(|e| OutputError::JsonWrite(e.to_string()))?;
+    fn write_raw(&mut self, chain: Option<Chain>, log: &Log) -> Result<()> {
+        let json = Self::to_json(chain, log)?;
 
         if self.ndjson {
             writeln!(self.writer, "{}", json).map_err(|e| OutputError::JsonWrite(e.to_string()))?;
@@ -84,23 +95,45 @@ impl JsonWriter {
     }
 }
 
+impl JsonWriter {
+    /// Serialize a log, prefixed with `chain`/`chain_id` if tagged
+    fn to_json<T: Serialize>(chain: Option<Chain>, log: &T) -> Result<String> {
+        let json = match chain {
+            Some(chain) => serde_json::to_string(&ChainTagged {
+                chain: chain.name(),
+                chain_id: chain.chain_id(),
+                log,
+            }),
+            None => serde_json::to_string(log),
+        };
+        Ok(json.map_err(|e| OutputError::JsonWrite(e.to_string()))?)
+    }
+}
+
 impl OutputWriter for JsonWriter {
     fn write_logs(&mut self, result: &FetchResult) -> Result<()> {
         match &result.logs {
             FetchLogs::Decoded(logs) => {
                 for log in logs {
-                    self.write_decoded(log)?;
+                    self.write_decoded(result.chain, log)?;
                 }
             }
             FetchLogs::Raw(logs) => {
                 for log in logs {
-                    self.write_raw(log)?;
+                    self.write_raw(result.chain, log)?;
                 }
             }
         }
         Ok(())
     }
 
+    fn flush(&mut self) -> Result<()> {
+        self.writer
+            .flush()
+            .map_err(|e| OutputError::JsonWrite(e.to_string()))?;
+        Ok(())
+    }
+
     fn finalize(&mut self) -> Result<()> {
         if !self.ndjson {
             writeln!(self.writer).map_err(|e| OutputError::JsonWrite(e.to_string()))?;
@@ -121,7 +154,6 @@ mod tests {
     use alloy::primitives::{Address, B256};
     use std::collections::HashMap;
 
-    #[allow(dead_code)]
     fn test_log() -> DecodedLog {
         DecodedLog {
             block_number: 12345,
@@ -134,6 +166,7 @@ mod tests {
             params: HashMap::new(),
             topics: vec![],
             data: vec![],
+            decoder: Default::default(),
         }
     }
 
@@ -150,4 +183,16 @@ mod tests {
 
         // Can't easily test without mocking, but structure is correct
     }
+
+    #[test]
+    fn test_chain_tagged_json() {
+        let json = JsonWriter::to_json(Some(Chain::Base), &test_log()).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["chain"], "base");
+        assert_eq!(value["chain_id"], 8453);
+        assert_eq!(value["block_number"], 12345);
+
+        let untagged = JsonWriter::to_json(None, &test_log()).unwrap();
+        assert!(!untagged.contains("chain_id"));
+    }
 }
@@ -2,22 +2,40 @@
 
 mod csv;
 mod json;
+mod query;
+mod rows;
 mod sqlite;
+mod tx_sqlite;
 
 pub use self::csv::CsvWriter;
 pub use self::json::JsonWriter;
-pub use self::sqlite::SqliteWriter;
+pub use self::query::{
+    read_schema, run_query, validate_read_only, ColumnSchema, DbSchema, IndexSchema, ParamValue,
+    QueryParam, QueryRow, TableSchema,
+};
+pub use self::rows::{parse_output_path, Row, RowFormat, RowWriter};
+pub use self::sqlite::{query_rows, QueryResult, SqliteWriter};
+pub use self::tx_sqlite::{TxSqliteWriter, TX_SCHEMA_VERSION};
 
 use crate::config::OutputFormat;
 use crate::error::Result;
 use crate::fetcher::FetchResult;
 use std::path::Path;
 
-/// Trait for output writers
+/// Trait for log output writers
+///
+/// List commands that produce typed records rather than logs write them
+/// through [`RowWriter`] instead.
 pub trait OutputWriter {
     /// Write a batch of logs
     fn write_logs(&mut self, logs: &FetchResult) -> Result<()>;
 
+    /// Flush written logs to the destination (used when following the
+    /// chain head, where `finalize` only runs on exit)
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+
     /// Finalize output (flush, close, etc.)
     fn finalize(&mut self) -> Result<()>;
 }
@@ -0,0 +1,677 @@
+//! Read-only SQL against exported databases
+//!
+//! Backs `ethcli db`. A statement must parse as a single `SELECT` (optionally
+//! behind `WITH`) before it runs, the database is opened read-only with
+//! `query_only` set, and values only ever reach SQLite as bound parameters.
+
+use super::rows::Row;
+use super::sqlite::collect_rows;
+use super::QueryResult;
+use crate::error::{OutputError, Result};
+use rusqlite::types::Value;
+use rusqlite::{Connection, OpenFlags};
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+use sqlparser::ast::{Query, SetExpr, Statement};
+use sqlparser::dialect::SQLiteDialect;
+use sqlparser::parser::Parser;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Reject anything but a single read-only query
+///
+/// `SELECT`, `VALUES` and `WITH ... SELECT` pass; DML (including DML behind
+/// `WITH`), DDL, `PRAGMA`, `ATTACH` and multiple statements do not.
+pub fn validate_read_only(sql: &str) -> Result<()> {
+    let statements = Parser::parse_sql(&SQLiteDialect {}, sql)
+        .map_err(|e| OutputError::InvalidQuery(e.to_string()))?;
+    let statement = match statements.as_slice() {
+        [statement] => statement,
+        [] => return Err(OutputError::InvalidQuery("empty statement".to_string()).into()),
+        _ => {
+            return Err(OutputError::InvalidQuery(format!(
+                "expected a single statement, found {}",
+                statements.len()
+            ))
+            .into())
+        }
+    };
+    match statement {
+        Statement::Query(query) if is_read_only(query) => Ok(()),
+        Statement::Query(_) => {
+            Err(OutputError::InvalidQuery("only SELECT queries are allowed".to_string()).into())
+        }
+        other => Err(OutputError::InvalidQuery(format!(
+            "only SELECT queries are allowed, found {}",
+            statement_kind(other)
+        ))
+        .into()),
+    }
+}
+
+/// Whether a query and its CTEs only read
+fn is_read_only(query: &Query) -> bool {
+    let ctes_read_only = query
+        .with
+        .as_ref()
+        .is_none_or(|with| with.cte_tables.iter().all(|cte| is_read_only(&cte.query)));
+    ctes_read_only && set_expr_read_only(&query.body)
+}
+
+fn set_expr_read_only(expr: &SetExpr) -> bool {
+    match expr {
+        SetExpr::Select(select) => select.into.is_none(),
+        SetExpr::Query(query) => is_read_only(query),
+        SetExpr::SetOperation { left, right, .. } => {
+            set_expr_read_only(left) && set_expr_read_only(right)
+        }
+        SetExpr::Values(_) | SetExpr::Table(_) => true,
+        SetExpr::Insert(_) | SetExpr::Update(_) => false,
+    }
+}
+
+/// First keyword of a statement, for error messages
+fn statement_kind(statement: &Statement) -> String {
+    statement
+        .to_string()
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_uppercase()
+}
+
+/// A value bound to a query parameter
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParamValue {
+    Null,
+    Integer(i64),
+    Real(f64),
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+impl ParamValue {
+    /// Infer the type of an untyped value
+    ///
+    /// Integers that fit `i64` bind as integers and decimals as reals;
+    /// everything else, including hex addresses and larger integers, binds
+    /// as text.
+    pub fn infer(value: &str) -> Self {
+        let numeric = !value.is_empty()
+            && value
+                .chars()
+                .all(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'));
+        if numeric {
+            if let Ok(n) = value.parse::<i64>() {
+                return Self::Integer(n);
+            }
+            if value.contains(['.', 'e', 'E']) {
+                if let Ok(f) = value.parse::<f64>() {
+                    return Self::Real(f);
+                }
+            }
+        }
+        Self::Text(value.to_string())
+    }
+
+    /// Parse `value` as an explicit type (`int`, `real`, `text`, `blob`, `null`)
+    pub fn typed(kind: &str, value: &str) -> Result<Self> {
+        let invalid = |what: &str| {
+            OutputError::InvalidParam(format!("'{}' is not a valid {}", value, what)).into()
+        };
+        match kind.to_ascii_lowercase().as_str() {
+            "int" | "integer" => value.parse().map(Self::Integer).map_err(|_| invalid(kind)),
+            "real" | "float" => value.parse().map(Self::Real).map_err(|_| invalid(kind)),
+            "text" | "str" => Ok(Self::Text(value.to_string())),
+            "blob" => value
+                .strip_prefix("0x")
+                .and_then(|hex_str| hex::decode(hex_str).ok())
+                .map(Self::Blob)
+                .ok_or_else(|| invalid("0x-prefixed hex blob")),
+            "null" => Ok(Self::Null),
+            other => Err(OutputError::InvalidParam(format!(
+                "unknown type '{}' (expected int, real, text, blob or null)",
+                other
+            ))
+            .into()),
+        }
+    }
+
+    fn to_sql(&self) -> Value {
+        match self {
+            Self::Null => Value::Null,
+            Self::Integer(n) => Value::Integer(*n),
+            Self::Real(f) => Value::Real(*f),
+            Self::Text(s) => Value::Text(s.clone()),
+            Self::Blob(b) => Value::Blob(b.clone()),
+        }
+    }
+}
+
+/// A `NAME[:TYPE]=VALUE` query parameter
+///
+/// `NAME` matches `:NAME`, `@NAME` or `$NAME` in the query; a number `N`
+/// matches `?N`, or the `N`th bare `?`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryParam {
+    pub name: String,
+    pub value: ParamValue,
+}
+
+impl FromStr for QueryParam {
+    type Err = crate::error::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (key, value) = s.split_once('=').ok_or_else(|| {
+            OutputError::InvalidParam(format!("'{}' is not NAME=VALUE or NAME:TYPE=VALUE", s))
+        })?;
+        let (name, kind) = match key.split_once(':') {
+            Some((name, kind)) => (name, Some(kind)),
+            None => (key, None),
+        };
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(OutputError::InvalidParam(format!(
+                "'{}' is not a valid parameter name (letters, digits and _)",
+                name
+            ))
+            .into());
+        }
+        let value = match kind {
+            Some(kind) => ParamValue::typed(kind, value)?,
+            None => ParamValue::infer(value),
+        };
+        Ok(Self {
+            name: name.to_string(),
+            value,
+        })
+    }
+}
+
+/// Run a validated, read-only query with bound parameters
+///
+/// Every parameter in the query needs a value and every value must be used,
+/// so a typo fails instead of silently binding NULL.
+pub fn run_query(path: &Path, sql: &str, params: &[QueryParam]) -> Result<QueryResult> {
+    validate_read_only(sql)?;
+
+    let mut by_name: BTreeMap<&str, &ParamValue> = BTreeMap::new();
+    for param in params {
+        if by_name.insert(&param.name, &param.value).is_some() {
+            return Err(OutputError::InvalidParam(format!(
+                "'{}' is given more than once",
+                param.name
+            ))
+            .into());
+        }
+    }
+
+    let conn = Connection::open_with_flags(
+        path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .map_err(OutputError::Sqlite)?;
+    conn.pragma_update(None, "query_only", true)
+        .map_err(OutputError::Sqlite)?;
+    let mut stmt = conn.prepare(sql).map_err(OutputError::Sqlite)?;
+
+    let mut used = BTreeSet::new();
+    for index in 1..=stmt.parameter_count() {
+        let key = match stmt.parameter_name(index) {
+            Some(name) => name[1..].to_string(),
+            None => index.to_string(),
+        };
+        let value = by_name.get(key.as_str()).ok_or_else(|| {
+            OutputError::InvalidParam(format!("no value for parameter '{}'", key))
+        })?;
+        stmt.raw_bind_parameter(index, value.to_sql())
+            .map_err(OutputError::Sqlite)?;
+        used.insert(key);
+    }
+    if let Some(unused) = by_name.keys().find(|name| !used.contains(**name)) {
+        return Err(OutputError::InvalidParam(format!(
+            "'{}' is not a parameter of the query",
+            unused
+        ))
+        .into());
+    }
+
+    let columns: Vec<String> = stmt.column_names().iter().map(|n| n.to_string()).collect();
+    let rows = collect_rows(&columns, stmt.raw_query())?;
+    Ok(QueryResult { columns, rows })
+}
+
+impl QueryResult {
+    /// Rows in SELECT column order, for [`super::RowWriter`]
+    ///
+    /// Pair with [`super::RowWriter::with_columns`] and
+    /// [`QueryResult::columns`].
+    pub fn into_rows(self) -> Vec<QueryRow> {
+        let columns: Arc<[String]> = self.columns.into();
+        self.rows
+            .into_iter()
+            .map(|mut object| QueryRow {
+                values: columns
+                    .iter()
+                    .map(|c| object.remove(c).unwrap_or(serde_json::Value::Null))
+                    .collect(),
+                columns: columns.clone(),
+            })
+            .collect()
+    }
+}
+
+/// One query result row, serialized as an object in column order
+#[derive(Debug, Clone)]
+pub struct QueryRow {
+    columns: Arc<[String]>,
+    values: Vec<serde_json::Value>,
+}
+
+impl Serialize for QueryRow {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.columns.len()))?;
+        for (column, value) in self.columns.iter().zip(&self.values) {
+            map.serialize_entry(column, value)?;
+        }
+        map.end()
+    }
+}
+
+impl Row for QueryRow {
+    // Columns come from the query; see `RowWriter::with_columns`
+    const COLUMNS: &'static [&'static str] = &[];
+
+    fn cells(&self) -> Vec<String> {
+        self.values
+            .iter()
+            .map(|value| match value {
+                serde_json::Value::Null => String::new(),
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            })
+            .collect()
+    }
+}
+
+/// Tables and views of a database, for `db schema`
+#[derive(Debug, Clone, Serialize)]
+pub struct DbSchema {
+    pub tables: Vec<TableSchema>,
+}
+
+/// A table or view
+#[derive(Debug, Clone, Serialize)]
+pub struct TableSchema {
+    pub name: String,
+    /// `table` or `view`
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub columns: Vec<ColumnSchema>,
+    pub indexes: Vec<IndexSchema>,
+}
+
+/// A column of a [`TableSchema`]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnSchema {
+    pub name: String,
+    /// Declared type (may be empty)
+    #[serde(rename = "type")]
+    pub decl_type: String,
+    pub not_null: bool,
+    pub primary_key: bool,
+    pub default: Option<String>,
+}
+
+/// An index of a [`TableSchema`]
+#[derive(Debug, Clone, Serialize)]
+pub struct IndexSchema {
+    pub name: String,
+    pub unique: bool,
+    pub columns: Vec<String>,
+}
+
+/// Read the tables, columns and indexes of a database
+///
+/// SQLite's internal `sqlite_*` tables are left out.
+pub fn read_schema(path: &Path) -> Result<DbSchema> {
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(OutputError::Sqlite)?;
+    let sqlite = |e| OutputError::Sqlite(e);
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT name, type FROM sqlite_master \
+             WHERE type IN ('table', 'view') AND name NOT LIKE 'sqlite_%' ORDER BY name",
+        )
+        .map_err(sqlite)?;
+    let objects: Vec<(String, String)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(sqlite)?
+        .collect::<std::result::Result<_, _>>()
+        .map_err(sqlite)?;
+
+    let mut tables = Vec::with_capacity(objects.len());
+    for (name, kind) in objects {
+        let columns = conn
+            .prepare("SELECT name, type, \"notnull\", dflt_value, pk FROM pragma_table_info(?1)")
+            .map_err(sqlite)?
+            .query_map([&name], |row| {
+                Ok(ColumnSchema {
+                    name: row.get(0)?,
+                    decl_type: row.get(1)?,
+                    not_null: row.get(2)?,
+                    default: row.get(3)?,
+                    primary_key: row.get::<_, i64>(4)? > 0,
+                })
+            })
+            .map_err(sqlite)?
+            .collect::<std::result::Result<_, _>>()
+            .map_err(sqlite)?;
+
+        let index_list: Vec<(String, bool)> = conn
+            .prepare("SELECT name, \"unique\" FROM pragma_index_list(?1) ORDER BY name")
+            .map_err(sqlite)?
+            .query_map([&name], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(sqlite)?
+            .collect::<std::result::Result<_, _>>()
+            .map_err(sqlite)?;
+        let mut indexes = Vec::with_capacity(index_list.len());
+        for (index, unique) in index_list {
+            let columns = conn
+                .prepare("SELECT name FROM pragma_index_info(?1) ORDER BY seqno")
+                .map_err(sqlite)?
+                .query_map([&index], |row| {
+                    // Expression indexes have no column name
+                    Ok(row
+                        .get::<_, Option<String>>(0)?
+                        .unwrap_or_else(|| "<expr>".to_string()))
+                })
+                .map_err(sqlite)?
+                .collect::<std::result::Result<_, _>>()
+                .map_err(sqlite)?;
+            indexes.push(IndexSchema {
+                name: index,
+                unique,
+                columns,
+            });
+        }
+
+        tables.push(TableSchema {
+            name,
+            kind,
+            columns,
+            indexes,
+        });
+    }
+    Ok(DbSchema { tables })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn fixture() -> (TempDir, std::path::PathBuf) {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("events.db");
+        let conn = Connection::open(&path).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE transfers (
+                 id INTEGER PRIMARY KEY,
+                 sender TEXT NOT NULL,
+                 amount REAL,
+                 block INTEGER,
+                 payload BLOB
+             );
+             CREATE INDEX idx_transfers_sender ON transfers(sender, block);
+             CREATE VIEW big AS SELECT * FROM transfers WHERE amount > 100;
+             INSERT INTO transfers VALUES (1, '0xabc', 50.5, 10, x'beef');
+             INSERT INTO transfers VALUES (2, '0xabc', 150.0, 20, NULL);
+             INSERT INTO transfers VALUES (3, '0xdef', 1000.0, 30, NULL);",
+        )
+        .unwrap();
+        (dir, path)
+    }
+
+    fn params(args: &[&str]) -> Vec<QueryParam> {
+        args.iter().map(|a| a.parse().unwrap()).collect()
+    }
+
+    fn rejected(sql: &str) -> String {
+        validate_read_only(sql).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_validate_accepts_reads() {
+        for sql in [
+            "SELECT * FROM transfers",
+            "select sender, count(*) from transfers group by sender order by 2 desc limit 5",
+            "WITH t AS (SELECT * FROM transfers) SELECT * FROM t",
+            "SELECT 1 UNION ALL SELECT 2",
+            "VALUES (1, 2)",
+            "SELECT * FROM transfers WHERE sender = :who AND block > ?2",
+            "SELECT name FROM pragma_table_info('transfers')",
+            "SELECT 1;",
+        ] {
+            assert!(validate_read_only(sql).is_ok(), "{sql}");
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_writes() {
+        assert!(rejected("DELETE FROM transfers").contains("DELETE"));
+        assert!(rejected("DROP TABLE transfers").contains("DROP"));
+        assert!(rejected("INSERT INTO transfers (id) VALUES (9)").contains("INSERT"));
+        assert!(rejected("UPDATE transfers SET amount = 0").contains("UPDATE"));
+        assert!(rejected("PRAGMA writable_schema = 1").contains("PRAGMA"));
+        assert!(rejected("ATTACH DATABASE '/tmp/x.db' AS x").contains("ATTACH"));
+        assert!(rejected("CREATE TABLE x (a)").contains("CREATE"));
+        assert!(rejected("").contains("empty"));
+        assert!(rejected("SELEC * FROM transfers").contains("Rejected query"));
+    }
+
+    #[test]
+    fn test_validate_rejects_injection_attempts() {
+        // Stacked statement after a valid SELECT
+        assert!(rejected("SELECT * FROM transfers; DROP TABLE transfers").contains("single"));
+        assert!(rejected("SELECT 1; SELECT 2").contains("single"));
+        // Comment-hidden second statement
+        assert!(rejected("SELECT 1 /* hi */; DELETE FROM transfers --").contains("single"));
+        // DML behind a CTE
+        assert!(validate_read_only(
+            "WITH t AS (SELECT 1) INSERT INTO transfers (id) SELECT * FROM t"
+        )
+        .is_err());
+        assert!(validate_read_only("WITH t AS (SELECT 1) DELETE FROM transfers").is_err());
+        // Quote breakout spliced into a saved query still stacks a statement
+        assert!(rejected(
+            "SELECT * FROM transfers WHERE sender = '' OR 1=1; DELETE FROM transfers; --'"
+        )
+        .contains("single"));
+    }
+
+    #[test]
+    fn test_param_parsing() {
+        let p: QueryParam = "from=0xabc".parse().unwrap();
+        assert_eq!(p.name, "from");
+        assert_eq!(p.value, ParamValue::Text("0xabc".to_string()));
+
+        assert_eq!(ParamValue::infer("42"), ParamValue::Integer(42));
+        assert_eq!(ParamValue::infer("-7"), ParamValue::Integer(-7));
+        assert_eq!(ParamValue::infer("1.5"), ParamValue::Real(1.5));
+        assert_eq!(ParamValue::infer("1e3"), ParamValue::Real(1000.0));
+        // Too big for i64: kept exact as text
+        assert_eq!(
+            ParamValue::infer(
+                "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+            ),
+            ParamValue::Text(
+                "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            ParamValue::infer("inf"),
+            ParamValue::Text("inf".to_string())
+        );
+        assert_eq!(ParamValue::infer(""), ParamValue::Text(String::new()));
+
+        let typed = params(&[
+            "a:text=42",
+            "b:int=7",
+            "c:real=2",
+            "d:blob=0xbeef",
+            "e:null=",
+        ]);
+        assert_eq!(typed[0].value, ParamValue::Text("42".to_string()));
+        assert_eq!(typed[1].value, ParamValue::Integer(7));
+        assert_eq!(typed[2].value, ParamValue::Real(2.0));
+        assert_eq!(typed[3].value, ParamValue::Blob(vec![0xbe, 0xef]));
+        assert_eq!(typed[4].value, ParamValue::Null);
+        // The value may itself contain '='
+        assert_eq!(
+            "q=a=b".parse::<QueryParam>().unwrap().value,
+            ParamValue::Text("a=b".to_string())
+        );
+
+        for bad in [
+            "noequals",
+            "=5",
+            "bad-name=1",
+            "x:int=abc",
+            "x:blob=beef",
+            "x:date=1",
+        ] {
+            assert!(bad.parse::<QueryParam>().is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_run_query_binds_named_and_positional() {
+        let (_dir, path) = fixture();
+
+        let result = run_query(
+            &path,
+            "SELECT id, amount FROM transfers WHERE sender = :who AND block >= @min ORDER BY id",
+            &params(&["who=0xabc", "min=15"]),
+        )
+        .unwrap();
+        assert_eq!(result.columns, vec!["id", "amount"]);
+        assert_eq!(result.rows.len(), 1);
+        assert_eq!(result.rows[0]["id"], 2);
+
+        let result = run_query(
+            &path,
+            "SELECT id FROM transfers WHERE amount > ? AND sender = ? ORDER BY id",
+            &params(&["1=100", "2=0xdef"]),
+        )
+        .unwrap();
+        assert_eq!(result.rows.len(), 1);
+        assert_eq!(result.rows[0]["id"], 3);
+
+        let result = run_query(
+            &path,
+            "SELECT id FROM transfers WHERE payload = $p",
+            &params(&["p:blob=0xbeef"]),
+        )
+        .unwrap();
+        assert_eq!(result.rows[0]["id"], 1);
+
+        // Blobs come back as hex
+        let result = run_query(&path, "SELECT payload FROM transfers WHERE id = 1", &[]).unwrap();
+        assert_eq!(result.rows[0]["payload"], "0xbeef");
+    }
+
+    #[test]
+    fn test_run_query_param_mismatches() {
+        let (_dir, path) = fixture();
+        let sql = "SELECT id FROM transfers WHERE sender = :who";
+
+        let err = run_query(&path, sql, &[]).unwrap_err().to_string();
+        assert!(err.contains("no value for parameter 'who'"), "{err}");
+
+        let err = run_query(&path, sql, &params(&["who=0xabc", "whom=0xdef"]))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("'whom' is not a parameter"), "{err}");
+
+        let err = run_query(&path, sql, &params(&["who=1", "who=2"]))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("more than once"), "{err}");
+    }
+
+    #[test]
+    fn test_run_query_values_cannot_inject() {
+        let (_dir, path) = fixture();
+        for payload in [
+            "0xabc' OR '1'='1",
+            "0xabc'; DROP TABLE transfers; --",
+            "x\") OR 1=1 --",
+        ] {
+            let result = run_query(
+                &path,
+                "SELECT id FROM transfers WHERE sender = :who",
+                &[QueryParam {
+                    name: "who".to_string(),
+                    value: ParamValue::Text(payload.to_string()),
+                }],
+            )
+            .unwrap();
+            assert!(result.rows.is_empty(), "{payload}");
+        }
+        let count = run_query(&path, "SELECT count(*) AS n FROM transfers", &[]).unwrap();
+        assert_eq!(count.rows[0]["n"], 3);
+    }
+
+    #[test]
+    fn test_run_query_cannot_write() {
+        let (_dir, path) = fixture();
+        // Rejected before SQLite sees it
+        assert!(run_query(&path, "DELETE FROM transfers", &[]).is_err());
+        // Side-effecting functions still fail on the read-only connection
+        assert!(run_query(&path, "SELECT load_extension('x')", &[]).is_err());
+        let count = run_query(&path, "SELECT count(*) AS n FROM transfers", &[]).unwrap();
+        assert_eq!(count.rows[0]["n"], 3);
+    }
+
+    #[test]
+    fn test_into_rows_keeps_column_order() {
+        let (_dir, path) = fixture();
+        let result = run_query(
+            &path,
+            "SELECT sender AS z, id AS a, amount FROM transfers ORDER BY id LIMIT 1",
+            &[],
+        )
+        .unwrap();
+        let rows = result.into_rows();
+        assert_eq!(
+            serde_json::to_string(&rows[0]).unwrap(),
+            r#"{"z":"0xabc","a":1,"amount":50.5}"#
+        );
+        assert_eq!(rows[0].cells(), vec!["0xabc", "1", "50.5"]);
+    }
+
+    #[test]
+    fn test_read_schema() {
+        let (_dir, path) = fixture();
+        let schema = read_schema(&path).unwrap();
+        let names: Vec<&str> = schema.tables.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["big", "transfers"]);
+        assert_eq!(schema.tables[0].kind, "view");
+
+        let transfers = &schema.tables[1];
+        assert_eq!(transfers.columns.len(), 5);
+        assert!(transfers.columns[0].primary_key);
+        assert_eq!(transfers.columns[1].name, "sender");
+        assert_eq!(transfers.columns[1].decl_type, "TEXT");
+        assert!(transfers.columns[1].not_null);
+        assert_eq!(transfers.indexes.len(), 1);
+        assert_eq!(transfers.indexes[0].name, "idx_transfers_sender");
+        assert!(!transfers.indexes[0].unique);
+        assert_eq!(transfers.indexes[0].columns, vec!["sender", "block"]);
+    }
+}
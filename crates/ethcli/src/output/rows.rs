@@ -0,0 +1,221 @@
+//! Typed row output for list commands
+//!
+//! Commands such as `account txs`, `account erc20` and `token holders` turn
+//! their results into [`Row`]s with a fixed column set. [`RowWriter`] writes
+//! them as a table, a JSON array, NDJSON or CSV, to stdout or a file. JSON,
+//! NDJSON and CSV rows are written as each batch arrives, so a paginated
+//! fetch only ever holds one page in memory; tables need every row to size
+//! their columns and are rendered on [`RowWriter::finalize`].
+
+use crate::error::{OutputError, Result};
+use crate::utils::{Alignment, Table};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+
+/// Output format for row-producing commands
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum RowFormat {
+    /// Human-readable table (rendered once all rows are in)
+    #[default]
+    #[value(alias = "pretty")]
+    Table,
+    /// JSON array, one row per line
+    Json,
+    /// Newline-delimited JSON (for streaming)
+    Ndjson,
+    /// CSV with a header row
+    Csv,
+}
+
+impl fmt::Display for RowFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RowFormat::Table => write!(f, "table"),
+            RowFormat::Json => write!(f, "json"),
+            RowFormat::Ndjson => write!(f, "ndjson"),
+            RowFormat::Csv => write!(f, "csv"),
+        }
+    }
+}
+
+/// Parse an `--output` file path, rejecting format names
+///
+/// `--output` used to select the format on some commands, so `--output json`
+/// would otherwise quietly write a file named `json`.
+pub fn parse_output_path(value: &str) -> std::result::Result<PathBuf, String> {
+    if RowFormat::from_str(value, true).is_ok() {
+        return Err(format!(
+            "'{value}' is an output format; use --format {value} (--output takes a file path)"
+        ));
+    }
+    Ok(PathBuf::from(value))
+}
+
+/// A record with a stable, documented column set
+pub trait Row: Serialize {
+    /// Column names in output order; also the keys of the JSON object
+    const COLUMNS: &'static [&'static str];
+
+    /// Cell values in [`Row::COLUMNS`] order, as written to CSV
+    fn cells(&self) -> Vec<String>;
+
+    /// Headers for table output (all of [`Row::COLUMNS`] by default)
+    fn table_columns() -> &'static [&'static str] {
+        Self::COLUMNS
+    }
+
+    /// Column alignments for table output (left by default)
+    fn table_alignments() -> &'static [Alignment] {
+        &[]
+    }
+
+    /// Cell values for [`Row::table_columns`]
+    fn table_cells(&self) -> Vec<String> {
+        self.cells()
+    }
+}
+
+/// Streaming writer for [`Row`]s
+pub struct RowWriter<T: Row, W: Write = Box<dyn Write + Send>> {
+    /// Output destination
+    writer: W,
+    /// Output format
+    format: RowFormat,
+    /// Column names for the CSV header
+    columns: Vec<String>,
+    /// Rows collected for table output
+    table: Table,
+    /// Whether the CSV header has been written
+    header_written: bool,
+    /// Count of rows written
+    count: usize,
+    _row: PhantomData<fn(&T)>,
+}
+
+impl<T: Row> RowWriter<T> {
+    /// Create a writer to `path`, or stdout if no path is given
+    pub fn new(format: RowFormat, path: Option<&Path>) -> Result<Self> {
+        let writer: Box<dyn Write + Send> = if let Some(p) = path {
+            let file = File::create(p)
+                .map_err(|e| OutputError::FileCreate(format!("{}: {}", p.display(), e)))?;
+            Box::new(BufWriter::new(file))
+        } else {
+            Box::new(BufWriter::new(io::stdout()))
+        };
+        Ok(Self::from_writer(format, writer))
+    }
+}
+
+impl<T: Row, W: Write> RowWriter<T, W> {
+    /// Create a writer over any destination
+    pub fn from_writer(format: RowFormat, writer: W) -> Self {
+        let table = Table::new(T::table_columns().iter().copied())
+            .with_alignments(T::table_alignments().iter().copied());
+        Self {
+            writer,
+            format,
+            columns: T::COLUMNS.iter().map(|c| c.to_string()).collect(),
+            table,
+            header_written: false,
+            count: 0,
+            _row: PhantomData,
+        }
+    }
+
+    /// Use columns only known at runtime, such as a SQL query's
+    ///
+    /// Replaces [`Row::COLUMNS`] and [`Row::table_columns`] as the CSV and
+    /// table headers. Call before writing any rows.
+    pub fn with_columns(mut self, columns: Vec<String>) -> Self {
+        self.table = Table::new(columns.iter().cloned());
+        self.columns = columns;
+        self
+    }
+
+    /// Write a batch of rows
+    pub fn write_rows(&mut self, rows: &[T]) -> Result<()> {
+        match self.format {
+            RowFormat::Table => {
+                for row in rows {
+                    self.table.add_row(row.table_cells());
+                }
+            }
+            RowFormat::Json => {
+                for (i, row) in rows.iter().enumerate() {
+                    let json = serde_json::to_string(row)
+                        .map_err(|e| OutputError::JsonWrite(e.to_string()))?;
+                    let prefix = if self.count + i == 0 { "[\n" } else { ",\n" };
+                    write!(self.writer, "{}  {}", prefix, json)
+                        .map_err(|e| OutputError::JsonWrite(e.to_string()))?;
+                }
+            }
+            RowFormat::Ndjson => {
+                for row in rows {
+                    let json = serde_json::to_string(row)
+                        .map_err(|e| OutputError::JsonWrite(e.to_string()))?;
+                    writeln!(self.writer, "{}", json)
+                        .map_err(|e| OutputError::JsonWrite(e.to_string()))?;
+                }
+            }
+            RowFormat::Csv => {
+                let mut csv = csv::Writer::from_writer(&mut self.writer);
+                if !self.header_written {
+                    csv.write_record(&self.columns)
+                        .map_err(|e| OutputError::CsvWrite(e.to_string()))?;
+                    self.header_written = true;
+                }
+                for row in rows {
+                    csv.write_record(row.cells())
+                        .map_err(|e| OutputError::CsvWrite(e.to_string()))?;
+                }
+                csv.flush()
+                    .map_err(|e| OutputError::CsvWrite(e.to_string()))?;
+            }
+        }
+        self.count += rows.len();
+        Ok(())
+    }
+
+    /// Flush written rows to the destination
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Finish the output: close the JSON array, render the table, or write
+    /// the CSV header if no rows came in
+    pub fn finalize(&mut self) -> Result<()> {
+        match self.format {
+            RowFormat::Table => write!(self.writer, "{}", self.table.render())?,
+            RowFormat::Json if self.count == 0 => {
+                writeln!(self.writer, "[]").map_err(|e| OutputError::JsonWrite(e.to_string()))?
+            }
+            RowFormat::Json => {
+                writeln!(self.writer, "\n]").map_err(|e| OutputError::JsonWrite(e.to_string()))?
+            }
+            RowFormat::Csv if !self.header_written => self.write_rows(&[])?,
+            RowFormat::Ndjson | RowFormat::Csv => {}
+        }
+        self.flush()
+    }
+
+    /// Number of rows written so far
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// The output format
+    pub fn format(&self) -> RowFormat {
+        self.format
+    }
+
+    /// Consume the writer, returning the destination
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
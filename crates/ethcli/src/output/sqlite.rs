@@ -1,15 +1,87 @@
 //! SQLite output writer
 
 use crate::abi::{DecodedLog, DecodedValue};
+use crate::config::Chain;
 use crate::error::{OutputError, Result};
 use crate::fetcher::{FetchLogs, FetchResult};
 use crate::output::OutputWriter;
 use alloy::rpc::types::Log;
-use rusqlite::{params, Connection};
+use rusqlite::Connection;
 use std::path::Path;
 
 use std::collections::HashMap as StdHashMap;
 
+/// Open a database tuned for bulk writes (WAL journal, relaxed sync)
+pub(super) fn open_connection(path: &Path) -> Result<Connection> {
+    let conn = Connection::open(path).map_err(OutputError::Sqlite)?;
+    conn.execute_batch("PRAGMA journal_mode=WAL; PRAGMA synchronous=NORMAL;")
+        .map_err(OutputError::Sqlite)?;
+    Ok(conn)
+}
+
+/// Run `f` inside a transaction, committing only if it succeeds
+pub(super) fn with_transaction<T>(
+    conn: &mut Connection,
+    f: impl FnOnce(&rusqlite::Transaction<'_>) -> Result<T>,
+) -> Result<T> {
+    let tx = conn.transaction().map_err(OutputError::Sqlite)?;
+    let value = f(&tx)?;
+    tx.commit().map_err(OutputError::Sqlite)?;
+    Ok(value)
+}
+
+/// Rows returned by [`query_rows`]
+#[derive(Debug, Clone, Default)]
+pub struct QueryResult {
+    /// Column names in SELECT order
+    pub columns: Vec<String>,
+    /// Rows as JSON objects keyed by column name
+    pub rows: Vec<serde_json::Map<String, serde_json::Value>>,
+}
+
+/// Run a read-only query against a SQLite database
+///
+/// Works against databases produced by both [`SqliteWriter`] (logs) and
+/// [`super::TxSqliteWriter`] (transactions).
+pub fn query_rows(path: &Path, sql: &str) -> Result<QueryResult> {
+    let conn = Connection::open_with_flags(path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(OutputError::Sqlite)?;
+    let mut stmt = conn.prepare(sql).map_err(OutputError::Sqlite)?;
+    let columns: Vec<String> = stmt.column_names().iter().map(|n| n.to_string()).collect();
+    let rows = stmt.query([]).map_err(OutputError::Sqlite)?;
+    Ok(QueryResult {
+        rows: collect_rows(&columns, rows)?,
+        columns,
+    })
+}
+
+/// Read every row of `rows` as a JSON object keyed by column name
+///
+/// Blobs become `0x` hex strings.
+pub(super) fn collect_rows(
+    names: &[String],
+    mut rows: rusqlite::Rows<'_>,
+) -> Result<Vec<serde_json::Map<String, serde_json::Value>>> {
+    let mut out = Vec::new();
+    while let Some(row) = rows.next().map_err(OutputError::Sqlite)? {
+        let mut object = serde_json::Map::with_capacity(names.len());
+        for (i, name) in names.iter().enumerate() {
+            let value = match row.get_ref(i).map_err(OutputError::Sqlite)? {
+                rusqlite::types::ValueRef::Null => serde_json::Value::Null,
+                rusqlite::types::ValueRef::Integer(n) => n.into(),
+                rusqlite::types::ValueRef::Real(f) => f.into(),
+                rusqlite::types::ValueRef::Text(t) => {
+                    String::from_utf8_lossy(t).into_owned().into()
+                }
+                rusqlite::types::ValueRef::Blob(b) => format!("0x{}", hex::encode(b)).into(),
+            };
+            object.insert(name.clone(), value);
+        }
+        out.push(object);
+    }
+    Ok(out)
+}
+
 /// SQLite output writer
 pub struct SqliteWriter {
     /// Database connection
@@ -23,19 +95,17 @@ pub struct SqliteWriter {
     /// Table created
     table_created: bool,
     /// Batch buffer
-    buffer: Vec<DecodedLog>,
+    buffer: Vec<(Option<Chain>, DecodedLog)>,
     /// Batch size for inserts
     batch_size: usize,
+    /// Whether tables have `chain`/`chain_id` columns (decided by the first batch)
+    chain_columns: Option<bool>,
 }
 
 impl SqliteWriter {
     /// Create a new SQLite writer
     pub fn new(path: &Path) -> Result<Self> {
-        let conn = Connection::open(path).map_err(OutputError::Sqlite)?;
-
-        // Enable WAL mode for better performance
-        conn.execute_batch("PRAGMA journal_mode=WAL; PRAGMA synchronous=NORMAL;")
-            .map_err(OutputError::Sqlite)?;
+        let conn = open_connection(path)?;
 
         Ok(Self {
             conn,
@@ -45,21 +115,42 @@ impl SqliteWriter {
             table_created: false,
             buffer: Vec::new(),
             batch_size: 1000,
+            chain_columns: None,
         })
     }
 
+    fn has_chain_columns(&self) -> bool {
+        self.chain_columns == Some(true)
+    }
+
+    /// Column definitions and index for chain-tagged tables
+    fn chain_column_sql(&self, table: &str) -> (&'static str, String) {
+        if self.has_chain_columns() {
+            (
+                "
+                chain TEXT,
+                chain_id INTEGER,",
+                format!("\n            CREATE INDEX IF NOT EXISTS idx_{table}_chain ON {table}(chain_id);"),
+            )
+        } else {
+            ("", String::new())
+        }
+    }
+
     /// Create the events table
     fn create_table(&mut self) -> Result<()> {
+        let (chain_cols, chain_index) = self.chain_column_sql("events");
         // Base columns
-        let mut create_sql = String::from(
+        let mut create_sql = format!(
             "CREATE TABLE IF NOT EXISTS events (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                id INTEGER PRIMARY KEY AUTOINCREMENT,{chain_cols}
                 block_number INTEGER NOT NULL,
                 transaction_hash TEXT NOT NULL,
                 log_index INTEGER NOT NULL,
                 address TEXT NOT NULL,
                 event_name TEXT NOT NULL,
                 event_signature TEXT NOT NULL,
+                decoder TEXT,
                 topics TEXT,
                 data BLOB",
         );
@@ -77,10 +168,26 @@ impl SqliteWriter {
             CREATE INDEX IF NOT EXISTS idx_address ON events(address);
             CREATE INDEX IF NOT EXISTS idx_event ON events(event_name);",
         );
+        create_sql.push_str(&chain_index);
 
         self.conn
             .execute_batch(&create_sql)
             .map_err(OutputError::Sqlite)?;
+
+        // Tables written before the decoder column existed get it in place
+        let has_decoder: bool = self
+            .conn
+            .query_row(
+                "SELECT EXISTS(SELECT 1 FROM pragma_table_info('events') WHERE name = 'decoder')",
+                [],
+                |row| row.get(0),
+            )
+            .map_err(OutputError::Sqlite)?;
+        if !has_decoder {
+            self.conn
+                .execute("ALTER TABLE events ADD COLUMN decoder TEXT", [])
+                .map_err(OutputError::Sqlite)?;
+        }
         self.table_created = true;
 
         Ok(())
@@ -161,7 +268,7 @@ impl SqliteWriter {
     }
 
     /// Collect columns from logs
-    fn collect_columns(&mut self, logs: &[DecodedLog]) {
+    fn collect_columns<'a>(&mut self, logs: impl IntoIterator<Item = &'a DecodedLog>) {
         for log in logs {
             for key in log.params.keys() {
                 if !self.columns.contains(key) {
@@ -172,29 +279,36 @@ impl SqliteWriter {
     }
 
     /// Insert a batch of logs
-    fn insert_batch(&mut self, logs: Vec<DecodedLog>) -> Result<()> {
+    fn insert_batch(&mut self, logs: Vec<(Option<Chain>, DecodedLog)>) -> Result<()> {
         if logs.is_empty() {
             return Ok(());
         }
 
         // Ensure all columns exist
-        for log in &logs {
+        for (_, log) in &logs {
             for key in log.params.keys() {
                 self.ensure_column(key)?;
             }
         }
 
         // Build INSERT statement
-        let mut cols = vec![
+        let chain_columns = self.has_chain_columns();
+        let mut cols = if chain_columns {
+            vec!["chain", "chain_id"]
+        } else {
+            Vec::new()
+        };
+        cols.extend([
             "block_number",
             "transaction_hash",
             "log_index",
             "address",
             "event_name",
             "event_signature",
+            "decoder",
             "topics",
             "data",
-        ];
+        ]);
 
         // Use the collision-safe column name mapping
         let param_cols: Vec<String> = self
@@ -219,12 +333,11 @@ impl SqliteWriter {
             placeholders.join(", ")
         );
 
-        let tx = self.conn.transaction().map_err(OutputError::Sqlite)?;
-
-        {
+        let columns = &self.columns;
+        with_transaction(&mut self.conn, |tx| {
             let mut stmt = tx.prepare(&sql).map_err(OutputError::Sqlite)?;
 
-            for log in &logs {
+            for (chain, log) in &logs {
                 let topics_json = match serde_json::to_string(&log.topics) {
                     Ok(json) => json,
                     Err(e) => {
@@ -237,19 +350,25 @@ impl SqliteWriter {
                     }
                 };
 
-                let mut values: Vec<Box<dyn rusqlite::ToSql>> = vec![
+                let mut values: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+                if chain_columns {
+                    values.push(Box::new(chain.map(|c| c.name())));
+                    values.push(Box::new(chain.map(|c| c.chain_id() as i64)));
+                }
+                values.extend::<[Box<dyn rusqlite::ToSql>; 9]>([
                     Box::new(log.block_number as i64),
                     Box::new(format!("{:#x}", log.transaction_hash)),
                     Box::new(log.log_index as i64),
                     Box::new(format!("{:#x}", log.address)),
                     Box::new(log.event_name.clone()),
                     Box::new(log.event_signature.clone()),
+                    Box::new(log.decoder.as_str()),
                     Box::new(topics_json),
                     Box::new(log.data.clone()),
-                ];
+                ]);
 
                 // Add parameter values
-                for col_name in &self.columns {
+                for col_name in columns {
                     let value = log
                         .params
                         .get(col_name)
@@ -262,10 +381,8 @@ impl SqliteWriter {
                 stmt.execute(params.as_slice())
                     .map_err(OutputError::Sqlite)?;
             }
-        }
-
-        tx.commit().map_err(OutputError::Sqlite)?;
-        Ok(())
+            Ok(())
+        })
     }
 
     /// Convert value to string for storage
@@ -289,12 +406,13 @@ impl SqliteWriter {
     }
 
     /// Write raw logs to a simpler table
-    fn write_raw_logs(&mut self, logs: &[Log]) -> Result<()> {
+    fn write_raw_logs(&mut self, chain: Option<Chain>, logs: &[Log]) -> Result<()> {
+        let (chain_cols, chain_index) = self.chain_column_sql("raw_logs");
         // Create raw logs table
         self.conn
-            .execute_batch(
+            .execute_batch(&format!(
                 "CREATE TABLE IF NOT EXISTS raw_logs (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                id INTEGER PRIMARY KEY AUTOINCREMENT,{chain_cols}
                 block_number INTEGER,
                 transaction_hash TEXT,
                 log_index INTEGER,
@@ -306,42 +424,57 @@ impl SqliteWriter {
                 data BLOB
             );
             CREATE INDEX IF NOT EXISTS idx_raw_block ON raw_logs(block_number);
-            CREATE INDEX IF NOT EXISTS idx_raw_address ON raw_logs(address);",
-            )
+            CREATE INDEX IF NOT EXISTS idx_raw_address ON raw_logs(address);{chain_index}",
+            ))
             .map_err(OutputError::Sqlite)?;
 
-        let tx = self.conn.transaction().map_err(OutputError::Sqlite)?;
+        let (chain_insert, chain_placeholders) = if self.has_chain_columns() {
+            ("chain, chain_id, ", "?, ?, ")
+        } else {
+            ("", "")
+        };
+        let sql = format!(
+            "INSERT INTO raw_logs ({chain_insert}block_number, transaction_hash, log_index, address, topic0, topic1, topic2, topic3, data)
+             VALUES ({chain_placeholders}?, ?, ?, ?, ?, ?, ?, ?, ?)"
+        );
+        let chain_columns = self.has_chain_columns();
 
-        {
-            let mut stmt = tx.prepare(
-                "INSERT INTO raw_logs (block_number, transaction_hash, log_index, address, topic0, topic1, topic2, topic3, data)
-                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
-            ).map_err(OutputError::Sqlite)?;
+        with_transaction(&mut self.conn, |tx| {
+            let mut stmt = tx.prepare(&sql).map_err(OutputError::Sqlite)?;
 
             for log in logs {
                 let topics = log.topics();
-                stmt.execute(params![
-                    log.block_number.map(|n| n as i64),
-                    log.transaction_hash.map(|h| format!("{:#x}", h)),
-                    log.log_index.map(|i| i as i64),
-                    format!("{:#x}", log.address()),
-                    topics.first().map(|t| format!("{:#x}", t)),
-                    topics.get(1).map(|t| format!("{:#x}", t)),
-                    topics.get(2).map(|t| format!("{:#x}", t)),
-                    topics.get(3).map(|t| format!("{:#x}", t)),
-                    log.data().data.to_vec(),
-                ])
-                .map_err(OutputError::Sqlite)?;
-            }
-        }
+                let mut values: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+                if chain_columns {
+                    values.push(Box::new(chain.map(|c| c.name())));
+                    values.push(Box::new(chain.map(|c| c.chain_id() as i64)));
+                }
+                values.extend::<[Box<dyn rusqlite::ToSql>; 9]>([
+                    Box::new(log.block_number.map(|n| n as i64)),
+                    Box::new(log.transaction_hash.map(|h| format!("{:#x}", h))),
+                    Box::new(log.log_index.map(|i| i as i64)),
+                    Box::new(format!("{:#x}", log.address())),
+                    Box::new(topics.first().map(|t| format!("{:#x}", t))),
+                    Box::new(topics.get(1).map(|t| format!("{:#x}", t))),
+                    Box::new(topics.get(2).map(|t| format!("{:#x}", t))),
+                    Box::new(topics.get(3).map(|t| format!("{:#x}", t))),
+                    Box::new(log.data().data.to_vec()),
+                ]);
 
-        tx.commit().map_err(OutputError::Sqlite)?;
-        Ok(())
+                let params: Vec<&dyn rusqlite::ToSql> = values.iter().map(|v| v.as_ref()).collect();
+                stmt.execute(params.as_slice())
+                    .map_err(OutputError::Sqlite)?;
+            }
+            Ok(())
+        })
     }
 }
 
 impl OutputWriter for SqliteWriter {
     fn write_logs(&mut self, result: &FetchResult) -> Result<()> {
+        if self.chain_columns.is_none() {
+            self.chain_columns = Some(result.chain.is_some());
+        }
         match &result.logs {
             FetchLogs::Decoded(logs) => {
                 // Collect columns from first batch
@@ -351,7 +484,8 @@ impl OutputWriter for SqliteWriter {
                 }
 
                 // Buffer logs
-                self.buffer.extend(logs.iter().cloned());
+                self.buffer
+                    .extend(logs.iter().map(|log| (result.chain, log.clone())));
 
                 // Flush if batch size reached
                 if self.buffer.len() >= self.batch_size {
@@ -360,7 +494,7 @@ impl OutputWriter for SqliteWriter {
                 }
             }
             FetchLogs::Raw(logs) => {
-                self.write_raw_logs(logs)?;
+                self.write_raw_logs(result.chain, logs)?;
             }
         }
         Ok(())
@@ -372,7 +506,7 @@ impl OutputWriter for SqliteWriter {
             // Take buffer first to avoid borrow conflict
             let batch = std::mem::take(&mut self.buffer);
             if !self.table_created {
-                self.collect_columns(&batch);
+                self.collect_columns(batch.iter().map(|(_, log)| log));
                 self.create_table()?;
             }
             self.insert_batch(batch)?;
@@ -390,6 +524,83 @@ impl OutputWriter for SqliteWriter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::abi::DecoderSource;
+
+    #[test]
+    fn test_chain_columns_raw_logs() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.db");
+        let mut writer = SqliteWriter::new(&path).unwrap();
+        for chain in [Chain::Ethereum, Chain::Polygon] {
+            writer
+                .write_logs(&FetchResult {
+                    logs: FetchLogs::Raw(vec![Log::default()]),
+                    stats: Default::default(),
+                    chain: Some(chain),
+                })
+                .unwrap();
+        }
+        writer.finalize().unwrap();
+        drop(writer);
+
+        let result = query_rows(
+            &path,
+            "SELECT chain, chain_id FROM raw_logs ORDER BY chain_id",
+        )
+        .unwrap();
+        assert_eq!(result.rows.len(), 2);
+        assert_eq!(result.rows[0]["chain"], "ethereum");
+        assert_eq!(result.rows[1]["chain_id"], 137);
+    }
+
+    #[test]
+    fn test_append_adds_decoder_to_old_table() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.db");
+        Connection::open(&path)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE events (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    block_number INTEGER NOT NULL,
+                    transaction_hash TEXT NOT NULL,
+                    log_index INTEGER NOT NULL,
+                    address TEXT NOT NULL,
+                    event_name TEXT NOT NULL,
+                    event_signature TEXT NOT NULL,
+                    topics TEXT,
+                    data BLOB
+                );",
+            )
+            .unwrap();
+
+        let mut writer = SqliteWriter::new(&path).unwrap();
+        writer
+            .write_logs(&FetchResult {
+                logs: FetchLogs::Decoded(vec![DecodedLog {
+                    block_number: 7,
+                    timestamp: None,
+                    transaction_hash: Default::default(),
+                    log_index: 0,
+                    address: Default::default(),
+                    event_name: "Transfer".to_string(),
+                    event_signature: "Transfer(address,address,uint256)".to_string(),
+                    params: Default::default(),
+                    topics: vec![],
+                    data: vec![],
+                    decoder: DecoderSource::Fetched,
+                }]),
+                stats: Default::default(),
+                chain: None,
+            })
+            .unwrap();
+        writer.finalize().unwrap();
+        drop(writer);
+
+        let result = query_rows(&path, "SELECT block_number, decoder FROM events").unwrap();
+        assert_eq!(result.rows.len(), 1);
+        assert_eq!(result.rows[0]["decoder"], "fetched");
+    }
 
     #[test]
     fn test_sanitize_column_name_basic() {
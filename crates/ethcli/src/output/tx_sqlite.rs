@@ -0,0 +1,401 @@
+//! SQLite output writer for transaction analyses
+//!
+//! Stores `ethcli tx` results relationally so large exports can be queried
+//! with SQL instead of walking one giant JSON array:
+//!
+//! - `transactions`: one row per transaction, keyed by hash
+//! - `events`: decoded logs, with parameters stored as JSON
+//! - `token_flows`: ERC20/721 transfers
+//!
+//! Batches are written in their own transaction as they complete, and
+//! transactions already present are skipped, so an interrupted export can be
+//! resumed by re-running the same command.
+
+use super::sqlite::{open_connection, with_transaction};
+use crate::error::{OutputError, Result};
+use crate::tx::TransactionAnalysis;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Schema version stored in `PRAGMA user_version`
+pub const TX_SCHEMA_VERSION: i64 = 1;
+
+const TX_SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS transactions (
+        hash TEXT PRIMARY KEY,
+        block_number INTEGER NOT NULL,
+        from_address TEXT NOT NULL,
+        to_address TEXT,
+        value TEXT NOT NULL,
+        gas_used INTEGER NOT NULL,
+        status INTEGER NOT NULL,
+        method TEXT
+    );
+    CREATE INDEX IF NOT EXISTS idx_tx_block ON transactions(block_number);
+    CREATE INDEX IF NOT EXISTS idx_tx_from ON transactions(from_address);
+    CREATE INDEX IF NOT EXISTS idx_tx_to ON transactions(to_address);
+
+    CREATE TABLE IF NOT EXISTS events (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        tx_hash TEXT NOT NULL REFERENCES transactions(hash) ON DELETE CASCADE,
+        log_index INTEGER NOT NULL,
+        address TEXT NOT NULL,
+        name TEXT,
+        params TEXT NOT NULL,
+        UNIQUE(tx_hash, log_index)
+    );
+    CREATE INDEX IF NOT EXISTS idx_events_tx ON events(tx_hash);
+    CREATE INDEX IF NOT EXISTS idx_events_address ON events(address);
+    CREATE INDEX IF NOT EXISTS idx_events_name ON events(name);
+
+    CREATE TABLE IF NOT EXISTS token_flows (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        tx_hash TEXT NOT NULL REFERENCES transactions(hash) ON DELETE CASCADE,
+        log_index INTEGER NOT NULL,
+        token TEXT NOT NULL,
+        from_address TEXT NOT NULL,
+        to_address TEXT NOT NULL,
+        amount TEXT NOT NULL,
+        symbol TEXT,
+        decimals INTEGER
+    );
+    CREATE INDEX IF NOT EXISTS idx_flows_tx ON token_flows(tx_hash);
+    CREATE INDEX IF NOT EXISTS idx_flows_token ON token_flows(token);
+    CREATE INDEX IF NOT EXISTS idx_flows_from ON token_flows(from_address);
+    CREATE INDEX IF NOT EXISTS idx_flows_to ON token_flows(to_address);
+";
+
+/// SQLite writer for transaction analyses
+pub struct TxSqliteWriter {
+    /// Database connection
+    conn: Connection,
+}
+
+impl TxSqliteWriter {
+    /// Open (or create) a transaction database
+    ///
+    /// Fails if the file holds a different ethcli database (e.g. a `logs`
+    /// export) or was written by a newer schema version.
+    pub fn new(path: &Path) -> Result<Self> {
+        let mut conn = open_connection(path)?;
+        Self::migrate(&mut conn)?;
+        Ok(Self { conn })
+    }
+
+    /// Create or validate the schema
+    fn migrate(conn: &mut Connection) -> Result<()> {
+        let version: i64 = conn
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .map_err(OutputError::Sqlite)?;
+
+        match version {
+            0 => {
+                // An unversioned database with tables isn't ours to extend
+                let tables: i64 = conn
+                    .query_row(
+                        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table'",
+                        [],
+                        |row| row.get(0),
+                    )
+                    .map_err(OutputError::Sqlite)?;
+                if tables > 0 {
+                    return Err(OutputError::Schema(
+                        "database already contains tables that were not created by `ethcli tx`"
+                            .to_string(),
+                    )
+                    .into());
+                }
+                with_transaction(conn, |tx| {
+                    tx.execute_batch(TX_SCHEMA).map_err(OutputError::Sqlite)?;
+                    tx.pragma_update(None, "user_version", TX_SCHEMA_VERSION)
+                        .map_err(OutputError::Sqlite)?;
+                    Ok(())
+                })
+            }
+            TX_SCHEMA_VERSION => Ok(()),
+            other => Err(OutputError::Schema(format!(
+                "schema version {other} is not supported (expected {TX_SCHEMA_VERSION})"
+            ))
+            .into()),
+        }
+    }
+
+    /// Normalize a hash to the stored form (lowercase, 0x-prefixed)
+    fn normalize_hash(hash: &str) -> String {
+        let hex = hash
+            .strip_prefix("0x")
+            .or_else(|| hash.strip_prefix("0X"))
+            .unwrap_or(hash);
+        format!("0x{}", hex.to_lowercase())
+    }
+
+    /// Whether a transaction is already stored
+    pub fn contains(&self, hash: &str) -> Result<bool> {
+        let found = self
+            .conn
+            .query_row(
+                "SELECT 1 FROM transactions WHERE hash = ?",
+                [Self::normalize_hash(hash)],
+                |_| Ok(()),
+            )
+            .optional()
+            .map_err(OutputError::Sqlite)?;
+        Ok(found.is_some())
+    }
+
+    /// Subset of `hashes` already stored, in normalized form
+    pub fn existing_hashes(&self, hashes: &[String]) -> Result<HashSet<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT 1 FROM transactions WHERE hash = ?")
+            .map_err(OutputError::Sqlite)?;
+
+        let mut existing = HashSet::new();
+        for hash in hashes {
+            let hash = Self::normalize_hash(hash);
+            if stmt.exists([&hash]).map_err(OutputError::Sqlite)? {
+                existing.insert(hash);
+            }
+        }
+        Ok(existing)
+    }
+
+    /// Write a batch of analyses in a single transaction
+    ///
+    /// Transactions already in the database are skipped along with their
+    /// events and token flows. Returns the number of transactions inserted.
+    pub fn write_batch(&mut self, analyses: &[TransactionAnalysis]) -> Result<usize> {
+        with_transaction(&mut self.conn, |tx| {
+            let mut insert_tx = tx
+                .prepare(
+                    "INSERT OR IGNORE INTO transactions
+                     (hash, block_number, from_address, to_address, value, gas_used, status, method)
+                     VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+                )
+                .map_err(OutputError::Sqlite)?;
+            let mut insert_event = tx
+                .prepare(
+                    "INSERT INTO events (tx_hash, log_index, address, name, params)
+                     VALUES (?, ?, ?, ?, ?)",
+                )
+                .map_err(OutputError::Sqlite)?;
+            let mut insert_flow = tx
+                .prepare(
+                    "INSERT INTO token_flows
+                     (tx_hash, log_index, token, from_address, to_address, amount, symbol, decimals)
+                     VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+                )
+                .map_err(OutputError::Sqlite)?;
+
+            let mut inserted = 0;
+            for analysis in analyses {
+                let hash = format!("{:#x}", analysis.hash);
+                let method = analysis
+                    .function_call
+                    .as_ref()
+                    .map(|call| call.name.clone().unwrap_or_else(|| call.selector.clone()));
+
+                let changed = insert_tx
+                    .execute(params![
+                        hash,
+                        analysis.block_number as i64,
+                        format!("{:#x}", analysis.from),
+                        analysis.to.map(|to| format!("{:#x}", to)),
+                        analysis.value.to_string(),
+                        analysis.gas_used as i64,
+                        analysis.status,
+                        method,
+                    ])
+                    .map_err(OutputError::Sqlite)?;
+                if changed == 0 {
+                    continue;
+                }
+                inserted += 1;
+
+                for event in &analysis.events {
+                    let params_json = serde_json::to_string(&event.params).unwrap_or_else(|e| {
+                        tracing::warn!("Failed to serialize event params in tx {}: {}", hash, e);
+                        "{}".to_string()
+                    });
+                    insert_event
+                        .execute(params![
+                            hash,
+                            event.log_index as i64,
+                            format!("{:#x}", event.address),
+                            event.name,
+                            params_json,
+                        ])
+                        .map_err(OutputError::Sqlite)?;
+                }
+
+                for flow in &analysis.token_flows {
+                    insert_flow
+                        .execute(params![
+                            hash,
+                            flow.log_index as i64,
+                            format!("{:#x}", flow.token),
+                            format!("{:#x}", flow.from),
+                            format!("{:#x}", flow.to),
+                            flow.amount,
+                            flow.token_label,
+                            flow.token_decimals,
+                        ])
+                        .map_err(OutputError::Sqlite)?;
+                }
+            }
+            Ok(inserted)
+        })
+    }
+
+    /// Finalize output (optimize query planner statistics)
+    pub fn finalize(&mut self) -> Result<()> {
+        self.conn
+            .execute_batch("PRAGMA optimize;")
+            .map_err(OutputError::Sqlite)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::query_rows;
+    use crate::tx::types::{AnalyzedEvent, EventParam, FunctionCall, TokenFlow};
+    use alloy::primitives::{address, b256, B256, U256};
+    use std::collections::HashMap;
+
+    fn make_analysis(hash: B256) -> TransactionAnalysis {
+        let mut params = HashMap::new();
+        params.insert("value".to_string(), EventParam::Uint("1000000".to_string()));
+
+        TransactionAnalysis {
+            hash,
+            block_number: 19_000_000,
+            from: address!("d8da6bf26964af9d7eed9e03e53415d37aa96045"),
+            from_label: None,
+            to: Some(address!("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48")),
+            to_label: None,
+            value: U256::ZERO,
+            gas_used: 51_000,
+            status: true,
+            contracts: Vec::new(),
+            events: vec![AnalyzedEvent {
+                log_index: 3,
+                address: address!("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"),
+                address_label: Some("USDC".to_string()),
+                name: Some("Transfer".to_string()),
+                signature: Some("Transfer(address,address,uint256)".to_string()),
+                params,
+                topic0: B256::ZERO,
+                is_transfer: true,
+            }],
+            token_flows: vec![TokenFlow {
+                token: address!("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"),
+                token_label: Some("USDC".to_string()),
+                token_decimals: Some(6),
+                from: address!("d8da6bf26964af9d7eed9e03e53415d37aa96045"),
+                from_label: None,
+                to: address!("c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"),
+                to_label: None,
+                amount: "1000000".to_string(),
+                log_index: 3,
+            }],
+            function_call: Some(FunctionCall {
+                selector: "0xa9059cbb".to_string(),
+                name: Some("transfer".to_string()),
+                signature: None,
+                params: Vec::new(),
+            }),
+            explorer_url: None,
+            failure_reason: None,
+            labels: vec![],
+            enriched: false,
+            warnings: vec![],
+        }
+    }
+
+    const HASH_A: B256 = b256!("1111111111111111111111111111111111111111111111111111111111111111");
+    const HASH_B: B256 = b256!("2222222222222222222222222222222222222222222222222222222222222222");
+
+    #[test]
+    fn test_write_batch_and_query() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("txs.db");
+
+        let mut writer = TxSqliteWriter::new(&path).unwrap();
+        assert_eq!(writer.write_batch(&[make_analysis(HASH_A)]).unwrap(), 1);
+        writer.finalize().unwrap();
+
+        let rows = query_rows(
+            &path,
+            "SELECT t.method, f.symbol, f.decimals, e.params
+             FROM transactions t
+             JOIN token_flows f ON f.tx_hash = t.hash
+             JOIN events e ON e.tx_hash = t.hash",
+        )
+        .unwrap()
+        .rows;
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0]["method"], "transfer");
+        assert_eq!(rows[0]["symbol"], "USDC");
+        assert_eq!(rows[0]["decimals"], 6);
+        assert_eq!(rows[0]["params"], r#"{"value":"1000000"}"#);
+    }
+
+    #[test]
+    fn test_resume_skips_existing_transactions() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("txs.db");
+
+        let mut writer = TxSqliteWriter::new(&path).unwrap();
+        writer.write_batch(&[make_analysis(HASH_A)]).unwrap();
+        drop(writer);
+
+        // Reopen as a resumed run would
+        let mut writer = TxSqliteWriter::new(&path).unwrap();
+        assert!(writer.contains(&format!("{:#x}", HASH_A)).unwrap());
+        assert!(!writer.contains(&format!("{:#x}", HASH_B)).unwrap());
+
+        let upper = format!("0X{}", hex::encode(HASH_A).to_uppercase());
+        let existing = writer
+            .existing_hashes(&[upper, format!("{:#x}", HASH_B)])
+            .unwrap();
+        assert_eq!(existing.len(), 1);
+        assert!(existing.contains(&format!("{:#x}", HASH_A)));
+
+        let inserted = writer
+            .write_batch(&[make_analysis(HASH_A), make_analysis(HASH_B)])
+            .unwrap();
+        assert_eq!(inserted, 1);
+
+        let rows = query_rows(&path, "SELECT COUNT(*) AS n FROM events")
+            .unwrap()
+            .rows;
+        assert_eq!(rows[0]["n"], 2);
+    }
+
+    #[test]
+    fn test_schema_version_checks() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let path = dir.path().join("txs.db");
+        TxSqliteWriter::new(&path).unwrap();
+        let rows = query_rows(&path, "PRAGMA user_version").unwrap().rows;
+        assert_eq!(rows[0]["user_version"], TX_SCHEMA_VERSION);
+
+        let newer = dir.path().join("newer.db");
+        Connection::open(&newer)
+            .unwrap()
+            .pragma_update(None, "user_version", TX_SCHEMA_VERSION + 1)
+            .unwrap();
+        assert!(TxSqliteWriter::new(&newer).is_err());
+
+        let foreign = dir.path().join("logs.db");
+        Connection::open(&foreign)
+            .unwrap()
+            .execute_batch("CREATE TABLE events (id INTEGER PRIMARY KEY)")
+            .unwrap();
+        assert!(TxSqliteWriter::new(&foreign).is_err());
+    }
+}
@@ -406,9 +406,11 @@ mod tests {
 
     #[test]
     fn test_error_rate() {
-        let mut health = EndpointHealth::default();
-        health.total_requests = 10;
-        health.failed_requests = 3;
+        let health = EndpointHealth {
+            total_requests: 10,
+            failed_requests: 3,
+            ..Default::default()
+        };
 
         assert!((health.error_rate() - 0.3).abs() < 0.001);
     }
@@ -2,16 +2,20 @@
 
 mod endpoint;
 mod health;
+pub mod monitor;
 pub mod multicall;
 mod optimizer;
 mod pool;
+mod raw;
 pub mod retry;
 pub mod selector;
 
 pub use endpoint::Endpoint;
 pub use health::{EndpointHealth, HealthTracker};
+pub use monitor::{EndpointMonitor, MonitorPolicy, MonitorState};
 pub use multicall::{MulticallBuilder, MulticallResult, MULTICALL3_ADDRESS};
 pub use optimizer::{optimize_endpoint, test_connectivity, OptimizationResult};
 pub use pool::RpcPool;
+pub use raw::RawRpc;
 pub use retry::{with_retry, with_simple_retry, RetryConfig, RetryError, RetryableError};
 pub use selector::{get_rpc_endpoint, get_rpc_url};
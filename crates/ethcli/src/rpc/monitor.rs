@@ -0,0 +1,1024 @@
+//! Scheduled endpoint probing and priority auto-tuning.
+//!
+//! `ethcli endpoints monitor` re-runs the optimizer probes on an interval and
+//! keeps a rolling per-endpoint history (latency, detected limits, failures)
+//! in a JSON state file next to the config. After each cycle,
+//! [`plan_changes`] proposes:
+//!
+//! - Priority adjustments within a configurable band. Endpoints on the same
+//!   chain are ranked by success rate, then median latency; the best gets the
+//!   top of the band and the rest are spread evenly down to the bottom.
+//! - Disabling endpoints after N consecutive failed probes.
+//! - Re-enabling endpoints the monitor disabled once a probe succeeds.
+//!
+//! Endpoints marked `pinned = true` keep their priority, and auto-tuned
+//! priorities never exceed the lowest pinned priority on the same chain.
+//! Endpoints disabled by hand are left alone.
+
+use crate::config::{Chain, ConfigFile, EndpointConfig};
+use crate::error::{ConfigError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Current state file format version
+pub const MONITOR_STATE_VERSION: u32 = 1;
+
+/// Probe samples kept per endpoint (one week at the default 6h interval)
+pub const HISTORY_LIMIT: usize = 28;
+
+/// Result of probing one endpoint once
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProbeSample {
+    /// Unix timestamp (seconds) of the probe
+    pub timestamp: u64,
+    /// Whether the endpoint answered correctly
+    pub ok: bool,
+    /// Round-trip latency of the connectivity check
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    /// Detected getLogs block range limit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_block_range: Option<u64>,
+    /// Detected getLogs result limit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_logs: Option<usize>,
+    /// Failure reason
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ProbeSample {
+    /// A successful probe
+    pub fn success(timestamp: u64, latency_ms: u64) -> Self {
+        Self {
+            timestamp,
+            ok: true,
+            latency_ms: Some(latency_ms),
+            max_block_range: None,
+            max_logs: None,
+            error: None,
+        }
+    }
+
+    /// A failed probe
+    pub fn failure(timestamp: u64, error: impl Into<String>) -> Self {
+        Self {
+            timestamp,
+            ok: false,
+            latency_ms: None,
+            max_block_range: None,
+            max_logs: None,
+            error: Some(error.into()),
+        }
+    }
+}
+
+/// Rolling probe history for one endpoint
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EndpointHistory {
+    /// Most recent samples, oldest first (at most [`HISTORY_LIMIT`])
+    #[serde(default)]
+    pub samples: VecDeque<ProbeSample>,
+    /// Failed probes since the last success
+    #[serde(default)]
+    pub consecutive_failures: u32,
+    /// Whether the monitor (not the user) disabled this endpoint
+    #[serde(default)]
+    pub disabled_by_monitor: bool,
+}
+
+impl EndpointHistory {
+    /// Add a sample, dropping the oldest beyond [`HISTORY_LIMIT`]
+    pub fn record(&mut self, sample: ProbeSample) {
+        if sample.ok {
+            self.consecutive_failures = 0;
+        } else {
+            self.consecutive_failures += 1;
+        }
+        self.samples.push_back(sample);
+        while self.samples.len() > HISTORY_LIMIT {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Fraction of successful samples
+    pub fn success_rate(&self) -> Option<f64> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let ok = self.samples.iter().filter(|s| s.ok).count();
+        Some(ok as f64 / self.samples.len() as f64)
+    }
+
+    /// Median latency of successful samples
+    pub fn median_latency_ms(&self) -> Option<u64> {
+        let mut latencies: Vec<u64> = self.samples.iter().filter_map(|s| s.latency_ms).collect();
+        if latencies.is_empty() {
+            return None;
+        }
+        latencies.sort_unstable();
+        Some(latencies[latencies.len() / 2])
+    }
+
+    /// Whether the latest probe succeeded
+    pub fn last_ok(&self) -> Option<bool> {
+        self.samples.back().map(|s| s.ok)
+    }
+}
+
+/// Persisted monitor state, keyed by endpoint URL
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonitorState {
+    /// State file format version
+    pub version: u32,
+    /// Per-endpoint history
+    #[serde(default)]
+    pub endpoints: BTreeMap<String, EndpointHistory>,
+}
+
+impl Default for MonitorState {
+    fn default() -> Self {
+        Self {
+            version: MONITOR_STATE_VERSION,
+            endpoints: BTreeMap::new(),
+        }
+    }
+}
+
+impl MonitorState {
+    /// Get the default state file path
+    ///
+    /// Can be overridden by setting the `ETHCLI_CONFIG_DIR` environment variable.
+    pub fn default_path() -> PathBuf {
+        if let Ok(config_dir) = std::env::var("ETHCLI_CONFIG_DIR") {
+            return PathBuf::from(config_dir).join("endpoint_monitor.json");
+        }
+
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("ethcli")
+            .join("endpoint_monitor.json")
+    }
+
+    /// Load from a path, starting empty if the file does not exist
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| ConfigError::InvalidFile(format!("{}: {}", path.display(), e)))?;
+        let state: Self = serde_json::from_str(&content)
+            .map_err(|e| ConfigError::InvalidFile(format!("{}: {}", path.display(), e)))?;
+        if state.version != MONITOR_STATE_VERSION {
+            return Err(ConfigError::InvalidFile(format!(
+                "{}: unsupported monitor state version {} (expected {})",
+                path.display(),
+                state.version,
+                MONITOR_STATE_VERSION
+            ))
+            .into());
+        }
+        Ok(state)
+    }
+
+    /// Save to a path (write to a temp file, then rename)
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                ConfigError::InvalidFile(format!("Failed to create directory: {}", e))
+            })?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(|e| {
+            ConfigError::InvalidFile(format!("Failed to serialize monitor state: {}", e))
+        })?;
+        let temp_path = path.with_extension(format!("{}.tmp", std::process::id()));
+        std::fs::write(&temp_path, content)
+            .and_then(|_| std::fs::rename(&temp_path, path))
+            .map_err(|e| {
+                let _ = std::fs::remove_file(&temp_path);
+                ConfigError::InvalidFile(format!("{}: {}", path.display(), e))
+            })?;
+        Ok(())
+    }
+
+    /// Record a probe result for an endpoint
+    pub fn record(&mut self, url: &str, sample: ProbeSample) {
+        self.endpoints
+            .entry(url.to_string())
+            .or_default()
+            .record(sample);
+    }
+
+    /// History for an endpoint, if it has been probed
+    pub fn history(&self, url: &str) -> Option<&EndpointHistory> {
+        self.endpoints.get(url)
+    }
+}
+
+/// Limits for automatic changes
+#[derive(Debug, Clone, Copy)]
+pub struct MonitorPolicy {
+    /// Lowest priority auto-tuning assigns
+    pub min_priority: u8,
+    /// Highest priority auto-tuning assigns
+    pub max_priority: u8,
+    /// Consecutive failed probes before an endpoint is disabled
+    pub failure_threshold: u32,
+    /// Samples required before an endpoint's priority is tuned
+    pub min_samples: usize,
+}
+
+impl Default for MonitorPolicy {
+    fn default() -> Self {
+        Self {
+            min_priority: 1,
+            max_priority: 10,
+            failure_threshold: 3,
+            min_samples: 2,
+        }
+    }
+}
+
+/// What a proposed change does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum ChangeKind {
+    /// Change the endpoint priority
+    Priority { from: u8, to: u8 },
+    /// Disable after repeated probe failures
+    Disable { consecutive_failures: u32 },
+    /// Re-enable after a successful probe
+    Enable,
+}
+
+/// A proposed change to one endpoint
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EndpointChange {
+    /// Endpoint URL
+    pub url: String,
+    /// The change
+    #[serde(flatten)]
+    pub kind: ChangeKind,
+}
+
+impl std::fmt::Display for EndpointChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            ChangeKind::Priority { from, to } => {
+                write!(f, "{}: priority {} -> {}", self.url, from, to)
+            }
+            ChangeKind::Disable {
+                consecutive_failures,
+            } => write!(
+                f,
+                "{}: disable ({} consecutive failed probes)",
+                self.url, consecutive_failures
+            ),
+            ChangeKind::Enable => write!(f, "{}: re-enable (probe succeeded)", self.url),
+        }
+    }
+}
+
+/// Whether the monitor should probe an endpoint
+///
+/// Enabled endpoints and endpoints the monitor itself disabled are probed;
+/// endpoints disabled by hand are skipped.
+pub fn should_probe(endpoint: &EndpointConfig, state: &MonitorState) -> bool {
+    endpoint.enabled
+        || state
+            .history(&endpoint.url)
+            .is_some_and(|h| h.disabled_by_monitor)
+}
+
+/// Propose changes from the recorded history
+pub fn plan_changes(
+    endpoints: &[EndpointConfig],
+    state: &MonitorState,
+    policy: &MonitorPolicy,
+) -> Vec<EndpointChange> {
+    let mut changes = Vec::new();
+    // Endpoints that end this cycle enabled, grouped by chain
+    let mut active: BTreeMap<u64, Vec<&EndpointConfig>> = BTreeMap::new();
+
+    for endpoint in endpoints {
+        let Some(history) = state.history(&endpoint.url) else {
+            if endpoint.enabled {
+                active
+                    .entry(endpoint.chain.chain_id())
+                    .or_default()
+                    .push(endpoint);
+            }
+            continue;
+        };
+
+        if endpoint.enabled {
+            if history.consecutive_failures >= policy.failure_threshold {
+                changes.push(EndpointChange {
+                    url: endpoint.url.clone(),
+                    kind: ChangeKind::Disable {
+                        consecutive_failures: history.consecutive_failures,
+                    },
+                });
+                continue;
+            }
+        } else if history.disabled_by_monitor && history.last_ok() == Some(true) {
+            changes.push(EndpointChange {
+                url: endpoint.url.clone(),
+                kind: ChangeKind::Enable,
+            });
+        } else {
+            continue;
+        }
+        active
+            .entry(endpoint.chain.chain_id())
+            .or_default()
+            .push(endpoint);
+    }
+
+    for chain_endpoints in active.values() {
+        changes.extend(plan_priorities(chain_endpoints, state, policy));
+    }
+    changes
+}
+
+/// Spread priorities for one chain's active endpoints across the band
+fn plan_priorities(
+    endpoints: &[&EndpointConfig],
+    state: &MonitorState,
+    policy: &MonitorPolicy,
+) -> Vec<EndpointChange> {
+    let pinned_cap = endpoints
+        .iter()
+        .filter(|e| e.pinned)
+        .map(|e| e.priority)
+        .min();
+    let top = pinned_cap.map_or(policy.max_priority, |p| p.min(policy.max_priority));
+    let bottom = policy.min_priority.min(top);
+
+    let mut ranked: Vec<(&EndpointConfig, f64, u64)> = endpoints
+        .iter()
+        .filter(|e| !e.pinned)
+        .filter_map(|e| {
+            let history = state.history(&e.url)?;
+            if history.samples.len() < policy.min_samples {
+                return None;
+            }
+            Some((
+                *e,
+                history.success_rate()?,
+                history.median_latency_ms().unwrap_or(u64::MAX),
+            ))
+        })
+        .collect();
+    ranked.sort_by(|a, b| {
+        b.1.total_cmp(&a.1)
+            .then(a.2.cmp(&b.2))
+            .then_with(|| a.0.url.cmp(&b.0.url))
+    });
+
+    let span = f64::from(top - bottom);
+    let steps = ranked.len().saturating_sub(1).max(1) as f64;
+    ranked
+        .iter()
+        .enumerate()
+        .filter_map(|(rank, (endpoint, _, _))| {
+            let target = top - (span * rank as f64 / steps).round() as u8;
+            (target != endpoint.priority).then(|| EndpointChange {
+                url: endpoint.url.clone(),
+                kind: ChangeKind::Priority {
+                    from: endpoint.priority,
+                    to: target,
+                },
+            })
+        })
+        .collect()
+}
+
+/// Apply changes to endpoints and note monitor-driven disables in the state
+pub fn apply_changes(
+    endpoints: &mut [EndpointConfig],
+    state: &mut MonitorState,
+    changes: &[EndpointChange],
+) {
+    for change in changes {
+        let Some(endpoint) = endpoints.iter_mut().find(|e| e.url == change.url) else {
+            continue;
+        };
+        let history = state.endpoints.entry(change.url.clone()).or_default();
+        match change.kind {
+            ChangeKind::Priority { to, .. } => endpoint.priority = to,
+            ChangeKind::Disable { .. } => {
+                endpoint.enabled = false;
+                history.disabled_by_monitor = true;
+            }
+            ChangeKind::Enable => {
+                endpoint.enabled = true;
+                history.disabled_by_monitor = false;
+            }
+        }
+    }
+}
+
+/// Format changelog lines for applied changes
+pub fn changelog_entry(timestamp: u64, changes: &[EndpointChange]) -> String {
+    let when = chrono::DateTime::from_timestamp(timestamp as i64, 0)
+        .map(|t| t.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+        .unwrap_or_else(|| timestamp.to_string());
+    changes
+        .iter()
+        .map(|c| format!("{} {}\n", when, c))
+        .collect()
+}
+
+/// Probes endpoints for the monitor
+pub trait EndpointProber {
+    /// Probe one endpoint
+    fn probe(&self, endpoint: &EndpointConfig) -> impl Future<Output = ProbeSample>;
+}
+
+/// Prober backed by the `endpoints optimize` checks
+///
+/// Latency comes from a connectivity check; block range and log limits come
+/// from a full optimization pass.
+pub struct OptimizerProber {
+    /// Timeout for each test in seconds
+    pub timeout_secs: u64,
+}
+
+impl EndpointProber for OptimizerProber {
+    async fn probe(&self, endpoint: &EndpointConfig) -> ProbeSample {
+        let timestamp = crate::utils::unix_timestamp_secs();
+        let start = std::time::Instant::now();
+        if let Err(e) = super::test_connectivity(&endpoint.url, self.timeout_secs).await {
+            return ProbeSample::failure(timestamp, e.to_string());
+        }
+        let latency_ms = start.elapsed().as_millis() as u64;
+
+        match super::optimize_endpoint(&endpoint.url, Some(endpoint.chain), self.timeout_secs).await
+        {
+            Ok(result) if result.error.is_none() => ProbeSample {
+                max_block_range: Some(result.config.max_block_range),
+                max_logs: Some(result.config.max_logs),
+                ..ProbeSample::success(timestamp, latency_ms)
+            },
+            Ok(result) => ProbeSample::failure(
+                timestamp,
+                result.error.unwrap_or_else(|| "optimization failed".into()),
+            ),
+            Err(e) => ProbeSample::failure(timestamp, e.to_string()),
+        }
+    }
+}
+
+/// Where the monitor reads endpoints from and writes changes to
+pub trait EndpointStore {
+    /// Load the current endpoints
+    fn load(&mut self) -> Result<Vec<EndpointConfig>>;
+
+    /// Persist endpoints after applying `changes`
+    fn save(
+        &mut self,
+        endpoints: &[EndpointConfig],
+        changes: &[EndpointChange],
+        timestamp: u64,
+    ) -> Result<()>;
+}
+
+/// Store backed by the config file, with an append-only changelog
+///
+/// The config is re-read before every save and only `priority` and `enabled`
+/// are patched (matched by URL), so other edits made while the monitor runs
+/// are preserved.
+pub struct ConfigFileStore {
+    /// Config file path
+    pub config_path: PathBuf,
+    /// Changelog path
+    pub changelog_path: PathBuf,
+}
+
+impl ConfigFileStore {
+    /// Store for the default config file, logging to `endpoint_changes.log`
+    /// alongside it
+    pub fn default_paths() -> Self {
+        let config_path = ConfigFile::default_path();
+        let changelog_path = config_path.with_file_name("endpoint_changes.log");
+        Self {
+            config_path,
+            changelog_path,
+        }
+    }
+}
+
+impl EndpointStore for ConfigFileStore {
+    fn load(&mut self) -> Result<Vec<EndpointConfig>> {
+        if !self.config_path.exists() {
+            return Ok(Vec::new());
+        }
+        Ok(ConfigFile::load(&self.config_path)?.endpoints)
+    }
+
+    fn save(
+        &mut self,
+        endpoints: &[EndpointConfig],
+        changes: &[EndpointChange],
+        timestamp: u64,
+    ) -> Result<()> {
+        let mut config = ConfigFile::load(&self.config_path)?;
+        for existing in &mut config.endpoints {
+            if let Some(updated) = endpoints.iter().find(|e| e.url == existing.url) {
+                existing.priority = updated.priority;
+                existing.enabled = updated.enabled;
+            }
+        }
+        config.save(&self.config_path)?;
+
+        use std::io::Write;
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.changelog_path)
+            .and_then(|mut f| f.write_all(changelog_entry(timestamp, changes).as_bytes()))
+            .map_err(|e| {
+                ConfigError::InvalidFile(format!("{}: {}", self.changelog_path.display(), e))
+            })?;
+        Ok(())
+    }
+}
+
+/// Outcome of one monitor cycle
+#[derive(Debug, Clone)]
+pub struct CycleReport {
+    /// Cycle number, starting at 1
+    pub cycle: u64,
+    /// Endpoints probed
+    pub probed: usize,
+    /// Probes that failed
+    pub failed: usize,
+    /// Proposed (or applied) changes
+    pub changes: Vec<EndpointChange>,
+    /// Whether the changes were written to the store
+    pub applied: bool,
+}
+
+/// Scheduling options for [`EndpointMonitor::run`]
+#[derive(Debug, Clone)]
+pub struct MonitorOptions {
+    /// Time between cycles
+    pub interval: Duration,
+    /// Stop after this many cycles (`None` runs forever)
+    pub max_cycles: Option<u64>,
+    /// Write changes to the store
+    pub apply: bool,
+}
+
+/// Runs probe cycles and tunes endpoints
+pub struct EndpointMonitor {
+    /// Probe history
+    pub state: MonitorState,
+    /// Change limits
+    pub policy: MonitorPolicy,
+    /// Only monitor endpoints for this chain
+    pub chain: Option<Chain>,
+    /// Where to persist `state` after each cycle (`None` keeps it in memory)
+    pub state_path: Option<PathBuf>,
+}
+
+impl EndpointMonitor {
+    /// Create a monitor with in-memory state
+    pub fn new(state: MonitorState, policy: MonitorPolicy) -> Self {
+        Self {
+            state,
+            policy,
+            chain: None,
+            state_path: None,
+        }
+    }
+
+    /// Probe endpoints once, record the results and plan changes
+    pub async fn run_cycle<P: EndpointProber>(
+        &mut self,
+        endpoints: &[EndpointConfig],
+        prober: &P,
+    ) -> (usize, usize, Vec<EndpointChange>) {
+        let targets: Vec<&EndpointConfig> = endpoints
+            .iter()
+            .filter(|e| self.chain.is_none_or(|c| e.chain == c))
+            .filter(|e| should_probe(e, &self.state))
+            .collect();
+
+        let samples = futures::future::join_all(targets.iter().map(|e| prober.probe(e))).await;
+        let failed = samples.iter().filter(|s| !s.ok).count();
+        for (endpoint, sample) in targets.iter().zip(samples) {
+            self.state.record(&endpoint.url, sample);
+        }
+
+        let scoped: Vec<EndpointConfig> = endpoints
+            .iter()
+            .filter(|e| self.chain.is_none_or(|c| e.chain == c))
+            .cloned()
+            .collect();
+        let changes = plan_changes(&scoped, &self.state, &self.policy);
+        (targets.len(), failed, changes)
+    }
+
+    /// Run cycles on a schedule, calling `on_cycle` after each
+    ///
+    /// Endpoints are reloaded from the store every cycle so edits made while
+    /// the monitor runs are picked up.
+    pub async fn run<P, S, F>(
+        &mut self,
+        prober: &P,
+        store: &mut S,
+        options: &MonitorOptions,
+        mut on_cycle: F,
+    ) -> Result<()>
+    where
+        P: EndpointProber,
+        S: EndpointStore,
+        F: FnMut(&CycleReport),
+    {
+        let mut cycle = 0u64;
+        loop {
+            cycle += 1;
+            let mut endpoints = store.load()?;
+            let (probed, failed, changes) = self.run_cycle(&endpoints, prober).await;
+
+            let applied = options.apply && !changes.is_empty();
+            if applied {
+                apply_changes(&mut endpoints, &mut self.state, &changes);
+                store.save(&endpoints, &changes, crate::utils::unix_timestamp_secs())?;
+            }
+            if let Some(path) = &self.state_path {
+                self.state.save(path)?;
+            }
+
+            on_cycle(&CycleReport {
+                cycle,
+                probed,
+                failed,
+                changes,
+                applied,
+            });
+
+            if options.max_cycles.is_some_and(|max| cycle >= max) {
+                return Ok(());
+            }
+            tokio::time::sleep(options.interval).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    fn endpoint(url: &str, priority: u8) -> EndpointConfig {
+        EndpointConfig::new(url)
+            .with_chain(Chain::Ethereum)
+            .with_priority(priority)
+    }
+
+    fn state_with(samples: &[(&str, &[Option<u64>])]) -> MonitorState {
+        let mut state = MonitorState::default();
+        for (url, results) in samples {
+            for (i, latency) in results.iter().enumerate() {
+                let sample = match latency {
+                    Some(ms) => ProbeSample::success(i as u64, *ms),
+                    None => ProbeSample::failure(i as u64, "timeout"),
+                };
+                state.record(url, sample);
+            }
+        }
+        state
+    }
+
+    /// Replays scripted probe results per URL (`None` = failure)
+    struct ScriptedProber {
+        script: Mutex<HashMap<String, VecDeque<Option<u64>>>>,
+    }
+
+    impl ScriptedProber {
+        fn new(script: &[(&str, &[Option<u64>])]) -> Self {
+            Self {
+                script: Mutex::new(
+                    script
+                        .iter()
+                        .map(|(url, r)| (url.to_string(), r.iter().copied().collect()))
+                        .collect(),
+                ),
+            }
+        }
+    }
+
+    impl EndpointProber for ScriptedProber {
+        async fn probe(&self, endpoint: &EndpointConfig) -> ProbeSample {
+            let next = self
+                .script
+                .lock()
+                .unwrap()
+                .get_mut(&endpoint.url)
+                .and_then(|q| q.pop_front())
+                .flatten();
+            match next {
+                Some(ms) => ProbeSample::success(0, ms),
+                None => ProbeSample::failure(0, "connection refused"),
+            }
+        }
+    }
+
+    #[derive(Default)]
+    struct MemoryStore {
+        endpoints: Vec<EndpointConfig>,
+        changelog: String,
+    }
+
+    impl EndpointStore for MemoryStore {
+        fn load(&mut self) -> Result<Vec<EndpointConfig>> {
+            Ok(self.endpoints.clone())
+        }
+
+        fn save(
+            &mut self,
+            endpoints: &[EndpointConfig],
+            changes: &[EndpointChange],
+            timestamp: u64,
+        ) -> Result<()> {
+            self.endpoints = endpoints.to_vec();
+            self.changelog
+                .push_str(&changelog_entry(timestamp, changes));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_history_is_rolling() {
+        let mut history = EndpointHistory::default();
+        for i in 0..(HISTORY_LIMIT as u64 + 5) {
+            history.record(ProbeSample::success(i, i));
+        }
+        assert_eq!(history.samples.len(), HISTORY_LIMIT);
+        assert_eq!(history.samples.front().unwrap().timestamp, 5);
+
+        history.record(ProbeSample::failure(100, "timeout"));
+        history.record(ProbeSample::failure(101, "timeout"));
+        assert_eq!(history.consecutive_failures, 2);
+        history.record(ProbeSample::success(102, 40));
+        assert_eq!(history.consecutive_failures, 0);
+    }
+
+    #[test]
+    fn test_state_file_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("endpoint_monitor.json");
+
+        assert!(MonitorState::load(&path).unwrap().endpoints.is_empty());
+
+        let mut state = state_with(&[("https://a", &[Some(120), None])]);
+        state
+            .endpoints
+            .get_mut("https://a")
+            .unwrap()
+            .disabled_by_monitor = true;
+        state.save(&path).unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json["version"], MONITOR_STATE_VERSION);
+        let history = &json["endpoints"]["https://a"];
+        assert_eq!(history["consecutive_failures"], 1);
+        assert_eq!(history["samples"][0]["latency_ms"], 120);
+        assert_eq!(history["samples"][1]["error"], "timeout");
+        assert!(history["samples"][1].get("latency_ms").is_none());
+
+        let loaded = MonitorState::load(&path).unwrap();
+        let history = loaded.history("https://a").unwrap();
+        assert!(history.disabled_by_monitor);
+        assert_eq!(history.samples.len(), 2);
+
+        std::fs::write(&path, r#"{"version": 99, "endpoints": {}}"#).unwrap();
+        assert!(MonitorState::load(&path).is_err());
+    }
+
+    #[test]
+    fn test_priorities_spread_across_band() {
+        let endpoints = vec![
+            endpoint("https://fast", 5),
+            endpoint("https://medium", 5),
+            endpoint("https://slow", 5),
+            endpoint("https://flaky", 5),
+        ];
+        let state = state_with(&[
+            ("https://fast", &[Some(40), Some(60)]),
+            ("https://medium", &[Some(150), Some(100)]),
+            ("https://slow", &[Some(900), Some(700)]),
+            // Fastest, but half its probes fail
+            ("https://flaky", &[Some(10), None, Some(10), Some(10)]),
+        ]);
+        let policy = MonitorPolicy {
+            min_priority: 2,
+            max_priority: 8,
+            ..Default::default()
+        };
+
+        let changes = plan_changes(&endpoints, &state, &policy);
+        let priority = |url: &str| {
+            changes.iter().find_map(|c| match c.kind {
+                ChangeKind::Priority { to, .. } if c.url == url => Some(to),
+                _ => None,
+            })
+        };
+        assert_eq!(priority("https://fast"), Some(8));
+        assert_eq!(priority("https://medium"), Some(6));
+        assert_eq!(priority("https://slow"), Some(4));
+        assert_eq!(priority("https://flaky"), Some(2));
+    }
+
+    #[test]
+    fn test_pinned_priority_caps_band() {
+        let endpoints = vec![
+            endpoint("https://pinned", 6).with_pinned(true),
+            endpoint("https://fast", 3),
+            endpoint("https://slow", 9),
+            // Different chain: not capped by the Ethereum pin
+            endpoint("https://polygon", 5).with_chain(Chain::Polygon),
+        ];
+        let state = state_with(&[
+            ("https://pinned", &[None, None]),
+            ("https://fast", &[Some(20), Some(30)]),
+            ("https://slow", &[Some(500), Some(600)]),
+            ("https://polygon", &[Some(50), Some(50)]),
+        ]);
+
+        let changes = plan_changes(&endpoints, &state, &MonitorPolicy::default());
+        assert!(changes.iter().all(|c| c.url != "https://pinned"));
+        assert!(changes.contains(&EndpointChange {
+            url: "https://fast".into(),
+            kind: ChangeKind::Priority { from: 3, to: 6 },
+        }));
+        assert!(changes.contains(&EndpointChange {
+            url: "https://slow".into(),
+            kind: ChangeKind::Priority { from: 9, to: 1 },
+        }));
+        assert!(changes.contains(&EndpointChange {
+            url: "https://polygon".into(),
+            kind: ChangeKind::Priority { from: 5, to: 10 },
+        }));
+    }
+
+    #[test]
+    fn test_disable_and_reenable() {
+        let mut endpoints = vec![
+            endpoint("https://down", 5),
+            endpoint("https://manual", 5),
+            endpoint("https://up", 10),
+        ];
+        endpoints[1].enabled = false;
+        let policy = MonitorPolicy {
+            failure_threshold: 2,
+            min_samples: 10,
+            ..Default::default()
+        };
+
+        let mut state = state_with(&[("https://down", &[Some(50), None])]);
+        assert!(plan_changes(&endpoints, &state, &policy).is_empty());
+
+        state.record("https://down", ProbeSample::failure(3, "timeout"));
+        // Manually disabled endpoints are not probed or re-enabled
+        assert!(!should_probe(&endpoints[1], &state));
+        state.record("https://manual", ProbeSample::success(3, 10));
+        let changes = plan_changes(&endpoints, &state, &policy);
+        assert_eq!(
+            changes,
+            vec![EndpointChange {
+                url: "https://down".into(),
+                kind: ChangeKind::Disable {
+                    consecutive_failures: 2
+                },
+            }]
+        );
+
+        apply_changes(&mut endpoints, &mut state, &changes);
+        assert!(!endpoints[0].enabled);
+        assert!(should_probe(&endpoints[0], &state));
+
+        state.record("https://down", ProbeSample::success(4, 80));
+        let changes = plan_changes(&endpoints, &state, &policy);
+        assert_eq!(changes[0].kind, ChangeKind::Enable);
+        apply_changes(&mut endpoints, &mut state, &changes);
+        assert!(endpoints[0].enabled);
+        assert!(!state.history("https://down").unwrap().disabled_by_monitor);
+    }
+
+    #[test]
+    fn test_changelog_entry() {
+        let changes = vec![
+            EndpointChange {
+                url: "https://a".into(),
+                kind: ChangeKind::Priority { from: 5, to: 8 },
+            },
+            EndpointChange {
+                url: "https://b".into(),
+                kind: ChangeKind::Disable {
+                    consecutive_failures: 3,
+                },
+            },
+        ];
+        assert_eq!(
+            changelog_entry(1_700_000_000, &changes),
+            "2023-11-14T22:13:20Z https://a: priority 5 -> 8\n\
+             2023-11-14T22:13:20Z https://b: disable (3 consecutive failed probes)\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_monitor_loop_with_simulated_probes() {
+        let mut store = MemoryStore {
+            endpoints: vec![endpoint("https://a", 5), endpoint("https://b", 5)],
+            ..Default::default()
+        };
+        let prober = ScriptedProber::new(&[
+            ("https://a", &[Some(50), Some(50), Some(50), Some(50)]),
+            ("https://b", &[Some(200), None, None, Some(100)]),
+        ]);
+        let mut monitor = EndpointMonitor::new(
+            MonitorState::default(),
+            MonitorPolicy {
+                failure_threshold: 2,
+                ..Default::default()
+            },
+        );
+        let options = MonitorOptions {
+            interval: Duration::from_millis(1),
+            max_cycles: Some(4),
+            apply: true,
+        };
+
+        let mut reports = Vec::new();
+        monitor
+            .run(&prober, &mut store, &options, |r| reports.push(r.clone()))
+            .await
+            .unwrap();
+
+        assert_eq!(reports.len(), 4);
+        assert_eq!(
+            reports.iter().map(|r| r.failed).collect::<Vec<_>>(),
+            vec![0, 1, 1, 0]
+        );
+        // Cycle 1: not enough samples to tune yet
+        assert!(reports[0].changes.is_empty());
+        // Cycle 2: a ranks above b
+        assert!(reports[1].applied);
+        // Cycle 3: b fails twice in a row and is disabled
+        assert!(reports[2]
+            .changes
+            .iter()
+            .any(|c| c.url == "https://b" && matches!(c.kind, ChangeKind::Disable { .. })));
+        // Cycle 4: b recovers and is re-enabled
+        assert!(reports[3]
+            .changes
+            .iter()
+            .any(|c| c.url == "https://b" && c.kind == ChangeKind::Enable));
+
+        assert!(store.endpoints.iter().all(|e| e.enabled));
+        assert_eq!(store.endpoints[0].priority, 10);
+        assert!(store.changelog.contains("https://b: disable"));
+        assert!(store.changelog.contains("https://b: re-enable"));
+    }
+
+    #[tokio::test]
+    async fn test_monitor_without_apply_leaves_store_untouched() {
+        let mut store = MemoryStore {
+            endpoints: vec![endpoint("https://a", 5)],
+            ..Default::default()
+        };
+        let prober = ScriptedProber::new(&[("https://a", &[None, None, None])]);
+        let mut monitor = EndpointMonitor::new(MonitorState::default(), MonitorPolicy::default());
+        let options = MonitorOptions {
+            interval: Duration::ZERO,
+            max_cycles: Some(3),
+            apply: false,
+        };
+
+        let mut last = None;
+        monitor
+            .run(&prober, &mut store, &options, |r| last = Some(r.clone()))
+            .await
+            .unwrap();
+
+        let last = last.unwrap();
+        assert!(!last.applied);
+        assert_eq!(
+            last.changes[0].kind,
+            ChangeKind::Disable {
+                consecutive_failures: 3
+            }
+        );
+        assert!(store.endpoints[0].enabled);
+        assert!(store.changelog.is_empty());
+    }
+}
@@ -3,6 +3,7 @@
 //! Uses the Multicall3 contract (0xcA11bde05977b3631167028862bE2a173976CA11)
 //! which is deployed on all major EVM chains at the same address.
 
+use alloy::eips::BlockId;
 use alloy::primitives::{Address, Bytes, U256};
 use alloy::providers::Provider;
 use alloy::sol;
@@ -38,12 +39,19 @@ sol! {
 #[derive(Debug, Default)]
 pub struct MulticallBuilder {
     calls: Vec<Call3>,
+    block: Option<BlockId>,
 }
 
 impl MulticallBuilder {
     /// Create a new multicall builder
     pub fn new() -> Self {
-        Self { calls: Vec::new() }
+        Self::default()
+    }
+
+    /// Execute against a specific block instead of latest
+    pub fn at_block(mut self, block: BlockId) -> Self {
+        self.block = Some(block);
+        self
     }
 
     /// Add a call to the batch
@@ -104,7 +112,12 @@ impl MulticallBuilder {
         while attempts < max_attempts {
             attempts += 1;
 
-            match provider.call(tx.clone()).await {
+            let mut call = provider.call(tx.clone());
+            if let Some(block) = self.block {
+                call = call.block(block);
+            }
+
+            match call.await {
                 Ok(result) => {
                     // Decode the results
                     let decoded: Vec<Result> = aggregate3Call::abi_decode_returns(&result)
@@ -170,6 +183,30 @@ impl MulticallResult {
         Some(U256::from_be_slice(&self.data[..32]))
     }
 
+    /// Try to decode as a strict ABI bool (for supportsInterface)
+    ///
+    /// Anything other than a 32-byte word holding 0 or 1 is rejected, so
+    /// contracts whose fallback returns arbitrary data are not mistaken for
+    /// answering `true`.
+    pub fn decode_bool(&self) -> Option<bool> {
+        if !self.success || self.data.len() != 32 {
+            return None;
+        }
+        match U256::from_be_slice(&self.data) {
+            v if v.is_zero() => Some(false),
+            v if v == U256::from(1) => Some(true),
+            _ => None,
+        }
+    }
+
+    /// Try to decode as an address (for ownerOf)
+    pub fn decode_address(&self) -> Option<Address> {
+        if !self.success || self.data.len() < 32 || self.data[..12].iter().any(|b| *b != 0) {
+            return None;
+        }
+        Some(Address::from_slice(&self.data[12..32]))
+    }
+
     /// Try to decode as uint8 (for decimals)
     pub fn decode_uint8(&self) -> Option<u8> {
         if !self.success || self.data.is_empty() {
@@ -219,7 +256,22 @@ impl MulticallResult {
 
 /// Common function selectors
 pub mod selectors {
-    use alloy::primitives::{Address, Bytes};
+    use alloy::primitives::{Address, Bytes, U256};
+
+    /// Selector followed by static 32-byte argument words
+    fn with_word(selector: [u8; 4], words: &[[u8; 32]]) -> Bytes {
+        let mut data = selector.to_vec();
+        for word in words {
+            data.extend_from_slice(word);
+        }
+        Bytes::from(data)
+    }
+
+    fn address_word(address: Address) -> [u8; 32] {
+        let mut word = [0u8; 32];
+        word[12..].copy_from_slice(address.as_slice());
+        word
+    }
 
     /// name() selector: 0x06fdde03
     pub fn name() -> Bytes {
@@ -249,6 +301,49 @@ pub mod selectors {
         Bytes::from(data)
     }
 
+    /// allowance(address,address) selector: 0xdd62ed3e
+    pub fn allowance(owner: Address, spender: Address) -> Bytes {
+        with_word(
+            [0xdd, 0x62, 0xed, 0x3e],
+            &[address_word(owner), address_word(spender)],
+        )
+    }
+
+    /// balanceOf(address,uint256) selector (ERC-1155): 0x00fdd58e
+    pub fn balance_of_id(address: Address, id: U256) -> Bytes {
+        with_word(
+            [0x00, 0xfd, 0xd5, 0x8e],
+            &[address_word(address), id.to_be_bytes()],
+        )
+    }
+
+    /// ownerOf(uint256) selector (ERC-721): 0x6352211e
+    pub fn owner_of(id: U256) -> Bytes {
+        with_word([0x63, 0x52, 0x21, 0x1e], &[id.to_be_bytes()])
+    }
+
+    /// supportsInterface(bytes4) selector (ERC-165): 0x01ffc9a7
+    pub fn supports_interface(interface_id: [u8; 4]) -> Bytes {
+        let mut word = [0u8; 32];
+        word[..4].copy_from_slice(&interface_id);
+        with_word([0x01, 0xff, 0xc9, 0xa7], &[word])
+    }
+
+    /// uri(uint256) selector (ERC-1155 metadata): 0x0e89341c
+    pub fn uri(id: U256) -> Bytes {
+        with_word([0x0e, 0x89, 0x34, 0x1c], &[id.to_be_bytes()])
+    }
+
+    /// baseURI() selector (common ERC-721 extension): 0x6c0360eb
+    pub fn base_uri() -> Bytes {
+        Bytes::from(vec![0x6c, 0x03, 0x60, 0xeb])
+    }
+
+    /// contractURI() selector (OpenSea collection metadata): 0xe8a3d485
+    pub fn contract_uri() -> Bytes {
+        Bytes::from(vec![0xe8, 0xa3, 0xd4, 0x85])
+    }
+
     /// Multicall3's getEthBalance(address) selector: 0x4d2301cc
     pub fn get_eth_balance(address: Address) -> Bytes {
         let mut data = vec![0x4d, 0x23, 0x01, 0xcc];
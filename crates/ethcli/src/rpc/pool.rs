@@ -575,6 +575,21 @@ impl RpcPool {
             .unwrap_or(DEFAULT_MAX_BLOCK_RANGE)
     }
 
+    /// URL, effective max block range and max logs of every endpoint
+    pub fn endpoint_limits(&self) -> Vec<(&str, u64, usize)> {
+        self.endpoints
+            .iter()
+            .map(|e| {
+                (
+                    e.url(),
+                    self.health
+                        .effective_max_block_range(e.url(), e.max_block_range()),
+                    e.max_logs(),
+                )
+            })
+            .collect()
+    }
+
     /// Get health info for all endpoints
     pub fn get_endpoint_health(&self) -> Vec<(String, u8, Option<EndpointHealth>)> {
         self.endpoints
@@ -0,0 +1,63 @@
+//! JSON-RPC calls by method name.
+//!
+//! [`RawRpc`] wraps an Alloy root provider for node-specific methods that the
+//! typed [`Provider`] API does not cover (`debug_*`, `anvil_*`, `evm_*`).
+
+use crate::error::{sanitize_error_message, Result, RpcError};
+use alloy::providers::{Provider, RootProvider};
+use serde_json::Value;
+
+/// Untyped JSON-RPC client for one endpoint
+#[derive(Clone)]
+pub struct RawRpc {
+    provider: RootProvider,
+}
+
+impl RawRpc {
+    /// Create a client for `url`
+    pub fn new(url: &str) -> Result<Self> {
+        let url: reqwest::Url = url.parse().map_err(|e| {
+            RpcError::ConnectionFailed(format!(
+                "Invalid URL {}: {}",
+                sanitize_error_message(url),
+                e
+            ))
+        })?;
+        Ok(Self {
+            provider: RootProvider::new_http(url),
+        })
+    }
+
+    /// Call `method` and return its result, failing on a JSON-RPC error
+    pub async fn request(&self, method: &str, params: Value) -> Result<Value> {
+        self.try_request(method, params)
+            .await?
+            .map_err(|message| RpcError::Provider(format!("{} failed: {}", method, message)).into())
+    }
+
+    /// Call `method`, keeping a JSON-RPC error response apart from a
+    /// transport failure
+    ///
+    /// The outer error is the transport failing; the inner one is the
+    /// node's error message, e.g. a revert during gas estimation.
+    pub async fn try_request(
+        &self,
+        method: &str,
+        params: Value,
+    ) -> Result<std::result::Result<Value, String>> {
+        match self
+            .provider
+            .raw_request::<_, Value>(method.to_string().into(), params)
+            .await
+        {
+            Ok(result) => Ok(Ok(result)),
+            Err(e) => match e.as_error_resp() {
+                Some(payload) => Ok(Err(payload.message.to_string())),
+                None => Err(RpcError::Provider(
+                    sanitize_error_message(&e.to_string()).into_owned(),
+                )
+                .into()),
+            },
+        }
+    }
+}
@@ -32,7 +32,7 @@ fn select_endpoint_config(config: &ConfigFile, chain: Chain) -> anyhow::Result<E
     }
 
     // Sort by priority (higher priority first)
-    chain_endpoints.sort_by(|a, b| b.priority.cmp(&a.priority));
+    chain_endpoints.sort_by_key(|e| std::cmp::Reverse(e.priority));
 
     // Get all endpoints with the highest priority
     let top_priority = chain_endpoints[0].priority;
@@ -0,0 +1,744 @@
+//! Verified source comparison
+//!
+//! Compares two verified source bundles (all files plus compiler settings
+//! and constructor arguments), for `contract diff`. Sources are normalized
+//! first so that line endings and embedded metadata hashes don't show up
+//! as differences.
+
+use foundry_block_explorers::contract::Metadata;
+use regex::Regex;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::LazyLock;
+
+/// IPFS and Swarm metadata hashes (`ipfs://Qm...`, `bzzr://...`)
+static METADATA_HASH: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(ipfs://|dweb:/ipfs/)[1-9A-HJ-NP-Za-km-z]{44,}|(bzzr|bzz-raw)://[0-9a-fA-F]{64}")
+        .expect("valid regex")
+});
+
+/// SPDX license and version pragma lines (Solidity and Vyper)
+static LICENSE_OR_PRAGMA: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^\s*(//\s*SPDX-License-Identifier:.*|pragma\s+(solidity|abicoder|experimental)\b[^;]*;\s*|#\s*(@version|pragma\s+version)\b.*)$",
+    )
+    .expect("valid regex")
+});
+
+/// A contract's verified sources and build settings
+#[derive(Debug, Clone, Default)]
+pub struct SourceBundle {
+    /// Contract name
+    pub contract_name: String,
+    /// Source files by path
+    pub files: BTreeMap<String, String>,
+    /// Settings compared alongside the sources, by name
+    pub settings: BTreeMap<String, String>,
+}
+
+impl SourceBundle {
+    /// Build a bundle from Etherscan `getsourcecode` metadata
+    ///
+    /// Single-file and flattened sources are keyed by contract name.
+    /// Compiler settings from standard-JSON input are flattened to dotted
+    /// keys (`settings.optimizer.runs`); `outputSelection` is left out as it
+    /// does not affect the bytecode.
+    pub fn from_metadata(metadata: &Metadata) -> Self {
+        let mut files: BTreeMap<String, String> = metadata
+            .sources()
+            .into_iter()
+            .map(|(path, entry)| (normalize_path(&path), entry.content))
+            .collect();
+        if let Some(source) = files.remove("Contract") {
+            let extension = if metadata.is_vyper() { "vy" } else { "sol" };
+            files.insert(format!("{}.{}", metadata.contract_name, extension), source);
+        }
+
+        let mut settings = BTreeMap::new();
+        settings.insert(
+            "compiler_version".to_string(),
+            metadata.compiler_version.clone(),
+        );
+        settings.insert(
+            "optimization_used".to_string(),
+            (metadata.optimization_used == 1).to_string(),
+        );
+        settings.insert("runs".to_string(), metadata.runs.to_string());
+        settings.insert("evm_version".to_string(), metadata.evm_version.clone());
+        settings.insert("libraries".to_string(), metadata.library.clone());
+        settings.insert(
+            "constructor_arguments".to_string(),
+            format!("0x{}", hex::encode(&metadata.constructor_arguments)),
+        );
+        if let Some(value) = metadata.source_code.settings() {
+            flatten_settings("settings", value, &mut settings);
+        }
+
+        Self {
+            contract_name: metadata.contract_name.clone(),
+            files,
+            settings,
+        }
+    }
+}
+
+/// Flatten a JSON settings object into dotted keys
+fn flatten_settings(prefix: &str, value: &serde_json::Value, out: &mut BTreeMap<String, String>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map {
+                if key == "outputSelection" {
+                    continue;
+                }
+                flatten_settings(&format!("{}.{}", prefix, key), value, out);
+            }
+        }
+        serde_json::Value::Null => {}
+        serde_json::Value::String(s) => {
+            out.insert(prefix.to_string(), s.clone());
+        }
+        other => {
+            out.insert(prefix.to_string(), other.to_string());
+        }
+    }
+}
+
+/// `contracts/A.sol`, `/contracts/A.sol` and `./contracts\A.sol` are the
+/// same path
+fn normalize_path(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let mut path = path.as_str();
+    loop {
+        if let Some(rest) = path.strip_prefix("./") {
+            path = rest;
+        } else if let Some(rest) = path.strip_prefix('/') {
+            path = rest;
+        } else {
+            break;
+        }
+    }
+    path.to_string()
+}
+
+/// What to ignore when comparing sources
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NormalizeOptions {
+    /// Treat CRLF and CR as LF, and ignore trailing blank lines
+    pub line_endings: bool,
+    /// Replace IPFS/Swarm metadata hashes with a placeholder
+    pub metadata_hashes: bool,
+    /// Drop SPDX license lines and `pragma solidity/abicoder/experimental`
+    /// (or Vyper `# @version`) lines
+    pub license_and_pragma: bool,
+}
+
+impl Default for NormalizeOptions {
+    fn default() -> Self {
+        Self {
+            line_endings: true,
+            metadata_hashes: true,
+            license_and_pragma: false,
+        }
+    }
+}
+
+impl NormalizeOptions {
+    /// Compare sources byte for byte
+    pub fn raw() -> Self {
+        Self {
+            line_endings: false,
+            metadata_hashes: false,
+            license_and_pragma: false,
+        }
+    }
+}
+
+/// Normalize a source file for comparison
+pub fn normalize_source(source: &str, options: &NormalizeOptions) -> String {
+    let mut out = if options.line_endings {
+        let mut s = source.replace("\r\n", "\n").replace('\r', "\n");
+        let trimmed = s.trim_end_matches('\n').len();
+        s.truncate(trimmed);
+        s.push('\n');
+        s
+    } else {
+        source.to_string()
+    };
+    if options.metadata_hashes {
+        out = METADATA_HASH
+            .replace_all(&out, |caps: &regex::Captures<'_>| {
+                let scheme = caps.get(1).or(caps.get(2)).map_or("", |m| m.as_str());
+                if scheme.ends_with('/') {
+                    format!("{}<hash>", scheme)
+                } else {
+                    format!("{}://<hash>", scheme)
+                }
+            })
+            .into_owned();
+    }
+    if options.license_and_pragma {
+        out = out
+            .split_inclusive('\n')
+            .filter(|line| !LICENSE_OR_PRAGMA.is_match(line.trim_end_matches(['\r', '\n'])))
+            .collect();
+    }
+    out
+}
+
+/// How files of the two bundles are paired up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum MatchMode {
+    /// Pair files with the same path
+    #[default]
+    Path,
+    /// Pair files with identical normalized content first (catches moved
+    /// files), then by path, then by file name where it is unique
+    Content,
+}
+
+/// Options for [`diff_bundles`]
+#[derive(Debug, Clone, Copy)]
+pub struct DiffOptions {
+    pub normalize: NormalizeOptions,
+    pub match_mode: MatchMode,
+    /// Context lines in unified diffs
+    pub context: usize,
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self {
+            normalize: NormalizeOptions::default(),
+            match_mode: MatchMode::Path,
+            context: 3,
+        }
+    }
+}
+
+/// Comparison result of one file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileStatus {
+    Identical,
+    Differs,
+    OnlyInA,
+    OnlyInB,
+}
+
+/// A file in either bundle and how it compares
+#[derive(Debug, Clone, Serialize)]
+pub struct FileDiff {
+    /// Path in bundle A
+    pub path_a: Option<String>,
+    /// Path in bundle B
+    pub path_b: Option<String>,
+    pub status: FileStatus,
+    /// Unified diff of the normalized sources (differing files only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
+}
+
+/// A compiler setting or constructor argument that differs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SettingDiff {
+    pub setting: String,
+    /// Value in A (`None` if A doesn't have the setting)
+    pub a: Option<String>,
+    /// Value in B
+    pub b: Option<String>,
+}
+
+/// Differences between two source bundles
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SourceDiff {
+    /// Every file, matched pairs first in path order
+    pub files: Vec<FileDiff>,
+    /// Differing settings, by name
+    pub settings: Vec<SettingDiff>,
+}
+
+impl SourceDiff {
+    fn count(&self, status: FileStatus) -> usize {
+        self.files.iter().filter(|f| f.status == status).count()
+    }
+
+    /// Files with the same normalized source
+    pub fn identical_files(&self) -> usize {
+        self.count(FileStatus::Identical)
+    }
+
+    /// Matched files whose sources differ
+    pub fn differing_files(&self) -> usize {
+        self.count(FileStatus::Differs)
+    }
+
+    /// Files only in A
+    pub fn only_in_a(&self) -> usize {
+        self.count(FileStatus::OnlyInA)
+    }
+
+    /// Files only in B
+    pub fn only_in_b(&self) -> usize {
+        self.count(FileStatus::OnlyInB)
+    }
+
+    /// Whether sources and settings all match
+    pub fn is_identical(&self) -> bool {
+        self.settings.is_empty() && self.files.iter().all(|f| f.status == FileStatus::Identical)
+    }
+
+    /// One-line summary, e.g. `2 files differ, 1 only in A, 3 settings differ`
+    pub fn summary(&self) -> String {
+        if self.is_identical() {
+            return format!("identical ({} files)", self.files.len());
+        }
+        let mut parts = Vec::new();
+        let plural =
+            |n: usize, one: &str, many: &str| format!("{} {}", n, if n == 1 { one } else { many });
+        if self.differing_files() > 0 {
+            parts.push(plural(
+                self.differing_files(),
+                "file differs",
+                "files differ",
+            ));
+        }
+        if self.only_in_a() > 0 {
+            parts.push(format!("{} only in A", self.only_in_a()));
+        }
+        if self.only_in_b() > 0 {
+            parts.push(format!("{} only in B", self.only_in_b()));
+        }
+        if !self.settings.is_empty() {
+            parts.push(plural(
+                self.settings.len(),
+                "setting differs",
+                "settings differ",
+            ));
+        }
+        parts.join(", ")
+    }
+}
+
+/// Compare two source bundles
+pub fn diff_bundles(a: &SourceBundle, b: &SourceBundle, options: &DiffOptions) -> SourceDiff {
+    let normalize = |files: &BTreeMap<String, String>| -> BTreeMap<String, String> {
+        files
+            .iter()
+            .map(|(path, source)| (path.clone(), normalize_source(source, &options.normalize)))
+            .collect()
+    };
+    let files_a = normalize(&a.files);
+    let files_b = normalize(&b.files);
+    let pairs = match_files(&files_a, &files_b, options.match_mode);
+
+    let mut files = Vec::new();
+    for (path_a, path_b) in &pairs {
+        let (source_a, source_b) = (&files_a[path_a], &files_b[path_b]);
+        let (status, diff) = if source_a == source_b {
+            (FileStatus::Identical, None)
+        } else {
+            (
+                FileStatus::Differs,
+                Some(unified_diff(
+                    path_a,
+                    path_b,
+                    source_a,
+                    source_b,
+                    options.context,
+                )),
+            )
+        };
+        files.push(FileDiff {
+            path_a: Some(path_a.clone()),
+            path_b: Some(path_b.clone()),
+            status,
+            diff,
+        });
+    }
+    let matched_a: BTreeSet<&String> = pairs.iter().map(|(a, _)| a).collect();
+    let matched_b: BTreeSet<&String> = pairs.iter().map(|(_, b)| b).collect();
+    files.extend(
+        files_a
+            .keys()
+            .filter(|p| !matched_a.contains(p))
+            .map(|p| FileDiff {
+                path_a: Some(p.clone()),
+                path_b: None,
+                status: FileStatus::OnlyInA,
+                diff: None,
+            }),
+    );
+    files.extend(
+        files_b
+            .keys()
+            .filter(|p| !matched_b.contains(p))
+            .map(|p| FileDiff {
+                path_a: None,
+                path_b: Some(p.clone()),
+                status: FileStatus::OnlyInB,
+                diff: None,
+            }),
+    );
+
+    let names: BTreeSet<&String> = a.settings.keys().chain(b.settings.keys()).collect();
+    let settings = names
+        .into_iter()
+        .filter(|name| a.settings.get(*name) != b.settings.get(*name))
+        .map(|name| SettingDiff {
+            setting: name.clone(),
+            a: a.settings.get(name).cloned(),
+            b: b.settings.get(name).cloned(),
+        })
+        .collect();
+
+    SourceDiff { files, settings }
+}
+
+/// Pair file paths of A and B, sorted by A's path
+fn match_files(
+    a: &BTreeMap<String, String>,
+    b: &BTreeMap<String, String>,
+    mode: MatchMode,
+) -> Vec<(String, String)> {
+    let mut pairs = Vec::new();
+    let mut left_a: BTreeSet<&String> = a.keys().collect();
+    let mut left_b: BTreeSet<&String> = b.keys().collect();
+
+    if mode == MatchMode::Content {
+        let mut by_hash: BTreeMap<[u8; 32], Vec<&String>> = BTreeMap::new();
+        for (path, source) in b {
+            by_hash
+                .entry(Sha256::digest(source.as_bytes()).into())
+                .or_default()
+                .push(path);
+        }
+        for (path, source) in a {
+            let hash: [u8; 32] = Sha256::digest(source.as_bytes()).into();
+            let Some(candidates) = by_hash.get_mut(&hash) else {
+                continue;
+            };
+            // Prefer the same path among identical copies
+            let index = candidates.iter().position(|p| *p == path).unwrap_or(0);
+            if let Some(other) = (!candidates.is_empty()).then(|| candidates.remove(index)) {
+                pairs.push((path.clone(), other.clone()));
+                left_a.remove(path);
+                left_b.remove(other);
+            }
+        }
+    }
+
+    for path in left_a.clone() {
+        if left_b.remove(path) {
+            left_a.remove(path);
+            pairs.push((path.clone(), path.clone()));
+        }
+    }
+
+    if mode == MatchMode::Content {
+        let unique_names = |paths: &BTreeSet<&String>| -> BTreeMap<String, String> {
+            let mut names: BTreeMap<String, Vec<&String>> = BTreeMap::new();
+            for path in paths {
+                names.entry(file_name(path)).or_default().push(path);
+            }
+            names
+                .into_iter()
+                .filter(|(_, paths)| paths.len() == 1)
+                .map(|(name, paths)| (name, paths[0].clone()))
+                .collect()
+        };
+        let names_b = unique_names(&left_b);
+        for (name, path_a) in unique_names(&left_a) {
+            if let Some(path_b) = names_b.get(&name) {
+                pairs.push((path_a, path_b.clone()));
+            }
+        }
+    }
+
+    pairs.sort();
+    pairs
+}
+
+fn file_name(path: &str) -> String {
+    path.rsplit('/').next().unwrap_or(path).to_string()
+}
+
+/// `git diff`-style unified diff of two normalized sources
+fn unified_diff(path_a: &str, path_b: &str, a: &str, b: &str, context: usize) -> String {
+    let lines_a: Vec<&str> = a.lines().collect();
+    let lines_b: Vec<&str> = b.lines().collect();
+    let hunks = difflib::unified_diff(&lines_a, &lines_b, "", "", "", "", context);
+
+    let mut out = format!("--- a/{}\n+++ b/{}\n", path_a, path_b);
+    // Skip difflib's own header lines
+    for line in hunks.iter().skip(2) {
+        out.push_str(line);
+        if !line.ends_with('\n') {
+            out.push('\n');
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/source_diff");
+
+    fn bundle(name: &str) -> SourceBundle {
+        let json = std::fs::read_to_string(format!("{}/{}", FIXTURES, name)).unwrap();
+        let metadata: Metadata = serde_json::from_str(&json).unwrap();
+        SourceBundle::from_metadata(&metadata)
+    }
+
+    fn status_of<'a>(diff: &'a SourceDiff, path: &str) -> &'a FileDiff {
+        diff.files
+            .iter()
+            .find(|f| f.path_a.as_deref() == Some(path) || f.path_b.as_deref() == Some(path))
+            .unwrap_or_else(|| panic!("{path} not in diff"))
+    }
+
+    #[test]
+    fn test_normalize_line_endings() {
+        let options = NormalizeOptions::default();
+        assert_eq!(
+            normalize_source("a\r\nb\r\n\r\n", &options),
+            normalize_source("a\nb", &options)
+        );
+        assert_eq!(normalize_source("a\rb", &options), "a\nb\n");
+        assert_ne!(
+            normalize_source("a\r\nb", &NormalizeOptions::raw()),
+            normalize_source("a\nb", &NormalizeOptions::raw())
+        );
+    }
+
+    #[test]
+    fn test_normalize_metadata_hashes() {
+        let options = NormalizeOptions::default();
+        let a = "// ipfs://QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG\n\
+                 // dweb:/ipfs/QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG\n\
+                 // bzzr://0000000000000000000000000000000000000000000000000000000000000001\n";
+        let b = "// ipfs://QmPChd2hVbrJ6bfo3WBcTW4iZnpHm8TEzWkLHmLpXhF68A\n\
+                 // dweb:/ipfs/QmPChd2hVbrJ6bfo3WBcTW4iZnpHm8TEzWkLHmLpXhF68A\n\
+                 // bzzr://00000000000000000000000000000000000000000000000000000000000000ff\n";
+        assert_eq!(normalize_source(a, &options), normalize_source(b, &options));
+        assert_eq!(
+            normalize_source(a, &options),
+            "// ipfs://<hash>\n// dweb:/ipfs/<hash>\n// bzzr://<hash>\n"
+        );
+        // Short strings that merely look like a scheme are left alone
+        assert_eq!(normalize_source("ipfs://Qm\n", &options), "ipfs://Qm\n");
+    }
+
+    #[test]
+    fn test_normalize_license_and_pragma() {
+        let a = "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.19;\npragma abicoder v2;\ncontract A {}\n";
+        let b = "// SPDX-License-Identifier: BUSL-1.1\npragma solidity 0.8.20;\ncontract A {}\n";
+        let default = NormalizeOptions::default();
+        assert_ne!(normalize_source(a, &default), normalize_source(b, &default));
+
+        let ignore = NormalizeOptions {
+            license_and_pragma: true,
+            ..default
+        };
+        assert_eq!(normalize_source(a, &ignore), "contract A {}\n");
+        assert_eq!(normalize_source(a, &ignore), normalize_source(b, &ignore));
+        // Vyper version pragmas
+        assert_eq!(
+            normalize_source("# @version 0.3.10\nx: uint256\n", &ignore),
+            "x: uint256\n"
+        );
+        // Pragmas in the middle of a line are code
+        assert_eq!(
+            normalize_source("string s = \"pragma solidity 0.8.0;\";\n", &ignore),
+            "string s = \"pragma solidity 0.8.0;\";\n"
+        );
+    }
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(normalize_path("/contracts/A.sol"), "contracts/A.sol");
+        assert_eq!(normalize_path("./contracts\\A.sol"), "contracts/A.sol");
+        assert_eq!(normalize_path("@oz/token/ERC20.sol"), "@oz/token/ERC20.sol");
+    }
+
+    #[test]
+    fn test_bundle_from_metadata() {
+        let a = bundle("vault_ethereum.json");
+        assert_eq!(a.contract_name, "Vault");
+        assert_eq!(
+            a.files.keys().collect::<Vec<_>>(),
+            vec![
+                "@openzeppelin/contracts/token/ERC20/IERC20.sol",
+                "contracts/Vault.sol",
+                "contracts/lib/FeeMath.sol",
+                "contracts/lib/Roles.sol",
+            ]
+        );
+        assert_eq!(a.settings["runs"], "200");
+        assert_eq!(a.settings["settings.optimizer.runs"], "200");
+        assert_eq!(a.settings["settings.viaIR"], "false");
+        assert!(!a.settings.keys().any(|k| k.contains("outputSelection")));
+
+        let flat = bundle("flat.json");
+        assert_eq!(flat.files.keys().collect::<Vec<_>>(), vec!["Greeter.sol"]);
+        assert_eq!(flat.settings["optimization_used"], "false");
+    }
+
+    #[test]
+    fn test_diff_by_path() {
+        let diff = diff_bundles(
+            &bundle("vault_ethereum.json"),
+            &bundle("vault_base.json"),
+            &DiffOptions::default(),
+        );
+
+        // CRLF vs LF and a different metadata hash only
+        assert_eq!(
+            status_of(&diff, "contracts/Vault.sol").status,
+            FileStatus::Identical
+        );
+        assert_eq!(
+            status_of(&diff, "@openzeppelin/contracts/token/ERC20/IERC20.sol").status,
+            FileStatus::Identical
+        );
+        // Only the license line and pragma differ
+        assert_eq!(
+            status_of(&diff, "contracts/lib/Roles.sol").status,
+            FileStatus::Differs
+        );
+        // A real change
+        let fee = status_of(&diff, "contracts/lib/FeeMath.sol");
+        assert_eq!(fee.status, FileStatus::Differs);
+        let patch = fee.diff.as_deref().unwrap();
+        assert!(patch
+            .starts_with("--- a/contracts/lib/FeeMath.sol\n+++ b/contracts/lib/FeeMath.sol\n@@"));
+        assert!(patch.contains("-    uint256 internal constant MAX_FEE = 1000;\n"));
+        assert!(patch.contains("+    uint256 internal constant MAX_FEE = 5000;\n"));
+        // Moved on B: unmatched by path
+        assert_eq!(
+            status_of(&diff, "contracts/Registry.sol").status,
+            FileStatus::OnlyInB
+        );
+        assert_eq!(
+            status_of(&diff, "src/utils/Pausable.sol").status,
+            FileStatus::OnlyInB
+        );
+        assert_eq!(diff.only_in_a(), 0);
+
+        let settings: Vec<&str> = diff.settings.iter().map(|s| s.setting.as_str()).collect();
+        assert_eq!(
+            settings,
+            vec!["constructor_arguments", "runs", "settings.optimizer.runs"]
+        );
+        assert_eq!(diff.settings[1].a.as_deref(), Some("200"));
+        assert_eq!(diff.settings[1].b.as_deref(), Some("1000"));
+        assert!(!diff.is_identical());
+        assert_eq!(
+            diff.summary(),
+            "2 files differ, 2 only in B, 3 settings differ"
+        );
+    }
+
+    #[test]
+    fn test_diff_ignoring_license_and_pragma() {
+        let options = DiffOptions {
+            normalize: NormalizeOptions {
+                license_and_pragma: true,
+                ..NormalizeOptions::default()
+            },
+            ..DiffOptions::default()
+        };
+        let diff = diff_bundles(
+            &bundle("vault_ethereum.json"),
+            &bundle("vault_base.json"),
+            &options,
+        );
+        assert_eq!(
+            status_of(&diff, "contracts/lib/Roles.sol").status,
+            FileStatus::Identical
+        );
+        assert_eq!(diff.differing_files(), 1);
+    }
+
+    #[test]
+    fn test_diff_by_content() {
+        let mut a = bundle("vault_ethereum.json");
+        // A has a Pausable under another path, identical to B's after normalization
+        a.files.insert(
+            "contracts/security/Pausable.sol".to_string(),
+            bundle("vault_base.json").files["src/utils/Pausable.sol"].replace('\n', "\r\n"),
+        );
+        a.files.insert(
+            "contracts/Registry.sol".to_string(),
+            "contract Registry {}\n".to_string(),
+        );
+        let b = bundle("vault_base.json");
+
+        let by_path = diff_bundles(&a, &b, &DiffOptions::default());
+        assert_eq!(by_path.only_in_a(), 1);
+        assert_eq!(by_path.only_in_b(), 1);
+
+        let by_content = diff_bundles(
+            &a,
+            &b,
+            &DiffOptions {
+                match_mode: MatchMode::Content,
+                ..DiffOptions::default()
+            },
+        );
+        let pausable = status_of(&by_content, "src/utils/Pausable.sol");
+        assert_eq!(
+            pausable.path_a.as_deref(),
+            Some("contracts/security/Pausable.sol")
+        );
+        assert_eq!(pausable.status, FileStatus::Identical);
+        // Same path, different content: still paired by path
+        assert_eq!(
+            status_of(&by_content, "contracts/Registry.sol").status,
+            FileStatus::Differs
+        );
+        assert_eq!(by_content.only_in_a() + by_content.only_in_b(), 0);
+    }
+
+    #[test]
+    fn test_content_mode_pairs_unique_file_names() {
+        let files = |entries: &[(&str, &str)]| SourceBundle {
+            files: entries
+                .iter()
+                .map(|(p, s)| (p.to_string(), s.to_string()))
+                .collect(),
+            ..SourceBundle::default()
+        };
+        let a = files(&[
+            ("contracts/Token.sol", "v1\n"),
+            ("a/Lib.sol", "x\n"),
+            ("b/Lib.sol", "y\n"),
+        ]);
+        let b = files(&[("src/Token.sol", "v2\n"), ("c/Lib.sol", "z\n")]);
+        let diff = diff_bundles(
+            &a,
+            &b,
+            &DiffOptions {
+                match_mode: MatchMode::Content,
+                ..DiffOptions::default()
+            },
+        );
+        // Token.sol is unique on both sides; Lib.sol is ambiguous in A
+        let token = status_of(&diff, "src/Token.sol");
+        assert_eq!(token.path_a.as_deref(), Some("contracts/Token.sol"));
+        assert_eq!(token.status, FileStatus::Differs);
+        assert_eq!(diff.only_in_a(), 2);
+        assert_eq!(diff.only_in_b(), 1);
+    }
+
+    #[test]
+    fn test_identical_bundles() {
+        let a = bundle("vault_ethereum.json");
+        let diff = diff_bundles(&a, &a.clone(), &DiffOptions::default());
+        assert!(diff.is_identical());
+        assert_eq!(diff.summary(), "identical (4 files)");
+        assert!(diff.files.iter().all(|f| f.diff.is_none()));
+    }
+}
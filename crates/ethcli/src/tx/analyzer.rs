@@ -2,77 +2,24 @@
 //!
 //! Coordinates fetching and analyzing Ethereum transactions.
 
-use crate::abi::AbiFetcher;
-use crate::config::Chain;
+use crate::abi::{decode_function_params, AbiFetcher, DecodedValue, LogDecoder};
+use crate::config::{AddressBook, Chain};
 use crate::error::Result;
 use crate::rpc::RpcPool;
 use crate::tx::addresses::{events, get_contract_info, get_label};
 use crate::tx::flow::parse_transfers;
 use crate::tx::types::{
     AnalyzedEvent, ContractCategory, ContractInfo, EventParam, FunctionCall, FunctionParam,
-    RawTxData, TransactionAnalysis,
+    LabelSource, RawTxData, TransactionAnalysis, Warning, WarningKind,
 };
 use alloy::consensus::Transaction as TxTrait;
-use alloy::primitives::U256;
+use alloy::json_abi::JsonAbi;
 use alloy::primitives::{Address, B256};
 use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 use std::sync::Arc;
 
-/// Convert U256 to f64 with ETH decimals (HIGH-001 fix)
-///
-/// Handles arbitrarily large values by:
-/// 1. Using u128 conversion when possible for precision
-/// 2. Falling back to string conversion for very large values
-/// 3. Saturating at f64::MAX to prevent overflow/panic
-fn u256_to_eth_f64(value: &U256) -> f64 {
-    const ETH_SCALE: f64 = 1e18;
-
-    // Try u128 first for better precision (covers values up to ~340 undecillion wei)
-    if let Ok(as_u128) = TryInto::<u128>::try_into(*value) {
-        return (as_u128 as f64) / ETH_SCALE;
-    }
-
-    // For values > u128::MAX, convert via string (loses precision but handles any size)
-    let s = value.to_string();
-    match s.parse::<f64>() {
-        Ok(v) if v.is_finite() => v / ETH_SCALE,
-        _ => f64::MAX, // Saturate on overflow or parse failure
-    }
-}
-
-/// Format token amount with decimals (LOW-002 fix)
-///
-/// Safely formats large token amounts without panicking on edge cases.
-/// Returns a human-readable string with 2 decimal places.
-fn format_token_amount(amount_str: &str, decimals: usize) -> String {
-    // Handle empty or non-numeric input
-    if amount_str.is_empty() || !amount_str.chars().all(|c| c.is_ascii_digit()) {
-        return "0.00".to_string();
-    }
-
-    if amount_str.len() > decimals {
-        let int_part = &amount_str[..amount_str.len() - decimals];
-        let frac_part = &amount_str[amount_str.len() - decimals..];
-        // Take first 2 decimal places
-        let frac_display = if frac_part.len() >= 2 {
-            &frac_part[..2]
-        } else {
-            frac_part
-        };
-        format!("{}.{}", int_part, frac_display)
-    } else {
-        // Amount is less than 1 whole token
-        let padding = "0".repeat(decimals - amount_str.len());
-        let padded = format!("{}{}", padding, amount_str);
-        let frac_display = if padded.len() >= 2 {
-            &padded[..2]
-        } else {
-            &padded
-        };
-        format!("0.{}", frac_display)
-    }
-}
+pub use crate::tx::render::format_analysis;
 
 /// Transaction analyzer
 pub struct TxAnalyzer {
@@ -82,6 +29,12 @@ pub struct TxAnalyzer {
     chain: Chain,
     /// ABI fetcher for Etherscan lookups (shared to avoid HTTP client recreation - PERF-002 fix)
     abi_fetcher: Arc<AbiFetcher>,
+    /// User-supplied ABI consulted before Etherscan and 4byte
+    user_abi: Option<Arc<JsonAbi>>,
+    /// Event decoder built from `user_abi`
+    user_decoder: Option<Arc<LogDecoder>>,
+    /// Address book used to label from/to and transfer counterparties
+    address_book: Option<Arc<AddressBook>>,
 }
 
 impl TxAnalyzer {
@@ -91,6 +44,9 @@ impl TxAnalyzer {
             pool,
             chain,
             abi_fetcher: Arc::new(AbiFetcher::new(None)?),
+            user_abi: None,
+            user_decoder: None,
+            address_book: None,
         })
     }
 
@@ -103,9 +59,31 @@ impl TxAnalyzer {
             pool,
             chain,
             abi_fetcher,
+            user_abi: None,
+            user_decoder: None,
+            address_book: None,
         }
     }
 
+    /// Decode function calls and events with a local ABI first
+    ///
+    /// The ABI can come from a JSON file or a human-readable ABI (see
+    /// [`crate::abi::parse_human_abi`]). Items it does not cover still fall
+    /// back to the built-in events, Etherscan and 4byte.
+    pub fn with_abi(mut self, abi: JsonAbi) -> Result<Self> {
+        self.user_decoder = Some(Arc::new(LogDecoder::from_abi(&abi)?));
+        self.user_abi = Some(Arc::new(abi));
+        Ok(self)
+    }
+
+    /// Label addresses in the results with names from an address book
+    ///
+    /// Address book names take precedence over the built-in labels.
+    pub fn with_address_book(mut self, book: AddressBook) -> Self {
+        self.address_book = Some(Arc::new(book));
+        self
+    }
+
     /// Get a reference to the ABI fetcher for sharing with other analyzers
     pub fn abi_fetcher(&self) -> Arc<AbiFetcher> {
         Arc::clone(&self.abi_fetcher)
@@ -137,7 +115,7 @@ impl TxAnalyzer {
         analysis.contracts = self.analyze_contracts(&raw);
 
         // Analyze events
-        analysis.events = self.analyze_events(&raw);
+        analysis.events = self.analyze_events(&raw, &mut analysis.warnings);
 
         // Parse token flows
         analysis.token_flows = parse_transfers(&raw.logs);
@@ -163,12 +141,24 @@ impl TxAnalyzer {
             }
         }
 
+        apply_builtin_labels(&mut analysis);
+
         // Enrich unknown contracts if requested
         if enrich {
-            self.enrich_contracts(&mut analysis.contracts).await;
-            self.enrich_token_flows(&mut analysis.token_flows).await;
+            analysis.enriched = true;
+            self.enrich_contracts(&mut analysis).await;
+            self.enrich_token_flows(&mut analysis).await;
         }
 
+        if let Some(book) = &self.address_book {
+            apply_address_book(&mut analysis, book);
+        }
+
+        analysis.explorer_url = self
+            .chain
+            .explorer_url()
+            .map(|explorer| format!("{}/tx/{:#x}", explorer, analysis.hash));
+
         Ok(analysis)
     }
 
@@ -185,6 +175,10 @@ impl TxAnalyzer {
 
         let selector = format!("0x{}", hex::encode(&input[..4]));
 
+        if let Some(call) = self.decode_with_user_abi(&selector, input) {
+            return Some(call);
+        }
+
         if fetch_abi {
             // Try to decode using Etherscan ABI
             if let Some(decoded) = self
@@ -225,8 +219,28 @@ impl TxAnalyzer {
         })
     }
 
+    /// Decode a function call against the user-supplied ABI
+    fn decode_with_user_abi(&self, selector: &str, input: &[u8]) -> Option<FunctionCall> {
+        let func = self
+            .user_abi
+            .as_ref()?
+            .functions()
+            .find(|f| f.selector().as_slice() == &input[..4])?;
+
+        Some(FunctionCall {
+            selector: selector.to_string(),
+            name: Some(func.name.clone()),
+            signature: Some(func.signature()),
+            params: decode_function_params(func, &input[4..])
+                .into_iter()
+                .map(|(name, ty, value)| FunctionParam { name, ty, value })
+                .collect(),
+        })
+    }
+
     /// Enrich unknown contracts with Etherscan metadata
-    async fn enrich_contracts(&self, contracts: &mut [ContractInfo]) {
+    async fn enrich_contracts(&self, analysis: &mut TransactionAnalysis) {
+        let contracts = &mut analysis.contracts;
         use futures::stream::{self, StreamExt};
 
         // MED-004 fix: Limit concurrency to prevent resource exhaustion
@@ -269,21 +283,36 @@ impl TxAnalyzer {
         .await;
 
         // Apply results
+        let mut labeled = Vec::new();
         for (i, result) in results {
-            if let Ok(metadata) = result {
-                if let Some(name) = metadata.name {
-                    contracts[i].label = Some(name);
-                    // If verified, it's likely a contract (not EOA)
-                    if metadata.is_verified && contracts[i].category == ContractCategory::Unknown {
-                        contracts[i].category = ContractCategory::Protocol;
+            match result {
+                Ok(metadata) => {
+                    if let Some(name) = metadata.name {
+                        labeled.push((contracts[i].address, name.clone()));
+                        contracts[i].label = Some(name);
+                        // If verified, it's likely a contract (not EOA)
+                        if metadata.is_verified
+                            && contracts[i].category == ContractCategory::Unknown
+                        {
+                            contracts[i].category = ContractCategory::Protocol;
+                        }
                     }
                 }
+                Err(e) => analysis.warnings.push(Warning::new(
+                    WarningKind::EnrichmentFailed,
+                    Some(contracts[i].address),
+                    format!("Etherscan contract lookup failed: {e}"),
+                )),
             }
         }
+        for (address, name) in labeled {
+            analysis.record_label(address, name, LabelSource::Etherscan);
+        }
     }
 
     /// Enrich token flows with token metadata
-    async fn enrich_token_flows(&self, flows: &mut [crate::tx::types::TokenFlow]) {
+    async fn enrich_token_flows(&self, analysis: &mut TransactionAnalysis) {
+        let flows = &mut analysis.token_flows;
         use futures::stream::{self, StreamExt};
 
         // MED-004 fix: Limit concurrency to prevent resource exhaustion
@@ -292,7 +321,7 @@ impl TxAnalyzer {
         // Collect unique tokens that need lookup
         let mut tokens_to_lookup: HashSet<Address> = HashSet::new();
         for flow in flows.iter() {
-            if flow.token_label.is_none() {
+            if flow.token_label.is_none() || flow.token_decimals.is_none() {
                 tokens_to_lookup.insert(flow.token);
             }
         }
@@ -320,13 +349,24 @@ impl TxAnalyzer {
         .collect()
         .await;
 
-        // Build symbol map from results
+        // Build symbol and decimals maps from results
         let mut token_symbols: HashMap<Address, String> = HashMap::new();
+        let mut token_decimals: HashMap<Address, u8> = HashMap::new();
         for (token, result) in results {
-            if let Ok(metadata) = result {
-                if let Some(symbol) = metadata.symbol {
-                    token_symbols.insert(token, symbol);
+            match result {
+                Ok(metadata) => {
+                    if let Some(symbol) = metadata.symbol {
+                        token_symbols.insert(token, symbol);
+                    }
+                    if let Some(decimals) = metadata.decimals {
+                        token_decimals.insert(token, decimals);
+                    }
                 }
+                Err(e) => analysis.warnings.push(Warning::new(
+                    WarningKind::EnrichmentFailed,
+                    Some(token),
+                    format!("token metadata lookup failed: {e}"),
+                )),
             }
         }
 
@@ -337,6 +377,14 @@ impl TxAnalyzer {
                     flow.token_label = Some(symbol.clone());
                 }
             }
+            if flow.token_decimals.is_none() {
+                flow.token_decimals = token_decimals.get(&flow.token).copied();
+            }
+        }
+        let mut token_symbols: Vec<_> = token_symbols.into_iter().collect();
+        token_symbols.sort();
+        for (token, symbol) in token_symbols {
+            analysis.record_label(token, symbol, LabelSource::TokenContract);
         }
     }
 
@@ -423,16 +471,39 @@ impl TxAnalyzer {
     }
 
     /// Analyze events in the transaction
-    fn analyze_events(&self, raw: &RawTxData) -> Vec<AnalyzedEvent> {
+    fn analyze_events(&self, raw: &RawTxData, warnings: &mut Vec<Warning>) -> Vec<AnalyzedEvent> {
         raw.logs
             .iter()
             .map(|log| {
                 let topic0 = log.topics().first().copied().unwrap_or_default();
+
+                // Events in the user-supplied ABI take precedence
+                if let Some(decoded) = self
+                    .user_decoder
+                    .as_ref()
+                    .and_then(|decoder| decoder.decode(log).ok())
+                {
+                    return AnalyzedEvent {
+                        log_index: log.log_index.unwrap_or(0),
+                        address: log.address(),
+                        address_label: get_label(&log.address()).map(String::from),
+                        name: Some(decoded.event_name),
+                        signature: Some(decoded.event_signature),
+                        params: decoded
+                            .params
+                            .into_iter()
+                            .map(|(name, value)| (name, event_param_from_decoded(value)))
+                            .collect(),
+                        topic0,
+                        is_transfer: topic0 == events::TRANSFER,
+                    };
+                }
+
                 let event_name = events::get_event_name(&topic0);
                 let is_transfer = topic0 == events::TRANSFER;
 
                 // Decode parameters based on known event signatures
-                let params = self.decode_event_params(log, &topic0);
+                let params = self.decode_event_params(log, &topic0, warnings);
 
                 AnalyzedEvent {
                     log_index: log.log_index.unwrap_or(0),
@@ -465,6 +536,7 @@ impl TxAnalyzer {
         &self,
         log: &alloy::rpc::types::Log,
         topic0: &B256,
+        warnings: &mut Vec<Warning>,
     ) -> HashMap<String, EventParam> {
         // PERF-010 fix: pre-allocate for typical event parameter count (3-4)
         let mut params = HashMap::with_capacity(4);
@@ -481,17 +553,8 @@ impl TxAnalyzer {
             );
             params.insert("to".to_string(), EventParam::Address(format!("{:#x}", to)));
 
-            let data = &log.data().data;
-            if data.len() >= 32 {
-                let value = alloy::primitives::U256::from_be_slice(&data[..32]);
+            if let Some(value) = first_word(log, "Transfer", warnings) {
                 params.insert("value".to_string(), EventParam::Uint(value.to_string()));
-            } else {
-                // LOW-001 fix: Log malformed event data for debugging
-                tracing::debug!(
-                    "Malformed Transfer event data: expected >= 32 bytes, got {} at {:?}",
-                    data.len(),
-                    log.address()
-                );
             }
         } else if *topic0 == events::APPROVAL && topics.len() >= 3 {
             // Approval(address indexed owner, address indexed spender, uint256 value)
@@ -507,16 +570,8 @@ impl TxAnalyzer {
                 EventParam::Address(format!("{:#x}", spender)),
             );
 
-            let data = &log.data().data;
-            if data.len() >= 32 {
-                let value = alloy::primitives::U256::from_be_slice(&data[..32]);
+            if let Some(value) = first_word(log, "Approval", warnings) {
                 params.insert("value".to_string(), EventParam::Uint(value.to_string()));
-            } else {
-                tracing::debug!(
-                    "Malformed Approval event data: expected >= 32 bytes, got {} at {:?}",
-                    data.len(),
-                    log.address()
-                );
             }
         } else if *topic0 == events::DEPOSIT && topics.len() >= 2 {
             // Deposit(address indexed dst, uint256 wad)
@@ -526,16 +581,8 @@ impl TxAnalyzer {
                 EventParam::Address(format!("{:#x}", dst)),
             );
 
-            let data = &log.data().data;
-            if data.len() >= 32 {
-                let wad = alloy::primitives::U256::from_be_slice(&data[..32]);
+            if let Some(wad) = first_word(log, "Deposit", warnings) {
                 params.insert("wad".to_string(), EventParam::Uint(wad.to_string()));
-            } else {
-                tracing::debug!(
-                    "Malformed Deposit event data: expected >= 32 bytes, got {} at {:?}",
-                    data.len(),
-                    log.address()
-                );
             }
         } else if *topic0 == events::WITHDRAWAL && topics.len() >= 2 {
             // Withdrawal(address indexed src, uint256 wad)
@@ -545,16 +592,8 @@ impl TxAnalyzer {
                 EventParam::Address(format!("{:#x}", src)),
             );
 
-            let data = &log.data().data;
-            if data.len() >= 32 {
-                let wad = alloy::primitives::U256::from_be_slice(&data[..32]);
+            if let Some(wad) = first_word(log, "Withdrawal", warnings) {
                 params.insert("wad".to_string(), EventParam::Uint(wad.to_string()));
-            } else {
-                tracing::debug!(
-                    "Malformed Withdrawal event data: expected >= 32 bytes, got {} at {:?}",
-                    data.len(),
-                    log.address()
-                );
             }
         }
 
@@ -562,350 +601,180 @@ impl TxAnalyzer {
     }
 }
 
-/// Format transaction analysis for display
-pub fn format_analysis(analysis: &TransactionAnalysis) -> String {
-    // LOW-005 fix: Pre-allocate for typical output size
-    let mut output = String::with_capacity(2048);
+/// First 32-byte word of a log's data, e.g. the amount of a Transfer
+fn first_word(
+    log: &alloy::rpc::types::Log,
+    event: &str,
+    warnings: &mut Vec<Warning>,
+) -> Option<alloy::primitives::U256> {
+    let data = &log.data().data;
+    if data.len() >= 32 {
+        return Some(alloy::primitives::U256::from_be_slice(&data[..32]));
+    }
+    // LOW-001 fix: Surface malformed event data instead of dropping it silently
+    warnings.push(Warning::new(
+        WarningKind::MalformedEvent,
+        Some(log.address()),
+        format!(
+            "malformed {event} event data: expected >= 32 bytes, got {}",
+            data.len()
+        ),
+    ));
+    None
+}
 
-    // Header
-    output.push_str(&format!("Transaction: {:#x}\n", analysis.hash));
-    output.push_str(&format!("Block: {}\n", analysis.block_number));
-    output.push_str(&format!("From: {:#x}\n", analysis.from));
+/// Convert a value decoded with an ABI into an event parameter
+fn event_param_from_decoded(value: DecodedValue) -> EventParam {
+    match value {
+        DecodedValue::Address(v) => EventParam::Address(v),
+        DecodedValue::Uint(v) => EventParam::Uint(v),
+        DecodedValue::Int(v) => EventParam::Int(v),
+        DecodedValue::Bool(v) => EventParam::Bool(v),
+        DecodedValue::Bytes(v) => EventParam::Bytes(v),
+        DecodedValue::String(v) => EventParam::String(v),
+        DecodedValue::Array(values) | DecodedValue::Tuple(values) => {
+            EventParam::Array(values.into_iter().map(event_param_from_decoded).collect())
+        }
+    }
+}
 
+/// Record built-in labels and fill the `to` label from the built-in table
+///
+/// Runs before enrichment and the address book, which both take precedence.
+pub fn apply_builtin_labels(analysis: &mut TransactionAnalysis) {
     if let Some(to) = analysis.to {
-        let label = get_label(&to)
-            .map(|l| format!(" ({})", l))
-            .unwrap_or_default();
-        output.push_str(&format!("To: {:#x}{}\n", to, label));
-    } else {
-        output.push_str("To: Contract Creation\n");
+        if analysis.to_label.is_none() {
+            analysis.to_label = get_label(&to).map(String::from);
+        }
     }
 
-    // HIGH-001 fix: Use safe U256 to f64 conversion
-    output.push_str(&format!(
-        "Value: {} wei ({:.6} ETH)\n",
-        analysis.value,
-        u256_to_eth_f64(&analysis.value)
-    ));
-    output.push_str(&format!("Gas Used: {}\n", analysis.gas_used));
-    output.push_str(&format!(
-        "Status: {}\n",
-        if analysis.status { "Success" } else { "Failed" }
-    ));
-
-    // Contracts
-    output.push_str("\nContracts Involved:\n");
+    let mut found: Vec<(Address, String)> = Vec::new();
+    for address in std::iter::once(analysis.from).chain(analysis.to) {
+        if let Some(label) = get_label(&address) {
+            found.push((address, label.to_string()));
+        }
+    }
     for contract in &analysis.contracts {
-        let label = contract
-            .label
-            .as_ref()
-            .map(|l| format!(" {}", l))
-            .unwrap_or_else(|| format!(" ({:?})", contract.category));
-        output.push_str(&format!("  {:#x}{}\n", contract.address, label));
+        if let Some(label) = &contract.label {
+            found.push((contract.address, label.clone()));
+        }
     }
-
-    // Events summary
-    output.push_str(&format!("\nEvents ({}):\n", analysis.events.len()));
-    for (i, event) in analysis.events.iter().enumerate().take(20) {
-        let name = event.name.as_deref().unwrap_or("Unknown");
-        let contract = event
-            .address_label
-            .as_ref()
-            .map(|l| format!("({})", l))
-            .unwrap_or_default();
-        output.push_str(&format!("  [{}] {} {}\n", i, name, contract));
+    for flow in &analysis.token_flows {
+        for (address, label) in [
+            (flow.token, &flow.token_label),
+            (flow.from, &flow.from_label),
+            (flow.to, &flow.to_label),
+        ] {
+            if let Some(label) = label {
+                found.push((address, label.clone()));
+            }
+        }
     }
-    if analysis.events.len() > 20 {
-        output.push_str(&format!("  ... and {} more\n", analysis.events.len() - 20));
+    for (address, label) in found {
+        analysis.record_label(address, label, LabelSource::Builtin);
     }
+}
 
-    // Token flows
-    if !analysis.token_flows.is_empty() {
-        output.push_str(&format!(
-            "\nToken Transfers ({}):\n",
-            analysis.token_flows.len()
-        ));
-        for flow in analysis.token_flows.iter().take(15) {
-            let token = flow.token_label.as_deref().unwrap_or("???");
-            let from_short = format!("{:#x}", flow.from);
-            let from_short = if from_short.len() > 12 {
-                &from_short[..12]
-            } else {
-                &from_short
-            };
-            let to_short = format!("{:#x}", flow.to);
-            let to_short = if to_short.len() > 12 {
-                &to_short[..12]
-            } else {
-                &to_short
-            };
-
-            // LOW-002 fix: Use safe token amount formatting
-            let amount_display = format_token_amount(&flow.amount, 18);
-
-            output.push_str(&format!(
-                "  {} {}... → {}... {} {}\n",
-                token, from_short, to_short, amount_display, token
-            ));
+/// Annotate from/to and transfer counterparties with address book labels
+///
+/// Address book names replace existing labels; addresses missing from the
+/// book keep theirs.
+pub fn apply_address_book(analysis: &mut TransactionAnalysis, book: &AddressBook) {
+    let label = |address: &Address| book.label_for(address).map(String::from);
+
+    let mut found = Vec::new();
+    if let Some(name) = label(&analysis.from) {
+        found.push((analysis.from, name.clone()));
+        analysis.from_label = Some(name);
+    }
+    if let Some((to, name)) = analysis.to.and_then(|to| label(&to).map(|n| (to, n))) {
+        found.push((to, name.clone()));
+        analysis.to_label = Some(name);
+    }
+    for flow in &mut analysis.token_flows {
+        if let Some(name) = label(&flow.from) {
+            found.push((flow.from, name.clone()));
+            flow.from_label = Some(name);
         }
-        if analysis.token_flows.len() > 15 {
-            output.push_str(&format!(
-                "  ... and {} more\n",
-                analysis.token_flows.len() - 15
-            ));
+        if let Some(name) = label(&flow.to) {
+            found.push((flow.to, name.clone()));
+            flow.to_label = Some(name);
         }
     }
-
-    // Function call
-    if let Some(func) = &analysis.function_call {
-        output.push_str("\nFunction Call:\n");
-        let name = func.name.as_deref().unwrap_or("Unknown");
-        output.push_str(&format!("  {} ({})\n", name, func.selector));
-        if let Some(sig) = &func.signature {
-            output.push_str(&format!("  Signature: {}\n", sig));
-        }
-        if !func.params.is_empty() {
-            output.push_str("  Parameters:\n");
-            for param in func.params.iter().take(10) {
-                output.push_str(&format!(
-                    "    {} ({}): {}\n",
-                    param.name, param.ty, param.value
-                ));
-            }
-            if func.params.len() > 10 {
-                output.push_str(&format!("    ... and {} more\n", func.params.len() - 10));
-            }
-        }
+    for (address, name) in found {
+        analysis.record_label(address, name, LabelSource::AddressBook);
     }
-
-    output
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tx::types::{AnalyzedEvent, FunctionCall, FunctionParam};
-    use alloy::primitives::{address, b256, B256, U256};
-    use std::collections::HashMap;
-
-    fn make_test_analysis() -> TransactionAnalysis {
-        TransactionAnalysis {
-            hash: b256!("0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef"),
-            block_number: 18_500_000,
-            from: address!("d8da6bf26964af9d7eed9e03e53415d37aa96045"),
-            to: Some(address!("c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2")),
-            value: U256::from(1_000_000_000_000_000_000u128), // 1 ETH
-            gas_used: 21000,
-            status: true,
-            contracts: vec![ContractInfo {
-                address: address!("c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"),
-                label: Some("WETH".to_string()),
-                category: ContractCategory::Token,
-            }],
-            events: vec![],
-            token_flows: vec![],
-            function_call: None,
-        }
-    }
+    use crate::tx::render::tests::make_test_analysis;
+    use alloy::primitives::address;
 
     #[test]
-    fn test_format_analysis_basic() {
-        let analysis = make_test_analysis();
-        let output = format_analysis(&analysis);
-
-        assert!(output.contains("Transaction: 0x1234"));
-        assert!(output.contains("Block: 18500000"));
-        assert!(output.contains("Status: Success"));
-        assert!(output.contains("WETH"));
-    }
-
-    #[test]
-    fn test_format_analysis_failed_tx() {
-        let mut analysis = make_test_analysis();
-        analysis.status = false;
-        let output = format_analysis(&analysis);
-
-        assert!(output.contains("Status: Failed"));
-    }
-
-    #[test]
-    fn test_format_analysis_contract_creation() {
-        let mut analysis = make_test_analysis();
-        analysis.to = None;
-        let output = format_analysis(&analysis);
-
-        assert!(output.contains("Contract Creation"));
-    }
-
-    // LOW-007: Edge case tests
-
-    #[test]
-    fn test_u256_to_eth_f64_normal() {
-        // 1 ETH = 1e18 wei
-        let one_eth = U256::from(1_000_000_000_000_000_000u128);
-        let result = u256_to_eth_f64(&one_eth);
-        assert!((result - 1.0).abs() < 0.0001);
-    }
-
-    #[test]
-    fn test_u256_to_eth_f64_small() {
-        // 1 wei
-        let one_wei = U256::from(1u64);
-        let result = u256_to_eth_f64(&one_wei);
-        assert!(result > 0.0 && result < 1e-17);
-    }
-
-    #[test]
-    fn test_u256_to_eth_f64_zero() {
-        let zero = U256::ZERO;
-        let result = u256_to_eth_f64(&zero);
-        assert_eq!(result, 0.0);
-    }
-
-    #[test]
-    fn test_u256_to_eth_f64_large() {
-        // Value larger than u128::MAX
-        let large = U256::MAX;
-        let result = u256_to_eth_f64(&large);
-        // Should not panic, and should return a large positive number
-        assert!(result > 0.0);
-        assert!(result.is_finite() || result == f64::MAX);
-    }
-
-    #[test]
-    fn test_format_token_amount_normal() {
-        // 1.23 tokens (with 18 decimals)
-        let amount = "1230000000000000000";
-        let result = format_token_amount(amount, 18);
-        assert_eq!(result, "1.23");
-    }
-
-    #[test]
-    fn test_format_token_amount_small() {
-        // 0.01 tokens
-        let amount = "10000000000000000";
-        let result = format_token_amount(amount, 18);
-        assert_eq!(result, "0.01");
-    }
-
-    #[test]
-    fn test_format_token_amount_very_small() {
-        // Very small amount (less than 0.01)
-        let amount = "1000000000000";
-        let result = format_token_amount(amount, 18);
-        assert_eq!(result, "0.00");
-    }
-
-    #[test]
-    fn test_format_token_amount_empty() {
-        let result = format_token_amount("", 18);
-        assert_eq!(result, "0.00");
-    }
-
-    #[test]
-    fn test_format_token_amount_invalid() {
-        let result = format_token_amount("not_a_number", 18);
-        assert_eq!(result, "0.00");
-    }
-
-    #[test]
-    fn test_format_token_amount_large() {
-        // 1 million tokens
-        let amount = "1000000000000000000000000";
-        let result = format_token_amount(amount, 18);
-        assert_eq!(result, "1000000.00");
-    }
-
-    #[test]
-    fn test_format_analysis_many_events() {
-        let mut analysis = make_test_analysis();
-        // Add 25 events (more than the 20 limit)
-        for i in 0..25 {
-            analysis.events.push(AnalyzedEvent {
-                log_index: i as u64,
-                address: address!("c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"),
-                address_label: Some("WETH".to_string()),
-                name: Some("Transfer".to_string()),
-                signature: Some("Transfer(address,address,uint256)".to_string()),
-                params: HashMap::new(),
-                topic0: B256::ZERO,
-                is_transfer: true,
-            });
-        }
-
-        let output = format_analysis(&analysis);
-        assert!(output.contains("... and 5 more"));
-    }
-
-    #[test]
-    fn test_format_analysis_many_token_flows() {
+    fn test_apply_address_book() {
+        use crate::config::AddressEntry;
         use crate::tx::types::TokenFlow;
 
-        let mut analysis = make_test_analysis();
-        // Add 20 token flows (more than the 15 limit)
-        for i in 0..20 {
-            analysis.token_flows.push(TokenFlow {
-                token: address!("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"),
-                token_label: Some("USDC".to_string()),
-                from: address!("d8da6bf26964af9d7eed9e03e53415d37aa96045"),
-                from_label: None,
-                to: address!("c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"),
-                to_label: None,
-                amount: "1000000".to_string(),
-                log_index: i as u64,
-            });
+        let mut book = AddressBook::default();
+        for (label, address) in [
+            ("me", "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045"),
+            ("desk", "0x1111111111111111111111111111111111111111"),
+        ] {
+            book.entries.insert(
+                label.to_string(),
+                AddressEntry {
+                    address: address.to_string(),
+                    description: None,
+                    tags: Vec::new(),
+                    chain: None,
+                },
+            );
         }
 
-        let output = format_analysis(&analysis);
-        assert!(output.contains("... and 5 more"));
-    }
-
-    #[test]
-    fn test_format_analysis_with_function_call() {
         let mut analysis = make_test_analysis();
-        analysis.function_call = Some(FunctionCall {
-            selector: "0xa9059cbb".to_string(),
-            name: Some("transfer".to_string()),
-            signature: Some("transfer(address,uint256)".to_string()),
-            params: vec![
-                FunctionParam {
-                    name: "to".to_string(),
-                    ty: "address".to_string(),
-                    value: "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string(),
-                },
-                FunctionParam {
-                    name: "amount".to_string(),
-                    ty: "uint256".to_string(),
-                    value: "1000000000000000000".to_string(),
-                },
-            ],
+        analysis.token_flows.push(TokenFlow {
+            token: address!("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"),
+            token_label: Some("USDC".to_string()),
+            token_decimals: Some(6),
+            from: address!("d8da6bf26964af9d7eed9e03e53415d37aa96045"),
+            from_label: None,
+            to: address!("1111111111111111111111111111111111111111"),
+            to_label: Some("Built-in".to_string()),
+            amount: "1000000".to_string(),
+            log_index: 0,
         });
 
-        let output = format_analysis(&analysis);
-        assert!(output.contains("Function Call:"));
-        assert!(output.contains("transfer"));
-        assert!(output.contains("0xa9059cbb"));
-        assert!(output.contains("Parameters:"));
-    }
+        apply_address_book(&mut analysis, &book);
+        assert_eq!(analysis.from_label.as_deref(), Some("me"));
+        assert_eq!(analysis.to_label, None);
+        assert_eq!(analysis.token_flows[0].from_label.as_deref(), Some("me"));
+        assert_eq!(analysis.token_flows[0].to_label.as_deref(), Some("desk"));
 
-    #[test]
-    fn test_format_analysis_empty_contracts() {
-        let mut analysis = make_test_analysis();
-        analysis.contracts.clear();
         let output = format_analysis(&analysis);
+        assert!(output.contains("From: 0xd8da6bf26964af9d7eed9e03e53415d37aa96045 (me)"));
+        assert!(output.contains("me → desk"));
 
-        // Should still have the "Contracts Involved:" header
-        assert!(output.contains("Contracts Involved:"));
+        let json = serde_json::to_value(&analysis).unwrap();
+        assert_eq!(json["from"], "0xd8da6bf26964af9d7eed9e03e53415d37aa96045");
+        assert_eq!(json["from_label"], "me");
+        assert!(json.get("to_label").is_none());
     }
 
     #[test]
-    fn test_format_analysis_large_value() {
-        let mut analysis = make_test_analysis();
-        // Set a very large value (> u128::MAX would overflow, but let's test a large u128)
-        analysis.value = U256::from(u128::MAX);
-        let output = format_analysis(&analysis);
-
-        // Should not panic and should contain ETH formatting
-        assert!(output.contains("ETH"));
+    fn test_event_param_from_decoded_tuple() {
+        let value = DecodedValue::Tuple(vec![
+            DecodedValue::Address("0x1111111111111111111111111111111111111111".to_string()),
+            DecodedValue::Array(vec![DecodedValue::Uint("42".to_string())]),
+        ]);
+
+        match event_param_from_decoded(value) {
+            EventParam::Array(items) => {
+                assert!(matches!(&items[0], EventParam::Address(_)));
+                assert!(matches!(&items[1], EventParam::Array(inner) if inner.len() == 1));
+            }
+            other => panic!("expected array, got {:?}", other),
+        }
     }
 }
@@ -57,6 +57,7 @@ fn parse_transfer_log(log: &Log) -> Option<TokenFlow> {
     Some(TokenFlow {
         token,
         token_label: get_label(&token).map(String::from),
+        token_decimals: None,
         from,
         from_label: get_label(&from).map(String::from),
         to,
@@ -161,6 +162,7 @@ mod tests {
             TokenFlow {
                 token,
                 token_label: Some("TEST".to_string()),
+                token_decimals: None,
                 from: other,
                 from_label: None,
                 to: addr,
@@ -171,6 +173,7 @@ mod tests {
             TokenFlow {
                 token,
                 token_label: Some("TEST".to_string()),
+                token_decimals: None,
                 from: addr,
                 from_label: None,
                 to: other,
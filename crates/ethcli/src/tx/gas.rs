@@ -0,0 +1,433 @@
+//! Gas breakdown by opcode category
+//!
+//! Replays a transaction with `debug_traceTransaction` (struct logger, no
+//! stack/memory/storage capture) and attributes each step's gas to a
+//! category. CALL/CREATE steps are charged only their own overhead: gas
+//! spent inside the callee is attributed to the callee's opcodes. Intrinsic
+//! gas is computed from the transaction, and whatever is left between the
+//! attributed total and the receipt's `gasUsed` (refunds, calldata floor
+//! pricing) is reported as its own line.
+
+use crate::error::{Result, RpcError};
+use crate::rpc::RawRpc;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+
+/// Where a unit of gas went
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GasCategory {
+    /// Base transaction cost, calldata, contract creation and access list
+    Intrinsic,
+    /// SSTORE / TSTORE
+    StorageWrite,
+    /// SLOAD / TLOAD
+    StorageRead,
+    /// CALL, STATICCALL, DELEGATECALL, CALLCODE, CREATE, CREATE2, SELFDESTRUCT
+    /// overhead (not the gas spent by the callee)
+    Calls,
+    /// LOG0-LOG4
+    Logs,
+    /// Everything else: arithmetic, memory, hashing, control flow
+    Computation,
+    /// Difference between the attributed gas and `gasUsed` (refunds are
+    /// negative)
+    Refund,
+}
+
+impl GasCategory {
+    /// Categories in display order
+    pub const ALL: [GasCategory; 7] = [
+        GasCategory::Intrinsic,
+        GasCategory::StorageWrite,
+        GasCategory::StorageRead,
+        GasCategory::Calls,
+        GasCategory::Logs,
+        GasCategory::Computation,
+        GasCategory::Refund,
+    ];
+
+    /// Category of an opcode name as reported by the struct logger
+    pub fn for_opcode(op: &str) -> Self {
+        match op {
+            "SSTORE" | "TSTORE" => GasCategory::StorageWrite,
+            "SLOAD" | "TLOAD" => GasCategory::StorageRead,
+            "CALL" | "CALLCODE" | "DELEGATECALL" | "STATICCALL" | "CREATE" | "CREATE2"
+            | "SELFDESTRUCT" => GasCategory::Calls,
+            op if op.starts_with("LOG") => GasCategory::Logs,
+            _ => GasCategory::Computation,
+        }
+    }
+
+    /// Human-readable name
+    pub fn label(&self) -> &'static str {
+        match self {
+            GasCategory::Intrinsic => "Intrinsic",
+            GasCategory::StorageWrite => "Storage writes",
+            GasCategory::StorageRead => "Storage reads",
+            GasCategory::Calls => "Calls",
+            GasCategory::Logs => "Log emission",
+            GasCategory::Computation => "Computation",
+            GasCategory::Refund => "Refund / adjustment",
+        }
+    }
+
+    fn opens_frame(op: &str) -> bool {
+        matches!(
+            op,
+            "CALL" | "CALLCODE" | "DELEGATECALL" | "STATICCALL" | "CREATE" | "CREATE2"
+        )
+    }
+}
+
+impl fmt::Display for GasCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// One step of a struct-logger trace
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLog {
+    /// Opcode name
+    pub op: String,
+    /// Gas remaining before the step
+    pub gas: u64,
+    /// Gas charged for the step (for calls, includes gas forwarded)
+    pub gas_cost: u64,
+    /// Call depth (1 for the top-level frame)
+    pub depth: u64,
+}
+
+/// Result of `debug_traceTransaction` with the default struct logger
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpcodeTrace {
+    /// Gas used by the transaction
+    pub gas: u64,
+    /// Whether the transaction reverted
+    #[serde(default)]
+    pub failed: bool,
+    /// Executed steps
+    #[serde(default)]
+    pub struct_logs: Vec<StructLog>,
+}
+
+/// Gas attributed to one category
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GasShare {
+    pub category: GasCategory,
+    /// Gas units (negative for refunds)
+    pub gas: i64,
+    /// Share of `gas_used` in percent
+    pub percent: f64,
+}
+
+/// Gas used by a transaction, split by category
+#[derive(Debug, Clone, Serialize)]
+pub struct GasBreakdown {
+    /// Transaction hash
+    pub hash: String,
+    /// Gas used according to the receipt
+    pub gas_used: u64,
+    /// Whether the transaction reverted
+    pub failed: bool,
+    /// One entry per category, in [`GasCategory::ALL`] order
+    pub categories: Vec<GasShare>,
+}
+
+impl GasBreakdown {
+    /// Attribute a trace's gas, given the transaction's intrinsic gas and
+    /// the receipt's `gasUsed`
+    pub fn from_trace(hash: &str, trace: &OpcodeTrace, intrinsic: u64, gas_used: u64) -> Self {
+        let mut totals = [0i64; GasCategory::ALL.len()];
+        let index = |c: GasCategory| GasCategory::ALL.iter().position(|x| *x == c).unwrap_or(0);
+
+        totals[index(GasCategory::Intrinsic)] = intrinsic as i64;
+        for (category, gas) in attribute_steps(&trace.struct_logs) {
+            totals[index(category)] += gas as i64;
+        }
+        let attributed: i64 = totals.iter().sum();
+        totals[index(GasCategory::Refund)] = gas_used as i64 - attributed;
+
+        let categories = GasCategory::ALL
+            .iter()
+            .zip(totals)
+            .map(|(&category, gas)| GasShare {
+                category,
+                gas,
+                percent: if gas_used == 0 {
+                    0.0
+                } else {
+                    gas as f64 * 100.0 / gas_used as f64
+                },
+            })
+            .collect();
+
+        Self {
+            hash: hash.to_string(),
+            gas_used,
+            failed: trace.failed,
+            categories,
+        }
+    }
+
+    /// Gas for one category
+    pub fn gas(&self, category: GasCategory) -> i64 {
+        self.categories
+            .iter()
+            .find(|s| s.category == category)
+            .map_or(0, |s| s.gas)
+    }
+}
+
+/// Gas charged by each step, excluding gas spent inside callees
+fn attribute_steps(logs: &[StructLog]) -> Vec<(GasCategory, u64)> {
+    logs.iter()
+        .enumerate()
+        .map(|(i, step)| {
+            let category = GasCategory::for_opcode(&step.op);
+            let next = logs.get(i + 1);
+            let cost = match next {
+                // Entered a callee: charge what the call cost minus what the
+                // callee itself used
+                Some(first) if GasCategory::opens_frame(&step.op) && first.depth > step.depth => {
+                    let end = logs[i + 1..]
+                        .iter()
+                        .position(|s| s.depth <= step.depth)
+                        .map(|offset| i + 1 + offset);
+                    match end {
+                        Some(end) => {
+                            let last = &logs[end - 1];
+                            let callee_used = first
+                                .gas
+                                .saturating_sub(last.gas.saturating_sub(last.gas_cost));
+                            step.gas
+                                .saturating_sub(logs[end].gas)
+                                .saturating_sub(callee_used)
+                        }
+                        None => step.gas_cost,
+                    }
+                }
+                // Call without callee steps (precompile, EOA, failed call):
+                // the gas actually consumed is what disappeared
+                Some(after) if GasCategory::opens_frame(&step.op) && after.depth == step.depth => {
+                    step.gas.saturating_sub(after.gas)
+                }
+                _ => step.gas_cost,
+            };
+            (category, cost)
+        })
+        .collect()
+}
+
+/// Intrinsic gas of a transaction (EIP-2028 calldata, EIP-2930 access
+/// list, EIP-3860 initcode)
+///
+/// `access_list` holds the storage key count of each accessed address.
+pub fn intrinsic_gas(input: &[u8], is_create: bool, access_list: &[usize]) -> u64 {
+    let calldata: u64 = input.iter().map(|b| if *b == 0 { 4 } else { 16 }).sum();
+    let create = if is_create {
+        32_000 + 2 * (input.len() as u64).div_ceil(32)
+    } else {
+        0
+    };
+    let access: u64 = access_list
+        .iter()
+        .map(|&keys| 2_400 + 1_900 * keys as u64)
+        .sum();
+    21_000 + calldata + create + access
+}
+
+/// Intrinsic gas from an `eth_getTransactionByHash` result
+fn intrinsic_from_tx(tx: &Value) -> Result<u64> {
+    let input = tx
+        .get("input")
+        .and_then(Value::as_str)
+        .ok_or_else(|| RpcError::InvalidResponse("transaction has no input field".into()))?;
+    let input = hex::decode(input.trim_start_matches("0x"))
+        .map_err(|e| RpcError::InvalidResponse(format!("invalid transaction input: {}", e)))?;
+    let is_create = tx.get("to").is_none_or(Value::is_null);
+    let access_list: Vec<usize> = tx
+        .get("accessList")
+        .and_then(Value::as_array)
+        .map(|entries| {
+            entries
+                .iter()
+                .map(|e| {
+                    e.get("storageKeys")
+                        .and_then(Value::as_array)
+                        .map_or(0, Vec::len)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    Ok(intrinsic_gas(&input, is_create, &access_list))
+}
+
+/// Trace a transaction on a debug-capable endpoint and break its gas down
+pub async fn trace_gas_breakdown(rpc_url: &str, hash: &str) -> Result<GasBreakdown> {
+    let rpc = RawRpc::new(rpc_url)?;
+
+    let tx = rpc
+        .request("eth_getTransactionByHash", serde_json::json!([hash]))
+        .await?;
+    if tx.is_null() {
+        return Err(RpcError::InvalidResponse(format!("transaction {} not found", hash)).into());
+    }
+    let receipt = rpc
+        .request("eth_getTransactionReceipt", serde_json::json!([hash]))
+        .await?;
+    let gas_used = receipt
+        .get("gasUsed")
+        .and_then(Value::as_str)
+        .and_then(|g| u64::from_str_radix(g.trim_start_matches("0x"), 16).ok())
+        .ok_or_else(|| RpcError::InvalidResponse(format!("no receipt for {} (pending?)", hash)))?;
+
+    let config = serde_json::json!({
+        "disableStack": true,
+        "disableMemory": true,
+        "disableStorage": true,
+        "enableReturnData": false,
+    });
+    let trace = rpc
+        .request("debug_traceTransaction", serde_json::json!([hash, config]))
+        .await
+        .map_err(|e| {
+            RpcError::Provider(format!(
+                "{}\ngas-breakdown needs an endpoint with debug_traceTransaction \
+             (set has_debug = true on one, or pass --rpc-url)",
+                e
+            ))
+        })?;
+    let trace: OpcodeTrace = serde_json::from_value(trace)
+        .map_err(|e| RpcError::InvalidResponse(format!("unexpected trace format: {}", e)))?;
+
+    Ok(GasBreakdown::from_trace(
+        hash,
+        &trace,
+        intrinsic_from_tx(&tx)?,
+        gas_used,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(op: &str, gas: u64, gas_cost: u64, depth: u64) -> StructLog {
+        StructLog {
+            op: op.to_string(),
+            gas,
+            gas_cost,
+            depth,
+        }
+    }
+
+    fn trace(struct_logs: Vec<StructLog>) -> OpcodeTrace {
+        OpcodeTrace {
+            gas: 0,
+            failed: false,
+            struct_logs,
+        }
+    }
+
+    #[test]
+    fn test_opcode_categories() {
+        assert_eq!(GasCategory::for_opcode("SSTORE"), GasCategory::StorageWrite);
+        assert_eq!(GasCategory::for_opcode("TLOAD"), GasCategory::StorageRead);
+        assert_eq!(GasCategory::for_opcode("STATICCALL"), GasCategory::Calls);
+        assert_eq!(GasCategory::for_opcode("LOG3"), GasCategory::Logs);
+        assert_eq!(
+            GasCategory::for_opcode("KECCAK256"),
+            GasCategory::Computation
+        );
+    }
+
+    #[test]
+    fn test_intrinsic_gas() {
+        assert_eq!(intrinsic_gas(&[], false, &[]), 21_000);
+        // transfer(address,uint256): 4 selector bytes + 2 words, 23 non-zero bytes
+        let mut input = vec![0xa9, 0x05, 0x9c, 0xbb];
+        input.extend([0u8; 12]);
+        input.extend([0x11; 20]);
+        input.extend([0u8; 29]);
+        input.extend([0x0f, 0x42, 0x40]);
+        assert_eq!(intrinsic_gas(&input, false, &[]), 21_000 + 27 * 16 + 41 * 4);
+        // 33-byte initcode is two words
+        assert_eq!(
+            intrinsic_gas(&[1; 33], true, &[2]),
+            21_000 + 33 * 16 + 32_000 + 4 + 2_400 + 2 * 1_900
+        );
+    }
+
+    #[test]
+    fn test_breakdown_excludes_callee_gas_from_call() {
+        let logs = vec![
+            step("PUSH1", 100_000, 3, 1),
+            step("SLOAD", 99_997, 2_100, 1),
+            // CALL forwards gas; the callee runs at depth 2
+            step("CALL", 97_897, 60_000, 1),
+            step("PUSH1", 57_000, 3, 2),
+            step("SSTORE", 56_997, 20_000, 2),
+            step("LOG1", 36_997, 750, 2),
+            step("STOP", 36_247, 0, 2),
+            // back in the caller: 2_600 call overhead + 20_753 used by the callee
+            step("SSTORE", 74_544, 2_900, 1),
+            step("STOP", 71_644, 0, 1),
+        ];
+        let breakdown = GasBreakdown::from_trace("0xabc", &trace(logs), 21_000, 29_000);
+
+        assert_eq!(breakdown.gas(GasCategory::Intrinsic), 21_000);
+        assert_eq!(breakdown.gas(GasCategory::StorageRead), 2_100);
+        assert_eq!(breakdown.gas(GasCategory::StorageWrite), 22_900);
+        assert_eq!(breakdown.gas(GasCategory::Logs), 750);
+        assert_eq!(breakdown.gas(GasCategory::Computation), 6);
+        assert_eq!(breakdown.gas(GasCategory::Calls), 2_600);
+        let attributed = 21_000 + 2_100 + 22_900 + 750 + 6 + 2_600;
+        assert_eq!(
+            breakdown.gas(GasCategory::Refund),
+            29_000 - attributed as i64
+        );
+
+        let total: f64 = breakdown.categories.iter().map(|s| s.percent).sum();
+        assert!((total - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_call_without_callee_steps() {
+        // STATICCALL to a precompile: no depth-2 steps
+        let logs = vec![
+            step("STATICCALL", 50_000, 49_000, 1),
+            step("POP", 46_500, 2, 1),
+            step("STOP", 46_498, 0, 1),
+        ];
+        let breakdown = GasBreakdown::from_trace("0xabc", &trace(logs), 21_000, 24_502);
+        assert_eq!(breakdown.gas(GasCategory::Calls), 3_500);
+        assert_eq!(breakdown.gas(GasCategory::Refund), 0);
+    }
+
+    #[test]
+    fn test_parse_struct_logger_trace() {
+        let trace: OpcodeTrace = serde_json::from_value(serde_json::json!({
+            "gas": 21_064,
+            "failed": false,
+            "returnValue": "",
+            "structLogs": [
+                {"pc": 0, "op": "PUSH1", "gas": 79_000, "gasCost": 3, "depth": 1},
+                {"pc": 2, "op": "STOP", "gas": 78_997, "gasCost": 0, "depth": 1}
+            ]
+        }))
+        .unwrap();
+        assert_eq!(trace.struct_logs.len(), 2);
+        assert_eq!(trace.struct_logs[0].gas_cost, 3);
+
+        let json = serde_json::to_value(GasBreakdown::from_trace("0xabc", &trace, 21_061, 21_064))
+            .unwrap();
+        assert_eq!(json["gas_used"], 21_064);
+        assert_eq!(json["categories"][0]["category"], "intrinsic");
+        assert_eq!(json["categories"][5]["gas"], 3);
+    }
+}
@@ -5,11 +5,19 @@
 //! - Decoding events with known signatures
 //! - Labeling known contracts and tokens
 //! - Tracking token flows
+//! - Breaking gas usage down by opcode category from a debug trace
+//! - Rendering the analysis as text, Markdown or compact JSON
 
 pub mod addresses;
 pub mod analyzer;
 pub mod flow;
+pub mod gas;
+pub mod render;
 pub mod types;
 
-pub use analyzer::{format_analysis, TxAnalyzer};
-pub use types::{AnalyzedEvent, ContractInfo, TokenFlow, TransactionAnalysis};
+pub use analyzer::TxAnalyzer;
+pub use render::format_analysis;
+pub use types::{
+    AddressLabel, AnalyzedEvent, ContractInfo, LabelSource, TokenFlow, TransactionAnalysis,
+    Warning, WarningKind,
+};
@@ -0,0 +1,905 @@
+//! Renderers for [`TransactionAnalysis`]
+//!
+//! Everything here is a pure function of the analysis, so library users can
+//! pick a rendering (terminal text, Markdown, compact JSON) without going
+//! through the CLI.
+
+use crate::tx::types::{TokenFlow, TransactionAnalysis};
+use alloy::primitives::{Address, U256};
+use serde_json::json;
+
+/// Convert U256 to f64 with ETH decimals (HIGH-001 fix)
+///
+/// Handles arbitrarily large values by:
+/// 1. Using u128 conversion when possible for precision
+/// 2. Falling back to string conversion for very large values
+/// 3. Saturating at f64::MAX to prevent overflow/panic
+fn u256_to_eth_f64(value: &U256) -> f64 {
+    const ETH_SCALE: f64 = 1e18;
+
+    // Try u128 first for better precision (covers values up to ~340 undecillion wei)
+    if let Ok(as_u128) = TryInto::<u128>::try_into(*value) {
+        return (as_u128 as f64) / ETH_SCALE;
+    }
+
+    // For values > u128::MAX, convert via string (loses precision but handles any size)
+    let s = value.to_string();
+    match s.parse::<f64>() {
+        Ok(v) if v.is_finite() => v / ETH_SCALE,
+        _ => f64::MAX, // Saturate on overflow or parse failure
+    }
+}
+
+/// Format token amount with decimals (LOW-002 fix)
+///
+/// Safely formats large token amounts without panicking on edge cases.
+/// Returns a human-readable string with 2 decimal places.
+fn format_token_amount(amount_str: &str, decimals: usize) -> String {
+    // Handle empty or non-numeric input
+    if amount_str.is_empty() || !amount_str.chars().all(|c| c.is_ascii_digit()) {
+        return "0.00".to_string();
+    }
+
+    if amount_str.len() > decimals {
+        let int_part = &amount_str[..amount_str.len() - decimals];
+        let frac_part = &amount_str[amount_str.len() - decimals..];
+        // Take first 2 decimal places
+        let frac_display = if frac_part.len() >= 2 {
+            &frac_part[..2]
+        } else {
+            frac_part
+        };
+        format!("{}.{}", int_part, frac_display)
+    } else {
+        // Amount is less than 1 whole token
+        let padding = "0".repeat(decimals - amount_str.len());
+        let padded = format!("{}{}", padding, amount_str);
+        let frac_display = if padded.len() >= 2 {
+            &padded[..2]
+        } else {
+            &padded
+        };
+        format!("0.{}", frac_display)
+    }
+}
+
+/// Format transaction analysis for terminal display
+///
+/// Pure over the analysis: labels, the failure reason and the explorer link
+/// all come from the model, so the output is the same wherever it is built.
+pub fn format_analysis(analysis: &TransactionAnalysis) -> String {
+    // LOW-005 fix: Pre-allocate for typical output size
+    let mut output = String::with_capacity(2048);
+
+    // Header
+    output.push_str(&format!("Transaction: {:#x}\n", analysis.hash));
+    output.push_str(&format!("Block: {}\n", analysis.block_number));
+    let from_label = analysis
+        .from_label
+        .as_ref()
+        .map(|l| format!(" ({})", l))
+        .unwrap_or_default();
+    output.push_str(&format!("From: {:#x}{}\n", analysis.from, from_label));
+
+    if let Some(to) = analysis.to {
+        let label = analysis
+            .to_label
+            .as_deref()
+            .map(|l| format!(" ({})", l))
+            .unwrap_or_default();
+        output.push_str(&format!("To: {:#x}{}\n", to, label));
+    } else {
+        output.push_str("To: Contract Creation\n");
+    }
+
+    // HIGH-001 fix: Use safe U256 to f64 conversion
+    output.push_str(&format!(
+        "Value: {} wei ({:.6} ETH)\n",
+        analysis.value,
+        u256_to_eth_f64(&analysis.value)
+    ));
+    output.push_str(&format!("Gas Used: {}\n", analysis.gas_used));
+    output.push_str(&format!(
+        "Status: {}\n",
+        if analysis.status { "Success" } else { "Failed" }
+    ));
+    if let Some(reason) = &analysis.failure_reason {
+        output.push_str(&format!("Failure Reason: {}\n", reason));
+    }
+
+    // Contracts
+    output.push_str("\nContracts Involved:\n");
+    for contract in &analysis.contracts {
+        let label = contract
+            .label
+            .as_ref()
+            .map(|l| format!(" {}", l))
+            .unwrap_or_else(|| format!(" ({:?})", contract.category));
+        output.push_str(&format!("  {:#x}{}\n", contract.address, label));
+    }
+
+    // Events summary
+    output.push_str(&format!("\nEvents ({}):\n", analysis.events.len()));
+    for (i, event) in analysis.events.iter().enumerate().take(20) {
+        let name = event.name.as_deref().unwrap_or("Unknown");
+        let contract = event
+            .address_label
+            .as_ref()
+            .map(|l| format!("({})", l))
+            .unwrap_or_default();
+        output.push_str(&format!("  [{}] {} {}\n", i, name, contract));
+    }
+    if analysis.events.len() > 20 {
+        output.push_str(&format!("  ... and {} more\n", analysis.events.len() - 20));
+    }
+
+    // Token flows
+    if !analysis.token_flows.is_empty() {
+        output.push_str(&format!(
+            "\nToken Transfers ({}):\n",
+            analysis.token_flows.len()
+        ));
+        for flow in analysis.token_flows.iter().take(15) {
+            let token = flow.token_label.as_deref().unwrap_or("???");
+            // Prefer the label, otherwise a shortened address
+            let party = |address: &Address, label: &Option<String>| match label {
+                Some(label) => label.clone(),
+                None => {
+                    let short = format!("{:#x}", address);
+                    format!("{}...", &short[..short.len().min(12)])
+                }
+            };
+
+            // LOW-002 fix: Use safe token amount formatting
+            let amount_display = format_token_amount(&flow.amount, 18);
+
+            output.push_str(&format!(
+                "  {} {} → {} {} {}\n",
+                token,
+                party(&flow.from, &flow.from_label),
+                party(&flow.to, &flow.to_label),
+                amount_display,
+                token
+            ));
+        }
+        if analysis.token_flows.len() > 15 {
+            output.push_str(&format!(
+                "  ... and {} more\n",
+                analysis.token_flows.len() - 15
+            ));
+        }
+    }
+
+    // Function call
+    if let Some(func) = &analysis.function_call {
+        output.push_str("\nFunction Call:\n");
+        let name = func.name.as_deref().unwrap_or("Unknown");
+        output.push_str(&format!("  {} ({})\n", name, func.selector));
+        if let Some(sig) = &func.signature {
+            output.push_str(&format!("  Signature: {}\n", sig));
+        }
+        if !func.params.is_empty() {
+            output.push_str("  Parameters:\n");
+            for param in func.params.iter().take(10) {
+                output.push_str(&format!(
+                    "    {} ({}): {}\n",
+                    param.name, param.ty, param.value
+                ));
+            }
+            if func.params.len() > 10 {
+                output.push_str(&format!("    ... and {} more\n", func.params.len() - 10));
+            }
+        }
+    }
+
+    if let Some(url) = &analysis.explorer_url {
+        // Separated by a blank line, as the CLI printed it before
+        output.push_str(&format!("\n\nExplorer: {}", url));
+    }
+
+    output
+}
+
+impl TransactionAnalysis {
+    /// Render as Markdown (for chat, issues and reports)
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::with_capacity(2048);
+        let party = |address: &Address, label: Option<&str>| match label {
+            Some(label) => format!("`{:#x}` ({})", address, md_escape(label)),
+            None => format!("`{:#x}`", address),
+        };
+
+        out.push_str(&format!("## Transaction `{:#x}`\n\n", self.hash));
+        out.push_str("| Field | Value |\n|---|---|\n");
+        out.push_str(&format!("| Block | {} |\n", self.block_number));
+        out.push_str(&format!(
+            "| From | {} |\n",
+            party(&self.from, self.from_label.as_deref())
+        ));
+        match self.to {
+            Some(to) => out.push_str(&format!(
+                "| To | {} |\n",
+                party(&to, self.to_label.as_deref())
+            )),
+            None => out.push_str("| To | Contract Creation |\n"),
+        }
+        out.push_str(&format!(
+            "| Value | {} wei ({:.6} ETH) |\n",
+            self.value,
+            u256_to_eth_f64(&self.value)
+        ));
+        out.push_str(&format!("| Gas Used | {} |\n", self.gas_used));
+        let status = match (&self.status, &self.failure_reason) {
+            (true, _) => "Success".to_string(),
+            (false, Some(reason)) => format!("Failed ({})", md_escape(reason)),
+            (false, None) => "Failed".to_string(),
+        };
+        out.push_str(&format!("| Status | {} |\n", status));
+        if let Some(url) = &self.explorer_url {
+            out.push_str(&format!("\n[View on explorer]({})\n", url));
+        }
+
+        if let Some(func) = &self.function_call {
+            out.push_str("\n### Function Call\n\n");
+            let name = func.signature.as_deref().or(func.name.as_deref());
+            match name {
+                Some(name) => out.push_str(&format!("`{}` (`{}`)\n", name, func.selector)),
+                None => out.push_str(&format!("Unknown (`{}`)\n", func.selector)),
+            }
+            if !func.params.is_empty() {
+                out.push('\n');
+                for param in &func.params {
+                    out.push_str(&format!(
+                        "- `{}` ({}): `{}`\n",
+                        param.name, param.ty, param.value
+                    ));
+                }
+            }
+        }
+
+        if !self.token_flows.is_empty() {
+            out.push_str(&format!(
+                "\n### Token Transfers ({})\n\n| Token | From | To | Amount |\n|---|---|---|---|\n",
+                self.token_flows.len()
+            ));
+            for flow in &self.token_flows {
+                out.push_str(&format!(
+                    "| {} | {} | {} | {} |\n",
+                    party(&flow.token, flow.token_label.as_deref()),
+                    party(&flow.from, flow.from_label.as_deref()),
+                    party(&flow.to, flow.to_label.as_deref()),
+                    flow_amount(flow)
+                ));
+            }
+        }
+
+        if !self.events.is_empty() {
+            out.push_str(&format!("\n### Events ({})\n\n", self.events.len()));
+            for event in &self.events {
+                let name = event.name.as_deref().unwrap_or("Unknown");
+                out.push_str(&format!(
+                    "{}. {} — {}\n",
+                    event.log_index,
+                    md_escape(name),
+                    party(&event.address, event.address_label.as_deref())
+                ));
+            }
+        }
+
+        if !self.warnings.is_empty() {
+            out.push_str("\n### Warnings\n\n");
+            for warning in &self.warnings {
+                out.push_str(&format!("- {}\n", md_escape(&warning.to_string())));
+            }
+        }
+
+        out
+    }
+
+    /// Render as single-line JSON with only the fields needed to understand
+    /// the transaction at a glance
+    ///
+    /// Event parameters, contract categories and label provenance are left
+    /// out; serialize the analysis itself for the full model.
+    pub fn to_compact_json(&self) -> String {
+        let party = |address: &Address, label: Option<&str>| match label {
+            Some(label) => json!({ "address": format!("{:#x}", address), "label": label }),
+            None => json!({ "address": format!("{:#x}", address) }),
+        };
+
+        let value = json!({
+            "hash": format!("{:#x}", self.hash),
+            "block": self.block_number,
+            "status": if self.status { "success" } else { "failed" },
+            "failure_reason": self.failure_reason,
+            "from": party(&self.from, self.from_label.as_deref()),
+            "to": self.to.map(|to| party(&to, self.to_label.as_deref())),
+            "value_wei": self.value.to_string(),
+            "gas_used": self.gas_used,
+            "function": self.function_call.as_ref().map(|f| {
+                f.signature.clone().or_else(|| f.name.clone()).unwrap_or_else(|| f.selector.clone())
+            }),
+            "transfers": self.token_flows.iter().map(|flow| json!({
+                "token": party(&flow.token, flow.token_label.as_deref()),
+                "from": format!("{:#x}", flow.from),
+                "to": format!("{:#x}", flow.to),
+                "amount": flow.amount,
+                "decimals": flow.token_decimals,
+            })).collect::<Vec<_>>(),
+            "events": self.events.iter().map(|e| e.name.as_deref().unwrap_or("Unknown")).collect::<Vec<_>>(),
+            "explorer_url": self.explorer_url,
+            "warnings": self.warnings.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        });
+        value.to_string()
+    }
+}
+
+/// Token amount with known decimals, raw base units otherwise
+fn flow_amount(flow: &TokenFlow) -> String {
+    match flow.token_decimals {
+        Some(decimals) => format_token_amount(&flow.amount, decimals as usize),
+        None => format!("{} (raw)", flow.amount),
+    }
+}
+
+/// Escape characters that would break a Markdown table cell
+fn md_escape(s: &str) -> String {
+    s.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::tx::addresses::get_label;
+    use crate::tx::analyzer::apply_builtin_labels;
+    use crate::tx::types::{
+        AnalyzedEvent, ContractCategory, ContractInfo, FunctionCall, FunctionParam, Warning,
+        WarningKind,
+    };
+    use alloy::primitives::{address, b256, B256};
+    use std::collections::HashMap;
+
+    pub(crate) fn make_test_analysis() -> TransactionAnalysis {
+        TransactionAnalysis {
+            hash: b256!("0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef"),
+            block_number: 18_500_000,
+            from: address!("d8da6bf26964af9d7eed9e03e53415d37aa96045"),
+            from_label: None,
+            to: Some(address!("c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2")),
+            to_label: None,
+            value: U256::from(1_000_000_000_000_000_000u128), // 1 ETH
+            gas_used: 21000,
+            status: true,
+            contracts: vec![ContractInfo {
+                address: address!("c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"),
+                label: Some("WETH".to_string()),
+                category: ContractCategory::Token,
+            }],
+            events: vec![],
+            token_flows: vec![],
+            function_call: None,
+            explorer_url: None,
+            failure_reason: None,
+            labels: vec![],
+            enriched: false,
+            warnings: vec![],
+        }
+    }
+
+    #[test]
+    fn test_format_analysis_basic() {
+        let analysis = make_test_analysis();
+        let output = format_analysis(&analysis);
+
+        assert!(output.contains("Transaction: 0x1234"));
+        assert!(output.contains("Block: 18500000"));
+        assert!(output.contains("Status: Success"));
+        assert!(output.contains("WETH"));
+    }
+
+    #[test]
+    fn test_format_analysis_failed_tx() {
+        let mut analysis = make_test_analysis();
+        analysis.status = false;
+        let output = format_analysis(&analysis);
+
+        assert!(output.contains("Status: Failed"));
+    }
+
+    #[test]
+    fn test_format_analysis_contract_creation() {
+        let mut analysis = make_test_analysis();
+        analysis.to = None;
+        let output = format_analysis(&analysis);
+
+        assert!(output.contains("Contract Creation"));
+    }
+
+    // LOW-007: Edge case tests
+
+    #[test]
+    fn test_u256_to_eth_f64_normal() {
+        // 1 ETH = 1e18 wei
+        let one_eth = U256::from(1_000_000_000_000_000_000u128);
+        let result = u256_to_eth_f64(&one_eth);
+        assert!((result - 1.0).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_u256_to_eth_f64_small() {
+        // 1 wei
+        let one_wei = U256::from(1u64);
+        let result = u256_to_eth_f64(&one_wei);
+        assert!(result > 0.0 && result < 1e-17);
+    }
+
+    #[test]
+    fn test_u256_to_eth_f64_zero() {
+        let zero = U256::ZERO;
+        let result = u256_to_eth_f64(&zero);
+        assert_eq!(result, 0.0);
+    }
+
+    #[test]
+    fn test_u256_to_eth_f64_large() {
+        // Value larger than u128::MAX
+        let large = U256::MAX;
+        let result = u256_to_eth_f64(&large);
+        // Should not panic, and should return a large positive number
+        assert!(result > 0.0);
+        assert!(result.is_finite() || result == f64::MAX);
+    }
+
+    #[test]
+    fn test_format_token_amount_normal() {
+        // 1.23 tokens (with 18 decimals)
+        let amount = "1230000000000000000";
+        let result = format_token_amount(amount, 18);
+        assert_eq!(result, "1.23");
+    }
+
+    #[test]
+    fn test_format_token_amount_small() {
+        // 0.01 tokens
+        let amount = "10000000000000000";
+        let result = format_token_amount(amount, 18);
+        assert_eq!(result, "0.01");
+    }
+
+    #[test]
+    fn test_format_token_amount_very_small() {
+        // Very small amount (less than 0.01)
+        let amount = "1000000000000";
+        let result = format_token_amount(amount, 18);
+        assert_eq!(result, "0.00");
+    }
+
+    #[test]
+    fn test_format_token_amount_empty() {
+        let result = format_token_amount("", 18);
+        assert_eq!(result, "0.00");
+    }
+
+    #[test]
+    fn test_format_token_amount_invalid() {
+        let result = format_token_amount("not_a_number", 18);
+        assert_eq!(result, "0.00");
+    }
+
+    #[test]
+    fn test_format_token_amount_large() {
+        // 1 million tokens
+        let amount = "1000000000000000000000000";
+        let result = format_token_amount(amount, 18);
+        assert_eq!(result, "1000000.00");
+    }
+
+    #[test]
+    fn test_format_analysis_many_events() {
+        let mut analysis = make_test_analysis();
+        // Add 25 events (more than the 20 limit)
+        for i in 0..25 {
+            analysis.events.push(AnalyzedEvent {
+                log_index: i as u64,
+                address: address!("c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"),
+                address_label: Some("WETH".to_string()),
+                name: Some("Transfer".to_string()),
+                signature: Some("Transfer(address,address,uint256)".to_string()),
+                params: HashMap::new(),
+                topic0: B256::ZERO,
+                is_transfer: true,
+            });
+        }
+
+        let output = format_analysis(&analysis);
+        assert!(output.contains("... and 5 more"));
+    }
+
+    #[test]
+    fn test_format_analysis_many_token_flows() {
+        use crate::tx::types::TokenFlow;
+
+        let mut analysis = make_test_analysis();
+        // Add 20 token flows (more than the 15 limit)
+        for i in 0..20 {
+            analysis.token_flows.push(TokenFlow {
+                token: address!("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"),
+                token_label: Some("USDC".to_string()),
+                token_decimals: Some(6),
+                from: address!("d8da6bf26964af9d7eed9e03e53415d37aa96045"),
+                from_label: None,
+                to: address!("c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"),
+                to_label: None,
+                amount: "1000000".to_string(),
+                log_index: i as u64,
+            });
+        }
+
+        let output = format_analysis(&analysis);
+        assert!(output.contains("... and 5 more"));
+    }
+
+    #[test]
+    fn test_format_analysis_with_function_call() {
+        let mut analysis = make_test_analysis();
+        analysis.function_call = Some(FunctionCall {
+            selector: "0xa9059cbb".to_string(),
+            name: Some("transfer".to_string()),
+            signature: Some("transfer(address,uint256)".to_string()),
+            params: vec![
+                FunctionParam {
+                    name: "to".to_string(),
+                    ty: "address".to_string(),
+                    value: "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string(),
+                },
+                FunctionParam {
+                    name: "amount".to_string(),
+                    ty: "uint256".to_string(),
+                    value: "1000000000000000000".to_string(),
+                },
+            ],
+        });
+
+        let output = format_analysis(&analysis);
+        assert!(output.contains("Function Call:"));
+        assert!(output.contains("transfer"));
+        assert!(output.contains("0xa9059cbb"));
+        assert!(output.contains("Parameters:"));
+    }
+
+    #[test]
+    fn test_format_analysis_empty_contracts() {
+        let mut analysis = make_test_analysis();
+        analysis.contracts.clear();
+        let output = format_analysis(&analysis);
+
+        // Should still have the "Contracts Involved:" header
+        assert!(output.contains("Contracts Involved:"));
+    }
+
+    #[test]
+    fn test_format_analysis_large_value() {
+        let mut analysis = make_test_analysis();
+        // Set a very large value (> u128::MAX would overflow, but let's test a large u128)
+        analysis.value = U256::from(u128::MAX);
+        let output = format_analysis(&analysis);
+
+        // Should not panic and should contain ETH formatting
+        assert!(output.contains("ETH"));
+    }
+
+    /// `format_analysis` as it was before labels and the explorer link
+    /// moved into the model, kept to pin the text output
+    fn legacy_format_analysis(analysis: &TransactionAnalysis) -> String {
+        // LOW-005 fix: Pre-allocate for typical output size
+        let mut output = String::with_capacity(2048);
+
+        // Header
+        output.push_str(&format!("Transaction: {:#x}\n", analysis.hash));
+        output.push_str(&format!("Block: {}\n", analysis.block_number));
+        let from_label = analysis
+            .from_label
+            .as_ref()
+            .map(|l| format!(" ({})", l))
+            .unwrap_or_default();
+        output.push_str(&format!("From: {:#x}{}\n", analysis.from, from_label));
+
+        if let Some(to) = analysis.to {
+            let label = analysis
+                .to_label
+                .as_deref()
+                .or_else(|| get_label(&to))
+                .map(|l| format!(" ({})", l))
+                .unwrap_or_default();
+            output.push_str(&format!("To: {:#x}{}\n", to, label));
+        } else {
+            output.push_str("To: Contract Creation\n");
+        }
+
+        // HIGH-001 fix: Use safe U256 to f64 conversion
+        output.push_str(&format!(
+            "Value: {} wei ({:.6} ETH)\n",
+            analysis.value,
+            u256_to_eth_f64(&analysis.value)
+        ));
+        output.push_str(&format!("Gas Used: {}\n", analysis.gas_used));
+        output.push_str(&format!(
+            "Status: {}\n",
+            if analysis.status { "Success" } else { "Failed" }
+        ));
+
+        // Contracts
+        output.push_str("\nContracts Involved:\n");
+        for contract in &analysis.contracts {
+            let label = contract
+                .label
+                .as_ref()
+                .map(|l| format!(" {}", l))
+                .unwrap_or_else(|| format!(" ({:?})", contract.category));
+            output.push_str(&format!("  {:#x}{}\n", contract.address, label));
+        }
+
+        // Events summary
+        output.push_str(&format!("\nEvents ({}):\n", analysis.events.len()));
+        for (i, event) in analysis.events.iter().enumerate().take(20) {
+            let name = event.name.as_deref().unwrap_or("Unknown");
+            let contract = event
+                .address_label
+                .as_ref()
+                .map(|l| format!("({})", l))
+                .unwrap_or_default();
+            output.push_str(&format!("  [{}] {} {}\n", i, name, contract));
+        }
+        if analysis.events.len() > 20 {
+            output.push_str(&format!("  ... and {} more\n", analysis.events.len() - 20));
+        }
+
+        // Token flows
+        if !analysis.token_flows.is_empty() {
+            output.push_str(&format!(
+                "\nToken Transfers ({}):\n",
+                analysis.token_flows.len()
+            ));
+            for flow in analysis.token_flows.iter().take(15) {
+                let token = flow.token_label.as_deref().unwrap_or("???");
+                // Prefer the label, otherwise a shortened address
+                let party = |address: &Address, label: &Option<String>| match label {
+                    Some(label) => label.clone(),
+                    None => {
+                        let short = format!("{:#x}", address);
+                        format!("{}...", &short[..short.len().min(12)])
+                    }
+                };
+
+                // LOW-002 fix: Use safe token amount formatting
+                let amount_display = format_token_amount(&flow.amount, 18);
+
+                output.push_str(&format!(
+                    "  {} {} → {} {} {}\n",
+                    token,
+                    party(&flow.from, &flow.from_label),
+                    party(&flow.to, &flow.to_label),
+                    amount_display,
+                    token
+                ));
+            }
+            if analysis.token_flows.len() > 15 {
+                output.push_str(&format!(
+                    "  ... and {} more\n",
+                    analysis.token_flows.len() - 15
+                ));
+            }
+        }
+
+        // Function call
+        if let Some(func) = &analysis.function_call {
+            output.push_str("\nFunction Call:\n");
+            let name = func.name.as_deref().unwrap_or("Unknown");
+            output.push_str(&format!("  {} ({})\n", name, func.selector));
+            if let Some(sig) = &func.signature {
+                output.push_str(&format!("  Signature: {}\n", sig));
+            }
+            if !func.params.is_empty() {
+                output.push_str("  Parameters:\n");
+                for param in func.params.iter().take(10) {
+                    output.push_str(&format!(
+                        "    {} ({}): {}\n",
+                        param.name, param.ty, param.value
+                    ));
+                }
+                if func.params.len() > 10 {
+                    output.push_str(&format!("    ... and {} more\n", func.params.len() - 10));
+                }
+            }
+        }
+
+        output
+    }
+
+    /// The old CLI printed the explorer link after the formatted analysis
+    fn legacy_cli_output(analysis: &TransactionAnalysis, explorer: Option<&str>) -> String {
+        let mut out = format!("{}\n", legacy_format_analysis(analysis));
+        if let Some(explorer) = explorer {
+            out.push_str(&format!(
+                "\nExplorer: {}/tx/{:#x}\n",
+                explorer, analysis.hash
+            ));
+        }
+        out
+    }
+
+    /// What the CLI prints now: `println!("{}", format_analysis(..))`
+    fn cli_output(analysis: &TransactionAnalysis) -> String {
+        format!("{}\n", format_analysis(analysis))
+    }
+
+    fn regression_fixtures() -> Vec<TransactionAnalysis> {
+        let base = make_test_analysis();
+
+        let mut creation = base.clone();
+        creation.to = None;
+
+        let mut busy = base.clone();
+        for i in 0..25u64 {
+            busy.events.push(AnalyzedEvent {
+                log_index: i,
+                address: address!("c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"),
+                address_label: Some("WETH".to_string()),
+                name: (i % 3 != 0).then(|| "Transfer".to_string()),
+                signature: None,
+                params: HashMap::new(),
+                topic0: B256::ZERO,
+                is_transfer: true,
+            });
+        }
+        for i in 0..18u64 {
+            busy.token_flows.push(TokenFlow {
+                token: address!("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"),
+                token_label: (i % 2 == 0).then(|| "USDC".to_string()),
+                token_decimals: Some(6),
+                from: address!("d8da6bf26964af9d7eed9e03e53415d37aa96045"),
+                from_label: (i % 4 == 0).then(|| "me".to_string()),
+                to: address!("1111111111111111111111111111111111111111"),
+                to_label: None,
+                amount: (1_000_000 * (i + 1)).to_string(),
+                log_index: i,
+            });
+        }
+        busy.function_call = Some(FunctionCall {
+            selector: "0x3593564c".to_string(),
+            name: Some("execute".to_string()),
+            signature: Some("execute(bytes,bytes[],uint256)".to_string()),
+            params: (0..12)
+                .map(|i| FunctionParam {
+                    name: format!("p{i}"),
+                    ty: "uint256".to_string(),
+                    value: i.to_string(),
+                })
+                .collect(),
+        });
+
+        let mut unlabeled_to = base.clone();
+        unlabeled_to.to = Some(address!("2222222222222222222222222222222222222222"));
+        unlabeled_to.from_label = Some("treasury".to_string());
+
+        vec![base, creation, busy, unlabeled_to]
+    }
+
+    #[test]
+    fn test_text_output_matches_legacy() {
+        for explorer in [Some("https://etherscan.io"), None] {
+            for legacy in regression_fixtures() {
+                let mut analysis = legacy.clone();
+                apply_builtin_labels(&mut analysis);
+                analysis.explorer_url = explorer.map(|e| format!("{}/tx/{:#x}", e, analysis.hash));
+                assert_eq!(
+                    cli_output(&analysis),
+                    legacy_cli_output(&legacy, explorer),
+                    "text output changed for {:?}",
+                    legacy.to
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_text_output_failed_tx_adds_reason_only() {
+        let mut legacy = make_test_analysis();
+        legacy.status = false;
+        let mut analysis = legacy.clone();
+        apply_builtin_labels(&mut analysis);
+        analysis.failure_reason = Some("execution reverted".to_string());
+
+        let expected = legacy_format_analysis(&legacy).replace(
+            "Status: Failed\n",
+            "Status: Failed\nFailure Reason: execution reverted\n",
+        );
+        assert_eq!(format_analysis(&analysis), expected);
+    }
+
+    #[test]
+    fn test_builtin_labels_recorded() {
+        let mut analysis = make_test_analysis();
+        apply_builtin_labels(&mut analysis);
+        assert_eq!(
+            analysis.to_label.as_deref(),
+            get_label(&analysis.to.unwrap())
+        );
+        let label = analysis.label_for(&analysis.to.unwrap()).unwrap();
+        assert_eq!(label.label, "WETH");
+        assert_eq!(label.source, crate::tx::types::LabelSource::Builtin);
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let mut analysis = regression_fixtures().swap_remove(2);
+        apply_builtin_labels(&mut analysis);
+        analysis.status = false;
+        analysis.failure_reason = Some("out of gas (used 21000 of 21000)".to_string());
+        analysis.explorer_url = Some("https://etherscan.io/tx/0x1234".to_string());
+        analysis.token_flows[1].token_decimals = None;
+        analysis.warnings.push(Warning::new(
+            WarningKind::MalformedEvent,
+            Some(address!("c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2")),
+            "malformed Transfer event data: expected >= 32 bytes, got 0",
+        ));
+
+        let md = analysis.to_markdown();
+        assert!(md.starts_with("## Transaction `0x1234"));
+        assert!(md.contains("| To | `0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2` (WETH) |"));
+        assert!(md.contains("| Status | Failed (out of gas (used 21000 of 21000)) |"));
+        assert!(md.contains("[View on explorer](https://etherscan.io/tx/0x1234)"));
+        assert!(md.contains("`execute(bytes,bytes[],uint256)` (`0x3593564c`)"));
+        assert!(md.contains("### Token Transfers (18)"));
+        assert!(md.contains("| 1.00 |"));
+        assert!(md.contains("| 2000000 (raw) |"));
+        assert!(md.contains("### Events (25)"));
+        assert!(
+            md.contains("### Warnings\n\n- 0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2: malformed")
+        );
+        // Nothing is truncated in Markdown
+        assert_eq!(md.matches("`p").count(), 12);
+    }
+
+    #[test]
+    fn test_to_compact_json() {
+        let mut analysis = regression_fixtures().swap_remove(2);
+        apply_builtin_labels(&mut analysis);
+        let compact = analysis.to_compact_json();
+        assert!(!compact.contains('\n'));
+
+        let value: serde_json::Value = serde_json::from_str(&compact).unwrap();
+        assert_eq!(value["status"], "success");
+        assert_eq!(value["block"], 18_500_000);
+        assert_eq!(value["to"]["label"], "WETH");
+        assert!(value["from"].get("label").is_none());
+        assert_eq!(value["function"], "execute(bytes,bytes[],uint256)");
+        assert_eq!(value["transfers"].as_array().unwrap().len(), 18);
+        assert_eq!(value["transfers"][0]["decimals"], 6);
+        assert_eq!(value["events"][0], "Unknown");
+        assert_eq!(value["events"][1], "Transfer");
+        assert!(value["warnings"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_analysis_json_backward_compatible() {
+        // Analyses serialized before the new fields existed still load
+        let mut json = serde_json::to_value(make_test_analysis()).unwrap();
+        for key in [
+            "labels",
+            "warnings",
+            "explorer_url",
+            "failure_reason",
+            "enriched",
+        ] {
+            assert!(
+                json.get(key).is_none(),
+                "{key} should be skipped when empty"
+            );
+        }
+        json.as_object_mut().unwrap().remove("function_call");
+        json["function_call"] = serde_json::Value::Null;
+        let back: TransactionAnalysis = serde_json::from_value(json).unwrap();
+        assert!(back.labels.is_empty() && back.warnings.is_empty() && !back.enriched);
+    }
+}
@@ -15,8 +15,14 @@ pub struct TransactionAnalysis {
     pub block_number: u64,
     /// From address
     pub from: Address,
+    /// Address book label for `from`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_label: Option<String>,
     /// To address (None for contract creation)
     pub to: Option<Address>,
+    /// Address book label for `to`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_label: Option<String>,
     /// Value transferred in wei
     pub value: U256,
     /// Gas used
@@ -31,6 +37,94 @@ pub struct TransactionAnalysis {
     pub token_flows: Vec<TokenFlow>,
     /// Decoded function call (if available)
     pub function_call: Option<FunctionCall>,
+    /// Block explorer link for the transaction
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explorer_url: Option<String>,
+    /// Why the transaction failed, as far as the receipt tells (failed txs only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_reason: Option<String>,
+    /// Every label applied to an address, with where it came from
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<AddressLabel>,
+    /// Whether Etherscan/token metadata enrichment was requested
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub enriched: bool,
+    /// Non-fatal problems hit during analysis
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<Warning>,
+}
+
+/// Where an address label came from
+///
+/// Ordered by precedence: a label from a later source replaces one from an
+/// earlier source.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum LabelSource {
+    /// Built-in table of well-known contracts and tokens
+    Builtin,
+    /// Token `symbol()` read during enrichment
+    TokenContract,
+    /// Etherscan contract name read during enrichment
+    Etherscan,
+    /// User address book
+    AddressBook,
+}
+
+/// A label applied to an address
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AddressLabel {
+    /// Labeled address
+    pub address: Address,
+    /// Label text
+    pub label: String,
+    /// Where the label came from
+    pub source: LabelSource,
+}
+
+/// Kind of non-fatal analysis problem
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningKind {
+    /// A known event had data too short to decode
+    MalformedEvent,
+    /// An Etherscan or token metadata lookup failed
+    EnrichmentFailed,
+}
+
+/// A non-fatal problem hit during analysis
+///
+/// Returned on the analysis instead of being printed, so library callers
+/// decide how to surface it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Warning {
+    /// What went wrong
+    pub kind: WarningKind,
+    /// Address involved, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address>,
+    /// Human-readable detail
+    pub message: String,
+}
+
+impl Warning {
+    /// Create a warning about `address`
+    pub fn new(kind: WarningKind, address: Option<Address>, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            address,
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.address {
+            Some(address) => write!(f, "{:#x}: {}", address, self.message),
+            None => f.write_str(&self.message),
+        }
+    }
 }
 
 /// Information about a contract involved in the transaction
@@ -108,6 +202,9 @@ pub struct TokenFlow {
     pub token: Address,
     /// Token symbol/label
     pub token_label: Option<String>,
+    /// Token decimals (only known after enrichment)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_decimals: Option<u8>,
     /// From address
     pub from: Address,
     /// From label
@@ -175,19 +272,71 @@ impl TransactionAnalysis {
     pub fn new(data: &RawTxData) -> Self {
         let tx = &data.tx;
         let receipt = &data.receipt;
+        let status = receipt.status();
+        let gas_limit = tx.inner.gas_limit();
+        let failure_reason = (!status).then(|| {
+            if receipt.gas_used >= gas_limit {
+                format!("out of gas (used {} of {})", receipt.gas_used, gas_limit)
+            } else {
+                "execution reverted".to_string()
+            }
+        });
 
         Self {
             hash: *tx.inner.tx_hash(),
             block_number: receipt.block_number.unwrap_or(0),
             from: tx.inner.signer(),
+            from_label: None,
             to: tx.inner.to(),
+            to_label: None,
             value: tx.inner.value(),
             gas_used: receipt.gas_used,
-            status: receipt.status(),
+            status,
             contracts: Vec::new(),
             events: Vec::new(),
             token_flows: Vec::new(),
             function_call: None,
+            explorer_url: None,
+            failure_reason,
+            labels: Vec::new(),
+            enriched: false,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Record a label for `address`
+    ///
+    /// An existing label is only replaced by one from a source of equal or
+    /// higher precedence (see [`LabelSource`]). Labels stay sorted by address.
+    pub fn record_label(
+        &mut self,
+        address: Address,
+        label: impl Into<String>,
+        source: LabelSource,
+    ) {
+        let label = label.into();
+        match self.labels.binary_search_by_key(&address, |l| l.address) {
+            Ok(i) if self.labels[i].source <= source => {
+                self.labels[i].label = label;
+                self.labels[i].source = source;
+            }
+            Ok(_) => {}
+            Err(i) => self.labels.insert(
+                i,
+                AddressLabel {
+                    address,
+                    label,
+                    source,
+                },
+            ),
         }
     }
+
+    /// Label recorded for `address`, if any
+    pub fn label_for(&self, address: &Address) -> Option<&AddressLabel> {
+        self.labels
+            .binary_search_by_key(address, |l| l.address)
+            .ok()
+            .map(|i| &self.labels[i])
+    }
 }
@@ -68,6 +68,46 @@ pub fn unix_timestamp_ms() -> u128 {
         .as_millis()
 }
 
+/// Parse a duration string like "30d", "6h", "2w", "90m", "30s" into seconds
+///
+/// Supported units:
+/// - s, sec, seconds: seconds
+/// - m, min, minutes: minutes
+/// - h, hr, hours: hours
+/// - d, days: days
+/// - w, weeks: weeks
+pub fn parse_duration_string(s: &str) -> anyhow::Result<f64> {
+    let s = s.trim().to_lowercase();
+
+    // Try to find where the number ends and unit begins
+    let (num_str, unit) = if let Some(pos) = s.find(|c: char| c.is_alphabetic()) {
+        (&s[..pos], s[pos..].trim())
+    } else {
+        // No unit found, assume days for backwards compatibility
+        (s.as_str(), "d")
+    };
+
+    let value: f64 = num_str
+        .trim()
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid duration number: '{num_str}'"))?;
+
+    let seconds = match unit {
+        "s" | "sec" | "secs" | "second" | "seconds" => value,
+        "m" | "min" | "mins" | "minute" | "minutes" => value * 60.0,
+        "h" | "hr" | "hrs" | "hour" | "hours" => value * 3600.0,
+        "d" | "day" | "days" => value * 86400.0,
+        "w" | "wk" | "wks" | "week" | "weeks" => value * 604800.0,
+        _ => {
+            return Err(anyhow::anyhow!(
+                "Unknown duration unit: '{unit}'. Use s/m/h/d/w (e.g., 30d, 6h, 2w, 90m, 30s)"
+            ))
+        }
+    };
+
+    Ok(seconds)
+}
+
 /// Validate that a string looks like an Ethereum address
 ///
 /// Returns true if the string starts with "0x" and is 42 characters long
@@ -0,0 +1,821 @@
+//! Balance and transaction watching for `ethcli account watch`.
+//!
+//! Every poll produces a [`Snapshot`] (native and token balances at one
+//! block) plus the account's transactions since the previous poll.
+//! [`AccountWatch::observe`] compares it with the persisted
+//! [`AccountState`] and returns a [`WatchEvent`] for each change.
+//!
+//! Thresholds are measured from the balance at the last alert (or at the
+//! first poll), not from the previous poll, so slow drifts still alert once
+//! they add up. An event is an alert when:
+//!
+//! - a balance moved at least its minimum change away from that reference;
+//! - a transaction was sent from the account (any value);
+//! - a transaction sent to the account carried at least the native minimum.
+//!
+//! State is saved after every poll so a restarted watcher neither re-alerts
+//! on changes it already reported nor misses ones that happened while it was
+//! down.
+
+use crate::config::Chain;
+use crate::error::{ConfigError, Result};
+use alloy::primitives::utils::{format_units, parse_units, ParseUnits};
+use alloy::primitives::{Address, U256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Current state file format version
+pub const WATCH_STATE_VERSION: u32 = 1;
+
+/// Longest wait between polls while the RPC keeps failing
+pub const MAX_BACKOFF: Duration = Duration::from_secs(600);
+
+/// Parse a native amount into wei
+///
+/// Accepts an `eth`/`ether`, `gwei` or `wei` suffix; plain numbers are in
+/// whole native units (`0.5` is 0.5 ETH).
+pub fn parse_native_amount(s: &str) -> Result<U256> {
+    let lower = s.trim().to_lowercase();
+    let (number, unit) = if let Some(n) = lower.strip_suffix("gwei") {
+        (n, "gwei")
+    } else if let Some(n) = lower.strip_suffix("wei") {
+        (n, "wei")
+    } else if let Some(n) = lower
+        .strip_suffix("ether")
+        .or_else(|| lower.strip_suffix("eth"))
+    {
+        (n, "ether")
+    } else {
+        (lower.as_str(), "ether")
+    };
+    let decimals = match unit {
+        "wei" => 0,
+        "gwei" => 9,
+        _ => 18,
+    };
+    parse_token_amount(number, decimals)
+        .map_err(|_| ConfigError::InvalidAmount(format!("'{}'", s)).into())
+}
+
+/// Parse a token amount in whole tokens into base units
+///
+/// `"1000"` with 6 decimals is 1,000,000,000. More fractional digits than
+/// the token has is an error rather than a silent truncation.
+pub fn parse_token_amount(s: &str, decimals: u8) -> Result<U256> {
+    let s = s.trim();
+    let fraction = s
+        .split_once('.')
+        .map_or("", |(_, f)| f.trim_end_matches('0'));
+    if fraction.len() > usize::from(decimals) {
+        return Err(ConfigError::InvalidAmount(format!(
+            "'{}' has more than {} decimal places",
+            s, decimals
+        ))
+        .into());
+    }
+    match parse_units(s, decimals) {
+        Ok(ParseUnits::U256(value)) => Ok(value),
+        Ok(ParseUnits::I256(_)) => {
+            Err(ConfigError::InvalidAmount(format!("'{}' is negative", s)).into())
+        }
+        Err(e) => Err(ConfigError::InvalidAmount(format!("'{}': {}", s, e)).into()),
+    }
+}
+
+/// A token whose balance is watched
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchedToken {
+    /// Token contract
+    pub address: Address,
+    /// Symbol, for event output
+    pub symbol: String,
+    /// Token decimals
+    pub decimals: u8,
+    /// Smallest change (in base units) that alerts
+    pub min_change: U256,
+}
+
+/// Balances at one block
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    /// Block the balances were read at
+    pub block: u64,
+    /// Native balance in wei
+    pub native: U256,
+    /// Token balances in base units
+    pub tokens: BTreeMap<Address, U256>,
+}
+
+/// A transaction to or from the watched account
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchedTx {
+    pub hash: String,
+    pub block: u64,
+    pub from: Address,
+    pub to: Option<Address>,
+    /// Value in wei
+    pub value: U256,
+}
+
+/// A balance as last seen, and as of the last alert
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrackedBalance {
+    /// Balance at the previous poll
+    pub last: U256,
+    /// Balance thresholds are measured from
+    pub reference: U256,
+}
+
+impl TrackedBalance {
+    fn new(balance: U256) -> Self {
+        Self {
+            last: balance,
+            reference: balance,
+        }
+    }
+}
+
+/// Persisted state of one watched account
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountState {
+    /// Last block observed (`None` before the first poll)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block: Option<u64>,
+    /// Native balance
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub native: Option<TrackedBalance>,
+    /// Token balances, keyed by lowercase token address
+    #[serde(default)]
+    pub tokens: BTreeMap<String, TrackedBalance>,
+}
+
+/// Persisted watch state, keyed by `<chain>:<address>`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchState {
+    /// State file format version
+    pub version: u32,
+    /// Per-account state
+    #[serde(default)]
+    pub accounts: BTreeMap<String, AccountState>,
+}
+
+impl Default for WatchState {
+    fn default() -> Self {
+        Self {
+            version: WATCH_STATE_VERSION,
+            accounts: BTreeMap::new(),
+        }
+    }
+}
+
+impl WatchState {
+    /// Get the default state file path
+    ///
+    /// Can be overridden by setting the `ETHCLI_CONFIG_DIR` environment variable.
+    pub fn default_path() -> PathBuf {
+        if let Ok(config_dir) = std::env::var("ETHCLI_CONFIG_DIR") {
+            return PathBuf::from(config_dir).join("account_watch.json");
+        }
+
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("ethcli")
+            .join("account_watch.json")
+    }
+
+    /// Load from a path, starting empty if the file does not exist
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| ConfigError::InvalidFile(format!("{}: {}", path.display(), e)))?;
+        let state: Self = serde_json::from_str(&content)
+            .map_err(|e| ConfigError::InvalidFile(format!("{}: {}", path.display(), e)))?;
+        if state.version != WATCH_STATE_VERSION {
+            return Err(ConfigError::InvalidFile(format!(
+                "{}: unsupported watch state version {} (expected {})",
+                path.display(),
+                state.version,
+                WATCH_STATE_VERSION
+            ))
+            .into());
+        }
+        Ok(state)
+    }
+
+    /// Save to a path (write to a temp file, then rename)
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                ConfigError::InvalidFile(format!("Failed to create directory: {}", e))
+            })?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(|e| {
+            ConfigError::InvalidFile(format!("Failed to serialize watch state: {}", e))
+        })?;
+        let temp_path = path.with_extension(format!("{}.tmp", std::process::id()));
+        std::fs::write(&temp_path, content)
+            .and_then(|_| std::fs::rename(&temp_path, path))
+            .map_err(|e| {
+                let _ = std::fs::remove_file(&temp_path);
+                ConfigError::InvalidFile(format!("{}: {}", path.display(), e))
+            })?;
+        Ok(())
+    }
+
+    /// State key of an account
+    pub fn key(chain: Chain, address: Address) -> String {
+        format!("{}:{:#x}", chain.name(), address)
+    }
+
+    /// State of an account, created empty on first use
+    pub fn account(&mut self, chain: Chain, address: Address) -> &mut AccountState {
+        self.accounts.entry(Self::key(chain, address)).or_default()
+    }
+}
+
+/// Direction of a watched transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TxDirection {
+    In,
+    Out,
+    /// Sent from the account to itself
+    #[serde(rename = "self")]
+    SelfTransfer,
+}
+
+/// What changed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WatchChange {
+    /// Native balance changed
+    NativeBalance {
+        symbol: String,
+        previous: String,
+        current: String,
+        /// Signed change since the previous poll, in native units
+        change: String,
+        /// Signed change since the previous poll, in wei
+        change_wei: String,
+    },
+    /// Token balance changed
+    TokenBalance {
+        token: String,
+        symbol: String,
+        decimals: u8,
+        previous: String,
+        current: String,
+        /// Signed change since the previous poll, in whole tokens
+        change: String,
+        /// Signed change since the previous poll, in base units
+        change_raw: String,
+    },
+    /// Transaction to or from the account
+    Transaction {
+        hash: String,
+        from: String,
+        to: Option<String>,
+        direction: TxDirection,
+        /// Value in native units
+        value: String,
+        value_wei: String,
+    },
+}
+
+/// One change event (printed as an NDJSON line)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WatchEvent {
+    /// Chain name
+    pub chain: String,
+    /// Watched address
+    pub address: String,
+    /// Block of the poll (or of the transaction)
+    pub block: u64,
+    #[serde(flatten)]
+    pub change: WatchChange,
+    /// Whether a threshold was crossed (the notify command runs for these)
+    pub alert: bool,
+}
+
+/// Exact decimal amount without trailing zeros or thousands separators
+fn format_amount(value: U256, decimals: u8) -> String {
+    let formatted = format_units(value, decimals).unwrap_or_else(|_| value.to_string());
+    match formatted.split_once('.') {
+        Some((int, frac)) if frac.trim_end_matches('0').is_empty() => int.to_string(),
+        Some((int, frac)) => format!("{}.{}", int, frac.trim_end_matches('0')),
+        None => formatted,
+    }
+}
+
+/// Signed difference `current - previous`, formatted with `decimals`
+fn signed_change(previous: U256, current: U256, decimals: u8) -> (String, String) {
+    let (sign, magnitude) = if current >= previous {
+        ("+", current - previous)
+    } else {
+        ("-", previous - current)
+    };
+    (
+        format!("{}{}", sign, format_amount(magnitude, decimals)),
+        format!("{}{}", sign, magnitude),
+    )
+}
+
+/// Update a tracked balance; returns `(previous, alert)` if it changed
+fn track(tracked: &mut TrackedBalance, current: U256, min_change: U256) -> Option<(U256, bool)> {
+    if current == tracked.last {
+        return None;
+    }
+    let previous = tracked.last;
+    tracked.last = current;
+    let drift = current.abs_diff(tracked.reference);
+    let alert = !drift.is_zero() && drift >= min_change;
+    if alert {
+        tracked.reference = current;
+    }
+    Some((previous, alert))
+}
+
+/// What to watch on one account
+#[derive(Debug, Clone)]
+pub struct AccountWatch {
+    pub chain: Chain,
+    pub address: Address,
+    /// Smallest native balance change (in wei) that alerts
+    pub min_native_change: U256,
+    /// Watched tokens
+    pub tokens: Vec<WatchedToken>,
+}
+
+impl AccountWatch {
+    /// Compare a poll with the saved state and update the state
+    ///
+    /// The first poll of an account (or of a newly added token) only records
+    /// a baseline. Transactions at or below the last observed block are
+    /// ignored, so overlapping scans don't repeat events.
+    pub fn observe(
+        &self,
+        state: &mut AccountState,
+        snapshot: &Snapshot,
+        txs: &[WatchedTx],
+    ) -> Vec<WatchEvent> {
+        let event = |block: u64, change: WatchChange, alert: bool| WatchEvent {
+            chain: self.chain.name().to_string(),
+            address: format!("{:#x}", self.address),
+            block,
+            change,
+            alert,
+        };
+        let mut events = Vec::new();
+
+        if let Some(last_block) = state.block {
+            let mut txs: Vec<&WatchedTx> = txs
+                .iter()
+                .filter(|tx| tx.block > last_block && tx.block <= snapshot.block)
+                .collect();
+            txs.sort_by_key(|tx| tx.block);
+            for tx in txs {
+                let direction = match (tx.from == self.address, tx.to == Some(self.address)) {
+                    (true, true) => TxDirection::SelfTransfer,
+                    (true, false) => TxDirection::Out,
+                    _ => TxDirection::In,
+                };
+                let alert = direction != TxDirection::In || tx.value >= self.min_native_change;
+                events.push(event(
+                    tx.block,
+                    WatchChange::Transaction {
+                        hash: tx.hash.clone(),
+                        from: format!("{:#x}", tx.from),
+                        to: tx.to.map(|to| format!("{:#x}", to)),
+                        direction,
+                        value: format_amount(tx.value, 18),
+                        value_wei: tx.value.to_string(),
+                    },
+                    alert,
+                ));
+            }
+        }
+        if state.block.is_none_or(|block| snapshot.block > block) {
+            state.block = Some(snapshot.block);
+        }
+
+        match &mut state.native {
+            None => state.native = Some(TrackedBalance::new(snapshot.native)),
+            Some(tracked) => {
+                if let Some((previous, alert)) =
+                    track(tracked, snapshot.native, self.min_native_change)
+                {
+                    let (change, change_wei) = signed_change(previous, snapshot.native, 18);
+                    events.push(event(
+                        snapshot.block,
+                        WatchChange::NativeBalance {
+                            symbol: self.chain.native_symbol().to_string(),
+                            previous: format_amount(previous, 18),
+                            current: format_amount(snapshot.native, 18),
+                            change,
+                            change_wei,
+                        },
+                        alert,
+                    ));
+                }
+            }
+        }
+
+        for token in &self.tokens {
+            let Some(&current) = snapshot.tokens.get(&token.address) else {
+                continue;
+            };
+            let key = format!("{:#x}", token.address);
+            let Some(tracked) = state.tokens.get_mut(&key) else {
+                state.tokens.insert(key, TrackedBalance::new(current));
+                continue;
+            };
+            if let Some((previous, alert)) = track(tracked, current, token.min_change) {
+                let (change, change_raw) = signed_change(previous, current, token.decimals);
+                events.push(event(
+                    snapshot.block,
+                    WatchChange::TokenBalance {
+                        token: key,
+                        symbol: token.symbol.clone(),
+                        decimals: token.decimals,
+                        previous: format_amount(previous, token.decimals),
+                        current: format_amount(current, token.decimals),
+                        change,
+                        change_raw,
+                    },
+                    alert,
+                ));
+            }
+        }
+
+        events
+    }
+}
+
+/// Exponential backoff for failed polls
+///
+/// The first failure waits one interval, then two, four, ... up to
+/// [`MAX_BACKOFF`] (or the interval itself, if that is longer).
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    interval: Duration,
+    failures: u32,
+}
+
+impl Backoff {
+    /// Backoff for a poll interval
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            failures: 0,
+        }
+    }
+
+    /// Record a failed poll and return how long to wait before the next
+    pub fn failure(&mut self) -> Duration {
+        let delay = self
+            .interval
+            .saturating_mul(2u32.saturating_pow(self.failures))
+            .min(MAX_BACKOFF.max(self.interval));
+        self.failures = self.failures.saturating_add(1);
+        delay
+    }
+
+    /// Record a successful poll
+    pub fn success(&mut self) {
+        self.failures = 0;
+    }
+
+    /// Consecutive failed polls
+    pub fn failures(&self) -> u32 {
+        self.failures
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ETH: u128 = 1_000_000_000_000_000_000;
+
+    fn wallet() -> Address {
+        Address::repeat_byte(0xaa)
+    }
+
+    fn usdc() -> Address {
+        "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"
+            .parse()
+            .unwrap()
+    }
+
+    fn watch(min_native: U256, min_usdc: U256) -> AccountWatch {
+        AccountWatch {
+            chain: Chain::Ethereum,
+            address: wallet(),
+            min_native_change: min_native,
+            tokens: vec![WatchedToken {
+                address: usdc(),
+                symbol: "USDC".to_string(),
+                decimals: 6,
+                min_change: min_usdc,
+            }],
+        }
+    }
+
+    fn snapshot(block: u64, native: u128, usdc_balance: u128) -> Snapshot {
+        Snapshot {
+            block,
+            native: U256::from(native),
+            tokens: BTreeMap::from([(usdc(), U256::from(usdc_balance))]),
+        }
+    }
+
+    /// Run a balance sequence, returning `(type, change, alert)` per poll
+    fn run(watch: &AccountWatch, polls: &[(u128, u128)]) -> Vec<Vec<(String, String, bool)>> {
+        let mut state = AccountState::default();
+        polls
+            .iter()
+            .enumerate()
+            .map(|(i, (native, usdc_balance))| {
+                watch
+                    .observe(
+                        &mut state,
+                        &snapshot(100 + i as u64, *native, *usdc_balance),
+                        &[],
+                    )
+                    .into_iter()
+                    .map(|e| match e.change {
+                        WatchChange::NativeBalance { change, .. } => {
+                            ("native".to_string(), change, e.alert)
+                        }
+                        WatchChange::TokenBalance { symbol, change, .. } => {
+                            (symbol, change, e.alert)
+                        }
+                        WatchChange::Transaction { hash, .. } => ("tx".to_string(), hash, e.alert),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_native_amount() {
+        let half = U256::from(ETH / 2);
+        assert_eq!(parse_native_amount("0.5eth").unwrap(), half);
+        assert_eq!(parse_native_amount("0.5 ETH").unwrap(), half);
+        assert_eq!(parse_native_amount("0.5ether").unwrap(), half);
+        assert_eq!(parse_native_amount("0.5").unwrap(), half);
+        assert_eq!(
+            parse_native_amount("20gwei").unwrap(),
+            U256::from(20_000_000_000u64)
+        );
+        assert_eq!(parse_native_amount("1000wei").unwrap(), U256::from(1000));
+        assert!(parse_native_amount("lots").is_err());
+        assert!(parse_native_amount("0.5wei").is_err());
+    }
+
+    #[test]
+    fn test_parse_token_amount_uses_decimals() {
+        assert_eq!(
+            parse_token_amount("1000", 6).unwrap(),
+            U256::from(1_000_000_000u64)
+        );
+        assert_eq!(parse_token_amount("0.25", 6).unwrap(), U256::from(250_000));
+        assert_eq!(
+            parse_token_amount("1000", 18).unwrap(),
+            U256::from(1000 * ETH)
+        );
+        assert_eq!(parse_token_amount("7", 0).unwrap(), U256::from(7));
+        // More precision than the token has
+        assert!(parse_token_amount("0.0000001", 6).is_err());
+        assert!(parse_token_amount("-1", 6).is_err());
+    }
+
+    #[test]
+    fn test_first_poll_is_baseline() {
+        let watch = watch(U256::ZERO, U256::ZERO);
+        let polls = run(&watch, &[(ETH, 5_000_000), (ETH, 5_000_000)]);
+        assert!(polls[0].is_empty());
+        assert!(polls[1].is_empty());
+    }
+
+    #[test]
+    fn test_changes_without_thresholds_all_alert() {
+        let watch = watch(U256::ZERO, U256::ZERO);
+        let polls = run(&watch, &[(ETH, 0), (ETH / 2, 0), (ETH / 2, 1_500_000)]);
+        assert_eq!(
+            polls[1],
+            vec![("native".to_string(), "-0.5".to_string(), true)]
+        );
+        assert_eq!(
+            polls[2],
+            vec![("USDC".to_string(), "+1.5".to_string(), true)]
+        );
+    }
+
+    #[test]
+    fn test_threshold_accumulates_small_changes() {
+        // Alert once the balance is 0.5 ETH away from the last alert
+        let watch = watch(U256::from(ETH / 2), U256::MAX);
+        let tenth = ETH / 10;
+        let polls = run(
+            &watch,
+            &[
+                (10 * ETH, 0),
+                (10 * ETH - 2 * tenth, 0),
+                (10 * ETH - 4 * tenth, 0),
+                (10 * ETH - 6 * tenth, 0),
+                (10 * ETH - 7 * tenth, 0),
+                (10 * ETH - 2 * tenth, 0),
+            ],
+        );
+        let alerts: Vec<Option<bool>> = polls
+            .iter()
+            .map(|events| events.first().map(|e| e.2))
+            .collect();
+        assert_eq!(
+            alerts,
+            vec![
+                None,
+                Some(false),
+                Some(false),
+                // 0.6 below the baseline
+                Some(true),
+                // 0.1 below the new reference
+                Some(false),
+                // 0.4 above it: changes are printed, no alert
+                Some(false),
+            ]
+        );
+        assert_eq!(polls[5][0].1, "+0.5");
+    }
+
+    #[test]
+    fn test_token_threshold_in_token_units() {
+        let watch = watch(U256::MAX, parse_token_amount("1000", 6).unwrap());
+        let polls = run(
+            &watch,
+            &[
+                (ETH, 10_000_000_000),
+                (ETH, 9_500_000_000),
+                (ETH, 9_000_000_000),
+                (ETH, 9_000_000_001),
+            ],
+        );
+        assert_eq!(
+            polls[1],
+            vec![("USDC".to_string(), "-500".to_string(), false)]
+        );
+        assert_eq!(
+            polls[2],
+            vec![("USDC".to_string(), "-500".to_string(), true)]
+        );
+        assert_eq!(
+            polls[3],
+            vec![("USDC".to_string(), "+0.000001".to_string(), false)]
+        );
+    }
+
+    #[test]
+    fn test_returning_to_reference_does_not_alert_with_zero_threshold() {
+        let watch = watch(U256::ZERO, U256::ZERO);
+        let mut state = AccountState::default();
+        watch.observe(&mut state, &snapshot(1, ETH, 0), &[]);
+        let native =
+            |events: Vec<WatchEvent>| events.into_iter().map(|e| e.alert).collect::<Vec<_>>();
+        assert_eq!(
+            native(watch.observe(&mut state, &snapshot(2, 2 * ETH, 0), &[])),
+            vec![true]
+        );
+        assert_eq!(state.native.unwrap().reference, U256::from(2 * ETH));
+        assert_eq!(
+            native(watch.observe(&mut state, &snapshot(3, ETH, 0), &[])),
+            vec![true]
+        );
+    }
+
+    #[test]
+    fn test_transactions() {
+        let watch = watch(U256::from(ETH), U256::MAX);
+        let mut state = AccountState::default();
+        let tx = |hash: &str, block: u64, from: Address, to: Address, value: u128| WatchedTx {
+            hash: hash.to_string(),
+            block,
+            from,
+            to: Some(to),
+            value: U256::from(value),
+        };
+        let other = Address::repeat_byte(0xbb);
+        let txs = vec![
+            tx("0x01", 100, other, wallet(), 5 * ETH),
+            tx("0x02", 102, wallet(), other, 0),
+            tx("0x03", 101, other, wallet(), ETH / 100),
+            tx("0x04", 101, other, wallet(), 2 * ETH),
+        ];
+
+        // Baseline at block 100: nothing reported
+        assert!(watch
+            .observe(&mut state, &snapshot(100, ETH, 0), &txs)
+            .is_empty());
+
+        let events = watch.observe(&mut state, &snapshot(102, ETH, 0), &txs);
+        let summary: Vec<(String, TxDirection, bool)> = events
+            .iter()
+            .map(|e| match &e.change {
+                WatchChange::Transaction {
+                    hash, direction, ..
+                } => (hash.clone(), *direction, e.alert),
+                other => panic!("unexpected {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                // Dust in: below the native threshold
+                ("0x03".to_string(), TxDirection::In, false),
+                ("0x04".to_string(), TxDirection::In, true),
+                // Anything out alerts
+                ("0x02".to_string(), TxDirection::Out, true),
+            ]
+        );
+        assert_eq!(events[1].block, 101);
+
+        // The same transactions again (overlapping scan): nothing new
+        assert!(watch
+            .observe(&mut state, &snapshot(102, ETH, 0), &txs)
+            .is_empty());
+        assert_eq!(state.block, Some(102));
+    }
+
+    #[test]
+    fn test_event_json() {
+        let watch = watch(U256::ZERO, U256::ZERO);
+        let mut state = AccountState::default();
+        watch.observe(&mut state, &snapshot(1, ETH, 0), &[]);
+        let events = watch.observe(&mut state, &snapshot(2, ETH + ETH / 4, 0), &[]);
+        let json = serde_json::to_value(&events[0]).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "chain": "ethereum",
+                "address": format!("{:#x}", wallet()),
+                "block": 2,
+                "type": "native_balance",
+                "symbol": "ETH",
+                "previous": "1",
+                "current": "1.25",
+                "change": "+0.25",
+                "change_wei": "+250000000000000000",
+                "alert": true
+            })
+        );
+    }
+
+    #[test]
+    fn test_state_survives_restart() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("account_watch.json");
+        let watch = watch(U256::from(ETH), U256::ZERO);
+
+        let mut state = WatchState::load(&path).unwrap();
+        let account = state.account(Chain::Ethereum, wallet());
+        watch.observe(account, &snapshot(1, 5 * ETH, 100), &[]);
+        let events = watch.observe(account, &snapshot(2, 3 * ETH, 100), &[]);
+        assert!(events[0].alert);
+        state.save(&path).unwrap();
+
+        // Restarted: the alerted balance is not reported again
+        let mut state = WatchState::load(&path).unwrap();
+        assert_eq!(state.version, WATCH_STATE_VERSION);
+        let account = state.account(Chain::Ethereum, wallet());
+        assert!(watch
+            .observe(account, &snapshot(3, 3 * ETH, 100), &[])
+            .is_empty());
+        // A new token gets a baseline, not an event
+        account.tokens.clear();
+        assert!(watch
+            .observe(account, &snapshot(4, 3 * ETH, 500), &[])
+            .is_empty());
+
+        std::fs::write(&path, r#"{"version": 99}"#).unwrap();
+        assert!(WatchState::load(&path).is_err());
+    }
+
+    #[test]
+    fn test_backoff() {
+        let mut backoff = Backoff::new(Duration::from_secs(30));
+        let delays: Vec<u64> = (0..7).map(|_| backoff.failure().as_secs()).collect();
+        assert_eq!(delays, vec![30, 60, 120, 240, 480, 600, 600]);
+        assert_eq!(backoff.failures(), 7);
+        backoff.success();
+        assert_eq!(backoff.failure(), Duration::from_secs(30));
+
+        // Intervals longer than the cap are kept
+        let mut slow = Backoff::new(Duration::from_secs(3600));
+        assert_eq!(slow.failure(), Duration::from_secs(3600));
+        assert_eq!(slow.failure(), Duration::from_secs(3600));
+    }
+}
@@ -8,7 +8,7 @@ use std::fs;
 use tempfile::TempDir;
 
 fn ethcli() -> Command {
-    Command::cargo_bin("ethcli").unwrap()
+    Command::new(assert_cmd::cargo::cargo_bin!("ethcli"))
 }
 
 /// Create a temp config directory with a minimal config file
@@ -56,7 +56,7 @@ chain = "ethereum"
 
 /// Create ethcli command with temp config directory
 fn ethcli_with_config(temp_dir: &TempDir) -> Command {
-    let mut cmd = Command::cargo_bin("ethcli").unwrap();
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ethcli"));
     cmd.env("ETHCLI_CONFIG_DIR", temp_dir.path());
     cmd
 }
@@ -128,6 +128,27 @@ fn test_cast_to_dec_without_prefix() {
         .stdout(predicate::str::contains("255"));
 }
 
+#[test]
+fn test_cast_calldata_decode_ndjson() {
+    let calldata = format!(
+        "0xa9059cbb{:0>64}{:064x}",
+        "000000000022d473030f116ddee9f6b43ac78ba3", 1000
+    );
+    let output = ethcli()
+        .args(["cast", "calldata-decode", &calldata])
+        .args(["--sig", "transfer(address,uint256)", "-o", "ndjson"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 1, "{stdout}");
+    let candidate: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+    assert_eq!(candidate["signature"], "transfer(address,uint256)");
+    assert_eq!(candidate["exact"], true);
+}
+
 #[test]
 fn test_cast_to_wei_eth() {
     ethcli()
@@ -354,6 +375,33 @@ fn test_cast_checksum_invalid_address() {
         .stderr(predicate::str::contains("Invalid address"));
 }
 
+#[test]
+fn test_output_flag_rejects_format_names() {
+    // `--output` used to select the format; it now takes a file path
+    let temp_dir = TempDir::new().unwrap();
+    for args in [
+        &[
+            "account",
+            "txs",
+            "0x0000000000000000000000000000000000000001",
+        ][..],
+        &[
+            "account",
+            "erc20",
+            "0x0000000000000000000000000000000000000001",
+        ][..],
+    ] {
+        ethcli()
+            .current_dir(temp_dir.path())
+            .args(args)
+            .args(["--output", "json"])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("use --format json"));
+    }
+    assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+}
+
 // ==================== ABI encode/decode tests ====================
 
 #[test]
@@ -434,6 +482,47 @@ fn test_endpoints_optimize_help() {
         .stdout(predicate::str::contains("Optimize endpoint"));
 }
 
+#[test]
+fn test_endpoints_monitor_help() {
+    ethcli()
+        .args(["endpoints", "monitor", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--interval"))
+        .stdout(predicate::str::contains("--apply"))
+        .stdout(predicate::str::contains("--dry-run"));
+}
+
+#[test]
+fn test_endpoints_monitor_dry_run_writes_nothing() {
+    let temp_dir = TempDir::new().unwrap();
+    ethcli_with_config(&temp_dir)
+        .args(["endpoints", "monitor", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[cycle 1] probed 0 endpoint(s)"));
+    assert!(!temp_dir.path().join("endpoint_monitor.json").exists());
+    assert!(!temp_dir.path().join("endpoint_changes.log").exists());
+}
+
+#[test]
+fn test_endpoints_monitor_rejects_inverted_band() {
+    let temp_dir = TempDir::new().unwrap();
+    ethcli_with_config(&temp_dir)
+        .args([
+            "endpoints",
+            "monitor",
+            "--dry-run",
+            "--min-priority",
+            "8",
+            "--max-priority",
+            "2",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("must not exceed"));
+}
+
 #[test]
 fn test_endpoints_list_with_temp_config() {
     let temp_dir = setup_temp_config();
@@ -573,3 +662,360 @@ fn test_config_path() {
         .success()
         .stdout(predicate::str::contains("config.toml"));
 }
+
+// ==================== Contract Events Tests ====================
+
+const EVENTS_ABI: &str = r#"[
+    {"type":"event","name":"Transfer","anonymous":false,"inputs":[
+        {"name":"from","type":"address","indexed":true},
+        {"name":"to","type":"address","indexed":true},
+        {"name":"value","type":"uint256","indexed":false}]},
+    {"type":"event","name":"Approval","anonymous":false,"inputs":[
+        {"name":"owner","type":"address","indexed":true},
+        {"name":"spender","type":"address","indexed":true},
+        {"name":"value","type":"uint256","indexed":false}]},
+    {"type":"function","name":"totalSupply","inputs":[],"outputs":[{"name":"","type":"uint256"}],"stateMutability":"view"}
+]"#;
+
+#[test]
+fn test_contract_events_from_abi_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let abi_path = temp_dir.path().join("erc20.json");
+    fs::write(&abi_path, EVENTS_ABI).unwrap();
+
+    ethcli()
+        .args([
+            "contract",
+            "events",
+            "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+            "--abi",
+            abi_path.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Transfer(address,address,uint256)",
+        ))
+        .stdout(predicate::str::contains(
+            "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+        ))
+        .stdout(predicate::str::contains(
+            "Approval(address,address,uint256)",
+        ));
+}
+
+#[test]
+fn test_contract_events_json() {
+    let temp_dir = TempDir::new().unwrap();
+    let abi_path = temp_dir.path().join("erc20.json");
+    fs::write(&abi_path, EVENTS_ABI).unwrap();
+
+    let output = ethcli()
+        .args([
+            "contract",
+            "events",
+            "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+            "--abi",
+            abi_path.to_str().unwrap(),
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let events: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let events = events.as_array().unwrap();
+    assert_eq!(events.len(), 2);
+    // Sorted by name
+    assert_eq!(events[0]["name"], "Approval");
+    assert_eq!(events[1]["signature"], "Transfer(address,address,uint256)");
+    assert_eq!(events[1]["indexed_params"].as_array().unwrap().len(), 2);
+    assert_eq!(events[1]["indexed_params"][0]["type"], "address");
+}
+
+#[test]
+fn test_contract_events_codegen_json_schema() {
+    let temp_dir = TempDir::new().unwrap();
+    let abi_path = temp_dir.path().join("erc20.json");
+    fs::write(&abi_path, EVENTS_ABI).unwrap();
+    let out_dir = temp_dir.path().join("schemas");
+
+    ethcli()
+        .args([
+            "contract",
+            "events",
+            "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+            "--abi",
+            abi_path.to_str().unwrap(),
+            "--codegen",
+            "json-schema",
+            "--out-dir",
+            out_dir.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+
+    let schema: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(out_dir.join("Transfer.schema.json")).unwrap())
+            .unwrap();
+    assert_eq!(schema["title"], "Transfer");
+    assert_eq!(schema["properties"]["value"]["type"], "string");
+    assert!(out_dir.join("Approval.schema.json").exists());
+
+    let manifest: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(out_dir.join("manifest.json")).unwrap()).unwrap();
+    assert_eq!(manifest[1]["struct"], "Transfer");
+    assert_eq!(
+        manifest[1]["topic0"],
+        "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+    );
+}
+
+#[test]
+fn test_contract_proxy_resolve_help() {
+    ethcli()
+        .args(["contract", "proxy-resolve", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("EIP-1967"))
+        .stdout(predicate::str::contains("--abi"))
+        .stdout(predicate::str::contains("--format"));
+}
+
+#[test]
+fn test_contract_abi_rpc_url_requires_follow_proxy() {
+    ethcli()
+        .args([
+            "contract",
+            "abi",
+            "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+            "--rpc-url",
+            "http://localhost:8545",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--follow-proxy"));
+}
+
+// ==================== Logs watch tests ====================
+
+#[test]
+fn test_logs_watch_conflicts_with_to_block() {
+    ethcli()
+        .args([
+            "logs",
+            "-c",
+            "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+            "-f",
+            "100",
+            "-t",
+            "200",
+            "--watch",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--to-block"));
+}
+
+#[test]
+fn test_logs_watch_rejects_csv() {
+    ethcli()
+        .args([
+            "logs",
+            "-c",
+            "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+            "-f",
+            "100",
+            "--watch",
+            "-o",
+            "csv",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("json and ndjson"));
+}
+
+#[test]
+fn test_logs_poll_interval_requires_watch() {
+    ethcli()
+        .args([
+            "logs",
+            "-c",
+            "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+            "--poll-interval",
+            "5",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--watch"));
+}
+
+// ==================== Tx sqlite tests ====================
+
+#[test]
+fn test_tx_help_shows_sqlite() {
+    ethcli()
+        .args(["tx", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--sqlite"))
+        .stdout(predicate::str::contains("query"));
+}
+
+#[test]
+fn test_tx_query_empty_database() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("txs.db");
+    let mut writer = ethcli::TxSqliteWriter::new(&db_path).unwrap();
+    writer.finalize().unwrap();
+
+    let output = ethcli()
+        .args([
+            "tx",
+            "query",
+            db_path.to_str().unwrap(),
+            "SELECT COUNT(*) AS n FROM transactions",
+            "--output",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let rows: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(rows[0]["n"], 0);
+}
+
+#[test]
+fn test_tx_query_is_read_only_with_params() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("txs.db");
+    let mut writer = ethcli::TxSqliteWriter::new(&db_path).unwrap();
+    writer.finalize().unwrap();
+    let db = db_path.to_str().unwrap();
+
+    ethcli()
+        .args(["tx", "query", db, "DELETE FROM transactions"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("only SELECT"));
+
+    let output = ethcli()
+        .args([
+            "tx",
+            "query",
+            db,
+            "SELECT :n AS n",
+            "-p",
+            "n=7",
+            "--output",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let rows: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(rows[0]["n"], 7);
+}
+
+#[test]
+fn test_tx_query_missing_database() {
+    let temp_dir = TempDir::new().unwrap();
+    ethcli()
+        .args([
+            "tx",
+            "query",
+            temp_dir.path().join("missing.db").to_str().unwrap(),
+            "SELECT 1",
+        ])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_tx_decode_input_rejects_bad_calldata() {
+    ethcli()
+        .args([
+            "tx",
+            "decode-input",
+            "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+            "0xa905",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("at least 4 bytes"));
+}
+
+#[test]
+fn test_sig_import_then_offline_lookup() {
+    let cache_dir = TempDir::new().unwrap();
+    let file = cache_dir.path().join("sigs.txt");
+    fs::write(
+        &file,
+        "frobnicate(uint256)\nevent Frobbed(address indexed who, uint256 amount)\n",
+    )
+    .unwrap();
+    let sig = |args: &[&str]| {
+        let mut cmd = ethcli();
+        cmd.env("XDG_CACHE_HOME", cache_dir.path())
+            .arg("sig")
+            .args(args);
+        cmd
+    };
+
+    sig(&["import", "--file", file.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Imported 1 function and 1 event signatures (0 already cached)",
+        ));
+    sig(&["import", "--file", file.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(2 already cached)"));
+
+    // Served from the imported entry without querying 4byte
+    let selector = String::from_utf8(
+        ethcli()
+            .args(["cast", "sig", "frobnicate(uint256)"])
+            .output()
+            .unwrap()
+            .stdout,
+    )
+    .unwrap();
+    sig(&["fn", selector.trim()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("frobnicate(uint256)"));
+}
+
+// ==================== Simulate assertion tests ====================
+
+#[test]
+fn test_simulate_assert_file_is_validated_before_simulating() {
+    let dir = TempDir::new().unwrap();
+    let target = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+
+    let bad = dir.path().join("bad.toml");
+    fs::write(&bad, "sucess = true\n").unwrap();
+    ethcli()
+        .args([
+            "simulate", "call", target, "--data", "0x", "--via", "tenderly",
+        ])
+        .arg("--assert-file")
+        .arg(&bad)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid assertion file"));
+
+    let good = dir.path().join("good.toml");
+    fs::write(&good, "success = true\n").unwrap();
+    ethcli()
+        .args(["simulate", "call", target, "--data", "0x", "--via", "cast"])
+        .arg("--assert-file")
+        .arg(&good)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("needs structured results"));
+}
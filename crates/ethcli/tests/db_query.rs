@@ -0,0 +1,284 @@
+//! `ethcli db` against a small events database
+//!
+//! The database mimics `logs -o sqlite` output: three transfers from two
+//! tokens. Injection attempts must fail (or match nothing) and leave every
+//! row in place.
+
+use assert_cmd::Command;
+use rusqlite::Connection;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+const USDC: &str = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+const DAI: &str = "0x6b175474e89094c44da98b954eedeac495271d0f";
+
+struct Fixture {
+    dir: TempDir,
+    db: PathBuf,
+}
+
+fn fixture(config: &str) -> Fixture {
+    let dir = TempDir::new().unwrap();
+    let db = dir.path().join("events.db");
+    let conn = Connection::open(&db).unwrap();
+    conn.execute_batch(&format!(
+        "CREATE TABLE events (
+             id INTEGER PRIMARY KEY AUTOINCREMENT,
+             block_number INTEGER NOT NULL,
+             address TEXT NOT NULL,
+             event_name TEXT NOT NULL,
+             value TEXT
+         );
+         CREATE INDEX idx_block ON events(block_number);
+         INSERT INTO events (block_number, address, event_name, value)
+             VALUES (100, '{USDC}', 'Transfer', '1000000');
+         INSERT INTO events (block_number, address, event_name, value)
+             VALUES (200, '{USDC}', 'Transfer', '2500000');
+         INSERT INTO events (block_number, address, event_name, value)
+             VALUES (300, '{DAI}', 'Transfer', '5');"
+    ))
+    .unwrap();
+    fs::write(dir.path().join("config.toml"), config).unwrap();
+    Fixture { dir, db }
+}
+
+fn ethcli(fixture: &Fixture, args: &[&str]) -> std::process::Output {
+    Command::new(assert_cmd::cargo::cargo_bin!("ethcli"))
+        .env("ETHCLI_CONFIG_DIR", fixture.dir.path())
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn db(fixture: &Fixture) -> &str {
+    fixture.db.to_str().unwrap()
+}
+
+fn row_count(db: &Path) -> i64 {
+    Connection::open(db)
+        .unwrap()
+        .query_row("SELECT count(*) FROM events", [], |row| row.get(0))
+        .unwrap()
+}
+
+fn json(output: &std::process::Output) -> serde_json::Value {
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn test_query_with_named_param() {
+    let f = fixture("");
+    let output = ethcli(
+        &f,
+        &[
+            "db",
+            "query",
+            db(&f),
+            "--sql",
+            "SELECT block_number, value FROM events WHERE address = :token ORDER BY block_number",
+            "--param",
+            &format!("token={USDC}"),
+            "-o",
+            "json",
+        ],
+    );
+    let rows = json(&output);
+    let rows = rows.as_array().unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0]["block_number"], 100);
+    assert_eq!(rows[1]["value"], "2500000");
+}
+
+#[test]
+fn test_query_csv_keeps_select_order() {
+    let f = fixture("");
+    let output = ethcli(
+        &f,
+        &[
+            "db",
+            "query",
+            db(&f),
+            "--sql",
+            "SELECT value, block_number AS block FROM events WHERE block_number > ?1 ORDER BY block",
+            "-p",
+            "1=150",
+            "-o",
+            "csv",
+        ],
+    );
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "value,block\n2500000,200\n5,300\n"
+    );
+}
+
+#[test]
+fn test_query_rejects_writes_and_stacked_statements() {
+    let f = fixture("");
+    for sql in [
+        "DELETE FROM events",
+        "DROP TABLE events",
+        "SELECT * FROM events; DROP TABLE events",
+        "SELECT 1; DELETE FROM events; --",
+        "WITH x AS (SELECT 1) DELETE FROM events",
+        "ATTACH DATABASE 'other.db' AS other",
+        "PRAGMA journal_mode = DELETE",
+    ] {
+        let output = ethcli(&f, &["db", "query", db(&f), "--sql", sql]);
+        assert!(!output.status.success(), "{sql}");
+        assert!(
+            String::from_utf8_lossy(&output.stderr).contains("Rejected query"),
+            "{sql}: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    assert_eq!(row_count(&f.db), 3);
+}
+
+#[test]
+fn test_query_param_values_are_not_sql() {
+    let f = fixture("");
+    for payload in [
+        format!("{USDC}' OR '1'='1"),
+        format!("{USDC}'; DROP TABLE events; --"),
+    ] {
+        let output = ethcli(
+            &f,
+            &[
+                "db",
+                "query",
+                db(&f),
+                "--sql",
+                "SELECT * FROM events WHERE address = :token",
+                "-p",
+                &format!("token={payload}"),
+                "-o",
+                "json",
+            ],
+        );
+        assert_eq!(json(&output), serde_json::json!([]), "{payload}");
+    }
+    assert_eq!(row_count(&f.db), 3);
+}
+
+#[test]
+fn test_query_param_errors() {
+    let f = fixture("");
+    let sql = "SELECT * FROM events WHERE address = :token";
+
+    let output = ethcli(&f, &["db", "query", db(&f), "--sql", sql]);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no value for parameter 'token'"));
+
+    let output = ethcli(
+        &f,
+        &[
+            "db",
+            "query",
+            db(&f),
+            "--sql",
+            sql,
+            "-p",
+            "token=x",
+            "-p",
+            "tokn=y",
+        ],
+    );
+    assert!(String::from_utf8_lossy(&output.stderr).contains("'tokn' is not a parameter"));
+
+    // Rejected by clap before anything runs
+    let output = ethcli(
+        &f,
+        &["db", "query", db(&f), "--sql", sql, "-p", "bad name=1"],
+    );
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("not a valid parameter name"));
+}
+
+#[test]
+fn test_report_uses_saved_query_and_defaults() {
+    let f = fixture(&format!(
+        r#"
+[queries.volume]
+sql = "SELECT count(*) AS transfers, sum(CAST(value AS INTEGER)) AS total FROM events WHERE address = :token AND block_number >= :from"
+description = "Transfers of one token"
+params = {{ token = "{USDC}", "from:int" = "0" }}
+"#
+    ));
+
+    let output = ethcli(
+        &f,
+        &["db", "report", "volume", "--db", db(&f), "-o", "json"],
+    );
+    let rows = json(&output);
+    assert_eq!(rows[0]["transfers"], 2);
+    assert_eq!(rows[0]["total"], 3_500_000);
+
+    let output = ethcli(
+        &f,
+        &[
+            "db",
+            "report",
+            "volume",
+            "--db",
+            db(&f),
+            "-p",
+            &format!("token={DAI}"),
+            "-o",
+            "ndjson",
+        ],
+    );
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "{\"transfers\":1,\"total\":5}\n"
+    );
+
+    let output = ethcli(&f, &["db", "report", "missing", "--db", db(&f)]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("available: volume"));
+
+    let output = ethcli(&f, &["db", "reports", "-o", "json"]);
+    let reports = json(&output);
+    assert_eq!(reports["volume"]["description"], "Transfers of one token");
+}
+
+#[test]
+fn test_report_saved_sql_is_validated() {
+    let f = fixture(
+        r#"
+[queries.wipe]
+sql = "DELETE FROM events"
+"#,
+    );
+    let output = ethcli(&f, &["db", "report", "wipe", "--db", db(&f)]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Rejected query"));
+    assert_eq!(row_count(&f.db), 3);
+}
+
+#[test]
+fn test_schema() {
+    let f = fixture("");
+    let output = ethcli(&f, &["db", "schema", db(&f), "-o", "json"]);
+    let schema = json(&output);
+    let events = &schema["tables"][0];
+    assert_eq!(events["name"], "events");
+    assert_eq!(events["columns"][0]["name"], "id");
+    assert_eq!(events["columns"][0]["primaryKey"], true);
+    assert_eq!(events["columns"][2]["type"], "TEXT");
+    assert_eq!(events["indexes"][0]["columns"][0], "block_number");
+
+    let output = ethcli(&f, &["db", "schema", db(&f)]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("events (table)"), "{stdout}");
+    assert!(stdout.contains("index idx_block (block_number)"));
+    assert!(!stdout.contains("sqlite_sequence"));
+}
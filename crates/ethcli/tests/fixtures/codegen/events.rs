@@ -0,0 +1,74 @@
+//! Event params for 0x0000000000000000000000000000000000000001
+//!
+//! Generated by `ethcli contract events --codegen rust`. Each struct
+//! deserializes the `params` object of a decoded log.
+
+#![allow(dead_code)]
+
+use serde::{Deserialize, Serialize};
+
+/// `OrderFilled(bytes32,string,(address,uint256[],uint64),address[2],int128,bool,uint8,bytes)`
+///
+/// topic0: `0x2d48e50169755e2f9e1ba5e74389665ee1c8cd8aec30dfed7b8b150d91d94074`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OrderFilled {
+    /// `bytes32` (indexed)
+    #[serde(rename = "orderHash")]
+    pub order_hash: String,
+    /// `string` (indexed)
+    pub memo: String,
+    /// `(address,uint256[],uint64)`
+    pub order: (String, Vec<String>, String),
+    /// `address[2]`
+    pub path: Vec<String>,
+    /// `int128`
+    pub delta: String,
+    /// `bool`
+    pub partial: bool,
+    /// `uint8`
+    #[serde(rename = "type")]
+    pub type_: String,
+    /// `bytes`
+    pub data: String,
+}
+
+/// `Paused(address)`
+///
+/// topic0: `0x62e78cea01bee320cd4e420270b5ea74000d11b0c9f74754ebdbfc544b05a258`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Paused {
+    /// `address`
+    #[serde(rename = "")]
+    pub param0: String,
+}
+
+/// `Refreshed()`
+///
+/// topic0: `0xb32c7a5df3250aea092f14905cc08583e3028646534e46b4f870f143e3c10c32`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Refreshed {}
+
+/// `Transfer(address,address,uint256)`
+///
+/// topic0: `0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Transfer {
+    /// `address` (indexed)
+    pub from: String,
+    /// `address` (indexed)
+    pub to: String,
+    /// `uint256`
+    pub value: String,
+}
+
+/// `(topic0, struct name)` for every non-anonymous event
+pub const TOPIC0: &[(&str, &str)] = &[
+    ("0x2d48e50169755e2f9e1ba5e74389665ee1c8cd8aec30dfed7b8b150d91d94074", "OrderFilled"),
+    ("0x62e78cea01bee320cd4e420270b5ea74000d11b0c9f74754ebdbfc544b05a258", "Paused"),
+    ("0xb32c7a5df3250aea092f14905cc08583e3028646534e46b4f870f143e3c10c32", "Refreshed"),
+    ("0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef", "Transfer"),
+];
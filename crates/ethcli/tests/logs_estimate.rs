@@ -0,0 +1,194 @@
+//! `ethcli logs --estimate` against a mocked JSON-RPC chain
+//!
+//! The mock returns one log every other block, so a 10k-block chunk holds
+//! 5k logs against a 1k `max_logs` limit. A capped mock cuts responses
+//! short the way some providers do instead of returning an error.
+
+use assert_cmd::Command;
+use std::fs;
+use std::time::Duration;
+use tempfile::TempDir;
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+const CONTRACT: &str = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+
+struct DenseChain {
+    /// Most logs returned per response; the rest are silently dropped
+    cap: Option<usize>,
+}
+
+fn hex_to_u64(value: &serde_json::Value) -> u64 {
+    u64::from_str_radix(value.as_str().unwrap().trim_start_matches("0x"), 16).unwrap()
+}
+
+impl Respond for DenseChain {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        let result = match body["method"].as_str().unwrap_or_default() {
+            "eth_chainId" => serde_json::json!("0x1"),
+            "eth_blockNumber" => serde_json::json!("0x186a0"),
+            "eth_getLogs" => {
+                let from = hex_to_u64(&body["params"][0]["fromBlock"]);
+                let to = hex_to_u64(&body["params"][0]["toBlock"]);
+                let logs: Vec<serde_json::Value> = (from..=to)
+                    .filter(|block| block % 2 == 0)
+                    .map(|block| {
+                        serde_json::json!({
+                            "address": CONTRACT,
+                            "topics": [format!("0x{:064x}", 1)],
+                            "data": "0x",
+                            "blockNumber": format!("{block:#x}"),
+                            "blockHash": format!("0x{block:064x}"),
+                            "transactionHash": format!("0x{block:064x}"),
+                            "transactionIndex": "0x0",
+                            "logIndex": "0x0",
+                            "removed": false
+                        })
+                    })
+                    .take(self.cap.unwrap_or(usize::MAX))
+                    .collect();
+                serde_json::json!(logs)
+            }
+            _ => serde_json::Value::Null,
+        };
+        ResponseTemplate::new(200)
+            .set_body_json(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": body["id"],
+                "result": result
+            }))
+            .set_delay(RESPONSE_DELAY)
+    }
+}
+
+/// Latency of every mocked response
+const RESPONSE_DELAY: Duration = Duration::from_millis(25);
+
+async fn estimate(extra: &[&str]) -> (std::process::Output, TempDir, TempDir) {
+    estimate_with(DenseChain { cap: None }, 1000, extra).await
+}
+
+async fn estimate_with(
+    chain: DenseChain,
+    max_logs: usize,
+    extra: &[&str],
+) -> (std::process::Output, TempDir, TempDir) {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(chain)
+        .mount(&server)
+        .await;
+
+    let config = TempDir::new().unwrap();
+    fs::write(
+        config.path().join("config.toml"),
+        format!(
+            "[settings]\nconcurrency = 5\nretry_attempts = 0\n\n[[endpoints]]\nurl = \"{}\"\nmax_block_range = 10000\nmax_logs = {}\npriority = 10\nchain = \"ethereum\"\n",
+            server.uri(),
+            max_logs
+        ),
+    )
+    .unwrap();
+    let cwd = TempDir::new().unwrap();
+
+    let config_path = config.path().to_path_buf();
+    let cwd_path = cwd.path().to_path_buf();
+    let extra: Vec<String> = extra.iter().map(|s| s.to_string()).collect();
+    let output = tokio::task::spawn_blocking(move || {
+        Command::new(assert_cmd::cargo::cargo_bin!("ethcli"))
+            .env("ETHCLI_CONFIG_DIR", config_path)
+            .env_remove("ETHERSCAN_API_KEY")
+            .current_dir(cwd_path)
+            .args([
+                "logs",
+                "-c",
+                CONTRACT,
+                "-f",
+                "0",
+                "-t",
+                "99999",
+                "--estimate",
+                "--samples",
+                "4",
+                "--quiet",
+            ])
+            .args(extra)
+            .output()
+            .unwrap()
+    })
+    .await
+    .unwrap();
+    (output, config, cwd)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_estimate_json() {
+    let (output, config, cwd) = estimate(&["-o", "json"]).await;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["blocks"], 100_000);
+    assert_eq!(json["samples"].as_array().unwrap().len(), 4);
+    assert_eq!(json["estimated_logs"], 50_000);
+    assert_eq!(json["chunk_size"], 10_000);
+    assert_eq!(json["chunks"], 10);
+    assert_eq!(json["max_logs"], 1_000);
+    // 5k logs per chunk: three halvings, 1 + 2 + 4 + 8 requests per chunk
+    assert_eq!(json["requests"], 150);
+    assert_eq!(json["suggested_chunk_size"], 1_600);
+    assert_eq!(json["feasible"], true);
+    assert!(!json["hot_ranges"].as_array().unwrap().is_empty());
+
+    // Nothing written besides the config we put there
+    assert_eq!(fs::read_dir(cwd.path()).unwrap().count(), 0);
+    let config_files: Vec<_> = fs::read_dir(config.path())
+        .unwrap()
+        .map(|e| e.unwrap().file_name())
+        .collect();
+    assert_eq!(config_files, vec!["config.toml"]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_estimate_summary() {
+    let (output, _config, _cwd) = estimate(&[]).await;
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Logs:          ~50,000"), "{stdout}");
+    assert!(stdout.contains("Requests:      ~150 at concurrency 5"));
+    assert!(stdout.contains("--chunk-size 1600"));
+    assert!(stdout.contains("Feasible:      yes"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_estimate_rejects_file_formats() {
+    let (output, _config, _cwd) = estimate(&["-o", "csv"]).await;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--format json"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_estimate_splits_truncated_samples() {
+    // Each 1k-block sample holds 500 logs but the provider returns at most
+    // 200, so every sample takes five requests to read in full
+    let (output, _config, _cwd) =
+        estimate_with(DenseChain { cap: Some(200) }, 200, &["-o", "json"]).await;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["estimated_logs"], 50_000);
+    for sample in json["samples"].as_array().unwrap() {
+        assert_eq!(sample["logs"], 500);
+    }
+
+    // Latency is per request, not per sample
+    let latency = json["latency_ms"].as_u64().unwrap();
+    let delay = RESPONSE_DELAY.as_millis() as u64;
+    assert!((delay..delay * 2).contains(&latency), "latency {latency}ms");
+}
@@ -0,0 +1,237 @@
+//! Multi-chain `logs` tests against two mocked JSON-RPC chains
+//!
+//! Each chain is a wiremock server answering `eth_getLogs` with one log per
+//! requested range; the binary is pointed at them via a temp config file.
+
+use assert_cmd::Command;
+use std::fs;
+use tempfile::TempDir;
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+const CONTRACT: &str = "0x000000000022d473030f116ddee9f6b43ac78ba3";
+const TRANSFER_TOPIC: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
+/// Answers JSON-RPC calls for a single chain
+struct ChainRpc {
+    chain_id: u64,
+}
+
+impl Respond for ChainRpc {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        let result = match body["method"].as_str().unwrap_or_default() {
+            "eth_chainId" => serde_json::json!(format!("{:#x}", self.chain_id)),
+            "eth_blockNumber" => serde_json::json!("0x3e8"),
+            "eth_getLogs" => {
+                let from = body["params"][0]["fromBlock"].clone();
+                serde_json::json!([{
+                    "address": CONTRACT,
+                    "topics": [
+                        TRANSFER_TOPIC,
+                        "0x0000000000000000000000000000000000000000000000000000000000000001",
+                        "0x0000000000000000000000000000000000000000000000000000000000000002"
+                    ],
+                    "data": format!("0x{:064x}", self.chain_id),
+                    "blockNumber": from,
+                    "blockHash": format!("0x{:064x}", self.chain_id),
+                    "transactionHash": format!("0x{:064x}", self.chain_id + 1),
+                    "transactionIndex": "0x0",
+                    "logIndex": "0x0",
+                    "removed": false
+                }])
+            }
+            _ => serde_json::Value::Null,
+        };
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": body["id"],
+            "result": result
+        }))
+    }
+}
+
+async fn mock_chain(chain_id: u64) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ChainRpc { chain_id })
+        .mount(&server)
+        .await;
+    server
+}
+
+/// Config with one endpoint per `(chain, url)`
+fn config_dir(endpoints: &[(&str, &str)]) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    let mut content =
+        String::from("[settings]\nconcurrency = 4\ntimeout_seconds = 5\nretry_attempts = 0\n");
+    for (chain, url) in endpoints {
+        content.push_str(&format!(
+            "\n[[endpoints]]\nurl = \"{url}\"\nmax_block_range = 100000\npriority = 10\nchain = \"{chain}\"\nnode_type = \"archive\"\n"
+        ));
+    }
+    fs::write(dir.path().join("config.toml"), content).unwrap();
+    dir
+}
+
+/// Run `ethcli logs` with `args` in a blocking task (the mocks need the runtime)
+async fn run_logs(config: &TempDir, args: Vec<String>) -> std::process::Output {
+    let config_path = config.path().to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        Command::new(assert_cmd::cargo::cargo_bin!("ethcli"))
+            .env("ETHCLI_CONFIG_DIR", config_path)
+            .env_remove("ETHERSCAN_API_KEY")
+            .arg("logs")
+            .args(args)
+            .output()
+            .unwrap()
+    })
+    .await
+    .unwrap()
+}
+
+fn logs_args(chains: &str, extra: &[&str]) -> Vec<String> {
+    let mut args: Vec<String> = [
+        "--chain",
+        chains,
+        "-c",
+        CONTRACT,
+        "-e",
+        "Transfer(address indexed from, address indexed to, uint256 value)",
+        "-f",
+        "100",
+        "-t",
+        "200",
+        "--quiet",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+    args.extend(extra.iter().map(|s| s.to_string()));
+    args
+}
+
+fn ndjson_rows(output: &std::process::Output) -> Vec<serde_json::Value> {
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_logs_two_chains_tagged_ndjson() {
+    let eth = mock_chain(1).await;
+    let arb = mock_chain(42161).await;
+    let config = config_dir(&[("ethereum", &eth.uri()), ("arbitrum", &arb.uri())]);
+
+    let output = run_logs(&config, logs_args("ethereum,arbitrum", &["-o", "ndjson"])).await;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let mut rows = ndjson_rows(&output);
+    rows.sort_by_key(|r| r["chain_id"].as_u64());
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0]["chain"], "ethereum");
+    assert_eq!(rows[0]["chain_id"], 1);
+    assert_eq!(rows[1]["chain"], "arbitrum");
+    assert_eq!(rows[1]["chain_id"], 42161);
+    assert_eq!(rows[1]["event_name"], "Transfer");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_logs_two_chains_csv_has_chain_columns() {
+    let eth = mock_chain(1).await;
+    let arb = mock_chain(42161).await;
+    let config = config_dir(&[("ethereum", &eth.uri()), ("arbitrum", &arb.uri())]);
+    let out = config.path().join("out.csv");
+
+    let output = run_logs(
+        &config,
+        logs_args(
+            "ethereum,arbitrum",
+            &["-o", "csv", "-O", out.to_str().unwrap(), "--raw"],
+        ),
+    )
+    .await;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let content = fs::read_to_string(&out).unwrap();
+    let mut lines: Vec<&str> = content.lines().collect();
+    assert!(lines[0].starts_with("chain,chain_id,block_number,"));
+    lines[1..].sort();
+    assert!(lines[1].starts_with("arbitrum,42161,100,"));
+    assert!(lines[2].starts_with("ethereum,1,100,"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_logs_failing_chain_does_not_abort_others() {
+    let eth = mock_chain(1).await;
+    // No endpoint configured for arbitrum, so that chain fails up front
+    let config = config_dir(&[("ethereum", &eth.uri())]);
+
+    let output = run_logs(&config, logs_args("ethereum,arbitrum", &["-o", "ndjson"])).await;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let rows = ndjson_rows(&output);
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["chain"], "ethereum");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("arbitrum (42161)"));
+
+    let strict = run_logs(
+        &config,
+        logs_args("ethereum,arbitrum", &["-o", "ndjson", "--strict"]),
+    )
+    .await;
+    assert!(!strict.status.success());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_logs_two_chains_shared_checkpoint() {
+    let eth = mock_chain(1).await;
+    let arb = mock_chain(42161).await;
+    let config = config_dir(&[("ethereum", &eth.uri()), ("arbitrum", &arb.uri())]);
+    let checkpoint = config.path().join("multi.checkpoint");
+
+    let args = logs_args(
+        "ethereum,arbitrum",
+        &[
+            "-o",
+            "ndjson",
+            "--resume",
+            "--checkpoint",
+            checkpoint.to_str().unwrap(),
+        ],
+    );
+    let output = run_logs(&config, args.clone()).await;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(ndjson_rows(&output).len(), 2);
+
+    let saved: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&checkpoint).unwrap()).unwrap();
+    assert_eq!(saved["version"], 2);
+    for chain_id in ["1", "42161"] {
+        assert_eq!(
+            saved["chains"][chain_id]["completed_ranges"],
+            serde_json::json!([[100, 200]])
+        );
+    }
+
+    // Resuming a finished run fetches nothing more on either chain
+    let resumed = run_logs(&config, args).await;
+    assert!(resumed.status.success());
+    assert!(ndjson_rows(&resumed).is_empty());
+}
@@ -0,0 +1,273 @@
+//! NFT holder reconstruction against hand-written transfer logs
+//!
+//! The library tests decode the fixtures in `tests/fixtures/nft` directly;
+//! the CLI tests serve them from a mocked JSON-RPC endpoint and run
+//! `ethcli token holders` end-to-end.
+
+use alloy::primitives::{address, Address, U256};
+use alloy::rpc::types::Log;
+use assert_cmd::Command;
+use ethcli::nft::{decode_transfers, transfer_topics, NftHolders};
+use std::fs;
+use tempfile::TempDir;
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+const ERC721: &str = "0x0000000000000000000000000000000000000721";
+const ERC1155: &str = "0x0000000000000000000000000000000000001155";
+
+const ALICE: Address = address!("00000000000000000000000000000000000a11ce");
+const BOB: Address = address!("0000000000000000000000000000000000000b0b");
+const CAROL: Address = address!("00000000000000000000000000000000000ca201");
+
+fn fixture(name: &str) -> serde_json::Value {
+    let path = format!("{}/tests/fixtures/nft/{}", env!("CARGO_MANIFEST_DIR"), name);
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+fn fixture_logs(name: &str) -> Vec<Log> {
+    serde_json::from_value(fixture(name)).unwrap()
+}
+
+fn id(n: u64) -> U256 {
+    U256::from(n)
+}
+
+// ==================== Library ====================
+
+#[test]
+fn test_transfer_topics() {
+    let topics: Vec<String> = transfer_topics()
+        .iter()
+        .map(|t| format!("{:#x}", t))
+        .collect();
+    assert_eq!(
+        topics,
+        [
+            "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+            "0xc3d58168c5ae7397731d063d5bbf3d657854427343f4c083240f7aacaa2d0f62",
+            "0x4a39dc06d4c0dbc64b70af90fd698a233a518aa5d07e595d983b8c0526c8f7fb",
+        ]
+    );
+}
+
+#[test]
+fn test_erc721_holders_from_logs() {
+    let logs = fixture_logs("erc721_logs.json");
+    let holders = NftHolders::from_logs(&logs);
+
+    assert_eq!(holders.transfer_count(), 7);
+    assert_eq!(holders.owner_of(id(1)), Some(BOB));
+    assert_eq!(holders.owner_of(id(2)), Some(CAROL));
+    // Burned
+    assert_eq!(holders.owner_of(id(3)), None);
+    assert_eq!(holders.owner_of(id(4)), Some(BOB));
+    assert_eq!(
+        holders.token_ids().collect::<Vec<_>>(),
+        [id(1), id(2), id(4)]
+    );
+
+    let ranked = holders.holders();
+    assert_eq!(ranked.len(), 2);
+    assert_eq!(ranked[0].address, BOB);
+    assert_eq!(ranked[0].total, id(2));
+    assert_eq!(ranked[0].tokens, [(id(1), id(1)), (id(4), id(1))]);
+    assert_eq!(ranked[1].address, CAROL);
+    assert_eq!(ranked[1].tokens, [(id(2), id(1))]);
+}
+
+#[test]
+fn test_erc721_holders_independent_of_log_order() {
+    let mut logs = fixture_logs("erc721_logs.json");
+    logs.reverse();
+    let holders = NftHolders::from_logs(&logs);
+    assert_eq!(holders.owner_of(id(1)), Some(BOB));
+    assert_eq!(holders.owner_of(id(3)), None);
+}
+
+#[test]
+fn test_erc1155_batch_decodes_to_rows() {
+    let logs = fixture_logs("erc1155_logs.json");
+    let rows = decode_transfers(&logs[2]);
+
+    assert_eq!(rows.len(), 2);
+    assert!(rows.iter().all(|r| r.from == ALICE && r.to == BOB));
+    assert!(rows.iter().all(|r| r.operator == Some(ALICE)));
+    assert!(rows.iter().all(|r| r.block_number == Some(201)));
+    assert_eq!((rows[0].token_id, rows[0].amount), (id(1), id(40)));
+    assert_eq!((rows[1].token_id, rows[1].amount), (id(2), id(5)));
+}
+
+#[test]
+fn test_erc1155_holders_from_logs() {
+    let logs = fixture_logs("erc1155_logs.json");
+    let holders = NftHolders::from_logs(&logs);
+
+    assert_eq!(holders.transfer_count(), 7);
+    assert_eq!(holders.balance_of(ALICE, id(1)), id(60));
+    assert_eq!(holders.balance_of(BOB, id(1)), id(25));
+    assert_eq!(holders.balance_of(BOB, id(2)), id(5));
+    assert_eq!(holders.balance_of(CAROL, id(1)), id(15));
+    // Several holders, so no single owner
+    assert_eq!(holders.owner_of(id(1)), None);
+    assert_eq!(holders.token_ids().collect::<Vec<_>>(), [id(1), id(2)]);
+
+    let totals: Vec<_> = holders
+        .holders()
+        .iter()
+        .map(|h| (h.address, h.total))
+        .collect();
+    assert_eq!(totals, [(ALICE, id(60)), (BOB, id(30)), (CAROL, id(15))]);
+
+    let of_two: Vec<_> = holders
+        .holders_of(id(2))
+        .iter()
+        .map(|h| h.address)
+        .collect();
+    assert_eq!(of_two, [BOB]);
+}
+
+// ==================== CLI ====================
+
+/// Serves the fixture logs whose block falls in the requested range
+struct FixtureRpc {
+    logs: serde_json::Value,
+}
+
+impl Respond for FixtureRpc {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        let block = |v: &serde_json::Value| {
+            u64::from_str_radix(v.as_str().unwrap().trim_start_matches("0x"), 16).unwrap()
+        };
+        let result = match body["method"].as_str().unwrap_or_default() {
+            "eth_chainId" => serde_json::json!("0x1"),
+            "eth_blockNumber" => serde_json::json!("0x3e8"),
+            "eth_getLogs" => {
+                let from = block(&body["params"][0]["fromBlock"]);
+                let to = block(&body["params"][0]["toBlock"]);
+                let logs: Vec<_> = self
+                    .logs
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .filter(|log| (from..=to).contains(&block(&log["blockNumber"])))
+                    .cloned()
+                    .collect();
+                serde_json::json!(logs)
+            }
+            _ => serde_json::Value::Null,
+        };
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": body["id"],
+            "result": result
+        }))
+    }
+}
+
+async fn mock_rpc(fixture_name: &str) -> (MockServer, TempDir) {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(FixtureRpc {
+            logs: fixture(fixture_name),
+        })
+        .mount(&server)
+        .await;
+
+    let dir = TempDir::new().unwrap();
+    fs::write(
+        dir.path().join("config.toml"),
+        format!(
+            "[settings]\nconcurrency = 2\ntimeout_seconds = 5\nretry_attempts = 0\n\n\
+             [[endpoints]]\nurl = \"{}\"\nmax_block_range = 2\npriority = 10\n\
+             chain = \"ethereum\"\nnode_type = \"archive\"\n",
+            server.uri()
+        ),
+    )
+    .unwrap();
+    (server, dir)
+}
+
+/// Run `ethcli token holders` in a blocking task (the mock needs the runtime)
+async fn run_holders(config: &TempDir, args: &[&str]) -> serde_json::Value {
+    let config_path = config.path().to_path_buf();
+    let args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+    let output = tokio::task::spawn_blocking(move || {
+        Command::new(assert_cmd::cargo::cargo_bin!("ethcli"))
+            .env("ETHCLI_CONFIG_DIR", config_path)
+            .env_remove("ETHERSCAN_API_KEY")
+            .args(["token", "holders"])
+            .args(args)
+            .args(["-o", "json", "--quiet"])
+            .output()
+            .unwrap()
+    })
+    .await
+    .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_cli_erc721_holders() {
+    let (_server, config) = mock_rpc("erc721_logs.json").await;
+    let result = run_holders(
+        &config,
+        &[
+            ERC721,
+            "--standard",
+            "erc721",
+            "--from-block",
+            "100",
+            "--to-block",
+            "110",
+        ],
+    )
+    .await;
+
+    assert_eq!(result["standard"], "erc721");
+    assert_eq!(result["transfers"], 7);
+    assert_eq!(result["holderCount"], 2);
+    let holders = result["holders"].as_array().unwrap();
+    assert_eq!(holders[0]["address"], format!("{:#x}", BOB));
+    assert_eq!(holders[0]["balance"], "2");
+    assert_eq!(holders[0]["tokens"][1]["tokenId"], "4");
+    assert_eq!(holders[1]["address"], format!("{:#x}", CAROL));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_cli_erc1155_holders_of_token_id() {
+    let (_server, config) = mock_rpc("erc1155_logs.json").await;
+    let result = run_holders(
+        &config,
+        &[
+            ERC1155,
+            "--standard",
+            "erc1155",
+            "--from-block",
+            "200",
+            "--to-block",
+            "210",
+            "--token-id",
+            "1",
+            "--limit",
+            "2",
+        ],
+    )
+    .await;
+
+    assert_eq!(result["tokenId"], "1");
+    assert_eq!(result["holderCount"], 3);
+    let holders = result["holders"].as_array().unwrap();
+    assert_eq!(holders.len(), 2);
+    assert_eq!(holders[0]["address"], format!("{:#x}", ALICE));
+    assert_eq!(holders[0]["balance"], "60");
+    assert_eq!(holders[1]["address"], format!("{:#x}", BOB));
+    assert_eq!(holders[1]["balance"], "25");
+}
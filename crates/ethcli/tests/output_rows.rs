@@ -0,0 +1,143 @@
+//! Golden-file tests for row output (`account txs`, `account erc20`,
+//! `token holders`)
+//!
+//! Rows are built from the Etherscan responses in `tests/fixtures/rows` and
+//! the NFT logs in `tests/fixtures/nft`, written in every format and compared
+//! with the `*.table`/`*.json`/`*.ndjson`/`*.csv` files next to them. Run with
+//! `UPDATE_GOLDEN=1` to rewrite the expected files after an intended change.
+
+use alloy::rpc::types::Log;
+use ethcli::cli::account::{Erc20TransferRow, TxRow};
+use ethcli::cli::token::HolderRow;
+use ethcli::nft::{NftHolders, TokenStandard};
+use ethcli::output::{Row, RowFormat, RowWriter};
+use foundry_block_explorers::account::{ERC20TokenTransferEvent, NormalTransaction};
+use serde::de::DeserializeOwned;
+use std::fs;
+use std::path::PathBuf;
+
+const FORMATS: [(RowFormat, &str); 4] = [
+    (RowFormat::Table, "table"),
+    (RowFormat::Json, "json"),
+    (RowFormat::Ndjson, "ndjson"),
+    (RowFormat::Csv, "csv"),
+];
+
+fn fixture_path(dir: &str, name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(dir)
+        .join(name)
+}
+
+fn fixture<T: DeserializeOwned>(dir: &str, name: &str) -> T {
+    serde_json::from_str(&fs::read_to_string(fixture_path(dir, name)).unwrap()).unwrap()
+}
+
+fn tx_rows() -> Vec<TxRow> {
+    let txs: Vec<NormalTransaction> = fixture("rows", "txlist.json");
+    txs.iter().map(TxRow::from).collect()
+}
+
+fn erc20_rows() -> Vec<Erc20TransferRow> {
+    let transfers: Vec<ERC20TokenTransferEvent> = fixture("rows", "tokentx.json");
+    transfers.iter().map(Erc20TransferRow::from).collect()
+}
+
+fn holder_rows() -> Vec<HolderRow> {
+    let logs: Vec<Log> = fixture("nft", "erc1155_logs.json");
+    NftHolders::from_logs(&logs)
+        .holders()
+        .iter()
+        .enumerate()
+        .map(|(i, h)| HolderRow::new(i + 1, h, TokenStandard::Erc1155))
+        .collect()
+}
+
+/// Write `rows` one per batch, as a paginated fetch would
+fn render<T: Row>(format: RowFormat, rows: &[T]) -> String {
+    let mut writer = RowWriter::<T, Vec<u8>>::from_writer(format, Vec::new());
+    for row in rows.chunks(1) {
+        writer.write_rows(row).unwrap();
+    }
+    writer.finalize().unwrap();
+    assert_eq!(writer.count(), rows.len());
+    String::from_utf8(writer.into_inner()).unwrap()
+}
+
+fn assert_golden<T: Row>(name: &str, rows: &[T]) {
+    for (format, ext) in FORMATS {
+        let actual = render(format, rows);
+        let path = fixture_path("rows", &format!("{name}.{ext}"));
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            fs::write(&path, &actual).unwrap();
+            continue;
+        }
+        let expected = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            actual,
+            expected,
+            "{} differs; rerun with UPDATE_GOLDEN=1 if the change is intended",
+            path.display()
+        );
+    }
+}
+
+/// JSON keys and the CSV header are the same column set, in the same order
+fn assert_columns_match_json<T: Row>(row: &T) {
+    let json = serde_json::to_string(row).unwrap();
+    let value: serde_json::Map<String, serde_json::Value> = serde_json::from_str(&json).unwrap();
+    assert_eq!(value.len(), T::COLUMNS.len());
+    for column in T::COLUMNS {
+        assert!(value.contains_key(*column), "missing JSON key {column}");
+    }
+    let first_key = json[2..].split('"').next().unwrap();
+    assert_eq!(first_key, T::COLUMNS[0]);
+    assert_eq!(row.cells().len(), T::COLUMNS.len());
+    assert_eq!(row.table_cells().len(), T::table_columns().len());
+}
+
+#[test]
+fn test_tx_rows_golden() {
+    assert_golden("txs", &tx_rows());
+}
+
+#[test]
+fn test_erc20_rows_golden() {
+    assert_golden("erc20", &erc20_rows());
+}
+
+#[test]
+fn test_holder_rows_golden() {
+    assert_golden("holders", &holder_rows());
+}
+
+#[test]
+fn test_row_columns_match_json_keys() {
+    assert_columns_match_json(&tx_rows()[0]);
+    assert_columns_match_json(&erc20_rows()[0]);
+    assert_columns_match_json(&holder_rows()[0]);
+}
+
+#[test]
+fn test_contract_creation_row() {
+    let rows = tx_rows();
+    let creation = &rows[2];
+    assert_eq!(creation.to, None);
+    assert_eq!(
+        creation.contract_address.as_deref(),
+        Some("0x0000000000000000000000000000000000c0ffee")
+    );
+    assert_eq!(creation.date_time, "11/01/2024 19:08:40 UTC");
+}
+
+#[test]
+fn test_empty_output() {
+    let rows: Vec<TxRow> = Vec::new();
+    assert_eq!(render(RowFormat::Json, &rows), "[]\n");
+    assert_eq!(render(RowFormat::Ndjson, &rows), "");
+    assert_eq!(
+        render(RowFormat::Csv, &rows),
+        format!("{}\n", TxRow::COLUMNS.join(","))
+    );
+}
@@ -0,0 +1,174 @@
+//! `ethcli rpc multicall` against a mocked JSON-RPC endpoint
+//!
+//! The mock decodes the aggregate3 calldata it receives and answers each
+//! call by its selector, so the tests exercise encoding, allowFailure
+//! handling and result decoding end-to-end.
+
+use alloy::primitives::{Address, Bytes, U256};
+use alloy::sol_types::SolCall;
+use assert_cmd::Command;
+use ethcli::rpc::multicall::{aggregate3Call, Result as CallResult, MULTICALL3_ADDRESS};
+use std::fs;
+use tempfile::TempDir;
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+const USDC: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+const TOTAL_SUPPLY: [u8; 4] = [0x18, 0x16, 0x0d, 0xdd];
+
+/// Answers `totalSupply()` with 1000 and reverts every other call
+struct Multicall3;
+
+impl Respond for Multicall3 {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        let result = match body["method"].as_str().unwrap_or_default() {
+            "eth_chainId" => serde_json::json!("0x1"),
+            "eth_call" => {
+                let tx = &body["params"][0];
+                assert_eq!(
+                    tx["to"].as_str().unwrap().parse::<Address>().unwrap(),
+                    MULTICALL3_ADDRESS
+                );
+                let input = tx["input"].as_str().or(tx["data"].as_str()).unwrap();
+                let input = hex::decode(input.trim_start_matches("0x")).unwrap();
+                let calls = aggregate3Call::abi_decode(&input).unwrap().calls;
+
+                let results: Vec<CallResult> = calls
+                    .iter()
+                    .map(|call| {
+                        if call.callData.as_ref() == TOTAL_SUPPLY {
+                            CallResult {
+                                success: true,
+                                returnData: U256::from(1000).to_be_bytes::<32>().into(),
+                            }
+                        } else {
+                            CallResult {
+                                success: false,
+                                returnData: Bytes::new(),
+                            }
+                        }
+                    })
+                    .collect();
+                // A reverting call that may not fail reverts the whole batch
+                if calls
+                    .iter()
+                    .zip(&results)
+                    .any(|(call, result)| !call.allowFailure && !result.success)
+                {
+                    return ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "id": body["id"],
+                        "error": {"code": 3, "message": "execution reverted: Multicall3: call failed"}
+                    }));
+                }
+                let encoded = aggregate3Call::abi_encode_returns(&results);
+                serde_json::json!(format!("0x{}", hex::encode(encoded)))
+            }
+            _ => serde_json::Value::Null,
+        };
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": body["id"],
+            "result": result
+        }))
+    }
+}
+
+async fn mock_rpc() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(Multicall3)
+        .mount(&server)
+        .await;
+    server
+}
+
+fn calls_file(json: &str) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("calls.json"), json).unwrap();
+    dir
+}
+
+/// Run `ethcli rpc multicall` in a blocking task (the mock needs the runtime)
+async fn run_multicall(server: &MockServer, dir: &TempDir, extra: &[&str]) -> std::process::Output {
+    let file = dir.path().join("calls.json");
+    let config_dir = dir.path().to_path_buf();
+    let url = server.uri();
+    let extra: Vec<String> = extra.iter().map(|s| s.to_string()).collect();
+    tokio::task::spawn_blocking(move || {
+        Command::new(assert_cmd::cargo::cargo_bin!("ethcli"))
+            .env("ETHCLI_CONFIG_DIR", config_dir)
+            .args(["rpc", "multicall", "--rpc-url", &url, "--file"])
+            .arg(file)
+            .args(extra)
+            .output()
+            .unwrap()
+    })
+    .await
+    .unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_multicall_reports_per_call_success() {
+    let server = mock_rpc().await;
+    let dir = calls_file(&format!(
+        r#"[
+            {{"to": "{USDC}", "data": "0x18160ddd", "decode": "uint256"}},
+            {{"to": "{USDC}", "data": "0x313ce567"}}
+        ]"#
+    ));
+
+    let output = run_multicall(&server, &dir, &["-o", "json"]).await;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let rows: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let rows = rows.as_array().unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0]["success"], true);
+    assert_eq!(rows[0]["to"], USDC);
+    assert!(rows[0]["decoded"].as_str().unwrap().contains("1000"));
+    assert_eq!(rows[1]["success"], false);
+    assert_eq!(rows[1]["returnData"], "0x");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("2 calls, 1 succeeded, 1 failed"));
+
+    // Everything went out in one eth_call
+    let eth_calls = server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .filter(|r| String::from_utf8_lossy(&r.body).contains("eth_call"))
+        .count();
+    assert_eq!(eth_calls, 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_multicall_strict_fails_batch() {
+    let server = mock_rpc().await;
+    let dir = calls_file(&format!(
+        r#"[{{"to": "{USDC}", "data": "0x18160ddd"}}, {{"to": "{USDC}", "data": "0x313ce567"}}]"#
+    ));
+
+    let output = run_multicall(&server, &dir, &["--strict"]).await;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Multicall failed"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_multicall_table_output() {
+    let server = mock_rpc().await;
+    let dir = calls_file(&format!(
+        r#"[{{"to": "{USDC}", "data": "0x18160ddd", "decode": "uint256"}}]"#
+    ));
+
+    let output = run_multicall(&server, &dir, &["--quiet"]).await;
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with(&format!("[0] ok   {USDC}")), "{stdout}");
+    assert!(stdout.contains("1000"));
+    assert!(output.stderr.is_empty());
+}
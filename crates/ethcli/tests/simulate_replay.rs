@@ -0,0 +1,233 @@
+//! `ethcli simulate replay` against a mocked anvil node
+
+use assert_cmd::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tempfile::TempDir;
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+const ALICE: &str = "0x00000000000000000000000000000000000000a1";
+const BOB: &str = "0x00000000000000000000000000000000000000b0";
+const TOKEN: &str = "0x00000000000000000000000000000000000000cc";
+const TRANSFER_TOPIC: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
+/// Mines each sent transaction of the fixture bundle: an ERC-20 transfer
+/// using `token_gas`, or reverting when `paused`, then a plain ETH transfer
+struct AnvilNode {
+    token_gas: u64,
+    paused: bool,
+    sent: AtomicUsize,
+}
+
+impl AnvilNode {
+    fn receipt(&self, index: usize) -> serde_json::Value {
+        let word = |addr: &str| format!("0x{:0>64}", &addr[2..]);
+        match index {
+            0 if self.paused => serde_json::json!({
+                "status": "0x0",
+                "gasUsed": format!("{:#x}", self.token_gas),
+                "logs": []
+            }),
+            0 => serde_json::json!({
+                "status": "0x1",
+                "gasUsed": format!("{:#x}", self.token_gas),
+                "logs": [{
+                    "address": TOKEN,
+                    "topics": [TRANSFER_TOPIC, word(ALICE), word(BOB)],
+                    "data": format!("0x{:064x}", 1000)
+                }]
+            }),
+            _ => serde_json::json!({"status": "0x1", "gasUsed": "0x5208", "logs": []}),
+        }
+    }
+}
+
+impl Respond for AnvilNode {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        let result = match body["method"].as_str().unwrap_or_default() {
+            "eth_sendTransaction" => {
+                let index = self.sent.fetch_add(1, Ordering::SeqCst);
+                serde_json::json!(format!("0x{:064x}", index))
+            }
+            "eth_getTransactionReceipt" => {
+                let hash = body["params"][0].as_str().unwrap();
+                let index = usize::from_str_radix(&hash[2..], 16).unwrap();
+                self.receipt(index)
+            }
+            "anvil_reset"
+            | "anvil_impersonateAccount"
+            | "anvil_stopImpersonatingAccount"
+            | "anvil_setNextBlockBaseFeePerGas" => serde_json::Value::Null,
+            other => {
+                return ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": body["id"],
+                    "error": {"code": -32601, "message": format!("unexpected method {}", other)}
+                }))
+            }
+        };
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": body["id"],
+            "result": result
+        }))
+    }
+}
+
+async fn replay(
+    token_gas: u64,
+    paused: bool,
+    extra: &[&str],
+) -> (std::process::Output, MockServer) {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(AnvilNode {
+            token_gas,
+            paused,
+            sent: AtomicUsize::new(0),
+        })
+        .mount(&server)
+        .await;
+    let dir = TempDir::new().unwrap();
+    let config_dir = dir.path().to_path_buf();
+    let bundle = format!(
+        "{}/tests/fixtures/replay/bundle_v1.json",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let url = server.uri();
+    let extra: Vec<String> = extra.iter().map(|s| s.to_string()).collect();
+    let output = tokio::task::spawn_blocking(move || {
+        Command::new(assert_cmd::cargo::cargo_bin!("ethcli"))
+            .env("ETHCLI_CONFIG_DIR", config_dir)
+            .args(["simulate", "replay", &bundle, "--anvil-url", &url])
+            .args(extra)
+            .output()
+            .unwrap()
+    })
+    .await
+    .unwrap();
+    (output, server)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_replay_within_tolerance() {
+    // 52000 -> 53000 gas is +1.92%, inside the default 5%
+    let (output, server) = replay(53_000, false, &["--at-block", "19500000", "-o", "json"]).await;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["backend"], "anvil");
+    assert_eq!(report["block_number"], 19_500_000);
+    assert_eq!(report["failed"], 0);
+    assert_eq!(report["passed"], 8);
+
+    // The node was reset to the requested block and both transactions sent
+    let requests = server.received_requests().await.unwrap();
+    let bodies: Vec<serde_json::Value> = requests
+        .iter()
+        .map(|r| serde_json::from_slice(&r.body).unwrap())
+        .collect();
+    assert_eq!(bodies[0]["method"], "anvil_reset");
+    assert_eq!(bodies[0]["params"][0]["forking"]["blockNumber"], 19_500_000);
+    let sent: Vec<&serde_json::Value> = bodies
+        .iter()
+        .filter(|b| b["method"] == "eth_sendTransaction")
+        .map(|b| &b["params"][0])
+        .collect();
+    assert_eq!(sent.len(), 2);
+    assert_eq!(sent[0]["to"], TOKEN);
+    assert_eq!(sent[0]["gas"], "0x186a0");
+    assert_eq!(sent[1]["value"], "0xde0b6b3a7640000");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_replay_gas_tolerance() {
+    // +15.38% gas fails the default tolerance but passes a 20% one
+    let (output, _) = replay(60_000, false, &[]).await;
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("FAIL  tx 0 gas"), "{stdout}");
+    assert!(
+        stdout.contains("52000 -> 60000 (+15.38%, tolerance 5%)"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("7 passed, 1 failed"), "{stdout}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("1 of 8 replay checks regressed"),
+        "{stderr}"
+    );
+
+    let (output, _) = replay(60_000, false, &["--gas-tolerance", "20"]).await;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_replay_regression_json() {
+    let (output, _) = replay(30_000, true, &["-o", "json", "--gas-tolerance", "50"]).await;
+    assert!(!output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    // Replayed at the bundle's own block when --at-block is omitted
+    assert_eq!(report["block_number"], 19_000_000);
+    let failed: Vec<(u64, &str)> = report["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|r| r["passed"] == false)
+        .map(|r| {
+            (
+                r["transaction"].as_u64().unwrap(),
+                r["check"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(failed, [(0, "status"), (0, "logs"), (0, "assets")]);
+    assert_eq!(report["results"][0]["detail"], "success -> reverted");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_replay_rejects_newer_bundle() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("bundle.json");
+    let mut bundle: serde_json::Value =
+        serde_json::from_str(include_str!("fixtures/replay/bundle_v1.json")).unwrap();
+    bundle["version"] = serde_json::json!(99);
+    std::fs::write(&path, bundle.to_string()).unwrap();
+
+    let output = Command::new(assert_cmd::cargo::cargo_bin!("ethcli"))
+        .env("ETHCLI_CONFIG_DIR", dir.path())
+        .args(["simulate", "replay"])
+        .arg(&path)
+        .args(["--anvil-url", "http://127.0.0.1:1"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("format version 99 is not supported"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn test_replay_missing_file_is_not_read_as_ids() {
+    let dir = TempDir::new().unwrap();
+    let output = Command::new(assert_cmd::cargo::cargo_bin!("ethcli"))
+        .env("ETHCLI_CONFIG_DIR", dir.path())
+        .args(["simulate", "replay"])
+        .arg(dir.path().join("bundel.json"))
+        .args(["--anvil-url", "http://127.0.0.1:1"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Failed to read bundle file"), "{stderr}");
+}
@@ -0,0 +1,238 @@
+//! `ethcli token approvals` against a mocked JSON-RPC chain
+//!
+//! The wallet approved two spenders on each of two tokens. One approval was
+//! revoked by a later event and one was spent down to zero without an
+//! event; an ERC-721 `Approval` log rides along and must be ignored.
+
+use alloy::primitives::{Address, Bytes, U256};
+use alloy::sol_types::SolCall;
+use assert_cmd::Command;
+use ethcli::rpc::multicall::{aggregate3Call, Result as CallResult};
+use std::fs;
+use tempfile::TempDir;
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+const WALLET: &str = "0x00000000000000000000000000000000000000aa";
+const USDC: &str = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+const DAI: &str = "0x6b175474e89094c44da98b954eedeac495271d0f";
+const NFT: &str = "0xbc4ca0eda7647a8ab7c2061c2e118a18a936f13d";
+const ROUTER: &str = "0x00000000000000000000000000000000000000b1";
+const VAULT: &str = "0x00000000000000000000000000000000000000b2";
+const BRIDGE: &str = "0x00000000000000000000000000000000000000b3";
+
+const APPROVAL_TOPIC: &str = "0x8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925";
+const SYMBOL: [u8; 4] = [0x95, 0xd8, 0x9b, 0x41];
+const DECIMALS: [u8; 4] = [0x31, 0x3c, 0xe5, 0x67];
+const ALLOWANCE: [u8; 4] = [0xdd, 0x62, 0xed, 0x3e];
+
+fn word(address: &str) -> String {
+    format!("0x{:0>64}", address.trim_start_matches("0x"))
+}
+
+fn approval_log(token: &str, spender: &str, amount: U256, block: u64) -> serde_json::Value {
+    serde_json::json!({
+        "address": token,
+        "topics": [APPROVAL_TOPIC, word(WALLET), word(spender)],
+        "data": format!("0x{:064x}", amount),
+        "blockNumber": format!("{block:#x}"),
+        "blockHash": format!("0x{block:064x}"),
+        "transactionHash": format!("0x{block:064x}"),
+        "transactionIndex": "0x0",
+        "logIndex": "0x0",
+        "removed": false
+    })
+}
+
+fn abi_string(s: &str) -> Bytes {
+    let mut out = U256::from(32).to_be_bytes_vec();
+    out.extend(U256::from(s.len()).to_be_bytes_vec());
+    let mut data = s.as_bytes().to_vec();
+    data.resize(32, 0);
+    out.extend(data);
+    out.into()
+}
+
+struct Chain;
+
+impl Chain {
+    fn call(target: Address, data: &[u8]) -> CallResult {
+        let target = format!("{:#x}", target);
+        let ok = |bytes: Bytes| CallResult {
+            success: true,
+            returnData: bytes,
+        };
+        match (&data[..4], target.as_str()) {
+            (s, USDC) if s == SYMBOL => ok(abi_string("USDC")),
+            (s, USDC) if s == DECIMALS => ok(U256::from(6).to_be_bytes_vec().into()),
+            (s, DAI) if s == SYMBOL => ok(abi_string("DAI")),
+            (s, DAI) if s == DECIMALS => ok(U256::from(18).to_be_bytes_vec().into()),
+            (s, token) if s == ALLOWANCE => {
+                let spender = format!("0x{}", hex::encode(&data[4 + 32 + 12..4 + 64]));
+                let allowance = match (token, spender.as_str()) {
+                    // Unlimited, partly spent
+                    (USDC, ROUTER) => U256::MAX - U256::from(5_000_000),
+                    // 50 DAI approved, 20 left
+                    (DAI, VAULT) => U256::from(20) * U256::from(10).pow(U256::from(18)),
+                    // Spent without an Approval event
+                    (DAI, BRIDGE) => U256::ZERO,
+                    other => panic!("unexpected allowance call {other:?}"),
+                };
+                ok(allowance.to_be_bytes_vec().into())
+            }
+            _ => CallResult {
+                success: false,
+                returnData: Bytes::new(),
+            },
+        }
+    }
+}
+
+impl Respond for Chain {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        let result = match body["method"].as_str().unwrap_or_default() {
+            "eth_chainId" => serde_json::json!("0x1"),
+            "eth_blockNumber" => serde_json::json!("0x3e8"),
+            "eth_getLogs" => {
+                let filter = &body["params"][0];
+                // Every contract, filtered on the owner topic
+                assert!(filter.get("address").is_none_or(|a| a.is_null()));
+                assert_eq!(filter["topics"][1], word(WALLET));
+
+                let mut erc721 = approval_log(NFT, ROUTER, U256::ZERO, 120);
+                erc721["topics"]
+                    .as_array_mut()
+                    .unwrap()
+                    .push(serde_json::json!(format!("0x{:064x}", 7)));
+                erc721["data"] = serde_json::json!("0x");
+                serde_json::json!([
+                    approval_log(USDC, ROUTER, U256::MAX, 100),
+                    erc721,
+                    approval_log(
+                        DAI,
+                        VAULT,
+                        U256::from(50) * U256::from(10).pow(U256::from(18)),
+                        150
+                    ),
+                    approval_log(DAI, BRIDGE, U256::from(10), 160),
+                    approval_log(USDC, VAULT, U256::from(1_000_000_000), 200),
+                    approval_log(USDC, VAULT, U256::ZERO, 300),
+                ])
+            }
+            "eth_call" => {
+                let tx = &body["params"][0];
+                let input = tx["input"].as_str().or(tx["data"].as_str()).unwrap();
+                let input = hex::decode(input.trim_start_matches("0x")).unwrap();
+                let calls = aggregate3Call::abi_decode(&input).unwrap().calls;
+                let results: Vec<CallResult> = calls
+                    .iter()
+                    .map(|call| Self::call(call.target, &call.callData))
+                    .collect();
+                let encoded = aggregate3Call::abi_encode_returns(&results);
+                serde_json::json!(format!("0x{}", hex::encode(encoded)))
+            }
+            _ => serde_json::Value::Null,
+        };
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": body["id"],
+            "result": result
+        }))
+    }
+}
+
+async fn approvals(extra: &[&str]) -> (std::process::Output, TempDir) {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(Chain)
+        .mount(&server)
+        .await;
+
+    let config = TempDir::new().unwrap();
+    fs::write(
+        config.path().join("config.toml"),
+        format!(
+            "[settings]\nretry_attempts = 0\n\n[[endpoints]]\nurl = \"{}\"\nmax_block_range = 10000\npriority = 10\nchain = \"ethereum\"\n",
+            server.uri()
+        ),
+    )
+    .unwrap();
+
+    let config_path = config.path().to_path_buf();
+    let extra: Vec<String> = extra.iter().map(|s| s.to_string()).collect();
+    let output = tokio::task::spawn_blocking(move || {
+        Command::new(assert_cmd::cargo::cargo_bin!("ethcli"))
+            .env("ETHCLI_CONFIG_DIR", config_path)
+            .env_remove("GOPLUS_APP_KEY")
+            .env_remove("GOPLUS_APP_SECRET")
+            .args([
+                "token",
+                "approvals",
+                WALLET,
+                "--from-block",
+                "0",
+                "--to-block",
+                "999",
+                "--quiet",
+            ])
+            .args(extra)
+            .output()
+            .unwrap()
+    })
+    .await
+    .unwrap();
+    (output, config)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_approvals_json() {
+    let (output, _config) = approvals(&["-o", "json"]).await;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["owner"], WALLET);
+    assert_eq!(json["approvalCount"], 2);
+    assert_eq!(json["unlimitedCount"], 1);
+
+    let rows = json["approvals"].as_array().unwrap();
+    // Unlimited first
+    assert_eq!(rows[0]["token"], USDC);
+    assert_eq!(rows[0]["symbol"], "USDC");
+    assert_eq!(rows[0]["spender"], ROUTER);
+    assert_eq!(rows[0]["unlimited"], true);
+    assert_eq!(rows[0]["block"], 100);
+    assert!(rows[0]["risk"].is_null());
+
+    assert_eq!(rows[1]["token"], DAI);
+    assert_eq!(rows[1]["spender"], VAULT);
+    assert_eq!(rows[1]["allowance"], "20000000000000000000");
+    assert_eq!(rows[1]["unlimited"], false);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_approvals_table() {
+    let (output, _config) = approvals(&[]).await;
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("UNLIMITED"), "{stdout}");
+    assert!(stdout.contains("DAI"));
+    assert!(!stdout.contains(BRIDGE));
+    assert!(stdout.contains("2 outstanding approval(s), 1 unlimited"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_approvals_csv() {
+    let (output, _config) = approvals(&["-o", "csv"]).await;
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines();
+    assert_eq!(
+        lines.next(),
+        Some("token,symbol,spender,allowance,unlimited,block,risk")
+    );
+    assert_eq!(lines.count(), 2);
+}
@@ -0,0 +1,119 @@
+//! `ethcli tx gas-breakdown` against a mocked debug JSON-RPC endpoint
+
+use assert_cmd::Command;
+use tempfile::TempDir;
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+const HASH: &str = "0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060";
+
+/// Serves a plain transfer with one SSTORE and one LOG, optionally without
+/// the debug namespace
+struct DebugNode {
+    debug: bool,
+}
+
+impl Respond for DebugNode {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        let result = match body["method"].as_str().unwrap_or_default() {
+            "eth_getTransactionByHash" => serde_json::json!({
+                "hash": HASH,
+                "to": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+                "input": "0x",
+            }),
+            "eth_getTransactionReceipt" => serde_json::json!({"gasUsed": "0x7530"}),
+            "debug_traceTransaction" if self.debug => serde_json::json!({
+                "gas": 30_000,
+                "failed": false,
+                "returnValue": "",
+                "structLogs": [
+                    {"pc": 0, "op": "PUSH1", "gas": 79_000, "gasCost": 3, "depth": 1},
+                    {"pc": 2, "op": "SSTORE", "gas": 78_997, "gasCost": 5_000, "depth": 1},
+                    {"pc": 3, "op": "LOG0", "gas": 73_997, "gasCost": 3_997, "depth": 1},
+                    {"pc": 4, "op": "STOP", "gas": 70_000, "gasCost": 0, "depth": 1}
+                ]
+            }),
+            _ => {
+                return ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": body["id"],
+                    "error": {"code": -32601, "message": "the method debug_traceTransaction does not exist/is not available"}
+                }))
+            }
+        };
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": body["id"],
+            "result": result
+        }))
+    }
+}
+
+async fn gas_breakdown(debug: bool, extra: &[&str]) -> std::process::Output {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(DebugNode { debug })
+        .mount(&server)
+        .await;
+    let dir = TempDir::new().unwrap();
+    let config_dir = dir.path().to_path_buf();
+    let url = server.uri();
+    let extra: Vec<String> = extra.iter().map(|s| s.to_string()).collect();
+    tokio::task::spawn_blocking(move || {
+        Command::new(assert_cmd::cargo::cargo_bin!("ethcli"))
+            .env("ETHCLI_CONFIG_DIR", config_dir)
+            .args(["tx", "gas-breakdown", HASH, "--rpc-url", &url])
+            .args(extra)
+            .output()
+            .unwrap()
+    })
+    .await
+    .unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_gas_breakdown_json() {
+    let output = gas_breakdown(true, &["--format", "json"]).await;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["gas_used"], 30_000);
+    let gas = |category: &str| {
+        json["categories"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|c| c["category"] == category)
+            .unwrap()["gas"]
+            .as_i64()
+            .unwrap()
+    };
+    assert_eq!(gas("intrinsic"), 21_000);
+    assert_eq!(gas("storage_write"), 5_000);
+    assert_eq!(gas("logs"), 3_997);
+    assert_eq!(gas("computation"), 3);
+    assert_eq!(gas("refund"), 0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_gas_breakdown_table() {
+    let output = gas_breakdown(true, &[]).await;
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Gas used: 30000"));
+    assert!(stdout.contains("Storage writes"));
+    assert!(stdout.contains("16.67%"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_gas_breakdown_without_debug_namespace() {
+    let output = gas_breakdown(false, &[]).await;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("does not exist"), "{stderr}");
+    assert!(stderr.contains("has_debug = true"), "{stderr}");
+}
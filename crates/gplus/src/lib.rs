@@ -46,6 +46,7 @@
 //! - **Address Security** - Malicious address detection
 //! - **NFT Security** - NFT collection verification
 //! - **Approval Security** - ERC20/721/1155 approval risks
+//! - **Revoke Calldata** - Offline `approve`/`setApprovalForAll` encoding to revoke risky approvals
 //!
 //! ## Supported Chains
 //!
@@ -58,10 +59,12 @@
 
 pub mod client;
 pub mod error;
+pub mod revoke;
 pub mod types;
 
 pub use client::{Client, Config, Credentials, RateLimitInfo, BASE_URL};
 pub use error::{Error, Result};
+pub use revoke::{build_revoke_calldata, TokenStandard};
 pub use types::{
     AddressSecurity, ApprovalSecurity, Chain, NftSecurity, TokenSecurity, TokenSecurityResponse,
 };
@@ -0,0 +1,106 @@
+//! Approval revoke calldata
+//!
+//! Builds the transaction data to revoke a spender's allowance once
+//! [`ApprovalSecurity`](crate::ApprovalSecurity) flags it. This is pure ABI
+//! encoding; nothing is sent to the network.
+
+use crate::error::{invalid_address, Result};
+use std::fmt;
+use std::str::FromStr;
+use yldfi_common::eth::is_valid_address;
+
+/// `approve(address,uint256)`
+const APPROVE_SELECTOR: &str = "095ea7b3";
+
+/// `setApprovalForAll(address,bool)`
+const SET_APPROVAL_FOR_ALL_SELECTOR: &str = "a22cb465";
+
+/// Token standard, which decides how an approval is revoked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TokenStandard {
+    /// Fungible token: `approve(spender, 0)`
+    #[default]
+    Erc20,
+    /// NFT collection: `setApprovalForAll(spender, false)`
+    Erc721,
+    /// Multi-token: `setApprovalForAll(spender, false)`
+    Erc1155,
+}
+
+impl TokenStandard {
+    /// Get the standard name
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Erc20 => "ERC20",
+            Self::Erc721 => "ERC721",
+            Self::Erc1155 => "ERC1155",
+        }
+    }
+}
+
+impl fmt::Display for TokenStandard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TokenStandard {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace(['-', '_'], "").as_str() {
+            "erc20" | "20" => Ok(Self::Erc20),
+            "erc721" | "721" => Ok(Self::Erc721),
+            "erc1155" | "1155" => Ok(Self::Erc1155),
+            _ => Err(format!("unknown token standard: {s}")),
+        }
+    }
+}
+
+/// Build calldata that revokes `spender`'s approval on `token`
+///
+/// Send the returned `0x`-prefixed data to `token`. ERC20 approvals are
+/// reset with `approve(spender, 0)`; ERC721 and ERC1155 operator approvals
+/// with `setApprovalForAll(spender, false)`.
+///
+/// # Errors
+///
+/// Returns an error if `token` or `spender` is not a valid address.
+///
+/// # Example
+///
+/// ```
+/// use gplus::{build_revoke_calldata, TokenStandard};
+///
+/// let data = build_revoke_calldata(
+///     "0xdac17f958d2ee523a2206206994597c13d831ec7",
+///     "0x1111111254eeb25477b68fb85ed929f73a960582",
+///     TokenStandard::Erc20,
+/// )?;
+/// assert!(data.starts_with("0x095ea7b3"));
+/// # Ok::<(), gplus::Error>(())
+/// ```
+pub fn build_revoke_calldata(
+    token: &str,
+    spender: &str,
+    standard: TokenStandard,
+) -> Result<String> {
+    if !is_valid_address(token) {
+        return Err(invalid_address(token));
+    }
+    if !is_valid_address(spender) {
+        return Err(invalid_address(spender));
+    }
+
+    let selector = match standard {
+        TokenStandard::Erc20 => APPROVE_SELECTOR,
+        TokenStandard::Erc721 | TokenStandard::Erc1155 => SET_APPROVAL_FOR_ALL_SELECTOR,
+    };
+    // Both calls take (address, 0): a zero amount or `false`
+    Ok(format!(
+        "0x{selector}{:0>64}{:064x}",
+        spender[2..].to_lowercase(),
+        0
+    ))
+}
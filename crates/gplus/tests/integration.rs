@@ -362,6 +362,47 @@ async fn test_approval_security_malicious() {
     assert!(!security.is_trusted());
 }
 
+// ==================== Revoke Calldata Tests ====================
+
+const USDT: &str = "0xdac17f958d2ee523a2206206994597c13d831ec7";
+const SPENDER: &str = "0x1111111254EEB25477B68FB85ED929F73A960582";
+
+#[test]
+fn test_revoke_calldata_erc20() {
+    let data = gplus::build_revoke_calldata(USDT, SPENDER, gplus::TokenStandard::Erc20).unwrap();
+    assert_eq!(
+        data,
+        concat!(
+            "0x095ea7b3",
+            "0000000000000000000000001111111254eeb25477b68fb85ed929f73a960582",
+            "0000000000000000000000000000000000000000000000000000000000000000"
+        )
+    );
+}
+
+#[test]
+fn test_revoke_calldata_nft_standards() {
+    for standard in [gplus::TokenStandard::Erc721, gplus::TokenStandard::Erc1155] {
+        let data = gplus::build_revoke_calldata(USDT, SPENDER, standard).unwrap();
+        assert!(data.starts_with("0xa22cb465"), "{standard}: {data}");
+        assert_eq!(data.len(), 2 + 8 + 128);
+        assert!(data.ends_with(&"0".repeat(64)));
+    }
+    assert_eq!(
+        "erc-1155".parse::<gplus::TokenStandard>(),
+        Ok(gplus::TokenStandard::Erc1155)
+    );
+    assert!("erc4626".parse::<gplus::TokenStandard>().is_err());
+}
+
+#[test]
+fn test_revoke_calldata_rejects_invalid_addresses() {
+    let err =
+        gplus::build_revoke_calldata("0x123", SPENDER, gplus::TokenStandard::Erc20).unwrap_err();
+    assert!(err.to_string().contains("Invalid address"));
+    assert!(gplus::build_revoke_calldata(USDT, "spender", gplus::TokenStandard::Erc20).is_err());
+}
+
 // ==================== Authentication Tests ====================
 
 #[tokio::test]
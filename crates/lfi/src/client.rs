@@ -2,9 +2,10 @@
 
 use crate::error::{self, Error, Result};
 use crate::types::{
-    Chain, ChainId, ChainsResponse, Connection, ConnectionsRequest, ConnectionsResponse, Quote,
-    QuoteRequest, Route, RoutesRequest, RoutesResponse, StatusRequest, StatusResponse, Token,
-    TokensRequest, TokensResponse, Tool, ToolsResponse,
+    Chain, ChainId, ChainsResponse, Connection, ConnectionsRequest, ConnectionsResponse,
+    ContractCallsQuoteRequest, Quote, QuoteRequest, Route, RoutesRequest, RoutesResponse,
+    StatusRequest, StatusResponse, Token, TokensRequest, TokensResponse, Tool, ToolsResponse,
+    ZapQuote, ZapRequest,
 };
 use reqwest::Client as HttpClient;
 use serde::de::DeserializeOwned;
@@ -320,6 +321,67 @@ impl Client {
         self.post("/advanced/stepTransaction", step).await
     }
 
+    // ========================================================================
+    // Contract Calls API
+    // ========================================================================
+
+    /// Get a quote that bridges/swaps and then executes contract calls on the
+    /// destination chain
+    pub async fn get_contract_calls_quote(
+        &self,
+        request: &ContractCallsQuoteRequest,
+    ) -> Result<Quote> {
+        let mut request = request.clone();
+        if request.integrator.is_none() {
+            request.integrator = self.integrator.clone();
+        }
+        if request.referrer.is_none() {
+            request.referrer = self.referrer.clone();
+        }
+        if request.fee.is_none() {
+            request.fee = self.fee;
+        }
+
+        self.post("/quote/contractCalls", &request).await
+    }
+
+    /// Quote a zap from any chain/token into an ERC-4626 vault
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use lfi::{chains, Client, Erc4626Vault, ZapRequest};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), lfi::Error> {
+    ///     let client = Client::with_integrator("my-app")?;
+    ///
+    ///     let vault = Erc4626Vault::new(
+    ///         chains::BASE,
+    ///         "0xYourVaultAddress",
+    ///         "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913", // USDC on Base
+    ///     );
+    ///     let zap = ZapRequest::to_erc4626_vault(
+    ///         chains::ARBITRUM,
+    ///         "0xaf88d065e77c8cC2239327C5EDb3A432268e5831",
+    ///         "100000000",
+    ///         vault,
+    ///         "0xYourAddress",
+    ///     );
+    ///
+    ///     let quote = client.get_zap_quote(&zap).await?;
+    ///     println!("Pay {} for ~{} shares", quote.from_amount(), quote.expected_shares);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_zap_quote(&self, request: &ZapRequest) -> Result<ZapQuote> {
+        let quote = self
+            .get_contract_calls_quote(&request.to_contract_calls_request()?)
+            .await?;
+        ZapQuote::from_quote(quote, request)
+    }
+
     // ========================================================================
     // Status API
     // ========================================================================
@@ -714,6 +776,179 @@ mod tests {
         assert_eq!(request.chains.as_ref().unwrap().len(), 2);
     }
 
+    const ARB_USDC: &str = "0xaf88d065e77c8cC2239327C5EDb3A432268e5831";
+    const BASE_USDC: &str = "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913";
+    const BASE_USDBC: &str = "0xd9aAEc86B65D86f6A7B5B1b0c42FFA531710b6CA";
+    const VAULT: &str = "0xbeeF010f9cb27031ad51e3333f9aF9C6B1228183";
+    const RECEIVER: &str = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+
+    fn zap_request() -> crate::types::ZapRequest {
+        crate::types::ZapRequest::to_erc4626_vault(
+            chains::ARBITRUM,
+            ARB_USDC,
+            "100000000",
+            crate::types::Erc4626Vault::new(chains::BASE, VAULT, BASE_USDC),
+            RECEIVER,
+        )
+        .with_slippage(0.5)
+    }
+
+    fn token(chain_id: u64, address: &str, symbol: &str) -> serde_json::Value {
+        serde_json::json!({
+            "address": address,
+            "chainId": chain_id,
+            "symbol": symbol,
+            "decimals": 6,
+            "name": symbol
+        })
+    }
+
+    /// Hand-written contract-calls quote in the `/quote` response shape for a
+    /// 100 USDC Arbitrum -> Base vault deposit
+    fn zap_quote_fixture(bridge_to: &str) -> Quote {
+        let arb_usdc = token(42161, ARB_USDC, "USDC");
+        let bridge_token = token(8453, bridge_to, "USDC");
+        let shares = token(8453, VAULT, "bbqUSDC");
+        serde_json::from_value(serde_json::json!({
+            "id": "0x4b1e9c1b7c8f2f8f0b5a1c6d2e3f4a5b6c7d8e9f0a1b2c3d4e5f60718293a4b5",
+            "type": "lifi",
+            "tool": "across",
+            "action": {
+                "fromChainId": 42161,
+                "fromToken": arb_usdc,
+                "fromAmount": "100142311",
+                "toChainId": 8453,
+                "toToken": shares,
+                "slippage": 0.005,
+                "fromAddress": RECEIVER,
+                "toAddress": RECEIVER
+            },
+            "estimate": {
+                "fromAmount": "100142311",
+                "toAmount": "100000000",
+                "toAmountMin": "100000000",
+                "approvalAddress": "0x1231DEB6f5749EF6cE6943a275A1D3E7486F4EaE",
+                "executionDuration": 64
+            },
+            "includedSteps": [
+                {
+                    "id": "bridge",
+                    "type": "cross",
+                    "tool": "across",
+                    "action": {
+                        "fromChainId": 42161,
+                        "fromToken": arb_usdc,
+                        "fromAmount": "100142311",
+                        "toChainId": 8453,
+                        "toToken": bridge_token
+                    },
+                    "estimate": {
+                        "fromAmount": "100142311",
+                        "toAmount": "100000000",
+                        "toAmountMin": "99500000"
+                    }
+                },
+                {
+                    "id": "deposit",
+                    "type": "custom",
+                    "tool": "custom",
+                    "action": {
+                        "fromChainId": 8453,
+                        "fromToken": bridge_token,
+                        "fromAmount": "100000000",
+                        "toChainId": 8453,
+                        "toToken": shares,
+                        "destinationCallData": "0x6e553f65"
+                    },
+                    "estimate": {
+                        "fromAmount": "100000000",
+                        "toAmount": "94321877",
+                        "toAmountMin": "93850267"
+                    }
+                }
+            ],
+            "transactionRequest": {
+                "to": "0x1231DEB6f5749EF6cE6943a275A1D3E7486F4EaE",
+                "data": "0x",
+                "value": "0x0",
+                "chainId": 42161
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_zap_deposit_calldata() {
+        let calldata = zap_request().deposit_calldata().unwrap();
+        assert_eq!(
+            calldata,
+            concat!(
+                "0x6e553f65",
+                "0000000000000000000000000000000000000000000000000000000005f5e100",
+                "000000000000000000000000d8da6bf26964af9d7eed9e03e53415d37aa96045"
+            )
+        );
+    }
+
+    #[test]
+    fn test_zap_contract_calls_request() {
+        let request = zap_request().to_contract_calls_request().unwrap();
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["toChain"], 8453);
+        assert_eq!(json["toToken"], BASE_USDC);
+        assert_eq!(json["toAmount"], "100000000");
+        assert_eq!(json["fromAddress"], RECEIVER);
+        let call = &json["contractCalls"][0];
+        assert_eq!(call["toContractAddress"], VAULT);
+        assert_eq!(call["contractOutputsToken"], VAULT);
+        assert_eq!(call["fromTokenAddress"], BASE_USDC);
+        assert_eq!(call["toContractGasLimit"], "300000");
+        assert!(call["toContractCallData"]
+            .as_str()
+            .unwrap()
+            .starts_with("0x6e553f65"));
+    }
+
+    #[test]
+    fn test_zap_rejects_invalid_input() {
+        let mut zap = zap_request();
+        zap.amount = "1.5".to_string();
+        assert!(matches!(
+            zap.deposit_calldata(),
+            Err(Error::Domain(crate::error::DomainError::InvalidAmount(_)))
+        ));
+
+        let mut zap = zap_request();
+        zap.vault.asset = "usdc".to_string();
+        assert!(matches!(
+            zap.to_contract_calls_request(),
+            Err(Error::Domain(
+                crate::error::DomainError::InvalidTokenAddress(_)
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_zap_quote_from_contract_calls_quote() {
+        let zap = zap_request();
+        let quote = crate::types::ZapQuote::from_quote(zap_quote_fixture(BASE_USDC), &zap).unwrap();
+        assert_eq!(quote.expected_shares, 94_321_877);
+        assert_eq!(quote.min_shares, 93_850_267);
+        assert_eq!(quote.from_amount(), "100142311");
+        assert_eq!(quote.deposit_amount, "100000000");
+    }
+
+    #[test]
+    fn test_zap_quote_asset_mismatch() {
+        let err = crate::types::ZapQuote::from_quote(zap_quote_fixture(BASE_USDBC), &zap_request())
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Domain(crate::error::DomainError::VaultAssetMismatch { .. })
+        ));
+        assert!(err.to_string().contains(BASE_USDBC));
+    }
+
     #[tokio::test]
     #[ignore]
     async fn test_get_quote_real() {
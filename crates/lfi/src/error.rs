@@ -29,6 +29,25 @@ pub enum DomainError {
     /// Slippage too high
     #[error("Slippage exceeded maximum allowed: {0}%")]
     SlippageExceeded(f64),
+
+    /// Invalid token amount
+    #[error("Invalid amount: {0}")]
+    InvalidAmount(String),
+
+    /// Zap route delivers a different token than the vault accepts
+    #[error(
+        "Vault asset {vault_asset} does not match the bridge destination token {bridge_token}"
+    )]
+    VaultAssetMismatch {
+        /// Asset the ERC-4626 vault accepts
+        vault_asset: String,
+        /// Token the bridge leg delivers on the vault chain
+        bridge_token: String,
+    },
+
+    /// Contract-calls quote has no step depositing into the vault
+    #[error("Quote does not include a deposit into vault {0}")]
+    MissingVaultDeposit(String),
 }
 
 /// Error type for LI.FI API operations
@@ -66,3 +85,24 @@ pub fn invalid_token_address(address: impl Into<String>) -> Error {
 pub fn slippage_exceeded(slippage: f64) -> Error {
     ApiError::domain(DomainError::SlippageExceeded(slippage))
 }
+
+/// Create an invalid amount error
+pub fn invalid_amount(amount: impl Into<String>) -> Error {
+    ApiError::domain(DomainError::InvalidAmount(amount.into()))
+}
+
+/// Create a vault asset mismatch error
+pub fn vault_asset_mismatch(
+    vault_asset: impl Into<String>,
+    bridge_token: impl Into<String>,
+) -> Error {
+    ApiError::domain(DomainError::VaultAssetMismatch {
+        vault_asset: vault_asset.into(),
+        bridge_token: bridge_token.into(),
+    })
+}
+
+/// Create a missing vault deposit error
+pub fn missing_vault_deposit(vault: impl Into<String>) -> Error {
+    ApiError::domain(DomainError::MissingVaultDeposit(vault.into()))
+}
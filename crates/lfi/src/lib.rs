@@ -11,6 +11,7 @@
 //! - **DEX aggregation**: Optimal routing through DEXs on each chain
 //! - **Route optimization**: Find the best route by price, speed, or security
 //! - **Transaction tracking**: Monitor cross-chain transaction status
+//! - **Vault zaps**: Bridge any token into an ERC-4626 vault ([`ZapRequest`])
 //!
 //! # Quick Start
 //!
@@ -51,7 +52,7 @@
 //! Use the advanced routes API to get multiple route options:
 //!
 //! ```no_run
-//! use lfi::{Client, RoutesRequest, RoutesOptions, RouteOrder, chains};
+//! use lfi::{Client, RoutesRequest, RoutesOptions, RouteOrder, RouteScorer, chains};
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<(), lfi::Error> {
@@ -76,6 +77,29 @@
 //!         println!("Route via {:?}", route.steps.iter().map(|s| &s.tool).collect::<Vec<_>>());
 //!         println!("  Output: {}", route.to_amount);
 //!         println!("  Gas cost: {:?}", route.gas_cost_usd);
+//!         // None if any step has no estimate, so the ETA is never too short
+//!         if let Some(eta) = route.total_estimated_duration() {
+//!             println!("  Arrives in ~{} min", eta.as_secs().div_ceil(60));
+//!         }
+//!     }
+//!
+//!     // Pick per objective: net USD output, estimated duration, or bridge risk
+//!     if let Some(route) = response.cheapest() {
+//!         println!("Cheapest: {} (net ${:.2})", route.id, route.net_output_usd().unwrap_or_default());
+//!     }
+//!     if let Some(route) = response.fastest() {
+//!         println!("Fastest: {} ({:?}s)", route.id, route.estimated_duration());
+//!     }
+//!     if let Some(route) = response.most_secure() {
+//!         println!("Most secure: {} ({} bridge hops)", route.id, route.bridge_hops());
+//!     }
+//!
+//!     // Or re-rank with your own weights: $0.50 per minute, distrust one bridge
+//!     let scorer = RouteScorer::default()
+//!         .with_time_weight(0.5)
+//!         .with_bridge_trust("hop", 0.95);
+//!     for scored in response.ranked_by(&scorer) {
+//!         println!("{}: {:.2}", scored.route.id, scored.score);
 //!     }
 //!
 //!     Ok(())
@@ -216,10 +240,12 @@
 
 pub mod client;
 pub mod error;
+pub mod scoring;
 pub mod types;
 
 pub use client::{Client, Config};
 pub use error::{Error, Result};
+pub use scoring::{RouteScorer, ScoredRoute};
 pub use types::{
     // Chain types
     chains,
@@ -234,6 +260,10 @@ pub use types::{
     Connection,
     ConnectionsRequest,
     ConnectionsResponse,
+    // Contract call / zap types
+    ContractCall,
+    ContractCallsQuoteRequest,
+    Erc4626Vault,
     Estimate,
     ExchangeOptions,
     FeeCost,
@@ -270,6 +300,8 @@ pub use types::{
     // Transaction types
     TransactionRequest,
     TransactionStatus,
+    ZapQuote,
+    ZapRequest,
 };
 
 /// Default base URL for the LI.FI API
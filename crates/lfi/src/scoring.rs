@@ -0,0 +1,362 @@
+//! Weighted route scoring
+//!
+//! [`RouteOrder`](crate::RouteOrder) asks LI.FI for one fixed ordering.
+//! [`RouteScorer`] re-ranks a [`RoutesResponse`](crate::RoutesResponse)
+//! locally, trading destination value against gas, estimated duration and
+//! how much each bridge is trusted.
+
+use crate::types::{Route, Step, StepType};
+use std::collections::BTreeMap;
+
+/// Trust multipliers for major bridges, keyed by LI.FI tool key
+///
+/// Canonical rollup bridges and the most battle-tested liquidity bridges
+/// are fully trusted; newer or more complex designs get a small discount.
+const DEFAULT_BRIDGE_TRUST: &[(&str, f64)] = &[
+    ("arbitrum", 1.0),
+    ("optimism", 1.0),
+    ("polygon", 1.0),
+    ("gnosis", 1.0),
+    ("across", 0.999),
+    ("stargate", 0.998),
+    ("stargatev2", 0.998),
+    ("celercircle", 0.998),
+    ("hop", 0.997),
+    ("amarok", 0.997),
+    ("cbridge", 0.995),
+    ("relay", 0.995),
+    ("mayan", 0.993),
+    ("symbiosis", 0.99),
+    ("allbridge", 0.99),
+];
+
+/// Scores routes from their USD figures, duration and bridges
+///
+/// ```text
+/// score = output_weight * to_amount_usd * trust
+///       - gas_weight    * gas_cost_usd
+///       - time_weight   * duration_minutes
+///       - missing_usd_penalty * (number of missing USD figures)
+/// ```
+///
+/// `trust` is the lowest multiplier among the bridges the route crosses
+/// (1.0 for same-chain routes). A missing destination value counts as 0,
+/// a missing gas cost as 0, and each costs `missing_usd_penalty`; a missing
+/// duration is taken as `assumed_duration_secs`. Scores are always finite.
+///
+/// ```
+/// use lfi::RouteScorer;
+///
+/// // Value speed at $1 per minute and distrust one bridge
+/// let scorer = RouteScorer::default()
+///     .with_time_weight(1.0)
+///     .with_bridge_trust("hop", 0.9);
+/// assert_eq!(scorer.trust_for("HOP"), 0.9);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteScorer {
+    /// Weight of the destination value in USD
+    pub output_weight: f64,
+    /// Weight of the gas cost in USD
+    pub gas_weight: f64,
+    /// USD cost per minute of estimated duration
+    pub time_weight: f64,
+    /// Trust multiplier per bridge tool key (lowercase)
+    pub bridge_trust: BTreeMap<String, f64>,
+    /// Multiplier for bridges not in `bridge_trust`
+    pub default_bridge_trust: f64,
+    /// USD subtracted for each missing USD figure
+    pub missing_usd_penalty: f64,
+    /// Duration assumed when a route has no estimate
+    pub assumed_duration_secs: u64,
+}
+
+impl Default for RouteScorer {
+    /// Conservative scorer
+    ///
+    /// Output and gas count at face value, time at $0.10 per minute,
+    /// unknown bridges are discounted 2%, a missing USD figure costs $25
+    /// and a missing duration is assumed to be an hour.
+    fn default() -> Self {
+        Self {
+            output_weight: 1.0,
+            gas_weight: 1.0,
+            time_weight: 0.1,
+            bridge_trust: DEFAULT_BRIDGE_TRUST
+                .iter()
+                .map(|(tool, trust)| ((*tool).to_string(), *trust))
+                .collect(),
+            default_bridge_trust: 0.98,
+            missing_usd_penalty: 25.0,
+            assumed_duration_secs: 3600,
+        }
+    }
+}
+
+impl RouteScorer {
+    /// Create the default (conservative) scorer
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the destination value weight
+    #[must_use]
+    pub fn with_output_weight(mut self, weight: f64) -> Self {
+        self.output_weight = weight;
+        self
+    }
+
+    /// Set the gas cost weight
+    #[must_use]
+    pub fn with_gas_weight(mut self, weight: f64) -> Self {
+        self.gas_weight = weight;
+        self
+    }
+
+    /// Set the USD cost per minute of estimated duration
+    #[must_use]
+    pub fn with_time_weight(mut self, weight: f64) -> Self {
+        self.time_weight = weight;
+        self
+    }
+
+    /// Set the trust multiplier for a bridge tool key
+    #[must_use]
+    pub fn with_bridge_trust(mut self, tool: impl Into<String>, trust: f64) -> Self {
+        self.bridge_trust
+            .insert(tool.into().to_ascii_lowercase(), trust);
+        self
+    }
+
+    /// Set the multiplier for bridges without an entry
+    #[must_use]
+    pub fn with_default_bridge_trust(mut self, trust: f64) -> Self {
+        self.default_bridge_trust = trust;
+        self
+    }
+
+    /// Set the penalty for each missing USD figure
+    #[must_use]
+    pub fn with_missing_usd_penalty(mut self, penalty: f64) -> Self {
+        self.missing_usd_penalty = penalty;
+        self
+    }
+
+    /// Set the duration assumed for routes without an estimate
+    #[must_use]
+    pub fn with_assumed_duration(mut self, secs: u64) -> Self {
+        self.assumed_duration_secs = secs;
+        self
+    }
+
+    /// Trust multiplier for a bridge tool key (case-insensitive)
+    #[must_use]
+    pub fn trust_for(&self, tool: &str) -> f64 {
+        self.bridge_trust
+            .get(&tool.to_ascii_lowercase())
+            .copied()
+            .unwrap_or(self.default_bridge_trust)
+    }
+
+    /// Lowest trust among the bridges a route crosses (1.0 when none)
+    #[must_use]
+    pub fn route_trust(&self, route: &Route) -> f64 {
+        fn min_trust(scorer: &RouteScorer, steps: &[Step], trust: f64) -> f64 {
+            steps.iter().fold(trust, |trust, step| {
+                let trust = if step.step_type == StepType::Cross {
+                    trust.min(scorer.trust_for(&step.tool))
+                } else {
+                    trust
+                };
+                min_trust(scorer, &step.included_steps, trust)
+            })
+        }
+        min_trust(self, &route.steps, 1.0)
+    }
+
+    /// Score a route; higher is better
+    #[must_use]
+    pub fn score(&self, route: &Route) -> f64 {
+        let mut missing = 0u32;
+        let output = route.to_amount_usd_value().unwrap_or_else(|| {
+            missing += 1;
+            0.0
+        });
+        let gas = route.gas_cost_usd_value().unwrap_or_else(|| {
+            missing += 1;
+            0.0
+        });
+        let minutes = route
+            .estimated_duration()
+            .unwrap_or(self.assumed_duration_secs) as f64
+            / 60.0;
+
+        let score = self.output_weight * output * self.route_trust(route)
+            - self.gas_weight * gas
+            - self.time_weight * minutes
+            - self.missing_usd_penalty * f64::from(missing);
+        if score.is_nan() {
+            f64::MIN
+        } else {
+            score
+        }
+    }
+}
+
+/// A route with the score it was ranked by
+#[derive(Debug, Clone, Copy)]
+pub struct ScoredRoute<'a> {
+    /// The route
+    pub route: &'a Route,
+    /// Its score under the scorer used for ranking
+    pub score: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::fixtures::RouteBuilder;
+    use crate::types::RoutesResponse;
+
+    fn scoring_fixture() -> RoutesResponse {
+        RoutesResponse {
+            routes: vec![
+                RouteBuilder::new("fast-hop")
+                    .output_usd("995")
+                    .gas_usd("2")
+                    .duration(60)
+                    .bridge("hop")
+                    .build(),
+                RouteBuilder::new("slow-across")
+                    .output_usd("998")
+                    .gas_usd("1")
+                    .duration(1200)
+                    .bridge("across")
+                    .build(),
+                RouteBuilder::new("new-bridge")
+                    .output_usd("999")
+                    .gas_usd("1")
+                    .duration(1800)
+                    .bridge("newbridge")
+                    .build(),
+                RouteBuilder::new("no-usd")
+                    .duration(30)
+                    .bridge("across")
+                    .build(),
+            ],
+            unavailable_routes: None,
+        }
+    }
+
+    fn ranked_ids(response: &RoutesResponse, scorer: &RouteScorer) -> Vec<String> {
+        response
+            .ranked_by(scorer)
+            .iter()
+            .map(|scored| scored.route.id.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_ranked_by_default_scorer() {
+        let response = scoring_fixture();
+        let ranked = response.ranked_by(&RouteScorer::default());
+
+        // 998 * 0.999 - 1 - 20min * 0.1 beats 995 * 0.997 - 2 - 1min * 0.1
+        assert_eq!(ranked[0].route.id, "slow-across");
+        assert!((ranked[0].score - 994.002).abs() < 1e-9);
+        assert_eq!(ranked[1].route.id, "fast-hop");
+        assert_eq!(ranked[2].route.id, "new-bridge");
+
+        // Missing USD figures cost an explicit penalty instead of NaN
+        assert_eq!(ranked[3].route.id, "no-usd");
+        assert!((ranked[3].score - (-50.05)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_ranked_by_weight_changes_reorder() {
+        let response = scoring_fixture();
+
+        // Time at $1 per minute: the one-minute route wins
+        let impatient = RouteScorer::default().with_time_weight(1.0);
+        assert_eq!(
+            ranked_ids(&response, &impatient)[..3],
+            ["fast-hop", "slow-across", "new-bridge"]
+        );
+
+        // Ignore time and trust the new bridge: highest output wins
+        let output_only = RouteScorer::default()
+            .with_time_weight(0.0)
+            .with_bridge_trust("NewBridge", 1.0);
+        assert_eq!(
+            ranked_ids(&response, &output_only)[..3],
+            ["new-bridge", "slow-across", "fast-hop"]
+        );
+
+        // Distrusting a bridge sinks its route
+        let no_across = RouteScorer::default().with_bridge_trust("across", 0.5);
+        assert_eq!(ranked_ids(&response, &no_across)[0], "fast-hop");
+    }
+
+    #[test]
+    fn test_ranked_by_ties_break_on_route_id() {
+        let tie = |id: &str| {
+            RouteBuilder::new(id)
+                .output_usd("990")
+                .gas_usd("1")
+                .duration(60)
+        };
+        let response = RoutesResponse {
+            routes: vec![
+                tie("b").bridge("across").build(),
+                tie("a").bridge("across").build(),
+                tie("c").build(),
+            ],
+            unavailable_routes: None,
+        };
+        // Same-chain route "c" has full trust and ranks first
+        assert_eq!(
+            ranked_ids(&response, &RouteScorer::default()),
+            ["c", "a", "b"]
+        );
+
+        let mut reversed = response.clone();
+        reversed.routes.reverse();
+        assert_eq!(
+            ranked_ids(&reversed, &RouteScorer::default()),
+            ["c", "a", "b"]
+        );
+    }
+
+    #[test]
+    fn test_scorer_missing_duration_and_nested_bridges() {
+        let scorer = RouteScorer::default();
+        let mut route = RouteBuilder::new("nested")
+            .output_usd("1000")
+            .gas_usd("0")
+            .bridge("across")
+            .build();
+        let mut inner = route.steps[0].clone();
+        inner.tool = "symbiosis".to_string();
+        route.steps[0].step_type = StepType::Lifi;
+        route.steps[0].included_steps = vec![inner];
+
+        assert!((scorer.route_trust(&route) - 0.99).abs() < 1e-12);
+        // 1000 * 0.99 - an assumed hour at $0.10/min
+        assert!((scorer.score(&route) - 984.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_route_trust_takes_weakest_bridge() {
+        let scorer = RouteScorer::default().with_default_bridge_trust(0.9);
+        let route = RouteBuilder::new("two-hops")
+            .output_usd("1000")
+            .gas_usd("0")
+            .duration(60)
+            .bridge("hop")
+            .bridge("unknown")
+            .build();
+        assert!((scorer.route_trust(&route) - 0.9).abs() < 1e-12);
+        assert_eq!(scorer.trust_for("ACROSS"), 0.999);
+    }
+}
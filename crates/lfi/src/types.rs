@@ -3,7 +3,9 @@
 //! This module contains request and response types for the LI.FI cross-chain
 //! bridge and DEX aggregator API.
 
+use crate::scoring::{RouteScorer, ScoredRoute};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 /// Chain ID type - uses numeric chain IDs
 pub type ChainId = u64;
@@ -471,6 +473,106 @@ pub struct RoutesResponse {
     pub unavailable_routes: Option<UnavailableRoutes>,
 }
 
+impl RoutesResponse {
+    /// Routes whose price impact and gas cost are within the given limits
+    ///
+    /// A route passes when its loss is at most `max_impact_pct` (gains always
+    /// pass) and its gas cost is at most `max_gas_usd`. Routes missing any of
+    /// the USD figures needed to check this are excluded.
+    #[must_use]
+    pub fn filter_acceptable(&self, max_impact_pct: f64, max_gas_usd: f64) -> Vec<&Route> {
+        self.routes
+            .iter()
+            .filter(|route| {
+                matches!(
+                    (route.price_impact_pct(), route.gas_cost_usd_value()),
+                    (Some(impact), Some(gas)) if impact >= -max_impact_pct && gas <= max_gas_usd
+                )
+            })
+            .collect()
+    }
+
+    /// Route with the highest net output in USD
+    ///
+    /// Net output is the destination value minus gas and any fees paid on
+    /// top of the source amount (see [`Route::net_output_usd`]). Routes
+    /// missing those USD figures are left out of the ranking; ties keep the
+    /// API's order.
+    #[must_use]
+    pub fn cheapest(&self) -> Option<&Route> {
+        self.best_by(Route::net_output_usd, |a, b| b.total_cmp(a))
+    }
+
+    /// Route with the shortest estimated duration
+    ///
+    /// Uses [`Route::estimated_duration`]; routes without a duration
+    /// estimate are left out of the ranking. Ties keep the API's order.
+    #[must_use]
+    pub fn fastest(&self) -> Option<&Route> {
+        self.best_by(Route::estimated_duration, Ord::cmp)
+    }
+
+    /// Route with the least bridge risk
+    ///
+    /// Routes LI.FI tags as `SAFEST` rank first, then routes crossing the
+    /// fewest bridges (see [`Route::bridge_hops`]). Routes without step
+    /// details are left out of the ranking. Ties keep the API's order.
+    #[must_use]
+    pub fn most_secure(&self) -> Option<&Route> {
+        self.best_by(
+            |route| {
+                (!route.steps.is_empty()).then(|| (!route.has_tag(SAFEST_TAG), route.bridge_hops()))
+            },
+            Ord::cmp,
+        )
+    }
+
+    /// All routes ranked by a custom [`RouteScorer`], best first
+    ///
+    /// Every route is scored (see [`RouteScorer::score`] for how missing
+    /// figures are penalised). Equal scores are ordered by route id so the
+    /// ranking does not depend on the API's order.
+    #[must_use]
+    pub fn ranked_by(&self, scorer: &RouteScorer) -> Vec<ScoredRoute<'_>> {
+        let mut ranked: Vec<ScoredRoute<'_>> = self
+            .routes
+            .iter()
+            .map(|route| ScoredRoute {
+                route,
+                score: scorer.score(route),
+            })
+            .collect();
+        ranked.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.route.id.cmp(&b.route.id))
+        });
+        ranked
+    }
+
+    /// First route with the lowest score under `cmp`, skipping unscored routes
+    fn best_by<K>(
+        &self,
+        score: impl Fn(&Route) -> Option<K>,
+        cmp: impl Fn(&K, &K) -> std::cmp::Ordering,
+    ) -> Option<&Route> {
+        let mut best: Option<(&Route, K)> = None;
+        for route in &self.routes {
+            let Some(key) = score(route) else { continue };
+            if best
+                .as_ref()
+                .is_none_or(|(_, best_key)| cmp(&key, best_key).is_lt())
+            {
+                best = Some((route, key));
+            }
+        }
+        best.map(|(route, _)| route)
+    }
+}
+
+/// Tag LI.FI puts on the route it considers safest
+const SAFEST_TAG: &str = "SAFEST";
+
 /// Unavailable routes with reasons
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -523,7 +625,7 @@ pub struct Route {
     /// Source amount
     pub from_amount: String,
     /// Source amount in USD
-    #[serde(default)]
+    #[serde(default, alias = "fromAmountUSD")]
     pub from_amount_usd: Option<String>,
     /// Sender address
     pub from_address: String,
@@ -534,7 +636,7 @@ pub struct Route {
     /// Destination amount (estimated)
     pub to_amount: String,
     /// Destination amount in USD (estimated)
-    #[serde(default)]
+    #[serde(default, alias = "toAmountUSD")]
     pub to_amount_usd: Option<String>,
     /// Minimum destination amount (after slippage)
     pub to_amount_min: String,
@@ -544,10 +646,10 @@ pub struct Route {
     /// Route steps
     pub steps: Vec<Step>,
     /// Gas cost in USD
-    #[serde(default)]
+    #[serde(default, alias = "gasCostUSD")]
     pub gas_cost_usd: Option<String>,
     /// Total execution time in seconds
-    #[serde(default)]
+    #[serde(default, deserialize_with = "deserialize_duration_secs")]
     pub execution_duration: Option<u64>,
     /// Tags for this route
     #[serde(default)]
@@ -557,6 +659,128 @@ pub struct Route {
     pub insurance: Option<Insurance>,
 }
 
+impl Route {
+    /// Signed price impact in percent, from the USD value in and out
+    ///
+    /// Negative values are a loss (e.g. `-1.5` means 1.5% less value out
+    /// than in); positive values indicate positive slippage. Returns `None`
+    /// when either USD amount is missing or the input value is zero.
+    #[must_use]
+    pub fn price_impact_pct(&self) -> Option<f64> {
+        let from = parse_usd(self.from_amount_usd.as_deref())?;
+        let to = parse_usd(self.to_amount_usd.as_deref())?;
+        (from > 0.0).then(|| (to - from) / from * 100.0)
+    }
+
+    /// Destination value in USD as a number
+    #[must_use]
+    pub fn to_amount_usd_value(&self) -> Option<f64> {
+        parse_usd(self.to_amount_usd.as_deref())
+    }
+
+    /// Gas cost in USD as a number
+    #[must_use]
+    pub fn gas_cost_usd_value(&self) -> Option<f64> {
+        parse_usd(self.gas_cost_usd.as_deref())
+    }
+
+    /// Destination value in USD after gas and fees not already taken from
+    /// the source amount
+    ///
+    /// Fees marked `included` are already reflected in `to_amount_usd`.
+    /// Returns `None` when the destination value, gas cost or any extra
+    /// fee's USD amount is missing.
+    #[must_use]
+    pub fn net_output_usd(&self) -> Option<f64> {
+        let to = parse_usd(self.to_amount_usd.as_deref())?;
+        let gas = self.gas_cost_usd_value()?;
+        let mut extra_fees = 0.0;
+        for fee in self
+            .steps
+            .iter()
+            .filter_map(|step| step.estimate.fee_costs.as_deref())
+            .flatten()
+            .filter(|fee| !fee.included)
+        {
+            extra_fees += parse_usd(fee.amount_usd.as_deref())?;
+        }
+        Some(to - gas - extra_fees)
+    }
+
+    /// Estimated end-to-end duration in seconds
+    ///
+    /// Uses the route-level `execution_duration`, falling back to the sum of
+    /// the step estimates when every step has one.
+    #[must_use]
+    pub fn estimated_duration(&self) -> Option<u64> {
+        if self.execution_duration.is_some() {
+            return self.execution_duration;
+        }
+        if self.steps.is_empty() {
+            return None;
+        }
+        self.steps
+            .iter()
+            .map(|step| step.estimate.execution_duration)
+            .sum()
+    }
+
+    /// Sum of the step ETAs (see [`Step::estimated_duration`])
+    ///
+    /// Returns `None` if there are no steps or any step lacks a duration,
+    /// rather than a misleadingly short total.
+    #[must_use]
+    pub fn total_estimated_duration(&self) -> Option<Duration> {
+        if self.steps.is_empty() {
+            return None;
+        }
+        self.steps.iter().map(Step::estimated_duration).sum()
+    }
+
+    /// Number of cross-chain (bridge) steps, including those inside
+    /// composite steps
+    #[must_use]
+    pub fn bridge_hops(&self) -> usize {
+        fn hops(steps: &[Step]) -> usize {
+            steps
+                .iter()
+                .map(|step| {
+                    usize::from(step.step_type == StepType::Cross) + hops(&step.included_steps)
+                })
+                .sum()
+        }
+        hops(&self.steps)
+    }
+
+    /// Whether LI.FI tagged this route with `tag` (e.g. `"CHEAPEST"`)
+    #[must_use]
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+}
+
+fn parse_usd(value: Option<&str>) -> Option<f64> {
+    value
+        .and_then(|v| v.parse::<f64>().ok())
+        .filter(|v| v.is_finite())
+}
+
+/// Accept `executionDuration` as whole or fractional seconds, rounding up
+fn deserialize_duration_secs<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(
+        match Option::<serde_json::Value>::deserialize(deserializer)? {
+            Some(serde_json::Value::Number(n)) => n.as_f64(),
+            Some(serde_json::Value::String(s)) => s.trim().parse().ok(),
+            _ => None,
+        }
+        .filter(|secs| secs.is_finite() && *secs >= 0.0)
+        .map(|secs| secs.ceil() as u64),
+    )
+}
+
 /// Insurance information for a route
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -598,6 +822,26 @@ pub struct Step {
     pub included_steps: Vec<Step>,
 }
 
+impl Step {
+    /// Estimated execution time of this step
+    ///
+    /// Uses the step's `estimate.executionDuration`. A composite step without
+    /// one falls back to the sum of its included steps, if they all have one.
+    #[must_use]
+    pub fn estimated_duration(&self) -> Option<Duration> {
+        if let Some(secs) = self.estimate.execution_duration {
+            return Some(Duration::from_secs(secs));
+        }
+        if self.included_steps.is_empty() {
+            return None;
+        }
+        self.included_steps
+            .iter()
+            .map(Step::estimated_duration)
+            .sum()
+    }
+}
+
 /// Type of step
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -686,7 +930,7 @@ pub struct Estimate {
     #[serde(default)]
     pub approval_address: Option<String>,
     /// Execution duration in seconds
-    #[serde(default)]
+    #[serde(default, deserialize_with = "deserialize_duration_secs")]
     pub execution_duration: Option<u64>,
     /// Fee costs
     #[serde(default)]
@@ -722,7 +966,7 @@ pub struct FeeCost {
     /// Fee amount
     pub amount: String,
     /// Fee amount in USD
-    #[serde(default)]
+    #[serde(default, alias = "amountUSD")]
     pub amount_usd: Option<String>,
     /// Percentage fee
     #[serde(default)]
@@ -761,7 +1005,7 @@ pub struct GasCost {
     /// Gas amount
     pub amount: String,
     /// Amount in USD
-    #[serde(default)]
+    #[serde(default, alias = "amountUSD")]
     pub amount_usd: Option<String>,
     /// Gas price
     #[serde(default)]
@@ -816,6 +1060,334 @@ pub struct TransactionRequest {
     pub max_priority_fee_per_gas: Option<String>,
 }
 
+// ============================================================================
+// Contract Call Types
+// ============================================================================
+
+/// A contract call to execute on the destination chain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractCall {
+    /// Amount of `from_token_address` the call consumes (base units)
+    pub from_amount: String,
+    /// Token the call consumes on the destination chain
+    pub from_token_address: String,
+    /// Contract to call
+    pub to_contract_address: String,
+    /// ABI-encoded call data
+    pub to_contract_call_data: String,
+    /// Gas limit for the call
+    pub to_contract_gas_limit: String,
+    /// Token the contract sends back to the caller, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contract_outputs_token: Option<String>,
+}
+
+/// Request body for a contract-calls quote (`POST /quote/contractCalls`)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractCallsQuoteRequest {
+    /// Source chain ID
+    pub from_chain: ChainId,
+    /// Source token address
+    pub from_token: String,
+    /// Sender address
+    pub from_address: String,
+    /// Destination chain ID
+    pub to_chain: ChainId,
+    /// Token the contract calls consume on the destination chain
+    pub to_token: String,
+    /// Amount of `to_token` the contract calls need (base units)
+    pub to_amount: String,
+    /// Calls to execute on the destination chain
+    pub contract_calls: Vec<ContractCall>,
+    /// Address receiving funds if the destination call fails
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_fallback_address: Option<String>,
+    /// Slippage tolerance in percent (e.g., 0.5 for 0.5%)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slippage: Option<f64>,
+    /// Integrator identifier
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub integrator: Option<String>,
+    /// Fee percentage for integrator (0-3%)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee: Option<f64>,
+    /// Referrer address
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub referrer: Option<String>,
+}
+
+/// An ERC-4626 vault targeted by a zap
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Erc4626Vault {
+    /// Chain the vault is deployed on
+    pub chain_id: ChainId,
+    /// Vault (share token) address
+    pub address: String,
+    /// Underlying asset the vault accepts (`asset()`)
+    pub asset: String,
+}
+
+impl Erc4626Vault {
+    /// Describe a vault by chain, address and underlying asset
+    pub fn new(chain_id: ChainId, address: impl Into<String>, asset: impl Into<String>) -> Self {
+        Self {
+            chain_id,
+            address: address.into(),
+            asset: asset.into(),
+        }
+    }
+}
+
+/// Default gas limit for the destination `deposit` call
+pub const DEFAULT_DEPOSIT_GAS_LIMIT: u64 = 300_000;
+
+/// `deposit(uint256,address)` selector
+const ERC4626_DEPOSIT_SELECTOR: &str = "6e553f65";
+
+/// Bridge from any chain/token and deposit into an ERC-4626 vault
+///
+/// Built on the contract-calls quote: LI.FI routes `from_token` to the vault
+/// asset on the vault chain, then calls `deposit(amount, receiver)`.
+#[derive(Debug, Clone)]
+pub struct ZapRequest {
+    /// Source chain ID
+    pub from_chain: ChainId,
+    /// Source token address
+    pub from_token: String,
+    /// Vault asset amount to deposit (base units)
+    pub amount: String,
+    /// Target vault
+    pub vault: Erc4626Vault,
+    /// Address receiving the vault shares
+    pub receiver: String,
+    /// Sender address (defaults to `receiver`)
+    pub from_address: Option<String>,
+    /// Slippage tolerance in percent
+    pub slippage: Option<f64>,
+    /// Gas limit for the deposit call
+    pub deposit_gas_limit: u64,
+}
+
+impl ZapRequest {
+    /// Zap `from_token` on `from_chain` into an ERC-4626 vault
+    ///
+    /// `amount` is the vault asset amount to deposit; LI.FI works out how
+    /// much `from_token` is needed to deliver it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lfi::{chains, Erc4626Vault, ZapRequest};
+    ///
+    /// let vault = Erc4626Vault::new(
+    ///     chains::BASE,
+    ///     "0xYourVaultAddress",
+    ///     "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913", // USDC on Base
+    /// );
+    /// let zap = ZapRequest::to_erc4626_vault(
+    ///     chains::ARBITRUM,
+    ///     "0xaf88d065e77c8cC2239327C5EDb3A432268e5831", // USDC on Arbitrum
+    ///     "100000000",                                  // 100 USDC into the vault
+    ///     vault,
+    ///     "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045",
+    /// )
+    /// .with_slippage(0.5);
+    /// assert!(zap.deposit_calldata().unwrap().starts_with("0x6e553f65"));
+    /// ```
+    pub fn to_erc4626_vault(
+        from_chain: ChainId,
+        from_token: impl Into<String>,
+        amount: impl Into<String>,
+        vault: Erc4626Vault,
+        receiver: impl Into<String>,
+    ) -> Self {
+        Self {
+            from_chain,
+            from_token: from_token.into(),
+            amount: amount.into(),
+            vault,
+            receiver: receiver.into(),
+            from_address: None,
+            slippage: None,
+            deposit_gas_limit: DEFAULT_DEPOSIT_GAS_LIMIT,
+        }
+    }
+
+    /// Set the sender address (defaults to the receiver)
+    #[must_use]
+    pub fn with_from_address(mut self, from_address: impl Into<String>) -> Self {
+        self.from_address = Some(from_address.into());
+        self
+    }
+
+    /// Set slippage tolerance in percent
+    #[must_use]
+    pub fn with_slippage(mut self, slippage: f64) -> Self {
+        self.slippage = Some(slippage);
+        self
+    }
+
+    /// Set the gas limit for the deposit call
+    #[must_use]
+    pub fn with_deposit_gas_limit(mut self, gas_limit: u64) -> Self {
+        self.deposit_gas_limit = gas_limit;
+        self
+    }
+
+    /// ABI-encoded `deposit(amount, receiver)` call data
+    pub fn deposit_calldata(&self) -> crate::error::Result<String> {
+        let assets = encode_uint256(&self.amount)?;
+        let receiver = encode_address(&self.receiver)?;
+        Ok(format!("0x{ERC4626_DEPOSIT_SELECTOR}{assets}{receiver}"))
+    }
+
+    /// Build the contract-calls quote request
+    ///
+    /// Validates addresses and the amount; the bridge leg's destination token
+    /// is the vault asset.
+    pub fn to_contract_calls_request(&self) -> crate::error::Result<ContractCallsQuoteRequest> {
+        encode_address(&self.from_token)?;
+        encode_address(&self.vault.address)?;
+        encode_address(&self.vault.asset)?;
+        let from_address = self.from_address.as_ref().unwrap_or(&self.receiver);
+        encode_address(from_address)?;
+
+        Ok(ContractCallsQuoteRequest {
+            from_chain: self.from_chain,
+            from_token: self.from_token.clone(),
+            from_address: from_address.clone(),
+            to_chain: self.vault.chain_id,
+            to_token: self.vault.asset.clone(),
+            to_amount: self.amount.clone(),
+            contract_calls: vec![ContractCall {
+                from_amount: self.amount.clone(),
+                from_token_address: self.vault.asset.clone(),
+                to_contract_address: self.vault.address.clone(),
+                to_contract_call_data: self.deposit_calldata()?,
+                to_contract_gas_limit: self.deposit_gas_limit.to_string(),
+                contract_outputs_token: Some(self.vault.address.clone()),
+            }],
+            to_fallback_address: Some(self.receiver.clone()),
+            slippage: self.slippage,
+            integrator: None,
+            fee: None,
+            referrer: None,
+        })
+    }
+}
+
+/// Contract-calls quote for a zap into an ERC-4626 vault
+#[derive(Debug, Clone)]
+pub struct ZapQuote {
+    /// Underlying LI.FI quote (holds the transaction request)
+    pub quote: Quote,
+    /// Target vault
+    pub vault: Erc4626Vault,
+    /// Vault asset amount deposited (base units)
+    pub deposit_amount: String,
+    /// Expected vault shares minted
+    pub expected_shares: u128,
+    /// Minimum vault shares after slippage
+    pub min_shares: u128,
+}
+
+impl ZapQuote {
+    /// Interpret a contract-calls quote for `request`
+    ///
+    /// Fails if the quote has no step minting vault shares, or if the token
+    /// the bridge leg delivers is not the vault asset.
+    pub fn from_quote(quote: Quote, request: &ZapRequest) -> crate::error::Result<Self> {
+        let vault = &request.vault;
+        let steps = &quote.included_steps;
+        let deposit_index = steps.iter().position(|s| {
+            s.action
+                .to_token
+                .address
+                .eq_ignore_ascii_case(&vault.address)
+        });
+
+        let (estimate, bridge_token) = match deposit_index {
+            Some(i) => {
+                // The deposit consumes what the previous step delivered
+                let bridge_token = match i.checked_sub(1) {
+                    Some(prev) => &steps[prev].action.to_token.address,
+                    None => &steps[i].action.from_token.address,
+                };
+                (&steps[i].estimate, bridge_token)
+            }
+            None if quote
+                .action
+                .to_token
+                .address
+                .eq_ignore_ascii_case(&vault.address) =>
+            {
+                (&quote.estimate, &quote.action.from_token.address)
+            }
+            None => return Err(crate::error::missing_vault_deposit(&vault.address)),
+        };
+
+        if !bridge_token.eq_ignore_ascii_case(&vault.asset) {
+            return Err(crate::error::vault_asset_mismatch(
+                &vault.asset,
+                bridge_token,
+            ));
+        }
+
+        let parse = |amount: &str| {
+            amount
+                .parse::<u128>()
+                .map_err(|_| crate::error::invalid_amount(amount))
+        };
+        let expected_shares = parse(&estimate.to_amount)?;
+        let min_shares = parse(&estimate.to_amount_min)?;
+
+        Ok(Self {
+            vault: vault.clone(),
+            deposit_amount: request.amount.clone(),
+            expected_shares,
+            min_shares,
+            quote,
+        })
+    }
+
+    /// Source token amount required (base units)
+    pub fn from_amount(&self) -> &str {
+        &self.quote.estimate.from_amount
+    }
+}
+
+/// Encode a decimal amount as a 32-byte ABI word
+fn encode_uint256(amount: &str) -> crate::error::Result<String> {
+    if amount.is_empty() || !amount.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(crate::error::invalid_amount(amount));
+    }
+    let mut word = [0u8; 32];
+    for digit in amount.bytes().map(|b| b - b'0') {
+        let mut carry = u16::from(digit);
+        for byte in word.iter_mut().rev() {
+            let value = u16::from(*byte) * 10 + carry;
+            *byte = (value & 0xff) as u8;
+            carry = value >> 8;
+        }
+        if carry != 0 {
+            return Err(crate::error::invalid_amount(amount));
+        }
+    }
+    Ok(word.iter().map(|b| format!("{b:02x}")).collect())
+}
+
+/// Encode a hex address as a left-padded 32-byte ABI word
+fn encode_address(address: &str) -> crate::error::Result<String> {
+    let hex = address
+        .strip_prefix("0x")
+        .filter(|h| h.len() == 40 && h.bytes().all(|b| b.is_ascii_hexdigit()))
+        .ok_or_else(|| crate::error::invalid_token_address(address))?;
+    Ok(format!("{:0>64}", hex.to_lowercase()))
+}
+
 // ============================================================================
 // Status Types
 // ============================================================================
@@ -1273,3 +1845,335 @@ pub struct ApiError {
     #[serde(default)]
     pub code: Option<String>,
 }
+
+/// Route builder shared by the unit tests
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use super::Route;
+
+    const USDC: &str = "0xaf88d065e77c8cC2239327C5EDb3A432268e5831";
+    const SENDER: &str = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+
+    /// Builds a 1000 USDC Ethereum -> Arbitrum [`Route`] worth 1000 USD in,
+    /// with no steps and no output, gas or duration figures until set
+    pub(crate) struct RouteBuilder {
+        id: String,
+        to_usd: Option<String>,
+        gas_usd: Option<String>,
+        duration: Option<u64>,
+        tags: Vec<String>,
+        steps: Vec<serde_json::Value>,
+    }
+
+    impl RouteBuilder {
+        pub(crate) fn new(id: &str) -> Self {
+            Self {
+                id: id.to_string(),
+                to_usd: None,
+                gas_usd: None,
+                duration: None,
+                tags: Vec::new(),
+                steps: Vec::new(),
+            }
+        }
+
+        pub(crate) fn output_usd(mut self, usd: &str) -> Self {
+            self.to_usd = Some(usd.to_string());
+            self
+        }
+
+        pub(crate) fn gas_usd(mut self, usd: &str) -> Self {
+            self.gas_usd = Some(usd.to_string());
+            self
+        }
+
+        /// Route-level execution duration in seconds
+        pub(crate) fn duration(mut self, seconds: u64) -> Self {
+            self.duration = Some(seconds);
+            self
+        }
+
+        pub(crate) fn tag(mut self, tag: &str) -> Self {
+            self.tags.push(tag.to_string());
+            self
+        }
+
+        /// Add an `across` step with an optional duration and `(usd, included)` fees
+        pub(crate) fn step(
+            self,
+            kind: &str,
+            duration: Option<u64>,
+            fees: &[(Option<&str>, bool)],
+        ) -> Self {
+            self.push_step(kind, "across", duration, fees)
+        }
+
+        /// Add a cross-chain step through `tool`
+        pub(crate) fn bridge(self, tool: &str) -> Self {
+            self.push_step("cross", tool, None, &[])
+        }
+
+        fn push_step(
+            mut self,
+            kind: &str,
+            tool: &str,
+            duration: Option<u64>,
+            fees: &[(Option<&str>, bool)],
+        ) -> Self {
+            let token = token();
+            let fees: Vec<serde_json::Value> = fees
+                .iter()
+                .map(|(usd, included)| {
+                    serde_json::json!({
+                        "name": "fee",
+                        "token": token,
+                        "amount": "1",
+                        "amountUSD": usd,
+                        "included": included
+                    })
+                })
+                .collect();
+            self.steps.push(serde_json::json!({
+                "id": format!("{}-{}", self.id, self.steps.len()),
+                "type": kind,
+                "tool": tool,
+                "action": {
+                    "fromChainId": 1,
+                    "fromToken": token,
+                    "fromAmount": "1000000000",
+                    "toChainId": 42161,
+                    "toToken": token
+                },
+                "estimate": {
+                    "fromAmount": "1000000000",
+                    "toAmount": "990000000",
+                    "toAmountMin": "985000000",
+                    "executionDuration": duration,
+                    "feeCosts": fees
+                }
+            }));
+            self
+        }
+
+        pub(crate) fn build(self) -> Route {
+            let token = token();
+            serde_json::from_value(serde_json::json!({
+                "id": self.id,
+                "fromChainId": 1,
+                "fromToken": token,
+                "fromAmount": "1000000000",
+                "fromAmountUSD": "1000",
+                "fromAddress": SENDER,
+                "toChainId": 42161,
+                "toToken": token,
+                "toAmount": "990000000",
+                "toAmountUSD": self.to_usd,
+                "toAmountMin": "985000000",
+                "steps": self.steps,
+                "gasCostUSD": self.gas_usd,
+                "executionDuration": self.duration,
+                "tags": self.tags
+            }))
+            .unwrap()
+        }
+    }
+
+    fn token() -> serde_json::Value {
+        serde_json::json!({
+            "address": USDC,
+            "chainId": 1,
+            "symbol": "USDC",
+            "decimals": 6,
+            "name": "USD Coin"
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fixtures::RouteBuilder;
+    use super::*;
+
+    #[test]
+    fn test_route_price_impact_is_signed() {
+        let loss = RouteBuilder::new("loss").output_usd("990").build();
+        assert!((loss.price_impact_pct().unwrap() + 1.0).abs() < 1e-9);
+
+        let gain = RouteBuilder::new("gain").output_usd("1005").build();
+        assert!((gain.price_impact_pct().unwrap() - 0.5).abs() < 1e-9);
+
+        let mut none = RouteBuilder::new("none").output_usd("990").build();
+        none.from_amount_usd = None;
+        assert!(none.price_impact_pct().is_none());
+    }
+
+    #[test]
+    fn test_routes_filter_acceptable() {
+        let response = RoutesResponse {
+            routes: vec![
+                RouteBuilder::new("ok")
+                    .output_usd("995")
+                    .gas_usd("3")
+                    .build(),
+                RouteBuilder::new("gain")
+                    .output_usd("1010")
+                    .gas_usd("1")
+                    .build(),
+                RouteBuilder::new("impact")
+                    .output_usd("950")
+                    .gas_usd("1")
+                    .build(),
+                RouteBuilder::new("gas")
+                    .output_usd("999")
+                    .gas_usd("25")
+                    .build(),
+                RouteBuilder::new("no-usd").gas_usd("1").build(),
+            ],
+            unavailable_routes: None,
+        };
+
+        let ids: Vec<&str> = response
+            .filter_acceptable(1.0, 10.0)
+            .iter()
+            .map(|r| r.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["ok", "gain"]);
+    }
+
+    #[test]
+    fn test_routes_cheapest_uses_net_output() {
+        let response = RoutesResponse {
+            routes: vec![
+                // 998 out but 5 gas and a 2 USD fee on top -> 991
+                RouteBuilder::new("high-out")
+                    .output_usd("998")
+                    .gas_usd("5")
+                    .step("cross", None, &[(Some("2"), false), (Some("4"), true)])
+                    .build(),
+                // 995 - 1 -> 994
+                RouteBuilder::new("net-best")
+                    .output_usd("995")
+                    .gas_usd("1")
+                    .build(),
+                // Would win, but the extra fee has no USD amount
+                RouteBuilder::new("unpriced-fee")
+                    .output_usd("999")
+                    .gas_usd("0.5")
+                    .step("cross", None, &[(None, false)])
+                    .build(),
+                RouteBuilder::new("no-gas").output_usd("1000").build(),
+            ],
+            unavailable_routes: None,
+        };
+
+        assert!((response.routes[0].net_output_usd().unwrap() - 991.0).abs() < 1e-9);
+        assert!(response.routes[2].net_output_usd().is_none());
+        assert_eq!(response.cheapest().unwrap().id, "net-best");
+    }
+
+    #[test]
+    fn test_routes_fastest_uses_route_or_step_durations() {
+        let priced = |id: &str| RouteBuilder::new(id).output_usd("995").gas_usd("1");
+        let response = RoutesResponse {
+            routes: vec![
+                priced("slow").duration(600).build(),
+                // No route-level estimate: 30 + 45 from the steps
+                priced("steps")
+                    .step("swap", Some(30), &[])
+                    .step("cross", Some(45), &[])
+                    .build(),
+                // One step without an estimate -> unranked
+                priced("partial")
+                    .step("swap", Some(5), &[])
+                    .step("cross", None, &[])
+                    .build(),
+                priced("tie").duration(75).build(),
+            ],
+            unavailable_routes: None,
+        };
+
+        assert_eq!(response.routes[1].estimated_duration(), Some(75));
+        assert_eq!(response.routes[2].estimated_duration(), None);
+        assert_eq!(
+            response.routes[1].total_estimated_duration(),
+            Some(Duration::from_secs(75))
+        );
+        // A missing step ETA makes the total unknown, not shorter
+        assert_eq!(
+            response.routes[2].steps[0].estimated_duration(),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(response.routes[2].total_estimated_duration(), None);
+        // Route-level estimate only, no steps to sum
+        assert_eq!(response.routes[0].total_estimated_duration(), None);
+        // Ties keep the API's order
+        assert_eq!(response.fastest().unwrap().id, "steps");
+    }
+
+    #[test]
+    fn test_step_durations_from_included_steps_and_fractions() {
+        let mut route = RouteBuilder::new("composite")
+            .step("lifi", None, &[])
+            .step("swap", Some(20), &[])
+            .build();
+        let parts = RouteBuilder::new("parts")
+            .step("swap", Some(30), &[])
+            .step("cross", Some(690), &[])
+            .build();
+        route.steps[0].included_steps = parts.steps;
+        assert_eq!(
+            route.steps[0].estimated_duration(),
+            Some(Duration::from_secs(720))
+        );
+        assert_eq!(
+            route.total_estimated_duration(),
+            Some(Duration::from_secs(740))
+        );
+
+        // LI.FI sends fractional seconds; they round up
+        let mut value = serde_json::to_value(&route).unwrap();
+        value["steps"][1]["estimate"]["executionDuration"] = serde_json::json!(20.2);
+        value["executionDuration"] = serde_json::json!("95.5");
+        let route: Route = serde_json::from_value(value).unwrap();
+        assert_eq!(route.steps[1].estimate.execution_duration, Some(21));
+        assert_eq!(route.execution_duration, Some(96));
+        assert_eq!(
+            route.total_estimated_duration(),
+            Some(Duration::from_secs(741))
+        );
+    }
+
+    #[test]
+    fn test_routes_most_secure() {
+        let mut response = RoutesResponse {
+            routes: vec![
+                RouteBuilder::new("no-steps").build(),
+                RouteBuilder::new("two")
+                    .step("cross", None, &[])
+                    .step("swap", None, &[])
+                    .step("cross", None, &[])
+                    .build(),
+                RouteBuilder::new("one")
+                    .tag("CHEAPEST")
+                    .step("swap", None, &[])
+                    .step("cross", None, &[])
+                    .build(),
+            ],
+            unavailable_routes: None,
+        };
+        assert_eq!(response.routes[1].bridge_hops(), 2);
+        assert_eq!(response.most_secure().unwrap().id, "one");
+
+        // LI.FI's own SAFEST tag outranks the hop count
+        response.routes[1].tags.push("SAFEST".to_string());
+        assert_eq!(response.most_secure().unwrap().id, "two");
+
+        let empty = RoutesResponse {
+            routes: vec![RouteBuilder::new("no-steps").build()],
+            unavailable_routes: None,
+        };
+        assert!(empty.most_secure().is_none());
+        assert!(empty.cheapest().is_none());
+        assert!(empty.fastest().is_none());
+    }
+}
@@ -0,0 +1,374 @@
+//! Compute-unit (CU) budgeting
+//!
+//! Moralis prices each endpoint in compute units and throttles on CU per
+//! second rather than request count. The client looks up the weight of each
+//! endpoint it calls in [`EndpointWeights`] and, when a budget is set with
+//! [`Config::with_cu_budget`](crate::Config::with_cu_budget), sleeps before
+//! sending a request that would push the last second's usage over budget.
+//! 429 responses pause the budgeter for the server's `Retry-After`.
+
+use crate::utils::EndpointWeight;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+use tokio::time::{sleep, Instant};
+
+/// CU assumed for endpoints missing from the weights map
+///
+/// Deliberately on the high side so unknown endpoints err towards pacing.
+pub const DEFAULT_ENDPOINT_WEIGHT: u32 = 10;
+
+/// Length of the sliding budget window
+const WINDOW: Duration = Duration::from_secs(1);
+
+/// Pause applied after a 429 without a `Retry-After` header
+const DEFAULT_RATE_LIMIT_PAUSE: Duration = Duration::from_secs(1);
+
+/// CU cost per endpoint path
+///
+/// Paths may be templates where segments starting with `:` or wrapped in
+/// `{}` match any value, e.g. `/:address/erc20` matches `/0xabc/erc20`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndpointWeights {
+    exact: HashMap<String, u32>,
+    templates: Vec<(Vec<String>, u32)>,
+    default_weight: u32,
+}
+
+impl Default for EndpointWeights {
+    fn default() -> Self {
+        Self::new(DEFAULT_ENDPOINT_WEIGHT)
+    }
+}
+
+impl EndpointWeights {
+    /// Create an empty map where every endpoint costs `default_weight`
+    #[must_use]
+    pub fn new(default_weight: u32) -> Self {
+        Self {
+            exact: HashMap::new(),
+            templates: Vec::new(),
+            default_weight,
+        }
+    }
+
+    /// Build from the `/info/endpointWeights` response
+    ///
+    /// Entries without a path or cost are skipped.
+    #[must_use]
+    pub fn from_endpoint_weights(weights: &[EndpointWeight], default_weight: u32) -> Self {
+        let mut map = Self::new(default_weight);
+        for weight in weights {
+            if let (Some(path), Some(cost)) = (weight.path.as_deref(), weight.cu_cost()) {
+                map.insert(path, cost);
+            }
+        }
+        map
+    }
+
+    /// Set the cost of a path or path template
+    pub fn insert(&mut self, path: &str, weight: u32) {
+        let segments = segments(path);
+        if segments.iter().any(|s| is_placeholder(s)) {
+            let segments: Vec<String> = segments.into_iter().map(str::to_string).collect();
+            self.templates.retain(|(existing, _)| *existing != segments);
+            self.templates.push((segments, weight));
+        } else {
+            self.exact.insert(segments.join("/"), weight);
+        }
+    }
+
+    /// Cost assumed for unknown endpoints
+    #[must_use]
+    pub fn default_weight(&self) -> u32 {
+        self.default_weight
+    }
+
+    /// Change the cost assumed for unknown endpoints
+    pub fn set_default_weight(&mut self, weight: u32) {
+        self.default_weight = weight;
+    }
+
+    /// Number of endpoints with a known cost
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.exact.len() + self.templates.len()
+    }
+
+    /// Whether no endpoint has a known cost
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// CU cost of a request path (query string ignored)
+    ///
+    /// Exact paths win over templates; among templates the one with the
+    /// fewest placeholders wins.
+    #[must_use]
+    pub fn weight_for(&self, path: &str) -> u32 {
+        let path = path.split('?').next().unwrap_or_default();
+        let segments = segments(path);
+        if let Some(weight) = self.exact.get(&segments.join("/")) {
+            return *weight;
+        }
+        self.templates
+            .iter()
+            .filter(|(template, _)| {
+                template.len() == segments.len()
+                    && template
+                        .iter()
+                        .zip(&segments)
+                        .all(|(t, s)| is_placeholder(t) || t == s)
+            })
+            .min_by_key(|(template, _)| template.iter().filter(|t| is_placeholder(t)).count())
+            .map_or(self.default_weight, |(_, weight)| *weight)
+    }
+}
+
+fn segments(path: &str) -> Vec<&str> {
+    path.split('/').filter(|s| !s.is_empty()).collect()
+}
+
+fn is_placeholder(segment: &str) -> bool {
+    segment.starts_with(':') || (segment.starts_with('{') && segment.ends_with('}'))
+}
+
+/// Snapshot of CU consumption
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CuStats {
+    /// Configured budget (`None` when only tracking)
+    pub cu_per_second: Option<u32>,
+    /// CU spent in the last second
+    pub cu_last_second: u32,
+    /// CU spent since the client was created
+    pub total_cu: u64,
+    /// Requests sent
+    pub requests: u64,
+    /// Requests that had to wait for budget
+    pub throttled_requests: u64,
+    /// Total time spent waiting for budget
+    pub time_throttled: Duration,
+    /// 429 responses received
+    pub rate_limited: u64,
+}
+
+#[derive(Debug, Default)]
+struct MeterState {
+    window: VecDeque<(Instant, u32)>,
+    paused_until: Option<Instant>,
+    total_cu: u64,
+    requests: u64,
+    throttled_requests: u64,
+    time_throttled: Duration,
+    rate_limited: u64,
+}
+
+impl MeterState {
+    fn prune(&mut self, now: Instant) {
+        while self
+            .window
+            .front()
+            .is_some_and(|(at, _)| now.duration_since(*at) >= WINDOW)
+        {
+            self.window.pop_front();
+        }
+    }
+
+    fn used(&self) -> u32 {
+        self.window.iter().map(|(_, cu)| cu).sum()
+    }
+}
+
+/// Sliding-window CU meter shared by a client and its clones
+#[derive(Debug, Default)]
+pub(crate) struct CuMeter {
+    cu_per_second: Option<u32>,
+    state: Mutex<MeterState>,
+}
+
+impl CuMeter {
+    pub(crate) fn new(cu_per_second: Option<u32>) -> Self {
+        Self {
+            cu_per_second,
+            state: Mutex::default(),
+        }
+    }
+
+    /// Wait until `weight` CU fits in the budget, then record it
+    ///
+    /// A request heavier than the whole budget is sent once the window is
+    /// empty rather than waiting forever.
+    pub(crate) async fn acquire(&self, weight: u32) {
+        let mut waited = Duration::ZERO;
+        loop {
+            let wait = {
+                let mut state = self.lock();
+                let now = Instant::now();
+                state.prune(now);
+                match self.wait_time(&state, now, weight) {
+                    Some(wait) => wait,
+                    None => {
+                        state.window.push_back((now, weight));
+                        state.total_cu += u64::from(weight);
+                        state.requests += 1;
+                        if !waited.is_zero() {
+                            state.throttled_requests += 1;
+                            state.time_throttled += waited;
+                        }
+                        return;
+                    }
+                }
+            };
+            sleep(wait).await;
+            waited += wait;
+        }
+    }
+
+    fn wait_time(&self, state: &MeterState, now: Instant, weight: u32) -> Option<Duration> {
+        if let Some(until) = state.paused_until.filter(|until| *until > now) {
+            return Some(until - now);
+        }
+        let limit = self.cu_per_second?;
+        let used = state.used();
+        if used == 0 || used.saturating_add(weight) <= limit {
+            return None;
+        }
+        // Wait for the oldest entries to expire until the request fits
+        let mut remaining = used;
+        for (at, cu) in &state.window {
+            remaining -= cu;
+            if remaining == 0 || remaining.saturating_add(weight) <= limit {
+                return Some((*at + WINDOW).saturating_duration_since(now));
+            }
+        }
+        None
+    }
+
+    /// Pause all requests after a 429
+    pub(crate) fn record_rate_limit(&self, retry_after: Option<Duration>) {
+        let mut state = self.lock();
+        let until = Instant::now() + retry_after.unwrap_or(DEFAULT_RATE_LIMIT_PAUSE);
+        state.paused_until = Some(state.paused_until.map_or(until, |p| p.max(until)));
+        state.rate_limited += 1;
+    }
+
+    pub(crate) fn stats(&self) -> CuStats {
+        let mut state = self.lock();
+        state.prune(Instant::now());
+        CuStats {
+            cu_per_second: self.cu_per_second,
+            cu_last_second: state.used(),
+            total_cu: state.total_cu,
+            requests: state.requests,
+            throttled_requests: state.throttled_requests,
+            time_throttled: state.time_throttled,
+            rate_limited: state.rate_limited,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, MeterState> {
+        // Every update leaves the state consistent, so recover from poisoning
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weight_lookup() {
+        let mut weights = EndpointWeights::new(7);
+        weights.insert("/:address/erc20", 20);
+        weights.insert("/{address}/nft", 50);
+        weights.insert("/block/:block_number_or_hash", 5);
+        weights.insert("/erc20/metadata", 10);
+        weights.insert("/erc20/:address/price", 30);
+
+        assert_eq!(weights.len(), 5);
+        assert_eq!(weights.weight_for("/0xabc/erc20?chain=eth"), 20);
+        assert_eq!(weights.weight_for("0xabc/nft"), 50);
+        assert_eq!(weights.weight_for("/block/123"), 5);
+        assert_eq!(weights.weight_for("/erc20/metadata"), 10);
+        assert_eq!(weights.weight_for("/erc20/0xabc/price"), 30);
+        assert_eq!(weights.weight_for("/wallets/0xabc/history"), 7);
+    }
+
+    #[test]
+    fn test_weights_from_api_response() {
+        let response: Vec<EndpointWeight> = serde_json::from_value(serde_json::json!([
+            { "endpoint": "getBlock", "path": "/block/:block_number_or_hash", "rateLimitCost": "5", "price": "5" },
+            { "endpoint": "getWalletNFTs", "path": "/:address/nft", "weight": 50 },
+            { "endpoint": "unknown" }
+        ]))
+        .unwrap();
+        let weights = EndpointWeights::from_endpoint_weights(&response, 3);
+        assert_eq!(weights.len(), 2);
+        assert_eq!(weights.weight_for("/block/1"), 5);
+        assert_eq!(weights.weight_for("/0xabc/nft"), 50);
+        assert_eq!(weights.weight_for("/info/endpointWeights"), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_paces_by_weight() {
+        let meter = CuMeter::new(Some(100));
+        let start = Instant::now();
+
+        // 40 + 50 fits in one second
+        meter.acquire(40).await;
+        meter.acquire(50).await;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        // A light request still fits, a heavy one waits for the window
+        meter.acquire(10).await;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+        meter.acquire(60).await;
+        assert_eq!(start.elapsed(), WINDOW);
+
+        let stats = meter.stats();
+        assert_eq!(stats.total_cu, 160);
+        assert_eq!(stats.requests, 4);
+        assert_eq!(stats.throttled_requests, 1);
+        assert_eq!(stats.time_throttled, WINDOW);
+        assert_eq!(stats.cu_last_second, 60);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_waits_only_for_enough_budget() {
+        let meter = CuMeter::new(Some(100));
+        let start = Instant::now();
+
+        meter.acquire(30).await;
+        sleep(Duration::from_millis(400)).await;
+        meter.acquire(70).await;
+
+        // Only the first 30 CU must expire, at t = 1s
+        meter.acquire(30).await;
+        assert_eq!(start.elapsed(), WINDOW);
+
+        // A request heavier than the budget goes once the window is empty
+        meter.acquire(250).await;
+        assert_eq!(start.elapsed(), 2 * WINDOW);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rate_limit_pauses_requests() {
+        let meter = CuMeter::new(None);
+        let start = Instant::now();
+
+        meter.acquire(1_000).await;
+        meter.record_rate_limit(Some(Duration::from_secs(3)));
+        meter.acquire(1).await;
+        assert_eq!(start.elapsed(), Duration::from_secs(3));
+
+        meter.record_rate_limit(None);
+        meter.acquire(1).await;
+        assert_eq!(start.elapsed(), Duration::from_secs(4));
+
+        let stats = meter.stats();
+        assert_eq!(stats.cu_per_second, None);
+        assert_eq!(stats.rate_limited, 2);
+        assert_eq!(stats.throttled_requests, 2);
+    }
+}
@@ -2,10 +2,11 @@
 
 use crate::analytics::AnalyticsApi;
 use crate::block::BlockApi;
+use crate::budget::{CuMeter, CuStats, EndpointWeights, DEFAULT_ENDPOINT_WEIGHT};
 use crate::defi::DefiApi;
 use crate::discovery::DiscoveryApi;
 use crate::entities::EntitiesApi;
-use crate::error::{self, Error, Result};
+use crate::error::{self, Error, PlanTier, Result};
 use crate::market::MarketApi;
 use crate::nft::NftApi;
 use crate::resolve::ResolveApi;
@@ -17,6 +18,7 @@ use crate::wallet::WalletApi;
 use reqwest::Client as HttpClient;
 use secrecy::{ExposeSecret, SecretString};
 use serde::de::DeserializeOwned;
+use std::sync::{Arc, PoisonError, RwLock};
 use std::time::Duration;
 use yldfi_common::http::HttpClientConfig;
 
@@ -31,6 +33,12 @@ pub struct Config {
     pub base_url: String,
     /// HTTP client configuration (timeout, proxy, user-agent)
     pub http: HttpClientConfig,
+    /// Compute units allowed per second (`None` disables pacing)
+    pub cu_per_second: Option<u32>,
+    /// CU assumed for endpoints without a known weight
+    pub default_endpoint_weight: u32,
+    /// Plan tier of the API key (`None` lets the API decide)
+    pub plan: Option<PlanTier>,
 }
 
 impl Config {
@@ -40,6 +48,9 @@ impl Config {
             api_key: SecretString::from(api_key.into()),
             base_url: BASE_URL.to_string(),
             http: HttpClientConfig::default(),
+            cu_per_second: None,
+            default_endpoint_weight: DEFAULT_ENDPOINT_WEIGHT,
+            plan: None,
         }
     }
 
@@ -76,6 +87,33 @@ impl Config {
         self.http.proxy = proxy;
         self
     }
+
+    /// Pace requests to stay under `cu_per_second` compute units
+    ///
+    /// Each request is charged the weight of its endpoint; call
+    /// [`Client::load_endpoint_weights`] to use Moralis' published weights.
+    #[must_use]
+    pub fn with_cu_budget(mut self, cu_per_second: u32) -> Self {
+        self.cu_per_second = Some(cu_per_second);
+        self
+    }
+
+    /// Set the CU assumed for endpoints without a known weight
+    #[must_use]
+    pub fn with_default_endpoint_weight(mut self, weight: u32) -> Self {
+        self.default_endpoint_weight = weight;
+        self
+    }
+
+    /// Declare the plan tier of the API key
+    ///
+    /// Endpoints above this tier fail fast with `PlanRequired` instead of
+    /// spending a request on a 402/403.
+    #[must_use]
+    pub fn with_plan(mut self, plan: PlanTier) -> Self {
+        self.plan = Some(plan);
+        self
+    }
 }
 
 impl std::fmt::Debug for Config {
@@ -84,6 +122,9 @@ impl std::fmt::Debug for Config {
             .field("api_key", &"[REDACTED]")
             .field("base_url", &self.base_url)
             .field("http", &self.http)
+            .field("cu_per_second", &self.cu_per_second)
+            .field("default_endpoint_weight", &self.default_endpoint_weight)
+            .field("plan", &self.plan)
             .finish()
     }
 }
@@ -94,6 +135,9 @@ pub struct Client {
     http: HttpClient,
     api_key: SecretString,
     base_url: String,
+    weights: Arc<RwLock<EndpointWeights>>,
+    cu: Arc<CuMeter>,
+    plan: Option<PlanTier>,
 }
 
 impl Client {
@@ -136,9 +180,91 @@ impl Client {
             http,
             api_key: config.api_key,
             base_url: config.base_url,
+            weights: Arc::new(RwLock::new(EndpointWeights::new(
+                config.default_endpoint_weight,
+            ))),
+            cu: Arc::new(CuMeter::new(config.cu_per_second)),
+            plan: config.plan,
         })
     }
 
+    /// Fetch `/info/endpointWeights` and use it for CU budgeting
+    ///
+    /// Replaces any previously loaded weights and keeps the configured
+    /// default for endpoints missing from the response. Returns the number
+    /// of endpoints with a known weight.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example() -> mrls::Result<()> {
+    /// use mrls::{Client, Config};
+    ///
+    /// let client = Client::with_config(Config::new("your-api-key").with_cu_budget(1_000))?;
+    /// client.load_endpoint_weights().await?;
+    /// let balance = client.wallet().get_native_balance("0x...", None).await?;
+    /// println!("{:?}", client.cu_stats());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn load_endpoint_weights(&self) -> Result<usize> {
+        let response = self.utils().get_endpoint_weights().await?;
+        let default_weight = self.read_weights().default_weight();
+        let weights = EndpointWeights::from_endpoint_weights(&response, default_weight);
+        let loaded = weights.len();
+        self.set_endpoint_weights(weights);
+        Ok(loaded)
+    }
+
+    /// Replace the endpoint weights used for CU budgeting
+    ///
+    /// Shared with clones of this client.
+    pub fn set_endpoint_weights(&self, weights: EndpointWeights) {
+        *self.weights.write().unwrap_or_else(PoisonError::into_inner) = weights;
+    }
+
+    /// CU charged for a request to `path`
+    #[must_use]
+    pub fn endpoint_weight(&self, path: &str) -> u32 {
+        self.read_weights().weight_for(path)
+    }
+
+    /// Current CU consumption, shared with clones of this client
+    #[must_use]
+    pub fn cu_stats(&self) -> CuStats {
+        self.cu.stats()
+    }
+
+    /// Plan tier declared with [`Config::with_plan`]
+    #[must_use]
+    pub fn plan(&self) -> Option<PlanTier> {
+        self.plan
+    }
+
+    /// Fail with `PlanRequired` if the declared plan is below `required`
+    ///
+    /// Passes when no plan was declared; the API's own 402/403 then maps to
+    /// the same error.
+    pub(crate) fn require_plan(&self, required: PlanTier, endpoint: &str) -> Result<()> {
+        match self.plan {
+            Some(plan) if plan < required => Err(error::plan_required(
+                required,
+                format!("{endpoint} requires the {required} plan, configured plan is {plan}"),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn read_weights(&self) -> std::sync::RwLockReadGuard<'_, EndpointWeights> {
+        self.weights.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Wait for CU budget before sending a request to `path`
+    async fn throttle(&self, path: &str) {
+        let weight = self.endpoint_weight(path);
+        self.cu.acquire(weight).await;
+    }
+
     /// Extract retry-after header value in seconds
     fn get_retry_after(response: &reqwest::Response) -> Option<u64> {
         response
@@ -157,17 +283,24 @@ impl Client {
     }
 
     /// Handle error response and convert to appropriate Error type
-    async fn handle_error_response(response: reqwest::Response) -> Error {
+    ///
+    /// A 429 also pauses the CU budgeter, so retries wait it out.
+    async fn handle_error_response(&self, response: reqwest::Response) -> Error {
         let status = response.status().as_u16();
         let retry_after = Self::get_retry_after(&response);
+        if status == 429 {
+            self.cu
+                .record_rate_limit(retry_after.map(Duration::from_secs));
+        }
         let body = response.text().await.unwrap_or_default();
-        Error::from_response(status, &body, retry_after)
+        error::from_response(status, &body, retry_after)
     }
 
     /// Make a GET request to the API
     pub(crate) async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
         // MRLS-002 fix: Use join_url for proper URL construction
         let url = self.join_url(path);
+        self.throttle(path).await;
         let response = self
             .http
             .get(&url)
@@ -176,7 +309,7 @@ impl Client {
             .await?;
 
         if !response.status().is_success() {
-            return Err(Self::handle_error_response(response).await);
+            return Err(self.handle_error_response(response).await);
         }
 
         let data = response.json().await?;
@@ -191,6 +324,7 @@ impl Client {
     ) -> Result<T> {
         // MRLS-002 fix: Use join_url for proper URL construction
         let url = self.join_url(path);
+        self.throttle(path).await;
         let response = self
             .http
             .get(&url)
@@ -200,7 +334,7 @@ impl Client {
             .await?;
 
         if !response.status().is_success() {
-            return Err(Self::handle_error_response(response).await);
+            return Err(self.handle_error_response(response).await);
         }
 
         let data = response.json().await?;
@@ -215,6 +349,7 @@ impl Client {
     ) -> Result<T> {
         // MRLS-002 fix: Use join_url for proper URL construction
         let url = self.join_url(path);
+        self.throttle(path).await;
         let response = self
             .http
             .post(&url)
@@ -224,7 +359,7 @@ impl Client {
             .await?;
 
         if !response.status().is_success() {
-            return Err(Self::handle_error_response(response).await);
+            return Err(self.handle_error_response(response).await);
         }
 
         let data = response.json().await?;
@@ -244,6 +379,7 @@ impl Client {
     ) -> Result<T> {
         // MRLS-002 fix: Use join_url for proper URL construction
         let url = self.join_url(path);
+        self.throttle(path).await;
         let response = self
             .http
             .post(&url)
@@ -254,7 +390,7 @@ impl Client {
             .await?;
 
         if !response.status().is_success() {
-            return Err(Self::handle_error_response(response).await);
+            return Err(self.handle_error_response(response).await);
         }
 
         let data = response.json().await?;
@@ -269,6 +405,7 @@ impl Client {
     ) -> Result<T> {
         // MRLS-002 fix: Use join_url for proper URL construction
         let url = self.join_url(path);
+        self.throttle(path).await;
         let response = self
             .http
             .put(&url)
@@ -278,7 +415,7 @@ impl Client {
             .await?;
 
         if !response.status().is_success() {
-            return Err(Self::handle_error_response(response).await);
+            return Err(self.handle_error_response(response).await);
         }
 
         let data = response.json().await?;
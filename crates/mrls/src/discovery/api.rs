@@ -1,10 +1,11 @@
 //! Discovery API client
 
 use super::types::{
-    DiscoveredToken, DiscoveryFilter, DiscoveryResponse, TokenAnalytics, TokenScore,
+    DiscoveredToken, DiscoveryFilter, DiscoveryResponse, DiscoveryRow, DiscoveryRows, TimeFrame,
+    TokenAnalytics, TokenScore,
 };
 use crate::client::Client;
-use crate::error::Result;
+use crate::error::{PlanTier, Result};
 use serde::Serialize;
 
 /// Query parameters for discovery endpoints
@@ -16,6 +17,15 @@ pub struct DiscoveryQuery {
     pub cursor: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub limit: Option<i32>,
+    /// Window for rankings and change figures
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_frame: Option<TimeFrame>,
+    /// Minimum market cap in USD
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_market_cap: Option<f64>,
+    /// Minimum security score (0-100), sent as `security_score`
+    #[serde(rename = "security_score", skip_serializing_if = "Option::is_none")]
+    pub security_score_min: Option<i32>,
 }
 
 impl DiscoveryQuery {
@@ -41,6 +51,40 @@ impl DiscoveryQuery {
         self.limit = Some(limit);
         self
     }
+
+    #[must_use]
+    pub fn time_frame(mut self, time_frame: TimeFrame) -> Self {
+        self.time_frame = Some(time_frame);
+        self
+    }
+
+    #[must_use]
+    pub fn min_market_cap(mut self, min_market_cap: f64) -> Self {
+        self.min_market_cap = Some(min_market_cap);
+        self
+    }
+
+    #[must_use]
+    pub fn security_score_min(mut self, score: i32) -> Self {
+        self.security_score_min = Some(score);
+        self
+    }
+
+    /// Whether `row` passes the market cap and security score filters
+    ///
+    /// Rows that don't report a value are kept.
+    #[must_use]
+    pub fn matches(&self, row: &DiscoveryRow) -> bool {
+        let cap_ok = match (self.min_market_cap, row.market_cap) {
+            (Some(min), Some(cap)) => cap >= min,
+            _ => true,
+        };
+        let score_ok = match (self.security_score_min, row.security_score) {
+            (Some(min), Some(score)) => score >= min,
+            _ => true,
+        };
+        cap_ok && score_ok
+    }
 }
 
 /// API for token discovery
@@ -54,17 +98,12 @@ impl<'a> DiscoveryApi<'a> {
         Self { client }
     }
 
-    /// Get tokens with rising liquidity
-    pub async fn get_rising_liquidity(
-        &self,
-        query: Option<&DiscoveryQuery>,
-    ) -> Result<DiscoveryResponse> {
-        let path = "/discovery/tokens/rising-liquidity";
-        if let Some(q) = query {
-            self.client.get_with_query(path, q).await
-        } else {
-            self.client.get(path).await
-        }
+    /// Tokens with rising liquidity over the query's time frame (Pro plan)
+    ///
+    /// Rows below the query's `min_market_cap` or `security_score_min` are
+    /// dropped even if the API returns them.
+    pub async fn get_rising_liquidity(&self, query: &DiscoveryQuery) -> Result<Vec<DiscoveryRow>> {
+        self.feed("/discovery/tokens/rising-liquidity", query).await
     }
 
     /// Get tokens with buying pressure
@@ -129,17 +168,11 @@ impl<'a> DiscoveryApi<'a> {
         }
     }
 
-    /// Get top gainers
-    pub async fn get_top_gainers(
-        &self,
-        query: Option<&DiscoveryQuery>,
-    ) -> Result<DiscoveryResponse> {
-        let path = "/discovery/tokens/top-gainers";
-        if let Some(q) = query {
-            self.client.get_with_query(path, q).await
-        } else {
-            self.client.get(path).await
-        }
+    /// Largest price gains over the query's time frame (Pro plan)
+    ///
+    /// See [`DiscoveryApi::get_rising_liquidity`] for filtering.
+    pub async fn get_top_gainers(&self, query: &DiscoveryQuery) -> Result<Vec<DiscoveryRow>> {
+        self.feed("/discovery/tokens/top-gainers", query).await
     }
 
     /// Get top losers
@@ -155,14 +188,33 @@ impl<'a> DiscoveryApi<'a> {
         }
     }
 
-    /// Get trending tokens
-    pub async fn get_trending(&self, query: Option<&DiscoveryQuery>) -> Result<DiscoveryResponse> {
-        let path = "/discovery/tokens/trending";
-        if let Some(q) = query {
-            self.client.get_with_query(path, q).await
-        } else {
-            self.client.get(path).await
-        }
+    /// Trending tokens over the query's time frame (Pro plan)
+    ///
+    /// Fails with `PlanRequired` before sending when the client was
+    /// configured with a lower plan via
+    /// [`Config::with_plan`](crate::Config::with_plan); a 402/403 from the
+    /// API maps to the same error. See
+    /// [`DiscoveryApi::get_rising_liquidity`] for filtering.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example(client: &mrls::Client) -> mrls::Result<()> {
+    /// use mrls::discovery::{DiscoveryQuery, TimeFrame};
+    ///
+    /// let query = DiscoveryQuery::new()
+    ///     .chain("eth")
+    ///     .time_frame(TimeFrame::OneWeek)
+    ///     .min_market_cap(50_000_000.0)
+    ///     .security_score_min(80);
+    /// for row in client.discovery().get_trending(&query).await? {
+    ///     println!("{:?} {:?}%", row.token_symbol, row.price_change(TimeFrame::OneWeek));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_trending(&self, query: &DiscoveryQuery) -> Result<Vec<DiscoveryRow>> {
+        self.feed("/discovery/tokens/trending", query).await
     }
 
     /// Get token analytics
@@ -200,6 +252,15 @@ impl<'a> DiscoveryApi<'a> {
         self.client.post("/discovery/tokens", filter).await
     }
 
+    /// Fetch a discovery feed as rows, applying the query's filters
+    async fn feed(&self, path: &str, query: &DiscoveryQuery) -> Result<Vec<DiscoveryRow>> {
+        self.client.require_plan(PlanTier::Pro, path)?;
+        let rows: DiscoveryRows = self.client.get_with_query(path, query).await?;
+        let mut rows = rows.into_rows();
+        rows.retain(|row| query.matches(row));
+        Ok(rows)
+    }
+
     /// Get single token details from discovery
     pub async fn get_token(&self, address: &str, chain: Option<&str>) -> Result<DiscoveredToken> {
         #[derive(Serialize)]
@@ -215,3 +276,144 @@ impl<'a> DiscoveryApi<'a> {
         self.client.get_with_query("/discovery/token", &query).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::DomainError;
+    use crate::{Config, Error};
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const TRENDING: &str = include_str!("../../tests/fixtures/discovery_trending.json");
+    const RISING: &str = include_str!("../../tests/fixtures/discovery_rising_liquidity.json");
+    const GAINERS: &str = include_str!("../../tests/fixtures/discovery_top_gainers.json");
+
+    fn client(server: &MockServer) -> Client {
+        Client::with_config(Config::new("test-key").base_url(server.uri())).unwrap()
+    }
+
+    async fn serve(server: &MockServer, endpoint: &str, body: &str) {
+        Mock::given(method("GET"))
+            .and(path(endpoint))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "application/json"))
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_trending_sends_filters_and_drops_rows_below_them() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/discovery/tokens/trending"))
+            .and(query_param("chain", "eth"))
+            .and(query_param("time_frame", "1w"))
+            .and(query_param("min_market_cap", "50000000.0"))
+            .and(query_param("security_score", "80"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(TRENDING, "application/json"))
+            .mount(&server)
+            .await;
+
+        let query = DiscoveryQuery::new()
+            .chain("eth")
+            .time_frame(TimeFrame::OneWeek)
+            .min_market_cap(50_000_000.0)
+            .security_score_min(80);
+        let rows = client(&server)
+            .discovery()
+            .get_trending(&query)
+            .await
+            .unwrap();
+
+        // SHIB is in the response but below the security score floor
+        assert_eq!(rows.len(), 1);
+        let pepe = &rows[0];
+        assert_eq!(pepe.token_symbol.as_deref(), Some("PEPE"));
+        assert_eq!(pepe.security_score, Some(88));
+        assert_eq!(pepe.price_change(TimeFrame::OneWeek), Some(18.2));
+        assert_eq!(pepe.volume_change(TimeFrame::OneDay), Some(98_000_000.0));
+        assert_eq!(pepe.price_change(TimeFrame::OneMonth), Some(-4.1));
+    }
+
+    #[tokio::test]
+    async fn test_rising_liquidity_rows() {
+        let server = MockServer::start().await;
+        serve(&server, "/discovery/tokens/rising-liquidity", RISING).await;
+        let api_client = client(&server);
+
+        let all = api_client
+            .discovery()
+            .get_rising_liquidity(&DiscoveryQuery::new().chain("base"))
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(
+            all[0].liquidity_change(TimeFrame::OneDay),
+            Some(2_350_000.0)
+        );
+        assert_eq!(all[0].liquidity_change(TimeFrame::OneWeek), None);
+
+        let filtered = api_client
+            .discovery()
+            .get_rising_liquidity(&DiscoveryQuery::new().min_market_cap(1_000_000.0))
+            .await
+            .unwrap();
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].token_symbol.as_deref(), Some("BRETT"));
+    }
+
+    #[tokio::test]
+    async fn test_top_gainers_accepts_wrapped_result() {
+        let server = MockServer::start().await;
+        serve(&server, "/discovery/tokens/top-gainers", GAINERS).await;
+
+        let rows = client(&server)
+            .discovery()
+            .get_top_gainers(&DiscoveryQuery::new().time_frame(TimeFrame::OneDay))
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].price_change(TimeFrame::OneDay), Some(14.7));
+        assert_eq!(rows[0].liquidity_change_usd, Default::default());
+    }
+
+    #[tokio::test]
+    async fn test_feed_plan_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/discovery/tokens/trending"))
+            .respond_with(ResponseTemplate::new(403).set_body_raw(
+                r#"{"message":"This endpoint requires a Pro plan"}"#,
+                "application/json",
+            ))
+            .mount(&server)
+            .await;
+
+        let err = client(&server)
+            .discovery()
+            .get_trending(&DiscoveryQuery::new())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Domain(DomainError::PlanRequired {
+                required_plan: PlanTier::Pro,
+                ..
+            })
+        ));
+
+        // A declared lower plan fails before anything is sent
+        let starter = Client::with_config(
+            Config::new("test-key")
+                .base_url("http://127.0.0.1:9")
+                .with_plan(PlanTier::Starter),
+        )
+        .unwrap();
+        let err = starter
+            .discovery()
+            .get_top_gainers(&DiscoveryQuery::new())
+            .await
+            .unwrap_err();
+        assert!(crate::error::is_plan_required(&err));
+    }
+}
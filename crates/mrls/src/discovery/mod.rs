@@ -1,7 +1,9 @@
 //! Discovery API - token discovery, trending, analytics, scores
 
 mod api;
+mod score;
 mod types;
 
 pub use api::{DiscoveryApi, DiscoveryQuery};
+pub use score::{MemoryScoreStore, ScoreObservation, ScoreStore, ScoreTracker};
 pub use types::*;
@@ -0,0 +1,299 @@
+//! Client-side token score history
+//!
+//! The score endpoint only reports the current score. [`ScoreTracker`]
+//! records each score it fetches in a [`ScoreStore`] so services can build
+//! history themselves; plug in a database-backed store to keep it across
+//! restarts.
+
+use super::types::TokenScore;
+use crate::client::Client;
+use crate::error::{self, Result};
+use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
+use std::sync::{Mutex, PoisonError};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A token score seen at a point in time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoreObservation {
+    /// Token address (lowercase)
+    pub token_address: String,
+    /// Chain the score was fetched for (lowercase)
+    pub chain: String,
+    /// Unix timestamp (seconds) of the observation
+    pub observed_at: u64,
+    /// Security score (0-100)
+    pub security_score: Option<i32>,
+    /// On chain strength index
+    pub on_chain_strength_index: Option<f64>,
+}
+
+/// Persistence for observed scores
+///
+/// [`ScoreTracker`] reports the store's errors as
+/// [`DomainError::ScoreStore`](crate::error::DomainError::ScoreStore).
+pub trait ScoreStore: Send + Sync {
+    /// The store's own error, e.g. an I/O or database error
+    type Error: std::fmt::Display;
+
+    /// Save an observation
+    fn record(&self, observation: ScoreObservation) -> std::result::Result<(), Self::Error>;
+
+    /// Observations for a token with `from <= observed_at <= to`, oldest
+    /// first
+    fn history(
+        &self,
+        token_address: &str,
+        chain: &str,
+        from: u64,
+        to: u64,
+    ) -> std::result::Result<Vec<ScoreObservation>, Self::Error>;
+}
+
+/// Score store that keeps observations in memory
+#[derive(Debug, Default)]
+pub struct MemoryScoreStore {
+    observations: Mutex<Vec<ScoreObservation>>,
+}
+
+impl MemoryScoreStore {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ScoreStore for MemoryScoreStore {
+    type Error = Infallible;
+
+    fn record(&self, observation: ScoreObservation) -> std::result::Result<(), Infallible> {
+        self.observations
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(observation);
+        Ok(())
+    }
+
+    fn history(
+        &self,
+        token_address: &str,
+        chain: &str,
+        from: u64,
+        to: u64,
+    ) -> std::result::Result<Vec<ScoreObservation>, Infallible> {
+        let mut history: Vec<ScoreObservation> = self
+            .observations
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .filter(|o| {
+                o.token_address == token_address
+                    && o.chain == chain
+                    && (from..=to).contains(&o.observed_at)
+            })
+            .cloned()
+            .collect();
+        history.sort_by_key(|o| o.observed_at);
+        Ok(history)
+    }
+}
+
+/// Records token scores as they are fetched and serves their history
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn example(client: &mrls::Client) -> mrls::Result<()> {
+/// use mrls::discovery::{MemoryScoreStore, ScoreTracker};
+///
+/// let tracker = ScoreTracker::new(MemoryScoreStore::new());
+/// let token = "0x6982508145454ce325ddbe47a25d4ec3d2311933";
+/// tracker.observe(client, token, "eth").await?;
+///
+/// for point in tracker.history(token, "eth", 0, u64::MAX)? {
+///     println!("{} {:?}", point.observed_at, point.security_score);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ScoreTracker<S: ScoreStore = MemoryScoreStore> {
+    store: S,
+}
+
+impl<S: ScoreStore> ScoreTracker<S> {
+    /// Create a tracker backed by `store`
+    #[must_use]
+    pub fn new(store: S) -> Self {
+        Self { store }
+    }
+
+    /// The backing store
+    #[must_use]
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Fetch the current score for a token and record it
+    pub async fn observe(
+        &self,
+        client: &Client,
+        token_address: &str,
+        chain: &str,
+    ) -> Result<ScoreObservation> {
+        let score = client
+            .discovery()
+            .get_token_score(token_address, Some(chain))
+            .await?;
+        self.record(token_address, chain, &score, unix_now())
+    }
+
+    /// Record a score fetched elsewhere, observed at `observed_at`
+    pub fn record(
+        &self,
+        token_address: &str,
+        chain: &str,
+        score: &TokenScore,
+        observed_at: u64,
+    ) -> Result<ScoreObservation> {
+        let observation = ScoreObservation {
+            token_address: token_address.to_ascii_lowercase(),
+            chain: chain.to_ascii_lowercase(),
+            observed_at,
+            security_score: score.security_score,
+            on_chain_strength_index: score.on_chain_strength_index,
+        };
+        self.store
+            .record(observation.clone())
+            .map_err(|e| error::score_store(e.to_string()))?;
+        Ok(observation)
+    }
+
+    /// Recorded scores for a token between `from` and `to` (unix seconds,
+    /// inclusive), oldest first
+    pub fn history(
+        &self,
+        token_address: &str,
+        chain: &str,
+        from: u64,
+        to: u64,
+    ) -> Result<Vec<ScoreObservation>> {
+        if from > to {
+            return Err(error::config(format!(
+                "score history range is empty: from {from} is after to {to}"
+            )));
+        }
+        self.store
+            .history(
+                &token_address.to_ascii_lowercase(),
+                &chain.to_ascii_lowercase(),
+                from,
+                to,
+            )
+            .map_err(|e| error::score_store(e.to_string()))
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::DomainError;
+    use crate::{Config, Error};
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const SCORE: &str = include_str!("../../tests/fixtures/token_score.json");
+    const PEPE: &str = "0x6982508145454ce325ddbe47a25d4ec3d2311933";
+
+    fn score(security_score: i32) -> TokenScore {
+        TokenScore {
+            security_score: Some(security_score),
+            ..serde_json::from_str(SCORE).unwrap()
+        }
+    }
+
+    #[test]
+    fn test_history_filters_range_and_normalizes_keys() {
+        let tracker = ScoreTracker::new(MemoryScoreStore::new());
+        tracker.record(PEPE, "eth", &score(90), 300).unwrap();
+        tracker.record(PEPE, "eth", &score(80), 100).unwrap();
+        tracker.record(PEPE, "eth", &score(85), 200).unwrap();
+        tracker.record(PEPE, "base", &score(10), 200).unwrap();
+
+        let upper = PEPE.to_ascii_uppercase().replace("0X", "0x");
+        let history = tracker.history(&upper, "ETH", 100, 250).unwrap();
+        let points: Vec<(u64, Option<i32>)> = history
+            .iter()
+            .map(|o| (o.observed_at, o.security_score))
+            .collect();
+        assert_eq!(points, [(100, Some(80)), (200, Some(85))]);
+        assert_eq!(history[0].on_chain_strength_index, Some(74.5));
+    }
+
+    #[test]
+    fn test_history_rejects_inverted_range() {
+        let tracker = ScoreTracker::new(MemoryScoreStore::new());
+        let err = tracker.history(PEPE, "eth", 10, 5).unwrap_err();
+        assert!(matches!(err, Error::Domain(DomainError::Config(_))));
+    }
+
+    /// Store whose backing file is unavailable
+    struct UnavailableStore;
+
+    impl ScoreStore for UnavailableStore {
+        type Error = std::io::Error;
+
+        fn record(&self, _: ScoreObservation) -> std::io::Result<()> {
+            Err(std::io::Error::other("disk full"))
+        }
+
+        fn history(
+            &self,
+            _: &str,
+            _: &str,
+            _: u64,
+            _: u64,
+        ) -> std::io::Result<Vec<ScoreObservation>> {
+            Err(std::io::ErrorKind::NotFound.into())
+        }
+    }
+
+    #[test]
+    fn test_store_errors_are_score_store_errors() {
+        let tracker = ScoreTracker::new(UnavailableStore);
+        let err = tracker.record(PEPE, "eth", &score(90), 100).unwrap_err();
+        match err {
+            Error::Domain(DomainError::ScoreStore(message)) => assert_eq!(message, "disk full"),
+            other => panic!("unexpected error: {other:?}"),
+        }
+        let err = tracker.history(PEPE, "eth", 0, 100).unwrap_err();
+        assert!(matches!(err, Error::Domain(DomainError::ScoreStore(_))));
+    }
+
+    #[tokio::test]
+    async fn test_observe_fetches_and_records() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(format!("/tokens/{PEPE}/score")))
+            .and(query_param("chain", "eth"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(SCORE, "application/json"))
+            .mount(&server)
+            .await;
+        let client = Client::with_config(Config::new("test-key").base_url(server.uri())).unwrap();
+
+        let tracker = ScoreTracker::new(MemoryScoreStore::new());
+        let observation = tracker.observe(&client, PEPE, "eth").await.unwrap();
+        assert_eq!(observation.security_score, Some(88));
+        assert!(observation.observed_at > 0);
+
+        let history = tracker.history(PEPE, "eth", 0, u64::MAX).unwrap();
+        assert_eq!(history, [observation]);
+    }
+}
@@ -116,3 +116,137 @@ pub struct DiscoveryResponse {
     /// Results
     pub result: Vec<DiscoveredToken>,
 }
+
+/// Window for discovery rankings and change figures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TimeFrame {
+    /// Last hour
+    #[serde(rename = "1h")]
+    OneHour,
+    /// Last 24 hours
+    #[serde(rename = "1d")]
+    OneDay,
+    /// Last 7 days
+    #[serde(rename = "1w")]
+    OneWeek,
+    /// Last 30 days
+    #[serde(rename = "1M")]
+    OneMonth,
+}
+
+impl TimeFrame {
+    /// Value sent as the `time_frame` query parameter
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimeFrame::OneHour => "1h",
+            TimeFrame::OneDay => "1d",
+            TimeFrame::OneWeek => "1w",
+            TimeFrame::OneMonth => "1M",
+        }
+    }
+}
+
+impl std::fmt::Display for TimeFrame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One figure per time frame
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct TimeFrameValues {
+    /// Last hour
+    #[serde(rename = "1h")]
+    pub one_hour: Option<f64>,
+    /// Last 24 hours
+    #[serde(rename = "1d")]
+    pub one_day: Option<f64>,
+    /// Last 7 days
+    #[serde(rename = "1w")]
+    pub one_week: Option<f64>,
+    /// Last 30 days
+    #[serde(rename = "1M")]
+    pub one_month: Option<f64>,
+}
+
+impl TimeFrameValues {
+    /// Figure for `time_frame`
+    #[must_use]
+    pub fn get(&self, time_frame: TimeFrame) -> Option<f64> {
+        match time_frame {
+            TimeFrame::OneHour => self.one_hour,
+            TimeFrame::OneDay => self.one_day,
+            TimeFrame::OneWeek => self.one_week,
+            TimeFrame::OneMonth => self.one_month,
+        }
+    }
+}
+
+/// Token row shared by the trending, rising-liquidity and top-gainers feeds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscoveryRow {
+    /// Chain id (hex, e.g. `0x1`)
+    pub chain_id: Option<String>,
+    /// Token address
+    pub token_address: String,
+    /// Token name
+    pub token_name: Option<String>,
+    /// Token symbol
+    pub token_symbol: Option<String>,
+    /// Token logo
+    pub token_logo: Option<String>,
+    /// Price USD
+    pub price_usd: Option<f64>,
+    /// Market cap USD
+    pub market_cap: Option<f64>,
+    /// Security score (0-100)
+    pub security_score: Option<i32>,
+    /// Holders count
+    pub holders: Option<i64>,
+    /// Price change in percent, per time frame
+    #[serde(default)]
+    pub price_percent_change_usd: TimeFrameValues,
+    /// Volume change in USD, per time frame
+    #[serde(default)]
+    pub volume_change_usd: TimeFrameValues,
+    /// Liquidity change in USD, per time frame
+    #[serde(default)]
+    pub liquidity_change_usd: TimeFrameValues,
+}
+
+impl DiscoveryRow {
+    /// Price change in percent over `time_frame`
+    #[must_use]
+    pub fn price_change(&self, time_frame: TimeFrame) -> Option<f64> {
+        self.price_percent_change_usd.get(time_frame)
+    }
+
+    /// Volume change in USD over `time_frame`
+    #[must_use]
+    pub fn volume_change(&self, time_frame: TimeFrame) -> Option<f64> {
+        self.volume_change_usd.get(time_frame)
+    }
+
+    /// Liquidity change in USD over `time_frame`
+    #[must_use]
+    pub fn liquidity_change(&self, time_frame: TimeFrame) -> Option<f64> {
+        self.liquidity_change_usd.get(time_frame)
+    }
+}
+
+/// Discovery feeds return a bare array; older responses wrap it in `result`
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum DiscoveryRows {
+    List(Vec<DiscoveryRow>),
+    Page { result: Vec<DiscoveryRow> },
+}
+
+impl DiscoveryRows {
+    pub(crate) fn into_rows(self) -> Vec<DiscoveryRow> {
+        match self {
+            DiscoveryRows::List(rows) | DiscoveryRows::Page { result: rows } => rows,
+        }
+    }
+}
@@ -18,7 +18,9 @@ pub struct ApiErrorResponse {
 }
 
 /// Required plan tier for an endpoint
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// Tiers are ordered, so `PlanTier::Pro > PlanTier::Starter`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 #[non_exhaustive]
 pub enum PlanTier {
     /// Free tier
@@ -71,6 +73,10 @@ pub enum DomainError {
     /// Insecure URL scheme (MED-002 fix)
     #[error("Insecure URL scheme: use HTTPS to protect API keys (got: {0})")]
     InsecureScheme(String),
+
+    /// A score store failed to read or write observations
+    #[error("Score store error: {0}")]
+    ScoreStore(String),
 }
 
 /// Error type for Moralis API operations
@@ -116,6 +122,11 @@ pub fn insecure_scheme(scheme: impl Into<String>) -> Error {
     ApiError::domain(DomainError::InsecureScheme(scheme.into()))
 }
 
+/// Create a score store error
+pub fn score_store(message: impl Into<String>) -> Error {
+    ApiError::domain(DomainError::ScoreStore(message.into()))
+}
+
 /// Create from HTTP response status and body
 ///
 /// Handles Moralis-specific error patterns:
@@ -159,6 +170,12 @@ pub fn from_response(status: u16, body: &str, retry_after: Option<u64>) -> Error
     }
 }
 
+/// Check if an error is a 404 `NotFound`
+#[must_use]
+pub fn is_not_found(error: &Error) -> bool {
+    matches!(error, ApiError::Domain(DomainError::NotFound(_)))
+}
+
 /// Check if an error is due to plan restrictions
 #[must_use]
 pub fn is_plan_required(error: &Error) -> bool {
@@ -79,8 +79,12 @@
 //! | Tier | Endpoints |
 //! |------|-----------|
 //! | **Free** | Most basic endpoints |
-//! | **Starter** | `get_token_score` |
-//! | **Pro** | Volume stats, token discovery, analytics, search |
+//! | **Starter** | `get_token_score`, wallet profitability |
+//! | **Pro** | Volume stats, token discovery, analytics, search, top movers |
+//!
+//! Declare your tier with `Config::with_plan` and gated endpoints such as
+//! `market().top_movers()` and `discovery().get_trending()` fail fast with `PlanRequired` instead of spending
+//! a request.
 //!
 //! ## Automatic Retries
 //!
@@ -111,7 +115,32 @@
 //! - `RetryConfig::quick()` - 2 retries, 50ms initial delay (interactive)
 //! - `RetryConfig::batch()` - 5 retries, 200ms initial delay (batch jobs)
 //! - `RetryConfig::none()` - No retries
+//!
+//! ## Compute-Unit Budget
+//!
+//! Moralis throttles on compute units (CU) per second. Set a budget and load
+//! the published endpoint weights to pace requests by their cost. A 429 also
+//! pauses the budgeter, so `with_retry` attempts wait out the `Retry-After`:
+//!
+//! ```no_run
+//! use mrls::{Client, Config};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), mrls::Error> {
+//!     let config = Config::new("your-api-key")
+//!         .with_cu_budget(1_000)
+//!         .with_default_endpoint_weight(5);
+//!     let client = Client::with_config(config)?;
+//!     client.load_endpoint_weights().await?;
+//!
+//!     client.token().get_price("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2", Some("eth")).await?;
+//!     let stats = client.cu_stats();
+//!     println!("{} CU in the last second, {} total", stats.cu_last_second, stats.total_cu);
+//!     Ok(())
+//! }
+//! ```
 
+pub mod budget;
 mod client;
 pub mod error;
 
@@ -131,6 +160,7 @@ pub mod volume;
 pub mod wallet;
 
 // Re-exports
+pub use budget::{CuStats, EndpointWeights, DEFAULT_ENDPOINT_WEIGHT};
 pub use client::{Client, Config};
 pub use error::{Error, PlanTier};
 pub use yldfi_common::http::HttpClientConfig;
@@ -1,8 +1,12 @@
 //! Market Data API client
 
-use super::types::{GlobalMarketCap, GlobalVolume, TopNftCollection, TopToken};
+use super::types::{
+    GlobalMarketCap, GlobalVolume, MoverDirection, TopMover, TopMoversResponse, TopNftCollection,
+    TopToken,
+};
 use crate::client::Client;
-use crate::error::Result;
+use crate::error::{PlanTier, Result};
+use crate::token::TrendingToken;
 use serde::Serialize;
 
 /// Query parameters for market data endpoints
@@ -64,6 +68,68 @@ impl<'a> MarketApi<'a> {
         }
     }
 
+    /// Trending tokens, optionally filtered to one chain
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example(client: &mrls::Client) -> mrls::Result<()> {
+    /// for token in client.market().trending_tokens(Some("base"), Some(10)).await? {
+    ///     println!("{:?} {:?}", token.token_symbol, token.price_change_24h);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn trending_tokens(
+        &self,
+        chain: Option<&str>,
+        limit: Option<usize>,
+    ) -> Result<Vec<TrendingToken>> {
+        let query = feed_query(chain, limit);
+        let mut tokens: Vec<TrendingToken> = self
+            .client
+            .get_with_query("/tokens/trending", &query)
+            .await?;
+        if let Some(limit) = limit {
+            tokens.truncate(limit);
+        }
+        Ok(tokens)
+    }
+
+    /// Top gainers or losers, strongest move first (Pro plan)
+    ///
+    /// Fails with `PlanRequired` before sending when the client was
+    /// configured with a lower plan via
+    /// [`Config::with_plan`](crate::Config::with_plan).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example(client: &mrls::Client) -> mrls::Result<()> {
+    /// use mrls::market::MoverDirection;
+    ///
+    /// let losers = client.market().top_movers(Some("eth"), MoverDirection::Losers, Some(5)).await?;
+    /// for mover in &losers {
+    ///     println!("{:?} {:?}%", mover.token_symbol, mover.price_percent_change);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn top_movers(
+        &self,
+        chain: Option<&str>,
+        direction: MoverDirection,
+        limit: Option<usize>,
+    ) -> Result<Vec<TopMover>> {
+        let path = "/market-data/erc20s/top-movers";
+        self.client.require_plan(PlanTier::Pro, path)?;
+        let response: TopMoversResponse = self
+            .client
+            .get_with_query(path, &feed_query(chain, limit))
+            .await?;
+        Ok(response.into_direction(direction, limit))
+    }
+
     /// Get top NFT collections
     pub async fn get_top_nft_collections(
         &self,
@@ -100,3 +166,52 @@ impl<'a> MarketApi<'a> {
         self.client.get("/market-data/global/volume").await
     }
 }
+
+fn feed_query(chain: Option<&str>, limit: Option<usize>) -> MarketQuery {
+    let mut query = MarketQuery::new();
+    if let Some(chain) = chain {
+        query = query.chain(chain);
+    }
+    if let Some(limit) = limit {
+        query = query.limit(i32::try_from(limit).unwrap_or(i32::MAX));
+    }
+    query
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::DomainError;
+    use crate::{Config, Error};
+
+    #[tokio::test]
+    async fn test_top_movers_plan_gate() {
+        let client = Client::with_config(
+            Config::new("test-key")
+                .base_url("http://127.0.0.1:9")
+                .with_plan(PlanTier::Starter),
+        )
+        .unwrap();
+
+        let err = client
+            .market()
+            .top_movers(None, MoverDirection::Gainers, None)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Domain(DomainError::PlanRequired {
+                required_plan: PlanTier::Pro,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_feed_query() {
+        let query = feed_query(Some("base"), Some(25));
+        assert_eq!(query.chain.as_deref(), Some("base"));
+        assert_eq!(query.limit, Some(25));
+        assert!(feed_query(None, None).limit.is_none());
+    }
+}
@@ -31,25 +31,104 @@ pub struct TopToken {
 }
 
 /// Top mover (gainer/loser)
+///
+/// Accepts both the camelCase and the snake_case field names Moralis uses.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TopMover {
     /// Token address
+    #[serde(alias = "token_address", alias = "contract_address")]
     pub token_address: Option<String>,
     /// Token name
+    #[serde(alias = "token_name")]
     pub token_name: Option<String>,
     /// Token symbol
+    #[serde(alias = "token_symbol")]
     pub token_symbol: Option<String>,
     /// Token logo
+    #[serde(alias = "token_logo")]
     pub token_logo: Option<String>,
     /// Price USD
+    #[serde(alias = "price_usd")]
     pub price_usd: Option<f64>,
     /// Price change percentage
+    #[serde(
+        alias = "price_percent_change",
+        alias = "price_24h_percent_change",
+        alias = "price24hPercentChange"
+    )]
     pub price_percent_change: Option<f64>,
     /// Volume 24h USD
+    #[serde(alias = "volume_24h_usd")]
     pub volume_24h_usd: Option<f64>,
 }
 
+/// Which side of the top-movers list to return
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MoverDirection {
+    /// Largest price increases
+    Gainers,
+    /// Largest price decreases
+    Losers,
+}
+
+impl MoverDirection {
+    /// Lowercase name, matching the response key
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Gainers => "gainers",
+            Self::Losers => "losers",
+        }
+    }
+}
+
+impl std::fmt::Display for MoverDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Top movers response, split into gainers and losers
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TopMoversResponse {
+    /// Tokens with the largest price increase
+    #[serde(default)]
+    pub gainers: Vec<TopMover>,
+    /// Tokens with the largest price decrease
+    #[serde(default)]
+    pub losers: Vec<TopMover>,
+}
+
+impl TopMoversResponse {
+    /// Take one side, strongest move first, truncated to `limit`
+    ///
+    /// Gainers sort by price change descending, losers ascending; tokens
+    /// without a price change go last.
+    #[must_use]
+    pub fn into_direction(self, direction: MoverDirection, limit: Option<usize>) -> Vec<TopMover> {
+        let mut movers = match direction {
+            MoverDirection::Gainers => self.gainers,
+            MoverDirection::Losers => self.losers,
+        };
+        let key = |m: &TopMover| {
+            m.price_percent_change.map(|c| match direction {
+                MoverDirection::Gainers => -c,
+                MoverDirection::Losers => c,
+            })
+        };
+        movers.sort_by(|a, b| match (key(a), key(b)) {
+            (Some(a), Some(b)) => a.total_cmp(&b),
+            (a, b) => a.is_none().cmp(&b.is_none()),
+        });
+        if let Some(limit) = limit {
+            movers.truncate(limit);
+        }
+        movers
+    }
+}
+
 /// Top NFT collection
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -102,3 +181,43 @@ pub struct MarketDataResponse<T> {
     /// Results
     pub result: Vec<T>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_top_movers_direction() {
+        let json = r#"{
+            "gainers": [
+                {"contract_address": "0xa", "token_symbol": "A", "price_24h_percent_change": 12.5, "volume_24h_usd": 1000.0},
+                {"tokenAddress": "0xb", "tokenSymbol": "B", "pricePercentChange": 40.0},
+                {"token_address": "0xc", "token_symbol": "C"}
+            ],
+            "losers": [
+                {"token_address": "0xd", "price_24h_percent_change": -5.0},
+                {"token_address": "0xe", "price_24h_percent_change": -30.0}
+            ]
+        }"#;
+        let response: TopMoversResponse = serde_json::from_str(json).unwrap();
+
+        let gainers = response
+            .clone()
+            .into_direction(MoverDirection::Gainers, None);
+        let order: Vec<_> = gainers
+            .iter()
+            .filter_map(|m| m.token_address.as_deref())
+            .collect();
+        assert_eq!(order, ["0xb", "0xa", "0xc"]);
+        assert_eq!(gainers[1].volume_24h_usd, Some(1000.0));
+
+        let losers = response.into_direction(MoverDirection::Losers, Some(1));
+        assert_eq!(losers.len(), 1);
+        assert_eq!(losers[0].token_address.as_deref(), Some("0xe"));
+
+        let empty: TopMoversResponse = serde_json::from_str("{}").unwrap();
+        assert!(empty
+            .into_direction(MoverDirection::Gainers, Some(3))
+            .is_empty());
+    }
+}
@@ -1,13 +1,14 @@
 //! Token API client
 
 use super::types::{
-    AggregatedPairStats, GetMultiplePricesRequest, HistoricalHolders, NewToken, PairOhlcv,
-    PairSniper, PairStats, TokenBondingStatus, TokenCategory, TokenHoldersResponse,
-    TokenHoldersSummary, TokenMetadata, TokenPair, TokenPrice, TokenResponse, TokenSearchResult,
-    TokenStats, TokenSwap, TokenTransfer, TopTrader, TrendingToken,
+    sort_pairs_by_liquidity, AggregatedPairStats, GetMultiplePricesRequest, HistoricalHolders,
+    HolderSummary, NewToken, PairOhlcv, PairSniper, PairStats, TokenBondingStatus, TokenCategory,
+    TokenHoldersResponse, TokenHoldersSummary, TokenMetadata, TokenPair, TokenPairsResponse,
+    TokenPrice, TokenResponse, TokenSearchResult, TokenStats, TokenSwap, TokenTransfer, TopTrader,
+    TrendingToken,
 };
 use crate::client::Client;
-use crate::error::Result;
+use crate::error::{is_not_found, Result};
 use serde::Serialize;
 
 /// Query parameters for token endpoints
@@ -19,6 +20,9 @@ pub struct TokenQuery {
     /// Include spam tokens
     #[serde(skip_serializing_if = "Option::is_none")]
     pub include_spam: Option<bool>,
+    /// Pagination cursor
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
 }
 
 impl TokenQuery {
@@ -43,6 +47,55 @@ impl TokenQuery {
     }
 }
 
+/// Query parameters for the token holders (owners) endpoint
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct TokenHoldersQuery {
+    /// Chain to query
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain: Option<String>,
+    /// Pagination cursor
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    /// Page size (max 100)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i32>,
+}
+
+impl TokenHoldersQuery {
+    /// Create a new query
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set chain
+    #[must_use]
+    pub fn chain(mut self, chain: impl Into<String>) -> Self {
+        self.chain = Some(chain.into());
+        self
+    }
+
+    /// Set cursor
+    #[must_use]
+    pub fn cursor(mut self, cursor: impl Into<String>) -> Self {
+        self.cursor = Some(cursor.into());
+        self
+    }
+
+    /// Set limit
+    #[must_use]
+    pub fn limit(mut self, limit: i32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
+/// Page size used when sampling holders for [`TokenApi::get_holder_summary`]
+pub const HOLDER_SUMMARY_PAGE_SIZE: i32 = 100;
+
+/// Maximum pages fetched by [`TokenApi::get_holder_summary`] (1,000 holders)
+pub const HOLDER_SUMMARY_MAX_PAGES: usize = 10;
+
 /// API for token operations
 pub struct TokenApi<'a> {
     client: &'a Client,
@@ -113,15 +166,45 @@ impl<'a> TokenApi<'a> {
         }
     }
 
-    /// Get token pairs (DEX liquidity pools)
+    /// Get token pairs (DEX liquidity pools), deepest liquidity first
+    ///
+    /// Follows the response cursor until every page is read, so the sort
+    /// covers all pairs. Tokens without any pairs yield an empty vec rather
+    /// than an error.
     pub async fn get_pairs(&self, address: &str, chain: Option<&str>) -> Result<Vec<TokenPair>> {
         let path = format!("/erc20/{address}/pairs");
-        if let Some(chain) = chain {
-            let query = TokenQuery::new().chain(chain);
-            self.client.get_with_query(&path, &query).await
-        } else {
-            self.client.get(&path).await
+        let mut query = TokenQuery::new();
+        query.chain = chain.map(str::to_string);
+
+        let mut pairs = Vec::new();
+        loop {
+            let page: TokenPairsResponse = match self.client.get_with_query(&path, &query).await {
+                Ok(page) => page,
+                Err(e) if is_not_found(&e) && pairs.is_empty() => return Ok(Vec::new()),
+                Err(e) => return Err(e),
+            };
+            pairs.extend(page.pairs);
+            match page.cursor {
+                Some(cursor) if !cursor.is_empty() && query.cursor.as_ref() != Some(&cursor) => {
+                    query.cursor = Some(cursor);
+                }
+                _ => break,
+            }
         }
+        sort_pairs_by_liquidity(&mut pairs);
+        Ok(pairs)
+    }
+
+    /// Get the pair with the deepest USD liquidity for a token
+    ///
+    /// Useful as the pricing venue for DEX-based pricing. Pairs without a
+    /// liquidity figure are only returned if no pair has one.
+    pub async fn get_deepest_pair(
+        &self,
+        address: &str,
+        chain: Option<&str>,
+    ) -> Result<Option<TokenPair>> {
+        Ok(self.get_pairs(address, chain).await?.into_iter().next())
     }
 
     /// Get top token holders
@@ -139,6 +222,61 @@ impl<'a> TokenApi<'a> {
         }
     }
 
+    /// Get a page of token holders, sorted by balance descending
+    pub async fn get_holders_page(
+        &self,
+        address: &str,
+        query: &TokenHoldersQuery,
+    ) -> Result<TokenHoldersResponse> {
+        let path = format!("/erc20/{address}/owners");
+        self.client.get_with_query(&path, query).await
+    }
+
+    /// Get holder concentration metrics for a token
+    ///
+    /// Fetches the holder count plus up to [`HOLDER_SUMMARY_MAX_PAGES`] pages
+    /// of the largest holders. Tokens with more holders than that are
+    /// sampled: top-10/top-50 percentages remain exact because the largest
+    /// holders come first, but `gini` is `None` and `approximate` is set.
+    /// Hitting the page cap also sets `approximate`, even when the API does
+    /// not report a holder count.
+    pub async fn get_holder_summary(
+        &self,
+        address: &str,
+        chain: Option<&str>,
+    ) -> Result<HolderSummary> {
+        let total_holders = self
+            .get_holders_summary(address, chain)
+            .await?
+            .total_holders
+            .and_then(|t| u64::try_from(t).ok());
+
+        let mut query = TokenHoldersQuery::new().limit(HOLDER_SUMMARY_PAGE_SIZE);
+        if let Some(chain) = chain {
+            query = query.chain(chain);
+        }
+
+        let mut holders = Vec::new();
+        let mut truncated = false;
+        for page_number in 1..=HOLDER_SUMMARY_MAX_PAGES {
+            let page = self.get_holders_page(address, &query).await?;
+            holders.extend(page.result);
+            match page.cursor {
+                Some(cursor) if !cursor.is_empty() => {
+                    truncated = page_number == HOLDER_SUMMARY_MAX_PAGES;
+                    query.cursor = Some(cursor);
+                }
+                _ => break,
+            }
+        }
+
+        Ok(HolderSummary::from_holders(
+            &holders,
+            total_holders,
+            truncated,
+        ))
+    }
+
     /// Get token swaps
     pub async fn get_swaps(
         &self,
@@ -453,3 +591,91 @@ impl<'a> TokenApi<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Config;
+    use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const TOKEN: &str = "0x6982508145454ce325ddbe47a25d4ec3d2311933";
+
+    #[tokio::test]
+    async fn test_holder_summary_at_page_cap_is_approximate() {
+        let server = MockServer::start().await;
+        // No holder count, so only the page cap can tell the list is partial
+        Mock::given(method("GET"))
+            .and(path(format!("/erc20/{TOKEN}/holders")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .mount(&server)
+            .await;
+        let holders: Vec<serde_json::Value> = (0..HOLDER_SUMMARY_PAGE_SIZE)
+            .map(|i| {
+                serde_json::json!({
+                    "owner": format!("0x{i:040x}"),
+                    "balance": "1",
+                    "percentage_relative_to_total_supply": 0.01
+                })
+            })
+            .collect();
+        Mock::given(method("GET"))
+            .and(path(format!("/erc20/{TOKEN}/owners")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "cursor": "more",
+                "result": holders
+            })))
+            .expect(HOLDER_SUMMARY_MAX_PAGES as u64)
+            .mount(&server)
+            .await;
+
+        let client = Client::with_config(Config::new("test-key").base_url(server.uri())).unwrap();
+        let summary = client
+            .token()
+            .get_holder_summary(TOKEN, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            summary.holders_sampled,
+            HOLDER_SUMMARY_MAX_PAGES * HOLDER_SUMMARY_PAGE_SIZE as usize
+        );
+        assert!(summary.approximate);
+        assert!(summary.gini.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pairs_follow_cursor_before_sorting() {
+        let server = MockServer::start().await;
+        let pair = |address: &str, liquidity: f64| serde_json::json!({ "pair_address": address, "liquidity_usd": liquidity });
+        Mock::given(method("GET"))
+            .and(path(format!("/erc20/{TOKEN}/pairs")))
+            .and(query_param("chain", "eth"))
+            .and(query_param_is_missing("cursor"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "cursor": "page2",
+                "pairs": [pair("0xshallow", 10.0)]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("/erc20/{TOKEN}/pairs")))
+            .and(query_param("chain", "eth"))
+            .and(query_param("cursor", "page2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "cursor": null,
+                "pairs": [pair("0xdeep", 1000.0)]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::with_config(Config::new("test-key").base_url(server.uri())).unwrap();
+        let pairs = client.token().get_pairs(TOKEN, Some("eth")).await.unwrap();
+        let order: Vec<_> = pairs
+            .iter()
+            .filter_map(|p| p.pair_address.as_deref())
+            .collect();
+        assert_eq!(order, ["0xdeep", "0xshallow"]);
+    }
+}
@@ -3,5 +3,5 @@
 mod api;
 mod types;
 
-pub use api::TokenApi;
+pub use api::{TokenApi, TokenHoldersQuery, HOLDER_SUMMARY_MAX_PAGES, HOLDER_SUMMARY_PAGE_SIZE};
 pub use types::*;
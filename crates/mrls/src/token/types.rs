@@ -88,25 +88,130 @@ pub struct TokenTransfer {
     pub possible_spam: Option<bool>,
 }
 
-/// Token pair
+/// One side of a DEX pair
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+pub struct PairToken {
+    /// Token address
+    pub token_address: Option<String>,
+    /// Token name
+    pub token_name: Option<String>,
+    /// Token symbol
+    pub token_symbol: Option<String>,
+    /// Token logo URL
+    pub token_logo: Option<String>,
+    /// Token decimals
+    pub token_decimals: Option<String>,
+    /// Position in the pair (`token0` or `token1`)
+    pub pair_token_type: Option<String>,
+    /// Liquidity on this side of the pair in USD
+    pub liquidity_usd: Option<f64>,
+}
+
+/// Token pair (DEX liquidity pool)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "RawTokenPair")]
 pub struct TokenPair {
     /// Pair address
     pub pair_address: Option<String>,
-    /// Pair label
+    /// Pair label (e.g. `PEPE/WETH`)
     pub pair_label: Option<String>,
     /// Exchange name
-    pub exchange_name: Option<String>,
+    pub exchange: Option<String>,
+    /// Exchange (factory) address
+    pub exchange_address: Option<String>,
     /// Exchange logo
     pub exchange_logo: Option<String>,
-    /// USD price
+    /// First token of the pair
+    pub token0: Option<PairToken>,
+    /// Second token of the pair
+    pub token1: Option<PairToken>,
+    /// USD price of the queried token in this pair
     pub usd_price: Option<f64>,
     /// USD price 24hr change
-    #[serde(rename = "usdPrice24hrPercentChange")]
     pub usd_price_24hr_percent_change: Option<f64>,
     /// Liquidity in USD
     pub liquidity_usd: Option<f64>,
+    /// Whether Moralis considers the pair inactive
+    pub inactive_pair: Option<bool>,
+}
+
+/// Wire format of [`TokenPair`]
+///
+/// Moralis returns both tokens as a `pair` array; this also accepts the
+/// flattened `token0`/`token1` shape `TokenPair` serializes to.
+#[derive(Deserialize)]
+struct RawTokenPair {
+    #[serde(alias = "pairAddress")]
+    pair_address: Option<String>,
+    #[serde(alias = "pairLabel")]
+    pair_label: Option<String>,
+    #[serde(alias = "exchange_name", alias = "exchangeName")]
+    exchange: Option<String>,
+    exchange_address: Option<String>,
+    #[serde(alias = "exchangeLogo")]
+    exchange_logo: Option<String>,
+    #[serde(default)]
+    pair: Vec<PairToken>,
+    token0: Option<PairToken>,
+    token1: Option<PairToken>,
+    #[serde(alias = "usdPrice")]
+    usd_price: Option<f64>,
+    #[serde(alias = "usdPrice24hrPercentChange")]
+    usd_price_24hr_percent_change: Option<f64>,
+    #[serde(alias = "liquidityUsd")]
+    liquidity_usd: Option<f64>,
+    inactive_pair: Option<bool>,
+}
+
+impl From<RawTokenPair> for TokenPair {
+    fn from(raw: RawTokenPair) -> Self {
+        let pair = raw.pair;
+        let take = |side: &str, index: usize| {
+            pair.iter()
+                .position(|t| t.pair_token_type.as_deref() == Some(side))
+                .or_else(|| (index < pair.len()).then_some(index))
+                .map(|i| pair[i].clone())
+        };
+        let token0 = raw.token0.or_else(|| take("token0", 0));
+        let token1 = raw.token1.or_else(|| take("token1", 1));
+
+        Self {
+            pair_address: raw.pair_address,
+            pair_label: raw.pair_label,
+            exchange: raw.exchange,
+            exchange_address: raw.exchange_address,
+            exchange_logo: raw.exchange_logo,
+            token0,
+            token1,
+            usd_price: raw.usd_price,
+            usd_price_24hr_percent_change: raw.usd_price_24hr_percent_change,
+            liquidity_usd: raw.liquidity_usd,
+            inactive_pair: raw.inactive_pair,
+        }
+    }
+}
+
+/// Token pairs response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenPairsResponse {
+    /// Cursor for pagination
+    pub cursor: Option<String>,
+    /// Page size
+    pub page_size: Option<i32>,
+    /// Pairs
+    #[serde(default)]
+    pub pairs: Vec<TokenPair>,
+}
+
+/// Sort pairs by USD liquidity, deepest first
+///
+/// Pairs without a liquidity figure sort last.
+pub fn sort_pairs_by_liquidity(pairs: &mut [TokenPair]) {
+    pairs.sort_by(|a, b| {
+        let a = a.liquidity_usd.unwrap_or(f64::NEG_INFINITY);
+        let b = b.liquidity_usd.unwrap_or(f64::NEG_INFINITY);
+        b.total_cmp(&a)
+    });
 }
 
 /// Top token holder
@@ -382,6 +487,89 @@ pub struct TokenHoldersSummary {
     pub holders_change_percent_24h: Option<f64>,
 }
 
+/// Holder concentration metrics for a token
+///
+/// Top-N percentages come from the largest holders, which the API returns
+/// first, so they stay accurate even when only a sample is fetched. The Gini
+/// coefficient needs the full distribution and is only computed when every
+/// holder was enumerated; otherwise `approximate` is set and `gini` is `None`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HolderSummary {
+    /// Total number of holders (from the holders summary endpoint when
+    /// available, otherwise the number of holders fetched)
+    pub total_holders: u64,
+    /// Percentage of supply held by the top 10 holders
+    pub top10_pct: f64,
+    /// Percentage of supply held by the top 50 holders
+    pub top50_pct: f64,
+    /// Gini coefficient of holder balances (0 = equal, 1 = concentrated)
+    pub gini: Option<f64>,
+    /// Number of holders the metrics were computed from
+    pub holders_sampled: usize,
+    /// Whether the holder list was truncated
+    pub approximate: bool,
+}
+
+impl HolderSummary {
+    /// Compute concentration metrics from holders sorted by balance descending
+    ///
+    /// `truncated` says paging stopped before the last holder, which marks
+    /// the summary approximate even when the holder count is unknown.
+    #[must_use]
+    pub fn from_holders(
+        holders: &[TokenHolder],
+        total_holders: Option<u64>,
+        truncated: bool,
+    ) -> Self {
+        let top_pct = |n: usize| -> f64 {
+            holders
+                .iter()
+                .take(n)
+                .filter_map(|h| h.percentage_relative_to_total_supply)
+                .sum()
+        };
+
+        let sampled = holders.len();
+        let total = total_holders.unwrap_or(sampled as u64).max(sampled as u64);
+        let approximate = truncated || (sampled as u64) < total;
+        let gini = if approximate {
+            None
+        } else {
+            let balances: Vec<f64> = holders
+                .iter()
+                .filter_map(|h| h.balance.parse::<f64>().ok())
+                .collect();
+            gini_coefficient(&balances)
+        };
+
+        Self {
+            total_holders: total,
+            top10_pct: top_pct(10),
+            top50_pct: top_pct(50),
+            gini,
+            holders_sampled: sampled,
+            approximate,
+        }
+    }
+}
+
+/// Gini coefficient of a set of non-negative values
+fn gini_coefficient(values: &[f64]) -> Option<f64> {
+    let mut sorted: Vec<f64> = values.iter().copied().filter(|v| *v >= 0.0).collect();
+    let total: f64 = sorted.iter().sum();
+    if sorted.is_empty() || total <= 0.0 {
+        return None;
+    }
+    sorted.sort_by(f64::total_cmp);
+    let n = sorted.len() as f64;
+    let weighted: f64 = sorted
+        .iter()
+        .enumerate()
+        .map(|(i, v)| (2.0 * (i as f64 + 1.0) - n - 1.0) * v)
+        .sum();
+    Some(weighted / (n * total))
+}
+
 /// Historical holders data point
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -465,3 +653,128 @@ pub struct TokenBondingStatus {
     /// Market cap USD
     pub market_cap_usd: Option<f64>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn holder(balance: &str, pct: f64) -> TokenHolder {
+        TokenHolder {
+            owner: "0x0".to_string(),
+            balance: balance.to_string(),
+            balance_formatted: None,
+            is_contract: None,
+            usd_value: None,
+            percentage_relative_to_total_supply: Some(pct),
+        }
+    }
+
+    #[test]
+    fn test_holder_summary_full_enumeration() {
+        let holders = vec![holder("70", 70.0), holder("20", 20.0), holder("10", 10.0)];
+        let summary = HolderSummary::from_holders(&holders, Some(3), false);
+        assert_eq!(summary.total_holders, 3);
+        assert!(!summary.approximate);
+        assert!((summary.top10_pct - 100.0).abs() < 1e-9);
+        // sorted [10, 20, 70]: (-2*10 + 0*20 + 2*70) / (3 * 100) = 0.4
+        assert!((summary.gini.unwrap() - 0.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_holder_summary_sampled() {
+        let holders: Vec<TokenHolder> = (0..60).map(|_| holder("1", 1.0)).collect();
+        let summary = HolderSummary::from_holders(&holders, Some(10_000), false);
+        assert!(summary.approximate);
+        assert!(summary.gini.is_none());
+        assert_eq!(summary.holders_sampled, 60);
+        assert!((summary.top10_pct - 10.0).abs() < 1e-9);
+        assert!((summary.top50_pct - 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_holder_summary_truncated_without_count() {
+        let holders = vec![holder("70", 70.0), holder("20", 20.0)];
+        let summary = HolderSummary::from_holders(&holders, None, true);
+        assert_eq!(summary.total_holders, 2);
+        assert!(summary.approximate);
+        assert!(summary.gini.is_none());
+    }
+
+    #[test]
+    fn test_token_pairs_parse_and_sort() {
+        let json = r#"{
+            "cursor": null,
+            "page_size": 50,
+            "pairs": [
+                {
+                    "exchange_address": "0x5C69bEe701ef814a2B6a3EDD4B1652CB9cc5aA6f",
+                    "exchange_name": "Uniswap v2",
+                    "pair_label": "PEPE/WETH",
+                    "pair_address": "0xa43fe16908251ee70ef74718545e4fe6c5ccec9f",
+                    "usd_price": 0.0000123,
+                    "liquidity_usd": 1500.5,
+                    "inactive_pair": false,
+                    "pair": [
+                        {"token_address": "0x6982", "token_symbol": "PEPE", "token_decimals": "18", "pair_token_type": "token0", "liquidity_usd": 750.0},
+                        {"token_address": "0xc02a", "token_symbol": "WETH", "token_decimals": "18", "pair_token_type": "token1", "liquidity_usd": 750.5}
+                    ]
+                },
+                {"pair_address": "0xnone", "exchange_name": "Sushiswap"},
+                {
+                    "pair_address": "0xdeep",
+                    "exchange_name": "Uniswap v3",
+                    "liquidity_usd": 9000000.0,
+                    "pair": [
+                        {"token_address": "0xc02a", "pair_token_type": "token1"},
+                        {"token_address": "0x6982", "pair_token_type": "token0"}
+                    ]
+                }
+            ]
+        }"#;
+        let mut pairs = serde_json::from_str::<TokenPairsResponse>(json)
+            .unwrap()
+            .pairs;
+        sort_pairs_by_liquidity(&mut pairs);
+
+        let order: Vec<_> = pairs
+            .iter()
+            .filter_map(|p| p.pair_address.as_deref())
+            .collect();
+        assert_eq!(
+            order,
+            [
+                "0xdeep",
+                "0xa43fe16908251ee70ef74718545e4fe6c5ccec9f",
+                "0xnone"
+            ]
+        );
+
+        // token0/token1 follow pair_token_type, not array order
+        let deep = &pairs[0];
+        assert_eq!(deep.exchange.as_deref(), Some("Uniswap v3"));
+        assert_eq!(
+            deep.token0.as_ref().unwrap().token_address.as_deref(),
+            Some("0x6982")
+        );
+        assert_eq!(
+            deep.token1.as_ref().unwrap().token_address.as_deref(),
+            Some("0xc02a")
+        );
+        assert!(pairs[2].token0.is_none());
+
+        // Serialized form round-trips
+        let json = serde_json::to_string(&pairs[1]).unwrap();
+        let back: TokenPair = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.token1.unwrap().token_symbol.as_deref(), Some("WETH"));
+        assert_eq!(back.liquidity_usd, Some(1500.5));
+
+        let empty: TokenPairsResponse = serde_json::from_str(r#"{"pairs": []}"#).unwrap();
+        assert!(empty.pairs.is_empty());
+    }
+
+    #[test]
+    fn test_gini_equal_distribution() {
+        assert_eq!(gini_coefficient(&[5.0, 5.0, 5.0]), Some(0.0));
+        assert_eq!(gini_coefficient(&[]), None);
+    }
+}
@@ -13,6 +13,9 @@ pub struct TransactionQuery {
     pub chain: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub include_internal_transactions: Option<bool>,
+    /// Extra data to include (e.g. `internal_transactions`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include: Option<String>,
 }
 
 impl TransactionQuery {
@@ -32,6 +35,12 @@ impl TransactionQuery {
         self.include_internal_transactions = Some(include);
         self
     }
+
+    #[must_use]
+    pub fn include(mut self, include: impl Into<String>) -> Self {
+        self.include = Some(include.into());
+        self
+    }
 }
 
 /// API for transaction operations
@@ -70,6 +79,26 @@ impl<'a> TransactionApi<'a> {
         }
     }
 
+    /// Get a verbose transaction, optionally with its internal transactions
+    ///
+    /// Pair with [`ValueFlow::summarize`](super::ValueFlow::summarize) for a
+    /// per-address digest of native and ERC-20 movements.
+    pub async fn get_verbose(
+        &self,
+        tx_hash: &str,
+        chain: Option<&str>,
+        include_internal: bool,
+    ) -> Result<VerboseTransaction> {
+        let mut query = TransactionQuery::new();
+        if let Some(chain) = chain {
+            query = query.chain(chain);
+        }
+        if include_internal {
+            query = query.include("internal_transactions");
+        }
+        self.get_transaction_verbose(tx_hash, Some(&query)).await
+    }
+
     /// Get transactions for an address
     pub async fn get_wallet_transactions(
         &self,
@@ -0,0 +1,488 @@
+//! Client-side value flow summaries for verbose transactions
+//!
+//! Nets native value movements (top-level value plus internal calls) and
+//! ERC-20 `Transfer` events per address. All amounts are `U256`, so token
+//! amounts anywhere in the uint256 range are handled without overflow.
+
+use super::types::{InternalTransaction, TransactionLog, VerboseTransaction};
+use alloy_primitives::U256;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// `keccak256("Transfer(address,address,uint256)")`
+pub const TRANSFER_TOPIC: &str =
+    "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
+/// Kind of internal call, parsed from the trace `type` field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InternalCallType {
+    Call,
+    CallCode,
+    DelegateCall,
+    StaticCall,
+    Create,
+    Create2,
+    /// `SELFDESTRUCT` (reported as `SUICIDE` by older tracers)
+    SelfDestruct,
+    Other(String),
+}
+
+impl InternalCallType {
+    /// Parse a trace type, case-insensitively
+    #[must_use]
+    pub fn parse(s: &str) -> Self {
+        match s.to_ascii_uppercase().as_str() {
+            "CALL" => Self::Call,
+            "CALLCODE" => Self::CallCode,
+            "DELEGATECALL" => Self::DelegateCall,
+            "STATICCALL" => Self::StaticCall,
+            "CREATE" => Self::Create,
+            "CREATE2" => Self::Create2,
+            "SELFDESTRUCT" | "SUICIDE" => Self::SelfDestruct,
+            _ => Self::Other(s.to_string()),
+        }
+    }
+
+    /// Whether a call of this kind can move native value between accounts
+    ///
+    /// `DELEGATECALL` and `STATICCALL` report the caller's value context but
+    /// never transfer it, and `CALLCODE` runs in the caller's own account.
+    #[must_use]
+    pub fn moves_value(&self) -> bool {
+        !matches!(self, Self::DelegateCall | Self::StaticCall | Self::CallCode)
+    }
+}
+
+impl InternalTransaction {
+    /// Parsed call type (`None` when the API omitted it)
+    #[must_use]
+    pub fn call_type(&self) -> Option<InternalCallType> {
+        self.tx_type.as_deref().map(InternalCallType::parse)
+    }
+
+    /// Value in wei (decimal or `0x` hex)
+    #[must_use]
+    pub fn value_wei(&self) -> Option<U256> {
+        self.value.as_deref().and_then(parse_amount)
+    }
+
+    /// Gas limit forwarded to the call
+    #[must_use]
+    pub fn gas_limit(&self) -> Option<u64> {
+        self.gas.as_deref().and_then(parse_u64)
+    }
+
+    /// Gas actually used by the call
+    #[must_use]
+    pub fn gas_used_u64(&self) -> Option<u64> {
+        self.gas_used.as_deref().and_then(parse_u64)
+    }
+}
+
+impl TransactionLog {
+    /// Decode this log as an ERC-20 `Transfer`
+    ///
+    /// ERC-721 transfers share the topic but index the token ID as a third
+    /// topic, so logs with `topic3` set are not treated as ERC-20.
+    #[must_use]
+    pub fn erc20_transfer(&self) -> Option<TokenTransfer> {
+        if !self.is_erc20_transfer_shape() {
+            return None;
+        }
+        Some(TokenTransfer {
+            token: normalize(self.address.as_deref()?),
+            from: topic_address(self.topic1.as_deref()?)?,
+            to: topic_address(self.topic2.as_deref()?)?,
+            amount: parse_amount(self.data.as_deref()?)?,
+            log_index: self.log_index.as_deref().and_then(parse_u64),
+        })
+    }
+}
+
+impl TransactionLog {
+    fn is_erc20_transfer_shape(&self) -> bool {
+        self.topic0
+            .as_deref()
+            .is_some_and(|t| t.eq_ignore_ascii_case(TRANSFER_TOPIC))
+            && self.topic3.as_deref().is_none_or(str::is_empty)
+    }
+}
+
+/// A decoded ERC-20 transfer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TokenTransfer {
+    /// Token contract (lowercase)
+    pub token: String,
+    /// Sender (lowercase)
+    pub from: String,
+    /// Recipient (lowercase)
+    pub to: String,
+    /// Raw amount in token base units
+    pub amount: U256,
+    /// Position of the event in the block
+    pub log_index: Option<u64>,
+}
+
+/// Gross and net movement of one asset for one address
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct AddressFlow {
+    /// Address (lowercase)
+    pub address: String,
+    /// Total sent
+    pub sent: U256,
+    /// Total received
+    pub received: U256,
+}
+
+impl AddressFlow {
+    /// Amount received beyond what was sent, if the address is a net receiver
+    #[must_use]
+    pub fn net_received(&self) -> Option<U256> {
+        (self.received > self.sent).then(|| self.received - self.sent)
+    }
+
+    /// Amount sent beyond what was received, if the address is a net payer
+    #[must_use]
+    pub fn net_sent(&self) -> Option<U256> {
+        (self.sent > self.received).then(|| self.sent - self.received)
+    }
+
+    /// Whether every unit that came in also went out (or nothing moved)
+    #[must_use]
+    pub fn is_balanced(&self) -> bool {
+        self.sent == self.received
+    }
+}
+
+/// Net token movements for one ERC-20
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TokenFlow {
+    /// Token contract (lowercase)
+    pub token: String,
+    /// Per-address flows, sorted by address
+    pub flows: Vec<AddressFlow>,
+}
+
+/// Digest of where value went in a transaction
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ValueFlow {
+    /// Per-address native value flows (top-level and internal), sorted by address
+    pub native: Vec<AddressFlow>,
+    /// Address with the largest net native outflow
+    pub net_payer: Option<String>,
+    /// Addresses with a net native inflow, largest first
+    pub net_receivers: Vec<String>,
+    /// ERC-20 transfers decoded from the logs, in log order
+    pub token_transfers: Vec<TokenTransfer>,
+    /// Per-token net flows derived from `token_transfers`
+    pub tokens: Vec<TokenFlow>,
+    /// Value-carrying entries skipped because an amount or address was missing
+    /// or unparseable
+    pub skipped: usize,
+}
+
+impl ValueFlow {
+    /// Summarize the value movements of a verbose transaction
+    ///
+    /// Native movements are the top-level `value` plus every internal call
+    /// that transfers value (`CALL`, `CREATE*`, `SELFDESTRUCT`). When the
+    /// first internal entry repeats the top-level call it is counted once.
+    /// Reverted transactions move nothing. Gas fees are not included.
+    #[must_use]
+    pub fn summarize(tx: &VerboseTransaction) -> Self {
+        if tx.receipt_status.as_deref() == Some("0") {
+            return Self::default();
+        }
+
+        let mut native = Ledger::default();
+        let mut skipped = 0;
+
+        let top_from = tx.from_address.as_deref();
+        let top_to = tx.to_address.as_deref().or_else(|| tx.created_contract());
+        let top_value = tx.value.as_deref().and_then(parse_amount);
+        if let (Some(from), Some(to), Some(value)) = (top_from, top_to, top_value) {
+            native.record(from, to, value);
+        }
+
+        let internal = tx.internal_transactions.as_deref().unwrap_or_default();
+        let skip_root = internal.first().is_some_and(|root| {
+            root.from.as_deref().map(normalize) == top_from.map(normalize)
+                && root.to.as_deref().map(normalize) == top_to.map(normalize)
+                && root.value_wei() == top_value
+        });
+        for call in internal.iter().skip(usize::from(skip_root)) {
+            if !call.call_type().is_none_or(|t| t.moves_value()) {
+                continue;
+            }
+            match (call.from.as_deref(), call.to.as_deref(), call.value_wei()) {
+                (_, _, Some(value)) if value.is_zero() => {}
+                (Some(from), Some(to), Some(value)) => native.record(from, to, value),
+                (_, _, None) if call.value.is_none() => {}
+                _ => skipped += 1,
+            }
+        }
+
+        let mut token_transfers = Vec::new();
+        for log in tx.logs.as_deref().unwrap_or_default() {
+            if let Some(transfer) = log.erc20_transfer() {
+                token_transfers.push(transfer);
+            } else if log.is_erc20_transfer_shape() {
+                skipped += 1;
+            }
+        }
+
+        let mut token_ledgers: BTreeMap<String, Ledger> = BTreeMap::new();
+        for t in &token_transfers {
+            token_ledgers
+                .entry(t.token.clone())
+                .or_default()
+                .record(&t.from, &t.to, t.amount);
+        }
+
+        let native = native.into_flows();
+        let net_payer = native
+            .iter()
+            .filter_map(|f| f.net_sent().map(|amount| (amount, &f.address)))
+            .max_by_key(|(amount, _)| *amount)
+            .map(|(_, address)| address.clone());
+        let mut receivers: Vec<_> = native
+            .iter()
+            .filter_map(|f| f.net_received().map(|amount| (amount, &f.address)))
+            .collect();
+        receivers.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+
+        Self {
+            net_receivers: receivers.into_iter().map(|(_, a)| a.clone()).collect(),
+            net_payer,
+            native,
+            token_transfers,
+            tokens: token_ledgers
+                .into_iter()
+                .map(|(token, ledger)| TokenFlow {
+                    token,
+                    flows: ledger.into_flows(),
+                })
+                .collect(),
+            skipped,
+        }
+    }
+
+    /// Native flow for an address, if it took part in the transaction
+    #[must_use]
+    pub fn native_flow(&self, address: &str) -> Option<&AddressFlow> {
+        let address = normalize(address);
+        self.native.iter().find(|f| f.address == address)
+    }
+
+    /// Net flow of `token` for `address`, if either took part
+    #[must_use]
+    pub fn token_flow(&self, token: &str, address: &str) -> Option<&AddressFlow> {
+        let (token, address) = (normalize(token), normalize(address));
+        self.tokens
+            .iter()
+            .find(|t| t.token == token)?
+            .flows
+            .iter()
+            .find(|f| f.address == address)
+    }
+}
+
+impl VerboseTransaction {
+    /// Summarize native and ERC-20 value movements; see [`ValueFlow::summarize`]
+    #[must_use]
+    pub fn value_flow(&self) -> ValueFlow {
+        ValueFlow::summarize(self)
+    }
+
+    // Contract creations have no `to_address`; the new contract is the
+    // target of the root CREATE trace.
+    fn created_contract(&self) -> Option<&str> {
+        self.internal_transactions
+            .as_deref()?
+            .first()
+            .filter(|c| {
+                matches!(
+                    c.call_type(),
+                    Some(InternalCallType::Create | InternalCallType::Create2)
+                )
+            })?
+            .to
+            .as_deref()
+    }
+}
+
+#[derive(Default)]
+struct Ledger(BTreeMap<String, AddressFlow>);
+
+impl Ledger {
+    fn record(&mut self, from: &str, to: &str, amount: U256) {
+        if amount.is_zero() {
+            return;
+        }
+        let from = normalize(from);
+        let to = normalize(to);
+        let entry = self.entry(from);
+        entry.sent = entry.sent.saturating_add(amount);
+        let entry = self.entry(to);
+        entry.received = entry.received.saturating_add(amount);
+    }
+
+    fn entry(&mut self, address: String) -> &mut AddressFlow {
+        self.0
+            .entry(address.clone())
+            .or_insert_with(|| AddressFlow {
+                address,
+                ..Default::default()
+            })
+    }
+
+    fn into_flows(self) -> Vec<AddressFlow> {
+        self.0.into_values().collect()
+    }
+}
+
+fn normalize(address: &str) -> String {
+    address.to_ascii_lowercase()
+}
+
+/// Parse a decimal or `0x`-prefixed hex amount; `0x` alone is zero
+fn parse_amount(s: &str) -> Option<U256> {
+    let s = s.trim();
+    match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some("") => Some(U256::ZERO),
+        Some(hex) => U256::from_str_radix(hex, 16).ok(),
+        None => U256::from_str(s).ok(),
+    }
+}
+
+fn parse_u64(s: &str) -> Option<u64> {
+    match s.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => s.parse().ok(),
+    }
+}
+
+/// Extract the address from a 32-byte indexed topic
+fn topic_address(topic: &str) -> Option<String> {
+    let hex = topic.strip_prefix("0x")?;
+    if hex.len() != 64 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    Some(format!("0x{}", hex[24..].to_ascii_lowercase()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NESTED: &str = include_str!("../../tests/fixtures/verbose_nested_internal.json");
+    const SELFDESTRUCT: &str =
+        include_str!("../../tests/fixtures/verbose_selfdestruct_refund.json");
+
+    const USER: &str = "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+    const ROUTER: &str = "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+    const POOL: &str = "0xcccccccccccccccccccccccccccccccccccccccc";
+    const TEMP: &str = "0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee";
+
+    fn eth(tenths: u64) -> U256 {
+        U256::from(tenths) * U256::from(100_000_000_000_000_000u64)
+    }
+
+    #[test]
+    fn test_nested_internal_native_flow() {
+        let tx: VerboseTransaction = serde_json::from_str(NESTED).unwrap();
+        let flow = tx.value_flow();
+
+        // Root CALL duplicates the top-level value and is counted once;
+        // the DELEGATECALL and STATICCALL move nothing.
+        let user = flow.native_flow(USER).unwrap();
+        assert_eq!(user.sent, eth(10));
+        assert_eq!(user.received, eth(3)); // SELFDESTRUCT refund
+        assert_eq!(user.net_sent(), Some(eth(7)));
+
+        assert!(flow.native_flow(ROUTER).unwrap().is_balanced());
+        assert!(flow.native_flow(TEMP).unwrap().is_balanced());
+        assert_eq!(flow.native_flow(POOL).unwrap().net_received(), Some(eth(7)));
+        assert!(flow
+            .native_flow("0xdddddddddddddddddddddddddddddddddddddddd")
+            .is_none());
+
+        assert_eq!(flow.net_payer.as_deref(), Some(USER));
+        assert_eq!(flow.net_receivers, [POOL]);
+    }
+
+    #[test]
+    fn test_nested_internal_token_transfers() {
+        let tx: VerboseTransaction = serde_json::from_str(NESTED).unwrap();
+        let flow = ValueFlow::summarize(&tx);
+
+        // ERC-721 transfer (topic3) and the Sync event are ignored; the
+        // malformed USDC transfer is counted as skipped.
+        assert_eq!(flow.token_transfers.len(), 3);
+        assert_eq!(flow.skipped, 1);
+        assert_eq!(flow.tokens.len(), 2);
+
+        let usdc = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+        assert_eq!(
+            flow.token_flow(usdc, USER).unwrap().net_received(),
+            Some(U256::from(2_000_000_000u64))
+        );
+
+        // Amounts beyond u128 net exactly
+        let big = "0x1111111111111111111111111111111111111111";
+        let two_200 = U256::from(1) << 200;
+        let router = flow.token_flow(big, ROUTER).unwrap();
+        assert_eq!(router.received, two_200);
+        assert_eq!(router.net_received(), Some(U256::from(5)));
+        assert_eq!(
+            flow.token_flow(big, POOL).unwrap().net_sent(),
+            Some(two_200)
+        );
+    }
+
+    #[test]
+    fn test_contract_creation_selfdestruct_refund() {
+        let tx: VerboseTransaction = serde_json::from_str(SELFDESTRUCT).unwrap();
+        assert_eq!(
+            tx.internal_transactions.as_ref().unwrap()[1].call_type(),
+            Some(InternalCallType::SelfDestruct)
+        );
+        let flow = tx.value_flow();
+
+        // The CREATE root (no to_address) is not double counted, and the
+        // full refund leaves everyone balanced.
+        let user = flow.native_flow(USER).unwrap();
+        assert_eq!(user.sent, eth(5));
+        assert_eq!(user.received, eth(5));
+        assert!(flow.native.iter().all(AddressFlow::is_balanced));
+        assert!(flow.net_payer.is_none());
+        assert!(flow.net_receivers.is_empty());
+    }
+
+    #[test]
+    fn test_reverted_transaction_moves_nothing() {
+        let mut tx: VerboseTransaction = serde_json::from_str(NESTED).unwrap();
+        tx.receipt_status = Some("0".to_string());
+        assert_eq!(tx.value_flow(), ValueFlow::default());
+    }
+
+    #[test]
+    fn test_internal_transaction_helpers() {
+        let tx: VerboseTransaction = serde_json::from_str(NESTED).unwrap();
+        let root = &tx.internal_transactions.as_ref().unwrap()[0];
+        assert_eq!(root.call_type(), Some(InternalCallType::Call));
+        assert_eq!(root.value_wei(), Some(eth(10)));
+        assert_eq!(root.gas_limit(), Some(400_000));
+        assert_eq!(root.gas_used_u64(), Some(250_000));
+
+        assert!(!InternalCallType::parse("delegatecall").moves_value());
+        assert!(InternalCallType::parse("create2").moves_value());
+        assert_eq!(
+            InternalCallType::parse("AUTH"),
+            InternalCallType::Other("AUTH".to_string())
+        );
+        assert_eq!(parse_amount("0x"), Some(U256::ZERO));
+        assert_eq!(parse_amount("0xff"), Some(U256::from(255)));
+    }
+}
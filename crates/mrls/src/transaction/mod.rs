@@ -1,7 +1,11 @@
 //! Transaction API - transaction data, decoded calls, internal transactions
 
 mod api;
+mod flow;
 mod types;
 
 pub use api::{TransactionApi, TransactionQuery};
+pub use flow::{
+    AddressFlow, InternalCallType, TokenFlow, TokenTransfer, ValueFlow, TRANSFER_TOPIC,
+};
 pub use types::*;
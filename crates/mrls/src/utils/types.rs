@@ -38,6 +38,40 @@ pub struct EndpointWeight {
     pub weight: Option<i32>,
     /// Rate limit per minute
     pub rate_limit_per_minute: Option<i32>,
+    /// CU counted against the per-second rate limit
+    #[serde(
+        default,
+        rename = "rateLimitCost",
+        deserialize_with = "deserialize_flexible_u32"
+    )]
+    pub rate_limit_cost: Option<u32>,
+    /// CU billed per call
+    #[serde(default, deserialize_with = "deserialize_flexible_u32")]
+    pub price: Option<u32>,
+}
+
+impl EndpointWeight {
+    /// CU cost for rate limiting: `rateLimitCost`, then `weight`, then `price`
+    #[must_use]
+    pub fn cu_cost(&self) -> Option<u32> {
+        self.rate_limit_cost
+            .or_else(|| self.weight.and_then(|w| u32::try_from(w).ok()))
+            .or(self.price)
+    }
+}
+
+/// Accept a CU cost sent as either a number or a numeric string
+fn deserialize_flexible_u32<'de, D>(deserializer: D) -> Result<Option<u32>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(
+        match Option::<serde_json::Value>::deserialize(deserializer)? {
+            Some(serde_json::Value::Number(n)) => n.as_u64().and_then(|n| u32::try_from(n).ok()),
+            Some(serde_json::Value::String(s)) => s.trim().parse().ok(),
+            _ => None,
+        },
+    )
 }
 
 /// Endpoint weights response
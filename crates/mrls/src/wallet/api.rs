@@ -1,12 +1,12 @@
 //! Wallet API client
 
 use super::types::{
-    ActiveChains, NativeBalance, NetWorth, PaginatedResponse, TokenApproval, TokenBalance,
-    TokenProfitability, WalletBalances, WalletHistoryEntry, WalletProfitability, WalletStats,
-    WalletTransaction,
+    ActiveChains, NativeBalance, NetWorth, PaginatedResponse, Profitability, ProfitabilitySummary,
+    TokenApproval, TokenBalance, TokenPnl, TokenPnlRows, WalletBalances, WalletHistoryEntry,
+    WalletProfitability, WalletStats, WalletTransaction,
 };
 use crate::client::Client;
-use crate::error::Result;
+use crate::error::{PlanTier, Result};
 use serde::Serialize;
 
 /// Query parameters for wallet endpoints
@@ -52,6 +52,46 @@ impl WalletQuery {
     }
 }
 
+/// Options for the profitability endpoints
+#[derive(Debug, Clone, Default)]
+pub struct ProfitabilityOptions {
+    /// Look-back window in days; `None` covers the wallet's whole history
+    pub days: Option<u32>,
+}
+
+impl ProfitabilityOptions {
+    /// Options covering the whole history
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only count trades from the last `days` days
+    #[must_use]
+    pub fn days(mut self, days: u32) -> Self {
+        self.days = Some(days);
+        self
+    }
+}
+
+#[derive(Serialize)]
+struct ProfitabilityQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    chain: Option<String>,
+    days: String,
+}
+
+impl ProfitabilityQuery {
+    fn new(chain: Option<&str>, options: &ProfitabilityOptions) -> Self {
+        Self {
+            chain: chain.map(std::string::ToString::to_string),
+            days: options
+                .days
+                .map_or_else(|| "all".to_string(), |days| days.to_string()),
+        }
+    }
+}
+
 /// API for wallet operations
 pub struct WalletApi<'a> {
     client: &'a Client,
@@ -173,18 +213,39 @@ impl<'a> WalletApi<'a> {
         self.client.get(&path).await
     }
 
-    /// Get wallet profitability by token
+    /// Get realized/unrealized PnL, trade count and win rate for a wallet
+    ///
+    /// Requires a Starter plan.
     pub async fn get_profitability(
         &self,
         address: &str,
-        query: Option<&WalletQuery>,
-    ) -> Result<PaginatedResponse<TokenProfitability>> {
+        chain: Option<&str>,
+        options: &ProfitabilityOptions,
+    ) -> Result<Profitability> {
+        let path = format!("/wallets/{address}/profitability/summary");
+        self.client.require_plan(PlanTier::Starter, &path)?;
+        let query = ProfitabilityQuery::new(chain, options);
+        let summary: ProfitabilitySummary = self.client.get_with_query(&path, &query).await?;
+        Ok(summary.into())
+    }
+
+    /// Get PnL per token a wallet has traded
+    ///
+    /// Requires a Starter plan.
+    pub async fn get_profitability_by_token(
+        &self,
+        address: &str,
+        chain: Option<&str>,
+    ) -> Result<Vec<TokenPnl>> {
         let path = format!("/wallets/{address}/profitability");
-        if let Some(q) = query {
-            self.client.get_with_query(&path, q).await
-        } else {
-            self.client.get(&path).await
-        }
+        self.client.require_plan(PlanTier::Starter, &path)?;
+        let query = ProfitabilityQuery::new(chain, &ProfitabilityOptions::new());
+        let rows: TokenPnlRows = self.client.get_with_query(&path, &query).await?;
+        Ok(rows
+            .into_rows()
+            .into_iter()
+            .map(TokenPnl::without_unbacked_pnl)
+            .collect())
     }
 
     /// Get balances for multiple wallets (batch)
@@ -213,3 +274,127 @@ impl<'a> WalletApi<'a> {
             .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::DomainError;
+    use crate::{Config, Error};
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const ADDRESS: &str = "0xd8da6bf26964af9d7eed9e03e53415d37aa96045";
+    const SUMMARY: &str = include_str!("../../tests/fixtures/wallet_profitability_summary.json");
+    const BY_TOKEN: &str = include_str!("../../tests/fixtures/wallet_profitability.json");
+
+    fn client(server: &MockServer) -> Client {
+        Client::with_config(Config::new("test-key").base_url(server.uri())).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_profitability_summary() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(format!("/wallets/{ADDRESS}/profitability/summary")))
+            .and(query_param("chain", "eth"))
+            .and(query_param("days", "30"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(SUMMARY, "application/json"))
+            .mount(&server)
+            .await;
+
+        let pnl = client(&server)
+            .wallet()
+            .get_profitability(ADDRESS, Some("eth"), &ProfitabilityOptions::new().days(30))
+            .await
+            .unwrap();
+        assert_eq!(pnl.realized_pnl_usd, Some(1250.5));
+        assert_eq!(pnl.unrealized_pnl_usd, None);
+        assert_eq!(pnl.total_trades, 40);
+        assert_eq!(pnl.win_rate, Some(0.75));
+    }
+
+    #[tokio::test]
+    async fn test_profitability_defaults_to_all_time() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(format!("/wallets/{ADDRESS}/profitability/summary")))
+            .and(query_param("days", "all"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_raw(r#"{"total_count_of_trades":"0"}"#, "application/json"),
+            )
+            .mount(&server)
+            .await;
+
+        let pnl = client(&server)
+            .wallet()
+            .get_profitability(ADDRESS, None, &ProfitabilityOptions::new())
+            .await
+            .unwrap();
+        assert_eq!(pnl.total_trades, 0);
+        assert_eq!(pnl.realized_pnl_usd, None);
+        assert_eq!(pnl.win_rate, None);
+    }
+
+    #[tokio::test]
+    async fn test_profitability_by_token() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(format!("/wallets/{ADDRESS}/profitability")))
+            .and(query_param("chain", "base"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(BY_TOKEN, "application/json"))
+            .mount(&server)
+            .await;
+
+        let tokens = client(&server)
+            .wallet()
+            .get_profitability_by_token(ADDRESS, Some("base"))
+            .await
+            .unwrap();
+        assert_eq!(tokens.len(), 3);
+
+        let pepe = &tokens[0];
+        assert_eq!(pepe.symbol.as_deref(), Some("PEPE"));
+        assert_eq!(pepe.realized_pnl_usd, Some(812.25));
+        assert_eq!(pepe.realized_pnl_percent, Some(40.6));
+        assert_eq!(pepe.count_of_trades, Some(6));
+
+        // A loss stays negative
+        assert_eq!(tokens[1].realized_pnl_usd, Some(-120.0));
+
+        // No trades behind the reported zero, so no PnL
+        let fresh = &tokens[2];
+        assert_eq!(fresh.count_of_trades, Some(0));
+        assert_eq!(fresh.realized_pnl_usd, None);
+        assert_eq!(fresh.avg_buy_price_usd, None);
+        assert_eq!(fresh.total_usd_invested, Some(50.0));
+    }
+
+    #[tokio::test]
+    async fn test_profitability_plan_gate() {
+        let free = Client::with_config(
+            Config::new("test-key")
+                .base_url("http://127.0.0.1:9")
+                .with_plan(PlanTier::Free),
+        )
+        .unwrap();
+        let err = free
+            .wallet()
+            .get_profitability(ADDRESS, None, &ProfitabilityOptions::new())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Domain(DomainError::PlanRequired {
+                required_plan: PlanTier::Starter,
+                ..
+            })
+        ));
+        let err = free
+            .wallet()
+            .get_profitability_by_token(ADDRESS, None)
+            .await
+            .unwrap_err();
+        assert!(crate::error::is_plan_required(&err));
+    }
+}
@@ -3,5 +3,5 @@
 mod api;
 mod types;
 
-pub use api::{WalletApi, WalletQuery};
+pub use api::{ProfitabilityOptions, WalletApi, WalletQuery};
 pub use types::*;
@@ -250,6 +250,204 @@ pub struct TokenProfitability {
     pub count_of_trades: Option<i64>,
 }
 
+/// Wallet-level profit and loss
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profitability {
+    /// Realized profit minus realized loss, in USD
+    pub realized_pnl_usd: Option<f64>,
+    /// Unrealized PnL in USD, when the API reports it
+    pub unrealized_pnl_usd: Option<f64>,
+    /// Number of trades in the window
+    pub total_trades: u64,
+    /// Share of closed trades that were profitable (0.0-1.0)
+    pub win_rate: Option<f64>,
+}
+
+/// Raw `/profitability/summary` body; Moralis sends snake_case numeric strings
+#[derive(Deserialize)]
+pub(crate) struct ProfitabilitySummary {
+    #[serde(
+        default,
+        alias = "totalRealizedProfitUsd",
+        deserialize_with = "deserialize_flexible_f64"
+    )]
+    total_realized_profit_usd: Option<f64>,
+    #[serde(
+        default,
+        alias = "totalRealizedLossUsd",
+        deserialize_with = "deserialize_flexible_f64"
+    )]
+    total_realized_loss_usd: Option<f64>,
+    #[serde(
+        default,
+        alias = "totalUnrealizedProfitUsd",
+        deserialize_with = "deserialize_flexible_f64"
+    )]
+    total_unrealized_profit_usd: Option<f64>,
+    #[serde(
+        default,
+        alias = "totalCountOfTrades",
+        deserialize_with = "deserialize_flexible_u64"
+    )]
+    total_count_of_trades: Option<u64>,
+    #[serde(
+        default,
+        alias = "totalCountOfProfitableTrades",
+        deserialize_with = "deserialize_flexible_u64"
+    )]
+    total_count_of_profitable_trades: Option<u64>,
+    #[serde(
+        default,
+        alias = "totalCountOfLosingTrades",
+        deserialize_with = "deserialize_flexible_u64"
+    )]
+    total_count_of_losing_trades: Option<u64>,
+}
+
+impl From<ProfitabilitySummary> for Profitability {
+    fn from(raw: ProfitabilitySummary) -> Self {
+        let realized_pnl_usd = match (raw.total_realized_profit_usd, raw.total_realized_loss_usd) {
+            (None, None) => None,
+            (profit, loss) => Some(profit.unwrap_or(0.0) - loss.unwrap_or(0.0).abs()),
+        };
+        let win_rate = match (
+            raw.total_count_of_profitable_trades,
+            raw.total_count_of_losing_trades,
+        ) {
+            (Some(won), Some(lost)) if won + lost > 0 => Some(won as f64 / (won + lost) as f64),
+            _ => None,
+        };
+        Self {
+            realized_pnl_usd,
+            unrealized_pnl_usd: raw.total_unrealized_profit_usd,
+            total_trades: raw.total_count_of_trades.unwrap_or(0),
+            win_rate,
+        }
+    }
+}
+
+/// Profit and loss for one token a wallet traded
+///
+/// PnL fields are `None` when the token has no trade history to price them
+/// from, not zero.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenPnl {
+    /// Token address
+    #[serde(alias = "tokenAddress")]
+    pub token_address: String,
+    /// Token name
+    #[serde(default, alias = "token_name", alias = "tokenName")]
+    pub name: Option<String>,
+    /// Token symbol
+    #[serde(default, alias = "token_symbol", alias = "tokenSymbol")]
+    pub symbol: Option<String>,
+    /// Realized profit or loss in USD
+    #[serde(
+        default,
+        alias = "realized_profit_usd",
+        alias = "realizedProfitUsd",
+        deserialize_with = "deserialize_flexible_f64"
+    )]
+    pub realized_pnl_usd: Option<f64>,
+    /// Realized profit or loss as a percentage of the cost basis
+    #[serde(
+        default,
+        alias = "realized_profit_percentage",
+        alias = "realizedProfitPercentage",
+        deserialize_with = "deserialize_flexible_f64"
+    )]
+    pub realized_pnl_percent: Option<f64>,
+    /// Average buy price in USD
+    #[serde(
+        default,
+        alias = "avgBuyPriceUsd",
+        deserialize_with = "deserialize_flexible_f64"
+    )]
+    pub avg_buy_price_usd: Option<f64>,
+    /// Average sell price in USD
+    #[serde(
+        default,
+        alias = "avgSellPriceUsd",
+        deserialize_with = "deserialize_flexible_f64"
+    )]
+    pub avg_sell_price_usd: Option<f64>,
+    /// Total USD spent buying the token
+    #[serde(
+        default,
+        alias = "totalUsdInvested",
+        deserialize_with = "deserialize_flexible_f64"
+    )]
+    pub total_usd_invested: Option<f64>,
+    /// Number of trades
+    #[serde(
+        default,
+        alias = "countOfTrades",
+        deserialize_with = "deserialize_flexible_u64"
+    )]
+    pub count_of_trades: Option<u64>,
+}
+
+impl TokenPnl {
+    /// Clear the PnL fields when there is no trade history behind them
+    ///
+    /// Moralis reports `"0"` for tokens it has no trades for, which reads as
+    /// break-even.
+    pub(crate) fn without_unbacked_pnl(mut self) -> Self {
+        if self.count_of_trades.unwrap_or(0) == 0 {
+            self.realized_pnl_usd = None;
+            self.realized_pnl_percent = None;
+            self.avg_buy_price_usd = None;
+            self.avg_sell_price_usd = None;
+        }
+        self
+    }
+}
+
+/// Per-token profitability comes wrapped in `result`
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum TokenPnlRows {
+    List(Vec<TokenPnl>),
+    Page { result: Vec<TokenPnl> },
+}
+
+impl TokenPnlRows {
+    pub(crate) fn into_rows(self) -> Vec<TokenPnl> {
+        match self {
+            TokenPnlRows::List(rows) | TokenPnlRows::Page { result: rows } => rows,
+        }
+    }
+}
+
+/// Accept a number sent as a number, a numeric string, or an empty string
+fn deserialize_flexible_f64<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(
+        match Option::<serde_json::Value>::deserialize(deserializer)? {
+            Some(serde_json::Value::Number(n)) => n.as_f64(),
+            Some(serde_json::Value::String(s)) => s.trim().parse().ok(),
+            _ => None,
+        }
+        .filter(|n: &f64| n.is_finite()),
+    )
+}
+
+/// Accept a count sent as either a number or a numeric string
+fn deserialize_flexible_u64<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(
+        match Option::<serde_json::Value>::deserialize(deserializer)? {
+            Some(serde_json::Value::Number(n)) => n.as_u64(),
+            Some(serde_json::Value::String(s)) => s.trim().parse().ok(),
+            _ => None,
+        },
+    )
+}
+
 /// Request for multi-wallet balances
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GetWalletBalancesRequest {
@@ -2,6 +2,7 @@
 //!
 //! Run with: MORALIS_API_KEY=your_key cargo test -p mrls --test live_api -- --ignored
 
+use mrls::discovery::{DiscoveryQuery, TimeFrame};
 use mrls::Client;
 
 /// Vitalik's address for testing
@@ -163,7 +164,7 @@ async fn test_get_token_pairs() {
     for pair in pairs.iter().take(5) {
         println!(
             "  {:?} on {:?} - ${:?} (liq: ${:?})",
-            pair.pair_label, pair.exchange_name, pair.usd_price, pair.liquidity_usd
+            pair.pair_label, pair.exchange, pair.usd_price, pair.liquidity_usd
         );
     }
 }
@@ -413,18 +414,18 @@ async fn test_get_top_gainers() {
     let client = Client::from_env().expect("MORALIS_API_KEY must be set");
     let tokens = client
         .discovery()
-        .get_top_gainers(None)
+        .get_top_gainers(&DiscoveryQuery::new().time_frame(TimeFrame::OneDay))
         .await
         .expect("Failed to get top gainers");
 
-    println!("Top gainers: {} found", tokens.result.len());
-    for token in tokens.result.iter().take(5) {
+    println!("Top gainers: {} found", tokens.len());
+    for token in tokens.iter().take(5) {
         println!(
             "  {} ({}) - ${:?} (+{:?}%)",
             token.token_name.as_deref().unwrap_or("?"),
             token.token_symbol.as_deref().unwrap_or("?"),
             token.price_usd,
-            token.price_change_24h
+            token.price_change(TimeFrame::OneDay)
         );
     }
 }
@@ -167,7 +167,7 @@ impl Client {
     }
 
     /// Make a GET request to the API with query parameters
-    async fn get_with_params<T: DeserializeOwned>(
+    pub(crate) async fn get_with_params<T: DeserializeOwned>(
         &self,
         url: &str,
         params: &[(&str, String)],
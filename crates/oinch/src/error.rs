@@ -32,6 +32,10 @@ pub enum DomainError {
     /// Missing API key
     #[error("API key is required for 1inch API")]
     MissingApiKey,
+
+    /// Limit order snapshot could not be loaded or saved
+    #[error("Snapshot store error: {0}")]
+    SnapshotStore(String),
 }
 
 /// Error type for 1inch API operations
@@ -74,6 +78,11 @@ pub fn missing_api_key() -> Error {
     ApiError::domain(DomainError::MissingApiKey)
 }
 
+/// Create a snapshot store error
+pub fn snapshot_store(message: impl Into<String>) -> Error {
+    ApiError::domain(DomainError::SnapshotStore(message.into()))
+}
+
 /// Create from HTTP response status and body
 ///
 /// This function includes special handling for 1inch-specific error messages
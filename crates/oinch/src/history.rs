@@ -0,0 +1,609 @@
+//! 1inch History API v2.0
+//!
+//! Decoded wallet history (swaps, transfers, approvals) with USD valuations,
+//! from `/history/v2.0/history/{address}/events`. Each event's token legs
+//! are joined with the response's token metadata so amounts can be read
+//! without a separate token lookup.
+
+use crate::client::Client;
+use crate::error::Result;
+use crate::types::{Chain, TokenInfo};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use yldfi_common::{with_retry, RetryConfig};
+
+/// API version for the history endpoint
+const HISTORY_API_VERSION: &str = "v2.0";
+
+/// Page size used by [`Client::collect_window`]
+pub const HISTORY_PAGE_SIZE: u32 = 100;
+
+/// Query parameters for [`Client::get_history`]
+///
+/// Timestamps are Unix milliseconds. The API's `toTimestampMs` is
+/// inclusive, so `to_ts` is sent as `to_ts - 1`.
+#[derive(Debug, Clone, Default)]
+pub struct HistoryQuery {
+    /// Restrict to one chain (all chains if unset)
+    pub chain_id: Option<Chain>,
+    /// Restrict to events touching this token
+    pub token_address: Option<String>,
+    /// Inclusive lower bound (Unix ms)
+    pub from_ts: Option<u64>,
+    /// Exclusive upper bound (Unix ms)
+    pub to_ts: Option<u64>,
+    /// Maximum number of events to return
+    pub limit: Option<u32>,
+}
+
+impl HistoryQuery {
+    /// Create an empty query (all chains, all tokens, API default limit)
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restrict to one chain
+    #[must_use]
+    pub fn with_chain(mut self, chain: Chain) -> Self {
+        self.chain_id = Some(chain);
+        self
+    }
+
+    /// Restrict to events touching a token
+    #[must_use]
+    pub fn with_token(mut self, token_address: impl Into<String>) -> Self {
+        self.token_address = Some(token_address.into());
+        self
+    }
+
+    /// Restrict to `[from_ts, to_ts)` in Unix milliseconds
+    #[must_use]
+    pub fn with_window(mut self, from_ts: u64, to_ts: u64) -> Self {
+        self.from_ts = Some(from_ts);
+        self.to_ts = Some(to_ts);
+        self
+    }
+
+    /// Set the page size
+    #[must_use]
+    pub fn with_limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Convert to query parameters
+    #[must_use]
+    pub fn to_query_params(&self) -> Vec<(&'static str, String)> {
+        let mut params = Vec::new();
+        if let Some(chain) = self.chain_id {
+            params.push(("chainId", chain.chain_id().to_string()));
+        }
+        if let Some(ref token) = self.token_address {
+            params.push(("tokenAddress", token.clone()));
+        }
+        if let Some(from) = self.from_ts {
+            params.push(("fromTimestampMs", from.to_string()));
+        }
+        if let Some(to) = self.to_ts {
+            params.push(("toTimestampMs", to.saturating_sub(1).to_string()));
+        }
+        if let Some(limit) = self.limit {
+            params.push(("limit", limit.to_string()));
+        }
+        params
+    }
+}
+
+/// One token movement within an event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenAction {
+    /// Token contract address
+    pub address: String,
+    /// Token standard (e.g., "ERC20", "Native")
+    #[serde(default)]
+    pub standard: Option<String>,
+    /// Sender
+    #[serde(default)]
+    pub from_address: Option<String>,
+    /// Recipient
+    #[serde(default)]
+    pub to_address: Option<String>,
+    /// Amount in the token's smallest unit, as a decimal string
+    pub amount: String,
+    /// Direction relative to the queried wallet ("In" or "Out")
+    #[serde(default)]
+    pub direction: Option<String>,
+    /// USD price per whole token at the time of the event
+    #[serde(default)]
+    pub price_to_usd: Option<f64>,
+    /// Token metadata, joined from the response's token list
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<TokenInfo>,
+}
+
+impl TokenAction {
+    /// Token symbol, if metadata was available
+    #[must_use]
+    pub fn symbol(&self) -> Option<&str> {
+        self.token.as_ref().map(|t| t.symbol.as_str())
+    }
+
+    /// Amount in whole tokens (needs token metadata for decimals)
+    #[must_use]
+    pub fn amount_decimal(&self) -> Option<f64> {
+        let decimals = self.token.as_ref()?.decimals;
+        let raw: f64 = self.amount.parse().ok()?;
+        Some(raw / 10f64.powi(i32::from(decimals)))
+    }
+
+    /// USD value of the movement (needs token metadata and a price)
+    #[must_use]
+    pub fn value_usd(&self) -> Option<f64> {
+        Some(self.amount_decimal()? * self.price_to_usd?)
+    }
+}
+
+/// Transaction-level details shared by every event kind
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventDetails {
+    /// Transaction hash
+    pub tx_hash: String,
+    /// Chain ID
+    pub chain_id: u64,
+    /// Block number
+    #[serde(default)]
+    pub block_number: Option<u64>,
+    /// Block time (Unix seconds)
+    #[serde(default)]
+    pub block_time_sec: Option<u64>,
+    /// Transaction status (e.g., "completed", "failed")
+    #[serde(default)]
+    pub status: Option<String>,
+    /// Transaction sender
+    #[serde(default)]
+    pub from_address: Option<String>,
+    /// Transaction target
+    #[serde(default)]
+    pub to_address: Option<String>,
+    /// Gas fee paid, in wei
+    #[serde(default)]
+    pub fee_in_wei: Option<String>,
+    /// Token movements in the transaction
+    #[serde(default)]
+    pub token_actions: Vec<TokenAction>,
+}
+
+/// Decoded event, tagged by the API's `type` field
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum HistoryEventKind {
+    /// DEX swap
+    #[serde(alias = "SwapExactInput", alias = "SwapExactOutput")]
+    Swap(EventDetails),
+    /// Token or native transfer
+    Transfer(EventDetails),
+    /// Token approval
+    Approve(EventDetails),
+    /// Batched call
+    Multicall(EventDetails),
+    /// Any event type this crate does not model
+    #[serde(other)]
+    Unknown,
+}
+
+impl HistoryEventKind {
+    /// Event details, unless the event type is unknown
+    #[must_use]
+    pub fn details(&self) -> Option<&EventDetails> {
+        match self {
+            Self::Swap(d) | Self::Transfer(d) | Self::Approve(d) | Self::Multicall(d) => Some(d),
+            Self::Unknown => None,
+        }
+    }
+
+    fn details_mut(&mut self) -> Option<&mut EventDetails> {
+        match self {
+            Self::Swap(d) | Self::Transfer(d) | Self::Approve(d) | Self::Multicall(d) => Some(d),
+            Self::Unknown => None,
+        }
+    }
+}
+
+/// A wallet history event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEvent {
+    /// Event ID
+    #[serde(default)]
+    pub id: Option<String>,
+    /// Event time (Unix milliseconds)
+    pub time_ms: u64,
+    /// Queried wallet address
+    #[serde(default)]
+    pub address: Option<String>,
+    /// Direction relative to the wallet ("in", "out", "self")
+    #[serde(default)]
+    pub direction: Option<String>,
+    /// 1inch's confidence in the decoding (e.g., "Reliable")
+    #[serde(default)]
+    pub rating: Option<String>,
+    /// Decoded event
+    pub details: HistoryEventKind,
+}
+
+/// Response from the history events endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryResponse {
+    /// Events, oldest first
+    #[serde(default)]
+    pub items: Vec<HistoryEvent>,
+    /// Token metadata keyed by address
+    #[serde(default, alias = "tokenMetadata")]
+    pub tokens: HashMap<String, TokenInfo>,
+}
+
+impl HistoryResponse {
+    /// Attach token metadata to every token leg (address match is
+    /// case-insensitive) and return the events
+    #[must_use]
+    pub fn into_joined_events(self) -> Vec<HistoryEvent> {
+        let tokens: HashMap<String, TokenInfo> = self
+            .tokens
+            .into_iter()
+            .map(|(address, info)| (address.to_lowercase(), info))
+            .collect();
+
+        let mut items = self.items;
+        for event in &mut items {
+            let Some(details) = event.details.details_mut() else {
+                continue;
+            };
+            for action in &mut details.token_actions {
+                if action.token.is_none() {
+                    action.token = tokens.get(&action.address.to_lowercase()).cloned();
+                }
+            }
+        }
+        items
+    }
+
+    /// Query to fetch the page after this one, or `None` if this was the last
+    ///
+    /// The API continues from the last event's timestamp via
+    /// `fromTimestampMs`. Events sharing that millisecond are returned again
+    /// and must be de-duplicated by the caller.
+    #[must_use]
+    pub fn next_query(&self, query: &HistoryQuery) -> Option<HistoryQuery> {
+        let limit = query.limit? as usize;
+        if self.items.len() < limit {
+            return None;
+        }
+        let last = self.items.iter().map(|e| e.time_ms).max()?;
+        if query.from_ts.is_some_and(|from| last <= from) {
+            // A full page within one millisecond: no way to advance
+            return None;
+        }
+        Some(HistoryQuery {
+            from_ts: Some(last),
+            ..query.clone()
+        })
+    }
+}
+
+fn event_key(event: &HistoryEvent) -> (Option<String>, Option<String>, u64) {
+    (
+        event.id.clone(),
+        event.details.details().map(|d| d.tx_hash.clone()),
+        event.time_ms,
+    )
+}
+
+impl Client {
+    /// Build the URL for a wallet's history events
+    fn history_url(&self, address: &str) -> String {
+        format!(
+            "{}/history/{}/history/{}/events",
+            self.config().base_url,
+            HISTORY_API_VERSION,
+            address
+        )
+    }
+
+    /// Fetch one page of decoded wallet history
+    ///
+    /// Token legs carry the matching token metadata (see
+    /// [`TokenAction::token`]).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use oinch::{Chain, Client, HistoryQuery};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), oinch::Error> {
+    ///     let client = Client::new("your-api-key")?;
+    ///     let query = HistoryQuery::new().with_chain(Chain::Ethereum).with_limit(50);
+    ///     let page = client.get_history("0xYourWalletAddress", &query).await?;
+    ///
+    ///     for event in page.into_joined_events() {
+    ///         println!("{} {:?}", event.time_ms, event.details.details().map(|d| &d.tx_hash));
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_history(
+        &self,
+        address: &str,
+        query: &HistoryQuery,
+    ) -> Result<HistoryResponse> {
+        let url = self.history_url(address);
+        self.get_with_params(&url, &query.to_query_params()).await
+    }
+
+    /// Collect up to `max` events in a time window, following pagination
+    ///
+    /// `range` is in Unix milliseconds, end exclusive. Each page is retried
+    /// on transient errors (rate limits, 5xx). Pages are sorted by time
+    /// before taking events, so with more than `max` events the oldest are
+    /// kept whatever order the API returns. Events are returned oldest
+    /// first with token metadata joined.
+    pub async fn collect_window(
+        &self,
+        address: &str,
+        range: Range<u64>,
+        max: usize,
+    ) -> Result<Vec<HistoryEvent>> {
+        let retry = RetryConfig::default();
+        let mut query = HistoryQuery::new()
+            .with_window(range.start, range.end)
+            .with_limit(HISTORY_PAGE_SIZE);
+        let mut events: Vec<HistoryEvent> = Vec::new();
+        let mut seen = HashSet::new();
+
+        while events.len() < max {
+            let page = with_retry(&retry, || self.get_history(address, &query))
+                .await
+                .map_err(|e| e.into_inner())?;
+            let next = page.next_query(&query);
+
+            let mut page = page.into_joined_events();
+            page.sort_by_key(|e| e.time_ms);
+            for event in page {
+                if events.len() >= max {
+                    break;
+                }
+                if seen.insert(event_key(&event)) {
+                    events.push(event);
+                }
+            }
+
+            match next {
+                Some(next) => query = next,
+                None => break,
+            }
+        }
+
+        events.sort_by_key(|e| e.time_ms);
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token_action(address: &str, amount: &str, price: f64) -> serde_json::Value {
+        serde_json::json!({
+            "address": address,
+            "standard": "ERC20",
+            "fromAddress": "0xwallet",
+            "toAddress": "0xpool",
+            "amount": amount,
+            "direction": "Out",
+            "priceToUsd": price
+        })
+    }
+
+    fn event(kind: &str, time_ms: u64, actions: Vec<serde_json::Value>) -> serde_json::Value {
+        serde_json::json!({
+            "id": format!("{kind}-{time_ms}"),
+            "timeMs": time_ms,
+            "address": "0xwallet",
+            "direction": "out",
+            "rating": "Reliable",
+            "details": {
+                "type": kind,
+                "txHash": format!("0xtx{time_ms}"),
+                "chainId": 1,
+                "blockNumber": 19_000_000,
+                "blockTimeSec": time_ms / 1000,
+                "status": "completed",
+                "tokenActions": actions
+            }
+        })
+    }
+
+    fn tokens() -> serde_json::Value {
+        serde_json::json!({
+            "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48": {
+                "address": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+                "symbol": "USDC",
+                "name": "USD Coin",
+                "decimals": 6
+            },
+            "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2": {
+                "address": "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+                "symbol": "WETH",
+                "name": "Wrapped Ether",
+                "decimals": 18
+            }
+        })
+    }
+
+    #[test]
+    fn test_swap_event_joins_token_metadata() {
+        let response: HistoryResponse = serde_json::from_value(serde_json::json!({
+            "items": [event("SwapExactInput", 1_700_000_000_000, vec![
+                token_action("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48", "2500000000", 1.0),
+                token_action("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2", "1000000000000000000", 2500.0),
+            ])],
+            "tokens": tokens()
+        }))
+        .unwrap();
+
+        let events = response.into_joined_events();
+        let HistoryEventKind::Swap(details) = &events[0].details else {
+            panic!("expected swap, got {:?}", events[0].details);
+        };
+        assert_eq!(details.tx_hash, "0xtx1700000000000");
+        assert_eq!(details.token_actions[0].symbol(), Some("USDC"));
+        assert_eq!(details.token_actions[0].amount_decimal(), Some(2500.0));
+        assert_eq!(details.token_actions[1].symbol(), Some("WETH"));
+        assert_eq!(details.token_actions[1].value_usd(), Some(2500.0));
+    }
+
+    #[test]
+    fn test_transfer_event() {
+        let response: HistoryResponse = serde_json::from_value(serde_json::json!({
+            "items": [event("Transfer", 1, vec![
+                token_action("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48", "1000000", 1.0),
+            ])],
+            "tokens": tokens()
+        }))
+        .unwrap();
+        let events = response.into_joined_events();
+        assert!(matches!(events[0].details, HistoryEventKind::Transfer(_)));
+        let action = &events[0].details.details().unwrap().token_actions[0];
+        assert_eq!(action.value_usd(), Some(1.0));
+    }
+
+    #[test]
+    fn test_approve_event_without_metadata() {
+        let response: HistoryResponse = serde_json::from_value(serde_json::json!({
+            "items": [event("Approve", 1, vec![
+                token_action("0xdead", "115792089237316195423570985008687907853269984665640564039457584007913129639935", 0.0),
+            ])]
+        }))
+        .unwrap();
+        let events = response.into_joined_events();
+        assert!(matches!(events[0].details, HistoryEventKind::Approve(_)));
+        let action = &events[0].details.details().unwrap().token_actions[0];
+        assert!(action.token.is_none());
+        assert_eq!(action.amount_decimal(), None);
+    }
+
+    #[test]
+    fn test_multicall_event() {
+        let response: HistoryResponse = serde_json::from_value(serde_json::json!({
+            "items": [event("Multicall", 1, vec![])]
+        }))
+        .unwrap();
+        assert!(matches!(
+            response.items[0].details,
+            HistoryEventKind::Multicall(_)
+        ));
+    }
+
+    #[test]
+    fn test_unknown_event() {
+        let response: HistoryResponse = serde_json::from_value(serde_json::json!({
+            "items": [event("LimitOrderFill", 1, vec![])]
+        }))
+        .unwrap();
+        let events = response.into_joined_events();
+        assert!(matches!(events[0].details, HistoryEventKind::Unknown));
+        assert!(events[0].details.details().is_none());
+    }
+
+    #[test]
+    fn test_query_params_and_pagination() {
+        let query = HistoryQuery::new()
+            .with_chain(Chain::Ethereum)
+            .with_token("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48")
+            .with_window(1_000, 5_000)
+            .with_limit(2);
+        assert_eq!(
+            query.to_query_params(),
+            vec![
+                ("chainId", "1".to_string()),
+                (
+                    "tokenAddress",
+                    "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".to_string()
+                ),
+                ("fromTimestampMs", "1000".to_string()),
+                ("toTimestampMs", "4999".to_string()),
+                ("limit", "2".to_string()),
+            ]
+        );
+
+        let full: HistoryResponse = serde_json::from_value(serde_json::json!({
+            "items": [event("Transfer", 1_500, vec![]), event("Transfer", 2_000, vec![])]
+        }))
+        .unwrap();
+        let next = full.next_query(&query).unwrap();
+        assert_eq!(next.from_ts, Some(2_000));
+        assert_eq!(next.to_ts, Some(5_000));
+        assert_eq!(next.chain_id, Some(Chain::Ethereum));
+
+        // Short page ends pagination
+        let short: HistoryResponse = serde_json::from_value(serde_json::json!({
+            "items": [event("Transfer", 3_000, vec![])]
+        }))
+        .unwrap();
+        assert!(short.next_query(&next).is_none());
+
+        // A full page that cannot advance also ends pagination
+        assert!(full
+            .next_query(&HistoryQuery {
+                from_ts: Some(2_000),
+                ..query
+            })
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_collect_window_sorts_pages_and_excludes_end() {
+        use crate::Config;
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        // Newest first, one event more than `max`
+        Mock::given(method("GET"))
+            .and(path("/history/v2.0/history/0xwallet/events"))
+            .and(query_param("fromTimestampMs", "1000"))
+            .and(query_param("toTimestampMs", "4999"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "items": [
+                    event("Transfer", 3_000, vec![]),
+                    event("Transfer", 2_000, vec![]),
+                    event("Transfer", 1_000, vec![]),
+                ]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let client =
+            Client::with_config(Config::new("test-api-key").with_base_url(server.uri())).unwrap();
+
+        let events = client
+            .collect_window("0xwallet", 1_000..5_000, 2)
+            .await
+            .unwrap();
+        let times: Vec<_> = events.iter().map(|e| e.time_ms).collect();
+        assert_eq!(times, [1_000, 2_000]);
+    }
+
+    #[test]
+    fn test_history_url() {
+        let client = Client::new("test-api-key").unwrap();
+        assert_eq!(
+            client.history_url("0xabc"),
+            "https://api.1inch.dev/history/v2.0/history/0xabc/events"
+        );
+    }
+}
@@ -149,10 +149,21 @@
 
 pub mod client;
 pub mod error;
+pub mod history;
+pub mod orderbook;
 pub mod types;
 
 pub use client::{Client, Config, DEFAULT_BASE_URL};
 pub use error::{Error, Result};
+pub use history::{
+    EventDetails, HistoryEvent, HistoryEventKind, HistoryQuery, HistoryResponse, TokenAction,
+    HISTORY_PAGE_SIZE,
+};
+pub use orderbook::{
+    LimitOrder, LimitOrderData, LimitOrderQuery, LimitOrderWatcher, MemorySnapshotStore,
+    OrderEvent, OrderSnapshot, OrderStatus, SnapshotStore, TrackedOrder, WatcherOptions,
+    ORDERBOOK_PAGE_SIZE,
+};
 pub use types::{
     AllowanceResponse, ApiErrorResponse, ApprovalTransaction, Chain, LiquiditySource,
     LiquiditySourcesResponse, ParseChainError, ProtocolInfo, QuoteRequest, QuoteResponse,
@@ -0,0 +1,1175 @@
+//! 1inch Orderbook API v4.0 and a watcher for a maker's limit orders
+//!
+//! [`LimitOrderWatcher`] polls a maker's active orders and turns changes in
+//! their remaining amounts and statuses into [`OrderEvent`]s. The last
+//! snapshot is kept in a [`SnapshotStore`] so a restarted watcher picks up
+//! where it left off instead of re-announcing every order.
+
+use crate::client::Client;
+use crate::error::Result;
+use crate::types::Chain;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use yldfi_common::{with_retry, RetryConfig};
+
+/// API version for the orderbook endpoints
+const ORDERBOOK_API_VERSION: &str = "v4.0";
+
+/// Largest page requested from the orderbook
+pub const ORDERBOOK_PAGE_SIZE: u32 = 100;
+
+/// Bit offset of the expiration timestamp in `makerTraits`
+const EXPIRATION_OFFSET: u32 = 80;
+
+/// Width of the expiration timestamp in `makerTraits`
+const EXPIRATION_BITS: u32 = 40;
+
+/// Orderbook status of a limit order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum OrderStatus {
+    /// Fillable
+    Valid,
+    /// Not fillable right now (e.g., maker balance or allowance too low)
+    TemporarilyInvalid,
+    /// Filled, cancelled or expired
+    Invalid,
+}
+
+impl OrderStatus {
+    /// Numeric code used by the `statuses` query parameter
+    #[must_use]
+    pub const fn code(self) -> u8 {
+        match self {
+            Self::Valid => 1,
+            Self::TemporarilyInvalid => 2,
+            Self::Invalid => 3,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for OrderStatus {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let code = match &value {
+            serde_json::Value::Number(n) => n.as_u64(),
+            serde_json::Value::String(s) => match s.to_ascii_lowercase().as_str() {
+                "valid" => Some(1),
+                "temporarilyinvalid" | "temporary_invalid" => Some(2),
+                "invalid" => Some(3),
+                other => other.parse().ok(),
+            },
+            _ => None,
+        };
+        match code {
+            Some(1) => Ok(Self::Valid),
+            Some(2) => Ok(Self::TemporarilyInvalid),
+            Some(3) => Ok(Self::Invalid),
+            _ => Err(serde::de::Error::custom(format!(
+                "unknown order status: {value}"
+            ))),
+        }
+    }
+}
+
+/// Signed order fields
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LimitOrderData {
+    /// Token the maker sells
+    pub maker_asset: String,
+    /// Token the maker buys
+    pub taker_asset: String,
+    /// Maker address
+    pub maker: String,
+    /// Receiver of the taker asset (zero address = maker)
+    #[serde(default)]
+    pub receiver: Option<String>,
+    /// Amount of the maker asset, in its smallest unit
+    pub making_amount: String,
+    /// Amount of the taker asset, in its smallest unit
+    pub taking_amount: String,
+    /// Order salt
+    #[serde(default)]
+    pub salt: Option<String>,
+    /// Packed maker traits (flags, nonce, expiration) as a hex uint256
+    #[serde(default)]
+    pub maker_traits: Option<String>,
+    /// Order extension
+    #[serde(default)]
+    pub extension: Option<String>,
+}
+
+/// A limit order as returned by the orderbook
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LimitOrder {
+    /// Order hash
+    pub order_hash: String,
+    /// Maker signature
+    #[serde(default)]
+    pub signature: Option<String>,
+    /// Creation time (ISO 8601)
+    #[serde(default)]
+    pub create_date_time: Option<String>,
+    /// Maker asset amount still unfilled
+    pub remaining_maker_amount: String,
+    /// Maker's balance of the maker asset
+    #[serde(default)]
+    pub maker_balance: Option<String>,
+    /// Maker's allowance of the maker asset
+    #[serde(default)]
+    pub maker_allowance: Option<String>,
+    /// Signed order
+    pub data: LimitOrderData,
+    /// Maker rate
+    #[serde(default)]
+    pub maker_rate: Option<String>,
+    /// Taker rate
+    #[serde(default)]
+    pub taker_rate: Option<String>,
+    /// Why the order is not fillable, if it isn't
+    #[serde(default)]
+    pub order_invalid_reason: Option<String>,
+    /// Status, when the endpoint reports one
+    #[serde(default)]
+    pub status: Option<OrderStatus>,
+}
+
+impl LimitOrder {
+    /// Status as reported, or inferred from `orderInvalidReason`
+    #[must_use]
+    pub fn effective_status(&self) -> OrderStatus {
+        self.status.unwrap_or(match self.order_invalid_reason {
+            Some(_) => OrderStatus::Invalid,
+            None => OrderStatus::Valid,
+        })
+    }
+
+    /// Expiration (Unix seconds) from `makerTraits`, `None` if the order
+    /// never expires
+    #[must_use]
+    pub fn expiration(&self) -> Option<u64> {
+        expiration_from_traits(self.data.maker_traits.as_deref()?)
+    }
+}
+
+/// Decode the 40-bit expiration at bits 80..120 of a hex `makerTraits`
+fn expiration_from_traits(traits: &str) -> Option<u64> {
+    let hex = traits.trim_start_matches("0x");
+    if !hex.is_ascii() {
+        return None;
+    }
+    // Bits 0..120 are the last 30 hex digits
+    let low = &hex[hex.len().saturating_sub(30)..];
+    let low = u128::from_str_radix(if low.is_empty() { "0" } else { low }, 16).ok()?;
+    let expiration = (low >> EXPIRATION_OFFSET) & ((1u128 << EXPIRATION_BITS) - 1);
+    (expiration != 0).then_some(expiration as u64)
+}
+
+/// Query parameters for [`Client::get_limit_orders_by_maker`]
+#[derive(Debug, Clone)]
+pub struct LimitOrderQuery {
+    /// 1-based page number
+    pub page: u32,
+    /// Page size
+    pub limit: u32,
+    /// Statuses to include
+    pub statuses: Vec<OrderStatus>,
+}
+
+impl Default for LimitOrderQuery {
+    fn default() -> Self {
+        Self {
+            page: 1,
+            limit: ORDERBOOK_PAGE_SIZE,
+            statuses: vec![OrderStatus::Valid, OrderStatus::TemporarilyInvalid],
+        }
+    }
+}
+
+impl LimitOrderQuery {
+    /// Active orders (valid or temporarily invalid), first page
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the page number (1-based)
+    #[must_use]
+    pub fn with_page(mut self, page: u32) -> Self {
+        self.page = page;
+        self
+    }
+
+    /// Set the page size
+    #[must_use]
+    pub fn with_limit(mut self, limit: u32) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Set the statuses to include
+    #[must_use]
+    pub fn with_statuses(mut self, statuses: Vec<OrderStatus>) -> Self {
+        self.statuses = statuses;
+        self
+    }
+
+    /// Convert to query parameters
+    #[must_use]
+    pub fn to_query_params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![
+            ("page", self.page.to_string()),
+            ("limit", self.limit.to_string()),
+        ];
+        for status in &self.statuses {
+            params.push(("statuses", status.code().to_string()));
+        }
+        params
+    }
+}
+
+impl Client {
+    /// Build the URL for an orderbook endpoint
+    fn orderbook_url(&self, chain: Chain, endpoint: &str) -> String {
+        format!(
+            "{}/orderbook/{}/{}/{}",
+            self.config().base_url,
+            ORDERBOOK_API_VERSION,
+            chain.chain_id(),
+            endpoint
+        )
+    }
+
+    /// Fetch one page of a maker's limit orders
+    pub async fn get_limit_orders_by_maker(
+        &self,
+        chain: Chain,
+        maker: &str,
+        query: &LimitOrderQuery,
+    ) -> Result<Vec<LimitOrder>> {
+        let url = self.orderbook_url(chain, &format!("address/{maker}"));
+        self.get_with_params(&url, &query.to_query_params()).await
+    }
+
+    /// Look up a single limit order by hash, including filled, cancelled
+    /// and expired ones
+    pub async fn get_limit_order(&self, chain: Chain, order_hash: &str) -> Result<LimitOrder> {
+        let url = self.orderbook_url(chain, &format!("order/{order_hash}"));
+        self.get_with_params(&url, &[]).await
+    }
+}
+
+/// Change to one of the watched maker's orders
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrderEvent {
+    /// An active order not in the previous snapshot
+    NewOrderDetected {
+        /// The order as listed
+        order: Box<LimitOrder>,
+    },
+    /// Part of the order was filled and the rest is still open
+    OrderPartiallyFilled {
+        /// Order hash
+        order_hash: String,
+        /// Maker amount filled since the previous snapshot
+        delta: u128,
+        /// Maker amount still unfilled
+        remaining: u128,
+    },
+    /// Nothing is left to fill
+    OrderFilled {
+        /// Order hash
+        order_hash: String,
+        /// Maker amount filled since the previous snapshot, if known
+        delta: Option<u128>,
+    },
+    /// The order was cancelled or otherwise invalidated before filling
+    OrderCancelled {
+        /// Order hash
+        order_hash: String,
+        /// `orderInvalidReason`, if reported
+        reason: Option<String>,
+    },
+    /// The order passed its expiration unfilled
+    OrderExpired {
+        /// Order hash
+        order_hash: String,
+    },
+}
+
+impl OrderEvent {
+    /// Hash of the order the event is about
+    #[must_use]
+    pub fn order_hash(&self) -> &str {
+        match self {
+            Self::NewOrderDetected { order } => &order.order_hash,
+            Self::OrderPartiallyFilled { order_hash, .. }
+            | Self::OrderFilled { order_hash, .. }
+            | Self::OrderCancelled { order_hash, .. }
+            | Self::OrderExpired { order_hash } => order_hash,
+        }
+    }
+}
+
+/// What the watcher remembers about an open order
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrackedOrder {
+    /// Maker amount still unfilled, as a decimal string
+    pub remaining: String,
+    /// Expiration (Unix seconds), if the order has one
+    pub expiration: Option<u64>,
+}
+
+impl TrackedOrder {
+    fn from_order(order: &LimitOrder) -> Self {
+        Self {
+            remaining: order.remaining_maker_amount.clone(),
+            expiration: order.expiration(),
+        }
+    }
+}
+
+/// Open orders at the last poll, keyed by order hash
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderSnapshot {
+    /// Open orders
+    pub orders: BTreeMap<String, TrackedOrder>,
+}
+
+impl OrderSnapshot {
+    /// Compare the next poll against this snapshot
+    ///
+    /// `listed` is the maker's active orders. Orders that dropped out of the
+    /// listing are classified from their entry in `resolved` (looked up by
+    /// hash). One that can't be resolved is reported expired once its
+    /// expiration has passed and is otherwise kept, to be retried on the
+    /// next poll. `now` is Unix seconds.
+    ///
+    /// Returns the next snapshot and the events, in order-hash order.
+    #[must_use]
+    pub fn diff(
+        &self,
+        listed: &[LimitOrder],
+        resolved: &HashMap<String, LimitOrder>,
+        now: u64,
+    ) -> (OrderSnapshot, Vec<OrderEvent>) {
+        let mut next = OrderSnapshot::default();
+        let mut events = Vec::new();
+        let listed: BTreeMap<&str, &LimitOrder> = listed
+            .iter()
+            .map(|order| (order.order_hash.as_str(), order))
+            .collect();
+
+        for (&hash, &order) in &listed {
+            match self.orders.get(hash) {
+                Some(previous) => {
+                    if !transition(previous, order, now, &mut events) {
+                        next.orders
+                            .insert(hash.to_string(), TrackedOrder::from_order(order));
+                    }
+                }
+                None => {
+                    let mut opened = Vec::new();
+                    let tracked = TrackedOrder::from_order(order);
+                    if transition(&tracked, order, now, &mut opened) {
+                        // Already closed the first time it was seen
+                        continue;
+                    }
+                    events.push(OrderEvent::NewOrderDetected {
+                        order: Box::new(order.clone()),
+                    });
+                    next.orders.insert(hash.to_string(), tracked);
+                }
+            }
+        }
+
+        for (hash, previous) in &self.orders {
+            if listed.contains_key(hash.as_str()) {
+                continue;
+            }
+            let closed = match resolved.get(hash) {
+                Some(order) => {
+                    let closed = transition(previous, order, now, &mut events);
+                    if !closed {
+                        next.orders
+                            .insert(hash.clone(), TrackedOrder::from_order(order));
+                    }
+                    closed
+                }
+                None if previous.expiration.is_some_and(|at| at <= now) => {
+                    events.push(OrderEvent::OrderExpired {
+                        order_hash: hash.clone(),
+                    });
+                    true
+                }
+                None => false,
+            };
+            if !closed && !next.orders.contains_key(hash) {
+                next.orders.insert(hash.clone(), previous.clone());
+            }
+        }
+
+        events.sort_by(|a, b| a.order_hash().cmp(b.order_hash()));
+        (next, events)
+    }
+}
+
+/// Push the events between `previous` and `order`; true if the order is
+/// closed and should stop being tracked
+fn transition(
+    previous: &TrackedOrder,
+    order: &LimitOrder,
+    now: u64,
+    events: &mut Vec<OrderEvent>,
+) -> bool {
+    let hash = &order.order_hash;
+    let before = previous.remaining.parse::<u128>().ok();
+    let after = order.remaining_maker_amount.parse::<u128>().ok();
+    let delta = before
+        .zip(after)
+        .and_then(|(before, after)| before.checked_sub(after))
+        .filter(|delta| *delta > 0);
+
+    if after == Some(0) {
+        events.push(OrderEvent::OrderFilled {
+            order_hash: hash.clone(),
+            delta,
+        });
+        return true;
+    }
+    if let (Some(delta), Some(remaining)) = (delta, after) {
+        events.push(OrderEvent::OrderPartiallyFilled {
+            order_hash: hash.clone(),
+            delta,
+            remaining,
+        });
+    }
+
+    let expired = order
+        .expiration()
+        .or(previous.expiration)
+        .is_some_and(|at| at <= now);
+    let invalid = order.effective_status() == OrderStatus::Invalid;
+    let reason = order.order_invalid_reason.clone();
+    let reason_expired = reason
+        .as_deref()
+        .is_some_and(|r| r.to_ascii_lowercase().contains("expire"));
+
+    if expired || (invalid && reason_expired) {
+        events.push(OrderEvent::OrderExpired {
+            order_hash: hash.clone(),
+        });
+        true
+    } else if invalid {
+        events.push(OrderEvent::OrderCancelled {
+            order_hash: hash.clone(),
+            reason,
+        });
+        true
+    } else {
+        false
+    }
+}
+
+/// Persistence for the watcher's last snapshot
+///
+/// Implementations report failures with [`crate::error::snapshot_store`].
+pub trait SnapshotStore: Send + Sync {
+    /// Last saved snapshot for a maker on a chain
+    fn load(&self, chain: Chain, maker: &str) -> Result<Option<OrderSnapshot>>;
+
+    /// Replace the saved snapshot for a maker on a chain
+    fn save(&self, chain: Chain, maker: &str, snapshot: &OrderSnapshot) -> Result<()>;
+}
+
+/// Snapshot store that keeps snapshots in memory
+#[derive(Debug, Default)]
+pub struct MemorySnapshotStore {
+    snapshots: Mutex<HashMap<(u64, String), OrderSnapshot>>,
+}
+
+impl MemorySnapshotStore {
+    /// Create an empty store
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SnapshotStore for MemorySnapshotStore {
+    fn load(&self, chain: Chain, maker: &str) -> Result<Option<OrderSnapshot>> {
+        Ok(self
+            .snapshots
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&(chain.chain_id(), maker.to_lowercase()))
+            .cloned())
+    }
+
+    fn save(&self, chain: Chain, maker: &str, snapshot: &OrderSnapshot) -> Result<()> {
+        self.snapshots
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert((chain.chain_id(), maker.to_lowercase()), snapshot.clone());
+        Ok(())
+    }
+}
+
+/// Settings for [`LimitOrderWatcher`]
+#[derive(Debug, Clone)]
+pub struct WatcherOptions {
+    /// Time between polls
+    pub poll_interval: Duration,
+    /// Random spread applied to each interval, as a fraction of it
+    /// (0.1 = ±10%)
+    pub jitter: f64,
+    /// Most orders fetched and tracked per poll
+    pub max_orders: usize,
+}
+
+impl Default for WatcherOptions {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(15),
+            jitter: 0.1,
+            max_orders: 500,
+        }
+    }
+}
+
+impl WatcherOptions {
+    /// Default options (15s interval, ±10% jitter, 500 orders)
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the poll interval
+    #[must_use]
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Set the jitter fraction (clamped to 0.0-1.0)
+    #[must_use]
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set the maximum number of orders tracked
+    #[must_use]
+    pub fn with_max_orders(mut self, max_orders: usize) -> Self {
+        self.max_orders = max_orders;
+        self
+    }
+
+    /// Interval before the next poll, with jitter applied
+    fn next_delay(&self) -> Duration {
+        let spread = self.jitter * (fastrand::f64() * 2.0 - 1.0);
+        self.poll_interval.mul_f64((1.0 + spread).max(0.0))
+    }
+}
+
+/// Polls a maker's limit orders and reports fills, cancellations and
+/// expirations
+///
+/// # Example
+///
+/// ```no_run
+/// use oinch::{Chain, Client, LimitOrderWatcher, MemorySnapshotStore};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), oinch::Error> {
+///     let client = Client::new("your-api-key")?;
+///     let watcher = LimitOrderWatcher::new(
+///         &client,
+///         Chain::Ethereum,
+///         "0xYourMakerAddress",
+///         MemorySnapshotStore::new(),
+///     );
+///
+///     let shutdown = async {
+///         let _ = tokio::signal::ctrl_c().await;
+///     };
+///     watcher
+///         .watch(shutdown, |event| {
+///             println!("{event:?}");
+///             Ok(())
+///         })
+///         .await?;
+///     Ok(())
+/// }
+/// ```
+pub struct LimitOrderWatcher<'a, S> {
+    client: &'a Client,
+    chain: Chain,
+    maker: String,
+    store: S,
+    options: WatcherOptions,
+}
+
+impl<'a, S: SnapshotStore> LimitOrderWatcher<'a, S> {
+    /// Watch `maker`'s orders on `chain`, persisting snapshots in `store`
+    #[must_use]
+    pub fn new(client: &'a Client, chain: Chain, maker: impl Into<String>, store: S) -> Self {
+        Self {
+            client,
+            chain,
+            maker: maker.into(),
+            store,
+            options: WatcherOptions::default(),
+        }
+    }
+
+    /// Set the watcher options
+    #[must_use]
+    pub fn with_options(mut self, options: WatcherOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// The snapshot store
+    #[must_use]
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Poll once, save the new snapshot and return the events
+    pub async fn poll_once(&self) -> Result<Vec<OrderEvent>> {
+        let (snapshot, events) = self.poll().await?;
+        self.store.save(self.chain, &self.maker, &snapshot)?;
+        Ok(events)
+    }
+
+    /// Poll until `shutdown` resolves, passing each event to `handler`
+    ///
+    /// The snapshot is saved after the handler has seen a poll's events, so
+    /// a restart only repeats events from a poll that was interrupted. Polls
+    /// that fail with a transient error (after retries) are skipped until
+    /// the next tick; other errors, and errors from `handler` or the store,
+    /// stop the watcher. Returns the number of events emitted.
+    pub async fn watch<F, Fut>(&self, shutdown: Fut, mut handler: F) -> Result<u64>
+    where
+        F: FnMut(OrderEvent) -> Result<()>,
+        Fut: std::future::Future<Output = ()>,
+    {
+        let mut emitted = 0u64;
+        tokio::pin!(shutdown);
+
+        loop {
+            let update = tokio::select! {
+                _ = &mut shutdown => break,
+                update = self.poll() => update,
+            };
+
+            match update {
+                Ok((snapshot, events)) => {
+                    for event in events {
+                        emitted += 1;
+                        handler(event)?;
+                    }
+                    self.store.save(self.chain, &self.maker, &snapshot)?;
+                }
+                Err(e) if e.is_retryable() => {}
+                Err(e) => return Err(e),
+            }
+
+            tokio::select! {
+                _ = &mut shutdown => break,
+                _ = tokio::time::sleep(self.options.next_delay()) => {}
+            }
+        }
+
+        Ok(emitted)
+    }
+
+    /// Fetch the current orders and diff them against the stored snapshot
+    async fn poll(&self) -> Result<(OrderSnapshot, Vec<OrderEvent>)> {
+        let previous = self
+            .store
+            .load(self.chain, &self.maker)?
+            .unwrap_or_default();
+        let listed = self.list_active().await?;
+
+        let mut resolved = HashMap::new();
+        for hash in previous.orders.keys() {
+            if listed.iter().any(|order| &order.order_hash == hash) {
+                continue;
+            }
+            match self.lookup(hash).await {
+                Ok(order) => {
+                    resolved.insert(hash.clone(), order);
+                }
+                // Not indexed (yet): retried next poll
+                Err(e) if e.status_code() == Some(404) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(previous.diff(&listed, &resolved, unix_now()))
+    }
+
+    /// Active orders, up to `max_orders`
+    async fn list_active(&self) -> Result<Vec<LimitOrder>> {
+        let retry = RetryConfig::default();
+        let mut orders = Vec::new();
+        let mut page = 1;
+
+        while orders.len() < self.options.max_orders {
+            let remaining = self.options.max_orders - orders.len();
+            let limit = ORDERBOOK_PAGE_SIZE.min(u32::try_from(remaining).unwrap_or(u32::MAX));
+            let query = LimitOrderQuery::new().with_page(page).with_limit(limit);
+            let batch = with_retry(&retry, || {
+                self.client
+                    .get_limit_orders_by_maker(self.chain, &self.maker, &query)
+            })
+            .await
+            .map_err(|e| e.into_inner())?;
+            let full = batch.len() >= limit as usize;
+            orders.extend(batch.into_iter().take(remaining));
+            if !full {
+                break;
+            }
+            page += 1;
+        }
+
+        Ok(orders)
+    }
+
+    async fn lookup(&self, order_hash: &str) -> Result<LimitOrder> {
+        with_retry(&RetryConfig::default(), || {
+            self.client.get_limit_order(self.chain, order_hash)
+        })
+        .await
+        .map_err(|e| e.into_inner())
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Config;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const MAKER: &str = "0x1111111111111111111111111111111111111111";
+    const NOW: u64 = 1_700_000_000;
+    /// 2100-01-01, so orders served to a live watcher aren't expired
+    const FAR: u64 = 4_102_444_800;
+
+    /// `makerTraits` with only an expiration set
+    fn traits(expiration: u64) -> String {
+        format!("{:#066x}", u128::from(expiration) << EXPIRATION_OFFSET)
+    }
+
+    fn order(hash: &str, remaining: &str) -> LimitOrder {
+        serde_json::from_value(serde_json::json!({
+            "orderHash": hash,
+            "signature": "0xsig",
+            "createDateTime": "2023-11-14T22:00:00.000Z",
+            "remainingMakerAmount": remaining,
+            "makerBalance": "1000",
+            "makerAllowance": "1000",
+            "data": {
+                "makerAsset": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+                "takerAsset": "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+                "maker": MAKER,
+                "makingAmount": "1000",
+                "takingAmount": "1",
+                "salt": "1",
+                "makerTraits": traits(FAR)
+            },
+            "orderInvalidReason": null
+        }))
+        .unwrap()
+    }
+
+    fn invalid(mut order: LimitOrder, reason: &str) -> LimitOrder {
+        order.order_invalid_reason = Some(reason.to_string());
+        order.status = Some(OrderStatus::Invalid);
+        order
+    }
+
+    fn expiring_at(mut order: LimitOrder, expiration: u64) -> LimitOrder {
+        order.data.maker_traits = Some(traits(expiration));
+        order
+    }
+
+    /// Run `polls` through `diff` starting from an empty snapshot
+    fn replay(polls: &[(Vec<LimitOrder>, Vec<LimitOrder>, u64)]) -> Vec<Vec<OrderEvent>> {
+        let mut snapshot = OrderSnapshot::default();
+        polls
+            .iter()
+            .map(|(listed, resolved, now)| {
+                let resolved = resolved
+                    .iter()
+                    .map(|o| (o.order_hash.clone(), o.clone()))
+                    .collect();
+                let (next, events) = snapshot.diff(listed, &resolved, *now);
+                snapshot = next;
+                events
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_expiration_from_maker_traits() {
+        assert_eq!(order("0xa", "1").expiration(), Some(FAR));
+        // Flags in the high bits and nonce/series below don't leak in
+        let packed = format!(
+            "0x8000000000000000000000000000000000{:010x}{:020x}",
+            NOW, 0xdead_u64
+        );
+        assert_eq!(expiration_from_traits(&packed), Some(NOW));
+        assert_eq!(expiration_from_traits("0x0"), None);
+        assert_eq!(expiration_from_traits("0x"), None);
+    }
+
+    #[test]
+    fn test_status_codes() {
+        let status: OrderStatus = serde_json::from_value(serde_json::json!(2)).unwrap();
+        assert_eq!(status, OrderStatus::TemporarilyInvalid);
+        let status: OrderStatus = serde_json::from_value(serde_json::json!("Invalid")).unwrap();
+        assert_eq!(status, OrderStatus::Invalid);
+        assert!(serde_json::from_value::<OrderStatus>(serde_json::json!(7)).is_err());
+
+        assert_eq!(order("0xa", "1").effective_status(), OrderStatus::Valid);
+        let mut reason_only = order("0xa", "1");
+        reason_only.order_invalid_reason = Some("order cancelled".into());
+        assert_eq!(reason_only.effective_status(), OrderStatus::Invalid);
+
+        assert_eq!(
+            LimitOrderQuery::new().with_page(2).to_query_params(),
+            vec![
+                ("page", "2".to_string()),
+                ("limit", "100".to_string()),
+                ("statuses", "1".to_string()),
+                ("statuses", "2".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_new_then_unchanged() {
+        let events = replay(&[
+            (vec![order("0xa", "1000")], vec![], NOW),
+            (vec![order("0xa", "1000")], vec![], NOW + 15),
+        ]);
+        assert!(matches!(
+            &events[0][..],
+            [OrderEvent::NewOrderDetected { order }] if order.order_hash == "0xa"
+        ));
+        assert!(events[1].is_empty());
+    }
+
+    #[test]
+    fn test_partial_fills_then_filled_after_disappearing() {
+        let events = replay(&[
+            (vec![order("0xa", "1000")], vec![], NOW),
+            (vec![order("0xa", "600")], vec![], NOW + 15),
+            (vec![order("0xa", "250")], vec![], NOW + 30),
+            // Fully filled orders leave the active listing
+            (
+                vec![],
+                vec![invalid(order("0xa", "0"), "order filled")],
+                NOW + 45,
+            ),
+            (vec![], vec![], NOW + 60),
+        ]);
+        assert_eq!(
+            events[1],
+            vec![OrderEvent::OrderPartiallyFilled {
+                order_hash: "0xa".into(),
+                delta: 400,
+                remaining: 600,
+            }]
+        );
+        assert_eq!(
+            events[2],
+            vec![OrderEvent::OrderPartiallyFilled {
+                order_hash: "0xa".into(),
+                delta: 350,
+                remaining: 250,
+            }]
+        );
+        assert_eq!(
+            events[3],
+            vec![OrderEvent::OrderFilled {
+                order_hash: "0xa".into(),
+                delta: Some(250),
+            }]
+        );
+        // No longer tracked, so nothing repeats
+        assert!(events[4].is_empty());
+    }
+
+    #[test]
+    fn test_filled_while_still_listed() {
+        let events = replay(&[
+            (vec![order("0xa", "1000")], vec![], NOW),
+            (vec![order("0xa", "0")], vec![], NOW + 15),
+        ]);
+        assert_eq!(
+            events[1],
+            vec![OrderEvent::OrderFilled {
+                order_hash: "0xa".into(),
+                delta: Some(1000),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_cancelled_after_partial_fill() {
+        let events = replay(&[
+            (vec![order("0xa", "1000")], vec![], NOW),
+            (
+                vec![],
+                vec![invalid(order("0xa", "700"), "order cancelled by maker")],
+                NOW + 15,
+            ),
+        ]);
+        assert_eq!(
+            events[1],
+            vec![
+                OrderEvent::OrderPartiallyFilled {
+                    order_hash: "0xa".into(),
+                    delta: 300,
+                    remaining: 700,
+                },
+                OrderEvent::OrderCancelled {
+                    order_hash: "0xa".into(),
+                    reason: Some("order cancelled by maker".into()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expired_by_status_or_by_time() {
+        // The orderbook says so
+        let events = replay(&[
+            (vec![order("0xa", "1000")], vec![], NOW),
+            (
+                vec![],
+                vec![invalid(order("0xa", "1000"), "order expired")],
+                NOW + 15,
+            ),
+        ]);
+        assert_eq!(
+            events[1],
+            vec![OrderEvent::OrderExpired {
+                order_hash: "0xa".into()
+            }]
+        );
+
+        // Gone and unresolvable, but past its expiration
+        let events = replay(&[
+            (
+                vec![expiring_at(order("0xb", "1000"), NOW + 10)],
+                vec![],
+                NOW,
+            ),
+            (vec![], vec![], NOW + 15),
+        ]);
+        assert_eq!(
+            events[1],
+            vec![OrderEvent::OrderExpired {
+                order_hash: "0xb".into()
+            }]
+        );
+
+        // Still listed after its expiration
+        let events = replay(&[
+            (
+                vec![expiring_at(order("0xc", "1000"), NOW + 10)],
+                vec![],
+                NOW,
+            ),
+            (
+                vec![expiring_at(order("0xc", "1000"), NOW + 10)],
+                vec![],
+                NOW + 15,
+            ),
+        ]);
+        assert_eq!(
+            events[1],
+            vec![OrderEvent::OrderExpired {
+                order_hash: "0xc".into()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_unresolved_disappearance_is_retried() {
+        let events = replay(&[
+            (vec![order("0xa", "1000")], vec![], NOW),
+            // Dropped from the listing, lookup not indexed yet, not expired
+            (vec![], vec![], NOW + 15),
+            (
+                vec![],
+                vec![invalid(order("0xa", "1000"), "order cancelled")],
+                NOW + 30,
+            ),
+        ]);
+        assert!(events[1].is_empty());
+        assert!(matches!(
+            &events[2][..],
+            [OrderEvent::OrderCancelled { order_hash, .. }] if order_hash == "0xa"
+        ));
+    }
+
+    #[test]
+    fn test_temporarily_invalid_stays_open() {
+        let mut low_balance = order("0xa", "1000");
+        low_balance.status = Some(OrderStatus::TemporarilyInvalid);
+        low_balance.order_invalid_reason = Some("not enough balance".into());
+
+        let events = replay(&[
+            (vec![order("0xa", "1000")], vec![], NOW),
+            (vec![low_balance], vec![], NOW + 15),
+            (vec![order("0xa", "900")], vec![], NOW + 30),
+        ]);
+        assert!(events[1].is_empty());
+        assert!(matches!(
+            &events[2][..],
+            [OrderEvent::OrderPartiallyFilled { delta: 100, .. }]
+        ));
+    }
+
+    #[test]
+    fn test_events_for_several_orders_are_ordered_by_hash() {
+        let events = replay(&[
+            (vec![order("0xb", "10"), order("0xa", "10")], vec![], NOW),
+            (
+                vec![order("0xc", "10"), order("0xb", "5")],
+                vec![invalid(order("0xa", "10"), "order cancelled")],
+                NOW + 15,
+            ),
+        ]);
+        let hashes: Vec<&str> = events[0].iter().map(OrderEvent::order_hash).collect();
+        assert_eq!(hashes, vec!["0xa", "0xb"]);
+        let hashes: Vec<&str> = events[1].iter().map(OrderEvent::order_hash).collect();
+        assert_eq!(hashes, vec!["0xa", "0xb", "0xc"]);
+    }
+
+    #[test]
+    fn test_jitter_stays_within_spread() {
+        let options = WatcherOptions::new()
+            .with_poll_interval(Duration::from_secs(10))
+            .with_jitter(0.2);
+        for _ in 0..100 {
+            let delay = options.next_delay();
+            assert!(delay >= Duration::from_secs(8) && delay <= Duration::from_secs(12));
+        }
+        let fixed = options.with_jitter(0.0);
+        assert_eq!(fixed.next_delay(), Duration::from_secs(10));
+    }
+
+    fn client(server: &MockServer) -> Client {
+        Client::with_config(Config::new("test-api-key").with_base_url(server.uri())).unwrap()
+    }
+
+    async fn serve_listing(server: &MockServer, orders: &[LimitOrder]) {
+        server.reset().await;
+        Mock::given(method("GET"))
+            .and(path(format!("/orderbook/v4.0/1/address/{MAKER}")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(orders))
+            .mount(server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/orderbook/v4.0/1/order/0xa"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(invalid(order("0xa", "0"), "order filled")),
+            )
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_restart_does_not_reemit() {
+        let server = MockServer::start().await;
+        let client = client(&server);
+        serve_listing(&server, &[order("0xa", "1000")]).await;
+
+        let store = MemorySnapshotStore::new();
+        let watcher = LimitOrderWatcher::new(&client, Chain::Ethereum, MAKER, store);
+        let events = watcher.poll_once().await.unwrap();
+        assert_eq!(events.len(), 1);
+
+        // A new watcher over the same store picks up the saved snapshot
+        let store = MemorySnapshotStore::new();
+        let snapshot = watcher
+            .store()
+            .load(Chain::Ethereum, MAKER)
+            .unwrap()
+            .unwrap();
+        store.save(Chain::Ethereum, MAKER, &snapshot).unwrap();
+        let restarted = LimitOrderWatcher::new(&client, Chain::Ethereum, MAKER, store);
+        assert!(restarted.poll_once().await.unwrap().is_empty());
+
+        serve_listing(&server, &[]).await;
+        assert_eq!(
+            restarted.poll_once().await.unwrap(),
+            vec![OrderEvent::OrderFilled {
+                order_hash: "0xa".into(),
+                delta: Some(1000),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_listing_respects_max_orders() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(format!("/orderbook/v4.0/1/address/{MAKER}")))
+            .and(query_param("page", "1"))
+            .and(query_param("limit", "2"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(vec![order("0xa", "1"), order("0xb", "1")]),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = client(&server);
+        let watcher =
+            LimitOrderWatcher::new(&client, Chain::Ethereum, MAKER, MemorySnapshotStore::new())
+                .with_options(WatcherOptions::new().with_max_orders(2));
+        assert_eq!(watcher.poll_once().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_watch_stops_on_shutdown() {
+        let server = MockServer::start().await;
+        let client = client(&server);
+        serve_listing(&server, &[order("0xa", "1000")]).await;
+
+        let watcher =
+            LimitOrderWatcher::new(&client, Chain::Ethereum, MAKER, MemorySnapshotStore::new())
+                .with_options(WatcherOptions::new().with_poll_interval(Duration::from_millis(10)));
+        let mut seen = Vec::new();
+        let emitted = watcher
+            .watch(tokio::time::sleep(Duration::from_millis(100)), |event| {
+                seen.push(event);
+                Ok(())
+            })
+            .await
+            .unwrap();
+        // Announced once, then unchanged on every later poll
+        assert_eq!(emitted, 1);
+        assert_eq!(seen.len(), 1);
+    }
+}
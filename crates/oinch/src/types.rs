@@ -42,6 +42,29 @@ pub enum Chain {
 }
 
 impl Chain {
+    /// Every supported chain, in declaration order
+    const ALL: &'static [Self] = &[
+        Self::Ethereum,
+        Self::Bsc,
+        Self::Polygon,
+        Self::Optimism,
+        Self::Arbitrum,
+        Self::Gnosis,
+        Self::Avalanche,
+        Self::Fantom,
+        Self::Klaytn,
+        Self::Aurora,
+        Self::ZkSync,
+        Self::Base,
+        Self::Linea,
+    ];
+
+    /// All chains supported by the 1inch API
+    #[must_use]
+    pub const fn all() -> &'static [Self] {
+        Self::ALL
+    }
+
     /// Get the numeric chain ID for API requests
     #[must_use]
     pub const fn chain_id(self) -> u64 {
@@ -90,6 +113,9 @@ impl Chain {
     }
 
     /// Parse chain from a string (name or chain ID)
+    ///
+    /// Accepts chain IDs, short aliases like `"arb"`, and the display names
+    /// returned by [`Chain::name`], case-insensitively.
     #[must_use]
     pub fn parse(s: &str) -> Option<Self> {
         // Try parsing as chain ID first
@@ -98,18 +124,18 @@ impl Chain {
         }
 
         // Try parsing as name
-        match s.to_lowercase().as_str() {
+        match s.trim().to_lowercase().as_str() {
             "ethereum" | "eth" | "mainnet" => Some(Self::Ethereum),
-            "bsc" | "bnb" | "binance" => Some(Self::Bsc),
+            "bsc" | "bnb" | "binance" | "bnb smart chain" => Some(Self::Bsc),
             "polygon" | "matic" => Some(Self::Polygon),
             "optimism" | "op" => Some(Self::Optimism),
-            "arbitrum" | "arb" => Some(Self::Arbitrum),
-            "gnosis" | "xdai" => Some(Self::Gnosis),
+            "arbitrum" | "arb" | "arbitrum one" => Some(Self::Arbitrum),
+            "gnosis" | "xdai" | "gnosis chain" => Some(Self::Gnosis),
             "avalanche" | "avax" => Some(Self::Avalanche),
             "fantom" | "ftm" => Some(Self::Fantom),
             "klaytn" | "klay" => Some(Self::Klaytn),
             "aurora" => Some(Self::Aurora),
-            "zksync" | "era" => Some(Self::ZkSync),
+            "zksync" | "era" | "zksync era" => Some(Self::ZkSync),
             "base" => Some(Self::Base),
             "linea" => Some(Self::Linea),
             _ => None,
@@ -118,12 +144,29 @@ impl Chain {
 }
 
 /// Error type for chain parsing
+///
+/// The message lists the supported chains with their IDs.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ParseChainError(String);
 
+impl ParseChainError {
+    /// The input that failed to parse
+    #[must_use]
+    pub fn input(&self) -> &str {
+        &self.0
+    }
+}
+
 impl fmt::Display for ParseChainError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "unknown chain: {}", self.0)
+        write!(f, "unknown chain: {} (supported: ", self.0)?;
+        for (i, chain) in Chain::all().iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{} = {}", chain.name(), chain.chain_id())?;
+        }
+        f.write_str(")")
     }
 }
 
@@ -773,6 +816,57 @@ mod tests {
         assert!("unknown".parse::<Chain>().is_err());
     }
 
+    #[test]
+    fn test_chain_all_round_trips() {
+        // Exhaustive match linking each variant to the next in declaration
+        // order: a new variant fails to compile until it is linked in here,
+        // and then fails below until it is also in `ALL`
+        let next = |chain: Chain| match chain {
+            Chain::Ethereum => Some(Chain::Bsc),
+            Chain::Bsc => Some(Chain::Polygon),
+            Chain::Polygon => Some(Chain::Optimism),
+            Chain::Optimism => Some(Chain::Arbitrum),
+            Chain::Arbitrum => Some(Chain::Gnosis),
+            Chain::Gnosis => Some(Chain::Avalanche),
+            Chain::Avalanche => Some(Chain::Fantom),
+            Chain::Fantom => Some(Chain::Klaytn),
+            Chain::Klaytn => Some(Chain::Aurora),
+            Chain::Aurora => Some(Chain::ZkSync),
+            Chain::ZkSync => Some(Chain::Base),
+            Chain::Base => Some(Chain::Linea),
+            Chain::Linea => None,
+        };
+        let variants: Vec<Chain> =
+            std::iter::successors(Some(Chain::Ethereum), |&c| next(c)).collect();
+        assert_eq!(Chain::all(), variants.as_slice());
+
+        for &chain in Chain::all() {
+            assert_eq!(Chain::from_chain_id(chain.chain_id()), Some(chain));
+            assert_eq!(chain.name().parse::<Chain>(), Ok(chain), "{}", chain.name());
+            assert_eq!(
+                chain.name().to_uppercase().parse::<Chain>(),
+                Ok(chain),
+                "{}",
+                chain.name()
+            );
+            assert_eq!(chain.to_string().parse::<Chain>(), Ok(chain));
+        }
+
+        let mut ids: Vec<u64> = Chain::all().iter().map(|c| c.chain_id()).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), Chain::all().len());
+    }
+
+    #[test]
+    fn test_parse_chain_error() {
+        let err = "solana".parse::<Chain>().unwrap_err();
+        assert_eq!(err.input(), "solana");
+        let message = err.to_string();
+        assert!(message.starts_with("unknown chain: solana (supported: Ethereum = 1, "));
+        assert!(message.ends_with("Linea = 59144)"));
+    }
+
     #[test]
     fn test_quote_request_builder() {
         let request = QuoteRequest::new(
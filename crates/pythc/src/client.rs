@@ -10,11 +10,20 @@ use yldfi_common::api::{ApiConfig, BaseClient};
 use yldfi_common::{with_retry, RetryConfig};
 
 use crate::error::{DomainError, Error, Result};
-use crate::types::{LatestPriceResponse, ParsedPriceFeed, PriceFeedId};
+use crate::types::{LatestPriceResponse, ParsedPriceFeed, PriceFeedId, PricePoint, Twap};
 
 /// Maximum length of error body to include in error messages
 const MAX_ERROR_BODY_LEN: usize = 500;
 
+/// Default maximum number of requests a single series or TWAP query may make
+pub const DEFAULT_MAX_SERIES_REQUESTS: u64 = 100;
+
+/// Number of samples [`Client::twap`] takes across its window
+pub const TWAP_SAMPLES: u64 = 30;
+
+/// Maximum number of concurrent requests while sampling a series
+const SERIES_CONCURRENCY: usize = 8;
+
 /// Base URLs for Pyth Hermes
 pub mod base_urls {
     /// Production Hermes
@@ -30,12 +39,14 @@ pub mod base_urls {
 #[derive(Debug, Clone)]
 pub struct Config {
     inner: ApiConfig,
+    max_series_requests: u64,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             inner: ApiConfig::new(base_urls::MAINNET),
+            max_series_requests: DEFAULT_MAX_SERIES_REQUESTS,
         }
     }
 }
@@ -52,6 +63,7 @@ impl Config {
     pub fn testnet() -> Self {
         Self {
             inner: ApiConfig::new(base_urls::TESTNET),
+            max_series_requests: DEFAULT_MAX_SERIES_REQUESTS,
         }
     }
 
@@ -76,6 +88,16 @@ impl Config {
         self
     }
 
+    /// Set the maximum number of requests a single series or TWAP query may make
+    ///
+    /// Queries that would need more requests fail with
+    /// [`DomainError::RequestBudgetExceeded`] before any request is sent.
+    #[must_use]
+    pub fn with_max_series_requests(mut self, max: u64) -> Self {
+        self.max_series_requests = max;
+        self
+    }
+
     /// Validate the configuration.
     ///
     /// Checks that the base URL is valid and uses HTTPS (except for localhost).
@@ -124,6 +146,7 @@ impl Config {
 pub struct Client {
     base: BaseClient,
     retry_config: RetryConfig,
+    max_series_requests: u64,
 }
 
 impl Client {
@@ -147,6 +170,7 @@ impl Client {
         // Validate URL format and security (HTTPS required for non-localhost)
         config.validate()?;
 
+        let max_series_requests = config.max_series_requests;
         let base = BaseClient::new(config.inner)
             .map_err(yldfi_common::api::ApiError::<DomainError>::HttpBuild)?;
 
@@ -155,6 +179,7 @@ impl Client {
             retry_config: RetryConfig::new(3)
                 .with_initial_delay(Duration::from_millis(100))
                 .with_max_delay(Duration::from_secs(5)),
+            max_series_requests,
         })
     }
 
@@ -201,6 +226,112 @@ impl Client {
         Ok(feeds.into_iter().next())
     }
 
+    /// Get the price update published at a given time
+    ///
+    /// Returns the first update at or after `publish_time` (unix seconds), or
+    /// `None` if Hermes has no update for that time.
+    pub async fn get_price_at(
+        &self,
+        feed_id: &str,
+        publish_time: i64,
+    ) -> Result<Option<ParsedPriceFeed>> {
+        if !validate_feed_id(feed_id) {
+            return Err(crate::error::invalid_feed_id(feed_id));
+        }
+        if publish_time < 0 {
+            return Err(crate::error::invalid_time_range(format!(
+                "publish time {publish_time} is before the unix epoch"
+            )));
+        }
+
+        let mut url = Url::parse(&self.base.url(&format!("/v2/updates/price/{publish_time}")))?;
+        url.query_pairs_mut()
+            .append_pair("ids[]", &normalize_feed_id(feed_id))
+            .append_pair("parsed", "true");
+
+        match self.get_url::<LatestPriceResponse>(&url).await {
+            Ok(response) => Ok(response.parsed.into_iter().next()),
+            Err(e) if e.status_code() == Some(404) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Get a price series between `start` and `end` (unix seconds)
+    ///
+    /// Hermes has no series endpoint, so this samples [`Self::get_price_at`]
+    /// at `start`, `start + resolution`, ... up to and including `end`, one
+    /// request per sample (at most 8 in flight). Each sample is the first
+    /// update at or after its time, so points are not exactly on the grid;
+    /// samples with no update are skipped and duplicate updates are merged.
+    /// Points are returned sorted by publish time.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::RequestBudgetExceeded`] with the computed
+    /// request count, before sending anything, if the range and resolution
+    /// need more requests than [`Config::with_max_series_requests`] allows.
+    pub async fn get_price_series(
+        &self,
+        feed_id: &str,
+        start: i64,
+        end: i64,
+        resolution: Duration,
+    ) -> Result<Vec<PricePoint>> {
+        if !validate_feed_id(feed_id) {
+            return Err(crate::error::invalid_feed_id(feed_id));
+        }
+        let times = sample_times(start, end, resolution, self.max_series_requests)?;
+
+        let feed_id = normalize_feed_id(feed_id).into_owned();
+        let mut pending = times.into_iter();
+        let mut tasks = tokio::task::JoinSet::new();
+        let mut points = Vec::new();
+        loop {
+            while tasks.len() < SERIES_CONCURRENCY {
+                let Some(time) = pending.next() else { break };
+                let client = self.clone();
+                let feed_id = feed_id.clone();
+                tasks.spawn(async move { client.get_price_at(&feed_id, time).await });
+            }
+            let Some(joined) = tasks.join_next().await else {
+                break;
+            };
+            let feed =
+                joined.map_err(|e| Error::api(0, format!("Sampling task failed: {e}")))??;
+            if let Some(point) = feed.as_ref().and_then(ParsedPriceFeed::price_point) {
+                points.push(point);
+            }
+        }
+
+        points.sort_by_key(|p| p.publish_time);
+        points.dedup_by_key(|p| p.publish_time);
+        Ok(points)
+    }
+
+    /// Time-weighted average price over the `window` ending now
+    ///
+    /// Samples [`TWAP_SAMPLES`] + 1 evenly spaced points with
+    /// [`Self::get_price_series`] and combines them with
+    /// [`Twap::from_points`]. Returns `None` if no prices were found.
+    pub async fn twap(&self, feed_id: &str, window: Duration) -> Result<Option<Twap>> {
+        let secs = i64::try_from(window.as_secs()).unwrap_or(i64::MAX);
+        if secs == 0 {
+            return Err(crate::error::invalid_time_range(
+                "TWAP window must be at least 1s",
+            ));
+        }
+        let end = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs() as i64);
+        let start = end.saturating_sub(secs);
+        let resolution = Duration::from_secs((secs as u64).div_ceil(TWAP_SAMPLES));
+
+        let points = self
+            .get_price_series(feed_id, start, end, resolution)
+            .await?;
+        Ok(Twap::from_points(&points, start, end))
+    }
+
     /// Get all available price feed IDs
     pub async fn get_price_feed_ids(&self) -> Result<Vec<PriceFeedId>> {
         self.get("/v2/price_feeds").await
@@ -265,6 +396,28 @@ impl Client {
     }
 }
 
+/// Sample times for a series query, checked against the request budget
+fn sample_times(start: i64, end: i64, resolution: Duration, budget: u64) -> Result<Vec<i64>> {
+    if start < 0 || end < start {
+        return Err(crate::error::invalid_time_range(format!(
+            "start {start} must be non-negative and not after end {end}"
+        )));
+    }
+    let step = resolution.as_secs();
+    if step == 0 {
+        return Err(crate::error::invalid_time_range(
+            "resolution must be at least 1s",
+        ));
+    }
+
+    let required = (end - start) as u64 / step + 1;
+    if required > budget {
+        return Err(crate::error::request_budget_exceeded(required, budget));
+    }
+
+    Ok((0..required).map(|i| start + (i * step) as i64).collect())
+}
+
 /// Normalize feed ID (ensure 0x prefix, lowercase)
 ///
 /// Returns a Cow to avoid allocation when the ID is already normalized.
@@ -30,6 +30,19 @@ pub enum DomainError {
     /// Invalid URL configuration
     #[error("Invalid URL: {0}")]
     InvalidUrl(String),
+
+    /// Invalid time range or resolution for a historical query
+    #[error("Invalid time range: {0}")]
+    InvalidTimeRange(String),
+
+    /// A historical query would need more requests than allowed
+    #[error("Query needs {required} requests, over the budget of {budget}")]
+    RequestBudgetExceeded {
+        /// Requests the query would make
+        required: u64,
+        /// Configured maximum
+        budget: u64,
+    },
 }
 
 /// Error type for Pyth API operations
@@ -65,6 +78,17 @@ pub fn invalid_url(msg: impl Into<String>) -> Error {
     ApiError::domain(DomainError::InvalidUrl(msg.into()))
 }
 
+/// Create an invalid time range error
+pub fn invalid_time_range(msg: impl Into<String>) -> Error {
+    ApiError::domain(DomainError::InvalidTimeRange(msg.into()))
+}
+
+/// Create a request budget exceeded error
+#[must_use]
+pub fn request_budget_exceeded(required: u64, budget: u64) -> Error {
+    ApiError::domain(DomainError::RequestBudgetExceeded { required, budget })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -88,4 +112,11 @@ mod tests {
         let err = stale_price();
         assert!(err.to_string().contains("Stale price data"));
     }
+
+    #[test]
+    fn test_request_budget_exceeded() {
+        let err = request_budget_exceeded(1441, 100);
+        assert!(err.to_string().contains("1441 requests"));
+        assert!(err.to_string().contains("budget of 100"));
+    }
 }
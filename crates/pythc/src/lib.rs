@@ -71,7 +71,13 @@ pub mod client;
 pub mod error;
 pub mod types;
 
-pub use client::{base_urls, feed_ids, symbol_to_feed_id, Client, Config};
-pub use error::{feed_not_found, invalid_feed_id, stale_price, Error, Result};
-pub use types::{LatestPriceResponse, ParsedPriceFeed, PriceData, PriceFeedId};
+pub use client::{
+    base_urls, feed_ids, symbol_to_feed_id, Client, Config, DEFAULT_MAX_SERIES_REQUESTS,
+    TWAP_SAMPLES,
+};
+pub use error::{
+    feed_not_found, invalid_feed_id, invalid_time_range, request_budget_exceeded, stale_price,
+    DomainError, Error, Result,
+};
+pub use types::{LatestPriceResponse, ParsedPriceFeed, PriceData, PriceFeedId, PricePoint, Twap};
 pub use yldfi_common::http::HttpClientConfig;
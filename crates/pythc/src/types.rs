@@ -149,3 +149,207 @@ impl ParsedPriceFeed {
         now - self.price.publish_time > max_age_secs
     }
 }
+
+/// A price observation as plain numbers
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PricePoint {
+    /// Publish time (unix timestamp)
+    pub publish_time: i64,
+    /// Price with the exponent applied
+    pub price: f64,
+    /// Confidence interval with the exponent applied
+    pub conf: f64,
+}
+
+impl ParsedPriceFeed {
+    /// The current price as a [`PricePoint`]
+    #[must_use]
+    pub fn price_point(&self) -> Option<PricePoint> {
+        Some(PricePoint {
+            publish_time: self.price.publish_time,
+            price: self.price_f64()?,
+            conf: self.confidence_f64()?,
+        })
+    }
+}
+
+/// Time-weighted average price over a window
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Twap {
+    /// Time-weighted average price
+    pub price: f64,
+    /// Largest confidence interval among the points that carried weight
+    pub conf: f64,
+    /// Window start (unix timestamp)
+    pub start: i64,
+    /// Window end (unix timestamp)
+    pub end: i64,
+    /// Seconds of the window covered by a price; the time before the
+    /// first point in the window is not covered
+    pub covered_secs: i64,
+    /// Number of points that carried weight
+    pub points: usize,
+}
+
+impl Twap {
+    /// Compute a TWAP over `[start, end]` from price points
+    ///
+    /// Prices are treated as a step function: each point holds from its
+    /// publish time until the next point, and the last one until `end`, so a
+    /// gap between points is priced at the value before the gap. A point
+    /// published before `start` carries into the window if it is the latest
+    /// such point. Returns `None` if no point lies at or before `end`.
+    ///
+    /// If all points share one timestamp at `end` (zero covered time), their
+    /// plain average is returned.
+    #[must_use]
+    pub fn from_points(points: &[PricePoint], start: i64, end: i64) -> Option<Self> {
+        let mut sorted: Vec<PricePoint> = points
+            .iter()
+            .copied()
+            .filter(|p| p.publish_time <= end && p.price.is_finite() && p.conf.is_finite())
+            .collect();
+        sorted.sort_by_key(|p| p.publish_time);
+
+        // Only the latest point before the window matters
+        let first_in_window = sorted.partition_point(|p| p.publish_time < start);
+        let sorted = &sorted[first_in_window.saturating_sub(1)..];
+        if sorted.is_empty() {
+            return None;
+        }
+
+        let mut weighted = 0.0;
+        let mut covered = 0i64;
+        let mut conf: f64 = 0.0;
+        let mut used = 0;
+        for (i, point) in sorted.iter().enumerate() {
+            let from = point.publish_time.max(start);
+            let to = sorted
+                .get(i + 1)
+                .map_or(end, |next| next.publish_time.min(end));
+            let secs = to - from;
+            if secs > 0 {
+                weighted += point.price * secs as f64;
+                covered += secs;
+                conf = conf.max(point.conf);
+                used += 1;
+            }
+        }
+
+        if covered == 0 {
+            // Every point sits at `end`
+            let at_end: Vec<&PricePoint> =
+                sorted.iter().filter(|p| p.publish_time >= start).collect();
+            if at_end.is_empty() {
+                return None;
+            }
+            return Some(Self {
+                price: at_end.iter().map(|p| p.price).sum::<f64>() / at_end.len() as f64,
+                conf: at_end.iter().map(|p| p.conf).fold(0.0, f64::max),
+                start,
+                end,
+                covered_secs: 0,
+                points: at_end.len(),
+            });
+        }
+
+        Some(Self {
+            price: weighted / covered as f64,
+            conf,
+            start,
+            end,
+            covered_secs: covered,
+            points: used,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(publish_time: i64, price: f64, conf: f64) -> PricePoint {
+        PricePoint {
+            publish_time,
+            price,
+            conf,
+        }
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn test_twap_evenly_spaced() {
+        let points = [
+            point(0, 10.0, 0.1),
+            point(10, 20.0, 0.2),
+            point(20, 30.0, 0.3),
+        ];
+        let twap = Twap::from_points(&points, 0, 30).unwrap();
+        assert!(close(twap.price, 20.0));
+        assert!(close(twap.conf, 0.3));
+        assert_eq!(twap.covered_secs, 30);
+        assert_eq!(twap.points, 3);
+    }
+
+    #[test]
+    fn test_twap_weights_by_interval_across_gaps() {
+        // 10 holds for 50s (including a gap), 20 for 10s
+        let points = [
+            point(100, 20.0, 0.5),
+            point(0, 10.0, 1.0),
+            point(50, 10.0, 0.2),
+        ];
+        let twap = Twap::from_points(&points, 0, 110).unwrap();
+        assert!(close(twap.price, (10.0 * 100.0 + 20.0 * 10.0) / 110.0));
+        assert!(close(twap.conf, 1.0));
+        assert_eq!(twap.points, 3);
+    }
+
+    #[test]
+    fn test_twap_window_edges() {
+        // The point before the window carries in; the one after is ignored
+        let points = [
+            point(-50, 8.0, 0.4),
+            point(-10, 10.0, 0.1),
+            point(30, 30.0, 0.2),
+            point(100, 99.0, 9.0),
+        ];
+        let twap = Twap::from_points(&points, 0, 40).unwrap();
+        assert!(close(twap.price, (10.0 * 30.0 + 30.0 * 10.0) / 40.0));
+        assert!(close(twap.conf, 0.2));
+        assert_eq!(twap.covered_secs, 40);
+        assert_eq!(twap.points, 2);
+
+        // Leading gap inside the window is not covered
+        let twap = Twap::from_points(&[point(20, 5.0, 0.1)], 0, 40).unwrap();
+        assert!(close(twap.price, 5.0));
+        assert_eq!(twap.covered_secs, 20);
+    }
+
+    #[test]
+    fn test_twap_degenerate() {
+        assert_eq!(Twap::from_points(&[], 0, 10), None);
+        assert_eq!(Twap::from_points(&[point(20, 1.0, 0.1)], 0, 10), None);
+
+        let twap = Twap::from_points(&[point(10, 1.0, 0.1), point(10, 3.0, 0.3)], 0, 10).unwrap();
+        assert!(close(twap.price, 2.0));
+        assert!(close(twap.conf, 0.3));
+        assert_eq!(twap.covered_secs, 0);
+    }
+
+    #[test]
+    fn test_price_point_applies_exponent() {
+        let feed: ParsedPriceFeed = serde_json::from_value(serde_json::json!({
+            "id": "ff61",
+            "price": { "price": "325000000000", "conf": "100000000", "expo": -8, "publish_time": 1704067200 }
+        }))
+        .unwrap();
+        let p = feed.price_point().unwrap();
+        assert_eq!(p.publish_time, 1_704_067_200);
+        assert!(close(p.price, 3250.0));
+        assert!(close(p.conf, 1.0));
+    }
+}
@@ -387,3 +387,144 @@ async fn test_symbol_to_feed_id() {
     // Unknown symbol
     assert_eq!(pythc::symbol_to_feed_id("UNKNOWN_TOKEN_XYZ"), None);
 }
+
+/// Hermes response with a single ETH/USD update
+fn price_update(price: &str, conf: &str, publish_time: i64) -> serde_json::Value {
+    serde_json::json!({
+        "binary": { "encoding": "hex", "data": ["deadbeef"] },
+        "parsed": [{
+            "id": "ff61491a931112ddf1bd8147cd1b641375f79f5825126d665480874634fd0ace",
+            "price": {
+                "price": price,
+                "conf": conf,
+                "expo": -8,
+                "publish_time": publish_time
+            }
+        }]
+    })
+}
+
+#[tokio::test]
+async fn test_get_price_at() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v2/updates/price/1704067200"))
+        .and(query_param("parsed", "true"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(price_update(
+            "325000000000",
+            "100000000",
+            1704067201,
+        )))
+        .mount(&mock_server)
+        .await;
+
+    let client = mock_client(&mock_server);
+    let feed = client
+        .get_price_at(pythc::feed_ids::ETH_USD, 1704067200)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(feed.price.publish_time, 1704067201);
+
+    // No update for that time
+    let missing = client
+        .get_price_at(pythc::feed_ids::ETH_USD, 1704067300)
+        .await
+        .unwrap();
+    assert!(missing.is_none());
+}
+
+#[tokio::test]
+async fn test_get_price_series_samples_and_skips_gaps() {
+    let mock_server = MockServer::start().await;
+
+    // Samples at 0, 60, 120 and 180; 120 has no update and 180 returns the
+    // same update as 60
+    for (time, price, publish_time) in [
+        (1704067200, "300000000000", 1704067200),
+        (1704067260, "310000000000", 1704067261),
+        (1704067380, "310000000000", 1704067261),
+    ] {
+        Mock::given(method("GET"))
+            .and(path(format!("/v2/updates/price/{time}")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(price_update(
+                price,
+                "100000000",
+                publish_time,
+            )))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path("/v2/updates/price/1704067320"))
+        .respond_with(ResponseTemplate::new(404))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = mock_client(&mock_server);
+    let points = client
+        .get_price_series(
+            pythc::feed_ids::ETH_USD,
+            1704067200,
+            1704067380,
+            std::time::Duration::from_secs(60),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(points.len(), 2);
+    assert_eq!(points[0].publish_time, 1704067200);
+    assert!((points[0].price - 3000.0).abs() < 0.001);
+    assert_eq!(points[1].publish_time, 1704067261);
+    assert!((points[1].conf - 1.0).abs() < 0.001);
+}
+
+#[tokio::test]
+async fn test_get_price_series_over_budget() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::with_config(
+        Config::default()
+            .with_base_url(mock_server.uri())
+            .with_max_series_requests(10),
+    )
+    .unwrap();
+
+    // One day at one-minute resolution
+    let err = client
+        .get_price_series(
+            pythc::feed_ids::ETH_USD,
+            1704067200,
+            1704067200 + 86_400,
+            std::time::Duration::from_secs(60),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        pythc::Error::Domain(pythc::DomainError::RequestBudgetExceeded {
+            required: 1441,
+            budget: 10
+        })
+    ));
+
+    let err = client
+        .get_price_series(
+            pythc::feed_ids::ETH_USD,
+            1704067200,
+            1704067100,
+            std::time::Duration::from_secs(60),
+        )
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Invalid time range"));
+}
@@ -384,20 +384,10 @@ pub struct Action {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ListActionsResponse {
     /// List of actions (handles null as empty)
-    #[serde(default, deserialize_with = "deserialize_null_default")]
+    #[serde(default, deserialize_with = "crate::utils::deserialize_null_default")]
     pub actions: Vec<Action>,
 }
 
-/// Deserialize null as default value
-fn deserialize_null_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
-where
-    D: serde::Deserializer<'de>,
-    T: Default + Deserialize<'de>,
-{
-    let opt = Option::deserialize(deserializer)?;
-    Ok(opt.unwrap_or_default())
-}
-
 /// Action execution log entry
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ActionLog {
@@ -753,6 +753,10 @@ pub struct TransactionInfo {
     /// Transaction logs
     #[serde(default)]
     pub logs: Option<Vec<serde_json::Value>>,
+
+    /// Decoded execution details (logs, state and balance diffs, asset changes)
+    #[serde(default)]
+    pub transaction_info: Option<serde_json::Value>,
 }
 
 /// Request for simulating a bundle of transactions
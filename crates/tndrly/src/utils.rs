@@ -1,7 +1,20 @@
 //! Utility functions for tndrly
 //!
-//! Re-exports from yldfi-common for Ethereum address and transaction validation.
+//! Re-exports from yldfi-common for Ethereum address and transaction validation,
+//! plus serde helpers shared by the API types.
+
+use serde::Deserialize;
 
 pub use yldfi_common::eth::{
     is_valid_address, is_valid_bytes32, is_valid_tx_hash, normalize_address, pad_to_32_bytes,
 };
+
+/// Deserialize null as default value
+pub(crate) fn deserialize_null_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    let opt = Option::deserialize(deserializer)?;
+    Ok(opt.unwrap_or_default())
+}
@@ -4,7 +4,10 @@
 //! and queried on specific networks, but there is no list, update, or delete
 //! endpoint in the current API.
 
-use super::types::{AddWalletRequest, AddWalletResponse, WalletOnNetwork};
+use super::types::{
+    AddWalletRequest, AddWalletResponse, WalletActivityPage, WalletActivityQuery,
+    WalletActivityResponse, WalletOnNetwork,
+};
 use crate::client::{encode_path_segment, Client};
 use crate::error::Result;
 
@@ -94,6 +97,89 @@ impl<'a> WalletsApi<'a> {
             ))
             .await
     }
+
+    /// Get the activity Tenderly has observed for a monitored wallet
+    ///
+    /// `wallet_id` is the project wallet ID returned by [`list`](Self::list)
+    /// (e.g., `"eth:1:0xd8dA..."`). Activity comes from the project
+    /// transactions feed filtered to that wallet, one page at a time. Wallets
+    /// with no recent activity return an empty page rather than an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `wallet_id` is empty or the query is invalid
+    /// (zero `page`/`per_page`, or a time range that ends before it starts).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use tndrly::wallets::WalletActivityQuery;
+    ///
+    /// let mut query = WalletActivityQuery::new().per_page(50).since(1_700_000_000);
+    /// loop {
+    ///     let page = client.wallets().get_activity("eth:1:0xd8dA...", Some(query.clone())).await?;
+    ///     for tx in &page.activity {
+    ///         println!("{} {:?}", tx.hash, tx.method);
+    ///     }
+    ///     match page.next_query(&query) {
+    ///         Some(next) => query = next,
+    ///         None => break,
+    ///     }
+    /// }
+    /// ```
+    pub async fn get_activity(
+        &self,
+        wallet_id: &str,
+        query: Option<WalletActivityQuery>,
+    ) -> Result<WalletActivityPage> {
+        if wallet_id.trim().is_empty() {
+            return Err(crate::error::invalid_param("wallet_id must not be empty"));
+        }
+        let query = query.unwrap_or_default();
+        query.validate().map_err(crate::error::invalid_param)?;
+
+        let request = ActivityRequest {
+            wallet_id,
+            page: query.page.unwrap_or(1),
+            per_page: query
+                .per_page
+                .unwrap_or(WalletActivityQuery::DEFAULT_PER_PAGE),
+            from_timestamp: query.from_timestamp,
+            to_timestamp: query.to_timestamp,
+        };
+        let response: WalletActivityResponse = self
+            .client
+            .get_with_query("/transactions", &request)
+            .await?;
+
+        Ok(request.into_page(response))
+    }
+}
+
+/// Query string for the project transactions feed, filtered to one wallet
+#[derive(serde::Serialize)]
+struct ActivityRequest<'q> {
+    #[serde(rename = "contractId[]")]
+    wallet_id: &'q str,
+    page: u32,
+    #[serde(rename = "perPage")]
+    per_page: u32,
+    #[serde(rename = "fromTimestamp", skip_serializing_if = "Option::is_none")]
+    from_timestamp: Option<u64>,
+    #[serde(rename = "toTimestamp", skip_serializing_if = "Option::is_none")]
+    to_timestamp: Option<u64>,
+}
+
+impl ActivityRequest<'_> {
+    fn into_page(self, response: WalletActivityResponse) -> WalletActivityPage {
+        let has_more = response.transactions.len() >= self.per_page as usize;
+        WalletActivityPage {
+            activity: response.transactions,
+            page: self.page,
+            per_page: self.per_page,
+            has_more,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -138,4 +224,86 @@ mod tests {
         assert_eq!(json["network_ids"][0], "1");
         assert_eq!(json["network_ids"][1], "137");
     }
+
+    #[test]
+    fn test_activity_request_query_string() {
+        let query = WalletActivityQuery::new().per_page(2).since(1_700_000_000);
+        let request = ActivityRequest {
+            wallet_id: "eth:1:0xabc",
+            page: 1,
+            per_page: 2,
+            from_timestamp: query.from_timestamp,
+            to_timestamp: query.to_timestamp,
+        };
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["contractId[]"], "eth:1:0xabc");
+        assert_eq!(json["perPage"], 2);
+        assert_eq!(json["fromTimestamp"], 1_700_000_000u64);
+        assert!(json.get("toTimestamp").is_none());
+    }
+
+    #[test]
+    fn test_activity_query_validation() {
+        assert!(WalletActivityQuery::new().validate().is_ok());
+        assert!(WalletActivityQuery::new().page(0).validate().is_err());
+        assert!(WalletActivityQuery::new().per_page(0).validate().is_err());
+        assert!(WalletActivityQuery::new()
+            .since(200)
+            .until(100)
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_activity_page_pagination() {
+        let response: WalletActivityResponse = serde_json::from_str(
+            r#"{"transactions": [
+                {"hash": "0x01", "network_id": "1", "block_number": 100,
+                 "timestamp": "2024-01-01T00:00:00Z", "from": "0xabc", "to": "0xdef",
+                 "value": "1000", "status": true, "gas_used": 21000, "method": "transfer",
+                 "events": [{"name": "Transfer", "contract": "0xdef", "inputs": {"value": "1000"}}]},
+                {"hash": "0x02", "status": false, "events": null}
+            ]}"#,
+        )
+        .unwrap();
+        let query = WalletActivityQuery::new().per_page(2).until(1_800_000_000);
+        let request = ActivityRequest {
+            wallet_id: "eth:1:0xabc",
+            page: 1,
+            per_page: 2,
+            from_timestamp: None,
+            to_timestamp: query.to_timestamp,
+        };
+
+        let page = request.into_page(response);
+        assert_eq!(page.activity.len(), 2);
+        assert_eq!(page.activity[0].events[0].name.as_deref(), Some("Transfer"));
+        assert!(!page.activity[1].status);
+        assert!(page.activity[1].events.is_empty());
+        assert!(page.has_more);
+
+        let next = page.next_query(&query).unwrap();
+        assert_eq!(next.page, Some(2));
+        assert_eq!(next.per_page, Some(2));
+        assert_eq!(next.to_timestamp, Some(1_800_000_000));
+    }
+
+    #[test]
+    fn test_activity_no_recent_activity_is_empty_page() {
+        for body in [r#"{"transactions": null}"#, "{}"] {
+            let response: WalletActivityResponse = serde_json::from_str(body).unwrap();
+            let request = ActivityRequest {
+                wallet_id: "eth:1:0xabc",
+                page: 1,
+                per_page: WalletActivityQuery::DEFAULT_PER_PAGE,
+                from_timestamp: None,
+                to_timestamp: None,
+            };
+            let page = request.into_page(response);
+            assert!(page.is_empty());
+            assert!(!page.has_more);
+            assert!(page.next_query(&WalletActivityQuery::new()).is_none());
+        }
+    }
 }
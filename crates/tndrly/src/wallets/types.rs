@@ -187,3 +187,187 @@ pub struct WalletTag {
     #[serde(default)]
     pub created_at: Option<String>,
 }
+
+/// Query parameters for a wallet's activity feed
+///
+/// Tenderly paginates with a 1-indexed `page` and `perPage`; the response
+/// carries no total, so a short page means there are no more results.
+#[derive(Debug, Clone, Default)]
+pub struct WalletActivityQuery {
+    /// Page number (1-indexed)
+    pub page: Option<u32>,
+
+    /// Items per page
+    pub per_page: Option<u32>,
+
+    /// Only include activity at or after this Unix timestamp (seconds)
+    pub from_timestamp: Option<u64>,
+
+    /// Only include activity at or before this Unix timestamp (seconds)
+    pub to_timestamp: Option<u64>,
+}
+
+impl WalletActivityQuery {
+    /// Default page size used when `per_page` is not set
+    pub const DEFAULT_PER_PAGE: u32 = 20;
+
+    /// Create a new query
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set page number (1-indexed)
+    #[must_use]
+    pub fn page(mut self, page: u32) -> Self {
+        self.page = Some(page);
+        self
+    }
+
+    /// Set items per page
+    #[must_use]
+    pub fn per_page(mut self, per_page: u32) -> Self {
+        self.per_page = Some(per_page);
+        self
+    }
+
+    /// Only include activity at or after this Unix timestamp (seconds)
+    #[must_use]
+    pub fn since(mut self, timestamp: u64) -> Self {
+        self.from_timestamp = Some(timestamp);
+        self
+    }
+
+    /// Only include activity at or before this Unix timestamp (seconds)
+    #[must_use]
+    pub fn until(mut self, timestamp: u64) -> Self {
+        self.to_timestamp = Some(timestamp);
+        self
+    }
+
+    /// Validate the request before sending
+    ///
+    /// Returns an error if:
+    /// - `page` or `per_page` is zero
+    /// - `from_timestamp` is after `to_timestamp`
+    pub fn validate(&self) -> Result<(), String> {
+        if self.page == Some(0) {
+            return Err("page is 1-indexed and must be at least 1".to_string());
+        }
+        if self.per_page == Some(0) {
+            return Err("per_page must be at least 1".to_string());
+        }
+        if let (Some(from), Some(to)) = (self.from_timestamp, self.to_timestamp) {
+            if from > to {
+                return Err(format!(
+                    "from_timestamp ({from}) must not be after to_timestamp ({to})"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A transaction Tenderly observed for a monitored wallet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletActivity {
+    /// Transaction hash
+    pub hash: String,
+
+    /// Network ID (e.g., "1")
+    #[serde(default)]
+    pub network_id: Option<String>,
+
+    /// Block number
+    #[serde(default)]
+    pub block_number: Option<u64>,
+
+    /// Block timestamp (RFC 3339)
+    #[serde(default)]
+    pub timestamp: Option<String>,
+
+    /// Sender address
+    #[serde(default)]
+    pub from: Option<String>,
+
+    /// Recipient address (`None` for contract creation)
+    #[serde(default)]
+    pub to: Option<String>,
+
+    /// Value in wei (decimal string)
+    #[serde(default)]
+    pub value: Option<String>,
+
+    /// Whether the transaction succeeded
+    #[serde(default)]
+    pub status: bool,
+
+    /// Gas used
+    #[serde(default)]
+    pub gas_used: Option<u64>,
+
+    /// Called method name, if decoded
+    #[serde(default)]
+    pub method: Option<String>,
+
+    /// Events emitted by the transaction, if decoded
+    #[serde(default, deserialize_with = "crate::utils::deserialize_null_default")]
+    pub events: Vec<WalletActivityEvent>,
+}
+
+/// A decoded event emitted in a wallet transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletActivityEvent {
+    /// Event name (e.g., "Transfer")
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// Emitting contract address
+    #[serde(default)]
+    pub contract: Option<String>,
+
+    /// Decoded event parameters
+    #[serde(default)]
+    pub inputs: Option<serde_json::Value>,
+}
+
+/// One page of a wallet's activity feed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletActivityPage {
+    /// Activity entries, newest first
+    pub activity: Vec<WalletActivity>,
+
+    /// Page number returned (1-indexed)
+    pub page: u32,
+
+    /// Page size requested
+    pub per_page: u32,
+
+    /// Whether another page may follow (the page was full)
+    pub has_more: bool,
+}
+
+impl WalletActivityPage {
+    /// Whether the page has no entries
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.activity.is_empty()
+    }
+
+    /// Query for the next page, keeping the same filters
+    #[must_use]
+    pub fn next_query(&self, query: &WalletActivityQuery) -> Option<WalletActivityQuery> {
+        self.has_more.then(|| WalletActivityQuery {
+            page: Some(self.page + 1),
+            per_page: Some(self.per_page),
+            ..query.clone()
+        })
+    }
+}
+
+/// Raw activity response (`transactions` is `null` when there is no activity)
+#[derive(Debug, Clone, Default, Deserialize)]
+pub(crate) struct WalletActivityResponse {
+    #[serde(default, deserialize_with = "crate::utils::deserialize_null_default")]
+    pub transactions: Vec<WalletActivity>,
+}
@@ -5,7 +5,7 @@ use alloy::primitives::Address;
 use crate::error::Result;
 use crate::lens::LensClient;
 use crate::subgraph::{SubgraphClient, SubgraphConfig};
-use crate::types::{PoolData, PoolDayData, PoolState, Swap};
+use crate::types::{PairReserves, PoolData, PoolDayData, PoolState, Swap};
 
 /// Configuration for the unified Uniswap client
 #[derive(Debug, Clone)]
@@ -98,6 +98,13 @@ impl Client {
         self.lens.get_pool_state(pool).await
     }
 
+    /// Get V2 pair reserves and TWAP price accumulators from on-chain
+    ///
+    /// See [`PairReserves`] for deriving a TWAP from two readings.
+    pub async fn get_pair_reserves_v2(&self, pair: Address) -> Result<PairReserves> {
+        self.lens.get_pair_reserves_v2(pair).await
+    }
+
     /// Get current pool liquidity from on-chain
     pub async fn get_liquidity(&self, pool: Address) -> Result<u128> {
         self.lens.get_liquidity(pool).await
@@ -3,12 +3,13 @@
 //! This module uses ephemeral lens contracts to query pool state directly
 //! from the blockchain without requiring any API keys.
 
+use alloy::eips::{BlockId, BlockNumberOrTag};
 use alloy::primitives::{Address, U256};
 use alloy::providers::{Provider, ProviderBuilder};
 use url::Url;
 
-use crate::error::{lens_error, rpc_error, Result};
-use crate::types::PoolState;
+use crate::error::{lens_error, pool_not_found, rpc_error, Result};
+use crate::types::{PairReserves, PoolState};
 
 /// Well-known Uniswap factory addresses
 pub mod factories {
@@ -255,6 +256,75 @@ impl LensClient {
         })
     }
 
+    /// Get V2 pair reserves and price accumulators
+    ///
+    /// All three calls (`getReserves`, `price0CumulativeLast`,
+    /// `price1CumulativeLast`) are pinned to the same block so the values are
+    /// consistent for TWAP math. Returns a pool-not-found error if `pair` has
+    /// no contract code.
+    pub async fn get_pair_reserves_v2(&self, pair: Address) -> Result<PairReserves> {
+        let block = self
+            .provider
+            .get_block_by_number(BlockNumberOrTag::Latest)
+            .await
+            .map_err(|e| rpc_error(e.to_string()))?
+            .ok_or_else(|| rpc_error("latest block not available"))?;
+        let block_id = BlockId::number(block.header.number);
+
+        let code = self
+            .provider
+            .get_code_at(pair)
+            .block_id(block_id)
+            .await
+            .map_err(|e| rpc_error(e.to_string()))?;
+        if code.is_empty() {
+            return Err(pool_not_found(format!("no V2 pair deployed at {pair}")));
+        }
+
+        // getReserves() / price0CumulativeLast() / price1CumulativeLast()
+        let (reserves, price0, price1) = tokio::try_join!(
+            self.call_at(pair, 0x0902f1ac, block_id, "getReserves"),
+            self.call_at(pair, 0x5909c0d5, block_id, "price0CumulativeLast"),
+            self.call_at(pair, 0x5a3d5493, block_id, "price1CumulativeLast"),
+        )?;
+
+        // (uint112 reserve0, uint112 reserve1, uint32 blockTimestampLast)
+        if reserves.len() < 96 || price0.len() < 32 || price1.len() < 32 {
+            return Err(pool_not_found(format!(
+                "{pair} does not look like a V2 pair (unexpected getReserves/cumulative response)"
+            )));
+        }
+
+        Ok(PairReserves {
+            reserve0: u128::from_be_bytes(reserves[16..32].try_into().unwrap()),
+            reserve1: u128::from_be_bytes(reserves[48..64].try_into().unwrap()),
+            block_timestamp_last: u32::from_be_bytes(reserves[92..96].try_into().unwrap()),
+            price0_cumulative: U256::from_be_slice(&price0[0..32]),
+            price1_cumulative: U256::from_be_slice(&price1[0..32]),
+            block_number: block.header.number,
+            block_timestamp: block.header.timestamp,
+        })
+    }
+
+    /// Call a zero-argument function at a given block
+    async fn call_at(
+        &self,
+        to: Address,
+        selector: u32,
+        block: BlockId,
+        name: &str,
+    ) -> Result<alloy::primitives::Bytes> {
+        let call_request = alloy::rpc::types::TransactionRequest::default()
+            .to(to)
+            .input(selector.to_be_bytes().to_vec().into());
+
+        self.provider
+            .call(call_request)
+            .block(block)
+            .await
+            .map_err(|e| lens_error(format!("{name} call failed: {e}")))
+    }
+
     /// Get pool liquidity
     pub async fn get_liquidity(&self, pool: Address) -> Result<u128> {
         // liquidity() selector
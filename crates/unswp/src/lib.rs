@@ -76,7 +76,8 @@ pub mod types;
 pub use client::{Client, Config};
 pub use error::{Error, Result};
 pub use types::{
-    PairDataV2, Pool, PoolData, PoolDataV4, PoolDayData, PoolState, Quote, Swap, Token,
+    PairDataV2, PairReserves, Pool, PoolData, PoolDataV4, PoolDayData, PoolState, Quote, Swap,
+    Token,
 };
 
 // Re-export commonly used items from submodules
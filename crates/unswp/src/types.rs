@@ -41,6 +41,93 @@ pub struct PoolState {
     pub unlocked: bool,
 }
 
+/// V2 pair reserves and price accumulators from on-chain query
+///
+/// `price0_cumulative` and `price1_cumulative` are the pair's
+/// `price{0,1}CumulativeLast` values: running sums of the UQ112x112 price
+/// multiplied by seconds elapsed, updated only on the first trade of a block.
+///
+/// To derive a TWAP, take two readings some time apart and pass them to
+/// [`PairReserves::twap`]. It brings each reading's accumulators up to its
+/// block timestamp (as `UniswapV2OracleLibrary.currentCumulativePrices`
+/// does), then divides the difference by the seconds between the readings:
+///
+/// ```text
+/// twap0 = (cumulative0(t1) - cumulative0(t0)) / (t1 - t0) / 2^112
+/// ```
+///
+/// Subtraction wraps, matching the on-chain overflow semantics. Prices are
+/// in raw token units; scale by `10^(decimals0 - decimals1)` for display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PairReserves {
+    /// Reserve of token0 (uint112)
+    pub reserve0: u128,
+    /// Reserve of token1 (uint112)
+    pub reserve1: u128,
+    /// Timestamp (mod 2^32) of the last block the reserves changed in
+    pub block_timestamp_last: u32,
+    /// `price0CumulativeLast`: token1 per token0 as UQ112x112 × seconds
+    pub price0_cumulative: U256,
+    /// `price1CumulativeLast`: token0 per token1 as UQ112x112 × seconds
+    pub price1_cumulative: U256,
+    /// Block the values were read at
+    pub block_number: u64,
+    /// Timestamp of the block the values were read at
+    pub block_timestamp: u64,
+}
+
+impl PairReserves {
+    /// Cumulative prices as of the read block's timestamp
+    ///
+    /// Adds the time elapsed since `block_timestamp_last` at the current
+    /// reserves, so readings without a trade in between still advance.
+    #[must_use]
+    pub fn current_cumulative_prices(&self) -> (U256, U256) {
+        // Truncation to u32 mirrors the pair's `block.timestamp % 2**32`
+        let elapsed = (self.block_timestamp as u32).wrapping_sub(self.block_timestamp_last);
+        if elapsed == 0 || self.reserve0 == 0 || self.reserve1 == 0 {
+            return (self.price0_cumulative, self.price1_cumulative);
+        }
+
+        let elapsed = U256::from(elapsed);
+        let price0: U256 = (U256::from(self.reserve1) << 112) / U256::from(self.reserve0);
+        let price1: U256 = (U256::from(self.reserve0) << 112) / U256::from(self.reserve1);
+        (
+            self.price0_cumulative
+                .wrapping_add(price0.wrapping_mul(elapsed)),
+            self.price1_cumulative
+                .wrapping_add(price1.wrapping_mul(elapsed)),
+        )
+    }
+
+    /// Time-weighted average prices between two readings of the same pair
+    ///
+    /// Returns `(token1 per token0, token0 per token1)` in raw units, or
+    /// `None` if `later` is not after `earlier`.
+    #[must_use]
+    pub fn twap(earlier: &Self, later: &Self) -> Option<(f64, f64)> {
+        let elapsed = later.block_timestamp.checked_sub(earlier.block_timestamp)?;
+        if elapsed == 0 {
+            return None;
+        }
+
+        let (start0, start1) = earlier.current_cumulative_prices();
+        let (end0, end1) = later.current_cumulative_prices();
+        let elapsed = U256::from(elapsed);
+        let to_f64 = |avg: U256| f64::from(avg) / 2f64.powi(112);
+        Some((
+            to_f64(end0.wrapping_sub(start0) / elapsed),
+            to_f64(end1.wrapping_sub(start1) / elapsed),
+        ))
+    }
+
+    /// Spot price of token0 in token1 (raw units) from the reserves
+    #[must_use]
+    pub fn spot_price0(&self) -> Option<f64> {
+        (self.reserve0 != 0).then(|| self.reserve1 as f64 / self.reserve0 as f64)
+    }
+}
+
 /// Quote result for a swap
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Quote {
@@ -93,6 +93,58 @@ fn test_sdk_reexports() {
     let _ = std::any::type_name::<unswp::v3_sdk::prelude::Pool>();
 }
 
+#[test]
+fn test_pair_reserves_twap() {
+    use alloy::primitives::U256;
+    use unswp::PairReserves;
+
+    // No trades between readings: the TWAP comes from the counterfactual
+    // accumulation at the current reserves
+    let earlier = PairReserves {
+        reserve0: 1_000,
+        reserve1: 2_000,
+        block_timestamp_last: 100,
+        price0_cumulative: U256::ZERO,
+        price1_cumulative: U256::ZERO,
+        block_number: 1,
+        block_timestamp: 100,
+    };
+    let later = PairReserves {
+        block_number: 5,
+        block_timestamp: 160,
+        ..earlier
+    };
+
+    let (twap0, twap1) = PairReserves::twap(&earlier, &later).unwrap();
+    assert!((twap0 - 2.0).abs() < 1e-12);
+    assert!((twap1 - 0.5).abs() < 1e-12);
+    assert_eq!(earlier.spot_price0(), Some(2.0));
+
+    // Accumulators wrap around U256::MAX like they do on-chain
+    let wrapped = PairReserves {
+        price0_cumulative: U256::MAX - (U256::from(2u8) << 112) * U256::from(10u8)
+            + U256::from(1u8),
+        price1_cumulative: U256::MAX,
+        block_timestamp: 110,
+        block_timestamp_last: 110,
+        ..earlier
+    };
+    let after_wrap = PairReserves {
+        price0_cumulative: (U256::from(2u8) << 112) * U256::from(10u8),
+        price1_cumulative: (U256::from(1u8) << 111) * U256::from(20u8) - U256::from(1u8),
+        block_timestamp: 130,
+        block_timestamp_last: 130,
+        ..earlier
+    };
+    let (twap0, twap1) = PairReserves::twap(&wrapped, &after_wrap).unwrap();
+    assert!((twap0 - 2.0).abs() < 1e-12);
+    assert!((twap1 - 0.5).abs() < 1e-12);
+
+    // Same or reversed readings have no TWAP
+    assert!(PairReserves::twap(&later, &later).is_none());
+    assert!(PairReserves::twap(&later, &earlier).is_none());
+}
+
 // Integration tests that require network access
 // Run with: cargo test -p unswp --test integration -- --ignored
 
@@ -142,3 +194,25 @@ async fn test_fetch_liquidity() {
         "Liquidity should be positive for active pool"
     );
 }
+
+#[tokio::test]
+#[ignore = "requires network access"]
+async fn test_fetch_pair_reserves_v2() {
+    use unswp::pools;
+
+    let client = Client::mainnet("https://eth.llamarpc.com").expect("Client creation failed");
+
+    let reserves = client
+        .get_pair_reserves_v2(pools::v2::MAINNET_WETH_USDC)
+        .await
+        .expect("Should fetch pair reserves");
+    assert!(reserves.reserve0 > 0 && reserves.reserve1 > 0);
+    assert!(!reserves.price0_cumulative.is_zero());
+
+    // An address without code is not a pair
+    let err = client
+        .get_pair_reserves_v2(alloy::primitives::Address::repeat_byte(0x42))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Pool not found"), "{err}");
+}
@@ -1,11 +1,68 @@
 //! Error types for the Velora (`ParaSwap`) API client
 //!
-//! This module re-exports the shared `ApiError` type from `yldfi_common`.
+//! This module provides the error types for the Velora API client,
+//! built on top of the shared `ApiError` infrastructure.
 
-pub use yldfi_common::api::{ApiError, ApiResult};
+use thiserror::Error;
+pub use yldfi_common::api::ApiError;
+
+/// Domain-specific errors for Velora API
+#[derive(Error, Debug)]
+pub enum DomainError {
+    /// Invalid request parameter
+    #[error("Invalid parameter: {0}")]
+    InvalidParam(String),
+
+    /// Partner fee settings were rejected, locally or by the API
+    #[error("Partner fee misconfigured: {0}")]
+    PartnerFee(String),
+}
 
 /// Error type for Velora API operations
-pub type Error = ApiError;
+pub type Error = ApiError<DomainError>;
 
 /// Result type for Velora API operations
-pub type Result<T> = ApiResult<T>;
+pub type Result<T> = std::result::Result<T, Error>;
+
+// Convenience constructors for domain errors
+/// Create an invalid parameter error
+pub fn invalid_param(message: impl Into<String>) -> Error {
+    ApiError::domain(DomainError::InvalidParam(message.into()))
+}
+
+/// Create a partner fee error
+pub fn partner_fee(message: impl Into<String>) -> Error {
+    ApiError::domain(DomainError::PartnerFee(message.into()))
+}
+
+/// Turn an API rejection of the partner fee fields into
+/// [`DomainError::PartnerFee`], leaving other errors unchanged
+///
+/// `ParaSwap` reports these as a 400 with `{"error": "..."}` naming the
+/// offending field.
+pub(crate) fn classify_transaction_error(error: Error) -> Error {
+    let ApiError::Api {
+        status: 400,
+        message,
+    } = &error
+    else {
+        return error;
+    };
+    let text = serde_json::from_str::<crate::types::ApiErrorResponse>(message)
+        .map_or_else(|_| message.clone(), |body| body.error);
+    let lower = text.to_ascii_lowercase();
+    let is_fee = [
+        "partnerfee",
+        "partneraddress",
+        "takesurplus",
+        "partner fee",
+        "surplus",
+    ]
+    .iter()
+    .any(|needle| lower.contains(needle));
+    if is_fee {
+        partner_fee(text)
+    } else {
+        error
+    }
+}
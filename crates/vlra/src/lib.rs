@@ -38,10 +38,10 @@
 pub mod error;
 pub mod types;
 
-pub use error::{Error, Result};
+pub use error::{DomainError, Error, Result};
 pub use types::{
-    ApiErrorResponse, Chain, PriceRequest, PriceResponse, PriceRoute, Route, Side, Swap,
-    SwapExchange, Token, TokenListResponse, TransactionRequest, TransactionResponse,
+    ApiErrorResponse, Chain, PriceRequest, PriceResponse, PriceRoute, Route, RouteSummary, Side,
+    Swap, SwapExchange, Token, TokenListResponse, TransactionRequest, TransactionResponse,
 };
 
 // Re-export common utilities
@@ -168,7 +168,10 @@ impl Client {
 
     /// Build a transaction from a price route
     ///
-    /// Returns transaction data ready for signing and sending.
+    /// Returns transaction data ready for signing and sending. The request is
+    /// checked with [`TransactionRequest::validate`] first, and API
+    /// rejections of the partner fee fields come back as
+    /// [`DomainError::PartnerFee`].
     ///
     /// # Arguments
     ///
@@ -203,6 +206,26 @@ impl Client {
     ///     println!("Send to: {}", tx.to);
     ///     println!("Data: {}", tx.data);
     ///
+    ///     // Buy exactly 1000 USDC, delivered to another wallet, with a 0.25%
+    ///     // partner fee
+    ///     let buy_request = PriceRequest::buy(
+    ///         "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE",
+    ///         "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+    ///         "1000000000",
+    ///     );
+    ///     let quote = client.get_price(Chain::Ethereum, &buy_request).await?;
+    ///     let tx_request = TransactionRequest::buy(
+    ///         &quote.price_route,
+    ///         "0xYourWalletAddress",
+    ///         "1000000000",
+    ///         50,
+    ///     )
+    ///     .with_receiver("0xRecipientAddress")
+    ///     .with_partner("0xYourFeeWallet", 25, false)
+    ///     .with_deadline(1_900_000_000);
+    ///     let tx = client.build_transaction(Chain::Ethereum, &tx_request).await?;
+    ///     println!("Value: {}", tx.value);
+    ///
     ///     Ok(())
     /// }
     /// ```
@@ -211,10 +234,12 @@ impl Client {
         chain: Chain,
         request: &TransactionRequest,
     ) -> Result<TransactionResponse> {
+        request.validate()?;
         let path = format!("/transactions/{}", chain.chain_id());
         self.base
             .post_json_with_headers(&path, request, self.build_headers())
             .await
+            .map_err(error::classify_transaction_error)
     }
 
     /// Get available tokens for a network
@@ -284,4 +309,138 @@ mod tests {
         assert_eq!(config.get_api_key(), Some("my-key"));
         assert_eq!(config.base_url, DEFAULT_BASE_URL);
     }
+
+    mod build_transaction {
+        use super::*;
+        use crate::types::tests::buy_route;
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        async fn client_for(server: &MockServer) -> Client {
+            Client::with_config(ApiConfig::new(server.uri())).unwrap()
+        }
+
+        #[tokio::test]
+        async fn test_buy_side_transaction() {
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(path("/transactions/1"))
+                .and(body_partial_json(serde_json::json!({
+                    "destAmount": "1000000000",
+                    "slippage": 50,
+                    "receiver": "0x2222222222222222222222222222222222222222",
+                    "partnerAddress": "0x3333333333333333333333333333333333333333",
+                    "partnerFeeBps": 25,
+                    "takeSurplus": false,
+                    "deadline": 1900000000u64
+                })))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_string(include_str!("../tests/fixtures/transaction_buy.json")),
+                )
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            let request = TransactionRequest::buy(
+                &buy_route(),
+                "0x1111111111111111111111111111111111111111",
+                "1000000000",
+                50,
+            )
+            .with_receiver("0x2222222222222222222222222222222222222222")
+            .with_partner("0x3333333333333333333333333333333333333333", 25, false)
+            .with_deadline(1_900_000_000);
+
+            let tx = client_for(&server)
+                .await
+                .build_transaction(Chain::Ethereum, &request)
+                .await
+                .unwrap();
+            assert_eq!(tx.chain_id, 1);
+            assert_eq!(tx.to, "0x6a000f20005980200259b80c5102003040001068");
+            assert_eq!(tx.value, "407216315468926413");
+            assert!(tx.data.starts_with("0x"));
+        }
+
+        #[tokio::test]
+        async fn test_fee_rejection_is_typed() {
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                    "error": "Invalid partnerFeeBps: must be at most 200"
+                })))
+                .mount(&server)
+                .await;
+
+            let request = TransactionRequest::buy(
+                &buy_route(),
+                "0x1111111111111111111111111111111111111111",
+                "1000000000",
+                50,
+            )
+            .with_partner("0x3333333333333333333333333333333333333333", 500, true);
+
+            let err = client_for(&server)
+                .await
+                .build_transaction(Chain::Ethereum, &request)
+                .await
+                .unwrap_err();
+            match err {
+                Error::Domain(DomainError::PartnerFee(message)) => {
+                    assert_eq!(message, "Invalid partnerFeeBps: must be at most 200");
+                }
+                other => panic!("expected partner fee error, got {other:?}"),
+            }
+        }
+
+        #[tokio::test]
+        async fn test_other_rejections_stay_api_errors() {
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                    "error": "Not enough USDC balance"
+                })))
+                .mount(&server)
+                .await;
+
+            let request = TransactionRequest::new(
+                &buy_route(),
+                "0x1111111111111111111111111111111111111111",
+                50,
+            );
+            let err = client_for(&server)
+                .await
+                .build_transaction(Chain::Ethereum, &request)
+                .await
+                .unwrap_err();
+            assert!(matches!(err, Error::Api { status: 400, .. }), "{err:?}");
+        }
+
+        #[tokio::test]
+        async fn test_invalid_request_is_not_sent() {
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .respond_with(ResponseTemplate::new(200))
+                .expect(0)
+                .mount(&server)
+                .await;
+
+            let mut request = TransactionRequest::new(
+                &buy_route(),
+                "0x1111111111111111111111111111111111111111",
+                50,
+            );
+            request.src_amount = Some("1".to_string());
+            let err = client_for(&server)
+                .await
+                .build_transaction(Chain::Ethereum, &request)
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(err, Error::Domain(DomainError::InvalidParam(_))),
+                "{err:?}"
+            );
+        }
+    }
 }
@@ -4,6 +4,11 @@
 //! including price routing, transaction building, and token lists.
 
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use yldfi_common::units::format_units;
+
+/// Placeholder address the API uses for the chain's native token
+const NATIVE_TOKEN: &str = "0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee";
 
 /// Supported chains for Velora/ParaSwap API
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -285,6 +290,92 @@ pub struct PriceResponse {
     pub price_route: PriceRoute,
 }
 
+impl PriceResponse {
+    /// Display-ready summary of the route
+    ///
+    /// The price route carries no token symbols, so the native token is
+    /// shown by its currency symbol and other tokens by a shortened address.
+    /// Use [`PriceResponse::summary_with_tokens`] to fill in real symbols.
+    #[must_use]
+    pub fn summary(&self) -> RouteSummary {
+        self.summary_with_tokens(&[])
+    }
+
+    /// Display-ready summary of the route, taking symbols from `tokens`
+    /// (e.g. the chain's token list)
+    #[must_use]
+    pub fn summary_with_tokens(&self, tokens: &[Token]) -> RouteSummary {
+        let route = &self.price_route;
+        RouteSummary {
+            src_symbol: token_symbol(&route.src_token, route.network, tokens),
+            dest_symbol: token_symbol(&route.dest_token, route.network, tokens),
+            src_human: format_units(&route.src_amount, route.src_decimals),
+            dest_human: format_units(&route.dest_amount, route.dest_decimals),
+            price_impact_pct: route.usd_price_impact_pct(),
+            best_route_dexs: route.dexs(),
+        }
+    }
+}
+
+/// Symbol for `address`: from `tokens`, the native currency, or a short
+/// address
+fn token_symbol(address: &str, network: u64, tokens: &[Token]) -> String {
+    if let Some(token) = tokens
+        .iter()
+        .find(|t| t.address.eq_ignore_ascii_case(address))
+    {
+        return token.symbol.clone();
+    }
+    if address.eq_ignore_ascii_case(NATIVE_TOKEN) {
+        return yldfi_common::Chain::from(network)
+            .native_currency()
+            .to_string();
+    }
+    match (
+        address.get(..6),
+        address.get(address.len().saturating_sub(4)..),
+    ) {
+        (Some(head), Some(tail)) if address.len() > 10 => format!("{head}…{tail}"),
+        _ => address.to_string(),
+    }
+}
+
+/// One-line summary of a price route, from [`PriceResponse::summary`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RouteSummary {
+    /// Source token symbol
+    pub src_symbol: String,
+    /// Destination token symbol
+    pub dest_symbol: String,
+    /// Source amount in token units (e.g. "1.5")
+    pub src_human: String,
+    /// Destination amount in token units
+    pub dest_human: String,
+    /// Price impact in percent, from the USD values (negative is a loss);
+    /// `None` when the API returned no USD data
+    pub price_impact_pct: Option<f64>,
+    /// Distinct DEXs used across all paths, in route order
+    pub best_route_dexs: Vec<String>,
+}
+
+impl fmt::Display for RouteSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} -> {} {} via {}",
+            self.src_human,
+            self.src_symbol,
+            self.dest_human,
+            self.dest_symbol,
+            self.best_route_dexs.join(", ")
+        )?;
+        if let Some(impact) = self.price_impact_pct {
+            write!(f, " (impact {impact:.2}%)")?;
+        }
+        Ok(())
+    }
+}
+
 /// Detailed price route information
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -319,12 +410,15 @@ pub struct PriceRoute {
     /// Estimated gas cost
     pub gas_cost: Option<String>,
     /// Gas cost in USD
+    #[serde(alias = "gasCostUSD")]
     pub gas_cost_usd: Option<String>,
     /// Side of the swap
     pub side: String,
     /// Source token USD value
+    #[serde(alias = "srcUSD")]
     pub src_usd: Option<String>,
     /// Destination token USD value
+    #[serde(alias = "destUSD")]
     pub dest_usd: Option<String>,
     /// Max impact percentage
     pub max_impact_reached: Option<bool>,
@@ -333,6 +427,39 @@ pub struct PriceRoute {
     pub price_impact: Option<String>,
 }
 
+impl PriceRoute {
+    /// Distinct exchange names used by the route, in the order they appear
+    #[must_use]
+    pub fn dexs(&self) -> Vec<String> {
+        let mut dexs: Vec<String> = Vec::new();
+        let exchanges = self
+            .best_route
+            .iter()
+            .flat_map(|route| &route.swaps)
+            .flat_map(|swap| &swap.swap_exchanges);
+        for exchange in exchanges {
+            if !dexs.contains(&exchange.exchange) {
+                dexs.push(exchange.exchange.clone());
+            }
+        }
+        dexs
+    }
+
+    /// Price impact in percent from the source and destination USD values
+    ///
+    /// Negative when the destination is worth less than the source. `None`
+    /// if either USD value is missing or the source value is zero.
+    #[must_use]
+    pub fn usd_price_impact_pct(&self) -> Option<f64> {
+        let src: f64 = self.src_usd.as_deref()?.parse().ok()?;
+        let dest: f64 = self.dest_usd.as_deref()?.parse().ok()?;
+        if src <= 0.0 || !dest.is_finite() {
+            return None;
+        }
+        Some((dest / src - 1.0) * 100.0)
+    }
+}
+
 /// Route segment in a multi-hop swap
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -380,56 +507,87 @@ pub struct SwapExchange {
 }
 
 /// Transaction build request
-#[derive(Debug, Clone, Serialize)]
+///
+/// Exactly one of `src_amount` (sell side) and `dest_amount` (buy side) is
+/// set; the other is derived by the API from the price route and
+/// `slippage`. [`crate::Client::build_transaction`] rejects requests that set
+/// both or neither.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionRequest {
     /// Source token address
     pub src_token: String,
+    /// Source token decimals
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub src_decimals: Option<u8>,
     /// Destination token address
     pub dest_token: String,
-    /// Source amount
-    pub src_amount: String,
-    /// Destination amount (from price route)
-    pub dest_amount: String,
+    /// Destination token decimals
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dest_decimals: Option<u8>,
+    /// Exact source amount to sell (sell side only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub src_amount: Option<String>,
+    /// Exact destination amount to buy (buy side only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dest_amount: Option<String>,
     /// Price route from `PriceResponse`
     pub price_route: serde_json::Value,
     /// Slippage tolerance in basis points (e.g., 100 = 1%)
     pub slippage: u32,
     /// User address executing the swap
     pub user_address: String,
-    /// Partner address for referral
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Partner name for referral tracking
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub partner: Option<String>,
+    /// Address that receives the partner fee
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partner_address: Option<String>,
+    /// Partner fee in basis points (e.g., 50 = 0.5%)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partner_fee_bps: Option<u32>,
+    /// Whether the partner collects positive slippage (surplus)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub take_surplus: Option<bool>,
     /// Receiver address (if different from user)
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub receiver: Option<String>,
-    /// Deadline timestamp (optional)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub deadline: Option<String>,
+    /// Deadline as a unix timestamp in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deadline: Option<u64>,
     /// Permit data for gasless approvals
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub permit: Option<String>,
     /// Ignore gas estimation
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub ignore_gas: Option<bool>,
     /// Ignore balance and allowance checks
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub ignore_checks: Option<bool>,
 }
 
 impl TransactionRequest {
     /// Create a new transaction request from a price route
+    ///
+    /// The exact amount follows the route's side: `src_amount` for a SELL
+    /// route, `dest_amount` for a BUY route.
     #[must_use]
     pub fn new(price_route: &PriceRoute, user_address: impl Into<String>, slippage: u32) -> Self {
+        let buy = price_route.side.eq_ignore_ascii_case("BUY");
         Self {
             src_token: price_route.src_token.clone(),
+            src_decimals: Some(price_route.src_decimals),
             dest_token: price_route.dest_token.clone(),
-            src_amount: price_route.src_amount.clone(),
-            dest_amount: price_route.dest_amount.clone(),
+            dest_decimals: Some(price_route.dest_decimals),
+            src_amount: (!buy).then(|| price_route.src_amount.clone()),
+            dest_amount: buy.then(|| price_route.dest_amount.clone()),
             price_route: serde_json::to_value(price_route).unwrap_or_default(),
             slippage,
             user_address: user_address.into(),
             partner: None,
+            partner_address: None,
+            partner_fee_bps: None,
+            take_surplus: None,
             receiver: None,
             deadline: None,
             permit: None,
@@ -438,6 +596,36 @@ impl TransactionRequest {
         }
     }
 
+    /// Create a buy-side (exact output) request for `dest_amount`
+    ///
+    /// `price_route` must come from a [`PriceRequest::buy`] quote.
+    #[must_use]
+    pub fn buy(
+        price_route: &PriceRoute,
+        user_address: impl Into<String>,
+        dest_amount: impl Into<String>,
+        slippage: u32,
+    ) -> Self {
+        Self::new(price_route, user_address, slippage).with_dest_amount(dest_amount)
+    }
+
+    /// Swap exactly `amount` of the source token, clearing any `dest_amount`
+    #[must_use]
+    pub fn with_src_amount(mut self, amount: impl Into<String>) -> Self {
+        self.src_amount = Some(amount.into());
+        self.dest_amount = None;
+        self
+    }
+
+    /// Receive exactly `amount` of the destination token, clearing any
+    /// `src_amount`
+    #[must_use]
+    pub fn with_dest_amount(mut self, amount: impl Into<String>) -> Self {
+        self.dest_amount = Some(amount.into());
+        self.src_amount = None;
+        self
+    }
+
     /// Set receiver address
     #[must_use]
     pub fn with_receiver(mut self, receiver: impl Into<String>) -> Self {
@@ -445,17 +633,32 @@ impl TransactionRequest {
         self
     }
 
-    /// Set partner address
+    /// Collect a partner fee of `fee_bps` to `address`, optionally taking
+    /// positive slippage as well
     #[must_use]
-    pub fn with_partner(mut self, partner: impl Into<String>) -> Self {
+    pub fn with_partner(
+        mut self,
+        address: impl Into<String>,
+        fee_bps: u32,
+        take_surplus: bool,
+    ) -> Self {
+        self.partner_address = Some(address.into());
+        self.partner_fee_bps = Some(fee_bps);
+        self.take_surplus = Some(take_surplus);
+        self
+    }
+
+    /// Set partner name for referral tracking
+    #[must_use]
+    pub fn with_partner_name(mut self, partner: impl Into<String>) -> Self {
         self.partner = Some(partner.into());
         self
     }
 
-    /// Set deadline timestamp
+    /// Set deadline as a unix timestamp in seconds
     #[must_use]
-    pub fn with_deadline(mut self, deadline: impl Into<String>) -> Self {
-        self.deadline = Some(deadline.into());
+    pub fn with_deadline(mut self, deadline: u64) -> Self {
+        self.deadline = Some(deadline);
         self
     }
 
@@ -472,6 +675,58 @@ impl TransactionRequest {
         self.ignore_checks = Some(ignore);
         self
     }
+
+    /// Whether this is a buy-side (exact output) request
+    #[must_use]
+    pub fn is_buy(&self) -> bool {
+        self.dest_amount.is_some()
+    }
+
+    /// Check the request before sending it
+    ///
+    /// Exactly one amount must be set and it must match the side of the
+    /// price route; partner fees need a fee address.
+    pub fn validate(&self) -> crate::Result<()> {
+        match (&self.src_amount, &self.dest_amount) {
+            (Some(_), Some(_)) => {
+                return Err(crate::error::invalid_param(
+                    "srcAmount and destAmount are mutually exclusive",
+                ))
+            }
+            (None, None) => {
+                return Err(crate::error::invalid_param(
+                    "one of srcAmount (sell) or destAmount (buy) is required",
+                ))
+            }
+            _ => {}
+        }
+
+        let route_side = self.price_route.get("side").and_then(|s| s.as_str());
+        if let Some(side) = route_side {
+            let route_buy = side.eq_ignore_ascii_case("BUY");
+            if route_buy != self.is_buy() {
+                return Err(crate::error::invalid_param(format!(
+                    "{} request needs a {} price route, got {side}",
+                    if self.is_buy() { "buy" } else { "sell" },
+                    if self.is_buy() { "BUY" } else { "SELL" },
+                )));
+            }
+        }
+
+        if self.partner_address.is_none()
+            && (self.partner_fee_bps.is_some_and(|bps| bps > 0) || self.take_surplus == Some(true))
+        {
+            return Err(crate::error::partner_fee(
+                "partnerFeeBps and takeSurplus require a partnerAddress",
+            ));
+        }
+        if self.partner_fee_bps.is_some_and(|bps| bps > 10_000) {
+            return Err(crate::error::partner_fee(
+                "partnerFeeBps cannot exceed 10000 (100%)",
+            ));
+        }
+        Ok(())
+    }
 }
 
 /// Transaction response ready for signing
@@ -532,7 +787,7 @@ pub struct ApiErrorResponse {
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
 
     #[test]
@@ -563,4 +818,210 @@ mod tests {
         assert_eq!(request.src_decimals, Some(18));
         assert_eq!(request.dest_decimals, Some(6));
     }
+
+    fn exchange(name: &str, percent: f64) -> serde_json::Value {
+        serde_json::json!({
+            "exchange": name,
+            "srcAmount": "0",
+            "destAmount": "0",
+            "percent": percent,
+        })
+    }
+
+    fn price_response(src_usd: Option<&str>, dest_usd: Option<&str>) -> PriceResponse {
+        serde_json::from_value(serde_json::json!({
+            "priceRoute": {
+                "blockNumber": 21000000,
+                "network": 1,
+                "srcToken": "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE",
+                "srcDecimals": 18,
+                "srcAmount": "1500000000000000000",
+                "destToken": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+                "destDecimals": 6,
+                "destAmount": "3712450000",
+                "bestRoute": [
+                    {
+                        "percent": 70.0,
+                        "swaps": [{
+                            "srcToken": "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE",
+                            "srcDecimals": 18,
+                            "destToken": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+                            "destDecimals": 6,
+                            "swapExchanges": [exchange("UniswapV3", 60.0), exchange("CurveV2", 40.0)]
+                        }]
+                    },
+                    {
+                        "percent": 30.0,
+                        "swaps": [
+                            {
+                                "srcToken": "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE",
+                                "srcDecimals": 18,
+                                "destToken": "0xdAC17F958D2ee523a2206206994597C13D831ec7",
+                                "destDecimals": 6,
+                                "swapExchanges": [exchange("UniswapV3", 100.0)]
+                            },
+                            {
+                                "srcToken": "0xdAC17F958D2ee523a2206206994597C13D831ec7",
+                                "srcDecimals": 6,
+                                "destToken": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+                                "destDecimals": 6,
+                                "swapExchanges": [exchange("BalancerV2", 100.0)]
+                            }
+                        ]
+                    }
+                ],
+                "tokenTransferProxy": "0x216b4b4ba9f3e719726886d34a177484278bfcae",
+                "contractAddress": "0xdef171fe48cf0115b1d80b88dc8eab59176fee57",
+                "contractMethod": "multiSwap",
+                "side": "SELL",
+                "srcUSD": src_usd,
+                "destUSD": dest_usd,
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_route_summary() {
+        let summary = price_response(Some("3725.00"), Some("3713.825")).summary();
+
+        assert_eq!(summary.src_symbol, "ETH");
+        assert_eq!(summary.dest_symbol, "0xA0b8…eB48");
+        assert_eq!(summary.src_human, "1.5");
+        assert_eq!(summary.dest_human, "3712.45");
+        assert_eq!(
+            summary.best_route_dexs,
+            ["UniswapV3", "CurveV2", "BalancerV2"]
+        );
+        let impact = summary.price_impact_pct.unwrap();
+        assert!((impact - -0.3).abs() < 1e-9, "{impact}");
+        assert_eq!(
+            summary.to_string(),
+            "1.5 ETH -> 3712.45 0xA0b8…eB48 via UniswapV3, CurveV2, BalancerV2 (impact -0.30%)"
+        );
+    }
+
+    /// Hand-written BUY route, see `tests/fixtures/README.md`
+    pub(crate) fn buy_route() -> PriceRoute {
+        serde_json::from_str(include_str!("../tests/fixtures/price_route_buy.json")).unwrap()
+    }
+
+    #[test]
+    fn test_sell_transaction_request_serialization() {
+        let route = price_response(None, None).price_route;
+        let request = TransactionRequest::new(&route, "0xuser", 100)
+            .with_partner_name("yldfi")
+            .with_ignore_checks(true);
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(json["srcAmount"], "1500000000000000000");
+        assert!(json.get("destAmount").is_none());
+        assert_eq!(json["srcDecimals"], 18);
+        assert_eq!(json["slippage"], 100);
+        assert_eq!(json["userAddress"], "0xuser");
+        assert_eq!(json["partner"], "yldfi");
+        assert_eq!(json["ignoreChecks"], true);
+        assert_eq!(json["priceRoute"]["side"], "SELL");
+        assert!(json.get("partnerAddress").is_none());
+        assert!(json.get("deadline").is_none());
+        assert!(request.validate().is_ok());
+
+        let back: TransactionRequest = serde_json::from_value(json).unwrap();
+        assert_eq!(back, request);
+    }
+
+    #[test]
+    fn test_buy_transaction_request_serialization() {
+        let request = TransactionRequest::buy(&buy_route(), "0xuser", "1000000000", 50)
+            .with_receiver("0xreceiver")
+            .with_partner("0xfees", 25, true)
+            .with_deadline(1_900_000_000);
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(json["destAmount"], "1000000000");
+        assert!(json.get("srcAmount").is_none());
+        assert_eq!(json["destDecimals"], 6);
+        assert_eq!(json["receiver"], "0xreceiver");
+        assert_eq!(json["partnerAddress"], "0xfees");
+        assert_eq!(json["partnerFeeBps"], 25);
+        assert_eq!(json["takeSurplus"], true);
+        assert_eq!(json["deadline"], 1_900_000_000u64);
+        assert_eq!(json["priceRoute"]["side"], "BUY");
+        assert!(request.is_buy());
+        assert!(request.validate().is_ok());
+
+        let back: TransactionRequest = serde_json::from_value(json).unwrap();
+        assert_eq!(back, request);
+    }
+
+    #[test]
+    fn test_new_follows_route_side() {
+        let request = TransactionRequest::new(&buy_route(), "0xuser", 50);
+        assert_eq!(request.dest_amount.as_deref(), Some("1000000000"));
+        assert_eq!(request.src_amount, None);
+
+        let request = request.with_src_amount("1");
+        assert_eq!(request.src_amount.as_deref(), Some("1"));
+        assert_eq!(request.dest_amount, None);
+    }
+
+    #[test]
+    fn test_transaction_request_validation() {
+        let is_invalid_param = |request: &TransactionRequest| {
+            matches!(
+                request.validate(),
+                Err(crate::Error::Domain(crate::DomainError::InvalidParam(_)))
+            )
+        };
+        let is_fee_error = |request: &TransactionRequest| {
+            matches!(
+                request.validate(),
+                Err(crate::Error::Domain(crate::DomainError::PartnerFee(_)))
+            )
+        };
+
+        let mut both = TransactionRequest::buy(&buy_route(), "0xuser", "1000000000", 50);
+        both.src_amount = Some("1".to_string());
+        assert!(is_invalid_param(&both));
+
+        let mut neither = TransactionRequest::buy(&buy_route(), "0xuser", "1000000000", 50);
+        neither.dest_amount = None;
+        assert!(is_invalid_param(&neither));
+
+        // A sell amount against a BUY route
+        let wrong_side = TransactionRequest::new(&buy_route(), "0xuser", 50).with_src_amount("1");
+        assert!(is_invalid_param(&wrong_side));
+
+        let mut no_address = TransactionRequest::new(&buy_route(), "0xuser", 50);
+        no_address.partner_fee_bps = Some(25);
+        assert!(is_fee_error(&no_address));
+
+        let too_high = TransactionRequest::new(&buy_route(), "0xuser", 50)
+            .with_partner("0xfees", 10_001, false);
+        assert!(is_fee_error(&too_high));
+    }
+
+    #[test]
+    fn test_buy_transaction_response_fixture() {
+        let tx: TransactionResponse =
+            serde_json::from_str(include_str!("../tests/fixtures/transaction_buy.json")).unwrap();
+        assert_eq!(tx.chain_id, 1);
+        assert_eq!(tx.gas_price.as_deref(), Some("18250000000"));
+        assert_eq!(tx.gas, None);
+    }
+
+    #[test]
+    fn test_route_summary_with_tokens_and_no_usd() {
+        let usdc: Token = serde_json::from_value(serde_json::json!({
+            "address": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+            "symbol": "USDC",
+            "decimals": 6,
+        }))
+        .unwrap();
+
+        let summary = price_response(None, Some("3713.825")).summary_with_tokens(&[usdc]);
+        assert_eq!(summary.dest_symbol, "USDC");
+        assert_eq!(summary.price_impact_pct, None);
+        assert!(!summary.to_string().contains("impact"));
+    }
 }
@@ -1,17 +1,25 @@
 //! HTTP client for Yearn's Kong GraphQL API
 
 use reqwest::Client as HttpClient;
-use std::sync::Arc;
-use std::time::Duration;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 use yldfi_common::api::{extract_retry_after, ApiConfig};
 use yldfi_common::rate_limit::RateLimiter;
 
 use crate::error::{from_response, graphql_error, Error, Result};
-use crate::types::GraphQLResponse;
+use crate::gas::{self, GAS_PRICE_CACHE_TTL};
+use crate::types::{GraphQLResponse, RiskWeights};
 
 /// Base URL for Kong API
 pub const BASE_URL: &str = "https://kong.yearn.farm/api/gql";
 
+/// Maximum queries in flight when one call fans out over chains, vaults or days
+pub const FAN_OUT_CONCURRENCY: usize = 8;
+
 /// Configuration for the Kong API client
 ///
 /// Use the builder pattern to customize timeouts, proxy, and rate limiting.
@@ -34,6 +42,15 @@ pub struct Config {
     inner: ApiConfig,
     /// Optional rate limiter
     rate_limiter: Option<RateLimiter>,
+    /// Optional cap on in-flight requests
+    max_concurrent_requests: Option<usize>,
+    /// Optional JSON-RPC gas oracle overriding the per-chain defaults
+    gas_oracle_url: Option<String>,
+    /// Weights for the composite vault risk score
+    risk_weights: RiskWeights,
+    /// Optional WebSocket endpoint for GraphQL subscriptions
+    #[cfg(feature = "websocket")]
+    subscription_url: Option<String>,
 }
 
 impl Config {
@@ -42,6 +59,11 @@ impl Config {
         Self {
             inner: ApiConfig::new(BASE_URL),
             rate_limiter: None,
+            max_concurrent_requests: None,
+            gas_oracle_url: None,
+            risk_weights: RiskWeights::default(),
+            #[cfg(feature = "websocket")]
+            subscription_url: None,
         }
     }
 
@@ -51,6 +73,12 @@ impl Config {
         self
     }
 
+    /// Set a custom base URL (for testing)
+    pub fn with_base_url(mut self, url: impl Into<String>) -> Self {
+        self.inner.base_url = url.into();
+        self
+    }
+
     /// Set a proxy URL for HTTP requests
     pub fn with_proxy(mut self, proxy: impl Into<String>) -> Self {
         self.inner.http.proxy = Some(proxy.into());
@@ -94,6 +122,74 @@ impl Config {
         self.rate_limiter = Some(limiter);
         self
     }
+
+    /// Set the maximum number of idle connections kept per host (default 10)
+    ///
+    /// Raise this alongside [`Config::with_max_concurrent_requests`] for
+    /// high-throughput workloads so parallel requests reuse connections.
+    pub fn with_connection_pool_size(mut self, size: usize) -> Self {
+        self.inner.http.pool_max_idle_per_host = size;
+        self
+    }
+
+    /// Bound the number of requests in flight at once
+    ///
+    /// Requests beyond the limit wait for a permit before being sent.
+    /// A limit of 0 is treated as 1.
+    pub fn with_max_concurrent_requests(mut self, n: usize) -> Self {
+        self.max_concurrent_requests = Some(n.max(1));
+        self
+    }
+
+    /// Use a custom JSON-RPC endpoint as the gas price oracle
+    ///
+    /// `{chain_id}` in the URL is replaced with the chain being queried.
+    /// Without this, a public RPC for each supported chain is used.
+    pub fn with_gas_oracle_url(mut self, url: impl Into<String>) -> Self {
+        self.gas_oracle_url = Some(url.into());
+        self
+    }
+
+    /// Use a custom WebSocket endpoint for GraphQL subscriptions
+    ///
+    /// Defaults to the API URL with `https` swapped for `wss`.
+    #[cfg(feature = "websocket")]
+    pub fn with_subscription_url(mut self, url: impl Into<String>) -> Self {
+        self.subscription_url = Some(url.into());
+        self
+    }
+
+    /// Set the weights of the composite vault risk score
+    ///
+    /// Used by [`VaultsApi::vault_risk_profile`](crate::vaults::VaultsApi::vault_risk_profile).
+    pub fn with_risk_weights(mut self, weights: RiskWeights) -> Self {
+        self.risk_weights = weights;
+        self
+    }
+
+    /// Configured risk score weights
+    #[must_use]
+    pub fn risk_weights(&self) -> &RiskWeights {
+        &self.risk_weights
+    }
+
+    /// Configured gas oracle URL, if any
+    #[must_use]
+    pub fn gas_oracle_url(&self) -> Option<&str> {
+        self.gas_oracle_url.as_deref()
+    }
+
+    /// Maximum idle connections per host
+    #[must_use]
+    pub fn connection_pool_size(&self) -> usize {
+        self.inner.http.pool_max_idle_per_host
+    }
+
+    /// Configured in-flight request limit, if any
+    #[must_use]
+    pub fn max_concurrent_requests(&self) -> Option<usize> {
+        self.max_concurrent_requests
+    }
 }
 
 impl Default for Config {
@@ -115,6 +211,19 @@ pub struct Client {
     base_url: String,
     /// Optional rate limiter (already Clone via Arc internally)
     rate_limiter: Option<RateLimiter>,
+    /// Optional in-flight request limiter, shared across clones
+    concurrency: Option<Arc<Semaphore>>,
+    /// Configured in-flight request limit
+    max_concurrent_requests: Option<usize>,
+    /// Gas oracle override
+    gas_oracle_url: Option<String>,
+    /// Gas prices in gwei by chain, shared across clones
+    gas_prices: Arc<Mutex<HashMap<u64, (f64, Instant)>>>,
+    /// Weights for the composite vault risk score
+    risk_weights: RiskWeights,
+    /// WebSocket endpoint override for subscriptions
+    #[cfg(feature = "websocket")]
+    subscription_url: Option<String>,
 }
 
 impl Client {
@@ -128,8 +237,17 @@ impl Client {
         let http = config.inner.build_client()?;
         Ok(Self {
             http: Arc::new(http),
-            base_url: BASE_URL.to_string(),
+            base_url: config.inner.base_url,
             rate_limiter: config.rate_limiter,
+            concurrency: config
+                .max_concurrent_requests
+                .map(|n| Arc::new(Semaphore::new(n))),
+            max_concurrent_requests: config.max_concurrent_requests,
+            gas_oracle_url: config.gas_oracle_url,
+            gas_prices: Arc::default(),
+            risk_weights: config.risk_weights,
+            #[cfg(feature = "websocket")]
+            subscription_url: config.subscription_url,
         })
     }
 
@@ -142,6 +260,13 @@ impl Client {
             http: Arc::new(http),
             base_url: BASE_URL.to_string(),
             rate_limiter: None,
+            concurrency: None,
+            max_concurrent_requests: None,
+            gas_oracle_url: None,
+            gas_prices: Arc::default(),
+            risk_weights: RiskWeights::default(),
+            #[cfg(feature = "websocket")]
+            subscription_url: None,
         }
     }
 
@@ -157,6 +282,78 @@ impl Client {
         self.rate_limiter.as_ref()
     }
 
+    /// Get the in-flight request limit if configured
+    #[must_use]
+    pub fn max_concurrent_requests(&self) -> Option<usize> {
+        self.max_concurrent_requests
+    }
+
+    /// Weights of the composite vault risk score
+    #[must_use]
+    pub fn risk_weights(&self) -> &RiskWeights {
+        &self.risk_weights
+    }
+
+    /// WebSocket endpoint used for GraphQL subscriptions
+    #[cfg(feature = "websocket")]
+    #[must_use]
+    pub fn subscription_url(&self) -> String {
+        self.subscription_url
+            .clone()
+            .unwrap_or_else(|| crate::subscribe::subscription_url(&self.base_url))
+    }
+
+    /// Subscribe to vault changes on a chain
+    ///
+    /// Opens a GraphQL subscription over WebSocket and yields one
+    /// [`VaultUpdate`](crate::types::VaultUpdate) per added, changed or removed
+    /// vault, each carrying the field diff from its previous state. The first
+    /// snapshot reports every vault as added.
+    ///
+    /// Returns [`DomainError::NotSupported`](crate::error::DomainError::NotSupported)
+    /// when the endpoint does not serve subscriptions; poll
+    /// [`VaultsApi::by_chain`](crate::vaults::VaultsApi::by_chain) and compare
+    /// snapshots with [`VaultUpdate::diff`](crate::types::VaultUpdate::diff) instead.
+    #[cfg(feature = "websocket")]
+    pub async fn subscribe_vaults(
+        &self,
+        chain_id: u64,
+    ) -> Result<impl futures_util::Stream<Item = Result<crate::types::VaultUpdate>>> {
+        crate::validate::chain_id(chain_id)?;
+        crate::subscribe::subscribe_vaults(self.subscription_url(), chain_id).await
+    }
+
+    /// Current gas price in gwei for a chain
+    ///
+    /// Prices are cached for [`GAS_PRICE_CACHE_TTL`]. Errors with
+    /// [`InvalidChainId`](crate::error::DomainError::InvalidChainId) for
+    /// chains Kong doesn't index, and for chains without a default oracle
+    /// unless one is configured.
+    pub async fn gas_price_gwei(&self, chain_id: u64) -> Result<f64> {
+        crate::validate::chain_id(chain_id)?;
+        if let Some((price, fetched)) = self.cached_gas_price(chain_id) {
+            if fetched.elapsed() < GAS_PRICE_CACHE_TTL {
+                return Ok(price);
+            }
+        }
+
+        let url = gas::oracle_url(self.gas_oracle_url.as_deref(), chain_id)?;
+        let price = gas::fetch_gas_price_gwei(&self.http, &url).await?;
+        self.gas_prices
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(chain_id, (price, Instant::now()));
+        Ok(price)
+    }
+
+    fn cached_gas_price(&self, chain_id: u64) -> Option<(f64, Instant)> {
+        self.gas_prices
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&chain_id)
+            .copied()
+    }
+
     /// Execute a GraphQL query
     pub async fn query<T>(&self, query: &str) -> Result<T>
     where
@@ -175,6 +372,17 @@ impl Client {
     where
         T: serde::de::DeserializeOwned,
     {
+        // Hold a concurrency permit for the lifetime of the request
+        let _permit = match &self.concurrency {
+            Some(semaphore) => Some(
+                semaphore
+                    .acquire()
+                    .await
+                    .map_err(|e| graphql_error(format!("Concurrency limiter closed: {e}")))?,
+            ),
+            None => None,
+        };
+
         // Acquire rate limit permit if configured
         if let Some(limiter) = &self.rate_limiter {
             limiter.acquire().await;
@@ -246,3 +454,36 @@ impl Client {
             .ok_or_else(|| graphql_error("No data in GraphQL response"))
     }
 }
+
+/// Run `f` over `items` as spawned tasks with at most `limit` in flight
+///
+/// Results come back in input order. The first error (or panicked task)
+/// fails the call and aborts the tasks still running.
+pub(crate) async fn fan_out<I, T, F, Fut>(items: I, limit: usize, f: F) -> Result<Vec<T>>
+where
+    I: IntoIterator,
+    F: Fn(I::Item) -> Fut,
+    Fut: Future<Output = Result<T>> + Send + 'static,
+    T: Send + 'static,
+{
+    let permits = Arc::new(Semaphore::new(limit.max(1)));
+    let mut tasks = JoinSet::new();
+    let mut len = 0;
+    for (index, item) in items.into_iter().enumerate() {
+        let permits = Arc::clone(&permits);
+        let task = f(item);
+        tasks.spawn(async move {
+            // The semaphore is never closed
+            let _permit = permits.acquire_owned().await;
+            (index, task.await)
+        });
+        len += 1;
+    }
+
+    let mut results: Vec<Option<T>> = std::iter::repeat_with(|| None).take(len).collect();
+    while let Some(joined) = tasks.join_next().await {
+        let (index, result) = joined.map_err(|e| graphql_error(e.to_string()))?;
+        results[index] = Some(result?);
+    }
+    Ok(results.into_iter().flatten().collect())
+}
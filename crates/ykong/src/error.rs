@@ -23,10 +23,32 @@ pub enum DomainError {
     #[error("Invalid chain ID: {0}")]
     InvalidChainId(u64),
 
+    /// Malformed address argument
+    #[error("Invalid address '{input}': {reason}")]
+    InvalidAddress {
+        /// Address as given
+        input: String,
+        /// Why it was rejected
+        reason: String,
+    },
+
     /// URL parse error
     #[error("URL parsing error: {0}")]
     UrlParse(#[from] url::ParseError),
 
+    /// Not enough data points for a calculation
+    #[error("Insufficient data: {available} data points available, {required} required")]
+    InsufficientData {
+        /// Data points required
+        required: usize,
+        /// Data points available
+        available: usize,
+    },
+
+    /// Feature not offered by the Kong deployment
+    #[error("Not supported: {0}")]
+    NotSupported(String),
+
     /// API endpoint removed
     #[error("API endpoint '{endpoint}' has been removed. {alternative}")]
     ApiEndpointRemoved {
@@ -56,6 +78,33 @@ pub fn strategy_not_found(address: impl Into<String>) -> Error {
     ApiError::domain(DomainError::StrategyNotFound(address.into()))
 }
 
+/// Create an invalid chain ID error
+#[must_use]
+pub fn invalid_chain_id(chain_id: u64) -> Error {
+    ApiError::domain(DomainError::InvalidChainId(chain_id))
+}
+
+/// Create an invalid address error
+pub fn invalid_address(input: impl Into<String>, reason: impl Into<String>) -> Error {
+    ApiError::domain(DomainError::InvalidAddress {
+        input: input.into(),
+        reason: reason.into(),
+    })
+}
+
+/// Create an insufficient data error
+pub fn insufficient_data(required: usize, available: usize) -> Error {
+    ApiError::domain(DomainError::InsufficientData {
+        required,
+        available,
+    })
+}
+
+/// Create a not supported error
+pub fn not_supported(message: impl Into<String>) -> Error {
+    ApiError::domain(DomainError::NotSupported(message.into()))
+}
+
 /// Create from HTTP response status and body
 #[must_use]
 pub fn from_response(status: u16, body: &str, retry_after: Option<u64>) -> Error {
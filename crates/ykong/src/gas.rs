@@ -0,0 +1,92 @@
+//! Gas price oracle used for harvest profitability
+//!
+//! Gas prices come from `eth_gasPrice` on a JSON-RPC endpoint. Each Kong
+//! chain has a public default; [`Config::with_gas_oracle_url`] overrides it,
+//! substituting `{chain_id}` if the URL contains it. Prices are cached per
+//! chain for [`GAS_PRICE_CACHE_TTL`].
+//!
+//! [`Config::with_gas_oracle_url`]: crate::Config::with_gas_oracle_url
+
+use crate::error::{self, Error, Result};
+use std::time::Duration;
+use yldfi_common::api::extract_retry_after;
+
+/// How long a fetched gas price is reused
+pub const GAS_PRICE_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Public JSON-RPC endpoint used as the gas oracle for a chain
+#[must_use]
+pub fn default_gas_oracle_url(chain_id: u64) -> Option<&'static str> {
+    match chain_id {
+        1 => Some("https://ethereum-rpc.publicnode.com"),
+        10 => Some("https://mainnet.optimism.io"),
+        137 => Some("https://polygon-rpc.com"),
+        8453 => Some("https://mainnet.base.org"),
+        42161 => Some("https://arb1.arbitrum.io/rpc"),
+        _ => None,
+    }
+}
+
+/// Wrapped native token whose Kong price values gas on a chain
+#[must_use]
+pub fn wrapped_native_token(chain_id: u64) -> Option<&'static str> {
+    match chain_id {
+        1 => Some("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"),
+        10 | 8453 => Some("0x4200000000000000000000000000000000000006"),
+        137 => Some("0x0d500B1d8E8eF31E21C99d1Db9A6444d3ADf1270"),
+        42161 => Some("0x82aF49447D8a07e3bd95BD0d56f35241523fBab1"),
+        _ => None,
+    }
+}
+
+/// Resolve the oracle URL for a chain, honouring a configured override
+pub(crate) fn oracle_url(configured: Option<&str>, chain_id: u64) -> Result<String> {
+    match configured {
+        Some(url) => Ok(url.replace("{chain_id}", &chain_id.to_string())),
+        None => default_gas_oracle_url(chain_id)
+            .map(str::to_string)
+            .ok_or_else(|| error::invalid_chain_id(chain_id)),
+    }
+}
+
+/// Fetch the current gas price in gwei with `eth_gasPrice`
+pub(crate) async fn fetch_gas_price_gwei(http: &reqwest::Client, url: &str) -> Result<f64> {
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "eth_gasPrice",
+        "params": []
+    });
+    let response = http
+        .post(url)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| Error::Api {
+            status: 0,
+            message: format!("Gas oracle request failed: {e}"),
+        })?;
+
+    let status = response.status().as_u16();
+    if !response.status().is_success() {
+        let retry_after = extract_retry_after(response.headers());
+        let body = response.text().await.unwrap_or_default();
+        return Err(Error::from_response(status, &body, retry_after));
+    }
+
+    let json: serde_json::Value = response.json().await.map_err(|e| Error::Api {
+        status,
+        message: format!("Failed to parse gas oracle response: {e}"),
+    })?;
+    parse_gas_price_gwei(&json).ok_or_else(|| Error::Api {
+        status,
+        message: format!("Gas oracle returned no gas price: {json}"),
+    })
+}
+
+/// Read a hex wei `result` from a JSON-RPC response as gwei
+fn parse_gas_price_gwei(json: &serde_json::Value) -> Option<f64> {
+    let hex = json.get("result")?.as_str()?;
+    let wei = u128::from_str_radix(hex.trim_start_matches("0x"), 16).ok()?;
+    Some(wei as f64 / 1e9)
+}
@@ -32,24 +32,36 @@
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! Address arguments are validated and may use any case; addresses in
+//! responses are EIP-55 checksummed. Chain IDs outside [`KONG_CHAIN_IDS`]
+//! are rejected before any request is sent.
 
 pub mod client;
 pub mod error;
+pub mod gas;
 pub mod prices;
 pub mod reports;
 pub mod strategies;
+#[cfg(feature = "websocket")]
+pub mod subscribe;
 pub mod tvls;
 pub mod types;
+mod validate;
 pub mod vaults;
 
 pub use client::{Client, Config, BASE_URL};
 pub use error::{Error, Result};
-pub use prices::PricesApi;
+pub use gas::{default_gas_oracle_url, wrapped_native_token, GAS_PRICE_CACHE_TTL};
+pub use prices::{realised_volatility_from_prices, PricesApi, MIN_VOLATILITY_DAYS};
 pub use reports::ReportsApi;
 pub use strategies::{StrategiesApi, StrategyFilter};
+#[cfg(feature = "websocket")]
+pub use subscribe::SUBSCRIPTION_PROTOCOL;
 pub use tvls::{TvlPeriod, TvlsApi};
 pub use types::*;
-pub use vaults::{VaultFilter, VaultsApi};
+pub use validate::KONG_CHAIN_IDS;
+pub use vaults::{TimeseriesQuery, VaultFilter, VaultsApi, APY_SERIES_LABEL, PPS_SERIES_LABEL};
 
 impl Client {
     /// Access the vaults API
@@ -1,9 +1,47 @@
 //! Token price queries from Kong API
 
-use crate::client::Client;
-use crate::error::Result;
+use crate::client::{fan_out, Client, FAN_OUT_CONCURRENCY};
+use crate::error::{self, Result};
 use crate::types::Price;
+use crate::validate;
 use serde::Deserialize;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Minimum days of price history needed to estimate volatility
+pub const MIN_VOLATILITY_DAYS: usize = 10;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+const DAYS_PER_YEAR: f64 = 365.0;
+
+/// Annualised volatility of daily log returns
+///
+/// `daily_prices` are USD prices one day apart, oldest first. Uses the sample
+/// standard deviation scaled by sqrt(365); returns a fraction (0.80 = 80%).
+/// Errors with [`error::DomainError::InsufficientData`] when fewer than
+/// [`MIN_VOLATILITY_DAYS`] positive prices are given.
+pub fn realised_volatility_from_prices(daily_prices: &[f64]) -> Result<f64> {
+    let prices: Vec<f64> = daily_prices
+        .iter()
+        .copied()
+        .filter(|p| p.is_finite() && *p > 0.0)
+        .collect();
+    if prices.len() < MIN_VOLATILITY_DAYS {
+        return Err(error::insufficient_data(MIN_VOLATILITY_DAYS, prices.len()));
+    }
+
+    let returns: Vec<f64> = prices.windows(2).map(|w| (w[1] / w[0]).ln()).collect();
+    let n = returns.len() as f64;
+    let mean = returns.iter().sum::<f64>() / n;
+    let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    Ok(variance.sqrt() * DAYS_PER_YEAR.sqrt())
+}
+
+/// Percentile rank (0-100) of `value` among `others`, counting ties as half
+fn percentile_rank(value: f64, others: &[f64]) -> f64 {
+    let below = others.iter().filter(|v| **v < value).count() as f64;
+    let equal = others.iter().filter(|v| **v == value).count() as f64;
+    (below + equal / 2.0) / others.len() as f64 * 100.0
+}
 
 /// Prices API
 pub struct PricesApi<'a> {
@@ -35,6 +73,8 @@ impl<'a> PricesApi<'a> {
     /// # }
     /// ```
     pub async fn get(&self, chain_id: u64, address: &str) -> Result<Vec<Price>> {
+        let chain_id = validate::chain_id(chain_id)?;
+        let address = validate::address(address)?;
         let query = format!(
             r#"{{
                 prices(chainId: {chain_id}, address: "{address}") {{
@@ -70,6 +110,8 @@ impl<'a> PricesApi<'a> {
         address: &str,
         timestamp: u64,
     ) -> Result<Vec<Price>> {
+        let chain_id = validate::chain_id(chain_id)?;
+        let address = validate::address(address)?;
         let query = format!(
             r#"{{
                 prices(chainId: {chain_id}, address: "{address}", timestamp: {timestamp}) {{
@@ -103,4 +145,108 @@ impl<'a> PricesApi<'a> {
         let price = self.current(chain_id, address).await?;
         Ok(price.map(|p| p.price_usd))
     }
+
+    /// Get one USD price per day for the last `days` days, oldest first
+    ///
+    /// Days without a price (e.g. before the token existed) are skipped.
+    pub async fn daily_history(
+        &self,
+        chain_id: u64,
+        address: &str,
+        days: u32,
+    ) -> Result<Vec<Price>> {
+        let chain_id = validate::chain_id(chain_id)?;
+        let address = validate::address(address)?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        let client = self.client.clone();
+        let address = address.to_string();
+        let per_day = fan_out(0..=u64::from(days), FAN_OUT_CONCURRENCY, move |day| {
+            let client = client.clone();
+            let address = address.clone();
+            let timestamp = now.saturating_sub(day * SECONDS_PER_DAY);
+            async move {
+                PricesApi::new(&client)
+                    .at_timestamp(chain_id, &address, timestamp)
+                    .await
+            }
+        })
+        .await?;
+
+        let mut prices: Vec<Price> = per_day
+            .into_iter()
+            .filter_map(|day_prices| day_prices.into_iter().find(|p| p.price_usd > 0.0))
+            .collect();
+        prices.sort_by_key(|p| p.timestamp);
+        prices.dedup_by_key(|p| p.timestamp);
+        Ok(prices)
+    }
+
+    /// Realised volatility over the last `window_days` days
+    ///
+    /// Annualised standard deviation of daily log returns, as a fraction
+    /// (0.80 = 80% vol). Errors with
+    /// [`InsufficientData`](error::DomainError::InsufficientData) when fewer
+    /// than [`MIN_VOLATILITY_DAYS`] days of prices exist.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example() -> ykong::error::Result<()> {
+    /// use ykong::Client;
+    ///
+    /// let client = Client::new()?;
+    /// let weth = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
+    /// let vol = client.prices().realised_volatility(1, weth, 30).await?;
+    /// println!("30d vol: {:.0}%", vol * 100.0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn realised_volatility(
+        &self,
+        chain_id: u64,
+        token_address: &str,
+        window_days: u32,
+    ) -> Result<f64> {
+        let history = self
+            .daily_history(chain_id, token_address, window_days)
+            .await?;
+        let prices: Vec<f64> = history.iter().map(|p| p.price_usd).collect();
+        realised_volatility_from_prices(&prices)
+    }
+
+    /// Percentile (0-100) of a token's realised volatility among comparison tokens
+    ///
+    /// Comparison tokens without enough history are left out; errors with
+    /// [`InsufficientData`](error::DomainError::InsufficientData) if the token
+    /// itself or every comparison token lacks history.
+    pub async fn volatility_percentile(
+        &self,
+        chain_id: u64,
+        token: &str,
+        days: u32,
+        comparison_tokens: &[&str],
+    ) -> Result<f64> {
+        validate::chain_id(chain_id)?;
+        validate::address(token)?;
+        validate::addresses(comparison_tokens)?;
+        let volatility = self.realised_volatility(chain_id, token, days).await?;
+
+        let mut others = Vec::with_capacity(comparison_tokens.len());
+        for other in comparison_tokens {
+            match self.realised_volatility(chain_id, other, days).await {
+                Ok(v) => others.push(v),
+                Err(error::Error::Domain(error::DomainError::InsufficientData { .. })) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        if others.is_empty() {
+            return Err(error::insufficient_data(1, 0));
+        }
+
+        Ok(percentile_rank(volatility, &others))
+    }
 }
@@ -1,8 +1,12 @@
 //! Vault and strategy report queries from Kong API
 
 use crate::client::Client;
-use crate::error::Result;
-use crate::types::{StrategyReport, VaultReport};
+use crate::error::{self, Result};
+use crate::types::{
+    CrossVaultComparison, FeeDrainAnalysis, StrategyReport, VaultReport, VaultReportStats,
+};
+use crate::validate;
+use crate::vaults::VaultsApi;
 use serde::Deserialize;
 
 /// Reports API for vault and strategy performance data
@@ -36,6 +40,8 @@ impl<'a> ReportsApi<'a> {
     /// # }
     /// ```
     pub async fn vault_reports(&self, chain_id: u64, address: &str) -> Result<Vec<VaultReport>> {
+        let chain_id = validate::chain_id(chain_id)?;
+        let address = validate::address(address)?;
         let query = format!(
             r#"{{
                 vaultReports(chainId: {chain_id}, address: "{address}") {{
@@ -110,6 +116,8 @@ impl<'a> ReportsApi<'a> {
         chain_id: u64,
         address: &str,
     ) -> Result<Vec<StrategyReport>> {
+        let chain_id = validate::chain_id(chain_id)?;
+        let address = validate::address(address)?;
         let query = format!(
             r#"{{
                 strategyReports(chainId: {chain_id}, address: "{address}") {{
@@ -182,4 +190,97 @@ impl<'a> ReportsApi<'a> {
         let total = reports.iter().filter_map(|r| r.profit_usd).sum();
         Ok(total)
     }
+
+    /// Compare two vaults' report histories head-to-head
+    ///
+    /// Both histories are fetched in parallel.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example() -> ykong::error::Result<()> {
+    /// use ykong::Client;
+    ///
+    /// let client = Client::new()?;
+    /// let cmp = client.reports().cross_vault_comparison(1, "0xA...", "0xB...").await?;
+    /// println!("Higher APY: {}", cmp.winner_by_apy);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn cross_vault_comparison(
+        &self,
+        chain_id: u64,
+        vault_a: &str,
+        vault_b: &str,
+    ) -> Result<CrossVaultComparison> {
+        let vault_a = validate::address(vault_a)?;
+        let vault_b = validate::address(vault_b)?;
+        let (reports_a, reports_b) = tokio::try_join!(
+            self.vault_reports(chain_id, &vault_a),
+            self.vault_reports(chain_id, &vault_b)
+        )?;
+
+        Ok(CrossVaultComparison::new(
+            VaultReportStats::from_reports(validate::checksum(&vault_a), &reports_a),
+            VaultReportStats::from_reports(validate::checksum(&vault_b), &reports_b),
+        ))
+    }
+
+    /// Project how fees reduce a deposit's yield over a holding period
+    ///
+    /// The gross APR is the average across the vault's harvest reports,
+    /// falling back to the vault's current gross APR when it has none. Fees
+    /// come from the vault's [`Fees`](crate::types::Fees).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example() -> ykong::error::Result<()> {
+    /// use ykong::Client;
+    ///
+    /// let client = Client::new()?;
+    /// let analysis = client.reports().fee_drain_analysis(1, "0x...", 10_000.0, 365).await?;
+    /// println!(
+    ///     "Fees take {:.1}% of yield; breakeven after {} days",
+    ///     analysis.fee_drag_pct,
+    ///     analysis.breakeven_days()
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fee_drain_analysis(
+        &self,
+        chain_id: u64,
+        vault_address: &str,
+        deposit_amount_usd: f64,
+        hold_days: u32,
+    ) -> Result<FeeDrainAnalysis> {
+        let vaults = VaultsApi::new(self.client);
+        let (vault, reports) = tokio::try_join!(
+            vaults.get(chain_id, vault_address),
+            self.vault_reports(chain_id, vault_address)
+        )?;
+        let vault = vault.ok_or_else(|| error::vault_not_found(vault_address))?;
+
+        let aprs: Vec<f64> = reports
+            .iter()
+            .filter_map(|r| r.apr.as_ref().and_then(|a| a.gross))
+            .collect();
+        let gross_apr = if aprs.is_empty() {
+            vault
+                .apy
+                .as_ref()
+                .and_then(|a| a.gross_apr)
+                .ok_or_else(|| error::insufficient_data(1, 0))?
+        } else {
+            aprs.iter().sum::<f64>() / aprs.len() as f64
+        };
+
+        Ok(FeeDrainAnalysis::new(
+            deposit_amount_usd,
+            hold_days,
+            gross_apr,
+            vault.fees.as_ref(),
+        ))
+    }
 }
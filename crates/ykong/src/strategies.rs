@@ -1,9 +1,17 @@
 //! Strategy-related GraphQL queries
 
 use crate::client::Client;
-use crate::error::Result;
-use crate::types::Strategy;
+use crate::error::{self, Result};
+use crate::gas::wrapped_native_token;
+use crate::prices::PricesApi;
+use crate::types::{
+    HarvestRoi, LivenessReport, ProtocolBreakdown, RiskSummary, SolvencyReport, Strategy,
+    StrategyRisk, HARVEST_GAS_UNITS,
+};
+use crate::validate;
+use crate::vaults::VaultsApi;
 use serde::Deserialize;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Strategy query builder for filtering strategies
 #[derive(Debug, Default, Clone)]
@@ -49,6 +57,20 @@ impl StrategyFilter {
         self
     }
 
+    /// Validate the chain ID and lowercase the addresses
+    fn normalized(mut self) -> Result<Self> {
+        if let Some(chain_id) = self.chain_id {
+            validate::chain_id(chain_id)?;
+        }
+        if let Some(vault) = self.vault.take() {
+            self.vault = Some(validate::address(&vault)?);
+        }
+        if let Some(addresses) = self.addresses.take() {
+            self.addresses = Some(validate::addresses(&addresses)?);
+        }
+        Ok(self)
+    }
+
     /// Build the GraphQL arguments string
     fn build_args(&self) -> String {
         let mut args = Vec::new();
@@ -102,7 +124,7 @@ impl<'a> StrategiesApi<'a> {
     /// # }
     /// ```
     pub async fn list(&self, filter: Option<StrategyFilter>) -> Result<Vec<Strategy>> {
-        let args = filter.unwrap_or_default().build_args();
+        let args = filter.unwrap_or_default().normalized()?.build_args();
         let query = format!(
             r"{{
                 strategies{args} {{
@@ -126,7 +148,7 @@ impl<'a> StrategiesApi<'a> {
                     isShutdown
                     keeper
                     strategist
-                    risk {{ riskLevel riskGroup }}
+                    risk {{ riskLevel riskGroup auditScore codeReviewScore complexityScore protocolSafetyScore }}
                     apy {{ net weeklyNet monthlyNet }}
                     tvl {{ close blockNumber blockTime }}
                 }}
@@ -156,6 +178,8 @@ impl<'a> StrategiesApi<'a> {
 
     /// Get a single strategy by address and chain
     pub async fn get(&self, chain_id: u64, address: &str) -> Result<Option<Strategy>> {
+        let chain_id = validate::chain_id(chain_id)?;
+        let address = validate::address(address)?;
         let query = format!(
             r#"{{
                 strategy(chainId: {chain_id}, address: "{address}") {{
@@ -194,4 +218,191 @@ impl<'a> StrategiesApi<'a> {
         let response: Response = self.client.query(&query).await?;
         Ok(response.strategy)
     }
+
+    /// Find active strategies on a chain that have not harvested recently
+    ///
+    /// Fetches every strategy on the chain and flags those whose last report
+    /// is older than `max_age_hours`. See [`LivenessReport::from_strategies`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example() -> ykong::error::Result<()> {
+    /// use ykong::Client;
+    ///
+    /// let client = Client::new()?;
+    /// let report = client.strategies().liveness_monitor(1, 72).await?;
+    /// for s in &report.stale_strategies {
+    ///     println!("{} ({:?}): {}h", s.strategy_address, s.urgency, s.hours_since_harvest);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn liveness_monitor(
+        &self,
+        chain_id: u64,
+        max_age_hours: u64,
+    ) -> Result<LivenessReport> {
+        let strategies = self.by_chain(chain_id).await?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Ok(LivenessReport::from_strategies(
+            &strategies,
+            max_age_hours,
+            now,
+        ))
+    }
+
+    /// Get risk attributes of a vault's strategies
+    ///
+    /// See [`StrategyRisk::from_strategies`] for how debt shares are derived.
+    pub async fn risk_score(&self, chain_id: u64, vault: &str) -> Result<Vec<StrategyRisk>> {
+        let strategies = self.by_vault(chain_id, vault).await?;
+        Ok(StrategyRisk::from_strategies(&strategies))
+    }
+
+    /// Summarise how a vault's debt is allocated across its strategies
+    ///
+    /// See [`RiskSummary::from_strategies`]. Risk scores Kong doesn't
+    /// provide are left as `None`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example() -> ykong::error::Result<()> {
+    /// use ykong::Client;
+    ///
+    /// let client = Client::new()?;
+    /// let summary = client.strategies().risk_summary(1, "0x...").await?;
+    /// println!(
+    ///     "{} strategies, largest holds {:.1}% of debt",
+    ///     summary.strategy_count, summary.concentration_pct
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn risk_summary(&self, chain_id: u64, vault: &str) -> Result<RiskSummary> {
+        let vault = validate::address(vault)?;
+        let strategies = self.by_vault(chain_id, &vault).await?;
+        Ok(RiskSummary::from_strategies(
+            validate::checksum(&vault),
+            &strategies,
+        ))
+    }
+
+    /// Get how a vault's strategy debt is spread across protocols
+    pub async fn protocol_breakdown(
+        &self,
+        chain_id: u64,
+        vault: &str,
+    ) -> Result<ProtocolBreakdown> {
+        let risks = self.risk_score(chain_id, vault).await?;
+        Ok(ProtocolBreakdown::from_strategy_risks(&risks))
+    }
+
+    /// Check a vault's strategies against their authorised debt ratios
+    ///
+    /// Fetches the vault and its strategies, then compares each strategy's
+    /// share of the vault's total assets with its `debtRatio`. See
+    /// [`SolvencyReport::from_strategies`] for the rules.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VaultNotFound`](error::DomainError::VaultNotFound) for an
+    /// unknown vault and [`InsufficientData`](error::DomainError::InsufficientData)
+    /// when Kong reports no (or zero) total assets, since no strategy's
+    /// share can be measured against it.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example() -> ykong::error::Result<()> {
+    /// use ykong::Client;
+    ///
+    /// let client = Client::new()?;
+    /// let report = client.strategies().solvency_check(1, "0x...").await?;
+    /// if !report.is_solvent {
+    ///     println!("{} strategies over their debt ratio", report.violations.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn solvency_check(
+        &self,
+        chain_id: u64,
+        vault_address: &str,
+    ) -> Result<SolvencyReport> {
+        let vaults = VaultsApi::new(self.client);
+        let (vault, strategies) = tokio::try_join!(
+            vaults.get(chain_id, vault_address),
+            self.by_vault(chain_id, vault_address)
+        )?;
+        let vault = vault.ok_or_else(|| error::vault_not_found(vault_address))?;
+        let total_assets = vault
+            .total_assets
+            .as_deref()
+            .and_then(|t| t.parse::<u128>().ok())
+            .filter(|&total| total > 0)
+            .ok_or_else(|| error::insufficient_data(1, 0))?;
+
+        Ok(SolvencyReport::from_strategies(total_assets, &strategies))
+    }
+
+    /// Estimate whether harvesting a strategy now covers its gas
+    ///
+    /// Prices [`HARVEST_GAS_UNITS`] of gas at the current oracle gas price
+    /// (see [`Client::gas_price_gwei`]) and the wrapped native token's Kong
+    /// price, against [`Strategy::accrued_profit_usd`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StrategyNotFound`](error::DomainError::StrategyNotFound) for
+    /// an unknown strategy, [`InvalidChainId`](error::DomainError::InvalidChainId)
+    /// for a chain without a known native token, and
+    /// [`InsufficientData`](error::DomainError::InsufficientData) if Kong has
+    /// no native token price.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example() -> ykong::error::Result<()> {
+    /// use ykong::Client;
+    ///
+    /// let client = Client::new()?;
+    /// let roi = client.strategies().compute_harvest_roi(1, "0x...").await?;
+    /// if roi.is_profitable {
+    ///     println!("Harvest nets {:.0}% over ${:.2} gas", roi.net_roi * 100.0, roi.gas_cost_usd);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn compute_harvest_roi(
+        &self,
+        chain_id: u64,
+        strategy_address: &str,
+    ) -> Result<HarvestRoi> {
+        validate::chain_id(chain_id)?;
+        validate::address(strategy_address)?;
+        let native =
+            wrapped_native_token(chain_id).ok_or_else(|| error::invalid_chain_id(chain_id))?;
+        let prices = PricesApi::new(self.client);
+        let (strategy, gas_price_gwei, native_price_usd) = tokio::try_join!(
+            self.get(chain_id, strategy_address),
+            self.client.gas_price_gwei(chain_id),
+            prices.usd(chain_id, native)
+        )?;
+        let strategy = strategy.ok_or_else(|| error::strategy_not_found(strategy_address))?;
+        let native_price_usd = native_price_usd.ok_or_else(|| error::insufficient_data(1, 0))?;
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Ok(HarvestRoi::new(
+            gas_price_gwei * HARVEST_GAS_UNITS as f64,
+            native_price_usd,
+            strategy.accrued_profit_usd(now),
+        ))
+    }
 }
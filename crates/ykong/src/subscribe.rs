@@ -0,0 +1,308 @@
+//! GraphQL subscriptions over WebSocket
+//!
+//! Kong speaks the `graphql-transport-ws` protocol on the same path as the
+//! HTTP endpoint. Deployments without a subscription server reject the
+//! upgrade or the `subscribe` message, which surfaces as
+//! [`DomainError::NotSupported`](crate::error::DomainError::NotSupported).
+
+use crate::error::{self, Error, Result};
+use crate::types::{Vault, VaultUpdate};
+use crate::vaults::VAULT_FIELDS;
+use futures_util::{SinkExt, Stream, StreamExt};
+use serde::Deserialize;
+use std::collections::VecDeque;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::{Error as WsError, Message};
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+/// WebSocket subprotocol used for subscriptions
+pub const SUBSCRIPTION_PROTOCOL: &str = "graphql-transport-ws";
+
+/// How long to wait for `connection_ack` before giving up
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Hint returned when the endpoint cannot serve subscriptions
+const POLLING_HINT: &str =
+    "poll `client.vaults().by_chain(chain_id)` and compare snapshots with `VaultUpdate::diff` instead";
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Derive the WebSocket URL from an HTTP API URL
+///
+/// `https://` becomes `wss://` and `http://` becomes `ws://`; other URLs are
+/// returned unchanged.
+#[must_use]
+pub fn subscription_url(base_url: &str) -> String {
+    if let Some(rest) = base_url.strip_prefix("https://") {
+        format!("wss://{rest}")
+    } else if let Some(rest) = base_url.strip_prefix("http://") {
+        format!("ws://{rest}")
+    } else {
+        base_url.to_string()
+    }
+}
+
+/// Server-to-client protocol message
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ServerMessage {
+    ConnectionAck,
+    Ping,
+    Pong,
+    Next { payload: serde_json::Value },
+    Error { payload: serde_json::Value },
+    Complete,
+}
+
+#[derive(Deserialize)]
+struct VaultsPayload {
+    data: Option<VaultsData>,
+    errors: Option<Vec<crate::types::GraphQLError>>,
+}
+
+#[derive(Deserialize)]
+struct VaultsData {
+    vaults: Vec<Vault>,
+}
+
+fn not_supported(url: &str, reason: impl std::fmt::Display) -> Error {
+    error::not_supported(format!(
+        "subscriptions are unavailable at {url} ({reason}); {POLLING_HINT}"
+    ))
+}
+
+fn transport_error(url: &str, e: impl std::fmt::Display) -> Error {
+    Error::Api {
+        status: 0,
+        message: format!("WebSocket error on {url}: {e}"),
+    }
+}
+
+fn error_messages(payload: &serde_json::Value) -> String {
+    let messages: Vec<&str> = match payload {
+        serde_json::Value::Array(errors) => errors
+            .iter()
+            .filter_map(|e| e.get("message").and_then(|m| m.as_str()))
+            .collect(),
+        other => other
+            .get("message")
+            .and_then(|m| m.as_str())
+            .into_iter()
+            .collect(),
+    };
+    if messages.is_empty() {
+        payload.to_string()
+    } else {
+        messages.join("; ")
+    }
+}
+
+fn parse_message(url: &str, message: &Message) -> Result<Option<ServerMessage>> {
+    let text = match message {
+        Message::Text(text) => text.as_str(),
+        Message::Binary(bytes) => std::str::from_utf8(bytes)
+            .map_err(|e| transport_error(url, format!("non-UTF-8 frame: {e}")))?,
+        _ => return Ok(None),
+    };
+    serde_json::from_str(text)
+        .map(Some)
+        .map_err(|e| transport_error(url, format!("malformed message: {e}")))
+}
+
+async fn send_json(socket: &mut Socket, url: &str, value: serde_json::Value) -> Result<()> {
+    socket
+        .send(Message::text(value.to_string()))
+        .await
+        .map_err(|e| transport_error(url, e))
+}
+
+/// Open the socket and complete the `connection_init` / `connection_ack` exchange
+async fn connect(url: &str) -> Result<Socket> {
+    let mut request = url
+        .into_client_request()
+        .map_err(|e| transport_error(url, e))?;
+    request.headers_mut().insert(
+        "Sec-WebSocket-Protocol",
+        HeaderValue::from_static(SUBSCRIPTION_PROTOCOL),
+    );
+
+    let (mut socket, _) = match tokio_tungstenite::connect_async(request).await {
+        Ok(connected) => connected,
+        Err(WsError::Http(response)) => {
+            return Err(not_supported(
+                url,
+                format!("upgrade rejected with HTTP {}", response.status()),
+            ))
+        }
+        Err(WsError::Protocol(e)) => return Err(not_supported(url, e)),
+        Err(e) => return Err(transport_error(url, e)),
+    };
+
+    send_json(
+        &mut socket,
+        url,
+        serde_json::json!({ "type": "connection_init" }),
+    )
+    .await?;
+
+    let ack = async {
+        while let Some(message) = socket.next().await {
+            let message = message.map_err(|e| transport_error(url, e))?;
+            match parse_message(url, &message)? {
+                Some(ServerMessage::ConnectionAck) => return Ok(()),
+                Some(ServerMessage::Ping) => {
+                    send_json(&mut socket, url, serde_json::json!({ "type": "pong" })).await?;
+                }
+                Some(other) => {
+                    return Err(not_supported(
+                        url,
+                        format!("unexpected {other:?} before connection_ack"),
+                    ))
+                }
+                None if message.is_close() => break,
+                None => {}
+            }
+        }
+        Err(not_supported(
+            url,
+            "connection closed before connection_ack",
+        ))
+    };
+
+    tokio::time::timeout(HANDSHAKE_TIMEOUT, ack)
+        .await
+        .map_err(|_| not_supported(url, "no connection_ack received"))??;
+
+    Ok(socket)
+}
+
+struct SubscriptionState {
+    socket: Socket,
+    url: String,
+    previous: Option<Vec<Vault>>,
+    pending: VecDeque<VaultUpdate>,
+    done: bool,
+}
+
+impl SubscriptionState {
+    /// Turn a `next` payload into queued updates
+    fn apply(&mut self, payload: serde_json::Value) -> Result<()> {
+        let payload: VaultsPayload = serde_json::from_value(payload)
+            .map_err(|e| transport_error(&self.url, format!("malformed payload: {e}")))?;
+        if let Some(errors) = payload.errors.filter(|e| !e.is_empty()) {
+            let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+            return Err(error::graphql_error(messages.join("; ")));
+        }
+        let Some(data) = payload.data else {
+            return Ok(());
+        };
+        let previous = self.previous.as_deref().unwrap_or_default();
+        self.pending
+            .extend(VaultUpdate::diff(previous, &data.vaults));
+        self.previous = Some(data.vaults);
+        Ok(())
+    }
+
+    async fn next_update(&mut self) -> Option<Result<VaultUpdate>> {
+        loop {
+            if let Some(update) = self.pending.pop_front() {
+                return Some(Ok(update));
+            }
+            if self.done {
+                return None;
+            }
+
+            let message = match self.socket.next().await {
+                Some(Ok(message)) => message,
+                Some(Err(WsError::ConnectionClosed)) | None => return None,
+                Some(Err(e)) => {
+                    self.done = true;
+                    return Some(Err(transport_error(&self.url, e)));
+                }
+            };
+
+            let parsed = match parse_message(&self.url, &message) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            };
+
+            match parsed {
+                Some(ServerMessage::Next { payload }) => {
+                    if let Err(e) = self.apply(payload) {
+                        self.done = true;
+                        return Some(Err(e));
+                    }
+                }
+                Some(ServerMessage::Error { payload }) => {
+                    self.done = true;
+                    let messages = error_messages(&payload);
+                    return Some(Err(if self.previous.is_none() {
+                        not_supported(&self.url, messages)
+                    } else {
+                        error::graphql_error(messages)
+                    }));
+                }
+                Some(ServerMessage::Ping) => {
+                    let pong = send_json(
+                        &mut self.socket,
+                        &self.url,
+                        serde_json::json!({ "type": "pong" }),
+                    )
+                    .await;
+                    if let Err(e) = pong {
+                        self.done = true;
+                        return Some(Err(e));
+                    }
+                }
+                Some(ServerMessage::Complete) => self.done = true,
+                Some(ServerMessage::ConnectionAck | ServerMessage::Pong) => {}
+                None if message.is_close() => self.done = true,
+                None => {}
+            }
+        }
+    }
+}
+
+/// Subscribe to vault changes on a chain
+///
+/// See [`Client::subscribe_vaults`](crate::Client::subscribe_vaults).
+pub(crate) async fn subscribe_vaults(
+    url: String,
+    chain_id: u64,
+) -> Result<impl Stream<Item = Result<VaultUpdate>>> {
+    let mut socket = connect(&url).await?;
+
+    let query = format!("subscription {{ vaults(chainId: {chain_id}) {{ {VAULT_FIELDS} }} }}");
+    send_json(
+        &mut socket,
+        &url,
+        serde_json::json!({
+            "id": "1",
+            "type": "subscribe",
+            "payload": { "query": query }
+        }),
+    )
+    .await?;
+
+    let state = SubscriptionState {
+        socket,
+        url,
+        previous: None,
+        pending: VecDeque::new(),
+        done: false,
+    };
+
+    Ok(futures_util::stream::unfold(
+        state,
+        |mut state| async move {
+            let item = state.next_update().await?;
+            Some((item, state))
+        },
+    ))
+}
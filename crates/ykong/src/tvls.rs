@@ -1,9 +1,12 @@
 //! TVL (Total Value Locked) queries from Kong API
 
-use crate::client::Client;
+use crate::client::{fan_out, Client, FAN_OUT_CONCURRENCY};
 use crate::error::Result;
-use crate::types::Tvl;
+use crate::types::{AgeVsTvlPoint, ProtocolTvlBreakdown, Tvl};
+use crate::validate;
+use crate::vaults::VaultsApi;
 use serde::Deserialize;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// TVL period for historical queries
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -17,7 +20,7 @@ pub enum TvlPeriod {
 }
 
 impl TvlPeriod {
-    fn as_str(&self) -> &'static str {
+    pub(crate) fn as_str(&self) -> &'static str {
         match self {
             TvlPeriod::Day => "day",
             TvlPeriod::Week => "week",
@@ -61,6 +64,8 @@ impl<'a> TvlsApi<'a> {
         period: TvlPeriod,
         limit: u32,
     ) -> Result<Vec<Tvl>> {
+        let chain_id = validate::chain_id(chain_id)?;
+        let address = validate::address(address)?;
         let query = format!(
             r#"{{
                 tvls(chainId: {}, address: "{}", period: "{}", limit: {}) {{
@@ -98,6 +103,8 @@ impl<'a> TvlsApi<'a> {
         limit: u32,
         timestamp: u64,
     ) -> Result<Vec<Tvl>> {
+        let chain_id = validate::chain_id(chain_id)?;
+        let address = validate::address(address)?;
         let query = format!(
             r#"{{
                 tvls(chainId: {}, address: "{}", period: "{}", limit: {}, timestamp: {}) {{
@@ -149,4 +156,77 @@ impl<'a> TvlsApi<'a> {
         let tvls = self.history(chain_id, address, TvlPeriod::Day, 1).await?;
         Ok(tvls.into_iter().next())
     }
+
+    /// Get daily TVL history for several vaults on one chain
+    ///
+    /// Vaults are queried concurrently, at most [`FAN_OUT_CONCURRENCY`] at a
+    /// time; the result holds one series per address, in the order given.
+    /// Any vault failing fails the whole call.
+    pub async fn series_for_vaults(
+        &self,
+        chain_id: u64,
+        addresses: &[String],
+        days: u32,
+    ) -> Result<Vec<Vec<Tvl>>> {
+        validate::chain_id(chain_id)?;
+        let addresses = validate::addresses(addresses)?;
+        let client = self.client.clone();
+        fan_out(addresses, FAN_OUT_CONCURRENCY, move |address| {
+            let client = client.clone();
+            async move { TvlsApi::new(&client).daily(chain_id, &address, days).await }
+        })
+        .await
+    }
+
+    /// Get vault age vs TVL for every vault on a chain, sorted by age
+    ///
+    /// Derived from a single vault listing; no per-vault TVL queries are made.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example() -> ykong::error::Result<()> {
+    /// use ykong::Client;
+    ///
+    /// let client = Client::new()?;
+    /// for point in client.tvls().vault_age_vs_tvl(1).await? {
+    ///     println!("{} days: ${:.0}", point.age_days, point.tvl_usd);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn vault_age_vs_tvl(&self, chain_id: u64) -> Result<Vec<AgeVsTvlPoint>> {
+        let vaults = VaultsApi::new(self.client).by_chain(chain_id).await?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Ok(AgeVsTvlPoint::from_vaults(&vaults, now))
+    }
+
+    /// Total Yearn TVL broken down by chain, version, asset and vault
+    ///
+    /// Queries endorsed vaults on every
+    /// [`YearnChain`](crate::types::YearnChain) concurrently and aggregates
+    /// their latest TVL. Any chain failing fails the whole call.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example() -> ykong::error::Result<()> {
+    /// use ykong::Client;
+    ///
+    /// let client = Client::new()?;
+    /// let breakdown = client.tvls().protocol_tvl_breakdown().await?;
+    /// println!("Total: ${:.0}", breakdown.total_usd);
+    /// for (address, tvl) in &breakdown.top_vaults {
+    ///     println!("{address}: ${tvl:.0}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn protocol_tvl_breakdown(&self) -> Result<ProtocolTvlBreakdown> {
+        let vaults = VaultsApi::new(self.client).endorsed_on_all_chains().await?;
+        Ok(ProtocolTvlBreakdown::from_vaults(&vaults))
+    }
 }
@@ -1,6 +1,7 @@
 //! Type definitions for Kong API responses
 
 use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 /// Helper module for deserializing values that can be either strings or integers
 mod string_or_int {
@@ -39,11 +40,93 @@ where
     string_or_int::deserialize(deserializer)
 }
 
+/// Deserialize an address, EIP-55 checksummed
+pub(crate) fn deserialize_address<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    String::deserialize(deserializer).map(|a| crate::validate::checksum(&a))
+}
+
+/// Deserialize an optional address, EIP-55 checksummed
+fn deserialize_optional_address<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer).map(|a| a.map(|a| crate::validate::checksum(&a)))
+}
+
+/// Deserialize an optional address list, EIP-55 checksummed
+fn deserialize_address_list<'de, D>(deserializer: D) -> Result<Option<Vec<String>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<Vec<String>>::deserialize(deserializer)
+        .map(|list| list.map(|list| list.iter().map(|a| crate::validate::checksum(a)).collect()))
+}
+
+/// Chains with Yearn vaults indexed by Kong
+///
+/// Discriminants are the EVM chain IDs. [`KONG_CHAIN_IDS`](crate::KONG_CHAIN_IDS)
+/// is built from these plus the chains Kong indexes without endorsed vaults.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[repr(u64)]
+pub enum YearnChain {
+    /// Ethereum mainnet (1)
+    Ethereum = 1,
+    /// Optimism (10)
+    Optimism = 10,
+    /// Polygon (137)
+    Polygon = 137,
+    /// Base (8453)
+    Base = 8453,
+    /// Arbitrum One (42161)
+    Arbitrum = 42161,
+}
+
+impl YearnChain {
+    /// Every supported chain
+    pub const ALL: [Self; 5] = [
+        Self::Ethereum,
+        Self::Optimism,
+        Self::Polygon,
+        Self::Base,
+        Self::Arbitrum,
+    ];
+
+    /// EVM chain ID
+    #[must_use]
+    pub const fn chain_id(self) -> u64 {
+        self as u64
+    }
+
+    /// Look up a chain by EVM chain ID
+    #[must_use]
+    pub fn from_chain_id(chain_id: u64) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.chain_id() == chain_id)
+    }
+}
+
+impl std::fmt::Display for YearnChain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Ethereum => "ethereum",
+            Self::Optimism => "optimism",
+            Self::Polygon => "polygon",
+            Self::Base => "base",
+            Self::Arbitrum => "arbitrum",
+        };
+        f.write_str(name)
+    }
+}
+
 /// A Yearn vault
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Vault {
     /// Vault contract address
+    #[serde(deserialize_with = "deserialize_address")]
     pub address: String,
     /// Vault name
     pub name: Option<String>,
@@ -69,6 +152,7 @@ pub struct Vault {
     /// Vault type (0 = default, 1 = automated, 2 = multi-strategy)
     pub vault_type: Option<i32>,
     /// Underlying asset token address
+    #[serde(default, deserialize_with = "deserialize_optional_address")]
     pub token: Option<String>,
     /// Total assets in the vault (raw)
     #[serde(default)]
@@ -92,14 +176,19 @@ pub struct Vault {
     #[serde(default)]
     pub performance_fee: Option<String>,
     /// Governance address
+    #[serde(default, deserialize_with = "deserialize_optional_address")]
     pub governance: Option<String>,
     /// Guardian address
+    #[serde(default, deserialize_with = "deserialize_optional_address")]
     pub guardian: Option<String>,
     /// Management address
+    #[serde(default, deserialize_with = "deserialize_optional_address")]
     pub management: Option<String>,
     /// Rewards address
+    #[serde(default, deserialize_with = "deserialize_optional_address")]
     pub rewards: Option<String>,
     /// Registry address
+    #[serde(default, deserialize_with = "deserialize_optional_address")]
     pub registry: Option<String>,
     /// Inception timestamp (returned as string from API)
     #[serde(default)]
@@ -118,10 +207,10 @@ pub struct Vault {
     /// Project name
     pub project_name: Option<String>,
     /// Withdrawal queue
-    #[serde(default)]
+    #[serde(default, deserialize_with = "deserialize_address_list")]
     pub withdrawal_queue: Option<Vec<String>>,
     /// Strategy addresses
-    #[serde(default)]
+    #[serde(default, deserialize_with = "deserialize_address_list")]
     pub strategies: Option<Vec<String>>,
     /// TVL data
     pub tvl: Option<SparklinePoint>,
@@ -137,11 +226,34 @@ pub struct Vault {
     pub asset: Option<Erc20>,
 }
 
+/// Lightweight vault projection for listings
+///
+/// Returned by [`crate::VaultsApi::summaries`]; fetch the full [`Vault`]
+/// with [`crate::VaultsApi::get`] when more detail is needed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultSummary {
+    /// Vault contract address
+    #[serde(deserialize_with = "deserialize_address")]
+    pub address: String,
+    /// Vault name
+    pub name: Option<String>,
+    /// Vault symbol
+    pub symbol: Option<String>,
+    /// Chain ID
+    pub chain_id: u64,
+    /// TVL in USD
+    pub tvl: Option<f64>,
+    /// Net APY (annualized, as a fraction)
+    pub apy_net: Option<f64>,
+}
+
 /// A Yearn strategy
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Strategy {
     /// Strategy contract address
+    #[serde(deserialize_with = "deserialize_address")]
     pub address: String,
     /// Strategy name
     pub name: Option<String>,
@@ -150,6 +262,7 @@ pub struct Strategy {
     /// API version
     pub api_version: Option<String>,
     /// Vault address this strategy belongs to
+    #[serde(default, deserialize_with = "deserialize_optional_address")]
     pub vault: Option<String>,
     /// Whether this is a v3 strategy
     pub v3: Option<bool>,
@@ -188,8 +301,10 @@ pub struct Strategy {
     /// Whether the strategy is shutdown
     pub is_shutdown: Option<bool>,
     /// Keeper address
+    #[serde(default, deserialize_with = "deserialize_optional_address")]
     pub keeper: Option<String>,
     /// Strategist address
+    #[serde(default, deserialize_with = "deserialize_optional_address")]
     pub strategist: Option<String>,
     /// Risk score
     pub risk: Option<RiskScore>,
@@ -199,6 +314,74 @@ pub struct Strategy {
     pub tvl: Option<SparklinePoint>,
 }
 
+/// Debt ratio tolerance, in basis points, before a strategy counts as over-allocated
+pub const SOLVENCY_TOLERANCE_BPS: u16 = 100;
+
+/// A strategy holding more of a vault's assets than its authorised debt ratio
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SolvencyViolation {
+    /// Strategy address
+    pub strategy_address: String,
+    /// Authorised debt ratio (basis points of vault assets)
+    pub authorised_bps: u16,
+    /// Actual debt as basis points of vault assets
+    pub actual_bps: u16,
+    /// Percentage points of vault assets above the authorised ratio
+    pub excess_pct: f64,
+}
+
+/// Result of checking a vault's strategies against their debt ratios
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SolvencyReport {
+    /// True when no strategy exceeds its authorised ratio
+    pub is_solvent: bool,
+    /// Strategies over their authorised ratio
+    pub violations: Vec<SolvencyViolation>,
+}
+
+impl SolvencyReport {
+    /// Check strategies against a vault's total assets (raw units)
+    ///
+    /// A strategy violates when its actual debt ratio exceeds the authorised
+    /// one by more than [`SOLVENCY_TOLERANCE_BPS`]. Strategies without a
+    /// `debtRatio` (e.g. v3 strategies) or `totalDebt` are skipped.
+    /// `vault_total_assets` must be non-zero: against zero assets any debt
+    /// counts as a violation.
+    #[must_use]
+    pub fn from_strategies(vault_total_assets: u128, strategies: &[Strategy]) -> Self {
+        let violations: Vec<SolvencyViolation> = strategies
+            .iter()
+            .filter_map(|strategy| {
+                let authorised = strategy.debt_ratio.as_deref()?.parse::<u16>().ok()?;
+                let debt = strategy.total_debt.as_deref()?.parse::<u128>().ok()?;
+                let actual = if debt == 0 {
+                    0
+                } else {
+                    debt.saturating_mul(10_000)
+                        .checked_div(vault_total_assets)
+                        .and_then(|bps| u16::try_from(bps).ok())
+                        .unwrap_or(u16::MAX)
+                };
+
+                (u32::from(actual) > u32::from(authorised) + u32::from(SOLVENCY_TOLERANCE_BPS))
+                    .then(|| SolvencyViolation {
+                        strategy_address: strategy.address.clone(),
+                        authorised_bps: authorised,
+                        actual_bps: actual,
+                        excess_pct: f64::from(actual - authorised) / 100.0,
+                    })
+            })
+            .collect();
+
+        Self {
+            is_solvent: violations.is_empty(),
+            violations,
+        }
+    }
+}
+
 /// APY (Annual Percentage Yield) data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -250,6 +433,297 @@ pub struct Fees {
 pub struct RiskScore {
     /// Overall risk level (1-5, lower is safer)
     pub risk_level: Option<i32>,
+    /// Risk group, usually the underlying protocol (e.g. "Curve")
+    #[serde(default)]
+    pub risk_group: Option<String>,
+    /// Audit score (1-5, lower is safer; absent if unaudited)
+    #[serde(default)]
+    pub audit_score: Option<f64>,
+    /// Code review score (1-5, lower is safer)
+    #[serde(default)]
+    pub code_review_score: Option<f64>,
+    /// Complexity score (1-5, lower is safer)
+    #[serde(default)]
+    pub complexity_score: Option<f64>,
+    /// Protocol safety score (1-5, lower is safer)
+    #[serde(default)]
+    pub protocol_safety_score: Option<f64>,
+}
+
+/// Weights for [`VaultRisk::composite_risk_score`]
+///
+/// Only the ratios matter; weights need not sum to one.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RiskWeights {
+    /// Debt-weighted strategy risk level
+    pub strategy: f64,
+    /// Protocol concentration (Herfindahl index)
+    pub concentration: f64,
+    /// Share of assets deployed rather than idle
+    pub liquidity: f64,
+    /// Share of debt in strategies without an audit score
+    pub audit: f64,
+}
+
+impl Default for RiskWeights {
+    fn default() -> Self {
+        Self {
+            strategy: 0.4,
+            concentration: 0.25,
+            liquidity: 0.15,
+            audit: 0.2,
+        }
+    }
+}
+
+/// Risk attributes of one strategy within its vault
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StrategyRisk {
+    /// Strategy address
+    pub address: String,
+    /// Strategy name
+    pub name: Option<String>,
+    /// Risk level (1-5, lower is safer)
+    pub risk_level: Option<i32>,
+    /// Risk group, usually the underlying protocol
+    pub risk_group: Option<String>,
+    /// Audit score (1-5, lower is safer)
+    pub audit_score: Option<f64>,
+    /// Share of the vault's strategy debt (0-1)
+    pub debt_share: f64,
+}
+
+impl StrategyRisk {
+    /// Build risks for a vault's strategies
+    ///
+    /// Debt shares are taken from `totalDebt`. If no strategy reports debt
+    /// they come from the authorised `debtRatio`s instead, and if there are
+    /// none of those either every strategy gets an equal share.
+    #[must_use]
+    pub fn from_strategies(strategies: &[Strategy]) -> Vec<Self> {
+        let amounts = |field: fn(&Strategy) -> Option<&str>| -> Vec<f64> {
+            strategies
+                .iter()
+                .map(|s| field(s).and_then(|d| d.parse::<f64>().ok()).unwrap_or(0.0))
+                .collect()
+        };
+        let mut debts = amounts(|s| s.total_debt.as_deref());
+        let mut total: f64 = debts.iter().sum();
+        if total <= 0.0 {
+            debts = amounts(|s| s.debt_ratio.as_deref());
+            total = debts.iter().sum();
+        }
+
+        strategies
+            .iter()
+            .zip(debts)
+            .map(|(strategy, debt)| {
+                let risk = strategy.risk.as_ref();
+                Self {
+                    address: strategy.address.clone(),
+                    name: strategy.name.clone(),
+                    risk_level: risk.and_then(|r| r.risk_level),
+                    risk_group: risk.and_then(|r| r.risk_group.clone()),
+                    audit_score: risk.and_then(|r| r.audit_score),
+                    debt_share: if total > 0.0 {
+                        debt / total
+                    } else {
+                        1.0 / strategies.len() as f64
+                    },
+                }
+            })
+            .collect()
+    }
+}
+
+/// Debt allocation and risk across a vault's strategies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RiskSummary {
+    /// Vault address
+    pub vault: String,
+    /// Sum of the strategies' `totalDebt` (raw asset units)
+    pub total_debt: u128,
+    /// Number of strategies
+    pub strategy_count: usize,
+    /// Largest single strategy's share of debt, in percent (0 without strategies)
+    pub concentration_pct: f64,
+    /// Strategies by descending debt share
+    pub strategies: Vec<StrategyRisk>,
+}
+
+impl RiskSummary {
+    /// Summarise a vault's strategies
+    ///
+    /// Debt shares follow [`StrategyRisk::from_strategies`], so a vault with
+    /// a single strategy is 100% concentrated.
+    #[must_use]
+    pub fn from_strategies(vault: impl Into<String>, strategies: &[Strategy]) -> Self {
+        let mut risks = StrategyRisk::from_strategies(strategies);
+        risks.sort_by(|a, b| b.debt_share.total_cmp(&a.debt_share));
+
+        Self {
+            vault: vault.into(),
+            total_debt: strategies
+                .iter()
+                .filter_map(|s| s.total_debt.as_deref()?.parse::<u128>().ok())
+                .fold(0u128, u128::saturating_add),
+            strategy_count: strategies.len(),
+            concentration_pct: risks.first().map_or(0.0, |r| r.debt_share * 100.0),
+            strategies: risks,
+        }
+    }
+
+    /// The strategy holding the largest share of debt
+    #[must_use]
+    pub fn largest(&self) -> Option<&StrategyRisk> {
+        self.strategies.first()
+    }
+}
+
+/// Debt held in one protocol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolShare {
+    /// Protocol (risk group), or `unknown`
+    pub protocol: String,
+    /// Share of the vault's strategy debt (0-1)
+    pub debt_share: f64,
+    /// Number of strategies in the protocol
+    pub strategy_count: usize,
+}
+
+/// How a vault's strategy debt is spread across protocols
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolBreakdown {
+    /// Protocols by descending debt share
+    pub protocols: Vec<ProtocolShare>,
+    /// Herfindahl index of the shares (0 = spread out, 1 = one protocol)
+    pub herfindahl_index: f64,
+}
+
+impl ProtocolBreakdown {
+    /// Group strategy risks by risk group
+    ///
+    /// Strategies without a risk group are pooled under `unknown`.
+    #[must_use]
+    pub fn from_strategy_risks(risks: &[StrategyRisk]) -> Self {
+        let mut by_protocol: BTreeMap<&str, (f64, usize)> = BTreeMap::new();
+        for risk in risks {
+            let entry = by_protocol
+                .entry(risk.risk_group.as_deref().unwrap_or("unknown"))
+                .or_default();
+            entry.0 += risk.debt_share;
+            entry.1 += 1;
+        }
+
+        let mut protocols: Vec<ProtocolShare> = by_protocol
+            .into_iter()
+            .map(|(protocol, (debt_share, strategy_count))| ProtocolShare {
+                protocol: protocol.to_string(),
+                debt_share,
+                strategy_count,
+            })
+            .collect();
+        // Stable sort keeps protocol names alphabetical among equal shares
+        protocols.sort_by(|a, b| b.debt_share.total_cmp(&a.debt_share));
+
+        Self {
+            herfindahl_index: protocols.iter().map(|p| p.debt_share.powi(2)).sum(),
+            protocols,
+        }
+    }
+
+    /// Largest single-protocol share (0 if there are no strategies)
+    #[must_use]
+    pub fn largest_share(&self) -> f64 {
+        self.protocols.first().map_or(0.0, |p| p.debt_share)
+    }
+}
+
+/// All risk dimensions of a vault
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultRisk {
+    /// Per-strategy risk
+    pub strategy_risk: Vec<StrategyRisk>,
+    /// Debt spread across protocols
+    pub protocol_concentration: ProtocolBreakdown,
+    /// Share of vault assets held idle and withdrawable at once (0-1), or
+    /// `None` when the vault reports no total assets
+    pub liquidity_score: Option<f64>,
+    /// Share of strategy debt in strategies with an audit score (0-1)
+    pub audit_coverage: f64,
+    /// Days since the last audit, when known (Kong does not publish audit dates)
+    pub days_since_audit: Option<u64>,
+    /// Weighted risk score, 0 (safest) to 100
+    pub composite_risk_score: u8,
+}
+
+impl VaultRisk {
+    /// Assess a vault from its strategies
+    ///
+    /// Each dimension is scaled to 0-1 risk before weighting: strategy risk
+    /// level 1-5 maps to 0-1 (unknown counts as 5), concentration is the
+    /// Herfindahl index, and liquidity and audit risk are one minus their
+    /// scores. Unknown liquidity counts as fully illiquid.
+    #[must_use]
+    pub fn new(vault: &Vault, strategies: &[Strategy], weights: &RiskWeights) -> Self {
+        let strategy_risk = StrategyRisk::from_strategies(strategies);
+        let protocol_concentration = ProtocolBreakdown::from_strategy_risks(&strategy_risk);
+
+        let parse = |value: Option<&str>| value.and_then(|v| v.parse::<f64>().ok());
+        let total_debt: f64 = strategies
+            .iter()
+            .filter_map(|s| parse(s.total_debt.as_deref()))
+            .sum();
+        let liquidity_score = parse(vault.total_assets.as_deref())
+            .filter(|total_assets| *total_assets > 0.0)
+            .map(|total_assets| ((total_assets - total_debt) / total_assets).clamp(0.0, 1.0));
+
+        let audit_coverage = if strategy_risk.is_empty() {
+            1.0
+        } else {
+            strategy_risk
+                .iter()
+                .filter(|r| r.audit_score.is_some())
+                .map(|r| r.debt_share)
+                .sum::<f64>()
+                .min(1.0)
+        };
+
+        let level_risk: f64 = strategy_risk
+            .iter()
+            .map(|r| {
+                let level = r.risk_level.map_or(5, |l| l.clamp(1, 5));
+                r.debt_share * f64::from(level - 1) / 4.0
+            })
+            .sum();
+
+        let total_weight =
+            weights.strategy + weights.concentration + weights.liquidity + weights.audit;
+        let composite = if total_weight > 0.0 {
+            (weights.strategy * level_risk
+                + weights.concentration * protocol_concentration.herfindahl_index
+                + weights.liquidity * (1.0 - liquidity_score.unwrap_or(0.0))
+                + weights.audit * (1.0 - audit_coverage))
+                / total_weight
+        } else {
+            0.0
+        };
+
+        Self {
+            strategy_risk,
+            protocol_concentration,
+            liquidity_score,
+            audit_coverage,
+            days_since_audit: None,
+            composite_risk_score: (composite * 100.0).round().clamp(0.0, 100.0) as u8,
+        }
+    }
 }
 
 /// Vault metadata
@@ -273,6 +747,7 @@ pub struct VaultMeta {
 #[serde(rename_all = "camelCase")]
 pub struct Erc20 {
     /// Token address
+    #[serde(deserialize_with = "deserialize_address")]
     pub address: String,
     /// Token name
     pub name: Option<String>,
@@ -288,6 +763,7 @@ pub struct Erc20 {
 #[serde(rename_all = "camelCase")]
 pub struct Price {
     /// Token address
+    #[serde(deserialize_with = "deserialize_address")]
     pub address: String,
     /// Chain ID
     pub chain_id: u64,
@@ -308,6 +784,7 @@ pub struct Tvl {
     /// Chain ID
     pub chain_id: u64,
     /// Address (vault or strategy)
+    #[serde(deserialize_with = "deserialize_address")]
     pub address: String,
     /// TVL value in USD
     pub value: f64,
@@ -330,10 +807,12 @@ pub struct VaultReport {
     /// Chain ID
     pub chain_id: u64,
     /// Vault address
+    #[serde(deserialize_with = "deserialize_address")]
     pub address: String,
     /// Event name
     pub event_name: String,
     /// Strategy address
+    #[serde(deserialize_with = "deserialize_address")]
     pub strategy: String,
     /// Gain amount (raw)
     pub gain: String,
@@ -404,6 +883,7 @@ pub struct StrategyReport {
     /// Chain ID
     pub chain_id: u64,
     /// Strategy address
+    #[serde(deserialize_with = "deserialize_address")]
     pub address: String,
     /// Event name
     pub event_name: String,
@@ -459,6 +939,999 @@ pub struct ReportApr {
     pub forward: Option<f64>,
 }
 
+/// Summary statistics over a vault's report history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultReportStats {
+    /// Vault address
+    pub address: String,
+    /// Number of reports the stats were computed from
+    pub report_count: usize,
+    /// Mean net APR across reports that carry one
+    pub avg_apy: Option<f64>,
+    /// Population standard deviation of the net APR (lower is more consistent)
+    pub apy_std_dev: Option<f64>,
+    /// Percentage of reports that recorded a loss
+    pub loss_rate_pct: f64,
+    /// Sum of `totalFees` across all reports (raw asset units, so not
+    /// comparable between vaults with different decimals)
+    pub total_fees_paid: u128,
+    /// Sum of `totalFeesUsd` across reports that carry one
+    pub total_fees_usd: Option<f64>,
+    /// Fees as a fraction of gross gains in USD, when both are priced
+    pub fee_ratio: Option<f64>,
+}
+
+impl VaultReportStats {
+    /// Compute stats from a vault's reports
+    #[must_use]
+    pub fn from_reports(address: impl Into<String>, reports: &[VaultReport]) -> Self {
+        let aprs: Vec<f64> = reports
+            .iter()
+            .filter_map(|r| r.apr.as_ref().and_then(|a| a.net))
+            .collect();
+        let (avg_apy, apy_std_dev) = if aprs.is_empty() {
+            (None, None)
+        } else {
+            let n = aprs.len() as f64;
+            let mean = aprs.iter().sum::<f64>() / n;
+            let variance = aprs.iter().map(|a| (a - mean).powi(2)).sum::<f64>() / n;
+            (Some(mean), Some(variance.sqrt()))
+        };
+
+        let losses = reports
+            .iter()
+            .filter(|r| r.loss.parse::<u128>().map(|l| l > 0).unwrap_or(false))
+            .count();
+        let loss_rate_pct = if reports.is_empty() {
+            0.0
+        } else {
+            losses as f64 / reports.len() as f64 * 100.0
+        };
+
+        let total_fees_paid = reports
+            .iter()
+            .filter_map(|r| r.total_fees.as_deref())
+            .filter_map(|f| f.parse::<u128>().ok())
+            .fold(0u128, u128::saturating_add);
+
+        let priced_fees: Vec<f64> = reports.iter().filter_map(|r| r.total_fees_usd).collect();
+        let total_fees_usd = (!priced_fees.is_empty()).then(|| priced_fees.iter().sum::<f64>());
+        let gains_usd: f64 = reports.iter().filter_map(|r| r.gain_usd).sum();
+        let fee_ratio = (gains_usd > 0.0).then(|| total_fees_usd.unwrap_or(0.0) / gains_usd);
+
+        Self {
+            address: address.into(),
+            report_count: reports.len(),
+            avg_apy,
+            apy_std_dev,
+            loss_rate_pct,
+            total_fees_paid,
+            total_fees_usd,
+            fee_ratio,
+        }
+    }
+}
+
+/// Head-to-head comparison of two vaults' report histories
+///
+/// Each `winner_by_*` field holds the winning vault's address, or `"tie"`.
+/// A vault without the data for a category (no reports, no net APRs, no
+/// USD-priced fees) is left out of it: the other vault wins, or it's a tie
+/// when neither has the data.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrossVaultComparison {
+    /// Stats for the first vault
+    pub vault_a_stats: VaultReportStats,
+    /// Stats for the second vault
+    pub vault_b_stats: VaultReportStats,
+    /// Vault with the higher average APY
+    pub winner_by_apy: String,
+    /// Vault with the lower APY standard deviation
+    pub winner_by_consistency: String,
+    /// Vault with the lower fee-to-gain ratio (total USD fees if gains
+    /// are unpriced)
+    pub winner_by_fee_efficiency: String,
+}
+
+impl CrossVaultComparison {
+    /// Label used when neither vault wins a category
+    pub const TIE: &'static str = "tie";
+
+    /// Compare two sets of vault stats
+    #[must_use]
+    pub fn new(vault_a_stats: VaultReportStats, vault_b_stats: VaultReportStats) -> Self {
+        use std::cmp::Ordering;
+
+        let pick = |ordering: Ordering| match ordering {
+            Ordering::Greater => vault_a_stats.address.clone(),
+            Ordering::Less => vault_b_stats.address.clone(),
+            Ordering::Equal => Self::TIE.to_string(),
+        };
+        // Higher wins; a vault without the figure (e.g. no reports) loses
+        let better = |a: Option<f64>, b: Option<f64>| match (a, b) {
+            (Some(a), Some(b)) => a.total_cmp(&b),
+            (Some(_), None) => Ordering::Greater,
+            (None, Some(_)) => Ordering::Less,
+            (None, None) => Ordering::Equal,
+        };
+        let (a, b) = (&vault_a_stats, &vault_b_stats);
+        let neg = |value: Option<f64>| value.map(|v| -v);
+
+        let winner_by_apy = pick(better(a.avg_apy, b.avg_apy));
+        let winner_by_consistency = pick(better(neg(a.apy_std_dev), neg(b.apy_std_dev)));
+        // Raw fee amounts aren't comparable across decimals, so stay in USD
+        let winner_by_fee_efficiency = pick(match (a.fee_ratio, b.fee_ratio) {
+            (Some(_), Some(_)) => better(neg(a.fee_ratio), neg(b.fee_ratio)),
+            _ => better(neg(a.total_fees_usd), neg(b.total_fees_usd)),
+        });
+
+        Self {
+            vault_a_stats,
+            vault_b_stats,
+            winner_by_apy,
+            winner_by_consistency,
+            winner_by_fee_efficiency,
+        }
+    }
+}
+
+/// Risk tolerance used when recommending a vault
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RiskProfile {
+    /// Only the lowest-risk vaults (risk level 1-2)
+    Conservative,
+    /// Vaults up to risk level 3
+    #[default]
+    Moderate,
+    /// Any risk level
+    Aggressive,
+}
+
+impl RiskProfile {
+    /// Highest Kong risk level (1-5) acceptable for this profile
+    #[must_use]
+    pub fn max_risk_level(self) -> i32 {
+        match self {
+            Self::Conservative => 2,
+            Self::Moderate => 3,
+            Self::Aggressive => 5,
+        }
+    }
+}
+
+/// A recommended vault for depositing a token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultRecommendation {
+    /// The recommended vault
+    pub vault: Vault,
+    /// Human-readable explanation of the pick
+    pub rationale: String,
+    /// Expected yield over one year at the current net APY
+    pub expected_annual_yield_usd: f64,
+    /// Estimated management and performance fees over one year
+    pub estimated_fees_usd: f64,
+    /// Whether a retired vault for the same token exists, so deposits there
+    /// may want to move here
+    ///
+    /// No migration path (migrator contract or registry entry) is checked;
+    /// moving means withdrawing and depositing.
+    pub replaces_retired_vault: bool,
+}
+
+impl VaultRecommendation {
+    /// Pick the best vault for `token_address` from a chain's vault list
+    ///
+    /// Shutdown and hidden vaults, and vaults above the profile's risk level,
+    /// are skipped; vaults without a risk score count as the highest risk.
+    /// Endorsed (`yearn`) vaults are preferred and the remaining candidates
+    /// are only considered when no endorsed vault qualifies. The highest net
+    /// APY wins, with TVL as the tie-breaker.
+    #[must_use]
+    pub fn select(
+        vaults: &[Vault],
+        token_address: &str,
+        risk_profile: RiskProfile,
+        amount_usd: f64,
+    ) -> Option<Self> {
+        let for_token: Vec<&Vault> = vaults
+            .iter()
+            .filter(|v| v.accepts_token(token_address))
+            .collect();
+
+        let candidates: Vec<&Vault> = for_token
+            .iter()
+            .copied()
+            .filter(|v| !v.is_retired())
+            .filter(|v| !v.meta.as_ref().and_then(|m| m.is_hidden).unwrap_or(false))
+            .filter(|v| {
+                v.risk.as_ref().and_then(|r| r.risk_level).unwrap_or(5)
+                    <= risk_profile.max_risk_level()
+            })
+            .collect();
+
+        let endorsed: Vec<&Vault> = candidates
+            .iter()
+            .copied()
+            .filter(|v| v.is_endorsed())
+            .collect();
+        let is_endorsed = !endorsed.is_empty();
+        let pool = if is_endorsed { endorsed } else { candidates };
+
+        let best = pool.into_iter().max_by(|a, b| {
+            a.net_apy()
+                .total_cmp(&b.net_apy())
+                .then(a.tvl_usd().total_cmp(&b.tvl_usd()))
+        })?;
+
+        let net_apy = best.net_apy();
+        let expected_annual_yield_usd = amount_usd * net_apy;
+        let estimated_fees_usd = best.fees.as_ref().map_or(0.0, |fees| {
+            let gross = best
+                .apy
+                .as_ref()
+                .and_then(|a| a.gross_apr)
+                .unwrap_or(net_apy);
+            let management = amount_usd * fees.management_fee.unwrap_or(0.0) / 10_000.0;
+            let performance = amount_usd * gross * fees.performance_fee.unwrap_or(0.0) / 10_000.0;
+            management + performance
+        });
+        let replaces_retired_vault = for_token
+            .iter()
+            .any(|v| v.is_retired() && !v.address.eq_ignore_ascii_case(&best.address));
+
+        let symbol = best
+            .asset
+            .as_ref()
+            .and_then(|a| a.symbol.clone())
+            .unwrap_or_else(|| token_address.to_string());
+        let rationale = if is_endorsed {
+            format!(
+                "Best endorsed {symbol} vault with {:.1}% APY and {} TVL",
+                net_apy * 100.0,
+                format_usd_compact(best.tvl_usd())
+            )
+        } else {
+            format!(
+                "Best {symbol} vault with {:.1}% APY and {} TVL (no endorsed vault available)",
+                net_apy * 100.0,
+                format_usd_compact(best.tvl_usd())
+            )
+        };
+
+        Some(Self {
+            vault: best.clone(),
+            rationale,
+            expected_annual_yield_usd,
+            estimated_fees_usd,
+            replaces_retired_vault,
+        })
+    }
+}
+
+impl Vault {
+    /// Whether the vault is an official Yearn (endorsed) vault
+    #[must_use]
+    pub fn is_endorsed(&self) -> bool {
+        self.yearn.unwrap_or(false)
+    }
+
+    /// Whether the vault is shut down or in emergency shutdown
+    #[must_use]
+    pub fn is_retired(&self) -> bool {
+        self.is_shutdown.unwrap_or(false) || self.emergency_shutdown.unwrap_or(false)
+    }
+
+    /// Net APY as a fraction, falling back to weekly then monthly net
+    #[must_use]
+    pub fn net_apy(&self) -> f64 {
+        self.reported_net_apy().unwrap_or(0.0)
+    }
+
+    /// [`net_apy`](Self::net_apy), or `None` if the vault reports no APY
+    #[must_use]
+    pub fn reported_net_apy(&self) -> Option<f64> {
+        self.apy
+            .as_ref()
+            .and_then(|a| a.net.or(a.weekly_net).or(a.monthly_net))
+    }
+
+    /// Latest TVL in USD
+    #[must_use]
+    pub fn tvl_usd(&self) -> f64 {
+        self.tvl.as_ref().and_then(|t| t.close).unwrap_or(0.0)
+    }
+
+    /// Whether the underlying asset's symbol is `symbol` (case-insensitive)
+    #[must_use]
+    pub fn has_asset_symbol(&self, symbol: &str) -> bool {
+        self.asset
+            .as_ref()
+            .and_then(|a| a.symbol.as_deref())
+            .is_some_and(|s| s.eq_ignore_ascii_case(symbol))
+    }
+
+    /// Highest net APY endorsed vault for an asset symbol
+    ///
+    /// Shutdown vaults are skipped; TVL breaks ties.
+    #[must_use]
+    pub fn top_yield<'a>(vaults: &'a [Vault], asset_symbol: &str) -> Option<&'a Vault> {
+        vaults
+            .iter()
+            .filter(|v| v.is_endorsed() && !v.is_retired() && v.has_asset_symbol(asset_symbol))
+            .max_by(|a, b| {
+                a.net_apy()
+                    .total_cmp(&b.net_apy())
+                    .then(a.tvl_usd().total_cmp(&b.tvl_usd()))
+            })
+    }
+
+    /// Whether the vault's underlying token is `token_address`
+    ///
+    /// Uses `token`, falling back to `asset.address`; case-insensitive.
+    #[must_use]
+    pub fn accepts_token(&self, token_address: &str) -> bool {
+        self.token
+            .as_deref()
+            .or(self.asset.as_ref().map(|a| a.address.as_str()))
+            .is_some_and(|t| t.eq_ignore_ascii_case(token_address))
+    }
+}
+
+/// Headline metrics for a single vault
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultMetrics {
+    /// Vault contract address
+    pub address: String,
+    /// Vault name
+    pub name: Option<String>,
+    /// Net APY as a fraction (see [`Vault::net_apy`])
+    pub net_apy: f64,
+    /// Latest TVL in USD
+    pub tvl_usd: f64,
+}
+
+impl From<&Vault> for VaultMetrics {
+    fn from(vault: &Vault) -> Self {
+        Self {
+            address: vault.address.clone(),
+            name: vault.name.clone(),
+            net_apy: vault.net_apy(),
+            tvl_usd: vault.tvl_usd(),
+        }
+    }
+}
+
+/// APY spread between the best and worst vaults for one underlying token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YieldGap {
+    /// Vault with the highest net APY
+    pub best_vault: VaultMetrics,
+    /// Vault with the lowest net APY
+    pub worst_vault: VaultMetrics,
+    /// Net APY difference in percentage points
+    pub gap_pct: f64,
+    /// Underlying token shared by both vaults (symbol, or address if unknown)
+    pub tokens_in_common: String,
+}
+
+impl YieldGap {
+    /// Find the widest net APY spread among `vaults`
+    ///
+    /// Vaults without a reported APY are skipped rather than counted as 0%.
+    /// Ties on APY are broken by TVL, so the larger vault is the best and the
+    /// smaller the worst. Returns `None` for fewer than two vaults with an
+    /// APY.
+    #[must_use]
+    pub fn from_vaults(vaults: &[Vault], token_address: &str) -> Option<Self> {
+        let vaults: Vec<&Vault> = vaults
+            .iter()
+            .filter(|v| v.reported_net_apy().is_some())
+            .collect();
+        if vaults.len() < 2 {
+            return None;
+        }
+        let by_apy = |a: &&Vault, b: &&Vault| {
+            a.net_apy()
+                .total_cmp(&b.net_apy())
+                .then(a.tvl_usd().total_cmp(&b.tvl_usd()))
+        };
+        let best = vaults.iter().copied().max_by(by_apy)?;
+        let worst = vaults.iter().copied().min_by(by_apy)?;
+        let tokens_in_common = best
+            .asset
+            .as_ref()
+            .and_then(|a| a.symbol.clone())
+            .unwrap_or_else(|| token_address.to_string());
+
+        Some(Self {
+            gap_pct: (best.net_apy() - worst.net_apy()) * 100.0,
+            best_vault: best.into(),
+            worst_vault: worst.into(),
+            tokens_in_common,
+        })
+    }
+}
+
+/// One vault in an age-vs-TVL scatter plot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgeVsTvlPoint {
+    /// Vault contract address
+    pub vault_address: String,
+    /// Whole days since inception
+    pub age_days: u64,
+    /// Latest TVL in USD
+    pub tvl_usd: f64,
+    /// Net APY in percent
+    pub net_apy_pct: f64,
+    /// Whether the vault is an official Yearn vault
+    pub is_endorsed: bool,
+}
+
+impl AgeVsTvlPoint {
+    /// Build points from vaults, sorted by `age_days` ascending
+    ///
+    /// Age is measured from `inceptTime`, falling back to `activation`;
+    /// vaults with neither are skipped. `now` is a unix timestamp in seconds.
+    #[must_use]
+    pub fn from_vaults(vaults: &[Vault], now: u64) -> Vec<Self> {
+        let mut points: Vec<Self> = vaults
+            .iter()
+            .filter_map(|vault| {
+                let created = vault
+                    .incept_time
+                    .as_deref()
+                    .or(vault.activation.as_deref())?
+                    .parse::<u64>()
+                    .ok()?;
+                Some(Self {
+                    vault_address: vault.address.clone(),
+                    age_days: now.saturating_sub(created) / 86_400,
+                    tvl_usd: vault.tvl_usd(),
+                    net_apy_pct: vault.net_apy() * 100.0,
+                    is_endorsed: vault.is_endorsed(),
+                })
+            })
+            .collect();
+        points.sort_by_key(|p| p.age_days);
+        points
+    }
+}
+
+/// Maximum number of entries in [`ProtocolTvlBreakdown::top_vaults`]
+pub const TOP_VAULTS_LIMIT: usize = 10;
+
+/// Total Yearn TVL split by chain, API version, asset and vault
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolTvlBreakdown {
+    /// Sum of every vault's latest TVL in USD
+    pub total_usd: f64,
+    /// TVL by chain ID
+    pub by_chain: HashMap<u64, f64>,
+    /// TVL by vault API version (`unknown` if unset)
+    pub by_version: HashMap<String, f64>,
+    /// TVL by underlying asset symbol (address, then `unknown`, if unset)
+    pub by_asset: HashMap<String, f64>,
+    /// Largest vaults as `(address, tvl_usd)`, descending, at most [`TOP_VAULTS_LIMIT`]
+    pub top_vaults: Vec<(String, f64)>,
+}
+
+impl ProtocolTvlBreakdown {
+    /// Aggregate the latest TVL of `vaults`
+    ///
+    /// Vaults are counted once per `(chain_id, address)`, so overlapping
+    /// listings do not inflate the total.
+    #[must_use]
+    pub fn from_vaults(vaults: &[Vault]) -> Self {
+        let mut breakdown = Self::default();
+        let mut seen = HashSet::new();
+        for vault in vaults {
+            if !seen.insert((vault.chain_id, vault.address.to_lowercase())) {
+                continue;
+            }
+            let tvl = vault.tvl_usd();
+            let version = vault.api_version.as_deref().unwrap_or("unknown");
+            let asset = vault
+                .asset
+                .as_ref()
+                .map(|a| a.symbol.as_deref().unwrap_or(&a.address))
+                .unwrap_or("unknown");
+
+            breakdown.total_usd += tvl;
+            *breakdown.by_chain.entry(vault.chain_id).or_default() += tvl;
+            *breakdown.by_version.entry(version.to_string()).or_default() += tvl;
+            *breakdown.by_asset.entry(asset.to_string()).or_default() += tvl;
+            breakdown.top_vaults.push((vault.address.clone(), tvl));
+        }
+        breakdown
+            .top_vaults
+            .sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        breakdown.top_vaults.truncate(TOP_VAULTS_LIMIT);
+        breakdown
+    }
+}
+
+/// One point of an aggregated TVL series
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TvlDataPoint {
+    /// Unix timestamp (seconds)
+    pub time: u64,
+    /// Summed TVL in USD
+    pub value: f64,
+}
+
+impl TvlDataPoint {
+    /// Sum per-vault TVL series into one series
+    ///
+    /// The output grid is the union of every timestamp seen in any series.
+    /// At each grid point a vault contributes its latest value at or before
+    /// that time (forward fill), or nothing if its series has not started:
+    ///
+    /// ```text
+    /// time      t0    t1    t2    t3
+    /// vault A   10    .     12    .      ->  10   10   12   12
+    /// vault B   .     5     .     7      ->   0    5    5    7
+    /// total                                  10   15   17   19
+    /// ```
+    ///
+    /// Points without a timestamp are ignored.
+    #[must_use]
+    pub fn sum_series(series: &[Vec<Tvl>]) -> Vec<Self> {
+        let mut sorted: Vec<Vec<(u64, f64)>> = series
+            .iter()
+            .map(|points| {
+                let mut points: Vec<(u64, f64)> = points
+                    .iter()
+                    .filter_map(|p| p.time.map(|t| (t, p.value)))
+                    .collect();
+                points.sort_by_key(|&(t, _)| t);
+                points
+            })
+            .collect();
+        sorted.retain(|points| !points.is_empty());
+
+        let mut grid: Vec<u64> = sorted.iter().flatten().map(|&(t, _)| t).collect();
+        grid.sort_unstable();
+        grid.dedup();
+
+        let mut cursors = vec![0usize; sorted.len()];
+        let mut last = vec![0.0; sorted.len()];
+        grid.into_iter()
+            .map(|time| {
+                let mut value = 0.0;
+                for (k, points) in sorted.iter().enumerate() {
+                    while cursors[k] < points.len() && points[cursors[k]].0 <= time {
+                        last[k] = points[cursors[k]].1;
+                        cursors[k] += 1;
+                    }
+                    value += last[k];
+                }
+                Self { time, value }
+            })
+            .collect()
+    }
+}
+
+/// How fees reduce a depositor's yield over a holding period
+///
+/// Gross yield compounds the gross APR daily. The management fee accrues
+/// linearly on the deposit and the performance fee is taken from gross gains.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeDrainAnalysis {
+    /// Deposit size in USD
+    pub deposit_amount_usd: f64,
+    /// Holding period in days
+    pub hold_days: u32,
+    /// Gross APR used for the projection (e.g., 0.08 = 8%)
+    pub gross_apr: f64,
+    /// Management fee (annual, basis points)
+    pub management_fee_bps: f64,
+    /// Performance fee (basis points)
+    pub performance_fee_bps: f64,
+    /// Yield before fees
+    pub gross_yield_usd: f64,
+    /// Management fee charged over the period
+    pub management_fee_usd: f64,
+    /// Performance fee charged on gross gains
+    pub performance_fee_usd: f64,
+    /// Yield after fees (negative if fees exceed gains)
+    pub net_yield_usd: f64,
+    /// Share of gross yield consumed by fees, in percent
+    pub fee_drag_pct: f64,
+}
+
+impl FeeDrainAnalysis {
+    /// Longest period `breakeven_days` searches (100 years)
+    pub const MAX_BREAKEVEN_DAYS: u32 = 36_500;
+
+    /// Project fees for a deposit held `hold_days` at `gross_apr`
+    ///
+    /// Missing fees count as zero.
+    #[must_use]
+    pub fn new(
+        deposit_amount_usd: f64,
+        hold_days: u32,
+        gross_apr: f64,
+        fees: Option<&Fees>,
+    ) -> Self {
+        let management_fee_bps = fees.and_then(|f| f.management_fee).unwrap_or(0.0);
+        let performance_fee_bps = fees.and_then(|f| f.performance_fee).unwrap_or(0.0);
+
+        let gross_yield_usd = deposit_amount_usd * compound_growth(gross_apr, hold_days);
+        let management_fee_usd =
+            deposit_amount_usd * management_fee_bps / 10_000.0 * f64::from(hold_days) / 365.0;
+        let performance_fee_usd = gross_yield_usd.max(0.0) * performance_fee_bps / 10_000.0;
+        let total_fees = management_fee_usd + performance_fee_usd;
+        let fee_drag_pct = if gross_yield_usd > 0.0 {
+            total_fees / gross_yield_usd * 100.0
+        } else {
+            0.0
+        };
+
+        Self {
+            deposit_amount_usd,
+            hold_days,
+            gross_apr,
+            management_fee_bps,
+            performance_fee_bps,
+            gross_yield_usd,
+            management_fee_usd,
+            performance_fee_usd,
+            net_yield_usd: gross_yield_usd - total_fees,
+            fee_drag_pct,
+        }
+    }
+
+    /// Days until yield after the performance fee covers the management fee
+    ///
+    /// Returns 0 without a management fee, and `u32::MAX` if the yield never
+    /// catches up within [`Self::MAX_BREAKEVEN_DAYS`].
+    #[must_use]
+    pub fn breakeven_days(&self) -> u32 {
+        if self.management_fee_bps <= 0.0 {
+            return 0;
+        }
+        let keep = 1.0 - self.performance_fee_bps / 10_000.0;
+        let daily_management = self.management_fee_bps / 10_000.0 / 365.0;
+        (1..=Self::MAX_BREAKEVEN_DAYS)
+            .find(|&day| {
+                compound_growth(self.gross_apr, day) * keep >= daily_management * f64::from(day)
+            })
+            .unwrap_or(u32::MAX)
+    }
+}
+
+/// Growth factor minus one for `apr` compounded daily over `days`
+fn compound_growth(apr: f64, days: u32) -> f64 {
+    (1.0 + apr / 365.0).powf(f64::from(days)) - 1.0
+}
+
+/// Format a USD amount as `$120M`, `$1.5B`, `$250K`
+fn format_usd_compact(value: f64) -> String {
+    let (scaled, suffix) = if value >= 1e9 {
+        (value / 1e9, "B")
+    } else if value >= 1e6 {
+        (value / 1e6, "M")
+    } else if value >= 1e3 {
+        (value / 1e3, "K")
+    } else {
+        return format!("${value:.0}");
+    };
+    let text = format!("{scaled:.1}");
+    format!("${}{suffix}", text.trim_end_matches(".0"))
+}
+
+/// Gas a strategy harvest is assumed to use
+pub const HARVEST_GAS_UNITS: u64 = 1_000_000;
+
+impl Strategy {
+    /// Profit accrued since the last report, in USD
+    ///
+    /// Accrues the gross APR (net APY if missing) linearly on the strategy's
+    /// TVL. Returns 0 without a last report. `now` is a unix timestamp.
+    #[must_use]
+    pub fn accrued_profit_usd(&self, now: u64) -> f64 {
+        let Some(last_report) = self.last_report else {
+            return 0.0;
+        };
+        let apr = self
+            .apy
+            .as_ref()
+            .and_then(|a| a.gross_apr.or(a.net))
+            .unwrap_or(0.0);
+        let tvl = self.tvl.as_ref().and_then(|t| t.close).unwrap_or(0.0);
+        let elapsed_days = now.saturating_sub(last_report) as f64 / 86_400.0;
+        tvl * apr * elapsed_days / 365.0
+    }
+}
+
+/// How overdue a stale strategy's harvest is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Urgency {
+    /// Past the window, but by less than 2x
+    Low,
+    /// Between 2x and 4x the window
+    Medium,
+    /// 4x the window or more
+    High,
+}
+
+impl Urgency {
+    /// Classify an age against the allowed window (both in hours)
+    #[must_use]
+    pub fn for_age(hours_since_harvest: u64, max_age_hours: u64) -> Self {
+        if hours_since_harvest >= max_age_hours.saturating_mul(4) {
+            Self::High
+        } else if hours_since_harvest >= max_age_hours.saturating_mul(2) {
+            Self::Medium
+        } else {
+            Self::Low
+        }
+    }
+}
+
+/// A strategy that has not harvested within the allowed window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaleStrategy {
+    /// Strategy address
+    pub strategy_address: String,
+    /// Vault the strategy reports to (empty if unknown)
+    pub vault_address: String,
+    /// Whole hours since the last report
+    pub hours_since_harvest: u64,
+    /// How overdue the harvest is
+    pub urgency: Urgency,
+}
+
+/// Harvest liveness of every active strategy on a chain
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LivenessReport {
+    /// Strategies that reported within the window
+    pub healthy: u32,
+    /// Strategies that did not
+    pub stale: u32,
+    /// Stale strategies, most overdue first
+    pub stale_strategies: Vec<StaleStrategy>,
+}
+
+impl LivenessReport {
+    /// Check `strategies` for reports older than `max_age_hours`
+    ///
+    /// Inactive and shutdown strategies are skipped. A strategy that has never
+    /// reported is aged from its activation (or inception) time, and skipped
+    /// if neither is known. `now` is a unix timestamp in seconds.
+    #[must_use]
+    pub fn from_strategies(strategies: &[Strategy], max_age_hours: u64, now: u64) -> Self {
+        let cutoff = now.saturating_sub(max_age_hours.saturating_mul(3_600));
+        let mut report = Self::default();
+        for strategy in strategies {
+            if strategy.is_active == Some(false) || strategy.is_shutdown == Some(true) {
+                continue;
+            }
+            let Some(last) = strategy
+                .last_report
+                .or(strategy.activation)
+                .or(strategy.incept_time)
+            else {
+                continue;
+            };
+            if last >= cutoff {
+                report.healthy += 1;
+                continue;
+            }
+            let hours_since_harvest = now.saturating_sub(last) / 3_600;
+            report.stale += 1;
+            report.stale_strategies.push(StaleStrategy {
+                strategy_address: strategy.address.clone(),
+                vault_address: strategy.vault.clone().unwrap_or_default(),
+                hours_since_harvest,
+                urgency: Urgency::for_age(hours_since_harvest, max_age_hours),
+            });
+        }
+        report
+            .stale_strategies
+            .sort_by_key(|s| std::cmp::Reverse(s.hours_since_harvest));
+        report
+    }
+}
+
+/// Whether harvesting a strategy now pays for its gas
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarvestRoi {
+    /// Total gas cost of the harvest in gwei
+    pub gas_cost_gwei: f64,
+    /// Gas cost in USD
+    pub gas_cost_usd: f64,
+    /// Profit the harvest would report, in USD
+    pub expected_profit_usd: f64,
+    /// Profit after gas relative to the gas cost (1.0 = profit is 2x gas)
+    pub net_roi: f64,
+    /// Whether the expected profit exceeds the gas cost
+    pub is_profitable: bool,
+}
+
+impl HarvestRoi {
+    /// Compute ROI from a total gas cost, the native token price and profit
+    ///
+    /// `net_roi` is 0 when the gas cost is 0.
+    #[must_use]
+    pub fn new(gas_cost_gwei: f64, native_price_usd: f64, expected_profit_usd: f64) -> Self {
+        let gas_cost_usd = gas_cost_gwei / 1e9 * native_price_usd;
+        let net_roi = if gas_cost_usd > 0.0 {
+            (expected_profit_usd - gas_cost_usd) / gas_cost_usd
+        } else {
+            0.0
+        };
+        Self {
+            gas_cost_gwei,
+            gas_cost_usd,
+            expected_profit_usd,
+            net_roi,
+            is_profitable: expected_profit_usd > gas_cost_usd,
+        }
+    }
+}
+
+/// How a vault changed between two snapshots
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum VaultChangeKind {
+    /// Vault appeared in the latest snapshot
+    Added,
+    /// Vault fields changed
+    Updated,
+    /// Vault is no longer in the latest snapshot
+    Removed,
+}
+
+/// One field that differs between two snapshots of a vault
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldChange {
+    /// Field path in API naming, with nested fields dotted (e.g. `apy.net`)
+    pub field: String,
+    /// Value in the previous snapshot
+    pub previous: serde_json::Value,
+    /// Value in the latest snapshot
+    pub current: serde_json::Value,
+}
+
+/// A vault change with the diff from its previous state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultUpdate {
+    /// Chain ID
+    pub chain_id: u64,
+    /// Vault contract address
+    pub address: String,
+    /// Kind of change
+    pub kind: VaultChangeKind,
+    /// Changed fields (empty for added and removed vaults)
+    pub changes: Vec<FieldChange>,
+    /// Latest vault state (`None` once removed)
+    pub vault: Option<Vault>,
+}
+
+impl VaultUpdate {
+    /// Diff two vault snapshots, matching vaults by address
+    ///
+    /// Updates follow the order of `current`, with removals last. Vaults
+    /// whose fields are all unchanged produce no update.
+    #[must_use]
+    pub fn diff(previous: &[Vault], current: &[Vault]) -> Vec<Self> {
+        let previous_by_address: HashMap<String, &Vault> = previous
+            .iter()
+            .map(|v| (v.address.to_lowercase(), v))
+            .collect();
+        let current_addresses: HashSet<String> =
+            current.iter().map(|v| v.address.to_lowercase()).collect();
+
+        let mut updates = Vec::new();
+        for vault in current {
+            let (kind, changes) = match previous_by_address.get(&vault.address.to_lowercase()) {
+                None => (VaultChangeKind::Added, Vec::new()),
+                Some(prev) => {
+                    let changes = field_changes(prev, vault);
+                    if changes.is_empty() {
+                        continue;
+                    }
+                    (VaultChangeKind::Updated, changes)
+                }
+            };
+            updates.push(Self {
+                chain_id: vault.chain_id,
+                address: vault.address.clone(),
+                kind,
+                changes,
+                vault: Some(vault.clone()),
+            });
+        }
+
+        updates.extend(
+            previous
+                .iter()
+                .filter(|v| !current_addresses.contains(&v.address.to_lowercase()))
+                .map(|v| Self {
+                    chain_id: v.chain_id,
+                    address: v.address.clone(),
+                    kind: VaultChangeKind::Removed,
+                    changes: Vec::new(),
+                    vault: None,
+                }),
+        );
+        updates
+    }
+}
+
+/// Field-level differences between two snapshots of one vault
+fn field_changes(previous: &Vault, current: &Vault) -> Vec<FieldChange> {
+    fn flatten(prefix: &str, value: serde_json::Value, out: &mut Vec<(String, serde_json::Value)>) {
+        match value {
+            serde_json::Value::Object(map) => {
+                for (key, value) in map {
+                    let path = if prefix.is_empty() {
+                        key
+                    } else {
+                        format!("{prefix}.{key}")
+                    };
+                    flatten(&path, value, out);
+                }
+            }
+            value => out.push((prefix.to_string(), value)),
+        }
+    }
+
+    let flat = |vault: &Vault| {
+        let mut out = Vec::new();
+        flatten(
+            "",
+            serde_json::to_value(vault).unwrap_or_default(),
+            &mut out,
+        );
+        out
+    };
+    let before: HashMap<String, serde_json::Value> = flat(previous).into_iter().collect();
+    let after = flat(current);
+    let after_fields: HashSet<&str> = after.iter().map(|(f, _)| f.as_str()).collect();
+
+    let mut changes: Vec<FieldChange> = after
+        .iter()
+        .filter_map(|(field, value)| {
+            let old = before.get(field).cloned().unwrap_or_default();
+            (old != *value).then(|| FieldChange {
+                field: field.clone(),
+                previous: old,
+                current: value.clone(),
+            })
+        })
+        .collect();
+    changes.extend(
+        before
+            .iter()
+            .filter(|(field, value)| !value.is_null() && !after_fields.contains(field.as_str()))
+            .map(|(field, value)| FieldChange {
+                field: field.clone(),
+                previous: value.clone(),
+                current: serde_json::Value::Null,
+            }),
+    );
+    changes.sort_by(|a, b| a.field.cmp(&b.field));
+    changes
+}
+
 /// TVL timeseries entry (legacy format)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -501,6 +1974,93 @@ pub struct VaultAccount {
     pub profit: Option<String>,
 }
 
+/// Timeseries point as returned by Kong, before parsing
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct RawTimeseriesPoint {
+    #[serde(default, deserialize_with = "deserialize_flexible_string")]
+    time: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_flexible_string")]
+    value: Option<String>,
+}
+
+impl RawTimeseriesPoint {
+    /// Whether the point carries a value (Kong returns `null` for gaps)
+    pub(crate) fn has_value(&self) -> bool {
+        self.value.is_some()
+    }
+}
+
+/// One dated point of a Kong timeseries (APY, price per share, ...)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RawTimeseriesPoint")]
+pub struct TimeseriesPoint {
+    /// Unix timestamp in seconds
+    pub time: u64,
+    /// Parsed value
+    pub value: f64,
+    /// Value exactly as returned by the API
+    pub raw: String,
+}
+
+impl TryFrom<RawTimeseriesPoint> for TimeseriesPoint {
+    type Error = String;
+
+    fn try_from(point: RawTimeseriesPoint) -> Result<Self, Self::Error> {
+        let time = point.time.ok_or("timeseries point without time")?;
+        let raw = point.value.ok_or("timeseries point without value")?;
+        Ok(Self {
+            time: time
+                .parse()
+                .map_err(|e| format!("invalid timeseries time '{time}': {e}"))?,
+            value: raw
+                .parse()
+                .map_err(|e| format!("invalid timeseries value '{raw}': {e}"))?,
+            raw,
+        })
+    }
+}
+
+/// APY and price per share at one timestamp
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerformancePoint {
+    /// Unix timestamp in seconds
+    pub time: u64,
+    /// Net APY (`None` if the APY series has no point at this time)
+    pub apy: Option<f64>,
+    /// Price per share (`None` if the PPS series has no point at this time)
+    pub pps: Option<f64>,
+}
+
+impl PerformancePoint {
+    /// Align APY and PPS series on their timestamps
+    ///
+    /// Outer join: every timestamp from either series appears once, sorted
+    /// ascending, with `None` where a series has no point.
+    #[must_use]
+    pub fn align(apy: &[TimeseriesPoint], pps: &[TimeseriesPoint]) -> Vec<Self> {
+        let mut points: BTreeMap<u64, Self> = BTreeMap::new();
+        let empty = |time| Self {
+            time,
+            apy: None,
+            pps: None,
+        };
+        for point in apy {
+            points
+                .entry(point.time)
+                .or_insert_with(|| empty(point.time))
+                .apy = Some(point.value);
+        }
+        for point in pps {
+            points
+                .entry(point.time)
+                .or_insert_with(|| empty(point.time))
+                .pps = Some(point.value);
+        }
+        points.into_values().collect()
+    }
+}
+
 /// GraphQL response wrapper
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GraphQLResponse<T> {
@@ -0,0 +1,76 @@
+//! Argument validation run before any query is sent
+//!
+//! Addresses are checked and lowercased for GraphQL arguments, and chain IDs
+//! must be ones Kong indexes. Bad input fails with
+//! [`InvalidAddress`](crate::error::DomainError::InvalidAddress) or
+//! [`InvalidChainId`](crate::error::DomainError::InvalidChainId) without a
+//! network round trip.
+
+use crate::error::{self, Result};
+use crate::types::YearnChain;
+use yldfi_common::eth::{normalize_address, to_checksum_address};
+
+/// Chains Kong indexes without endorsed vaults: Gnosis, Sonic, Fantom, Katana
+pub(crate) const OTHER_KONG_CHAIN_IDS: [u64; 4] = [100, 146, 250, 747_474];
+
+/// Chain IDs indexed by Kong
+///
+/// Every [`YearnChain`], which only lists the chains with endorsed vaults,
+/// followed by the chains without them.
+pub const KONG_CHAIN_IDS: [u64; YearnChain::ALL.len() + OTHER_KONG_CHAIN_IDS.len()] = {
+    let mut ids = [0; YearnChain::ALL.len() + OTHER_KONG_CHAIN_IDS.len()];
+    let mut i = 0;
+    while i < YearnChain::ALL.len() {
+        ids[i] = YearnChain::ALL[i].chain_id();
+        i += 1;
+    }
+    let mut j = 0;
+    while j < OTHER_KONG_CHAIN_IDS.len() {
+        ids[i + j] = OTHER_KONG_CHAIN_IDS[j];
+        j += 1;
+    }
+    ids
+};
+
+/// Check an address argument and lowercase it
+pub(crate) fn address(input: &str) -> Result<String> {
+    let trimmed = input.trim();
+    let Some(hex) = trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+    else {
+        return Err(error::invalid_address(input, "missing 0x prefix"));
+    };
+    if let Some(c) = hex.chars().find(|c| !c.is_ascii_hexdigit()) {
+        return Err(error::invalid_address(
+            input,
+            format!("invalid hex character '{c}'"),
+        ));
+    }
+    if hex.len() != 40 {
+        return Err(error::invalid_address(
+            input,
+            format!("expected 40 hex digits, got {}", hex.len()),
+        ));
+    }
+    normalize_address(trimmed).ok_or_else(|| error::invalid_address(input, "not an address"))
+}
+
+/// Check every address in a list, lowercasing them
+pub(crate) fn addresses<S: AsRef<str>>(inputs: &[S]) -> Result<Vec<String>> {
+    inputs.iter().map(|a| address(a.as_ref())).collect()
+}
+
+/// Check a chain ID argument
+pub(crate) fn chain_id(chain_id: u64) -> Result<u64> {
+    if KONG_CHAIN_IDS.contains(&chain_id) {
+        Ok(chain_id)
+    } else {
+        Err(error::invalid_chain_id(chain_id))
+    }
+}
+
+/// EIP-55 checksum an address, leaving anything that isn't one untouched
+pub(crate) fn checksum(address: &str) -> String {
+    to_checksum_address(address).unwrap_or_else(|| address.to_string())
+}
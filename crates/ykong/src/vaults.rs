@@ -1,10 +1,60 @@
 //! Vault-related GraphQL queries
 
-use crate::client::Client;
-use crate::error::Result;
-use crate::types::{Vault, VaultAccount};
+use crate::client::{fan_out, Client, FAN_OUT_CONCURRENCY};
+use crate::error::{self, Result};
+use crate::strategies::StrategiesApi;
+use crate::tvls::{TvlPeriod, TvlsApi};
+use crate::types::{
+    PerformancePoint, RawTimeseriesPoint, RiskProfile, SparklinePoint, TimeseriesPoint,
+    TvlDataPoint, Vault, VaultAccount, VaultRecommendation, VaultRisk, VaultSummary, YearnChain,
+    YieldGap,
+};
+use crate::validate;
 use serde::Deserialize;
 
+/// Selection set for a full [`Vault`]
+pub(crate) const VAULT_FIELDS: &str = "
+    address
+    name
+    symbol
+    chainId
+    apiVersion
+    decimals
+    v3
+    yearn
+    erc4626
+    isShutdown
+    emergencyShutdown
+    vaultType
+    token
+    totalAssets
+    totalSupply
+    pricePerShare
+    depositLimit
+    availableDepositLimit
+    managementFee
+    performanceFee
+    governance
+    guardian
+    management
+    rewards
+    registry
+    inceptTime
+    inceptBlock
+    lastReport
+    activation
+    projectId
+    projectName
+    withdrawalQueue
+    strategies
+    tvl { close blockTime }
+    apy { net weeklyNet monthlyNet inceptionNet grossApr blockTime }
+    fees { managementFee performanceFee }
+    risk { riskLevel }
+    meta { displayName description category isHidden isBoosted }
+    asset { address name symbol decimals }
+";
+
 /// Vault query builder for filtering vaults
 #[derive(Debug, Default, Clone)]
 pub struct VaultFilter {
@@ -81,6 +131,17 @@ impl VaultFilter {
         self
     }
 
+    /// Validate the chain ID and lowercase the addresses
+    fn normalized(mut self) -> Result<Self> {
+        if let Some(chain_id) = self.chain_id {
+            validate::chain_id(chain_id)?;
+        }
+        if let Some(addresses) = self.addresses.take() {
+            self.addresses = Some(validate::addresses(&addresses)?);
+        }
+        Ok(self)
+    }
+
     /// Build the GraphQL arguments string
     fn build_args(&self) -> String {
         let mut args = Vec::new();
@@ -119,6 +180,82 @@ impl VaultFilter {
     }
 }
 
+/// Timeseries label for APY derived from price-per-share deltas
+pub const APY_SERIES_LABEL: &str = "apy-bwd-delta-pps";
+
+/// Timeseries label for price per share
+pub const PPS_SERIES_LABEL: &str = "pps";
+
+/// Builder for Kong's `timeseries` query
+///
+/// Kong keeps several series per address; `label` selects the series and
+/// `component` the value within it (e.g. `net` for APY).
+#[derive(Debug, Clone)]
+pub struct TimeseriesQuery {
+    chain_id: u64,
+    address: String,
+    label: String,
+    component: Option<String>,
+    period: TvlPeriod,
+    limit: u32,
+}
+
+impl TimeseriesQuery {
+    /// Query the `label` series for an address (daily, 30 points)
+    #[must_use]
+    pub fn new(chain_id: u64, address: impl Into<String>, label: impl Into<String>) -> Self {
+        Self {
+            chain_id,
+            address: address.into(),
+            label: label.into(),
+            component: None,
+            period: TvlPeriod::Day,
+            limit: 30,
+        }
+    }
+
+    /// Select a component of the series
+    #[must_use]
+    pub fn component(mut self, component: impl Into<String>) -> Self {
+        self.component = Some(component.into());
+        self
+    }
+
+    /// Set the bucket period
+    #[must_use]
+    pub fn period(mut self, period: TvlPeriod) -> Self {
+        self.period = period;
+        self
+    }
+
+    /// Set the maximum number of points
+    #[must_use]
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Build the GraphQL query
+    ///
+    /// String arguments are JSON-escaped, which is also valid GraphQL.
+    #[must_use]
+    pub fn build(&self) -> String {
+        let quote = |value: &str| serde_json::Value::from(value).to_string();
+        let mut args = vec![
+            format!("chainId: {}", self.chain_id),
+            format!("address: {}", quote(&self.address)),
+            format!("label: {}", quote(&self.label)),
+        ];
+        if let Some(ref component) = self.component {
+            args.push(format!("component: {}", quote(component)));
+        }
+        args.push(format!("period: {}", quote(self.period.as_str())));
+        args.push(format!("limit: {}", self.limit));
+
+        format!("{{ timeseries({}) {{ time value }} }}", args.join(", "))
+    }
+}
+
 /// Vaults API
 pub struct VaultsApi<'a> {
     client: &'a Client,
@@ -146,49 +283,11 @@ impl<'a> VaultsApi<'a> {
     /// # }
     /// ```
     pub async fn list(&self, filter: Option<VaultFilter>) -> Result<Vec<Vault>> {
-        let args = filter.unwrap_or_default().build_args();
+        let args = filter.unwrap_or_default().normalized()?.build_args();
         let query = format!(
             r"{{
                 vaults{args} {{
-                    address
-                    name
-                    symbol
-                    chainId
-                    apiVersion
-                    decimals
-                    v3
-                    yearn
-                    erc4626
-                    isShutdown
-                    emergencyShutdown
-                    vaultType
-                    token
-                    totalAssets
-                    totalSupply
-                    pricePerShare
-                    depositLimit
-                    availableDepositLimit
-                    managementFee
-                    performanceFee
-                    governance
-                    guardian
-                    management
-                    rewards
-                    registry
-                    inceptTime
-                    inceptBlock
-                    lastReport
-                    activation
-                    projectId
-                    projectName
-                    withdrawalQueue
-                    strategies
-                    tvl {{ close blockTime }}
-                    apy {{ net weeklyNet monthlyNet inceptionNet grossApr blockTime }}
-                    fees {{ managementFee performanceFee }}
-                    risk {{ riskLevel }}
-                    meta {{ displayName description category isHidden isBoosted }}
-                    asset {{ address name symbol decimals }}
+                    {VAULT_FIELDS}
                 }}
             }}"
         );
@@ -220,6 +319,103 @@ impl<'a> VaultsApi<'a> {
         self.list(Some(VaultFilter::new().chain_id(chain_id))).await
     }
 
+    /// Get vaults on a chain whose underlying is `token_address`
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example() -> ykong::error::Result<()> {
+    /// use ykong::Client;
+    ///
+    /// let client = Client::new()?;
+    /// let usdc = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+    /// let vaults = client.vaults().by_token(1, usdc).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn by_token(&self, chain_id: u64, token_address: &str) -> Result<Vec<Vault>> {
+        let token_address = validate::address(token_address)?;
+        let vaults = self.by_chain(chain_id).await?;
+        Ok(vaults
+            .into_iter()
+            .filter(|v| v.accepts_token(&token_address))
+            .collect())
+    }
+
+    /// Get a lightweight summary of vaults (with optional filter)
+    ///
+    /// Only requests address, name, symbol, chain, TVL and net APY, which
+    /// keeps responses small enough for chains with hundreds of vaults.
+    pub async fn list_summaries(&self, filter: Option<VaultFilter>) -> Result<Vec<VaultSummary>> {
+        let args = filter.unwrap_or_default().normalized()?.build_args();
+        let query = format!(
+            r"{{
+                vaults{args} {{
+                    address
+                    name
+                    symbol
+                    chainId
+                    tvl {{ close }}
+                    apy {{ net }}
+                }}
+            }}"
+        );
+
+        #[derive(Deserialize)]
+        struct NetApy {
+            net: Option<f64>,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct SummaryRow {
+            #[serde(deserialize_with = "crate::types::deserialize_address")]
+            address: String,
+            name: Option<String>,
+            symbol: Option<String>,
+            chain_id: u64,
+            tvl: Option<SparklinePoint>,
+            apy: Option<NetApy>,
+        }
+
+        #[derive(Deserialize)]
+        struct Response {
+            vaults: Vec<SummaryRow>,
+        }
+
+        let response: Response = self.client.query(&query).await?;
+        Ok(response
+            .vaults
+            .into_iter()
+            .map(|row| VaultSummary {
+                address: row.address,
+                name: row.name,
+                symbol: row.symbol,
+                chain_id: row.chain_id,
+                tvl: row.tvl.and_then(|t| t.close),
+                apy_net: row.apy.and_then(|a| a.net),
+            })
+            .collect())
+    }
+
+    /// Get a lightweight summary of vaults on a chain
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example() -> ykong::error::Result<()> {
+    /// use ykong::Client;
+    ///
+    /// let client = Client::new()?;
+    /// let summaries = client.vaults().summaries(1).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn summaries(&self, chain_id: u64) -> Result<Vec<VaultSummary>> {
+        self.list_summaries(Some(VaultFilter::new().chain_id(chain_id)))
+            .await
+    }
+
     /// Get only v3 vaults
     pub async fn v3_vaults(&self) -> Result<Vec<Vault>> {
         self.list(Some(VaultFilter::new().v3(true))).await
@@ -230,6 +426,57 @@ impl<'a> VaultsApi<'a> {
         self.list(Some(VaultFilter::new().yearn(true))).await
     }
 
+    /// Get Yearn official (endorsed) vaults on a chain
+    pub async fn endorsed(&self, chain_id: u64) -> Result<Vec<Vault>> {
+        self.list(Some(VaultFilter::new().chain_id(chain_id).yearn(true)))
+            .await
+    }
+
+    /// Daily total TVL of a chain's endorsed vaults
+    ///
+    /// Lists the chain's endorsed vaults with [`endorsed`](Self::endorsed),
+    /// fetches `days` of daily TVL for each with
+    /// [`TvlsApi::series_for_vaults`], then sums them on a shared timestamp
+    /// grid with forward fill (see [`TvlDataPoint::sum_series`]):
+    ///
+    /// ```text
+    /// endorsed(chain) ──> [vault A, vault B, ...]
+    ///                        │        │
+    ///                  daily TVL  daily TVL      (one query per vault)
+    ///                        │        │
+    ///                        └──┬─────┘
+    ///             align on union of timestamps,
+    ///             forward-fill gaps, sum per point
+    ///                           │
+    ///                   Vec<TvlDataPoint>
+    /// ```
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example() -> ykong::error::Result<()> {
+    /// use ykong::Client;
+    ///
+    /// let client = Client::new()?;
+    /// for point in client.vaults().endorsed_tvl_chart(1, 30).await? {
+    ///     println!("{}: ${:.0}", point.time, point.value);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn endorsed_tvl_chart(&self, chain_id: u64, days: u32) -> Result<Vec<TvlDataPoint>> {
+        let addresses: Vec<String> = self
+            .endorsed(chain_id)
+            .await?
+            .into_iter()
+            .map(|v| v.address)
+            .collect();
+        let series = TvlsApi::new(self.client)
+            .series_for_vaults(chain_id, &addresses, days)
+            .await?;
+        Ok(TvlDataPoint::sum_series(&series))
+    }
+
     /// Get a single vault by address and chain
     ///
     /// # Example
@@ -244,48 +491,12 @@ impl<'a> VaultsApi<'a> {
     /// # }
     /// ```
     pub async fn get(&self, chain_id: u64, address: &str) -> Result<Option<Vault>> {
+        let chain_id = validate::chain_id(chain_id)?;
+        let address = validate::address(address)?;
         let query = format!(
             r#"{{
                 vault(chainId: {chain_id}, address: "{address}") {{
-                    address
-                    name
-                    symbol
-                    chainId
-                    apiVersion
-                    decimals
-                    v3
-                    yearn
-                    erc4626
-                    isShutdown
-                    emergencyShutdown
-                    vaultType
-                    token
-                    totalAssets
-                    totalSupply
-                    pricePerShare
-                    depositLimit
-                    availableDepositLimit
-                    managementFee
-                    performanceFee
-                    governance
-                    guardian
-                    management
-                    rewards
-                    registry
-                    inceptTime
-                    inceptBlock
-                    lastReport
-                    activation
-                    projectId
-                    projectName
-                    withdrawalQueue
-                    strategies
-                    tvl {{ close blockTime }}
-                    apy {{ net weeklyNet monthlyNet inceptionNet grossApr blockTime }}
-                    fees {{ managementFee performanceFee }}
-                    risk {{ riskLevel }}
-                    meta {{ displayName description category isHidden isBoosted }}
-                    asset {{ address name symbol decimals }}
+                    {VAULT_FIELDS}
                 }}
             }}"#
         );
@@ -299,6 +510,242 @@ impl<'a> VaultsApi<'a> {
         Ok(response.vault)
     }
 
+    /// Recommend the best vault for depositing a token
+    ///
+    /// Considers only endorsed vaults unless none qualify. See
+    /// [`VaultRecommendation::select`] for the ranking rules.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example() -> ykong::error::Result<()> {
+    /// use ykong::{Client, RiskProfile};
+    ///
+    /// let client = Client::new()?;
+    /// let usdc = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+    /// let rec = client
+    ///     .vaults()
+    ///     .for_wallet(1, usdc, RiskProfile::Conservative, 10_000.0)
+    ///     .await?;
+    /// println!("{}", rec.rationale);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn for_wallet(
+        &self,
+        chain_id: u64,
+        token_address: &str,
+        risk_profile: RiskProfile,
+        amount_usd: f64,
+    ) -> Result<VaultRecommendation> {
+        validate::address(token_address)?;
+        let vaults = self.by_chain(chain_id).await?;
+        VaultRecommendation::select(&vaults, token_address, risk_profile, amount_usd)
+            .ok_or_else(|| error::vault_not_found(format!("no vault for token {token_address}")))
+    }
+
+    /// APY spread between endorsed vaults accepting the same token
+    ///
+    /// Only endorsed vaults that are not shut down are compared. See
+    /// [`YieldGap::from_vaults`] for how the best and worst are picked.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InsufficientData`](error::DomainError::InsufficientData) if
+    /// fewer than two endorsed vaults accepting the token report an APY.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example() -> ykong::error::Result<()> {
+    /// use ykong::Client;
+    ///
+    /// let client = Client::new()?;
+    /// let usdc = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+    /// let gap = client.vaults().yield_gap(1, usdc).await?;
+    /// println!("{} gap: {:.2}pp", gap.tokens_in_common, gap.gap_pct);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn yield_gap(&self, chain_id: u64, token_address: &str) -> Result<YieldGap> {
+        let endorsed: Vec<Vault> = self
+            .by_token(chain_id, token_address)
+            .await?
+            .into_iter()
+            .filter(|v| v.is_endorsed() && !v.is_retired())
+            .collect();
+        YieldGap::from_vaults(&endorsed, token_address).ok_or_else(|| {
+            let with_apy = endorsed
+                .iter()
+                .filter(|v| v.reported_net_apy().is_some())
+                .count();
+            error::insufficient_data(2, with_apy)
+        })
+    }
+
+    /// Best-yielding endorsed vault for an asset across every [`YearnChain`]
+    ///
+    /// Queries endorsed vaults on all chains concurrently and returns the one
+    /// with the highest net APY whose underlying symbol is `asset_symbol`
+    /// (case-insensitive), or `None` if no chain has one. Shutdown vaults are
+    /// skipped. Any chain failing fails the whole call.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example() -> ykong::error::Result<()> {
+    /// use ykong::Client;
+    ///
+    /// let client = Client::new()?;
+    /// if let Some(vault) = client.vaults().all_chains_top_yield("USDC").await? {
+    ///     println!("{} on chain {}: {:.2}%", vault.address, vault.chain_id, vault.net_apy() * 100.0);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn all_chains_top_yield(&self, asset_symbol: &str) -> Result<Option<Vault>> {
+        let vaults = self.endorsed_on_all_chains().await?;
+        Ok(Vault::top_yield(&vaults, asset_symbol).cloned())
+    }
+
+    /// Endorsed vaults on every [`YearnChain`], queried concurrently
+    pub(crate) async fn endorsed_on_all_chains(&self) -> Result<Vec<Vault>> {
+        let client = self.client.clone();
+        let per_chain = fan_out(YearnChain::ALL, FAN_OUT_CONCURRENCY, move |chain| {
+            let client = client.clone();
+            async move {
+                let filter = VaultFilter::new().chain_id(chain.chain_id()).yearn(true);
+                VaultsApi::new(&client).list(Some(filter)).await
+            }
+        })
+        .await?;
+        Ok(per_chain.into_iter().flatten().collect())
+    }
+
+    /// Assess a vault across all risk dimensions
+    ///
+    /// Fetches the vault and its strategies concurrently and scores them
+    /// with the client's [`RiskWeights`](crate::types::RiskWeights) (see
+    /// [`Config::with_risk_weights`](crate::Config::with_risk_weights)).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example() -> ykong::error::Result<()> {
+    /// use ykong::Client;
+    ///
+    /// let client = Client::new()?;
+    /// let risk = client.vaults().vault_risk_profile(1, "0x...").await?;
+    /// println!(
+    ///     "Risk {}/100, {:.0}% in the largest protocol",
+    ///     risk.composite_risk_score,
+    ///     risk.protocol_concentration.largest_share() * 100.0
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn vault_risk_profile(
+        &self,
+        chain_id: u64,
+        vault_address: &str,
+    ) -> Result<VaultRisk> {
+        let strategies = StrategiesApi::new(self.client);
+        let (vault, strategies) = tokio::try_join!(
+            self.get(chain_id, vault_address),
+            strategies.by_vault(chain_id, vault_address)
+        )?;
+        let vault = vault.ok_or_else(|| error::vault_not_found(vault_address))?;
+
+        Ok(VaultRisk::new(
+            &vault,
+            &strategies,
+            self.client.risk_weights(),
+        ))
+    }
+
+    /// Run a timeseries query, skipping points without a value
+    pub async fn timeseries(&self, query: &TimeseriesQuery) -> Result<Vec<TimeseriesPoint>> {
+        #[derive(Deserialize)]
+        struct Response {
+            timeseries: Vec<RawTimeseriesPoint>,
+        }
+
+        let query = TimeseriesQuery {
+            chain_id: validate::chain_id(query.chain_id)?,
+            address: validate::address(&query.address)?,
+            ..query.clone()
+        };
+        let response: Response = self.client.query(&query.build()).await?;
+        response
+            .timeseries
+            .into_iter()
+            .filter(RawTimeseriesPoint::has_value)
+            .map(|point| TimeseriesPoint::try_from(point).map_err(error::graphql_error))
+            .collect()
+    }
+
+    /// Get net APY history for a vault
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example() -> ykong::error::Result<()> {
+    /// use ykong::{Client, TvlPeriod};
+    ///
+    /// let client = Client::new()?;
+    /// let apy = client.vaults().apy_history(1, "0x...", TvlPeriod::Day, 90).await?;
+    /// for point in apy {
+    ///     println!("{}: {:.2}%", point.time, point.value * 100.0);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn apy_history(
+        &self,
+        chain_id: u64,
+        vault: &str,
+        period: TvlPeriod,
+        limit: u32,
+    ) -> Result<Vec<TimeseriesPoint>> {
+        let query = TimeseriesQuery::new(chain_id, vault, APY_SERIES_LABEL)
+            .component("net")
+            .period(period)
+            .limit(limit);
+        self.timeseries(&query).await
+    }
+
+    /// Get price-per-share history for a vault
+    pub async fn pps_history(
+        &self,
+        chain_id: u64,
+        vault: &str,
+        period: TvlPeriod,
+        limit: u32,
+    ) -> Result<Vec<TimeseriesPoint>> {
+        let query = TimeseriesQuery::new(chain_id, vault, PPS_SERIES_LABEL)
+            .component("humanized")
+            .period(period)
+            .limit(limit);
+        self.timeseries(&query).await
+    }
+
+    /// Get APY and price-per-share history aligned on timestamps
+    ///
+    /// Both series are fetched concurrently; see [`PerformancePoint::align`].
+    pub async fn performance_history(
+        &self,
+        chain_id: u64,
+        vault: &str,
+        period: TvlPeriod,
+        limit: u32,
+    ) -> Result<Vec<PerformancePoint>> {
+        let (apy, pps) = tokio::try_join!(
+            self.apy_history(chain_id, vault, period, limit),
+            self.pps_history(chain_id, vault, period, limit),
+        )?;
+        Ok(PerformancePoint::align(&apy, &pps))
+    }
+
     /// Get vault accounts (user positions) for an address
     ///
     /// **DEPRECATED:** The Kong API removed user position queries in 2024.
@@ -4,7 +4,15 @@
 //! Tests marked with #[ignore] require network access to the Kong API.
 
 use std::time::Duration;
-use ykong::{Client, Config};
+use ykong::error::DomainError;
+use ykong::{
+    realised_volatility_from_prices, AgeVsTvlPoint, Client, Config, CrossVaultComparison, Error,
+    FeeDrainAnalysis, Fees, HarvestRoi, LivenessReport, PerformancePoint, ProtocolBreakdown,
+    ProtocolTvlBreakdown, RiskProfile, RiskSummary, RiskWeights, SolvencyReport, Strategy,
+    StrategyRisk, TimeseriesPoint, TimeseriesQuery, Tvl, TvlDataPoint, TvlPeriod, Urgency, Vault,
+    VaultChangeKind, VaultRecommendation, VaultReport, VaultReportStats, VaultRisk, VaultUpdate,
+    YearnChain, YieldGap, MIN_VOLATILITY_DAYS, TOP_VAULTS_LIMIT,
+};
 
 #[test]
 fn test_client_creation() {
@@ -31,9 +39,8 @@ fn test_client_cloneable() {
     let cloned = client.clone();
 
     // Both should have the same base URL (verifies Arc sharing works)
-    assert_eq!(
+    assert!(
         std::ptr::eq(client.http(), cloned.http()),
-        true,
         "Cloned clients should share the same HTTP client"
     );
 }
@@ -60,6 +67,95 @@ fn test_no_rate_limiter_by_default() {
     );
 }
 
+#[test]
+fn test_connection_pool_and_concurrency_config() {
+    let config = Config::new();
+    assert_eq!(config.connection_pool_size(), 10);
+    assert!(config.max_concurrent_requests().is_none());
+
+    let config = Config::new()
+        .with_connection_pool_size(64)
+        .with_max_concurrent_requests(16);
+    assert_eq!(config.connection_pool_size(), 64);
+    assert_eq!(config.max_concurrent_requests(), Some(16));
+
+    let client = Client::with_config(config).expect("Client creation failed");
+    assert_eq!(client.max_concurrent_requests(), Some(16));
+    assert_eq!(
+        Config::new()
+            .with_max_concurrent_requests(0)
+            .max_concurrent_requests(),
+        Some(1)
+    );
+}
+
+#[tokio::test]
+async fn test_gas_oracle_config() {
+    let config = Config::new().with_gas_oracle_url("https://rpc.example/{chain_id}");
+    assert_eq!(
+        config.gas_oracle_url(),
+        Some("https://rpc.example/{chain_id}")
+    );
+    assert_eq!(
+        ykong::wrapped_native_token(8453),
+        Some("0x4200000000000000000000000000000000000006")
+    );
+
+    // Chains without a default oracle fail before any request is sent
+    let client = Client::new().unwrap();
+    let err = client.gas_price_gwei(999).await.unwrap_err();
+    assert!(matches!(
+        err,
+        Error::Domain(DomainError::InvalidChainId(999))
+    ));
+}
+
+/// Answers each `tvls` query with one point whose value is the vault's
+/// last address byte, later vaults answering sooner
+struct TvlNode;
+
+impl wiremock::Respond for TvlNode {
+    fn respond(&self, request: &wiremock::Request) -> wiremock::ResponseTemplate {
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        let query = body["query"].as_str().unwrap();
+        let start = query.find("address: \"").unwrap() + 10;
+        let address = &query[start..start + 42];
+        let byte = u8::from_str_radix(&address[40..], 16).unwrap();
+        wiremock::ResponseTemplate::new(200)
+            .set_delay(Duration::from_millis(u64::from(20 - byte)))
+            .set_body_json(serde_json::json!({ "data": { "tvls": [{
+                "chainId": 1,
+                "address": address,
+                "value": f64::from(byte),
+                "priceUsd": 1.0,
+                "priceSource": "mock",
+                "period": "day",
+                "blockNumber": 1,
+                "time": 1_700_000_000
+            }] } }))
+    }
+}
+
+#[tokio::test]
+async fn test_series_for_vaults_keeps_input_order() {
+    let server = wiremock::MockServer::start().await;
+    wiremock::Mock::given(wiremock::matchers::method("POST"))
+        .respond_with(TvlNode)
+        .expect(12)
+        .mount(&server)
+        .await;
+    let client = Client::with_config(Config::new().with_base_url(server.uri())).unwrap();
+
+    let addresses: Vec<String> = (1..=12u8).map(|i| format!("0x{:040x}", i)).collect();
+    let series = client
+        .tvls()
+        .series_for_vaults(1, &addresses, 1)
+        .await
+        .unwrap();
+    let values: Vec<f64> = series.iter().map(|s| s[0].value).collect();
+    assert_eq!(values, (1..=12).map(f64::from).collect::<Vec<_>>());
+}
+
 // Integration tests that require network access
 // Run with: cargo test -p ykong --test integration -- --ignored
 
@@ -80,6 +176,34 @@ async fn test_fetch_vaults() {
     assert!(!vaults.is_empty(), "Should have at least one vault");
 }
 
+#[tokio::test]
+#[ignore = "requires network access"]
+async fn test_fetch_vault_summaries() {
+    let client = Client::new().expect("Client creation failed");
+
+    let summaries = client
+        .vaults()
+        .summaries(1)
+        .await
+        .expect("Should fetch vault summaries");
+    assert!(!summaries.is_empty(), "Should have at least one vault");
+    assert!(summaries.iter().all(|s| s.chain_id == 1));
+}
+
+#[tokio::test]
+#[ignore = "requires network access"]
+async fn test_fetch_realised_volatility() {
+    let client = Client::new().expect("Client creation failed");
+    let weth = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
+
+    let vol = client
+        .prices()
+        .realised_volatility(1, weth, 30)
+        .await
+        .expect("Should compute WETH volatility");
+    assert!(vol > 0.0 && vol < 5.0, "Implausible volatility: {vol}");
+}
+
 #[tokio::test]
 #[ignore = "requires network access"]
 async fn test_rate_limited_requests() {
@@ -101,3 +225,1048 @@ async fn test_rate_limited_requests() {
         elapsed
     );
 }
+
+// Fixture builders shared by the analytics tests below
+
+/// Deserializes `base` with `fields` laid over its top-level keys
+fn fixture<T: serde::de::DeserializeOwned>(
+    mut base: serde_json::Value,
+    fields: serde_json::Value,
+) -> T {
+    base.as_object_mut()
+        .unwrap()
+        .extend(fields.as_object().unwrap().clone());
+    serde_json::from_value(base).expect("valid fixture")
+}
+
+/// An endorsed, live, low-risk mainnet USDC vault with a 10% performance fee
+fn vault(address: &str, net: f64, tvl: f64) -> Vault {
+    vault_with(address, net, tvl, serde_json::json!({}))
+}
+
+/// [`vault`] with `fields` overriding the defaults
+fn vault_with(address: &str, net: f64, tvl: f64, fields: serde_json::Value) -> Vault {
+    fixture(
+        serde_json::json!({
+            "address": address,
+            "chainId": 1,
+            "yearn": true,
+            "isShutdown": false,
+            "token": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+            "tvl": { "close": tvl },
+            "apy": { "net": net, "grossApr": net * 1.1 },
+            "fees": { "managementFee": 0.0, "performanceFee": 1000.0 },
+            "risk": { "riskLevel": 1 },
+            "asset": { "address": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48", "symbol": "USDC" }
+        }),
+        fields,
+    )
+}
+
+/// A mainnet strategy of `0xvault` with `fields` set
+fn strategy(address: &str, fields: serde_json::Value) -> Strategy {
+    fixture(
+        serde_json::json!({ "address": address, "chainId": 1, "vault": "0xvault" }),
+        fields,
+    )
+}
+
+fn vault_report(
+    address: &str,
+    loss: &str,
+    net_apr: f64,
+    fees_usd: f64,
+    gain_usd: f64,
+) -> VaultReport {
+    fixture(
+        serde_json::json!({
+            "chainId": 1,
+            "address": address,
+            "eventName": "StrategyReported",
+            "strategy": "0xstrategy",
+            "gain": "1000",
+            "totalFees": "10",
+            "blockNumber": 1,
+            "blockTime": 1,
+            "logIndex": 0,
+            "transactionHash": "0x00"
+        }),
+        serde_json::json!({
+            "loss": loss,
+            "totalFeesUsd": fees_usd,
+            "gainUsd": gain_usd,
+            "apr": { "net": net_apr }
+        }),
+    )
+}
+
+#[test]
+fn test_cross_vault_comparison() {
+    let a = [
+        vault_report("0xa", "0", 0.10, 1.0, 100.0),
+        vault_report("0xa", "5", 0.20, 1.0, 100.0),
+    ];
+    let b = [
+        vault_report("0xb", "0", 0.12, 5.0, 100.0),
+        vault_report("0xb", "0", 0.12, 5.0, 100.0),
+    ];
+
+    let stats_a = VaultReportStats::from_reports("0xa", &a);
+    assert_eq!(stats_a.report_count, 2);
+    assert!((stats_a.avg_apy.unwrap() - 0.15).abs() < 1e-9);
+    assert!((stats_a.apy_std_dev.unwrap() - 0.05).abs() < 1e-9);
+    assert!((stats_a.loss_rate_pct - 50.0).abs() < 1e-9);
+    assert_eq!(stats_a.total_fees_paid, 20);
+    assert_eq!(stats_a.total_fees_usd, Some(2.0));
+
+    let cmp = CrossVaultComparison::new(stats_a, VaultReportStats::from_reports("0xb", &b));
+    assert_eq!(cmp.winner_by_apy, "0xa");
+    assert_eq!(cmp.winner_by_consistency, "0xb");
+    assert_eq!(cmp.winner_by_fee_efficiency, "0xa");
+}
+
+#[test]
+fn test_vault_report_stats_empty() {
+    let stats = VaultReportStats::from_reports("0xa", &[]);
+    assert_eq!(stats.report_count, 0);
+    assert!(stats.avg_apy.is_none());
+    assert!(stats.apy_std_dev.is_none());
+    assert_eq!(stats.loss_rate_pct, 0.0);
+    assert!(stats.fee_ratio.is_none());
+    assert!(stats.total_fees_usd.is_none());
+
+    let cmp = CrossVaultComparison::new(stats.clone(), stats);
+    assert_eq!(cmp.winner_by_apy, CrossVaultComparison::TIE);
+    assert_eq!(cmp.winner_by_consistency, CrossVaultComparison::TIE);
+    assert_eq!(cmp.winner_by_fee_efficiency, CrossVaultComparison::TIE);
+}
+
+#[test]
+fn test_cross_vault_comparison_skips_vault_without_reports() {
+    // Volatile and fee-heavy, but the only vault with any history
+    let a = [
+        vault_report("0xa", "0", 0.05, 30.0, 100.0),
+        vault_report("0xa", "0", 0.25, 30.0, 100.0),
+    ];
+    let cmp = CrossVaultComparison::new(
+        VaultReportStats::from_reports("0xa", &a),
+        VaultReportStats::from_reports("0xempty", &[]),
+    );
+    assert_eq!(cmp.winner_by_apy, "0xa");
+    assert_eq!(cmp.winner_by_consistency, "0xa");
+    assert_eq!(cmp.winner_by_fee_efficiency, "0xa");
+}
+
+#[test]
+fn test_cross_vault_fee_fallback_uses_usd() {
+    // Gains unpriced: raw fees (10 per report) say nothing across decimals,
+    // so the vault paying fewer USD in fees wins
+    let unpriced = |address: &str, fees_usd: f64| {
+        let mut report = vault_report(address, "0", 0.1, fees_usd, 0.0);
+        report.gain_usd = None;
+        report
+    };
+    let a = [unpriced("0xa", 50.0)];
+    let b = [unpriced("0xb", 5.0), unpriced("0xb", 5.0)];
+    let cmp = CrossVaultComparison::new(
+        VaultReportStats::from_reports("0xa", &a),
+        VaultReportStats::from_reports("0xb", &b),
+    );
+    assert!(cmp.vault_a_stats.total_fees_paid < cmp.vault_b_stats.total_fees_paid);
+    assert_eq!(cmp.winner_by_fee_efficiency, "0xb");
+}
+
+#[test]
+fn test_vault_recommendation_prefers_endorsed() {
+    let usdc = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+    let vaults = vec![
+        vault_with(
+            "0xcommunity",
+            0.20,
+            5_000_000.0,
+            serde_json::json!({ "yearn": false, "risk": { "riskLevel": 2 } }),
+        ),
+        vault("0xendorsed", 0.082, 120_000_000.0),
+        vault_with(
+            "0xold",
+            0.01,
+            1_000.0,
+            serde_json::json!({ "isShutdown": true }),
+        ),
+    ];
+
+    let rec = VaultRecommendation::select(&vaults, usdc, RiskProfile::Conservative, 10_000.0)
+        .expect("recommendation");
+    assert_eq!(rec.vault.address, "0xendorsed");
+    assert_eq!(
+        rec.rationale,
+        "Best endorsed USDC vault with 8.2% APY and $120M TVL"
+    );
+    assert!((rec.expected_annual_yield_usd - 820.0).abs() < 1e-6);
+    // 10% performance fee on 9.02% gross
+    assert!((rec.estimated_fees_usd - 90.2).abs() < 1e-6);
+    assert!(rec.replaces_retired_vault);
+}
+
+#[test]
+fn test_vault_recommendation_falls_back_and_respects_risk() {
+    let usdc = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+    let vaults = vec![
+        vault_with(
+            "0xsafe",
+            0.05,
+            2_500_000.0,
+            serde_json::json!({ "yearn": false }),
+        ),
+        vault_with(
+            "0xrisky",
+            0.30,
+            1_000_000.0,
+            serde_json::json!({ "yearn": false, "risk": { "riskLevel": 4 } }),
+        ),
+    ];
+
+    let rec = VaultRecommendation::select(&vaults, usdc, RiskProfile::Moderate, 1_000.0).unwrap();
+    assert_eq!(rec.vault.address, "0xsafe");
+    assert!(rec.rationale.contains("no endorsed vault"));
+    assert!(!rec.replaces_retired_vault);
+
+    let rec = VaultRecommendation::select(&vaults, usdc, RiskProfile::Aggressive, 1_000.0).unwrap();
+    assert_eq!(rec.vault.address, "0xrisky");
+
+    assert!(VaultRecommendation::select(&vaults, "0xdead", RiskProfile::Aggressive, 1.0).is_none());
+}
+
+#[test]
+fn test_yield_gap() {
+    let usdc = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+    let vaults = vec![
+        vault("0xmid", 0.05, 10_000_000.0),
+        vault("0xbest", 0.082, 120_000_000.0),
+        vault_with(
+            "0xworst",
+            0.031,
+            2_000_000.0,
+            serde_json::json!({ "risk": { "riskLevel": 2 } }),
+        ),
+    ];
+
+    let gap = YieldGap::from_vaults(&vaults, usdc).unwrap();
+    assert_eq!(gap.best_vault.address, "0xbest");
+    assert_eq!(gap.worst_vault.address, "0xworst");
+    assert!((gap.gap_pct - 5.1).abs() < 1e-9);
+    assert_eq!(gap.tokens_in_common, "USDC");
+
+    assert!(YieldGap::from_vaults(&vaults[..1], usdc).is_none());
+
+    // A vault without an APY is skipped, not counted as 0%
+    let mut with_unknown = vaults.clone();
+    with_unknown.push(vault_with(
+        "0xnew",
+        0.0,
+        500_000.0,
+        serde_json::json!({ "apy": null }),
+    ));
+    let gap = YieldGap::from_vaults(&with_unknown, usdc).unwrap();
+    assert_eq!(gap.worst_vault.address, "0xworst");
+    assert!((gap.gap_pct - 5.1).abs() < 1e-9);
+    assert!(YieldGap::from_vaults(&with_unknown[2..], usdc).is_none());
+}
+
+#[test]
+fn test_top_yield_across_chains() {
+    let mut base = vault("0xbase", 0.11, 8_000_000.0);
+    base.chain_id = YearnChain::Base.chain_id();
+    let vaults = vec![
+        vault("0xmainnet", 0.082, 120_000_000.0),
+        base,
+        vault_with(
+            "0xcommunity",
+            0.25,
+            1_000_000.0,
+            serde_json::json!({ "yearn": false }),
+        ),
+        vault_with(
+            "0xshutdown",
+            0.40,
+            1_000.0,
+            serde_json::json!({ "isShutdown": true }),
+        ),
+    ];
+
+    let best = Vault::top_yield(&vaults, "usdc").unwrap();
+    assert_eq!(best.address, "0xbase");
+    assert_eq!(
+        YearnChain::from_chain_id(best.chain_id),
+        Some(YearnChain::Base)
+    );
+    assert!(Vault::top_yield(&vaults, "DAI").is_none());
+    assert!(Vault::top_yield(&vaults[2..], "USDC").is_none());
+}
+
+#[test]
+fn test_harvest_roi() {
+    let strategy = strategy(
+        "0xstrategy",
+        serde_json::json!({
+            "lastReport": 1_700_000_000u64,
+            "apy": { "net": 0.05, "grossApr": 0.073 },
+            "tvl": { "close": 10_000_000.0 }
+        }),
+    );
+    // 10 days at 7.3% gross on $10M
+    let profit = strategy.accrued_profit_usd(1_700_000_000 + 10 * 86_400);
+    assert!((profit - 20_000.0).abs() < 1e-6);
+
+    // 1M gas at 20 gwei and $2,500 ETH = $50
+    let roi = HarvestRoi::new(20.0 * 1e6, 2_500.0, profit);
+    assert!((roi.gas_cost_usd - 50.0).abs() < 1e-9);
+    assert!((roi.net_roi - 399.0).abs() < 1e-9);
+    assert!(roi.is_profitable);
+
+    let roi = HarvestRoi::new(20.0 * 1e6, 2_500.0, 10.0);
+    assert!((roi.net_roi + 0.8).abs() < 1e-9);
+    assert!(!roi.is_profitable);
+}
+
+#[test]
+fn test_liveness_report() {
+    let now = 1_700_000_000;
+    let hours_ago = |h: u64| now - h * 3_600;
+    let strategies = vec![
+        strategy(
+            "0xfresh",
+            serde_json::json!({ "lastReport": hours_ago(10) }),
+        ),
+        strategy(
+            "0xlate",
+            serde_json::json!({ "lastReport": hours_ago(100) }),
+        ),
+        strategy(
+            "0xdead",
+            serde_json::json!({ "lastReport": hours_ago(400) }),
+        ),
+        // Never reported: aged from activation
+        strategy("0xnew", serde_json::json!({ "activation": hours_ago(200) })),
+        strategy(
+            "0xoff",
+            serde_json::json!({ "lastReport": hours_ago(900), "isActive": false }),
+        ),
+        strategy("0xundated", serde_json::json!({})),
+    ];
+
+    let report = LivenessReport::from_strategies(&strategies, 72, now);
+    assert_eq!(report.healthy, 1);
+    assert_eq!(report.stale, 3);
+    let order: Vec<&str> = report
+        .stale_strategies
+        .iter()
+        .map(|s| s.strategy_address.as_str())
+        .collect();
+    assert_eq!(order, vec!["0xdead", "0xnew", "0xlate"]);
+    assert_eq!(report.stale_strategies[0].hours_since_harvest, 400);
+    assert_eq!(report.stale_strategies[0].urgency, Urgency::High);
+    assert_eq!(report.stale_strategies[1].urgency, Urgency::Medium);
+    assert_eq!(report.stale_strategies[2].urgency, Urgency::Low);
+    assert_eq!(report.stale_strategies[2].vault_address, "0xvault");
+}
+
+#[test]
+fn test_solvency_report() {
+    let strategies = vec![
+        // 50% actual vs 50% authorised
+        strategy(
+            "0xok",
+            serde_json::json!({ "debtRatio": "5000", "totalDebt": "500000" }),
+        ),
+        // 30.5% actual vs 30% authorised: within the 1% tolerance
+        strategy(
+            "0xtolerated",
+            serde_json::json!({ "debtRatio": "3000", "totalDebt": "305000" }),
+        ),
+        // 25% actual vs 10% authorised
+        strategy(
+            "0xover",
+            serde_json::json!({ "debtRatio": "1000", "totalDebt": "250000" }),
+        ),
+        // v3 strategies carry no debt ratio
+        strategy("0xv3", serde_json::json!({ "totalDebt": "900000" })),
+    ];
+
+    let report = SolvencyReport::from_strategies(1_000_000, &strategies);
+    assert!(!report.is_solvent);
+    assert_eq!(report.violations.len(), 1);
+    let violation = &report.violations[0];
+    assert_eq!(violation.strategy_address, "0xover");
+    assert_eq!(violation.authorised_bps, 1000);
+    assert_eq!(violation.actual_bps, 2500);
+    assert!((violation.excess_pct - 15.0).abs() < f64::EPSILON);
+
+    let report = SolvencyReport::from_strategies(1_000_000, &strategies[..2]);
+    assert!(report.is_solvent);
+    assert!(report.violations.is_empty());
+}
+
+#[tokio::test]
+async fn test_solvency_check_needs_total_assets() {
+    const VAULT: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+    for total_assets in [serde_json::Value::Null, serde_json::json!("0")] {
+        let server = wiremock::MockServer::start().await;
+        let vault = serde_json::json!({
+            "address": VAULT,
+            "chainId": 1,
+            "totalAssets": total_assets
+        });
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .respond_with(move |request: &wiremock::Request| {
+                let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+                let data = if body["query"].as_str().unwrap().contains("strategies(") {
+                    serde_json::json!({ "strategies": [{
+                        "address": "0xok",
+                        "chainId": 1,
+                        "vault": VAULT,
+                        "debtRatio": "5000",
+                        "totalDebt": "500000"
+                    }] })
+                } else {
+                    serde_json::json!({ "vault": vault })
+                };
+                wiremock::ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "data": data }))
+            })
+            .mount(&server)
+            .await;
+        let client = Client::with_config(Config::new().with_base_url(server.uri())).unwrap();
+
+        // Unknown or zero assets must not flag a healthy vault as insolvent
+        let err = client
+            .strategies()
+            .solvency_check(1, VAULT)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::Domain(DomainError::InsufficientData { .. })),
+            "{total_assets}: {err:?}"
+        );
+    }
+}
+
+/// Two audited Curve strategies and an unaudited Aave one, 800k deployed
+fn curve_and_aave_strategies() -> Vec<Strategy> {
+    vec![
+        strategy(
+            "0xcurve1",
+            serde_json::json!({
+                "totalDebt": "400000",
+                "risk": { "riskLevel": 1, "riskGroup": "Curve", "auditScore": 1 }
+            }),
+        ),
+        strategy(
+            "0xcurve2",
+            serde_json::json!({
+                "totalDebt": "200000",
+                "risk": { "riskLevel": 3, "riskGroup": "Curve", "auditScore": 1 }
+            }),
+        ),
+        strategy(
+            "0xaave",
+            serde_json::json!({
+                "totalDebt": "200000",
+                "risk": { "riskLevel": 5, "riskGroup": "Aave" }
+            }),
+        ),
+    ]
+}
+
+/// A 1M vault for [`curve_and_aave_strategies`]
+fn risk_vault() -> Vault {
+    let mut vault = vault_with(
+        "0xvault",
+        0.05,
+        1_000_000.0,
+        serde_json::json!({ "risk": { "riskLevel": 2 } }),
+    );
+    vault.total_assets = Some("1000000".to_string());
+    vault
+}
+
+#[test]
+fn test_protocol_breakdown() {
+    let risks = StrategyRisk::from_strategies(&curve_and_aave_strategies());
+    assert_eq!(risks[0].debt_share, 0.5);
+    assert_eq!(risks[2].risk_group.as_deref(), Some("Aave"));
+
+    let breakdown = ProtocolBreakdown::from_strategy_risks(&risks);
+    assert_eq!(breakdown.protocols[0].protocol, "Curve");
+    assert_eq!(breakdown.protocols[0].strategy_count, 2);
+    assert_eq!(breakdown.largest_share(), 0.75);
+    assert!((breakdown.herfindahl_index - 0.625).abs() < 1e-12);
+}
+
+#[test]
+fn test_vault_risk() {
+    let risk = VaultRisk::new(
+        &risk_vault(),
+        &curve_and_aave_strategies(),
+        &RiskWeights::default(),
+    );
+    // 800k of 1M deployed: 20% idle
+    assert!((risk.liquidity_score.unwrap() - 0.2).abs() < 1e-12);
+    assert!((risk.audit_coverage - 0.75).abs() < 1e-12);
+    assert_eq!(risk.days_since_audit, None);
+    // level 0.5*0 + 0.25*0.5 + 0.25*1 = 0.375
+    // (0.4*0.375 + 0.25*0.625 + 0.15*0.8 + 0.2*0.25) / 1.0 = 0.48125
+    assert_eq!(risk.composite_risk_score, 48);
+}
+
+#[test]
+fn test_vault_risk_custom_weights() {
+    // Only the strategy dimension counts
+    let weights = RiskWeights {
+        strategy: 2.0,
+        concentration: 0.0,
+        liquidity: 0.0,
+        audit: 0.0,
+    };
+    let risk = VaultRisk::new(&risk_vault(), &curve_and_aave_strategies(), &weights);
+    assert_eq!(risk.composite_risk_score, 38);
+}
+
+#[test]
+fn test_vault_risk_without_strategies() {
+    // Fully liquid and nothing concentrated
+    let idle = VaultRisk::new(&risk_vault(), &[], &RiskWeights::default());
+    assert_eq!(idle.liquidity_score, Some(1.0));
+    assert_eq!(idle.composite_risk_score, 0);
+}
+
+#[test]
+fn test_vault_risk_without_total_assets() {
+    // Unknown liquidity scores as fully illiquid: 0.15 * 1.0 on top of 48.125
+    let mut vault = risk_vault();
+    vault.total_assets = None;
+    let risk = VaultRisk::new(
+        &vault,
+        &curve_and_aave_strategies(),
+        &RiskWeights::default(),
+    );
+    assert_eq!(risk.liquidity_score, None);
+    assert_eq!(risk.composite_risk_score, 51);
+}
+
+/// Curve, unscored and Aave strategies holding 1M between them
+fn summary_strategies() -> Vec<Strategy> {
+    vec![
+        strategy(
+            "0xcurve",
+            serde_json::json!({
+                "totalDebt": "300000",
+                "risk": { "riskLevel": 2, "riskGroup": "Curve", "auditScore": 1 }
+            }),
+        ),
+        strategy("0xnoscore", serde_json::json!({ "totalDebt": "100000" })),
+        strategy(
+            "0xaave",
+            serde_json::json!({
+                "totalDebt": "600000",
+                "risk": { "riskLevel": 4, "riskGroup": "Aave" }
+            }),
+        ),
+    ]
+}
+
+#[test]
+fn test_risk_summary() {
+    let summary = RiskSummary::from_strategies("0xvault", &summary_strategies());
+    assert_eq!(summary.total_debt, 1_000_000);
+    assert_eq!(summary.strategy_count, 3);
+    assert!((summary.concentration_pct - 60.0).abs() < 1e-9);
+    let order: Vec<&str> = summary
+        .strategies
+        .iter()
+        .map(|s| s.address.as_str())
+        .collect();
+    assert_eq!(order, ["0xaave", "0xcurve", "0xnoscore"]);
+    assert_eq!(summary.largest().unwrap().risk_level, Some(4));
+    // Kong scores that are absent stay absent
+    assert_eq!(summary.strategies[1].audit_score, Some(1.0));
+    assert_eq!(summary.strategies[2].risk_level, None);
+    assert_eq!(summary.strategies[2].audit_score, None);
+}
+
+#[test]
+fn test_risk_summary_single_strategy() {
+    let single = RiskSummary::from_strategies("0xvault", &summary_strategies()[..1]);
+    assert_eq!(single.concentration_pct, 100.0);
+    assert_eq!(single.total_debt, 300_000);
+}
+
+#[test]
+fn test_risk_summary_undeployed() {
+    // Nothing deployed yet: shares come from the debt ratios
+    let undeployed = vec![
+        strategy(
+            "0xa",
+            serde_json::json!({ "debtRatio": "2500", "totalDebt": "0" }),
+        ),
+        strategy(
+            "0xb",
+            serde_json::json!({ "debtRatio": "7500", "totalDebt": "0" }),
+        ),
+    ];
+    let summary = RiskSummary::from_strategies("0xvault", &undeployed);
+    assert_eq!(summary.total_debt, 0);
+    assert_eq!(summary.concentration_pct, 75.0);
+    assert_eq!(summary.largest().unwrap().address, "0xb");
+
+    let empty = RiskSummary::from_strategies("0xvault", &[]);
+    assert_eq!(empty.strategy_count, 0);
+    assert_eq!(empty.concentration_pct, 0.0);
+    assert!(empty.largest().is_none());
+}
+
+#[test]
+fn test_config_risk_weights() {
+    let weights = RiskWeights {
+        strategy: 1.0,
+        concentration: 1.0,
+        liquidity: 0.0,
+        audit: 0.0,
+    };
+    let config = Config::new().with_risk_weights(weights);
+    assert_eq!(config.risk_weights(), &weights);
+    let client = Client::with_config(config).unwrap();
+    assert_eq!(client.risk_weights(), &weights);
+    assert_eq!(
+        Client::new().unwrap().risk_weights(),
+        &RiskWeights::default()
+    );
+}
+
+#[test]
+fn test_age_vs_tvl_points() {
+    let now = 1_700_000_000;
+    let mut young = vault_with(
+        "0xyoung",
+        0.12,
+        2_000_000.0,
+        serde_json::json!({ "yearn": false, "risk": { "riskLevel": 2 } }),
+    );
+    young.incept_time = Some((now - 10 * 86_400).to_string());
+    let mut old = vault("0xold", 0.05, 90_000_000.0);
+    old.incept_time = Some((now - 400 * 86_400 - 3_600).to_string());
+    // Falls back to activation when inceptTime is missing
+    let mut activated = vault("0xactivated", 0.07, 10_000_000.0);
+    activated.activation = Some((now - 120 * 86_400).to_string());
+    let undated = vault("0xundated", 0.07, 10_000_000.0);
+
+    let points = AgeVsTvlPoint::from_vaults(&[old, undated, young, activated], now);
+    let ages: Vec<u64> = points.iter().map(|p| p.age_days).collect();
+    assert_eq!(ages, vec![10, 120, 400]);
+    assert_eq!(points[0].vault_address, "0xyoung");
+    assert!(!points[0].is_endorsed);
+    assert!((points[0].net_apy_pct - 12.0).abs() < 1e-9);
+    assert!((points[2].tvl_usd - 90_000_000.0).abs() < f64::EPSILON);
+    assert!(points[2].is_endorsed);
+}
+
+#[test]
+fn test_tvl_sum_series_forward_fills() {
+    let point = |time: Option<u64>, value: f64| -> Tvl {
+        serde_json::from_value(serde_json::json!({
+            "chainId": 1,
+            "address": "0xvault",
+            "value": value,
+            "priceSource": "kong",
+            "period": "day",
+            "blockNumber": 0,
+            "time": time
+        }))
+        .unwrap()
+    };
+    let series = vec![
+        // Out of order on purpose
+        vec![point(Some(200), 12.0), point(Some(0), 10.0)],
+        vec![
+            point(Some(100), 5.0),
+            point(Some(300), 7.0),
+            point(None, 99.0),
+        ],
+        Vec::new(),
+    ];
+
+    let summed = TvlDataPoint::sum_series(&series);
+    let expected = [(0, 10.0), (100, 15.0), (200, 17.0), (300, 19.0)];
+    assert_eq!(summed.len(), expected.len());
+    for (point, (time, value)) in summed.iter().zip(expected) {
+        assert_eq!(point.time, time);
+        assert!((point.value - value).abs() < 1e-9);
+    }
+    assert!(TvlDataPoint::sum_series(&[]).is_empty());
+}
+
+#[test]
+fn test_protocol_tvl_breakdown() {
+    let mut vaults: Vec<Vault> = (0..12)
+        .map(|i| vault(&format!("0x{i:02}"), 0.05, f64::from(i) * 1_000.0))
+        .collect();
+    vaults[0].api_version = Some("3.0.2".to_string());
+    vaults[11].api_version = Some("3.0.2".to_string());
+    vaults[11].chain_id = 42161;
+    vaults[10].asset = None;
+    // Duplicate listing is counted once
+    vaults.push(vaults[5].clone());
+
+    let breakdown = ProtocolTvlBreakdown::from_vaults(&vaults);
+    assert!((breakdown.total_usd - 66_000.0).abs() < 1e-9);
+    assert!((breakdown.by_chain[&1] - 55_000.0).abs() < 1e-9);
+    assert!((breakdown.by_chain[&42161] - 11_000.0).abs() < 1e-9);
+    assert!((breakdown.by_version["3.0.2"] - 11_000.0).abs() < 1e-9);
+    assert!((breakdown.by_version["unknown"] - 55_000.0).abs() < 1e-9);
+    assert!((breakdown.by_asset["USDC"] - 56_000.0).abs() < 1e-9);
+    assert!((breakdown.by_asset["unknown"] - 10_000.0).abs() < 1e-9);
+
+    assert_eq!(breakdown.top_vaults.len(), TOP_VAULTS_LIMIT);
+    assert_eq!(breakdown.top_vaults[0], ("0x11".to_string(), 11_000.0));
+    assert_eq!(breakdown.top_vaults[9].0, "0x02");
+
+    assert!(ProtocolTvlBreakdown::from_vaults(&[]).top_vaults.is_empty());
+}
+
+#[test]
+fn test_realised_volatility_from_prices() {
+    // Constant daily growth has no volatility
+    let steady: Vec<f64> = (0..30).map(|i| 100.0 * 1.01f64.powi(i)).collect();
+    assert!(realised_volatility_from_prices(&steady).unwrap() < 1e-9);
+
+    // Alternating +/-10% moves: 5 up and 4 down log returns of ln(1.1)
+    let choppy: Vec<f64> = (0..10)
+        .map(|i| if i % 2 == 0 { 100.0 } else { 110.0 })
+        .collect();
+    let r = 1.1f64.ln();
+    let mean = r / 9.0;
+    let variance = (5.0 * (r - mean).powi(2) + 4.0 * (-r - mean).powi(2)) / 8.0;
+    let expected = variance.sqrt() * 365f64.sqrt();
+    let vol = realised_volatility_from_prices(&choppy).unwrap();
+    assert!((vol - expected).abs() < 1e-12);
+    assert!(vol > 1.9 && vol < 2.0);
+
+    let err = realised_volatility_from_prices(&choppy[..MIN_VOLATILITY_DAYS - 1]).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::Domain(DomainError::InsufficientData {
+            required: 10,
+            available: 9
+        })
+    ));
+}
+
+#[test]
+fn test_fee_drain_analysis() {
+    let fees: Fees = serde_json::from_value(
+        serde_json::json!({ "managementFee": 200.0, "performanceFee": 1000.0 }),
+    )
+    .unwrap();
+    let analysis = FeeDrainAnalysis::new(10_000.0, 365, 0.10, Some(&fees));
+
+    // 10% APR compounded daily for a year
+    assert!((analysis.gross_yield_usd - 1051.56).abs() < 0.01);
+    assert!((analysis.management_fee_usd - 200.0).abs() < 1e-9);
+    assert!((analysis.performance_fee_usd - analysis.gross_yield_usd * 0.1).abs() < 1e-9);
+    assert!(
+        (analysis.net_yield_usd
+            - (analysis.gross_yield_usd
+                - analysis.management_fee_usd
+                - analysis.performance_fee_usd))
+            .abs()
+            < 1e-9
+    );
+    assert!((analysis.fee_drag_pct - 29.02).abs() < 0.01);
+    // Yield after performance fee (~9%) outpaces the 2% management fee immediately
+    assert_eq!(analysis.breakeven_days(), 1);
+
+    let no_fees = FeeDrainAnalysis::new(10_000.0, 30, 0.05, None);
+    assert_eq!(no_fees.fee_drag_pct, 0.0);
+    assert_eq!(no_fees.net_yield_usd, no_fees.gross_yield_usd);
+    assert_eq!(no_fees.breakeven_days(), 0);
+}
+
+#[test]
+fn test_fee_drain_breakeven_with_compounding() {
+    let fees: Fees = serde_json::from_value(
+        serde_json::json!({ "managementFee": 200.0, "performanceFee": 0.0 }),
+    )
+    .unwrap();
+
+    // 1.9% gross trails a 2% management fee until compounding catches up
+    let slow = FeeDrainAnalysis::new(1_000.0, 365, 0.019, Some(&fees));
+    assert!(slow.net_yield_usd < 0.0);
+    let days = slow.breakeven_days();
+    assert!(
+        days > 365 && days < FeeDrainAnalysis::MAX_BREAKEVEN_DAYS,
+        "{days}"
+    );
+    assert!(FeeDrainAnalysis::new(1_000.0, days, 0.019, Some(&fees)).net_yield_usd >= 0.0);
+
+    let never = FeeDrainAnalysis::new(1_000.0, 365, 0.0, Some(&fees));
+    assert_eq!(never.fee_drag_pct, 0.0);
+    assert_eq!(never.breakeven_days(), u32::MAX);
+}
+
+#[test]
+fn test_vault_update_diff() {
+    let before = vec![
+        vault("0xAAA", 0.05, 1_000.0),
+        vault("0xbbb", 0.03, 2_000.0),
+        vault("0xccc", 0.04, 3_000.0),
+    ];
+    let after = vec![
+        vault_with(
+            "0xddd",
+            0.10,
+            500.0,
+            serde_json::json!({ "yearn": false, "risk": { "riskLevel": 2 } }),
+        ),
+        vault("0xaaa", 0.06, 1_000.0),
+        vault("0xbbb", 0.03, 2_000.0),
+    ];
+
+    let updates = VaultUpdate::diff(&before, &after);
+    assert_eq!(updates.len(), 3);
+
+    assert_eq!(updates[0].address, "0xddd");
+    assert_eq!(updates[0].kind, VaultChangeKind::Added);
+    assert!(updates[0].changes.is_empty());
+
+    // Addresses match case-insensitively; unchanged 0xbbb is skipped
+    assert_eq!(updates[1].address, "0xaaa");
+    assert_eq!(updates[1].kind, VaultChangeKind::Updated);
+    let fields: Vec<&str> = updates[1]
+        .changes
+        .iter()
+        .map(|c| c.field.as_str())
+        .collect();
+    assert_eq!(fields, ["address", "apy.grossApr", "apy.net"]);
+    assert_eq!(updates[1].changes[2].previous, serde_json::json!(0.05));
+    assert_eq!(updates[1].changes[2].current, serde_json::json!(0.06));
+
+    assert_eq!(updates[2].address, "0xccc");
+    assert_eq!(updates[2].kind, VaultChangeKind::Removed);
+    assert!(updates[2].vault.is_none());
+
+    assert!(VaultUpdate::diff(&after, &after).is_empty());
+}
+
+#[test]
+fn test_timeseries_query_snapshot() {
+    let query = TimeseriesQuery::new(1, "0xVault", ykong::APY_SERIES_LABEL)
+        .component("net")
+        .period(TvlPeriod::Week)
+        .limit(12)
+        .build();
+    assert_eq!(
+        query,
+        r#"{ timeseries(chainId: 1, address: "0xVault", label: "apy-bwd-delta-pps", component: "net", period: "week", limit: 12) { time value } }"#
+    );
+
+    // Component is omitted when unset
+    let query = TimeseriesQuery::new(10, "0xVault", ykong::PPS_SERIES_LABEL).build();
+    assert_eq!(
+        query,
+        r#"{ timeseries(chainId: 10, address: "0xVault", label: "pps", period: "day", limit: 30) { time value } }"#
+    );
+
+    // Quotes in arguments are escaped rather than ending the string
+    let query = TimeseriesQuery::new(1, "0x1", r#"pps") { x } #"#).build();
+    assert!(query.contains(r#"label: "pps\") { x } #""#));
+}
+
+#[test]
+fn test_timeseries_point_parsing() {
+    let point: TimeseriesPoint =
+        serde_json::from_value(serde_json::json!({ "time": "1700000000", "value": "0.0512" }))
+            .unwrap();
+    assert_eq!(point.time, 1_700_000_000);
+    assert_eq!(point.value, 0.0512);
+    assert_eq!(point.raw, "0.0512");
+
+    // Numeric JSON is accepted too
+    let point: TimeseriesPoint =
+        serde_json::from_value(serde_json::json!({ "time": 1700000000, "value": 1.0234 })).unwrap();
+    assert_eq!(point.raw, "1.0234");
+
+    assert!(serde_json::from_value::<TimeseriesPoint>(
+        serde_json::json!({ "time": "1700000000", "value": "n/a" })
+    )
+    .is_err());
+}
+
+#[test]
+fn test_performance_history_alignment() {
+    // APY is missing the first day, PPS the last
+    let apy: Vec<TimeseriesPoint> = serde_json::from_value(serde_json::json!([
+        { "time": "1700172800", "value": "0.06" },
+        { "time": "1700086400", "value": "0.05" },
+        { "time": "1700259200", "value": "0.07" }
+    ]))
+    .unwrap();
+    let pps: Vec<TimeseriesPoint> = serde_json::from_value(serde_json::json!([
+        { "time": "1700000000", "value": "1.000" },
+        { "time": "1700086400", "value": "1.001" },
+        { "time": "1700172800", "value": "1.002" }
+    ]))
+    .unwrap();
+
+    let points = PerformancePoint::align(&apy, &pps);
+    let rows: Vec<_> = points.iter().map(|p| (p.time, p.apy, p.pps)).collect();
+    assert_eq!(
+        rows,
+        [
+            (1_700_000_000, None, Some(1.000)),
+            (1_700_086_400, Some(0.05), Some(1.001)),
+            (1_700_172_800, Some(0.06), Some(1.002)),
+            (1_700_259_200, Some(0.07), None),
+        ]
+    );
+
+    assert!(PerformancePoint::align(&[], &[]).is_empty());
+}
+
+#[cfg(feature = "websocket")]
+// The handshake callback signature is fixed by tungstenite
+#[allow(clippy::result_large_err)]
+mod websocket {
+    use super::{vault, vault_with};
+    use futures_util::{SinkExt, StreamExt};
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+    use tokio_tungstenite::tungstenite::http::StatusCode;
+    use tokio_tungstenite::tungstenite::Message;
+    use ykong::error::DomainError;
+    use ykong::{Client, Config, Error, VaultChangeKind, SUBSCRIPTION_PROTOCOL};
+
+    fn client(addr: std::net::SocketAddr) -> Client {
+        Client::with_config(Config::new().with_subscription_url(format!("ws://{addr}/api/gql")))
+            .unwrap()
+    }
+
+    fn next(vaults: serde_json::Value) -> Message {
+        Message::text(
+            serde_json::json!({
+                "id": "1",
+                "type": "next",
+                "payload": { "data": { "vaults": vaults } }
+            })
+            .to_string(),
+        )
+    }
+
+    #[test]
+    fn test_subscription_url_default() {
+        let client = Client::new().unwrap();
+        assert_eq!(client.subscription_url(), "wss://kong.yearn.farm/api/gql");
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_vaults_yields_diffs() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let server = tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut ws =
+                tokio_tungstenite::accept_hdr_async(tcp, |req: &Request, mut resp: Response| {
+                    let protocol = req.headers().get("Sec-WebSocket-Protocol").cloned();
+                    assert_eq!(protocol.as_ref().unwrap(), SUBSCRIPTION_PROTOCOL);
+                    resp.headers_mut()
+                        .insert("Sec-WebSocket-Protocol", protocol.unwrap());
+                    Ok(resp)
+                })
+                .await
+                .unwrap();
+
+            let init = ws.next().await.unwrap().unwrap();
+            assert!(init.to_text().unwrap().contains("connection_init"));
+            ws.send(Message::text(r#"{"type":"connection_ack"}"#))
+                .await
+                .unwrap();
+
+            let subscribe = ws.next().await.unwrap().unwrap();
+            let subscribe: serde_json::Value =
+                serde_json::from_str(subscribe.to_text().unwrap()).unwrap();
+            assert_eq!(subscribe["type"], "subscribe");
+            let query = subscribe["payload"]["query"].as_str().unwrap();
+            assert!(query.contains("vaults(chainId: 1)"));
+
+            let first = serde_json::to_value(vec![
+                vault("0xaaa", 0.05, 1_000.0),
+                vault("0xbbb", 0.03, 2_000.0),
+            ])
+            .unwrap();
+            let second = serde_json::to_value(vec![vault_with(
+                "0xaaa",
+                0.05,
+                1_000.0,
+                serde_json::json!({ "isShutdown": true }),
+            )])
+            .unwrap();
+            ws.send(next(first)).await.unwrap();
+            ws.send(next(second)).await.unwrap();
+            ws.send(Message::text(r#"{"id":"1","type":"complete"}"#))
+                .await
+                .unwrap();
+        });
+
+        let stream = client(addr).subscribe_vaults(1).await.unwrap();
+        let updates: Vec<_> = stream.collect().await;
+        server.await.unwrap();
+
+        let updates: Vec<_> = updates.into_iter().map(Result::unwrap).collect();
+        let kinds: Vec<_> = updates
+            .iter()
+            .map(|u| (u.address.as_str(), u.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                ("0xaaa", VaultChangeKind::Added),
+                ("0xbbb", VaultChangeKind::Added),
+                ("0xaaa", VaultChangeKind::Updated),
+                ("0xbbb", VaultChangeKind::Removed),
+            ]
+        );
+        assert_eq!(updates[2].changes.len(), 1);
+        assert_eq!(updates[2].changes[0].field, "isShutdown");
+        assert_eq!(updates[2].vault.as_ref().unwrap().is_shutdown, Some(true));
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_vaults_not_supported() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let server = tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let _ = tokio_tungstenite::accept_hdr_async(tcp, |_: &Request, _: Response| {
+                let mut reject =
+                    tokio_tungstenite::tungstenite::handshake::server::ErrorResponse::new(None);
+                *reject.status_mut() = StatusCode::NOT_FOUND;
+                Err(reject)
+            })
+            .await;
+        });
+
+        let err = match client(addr).subscribe_vaults(1).await {
+            Ok(_) => panic!("expected subscription to be rejected"),
+            Err(e) => e,
+        };
+        server.await.unwrap();
+
+        match err {
+            Error::Domain(DomainError::NotSupported(message)) => {
+                assert!(message.contains("by_chain"), "{message}");
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,349 @@
+//! Address and chain ID validation
+//!
+//! Bad arguments must fail before anything is sent, so every test runs
+//! against a mock server and checks it received no requests. Case variants
+//! of the same address must produce the same query and the same result.
+
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+use ykong::error::DomainError;
+use ykong::{Client, Config, Error, StrategyFilter, TimeseriesQuery, TvlPeriod, VaultFilter};
+use ykong::{RiskProfile, YearnChain, KONG_CHAIN_IDS};
+
+const VAULT: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+const STRATEGY: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
+const BAD: &str = "0x1234";
+
+/// Answers vault, strategy and price queries with lowercase addresses
+struct Kong;
+
+impl Respond for Kong {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        let query = body["query"].as_str().unwrap();
+        let data = if query.contains("strategies(") {
+            serde_json::json!({ "strategies": [{
+                "address": STRATEGY.to_lowercase(),
+                "chainId": 1,
+                "vault": VAULT.to_lowercase(),
+                "keeper": null,
+                "totalDebt": "100"
+            }] })
+        } else if query.contains("vault(") {
+            serde_json::json!({ "vault": {
+                "address": VAULT.to_lowercase(),
+                "chainId": 1,
+                "token": VAULT.to_uppercase().replacen("0X", "0x", 1),
+                "strategies": [STRATEGY.to_lowercase()],
+                "asset": { "address": VAULT.to_lowercase(), "symbol": "USDC" }
+            } })
+        } else if query.contains("prices(") {
+            serde_json::json!({ "prices": [{
+                "address": VAULT.to_lowercase(),
+                "chainId": 1,
+                "priceUsd": 1.0,
+                "priceSource": "test",
+                "blockNumber": 1,
+                "timestamp": 1
+            }] })
+        } else {
+            serde_json::json!({})
+        };
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({ "data": data }))
+    }
+}
+
+async fn mock_kong() -> (MockServer, Client) {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(Kong)
+        .mount(&server)
+        .await;
+    let client = Client::with_config(Config::new().with_base_url(server.uri())).unwrap();
+    (server, client)
+}
+
+async fn query_bodies(server: &MockServer) -> Vec<String> {
+    server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|r| String::from_utf8_lossy(&r.body).into_owned())
+        .collect()
+}
+
+fn assert_invalid_address<T: std::fmt::Debug>(result: ykong::Result<T>, input: &str) {
+    match result {
+        Err(Error::Domain(DomainError::InvalidAddress { input: got, .. })) => {
+            assert_eq!(got, input);
+        }
+        other => panic!("expected InvalidAddress for {input:?}, got {other:?}"),
+    }
+}
+
+fn assert_invalid_chain<T: std::fmt::Debug>(result: ykong::Result<T>, chain_id: u64) {
+    match result {
+        Err(Error::Domain(DomainError::InvalidChainId(got))) => assert_eq!(got, chain_id),
+        other => panic!("expected InvalidChainId({chain_id}), got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_invalid_address_reasons() {
+    let (server, client) = mock_kong().await;
+    let vaults = client.vaults();
+
+    for (input, reason) in [
+        (
+            "a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+            "missing 0x prefix",
+        ),
+        (BAD, "expected 40 hex digits, got 4"),
+        (
+            "0xg0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+            "invalid hex character 'g'",
+        ),
+        (
+            "0xa0b86991\" }) { injected } #",
+            "invalid hex character '\"'",
+        ),
+    ] {
+        let err = vaults.get(1, input).await.unwrap_err();
+        match err {
+            Error::Domain(DomainError::InvalidAddress {
+                input: got,
+                reason: why,
+            }) => {
+                assert_eq!(got, input);
+                assert_eq!(why, reason);
+            }
+            other => panic!("unexpected error for {input:?}: {other:?}"),
+        }
+    }
+    assert!(query_bodies(&server).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_vault_methods_reject_bad_addresses() {
+    let (server, client) = mock_kong().await;
+    let vaults = client.vaults();
+
+    assert_invalid_address(vaults.get(1, BAD).await, BAD);
+    assert_invalid_address(vaults.by_token(1, BAD).await, BAD);
+    assert_invalid_address(
+        vaults
+            .for_wallet(1, BAD, RiskProfile::Moderate, 1_000.0)
+            .await,
+        BAD,
+    );
+    assert_invalid_address(vaults.yield_gap(1, BAD).await, BAD);
+    assert_invalid_address(vaults.vault_risk_profile(1, BAD).await, BAD);
+    assert_invalid_address(
+        vaults
+            .list(Some(
+                VaultFilter::new().addresses(vec![VAULT.into(), BAD.into()]),
+            ))
+            .await,
+        BAD,
+    );
+    assert_invalid_address(
+        vaults
+            .list_summaries(Some(VaultFilter::new().addresses(vec![BAD.into()])))
+            .await,
+        BAD,
+    );
+    assert_invalid_address(
+        vaults
+            .timeseries(&TimeseriesQuery::new(1, BAD, "apy-bwd-delta-pps"))
+            .await,
+        BAD,
+    );
+    assert_invalid_address(vaults.apy_history(1, BAD, TvlPeriod::Day, 7).await, BAD);
+    assert_invalid_address(vaults.pps_history(1, BAD, TvlPeriod::Day, 7).await, BAD);
+    assert_invalid_address(
+        vaults.performance_history(1, BAD, TvlPeriod::Day, 7).await,
+        BAD,
+    );
+    assert!(query_bodies(&server).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_strategy_methods_reject_bad_addresses() {
+    let (server, client) = mock_kong().await;
+    let strategies = client.strategies();
+
+    assert_invalid_address(strategies.get(1, BAD).await, BAD);
+    assert_invalid_address(strategies.by_vault(1, BAD).await, BAD);
+    assert_invalid_address(
+        strategies
+            .list(Some(StrategyFilter::new().vault(BAD)))
+            .await,
+        BAD,
+    );
+    assert_invalid_address(
+        strategies
+            .list(Some(StrategyFilter::new().addresses(vec![BAD.into()])))
+            .await,
+        BAD,
+    );
+    assert_invalid_address(strategies.risk_score(1, BAD).await, BAD);
+    assert_invalid_address(strategies.risk_summary(1, BAD).await, BAD);
+    assert_invalid_address(strategies.protocol_breakdown(1, BAD).await, BAD);
+    assert_invalid_address(strategies.solvency_check(1, BAD).await, BAD);
+    assert_invalid_address(strategies.compute_harvest_roi(1, BAD).await, BAD);
+    assert!(query_bodies(&server).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_price_report_and_tvl_methods_reject_bad_addresses() {
+    let (server, client) = mock_kong().await;
+    let prices = client.prices();
+    let reports = client.reports();
+    let tvls = client.tvls();
+
+    assert_invalid_address(prices.get(1, BAD).await, BAD);
+    assert_invalid_address(prices.at_timestamp(1, BAD, 1).await, BAD);
+    assert_invalid_address(prices.current(1, BAD).await, BAD);
+    assert_invalid_address(prices.usd(1, BAD).await, BAD);
+    assert_invalid_address(prices.daily_history(1, BAD, 7).await, BAD);
+    assert_invalid_address(prices.realised_volatility(1, BAD, 7).await, BAD);
+    assert_invalid_address(prices.volatility_percentile(1, VAULT, 7, &[BAD]).await, BAD);
+
+    assert_invalid_address(reports.vault_reports(1, BAD).await, BAD);
+    assert_invalid_address(reports.strategy_reports(1, BAD).await, BAD);
+    assert_invalid_address(reports.latest_vault_report(1, BAD).await, BAD);
+    assert_invalid_address(reports.latest_strategy_report(1, BAD).await, BAD);
+    assert_invalid_address(reports.vault_total_gains_usd(1, BAD).await, BAD);
+    assert_invalid_address(reports.strategy_total_profits_usd(1, BAD).await, BAD);
+    assert_invalid_address(reports.cross_vault_comparison(1, VAULT, BAD).await, BAD);
+    assert_invalid_address(reports.fee_drain_analysis(1, BAD, 1_000.0, 30).await, BAD);
+
+    assert_invalid_address(tvls.history(1, BAD, TvlPeriod::Day, 7).await, BAD);
+    assert_invalid_address(tvls.history_from(1, BAD, TvlPeriod::Day, 7, 1).await, BAD);
+    assert_invalid_address(tvls.daily(1, BAD, 7).await, BAD);
+    assert_invalid_address(tvls.weekly(1, BAD, 4).await, BAD);
+    assert_invalid_address(tvls.monthly(1, BAD, 3).await, BAD);
+    assert_invalid_address(tvls.current(1, BAD).await, BAD);
+    assert_invalid_address(
+        tvls.series_for_vaults(1, &[VAULT.to_string(), BAD.to_string()], 7)
+            .await,
+        BAD,
+    );
+    assert!(query_bodies(&server).await.is_empty());
+}
+
+#[test]
+fn test_kong_chain_ids_cover_yearn_chains() {
+    for chain in YearnChain::ALL {
+        assert!(KONG_CHAIN_IDS.contains(&chain.chain_id()), "{chain}");
+    }
+    let mut ids = KONG_CHAIN_IDS.to_vec();
+    ids.sort_unstable();
+    ids.dedup();
+    assert_eq!(ids.len(), KONG_CHAIN_IDS.len());
+    assert_eq!(YearnChain::Arbitrum.chain_id(), 42161);
+}
+
+#[tokio::test]
+async fn test_methods_reject_unknown_chains() {
+    let (server, client) = mock_kong().await;
+    assert!(!KONG_CHAIN_IDS.contains(&0));
+
+    for chain_id in [0, 999] {
+        assert_invalid_chain(client.vaults().by_chain(chain_id).await, chain_id);
+        assert_invalid_chain(client.vaults().summaries(chain_id).await, chain_id);
+        assert_invalid_chain(client.vaults().endorsed(chain_id).await, chain_id);
+        assert_invalid_chain(client.vaults().get(chain_id, VAULT).await, chain_id);
+        assert_invalid_chain(
+            client.vaults().endorsed_tvl_chart(chain_id, 7).await,
+            chain_id,
+        );
+        assert_invalid_chain(
+            client
+                .vaults()
+                .apy_history(chain_id, VAULT, TvlPeriod::Day, 7)
+                .await,
+            chain_id,
+        );
+        assert_invalid_chain(client.strategies().by_chain(chain_id).await, chain_id);
+        assert_invalid_chain(client.strategies().get(chain_id, STRATEGY).await, chain_id);
+        assert_invalid_chain(
+            client.strategies().liveness_monitor(chain_id, 24).await,
+            chain_id,
+        );
+        assert_invalid_chain(
+            client
+                .strategies()
+                .compute_harvest_roi(chain_id, STRATEGY)
+                .await,
+            chain_id,
+        );
+        assert_invalid_chain(client.prices().get(chain_id, VAULT).await, chain_id);
+        assert_invalid_chain(
+            client.reports().vault_reports(chain_id, VAULT).await,
+            chain_id,
+        );
+        assert_invalid_chain(client.tvls().daily(chain_id, VAULT, 7).await, chain_id);
+        assert_invalid_chain(client.tvls().vault_age_vs_tvl(chain_id).await, chain_id);
+        assert_invalid_chain(client.gas_price_gwei(chain_id).await, chain_id);
+    }
+    assert!(query_bodies(&server).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_case_variants_are_equivalent() {
+    let (server, client) = mock_kong().await;
+    let variants = [
+        VAULT.to_string(),
+        VAULT.to_lowercase(),
+        VAULT.to_uppercase().replacen("0X", "0x", 1),
+        VAULT.to_uppercase(),
+        format!("  {VAULT} "),
+    ];
+
+    let mut results = Vec::new();
+    for variant in &variants {
+        let vault = client.vaults().get(1, variant).await.unwrap().unwrap();
+        let strategies = client.strategies().by_vault(1, variant).await.unwrap();
+        let price = client.prices().usd(1, variant).await.unwrap();
+        results.push(serde_json::json!({
+            "vault": vault,
+            "strategies": strategies,
+            "price": price,
+        }));
+    }
+    assert!(results.windows(2).all(|pair| pair[0] == pair[1]));
+
+    // Every query carried the lowercase address
+    let bodies = query_bodies(&server).await;
+    assert_eq!(bodies.len(), variants.len() * 3);
+    for body in bodies {
+        assert!(body.contains(&VAULT.to_lowercase()), "{body}");
+        assert!(!body.contains(VAULT), "{body}");
+    }
+}
+
+#[tokio::test]
+async fn test_response_addresses_are_checksummed() {
+    let (_server, client) = mock_kong().await;
+
+    let vault = client.vaults().get(1, VAULT).await.unwrap().unwrap();
+    assert_eq!(vault.address, VAULT);
+    assert_eq!(vault.token.as_deref(), Some(VAULT));
+    assert_eq!(vault.strategies, Some(vec![STRATEGY.to_string()]));
+    assert_eq!(vault.asset.unwrap().address, VAULT);
+
+    let strategies = client.strategies().by_vault(1, VAULT).await.unwrap();
+    assert_eq!(strategies[0].address, STRATEGY);
+    assert_eq!(strategies[0].vault.as_deref(), Some(VAULT));
+    assert_eq!(strategies[0].keeper, None);
+
+    let summary = client
+        .strategies()
+        .risk_summary(1, &VAULT.to_lowercase())
+        .await
+        .unwrap();
+    assert_eq!(summary.vault, VAULT);
+    assert_eq!(summary.strategies[0].address, STRATEGY);
+}
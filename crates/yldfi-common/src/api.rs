@@ -1239,6 +1239,7 @@ mod tests {
 
     // Test with domain-specific errors
     #[derive(Debug, thiserror::Error)]
+    #[allow(dead_code)]
     enum TestDomainError {
         #[error("No route found")]
         NoRouteFound,
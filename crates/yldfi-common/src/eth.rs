@@ -2,6 +2,7 @@
 //!
 //! This module provides:
 //! - Validation functions: `is_valid_address`, `is_valid_tx_hash`, etc.
+//! - EIP-55 checksumming: `to_checksum_address`
 //! - Newtype wrappers: `Address`, `TxHash` for type-safe validated values
 //!
 //! # Example
@@ -20,6 +21,7 @@
 
 use std::fmt;
 use std::str::FromStr;
+use tiny_keccak::{Hasher, Keccak};
 
 // ============================================================================
 // Address Newtype
@@ -235,6 +237,48 @@ pub fn normalize_address(address: &str) -> Option<String> {
     Some(address.to_lowercase())
 }
 
+/// Converts an Ethereum address to its EIP-55 mixed-case checksum form.
+///
+/// The input case is ignored. Returns `None` if the address is not valid.
+///
+/// # Examples
+///
+/// ```
+/// use yldfi_common::eth::to_checksum_address;
+///
+/// assert_eq!(
+///     to_checksum_address("0xd8da6bf26964af9d7eed9e03e53415d37aa96045"),
+///     Some("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string())
+/// );
+/// assert_eq!(to_checksum_address("0x123"), None);
+/// ```
+#[must_use]
+pub fn to_checksum_address(address: &str) -> Option<String> {
+    let lower = normalize_address(address)?;
+    let hex = &lower[2..];
+
+    let mut hash = [0u8; 32];
+    let mut hasher = Keccak::v256();
+    hasher.update(hex.as_bytes());
+    hasher.finalize(&mut hash);
+
+    let mut checksummed = String::with_capacity(42);
+    checksummed.push_str("0x");
+    for (i, c) in hex.chars().enumerate() {
+        let nibble = if i % 2 == 0 {
+            hash[i / 2] >> 4
+        } else {
+            hash[i / 2] & 0x0f
+        };
+        if nibble >= 8 {
+            checksummed.push(c.to_ascii_uppercase());
+        } else {
+            checksummed.push(c);
+        }
+    }
+    Some(checksummed)
+}
+
 /// Validates a transaction hash format.
 ///
 /// Returns `true` if the hash:
@@ -372,6 +416,27 @@ mod tests {
         )); // Invalid hex
     }
 
+    #[test]
+    fn test_to_checksum_address() {
+        // Test vectors from EIP-55
+        for expected in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ] {
+            assert_eq!(
+                to_checksum_address(&expected.to_lowercase()).as_deref(),
+                Some(expected)
+            );
+            assert_eq!(
+                to_checksum_address(&expected.to_uppercase().replacen("0X", "0x", 1)).as_deref(),
+                Some(expected)
+            );
+        }
+        assert_eq!(to_checksum_address("invalid"), None);
+    }
+
     #[test]
     fn test_normalize_address() {
         assert_eq!(
@@ -8,6 +8,7 @@
 //! - [`eth`] - Ethereum address and transaction hash validation
 //! - [`chains`] - EVM chain ID and name mappings
 //! - [`units`] - Wei/Gwei/Ether conversion utilities
+//! - [`time`] - ISO 8601 timestamp parsing
 //!
 //! ## Retry Utilities
 //!
@@ -71,6 +72,7 @@ pub mod eth;
 pub mod http;
 pub mod rate_limit;
 pub mod retry;
+pub mod time;
 pub mod units;
 
 pub use retry::{with_retry, with_simple_retry, RetryConfig, RetryError, RetryableError};
@@ -0,0 +1,158 @@
+//! ISO 8601 timestamp parsing
+//!
+//! APIs return timestamps as RFC 3339 strings, naive UTC date-times or bare
+//! dates. [`parse_iso8601`] turns any of these into Unix seconds without
+//! pulling in a date library.
+//!
+//! ```
+//! use yldfi_common::time::parse_iso8601;
+//!
+//! assert_eq!(parse_iso8601("2024-05-13T12:34:56Z"), Some(1_715_603_696));
+//! assert_eq!(parse_iso8601("2024-05-13 14:34:56+02:00"), Some(1_715_603_696));
+//! assert_eq!(parse_iso8601("2024-05-13"), Some(1_715_558_400));
+//! ```
+
+/// Parse an ISO 8601 date or date-time to Unix seconds
+///
+/// Accepts `YYYY-MM-DD`, optionally followed by `T` or a space and
+/// `hh:mm:ss` with optional fractional seconds, then an optional `Z` or
+/// `±hh:mm` offset. Times without an offset are taken as UTC.
+#[must_use]
+pub fn parse_iso8601(s: &str) -> Option<i64> {
+    let s = s.trim();
+    let (date, time) = match s.split_once(['T', ' ']) {
+        Some((date, time)) => (date, Some(time)),
+        None => (s, None),
+    };
+    let mut date_parts = date.splitn(3, '-');
+    let year: i64 = date_parts.next()?.parse().ok()?;
+    let month: i64 = date_parts.next()?.parse().ok()?;
+    let day: i64 = date_parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    let days = days_from_civil(year, month, day);
+    let Some(time) = time else {
+        return Some(days * 86_400);
+    };
+
+    // Split off the UTC offset ("Z", "+hh:mm" or "-hh:mm")
+    let (clock, offset_secs) = if let Some(clock) = time.strip_suffix('Z') {
+        (clock, 0)
+    } else if let Some(idx) = time.rfind(['+', '-']) {
+        let (clock, offset) = time.split_at(idx);
+        let (hours, minutes) = offset[1..].split_once(':')?;
+        let secs = hours.parse::<i64>().ok()? * 3600 + minutes.parse::<i64>().ok()? * 60;
+        (clock, if offset.starts_with('-') { -secs } else { secs })
+    } else {
+        (time, 0)
+    };
+    let clock = clock.split('.').next()?;
+    let mut clock_parts = clock.splitn(3, ':');
+    let hour: i64 = clock_parts.next()?.parse().ok()?;
+    let minute: i64 = clock_parts.next()?.parse().ok()?;
+    let second: i64 = clock_parts.next()?.parse().ok()?;
+    if hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    Some(days * 86_400 + hour * 3600 + minute * 60 + second - offset_secs)
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date
+///
+/// Howard Hinnant's `days_from_civil`.
+#[must_use]
+pub fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// `(year, month, day)` for days since 1970-01-01
+///
+/// Inverse of [`days_from_civil`].
+#[must_use]
+pub fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    (yoe + era * 400 + i64::from(month <= 2), month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_naive_utc() {
+        assert_eq!(parse_iso8601("1970-01-01T00:00:00"), Some(0));
+        assert_eq!(parse_iso8601("2024-05-13T12:34:56"), Some(1_715_603_696));
+        assert_eq!(parse_iso8601(" 2024-05-13 12:34:56 "), Some(1_715_603_696));
+        assert_eq!(
+            parse_iso8601("2024-05-13T12:34:56.789"),
+            Some(1_715_603_696)
+        );
+    }
+
+    #[test]
+    fn test_parse_offsets() {
+        assert_eq!(parse_iso8601("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(
+            parse_iso8601("2024-04-02T10:00:00.123456Z"),
+            Some(1_712_052_000)
+        );
+        assert_eq!(
+            parse_iso8601("2024-05-01T02:00:00+02:00"),
+            Some(1_714_521_600)
+        );
+        assert_eq!(
+            parse_iso8601("2024-04-30T22:00:00-02:00"),
+            Some(1_714_521_600)
+        );
+        assert_eq!(
+            parse_iso8601("2024-05-13 12:34:56+00:00"),
+            Some(1_715_603_696)
+        );
+        // Before the epoch
+        assert_eq!(parse_iso8601("1969-12-31T23:59:59Z"), Some(-1));
+    }
+
+    #[test]
+    fn test_parse_dates() {
+        assert_eq!(parse_iso8601("1970-01-01"), Some(0));
+        assert_eq!(parse_iso8601("2024-02-29"), Some(19_782 * 86_400));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert_eq!(parse_iso8601("yesterday"), None);
+        assert_eq!(parse_iso8601("not a date"), None);
+        assert_eq!(parse_iso8601(""), None);
+        assert_eq!(parse_iso8601("2024-13-01"), None);
+        assert_eq!(parse_iso8601("2024-05-00T00:00:00Z"), None);
+        assert_eq!(parse_iso8601("2024-05-13T25:00:00Z"), None);
+        assert_eq!(parse_iso8601("2024-05-13T12:34"), None);
+        assert_eq!(parse_iso8601("2024-05-13T12:34:56+0200"), None);
+    }
+
+    #[test]
+    fn test_civil_days_round_trip() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2024, 2, 29), 19_782);
+        assert_eq!(days_from_civil(1969, 12, 31), -1);
+        for days in [-719_468, -1, 0, 19_782, 20_000, 100_000] {
+            let (y, m, d) = civil_from_days(days);
+            assert_eq!(days_from_civil(y, m, d), days);
+        }
+        assert_eq!(civil_from_days(19_782), (2024, 2, 29));
+    }
+}
@@ -6,7 +6,7 @@
 use crate::error::{Error, Result};
 use crate::types::{
     ApiError, Chain, PriceRequest, PriceResponse, QuoteRequest, QuoteResponse, Source,
-    SourcesResponse,
+    SourcesResponse, SwapMode,
 };
 use reqwest::Client as HttpClient;
 use serde::de::DeserializeOwned;
@@ -225,6 +225,11 @@ impl Client {
         headers
     }
 
+    /// Build a swap endpoint URL for the given allowance mode
+    fn swap_url(&self, mode: SwapMode, endpoint: &str) -> String {
+        format!("{}/swap/{}/{endpoint}", self.api_base_url(), mode.path())
+    }
+
     /// Make a GET request to the API
     async fn get<T: DeserializeOwned>(&self, url: &str, params: &[(String, String)]) -> Result<T> {
         let response = self
@@ -301,10 +306,13 @@ impl Client {
     /// }
     /// ```
     pub async fn get_quote(&self, chain: Chain, request: &QuoteRequest) -> Result<QuoteResponse> {
-        let url = format!("{}/swap/permit2/quote", self.api_base_url());
+        request.validate()?;
+        let url = self.swap_url(request.mode, "quote");
         let mut params = request.to_query_params();
         self.add_chain_param(chain, &mut params);
-        self.get(&url, &params).await
+        let mut quote: QuoteResponse = self.get(&url, &params).await?;
+        quote.mode = request.mode;
+        Ok(quote)
     }
 
     /// Get an indicative price (no transaction data)
@@ -346,7 +354,8 @@ impl Client {
     /// }
     /// ```
     pub async fn get_price(&self, chain: Chain, request: &PriceRequest) -> Result<PriceResponse> {
-        let url = format!("{}/swap/permit2/price", self.api_base_url());
+        request.validate()?;
+        let url = self.swap_url(request.mode, "price");
         let mut params = request.to_query_params();
         self.add_chain_param(chain, &mut params);
         self.get(&url, &params).await
@@ -438,6 +447,19 @@ mod tests {
         assert_eq!(params, vec![("chainId".to_string(), "137".to_string())]);
     }
 
+    #[test]
+    fn test_swap_url_by_mode() {
+        let client = Client::new().unwrap();
+        assert_eq!(
+            client.swap_url(SwapMode::Permit2, "quote"),
+            "https://api.0x.org/swap/permit2/quote"
+        );
+        assert_eq!(
+            client.swap_url(SwapMode::AllowanceHolder, "price"),
+            "https://api.0x.org/swap/allowance-holder/price"
+        );
+    }
+
     #[test]
     fn test_build_headers() {
         let client = Client::with_api_key("test-api-key").unwrap();
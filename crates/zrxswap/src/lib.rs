@@ -8,7 +8,7 @@
 //!
 //! - Multi-chain support (Ethereum, Polygon, Arbitrum, Optimism, Base, BSC, and more)
 //! - Professional-grade liquidity aggregation across 100+ DEXs
-//! - Permit2 integration for efficient token approvals
+//! - Permit2 and AllowanceHolder approval modes
 //! - Gasless trading support
 //! - MEV protection options
 //! - Type-safe request/response handling
@@ -129,6 +129,37 @@
 //! }
 //! ```
 //!
+//! # AllowanceHolder and Selling the Entire Balance
+//!
+//! Smart contracts and wallets that cannot sign EIP-712 messages should use
+//! the AllowanceHolder endpoints instead of Permit2. Contract integrations
+//! that cannot know the exact amount up front can sell their whole balance:
+//!
+//! ```no_run
+//! use zrxswap::{Client, Chain, QuoteApproval, QuoteRequest, SwapMode};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), zrxswap::Error> {
+//!     let client = Client::with_api_key("your-api-key")?;
+//!
+//!     let request = QuoteRequest::sell_all(
+//!         "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2", // WETH
+//!         "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48", // USDC
+//!     )
+//!     .with_taker("0xYourContractAddress")
+//!     .with_mode(SwapMode::AllowanceHolder);
+//!
+//!     let quote = client.get_quote(Chain::Ethereum, &request).await?;
+//!
+//!     // No signature needed: approve the spender, then send the transaction
+//!     if let QuoteApproval::AllowanceHolder { spender: Some(spender) } = quote.approval() {
+//!         println!("Approve {spender} before swapping");
+//!     }
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
 //! # Supported Chains
 //!
 //! 0x supports swaps on the following chains:
@@ -186,8 +217,8 @@ pub use client::{Client, Config, DEFAULT_BASE_URL};
 pub use error::{Error, Result};
 pub use types::{
     AllowanceIssue, ApiError, BalanceIssue, Chain, LiquiditySource, Permit2Data, PriceRequest,
-    PriceResponse, QuoteIssues, QuoteRequest, QuoteResponse, Route, RouteFill, RouteToken, Source,
-    SourcesResponse, TokenMetadata, Transaction, ValidationError,
+    PriceResponse, QuoteApproval, QuoteIssues, QuoteRequest, QuoteResponse, Route, RouteFill,
+    RouteToken, Source, SourcesResponse, SwapMode, TokenMetadata, Transaction, ValidationError,
 };
 
 // Re-export common utilities
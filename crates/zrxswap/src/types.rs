@@ -5,6 +5,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::error::validation_error;
+
 /// Supported chains for 0x API
 ///
 /// Each chain has a specific chain ID used for API requests and
@@ -181,10 +183,45 @@ impl From<Chain> for yldfi_common::Chain {
     }
 }
 
+/// Allowance mode used for a swap
+///
+/// 0x v2 exposes the same swap under two endpoint families. Permit2 is the
+/// default and suits EOAs that can sign an EIP-712 message per trade.
+/// AllowanceHolder suits smart contracts and wallets that cannot sign: the
+/// taker approves the AllowanceHolder contract directly and no signature is
+/// needed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SwapMode {
+    /// `/swap/permit2/*` endpoints (default)
+    #[default]
+    Permit2,
+    /// `/swap/allowance-holder/*` endpoints
+    AllowanceHolder,
+}
+
+impl SwapMode {
+    /// Endpoint path segment for this mode
+    #[must_use]
+    pub fn path(&self) -> &'static str {
+        match self {
+            Self::Permit2 => "permit2",
+            Self::AllowanceHolder => "allowance-holder",
+        }
+    }
+}
+
+impl std::fmt::Display for SwapMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.path())
+    }
+}
+
 /// Quote request parameters for the 0x Swap API v2
 ///
 /// Use this to get a swap quote with full transaction data ready for execution.
-/// The quote uses Permit2 for token approvals when possible.
+/// The quote uses Permit2 for token approvals unless [`SwapMode::AllowanceHolder`]
+/// is selected with [`QuoteRequest::with_mode`].
 #[derive(Debug, Clone, Default)]
 pub struct QuoteRequest {
     /// Address of the token to sell
@@ -215,6 +252,11 @@ pub struct QuoteRequest {
     pub buy_token_percentage_fee: Option<String>,
     /// Intent on fulfillment (for gasless trades)
     pub intent_on_filling: Option<bool>,
+    /// Sell the taker's entire sell token balance at execution time.
+    /// Mutually exclusive with `sell_amount`.
+    pub sell_entire_balance: Option<bool>,
+    /// Allowance mode, which selects the endpoint family
+    pub mode: SwapMode,
 }
 
 impl QuoteRequest {
@@ -239,6 +281,21 @@ impl QuoteRequest {
         }
     }
 
+    /// Create a new quote request that sells the taker's entire balance
+    ///
+    /// Useful for contract integrations that cannot know the exact amount
+    /// ahead of time. Equivalent to building a request without amounts and
+    /// calling [`with_sell_entire_balance`](Self::with_sell_entire_balance).
+    #[must_use]
+    pub fn sell_all(sell_token: impl Into<String>, buy_token: impl Into<String>) -> Self {
+        Self {
+            sell_token: sell_token.into(),
+            buy_token: buy_token.into(),
+            ..Default::default()
+        }
+        .with_sell_entire_balance()
+    }
+
     /// Create a new quote request for buying a specific amount
     ///
     /// # Arguments
@@ -322,6 +379,52 @@ impl QuoteRequest {
         self
     }
 
+    /// Sell the taker's entire balance of the sell token
+    ///
+    /// Cannot be combined with an explicit `sell_amount`; see
+    /// [`validate`](Self::validate).
+    #[must_use]
+    pub fn with_sell_entire_balance(mut self) -> Self {
+        self.sell_entire_balance = Some(true);
+        self
+    }
+
+    /// Select the allowance mode (Permit2 or AllowanceHolder)
+    #[must_use]
+    pub fn with_mode(mut self, mode: SwapMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Check the request for parameter combinations the API rejects
+    ///
+    /// Called by [`Client::get_quote`](crate::Client::get_quote) and
+    /// [`Client::get_price`](crate::Client::get_price) before sending.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zrxswap::QuoteRequest;
+    ///
+    /// let usdc = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+    /// let weth = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
+    ///
+    /// assert!(QuoteRequest::sell_all(usdc, weth).validate().is_ok());
+    /// assert!(QuoteRequest::sell(usdc, weth, "1000000")
+    ///     .with_sell_entire_balance()
+    ///     .validate()
+    ///     .is_err());
+    /// ```
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.sell_entire_balance == Some(true) && self.sell_amount.is_some() {
+            return Err(validation_error(
+                "sellEntireBalance",
+                "cannot be combined with an explicit sell_amount",
+            ));
+        }
+        Ok(())
+    }
+
     /// Convert to query parameters for the API request
     #[must_use]
     pub fn to_query_params(&self) -> Vec<(String, String)> {
@@ -366,6 +469,9 @@ impl QuoteRequest {
         if let Some(intent) = self.intent_on_filling {
             params.push(("intentOnFilling".to_string(), intent.to_string()));
         }
+        if let Some(entire) = self.sell_entire_balance {
+            params.push(("sellEntireBalance".to_string(), entire.to_string()));
+        }
 
         params
     }
@@ -380,6 +486,9 @@ pub type PriceRequest = QuoteRequest;
 /// Quote response from the 0x Swap API v2
 ///
 /// Contains the swap route, pricing, and transaction data ready for execution.
+/// Permit2 and AllowanceHolder quotes share these fields; the parts that
+/// differ (the permit2 block and the spender to approve) are exposed through
+/// [`QuoteResponse::approval`].
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QuoteResponse {
@@ -433,9 +542,47 @@ pub struct QuoteResponse {
     /// Whether liquidity is available for this swap
     #[serde(default)]
     pub liquidity_available: Option<bool>,
-    /// Allowance target (Permit2 contract address)
+    /// Allowance target (Permit2 or AllowanceHolder contract address)
     #[serde(default)]
     pub allowance_target: Option<String>,
+    /// Allowance mode the quote was requested with. Not part of the API
+    /// response; set by the client from the request.
+    #[serde(default)]
+    pub mode: SwapMode,
+}
+
+/// Approval requirements of a quote, by allowance mode
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QuoteApproval<'a> {
+    /// Approve the Permit2 contract, then sign `permit2.eip712` and append
+    /// the signature to the transaction data
+    Permit2 {
+        /// Contract the taker must approve (the Permit2 contract)
+        spender: Option<&'a str>,
+        /// EIP-712 payload to sign (absent e.g. for native token sells)
+        permit2: Option<&'a Permit2Data>,
+    },
+    /// Approve the AllowanceHolder contract; no signature is required
+    AllowanceHolder {
+        /// Contract the taker must approve (the AllowanceHolder contract)
+        spender: Option<&'a str>,
+    },
+}
+
+impl QuoteApproval<'_> {
+    /// Contract the taker must approve for the sell token
+    #[must_use]
+    pub fn spender(&self) -> Option<&str> {
+        match self {
+            Self::Permit2 { spender, .. } | Self::AllowanceHolder { spender } => *spender,
+        }
+    }
+
+    /// Whether an EIP-712 signature must be produced before submitting
+    #[must_use]
+    pub fn requires_signature(&self) -> bool {
+        matches!(self, Self::Permit2 { permit2: Some(p), .. } if p.eip712.is_some())
+    }
 }
 
 impl QuoteResponse {
@@ -456,6 +603,77 @@ impl QuoteResponse {
     pub fn has_transaction(&self) -> bool {
         self.transaction.is_some()
     }
+
+    /// Approval requirements for this quote's allowance mode
+    ///
+    /// The spender comes from the allowance issue when the API reports one,
+    /// falling back to `allowance_target`. AllowanceHolder quotes never carry
+    /// a permit2 block, so any stray one is ignored in that mode.
+    #[must_use]
+    pub fn approval(&self) -> QuoteApproval<'_> {
+        let spender = self
+            .issues
+            .as_ref()
+            .and_then(|i| i.allowance.as_ref())
+            .and_then(|a| a.spender.as_deref())
+            .or(self.allowance_target.as_deref());
+        match self.mode {
+            SwapMode::Permit2 => QuoteApproval::Permit2 {
+                spender,
+                permit2: self.permit2.as_ref(),
+            },
+            SwapMode::AllowanceHolder => QuoteApproval::AllowanceHolder { spender },
+        }
+    }
+
+    /// Signed percent change from an earlier indicative price to this firm quote
+    ///
+    /// Positive means the firm quote is better for the taker, negative means
+    /// worse. For sell-side requests (fixed `sell_amount`) this compares the
+    /// buy amounts; for buy-side requests (fixed `buy_amount`) it compares
+    /// the sell amounts, so paying less is an improvement. If neither side
+    /// matches, the effective rates are compared. Returns `None` if an
+    /// amount is missing, unparseable or zero.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example(client: &zrxswap::Client, request: &zrxswap::QuoteRequest) -> zrxswap::Result<()> {
+    /// use zrxswap::Chain;
+    ///
+    /// let price = client.get_price(Chain::Ethereum, request).await?;
+    /// let quote = client.get_quote(Chain::Ethereum, request).await?;
+    /// if quote.deviation_from(&price).is_some_and(|pct| pct < -0.5) {
+    ///     eprintln!("Warning: firm quote is worse than the indicative price");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn deviation_from(&self, price: &PriceResponse) -> Option<f64> {
+        let amount = |a: &str| a.parse::<f64>().ok().filter(|v| *v > 0.0);
+        let (Some(quote_sell), Some(quote_buy), Some(price_sell), Some(price_buy)) = (
+            amount(&self.sell_amount),
+            amount(&self.buy_amount),
+            amount(&price.sell_amount),
+            amount(&price.buy_amount),
+        ) else {
+            return None;
+        };
+
+        let deviation = if self.sell_amount == price.sell_amount {
+            // Sell side: more received is better
+            (quote_buy - price_buy) / price_buy * 100.0
+        } else if self.buy_amount == price.buy_amount {
+            // Buy side: less paid is better
+            (price_sell - quote_sell) / price_sell * 100.0
+        } else {
+            let quote_rate = quote_buy / quote_sell;
+            let price_rate = price_buy / price_sell;
+            (quote_rate - price_rate) / price_rate * 100.0
+        };
+        Some(deviation)
+    }
 }
 
 /// Price response from the 0x Swap API v2
@@ -628,9 +846,15 @@ impl Transaction {
 }
 
 /// Permit2 data for gasless approvals
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Permit2Data {
+    /// Payload type (always "Permit2")
+    #[serde(default, rename = "type")]
+    pub kind: Option<String>,
+    /// EIP-712 hash of the payload
+    #[serde(default)]
+    pub hash: Option<String>,
     /// EIP-712 typed data for signing
     #[serde(default)]
     pub eip712: Option<serde_json::Value>,
@@ -703,6 +927,9 @@ pub struct AllowanceIssue {
     /// Required allowance
     #[serde(default)]
     pub expected: Option<String>,
+    /// Contract the taker must approve
+    #[serde(default)]
+    pub spender: Option<String>,
 }
 
 /// Balance issue details
@@ -839,6 +1066,130 @@ mod tests {
         assert!(params.contains(&("slippageBps".to_string(), "50".to_string())));
     }
 
+    fn amounts<T: serde::de::DeserializeOwned>(sell: &str, buy: &str) -> T {
+        serde_json::from_value(serde_json::json!({
+            "sellAmount": sell,
+            "buyAmount": buy,
+            "sellToken": "0xA",
+            "buyToken": "0xB"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_quote_deviation_sell_side() {
+        let price: PriceResponse = amounts("1000000", "2000");
+        let worse: QuoteResponse = amounts("1000000", "1980");
+        let better: QuoteResponse = amounts("1000000", "2010");
+
+        assert!((worse.deviation_from(&price).unwrap() - -1.0).abs() < 1e-9);
+        assert!((better.deviation_from(&price).unwrap() - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_quote_deviation_buy_side() {
+        // Fixed buy amount: paying more is worse
+        let price: PriceResponse = amounts("1000000", "2000");
+        let worse: QuoteResponse = amounts("1020000", "2000");
+        let better: QuoteResponse = amounts("990000", "2000");
+
+        assert!((worse.deviation_from(&price).unwrap() - -2.0).abs() < 1e-9);
+        assert!((better.deviation_from(&price).unwrap() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_quote_deviation_rate_fallback() {
+        let price: PriceResponse = amounts("100", "200");
+        let quote: QuoteResponse = amounts("200", "380");
+        assert!((quote.deviation_from(&price).unwrap() - -5.0).abs() < 1e-9);
+
+        let empty: QuoteResponse = amounts("0", "380");
+        assert_eq!(empty.deviation_from(&price), None);
+        let free: PriceResponse = amounts("100", "0");
+        assert_eq!(quote.deviation_from(&free), None);
+    }
+
+    const PERMIT2_QUOTE: &str = include_str!("../tests/fixtures/quote_permit2.json");
+    const ALLOWANCE_HOLDER_QUOTE: &str =
+        include_str!("../tests/fixtures/quote_allowance_holder.json");
+
+    #[test]
+    fn test_sell_entire_balance() {
+        let request = QuoteRequest::sell_all("0xA", "0xB").with_taker("0xC");
+        assert!(request.validate().is_ok());
+        assert!(request.sell_amount.is_none());
+
+        let params = request.to_query_params();
+        assert!(params.contains(&("sellEntireBalance".to_string(), "true".to_string())));
+        assert!(!params.iter().any(|(k, _)| k == "sellAmount"));
+    }
+
+    #[test]
+    fn test_sell_entire_balance_rejects_sell_amount() {
+        let request = QuoteRequest::sell("0xA", "0xB", "100").with_sell_entire_balance();
+        let err = request.validate().unwrap_err();
+        assert!(err.to_string().contains("sellEntireBalance"));
+    }
+
+    #[test]
+    fn test_swap_mode() {
+        assert_eq!(QuoteRequest::default().mode, SwapMode::Permit2);
+        assert_eq!(SwapMode::Permit2.path(), "permit2");
+        assert_eq!(SwapMode::AllowanceHolder.to_string(), "allowance-holder");
+
+        let request = QuoteRequest::sell("0xA", "0xB", "100").with_mode(SwapMode::AllowanceHolder);
+        assert_eq!(request.mode, SwapMode::AllowanceHolder);
+        // The mode selects the endpoint, it is not a query parameter
+        assert!(!request.to_query_params().iter().any(|(k, _)| k == "mode"));
+    }
+
+    #[test]
+    fn test_permit2_quote_fixture() {
+        let quote: QuoteResponse = serde_json::from_str(PERMIT2_QUOTE).unwrap();
+        assert_eq!(quote.mode, SwapMode::Permit2);
+        assert_eq!(quote.gas_estimate(), None);
+        assert_eq!(
+            quote.transaction.as_ref().unwrap().gas_limit(),
+            Some(180_000)
+        );
+
+        let approval = quote.approval();
+        assert_eq!(
+            approval.spender(),
+            Some("0x000000000022D473030F116dDEE9F6B43aC78BA3")
+        );
+        assert!(approval.requires_signature());
+        let QuoteApproval::Permit2 {
+            permit2: Some(permit2),
+            ..
+        } = approval
+        else {
+            panic!("expected a Permit2 approval with a permit2 block");
+        };
+        assert_eq!(permit2.kind.as_deref(), Some("Permit2"));
+        assert!(permit2.hash.is_some());
+    }
+
+    #[test]
+    fn test_allowance_holder_quote_fixture() {
+        let mut quote: QuoteResponse = serde_json::from_str(ALLOWANCE_HOLDER_QUOTE).unwrap();
+        assert!(quote.permit2.is_none());
+        quote.mode = SwapMode::AllowanceHolder;
+
+        let approval = quote.approval();
+        assert_eq!(
+            approval,
+            QuoteApproval::AllowanceHolder {
+                spender: Some("0x0000000000001fF3684f28c67538d4D072C22734")
+            }
+        );
+        assert!(!approval.requires_signature());
+        assert_eq!(
+            quote.transaction.as_ref().map(|tx| tx.to.as_str()),
+            approval.spender()
+        );
+    }
+
     #[test]
     fn test_liquidity_source_proportion() {
         let source = LiquiditySource {